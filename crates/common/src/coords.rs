@@ -325,6 +325,158 @@ impl IntoIterator for &CellRange {
     }
 }
 
+// ----------------------------------------------
+// MapRotation
+// ----------------------------------------------
+
+// Clockwise rotation of the isometric view in 90 degree steps.
+// Map cells are always stored unrotated; "view" cells are where
+// a map cell ends up on screen for the current rotation.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MapRotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl MapRotation {
+    #[inline]
+    pub const fn next_clockwise(self) -> Self {
+        match self {
+            Self::Deg0 => Self::Deg90,
+            Self::Deg90 => Self::Deg180,
+            Self::Deg180 => Self::Deg270,
+            Self::Deg270 => Self::Deg0,
+        }
+    }
+
+    #[inline]
+    pub const fn next_counter_clockwise(self) -> Self {
+        match self {
+            Self::Deg0 => Self::Deg270,
+            Self::Deg90 => Self::Deg0,
+            Self::Deg180 => Self::Deg90,
+            Self::Deg270 => Self::Deg180,
+        }
+    }
+
+    #[inline]
+    pub const fn inverse(self) -> Self {
+        match self {
+            Self::Deg0 => Self::Deg0,
+            Self::Deg90 => Self::Deg270,
+            Self::Deg180 => Self::Deg180,
+            Self::Deg270 => Self::Deg90,
+        }
+    }
+
+    #[inline]
+    pub const fn degrees(self) -> i32 {
+        match self {
+            Self::Deg0 => 0,
+            Self::Deg90 => 90,
+            Self::Deg180 => 180,
+            Self::Deg270 => 270,
+        }
+    }
+
+    #[inline]
+    pub const fn is_identity(self) -> bool {
+        matches!(self, Self::Deg0)
+    }
+
+    // Map cell -> view cell. `map_size` is the unrotated map size in cells.
+    #[inline]
+    pub fn rotate_cell(self, cell: Cell, map_size: Size) -> Cell {
+        match self {
+            Self::Deg0 => cell,
+            Self::Deg90 => Cell::new(cell.y, map_size.width - 1 - cell.x),
+            Self::Deg180 => Cell::new(map_size.width - 1 - cell.x, map_size.height - 1 - cell.y),
+            Self::Deg270 => Cell::new(map_size.height - 1 - cell.y, cell.x),
+        }
+    }
+
+    // View cell -> map cell. Inverse of rotate_cell().
+    #[inline]
+    pub fn unrotate_cell(self, cell: Cell, map_size: Size) -> Cell {
+        match self {
+            Self::Deg0 => cell,
+            Self::Deg90 => Cell::new(map_size.width - 1 - cell.y, cell.x),
+            Self::Deg180 => Cell::new(map_size.width - 1 - cell.x, map_size.height - 1 - cell.y),
+            Self::Deg270 => Cell::new(cell.y, map_size.height - 1 - cell.x),
+        }
+    }
+
+    // Rotated cell range with start/end normalized back to min/max corners.
+    pub fn rotate_cell_range(self, range: CellRange, map_size: Size) -> CellRange {
+        if self.is_identity() {
+            return range;
+        }
+        let a = self.rotate_cell(range.start, map_size);
+        let b = self.rotate_cell(range.end, map_size);
+        CellRange::new(Cell::new(a.x.min(b.x), a.y.min(b.y)), Cell::new(a.x.max(b.x), a.y.max(b.y)))
+    }
+
+    // View cell range -> map cell range. Inverse of rotate_cell_range().
+    pub fn unrotate_cell_range(self, range: CellRange, map_size: Size) -> CellRange {
+        if self.is_identity() {
+            return range;
+        }
+        let a = self.unrotate_cell(range.start, map_size);
+        let b = self.unrotate_cell(range.end, map_size);
+        CellRange::new(Cell::new(a.x.min(b.x), a.y.min(b.y)), Cell::new(a.x.max(b.x), a.y.max(b.y)))
+    }
+
+    // Map size as seen from the rotated view (width/height swap at 90/270).
+    #[inline]
+    pub fn rotate_size(self, map_size: Size) -> Size {
+        match self {
+            Self::Deg0 | Self::Deg180 => map_size,
+            Self::Deg90 | Self::Deg270 => Size::new(map_size.height, map_size.width),
+        }
+    }
+
+    // Rotates a direction/offset in cell space (no map size involved).
+    #[inline]
+    pub fn rotate_cell_delta(self, delta: Vec2) -> Vec2 {
+        match self {
+            Self::Deg0 => delta,
+            Self::Deg90 => Vec2::new(delta.y, -delta.x),
+            Self::Deg180 => Vec2::new(-delta.x, -delta.y),
+            Self::Deg270 => Vec2::new(-delta.y, delta.x),
+        }
+    }
+
+    // Rotates a point in iso space around the map center. Assumes a square map,
+    // where the integer cell coords of rotate_cell() are the same cell centers.
+    pub fn rotate_iso_point(self, iso_point: IsoPointF32, map_size: Size) -> IsoPointF32 {
+        if self.is_identity() {
+            return iso_point;
+        }
+        let center = Vec2::new((map_size.width - 1) as f32, (map_size.height - 1) as f32) * 0.5;
+        let center_iso = cell_to_iso_f32(CellF32(center));
+        IsoPointF32(center_iso.0 + self.rotate_iso_delta(iso_point.0 - center_iso.0))
+    }
+
+    // Rotates an offset in iso space, e.g. a sprite draw offset relative to its base cell.
+    #[inline]
+    pub fn rotate_iso_delta(self, delta: Vec2) -> Vec2 {
+        if self.is_identity() {
+            return delta;
+        }
+        let cell_delta = iso_to_cell_f32(IsoPointF32(delta));
+        cell_to_iso_f32(CellF32(self.rotate_cell_delta(cell_delta.0))).0
+    }
+}
+
+impl std::fmt::Display for MapRotation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}\u{b0}", self.degrees())
+    }
+}
+
 // ----------------------------------------------
 // WorldToScreenTransform
 // ----------------------------------------------
@@ -700,3 +852,46 @@ impl IsoDiamond {
         Rect::from_points(&corners)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_rotation_round_trip() {
+        let map_size = Size::new(5, 3);
+        let mut rotation = MapRotation::Deg0;
+        for _ in 0..4 {
+            for cell in &CellRange::new(Cell::zero(), Cell::new(4, 2)) {
+                let view_cell = rotation.rotate_cell(cell, map_size);
+                let view_size = rotation.rotate_size(map_size);
+                assert!(view_cell.x >= 0 && view_cell.x < view_size.width, "{rotation} {cell} -> {view_cell}");
+                assert!(view_cell.y >= 0 && view_cell.y < view_size.height, "{rotation} {cell} -> {view_cell}");
+                assert_eq!(rotation.unrotate_cell(view_cell, map_size), cell);
+            }
+            rotation = rotation.next_clockwise();
+        }
+        assert_eq!(rotation, MapRotation::Deg0);
+    }
+
+    #[test]
+    fn test_map_rotation_deltas() {
+        let delta = Vec2::new(1.0, 0.0);
+        assert_eq!(MapRotation::Deg90.rotate_cell_delta(delta), Vec2::new(0.0, -1.0));
+        assert_eq!(MapRotation::Deg180.rotate_cell_delta(delta), Vec2::new(-1.0, 0.0));
+        assert_eq!(MapRotation::Deg270.rotate_cell_delta(delta), Vec2::new(0.0, 1.0));
+
+        let range = CellRange::new(Cell::new(1, 0), Cell::new(2, 1));
+        let rotated = MapRotation::Deg90.rotate_cell_range(range, Size::new(4, 4));
+        assert!(rotated == CellRange::new(Cell::new(0, 1), Cell::new(1, 2)), "{rotated}");
+
+        // Rotating an iso point must land on the same place as rotating its cell.
+        let map_size = Size::new(4, 4);
+        let cell = Cell::new(3, 1);
+        for rotation in [MapRotation::Deg90, MapRotation::Deg180, MapRotation::Deg270] {
+            let iso_point = IsoPointF32::from_integer_iso(cell_to_iso(cell));
+            let expected = IsoPointF32::from_integer_iso(cell_to_iso(rotation.rotate_cell(cell, map_size)));
+            assert_eq!(rotation.rotate_iso_point(iso_point, map_size), expected, "{rotation}");
+        }
+    }
+}
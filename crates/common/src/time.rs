@@ -10,8 +10,9 @@ use web_time as time;
 // Type Aliases
 // ----------------------------------------------
 
-pub type Instant  = time::Instant;
-pub type Duration = time::Duration;
+pub type Instant    = time::Instant;
+pub type Duration   = time::Duration;
+pub type SystemTime = time::SystemTime;
 
 pub type Seconds = f32;
 pub type Milliseconds = f32;
@@ -22,6 +23,39 @@ pub fn elapsed_seconds(a: Instant, b: Instant) -> Seconds {
     elapsed.as_secs_f32()
 }
 
+// ----------------------------------------------
+// Wall-clock Time
+// ----------------------------------------------
+
+// Seconds since the Unix epoch. Zero if the system clock is set before the epoch.
+pub fn unix_timestamp_secs() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
+
+// Formats a Unix timestamp as "YYYY-MM-DD HH:MM" in UTC.
+pub fn format_unix_timestamp(timestamp_secs: u64) -> String {
+    const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+    let days = (timestamp_secs / SECS_PER_DAY) as i64;
+    let secs_of_day = timestamp_secs % SECS_PER_DAY;
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's days_to_civil algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153; // March based.
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    let hours = secs_of_day / 3600;
+    let minutes = (secs_of_day % 3600) / 60;
+
+    format!("{year:04}-{month:02}-{day:02} {hours:02}:{minutes:02}")
+}
+
 // ----------------------------------------------
 // FrameClock
 // ----------------------------------------------
@@ -191,3 +225,19 @@ impl PerfTimer {
         self.0.elapsed().as_secs_f32() * 1000.0
     }
 }
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_unix_timestamp() {
+        assert_eq!(format_unix_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_unix_timestamp(951_782_400), "2000-02-29 00:00");
+        assert_eq!(format_unix_timestamp(1_700_000_000), "2023-11-14 22:13");
+    }
+}
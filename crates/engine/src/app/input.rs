@@ -1,7 +1,10 @@
+use std::{collections::BTreeMap, fmt};
+
 use bitflags::bitflags;
 use common::Vec2;
 use enum_dispatch::enum_dispatch;
-use strum::EnumCount;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount};
 
 // ----------------------------------------------
 // Internal backend implementations
@@ -13,6 +16,9 @@ pub(crate) enum InputSystemBackendImpl {
 
     #[cfg(feature = "desktop")]
     Glfw(super::glfw::GlfwInputSystemBackend),
+
+    #[cfg(feature = "desktop")]
+    Null(super::null::NullInputSystemBackend),
 }
 
 // ----------------------------------------------
@@ -62,6 +68,14 @@ impl InputSystem {
     }
 }
 
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Approximate pixel distance of one scroll wheel line, for converting
+// precise (trackpad) pixel deltas to line based scroll amounts.
+pub const PIXELS_PER_SCROLL_LINE: f32 = 20.0;
+
 // ----------------------------------------------
 // InputAction
 // ----------------------------------------------
@@ -79,7 +93,7 @@ pub enum InputAction {
 // ----------------------------------------------
 
 bitflags! {
-    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
     pub struct InputModifiers: u8 {
         const Shift    = 1 << 0;
         const Control  = 1 << 1;
@@ -90,11 +104,16 @@ bitflags! {
     }
 }
 
+impl InputModifiers {
+    // Modifiers that take part in key binding matches. Lock keys are ignored.
+    pub const BINDABLE: Self = Self::Shift.union(Self::Control).union(Self::Alt).union(Self::Super);
+}
+
 // ----------------------------------------------
 // MouseButton
 // ----------------------------------------------
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, EnumCount)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Display, EnumCount, Serialize, Deserialize)]
 pub enum MouseButton {
     Left,
     Right,
@@ -108,7 +127,7 @@ pub enum MouseButton {
 // InputKey
 // ----------------------------------------------
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, EnumCount)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Display, EnumCount, Serialize, Deserialize)]
 pub enum InputKey {
     Unknown,
 
@@ -179,3 +198,306 @@ pub enum InputKey {
     RightSuper,
     Menu,
 }
+
+// ----------------------------------------------
+// InputTrigger
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputTrigger {
+    Key(InputKey),
+    Mouse(MouseButton),
+}
+
+impl fmt::Display for InputTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key)      => write!(f, "{key}"),
+            Self::Mouse(button) => write!(f, "Mouse {button}"),
+        }
+    }
+}
+
+// ----------------------------------------------
+// InputBinding
+// ----------------------------------------------
+
+// A key or mouse button plus the exact set of modifiers that must be held with it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct InputBinding {
+    pub trigger: InputTrigger,
+    #[serde(default)]
+    pub modifiers: InputModifiers,
+}
+
+impl InputBinding {
+    #[inline]
+    pub const fn key(key: InputKey) -> Self {
+        Self { trigger: InputTrigger::Key(key), modifiers: InputModifiers::empty() }
+    }
+
+    #[inline]
+    pub const fn key_with_modifiers(key: InputKey, modifiers: InputModifiers) -> Self {
+        Self { trigger: InputTrigger::Key(key), modifiers }
+    }
+
+    #[inline]
+    pub const fn mouse(button: MouseButton) -> Self {
+        Self { trigger: InputTrigger::Mouse(button), modifiers: InputModifiers::empty() }
+    }
+
+    #[inline]
+    pub fn matches_key(&self, key: InputKey, modifiers: InputModifiers) -> bool {
+        self.trigger == InputTrigger::Key(key) && self.modifiers == (modifiers & InputModifiers::BINDABLE)
+    }
+
+    #[inline]
+    pub fn matches_mouse_button(&self, button: MouseButton, modifiers: InputModifiers) -> bool {
+        self.trigger == InputTrigger::Mouse(button) && self.modifiers == (modifiers & InputModifiers::BINDABLE)
+    }
+
+    // Held state of the key or button, ignoring modifiers.
+    #[inline]
+    pub fn is_down(&self, input_sys: &InputSystem) -> bool {
+        match self.trigger {
+            InputTrigger::Key(key)      => input_sys.key_state(key) != InputAction::Release,
+            InputTrigger::Mouse(button) => input_sys.mouse_button_state(button) != InputAction::Release,
+        }
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MODIFIER_NAMES: [(InputModifiers, &str); 4] = [
+            (InputModifiers::Control, "Ctrl"),
+            (InputModifiers::Super,   "Cmd"),
+            (InputModifiers::Alt,     "Alt"),
+            (InputModifiers::Shift,   "Shift"),
+        ];
+
+        for (modifier, name) in MODIFIER_NAMES {
+            if self.modifiers.intersects(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        write!(f, "{}", self.trigger)
+    }
+}
+
+// ----------------------------------------------
+// InputBindingSlot / InputBindingSlots
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InputBindingSlot {
+    Primary,
+    Alternate,
+}
+
+// Each action can be triggered by up to two independent bindings.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindingSlots {
+    pub primary: Option<InputBinding>,
+    pub alternate: Option<InputBinding>,
+}
+
+impl InputBindingSlots {
+    #[inline]
+    pub const fn new(primary: Option<InputBinding>, alternate: Option<InputBinding>) -> Self {
+        Self { primary, alternate }
+    }
+
+    #[inline]
+    pub fn get(&self, slot: InputBindingSlot) -> Option<InputBinding> {
+        match slot {
+            InputBindingSlot::Primary   => self.primary,
+            InputBindingSlot::Alternate => self.alternate,
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, slot: InputBindingSlot, binding: Option<InputBinding>) {
+        match slot {
+            InputBindingSlot::Primary   => self.primary = binding,
+            InputBindingSlot::Alternate => self.alternate = binding,
+        }
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &InputBinding> {
+        self.primary.iter().chain(self.alternate.iter())
+    }
+}
+
+// ----------------------------------------------
+// InputKeyMap
+// ----------------------------------------------
+
+// Maps application defined actions to input bindings. Serializable, so the
+// application can persist it to a bindings file. Also tracks an optional pending
+// rebind, where the next key or mouse button pressed replaces an action binding.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "Action: Serialize", deserialize = "Action: Deserialize<'de>"))]
+pub struct InputKeyMap<Action: Copy + Ord> {
+    bindings: BTreeMap<Action, InputBindingSlots>,
+
+    #[serde(skip)]
+    capture: Option<(Action, InputBindingSlot)>,
+}
+
+impl<Action: Copy + Ord> Default for InputKeyMap<Action> {
+    fn default() -> Self {
+        Self { bindings: BTreeMap::new(), capture: None }
+    }
+}
+
+impl<Action: Copy + Ord> InputKeyMap<Action> {
+    pub fn with_defaults(defaults: &[(Action, InputBindingSlots)]) -> Self {
+        Self { bindings: defaults.iter().copied().collect(), capture: None }
+    }
+
+    // Adds default bindings for actions missing from the map, e.g.
+    // actions added after the bindings file was last saved.
+    pub fn merge_defaults(&mut self, defaults: &[(Action, InputBindingSlots)]) {
+        for (action, slots) in defaults {
+            self.bindings.entry(*action).or_insert(*slots);
+        }
+    }
+
+    #[inline]
+    pub fn bindings(&self, action: Action) -> InputBindingSlots {
+        self.bindings.get(&action).copied().unwrap_or_default()
+    }
+
+    // Binds `binding` to the action slot. The same binding is removed from any
+    // other action or slot first, so one input never triggers two actions.
+    pub fn bind(&mut self, action: Action, slot: InputBindingSlot, binding: Option<InputBinding>) {
+        if let Some(binding) = binding {
+            for slots in self.bindings.values_mut() {
+                if slots.primary == Some(binding) {
+                    slots.primary = None;
+                }
+                if slots.alternate == Some(binding) {
+                    slots.alternate = None;
+                }
+            }
+        }
+
+        self.bindings.entry(action).or_default().set(slot, binding);
+    }
+
+    #[inline]
+    pub fn is_key_bound_to(&self, action: Action, key: InputKey, modifiers: InputModifiers) -> bool {
+        self.bindings(action).iter().any(|binding| binding.matches_key(key, modifiers))
+    }
+
+    #[inline]
+    pub fn is_mouse_button_bound_to(&self, action: Action, button: MouseButton, modifiers: InputModifiers) -> bool {
+        self.bindings(action).iter().any(|binding| binding.matches_mouse_button(button, modifiers))
+    }
+
+    // True while any key or mouse button bound to the action is held down.
+    #[inline]
+    pub fn is_action_down(&self, action: Action, input_sys: &InputSystem) -> bool {
+        self.bindings(action).iter().any(|binding| binding.is_down(input_sys))
+    }
+
+    // ----------------------
+    // Interactive rebinding:
+    // ----------------------
+
+    #[inline]
+    pub fn begin_capture(&mut self, action: Action, slot: InputBindingSlot) {
+        self.capture = Some((action, slot));
+    }
+
+    #[inline]
+    pub fn cancel_capture(&mut self) {
+        self.capture = None;
+    }
+
+    #[inline]
+    pub fn is_capturing(&self) -> bool {
+        self.capture.is_some()
+    }
+
+    #[inline]
+    pub fn capture_target(&self) -> Option<(Action, InputBindingSlot)> {
+        self.capture
+    }
+
+    // Binds `binding` to the pending capture target and ends the capture.
+    // Returns the rebound action, or None if no capture was in progress.
+    pub fn complete_capture(&mut self, binding: InputBinding) -> Option<Action> {
+        let (action, slot) = self.capture.take()?;
+        self.bind(action, slot, Some(binding));
+        Some(action)
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+    enum TestAction {
+        Undo,
+        Redo,
+    }
+
+    fn test_keymap() -> InputKeyMap<TestAction> {
+        InputKeyMap::with_defaults(&[
+            (TestAction::Undo, InputBindingSlots::new(
+                Some(InputBinding::key_with_modifiers(InputKey::Z, InputModifiers::Control)),
+                None,
+            )),
+            (TestAction::Redo, InputBindingSlots::new(
+                Some(InputBinding::key_with_modifiers(InputKey::Z, InputModifiers::Control | InputModifiers::Shift)),
+                Some(InputBinding::key_with_modifiers(InputKey::Y, InputModifiers::Control)),
+            )),
+        ])
+    }
+
+    #[test]
+    fn modifiers_must_match_exactly() {
+        let keymap = test_keymap();
+
+        assert!(keymap.is_key_bound_to(TestAction::Undo, InputKey::Z, InputModifiers::Control));
+        assert!(!keymap.is_key_bound_to(TestAction::Undo, InputKey::Z, InputModifiers::Control | InputModifiers::Shift));
+        assert!(keymap.is_key_bound_to(TestAction::Redo, InputKey::Z, InputModifiers::Control | InputModifiers::Shift));
+
+        // Lock keys are ignored.
+        assert!(keymap.is_key_bound_to(TestAction::Undo, InputKey::Z, InputModifiers::Control | InputModifiers::CapsLock));
+    }
+
+    #[test]
+    fn rebinding_removes_duplicate_binding() {
+        let mut keymap = test_keymap();
+        let ctrl_y = InputBinding::key_with_modifiers(InputKey::Y, InputModifiers::Control);
+
+        keymap.begin_capture(TestAction::Undo, InputBindingSlot::Alternate);
+        assert_eq!(keymap.complete_capture(ctrl_y), Some(TestAction::Undo));
+        assert!(!keymap.is_capturing());
+
+        assert_eq!(keymap.bindings(TestAction::Undo).alternate, Some(ctrl_y));
+        assert_eq!(keymap.bindings(TestAction::Redo).alternate, None);
+    }
+
+    #[test]
+    fn missing_actions_get_defaults() {
+        let mut keymap = InputKeyMap::with_defaults(&[]);
+        keymap.bind(TestAction::Undo, InputBindingSlot::Primary, Some(InputBinding::key(InputKey::U)));
+
+        let defaults = test_keymap();
+        keymap.merge_defaults(&defaults.bindings.iter().map(|(action, slots)| (*action, *slots)).collect::<Vec<_>>());
+
+        assert_eq!(keymap.bindings(TestAction::Undo).primary, Some(InputBinding::key(InputKey::U)));
+        assert_eq!(keymap.bindings(TestAction::Redo), defaults.bindings(TestAction::Redo));
+    }
+}
@@ -19,12 +19,18 @@ pub(crate) mod winit;
 #[cfg(feature = "desktop")]
 mod glfw;
 
+#[cfg(feature = "desktop")]
+mod null;
+
 #[enum_dispatch]
 enum ApplicationBackendImpl {
     Winit(winit::WinitApplicationBackend),
 
     #[cfg(feature = "desktop")]
     Glfw(glfw::GlfwApplicationBackend),
+
+    #[cfg(feature = "desktop")]
+    Null(null::NullApplicationBackend),
 }
 
 #[derive(Copy, Clone, Default, PartialEq, Eq, Display, Serialize, Deserialize)]
@@ -34,6 +40,10 @@ pub enum ApplicationApi {
 
     #[cfg(feature = "desktop")]
     Glfw,
+
+    // No window, input or presentation. Used for headless runs, together with RenderApi::Null.
+    #[cfg(feature = "desktop")]
+    Null,
 }
 
 // ----------------------------------------------
@@ -113,6 +123,9 @@ impl Application {
 
             #[cfg(feature = "desktop")]
             ApplicationApi::Glfw => ApplicationBackendImpl::from(glfw::GlfwApplicationBackend::new(&params)),
+
+            #[cfg(feature = "desktop")]
+            ApplicationApi::Null => ApplicationBackendImpl::from(null::NullApplicationBackend::new(&params)),
         };
 
         let input_system = backend.new_input_system();
@@ -131,6 +144,19 @@ impl Application {
         self.app_api
     }
 
+    // Running without a window, audio or rendering.
+    #[cfg(feature = "desktop")]
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        self.app_api == ApplicationApi::Null
+    }
+
+    #[cfg(feature = "web")]
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        false
+    }
+
     // Optional context passed to the RenderSystem (e.g.: Arc<Window> for wgpu).
     #[inline]
     pub fn app_context(&self) -> Option<&dyn std::any::Any> {
@@ -197,6 +223,10 @@ pub enum ApplicationEvent {
     CharInput(char),
     Scroll(Vec2),
     MouseButton(MouseButton, InputAction, InputModifiers),
+
+    // Trackpad gestures (Winit backend only):
+    PinchZoom(f32),   // Magnification delta. Positive values zoom in.
+    PanGesture(Vec2), // Two-finger pan delta in logical pixels.
 }
 
 pub type ApplicationEventList = SmallVec<[ApplicationEvent; 16]>;
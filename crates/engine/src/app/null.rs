@@ -0,0 +1,92 @@
+use common::{Size, Vec2};
+
+use super::{
+    ApplicationApi,
+    ApplicationBackend,
+    ApplicationEventList,
+    ApplicationInitParams,
+    input::{InputAction, InputKey, InputSystem, InputSystemBackend, InputSystemBackendImpl, MouseButton},
+};
+use crate::{log, render::RenderApi};
+
+// ----------------------------------------------
+// NullApplicationBackend
+// ----------------------------------------------
+
+// Application without a window, for headless runs. Never produces any events;
+// the window size from the init params only sets the viewport size.
+pub struct NullApplicationBackend {
+    should_quit: bool,
+    window_size: Size,
+}
+
+impl NullApplicationBackend {
+    pub fn new(params: &ApplicationInitParams) -> Self {
+        assert!(params.app_api == ApplicationApi::Null);
+        assert!(params.render_api == RenderApi::Null);
+
+        log::info!(log::channel!("app"), "--- App Backend: Null (Headless) ---");
+
+        Self { should_quit: false, window_size: params.window_size }
+    }
+}
+
+impl ApplicationBackend for NullApplicationBackend {
+    fn new_input_system(&mut self) -> InputSystem {
+        InputSystem::new(InputSystemBackendImpl::Null(NullInputSystemBackend))
+    }
+
+    fn app_context(&self) -> Option<&dyn std::any::Any> {
+        None
+    }
+
+    fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+
+    fn request_quit(&mut self) {
+        self.should_quit = true;
+    }
+
+    fn poll_events(&mut self) -> ApplicationEventList {
+        ApplicationEventList::new()
+    }
+
+    fn present(&mut self) {}
+
+    fn window_size(&self) -> Size {
+        self.window_size
+    }
+
+    fn framebuffer_size(&self) -> Size {
+        self.window_size
+    }
+
+    fn content_scale(&self) -> Vec2 {
+        Vec2::one()
+    }
+}
+
+// ----------------------------------------------
+// NullInputSystemBackend
+// ----------------------------------------------
+
+// No keys or buttons are ever pressed and the cursor stays at the origin.
+pub struct NullInputSystemBackend;
+
+impl InputSystemBackend for NullInputSystemBackend {
+    #[inline]
+    fn cursor_pos(&self) -> Vec2 {
+        Vec2::zero()
+    }
+
+    #[inline]
+    fn mouse_button_state(&self, _button: MouseButton) -> InputAction {
+        InputAction::Release
+    }
+
+    #[inline]
+    fn key_state(&self, _key: InputKey) -> InputAction {
+        InputAction::Release
+    }
+}
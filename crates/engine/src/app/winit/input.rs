@@ -2,7 +2,7 @@ use common::{Vec2, mem::RcMut};
 use strum::EnumCount;
 use winit::keyboard::{KeyCode, PhysicalKey};
 
-use crate::app::input::{InputAction, InputKey, InputModifiers, InputSystemBackend, MouseButton};
+use crate::app::input::{InputAction, InputKey, InputModifiers, InputSystemBackend, MouseButton, PIXELS_PER_SCROLL_LINE};
 
 // ----------------------------------------------
 // WinitInputState
@@ -278,7 +278,7 @@ pub fn winit_mouse_scroll_delta_to_vec2(delta: winit::event::MouseScrollDelta) -
         }
         winit::event::MouseScrollDelta::PixelDelta(pos) => {
             // Convert pixel delta to approximate line counts.
-            Vec2::new(pos.x as f32 / 20.0, pos.y as f32 / 20.0)
+            Vec2::new(pos.x as f32 / PIXELS_PER_SCROLL_LINE, pos.y as f32 / PIXELS_PER_SCROLL_LINE)
         }
     }
 }
@@ -99,6 +99,9 @@ impl WinitApplicationBackend {
 
             #[cfg(feature = "desktop")]
             RenderApi::OpenGl => WinitWindowManagerImpl::from(opengl::WinitWindowManager::new(params)),
+
+            #[cfg(feature = "desktop")]
+            RenderApi::Null => panic!("Null render backend requires the Null app backend!"),
         })
     }
 
@@ -191,9 +194,25 @@ impl WinitApplicationBackend {
                 }
             }
             WindowEvent::MouseWheel { delta, .. } => {
+                // MacOS trackpads report precise pixel deltas for two-finger swipes; treat those
+                // as panning. Regular mouse wheels report line deltas and keep scrolling/zooming.
+                #[cfg(target_os = "macos")]
+                if let winit::event::MouseScrollDelta::PixelDelta(pos) = delta {
+                    let scale = self.content_scale();
+                    events.push(ApplicationEvent::PanGesture(Vec2::new(pos.x as f32 / scale.x, pos.y as f32 / scale.y)));
+                    return;
+                }
+
                 let scroll = input::winit_mouse_scroll_delta_to_vec2(delta);
                 events.push(ApplicationEvent::Scroll(scroll));
             }
+            WindowEvent::PinchGesture { delta, .. } => {
+                events.push(ApplicationEvent::PinchZoom(delta as f32));
+            }
+            WindowEvent::PanGesture { delta, .. } => {
+                let scale = self.content_scale();
+                events.push(ApplicationEvent::PanGesture(Vec2::new(delta.x / scale.x, delta.y / scale.y)));
+            }
             WindowEvent::CursorLeft { .. } => {
                 if self.confine_cursor {
                     // When confinement is enabled: the title bar is outside the content
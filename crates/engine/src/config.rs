@@ -57,6 +57,14 @@ pub trait Configs {
     fn load_file<T>(config_file_name: PathRef) -> T
     where
         T: Configs + Sized + Default + DeserializeOwned,
+    {
+        Self::try_load_file(config_file_name).unwrap_or_default()
+    }
+
+    // Loads the config file, or returns None if it couldn't be read or deserialized.
+    fn try_load_file<T>(config_file_name: PathRef) -> Option<T>
+    where
+        T: Configs + Sized + DeserializeOwned,
     {
         debug_assert!(!config_file_name.is_empty());
 
@@ -66,17 +74,17 @@ pub trait Configs {
 
         if let Err(err) = state.read_file(&config_json_path) {
             log::error!(log::channel!("config"), "Failed to read config file from path {config_json_path}: {err}");
-            return T::default();
+            return None;
         }
 
         match state.load_new_instance::<T>() {
-            Ok(configs) => configs,
+            Ok(configs) => Some(configs),
             Err(err) => {
                 log::error!(
                     log::channel!("config"),
                     "Failed to deserialize config file from path {config_json_path}: {err}"
                 );
-                T::default()
+                None
             }
         }
     }
@@ -102,6 +110,12 @@ macro_rules! configurations {
                 use $crate::config::Configs;
                 <$configs_type>::get().save_file($crate::file_sys::paths::PathRef::from_str($configs_path))
             }
+            // Reads a separate instance from the config file, leaving the global one untouched (e.g. for hot-reloading).
+            // Not post-loaded. None if the file failed to load.
+            pub fn read_file() -> Option<$configs_type> {
+                use $crate::config::Configs;
+                <$configs_type>::try_load_file($crate::file_sys::paths::PathRef::from_str($configs_path))
+            }
         }
     };
 }
@@ -126,12 +140,21 @@ pub struct EngineConfigs {
     // Graphics:
     pub render_api: RenderApi,
     pub use_packed_texture_atlas: bool,
+    pub async_tile_set_loading: bool, // Load tile set textures in the background after startup. Ignored with a packed atlas.
+    pub selection_outline: bool, // Outline hovered/selected objects. Falls back to a color tint if disabled.
+    pub cache_terrain_chunks: bool, // Draw terrain from cached per-chunk GPU meshes instead of per-tile sprites.
+    pub parallel_tile_gather: bool, // Collect and depth sort visible tiles on worker threads (desktop only).
     #[debug_ui(nested)]
     pub texture_settings: TextureSettings,
 
+    // UI:
+    pub ui_scale: f32, // Global UI scale on top of the window content scale (e.g. 1.5 on 4K displays).
+
     // Sound System:
     #[debug_ui(skip)]
     pub sound_settings: SoundGlobalSettings,
+    #[debug_ui(skip)]
+    pub sound_output_device: Option<String>, // None for the system default audio output.
 
     // Debug Grid:
     pub grid_color: Color,
@@ -139,6 +162,8 @@ pub struct EngineConfigs {
 
     // Debug Log:
     pub log_level: log::Level,
+    #[debug_ui(skip)]
+    pub log_file_sinks: Vec<log::LogFileSinkConfig>, // Per-channel log files (desktop only).
 }
 
 impl Default for EngineConfigs {
@@ -157,10 +182,18 @@ impl Default for EngineConfigs {
             // Graphics:
             render_api: RenderApi::default(),
             use_packed_texture_atlas: false,
+            async_tile_set_loading: false,
+            selection_outline: true,
+            cache_terrain_chunks: true,
+            parallel_tile_gather: false,
             texture_settings: TextureSettings::default(),
 
+            // UI:
+            ui_scale: 1.0,
+
             // Sound System:
             sound_settings: SoundGlobalSettings::default(),
+            sound_output_device: None,
 
             // Debug Grid:
             grid_color: Color::white(),
@@ -168,6 +201,7 @@ impl Default for EngineConfigs {
 
             // Debug Log:
             log_level: log::Level::Verbose,
+            log_file_sinks: Vec::new(),
         }
     }
 }
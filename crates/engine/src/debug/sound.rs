@@ -2,7 +2,7 @@ use common::{Vec2, coords::IsoPointF32};
 
 use crate::{
     file_sys::paths::PathRef,
-    sound::SoundSystem,
+    sound::{SoundBuses, SoundEventKey, SoundSystem},
     ui::{self, DrawDebugUi, UiStaticVar, UiSystem},
 };
 
@@ -18,12 +18,44 @@ impl DrawDebugUi for SoundSystem {
         ui.text(common::format_small!("Listener Pos   : {}", self.listener_position()));
         ui.text(common::format_small!("Sounds Playing : {}", self.sounds_playing()));
         ui.text(common::format_small!("Sounds Loaded  : {}", self.sounds_loaded()));
+        ui.text(common::format_small!("Ducking        : {:.2}", self.narration_ducking()));
+        ui.text(common::format_small!("Output Device  : {}", self.current_output_device().unwrap_or("Default")));
+        ui.text(common::format_small!("Caption        : {}", self.current_caption().unwrap_or("None")));
 
         ui.separator();
 
         let mut new_settings = self.current_sound_settings();
         new_settings.draw_debug_ui(ui_sys);
 
+        ui.separator();
+
+        // Bus mute/solo/ducking toggles:
+        for bus in std::iter::once(SoundBuses::Master).chain(SoundBuses::CATEGORIES) {
+            let _id = ui.push_id_usize(bus.bits() as usize);
+
+            let mut muted = new_settings.is_bus_muted(bus);
+            if ui.checkbox("Mute", &mut muted) {
+                new_settings.set_bus_muted(bus, muted);
+            }
+
+            if bus != SoundBuses::Master {
+                ui.same_line();
+                let mut soloed = new_settings.is_bus_soloed(bus);
+                if ui.checkbox("Solo", &mut soloed) {
+                    new_settings.set_bus_soloed(bus, soloed);
+                }
+
+                ui.same_line();
+                let mut ducked = new_settings.narration_ducked_buses.intersects(bus);
+                if ui.checkbox("Duck", &mut ducked) {
+                    new_settings.narration_ducked_buses.set(bus, ducked);
+                }
+            }
+
+            ui.same_line();
+            ui.text(common::format_small!("{bus:?}"));
+        }
+
         // Only update volumes if anything was changed.
         if new_settings != self.current_sound_settings() {
             self.change_sound_settings(new_settings);
@@ -42,14 +74,17 @@ impl DrawDebugUi for SoundSystem {
 
             ui.text("SFX:");
 
-            if ui.button("Play SFX (bleep)") {
-                let sound_key = self.load_sfx(PathRef::from_str("test/bleep.ogg"));
-                self.play_sfx(sound_key, *LOOPING);
-            }
+            let mut event_names: Vec<&str> = self.sound_event_names().collect();
+            event_names.sort_unstable();
+
+            static EVENT_INDEX: UiStaticVar<usize> = UiStaticVar::new(0);
+            ui.combo_simple_string("Sound Events", EVENT_INDEX.as_mut(), &event_names);
 
-            if ui.button("Play SFX (drums)") {
-                let sound_key = self.load_sfx(PathRef::from_str("test/drums.ogg"));
-                self.play_sfx(sound_key, *LOOPING);
+            if ui.button("Play Sound Event")
+                && let Some(event_name) = event_names.get(*EVENT_INDEX)
+            {
+                let event_key = SoundEventKey::new(event_name);
+                self.play_event(event_key, *LOOPING);
             }
 
             if ui.button("Stop All SFX") {
@@ -137,6 +137,12 @@ impl Engine {
         !self.app.should_quit()
     }
 
+    // Running with the Null app/render backends and no audio.
+    #[inline]
+    pub fn is_headless(&self) -> bool {
+        self.app.is_headless()
+    }
+
     #[inline]
     pub fn app_events(&self) -> &ApplicationEventList {
         &self.frame_events
@@ -155,6 +161,7 @@ impl Engine {
         let input_sys = self.app.input_system();
 
         self.render_system.begin_frame(self.app.window_size(), self.app.framebuffer_size());
+        self.ui_system.apply_pending_font_changes(&mut self.render_system);
         self.ui_system.begin_frame(&self.app, input_sys, self.frame_clock.delta_time());
 
         let begin_frame_time_ms = begin_frame_timer.end();
@@ -208,9 +215,14 @@ impl Engine {
         log::info!(log::channel!("engine"), "Content Scale: {}", app.content_scale());
 
         let ui_system = UiSystem::new(&mut render_system);
+        ui_system.set_ui_scale(configs.ui_scale);
         log::info!(log::channel!("engine"), "UiSystem initialized.");
 
-        let mut sound_system = SoundSystem::new(configs.sound_settings);
+        let mut sound_system = if app.is_headless() {
+            SoundSystem::new_null(configs.sound_settings)
+        } else {
+            SoundSystem::new(configs.sound_settings, configs.sound_output_device.as_deref())
+        };
         crate::ui::sound::initialize(&mut sound_system);
         log::info!(log::channel!("engine"), "SoundSystem initialized.");
 
@@ -268,6 +280,17 @@ impl Engine {
                         events_forwarded.push(event);
                     }
                 }
+                ApplicationEvent::PinchZoom(_) => {
+                    if !self.ui_system.is_handling_mouse_input() {
+                        events_forwarded.push(event);
+                    }
+                }
+                ApplicationEvent::PanGesture(delta) => {
+                    // Two-finger pans over a UI window scroll it, like a scroll wheel would.
+                    if self.ui_system.on_scroll(delta / PIXELS_PER_SCROLL_LINE).not_handled() {
+                        events_forwarded.push(event);
+                    }
+                }
             }
         }
 
@@ -0,0 +1,298 @@
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+use common::hash::{self, PreHashedKeyMap, StringHash};
+
+use super::{CollectFlags, paths};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Archive file name, expected next to the assets directory (under base_path).
+pub const ASSET_ARCHIVE_FILE_NAME: &str = "assets.pak";
+
+pub const ASSET_ARCHIVE_MAGIC: [u8; 4] = *b"HPAK";
+pub const ASSET_ARCHIVE_VERSION: u32 = 1;
+
+// ----------------------------------------------
+// AssetArchive
+// ----------------------------------------------
+
+// Read-only packed asset archive (.pak) used by release bundles in place of
+// the loose `assets/` directory. Files are stored uncompressed, back to back,
+// with an index up front, so loading an asset is a single seek + read.
+//
+// Layout (all integers little-endian):
+//   magic:   [u8; 4] = "HPAK"
+//   version: u32
+//   count:   u32
+//   index:   count * { path_len: u32, path: [u8; path_len], offset: u64, size: u64 }
+//   data:    raw file contents, offsets relative to the start of the archive.
+//
+// Entry paths are UTF-8, relative to the assets directory and use forward
+// slash separators (e.g. "configs/game.json").
+pub struct AssetArchive<R> {
+    reader: R,
+    entries: PreHashedKeyMap<StringHash, AssetArchiveEntry>,
+}
+
+struct AssetArchiveEntry {
+    path: String,
+    offset: u64,
+    size: u64,
+}
+
+impl<R: Read + Seek> AssetArchive<R> {
+    // Reads and validates the archive header and index. File contents are only read on demand.
+    pub fn open(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != ASSET_ARCHIVE_MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Not an asset archive (bad magic)"));
+        }
+
+        let version = read_u32(&mut reader)?;
+        if version != ASSET_ARCHIVE_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unsupported asset archive version {version} (expected {ASSET_ARCHIVE_VERSION})"),
+            ));
+        }
+
+        let count = read_u32(&mut reader)? as usize;
+        let mut entries = PreHashedKeyMap::default();
+        entries.reserve(count);
+
+        for _ in 0..count {
+            let path_len = read_u32(&mut reader)? as usize;
+            let mut path_bytes = vec![0u8; path_len];
+            reader.read_exact(&mut path_bytes)?;
+
+            let path = String::from_utf8(path_bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            let offset = read_u64(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+
+            let path_hash = hash::fnv1a_from_str(&path);
+            if entries.insert(path_hash, AssetArchiveEntry { path, offset, size }).is_some() {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "Duplicate or colliding asset archive entry"));
+            }
+        }
+
+        Ok(Self { reader, entries })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, relative_path: &str) -> bool {
+        self.find_entry(relative_path).is_some()
+    }
+
+    // True if any archived file lives under `relative_dir` (at any depth).
+    pub fn contains_dir(&self, relative_dir: &str) -> bool {
+        let relative_dir = relative_dir.trim_end_matches(paths::SEPARATOR_CHAR);
+        if relative_dir.is_empty() {
+            return !self.entries.is_empty();
+        }
+
+        self.entries.values().any(|entry| {
+            entry.path.strip_prefix(relative_dir).is_some_and(|rest| rest.starts_with(paths::SEPARATOR_CHAR))
+        })
+    }
+
+    pub fn read(&mut self, relative_path: &str) -> io::Result<Vec<u8>> {
+        let (offset, size) = match self.find_entry(relative_path) {
+            Some(entry) => (entry.offset, entry.size),
+            None => return Err(io::Error::new(io::ErrorKind::NotFound, "Asset not found in archive")),
+        };
+
+        let mut data = vec![0u8; size as usize];
+        self.reader.seek(SeekFrom::Start(offset))?;
+        self.reader.read_exact(&mut data)?;
+        Ok(data)
+    }
+
+    // Mirrors the loose file directory scan: returns the direct children of `relative_dir`
+    // joined to `root` (usually the assets directory), or bare file names with
+    // CollectFlags::FilenamesOnly. Sub directories are implied by the file entries.
+    pub fn collect_dir_entries(
+        &self,
+        relative_dir: &str,
+        root: &Path,
+        flags: CollectFlags,
+        extension: Option<&str>,
+    ) -> Vec<PathBuf> {
+        let relative_dir = relative_dir.trim_end_matches(paths::SEPARATOR_CHAR);
+        let mut result = Vec::new();
+
+        for entry in self.entries.values() {
+            let child = {
+                if relative_dir.is_empty() {
+                    entry.path.as_str()
+                } else {
+                    match entry.path.strip_prefix(relative_dir).and_then(|rest| rest.strip_prefix(paths::SEPARATOR_CHAR)) {
+                        Some(child) => child,
+                        None => continue,
+                    }
+                }
+            };
+
+            let join_dir = |name: &str| root.join(relative_dir).join(name);
+
+            match child.split_once(paths::SEPARATOR_CHAR) {
+                Some((sub_dir, _)) => {
+                    if flags.intersects(CollectFlags::SubDirs) {
+                        let sub_dir_path = join_dir(sub_dir);
+                        if !result.contains(&sub_dir_path) {
+                            result.push(sub_dir_path);
+                        }
+                    }
+                }
+                None => {
+                    if !flags.intersects(CollectFlags::Files) {
+                        continue;
+                    }
+                    if let Some(extension) = extension {
+                        if child.rsplit_once('.').is_none_or(|(_, ext)| ext != extension) {
+                            continue;
+                        }
+                    }
+                    if flags.intersects(CollectFlags::FilenamesOnly) {
+                        result.push(PathBuf::from(child));
+                    } else {
+                        result.push(join_dir(child));
+                    }
+                }
+            }
+        }
+
+        // Index is a hash map; keep results stable like a sorted directory listing.
+        result.sort();
+        result
+    }
+
+    fn find_entry(&self, relative_path: &str) -> Option<&AssetArchiveEntry> {
+        let path_hash = hash::fnv1a_from_str(relative_path);
+        self.entries.get(&path_hash).filter(|entry| entry.path == relative_path)
+    }
+}
+
+// ----------------------------------------------
+// Internal helpers
+// ----------------------------------------------
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    // Same layout the bundler tool writes.
+    fn build_archive(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut index_size = 12;
+        for (path, _) in files {
+            index_size += 4 + path.len() + 16;
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&ASSET_ARCHIVE_MAGIC);
+        bytes.extend_from_slice(&ASSET_ARCHIVE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(files.len() as u32).to_le_bytes());
+
+        let mut offset = index_size as u64;
+        for (path, data) in files {
+            bytes.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(path.as_bytes());
+            bytes.extend_from_slice(&offset.to_le_bytes());
+            bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            offset += data.len() as u64;
+        }
+
+        for (_, data) in files {
+            bytes.extend_from_slice(data);
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn read_archived_files() {
+        let bytes = build_archive(&[
+            ("configs/game.json", b"{}"),
+            ("tiles/terrain/grass.png", b"PNG"),
+            ("sounds/music/theme.mp3", b"MP3"),
+        ]);
+
+        let mut archive = AssetArchive::open(Cursor::new(bytes)).unwrap();
+        assert_eq!(archive.len(), 3);
+
+        assert!(archive.contains("configs/game.json"));
+        assert!(!archive.contains("configs/missing.json"));
+
+        assert_eq!(archive.read("tiles/terrain/grass.png").unwrap(), b"PNG");
+        assert_eq!(archive.read("configs/game.json").unwrap(), b"{}");
+        assert!(archive.read("configs").is_err());
+    }
+
+    #[test]
+    fn collect_archived_dir_entries() {
+        let bytes = build_archive(&[
+            ("tiles/terrain/grass.png", b""),
+            ("tiles/terrain/grass.json", b""),
+            ("tiles/buildings/house.png", b""),
+            ("tiles/readme.txt", b""),
+        ]);
+
+        let archive = AssetArchive::open(Cursor::new(bytes)).unwrap();
+
+        assert!(archive.contains_dir("tiles"));
+        assert!(archive.contains_dir("tiles/terrain/"));
+        assert!(!archive.contains_dir("tile"));
+
+        let sub_dirs = archive.collect_dir_entries("tiles", Path::new(""), CollectFlags::SubDirs, None);
+        assert_eq!(sub_dirs, [PathBuf::from("tiles/buildings"), PathBuf::from("tiles/terrain")]);
+
+        let flags = CollectFlags::Files | CollectFlags::FilenamesOnly;
+        let files = archive.collect_dir_entries("tiles/terrain", Path::new("assets"), flags, Some("png"));
+        assert_eq!(files, [PathBuf::from("grass.png")]);
+
+        let files = archive.collect_dir_entries("tiles", Path::new("assets"), CollectFlags::Files, None);
+        assert_eq!(files, [PathBuf::from("assets/tiles/readme.txt")]);
+    }
+
+    #[test]
+    fn reject_bad_header() {
+        assert!(AssetArchive::open(Cursor::new(b"ZIP!\x01\0\0\0\0\0\0\0".to_vec())).is_err());
+
+        let mut bytes = build_archive(&[]);
+        bytes[4] = 99; // Bump version.
+        assert!(AssetArchive::open(Cursor::new(bytes)).is_err());
+    }
+}
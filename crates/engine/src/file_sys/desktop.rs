@@ -1,6 +1,10 @@
-use std::{fs, sync::LazyLock};
+use std::{
+    fs,
+    io::{BufReader, BufWriter, Write},
+    sync::{LazyLock, Mutex},
+};
 
-use super::*;
+use super::{archive::AssetArchive, *};
 use crate::log;
 
 // ----------------------------------------------
@@ -8,6 +12,8 @@ use crate::log;
 // ----------------------------------------------
 
 // FileSystemBackend implementation for any platform that support std::fs.
+// If an asset archive (assets.pak) is present under base_path, paths inside
+// the assets directory are served from it, with loose files as fallback.
 pub struct StandardFileSystemBackend;
 
 impl FileSystemBackend for StandardFileSystemBackend {
@@ -27,19 +33,40 @@ impl FileSystemBackend for StandardFileSystemBackend {
         &CACHED_PATHS.assets_path
     }
 
+    #[inline]
+    fn user_config_path(&self) -> Option<&'static paths::FixedPath> {
+        CACHED_PATHS.user_config_path.as_ref()
+    }
+
     #[inline]
     fn exists(&self, path: impl AsRef<Path>) -> bool {
-        fs::exists(path).is_ok_and(|exists| exists)
+        let archived = with_asset_archive(path.as_ref(), |archive, relative_path| {
+            archive.contains(relative_path) || archive.contains_dir(relative_path)
+        });
+
+        archived.is_some_and(|archived| archived) || fs::exists(path).is_ok_and(|exists| exists)
     }
 
     #[inline]
+    fn is_archived(&self, path: impl AsRef<Path>) -> bool {
+        with_asset_archive(path.as_ref(), |archive, relative_path| archive.contains(relative_path))
+            .is_some_and(|archived| archived)
+    }
+
     fn load_bytes(&mut self, path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
-        fs::read(path)
+        let archived = with_asset_archive(path.as_ref(), |archive, relative_path| {
+            if archive.contains(relative_path) { Some(archive.read(relative_path)) } else { None }
+        });
+
+        match archived.flatten() {
+            Some(result) => result,
+            None => fs::read(path),
+        }
     }
 
-    #[inline]
     fn load_string(&mut self, path: impl AsRef<Path>) -> io::Result<String> {
-        fs::read_to_string(path)
+        let bytes = self.load_bytes(path)?;
+        String::from_utf8(bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
     }
 
     #[inline]
@@ -47,6 +74,33 @@ impl FileSystemBackend for StandardFileSystemBackend {
         fs::write(path, data)
     }
 
+    fn write_file_streamed(
+        &self,
+        path: impl AsRef<Path>,
+        write_fn: impl FnOnce(&mut dyn io::Write) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+
+        let write_temp = || -> io::Result<()> {
+            let mut writer = BufWriter::new(fs::File::create(&temp_path)?);
+            write_fn(&mut writer)?;
+            writer.flush()?;
+            writer.get_ref().sync_all()
+        };
+
+        // Only replace the target once the temp file is complete.
+        let result = write_temp().and_then(|_| fs::rename(&temp_path, path));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        result
+    }
+
     #[inline]
     fn remove_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
         fs::remove_file(path)
@@ -67,6 +121,19 @@ impl FileSystemBackend for StandardFileSystemBackend {
         flags: CollectFlags,
         extension: Option<&str>,
     ) -> io::Result<Vec<PathBuf>> {
+        let archived = with_asset_archive(path.as_ref(), |archive, relative_path| {
+            if archive.contains_dir(relative_path) {
+                let assets_path = CACHED_PATHS.assets_path.as_path();
+                Some(archive.collect_dir_entries(relative_path, assets_path, flags, extension))
+            } else {
+                None
+            }
+        });
+
+        if let Some(entries) = archived.flatten() {
+            return Ok(entries);
+        }
+
         let mut result = Vec::new();
 
         let entries = match fs::read_dir(path) {
@@ -132,6 +199,43 @@ impl StandardFileSystemBackend {
     }
 }
 
+// ----------------------------------------------
+// Asset Archive
+// ----------------------------------------------
+
+type MountedAssetArchive = Mutex<AssetArchive<BufReader<fs::File>>>;
+
+// Mounted on first use. Dev runs have no archive and read loose files only.
+static ASSET_ARCHIVE: LazyLock<Option<MountedAssetArchive>> = LazyLock::new(mount_asset_archive);
+
+fn mount_asset_archive() -> Option<MountedAssetArchive> {
+    let archive_path = CACHED_PATHS.base_path.join(archive::ASSET_ARCHIVE_FILE_NAME);
+    let file = fs::File::open(&archive_path).ok()?;
+
+    match AssetArchive::open(BufReader::new(file)) {
+        Ok(archive) => {
+            log::info!(log::channel!("file_sys"), "Mounted asset archive '{archive_path}' ({} files).", archive.len());
+            Some(Mutex::new(archive))
+        }
+        Err(err) => {
+            log::error!(log::channel!("file_sys"), "Failed to mount asset archive '{archive_path}': {err}");
+            None
+        }
+    }
+}
+
+// Runs `op` with the archive entry path for `path`. None if no archive is
+// mounted or `path` is not inside the assets directory.
+fn with_asset_archive<T>(path: &Path, op: impl FnOnce(&mut AssetArchive<BufReader<fs::File>>, &str) -> T) -> Option<T> {
+    let archive = ASSET_ARCHIVE.as_ref()?;
+
+    let relative_path = path.to_str()?.strip_prefix(CACHED_PATHS.assets_path.as_str())?;
+    let relative_path = paths::FixedPath::from_str(relative_path).normalized();
+
+    let mut archive = archive.lock().ok()?;
+    Some(op(&mut archive, relative_path.as_str()))
+}
+
 // ----------------------------------------------
 // Platform Path Handling
 // ----------------------------------------------
@@ -139,11 +243,18 @@ impl StandardFileSystemBackend {
 struct CachedPaths {
     base_path: paths::FixedPath,
     assets_path: paths::AssetPath,
+    user_config_path: Option<paths::FixedPath>,
 }
 
 // Cached on first use.
-static CACHED_PATHS: LazyLock<CachedPaths> =
-    LazyLock::new(|| CachedPaths { base_path: find_base_path(), assets_path: find_assets_path() });
+static CACHED_PATHS: LazyLock<CachedPaths> = LazyLock::new(|| CachedPaths {
+    base_path: find_base_path(),
+    assets_path: find_assets_path(),
+    user_config_path: find_user_config_path(),
+});
+
+// Name of the per-user directory created under the OS config directory.
+const USER_CONFIG_DIR_NAME: &str = "HeritageBuilder";
 
 fn find_base_path() -> paths::FixedPath {
     #[cfg(target_os = "macos")]
@@ -173,6 +284,26 @@ fn find_assets_path() -> paths::AssetPath {
     project_relative("assets")
 }
 
+fn find_user_config_path() -> Option<paths::FixedPath> {
+    let env_path = |var: &str| std::env::var_os(var).filter(|value| !value.is_empty()).map(PathBuf::from);
+
+    let os_config_dir = if cfg!(target_os = "windows") {
+        env_path("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        env_path("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        env_path("XDG_CONFIG_HOME").or_else(|| env_path("HOME").map(|home| home.join(".config")))
+    };
+
+    match os_config_dir {
+        Some(dir) => Some(paths::FixedPath::from_path(&dir).join(USER_CONFIG_DIR_NAME)),
+        None => {
+            log::warning!("Couldn't find the user config directory. Player settings will not persist.");
+            None
+        }
+    }
+}
+
 // Fallback for non-MacOS platforms or when running unbundled.
 // Returns a path relative to the project root.
 fn project_relative(relative_path: &str) -> paths::FixedPath {
@@ -222,3 +353,34 @@ fn macos_bundle_resources_path() -> Option<paths::FixedPath> {
 
     None
 }
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_streamed_write_keeps_existing_file() {
+        let path = std::env::temp_dir().join("heritage_builder_streamed_write_test.json");
+        let backend = StandardFileSystemBackend::get();
+
+        backend.write_file(&path, "previous").unwrap();
+
+        let result = backend.write_file_streamed(&path, |writer| {
+            writer.write_all(b"partial")?;
+            Err(io::Error::other("serialization failed"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "previous");
+        assert!(!fs::exists(path.with_extension("json.tmp")).unwrap());
+
+        backend.write_file_streamed(&path, |writer| writer.write_all(b"new")).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+
+        let _ = fs::remove_file(&path);
+    }
+}
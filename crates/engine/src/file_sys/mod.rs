@@ -6,6 +6,8 @@ use std::{
 use bitflags::bitflags;
 
 pub mod paths;
+pub mod archive;
+pub mod watcher;
 
 #[cfg(feature = "desktop")]
 mod desktop;
@@ -24,7 +26,8 @@ pub use web::preload_asset_cache;
 // ----------------------------------------------
 
 // File System operations wrapper.
-// - On desktop, accesses the filesystem directly using std::fs.
+// - On desktop, accesses the filesystem directly using std::fs,
+//   or the packed asset archive (assets.pak) in release bundles.
 // - On Web/WASM, reads from the pre-loaded asset cache.
 trait FileSystemBackend: Sized {
     // Tries to set the current working directory.
@@ -38,15 +41,27 @@ trait FileSystemBackend: Sized {
     // On other platforms or in dev runs, it falls back to `./assets`.
     fn assets_path(&self) -> &'static paths::AssetPath;
 
+    // Per-user directory for player preferences, following each OS' conventions.
+    // None if the platform has no writable filesystem (Web/WASM).
+    fn user_config_path(&self) -> Option<&'static paths::FixedPath>;
+
     // Test if the path exists (might be a directory or a file).
     fn exists(&self, path: impl AsRef<Path>) -> bool;
 
+    // Test if the file is served from a mounted asset archive instead of a loose file.
+    fn is_archived(&self, path: impl AsRef<Path>) -> bool;
+
     // Load file contents into memory.
     fn load_bytes(&mut self, path: impl AsRef<Path>) -> io::Result<Vec<u8>>;
     fn load_string(&mut self, path: impl AsRef<Path>) -> io::Result<String>;
 
     // Create/remove files/directories.
     fn write_file(&self, path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> io::Result<()>;
+    fn write_file_streamed(
+        &self,
+        path: impl AsRef<Path>,
+        write_fn: impl FnOnce(&mut dyn io::Write) -> io::Result<()>,
+    ) -> io::Result<()>;
     fn remove_file(&self, path: impl AsRef<Path>) -> io::Result<()>;
     fn create_path(&self, path: impl AsRef<Path>) -> io::Result<()>;
 
@@ -69,6 +84,13 @@ pub fn exists(path: impl AsRef<Path>) -> bool {
     FileSystemBackendImpl::get().exists(path)
 }
 
+// Checks if a file is served from the mounted asset archive (release bundles).
+// Consumers that would otherwise stream straight from disk must use load_bytes().
+#[inline]
+pub fn is_archived(path: impl AsRef<Path>) -> bool {
+    FileSystemBackendImpl::get().is_archived(path)
+}
+
 // Reads the entire contents of a file into a byte vector.
 #[inline]
 pub fn load_bytes(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
@@ -87,6 +109,16 @@ pub fn write_file(path: impl AsRef<Path>, data: impl AsRef<[u8]>) -> io::Result<
     FileSystemBackendImpl::get().write_file(path, data)
 }
 
+// Streams data into `<path>.tmp` through `write_fn`, then renames it over `path` once
+// everything was written and flushed. If any step fails the existing file is left intact.
+#[inline]
+pub fn write_file_streamed(
+    path: impl AsRef<Path>,
+    write_fn: impl FnOnce(&mut dyn io::Write) -> io::Result<()>,
+) -> io::Result<()> {
+    FileSystemBackendImpl::get().write_file_streamed(path, write_fn)
+}
+
 // Removes a file at the given path.
 #[inline]
 pub fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
@@ -389,6 +389,16 @@ pub fn assets_path() -> &'static AssetPath {
     FileSystemBackendImpl::get().assets_path()
 }
 
+// Per-user directory for player preferences:
+// - Windows: `%APPDATA%/HeritageBuilder`
+// - MacOS:   `~/Library/Application Support/HeritageBuilder`
+// - Linux:   `$XDG_CONFIG_HOME/HeritageBuilder` or `~/.config/HeritageBuilder`
+// None on Web/WASM, which has no writable filesystem.
+#[inline]
+pub fn user_config_path() -> Option<&'static FixedPath> {
+    FileSystemBackendImpl::get().user_config_path()
+}
+
 // Tries to set the current working directory.
 #[inline]
 pub fn set_working_directory(path: impl AsRef<Path>) {
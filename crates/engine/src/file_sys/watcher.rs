@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use crate::log;
+
+// ----------------------------------------------
+// FileWatcher
+// ----------------------------------------------
+
+// Watches a directory tree for created or modified files, for hot-reloading
+// assets during development. Only available on desktop and for loose files;
+// assets served from an archive or from the web asset cache cannot be watched.
+pub struct FileWatcher {
+    root: PathBuf, // Canonical path of the watched directory.
+
+    #[cfg(feature = "desktop")]
+    _watcher: notify::RecommendedWatcher,
+
+    #[cfg(feature = "desktop")]
+    receiver: std::sync::mpsc::Receiver<notify::Result<notify::Event>>,
+}
+
+impl FileWatcher {
+    #[cfg(feature = "desktop")]
+    pub fn new(path: impl AsRef<Path>) -> Option<Self> {
+        use notify::Watcher;
+
+        let path = path.as_ref();
+
+        let root = match std::fs::canonicalize(path) {
+            Ok(root) => root,
+            Err(err) => {
+                log::error!(log::channel!("file_sys"), "Cannot watch '{}': {err}", path.display());
+                return None;
+            }
+        };
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        let mut watcher = match notify::recommended_watcher(sender) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                log::error!(log::channel!("file_sys"), "Failed to create file watcher: {err}");
+                return None;
+            }
+        };
+
+        if let Err(err) = watcher.watch(&root, notify::RecursiveMode::Recursive) {
+            log::error!(log::channel!("file_sys"), "Failed to watch '{}': {err}", root.display());
+            return None;
+        }
+
+        log::info!(log::channel!("file_sys"), "Watching '{}' for file changes.", root.display());
+        Some(Self { root, _watcher: watcher, receiver })
+    }
+
+    #[cfg(feature = "web")]
+    pub fn new(path: impl AsRef<Path>) -> Option<Self> {
+        log::info!(log::channel!("file_sys"), "File watching not supported on Web. Ignoring '{}'.", path.as_ref().display());
+        None
+    }
+
+    // Files created or modified since the last call, relative to the watched directory.
+    // Each file is listed once, even if it was written to several times.
+    #[cfg(feature = "desktop")]
+    pub fn poll_changed_files(&mut self) -> Vec<PathBuf> {
+        let mut changed_files: Vec<PathBuf> = Vec::new();
+
+        for result in self.receiver.try_iter() {
+            let event = match result {
+                Ok(event) => event,
+                Err(err) => {
+                    log::error!(log::channel!("file_sys"), "File watcher error: {err}");
+                    continue;
+                }
+            };
+
+            if !matches!(event.kind, notify::EventKind::Create(_) | notify::EventKind::Modify(_)) {
+                continue;
+            }
+
+            for path in event.paths {
+                if let Ok(relative_path) = path.strip_prefix(&self.root)
+                    && !changed_files.iter().any(|changed| changed == relative_path)
+                {
+                    changed_files.push(relative_path.to_path_buf());
+                }
+            }
+        }
+
+        changed_files
+    }
+
+    #[cfg(feature = "web")]
+    pub fn poll_changed_files(&mut self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
@@ -105,11 +105,21 @@ impl FileSystemBackend for WebFileSystemBackend {
         &CACHED_PATHS.assets_path
     }
 
+    #[inline]
+    fn user_config_path(&self) -> Option<&'static paths::FixedPath> {
+        None
+    }
+
     #[inline]
     fn exists(&self, path: impl AsRef<Path>) -> bool {
         self.find_asset(path).is_some()
     }
 
+    #[inline]
+    fn is_archived(&self, _path: impl AsRef<Path>) -> bool {
+        false // Assets are preloaded into the cache; no archive on Web/WASM.
+    }
+
     #[inline]
     fn load_bytes(&mut self, path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
         // NOTE: Data is consumed after one cache lookup. This avoids having to clone the data.
@@ -130,6 +140,14 @@ impl FileSystemBackend for WebFileSystemBackend {
         Err(io::Error::new(io::ErrorKind::Unsupported, "Direct file write not supported on Web/WASM"))
     }
 
+    fn write_file_streamed(
+        &self,
+        _path: impl AsRef<Path>,
+        _write_fn: impl FnOnce(&mut dyn io::Write) -> io::Result<()>,
+    ) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Direct file write not supported on Web/WASM"))
+    }
+
     fn remove_file(&self, _path: impl AsRef<Path>) -> io::Result<()> {
         Err(io::Error::new(io::ErrorKind::Unsupported, "File removal not supported on Web/WASM"))
     }
@@ -1,10 +1,7 @@
 use std::{
     fmt,
     hash::{Hash, Hasher},
-    sync::{
-        OnceLock,
-        atomic::{AtomicBool, AtomicU32, Ordering},
-    },
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
 };
 
 use common::{
@@ -22,6 +19,12 @@ mod desktop;
 #[cfg(feature = "web")]
 mod web;
 
+mod sink;
+pub use sink::{LogFileSinkConfig, LogSink, LogSinkFilter, LogSinkId, add_sink, remove_sink};
+
+#[cfg(feature = "desktop")]
+pub use sink::{FileSink, register_file_sinks};
+
 // ----------------------------------------------
 // Log Levels
 // ----------------------------------------------
@@ -78,6 +81,11 @@ impl Channel {
     pub const fn new(name: &'static str) -> Self {
         Self { name, hash: hash::fnv1a_from_str(name) }
     }
+
+    // Hash of a channel created with `log::channel!(name)`, for matching channels by name at runtime.
+    pub fn hash_for_name(name: &str) -> StringHash {
+        hash::fnv1a_from_str(&format!(" [{name}]"))
+    }
 }
 
 impl Hash for Channel {
@@ -95,24 +103,17 @@ macro_rules! log_channel {
 }
 
 // ----------------------------------------------
-// Log Listener
+// Log Record
 // ----------------------------------------------
 
+// What log sinks receive. See `log::add_sink()`.
+#[derive(Clone)]
 pub struct Record {
     pub level: Level,
     pub channel: Option<Channel>,
     pub location: Location,
     pub message: String,
-}
-
-// One global listener, set once.
-static LISTENER: OnceLock<Box<dyn Fn(Record) + Send + Sync>> = OnceLock::new();
-
-pub fn set_listener<F>(listener_fn: F)
-where
-    F: Fn(Record) + Send + Sync + 'static,
-{
-    LISTENER.set(Box::new(listener_fn)).unwrap_or_else(|_| panic!("Log listener can only be set once!"));
+    pub timestamp_secs: u64, // Unix timestamp.
 }
 
 // ----------------------------------------------
@@ -166,9 +167,7 @@ pub fn print_internal(level: Level, channel: Option<Channel>, location: &Locatio
     #[cfg(feature = "web")]
     web::output_log(level, channel, location, args);
 
-    if let Some(listener) = LISTENER.get() {
-        listener(Record { level, channel, location: *location, message: args.to_string() });
-    }
+    sink::dispatch(level, channel, location, args);
 }
 
 // Shared helper used by all logging macros.
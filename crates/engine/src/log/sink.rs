@@ -0,0 +1,297 @@
+use std::{
+    fmt,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+};
+
+use common::{hash::StringHash, time};
+use serde::{Deserialize, Serialize};
+
+use super::{Channel, Level, Location, Record};
+
+// ----------------------------------------------
+// LogSink
+// ----------------------------------------------
+
+// Receives every log record accepted by the filter it was registered with.
+// Sinks are called with the sink registry locked, so they must never log themselves.
+pub trait LogSink: Send {
+    fn write(&mut self, record: &Record);
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct LogSinkId(u32);
+
+// ----------------------------------------------
+// LogSinkFilter
+// ----------------------------------------------
+
+pub struct LogSinkFilter {
+    pub min_level: Level,
+    // None accepts all records, including the ones without a channel.
+    pub channels: Option<Vec<StringHash>>,
+}
+
+impl LogSinkFilter {
+    pub fn all() -> Self {
+        Self { min_level: Level::Verbose, channels: None }
+    }
+
+    // Channel names as given to `log::channel!`, e.g. "session" or "sound".
+    pub fn with_channel_names<S: AsRef<str>>(names: &[S]) -> Self {
+        let channels = names.iter().map(|name| Channel::hash_for_name(name.as_ref())).collect();
+        Self { min_level: Level::Verbose, channels: Some(channels) }
+    }
+
+    #[inline]
+    fn accepts(&self, level: Level, channel: Option<Channel>) -> bool {
+        if level < self.min_level {
+            return false;
+        }
+
+        match (&self.channels, channel) {
+            (None, _) => true,
+            (Some(channels), Some(channel)) => channels.contains(&channel.hash),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+// ----------------------------------------------
+// Sink Registry
+// ----------------------------------------------
+
+struct RegisteredSink {
+    id: LogSinkId,
+    filter: LogSinkFilter,
+    sink: Box<dyn LogSink>,
+}
+
+static SINKS: Mutex<Vec<RegisteredSink>> = Mutex::new(Vec::new());
+static HAS_SINKS: AtomicBool = AtomicBool::new(false); // Skips formatting & locking when empty.
+static NEXT_SINK_ID: AtomicU32 = AtomicU32::new(1);
+
+pub fn add_sink(filter: LogSinkFilter, sink: Box<dyn LogSink>) -> LogSinkId {
+    let id = LogSinkId(NEXT_SINK_ID.fetch_add(1, Ordering::Relaxed));
+
+    let mut sinks = SINKS.lock().unwrap();
+    sinks.push(RegisteredSink { id, filter, sink });
+    HAS_SINKS.store(true, Ordering::Relaxed);
+
+    id
+}
+
+pub fn remove_sink(id: LogSinkId) -> bool {
+    let mut sinks = SINKS.lock().unwrap();
+
+    let Some(index) = sinks.iter().position(|registered| registered.id == id) else {
+        return false;
+    };
+
+    sinks.remove(index);
+    HAS_SINKS.store(!sinks.is_empty(), Ordering::Relaxed);
+    true
+}
+
+pub(super) fn dispatch(level: Level, channel: Option<Channel>, location: &Location, args: fmt::Arguments) {
+    if !HAS_SINKS.load(Ordering::Relaxed) {
+        return;
+    }
+
+    // Tolerate poisoning so a sink that panicked doesn't take down logging from the panic hook.
+    let mut sinks = SINKS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    // Only format the message if some sink wants it.
+    let mut record = None;
+
+    for registered in sinks.iter_mut() {
+        if !registered.filter.accepts(level, channel) {
+            continue;
+        }
+
+        let record = record.get_or_insert_with(|| Record {
+            level,
+            channel,
+            location: *location,
+            message: args.to_string(),
+            timestamp_secs: time::unix_timestamp_secs(),
+        });
+
+        registered.sink.write(record);
+    }
+}
+
+// ----------------------------------------------
+// LogFileSinkConfig
+// ----------------------------------------------
+
+// Routes a set of channels to their own file under the logs directory.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogFileSinkConfig {
+    pub file_name: String,
+    pub channels: Vec<String>, // Empty routes every channel to the file.
+    pub min_level: Level,
+    pub max_file_size_kb: u32, // The file is rotated once it grows past this size.
+    pub max_rotated_files: u32,
+}
+
+impl Default for LogFileSinkConfig {
+    fn default() -> Self {
+        Self {
+            file_name: String::new(),
+            channels: Vec::new(),
+            min_level: Level::Verbose,
+            max_file_size_kb: 1024,
+            max_rotated_files: 3,
+        }
+    }
+}
+
+// ----------------------------------------------
+// FileSink
+// ----------------------------------------------
+
+// Writes timestamped lines to a file, rotating it once it exceeds `max_file_size_bytes`:
+// `name.log` -> `name.log.1` -> `name.log.2` ..., keeping at most `max_rotated_files` old files.
+#[cfg(feature = "desktop")]
+pub struct FileSink {
+    path: std::path::PathBuf,
+    file: Option<std::fs::File>,
+    file_size_bytes: u64,
+    max_file_size_bytes: u64,
+    max_rotated_files: u32,
+}
+
+#[cfg(feature = "desktop")]
+impl FileSink {
+    pub fn new(path: impl Into<std::path::PathBuf>, max_file_size_bytes: u64, max_rotated_files: u32) -> Self {
+        let mut sink = Self {
+            path: path.into(),
+            file: None,
+            file_size_bytes: 0,
+            max_file_size_bytes,
+            max_rotated_files,
+        };
+        sink.open();
+        sink
+    }
+
+    fn open(&mut self) {
+        if let Some(parent) = self.path.parent() {
+            let _ = crate::file_sys::create_path(parent);
+        }
+
+        self.file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path).ok();
+        self.file_size_bytes = self.file.as_ref().and_then(|file| file.metadata().ok()).map_or(0, |meta| meta.len());
+    }
+
+    fn rotated_path(&self, index: u32) -> std::path::PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    fn rotate(&mut self) {
+        self.file = None;
+
+        if self.max_rotated_files == 0 {
+            let _ = std::fs::remove_file(&self.path);
+        } else {
+            // Shift older files up, dropping the oldest.
+            let _ = std::fs::remove_file(self.rotated_path(self.max_rotated_files));
+            for index in (1..self.max_rotated_files).rev() {
+                let _ = std::fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+            }
+            let _ = std::fs::rename(&self.path, self.rotated_path(1));
+        }
+
+        self.open();
+    }
+}
+
+#[cfg(feature = "desktop")]
+impl LogSink for FileSink {
+    fn write(&mut self, record: &Record) {
+        use std::io::Write;
+
+        if self.max_file_size_bytes != 0 && self.file_size_bytes >= self.max_file_size_bytes {
+            self.rotate();
+        }
+
+        let Some(file) = &mut self.file else {
+            return; // Failed to open. Can't log the error from inside a sink.
+        };
+
+        let chan_str = record.channel.map(|chan| chan.name).unwrap_or_default();
+        let line = format!(
+            "{}:{:02} [{:?}]{} {}\n",
+            time::format_unix_timestamp(record.timestamp_secs),
+            record.timestamp_secs % 60,
+            record.level,
+            chan_str,
+            record.message
+        );
+
+        if file.write_all(line.as_bytes()).is_ok() {
+            self.file_size_bytes += line.len() as u64;
+        } else {
+            self.file = None;
+        }
+    }
+}
+
+// Registers a FileSink under the logs directory for each config entry.
+#[cfg(feature = "desktop")]
+pub fn register_file_sinks(sink_configs: &[LogFileSinkConfig]) {
+    for config in sink_configs {
+        if config.file_name.is_empty() {
+            super::error!(super::channel!("log"), "Log file sink with empty file name. Ignored.");
+            continue;
+        }
+
+        let mut filter = if config.channels.is_empty() {
+            LogSinkFilter::all()
+        } else {
+            LogSinkFilter::with_channel_names(&config.channels)
+        };
+        filter.min_level = config.min_level;
+
+        let path = super::logs_path().join(&config.file_name);
+        let sink = FileSink::new(path.as_str(), config.max_file_size_kb as u64 * 1024, config.max_rotated_files);
+
+        add_sink(filter, Box::new(sink));
+
+        super::info!(
+            super::channel!("log"),
+            "Routing log channels {:?} to {path}.",
+            config.channels
+        );
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_matches_channels_by_name_and_level() {
+        let mut filter = LogSinkFilter::with_channel_names(&["sound"]);
+        filter.min_level = Level::Info;
+
+        let sound = crate::log::channel!("sound");
+        let render = crate::log::channel!("render");
+
+        assert!(filter.accepts(Level::Warning, Some(sound)));
+        assert!(!filter.accepts(Level::Verbose, Some(sound)));
+        assert!(!filter.accepts(Level::Error, Some(render)));
+        assert!(!filter.accepts(Level::Error, None));
+        assert!(LogSinkFilter::all().accepts(Level::Verbose, None));
+    }
+}
@@ -1,6 +1,9 @@
 use std::{fmt::Write as _, panic, path::Path};
 use backtrace::Backtrace;
-use crate::log;
+use crate::{file_sys, log, platform};
+
+// Written next to the runtime log (see `log::logs_path`).
+const CRASH_REPORT_FILENAME: &str = "crash_report.log";
 
 // ----------------------------------------------
 // DebugBacktrace
@@ -82,7 +85,42 @@ pub fn initialize(set_panic_hook: bool) {
         log::error!("         BACKTRACE        ");
         log::error!("==========================");
 
-        let backtrace = DebugBacktrace::capture();
-        log::error!("\n{}\n", backtrace.to_string(0, 0));
+        let backtrace = DebugBacktrace::capture().to_string(0, 0);
+        log::error!("\n{backtrace}\n");
+
+        let sections = platform::crash_report_sections();
+        if !sections.is_empty() {
+            log::error!("\n{sections}");
+        }
+
+        write_crash_report_file(error_message, &location, &backtrace, &sections);
+
+        platform::run_crash_emergency_fn();
     }));
 }
+
+// ----------------------------------------------
+// Internal helpers
+// ----------------------------------------------
+
+fn write_crash_report_file(error_message: &str, location: &str, backtrace: &str, sections: &str) {
+    let mut report = String::new();
+
+    writeln!(report, "Message  : {error_message}").unwrap();
+    writeln!(report, "Location : {location}").unwrap();
+    writeln!(report).unwrap();
+    writeln!(report, "==========================").unwrap();
+    writeln!(report, "         BACKTRACE        ").unwrap();
+    writeln!(report, "==========================").unwrap();
+    writeln!(report, "\n{backtrace}").unwrap();
+    report.push_str(sections);
+
+    let logs_path = log::logs_path();
+    let _ = file_sys::create_path(&logs_path);
+
+    let file_path = logs_path.join(CRASH_REPORT_FILENAME);
+    match std::fs::write(file_path.as_str(), report) {
+        Ok(_) => log::error!("Crash report written to: {file_path}"),
+        Err(err) => log::error!("Failed to write crash report file {file_path}: {err}"),
+    }
+}
@@ -1,3 +1,4 @@
+use std::{fmt::Write as _, sync::Mutex};
 use strum::Display;
 
 use crate::{file_sys::paths, log};
@@ -51,6 +52,58 @@ pub fn build_profile() -> BuildProfile {
     }
 }
 
+// ----------------------------------------------
+// Crash Report Sections
+// ----------------------------------------------
+
+// Returns extra diagnostics text to append to the crash report, e.g. recent game history.
+// Called from the panic hook, so it must not panic itself and should avoid locking.
+pub type CrashReportSectionFn = fn() -> String;
+
+static CRASH_REPORT_SECTIONS: Mutex<Vec<(&'static str, CrashReportSectionFn)>> = Mutex::new(Vec::new());
+
+pub fn add_crash_report_section(name: &'static str, section_fn: CrashReportSectionFn) {
+    let mut sections = CRASH_REPORT_SECTIONS.lock().unwrap();
+    if !sections.iter().any(|(section_name, _)| *section_name == name) {
+        sections.push((name, section_fn));
+    }
+}
+
+fn crash_report_sections() -> String {
+    // Don't deadlock or double panic if we crashed while registering a section.
+    let Ok(sections) = CRASH_REPORT_SECTIONS.try_lock() else {
+        return String::new();
+    };
+
+    let mut out = String::new();
+
+    for (name, section_fn) in sections.iter() {
+        writeln!(out, "==========================").unwrap();
+        writeln!(out, "  {name}").unwrap();
+        writeln!(out, "==========================").unwrap();
+        writeln!(out, "\n{}\n", section_fn()).unwrap();
+    }
+
+    out
+}
+
+// Last chance callback, e.g. an emergency save. Runs from the panic hook after the crash
+// report has been logged and written, so a second panic inside it only loses the callback.
+pub type CrashEmergencyFn = fn();
+
+static CRASH_EMERGENCY_FN: Mutex<Option<CrashEmergencyFn>> = Mutex::new(None);
+
+pub fn set_crash_emergency_fn(emergency_fn: Option<CrashEmergencyFn>) {
+    *CRASH_EMERGENCY_FN.lock().unwrap() = emergency_fn;
+}
+
+fn run_crash_emergency_fn() {
+    let emergency_fn = CRASH_EMERGENCY_FN.try_lock().ok().and_then(|emergency_fn| *emergency_fn);
+    if let Some(emergency_fn) = emergency_fn {
+        emergency_fn();
+    }
+}
+
 // ----------------------------------------------
 // Platform Initialization
 // ----------------------------------------------
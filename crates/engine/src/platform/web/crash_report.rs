@@ -1,4 +1,5 @@
-use crate::log;
+use std::panic;
+use crate::{log, platform};
 
 pub fn initialize(set_panic_hook: bool) {
     if !set_panic_hook {
@@ -7,7 +8,16 @@ pub fn initialize(set_panic_hook: bool) {
 
     log::info!(log::channel!("crash_report"), "Setting WASM panic hook ...");
 
-    // Install console_error_panic_hook so that panics produce readable
+    // Use console_error_panic_hook so that panics produce readable
     // stack traces in the browser console instead of "unreachable executed".
-    console_error_panic_hook::set_once();
+    panic::set_hook(Box::new(|panic_info| {
+        console_error_panic_hook::hook(panic_info);
+
+        let sections = platform::crash_report_sections();
+        if !sections.is_empty() {
+            log::error!("\n{sections}");
+        }
+
+        platform::run_crash_emergency_fn();
+    }));
 }
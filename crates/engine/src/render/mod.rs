@@ -1,5 +1,6 @@
-use common::{Color, Rect, RectTexCoords, Size, Vec2, mem::RcMut, time::Milliseconds};
+use common::{Color, Rect, RectTexCoords, Size, Vec2, coords::WorldToScreenTransform, mem::RcMut, time::Milliseconds};
 use enum_dispatch::enum_dispatch;
+use image::RgbaImage;
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
@@ -17,12 +18,18 @@ pub(crate) mod wgpu;
 #[cfg(feature = "desktop")]
 mod opengl;
 
+#[cfg(feature = "desktop")]
+mod null;
+
 #[enum_dispatch]
 enum RenderSystemBackendImpl {
     Wgpu(wgpu::WgpuRenderSystemBackend),
 
     #[cfg(feature = "desktop")]
     OpenGl(opengl::OpenGlRenderSystemBackend),
+
+    #[cfg(feature = "desktop")]
+    Null(null::NullRenderSystemBackend),
 }
 
 #[derive(Copy, Clone, Default, PartialEq, Eq, Display, Serialize, Deserialize)]
@@ -32,6 +39,10 @@ pub enum RenderApi {
 
     #[cfg(feature = "desktop")]
     OpenGl,
+
+    // Draws nothing. Used for headless runs, together with ApplicationApi::Null.
+    #[cfg(feature = "desktop")]
+    Null,
 }
 
 // ----------------------------------------------
@@ -75,8 +86,30 @@ trait RenderSystemBackend: Sized {
         tex_coords: &RectTexCoords,
         texture: texture::TextureHandle,
         color: Color,
+        palette: PaletteSwap,
     );
 
+    // Selection outline mask (drawn as a screen-space outline after the sprites pass):
+    fn draw_outline_mask_rect(
+        &mut self,
+        rect: Rect,
+        tex_coords: &RectTexCoords,
+        texture: texture::TextureHandle,
+        outline_color: Color,
+    );
+
+    // Static sprite meshes (geometry kept resident on the GPU between frames):
+    fn new_static_sprite_mesh(&mut self) -> StaticSpriteMeshHandle;
+    fn update_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, sprites: &[StaticSprite]);
+    fn release_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle);
+    fn draw_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, transform: WorldToScreenTransform);
+
+    // Post-processing:
+    fn set_color_grading(&mut self, color_grading: Option<ColorGrading>);
+
+    // Frame capture (reads back the offscreen render target):
+    fn capture_frame(&mut self) -> Option<CapturedFrame>;
+
     // Line/point debug drawing:
     fn draw_line(&mut self, from_pos: Vec2, to_pos: Vec2, from_color: Color, to_color: Color);
     fn draw_point(&mut self, pt: Vec2, color: Color, size: f32);
@@ -118,6 +151,10 @@ pub struct RenderStats {
     pub points_drawn: u32,
     pub texture_changes: u32,
     pub draw_calls: u32,
+    pub sprites_submitted: u32, // Sprite draws requested this frame (quads/triangle lists).
+    pub sprite_batches: u32,    // Sprite draw calls after merging runs sharing texture + tint.
+    pub static_meshes: u32,     // Static sprite meshes drawn this frame.
+    pub buffer_reallocs: u32,   // GPU vertex/index buffer storage (re)allocations. Should settle to zero.
     pub render_submit_time_ms: Milliseconds,
 
     // Peaks for the whole run:
@@ -126,6 +163,10 @@ pub struct RenderStats {
     pub peak_points_drawn: u32,
     pub peak_texture_changes: u32,
     pub peak_draw_calls: u32,
+    pub peak_sprites_submitted: u32,
+    pub peak_sprite_batches: u32,
+    pub peak_static_meshes: u32,
+    pub peak_buffer_reallocs: u32,
 }
 
 // ----------------------------------------------
@@ -171,6 +212,7 @@ pub struct RenderSystem {
     render_api: RenderApi,
     backend: RenderSystemBackendImpl,
     tex_cache: texture::TextureCache,
+    color_grading: Option<ColorGrading>,
 }
 
 impl RenderSystem {
@@ -191,6 +233,7 @@ impl RenderSystem {
             let backend = match params.render_api {
                 RenderApi::Wgpu   => RenderSystemBackendImpl::from(wgpu::WgpuRenderSystemBackend::new(&params)),
                 RenderApi::OpenGl => RenderSystemBackendImpl::from(opengl::OpenGlRenderSystemBackend::new(&params)),
+                RenderApi::Null   => RenderSystemBackendImpl::from(null::NullRenderSystemBackend::new(&params)),
             };
 
             #[cfg(feature = "web")]
@@ -208,7 +251,7 @@ impl RenderSystem {
             let tex_cache =
                 texture::TextureCache::new(render_system, params.tex_cache_initial_capacity, params.texture_settings);
 
-            Self { render_api: params.render_api, backend, tex_cache }
+            Self { render_api: params.render_api, backend, tex_cache, color_grading: None }
         });
 
         render_system.tex_cache.create_default_textures();
@@ -312,7 +355,106 @@ impl RenderSystem {
         texture: texture::TextureHandle,
         color: Color,
     ) {
-        self.backend.draw_textured_colored_rect(rect, tex_coords, texture, color);
+        self.backend.draw_textured_colored_rect(rect, tex_coords, texture, color, PaletteSwap::NONE);
+    }
+
+    // Same as draw_textured_colored_rect() but recolors the sprite's palette key pixels. See PaletteSwap.
+    #[inline]
+    pub fn draw_palette_swapped_rect(
+        &mut self,
+        rect: Rect,
+        tex_coords: &RectTexCoords,
+        texture: texture::TextureHandle,
+        color: Color,
+        palette: PaletteSwap,
+    ) {
+        self.backend.draw_textured_colored_rect(rect, tex_coords, texture, color, palette);
+    }
+
+    // Adds a sprite silhouette to the selection outline mask. The sprite itself is not drawn;
+    // callers still draw it normally with draw_textured_colored_rect(). Once all sprites are
+    // drawn, a colored outline of `outline_color` is traced around the combined silhouettes
+    // of the frame. Overlapping sprites merge into a single outline.
+    #[inline]
+    pub fn draw_outline_mask_rect(
+        &mut self,
+        rect: Rect,
+        tex_coords: &RectTexCoords,
+        texture: texture::TextureHandle,
+        outline_color: Color,
+    ) {
+        self.backend.draw_outline_mask_rect(rect, tex_coords, texture, outline_color);
+    }
+
+    // Static sprite meshes are for geometry that rarely changes, like terrain. Sprite rects are
+    // given in world space and uploaded once; the world-to-screen transform is applied on the GPU
+    // when drawing, so camera panning and zooming never touch the vertices. Meshes draw before
+    // any sprites submitted with draw_textured_colored_rect() in the same frame.
+    #[inline]
+    pub fn new_static_sprite_mesh(&mut self) -> StaticSpriteMeshHandle {
+        self.backend.new_static_sprite_mesh()
+    }
+
+    #[inline]
+    pub fn update_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, sprites: &[StaticSprite]) {
+        debug_assert!(mesh.is_valid());
+        debug_assert!(sprites.len() <= MAX_STATIC_MESH_SPRITES, "Too many sprites for a static mesh!");
+        self.backend.update_static_sprite_mesh(mesh, sprites);
+    }
+
+    #[inline]
+    pub fn release_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle) {
+        debug_assert!(mesh.is_valid());
+        self.backend.release_static_sprite_mesh(mesh);
+    }
+
+    // Each mesh can be drawn at most once per frame. No culling is done here; callers
+    // should skip meshes whose world space bounds are offscreen.
+    #[inline]
+    pub fn draw_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, transform: WorldToScreenTransform) {
+        debug_assert!(mesh.is_valid());
+        debug_assert!(transform.is_valid());
+        self.backend.draw_static_sprite_mesh(mesh, transform);
+    }
+
+    // ----------------------
+    // Post-processing:
+    // ----------------------
+
+    // Color grades the world (everything but the UI) through a color lookup table, e.g. for a
+    // night or seasonal look. Stays in effect until changed; pass None to disable.
+    #[inline]
+    pub fn set_color_grading(&mut self, color_grading: Option<ColorGrading>) {
+        if let Some(color_grading) = &color_grading {
+            debug_assert!(color_grading.lut.is_valid());
+            debug_assert!((0.0..=1.0).contains(&color_grading.strength));
+        }
+        self.color_grading = color_grading;
+        self.backend.set_color_grading(color_grading);
+    }
+
+    #[inline]
+    pub fn color_grading(&self) -> Option<ColorGrading> {
+        self.color_grading
+    }
+
+    // ----------------------
+    // Frame capture:
+    // ----------------------
+
+    // Reads back the world drawn by the last completed frame: the offscreen render target before
+    // color grading and without the UI. Call between frames. Stalls until the GPU catches up, so
+    // it is meant for screenshots, not per-frame use. Returns None if the readback failed.
+    pub fn capture_frame(&mut self) -> Option<CapturedFrame> {
+        let mut frame = self.backend.capture_frame()?;
+        debug_assert!(frame.pixels.len() == (frame.size.width * frame.size.height * 4) as usize);
+
+        // Blending leaves partial alpha in the render target. Captures are always opaque.
+        for pixel in frame.pixels.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+
+        Some(frame)
     }
 
     #[inline]
@@ -526,6 +668,137 @@ impl RenderSystem {
     }
 }
 
+// ----------------------------------------------
+// StaticSprite / StaticSpriteMeshHandle
+// ----------------------------------------------
+
+// Sprites are 4 vertices each and static meshes use 16-bit indices.
+pub const MAX_STATIC_MESH_SPRITES: usize = (u16::MAX as usize + 1) / 4;
+
+#[derive(Copy, Clone)]
+pub struct StaticSprite {
+    pub rect: Rect, // World space, before applying the WorldToScreenTransform.
+    pub tex_coords: RectTexCoords,
+    pub texture: texture::TextureHandle,
+    pub color: Color,
+    pub palette: PaletteSwap,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct StaticSpriteMeshHandle(u32);
+
+impl StaticSpriteMeshHandle {
+    #[inline]
+    pub const fn invalid() -> Self {
+        Self(u32::MAX)
+    }
+
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.0 != u32::MAX
+    }
+
+    #[inline]
+    fn index(&self) -> usize {
+        debug_assert!(self.is_valid());
+        self.0 as usize
+    }
+}
+
+impl Default for StaticSpriteMeshHandle {
+    #[inline]
+    fn default() -> Self {
+        Self::invalid()
+    }
+}
+
+// Backends keep their meshes in a Vec of slots indexed by handle.
+// Released slots are reused by the next allocation.
+fn alloc_static_mesh_slot<T>(slots: &mut Vec<Option<T>>, mesh: T) -> StaticSpriteMeshHandle {
+    if let Some(index) = slots.iter().position(Option::is_none) {
+        slots[index] = Some(mesh);
+        StaticSpriteMeshHandle(index as u32)
+    } else {
+        slots.push(Some(mesh));
+        StaticSpriteMeshHandle((slots.len() - 1) as u32)
+    }
+}
+
+fn static_mesh_slot<T>(slots: &mut [Option<T>], mesh: StaticSpriteMeshHandle) -> &mut T {
+    slots[mesh.index()].as_mut().expect("Static sprite mesh was released!")
+}
+
+fn free_static_mesh_slot<T>(slots: &mut [Option<T>], mesh: StaticSpriteMeshHandle) {
+    let slot = &mut slots[mesh.index()];
+    debug_assert!(slot.is_some(), "Static sprite mesh released twice!");
+    *slot = None;
+}
+
+// ----------------------------------------------
+// PaletteSwap
+// ----------------------------------------------
+
+// Recolors sprite pixels painted in a palette key color, keeping their shading. Any shade
+// of pure magenta (R = B, G = 0) is a primary key pixel and any shade of pure cyan (G = B,
+// R = 0) is a secondary key pixel. A key pixel becomes the channel color scaled by the key
+// brightness, blended in by the channel alpha. Transparent channels leave key pixels as-is.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaletteSwap {
+    #[serde(default = "Color::transparent")]
+    pub primary: Color,
+
+    #[serde(default = "Color::transparent")]
+    pub secondary: Color,
+}
+
+impl PaletteSwap {
+    pub const NONE: Self = Self { primary: Color::transparent(), secondary: Color::transparent() };
+
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.primary.a <= 0.0 && self.secondary.a <= 0.0
+    }
+}
+
+impl Default for PaletteSwap {
+    #[inline]
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+// ----------------------------------------------
+// ColorGrading
+// ----------------------------------------------
+
+// Color lookup tables are 2D strips of N slices of NxN texels, so the texture is N*N wide
+// and N tall. Red increases left to right within a slice, green top to bottom and blue
+// with each slice. See `texture::TextureCache::new_color_lut()` to build one in code.
+#[derive(Copy, Clone, PartialEq)]
+pub struct ColorGrading {
+    pub lut: texture::TextureHandle,
+    pub strength: f32, // [0,1] blend between the original and graded colors.
+}
+
+// ----------------------------------------------
+// CapturedFrame
+// ----------------------------------------------
+
+// Frame pixels read back from the GPU with RenderSystem::capture_frame(). RGBA8, rows top to
+// bottom. Sized to the offscreen render target, which can be larger than the logical viewport.
+pub struct CapturedFrame {
+    pub size: Size,
+    pub pixels: Vec<u8>,
+}
+
+impl CapturedFrame {
+    #[inline]
+    pub fn into_image(self) -> RgbaImage {
+        RgbaImage::from_raw(self.size.width as u32, self.size.height as u32, self.pixels)
+            .expect("Captured frame pixels do not match its size!")
+    }
+}
+
 // ----------------------------------------------
 // Helper types/functions
 // ----------------------------------------------
@@ -0,0 +1,264 @@
+use common::{
+    Color,
+    Rect,
+    RectTexCoords,
+    Size,
+    Vec2,
+    coords::WorldToScreenTransform,
+    hash::{self, StringHash},
+};
+
+use super::{
+    CapturedFrame,
+    ColorGrading,
+    DrawIndex,
+    PaletteSwap,
+    RenderApi,
+    RenderStats,
+    RenderSystemBackend,
+    RenderSystemInitParams,
+    StaticSprite,
+    StaticSpriteMeshHandle,
+    texture::{Texture, TextureBackendImpl, TextureCache, TextureFilter, TextureHandle, TextureSettings, TextureWrapMode},
+};
+use crate::{
+    log,
+    ui::{UiDrawIndex, UiDrawVertex, UiRenderFrameBundle},
+};
+
+// ----------------------------------------------
+// NullTexture
+// ----------------------------------------------
+
+// Texture metadata only. No pixels are stored.
+pub struct NullTexture {
+    name: String,
+    size: Size,
+    settings: TextureSettings,
+    allow_settings_change: bool,
+}
+
+impl NullTexture {
+    fn release(&mut self) {
+        self.name = String::new();
+        self.size = Size::zero();
+    }
+}
+
+impl Texture for NullTexture {
+    #[inline]
+    fn is_valid(&self) -> bool {
+        self.size.is_valid()
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn hash(&self) -> StringHash {
+        hash::fnv1a_from_str(&self.name)
+    }
+
+    #[inline]
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    #[inline]
+    fn has_mipmaps(&self) -> bool {
+        self.settings.mipmaps
+    }
+
+    #[inline]
+    fn filter(&self) -> TextureFilter {
+        self.settings.filter
+    }
+
+    #[inline]
+    fn wrap_mode(&self) -> TextureWrapMode {
+        self.settings.wrap_mode
+    }
+
+    #[inline]
+    fn allow_settings_change(&self) -> bool {
+        self.allow_settings_change
+    }
+}
+
+// ----------------------------------------------
+// NullRenderSystemBackend
+// ----------------------------------------------
+
+// Render backend that draws nothing, for headless runs. Textures and static meshes are
+// still allocated so handles stay valid, and the UI draw data is built and discarded.
+pub struct NullRenderSystemBackend {
+    viewport: Rect,
+    static_meshes: Vec<Option<()>>,
+}
+
+impl NullRenderSystemBackend {
+    pub fn new(params: &RenderSystemInitParams) -> Self {
+        debug_assert!(params.render_api == RenderApi::Null);
+        debug_assert!(params.viewport_size.is_valid());
+
+        log::info!(log::channel!("render"), "--- Render Backend: Null (Headless) ---");
+
+        Self {
+            viewport: Rect::from_pos_and_size(Vec2::zero(), params.viewport_size.to_vec2()),
+            static_meshes: Vec::new(),
+        }
+    }
+}
+
+impl RenderSystemBackend for NullRenderSystemBackend {
+    // ----------------------
+    // Begin/End frame:
+    // ----------------------
+
+    fn begin_frame(&mut self, viewport_size: Size, _framebuffer_size: Size) {
+        self.set_viewport_size(viewport_size);
+    }
+
+    fn end_frame(&mut self, ui_frame_bundle: &mut UiRenderFrameBundle, _tex_cache: &mut TextureCache) -> RenderStats {
+        // The UI frame must still be closed, even though nothing gets drawn.
+        ui_frame_bundle.render();
+        RenderStats::default()
+    }
+
+    // ----------------------
+    // Viewport/Framebuffer:
+    // ----------------------
+
+    fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    fn set_viewport_size(&mut self, new_size: Size) {
+        debug_assert!(new_size.is_valid());
+        self.viewport = Rect::from_pos_and_size(Vec2::zero(), new_size.to_vec2());
+    }
+
+    fn set_framebuffer_size(&mut self, _new_size: Size) {}
+
+    // ----------------------
+    // UI (ImGui) Drawing:
+    // ----------------------
+
+    fn begin_ui_render(&mut self) {}
+
+    fn end_ui_render(&mut self) {}
+
+    fn set_ui_draw_buffers(&mut self, _vtx_buffer: &[UiDrawVertex], _idx_buffer: &[UiDrawIndex]) {}
+
+    fn draw_ui_elements(
+        &mut self,
+        _first_index: u32,
+        _index_count: u32,
+        _texture: TextureHandle,
+        _tex_cache: &mut TextureCache,
+        _clip_rect: Rect,
+    ) {
+    }
+
+    // ----------------------
+    // Draw commands:
+    // ----------------------
+
+    fn draw_colored_indexed_triangles(&mut self, _vertices: &[Vec2], _indices: &[DrawIndex], _color: Color) {}
+
+    fn draw_textured_colored_rect(
+        &mut self,
+        _rect: Rect,
+        _tex_coords: &RectTexCoords,
+        _texture: TextureHandle,
+        _color: Color,
+        _palette: PaletteSwap,
+    ) {
+    }
+
+    fn draw_outline_mask_rect(
+        &mut self,
+        _rect: Rect,
+        _tex_coords: &RectTexCoords,
+        _texture: TextureHandle,
+        _outline_color: Color,
+    ) {
+    }
+
+    // ----------------------
+    // Static sprite meshes:
+    // ----------------------
+
+    fn new_static_sprite_mesh(&mut self) -> StaticSpriteMeshHandle {
+        super::alloc_static_mesh_slot(&mut self.static_meshes, ())
+    }
+
+    fn update_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, _sprites: &[StaticSprite]) {
+        super::static_mesh_slot(&mut self.static_meshes, mesh); // Still catches use after release.
+    }
+
+    fn release_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle) {
+        super::free_static_mesh_slot(&mut self.static_meshes, mesh);
+    }
+
+    fn draw_static_sprite_mesh(&mut self, _mesh: StaticSpriteMeshHandle, _transform: WorldToScreenTransform) {}
+
+    // ----------------------
+    // Post-processing:
+    // ----------------------
+
+    fn set_color_grading(&mut self, _color_grading: Option<ColorGrading>) {}
+
+    // ----------------------
+    // Frame capture:
+    // ----------------------
+
+    fn capture_frame(&mut self) -> Option<CapturedFrame> {
+        log::error!(log::channel!("render"), "Frame capture is not supported by the Null render backend.");
+        None
+    }
+
+    // ----------------------
+    // Debug drawing:
+    // ----------------------
+
+    fn draw_line(&mut self, _from_pos: Vec2, _to_pos: Vec2, _from_color: Color, _to_color: Color) {}
+
+    fn draw_point(&mut self, _pt: Vec2, _color: Color, _size: f32) {}
+
+    // ----------------------
+    // Texture Allocation:
+    // ----------------------
+
+    fn new_texture_from_pixels(
+        &mut self,
+        name: &str,
+        size: Size,
+        _pixels: &[u8],
+        settings: TextureSettings,
+        allow_settings_change: bool,
+    ) -> TextureBackendImpl {
+        TextureBackendImpl::Null(NullTexture { name: name.into(), size, settings, allow_settings_change })
+    }
+
+    fn update_texture_pixels(
+        &mut self,
+        _texture: &mut TextureBackendImpl,
+        _offset_x: u32,
+        _offset_y: u32,
+        _size: Size,
+        _mip_level: u32,
+        _pixels: &[u8],
+    ) {
+    }
+
+    fn update_texture_settings(&mut self, texture: &mut TextureBackendImpl, settings: TextureSettings) {
+        texture.as_null_mut().settings = settings;
+    }
+
+    fn release_texture(&mut self, texture: &mut TextureBackendImpl) {
+        texture.as_null_mut().release();
+    }
+}
@@ -10,7 +10,7 @@ use super::{
     context::{PrimitiveTopology, RenderContext},
     shader::ShaderProgram,
 };
-use crate::render;
+use crate::render::{self, PaletteSwap};
 
 // ----------------------------------------------
 // DrawBatch
@@ -20,6 +20,7 @@ pub struct DrawBatchEntry {
     slice: IndexBufferSlice,
     pub texture: render::texture::TextureHandle,
     pub color: Color,
+    pub palette: PaletteSwap,
 }
 
 pub struct DrawBatch<V, I>
@@ -30,6 +31,8 @@ where
     vertices: Vec<V>,
     indices: Vec<I>,
     entries: Vec<DrawBatchEntry>,
+    submitted_entries: u32,
+    buffer_reallocs: u32,
     vertex_array: VertexArray,
     primitive_topology: PrimitiveTopology,
     needs_sync: bool,
@@ -40,25 +43,41 @@ where
     V: VertexTrait + Copy,
     I: IndexTrait + Copy + TryFrom<usize> + Into<usize>,
 {
+    // Batch refilled every frame.
     pub fn new(
         vertices_capacity: u32,
         indices_capacity: u32,
         entries_capacity: u32,
         primitive_topology: PrimitiveTopology,
+    ) -> Self {
+        Self::with_usage_hint(
+            vertices_capacity,
+            indices_capacity,
+            entries_capacity,
+            primitive_topology,
+            BufferUsageHint::StreamDraw,
+        )
+    }
+
+    pub fn with_usage_hint(
+        vertices_capacity: u32,
+        indices_capacity: u32,
+        entries_capacity: u32,
+        primitive_topology: PrimitiveTopology,
+        usage_hint: BufferUsageHint,
     ) -> Self {
         let vertex_layout = V::layout();
         let vertex_stride = V::stride();
 
-        let vertex_buffer =
-            VertexBuffer::with_uninitialized_data(vertices_capacity, vertex_stride as u32, BufferUsageHint::DynamicDraw);
-
-        let index_buffer =
-            IndexBuffer::with_uninitialized_data(indices_capacity, I::index_type(), BufferUsageHint::DynamicDraw);
+        let vertex_buffer = VertexBuffer::with_uninitialized_data(vertices_capacity, vertex_stride as u32, usage_hint);
+        let index_buffer = IndexBuffer::with_uninitialized_data(indices_capacity, I::index_type(), usage_hint);
 
         Self {
             vertices: Vec::with_capacity(vertices_capacity as usize),
             indices: Vec::with_capacity(indices_capacity as usize),
             entries: Vec::with_capacity(entries_capacity as usize),
+            submitted_entries: 0,
+            buffer_reallocs: 0,
             vertex_array: VertexArray::new(vertex_buffer, index_buffer, vertex_layout, vertex_stride),
             primitive_topology,
             needs_sync: false,
@@ -68,13 +87,38 @@ where
     pub fn add_entry(&mut self, vertices: &[V], indices: &[I], texture: render::texture::TextureHandle, color: Color)
     where
         <I as TryFrom<usize>>::Error: Debug,
+    {
+        self.add_entry_with_palette(vertices, indices, texture, color, PaletteSwap::NONE);
+    }
+
+    pub fn add_entry_with_palette(
+        &mut self,
+        vertices: &[V],
+        indices: &[I],
+        texture: render::texture::TextureHandle,
+        color: Color,
+        palette: PaletteSwap,
+    ) where
+        <I as TryFrom<usize>>::Error: Debug,
     {
         let ib_slice_start = self.add_fast(vertices, indices);
+        self.submitted_entries += 1;
+
+        // Merge with the previous entry if it shares texture (e.g. same atlas page), tint and palette.
+        // Indices are appended in order, so the previous slice always ends where this one starts.
+        if let Some(last_entry) = self.entries.last_mut() {
+            if last_entry.texture == texture && last_entry.color == color && last_entry.palette == palette {
+                debug_assert!((last_entry.slice.start + last_entry.slice.count) as usize == ib_slice_start);
+                last_entry.slice.count += indices.len() as u32;
+                return;
+            }
+        }
 
         self.entries.push(DrawBatchEntry {
             slice: IndexBufferSlice { start: ib_slice_start as u32, count: indices.len() as u32 },
             texture,
             color,
+            palette,
         });
     }
 
@@ -85,13 +129,17 @@ where
         let new_vb_size = self.vertices.len() + vertices.len();
         if new_vb_size > self.vertex_array.vertex_buffer().count() as usize {
             self.vertices.reserve(vertices.len());
-            self.vertex_array.vertex_buffer_mut().resize(new_vb_size);
+            if self.vertex_array.vertex_buffer_mut().resize(new_vb_size) {
+                self.buffer_reallocs += 1;
+            }
         }
 
         let new_ib_size = self.indices.len() + indices.len();
         if new_ib_size > self.vertex_array.index_buffer().count() as usize {
             self.indices.reserve(indices.len());
-            self.vertex_array.index_buffer_mut().resize(new_ib_size);
+            if self.vertex_array.index_buffer_mut().resize(new_ib_size) {
+                self.buffer_reallocs += 1;
+            }
         }
 
         let ib_slice_start = self.indices.len();
@@ -157,6 +205,35 @@ where
         render_context.unset_vertex_array();
     }
 
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    // Number of add_entry() calls since the last clear().
+    #[inline]
+    pub fn submitted_entries(&self) -> u32 {
+        self.submitted_entries
+    }
+
+    #[inline]
+    pub fn index_count(&self) -> u32 {
+        self.indices.len() as u32
+    }
+
+    // Number of draw calls draw_entries() will issue after merging.
+    #[inline]
+    pub fn entry_count(&self) -> u32 {
+        self.entries.len() as u32
+    }
+
+    // GPU buffer storage reallocations since the last clear(). Buffers only
+    // ever grow, so this should settle to zero after the first few frames.
+    #[inline]
+    pub fn buffer_reallocs(&self) -> u32 {
+        self.buffer_reallocs
+    }
+
     pub fn sync(&mut self) {
         if self.vertices.is_empty() || !self.needs_sync {
             return;
@@ -172,6 +249,8 @@ where
         self.vertices.clear();
         self.indices.clear();
         self.entries.clear();
+        self.submitted_entries = 0;
+        self.buffer_reallocs = 0;
         self.needs_sync = false;
     }
 }
@@ -234,12 +313,13 @@ impl UiDrawBatch {
         render_context.unset_shader_program();
     }
 
+    // Returns the number of GPU buffer storage reallocations needed to fit the data.
     pub fn sync(
         &mut self,
         render_context: &mut RenderContext,
         vtx_buffer: &[render::UiDrawVertex],
         idx_buffer: &[render::UiDrawIndex],
-    ) {
+    ) -> u32 {
         debug_assert!(self.pass_started);
         self.synced = true;
 
@@ -247,19 +327,21 @@ impl UiDrawBatch {
         // its VB/IB bindings with the following buffer updates.
         render_context.unset_vertex_array();
 
+        let mut buffer_reallocs = 0;
+
         // VB
-        let new_vertex_count = vtx_buffer.len();
-        if new_vertex_count > self.vertex_array.vertex_count() as usize {
-            self.vertex_array.vertex_buffer_mut().resize(new_vertex_count);
+        if self.vertex_array.vertex_buffer_mut().resize(vtx_buffer.len()) {
+            buffer_reallocs += 1;
         }
         self.vertex_array.vertex_buffer().set_data(vtx_buffer);
 
         // IB
-        let new_index_count = idx_buffer.len();
-        if new_index_count > self.vertex_array.index_count() as usize {
-            self.vertex_array.index_buffer_mut().resize(new_index_count);
+        if self.vertex_array.index_buffer_mut().resize(idx_buffer.len()) {
+            buffer_reallocs += 1;
         }
         self.vertex_array.index_buffer().set_data(idx_buffer);
+
+        buffer_reallocs
     }
 
     pub fn draw(&mut self, render_context: &mut RenderContext, first_index: u32, index_count: u32) {
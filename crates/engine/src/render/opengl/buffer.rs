@@ -70,6 +70,9 @@ impl VertexBuffer {
         Self::set_data_raw(self, vertices.as_ptr() as *const c_void, vertices.len() as u32);
     }
 
+    // Uploads `count` vertices to the start of the buffer. The GPU storage keeps its full
+    // capacity and is orphaned first, so the driver can hand out fresh memory instead of
+    // stalling on draws from the previous frame that still read the old contents.
     pub fn set_data_raw(&self, vertices: *const c_void, count: u32) {
         debug_assert!(count != 0);
         debug_assert!(count <= self.count);
@@ -77,22 +80,26 @@ impl VertexBuffer {
 
         unsafe {
             gl::BindBuffer(gl::ARRAY_BUFFER, self.handle);
-
-            let buffer_size = count * self.stride;
-            gl::BufferData(
-                gl::ARRAY_BUFFER,
-                buffer_size as gl::types::GLsizeiptr,
-                vertices, // may be null
-                self.usage_hint as gl::types::GLenum,
-            );
-
+            orphan_and_upload(gl::ARRAY_BUFFER, self.count * self.stride, count * self.stride, vertices, self.usage_hint);
             gl::BindBuffer(gl::ARRAY_BUFFER, NULL_BUFFER_HANDLE);
         }
     }
 
-    pub fn resize(&mut self, new_size: usize) {
+    // Grows the capacity to fit at least `new_size` vertices. Never shrinks.
+    // Returns true if the GPU storage will be reallocated on the next upload.
+    pub fn resize(&mut self, new_size: usize) -> bool {
         debug_assert!(new_size != 0);
-        self.count = new_size as u32;
+        debug_assert!(self.is_valid());
+
+        if new_size <= self.count as usize {
+            return false;
+        }
+
+        // Grow geometrically so reallocations stay rare as a map gets denser.
+        // The actual GPU storage is re-specified by the next set_data() call.
+        self.count = new_size.next_power_of_two() as u32;
+
+        true
     }
 
     fn create_buffer(vertices: *const c_void, count: u32, stride: u32, usage_hint: BufferUsageHint) -> Self {
@@ -231,29 +238,36 @@ impl IndexBuffer {
         Self::set_data_raw(self, indices.as_ptr() as *const c_void, indices.len() as u32);
     }
 
+    // Same as VertexBuffer::set_data_raw(): orphans the full storage, then uploads `count` indices.
+    // NOTE: Binding GL_ELEMENT_ARRAY_BUFFER changes the bound VAO state, so no VAO can be bound here.
     pub fn set_data_raw(&self, indices: *const c_void, count: u32) {
         debug_assert!(count != 0);
         debug_assert!(count <= self.count);
         debug_assert!(self.is_valid());
 
+        let index_size = self.index_type.size_in_bytes() as u32;
+
         unsafe {
             gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, self.handle);
-
-            let buffer_size = (count as usize) * self.index_type.size_in_bytes();
-            gl::BufferData(
-                gl::ELEMENT_ARRAY_BUFFER,
-                buffer_size as gl::types::GLsizeiptr,
-                indices, // may be null
-                self.usage_hint as gl::types::GLenum,
-            );
-
+            let capacity_bytes = self.count * index_size;
+            orphan_and_upload(gl::ELEMENT_ARRAY_BUFFER, capacity_bytes, count * index_size, indices, self.usage_hint);
             gl::BindBuffer(gl::ELEMENT_ARRAY_BUFFER, NULL_BUFFER_HANDLE);
         }
     }
 
-    pub fn resize(&mut self, new_size: usize) {
+    // Grows the capacity to fit at least `new_size` indices. Never shrinks.
+    // Returns true if the GPU storage will be reallocated on the next upload.
+    pub fn resize(&mut self, new_size: usize) -> bool {
         debug_assert!(new_size != 0);
-        self.count = new_size as u32;
+        debug_assert!(self.is_valid());
+
+        if new_size <= self.count as usize {
+            return false;
+        }
+
+        self.count = new_size.next_power_of_two() as u32;
+
+        true
     }
 
     fn create_buffer(indices: *const c_void, count: u32, index_type: IndexType, usage_hint: BufferUsageHint) -> Self {
@@ -443,3 +457,29 @@ impl Drop for VertexArray {
         }
     }
 }
+
+// ----------------------------------------------
+// Helpers
+// ----------------------------------------------
+
+// Buffer orphaning: re-specifying the whole storage with null data detaches it from any
+// pending draws, then the new contents go into the fresh storage with glBufferSubData.
+// Persistent mapping (GL_ARB_buffer_storage) would avoid even this, but needs GL 4.4
+// which is not available on macOS, so orphaning is the portable option.
+//
+// SAFETY: The target buffer must be bound and `data` must point to at least `upload_bytes`.
+unsafe fn orphan_and_upload(
+    target: gl::types::GLenum,
+    capacity_bytes: u32,
+    upload_bytes: u32,
+    data: *const c_void,
+    usage_hint: BufferUsageHint,
+) {
+    debug_assert!(!data.is_null());
+    debug_assert!(upload_bytes <= capacity_bytes);
+
+    unsafe {
+        gl::BufferData(target, capacity_bytes as gl::types::GLsizeiptr, ptr::null(), usage_hint as gl::types::GLenum);
+        gl::BufferSubData(target, 0, upload_bytes as gl::types::GLsizeiptr, data);
+    }
+}
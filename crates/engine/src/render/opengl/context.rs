@@ -218,10 +218,16 @@ impl RenderContext {
     }
 
     pub fn set_texture(&mut self, texture: &OpenGlTexture) -> &mut Self {
+        self.set_texture_on_unit(texture, texture.tex_unit())
+    }
+
+    // Binds `texture` to a unit other than its own, for shaders sampling multiple textures.
+    pub fn set_texture_on_unit(&mut self, texture: &OpenGlTexture, tex_unit: TextureUnit) -> &mut Self {
         use render::texture::Texture;
         debug_assert!(texture.is_valid());
+        debug_assert!((tex_unit.0 as usize) < MAX_TEXTURE_UNITS);
 
-        let tex_unit = texture.tex_unit().0 as usize;
+        let tex_unit = tex_unit.0 as usize;
         let tex_handle = texture.handle();
 
         if self.current_texture[tex_unit] != tex_handle {
@@ -343,6 +349,15 @@ impl RenderContext {
         }
     }
 
+    // Clears the color buffer of the currently bound render target without
+    // changing the frame clear color.
+    pub fn clear_color_buffer(&mut self, color: Color) {
+        unsafe {
+            gl::ClearColor(color.r, color.g, color.b, color.a);
+            gl::Clear(gl::COLOR_BUFFER_BIT);
+        }
+    }
+
     pub fn end_frame(&mut self) {
         unsafe {
             // Clear transient GL states.
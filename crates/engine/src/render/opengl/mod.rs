@@ -1,8 +1,9 @@
 use std::ffi::{CStr, c_char};
 
 use arrayvec::ArrayVec;
-use common::{Color, Rect, RectTexCoords, Size, Vec2, time::PerfTimer};
+use common::{Color, Rect, RectTexCoords, Size, Vec2, coords::WorldToScreenTransform, time::PerfTimer};
 use batch::*;
+use buffer::BufferUsageHint;
 use context::*;
 use shader::*;
 use target::*;
@@ -11,7 +12,10 @@ use vertex::*;
 
 pub use texture::OpenGlTexture;
 
-use super::{RenderApi, RenderStats, RenderSystemBackend, RenderSystemInitParams};
+use super::{
+    CapturedFrame, ColorGrading, PaletteSwap, RenderApi, RenderStats, RenderSystemBackend, RenderSystemInitParams,
+    StaticSprite, StaticSpriteMeshHandle,
+};
 use crate::{log, ui::UiRenderFrameBundle};
 
 mod batch;
@@ -46,6 +50,25 @@ struct OpenGlSystemState {
 
     ui_batch: UiDrawBatch,
     ui_shader: ui::Shader,
+
+    // Selection outline: silhouettes rendered to the mask RT, then traced
+    // over the offscreen RT with a single viewport sized quad.
+    outline_mask_render_target: RenderTarget,
+    outline_mask_batch: DrawBatch<SpriteVertex2D, SpriteIndex2D>,
+    outline_mask_shader: outline_mask::Shader,
+    outline_batch: DrawBatch<SpriteVertex2D, SpriteIndex2D>,
+    outline_shader: outline::Shader,
+
+    // Color grading: the offscreen RT is drawn to the screen with a single
+    // viewport sized quad passing through the LUT instead of a plain blit.
+    color_grade_batch: DrawBatch<SpriteVertex2D, SpriteIndex2D>,
+    color_grade_shader: color_grade::Shader,
+    color_grading: Option<ColorGrading>,
+
+    // Static sprite meshes, indexed by StaticSpriteMeshHandle. Drawn with
+    // the sprites shader and their own world-to-screen transform.
+    static_meshes: Vec<Option<DrawBatch<SpriteVertex2D, SpriteIndex2D>>>,
+    static_mesh_draws: Vec<(StaticSpriteMeshHandle, WorldToScreenTransform)>,
 }
 
 impl OpenGlSystemState {
@@ -63,6 +86,9 @@ impl OpenGlSystemState {
         self.lines_shader.set_viewport_size(self.viewport.size());
         self.points_shader.set_viewport_size(self.viewport.size());
         self.ui_shader.set_viewport_size(self.viewport.size());
+        self.outline_mask_shader.set_viewport_size(self.viewport.size());
+        self.outline_shader.set_viewport_size(self.viewport.size());
+        self.color_grade_shader.set_viewport_size(self.viewport.size());
     }
 
     fn set_framebuffer_size(&mut self, new_size: Size) {
@@ -70,6 +96,46 @@ impl OpenGlSystemState {
         self.framebuffer_size = new_size;
     }
 
+    fn set_world_transform(&self, transform: WorldToScreenTransform) {
+        self.sprites_shader.set_world_offset(transform.offset);
+        self.sprites_shader.set_world_scaling(transform.scaling);
+    }
+
+    fn flush_static_meshes(&mut self, tex_cache: &mut super::texture::TextureCache) {
+        debug_assert!(self.frame_started);
+
+        if self.static_mesh_draws.is_empty() {
+            return;
+        }
+
+        let mut set_shader_vars_fn = |render_ctx: &mut RenderContext, entry: &DrawBatchEntry| {
+            let gl_texture = tex_cache.texture_for_handle(entry.texture).as_opengl();
+            render_ctx.set_texture(gl_texture);
+
+            self.sprites_shader.set_sprite_tint(entry.color);
+            self.sprites_shader.set_sprite_texture(gl_texture);
+            self.sprites_shader.set_palette_primary(entry.palette.primary);
+            self.sprites_shader.set_palette_secondary(entry.palette.secondary);
+        };
+
+        for &(mesh, transform) in &self.static_mesh_draws {
+            let batch = super::static_mesh_slot(&mut self.static_meshes, mesh);
+
+            self.sprites_shader.set_world_offset(transform.offset);
+            self.sprites_shader.set_world_scaling(transform.scaling);
+
+            batch.sync();
+            batch.draw_entries(&mut self.render_context, &self.sprites_shader.program, &mut set_shader_vars_fn);
+
+            self.stats.triangles_drawn += batch.index_count() / 3;
+            self.stats.static_meshes += 1;
+        }
+
+        // Back to screen space for the regular sprites batch.
+        self.set_world_transform(WorldToScreenTransform::default());
+        self.static_mesh_draws.clear();
+    }
+
     fn flush_sprites(&mut self, tex_cache: &mut super::texture::TextureCache) {
         debug_assert!(self.frame_started);
 
@@ -79,16 +145,135 @@ impl OpenGlSystemState {
 
             self.sprites_shader.set_sprite_tint(entry.color);
             self.sprites_shader.set_sprite_texture(gl_texture);
+            self.sprites_shader.set_palette_primary(entry.palette.primary);
+            self.sprites_shader.set_palette_secondary(entry.palette.secondary);
         };
 
+        self.stats.sprites_submitted += self.sprites_batch.submitted_entries();
+        self.stats.sprite_batches += self.sprites_batch.entry_count();
+        self.stats.buffer_reallocs += self.sprites_batch.buffer_reallocs();
+
         self.sprites_batch.sync();
         self.sprites_batch.draw_entries(&mut self.render_context, &self.sprites_shader.program, set_shader_vars_fn);
         self.sprites_batch.clear();
     }
 
+    fn flush_outlines(&mut self, tex_cache: &mut super::texture::TextureCache) {
+        debug_assert!(self.frame_started);
+
+        if self.outline_mask_batch.is_empty() {
+            return;
+        }
+
+        // Silhouettes to the mask RT:
+        {
+            self.render_context.set_offscreen_render_target(&self.outline_mask_render_target);
+            self.render_context.clear_color_buffer(Color::transparent());
+
+            let set_shader_vars_fn = |render_ctx: &mut RenderContext, entry: &DrawBatchEntry| {
+                let gl_texture = tex_cache.texture_for_handle(entry.texture).as_opengl();
+                render_ctx.set_texture(gl_texture);
+
+                self.outline_mask_shader.set_sprite_tint(entry.color);
+                self.outline_mask_shader.set_sprite_texture(gl_texture);
+            };
+
+            self.stats.buffer_reallocs += self.outline_mask_batch.buffer_reallocs();
+            self.outline_mask_batch.sync();
+            self.outline_mask_batch.draw_entries(
+                &mut self.render_context,
+                &self.outline_mask_shader.program,
+                set_shader_vars_fn,
+            );
+            self.outline_mask_batch.clear();
+        }
+
+        // Outline traced over the offscreen RT:
+        {
+            self.render_context.set_offscreen_render_target(&self.offscreen_render_target);
+
+            let viewport_rect = Rect::from_pos_and_size(Vec2::zero(), self.viewport.size());
+            let vertices = [
+                SpriteVertex2D { position: viewport_rect.bottom_left(),  tex_coords: Vec2::default() },
+                SpriteVertex2D { position: viewport_rect.top_left(),     tex_coords: Vec2::default() },
+                SpriteVertex2D { position: viewport_rect.top_right(),    tex_coords: Vec2::default() },
+                SpriteVertex2D { position: viewport_rect.bottom_right(), tex_coords: Vec2::default() },
+            ];
+            const INDICES: [SpriteIndex2D; 6] = [0, 1, 2, 2, 3, 0];
+
+            self.outline_batch.add_entry(&vertices, &INDICES, super::texture::TextureHandle::white(), Color::white());
+
+            let mask_texture = self.outline_mask_render_target.color_texture();
+            self.outline_shader.set_mask_texture(mask_texture);
+
+            self.outline_batch.sync();
+            self.outline_batch.draw_entries(
+                &mut self.render_context,
+                &self.outline_shader.program,
+                |render_ctx: &mut RenderContext, _: &DrawBatchEntry| {
+                    render_ctx.set_texture(mask_texture);
+                },
+            );
+            self.outline_batch.clear();
+        }
+    }
+
+    fn blit_to_screen(&mut self, tex_cache: &mut super::texture::TextureCache) {
+        debug_assert!(self.frame_started);
+
+        let color_grading = match self.color_grading {
+            Some(color_grading) if color_grading.strength > 0.0 => color_grading,
+            _ => {
+                self.offscreen_render_target.blit_to_screen(self.framebuffer_size);
+                return;
+            }
+        };
+
+        self.render_context.unset_offscreen_render_target();
+        self.render_context.set_viewport(Rect::from_pos_and_size(Vec2::zero(), self.framebuffer_size.to_vec2()));
+
+        // Overwrite the screen, no blending with whatever was there.
+        self.render_context.set_alpha_blend(AlphaBlend::Disabled);
+
+        let viewport_rect = Rect::from_pos_and_size(Vec2::zero(), self.viewport.size());
+        let vertices = [
+            SpriteVertex2D { position: viewport_rect.bottom_left(),  tex_coords: Vec2::default() },
+            SpriteVertex2D { position: viewport_rect.top_left(),     tex_coords: Vec2::default() },
+            SpriteVertex2D { position: viewport_rect.top_right(),    tex_coords: Vec2::default() },
+            SpriteVertex2D { position: viewport_rect.bottom_right(), tex_coords: Vec2::default() },
+        ];
+        const INDICES: [SpriteIndex2D; 6] = [0, 1, 2, 2, 3, 0];
+
+        self.color_grade_batch.add_entry(&vertices, &INDICES, super::texture::TextureHandle::white(), Color::white());
+
+        const LUT_TEXTURE_UNIT: TextureUnit = TextureUnit(1);
+
+        let scene_texture = self.offscreen_render_target.color_texture();
+        let lut_texture = tex_cache.texture_for_handle(color_grading.lut).as_opengl();
+
+        self.color_grade_shader.set_framebuffer_size(self.framebuffer_size.to_vec2());
+        self.color_grade_shader.set_color_grading_strength(color_grading.strength);
+        self.color_grade_shader.set_scene_texture(scene_texture);
+        self.color_grade_shader.set_lut_texture(LUT_TEXTURE_UNIT.0 as i32);
+
+        self.color_grade_batch.sync();
+        self.color_grade_batch.draw_entries(
+            &mut self.render_context,
+            &self.color_grade_shader.program,
+            |render_ctx: &mut RenderContext, _: &DrawBatchEntry| {
+                render_ctx.set_texture_on_unit(lut_texture, LUT_TEXTURE_UNIT);
+                render_ctx.set_texture(scene_texture);
+            },
+        );
+        self.color_grade_batch.clear();
+
+        self.render_context.set_alpha_blend(AlphaBlend::Enabled);
+    }
+
     fn flush_lines(&mut self) {
         debug_assert!(self.frame_started);
 
+        self.stats.buffer_reallocs += self.lines_batch.buffer_reallocs();
         self.lines_batch.sync();
         self.lines_batch.draw_fast(&mut self.render_context, &self.lines_shader.program);
         self.lines_batch.clear();
@@ -97,6 +282,7 @@ impl OpenGlSystemState {
     fn flush_points(&mut self) {
         debug_assert!(self.frame_started);
 
+        self.stats.buffer_reallocs += self.points_batch.buffer_reallocs();
         self.points_batch.sync();
         self.points_batch.draw_fast(&mut self.render_context, &self.points_shader.program);
         self.points_batch.clear();
@@ -123,8 +309,11 @@ impl OpenGlRenderSystemBackend {
 
         log::info!(log::channel!("render"), "--- Render Backend: OpenGL ---");
 
+        let render_target_size = params.viewport_size.max(params.framebuffer_size);
         let offscreen_render_target =
-            RenderTarget::new(params.viewport_size.max(params.framebuffer_size), TextureFilter::Linear);
+            RenderTarget::new("offscreen_render_target", render_target_size, TextureFilter::Linear);
+        let outline_mask_render_target =
+            RenderTarget::new("outline_mask_render_target", render_target_size, TextureFilter::Nearest);
 
         let mut s = Box::new(OpenGlSystemState {
             frame_started: false,
@@ -146,11 +335,35 @@ impl OpenGlRenderSystemBackend {
 
             ui_batch: UiDrawBatch::new(),
             ui_shader: ui::Shader::load(),
+
+            outline_mask_render_target,
+            outline_mask_batch: DrawBatch::new(64, 64, 16, PrimitiveTopology::Triangles),
+            outline_mask_shader: outline_mask::Shader::load(),
+            outline_batch: DrawBatch::new(4, 6, 1, PrimitiveTopology::Triangles),
+            outline_shader: outline::Shader::load(),
+
+            color_grade_batch: DrawBatch::new(4, 6, 1, PrimitiveTopology::Triangles),
+            color_grade_shader: color_grade::Shader::load(),
+            color_grading: None,
+
+            static_meshes: Vec::new(),
+            static_mesh_draws: Vec::new(),
         });
 
         s.set_viewport_size(params.viewport_size);
         s.set_framebuffer_size(params.framebuffer_size);
 
+        // Shaders sharing the sprites vertex stage draw in screen space. Uniforms default to zero, so set
+        // an identity world transform explicitly. Only static meshes change the sprites shader transform.
+        let identity = WorldToScreenTransform::default();
+        s.set_world_transform(identity);
+        s.outline_mask_shader.set_world_offset(identity.offset);
+        s.outline_mask_shader.set_world_scaling(identity.scaling);
+        s.outline_shader.set_world_offset(identity.offset);
+        s.outline_shader.set_world_scaling(identity.scaling);
+        s.color_grade_shader.set_world_offset(identity.offset);
+        s.color_grade_shader.set_world_scaling(identity.scaling);
+
         // Pure 2D rendering, no depth test or back-face culling.
         s.render_context
             .set_clear_color(params.clear_color)
@@ -189,11 +402,15 @@ impl RenderSystemBackend for OpenGlRenderSystemBackend {
         s.render_context.begin_frame();
         s.frame_started = true;
 
-        s.stats.triangles_drawn  = 0;
-        s.stats.lines_drawn      = 0;
-        s.stats.points_drawn     = 0;
-        s.stats.texture_changes  = 0;
-        s.stats.draw_calls       = 0;
+        s.stats.triangles_drawn   = 0;
+        s.stats.lines_drawn       = 0;
+        s.stats.points_drawn      = 0;
+        s.stats.texture_changes   = 0;
+        s.stats.draw_calls        = 0;
+        s.stats.sprites_submitted = 0;
+        s.stats.sprite_batches    = 0;
+        s.stats.static_meshes     = 0;
+        s.stats.buffer_reallocs   = 0;
 
         s.stats.render_submit_time_ms = 0.0;
     }
@@ -208,12 +425,14 @@ impl RenderSystemBackend for OpenGlRenderSystemBackend {
 
         let render_submit_timer = PerfTimer::begin();
 
+        s.flush_static_meshes(tex_cache); // Under all other sprites.
         s.flush_sprites(tex_cache);
+        s.flush_outlines(tex_cache); // On top of sprites, under debug lines/points.
         s.flush_lines();
         s.flush_points();
 
-        // Blit OffscreenRT to the screen framebuffer.
-        s.offscreen_render_target.blit_to_screen(s.framebuffer_size);
+        // Blit OffscreenRT to the screen framebuffer, color graded if enabled.
+        s.blit_to_screen(tex_cache);
 
         // Reset viewport to default screen framebuffer size.
         s.render_context.set_viewport(Rect::from_pos_and_size(Vec2::zero(), s.framebuffer_size.to_vec2()));
@@ -226,13 +445,17 @@ impl RenderSystemBackend for OpenGlRenderSystemBackend {
 
         s.stats.render_submit_time_ms = render_submit_timer.end();
 
-        s.stats.texture_changes      = s.render_context.texture_changes();
-        s.stats.draw_calls           = s.render_context.draw_calls();
-        s.stats.peak_triangles_drawn = s.stats.triangles_drawn.max(s.stats.peak_triangles_drawn);
-        s.stats.peak_lines_drawn     = s.stats.lines_drawn.max(s.stats.peak_lines_drawn);
-        s.stats.peak_points_drawn    = s.stats.points_drawn.max(s.stats.peak_points_drawn);
-        s.stats.peak_texture_changes = s.stats.texture_changes.max(s.stats.peak_texture_changes);
-        s.stats.peak_draw_calls      = s.stats.draw_calls.max(s.stats.peak_draw_calls);
+        s.stats.texture_changes        = s.render_context.texture_changes();
+        s.stats.draw_calls             = s.render_context.draw_calls();
+        s.stats.peak_triangles_drawn   = s.stats.triangles_drawn.max(s.stats.peak_triangles_drawn);
+        s.stats.peak_lines_drawn       = s.stats.lines_drawn.max(s.stats.peak_lines_drawn);
+        s.stats.peak_points_drawn      = s.stats.points_drawn.max(s.stats.peak_points_drawn);
+        s.stats.peak_texture_changes   = s.stats.texture_changes.max(s.stats.peak_texture_changes);
+        s.stats.peak_draw_calls        = s.stats.draw_calls.max(s.stats.peak_draw_calls);
+        s.stats.peak_sprites_submitted = s.stats.sprites_submitted.max(s.stats.peak_sprites_submitted);
+        s.stats.peak_sprite_batches    = s.stats.sprite_batches.max(s.stats.peak_sprite_batches);
+        s.stats.peak_static_meshes     = s.stats.static_meshes.max(s.stats.peak_static_meshes);
+        s.stats.peak_buffer_reallocs   = s.stats.buffer_reallocs.max(s.stats.peak_buffer_reallocs);
 
         s.stats
     }
@@ -275,7 +498,7 @@ impl RenderSystemBackend for OpenGlRenderSystemBackend {
     fn set_ui_draw_buffers(&mut self, vtx_buffer: &[super::UiDrawVertex], idx_buffer: &[super::UiDrawIndex]) {
         debug_assert!(!vtx_buffer.is_empty() && !idx_buffer.is_empty());
         let s = self.state_mut();
-        s.ui_batch.sync(&mut s.render_context, vtx_buffer, idx_buffer);
+        s.stats.buffer_reallocs += s.ui_batch.sync(&mut s.render_context, vtx_buffer, idx_buffer);
     }
 
     fn draw_ui_elements(
@@ -334,6 +557,37 @@ impl RenderSystemBackend for OpenGlRenderSystemBackend {
         tex_coords: &RectTexCoords,
         texture: super::texture::TextureHandle,
         color: Color,
+        palette: PaletteSwap,
+    ) {
+        let s = self.state_mut();
+        debug_assert!(s.frame_started);
+
+        if super::is_rect_fully_offscreen(&s.viewport, &rect) {
+            return; // Cull if fully offscreen.
+        }
+
+        let vertices = [
+            SpriteVertex2D { position: rect.bottom_left(),  tex_coords: tex_coords.bottom_left()  },
+            SpriteVertex2D { position: rect.top_left(),     tex_coords: tex_coords.top_left()     },
+            SpriteVertex2D { position: rect.top_right(),    tex_coords: tex_coords.top_right()    },
+            SpriteVertex2D { position: rect.bottom_right(), tex_coords: tex_coords.bottom_right() },
+        ];
+
+        const INDICES: [SpriteIndex2D; 6] = [
+            0, 1, 2, // first triangle
+            2, 3, 0, // second triangle
+        ];
+
+        s.sprites_batch.add_entry_with_palette(&vertices, &INDICES, texture, color, palette);
+        s.stats.triangles_drawn += 2;
+    }
+
+    fn draw_outline_mask_rect(
+        &mut self,
+        rect: Rect,
+        tex_coords: &RectTexCoords,
+        texture: super::texture::TextureHandle,
+        outline_color: Color,
     ) {
         let s = self.state_mut();
         debug_assert!(s.frame_started);
@@ -354,10 +608,85 @@ impl RenderSystemBackend for OpenGlRenderSystemBackend {
             2, 3, 0, // second triangle
         ];
 
-        s.sprites_batch.add_entry(&vertices, &INDICES, texture, color);
+        s.outline_mask_batch.add_entry(&vertices, &INDICES, texture, outline_color);
         s.stats.triangles_drawn += 2;
     }
 
+    // ----------------------
+    // Static sprite meshes:
+    // ----------------------
+
+    fn new_static_sprite_mesh(&mut self) -> StaticSpriteMeshHandle {
+        let s = self.state_mut();
+
+        // Rebuilt only when the mesh contents change, not every frame.
+        let batch = DrawBatch::with_usage_hint(64, 96, 8, PrimitiveTopology::Triangles, BufferUsageHint::DynamicDraw);
+        super::alloc_static_mesh_slot(&mut s.static_meshes, batch)
+    }
+
+    fn update_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, sprites: &[StaticSprite]) {
+        let s = self.state_mut();
+        let batch = super::static_mesh_slot(&mut s.static_meshes, mesh);
+
+        batch.clear();
+
+        for sprite in sprites {
+            let rect = &sprite.rect;
+            let tex_coords = &sprite.tex_coords;
+
+            let vertices = [
+                SpriteVertex2D { position: rect.bottom_left(),  tex_coords: tex_coords.bottom_left()  },
+                SpriteVertex2D { position: rect.top_left(),     tex_coords: tex_coords.top_left()     },
+                SpriteVertex2D { position: rect.top_right(),    tex_coords: tex_coords.top_right()    },
+                SpriteVertex2D { position: rect.bottom_right(), tex_coords: tex_coords.bottom_right() },
+            ];
+
+            const INDICES: [SpriteIndex2D; 6] = [
+                0, 1, 2, // first triangle
+                2, 3, 0, // second triangle
+            ];
+
+            batch.add_entry_with_palette(&vertices, &INDICES, sprite.texture, sprite.color, sprite.palette);
+        }
+
+        // GPU upload is deferred to sync() when the mesh is next drawn.
+        s.stats.buffer_reallocs += batch.buffer_reallocs();
+    }
+
+    fn release_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle) {
+        let s = self.state_mut();
+        s.static_mesh_draws.retain(|(pending_mesh, _)| *pending_mesh != mesh);
+        super::free_static_mesh_slot(&mut s.static_meshes, mesh);
+    }
+
+    fn draw_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, transform: WorldToScreenTransform) {
+        let s = self.state_mut();
+        debug_assert!(s.frame_started);
+        debug_assert!(s.static_mesh_draws.iter().all(|(pending_mesh, _)| *pending_mesh != mesh));
+
+        s.static_mesh_draws.push((mesh, transform));
+    }
+
+    // ----------------------
+    // Post-processing:
+    // ----------------------
+
+    fn set_color_grading(&mut self, color_grading: Option<ColorGrading>) {
+        self.state_mut().color_grading = color_grading;
+    }
+
+    // ----------------------
+    // Frame capture:
+    // ----------------------
+
+    fn capture_frame(&mut self) -> Option<CapturedFrame> {
+        let s = self.state();
+        debug_assert!(!s.frame_started);
+
+        let pixels = s.offscreen_render_target.read_pixels();
+        Some(CapturedFrame { size: s.offscreen_render_target.size(), pixels })
+    }
+
     // ----------------------
     // Debug drawing:
     // ----------------------
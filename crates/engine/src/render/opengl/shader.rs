@@ -302,9 +302,17 @@ pub fn set_variable_by_name<T: ShaderVarTrait>(shader_program: &ShaderProgram, v
 }
 
 macro_rules! shader {
+    // Shader with its own vertex stage: shaders/<mod_name>.vert + shaders/<mod_name>.frag
     (
         $mod_name:ident,
         $($field:ident : $field_type:ty),* $(,)?
+    ) => {
+        shader!($mod_name(vs = $mod_name), $($field: $field_type),*);
+    };
+    // Shader sharing the vertex stage of another: shaders/<vs_name>.vert + shaders/<mod_name>.frag
+    (
+        $mod_name:ident(vs = $vs_name:ident),
+        $($field:ident : $field_type:ty),* $(,)?
     ) => {
         pub mod $mod_name {
             use super::*;
@@ -323,7 +331,7 @@ macro_rules! shader {
             impl Shader {
                 pub fn load() -> Self {
                     const VS_SRC: &str = include_str!(
-                        concat!("shaders/", stringify!($mod_name), ".vert")
+                        concat!("shaders/", stringify!($vs_name), ".vert")
                     );
                     const FS_SRC: &str = include_str!(
                         concat!("shaders/", stringify!($mod_name), ".frag")
@@ -361,8 +369,12 @@ shader!(
     sprites,
     // Uniform variables:
     viewport_size: Vec2,
+    world_offset: Vec2,
+    world_scaling: f32,
     sprite_tint: Color,
     sprite_texture: &OpenGlTexture,
+    palette_primary: Color,
+    palette_secondary: Color,
 );
 
 shader!(
@@ -383,3 +395,36 @@ shader!(
     viewport_size: Vec2,
     sprite_texture: &OpenGlTexture,
 );
+
+// Selection outline shaders reuse the sprites vertex stage.
+shader!(
+    outline_mask(vs = sprites),
+    // Uniform variables:
+    viewport_size: Vec2,
+    world_offset: Vec2,
+    world_scaling: f32,
+    sprite_tint: Color,
+    sprite_texture: &OpenGlTexture,
+);
+
+shader!(
+    outline(vs = sprites),
+    // Uniform variables:
+    viewport_size: Vec2,
+    world_offset: Vec2,
+    world_scaling: f32,
+    mask_texture: &OpenGlTexture,
+);
+
+// Color grading blit also reuses the sprites vertex stage.
+shader!(
+    color_grade(vs = sprites),
+    // Uniform variables:
+    viewport_size: Vec2,
+    world_offset: Vec2,
+    world_scaling: f32,
+    framebuffer_size: Vec2,
+    color_grading_strength: f32,
+    scene_texture: &OpenGlTexture,
+    lut_texture: i32, // Texture unit index.
+);
@@ -0,0 +1,39 @@
+#version 330 core
+
+// Color grading: copies the offscreen render target to the screen, passing
+// the colors through a lookup table. The LUT is a strip of N slices of NxN
+// texels (N*N wide, N tall). Red increases along each slice, green downwards
+// and blue with each slice.
+
+uniform vec2 framebuffer_size;
+uniform float color_grading_strength;
+uniform sampler2D scene_texture; // @ tmu:0
+uniform sampler2D lut_texture;   // @ tmu:1
+
+out vec4 frag_color;
+
+vec3 sample_lut_slice(vec2 red_green, float blue_slice, float lut_size) {
+    // Offset by half a texel so linear filtering never bleeds into the neighboring slice.
+    vec2 uv = vec2(
+        (blue_slice * lut_size + red_green.x + 0.5) / (lut_size * lut_size),
+        (red_green.y + 0.5) / lut_size);
+    return textureLod(lut_texture, uv, 0.0).rgb;
+}
+
+void main() {
+    // The render target is stretched over the whole framebuffer, same as a plain blit.
+    vec4 color = texture(scene_texture, gl_FragCoord.xy / framebuffer_size);
+
+    float lut_size = float(textureSize(lut_texture, 0).y);
+    vec3 scaled = clamp(color.rgb, 0.0, 1.0) * (lut_size - 1.0);
+
+    // Blue selects the slice; interpolate manually between the two nearest slices.
+    float blue_slice_0 = floor(scaled.b);
+    float blue_slice_1 = min(blue_slice_0 + 1.0, lut_size - 1.0);
+    vec3 graded = mix(
+        sample_lut_slice(scaled.rg, blue_slice_0, lut_size),
+        sample_lut_slice(scaled.rg, blue_slice_1, lut_size),
+        scaled.b - blue_slice_0);
+
+    frag_color = vec4(mix(color.rgb, graded, color_grading_strength), color.a);
+}
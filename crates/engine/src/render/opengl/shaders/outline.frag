@@ -0,0 +1,42 @@
+#version 330 core
+
+// Selection outline: traces a colored outline around the silhouettes rendered
+// to the outline mask render target. The mask holds the outline color where a
+// selected sprite is opaque and is fully transparent elsewhere. Pixels inside a
+// silhouette are left untouched.
+
+const int OUTLINE_THICKNESS = 2; // In render target pixels.
+
+uniform sampler2D mask_texture; // @ tmu:0
+
+out vec4 frag_color;
+
+void main() {
+    // Mask and destination render targets have the same size, so the
+    // fragment position addresses the mask texel directly.
+    ivec2 mask_size = textureSize(mask_texture, 0);
+    ivec2 center = ivec2(gl_FragCoord.xy);
+
+    if (texelFetch(mask_texture, center, 0).a > 0.0) {
+        frag_color = vec4(0.0);
+        return;
+    }
+
+    // Pick the strongest mask texel within a circle of OUTLINE_THICKNESS radius.
+    vec4 outline = vec4(0.0);
+    for (int y = -OUTLINE_THICKNESS; y <= OUTLINE_THICKNESS; ++y) {
+        for (int x = -OUTLINE_THICKNESS; x <= OUTLINE_THICKNESS; ++x) {
+            if ((x * x) + (y * y) > (OUTLINE_THICKNESS * OUTLINE_THICKNESS)) {
+                continue;
+            }
+
+            ivec2 coords = clamp(center + ivec2(x, y), ivec2(0), mask_size - ivec2(1));
+            vec4 mask = texelFetch(mask_texture, coords, 0);
+            if (mask.a > outline.a) {
+                outline = mask;
+            }
+        }
+    }
+
+    frag_color = outline;
+}
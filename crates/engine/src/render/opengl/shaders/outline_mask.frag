@@ -0,0 +1,17 @@
+#version 330 core
+
+uniform vec4 sprite_tint; // Outline color.
+uniform sampler2D sprite_texture; // @ tmu:0
+
+in vec2 tex_coords;
+
+out vec4 frag_color;
+
+// Selection outline mask: flat outline color wherever the sprite is opaque enough.
+// Only the silhouette matters here, so the texture color is ignored.
+void main() {
+    if (texture(sprite_texture, tex_coords).a < 0.5) {
+        discard;
+    }
+    frag_color = sprite_tint;
+}
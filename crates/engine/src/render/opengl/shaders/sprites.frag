@@ -3,10 +3,42 @@
 uniform vec4 sprite_tint;
 uniform sampler2D sprite_texture; // @ tmu:0
 
+// Palette swap colors (see PaletteSwap). Transparent = no swap.
+// Shades of pure magenta take the primary color, shades of pure cyan the secondary.
+uniform vec4 palette_primary;
+uniform vec4 palette_secondary;
+
+// Max deviation from an exact key color shade, to tolerate compression/filtering.
+const float PALETTE_KEY_TOLERANCE = 0.02;
+
 in vec2 tex_coords;
 
 out vec4 frag_color;
 
+vec4 apply_palette_swap(vec4 texel) {
+    vec3 rgb = texel.rgb;
+
+    bool is_primary_key = abs(texel.r - texel.b) <= PALETTE_KEY_TOLERANCE
+        && texel.g <= PALETTE_KEY_TOLERANCE
+        && texel.r > PALETTE_KEY_TOLERANCE;
+    if (is_primary_key) {
+        rgb = mix(rgb, palette_primary.rgb * texel.r, palette_primary.a);
+    }
+
+    bool is_secondary_key = abs(texel.g - texel.b) <= PALETTE_KEY_TOLERANCE
+        && texel.r <= PALETTE_KEY_TOLERANCE
+        && texel.g > PALETTE_KEY_TOLERANCE;
+    if (is_secondary_key) {
+        rgb = mix(rgb, palette_secondary.rgb * texel.g, palette_secondary.a);
+    }
+
+    return vec4(rgb, texel.a);
+}
+
 void main() {
-    frag_color = texture(sprite_texture, tex_coords) * sprite_tint;
+    vec4 texel = texture(sprite_texture, tex_coords);
+    if (palette_primary.a > 0.0 || palette_secondary.a > 0.0) {
+        texel = apply_palette_swap(texel);
+    }
+    frag_color = texel * sprite_tint;
 }
@@ -2,6 +2,11 @@
 
 uniform vec2 viewport_size;
 
+// World-to-screen transform. Identity (scaling=1, offset=0) for sprites
+// submitted in screen space; set per static sprite mesh otherwise.
+uniform vec2 world_offset;
+uniform float world_scaling;
+
 layout(location = 0) in vec2 in_position;
 layout(location = 1) in vec2 in_tex_coords;
 
@@ -11,11 +16,13 @@ void main() {
     // Flip UVs here:
     tex_coords = vec2(in_tex_coords.x, 1.0 - in_tex_coords.y);
 
+    vec2 screen_position = (in_position * world_scaling) + world_offset;
+
     // Map to normalized clip coordinates:
-    // 'in_position' comes in as screen space.
+    // 'screen_position' is in screen space.
     vec2 ndc = vec2(
-        (in_position.x / viewport_size.x) * 2.0 - 1.0,
-        1.0 - (in_position.y / viewport_size.y) * 2.0); // Origin: top-left corner.
+        (screen_position.x / viewport_size.x) * 2.0 - 1.0,
+        1.0 - (screen_position.y / viewport_size.y) * 2.0); // Origin: top-left corner.
 
     gl_Position = vec4(ndc, 0.0, 1.0);
 }
@@ -1,3 +1,5 @@
+use std::ffi::c_void;
+
 use common::Size;
 
 use super::{
@@ -19,11 +21,11 @@ pub struct RenderTarget {
 }
 
 impl RenderTarget {
-    pub fn new(size: Size, sampling_filter: TextureFilter) -> Self {
+    pub fn new(name: &str, size: Size, sampling_filter: TextureFilter) -> Self {
         debug_assert!(size.is_valid());
 
         let color_rt_texture = OpenGlTexture::new(TextureCreationParams {
-            name: "offscreen_render_target",
+            name,
             size,
             pixels: &[],
             settings: TextureSettings { filter: sampling_filter, wrap_mode: TextureWrapMode::ClampToEdge, mipmaps: false },
@@ -47,7 +49,8 @@ impl RenderTarget {
             if framebuffer_status != gl::FRAMEBUFFER_COMPLETE {
                 log::error!(
                     log::channel!("render"),
-                    "Invalid framebuffer status for 'offscreen_render_target': 0x{:X}",
+                    "Invalid framebuffer status for '{}': 0x{:X}",
+                    name,
                     framebuffer_status
                 );
             }
@@ -96,6 +99,42 @@ impl RenderTarget {
         }
     }
 
+    // Reads back the color buffer as RGBA8 pixels, rows top to bottom.
+    pub fn read_pixels(&self) -> Vec<u8> {
+        debug_assert!(self.is_valid());
+
+        let row_size = (self.size.width * 4) as usize;
+        let row_count = self.size.height as usize;
+        let mut pixels = vec![0; row_size * row_count];
+
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.framebuffer_handle);
+            gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+
+            gl::ReadPixels(
+                0,
+                0,
+                self.size.width,
+                self.size.height,
+                gl::RGBA,
+                gl::UNSIGNED_BYTE,
+                pixels.as_mut_ptr() as *mut c_void,
+            );
+
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, NULL_BUFFER_HANDLE);
+        }
+
+        panic_if_gl_error();
+
+        // OpenGL returns the bottom row first.
+        for row in 0..(row_count / 2) {
+            let (upper, lower) = pixels.split_at_mut((row_count - 1 - row) * row_size);
+            upper[(row * row_size)..((row + 1) * row_size)].swap_with_slice(&mut lower[..row_size]);
+        }
+
+        pixels
+    }
+
     #[inline]
     pub fn is_valid(&self) -> bool {
         use render::texture::Texture;
@@ -112,6 +151,11 @@ impl RenderTarget {
     pub fn size(&self) -> Size {
         self.size
     }
+
+    #[inline]
+    pub fn color_texture(&self) -> &OpenGlTexture {
+        &self.color_rt_texture
+    }
 }
 
 impl Drop for RenderTarget {
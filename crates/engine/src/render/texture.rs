@@ -145,6 +145,9 @@ pub(super) enum TextureBackendImpl {
 
     #[cfg(feature = "desktop")]
     OpenGl(opengl::OpenGlTexture),
+
+    #[cfg(feature = "desktop")]
+    Null(null::NullTexture),
 }
 
 macro_rules! texture_backend_type_casts {
@@ -175,6 +178,9 @@ impl TextureBackendImpl {
 
     #[cfg(feature = "desktop")]
     texture_backend_type_casts! { OpenGl, as_opengl, as_opengl_mut, opengl::OpenGlTexture }
+
+    #[cfg(feature = "desktop")]
+    texture_backend_type_casts! { Null, as_null, as_null_mut, null::NullTexture }
 }
 
 // ----------------------------------------------
@@ -212,6 +218,14 @@ impl TextureCache {
         None
     }
 
+    // Size in pixels of a loaded texture. Built-in textures are 8x8.
+    pub fn texture_size(&self, handle: TextureHandle) -> Size {
+        match handle {
+            TextureHandle::Index(idx) => self.texture_at_index(idx).size(),
+            TextureHandle::Invalid | TextureHandle::White => Size::new(8, 8),
+        }
+    }
+
     // Load texture with default settings, which can be overridden by change_texture_settings().
     pub fn load_texture(&mut self, file_path: PathRef) -> TextureHandle {
         self.load_texture_with_settings(file_path, None)
@@ -237,6 +251,48 @@ impl TextureCache {
         self.new_initialized_texture(file_path.as_str(), size, pixels, settings)
     }
 
+    // Re-reads the image file of an already loaded texture, e.g. after it was edited on disk.
+    // The texture keeps its handle and settings, so anything referencing it picks up the new
+    // pixels. Returns false if the texture isn't loaded or the image failed to load (old one is kept).
+    pub fn reload_texture(&mut self, file_path: PathRef) -> bool {
+        let Some(TextureHandle::Index(idx)) = self.find_loaded_texture(file_path.as_str()) else {
+            return false;
+        };
+
+        let image = match Self::load_image_file(file_path) {
+            Ok(image) => image,
+            Err(_) => {
+                log::error!(log::channel!("render"), "Failed to reload texture '{file_path}'.");
+                return false;
+            }
+        };
+
+        let (settings, allow_settings_change) = {
+            let texture = self.texture_at_index(idx);
+            let settings = TextureSettings {
+                filter: texture.filter(),
+                wrap_mode: texture.wrap_mode(),
+                mipmaps: texture.has_mipmaps(),
+            };
+            (settings, texture.allow_settings_change())
+        };
+
+        let mut old_handle = TextureHandle::Index(idx);
+        self.release_texture(&mut old_handle);
+
+        let size = Size::new(image.width() as i32, image.height() as i32);
+        let new_handle = self.new_initialized_texture(
+            file_path.as_str(),
+            size,
+            image.as_raw(),
+            if allow_settings_change { None } else { Some(settings) },
+        );
+
+        // The slab hands back the slot just freed, so the handle doesn't change.
+        debug_assert!(new_handle == TextureHandle::Index(idx));
+        true
+    }
+
     // If settings are provided they will be used and will not be affected by change_texture_settings().
     pub fn new_uninitialized_texture(&mut self, name: &str, size: Size, settings: Option<TextureSettings>) -> TextureHandle {
         let pixels = []; // Empty pixels slice = uninitialized.
@@ -274,6 +330,40 @@ impl TextureCache {
         self.register_new_texture(texture)
     }
 
+    // Color lookup table for ColorGrading with `lut_size` slices, filled by `grade_fn`, which maps
+    // each input RGB color in [0,1] to its graded color. Uses linear filtering to interpolate
+    // between table entries. Tables can also be authored as images and loaded like any texture.
+    pub fn new_color_lut(
+        &mut self,
+        name: &str,
+        lut_size: u32,
+        grade_fn: impl Fn([f32; 3]) -> [f32; 3],
+    ) -> TextureHandle {
+        debug_assert!(lut_size >= 2);
+
+        let width = lut_size * lut_size;
+        let max_value = (lut_size - 1) as f32;
+        let mut pixels = Vec::with_capacity((width * lut_size * 4) as usize);
+
+        for green in 0..lut_size {
+            for blue in 0..lut_size {
+                for red in 0..lut_size {
+                    let color = [red as f32 / max_value, green as f32 / max_value, blue as f32 / max_value];
+                    let graded = grade_fn(color);
+                    pixels.extend(graded.iter().map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8));
+                    pixels.push(255);
+                }
+            }
+        }
+
+        let settings = TextureSettings {
+            filter: TextureFilter::Linear,
+            wrap_mode: TextureWrapMode::ClampToEdge,
+            mipmaps: false,
+        };
+        self.new_initialized_texture(name, Size::new(width as i32, lut_size as i32), &pixels, Some(settings))
+    }
+
     // Update texture mip-level sub-rect or whole texture.
     pub fn update_texture(
         &mut self,
@@ -356,6 +446,18 @@ impl TextureCache {
         self.settings
     }
 
+    // Approximate GPU memory held by loaded textures, assuming RGBA8 pixels.
+    // A full mipmap chain adds about one third on top of the base level.
+    pub fn memory_usage_estimate(&self) -> usize {
+        let mut estimate = 0;
+        for (_, texture) in &self.textures {
+            let size = texture.size();
+            let base_level_bytes = (size.width * size.height) as usize * 4;
+            estimate += if texture.has_mipmaps() { base_level_bytes * 4 / 3 } else { base_level_bytes };
+        }
+        estimate
+    }
+
     // ----------------------
     // Internal:
     // ----------------------
@@ -12,6 +12,7 @@ use crate::render;
 // DrawBatchEntry
 // ----------------------------------------------
 
+#[derive(Copy, Clone)]
 pub struct DrawBatchEntry {
     pub first_index: u32,
     pub index_count: u32,
@@ -26,6 +27,7 @@ pub struct DrawBatch<V: Copy, I: Copy> {
     vertices: Vec<V>,
     indices: Vec<I>,
     entries: Vec<DrawBatchEntry>,
+    submitted_entries: u32,
 }
 
 impl<V, I> DrawBatch<V, I>
@@ -38,6 +40,7 @@ where
             vertices: Vec::with_capacity(vertices_capacity),
             indices: Vec::with_capacity(indices_capacity),
             entries: Vec::with_capacity(entries_capacity),
+            submitted_entries: 0,
         }
     }
 
@@ -46,6 +49,18 @@ where
         <I as TryFrom<usize>>::Error: Debug,
     {
         let first_index = self.add_fast(vertices, indices);
+        self.submitted_entries += 1;
+
+        // Merge with the previous entry if it samples the same texture (e.g. same atlas page).
+        // Tint is per-vertex here, so only the texture binding splits draw calls.
+        if let Some(last_entry) = self.entries.last_mut() {
+            if last_entry.texture == texture {
+                debug_assert!((last_entry.first_index + last_entry.index_count) as usize == first_index);
+                last_entry.index_count += indices.len() as u32;
+                return;
+            }
+        }
+
         self.entries.push(DrawBatchEntry { first_index: first_index as u32, index_count: indices.len() as u32, texture });
     }
 
@@ -82,6 +97,12 @@ where
         &self.entries
     }
 
+    // Number of add_entry() calls since the last clear().
+    #[inline]
+    pub fn submitted_entries(&self) -> u32 {
+        self.submitted_entries
+    }
+
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.vertices.is_empty()
@@ -91,6 +112,7 @@ where
         self.vertices.clear();
         self.indices.clear();
         self.entries.clear();
+        self.submitted_entries = 0;
     }
 }
 
@@ -201,7 +223,10 @@ impl GpuVertexIndexBuffers {
         Self { label, vertex_buffer, index_buffer, vertex_capacity: vb_bytes, index_capacity: ib_bytes }
     }
 
-    pub fn ensure_capacity(&mut self, device: &wgpu::Device, vb_bytes: usize, ib_bytes: usize) {
+    // Returns the number of buffers that had to be reallocated.
+    pub fn ensure_capacity(&mut self, device: &wgpu::Device, vb_bytes: usize, ib_bytes: usize) -> u32 {
+        let mut reallocs = 0;
+
         if vb_bytes > self.vertex_capacity {
             let new_cap = vb_bytes.next_power_of_two();
             self.vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -211,6 +236,7 @@ impl GpuVertexIndexBuffers {
                 mapped_at_creation: false,
             });
             self.vertex_capacity = new_cap;
+            reallocs += 1;
         }
 
         if ib_bytes > self.index_capacity {
@@ -222,20 +248,23 @@ impl GpuVertexIndexBuffers {
                 mapped_at_creation: false,
             });
             self.index_capacity = new_cap;
+            reallocs += 1;
         }
+
+        reallocs
     }
 
-    // Upload a typed batch (Pod vertices + indices) the GPU.
+    // Upload a typed batch (Pod vertices + indices) the GPU. Returns the number of buffer reallocations.
     pub fn upload<V: bytemuck::Pod, I: bytemuck::Pod>(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         vertices: &[V],
         indices: &[I],
-    ) {
+    ) -> u32 {
         let vb_bytes = std::mem::size_of_val(vertices);
         let ib_bytes = std::mem::size_of_val(indices);
-        self.ensure_capacity(device, vb_bytes, ib_bytes);
+        let reallocs = self.ensure_capacity(device, vb_bytes, ib_bytes);
 
         if !vertices.is_empty() {
             queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(vertices));
@@ -244,14 +273,22 @@ impl GpuVertexIndexBuffers {
         if !indices.is_empty() {
             queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(indices));
         }
+
+        reallocs
     }
 
     // Upload raw bytes (for UI batch data that isn't bytemuck::Pod).
     // Handles the 4-byte alignment padding required by wgpu's write_buffer.
-    pub fn upload_bytes(&mut self, device: &wgpu::Device, queue: &wgpu::Queue, vertex_bytes: &[u8], index_bytes: &[u8]) {
+    pub fn upload_bytes(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        vertex_bytes: &[u8],
+        index_bytes: &[u8],
+    ) -> u32 {
         let vb_bytes = align_to_4(vertex_bytes.len());
         let ib_bytes = align_to_4(index_bytes.len());
-        self.ensure_capacity(device, vb_bytes, ib_bytes);
+        let reallocs = self.ensure_capacity(device, vb_bytes, ib_bytes);
 
         if !vertex_bytes.is_empty() {
             let data = pad_to_alignment::<4>(vertex_bytes);
@@ -262,6 +299,8 @@ impl GpuVertexIndexBuffers {
             let data = pad_to_alignment::<4>(index_bytes);
             queue.write_buffer(&self.index_buffer, 0, &data);
         }
+
+        reallocs
     }
 
     // Bind vertex and index buffers to a render pass.
@@ -293,3 +332,43 @@ pub fn pad_to_alignment<const ALIGN: usize>(data: &[u8]) -> Cow<'_, [u8]> {
         Cow::Owned(padded)
     }
 }
+
+// ----------------------------------------------
+// StaticSpriteMesh
+// ----------------------------------------------
+
+// Sprite geometry kept resident on the GPU between frames. Each mesh has its
+// own uniforms, so it can be drawn with its own world-to-screen transform.
+pub struct StaticSpriteMesh {
+    pub buffers: GpuVertexIndexBuffers,
+    pub entries: Vec<DrawBatchEntry>,
+    pub index_count: u32,
+    pub uniform_buffer: wgpu::Buffer,
+    pub uniform_bind_group: wgpu::BindGroup,
+}
+
+impl StaticSpriteMesh {
+    pub fn new(device: &wgpu::Device, uniform_bind_group_layout: &wgpu::BindGroupLayout) -> Self {
+        let buffers = GpuVertexIndexBuffers::new(
+            device,
+            "static_sprite_mesh",
+            64 * std::mem::size_of::<super::vertex::SpriteVertex2D>(),
+            96 * std::mem::size_of::<super::vertex::SpriteIndex2D>(),
+        );
+
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("static_sprite_mesh_uniform_vars"),
+            size: std::mem::size_of::<super::ShaderUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("static_sprite_mesh_uniform_vars_bind_group"),
+            layout: uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry { binding: 0, resource: uniform_buffer.as_entire_binding() }],
+        });
+
+        Self { buffers, entries: Vec::new(), index_count: 0, uniform_buffer, uniform_bind_group }
+    }
+}
@@ -1,5 +1,5 @@
 use arrayvec::ArrayVec;
-use common::{Color, Rect, RectTexCoords, Size, Vec2, time::PerfTimer};
+use common::{Color, Rect, RectTexCoords, Size, Vec2, coords::WorldToScreenTransform, time::PerfTimer};
 use batch::*;
 use target::*;
 use texture::*;
@@ -7,7 +7,7 @@ use vertex::*;
 
 pub use texture::WgpuTexture;
 
-use super::{RenderStats, RenderSystemBackend};
+use super::{RenderStats, RenderSystemBackend, StaticSprite, StaticSpriteMeshHandle};
 use crate::{log, ui::UiRenderFrameBundle};
 
 mod batch;
@@ -24,7 +24,22 @@ mod vertex;
 #[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct ShaderUniforms {
     viewport_size: [f32; 2],
-    _padding: [f32; 2], // Align to 16 bytes for uniform buffer.
+    world_offset: [f32; 2],      // Only used by the sprites shader.
+    world_scaling: f32,          // Only used by the sprites shader.
+    color_grading_strength: f32, // Only used by the color grading blit.
+    _padding: [f32; 2],          // Align to 16 bytes for uniform buffer.
+}
+
+impl ShaderUniforms {
+    fn new(viewport: Rect, transform: WorldToScreenTransform, color_grading_strength: f32) -> Self {
+        Self {
+            viewport_size: [viewport.width(), viewport.height()],
+            world_offset: [transform.offset.x, transform.offset.y],
+            world_scaling: transform.scaling,
+            color_grading_strength,
+            _padding: [0.0; 2],
+        }
+    }
 }
 
 // ----------------------------------------------
@@ -46,8 +61,12 @@ struct WgpuSystemState {
     points_pipeline: wgpu::RenderPipeline,
     ui_pipeline: wgpu::RenderPipeline,
     blit_pipeline: wgpu::RenderPipeline,
+    color_grade_pipeline: wgpu::RenderPipeline,
+    outline_mask_pipeline: wgpu::RenderPipeline,
+    outline_pipeline: wgpu::RenderPipeline,
 
     // Shared bind group layouts.
+    uniform_bind_group_layout: wgpu::BindGroupLayout,
     texture_bind_group_layout: wgpu::BindGroupLayout,
     blit_texture_layout: wgpu::BindGroupLayout,
 
@@ -60,16 +79,29 @@ struct WgpuSystemState {
     lines_batch: DrawBatch<LineVertex2D, LineIndex2D>,
     points_batch: DrawBatch<PointVertex2D, PointIndex2D>,
     ui_batch: UiDrawBatch,
+    outline_mask_batch: DrawBatch<SpriteVertex2D, SpriteIndex2D>,
 
     // GPU-side buffers.
     sprites_gpu: GpuVertexIndexBuffers,
     lines_gpu: GpuVertexIndexBuffers,
     points_gpu: GpuVertexIndexBuffers,
     ui_gpu: GpuVertexIndexBuffers,
+    outline_mask_gpu: GpuVertexIndexBuffers,
+
+    // Static sprite meshes, indexed by StaticSpriteMeshHandle.
+    static_meshes: Vec<Option<StaticSpriteMesh>>,
+    static_mesh_draws: Vec<(StaticSpriteMeshHandle, WorldToScreenTransform)>,
+    static_mesh_scratch_batch: DrawBatch<SpriteVertex2D, SpriteIndex2D>, // Builds mesh data before upload.
 
     // Offscreen render target.
     offscreen_render_target: RenderTarget,
 
+    // Selection outline mask, same size as the offscreen render target.
+    outline_mask_render_target: RenderTarget,
+
+    // Applied when blitting the offscreen render target to the surface.
+    color_grading: Option<super::ColorGrading>,
+
     // UI draw commands recorded during the frame.
     ui_draw_commands: Vec<UiDrawCommand>,
     ui_base_vertex: i32,
@@ -108,6 +140,8 @@ impl WgpuSystemState {
             if self.offscreen_render_target.needs_resize(rt_size) {
                 self.offscreen_render_target =
                     RenderTarget::new(&self.device, rt_size, self.surface_format, &self.blit_texture_layout);
+                self.outline_mask_render_target =
+                    RenderTarget::new(&self.device, rt_size, self.surface_format, &self.blit_texture_layout);
             }
         }
     }
@@ -239,6 +273,20 @@ impl WgpuRenderSystemBackend {
         let points_pipeline = pipeline::create_points_pipeline(&device, surface_format, &uniform_bind_group_layout);
         let ui_pipeline = pipeline::create_ui_pipeline(&device, surface_format, &uniform_bind_group_layout, &texture_bind_group_layout);
         let blit_pipeline = pipeline::create_blit_pipeline(&device, surface_format, &blit_texture_layout);
+        let color_grade_pipeline = pipeline::create_color_grade_pipeline(
+            &device,
+            surface_format,
+            &blit_texture_layout,
+            &texture_bind_group_layout,
+            &uniform_bind_group_layout,
+        );
+        let outline_mask_pipeline = pipeline::create_outline_mask_pipeline(
+            &device,
+            surface_format,
+            &uniform_bind_group_layout,
+            &texture_bind_group_layout,
+        );
+        let outline_pipeline = pipeline::create_outline_pipeline(&device, surface_format, &blit_texture_layout);
 
         // Uniform buffer.
         let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -257,6 +305,7 @@ impl WgpuRenderSystemBackend {
         // Offscreen render target.
         let rt_size = viewport_size.max(framebuffer_size);
         let offscreen_render_target = RenderTarget::new(&device, rt_size, surface_format, &blit_texture_layout);
+        let outline_mask_render_target = RenderTarget::new(&device, rt_size, surface_format, &blit_texture_layout);
 
         // GPU buffers.
         let sprites_gpu = GpuVertexIndexBuffers::new(
@@ -283,6 +332,12 @@ impl WgpuRenderSystemBackend {
             1024 * std::mem::size_of::<UiVertex2D>(),
             1024 * std::mem::size_of::<super::UiDrawIndex>(),
         );
+        let outline_mask_gpu = GpuVertexIndexBuffers::new(
+            &device,
+            "outline_mask",
+            64 * std::mem::size_of::<SpriteVertex2D>(),
+            64 * std::mem::size_of::<SpriteIndex2D>(),
+        );
 
         log::info!(log::channel!("render"), "Wgpu initialized.");
         log::info!(log::channel!("render"), " - Surface format: {:?}", surface_format);
@@ -301,7 +356,11 @@ impl WgpuRenderSystemBackend {
             points_pipeline,
             ui_pipeline,
             blit_pipeline,
+            color_grade_pipeline,
+            outline_mask_pipeline,
+            outline_pipeline,
 
+            uniform_bind_group_layout,
             texture_bind_group_layout,
             blit_texture_layout,
 
@@ -312,13 +371,22 @@ impl WgpuRenderSystemBackend {
             lines_batch: DrawBatch::new(64, 64, 0),
             points_batch: DrawBatch::new(64, 64, 0),
             ui_batch: UiDrawBatch::new(),
+            outline_mask_batch: DrawBatch::new(64, 64, 16),
 
             sprites_gpu,
             lines_gpu,
             points_gpu,
             ui_gpu,
+            outline_mask_gpu,
+
+            static_meshes: Vec::new(),
+            static_mesh_draws: Vec::new(),
+            static_mesh_scratch_batch: DrawBatch::new(0, 0, 0),
 
             offscreen_render_target,
+            outline_mask_render_target,
+
+            color_grading: None,
 
             ui_draw_commands: Vec::with_capacity(64),
             ui_base_vertex: 0,
@@ -364,6 +432,10 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
         s.stats.points_drawn          = 0;
         s.stats.texture_changes       = 0;
         s.stats.draw_calls            = 0;
+        s.stats.sprites_submitted     = 0;
+        s.stats.sprite_batches        = 0;
+        s.stats.static_meshes         = 0;
+        s.stats.buffer_reallocs       = 0;
         s.stats.render_submit_time_ms = 0.0;
     }
 
@@ -385,14 +457,27 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
         debug_assert!(s.framebuffer_size.is_valid());
 
         // Upload viewport uniform.
-        let uniforms = ShaderUniforms { viewport_size: [s.viewport.width(), s.viewport.height()], _padding: [0.0; 2] };
+        let color_grading_strength = s.color_grading.map_or(0.0, |color_grading| color_grading.strength);
+        let uniforms = ShaderUniforms::new(s.viewport, WorldToScreenTransform::default(), color_grading_strength);
         s.queue.write_buffer(&s.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
 
+        // Each static mesh is drawn at most once per frame, so its uniforms only need this frame's transform.
+        for &(mesh, transform) in &s.static_mesh_draws {
+            let static_mesh = super::static_mesh_slot(&mut s.static_meshes, mesh);
+            let uniforms = ShaderUniforms::new(s.viewport, transform, 0.0);
+            s.queue.write_buffer(&static_mesh.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+        }
+
         // Upload batch data to GPU.
-        s.sprites_gpu.upload(&s.device, &s.queue, s.sprites_batch.vertices(), s.sprites_batch.indices());
-        s.lines_gpu.upload(&s.device, &s.queue, s.lines_batch.vertices(), s.lines_batch.indices());
-        s.points_gpu.upload(&s.device, &s.queue, s.points_batch.vertices(), s.points_batch.indices());
-        s.ui_gpu.upload_bytes(&s.device, &s.queue, s.ui_batch.vertex_bytes(), s.ui_batch.index_bytes());
+        let (device, queue) = (&s.device, &s.queue);
+        let mut buffer_reallocs = 0;
+        buffer_reallocs += s.sprites_gpu.upload(device, queue, s.sprites_batch.vertices(), s.sprites_batch.indices());
+        buffer_reallocs += s.lines_gpu.upload(device, queue, s.lines_batch.vertices(), s.lines_batch.indices());
+        buffer_reallocs += s.points_gpu.upload(device, queue, s.points_batch.vertices(), s.points_batch.indices());
+        buffer_reallocs += s.ui_gpu.upload_bytes(device, queue, s.ui_batch.vertex_bytes(), s.ui_batch.index_bytes());
+        buffer_reallocs +=
+            s.outline_mask_gpu.upload(device, queue, s.outline_mask_batch.vertices(), s.outline_mask_batch.indices());
+        s.stats.buffer_reallocs += buffer_reallocs;
 
         // Acquire surface texture.
         let output = match s.surface.get_current_texture() {
@@ -407,6 +492,40 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
 
         let mut encoder = s.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("frame_encoder") });
 
+        // ---- Pass 0: Render selection outline silhouettes to the mask RT ----
+        if !s.outline_mask_batch.is_empty() {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("outline_mask_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: s.outline_mask_render_target.view(),
+                    depth_slice: None,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                ..Default::default()
+            });
+
+            pass.set_pipeline(&s.outline_mask_pipeline);
+            pass.set_bind_group(0, Some(&s.uniform_bind_group), &[]);
+            s.outline_mask_gpu.bind_to_render_pass(&mut pass, vertex::size_to_index_format::<SpriteIndex2D>());
+
+            let mut last_texture = super::texture::TextureHandle::invalid();
+            for entry in s.outline_mask_batch.entries() {
+                if entry.texture != last_texture {
+                    last_texture = entry.texture;
+                    let bg = tex_cache.texture_for_handle(entry.texture).as_wgpu().bind_group();
+                    pass.set_bind_group(1, Some(bg), &[]);
+                    s.stats.texture_changes += 1;
+                }
+                pass.draw_indexed(entry.first_index..entry.first_index + entry.index_count, 0, 0..1);
+                s.stats.draw_calls += 1;
+            }
+        }
+
         // ---- Pass 1: Render world to offscreen RT ----
         {
             let cc = &s.clear_color;
@@ -424,6 +543,36 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
                 ..Default::default()
             });
 
+            // Draw static sprite meshes under all other sprites.
+            if !s.static_mesh_draws.is_empty() {
+                pass.set_pipeline(&s.sprites_pipeline);
+
+                let mut last_texture = super::texture::TextureHandle::invalid();
+                for &(mesh, _) in &s.static_mesh_draws {
+                    let static_mesh = super::static_mesh_slot(&mut s.static_meshes, mesh);
+                    if static_mesh.entries.is_empty() {
+                        continue;
+                    }
+
+                    pass.set_bind_group(0, Some(&static_mesh.uniform_bind_group), &[]);
+                    static_mesh.buffers.bind_to_render_pass(&mut pass, vertex::size_to_index_format::<SpriteIndex2D>());
+
+                    for entry in &static_mesh.entries {
+                        if entry.texture != last_texture {
+                            last_texture = entry.texture;
+                            let bg = tex_cache.texture_for_handle(entry.texture).as_wgpu().bind_group();
+                            pass.set_bind_group(1, Some(bg), &[]);
+                            s.stats.texture_changes += 1;
+                        }
+                        pass.draw_indexed(entry.first_index..entry.first_index + entry.index_count, 0, 0..1);
+                        s.stats.draw_calls += 1;
+                    }
+
+                    s.stats.triangles_drawn += static_mesh.index_count / 3;
+                    s.stats.static_meshes += 1;
+                }
+            }
+
             // Draw sprites.
             if !s.sprites_batch.is_empty() {
                 pass.set_pipeline(&s.sprites_pipeline);
@@ -443,6 +592,14 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
                 }
             }
 
+            // Trace selection outlines on top of the sprites, under debug lines/points.
+            if !s.outline_mask_batch.is_empty() {
+                pass.set_pipeline(&s.outline_pipeline);
+                pass.set_bind_group(0, Some(s.outline_mask_render_target.bind_group()), &[]);
+                pass.draw(0..3, 0..1); // Fullscreen triangle, no vertex buffer.
+                s.stats.draw_calls += 1;
+            }
+
             // Draw lines.
             if !s.lines_batch.is_empty() {
                 pass.set_pipeline(&s.lines_pipeline);
@@ -462,7 +619,7 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
             }
         }
 
-        // ---- Pass 2: Blit offscreen RT to surface ----
+        // ---- Pass 2: Blit offscreen RT to surface, color graded if enabled ----
         {
             let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
                 label: Some("blit_pass"),
@@ -476,7 +633,16 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
                 ..Default::default()
             });
 
-            pass.set_pipeline(&s.blit_pipeline);
+            match s.color_grading {
+                Some(color_grading) if color_grading.strength > 0.0 => {
+                    let lut_bg = tex_cache.texture_for_handle(color_grading.lut).as_wgpu().bind_group();
+                    pass.set_pipeline(&s.color_grade_pipeline);
+                    pass.set_bind_group(1, Some(lut_bg), &[]);
+                    pass.set_bind_group(2, Some(&s.uniform_bind_group), &[]);
+                }
+                _ => pass.set_pipeline(&s.blit_pipeline),
+            }
+
             pass.set_bind_group(0, Some(s.offscreen_render_target.bind_group()), &[]);
             pass.draw(0..3, 0..1); // Fullscreen triangle, no vertex buffer.
             s.stats.draw_calls += 1;
@@ -531,23 +697,32 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
         s.queue.submit(std::iter::once(encoder.finish()));
         output.present();
 
+        s.stats.sprites_submitted = s.sprites_batch.submitted_entries();
+        s.stats.sprite_batches    = s.sprites_batch.entries().len() as u32;
+
         // Clear batches for next frame.
         s.sprites_batch.clear();
         s.lines_batch.clear();
         s.points_batch.clear();
         s.ui_batch.clear();
+        s.outline_mask_batch.clear();
+        s.static_mesh_draws.clear();
         s.ui_draw_commands.clear();
         s.ui_base_vertex = 0;
         s.ui_index_offset = 0;
 
         s.frame_started = false;
 
-        s.stats.render_submit_time_ms = render_submit_timer.end();
-        s.stats.peak_triangles_drawn  = s.stats.triangles_drawn.max(s.stats.peak_triangles_drawn);
-        s.stats.peak_lines_drawn      = s.stats.lines_drawn.max(s.stats.peak_lines_drawn);
-        s.stats.peak_points_drawn     = s.stats.points_drawn.max(s.stats.peak_points_drawn);
-        s.stats.peak_texture_changes  = s.stats.texture_changes.max(s.stats.peak_texture_changes);
-        s.stats.peak_draw_calls       = s.stats.draw_calls.max(s.stats.peak_draw_calls);
+        s.stats.render_submit_time_ms  = render_submit_timer.end();
+        s.stats.peak_triangles_drawn   = s.stats.triangles_drawn.max(s.stats.peak_triangles_drawn);
+        s.stats.peak_lines_drawn       = s.stats.lines_drawn.max(s.stats.peak_lines_drawn);
+        s.stats.peak_points_drawn      = s.stats.points_drawn.max(s.stats.peak_points_drawn);
+        s.stats.peak_texture_changes   = s.stats.texture_changes.max(s.stats.peak_texture_changes);
+        s.stats.peak_draw_calls        = s.stats.draw_calls.max(s.stats.peak_draw_calls);
+        s.stats.peak_sprites_submitted = s.stats.sprites_submitted.max(s.stats.peak_sprites_submitted);
+        s.stats.peak_sprite_batches    = s.stats.sprite_batches.max(s.stats.peak_sprite_batches);
+        s.stats.peak_static_meshes     = s.stats.static_meshes.max(s.stats.peak_static_meshes);
+        s.stats.peak_buffer_reallocs   = s.stats.buffer_reallocs.max(s.stats.peak_buffer_reallocs);
 
         s.stats
     }
@@ -645,6 +820,7 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
         tex_coords: &RectTexCoords,
         texture: super::texture::TextureHandle,
         color: Color,
+        palette: super::PaletteSwap,
     ) {
         let s = self.state_mut();
         debug_assert!(s.frame_started);
@@ -654,10 +830,10 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
         }
 
         let vertices = [
-            SpriteVertex2D::new(rect.bottom_left(),  tex_coords.bottom_left(),  color),
-            SpriteVertex2D::new(rect.top_left(),     tex_coords.top_left(),     color),
-            SpriteVertex2D::new(rect.top_right(),    tex_coords.top_right(),    color),
-            SpriteVertex2D::new(rect.bottom_right(), tex_coords.bottom_right(), color),
+            SpriteVertex2D::with_palette(rect.bottom_left(),  tex_coords.bottom_left(),  color, palette),
+            SpriteVertex2D::with_palette(rect.top_left(),     tex_coords.top_left(),     color, palette),
+            SpriteVertex2D::with_palette(rect.top_right(),    tex_coords.top_right(),    color, palette),
+            SpriteVertex2D::with_palette(rect.bottom_right(), tex_coords.bottom_right(), color, palette),
         ];
 
         const INDICES: [SpriteIndex2D; 6] = [0, 1, 2, 2, 3, 0];
@@ -666,6 +842,200 @@ impl RenderSystemBackend for WgpuRenderSystemBackend {
         s.stats.triangles_drawn += 2;
     }
 
+    fn draw_outline_mask_rect(
+        &mut self,
+        rect: Rect,
+        tex_coords: &RectTexCoords,
+        texture: super::texture::TextureHandle,
+        outline_color: Color,
+    ) {
+        let s = self.state_mut();
+        debug_assert!(s.frame_started);
+
+        if super::is_rect_fully_offscreen(&s.viewport, &rect) {
+            return;
+        }
+
+        let vertices = [
+            SpriteVertex2D::new(rect.bottom_left(),  tex_coords.bottom_left(),  outline_color),
+            SpriteVertex2D::new(rect.top_left(),     tex_coords.top_left(),     outline_color),
+            SpriteVertex2D::new(rect.top_right(),    tex_coords.top_right(),    outline_color),
+            SpriteVertex2D::new(rect.bottom_right(), tex_coords.bottom_right(), outline_color),
+        ];
+
+        const INDICES: [SpriteIndex2D; 6] = [0, 1, 2, 2, 3, 0];
+
+        s.outline_mask_batch.add_entry(&vertices, &INDICES, texture);
+        s.stats.triangles_drawn += 2;
+    }
+
+    // ----------------------
+    // Static sprite meshes:
+    // ----------------------
+
+    fn new_static_sprite_mesh(&mut self) -> StaticSpriteMeshHandle {
+        let s = self.state_mut();
+        let static_mesh = StaticSpriteMesh::new(&s.device, &s.uniform_bind_group_layout);
+        super::alloc_static_mesh_slot(&mut s.static_meshes, static_mesh)
+    }
+
+    fn update_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, sprites: &[StaticSprite]) {
+        let s = self.state_mut();
+        let scratch = &mut s.static_mesh_scratch_batch;
+
+        scratch.clear();
+
+        for sprite in sprites {
+            let rect = &sprite.rect;
+            let tex_coords = &sprite.tex_coords;
+            let (color, palette) = (sprite.color, sprite.palette);
+
+            let vertices = [
+                SpriteVertex2D::with_palette(rect.bottom_left(),  tex_coords.bottom_left(),  color, palette),
+                SpriteVertex2D::with_palette(rect.top_left(),     tex_coords.top_left(),     color, palette),
+                SpriteVertex2D::with_palette(rect.top_right(),    tex_coords.top_right(),    color, palette),
+                SpriteVertex2D::with_palette(rect.bottom_right(), tex_coords.bottom_right(), color, palette),
+            ];
+
+            const INDICES: [SpriteIndex2D; 6] = [0, 1, 2, 2, 3, 0];
+
+            scratch.add_entry(&vertices, &INDICES, sprite.texture);
+        }
+
+        // Written to the GPU buffers now; queued writes land before the next frame is submitted.
+        let static_mesh = super::static_mesh_slot(&mut s.static_meshes, mesh);
+        s.stats.buffer_reallocs += static_mesh.buffers.upload(&s.device, &s.queue, scratch.vertices(), scratch.indices());
+
+        static_mesh.entries.clear();
+        static_mesh.entries.extend_from_slice(scratch.entries());
+        static_mesh.index_count = scratch.indices().len() as u32;
+
+        scratch.clear();
+    }
+
+    fn release_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle) {
+        let s = self.state_mut();
+        s.static_mesh_draws.retain(|(pending_mesh, _)| *pending_mesh != mesh);
+        super::free_static_mesh_slot(&mut s.static_meshes, mesh);
+    }
+
+    fn draw_static_sprite_mesh(&mut self, mesh: StaticSpriteMeshHandle, transform: WorldToScreenTransform) {
+        let s = self.state_mut();
+        debug_assert!(s.frame_started);
+        debug_assert!(s.static_mesh_draws.iter().all(|(pending_mesh, _)| *pending_mesh != mesh));
+
+        s.static_mesh_draws.push((mesh, transform));
+    }
+
+    // ----------------------
+    // Post-processing:
+    // ----------------------
+
+    fn set_color_grading(&mut self, color_grading: Option<super::ColorGrading>) {
+        self.state_mut().color_grading = color_grading;
+    }
+
+    // ----------------------
+    // Frame capture:
+    // ----------------------
+
+    fn capture_frame(&mut self) -> Option<super::CapturedFrame> {
+        let s = self.state();
+        debug_assert!(!s.frame_started);
+
+        // Mapped buffers only resolve asynchronously on WebGPU; we cannot wait for them here.
+        if cfg!(feature = "web") {
+            log::error!(log::channel!("render"), "Frame capture is not supported on Web/WASM.");
+            return None;
+        }
+
+        let swap_red_blue = match s.surface_format {
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            format => {
+                log::error!(log::channel!("render"), "Frame capture unsupported for surface format {format:?}.");
+                return None;
+            }
+        };
+
+        let size = s.offscreen_render_target.size();
+        let (width, height) = (size.width as u32, size.height as u32);
+
+        // Buffer rows must be padded to COPY_BYTES_PER_ROW_ALIGNMENT.
+        let row_size = width * 4;
+        let padded_row_size = row_size.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let readback_buffer = s.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("capture_frame_buffer"),
+            size: (padded_row_size * height) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder =
+            s.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("capture_frame_encoder") });
+
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: s.offscreen_render_target.texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback_buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_size),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+
+        s.queue.submit(Some(encoder.finish()));
+
+        let buffer_slice = readback_buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+
+        if let Err(err) = s.device.poll(wgpu::PollType::wait_indefinitely()) {
+            log::error!(log::channel!("render"), "Frame capture GPU wait failed: {err}");
+            return None;
+        }
+
+        match receiver.try_recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                log::error!(log::channel!("render"), "Failed to map frame capture buffer: {err}");
+                return None;
+            }
+            Err(_) => {
+                log::error!(log::channel!("render"), "Frame capture buffer was not mapped.");
+                return None;
+            }
+        }
+
+        let mut pixels = Vec::with_capacity((row_size * height) as usize);
+        {
+            let mapped = buffer_slice.get_mapped_range();
+            for row in mapped.chunks_exact(padded_row_size as usize) {
+                pixels.extend_from_slice(&row[..row_size as usize]);
+            }
+        }
+        readback_buffer.unmap();
+
+        if swap_red_blue {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Some(super::CapturedFrame { size, pixels })
+    }
+
     // ----------------------
     // Debug drawing:
     // ----------------------
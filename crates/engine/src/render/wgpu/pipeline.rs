@@ -9,7 +9,7 @@ pub fn create_uniform_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGrou
         label: Some("uniform_bind_group_layout"),
         entries: &[wgpu::BindGroupLayoutEntry {
             binding: 0,
-            visibility: wgpu::ShaderStages::VERTEX,
+            visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
             ty: wgpu::BindingType::Buffer {
                 ty: wgpu::BufferBindingType::Uniform,
                 has_dynamic_offset: false,
@@ -301,3 +301,131 @@ pub fn create_blit_pipeline(
         cache: None,
     })
 }
+
+// Same fullscreen blit, but passing the colors through a color grading lookup table.
+pub fn create_color_grade_pipeline(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    texture_layout: &wgpu::BindGroupLayout,
+    lut_texture_layout: &wgpu::BindGroupLayout,
+    uniform_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("color_grade_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/blit.wgsl").into()),
+    });
+
+    let layout =
+        create_pipeline_layout(device, "color_grade_pipeline_layout", &[texture_layout, lut_texture_layout, uniform_layout]);
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("color_grade_pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[], // No vertex buffer; generated from vertex_index.
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_color_grade"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: target_format,
+                blend: None, // Blit pass, no blending.
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleList, ..Default::default() },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+// ----------------------------------------------
+// Selection Outline Pipelines
+// ----------------------------------------------
+
+// Renders selected sprites as flat colored silhouettes into the outline mask render target.
+pub fn create_outline_mask_pipeline(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    uniform_layout: &wgpu::BindGroupLayout,
+    texture_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("outline_mask_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/sprites.wgsl").into()),
+    });
+
+    let layout = create_pipeline_layout(device, "outline_mask_pipeline_layout", &[uniform_layout, texture_layout]);
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("outline_mask_pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[vertex::SpriteVertex2D::LAYOUT],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_outline_mask"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: target_format,
+                blend: None, // Transparent texels are discarded, so later sprites just overwrite.
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleList, ..Default::default() },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    })
+}
+
+// Fullscreen pass that traces the outline mask silhouettes over the offscreen render target.
+pub fn create_outline_pipeline(
+    device: &wgpu::Device,
+    target_format: wgpu::TextureFormat,
+    texture_layout: &wgpu::BindGroupLayout,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("outline_shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("shaders/outline.wgsl").into()),
+    });
+
+    let layout = create_pipeline_layout(device, "outline_pipeline_layout", &[texture_layout]);
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("outline_pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[], // No vertex buffer; generated from vertex_index.
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: target_format,
+                blend: Some(alpha_blend_state()),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState { topology: wgpu::PrimitiveTopology::TriangleList, ..Default::default() },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview_mask: None,
+        cache: None,
+    })
+}
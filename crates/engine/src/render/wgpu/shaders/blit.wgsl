@@ -4,6 +4,20 @@
 @group(0) @binding(0) var src_texture: texture_2d<f32>;
 @group(0) @binding(1) var src_sampler: sampler;
 
+// Color grading only (fs_color_grade).
+// The LUT is a strip of N slices of NxN texels: N*N wide, N tall.
+@group(1) @binding(0) var lut_texture: texture_2d<f32>;
+@group(1) @binding(1) var lut_sampler: sampler;
+
+struct Uniforms {
+    viewport_size:          vec2f,
+    world_offset:           vec2f,
+    world_scaling:          f32,
+    color_grading_strength: f32,
+}
+
+@group(2) @binding(0) var<uniform> uniforms: Uniforms;
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4f,
     @location(0)       tex_coords:    vec2f,
@@ -29,3 +43,31 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4f {
     return textureSample(src_texture, src_sampler, in.tex_coords);
 }
+
+fn sample_lut_slice(red_green: vec2f, blue_slice: f32, lut_size: f32) -> vec3f {
+    // Offset by half a texel so linear filtering never bleeds into the neighboring slice.
+    let uv = vec2f(
+        (blue_slice * lut_size + red_green.x + 0.5) / (lut_size * lut_size),
+        (red_green.y + 0.5) / lut_size,
+    );
+    return textureSampleLevel(lut_texture, lut_sampler, uv, 0.0).rgb;
+}
+
+@fragment
+fn fs_color_grade(in: VertexOutput) -> @location(0) vec4f {
+    let color = textureSample(src_texture, src_sampler, in.tex_coords);
+
+    let lut_size = f32(textureDimensions(lut_texture).y);
+    let scaled = clamp(color.rgb, vec3f(0.0), vec3f(1.0)) * (lut_size - 1.0);
+
+    // Blue selects the slice; interpolate manually between the two nearest slices.
+    let blue_slice_0 = floor(scaled.b);
+    let blue_slice_1 = min(blue_slice_0 + 1.0, lut_size - 1.0);
+    let graded = mix(
+        sample_lut_slice(scaled.rg, blue_slice_0, lut_size),
+        sample_lut_slice(scaled.rg, blue_slice_1, lut_size),
+        scaled.b - blue_slice_0,
+    );
+
+    return vec4f(mix(color.rgb, graded, uniforms.color_grading_strength), color.a);
+}
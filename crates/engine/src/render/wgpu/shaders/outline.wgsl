@@ -0,0 +1,53 @@
+// Selection outline shader: fullscreen triangle that traces a colored outline
+// around the silhouettes rendered to the outline mask render target.
+// The mask holds the outline color where a selected sprite is opaque and is
+// fully transparent elsewhere. Pixels inside a silhouette are left untouched.
+
+const OUTLINE_THICKNESS: i32 = 2; // In render target pixels.
+
+@group(0) @binding(0) var mask_texture: texture_2d<f32>;
+@group(0) @binding(1) var mask_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4f,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Same fullscreen triangle as the blit shader.
+    var out: VertexOutput;
+    let x = f32(i32(vertex_index  & 1u)) * 4.0 - 1.0;
+    let y = f32(i32(vertex_index >> 1u)) * 4.0 - 1.0;
+    out.clip_position = vec4f(x, y, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4f {
+    // Mask and destination render targets have the same size, so the
+    // fragment position addresses the mask texel directly.
+    let mask_size = vec2i(textureDimensions(mask_texture));
+    let center = vec2i(in.clip_position.xy);
+
+    if textureLoad(mask_texture, center, 0).a > 0.0 {
+        return vec4f(0.0);
+    }
+
+    // Pick the strongest mask texel within a circle of OUTLINE_THICKNESS radius.
+    var outline = vec4f(0.0);
+    for (var y = -OUTLINE_THICKNESS; y <= OUTLINE_THICKNESS; y++) {
+        for (var x = -OUTLINE_THICKNESS; x <= OUTLINE_THICKNESS; x++) {
+            if (x * x) + (y * y) > (OUTLINE_THICKNESS * OUTLINE_THICKNESS) {
+                continue;
+            }
+
+            let coords = clamp(center + vec2i(x, y), vec2i(0), mask_size - vec2i(1));
+            let mask = textureLoad(mask_texture, coords, 0);
+            if mask.a > outline.a {
+                outline = mask;
+            }
+        }
+    }
+
+    return outline;
+}
@@ -1,8 +1,13 @@
 // Sprites shader: textured + tinted quads.
-// Vertex color carries the per-sprite tint.
+// Vertex color carries the per-sprite tint and the palette
+// colors carry the per-sprite palette swap (see PaletteSwap).
 
 struct Uniforms {
     viewport_size: vec2f,
+    // World-to-screen transform. Identity (scaling=1, offset=0) for sprites
+    // submitted in screen space; set per static sprite mesh otherwise.
+    world_offset:  vec2f,
+    world_scaling: f32,
 }
 
 @group(0) @binding(0) var<uniform> uniforms: Uniforms;
@@ -10,17 +15,24 @@ struct Uniforms {
 @group(1) @binding(1) var sprite_sampler: sampler;
 
 struct VertexInput {
-    @location(0) position:   vec2f,
-    @location(1) tex_coords: vec2f,
-    @location(2) color:      vec4f,
+    @location(0) position:          vec2f,
+    @location(1) tex_coords:        vec2f,
+    @location(2) color:             vec4f,
+    @location(3) palette_primary:   vec4f,
+    @location(4) palette_secondary: vec4f,
 }
 
 struct VertexOutput {
-    @builtin(position) clip_position: vec4f,
-    @location(0)       tex_coords:    vec2f,
-    @location(1)       color:         vec4f,
+    @builtin(position) clip_position:     vec4f,
+    @location(0)       tex_coords:        vec2f,
+    @location(1)       color:             vec4f,
+    @location(2)       palette_primary:   vec4f,
+    @location(3)       palette_secondary: vec4f,
 }
 
+// Max deviation from an exact key color shade, to tolerate compression/filtering.
+const PALETTE_KEY_TOLERANCE: f32 = 0.02;
+
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -28,18 +40,59 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     // Flip V coordinate (image origin is top-left, texture origin is bottom-left).
     out.tex_coords = vec2f(in.tex_coords.x, 1.0 - in.tex_coords.y);
     out.color = in.color;
+    out.palette_primary = in.palette_primary;
+    out.palette_secondary = in.palette_secondary;
+
+    let screen_position = (in.position * uniforms.world_scaling) + uniforms.world_offset;
 
     // Screen-space to NDC. Origin: top-left corner.
     let ndc = vec2f(
-        (in.position.x / uniforms.viewport_size.x) * 2.0 - 1.0,
-        1.0 - (in.position.y / uniforms.viewport_size.y) * 2.0,
+        (screen_position.x / uniforms.viewport_size.x) * 2.0 - 1.0,
+        1.0 - (screen_position.y / uniforms.viewport_size.y) * 2.0,
     );
     out.clip_position = vec4f(ndc, 0.0, 1.0);
 
     return out;
 }
 
+// Recolors shades of pure magenta with the primary palette color
+// and shades of pure cyan with the secondary one.
+fn apply_palette_swap(texel: vec4f, primary: vec4f, secondary: vec4f) -> vec4f {
+    var rgb = texel.rgb;
+
+    let is_primary_key = abs(texel.r - texel.b) <= PALETTE_KEY_TOLERANCE
+        && texel.g <= PALETTE_KEY_TOLERANCE
+        && texel.r > PALETTE_KEY_TOLERANCE;
+    if is_primary_key {
+        rgb = mix(rgb, primary.rgb * texel.r, primary.a);
+    }
+
+    let is_secondary_key = abs(texel.g - texel.b) <= PALETTE_KEY_TOLERANCE
+        && texel.r <= PALETTE_KEY_TOLERANCE
+        && texel.g > PALETTE_KEY_TOLERANCE;
+    if is_secondary_key {
+        rgb = mix(rgb, secondary.rgb * texel.g, secondary.a);
+    }
+
+    return vec4f(rgb, texel.a);
+}
+
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4f {
-    return textureSample(sprite_texture, sprite_sampler, in.tex_coords) * in.color;
+    var texel = textureSample(sprite_texture, sprite_sampler, in.tex_coords);
+    if in.palette_primary.a > 0.0 || in.palette_secondary.a > 0.0 {
+        texel = apply_palette_swap(texel, in.palette_primary, in.palette_secondary);
+    }
+    return texel * in.color;
+}
+
+// Selection outline mask: flat vertex color wherever the sprite is opaque enough.
+// Only the silhouette matters here, so the texture color is ignored.
+@fragment
+fn fs_outline_mask(in: VertexOutput) -> @location(0) vec4f {
+    let alpha = textureSample(sprite_texture, sprite_sampler, in.tex_coords).a;
+    if alpha < 0.5 {
+        discard;
+    }
+    return in.color;
 }
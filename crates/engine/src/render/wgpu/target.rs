@@ -30,7 +30,10 @@ impl RenderTarget {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            // COPY_SRC for frame capture readbacks.
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
 
@@ -60,6 +63,11 @@ impl RenderTarget {
         self.size
     }
 
+    #[inline]
+    pub fn texture(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+
     #[inline]
     pub fn view(&self) -> &wgpu::TextureView {
         &self.view
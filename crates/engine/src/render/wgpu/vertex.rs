@@ -1,5 +1,5 @@
 use common::{Color, Vec2};
-use crate::render;
+use crate::render::{self, PaletteSwap};
 
 // ----------------------------------------------
 // Compile time index size to wgpu::IndexFormat
@@ -17,13 +17,16 @@ pub const fn size_to_index_format<T>() -> wgpu::IndexFormat {
 // Sprite Vertex
 // ----------------------------------------------
 
-// Sprite vertex: position + tex_coords + color (tint baked per-vertex).
+// Sprite vertex: position + tex_coords + color (tint baked per-vertex)
+// + palette swap channel colors (u8x4 normalized, all zeros = no swap).
 #[repr(C)]
 #[derive(Copy, Clone, Default, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct SpriteVertex2D {
-    pub position:   [f32; 2],
-    pub tex_coords: [f32; 2],
-    pub color:      [f32; 4],
+    pub position:          [f32; 2],
+    pub tex_coords:        [f32; 2],
+    pub color:             [f32; 4],
+    pub palette_primary:   [u8; 4],
+    pub palette_secondary: [u8; 4],
 }
 
 pub type SpriteIndex2D = u16;
@@ -36,19 +39,33 @@ impl SpriteVertex2D {
             0 => Float32x2, // position
             1 => Float32x2, // tex_coords
             2 => Float32x4, // color
+            3 => Unorm8x4,  // palette_primary
+            4 => Unorm8x4,  // palette_secondary
         ],
     };
 
     #[inline]
     pub fn new(position: Vec2, tex_coords: Vec2, color: Color) -> Self {
+        Self::with_palette(position, tex_coords, color, PaletteSwap::NONE)
+    }
+
+    #[inline]
+    pub fn with_palette(position: Vec2, tex_coords: Vec2, color: Color, palette: PaletteSwap) -> Self {
         Self {
-            position:   [position.x, position.y],
-            tex_coords: [tex_coords.x, tex_coords.y],
-            color:      [color.r, color.g, color.b, color.a],
+            position:          [position.x, position.y],
+            tex_coords:        [tex_coords.x, tex_coords.y],
+            color:             [color.r, color.g, color.b, color.a],
+            palette_primary:   color_to_unorm8(palette.primary),
+            palette_secondary: color_to_unorm8(palette.secondary),
         }
     }
 }
 
+#[inline]
+fn color_to_unorm8(color: Color) -> [u8; 4] {
+    color.clamp().to_array().map(|channel| (channel * 255.0).round() as u8)
+}
+
 // ----------------------------------------------
 // Line Vertex
 // ----------------------------------------------
@@ -10,6 +10,9 @@ use crate::{
     render::{RenderApi, RenderSystem, RenderSystemInitParams},
 };
 
+// Runs the engine with the Null app, render and sound backends (no window, no audio).
+const HEADLESS_ARG: &str = "--headless";
+
 // ----------------------------------------------
 // DesktopRunner
 // ----------------------------------------------
@@ -50,11 +53,20 @@ impl DesktopRunner {
         log::info!(log::channel!("engine"), "Configs Loaded.");
 
         log::set_level(engine_configs.log_level);
+        log::register_file_sinks(&engine_configs.log_file_sinks);
 
-        let app_api = engine_configs.app_api;
+        let mut app_api = engine_configs.app_api;
         let mut render_api = engine_configs.render_api;
 
-        if app_api == ApplicationApi::Glfw && render_api != RenderApi::OpenGl {
+        // Headless runs have no window to render to, so the Null backends always go together.
+        let headless = std::env::args().any(|arg| arg == HEADLESS_ARG)
+            || app_api == ApplicationApi::Null
+            || render_api == RenderApi::Null;
+
+        if headless {
+            app_api = ApplicationApi::Null;
+            render_api = RenderApi::Null;
+        } else if app_api == ApplicationApi::Glfw && render_api != RenderApi::OpenGl {
             log::warning!(log::channel!("engine"), "Glfw is only compatible OpenGl. Setting render backend to OpenGl.");
             render_api = RenderApi::OpenGl;
         }
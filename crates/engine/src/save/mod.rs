@@ -37,6 +37,14 @@ pub trait SaveState: Any {
     fn write_file<P>(&self, path: P) -> io::Result<()>
     where
         P: AsRef<Path>;
+
+    // Serializes the instance straight into the file, bypassing the
+    // in-memory buffer. Avoids holding the whole save document in memory.
+    // Written to a temp file first, so a failed save never clobbers the previous one.
+    fn save_to_file<T, P>(&self, instance: &T, path: P) -> io::Result<()>
+    where
+        T: Serialize,
+        P: AsRef<Path>;
 }
 
 #[enum_dispatch]
@@ -124,6 +132,21 @@ impl SaveState for JsonSaveState {
     {
         file_sys::write_file(path, &self.buffer)
     }
+
+    fn save_to_file<T, P>(&self, instance: &T, path: P) -> io::Result<()>
+    where
+        T: Serialize,
+        P: AsRef<Path>,
+    {
+        file_sys::write_file_streamed(path, |writer| {
+            if self.pretty {
+                serde_json::to_writer_pretty(writer, instance)?;
+            } else {
+                serde_json::to_writer(writer, instance)?;
+            }
+            Ok(())
+        })
+    }
 }
 
 #[inline]
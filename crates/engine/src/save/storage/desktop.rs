@@ -65,13 +65,10 @@ impl SaveGameStorageBackend for FileSysSaveGameStorageBackend {
         // this function might fail if any element of the path already exists.
         let _ = file_sys::create_path(&absolute_path);
 
-        let mut state = new_json_save_state(true);
+        let state = new_json_save_state(true);
 
-        if let Err(err) = state.save(instance) {
-            return Err(format!("Failed to save game: {err}"));
-        }
-
-        if let Err(err) = state.write_file(&absolute_path) {
+        // Stream directly to disk; large maps would otherwise need the full JSON string in memory.
+        if let Err(err) = state.save_to_file(instance, &absolute_path) {
             return Err(format!("Failed to write save game file '{absolute_path}': {err}"));
         }
 
@@ -83,6 +80,16 @@ impl SaveGameStorageBackend for FileSysSaveGameStorageBackend {
 
         file_sys::remove_file(&absolute_path).map_err(|err| format!("Failed to delete save file '{absolute_path}': {err}"))
     }
+
+    fn copy_save_file(&self, src_file: PathRef, dest_file: PathRef) -> SaveResult {
+        let src_path = self.make_absolute_save_path(src_file);
+        let dest_path = self.make_absolute_save_path(dest_file);
+
+        // Plain file copy; saves always live on disk, never in the asset archive.
+        std::fs::copy(&src_path, &dest_path)
+            .map(|_| ())
+            .map_err(|err| format!("Failed to copy save file '{src_path}' to '{dest_path}': {err}"))
+    }
 }
 
 // ----------------------------------------------
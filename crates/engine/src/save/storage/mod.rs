@@ -46,6 +46,10 @@ trait SaveGameStorageBackend: Sized {
     // Deletes a named save file.
     // `save_file` is relative to save_files_path.
     fn delete_save_file(&self, save_file: PathRef) -> SaveResult;
+
+    // Copies a save file as-is, without deserializing it. Overwrites any existing `dest_file`.
+    // Both paths are relative to save_files_path.
+    fn copy_save_file(&self, src_file: PathRef, dest_file: PathRef) -> SaveResult;
 }
 
 // ----------------------------------------------
@@ -54,6 +58,8 @@ trait SaveGameStorageBackend: Sized {
 
 pub const AUTOSAVE_FILE_NAME:     PathRef = PathRef::from_str("autosave");
 pub const DEFAULT_SAVE_FILE_NAME: PathRef = PathRef::from_str("save_game");
+pub const QUICKSAVE_FILE_NAME:    PathRef = PathRef::from_str("quicksave");
+pub const CRASH_SAVE_FILE_NAME:   PathRef = PathRef::from_str("crash_save");
 
 #[inline]
 pub fn save_files_path() -> FixedPath {
@@ -93,3 +99,8 @@ where
 pub fn delete_save_file(save_file: PathRef) -> SaveResult {
     SaveGameStorageBackendImpl::get().delete_save_file(save_file)
 }
+
+#[inline]
+pub fn copy_save_file(src_file: PathRef, dest_file: PathRef) -> SaveResult {
+    SaveGameStorageBackendImpl::get().copy_save_file(src_file, dest_file)
+}
@@ -118,6 +118,19 @@ impl SaveGameStorageBackend for WebSaveGameStorageBackend {
             .remove_item(key.as_str())
             .map_err(|_| format!("Failed to delete save '{save_file}' from Browser Local Storage."))
     }
+
+    fn copy_save_file(&self, src_file: PathRef, dest_file: PathRef) -> SaveResult {
+        let storage = Self::browser_local_storage().ok_or_else(|| "Browser Local Storage not available.".to_string())?;
+
+        let save_data = storage
+            .get_item(self.make_save_key(src_file).as_str())
+            .map_err(|_| format!("Failed to read save '{src_file}' from Browser Local Storage."))?
+            .ok_or_else(|| format!("Save file '{src_file}' not found in Browser Local Storage."))?;
+
+        storage
+            .set_item(self.make_save_key(dest_file).as_str(), &save_data)
+            .map_err(|_| format!("Failed to write save '{dest_file}' to Browser Local Storage (quota exceeded?)"))
+    }
 }
 
 // ----------------------------------------------
@@ -0,0 +1,178 @@
+use serde::{Deserialize, Serialize};
+
+use common::hash::{self, PreHashedKeyMap, StringHash};
+
+use super::{SoundAssetRegistry, SoundKind, SoundSystem};
+use crate::{file_sys::paths::PathRef, log};
+
+// ----------------------------------------------
+// SoundBankDef
+// ----------------------------------------------
+
+// A group of sounds loaded and unloaded together, e.g. all sounds used by a game state.
+// Paths are relative to each sound kind's asset directory (e.g. "sounds/sfx/").
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundBankDef {
+    pub sfx: Vec<String>,
+    pub ambience: Vec<String>,
+    pub music: Vec<String>,
+    pub narration: Vec<String>,
+}
+
+impl SoundBankDef {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sfx.is_empty() && self.ambience.is_empty() && self.music.is_empty() && self.narration.is_empty()
+    }
+
+    fn for_each_sound<F>(&self, mut visitor_fn: F)
+    where
+        F: FnMut(SoundKind, PathRef),
+    {
+        let groups = [
+            (SoundKind::Sfx, &self.sfx),
+            (SoundKind::Ambience, &self.ambience),
+            (SoundKind::Music, &self.music),
+            (SoundKind::Narration, &self.narration),
+        ];
+
+        for (kind, paths) in groups {
+            for path in paths {
+                visitor_fn(kind, PathRef::from_str(path));
+            }
+        }
+    }
+}
+
+// ----------------------------------------------
+// SoundBank
+// ----------------------------------------------
+
+// A loaded SoundBankDef. Must be returned to `SoundSystem::unload_bank()`.
+pub struct SoundBank {
+    name: String,
+    sounds: Vec<(SoundKind, StringHash)>,
+}
+
+impl SoundBank {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn sounds_count(&self) -> usize {
+        self.sounds.len()
+    }
+}
+
+// ----------------------------------------------
+// SoundBankRefs
+// ----------------------------------------------
+
+// References held on each loaded sound, one per loaded bank that lists it plus one
+// per direct `SoundSystem::load_*()` call not yet matched by an `unload_*()`.
+// A sound is unloaded once its last reference is released.
+#[derive(Default)]
+pub(super) struct SoundBankRefs {
+    counts: PreHashedKeyMap<StringHash, u32>,
+}
+
+impl SoundBankRefs {
+    #[inline]
+    pub(super) fn add_ref(&mut self, key_hash: StringHash) {
+        debug_assert!(key_hash != hash::NULL_HASH);
+        *self.counts.entry(key_hash).or_insert(0) += 1;
+    }
+
+    // Returns true if that was the last reference, so the sound can be unloaded.
+    pub(super) fn release(&mut self, key_hash: StringHash) -> bool {
+        let Some(ref_count) = self.counts.get_mut(&key_hash) else {
+            return false;
+        };
+
+        debug_assert!(*ref_count != 0);
+        *ref_count -= 1;
+
+        if *ref_count == 0 {
+            self.counts.remove(&key_hash);
+            return true;
+        }
+
+        false
+    }
+}
+
+// ----------------------------------------------
+// SoundSystem bank loading
+// ----------------------------------------------
+
+impl SoundSystem {
+    // Loads every sound in the bank upfront so the first play doesn't hitch on file loading.
+    pub fn load_bank(&mut self, name: &str, bank_def: &SoundBankDef) -> SoundBank {
+        let mut bank = SoundBank { name: name.to_string(), sounds: Vec::new() };
+
+        if !self.is_initialized() {
+            return bank;
+        }
+
+        bank_def.for_each_sound(|kind, path| {
+            let key_hash = match kind {
+                SoundKind::Sfx => self.registry.load_sfx(path).hash,
+                SoundKind::Ambience => self.registry.load_ambience(path).hash,
+                SoundKind::Music => self.registry.load_music(path).hash,
+                SoundKind::Narration => self.registry.load_narration(path).hash,
+                SoundKind::SpatialAmbience => unreachable!("Spatial sounds are loaded as ambience!"),
+                SoundKind::Ui => unreachable!("UI sounds are loaded as SFX!"),
+            };
+
+            if key_hash == hash::NULL_HASH {
+                log::error!(log::channel!("sound"), "Sound bank '{name}': Failed to load '{path}'.");
+                return;
+            }
+
+            self.bank_refs.add_ref(key_hash);
+            bank.sounds.push((kind, key_hash));
+        });
+
+        log::info!(log::channel!("sound"), "Loaded sound bank '{name}' ({} sounds).", bank.sounds.len());
+        bank
+    }
+
+    // Unloads sounds no longer referenced by any loaded bank nor direct load. Instances
+    // that are currently playing are unaffected and will play until finished or stopped.
+    pub fn unload_bank(&mut self, bank: SoundBank) {
+        let mut unloaded_count = 0;
+
+        for (kind, key_hash) in bank.sounds {
+            if self.release_sound(kind, key_hash) {
+                unloaded_count += 1;
+            }
+        }
+
+        log::info!(log::channel!("sound"), "Unloaded sound bank '{}' ({unloaded_count} sounds freed).", bank.name);
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_reference_releases_sound() {
+        let mut refs = SoundBankRefs::default();
+        let key_hash = hash::fnv1a_from_str("birds_chirping.mp3");
+
+        refs.add_ref(key_hash); // Bank.
+        refs.add_ref(key_hash); // Direct load of the same sound.
+
+        assert!(!refs.release(key_hash), "direct load still holds the sound");
+        assert!(refs.release(key_hash), "last reference should free the sound");
+        assert!(!refs.release(key_hash), "sound is no longer tracked");
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use common::{
+    hash::{PreHashedKeyMap, StringHash},
+    time::{self, Instant, Seconds},
+};
+
+use super::{NarrationSoundKey, SoundHandle, SoundSystem};
+
+// ----------------------------------------------
+// CaptionLine
+// ----------------------------------------------
+
+// One line of text shown while a narration sound plays.
+// Times are in seconds from the start of the narration.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CaptionLine {
+    pub start_secs: Seconds,
+    pub end_secs: Seconds,
+    pub text: String,
+}
+
+// Line showing `elapsed_secs` into the narration, if any. First match wins on overlaps.
+fn caption_at(lines: &[CaptionLine], elapsed_secs: Seconds) -> Option<&str> {
+    lines
+        .iter()
+        .find(|line| elapsed_secs >= line.start_secs && elapsed_secs < line.end_secs)
+        .map(|line| line.text.as_str())
+}
+
+// ----------------------------------------------
+// NarrationCaptions
+// ----------------------------------------------
+
+struct PlayingCaptions {
+    key_hash: StringHash,
+    handle: SoundHandle,
+    started: Instant,
+}
+
+// Caption tracks by narration sound key, plus the narration currently captioned.
+#[derive(Default)]
+pub(super) struct NarrationCaptions {
+    tracks: PreHashedKeyMap<StringHash, Vec<CaptionLine>>,
+    playing: Option<PlayingCaptions>,
+}
+
+impl NarrationCaptions {
+    // Called for every narration played. Narrations without a track clear the current caption.
+    pub(super) fn on_narration_played(&mut self, sound_key: NarrationSoundKey, handle: SoundHandle) {
+        self.playing = (handle.is_valid() && self.tracks.contains_key(&sound_key.hash)).then(|| PlayingCaptions {
+            key_hash: sound_key.hash,
+            handle,
+            started: Instant::now(),
+        });
+    }
+}
+
+// ----------------------------------------------
+// SoundSystem narration captions
+// ----------------------------------------------
+
+impl SoundSystem {
+    // Attaches a caption track to a loaded narration sound, replacing any previous one.
+    pub fn set_narration_captions(&mut self, sound_key: NarrationSoundKey, lines: Vec<CaptionLine>) {
+        self.narration_captions.tracks.insert(sound_key.hash, lines);
+    }
+
+    pub fn clear_narration_captions(&mut self) {
+        self.narration_captions.tracks.clear();
+        self.narration_captions.playing = None;
+    }
+
+    #[inline]
+    pub fn has_narration_captions(&self, sound_key: NarrationSoundKey) -> bool {
+        self.narration_captions.tracks.contains_key(&sound_key.hash)
+    }
+
+    // Caption text for the narration playing right now, if it has a
+    // track and one of its lines is due. Text is returned as authored.
+    pub fn current_caption(&self) -> Option<&str> {
+        let playing = self.narration_captions.playing.as_ref()?;
+
+        if !self.is_playing(playing.handle) {
+            return None;
+        }
+
+        let lines = self.narration_captions.tracks.get(&playing.key_hash)?;
+        caption_at(lines, time::elapsed_seconds(Instant::now(), playing.started))
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caption_lookup_by_time() {
+        let line = |start_secs, end_secs, text: &str| CaptionLine { start_secs, end_secs, text: text.into() };
+        let lines = [line(0.5, 2.0, "first"), line(2.0, 4.0, "second"), line(5.0, 6.0, "third")];
+
+        assert_eq!(caption_at(&lines, 0.0), None);
+        assert_eq!(caption_at(&lines, 0.5), Some("first"));
+        assert_eq!(caption_at(&lines, 2.0), Some("second"));
+        assert_eq!(caption_at(&lines, 4.5), None); // Gap between lines.
+        assert_eq!(caption_at(&lines, 6.0), None);
+    }
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+use common::{
+    coords::{Cell, IsoPointF32},
+    hash::{self, PreHashedKeyMap, StringHash},
+    time::{self, Instant, Seconds},
+};
+
+use super::{SoundAssetRegistry, SoundHandle, SoundKey, SoundKind, SoundSystem};
+use crate::{file_sys::paths::PathRef, log};
+
+// ----------------------------------------------
+// SoundEventKey
+// ----------------------------------------------
+
+// Typed lookup key for a sound event, hashed from the event name in the manifest.
+// Can be declared as a constant, e.g.: `const ALERT: SoundEventKey = SoundEventKey::new("ui.alert");`
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct SoundEventKey {
+    hash: StringHash,
+}
+
+impl SoundEventKey {
+    #[inline]
+    pub const fn new(event_name: &str) -> Self {
+        Self { hash: hash::fnv1a_from_str(event_name) }
+    }
+}
+
+// ----------------------------------------------
+// SoundEventDef
+// ----------------------------------------------
+
+// A named sound effect, as declared in the sound events manifest.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SoundEventDef {
+    pub name: String,
+    // SFX paths relative to "sounds/sfx/". One is picked at random every time the event plays.
+    pub files: Vec<String>,
+    // [0,1] scale applied on top of the SFX bus volume.
+    #[serde(default = "default_event_volume")]
+    pub volume: f32,
+    // Plays of the event within this many seconds of the previous one are dropped.
+    #[serde(default)]
+    pub cooldown_secs: Seconds,
+}
+
+fn default_event_volume() -> f32 {
+    1.0
+}
+
+// ----------------------------------------------
+// SoundEvent
+// ----------------------------------------------
+
+struct SoundEvent {
+    name: String,
+    files: Vec<String>, // Only the files that loaded successfully.
+    volume: f32,
+    cooldown_secs: Seconds,
+    last_file: Option<usize>,
+    last_played: Option<Instant>,
+}
+
+impl SoundEvent {
+    // Picks the file to play, or None if the event is still cooling down.
+    fn trigger(&mut self, now: Instant, rng: &mut EventRng) -> Option<usize> {
+        if self.files.is_empty() {
+            return None;
+        }
+
+        if let Some(last_played) = self.last_played
+            && time::elapsed_seconds(now, last_played) < self.cooldown_secs
+        {
+            return None;
+        }
+
+        // Random variation, never the same one twice in a row.
+        let mut index = rng.next_index(self.files.len());
+        if self.files.len() > 1 && Some(index) == self.last_file {
+            index = (index + 1) % self.files.len();
+        }
+
+        self.last_file = Some(index);
+        self.last_played = Some(now);
+        Some(index)
+    }
+}
+
+// Tiny xorshift generator for picking variations. Audio-only, so it
+// doesn't need to be seeded or share the simulation's random generator.
+struct EventRng(u64);
+
+impl EventRng {
+    fn next_index(&mut self, len: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % len as u64) as usize
+    }
+}
+
+impl Default for EventRng {
+    fn default() -> Self {
+        Self(0x9E37_79B9_7F4A_7C15)
+    }
+}
+
+// ----------------------------------------------
+// SoundEvents
+// ----------------------------------------------
+
+// Sound events registered from the manifest, by name hash.
+#[derive(Default)]
+pub(super) struct SoundEvents {
+    events: PreHashedKeyMap<StringHash, SoundEvent>,
+    rng: EventRng,
+}
+
+// ----------------------------------------------
+// SoundSystem sound events
+// ----------------------------------------------
+
+impl SoundSystem {
+    // Replaces the registered sound events. Every file is loaded upfront and
+    // missing ones are dropped, so errors are only logged once here.
+    pub fn load_sound_events(&mut self, event_defs: &[SoundEventDef]) {
+        self.sound_events.events.clear();
+
+        if !self.is_initialized() {
+            return;
+        }
+
+        for def in event_defs {
+            let files: Vec<String> = def
+                .files
+                .iter()
+                .filter(|path| self.registry.load_sfx(PathRef::from_str(path)).is_valid())
+                .cloned()
+                .collect();
+
+            if files.len() != def.files.len() {
+                log::error!(log::channel!("sound"), "Sound event '{}': Failed to load some of its files.", def.name);
+            }
+
+            let key = SoundEventKey::new(&def.name);
+            let event = SoundEvent {
+                name: def.name.clone(),
+                files,
+                volume: def.volume.clamp(0.0, 1.0),
+                cooldown_secs: def.cooldown_secs,
+                last_file: None,
+                last_played: None,
+            };
+
+            if self.sound_events.events.insert(key.hash, event).is_some() {
+                log::error!(log::channel!("sound"), "Duplicate sound event '{}'!", def.name);
+            }
+        }
+
+        log::info!(log::channel!("sound"), "Loaded {} sound events.", self.sound_events.events.len());
+    }
+
+    #[inline]
+    pub fn has_sound_event(&self, key: SoundEventKey) -> bool {
+        self.sound_events.events.contains_key(&key.hash)
+    }
+
+    // Names of all registered sound events, in no particular order.
+    pub fn sound_event_names(&self) -> impl Iterator<Item = &str> {
+        self.sound_events.events.values().map(|event| event.name.as_str())
+    }
+
+    // Plays one of the event's variations as a regular SFX.
+    // Returns an invalid handle if the event is unknown or cooling down.
+    pub fn play_event(&mut self, key: SoundEventKey, looping: bool) -> SoundHandle {
+        self.play_event_backend(key, SoundKind::Sfx, IsoPointF32::default(), looping)
+    }
+
+    // Positional version of `play_event`. See `play_sfx_at_cell`.
+    pub fn play_event_at_cell(&mut self, key: SoundEventKey, cell: Cell, looping: bool) -> SoundHandle {
+        self.play_event_backend(key, SoundKind::SpatialAmbience, super::cell_world_position(cell), looping)
+    }
+
+    fn play_event_backend(
+        &mut self,
+        key: SoundEventKey,
+        kind: SoundKind,
+        position: IsoPointF32,
+        looping: bool,
+    ) -> SoundHandle {
+        let sound_events = &mut self.sound_events;
+        let Some(event) = sound_events.events.get_mut(&key.hash) else {
+            return SoundHandle::invalid(kind);
+        };
+
+        let Some(file_index) = event.trigger(Instant::now(), &mut sound_events.rng) else {
+            return SoundHandle::invalid(kind);
+        };
+
+        // Reloads the file if the registry was unloaded since the events were registered.
+        let sound_key = self.registry.load_sfx(PathRef::from_str(&event.files[file_index]));
+        let volume = event.volume;
+
+        self.play_backend(kind, sound_key.hash, position, looping, volume)
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_cooldown_and_variations() {
+        let mut event = SoundEvent {
+            name: "test".into(),
+            files: vec!["a.ogg".into(), "b.ogg".into()],
+            volume: 1.0,
+            cooldown_secs: 1.0,
+            last_file: None,
+            last_played: None,
+        };
+
+        let mut rng = EventRng::default();
+        let start = Instant::now();
+
+        let first = event.trigger(start, &mut rng).unwrap();
+        assert!(event.trigger(start, &mut rng).is_none()); // Cooling down.
+
+        let later = start + time::Duration::from_secs(2);
+        let second = event.trigger(later, &mut rng).unwrap();
+        assert_ne!(first, second); // No repeats with more than one variation.
+    }
+}
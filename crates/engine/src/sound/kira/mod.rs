@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, time::Duration};
+use std::{io::Cursor, marker::PhantomData, time::Duration};
 
 use common::{
     coords::IsoPointF32,
@@ -6,9 +6,11 @@ use common::{
     mem::RcMut,
     time::Seconds,
 };
+use cpal::traits::{DeviceTrait, HostTrait};
 use kira::{
     AudioManager,
     AudioManagerSettings,
+    Frame,
     Tween,
     backend::DefaultBackend,
     sound::{
@@ -34,7 +36,10 @@ use super::{
     SoundKind,
 };
 use crate::{
-    file_sys::paths::{self, AssetPath, PathRef},
+    file_sys::{
+        self,
+        paths::{self, AssetPath, PathRef},
+    },
     log,
 };
 
@@ -51,23 +56,24 @@ pub(super) struct KiraSoundSystemBackend {
     spatial: SpatialController, // NOTE: Spatial sounds share the ambience track.
     music: MusicController,
     narration: NarrationController,
+    ui: SfxController, // NOTE: UI sounds share the SFX registry but have their own track.
+
+    // Last music track played, so it can be resumed on another output device.
+    music_playback: Option<MusicPlayback>,
 
     // Debug:
     listener_position: IsoPointF32,
 }
 
-impl super::SoundSystemBackend for KiraSoundSystemBackend {
-    type Registry = KiraSoundAssetRegistry;
-
-    fn new() -> Option<Box<Self>> {
-        let mut manager = match AudioManager::<DefaultBackend>::new(AudioManagerSettings::default()) {
-            Ok(manager) => manager,
-            Err(err) => {
-                log::error!(log::channel!("sound"), "Failed to initialize Kira AudioManager: {err}");
-                return None;
-            }
-        };
+#[derive(Copy, Clone)]
+struct MusicPlayback {
+    handle: SoundHandle,
+    key_hash: StringHash,
+    looping: bool,
+}
 
+impl KiraSoundSystemBackend {
+    fn with_manager(mut manager: AudioManager) -> Option<Box<Self>> {
         let sfx_track = match manager.add_sub_track(TrackBuilder::default()) {
             Ok(track) => track,
             Err(err) => {
@@ -100,6 +106,14 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
             }
         };
 
+        let ui_track = match manager.add_sub_track(TrackBuilder::default()) {
+            Ok(track) => track,
+            Err(err) => {
+                log::error!(log::channel!("sound"), "Failed to create Kira Sub-Track for UI: {err}");
+                return None;
+            }
+        };
+
         // NOTE: Ambience Track is shared between AmbienceController and SpatialController.
         let ambience_track_rc = RcMut::new(ambience_track);
 
@@ -108,6 +122,7 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
         let spatial = SpatialController::new(ambience_track_rc, SoundKind::SpatialAmbience);
         let music = MusicController::new(music_track, SoundKind::Music);
         let narration = NarrationController::new(narration_track, SoundKind::Narration);
+        let ui = SfxController::new(ui_track, SoundKind::Ui);
 
         Some(Box::new(Self {
             manager,
@@ -116,9 +131,71 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
             spatial,
             music,
             narration,
+            ui,
+            music_playback: None,
             listener_position: IsoPointF32::default(),
         }))
     }
+}
+
+impl super::SoundSystemBackend for KiraSoundSystemBackend {
+    type Registry = KiraSoundAssetRegistry;
+
+    fn new(output_device: Option<&str>) -> Option<Box<Self>> {
+        Self::with_manager(create_audio_manager(output_device)?)
+    }
+
+    fn output_devices() -> Vec<String> {
+        match cpal::default_host().output_devices() {
+            Ok(devices) => devices.filter_map(|device| device.name().ok()).collect(),
+            Err(err) => {
+                log::error!(log::channel!("sound"), "Failed to enumerate audio output devices: {err}");
+                Vec::new()
+            }
+        }
+    }
+
+    fn set_output_device(
+        &mut self,
+        output_device: Option<&str>,
+        registry: &KiraSoundAssetRegistry,
+        settings: &SoundGlobalSettings,
+    ) -> bool {
+        let Some(mut new_backend) = create_audio_manager(output_device).and_then(Self::with_manager) else {
+            return false;
+        };
+
+        // Keep handing out handles past the ones given by this backend, so stale
+        // handles held by the game don't alias sounds played on the new device.
+        new_backend.sfx.generation = self.sfx.generation;
+        new_backend.ambience.generation = self.ambience.generation;
+        new_backend.spatial.generation = self.spatial.generation;
+        new_backend.music.generation = self.music.generation;
+        new_backend.narration.generation = self.narration.generation;
+        new_backend.ui.generation = self.ui.generation;
+
+        // Resume music from the same position, under the same handle.
+        if let Some(playback) = self.music_playback
+            && let Some(position_secs) = self.music.try_get_sound(playback.handle).map(|music| music.handle.position())
+            && let Some(sound) = registry.music.get(&playback.key_hash)
+        {
+            let volume = settings.master_volume(SoundKind::Music);
+            let fade_in = settings.fade_in_secs(SoundKind::Music);
+
+            let music_track = new_backend.music.track.as_handle_mut();
+            if let Some(handle) = sound.play_from(music_track, volume, fade_in, playback.looping, position_secs) {
+                let generation = playback.handle.generation;
+                new_backend.music.restore(playback.handle, StreamedSoundInstance { handle, generation });
+                new_backend.music_playback = Some(playback);
+            }
+        }
+
+        new_backend.listener_position = self.listener_position;
+
+        // Dropping the previous AudioManager stops everything still playing on the old device.
+        *self = *new_backend;
+        true
+    }
 
     fn update(&mut self, listener_position: IsoPointF32, settings: &SoundGlobalSettings) {
         self.listener_position = listener_position;
@@ -127,6 +204,7 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
         self.spatial.update(listener_position, settings);
         self.music.update(listener_position, settings);
         self.narration.update(listener_position, settings);
+        self.ui.update(listener_position, settings);
     }
 
     fn set_volumes(&mut self, settings: &SoundGlobalSettings) {
@@ -135,6 +213,7 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
         self.spatial.set_volume(settings.master_volume(SoundKind::SpatialAmbience));
         self.music.set_volume(settings.master_volume(SoundKind::Music));
         self.narration.set_volume(settings.master_volume(SoundKind::Narration));
+        self.ui.set_volume(settings.master_volume(SoundKind::Ui));
     }
 
     fn listener_position(&self) -> IsoPointF32 {
@@ -147,10 +226,15 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
             + self.spatial.playing_count()
             + self.music.playing_count()
             + self.narration.playing_count()
+            + self.ui.playing_count()
+    }
+
+    fn is_narration_playing(&self) -> bool {
+        self.narration.playing_count() != 0
     }
 
     fn play(&mut self, params: PlaySoundParams<KiraSoundAssetRegistry>) -> SoundHandle {
-        let volume = params.settings.master_volume(params.kind);
+        let volume = params.settings.master_volume(params.kind) * params.volume;
         let fade_in = params.settings.fade_in_secs(params.kind);
         let fade_out = params.settings.fade_out_secs(params.kind);
 
@@ -166,14 +250,18 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
                 }
             }
             SoundKind::SpatialAmbience => {
-                // NOTE: Spatial sounds share the ambience registry.
-                if let Some(sound) = params.registry.ambience.get(&params.key_hash) {
+                // NOTE: Spatial sounds share the ambience registry. Positional SFX come from the SFX registry.
+                let sound = params.registry.ambience.get(&params.key_hash);
+                let sound = sound.or_else(|| params.registry.sfx.get(&params.key_hash));
+                if let Some(sound) = sound {
                     return self.spatial.play(sound, params.position, volume, fade_in, fade_out, params.looping);
                 }
             }
             SoundKind::Music => {
                 if let Some(sound) = params.registry.music.get(&params.key_hash) {
-                    return self.music.play(sound, params.position, volume, fade_in, fade_out, params.looping);
+                    let handle = self.music.play(sound, params.position, volume, fade_in, fade_out, params.looping);
+                    self.music_playback = Some(MusicPlayback { handle, key_hash: params.key_hash, looping: params.looping });
+                    return handle;
                 }
             }
             SoundKind::Narration => {
@@ -181,6 +269,12 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
                     return self.narration.play(sound, params.position, volume, fade_in, fade_out, params.looping);
                 }
             }
+            SoundKind::Ui => {
+                // NOTE: UI sounds share the SFX registry.
+                if let Some(sound) = params.registry.sfx.get(&params.key_hash) {
+                    return self.ui.play(sound, params.position, volume, fade_in, fade_out, params.looping);
+                }
+            }
         }
 
         SoundHandle::invalid(params.kind)
@@ -199,6 +293,7 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
             SoundKind::SpatialAmbience => self.spatial.stop(sound_handle, fade_out),
             SoundKind::Music           => self.music.stop(sound_handle, fade_out),
             SoundKind::Narration       => self.narration.stop(sound_handle, fade_out),
+            SoundKind::Ui              => self.ui.stop(sound_handle, fade_out),
         }
     }
 
@@ -209,6 +304,7 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
             SoundKind::SpatialAmbience => self.spatial.stop_all(fade_out),
             SoundKind::Music           => self.music.stop_all(fade_out),
             SoundKind::Narration       => self.narration.stop_all(fade_out),
+            SoundKind::Ui              => self.ui.stop_all(fade_out),
         }
     }
 
@@ -218,6 +314,7 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
         self.spatial.stop_all(settings.fade_out_secs(SoundKind::SpatialAmbience));
         self.music.stop_all(settings.fade_out_secs(SoundKind::Music));
         self.narration.stop_all(settings.fade_out_secs(SoundKind::Narration));
+        self.ui.stop_all(settings.fade_out_secs(SoundKind::Ui));
     }
 
     fn is_playing(&self, sound_handle: SoundHandle) -> bool {
@@ -227,10 +324,39 @@ impl super::SoundSystemBackend for KiraSoundSystemBackend {
             SoundKind::SpatialAmbience => self.spatial.is_playing(sound_handle),
             SoundKind::Music           => self.music.is_playing(sound_handle),
             SoundKind::Narration       => self.narration.is_playing(sound_handle),
+            SoundKind::Ui              => self.ui.is_playing(sound_handle),
+        }
+    }
+}
+
+// ----------------------------------------------
+// Audio output devices
+// ----------------------------------------------
+
+fn create_audio_manager(output_device: Option<&str>) -> Option<AudioManager> {
+    let mut settings = AudioManagerSettings::<DefaultBackend>::default();
+
+    if let Some(device_name) = output_device {
+        match find_output_device(device_name) {
+            Some(device) => settings.backend_settings.device = Some(device),
+            None => log::warning!(log::channel!("sound"), "Audio output device '{device_name}' not found. Using default."),
+        }
+    }
+
+    match AudioManager::<DefaultBackend>::new(settings) {
+        Ok(manager) => Some(manager),
+        Err(err) => {
+            log::error!(log::channel!("sound"), "Failed to initialize Kira AudioManager: {err}");
+            None
         }
     }
 }
 
+fn find_output_device(device_name: &str) -> Option<cpal::Device> {
+    let mut devices = cpal::default_host().output_devices().ok()?;
+    devices.find(|device| device.name().is_ok_and(|name| name == device_name))
+}
+
 // ----------------------------------------------
 // SoundAsset/StaticSoundAsset/StreamedSoundAsset
 // ----------------------------------------------
@@ -302,10 +428,33 @@ impl SoundAsset for StreamedSoundAsset {
         fade_in_secs: Seconds,
         looping: bool,
     ) -> Option<Self::BackendSoundHandle> {
+        self.play_from(track, volume, fade_in_secs, looping, 0.0)
+    }
+}
+
+impl StreamedSoundAsset {
+    // Starts playback `start_position_secs` into the sound.
+    fn play_from(
+        &self,
+        track: &mut TrackHandle,
+        volume: f32,
+        fade_in_secs: Seconds,
+        looping: bool,
+        start_position_secs: f64,
+    ) -> Option<StreamingSoundHandle<FromFileError>> {
         debug_assert!(!self.path.is_empty());
 
-        let sound_data = match StreamingSoundData::from_file(&self.path) {
-            Ok(sound_data) => sound_data.volume(super::linear_to_decibels(volume)),
+        // Archived sounds can't be streamed from disk; decode from an in-memory copy instead.
+        let sound_data = if file_sys::is_archived(&self.path) {
+            file_sys::load_bytes(&self.path)
+                .map_err(FromFileError::IoError)
+                .and_then(|bytes| StreamingSoundData::from_cursor(Cursor::new(bytes)))
+        } else {
+            StreamingSoundData::from_file(&self.path)
+        };
+
+        let sound_data = match sound_data {
+            Ok(sound_data) => sound_data.volume(super::linear_to_decibels(volume)).start_position(start_position_secs),
             Err(err) => {
                 log::error!(log::channel!("sound"), "Failed to load StreamedSound '{}': {err}", self.path);
                 return None;
@@ -394,6 +543,17 @@ impl super::SoundAssetRegistry for KiraSoundAssetRegistry {
         load_streamed_sound(&mut self.narration, &self.paths.narration, path)
     }
 
+    // NOTE: Playing instances hold their own reference to the sound data,
+    // so they keep playing until finished even after the asset is unloaded.
+    fn unload(&mut self, kind: SoundKind, key_hash: StringHash) -> bool {
+        match kind {
+            SoundKind::Sfx | SoundKind::Ui => self.sfx.remove(&key_hash).is_some(),
+            SoundKind::Ambience | SoundKind::SpatialAmbience => self.ambience.remove(&key_hash).is_some(),
+            SoundKind::Music => self.music.remove(&key_hash).is_some(),
+            SoundKind::Narration => self.narration.remove(&key_hash).is_some(),
+        }
+    }
+
     fn unload_all(&mut self) {
         self.sfx.clear();
         self.ambience.clear();
@@ -404,6 +564,15 @@ impl super::SoundAssetRegistry for KiraSoundAssetRegistry {
     fn sounds_loaded(&self) -> usize {
         self.sfx.len() + self.ambience.len() + self.music.len() + self.narration.len()
     }
+
+    // Music & narration stream from disk, so only SFX and ambience hold sample data.
+    fn memory_usage_estimate(&self) -> usize {
+        self.sfx
+            .values()
+            .chain(self.ambience.values())
+            .map(|asset| asset.data.frames.len() * std::mem::size_of::<Frame>())
+            .sum()
+    }
 }
 
 fn load_static_sound<Key: SoundKey>(
@@ -420,7 +589,12 @@ fn load_static_sound<Key: SoundKey>(
         return Key::new(sound_hash); // Already loaded.
     }
 
-    let sound_data = match StaticSoundData::from_file(&sound_path) {
+    let sound_data = match file_sys::load_bytes(&sound_path) {
+        Ok(bytes) => StaticSoundData::from_cursor(Cursor::new(bytes)),
+        Err(err) => Err(FromFileError::IoError(err)),
+    };
+
+    let sound_data = match sound_data {
         Ok(sound_data) => sound_data,
         Err(err) => {
             log::error!(log::channel!("sound"), "Failed to load sound '{sound_path}': {err}");
@@ -601,6 +775,15 @@ where
     fn playing_count(&self) -> usize {
         self.sounds.len()
     }
+
+    // Puts a sound carried over from another controller back under its previous handle.
+    fn restore(&mut self, sound_handle: SoundHandle, sound: Inst) {
+        debug_assert!(sound_handle.kind == self.kind);
+        debug_assert!(self.sounds.is_empty(), "Sounds can only be restored into an empty controller!");
+
+        self.sounds = std::iter::once((sound_handle.index as usize, sound)).collect();
+        self.generation = self.generation.max(sound_handle.generation);
+    }
 }
 
 // ----------------------------------------------
@@ -744,7 +927,7 @@ impl SoundInstance for SpatialSoundInstance {
 // Specialized sound controllers
 // ----------------------------------------------
 
-// Plays general sound effects like UI clicks, alerts, popups.
+// Plays general sound effects like alerts and popups. UI clicks use a second instance on the UI track.
 type SfxController = SoundController<
     false,
     false,
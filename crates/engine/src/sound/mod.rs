@@ -1,13 +1,21 @@
+use bitflags::bitflags;
 use common::{
     Vec2,
-    coords::IsoPointF32,
+    coords::{self, Cell, CellF32, IsoPointF32},
     hash::{self, StringHash},
-    time::Seconds,
+    time::{self, Instant, Seconds},
 };
 use proc_macros::DrawDebugUi;
 use serde::{Deserialize, Serialize};
 
-use crate::file_sys::paths::PathRef;
+use crate::{file_sys::paths::PathRef, log};
+
+pub mod bank;
+pub mod captions;
+pub mod events;
+pub use bank::{SoundBank, SoundBankDef};
+pub use captions::CaptionLine;
+pub use events::{SoundEventDef, SoundEventKey};
 
 // ----------------------------------------------
 // Internal backend implementations
@@ -86,6 +94,50 @@ pub enum SoundKind {
     SpatialAmbience,
     Music,
     Narration,
+    Ui, // UI clicks and popups. Loaded as SFX, played on their own bus.
+}
+
+impl SoundKind {
+    #[inline]
+    pub fn bus(self) -> SoundBuses {
+        match self {
+            Self::Sfx             => SoundBuses::Sfx,
+            Self::Ambience        => SoundBuses::Ambience,
+            Self::SpatialAmbience => SoundBuses::Ambience,
+            Self::Music           => SoundBuses::Music,
+            Self::Narration       => SoundBuses::Narration,
+            Self::Ui              => SoundBuses::Ui,
+        }
+    }
+}
+
+// ----------------------------------------------
+// SoundBuses
+// ----------------------------------------------
+
+// Mixer bus hierarchy. Every sound kind plays on a category bus, and all of them feed into Master:
+//
+//   Master
+//     ├─ Music
+//     ├─ Sfx
+//     ├─ Ambience (ambience + spatial ambience)
+//     ├─ Narration
+//     └─ Ui
+bitflags! {
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+    pub struct SoundBuses: u8 {
+        const Master    = 1 << 0;
+        const Music     = 1 << 1;
+        const Sfx       = 1 << 2;
+        const Ambience  = 1 << 3;
+        const Narration = 1 << 4;
+        const Ui        = 1 << 5;
+    }
+}
+
+impl SoundBuses {
+    // Children of the Master bus.
+    pub const CATEGORIES: [Self; 5] = [Self::Music, Self::Sfx, Self::Ambience, Self::Narration, Self::Ui];
 }
 
 // ----------------------------------------------
@@ -123,7 +175,12 @@ impl SoundHandle {
 // ----------------------------------------------
 
 #[derive(Copy, Clone, PartialEq, DrawDebugUi, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SoundGlobalSettings {
+    // Master bus volume, scales all the category volumes below.
+    #[debug_ui(edit, widget = "slider", min = "0", max = "1", separator)]
+    pub master_bus_volume: f32,
+
     // Master volumes:
     #[debug_ui(edit, widget = "slider", min = "0", max = "1")]
     pub spatial_master_volume: f32,
@@ -137,9 +194,18 @@ pub struct SoundGlobalSettings {
     #[debug_ui(edit, widget = "slider", min = "0", max = "1")]
     pub narration_master_volume: f32,
 
-    #[debug_ui(edit, widget = "slider", min = "0", max = "1", separator)]
+    #[debug_ui(edit, widget = "slider", min = "0", max = "1")]
     pub sfx_master_volume: f32,
 
+    #[debug_ui(edit, widget = "slider", min = "0", max = "1", separator)]
+    pub ui_master_volume: f32,
+
+    // Muting Master silences everything. Soloing any category bus silences all the others.
+    #[debug_ui(skip)]
+    pub muted_buses: SoundBuses,
+    #[debug_ui(skip)]
+    pub soloed_buses: SoundBuses,
+
     // Cutoff distance from the camera where we mute spatial sounds.
     #[debug_ui(edit, widget = "slider", min = "0", max = "1000")]
     pub spatial_cutoff_distance: f32,
@@ -169,19 +235,35 @@ pub struct SoundGlobalSettings {
 
     #[debug_ui(edit, widget = "slider", min = "0", max = "10")]
     pub sfx_fade_in_secs: Seconds,
-    #[debug_ui(edit, widget = "slider", min = "0", max = "10")]
+    #[debug_ui(edit, widget = "slider", min = "0", max = "10", separator)]
     pub sfx_fade_out_secs: Seconds,
+
+    // Volume reduction of the ducked buses while narration is playing; [0,1].
+    // Spatial sounds are world SFX (fires, construction), so they are never ducked.
+    #[debug_ui(edit, widget = "slider", min = "0", max = "1")]
+    pub narration_ducking_amount: f32,
+    // Time to ramp the ducking fully in or out.
+    #[debug_ui(edit, widget = "slider", min = "0", max = "5")]
+    pub narration_ducking_ramp_secs: Seconds,
+    #[debug_ui(skip)]
+    pub narration_ducked_buses: SoundBuses,
 }
 
 impl Default for SoundGlobalSettings {
     fn default() -> Self {
         Self {
             // Master volumes:
+            master_bus_volume: 1.0,
             spatial_master_volume: 1.0,
             ambience_master_volume: 1.0,
             music_master_volume: 1.0,
             narration_master_volume: 1.0,
             sfx_master_volume: 1.0,
+            ui_master_volume: 1.0,
+
+            // Bus mute/solo:
+            muted_buses: SoundBuses::empty(),
+            soloed_buses: SoundBuses::empty(),
 
             // Spatial ambience:
             spatial_cutoff_distance: 500.0,
@@ -198,6 +280,11 @@ impl Default for SoundGlobalSettings {
             narration_fade_out_secs: 3.0,
             sfx_fade_in_secs: 0.0,
             sfx_fade_out_secs: 0.0,
+
+            // Ducking:
+            narration_ducking_amount: 0.5,
+            narration_ducking_ramp_secs: 0.5,
+            narration_ducked_buses: SoundBuses::Music | SoundBuses::Ambience,
         }
     }
 }
@@ -211,6 +298,7 @@ impl SoundGlobalSettings {
             SoundKind::SpatialAmbience => self.spatial_master_volume,
             SoundKind::Music           => self.music_master_volume,
             SoundKind::Narration       => self.narration_master_volume,
+            SoundKind::Ui              => self.ui_master_volume,
         }
     }
 
@@ -222,6 +310,7 @@ impl SoundGlobalSettings {
             SoundKind::SpatialAmbience => self.spatial_fade_in_secs,
             SoundKind::Music           => self.music_fade_in_secs,
             SoundKind::Narration       => self.narration_fade_in_secs,
+            SoundKind::Ui              => self.sfx_fade_in_secs,
         }
     }
 
@@ -233,6 +322,70 @@ impl SoundGlobalSettings {
             SoundKind::SpatialAmbience => self.spatial_fade_out_secs,
             SoundKind::Music           => self.music_fade_out_secs,
             SoundKind::Narration       => self.narration_fade_out_secs,
+            SoundKind::Ui              => self.sfx_fade_out_secs,
+        }
+    }
+
+    #[inline]
+    pub fn is_bus_muted(&self, bus: SoundBuses) -> bool {
+        self.muted_buses.intersects(bus)
+    }
+
+    #[inline]
+    pub fn is_bus_soloed(&self, bus: SoundBuses) -> bool {
+        self.soloed_buses.intersects(bus)
+    }
+
+    #[inline]
+    pub fn set_bus_muted(&mut self, bus: SoundBuses, muted: bool) {
+        self.muted_buses.set(bus, muted);
+    }
+
+    #[inline]
+    pub fn set_bus_soloed(&mut self, bus: SoundBuses, soloed: bool) {
+        self.soloed_buses.set(bus, soloed);
+    }
+
+    // True if sounds on this category bus reach the output, taking mute and solo into account.
+    pub fn is_bus_audible(&self, bus: SoundBuses) -> bool {
+        if self.muted_buses.intersects(SoundBuses::Master | bus) {
+            return false;
+        }
+
+        // Master is not a category; soloing it has no effect.
+        let soloed = self.soloed_buses - SoundBuses::Master;
+        soloed.is_empty() || soloed.intersects(bus)
+    }
+
+    // Final volume of a sound kind after the bus hierarchy: master * category bus,
+    // with mute/solo and narration ducking applied. `ducking` is how far the
+    // ducking has ramped in, from 0 (none) to 1 (full `narration_ducking_amount`).
+    fn output_volume(&self, kind: SoundKind, ducking: f32) -> f32 {
+        let bus = kind.bus();
+        if !self.is_bus_audible(bus) {
+            return 0.0;
+        }
+
+        let mut volume = self.master_bus_volume * self.master_volume(kind);
+
+        if kind != SoundKind::SpatialAmbience && self.narration_ducked_buses.intersects(bus) {
+            volume *= 1.0 - self.narration_ducking_amount.clamp(0.0, 1.0) * ducking.clamp(0.0, 1.0);
+        }
+
+        volume
+    }
+
+    // Copy of these settings with each kind's master volume replaced by its final output volume.
+    // The backends only know about per-kind volumes, so this is what they play with.
+    fn mixed(&self, ducking: f32) -> Self {
+        Self {
+            spatial_master_volume: self.output_volume(SoundKind::SpatialAmbience, ducking),
+            ambience_master_volume: self.output_volume(SoundKind::Ambience, ducking),
+            music_master_volume: self.output_volume(SoundKind::Music, ducking),
+            narration_master_volume: self.output_volume(SoundKind::Narration, ducking),
+            sfx_master_volume: self.output_volume(SoundKind::Sfx, ducking),
+            ui_master_volume: self.output_volume(SoundKind::Ui, ducking),
+            ..*self
         }
     }
 }
@@ -251,6 +404,7 @@ where
     key_hash: StringHash,
     position: IsoPointF32,
     looping: bool,
+    volume: f32, // Scale applied to the master volume of `kind`.
 }
 
 // ----------------------------------------------
@@ -260,11 +414,21 @@ where
 trait SoundSystemBackend: Sized {
     type Registry: SoundAssetRegistry;
 
-    fn new() -> Option<Box<Self>>;
+    // `output_device` is one of the names from `output_devices()`, or None for the system default.
+    fn new(output_device: Option<&str>) -> Option<Box<Self>>;
+    fn output_devices() -> Vec<String>;
+    fn set_output_device(
+        &mut self,
+        output_device: Option<&str>,
+        registry: &Self::Registry,
+        settings: &SoundGlobalSettings,
+    ) -> bool;
+
     fn update(&mut self, listener_position: IsoPointF32, settings: &SoundGlobalSettings);
     fn set_volumes(&mut self, settings: &SoundGlobalSettings);
     fn listener_position(&self) -> IsoPointF32;
     fn sounds_playing(&self) -> usize;
+    fn is_narration_playing(&self) -> bool;
 
     fn play(&mut self, params: PlaySoundParams<Self::Registry>) -> SoundHandle;
     fn stop(&mut self, sound_handle: SoundHandle, settings: &SoundGlobalSettings);
@@ -279,8 +443,10 @@ trait SoundAssetRegistry: Sized {
     fn load_ambience(&mut self, path: PathRef) -> AmbienceSoundKey;
     fn load_music(&mut self, path: PathRef) -> MusicSoundKey;
     fn load_narration(&mut self, path: PathRef) -> NarrationSoundKey;
+    fn unload(&mut self, kind: SoundKind, key_hash: StringHash) -> bool;
     fn unload_all(&mut self);
     fn sounds_loaded(&self) -> usize;
+    fn memory_usage_estimate(&self) -> usize;
 }
 
 // ----------------------------------------------
@@ -291,11 +457,52 @@ pub struct SoundSystem {
     backend: Option<Box<SoundSystemBackendImpl>>,
     registry: SoundAssetRegistryImpl,
     settings: SoundGlobalSettings,
+
+    // Audio output device name. None uses the system default device.
+    output_device: Option<String>,
+
+    // How far music and ambience are ducked under a narration line, [0,1].
+    // Ramps in while narration plays and back out once it's done.
+    narration_ducking: f32,
+    last_update_time: Instant,
+
+    // Ref counts for loaded sounds, from sound banks and direct loads.
+    bank_refs: bank::SoundBankRefs,
+
+    // Named sound effects registered from the sound events manifest.
+    sound_events: events::SoundEvents,
+
+    // Timed caption tracks for narration sounds.
+    narration_captions: captions::NarrationCaptions,
 }
 
 impl SoundSystem {
-    pub fn new(settings: SoundGlobalSettings) -> Self {
-        Self { backend: SoundSystemBackendImpl::new(), registry: SoundAssetRegistryImpl::new(), settings }
+    pub fn new(settings: SoundGlobalSettings, output_device: Option<&str>) -> Self {
+        Self::with_backend(SoundSystemBackendImpl::new(output_device), settings, output_device)
+    }
+
+    // SoundSystem without an audio backend, for headless runs. Same as when the backend
+    // fails to initialize: nothing gets loaded and all playback requests are ignored.
+    pub fn new_null(settings: SoundGlobalSettings) -> Self {
+        Self::with_backend(None, settings, None)
+    }
+
+    fn with_backend(
+        backend: Option<Box<SoundSystemBackendImpl>>,
+        settings: SoundGlobalSettings,
+        output_device: Option<&str>,
+    ) -> Self {
+        Self {
+            backend,
+            registry: SoundAssetRegistryImpl::new(),
+            settings,
+            output_device: output_device.map(String::from),
+            narration_ducking: 0.0,
+            last_update_time: Instant::now(),
+            bank_refs: bank::SoundBankRefs::default(),
+            sound_events: events::SoundEvents::default(),
+            narration_captions: captions::NarrationCaptions::default(),
+        }
     }
 
     #[inline]
@@ -310,9 +517,58 @@ impl SoundSystem {
 
     pub fn change_sound_settings(&mut self, settings: SoundGlobalSettings) {
         self.settings = settings;
+        let active_settings = self.active_settings();
         if let Some(backend) = &mut self.backend {
-            backend.set_volumes(&self.settings);
+            backend.set_volumes(&active_settings);
+        }
+    }
+
+    // Names of the audio output devices available, for the settings UI.
+    #[inline]
+    pub fn output_devices(&self) -> Vec<String> {
+        SoundSystemBackendImpl::output_devices()
+    }
+
+    #[inline]
+    pub fn current_output_device(&self) -> Option<&str> {
+        self.output_device.as_deref()
+    }
+
+    // Switches audio output at runtime. The loaded sounds are kept and the music track
+    // resumes from where it was, but other sounds playing are cut. Returns false on failure,
+    // in which case the previous device remains in use.
+    pub fn set_output_device(&mut self, output_device: Option<&str>) -> bool {
+        if output_device == self.current_output_device() && self.is_initialized() {
+            return true;
+        }
+
+        let active_settings = self.active_settings();
+        let switched = match &mut self.backend {
+            Some(backend) => backend.set_output_device(output_device, &self.registry, &active_settings),
+            None => {
+                // Backend failed to initialize with the previous device; try again with this one.
+                self.backend = SoundSystemBackendImpl::new(output_device);
+                self.backend.is_some()
+            }
+        };
+
+        if switched {
+            self.output_device = output_device.map(String::from);
+            log::info!(log::channel!("sound"), "Audio output device set to '{}'.", output_device.unwrap_or("Default"));
         }
+
+        switched
+    }
+
+    #[inline]
+    pub fn narration_ducking(&self) -> f32 {
+        self.narration_ducking
+    }
+
+    // User settings mixed down through the bus hierarchy, with narration ducking applied if active.
+    #[inline]
+    fn active_settings(&self) -> SoundGlobalSettings {
+        self.settings.mixed(self.narration_ducking)
     }
 
     // ----------------------
@@ -334,13 +590,38 @@ impl SoundSystem {
         self.registry.sounds_loaded()
     }
 
+    // Decoded sample data held by the asset registry. Streamed sounds are not counted.
+    #[inline]
+    pub fn memory_usage_estimate(&self) -> usize {
+        self.registry.memory_usage_estimate()
+    }
+
     // ----------------------
     // Per-frame update:
     // ----------------------
 
     pub fn update(&mut self, listener_position: IsoPointF32) {
+        let now = Instant::now();
+        let delta_time_secs = time::elapsed_seconds(now, self.last_update_time);
+        self.last_update_time = now;
+
+        // Duck music and ambience while a narration line plays, restore once it's done.
+        let narration_playing = self.backend.as_ref().is_some_and(|backend| backend.is_narration_playing());
+        let prev_ducking = self.narration_ducking;
+        self.narration_ducking = ramp_ducking(
+            prev_ducking,
+            narration_playing,
+            delta_time_secs,
+            self.settings.narration_ducking_ramp_secs,
+        );
+        let ducking_changed = self.narration_ducking != prev_ducking;
+
+        let active_settings = self.active_settings();
         if let Some(backend) = &mut self.backend {
-            backend.update(listener_position, &self.settings);
+            if ducking_changed {
+                backend.set_volumes(&active_settings);
+            }
+            backend.update(listener_position, &active_settings);
         }
         // Else if backend failed to initialize we'll operate as a no-op/null SoundSystem.
     }
@@ -353,34 +634,77 @@ impl SoundSystem {
         if !self.is_initialized() {
             return SfxSoundKey::invalid();
         }
-        self.registry.load_sfx(path)
+        let key = self.registry.load_sfx(path);
+        if key.is_valid() {
+            self.bank_refs.add_ref(key.hash);
+        }
+        key
     }
 
     pub fn load_ambience(&mut self, path: PathRef) -> AmbienceSoundKey {
         if !self.is_initialized() {
             return AmbienceSoundKey::invalid();
         }
-        self.registry.load_ambience(path)
+        let key = self.registry.load_ambience(path);
+        if key.is_valid() {
+            self.bank_refs.add_ref(key.hash);
+        }
+        key
     }
 
     pub fn load_music(&mut self, path: PathRef) -> MusicSoundKey {
         if !self.is_initialized() {
             return MusicSoundKey::invalid();
         }
-        self.registry.load_music(path)
+        let key = self.registry.load_music(path);
+        if key.is_valid() {
+            self.bank_refs.add_ref(key.hash);
+        }
+        key
     }
 
     pub fn load_narration(&mut self, path: PathRef) -> NarrationSoundKey {
         if !self.is_initialized() {
             return NarrationSoundKey::invalid();
         }
-        self.registry.load_narration(path)
+        let key = self.registry.load_narration(path);
+        if key.is_valid() {
+            self.bank_refs.add_ref(key.hash);
+        }
+        key
+    }
+
+    // Releases a sound loaded with the matching load_*() call. The sound is
+    // unloaded once no sound bank nor any other direct load references it.
+    pub fn unload_sfx(&mut self, sound_key: SfxSoundKey) {
+        self.release_sound(SoundKind::Sfx, sound_key.hash);
+    }
+
+    pub fn unload_ambience(&mut self, sound_key: AmbienceSoundKey) {
+        self.release_sound(SoundKind::Ambience, sound_key.hash);
+    }
+
+    pub fn unload_music(&mut self, sound_key: MusicSoundKey) {
+        self.release_sound(SoundKind::Music, sound_key.hash);
+    }
+
+    pub fn unload_narration(&mut self, sound_key: NarrationSoundKey) {
+        self.release_sound(SoundKind::Narration, sound_key.hash);
+    }
+
+    // Returns true if this was the last reference and the sound got unloaded.
+    fn release_sound(&mut self, kind: SoundKind, key_hash: StringHash) -> bool {
+        if key_hash == hash::NULL_HASH || !self.bank_refs.release(key_hash) {
+            return false;
+        }
+        self.registry.unload(kind, key_hash)
     }
 
     // Clears the sound registry. Note that any sound still playing is
     // not freed immediately, only after it finishes playing or is stopped.
     pub fn unload_all(&mut self) {
         self.registry.unload_all();
+        self.bank_refs = bank::SoundBankRefs::default();
     }
 
     // ----------------------
@@ -388,11 +712,16 @@ impl SoundSystem {
     // ----------------------
 
     pub fn play_sfx(&mut self, sound_key: SfxSoundKey, looping: bool) -> SoundHandle {
-        self.play_backend(SoundKind::Sfx, sound_key.hash, IsoPointF32::default(), looping)
+        self.play_backend(SoundKind::Sfx, sound_key.hash, IsoPointF32::default(), looping, 1.0)
     }
 
     pub fn play_ambience(&mut self, sound_key: AmbienceSoundKey, looping: bool) -> SoundHandle {
-        self.play_backend(SoundKind::Ambience, sound_key.hash, IsoPointF32::default(), looping)
+        self.play_backend(SoundKind::Ambience, sound_key.hash, IsoPointF32::default(), looping, 1.0)
+    }
+
+    // `volume` scales the ambience bus volume, e.g. to follow the strength of the weather.
+    pub fn play_ambience_at_volume(&mut self, sound_key: AmbienceSoundKey, looping: bool, volume: f32) -> SoundHandle {
+        self.play_backend(SoundKind::Ambience, sound_key.hash, IsoPointF32::default(), looping, volume.clamp(0.0, 1.0))
     }
 
     pub fn play_spatial_ambience(
@@ -401,26 +730,60 @@ impl SoundSystem {
         world_position: IsoPointF32,
         looping: bool,
     ) -> SoundHandle {
-        self.play_backend(SoundKind::SpatialAmbience, sound_key.hash, world_position, looping)
+        self.play_backend(SoundKind::SpatialAmbience, sound_key.hash, world_position, looping, 1.0)
     }
 
     pub fn play_music(&mut self, sound_key: MusicSoundKey, looping: bool) -> SoundHandle {
-        self.play_backend(SoundKind::Music, sound_key.hash, IsoPointF32::default(), looping)
+        self.play_backend(SoundKind::Music, sound_key.hash, IsoPointF32::default(), looping, 1.0)
+    }
+
+    // Positional SFX at a tile map cell, attenuated and panned by its distance to the listener.
+    // Plays on the spatial ambience controller, so it is mixed into the ambience bus.
+    pub fn play_sfx_at_cell(&mut self, sound_key: SfxSoundKey, cell: Cell, looping: bool) -> SoundHandle {
+        self.play_backend(SoundKind::SpatialAmbience, sound_key.hash, cell_world_position(cell), looping, 1.0)
+    }
+
+    // Distance from the listener to a tile map cell, in the same units as `spatial_cutoff_distance`.
+    pub fn cell_listener_distance(&self, cell: Cell) -> f32 {
+        (cell_world_position(cell).0 - self.listener_position()).length()
+    }
+
+    // True if a sound played at this cell would not be fully attenuated by distance.
+    #[inline]
+    pub fn is_cell_audible(&self, cell: Cell) -> bool {
+        self.cell_listener_distance(cell) < self.settings.spatial_cutoff_distance
     }
 
     pub fn play_narration(&mut self, sound_key: NarrationSoundKey, looping: bool) -> SoundHandle {
-        self.play_backend(SoundKind::Narration, sound_key.hash, IsoPointF32::default(), looping)
+        let handle = self.play_backend(SoundKind::Narration, sound_key.hash, IsoPointF32::default(), looping, 1.0);
+        self.narration_captions.on_narration_played(sound_key, handle);
+        handle
+    }
+
+    // UI sounds are loaded as SFX but play on the UI bus.
+    pub fn play_ui(&mut self, sound_key: SfxSoundKey, looping: bool) -> SoundHandle {
+        self.play_backend(SoundKind::Ui, sound_key.hash, IsoPointF32::default(), looping, 1.0)
     }
 
-    fn play_backend(&mut self, kind: SoundKind, key_hash: StringHash, position: IsoPointF32, looping: bool) -> SoundHandle {
+    // `volume` scales the sound's bus volume.
+    fn play_backend(
+        &mut self,
+        kind: SoundKind,
+        key_hash: StringHash,
+        position: IsoPointF32,
+        looping: bool,
+        volume: f32,
+    ) -> SoundHandle {
+        let active_settings = self.active_settings();
         if let Some(backend) = &mut self.backend {
             return backend.play(PlaySoundParams {
                 registry: &self.registry,
-                settings: &self.settings,
+                settings: &active_settings,
                 kind,
                 key_hash,
                 position,
                 looping,
+                volume,
             });
         }
         SoundHandle::invalid(kind)
@@ -491,6 +854,23 @@ impl SoundSystem {
 // Utilities
 // ----------------------------------------------
 
+#[inline]
+fn cell_world_position(cell: Cell) -> IsoPointF32 {
+    coords::cell_to_iso_f32(CellF32::from_integer_cell(cell))
+}
+
+// Moves the narration ducking level towards fully in (1) or out (0), over `ramp_secs`.
+#[inline]
+fn ramp_ducking(ducking: f32, narration_playing: bool, delta_time_secs: Seconds, ramp_secs: Seconds) -> f32 {
+    let target = if narration_playing { 1.0 } else { 0.0 };
+    if ramp_secs <= 0.0 {
+        return target;
+    }
+
+    let step = delta_time_secs / ramp_secs;
+    if narration_playing { (ducking + step).min(target) } else { (ducking - step).max(target) }
+}
+
 #[inline]
 fn linear_to_decibels(mut volume: f32) -> f32 {
     volume = volume.clamp(0.0, 1.0);
@@ -502,3 +882,43 @@ fn linear_to_decibels(mut volume: f32) -> f32 {
 
     20.0 * volume.log10()
 }
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ducking_ramps_in_and_out() {
+        let ducking = ramp_ducking(0.0, true, 0.25, 0.5);
+        assert_eq!(ducking, 0.5);
+        assert_eq!(ramp_ducking(ducking, true, 1.0, 0.5), 1.0);
+        assert_eq!(ramp_ducking(ducking, false, 0.125, 0.5), 0.25);
+        assert_eq!(ramp_ducking(ducking, false, 0.0, 0.0), 0.0); // No ramp, switches instantly.
+    }
+
+    #[test]
+    fn bus_mixing() {
+        let mut settings = SoundGlobalSettings { master_bus_volume: 0.5, ..Default::default() };
+        assert_eq!(settings.output_volume(SoundKind::Music, 0.0), 0.5);
+
+        // Ducked under narration; narration itself, SFX and spatial sounds are not.
+        assert_eq!(settings.output_volume(SoundKind::Music, 1.0), 0.25);
+        assert_eq!(settings.output_volume(SoundKind::Ambience, 0.5), 0.375);
+        assert_eq!(settings.output_volume(SoundKind::SpatialAmbience, 1.0), 0.5);
+        assert_eq!(settings.output_volume(SoundKind::Narration, 1.0), 0.5);
+        assert_eq!(settings.output_volume(SoundKind::Sfx, 1.0), 0.5);
+
+        // Soloing a category silences the others. Spatial ambience plays on the ambience bus.
+        settings.set_bus_soloed(SoundBuses::Ambience, true);
+        assert_eq!(settings.output_volume(SoundKind::Music, 0.0), 0.0);
+        assert_eq!(settings.output_volume(SoundKind::SpatialAmbience, 0.0), 0.5);
+
+        // Muting Master silences everything, soloed or not.
+        settings.set_bus_muted(SoundBuses::Master, true);
+        assert_eq!(settings.output_volume(SoundKind::Ambience, 0.0), 0.0);
+    }
+}
@@ -7,9 +7,11 @@
 //   AudioContext
 //     └─ destination
 //          ├─ sfxGain        (SFX master volume)
-//          ├─ ambienceGain   (Ambience + Spatial master volume)
+//          ├─ ambienceGain   (Ambience master volume)
+//          ├─ spatialGain    (Spatial master volume, not ducked under narration)
 //          ├─ musicGain      (Music master volume)
-//          └─ narrationGain  (Narration master volume)
+//          ├─ narrationGain  (Narration master volume)
+//          └─ uiGain         (UI master volume)
 //
 // Each playing sound gets its own chain:
 //   AudioBufferSourceNode → GainNode (per-sound volume/fade)
@@ -61,8 +63,10 @@ pub struct WebAudioSoundSystemBackend {
     // Per-kind master gain nodes, connected to ctx.destination.
     sfx_gain: web_sys::GainNode,
     ambience_gain: web_sys::GainNode,
+    spatial_gain: web_sys::GainNode,
     music_gain: web_sys::GainNode,
     narration_gain: web_sys::GainNode,
+    ui_gain: web_sys::GainNode,
 
     // Per-kind sound instance pools.
     sfx: SoundPool,
@@ -70,6 +74,7 @@ pub struct WebAudioSoundSystemBackend {
     spatial: SoundPool,
     music: SoundPool,
     narration: SoundPool,
+    ui: SoundPool,
 
     listener_position: IsoPointF32,
     resumed: bool,
@@ -78,7 +83,8 @@ pub struct WebAudioSoundSystemBackend {
 impl SoundSystemBackend for WebAudioSoundSystemBackend {
     type Registry = WebAudioSoundAssetRegistry;
 
-    fn new() -> Option<Box<Self>> {
+    // NOTE: Browsers route audio to the OS default output, so there's no device selection.
+    fn new(_output_device: Option<&str>) -> Option<Box<Self>> {
         let ctx = match web_sys::AudioContext::new() {
             Ok(ctx) => ctx,
             Err(err) => {
@@ -91,8 +97,10 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
 
         let sfx_gain = create_gain_node(&ctx, &destination)?;
         let ambience_gain = create_gain_node(&ctx, &destination)?;
+        let spatial_gain = create_gain_node(&ctx, &destination)?;
         let music_gain = create_gain_node(&ctx, &destination)?;
         let narration_gain = create_gain_node(&ctx, &destination)?;
+        let ui_gain = create_gain_node(&ctx, &destination)?;
 
         log::info!(
             log::channel!("sound"),
@@ -105,18 +113,35 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
             ctx,
             sfx_gain,
             ambience_gain,
+            spatial_gain,
             music_gain,
             narration_gain,
+            ui_gain,
             sfx: SoundPool::new(SoundKind::Sfx),
             ambience: SoundPool::new(SoundKind::Ambience),
             spatial: SoundPool::new(SoundKind::SpatialAmbience),
             music: SoundPool::new(SoundKind::Music),
             narration: SoundPool::new(SoundKind::Narration),
+            ui: SoundPool::new(SoundKind::Ui),
             listener_position: IsoPointF32::default(),
             resumed: false,
         }))
     }
 
+    fn output_devices() -> Vec<String> {
+        Vec::new()
+    }
+
+    fn set_output_device(
+        &mut self,
+        _output_device: Option<&str>,
+        _registry: &WebAudioSoundAssetRegistry,
+        _settings: &SoundGlobalSettings,
+    ) -> bool {
+        log::warning!(log::channel!("sound"), "Audio output device selection is not supported on Web.");
+        false
+    }
+
     fn update(&mut self, listener_position: IsoPointF32, settings: &SoundGlobalSettings) {
         self.listener_position = listener_position;
 
@@ -126,6 +151,7 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
         self.spatial.remove_stopped(now);
         self.music.remove_stopped(now);
         self.narration.remove_stopped(now);
+        self.ui.remove_stopped(now);
 
         // Update spatial sound volumes and panning based on listener position.
         for (_, sound) in &mut self.spatial.sounds {
@@ -137,9 +163,10 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
         let now = self.ctx.current_time();
         set_gain(&self.sfx_gain, settings.sfx_master_volume, now);
         set_gain(&self.ambience_gain, settings.ambience_master_volume, now);
-        // Spatial shares ambience gain node.
+        set_gain(&self.spatial_gain, settings.spatial_master_volume, now);
         set_gain(&self.music_gain, settings.music_master_volume, now);
         set_gain(&self.narration_gain, settings.narration_master_volume, now);
+        set_gain(&self.ui_gain, settings.ui_master_volume, now);
     }
 
     fn listener_position(&self) -> IsoPointF32 {
@@ -152,6 +179,11 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
             + self.spatial.sounds.len()
             + self.music.sounds.len()
             + self.narration.sounds.len()
+            + self.ui.sounds.len()
+    }
+
+    fn is_narration_playing(&self) -> bool {
+        !self.narration.sounds.is_empty()
     }
 
     fn play(&mut self, params: PlaySoundParams<Self::Registry>) -> SoundHandle {
@@ -164,18 +196,23 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
         let (pool, track_gain) = match params.kind {
             SoundKind::Sfx             => (&mut self.sfx,        &self.sfx_gain),
             SoundKind::Ambience        => (&mut self.ambience,   &self.ambience_gain),
-            SoundKind::SpatialAmbience => (&mut self.spatial,    &self.ambience_gain),
+            SoundKind::SpatialAmbience => (&mut self.spatial,    &self.spatial_gain),
             SoundKind::Music           => (&mut self.music,      &self.music_gain),
             SoundKind::Narration       => (&mut self.narration,  &self.narration_gain),
+            SoundKind::Ui              => (&mut self.ui,         &self.ui_gain),
         };
 
         // Look up the AudioBuffer for this sound.
         let buffer = match params.kind {
-            SoundKind::SpatialAmbience => params.registry.ambience.get(&params.key_hash),
+            // Positional SFX come from the SFX registry.
+            SoundKind::SpatialAmbience => {
+                params.registry.ambience.get(&params.key_hash).or_else(|| params.registry.sfx.get(&params.key_hash))
+            }
             SoundKind::Sfx             => params.registry.sfx.get(&params.key_hash),
             SoundKind::Ambience        => params.registry.ambience.get(&params.key_hash),
             SoundKind::Music           => params.registry.music.get(&params.key_hash),
             SoundKind::Narration       => params.registry.narration.get(&params.key_hash),
+            SoundKind::Ui              => params.registry.sfx.get(&params.key_hash),
         };
 
         let buffer = match buffer {
@@ -189,7 +226,7 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
             None => return SoundHandle::invalid(params.kind),
         };
 
-        let volume = params.settings.master_volume(params.kind);
+        let volume = params.settings.master_volume(params.kind) * params.volume;
         let fade_in = params.settings.fade_in_secs(params.kind);
         let spatial = params.kind == SoundKind::SpatialAmbience;
 
@@ -225,6 +262,7 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
             SoundKind::SpatialAmbience => &mut self.spatial,
             SoundKind::Music           => &mut self.music,
             SoundKind::Narration       => &mut self.narration,
+            SoundKind::Ui              => &mut self.ui,
         };
         pool.stop_one(&self.ctx, sound_handle, fade_out);
     }
@@ -236,6 +274,7 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
             SoundKind::SpatialAmbience => &mut self.spatial,
             SoundKind::Music           => &mut self.music,
             SoundKind::Narration       => &mut self.narration,
+            SoundKind::Ui              => &mut self.ui,
         };
         pool.stop_all(&self.ctx, fade_out);
     }
@@ -246,6 +285,7 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
         self.spatial.stop_all(&self.ctx, settings.fade_out_secs(SoundKind::SpatialAmbience));
         self.music.stop_all(&self.ctx, settings.fade_out_secs(SoundKind::Music));
         self.narration.stop_all(&self.ctx, settings.fade_out_secs(SoundKind::Narration));
+        self.ui.stop_all(&self.ctx, settings.fade_out_secs(SoundKind::Ui));
     }
 
     fn is_playing(&self, sound_handle: SoundHandle) -> bool {
@@ -255,6 +295,7 @@ impl SoundSystemBackend for WebAudioSoundSystemBackend {
             SoundKind::SpatialAmbience => &self.spatial,
             SoundKind::Music           => &self.music,
             SoundKind::Narration       => &self.narration,
+            SoundKind::Ui              => &self.ui,
         };
         pool.is_playing(sound_handle)
     }
@@ -322,6 +363,18 @@ impl SoundAssetRegistry for WebAudioSoundAssetRegistry {
         load_sound(&mut self.narration, &self.paths.narration, path)
     }
 
+    // NOTE: Playing instances hold their own AudioBuffer reference,
+    // so they keep playing until finished even after the asset is unloaded.
+    fn unload(&mut self, kind: SoundKind, key_hash: StringHash) -> bool {
+        let hash_map = match kind {
+            SoundKind::Sfx | SoundKind::Ui => &mut self.sfx,
+            SoundKind::Ambience | SoundKind::SpatialAmbience => &mut self.ambience,
+            SoundKind::Music => &mut self.music,
+            SoundKind::Narration => &mut self.narration,
+        };
+        hash_map.remove(&key_hash).is_some()
+    }
+
     fn unload_all(&mut self) {
         self.sfx.clear();
         self.ambience.clear();
@@ -332,6 +385,18 @@ impl SoundAssetRegistry for WebAudioSoundAssetRegistry {
     fn sounds_loaded(&self) -> usize {
         self.sfx.len() + self.ambience.len() + self.music.len() + self.narration.len()
     }
+
+    // Decoded buffers are 32-bit float samples per channel.
+    fn memory_usage_estimate(&self) -> usize {
+        self.sfx
+            .values()
+            .chain(self.ambience.values())
+            .chain(self.music.values())
+            .chain(self.narration.values())
+            .filter_map(|asset| asset.buffer.borrow().as_ref().map(|buffer| buffer.length() * buffer.number_of_channels()))
+            .map(|sample_count| sample_count as usize * std::mem::size_of::<f32>())
+            .sum()
+    }
 }
 
 fn load_sound<Key: SoundKey>(
@@ -20,7 +20,10 @@ use crate::{
         Application,
         input::{InputAction, InputKey, InputModifiers, InputSystem, MouseButton},
     },
-    file_sys::paths::{self, AssetPath},
+    file_sys::{
+        self,
+        paths::{self, AssetPath},
+    },
     log,
     platform,
     render::{
@@ -106,6 +109,8 @@ struct UiSystemInner {
     ui_ptr: Option<RawPtr<imgui::Ui>>,
     current_theme: UiTheme,
     current_font_scale: Option<UiFontScale>,
+    pending_glyph_ranges: Option<UiFontGlyphRanges>,
+    ui_scale: f32,
     show_ui_debug_menu: bool,
 }
 
@@ -118,6 +123,8 @@ impl UiSystem {
             ui_ptr: None,
             current_theme: UiTheme::Dev,
             current_font_scale: None,
+            pending_glyph_ranges: None,
+            ui_scale: 1.0,
             show_ui_debug_menu: false,
         };
 
@@ -132,7 +139,7 @@ impl UiSystem {
 
         let theme_font_handle = self.inner.context.fonts.front_for_theme(self.inner.current_theme);
 
-        let ui = self.inner.context.begin_frame(app, input_sys, delta_time_secs);
+        let ui = self.inner.context.begin_frame(app, input_sys, delta_time_secs, self.inner.ui_scale);
 
         internal::push_font(ui, theme_font_handle);
 
@@ -215,6 +222,28 @@ impl UiSystem {
         self.inner.context.fonts()
     }
 
+    #[inline]
+    pub fn font_glyph_ranges(&self) -> UiFontGlyphRanges {
+        self.inner.context.fonts.glyph_ranges
+    }
+
+    // Merges the fallback font for `glyph_ranges` into the in-game font. The font atlas
+    // is rebuilt before the next frame starts, since fonts can't change mid-frame.
+    #[inline]
+    pub fn request_font_glyph_ranges(&self, glyph_ranges: UiFontGlyphRanges) {
+        self.inner.as_mut().pending_glyph_ranges = Some(glyph_ranges);
+    }
+
+    // Called by the Engine before begin_frame.
+    pub(crate) fn apply_pending_font_changes(&mut self, render_sys: &mut RenderSystem) {
+        debug_assert!(!self.frame_started());
+
+        if let Some(glyph_ranges) = self.inner.pending_glyph_ranges.take() {
+            // On failure the current fonts are kept.
+            self.inner.context.set_font_glyph_ranges(render_sys, glyph_ranges);
+        }
+    }
+
     #[inline]
     pub fn ui(&self) -> &imgui::Ui {
         self.inner.ui_ptr.as_ref().expect("Called UiSystem::ui() outside begin_frame/end_frame!")
@@ -225,6 +254,30 @@ impl UiSystem {
         UiTextureHandle::new(tex_handle.pack())
     }
 
+    // ----------------------
+    // Ui Scale:
+    // ----------------------
+
+    pub const MIN_UI_SCALE: f32 = 0.5;
+    pub const MAX_UI_SCALE: f32 = 3.0;
+
+    // Scales all fonts, widget sizes and layout. Takes effect on the next frame.
+    #[inline]
+    pub fn set_ui_scale(&self, ui_scale: f32) {
+        self.inner.as_mut().ui_scale = ui_scale.clamp(Self::MIN_UI_SCALE, Self::MAX_UI_SCALE);
+    }
+
+    #[inline]
+    pub fn ui_scale(&self) -> f32 {
+        self.inner.ui_scale
+    }
+
+    // Window space (e.g. the cursor or a tile's screen position) to UI space.
+    #[inline]
+    pub fn screen_to_ui(&self, screen_pos: Vec2) -> Vec2 {
+        screen_pos / self.inner.ui_scale
+    }
+
     // ----------------------
     // Ui Theme:
     // ----------------------
@@ -320,6 +373,9 @@ pub struct UiFonts {
 
     // In-game UI / HUD fonts:
     pub in_game: UiFontHandle,
+
+    // Extra glyphs merged into the in-game font.
+    pub glyph_ranges: UiFontGlyphRanges,
 }
 
 impl UiFonts {
@@ -339,6 +395,45 @@ impl UiFonts {
     }
 }
 
+// ----------------------------------------------
+// UiFontGlyphRanges
+// ----------------------------------------------
+
+// Scripts the in-game font doesn't cover are drawn with glyphs merged in from a
+// fallback font, loaded on demand from `assets/fonts/`. Latin needs no fallback.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default)]
+pub enum UiFontGlyphRanges {
+    #[default]
+    Latin,
+    Cyrillic,
+    ChineseSimplified,
+    Japanese,
+    Korean,
+}
+
+impl UiFontGlyphRanges {
+    #[inline]
+    pub fn fallback_font_file(self) -> Option<&'static str> {
+        match self {
+            Self::Latin             => None,
+            Self::Cyrillic          => Some("noto_sans_regular.ttf"),
+            Self::ChineseSimplified => Some("noto_sans_sc_regular.otf"),
+            Self::Japanese          => Some("noto_sans_jp_regular.otf"),
+            Self::Korean            => Some("noto_sans_kr_regular.otf"),
+        }
+    }
+
+    fn to_imgui_glyph_ranges(self) -> imgui::FontGlyphRanges {
+        match self {
+            Self::Latin             => imgui::FontGlyphRanges::default(),
+            Self::Cyrillic          => imgui::FontGlyphRanges::cyrillic(),
+            Self::ChineseSimplified => imgui::FontGlyphRanges::chinese_simplified_common(),
+            Self::Japanese          => imgui::FontGlyphRanges::japanese(),
+            Self::Korean            => imgui::FontGlyphRanges::korean(),
+        }
+    }
+}
+
 // ----------------------------------------------
 // UiFontScale
 // ----------------------------------------------
@@ -386,13 +481,19 @@ impl UiContext {
         let mut ctx = imgui::Context::create();
         ctx.set_ini_filename(None); // 'None' disables automatic "imgui.ini" saving.
 
-        let fonts = Self::load_custom_fonts(&mut ctx);
+        let fonts = Self::load_custom_fonts(&mut ctx, UiFontGlyphRanges::Latin, None);
         let renderer = UiRenderer::new(render_sys, &mut ctx);
 
         Self { ctx, fonts, renderer, frame_started: false }
     }
 
-    fn begin_frame(&mut self, app: &Application, input_sys: &InputSystem, delta_time_secs: Seconds) -> &imgui::Ui {
+    fn begin_frame(
+        &mut self,
+        app: &Application,
+        input_sys: &InputSystem,
+        delta_time_secs: Seconds,
+        ui_scale: f32,
+    ) -> &imgui::Ui {
         debug_assert!(!self.frame_started);
         self.frame_started = true;
 
@@ -400,13 +501,16 @@ impl UiContext {
         io.update_delta_time(std::time::Duration::from_secs_f32(delta_time_secs));
 
         let fb_size = app.framebuffer_size().to_vec2();
-        let content_scale = app.content_scale();
 
-        io.display_size = [fb_size.x / content_scale.x, fb_size.y / content_scale.y];
-        io.display_framebuffer_scale = [content_scale.x, content_scale.y];
+        // The UI scale is applied on top of the content scale, so ImGui lays out a
+        // smaller display and everything is magnified when rendered to the framebuffer.
+        let scale = app.content_scale() * ui_scale;
+
+        io.display_size = [fb_size.x / scale.x, fb_size.y / scale.y];
+        io.display_framebuffer_scale = [scale.x, scale.y];
 
         // Send mouse/keyboard input to ImGui. The rest is handled by application events.
-        self.update_input(input_sys);
+        self.update_input(input_sys, ui_scale);
 
         // Start new ImGui frame. Use the returned `ui` object to build the UI windows.
         self.ctx.new_frame()
@@ -423,6 +527,36 @@ impl UiContext {
         &self.fonts
     }
 
+    fn set_font_glyph_ranges(&mut self, render_sys: &mut RenderSystem, glyph_ranges: UiFontGlyphRanges) -> bool {
+        debug_assert!(!self.frame_started);
+
+        if self.fonts.glyph_ranges == glyph_ranges {
+            return true;
+        }
+
+        let fallback_font_data = match glyph_ranges.fallback_font_file() {
+            Some(file_name) => {
+                let font_path = paths::assets_path().join("fonts").join(file_name);
+                match file_sys::load_bytes(&font_path) {
+                    Ok(font_data) => Some(font_data),
+                    Err(err) => {
+                        log::error!(log::channel!("ui"), "Failed to load fallback font '{font_path}': {err}");
+                        return false;
+                    }
+                }
+            }
+            None => None,
+        };
+
+        // Font handles are invalidated, so all fonts are added again.
+        self.ctx.fonts().clear();
+        self.fonts = Self::load_custom_fonts(&mut self.ctx, glyph_ranges, fallback_font_data.as_deref());
+        self.renderer.rebuild_font_atlas(render_sys, &mut self.ctx);
+
+        log::info!(log::channel!("ui"), "UI font glyph ranges set to {glyph_ranges:?}.");
+        true
+    }
+
     fn on_key_input(&mut self, key: InputKey, action: InputAction) {
         let io = self.ctx.io_mut();
         let pressed = action != InputAction::Release;
@@ -442,10 +576,10 @@ impl UiContext {
         io.mouse_wheel += amount.y;
     }
 
-    fn update_input(&mut self, input_sys: &InputSystem) {
+    fn update_input(&mut self, input_sys: &InputSystem, ui_scale: f32) {
         let io = self.ctx.io_mut();
 
-        let cursor_pos = input_sys.cursor_pos();
+        let cursor_pos = input_sys.cursor_pos() / ui_scale;
         io.mouse_pos = [cursor_pos.x, cursor_pos.y];
 
         io.mouse_down[0] = input_sys.mouse_button_state(MouseButton::Left) == InputAction::Press;
@@ -698,7 +832,11 @@ impl UiContext {
         style.tab_rounding = 4.0;
     }
 
-    fn load_custom_fonts(ctx: &mut imgui::Context) -> UiFonts {
+    fn load_custom_fonts(
+        ctx: &mut imgui::Context,
+        glyph_ranges: UiFontGlyphRanges,
+        fallback_font_data: Option<&[u8]>,
+    ) -> UiFonts {
         const DEV_FONT_DATA: &[u8] = include_bytes!(
             "../../../../assets/fonts/source_code_pro_semi_bold.ttf"
         );
@@ -734,16 +872,58 @@ impl UiContext {
                 None
             ),
             // In-game UI / HUD fonts:
-            in_game: Self::load_font(
+            in_game: Self::load_font_with_fallback(
                 fonts,
                 IN_GAME_FONT_DATA,
                 UiFonts::IN_GAME_FONT_SIZE,
-                None,
-                Some([1.0, 0.0])
+                Some([1.0, 0.0]),
+                fallback_font_data.map(|data| (data, glyph_ranges.to_imgui_glyph_ranges()))
             ),
+            glyph_ranges,
         }
     }
 
+    // Glyphs missing from `font_data` are merged in from the fallback font, if any.
+    fn load_font_with_fallback(
+        fonts: &mut imgui::FontAtlas,
+        font_data: &[u8],
+        font_size: f32,
+        glyph_extra_spacing: Option<[f32; 2]>,
+        fallback: Option<(&[u8], imgui::FontGlyphRanges)>,
+    ) -> UiFontHandle {
+        let Some((fallback_font_data, fallback_glyph_ranges)) = fallback else {
+            return Self::load_font(fonts, font_data, font_size, None, glyph_extra_spacing);
+        };
+
+        // Sources after the first are merged into the same font.
+        fonts.add_font(&[
+            imgui::FontSource::TtfData {
+                data: font_data,
+                size_pixels: font_size,
+                config: Some(imgui::FontConfig {
+                    oversample_h: 3,
+                    oversample_v: 3,
+                    pixel_snap_h: false,
+                    glyph_extra_spacing: glyph_extra_spacing.unwrap_or([0.0, 0.0]),
+                    ..Default::default()
+                }),
+            },
+            imgui::FontSource::TtfData {
+                data: fallback_font_data,
+                size_pixels: font_size,
+                config: Some(imgui::FontConfig {
+                    // No oversampling, CJK ranges have thousands of glyphs and would bloat the atlas.
+                    oversample_h: 1,
+                    oversample_v: 1,
+                    pixel_snap_h: false,
+                    glyph_ranges: fallback_glyph_ranges,
+                    glyph_extra_spacing: glyph_extra_spacing.unwrap_or([0.0, 0.0]),
+                    ..Default::default()
+                }),
+            },
+        ])
+    }
+
     fn load_font(
         fonts: &mut imgui::FontAtlas,
         font_data: &[u8],
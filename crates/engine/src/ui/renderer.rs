@@ -52,6 +52,16 @@ pub struct UiRenderer {
 
 impl UiRenderer {
     pub fn new(render_sys: &mut RenderSystem, ctx: &mut imgui::Context) -> Self {
+        Self { font_atlas_tex_handle: Self::build_font_atlas_texture(render_sys, ctx) }
+    }
+
+    // Uploads the font atlas again after fonts were added or removed.
+    pub fn rebuild_font_atlas(&mut self, render_sys: &mut RenderSystem, ctx: &mut imgui::Context) {
+        render_sys.texture_cache_mut().release_texture(&mut self.font_atlas_tex_handle);
+        self.font_atlas_tex_handle = Self::build_font_atlas_texture(render_sys, ctx);
+    }
+
+    fn build_font_atlas_texture(render_sys: &mut RenderSystem, ctx: &mut imgui::Context) -> TextureHandle {
         let tex_cache = render_sys.texture_cache_mut();
 
         let font_atlas = ctx.fonts();
@@ -72,7 +82,7 @@ impl UiRenderer {
 
         font_atlas.tex_id = UiTextureHandle::new(font_atlas_tex_handle.pack());
 
-        Self { font_atlas_tex_handle }
+        font_atlas_tex_handle
     }
 
     pub fn render(&self, render_sys: &mut RenderSystem, ctx: &mut imgui::Context) {
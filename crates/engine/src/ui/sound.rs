@@ -104,7 +104,7 @@ impl UiSound {
 
         Self {
             key: sound_sys.load_sfx((&path).into()),
-            handle: SoundHandle::invalid(SoundKind::Sfx), // Handle set when we first play the sound.
+            handle: SoundHandle::invalid(SoundKind::Ui),  // Handle set when we first play the sound.
             last_play_time: None,                         // Never played.
             cooldown,
         }
@@ -143,7 +143,7 @@ impl UiSound {
 
         if cooldown_elapsed {
             let looping = flags.intersects(UiPlaySoundFlags::Looping);
-            self.handle = sound_sys.play_sfx(self.key, looping);
+            self.handle = sound_sys.play_ui(self.key, looping);
             self.last_play_time = Some(time_now);
         }
     }
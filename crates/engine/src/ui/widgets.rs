@@ -359,6 +359,8 @@ pub enum UiWidgetImpl {
     UiTextButton,
     UiSpriteButton,
     UiSeparator,
+    UiColorRamp,
+    UiLineChart,
     UiSpriteIcon,
     UiSlider,
     UiCheckbox,
@@ -1741,6 +1743,12 @@ impl UiTextButton {
     pub fn enable(&mut self, enable: bool) {
         self.enabled = enable;
     }
+
+    #[inline]
+    pub fn set_label(&mut self, label: String) {
+        debug_assert!(!label.is_empty());
+        self.label = label;
+    }
 }
 
 // ----------------------------------------------
@@ -2158,6 +2166,184 @@ impl UiSeparator {
     }
 }
 
+// ----------------------------------------------
+// UiColorRampParams
+// ----------------------------------------------
+
+#[derive(Default)]
+pub struct UiColorRampParams {
+    pub colors: Vec<Color>, // Evenly spaced color stops, left to right.
+    pub size: Vec2,
+}
+
+// ----------------------------------------------
+// UiColorRamp
+// ----------------------------------------------
+
+// Horizontal color gradient strip, e.g. a heatmap legend.
+pub struct UiColorRamp {
+    colors: Vec<Color>,
+    size: Vec2,
+}
+
+impl UiWidget for UiColorRamp {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn draw(&mut self, context: &mut dyn UiWidgetContext) {
+        debug_assert!(context.is_inside_widget_window());
+
+        let ui = context.ui_sys().ui();
+
+        // Reserve the space, then paint over it.
+        ui.dummy(self.size.to_array());
+
+        if self.colors.is_empty() {
+            return;
+        }
+
+        let draw_list = ui.get_window_draw_list();
+        let min = Vec2::from_array(ui.item_rect_min());
+        let max = Vec2::from_array(ui.item_rect_max());
+
+        if self.colors.len() == 1 {
+            draw_list.add_rect(min.to_array(), max.to_array(), self.colors[0].to_array()).filled(true).build();
+            return;
+        }
+
+        let segment_width = (max.x - min.x) / (self.colors.len() - 1) as f32;
+
+        for (index, pair) in self.colors.windows(2).enumerate() {
+            let left = pair[0].to_array();
+            let right = pair[1].to_array();
+
+            let segment_min = [min.x + (segment_width * index as f32), min.y];
+            let segment_max = [min.x + (segment_width * (index + 1) as f32), max.y];
+
+            draw_list.add_rect_filled_multicolor(segment_min, segment_max, left, right, right, left);
+        }
+    }
+
+    fn measure(&self, _context: &dyn UiWidgetContext) -> Vec2 {
+        self.size
+    }
+}
+
+impl UiColorRamp {
+    pub fn new(_context: &mut dyn UiWidgetContext, params: UiColorRampParams) -> Self {
+        debug_assert!(params.size.x > 0.0 && params.size.y > 0.0);
+        Self { colors: params.colors, size: params.size }
+    }
+
+    #[inline]
+    pub fn set_colors(&mut self, colors: Vec<Color>) {
+        self.colors = colors;
+    }
+}
+
+// ----------------------------------------------
+// UiLineChartParams
+// ----------------------------------------------
+
+#[derive(Default)]
+pub struct UiLineChartParams {
+    pub values: Vec<f32>, // Plotted left to right.
+    pub size: Vec2,
+    pub line_color: Option<Color>,
+    pub frame_color: Option<Color>,
+    pub scale_min: Option<f32>, // Vertical range. Fits the values if not set.
+    pub scale_max: Option<f32>,
+}
+
+// ----------------------------------------------
+// UiLineChart
+// ----------------------------------------------
+
+// Simple line graph of a series of values, e.g. a stat over time.
+pub struct UiLineChart {
+    values: Vec<f32>,
+    size: Vec2,
+    line_color: Color,
+    frame_color: Option<Color>,
+    scale_min: Option<f32>,
+    scale_max: Option<f32>,
+}
+
+impl UiWidget for UiLineChart {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn draw(&mut self, context: &mut dyn UiWidgetContext) {
+        debug_assert!(context.is_inside_widget_window());
+
+        let ui = context.ui_sys().ui();
+
+        // Reserve the space, then paint over it.
+        ui.dummy(self.size.to_array());
+
+        let draw_list = ui.get_window_draw_list();
+        let min = Vec2::from_array(ui.item_rect_min());
+        let max = Vec2::from_array(ui.item_rect_max());
+
+        if let Some(frame_color) = self.frame_color {
+            draw_list.add_rect(min.to_array(), max.to_array(), frame_color.to_array()).build();
+        }
+
+        if self.values.len() < 2 {
+            return;
+        }
+
+        let (scale_min, scale_max) = self.scale_range();
+        let inv_range = 1.0 / (scale_max - scale_min);
+        let step_x = (max.x - min.x) / (self.values.len() - 1) as f32;
+
+        let points: Vec<[f32; 2]> = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let t = ((value - scale_min) * inv_range).clamp(0.0, 1.0);
+                [min.x + (step_x * index as f32), max.y - ((max.y - min.y) * t)]
+            })
+            .collect();
+
+        draw_list.add_polyline(points, self.line_color.to_array()).thickness(2.0).build();
+    }
+
+    fn measure(&self, _context: &dyn UiWidgetContext) -> Vec2 {
+        self.size
+    }
+}
+
+impl UiLineChart {
+    pub fn new(_context: &mut dyn UiWidgetContext, params: UiLineChartParams) -> Self {
+        debug_assert!(params.size.x > 0.0 && params.size.y > 0.0);
+        Self {
+            values: params.values,
+            size: params.size,
+            line_color: params.line_color.unwrap_or_else(Color::white),
+            frame_color: params.frame_color,
+            scale_min: params.scale_min,
+            scale_max: params.scale_max,
+        }
+    }
+
+    #[inline]
+    pub fn set_values(&mut self, values: Vec<f32>) {
+        self.values = values;
+    }
+
+    fn scale_range(&self) -> (f32, f32) {
+        let scale_min = self.scale_min.unwrap_or_else(|| self.values.iter().copied().fold(f32::INFINITY, f32::min));
+        let scale_max = self.scale_max.unwrap_or_else(|| self.values.iter().copied().fold(f32::NEG_INFINITY, f32::max));
+
+        // Flat series; center the line.
+        if scale_max - scale_min <= f32::EPSILON { (scale_min - 1.0, scale_max + 1.0) } else { (scale_min, scale_max) }
+    }
+}
+
 // ----------------------------------------------
 // UiSpriteIconParams
 // ----------------------------------------------
@@ -2302,6 +2488,10 @@ impl UiSpriteIcon {
         self.sprite = Some(sprite);
     }
 
+    pub fn clear_sprite(&mut self) {
+        self.sprite = None;
+    }
+
     pub fn set_tex_coords(&mut self, tex_coords: RectTexCoords) {
         self.tex_coords = tex_coords;
     }
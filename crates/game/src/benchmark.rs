@@ -0,0 +1,505 @@
+// Performance benchmark run mode, enabled with the `--benchmark` command line argument.
+//
+// Starts a session on a procedurally generated city (see `create_synthetic_city_tile_map`),
+// circles the camera around the map for a fixed duration, then writes frame and simulation
+// time percentiles to a JSON report and quits. Optional arguments:
+//
+//   --benchmark-secs=<N>       Measured run duration in seconds.
+//   --benchmark-map-size=<N>   Map width and height in cells.
+//   --benchmark-buildings=<N>  Number of buildings to place.
+//   --benchmark-units=<N>      Number of units to place.
+//   --benchmark-report=<PATH>  Report file. Defaults to `benchmarks/benchmark_<N>.json`.
+
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use common::{
+    Size,
+    Vec2,
+    coords::{self, Cell, CellF32, IsoPointF32},
+    format_fixed_string,
+    time::{Milliseconds, Seconds},
+};
+use engine::{
+    file_sys::{self, paths},
+    log,
+    save,
+};
+
+use crate::{
+    GameLoopStats,
+    config::{GameConfigs, LoadMapSetting},
+    sim::{RngStream, Simulation},
+    unit::config::UnitConfigKey,
+    world::{World, object::{Spawner, SpawnerResult}},
+    tile::{
+        self,
+        TileFlags,
+        TileMap,
+        TileMapLayerKind,
+        sets::{TileDef, TileSets},
+        road,
+    },
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const BENCHMARK_REPORTS_DIR: &str = "benchmarks";
+
+const DEFAULT_DURATION_SECS: Seconds = 60.0;
+const DEFAULT_MAP_SIZE_IN_CELLS: i32 = 192;
+const DEFAULT_BUILDING_COUNT: u32 = 3000;
+const DEFAULT_UNIT_COUNT: u32 = 2000;
+
+// Frames skipped before measuring, so first-frame costs (path graph build,
+// tile texture streaming) don't skew the results.
+const WARMUP_FRAMES: u32 = 120;
+
+// The camera circles the map center once every CAMERA_LAP_SECS.
+const CAMERA_LAP_SECS: Seconds = 30.0;
+const CAMERA_PATH_RADIUS: f32 = 0.3; // Fraction of the map size.
+
+// Road grid spacing. Each city block is (CITY_BLOCK_STRIDE - 1)^2 cells.
+const CITY_BLOCK_STRIDE: i32 = 8;
+
+// ----------------------------------------------
+// BenchmarkSettings
+// ----------------------------------------------
+
+#[derive(Clone)]
+pub struct BenchmarkSettings {
+    pub duration_secs: Seconds,
+    pub map_size_in_cells: Size,
+    pub building_count: u32,
+    pub unit_count: u32,
+    pub report_path: Option<PathBuf>,
+}
+
+impl Default for BenchmarkSettings {
+    fn default() -> Self {
+        Self {
+            duration_secs: DEFAULT_DURATION_SECS,
+            map_size_in_cells: Size::new(DEFAULT_MAP_SIZE_IN_CELLS, DEFAULT_MAP_SIZE_IN_CELLS),
+            building_count: DEFAULT_BUILDING_COUNT,
+            unit_count: DEFAULT_UNIT_COUNT,
+            report_path: None,
+        }
+    }
+}
+
+impl BenchmarkSettings {
+    // None unless `--benchmark` is among the arguments.
+    fn from_args(args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut enabled = false;
+        let mut settings = Self::default();
+
+        for arg in args {
+            let (name, value) = arg.split_once('=').unwrap_or((&arg, ""));
+            match name {
+                "--benchmark"           => enabled = true,
+                "--benchmark-secs"      => settings.duration_secs = parse_arg(name, value, settings.duration_secs),
+                "--benchmark-buildings" => settings.building_count = parse_arg(name, value, settings.building_count),
+                "--benchmark-units"     => settings.unit_count = parse_arg(name, value, settings.unit_count),
+                "--benchmark-report"    => settings.report_path = Some(PathBuf::from(value)),
+                "--benchmark-map-size"  => {
+                    let size = parse_arg(name, value, settings.map_size_in_cells.width).max(CITY_BLOCK_STRIDE);
+                    settings.map_size_in_cells = Size::new(size, size);
+                }
+                _ => {}
+            }
+        }
+
+        enabled.then_some(settings)
+    }
+}
+
+fn parse_arg<T: std::str::FromStr + Copy>(name: &str, value: &str, default_value: T) -> T {
+    value.parse().unwrap_or_else(|_| {
+        log::error!(log::channel!("benchmark"), "Invalid value for {name}: '{value}'. Using default.");
+        default_value
+    })
+}
+
+// ----------------------------------------------
+// Benchmark
+// ----------------------------------------------
+
+enum BenchmarkState {
+    WaitingForSession,
+    WarmingUp { frames_left: u32 },
+    Running { elapsed_secs: Seconds },
+}
+
+pub struct Benchmark {
+    settings: BenchmarkSettings,
+    state: BenchmarkState,
+    frame_times_ms: Vec<Milliseconds>,
+    sim_times_ms: Vec<Milliseconds>,
+}
+
+impl Benchmark {
+    pub fn from_command_line() -> Option<Self> {
+        let settings = BenchmarkSettings::from_args(std::env::args())?;
+
+        log::info!(
+            log::channel!("benchmark"),
+            "--- Benchmark: {}s, map {}, {} buildings, {} units ---",
+            settings.duration_secs,
+            settings.map_size_in_cells,
+            settings.building_count,
+            settings.unit_count
+        );
+
+        Some(Self {
+            settings,
+            state: BenchmarkState::WaitingForSession,
+            frame_times_ms: Vec::new(),
+            sim_times_ms: Vec::new(),
+        })
+    }
+
+    pub fn load_map_setting(&self) -> LoadMapSetting {
+        LoadMapSetting::SyntheticCity {
+            size_in_cells: self.settings.map_size_in_cells,
+            building_count: self.settings.building_count,
+            unit_count: self.settings.unit_count,
+        }
+    }
+
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, BenchmarkState::Running { .. })
+    }
+
+    // Advances the run once the benchmark session is up. Returns true when the measured duration is over.
+    pub fn update(&mut self, delta_time_secs: Seconds) -> bool {
+        match &mut self.state {
+            BenchmarkState::WaitingForSession => {
+                self.state = BenchmarkState::WarmingUp { frames_left: WARMUP_FRAMES };
+            }
+            BenchmarkState::WarmingUp { frames_left } => {
+                *frames_left = frames_left.saturating_sub(1);
+                if *frames_left == 0 {
+                    log::info!(log::channel!("benchmark"), "Benchmark: warm up done, measuring ...");
+                    self.state = BenchmarkState::Running { elapsed_secs: 0.0 };
+                }
+            }
+            BenchmarkState::Running { elapsed_secs } => {
+                *elapsed_secs += delta_time_secs;
+                return *elapsed_secs >= self.settings.duration_secs;
+            }
+        }
+        false
+    }
+
+    pub fn record_frame(&mut self, stats: &GameLoopStats) {
+        if self.is_running() {
+            self.frame_times_ms.push(stats.total_frame_time_ms);
+            self.sim_times_ms.push(stats.sim_frame_time_ms);
+        }
+    }
+
+    // Scripted camera path: a circle around the map center.
+    pub fn camera_position(&self, map_size_in_cells: Size) -> IsoPointF32 {
+        let elapsed_secs = match self.state {
+            BenchmarkState::Running { elapsed_secs } => elapsed_secs,
+            _ => 0.0,
+        };
+
+        let map_size = map_size_in_cells.to_vec2();
+        let angle = (elapsed_secs / CAMERA_LAP_SECS) * std::f32::consts::TAU;
+        let offset = Vec2::new(angle.cos(), angle.sin()) * map_size * CAMERA_PATH_RADIUS;
+
+        coords::cell_to_iso_f32(CellF32((map_size * 0.5) + offset))
+    }
+
+    pub fn write_report(&self, world: &World) -> bool {
+        let report = BenchmarkReport {
+            game_version: crate::version(),
+            map_size_in_cells: self.settings.map_size_in_cells,
+            building_count: world.buildings_stats().0,
+            unit_count: world.units_stats().0,
+            duration_secs: self.settings.duration_secs,
+            frame_count: self.frame_times_ms.len(),
+            frame_time: TimingPercentiles::from_samples(&self.frame_times_ms),
+            sim_time: TimingPercentiles::from_samples(&self.sim_times_ms),
+        };
+
+        let file_path = match &self.settings.report_path {
+            Some(report_path) => report_path.clone(),
+            None => default_report_path(),
+        };
+
+        if let Some(parent_dir) = file_path.parent() {
+            let _ = file_sys::create_path(parent_dir);
+        }
+
+        let mut state = save::new_json_save_state(true);
+
+        if let Err(err) = state.save(&report) {
+            log::error!(log::channel!("benchmark"), "Failed to serialize benchmark report: {err}");
+            return false;
+        }
+
+        if let Err(err) = state.write_file(&file_path) {
+            log::error!(log::channel!("benchmark"), "Failed to write benchmark report {}: {err}", file_path.display());
+            return false;
+        }
+
+        log::info!(
+            log::channel!("benchmark"),
+            "--- Benchmark done: {} frames, p50 {:.2}ms, p99 {:.2}ms. Report saved to {} ---",
+            report.frame_count,
+            report.frame_time.p50_ms,
+            report.frame_time.p99_ms,
+            file_path.display()
+        );
+        true
+    }
+}
+
+// First free `benchmarks/benchmark_<N>.json`.
+fn default_report_path() -> PathBuf {
+    let reports_dir = paths::base_path().join(BENCHMARK_REPORTS_DIR);
+    (0..)
+        .map(|index| reports_dir.join(format_fixed_string!(64, "benchmark_{index:03}.json")))
+        .find(|file_path| !file_sys::exists(file_path))
+        .unwrap()
+        .to_path_buf()
+}
+
+// ----------------------------------------------
+// BenchmarkReport
+// ----------------------------------------------
+
+#[derive(Default, Serialize)]
+struct TimingPercentiles {
+    min_ms: Milliseconds,
+    avg_ms: Milliseconds,
+    p50_ms: Milliseconds,
+    p90_ms: Milliseconds,
+    p95_ms: Milliseconds,
+    p99_ms: Milliseconds,
+    max_ms: Milliseconds,
+}
+
+impl TimingPercentiles {
+    fn from_samples(samples: &[Milliseconds]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        // Nearest-rank percentile.
+        let percentile = |p: f32| {
+            let rank = ((p * sorted.len() as f32).ceil() as usize).clamp(1, sorted.len());
+            sorted[rank - 1]
+        };
+
+        Self {
+            min_ms: sorted[0],
+            avg_ms: sorted.iter().sum::<Milliseconds>() / sorted.len() as Milliseconds,
+            p50_ms: percentile(0.50),
+            p90_ms: percentile(0.90),
+            p95_ms: percentile(0.95),
+            p99_ms: percentile(0.99),
+            max_ms: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct BenchmarkReport {
+    game_version: &'static str,
+    map_size_in_cells: Size,
+    building_count: usize, // Spawned at the end of the run.
+    unit_count: usize,
+    duration_secs: Seconds,
+    frame_count: usize,
+    frame_time: TimingPercentiles,
+    sim_time: TimingPercentiles,
+}
+
+// ----------------------------------------------
+// Synthetic city generator
+// ----------------------------------------------
+
+// Grass map with a dirt road grid, city blocks packed with buildings and units placed along the roads.
+// Layout is deterministic for a given size and counts, so reports are comparable between runs.
+pub fn create_synthetic_city_tile_map(
+    world: &mut World,
+    map_size_in_cells: Size,
+    building_count: u32,
+    unit_count: u32,
+) -> TileMap {
+    log::info!(
+        log::channel!("benchmark"),
+        "Creating synthetic city tile map. Size: {map_size_in_cells}, Buildings: {building_count}, Units: {unit_count}"
+    );
+
+    let configs = GameConfigs::get();
+    let mut tile_map = TileMap::new(map_size_in_cells, None);
+
+    // Create a temp Simulation instance so we can create a SimContext to spawn the tiles.
+    let mut sim = Simulation::new(map_size_in_cells, configs);
+    let context = sim.new_sim_context(0.0, &mut tile_map, world);
+
+    let mut spawner = Spawner::new(&context);
+    spawner.set_subtract_tile_cost(false);
+    spawner.set_check_unlocks(false);
+
+    let is_road_cell = |cell: Cell| (cell.x % CITY_BLOCK_STRIDE) == 0 || (cell.y % CITY_BLOCK_STRIDE) == 0;
+
+    // Terrain & roads:
+    let grass_tile_def = find_terrain_tile("grass");
+    let road_tile_def = road::tile_def(road::RoadKind::Dirt);
+    let mut road_cells = Vec::new();
+
+    for y in 0..map_size_in_cells.height {
+        for x in 0..map_size_in_cells.width {
+            let cell = Cell::new(x, y);
+            let tile_def = if is_road_cell(cell) {
+                road_cells.push(cell);
+                road_tile_def
+            } else if let Some(grass_tile_def) = grass_tile_def {
+                grass_tile_def
+            } else {
+                continue;
+            };
+
+            match spawner.try_spawn_tile_with_def(cell, tile_def) {
+                SpawnerResult::Tile(tile) => {
+                    if tile.has_flags(TileFlags::RandomizePlacement) {
+                        tile.set_random_variation_index(context.rng_stream_mut(RngStream::Placement));
+                    }
+                }
+                SpawnerResult::Err(err) => {
+                    log::error!(
+                        log::channel!("benchmark"),
+                        "Failed to place Terrain tile: {} - {}",
+                        err.reason,
+                        err.message
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Buildings: mostly houses, with a well or market every few placements.
+    let house_tile_def = find_building_tile("house0");
+    let well_tile_def = find_building_tile("small_well");
+    let market_tile_def = find_building_tile("market");
+    let mut buildings_placed = 0;
+
+    'placement: for y in 0..map_size_in_cells.height {
+        for x in 0..map_size_in_cells.width {
+            if buildings_placed >= building_count {
+                break 'placement;
+            }
+
+            let cell = Cell::new(x, y);
+            if is_road_cell(cell) {
+                continue;
+            }
+
+            let tile_def = match buildings_placed % 20 {
+                0     => market_tile_def,
+                1 | 2 => well_tile_def,
+                _     => house_tile_def,
+            };
+
+            let Some(tile_def) = tile_def.or(house_tile_def) else {
+                break 'placement;
+            };
+
+            // Keep buildings inside their block so the road grid stays intact.
+            let size = tile_def.size_in_cells();
+            let end_cell = Cell::new(cell.x + size.width - 1, cell.y + size.height - 1);
+            if (cell.x / CITY_BLOCK_STRIDE) != (end_cell.x / CITY_BLOCK_STRIDE)
+                || (cell.y / CITY_BLOCK_STRIDE) != (end_cell.y / CITY_BLOCK_STRIDE)
+                || end_cell.x >= map_size_in_cells.width
+                || end_cell.y >= map_size_in_cells.height
+            {
+                continue;
+            }
+
+            // Cells already covered by a larger building simply fail to place.
+            if spawner.try_spawn_building_with_tile_def(cell, tile_def).is_ok() {
+                buildings_placed += 1;
+            }
+        }
+    }
+
+    // Units: spread evenly over the road cells.
+    let mut units_placed = 0;
+
+    if !road_cells.is_empty() {
+        let step = (road_cells.len() / unit_count.max(1) as usize).max(1);
+        for index in 0..unit_count as usize {
+            let cell = road_cells[(index * step) % road_cells.len()];
+            if spawner.try_spawn_unit_with_config(cell, UnitConfigKey::Peasant).is_ok() {
+                units_placed += 1;
+            }
+        }
+    }
+
+    log::info!(log::channel!("benchmark"), "Synthetic city: placed {buildings_placed} buildings and {units_placed} units.");
+
+    crate::debug::utils::refresh_cached_tile_visuals(&mut tile_map);
+    tile_map
+}
+
+fn find_terrain_tile(tile_name: &str) -> Option<&'static TileDef> {
+    TileSets::get().find_tile_def_by_name(
+        TileMapLayerKind::Terrain,
+        tile::sets::TERRAIN_LAND_CATEGORY.string,
+        tile_name,
+    )
+}
+
+fn find_building_tile(tile_name: &str) -> Option<&'static TileDef> {
+    TileSets::get().find_tile_def_by_name(
+        TileMapLayerKind::Objects,
+        tile::sets::OBJECTS_BUILDINGS_CATEGORY.string,
+        tile_name,
+    )
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_parsed_from_args() {
+        let args = |list: &[&str]| list.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter();
+
+        assert!(BenchmarkSettings::from_args(args(&["game", "--benchmark-secs=10"])).is_none());
+
+        let benchmark_args = ["game", "--benchmark", "--benchmark-secs=10", "--benchmark-units=abc"];
+        let settings = BenchmarkSettings::from_args(args(&benchmark_args)).unwrap();
+        assert_eq!(settings.duration_secs, 10.0);
+        assert_eq!(settings.unit_count, DEFAULT_UNIT_COUNT); // Invalid value keeps the default.
+    }
+
+    #[test]
+    fn timing_percentiles() {
+        let samples: Vec<Milliseconds> = (1..=100).rev().map(|ms| ms as Milliseconds).collect();
+        let timings = TimingPercentiles::from_samples(&samples);
+
+        assert_eq!(timings.min_ms, 1.0);
+        assert_eq!(timings.max_ms, 100.0);
+        assert_eq!(timings.p50_ms, 50.0);
+        assert_eq!(timings.p99_ms, 99.0);
+        assert_eq!(timings.avg_ms, 50.5);
+    }
+}
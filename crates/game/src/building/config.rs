@@ -8,14 +8,18 @@ use super::{
     BuildingArchetype,
     BuildingArchetypeKind,
     BuildingKind,
+    construction::BuildingConstructionConfig,
+    entrance::BuildingEntranceConfig,
     house::{HouseBuilding, HouseConfig, HouseLevel, HouseLevelConfig},
     producer::{ProducerBuilding, ProducerConfig},
+    production_chain::ProductionChains,
     service::{ServiceBuilding, ServiceConfig},
     storage::{StorageBuilding, StorageConfig},
 };
 use crate::{
     sim::{RandomGenerator, resources::{RESOURCE_KIND_COUNT, ServiceKind}},
     tile::sets::TileDef,
+    world::config_reload::hot_reload_configs,
 };
 
 // ----------------------------------------------
@@ -28,10 +32,65 @@ pub trait BuildingConfig {
     fn tile_def_name_hash(&self) -> StringHash;
     fn post_load(&mut self, index: usize) -> bool;
     fn draw_debug_ui(&self, ui_sys: &UiSystem);
+
+    // Explicit entrance cell, if any. Buildings without one can be accessed from any surrounding cell.
+    fn entrance(&self) -> Option<&BuildingEntranceConfig> {
+        None
+    }
+
+    // Max road distance to houses this building hires workers from.
+    // None falls back to `SimConfigs::workers_search_radius`.
+    fn hiring_radius(&self) -> Option<i32> {
+        None
+    }
+
+    // Employers with higher priority get workers first from the labor market.
+    fn labor_priority(&self) -> u32 {
+        0
+    }
+
+    // Player-placed buildings start as a construction site if set. Appear fully built otherwise.
+    fn construction(&self) -> Option<&BuildingConstructionConfig> {
+        None
+    }
 }
 
 macro_rules! building_config {
-    ($config_struct:ident) => {
+    // Optional trailing features map to config struct fields:
+    //  - `entrance`: `entrance: Option<BuildingEntranceConfig>` field.
+    //  - `hiring_radius`: `hiring_radius: Option<i32>` field.
+    //  - `labor_priority`: `labor_priority: u32` field.
+    //  - `construction`: `construction: Option<BuildingConstructionConfig>` field.
+    ($config_struct:ident $(, $feature:ident)*) => {
+        $crate::building::config::building_config! { @impl $config_struct, {
+            $($crate::building::config::building_config! { @method $feature })*
+        }}
+    };
+    (@method entrance) => {
+        #[inline]
+        fn entrance(&self) -> Option<&$crate::building::entrance::BuildingEntranceConfig> {
+            self.entrance.as_ref()
+        }
+    };
+    (@method hiring_radius) => {
+        #[inline]
+        fn hiring_radius(&self) -> Option<i32> {
+            self.hiring_radius
+        }
+    };
+    (@method labor_priority) => {
+        #[inline]
+        fn labor_priority(&self) -> u32 {
+            self.labor_priority
+        }
+    };
+    (@method construction) => {
+        #[inline]
+        fn construction(&self) -> Option<&$crate::building::construction::BuildingConstructionConfig> {
+            self.construction.as_ref()
+        }
+    };
+    (@impl $config_struct:ident, { $($extra_methods:tt)* }) => {
         impl $crate::building::config::BuildingConfig for $config_struct {
             #[inline]
             fn building_kind(&self) -> $crate::building::BuildingKind {
@@ -83,6 +142,8 @@ macro_rules! building_config {
                 let mut config_copy = self.clone();
                 engine::ui::DrawDebugUi::draw_debug_ui_with_header(&mut config_copy, "Config", ui_sys);
             }
+
+            $($extra_methods)*
         }
     };
 }
@@ -156,6 +217,9 @@ pub struct BuildingConfigs {
     #[serde(skip)]
     storage_mapping: HashMap<BuildingKind, usize>, // BuildingKind => storage_configs[index]
 
+    #[serde(skip)]
+    production_chains: ProductionChains, // Derived from producer_configs.
+
     // Default fallback configs:
     #[serde(skip)]
     default_house_level_config: HouseLevelConfig,
@@ -263,12 +327,49 @@ impl BuildingConfigs {
         }
     }
 
+    #[inline]
+    pub fn production_chains(&'static self) -> &'static ProductionChains {
+        &self.production_chains
+    }
+
     pub fn find_building_archetype_kind_for_tile_def(&'static self, tile_def: &TileDef) -> Option<BuildingArchetypeKind> {
         debug_assert!(tile_def.hash != hash::NULL_HASH);
 
         self.tile_def_mapping.get(&tile_def.hash).map(|entry| entry.archetype_kind)
     }
 
+    pub fn find_building_kind_for_tile_def(&'static self, tile_def: &TileDef) -> Option<BuildingKind> {
+        debug_assert!(tile_def.hash != hash::NULL_HASH);
+
+        self.tile_def_mapping.get(&tile_def.hash).map(|entry| match entry.archetype_kind {
+            BuildingArchetypeKind::ProducerBuilding => self.producer_configs[entry.index].kind,
+            BuildingArchetypeKind::StorageBuilding  => self.storage_configs[entry.index].kind,
+            BuildingArchetypeKind::ServiceBuilding  => self.service_configs[entry.index].kind,
+            BuildingArchetypeKind::HouseBuilding    => BuildingKind::House,
+        })
+    }
+
+    // (min, max) workers employed by the building placed with this tile. None for houses.
+    pub fn find_workers_for_tile_def(&'static self, tile_def: &TileDef) -> Option<(u32, u32)> {
+        debug_assert!(tile_def.hash != hash::NULL_HASH);
+
+        self.tile_def_mapping.get(&tile_def.hash).and_then(|entry| match entry.archetype_kind {
+            BuildingArchetypeKind::ProducerBuilding => {
+                let config = &self.producer_configs[entry.index];
+                Some((config.min_workers, config.max_workers))
+            }
+            BuildingArchetypeKind::StorageBuilding => {
+                let config = &self.storage_configs[entry.index];
+                Some((config.min_workers, config.max_workers))
+            }
+            BuildingArchetypeKind::ServiceBuilding => {
+                let config = &self.service_configs[entry.index];
+                Some((config.min_workers, config.max_workers))
+            }
+            BuildingArchetypeKind::HouseBuilding => None,
+        })
+    }
+
     fn post_load(&'static mut self) {
         self.house_config.kind = BuildingKind::House;
         self.house_config.post_load(0);
@@ -298,14 +399,24 @@ impl BuildingConfigs {
         }
 
         // HOUSE LEVELS:
+        let mut prev_level_tile_def_name_hash = StringHash::default();
         for (index, config) in &mut self.house_levels.iter_mut().enumerate() {
             config.kind = BuildingKind::House;
+            config.entrance = self.house_config.entrance.clone();
 
             if !config.post_load(index) {
                 // Entries that fail to load will not be visible in the lookup table.
                 continue;
             }
 
+            // Consecutive levels can share a TileDef and only differ by `tile_variation`.
+            // Looking up the TileDef then resolves to the lowest of these levels.
+            let shares_prev_level_tile_def = index != 0 && config.tile_def_name_hash == prev_level_tile_def_name_hash;
+            prev_level_tile_def_name_hash = config.tile_def_name_hash;
+            if shares_prev_level_tile_def {
+                continue;
+            }
+
             let entry = BuildingConfigEntry { archetype_kind: BuildingArchetypeKind::HouseBuilding, index };
 
             if self.tile_def_mapping.insert(config.tile_def_name_hash, entry).is_some() {
@@ -336,6 +447,16 @@ impl BuildingConfigs {
                 continue;
             }
 
+            if config.farm_field.as_ref().is_some_and(|farm_field| !farm_field.validate(&config.name)) {
+                // Falls back to timed production.
+                config.farm_field = None;
+            }
+
+            if config.construction.as_ref().is_some_and(|construction| !construction.validate(&config.name)) {
+                // Placed fully built.
+                config.construction = None;
+            }
+
             let entry = BuildingConfigEntry { archetype_kind: BuildingArchetypeKind::ProducerBuilding, index };
 
             if self.tile_def_mapping.insert(config.tile_def_name_hash, entry).is_some() {
@@ -349,6 +470,8 @@ impl BuildingConfigs {
             }
         }
 
+        self.production_chains = ProductionChains::build(&self.producer_configs);
+
         // SERVICES:
         for (index, config) in &mut self.service_configs.iter_mut().enumerate() {
             if !config.kind.intersects(BuildingKind::services()) || !config.kind.is_single_building() {
@@ -366,6 +489,11 @@ impl BuildingConfigs {
                 continue;
             }
 
+            if config.construction.as_ref().is_some_and(|construction| !construction.validate(&config.name)) {
+                // Placed fully built.
+                config.construction = None;
+            }
+
             let entry = BuildingConfigEntry { archetype_kind: BuildingArchetypeKind::ServiceBuilding, index };
 
             if self.tile_def_mapping.insert(config.tile_def_name_hash, entry).is_some() {
@@ -405,6 +533,11 @@ impl BuildingConfigs {
                 continue;
             }
 
+            if config.construction.as_ref().is_some_and(|construction| !construction.validate(&config.name)) {
+                // Placed fully built.
+                config.construction = None;
+            }
+
             let entry = BuildingConfigEntry { archetype_kind: BuildingArchetypeKind::StorageBuilding, index };
 
             if self.tile_def_mapping.insert(config.tile_def_name_hash, entry).is_some() {
@@ -428,6 +561,25 @@ impl BuildingConfigs {
         }
     }
 
+    // Re-reads the config file and patches the loaded producer, service and storage configs in place.
+    // Houses and entries added, removed or with a different kind are left as is until a restart.
+    // Returns true if any config changed; see `config_reload::reload_object_configs()`.
+    pub fn hot_reload() -> bool {
+        let Some(new_configs) = Self::read_file() else {
+            return false;
+        };
+
+        let configs = Self::get_mut();
+        let mut changed_count = 0;
+
+        changed_count += hot_reload_configs("ProducerConfig", &mut configs.producer_configs, &new_configs.producer_configs);
+        changed_count += hot_reload_configs("ServiceConfig", &mut configs.service_configs, &new_configs.service_configs);
+        changed_count += hot_reload_configs("StorageConfig", &mut configs.storage_configs, &new_configs.storage_configs);
+
+        log::info!(log::channel!("config"), "BuildingConfigs hot-reloaded: {changed_count} configs changed.");
+        changed_count != 0
+    }
+
     pub(crate) fn house_config_mut(&mut self) -> &mut HouseConfig {
         &mut self.house_config
     }
@@ -0,0 +1,368 @@
+use arrayvec::ArrayVec;
+use serde::{Deserialize, Serialize};
+
+use common::{
+    callback::{self, Callback},
+    hash::StrHashPair,
+    time::{CountdownTimer, Seconds},
+};
+use engine::log;
+
+use super::{Building, BuildingContext, BuildingKind};
+use crate::{
+    pathfind::NodeKind as PathNodeKind,
+    sim::{
+        SimCmds,
+        SimContext,
+        resources::{RESOURCE_KIND_COUNT, ResourceKind, ShoppingList, StockItem},
+    },
+    unit::{
+        Unit,
+        UnitTaskHelper,
+        runner::Runner,
+        task::UnitTaskFetchCompletionCallback,
+    },
+    world::object::GameObject,
+};
+
+// ----------------------------------------------
+// BuildingConstructionConfig
+// ----------------------------------------------
+
+// Buildings with this config are placed as a construction site and only become
+// operational once carriers have brought all materials over and the build timer ran out.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BuildingConstructionConfig {
+    // Time (secs) to build once all materials are on site.
+    pub build_time_secs: Seconds,
+
+    // Materials carriers have to fetch from storage before building starts.
+    #[serde(default)]
+    pub materials: Vec<StockItem>,
+
+    // Where carriers fetch the materials from.
+    pub fetch_from_storage_kinds: BuildingKind,
+}
+
+impl BuildingConstructionConfig {
+    pub fn validate(&self, building_name: &str) -> bool {
+        if !self.build_time_secs.is_finite() || self.build_time_secs < 0.0 {
+            log::error!(log::channel!("config"), "'{building_name}': Construction `build_time_secs` cannot be negative!");
+            return false;
+        }
+
+        if self.materials.len() > RESOURCE_KIND_COUNT {
+            log::error!(
+                log::channel!("config"),
+                "'{building_name}': Too many construction materials ({}, max {RESOURCE_KIND_COUNT})!",
+                self.materials.len()
+            );
+            return false;
+        }
+
+        for (index, material) in self.materials.iter().enumerate() {
+            if !material.kind.is_single_resource() || material.count == 0 {
+                log::error!(
+                    log::channel!("config"),
+                    "'{building_name}': Invalid construction material [{index}]: {material}."
+                );
+                return false;
+            }
+
+            if self.materials[..index].iter().any(|other| other.kind == material.kind) {
+                log::error!(
+                    log::channel!("config"),
+                    "'{building_name}': Duplicate construction material '{}'!",
+                    material.kind
+                );
+                return false;
+            }
+        }
+
+        if !self.materials.is_empty() && self.fetch_from_storage_kinds.is_empty() {
+            log::error!(
+                log::channel!("config"),
+                "'{building_name}': Construction materials need a `fetch_from_storage_kinds` to fetch them from!"
+            );
+            return false;
+        }
+
+        true
+    }
+}
+
+// ----------------------------------------------
+// ConstructionMaterial
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct ConstructionMaterial {
+    pub kind: ResourceKind,
+    pub required: u32,
+    pub delivered: u32,
+}
+
+impl ConstructionMaterial {
+    #[inline]
+    pub fn missing(&self) -> u32 {
+        self.required - self.delivered
+    }
+}
+
+// ----------------------------------------------
+// BuildingConstruction
+// ----------------------------------------------
+
+// Construction site state for a building that was just placed. While this is
+// present the building does not run its archetype update, hire workers or trade resources.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BuildingConstruction {
+    materials: ArrayVec<ConstructionMaterial, RESOURCE_KIND_COUNT>,
+    build_timer: CountdownTimer,
+
+    // Carrier out fetching materials, and cooldown before sending another one
+    // after coming back empty-handed.
+    carrier: Runner,
+    fetch_cooldown: CountdownTimer,
+
+    // Variation picked on placement. Restored once the scaffold comes down.
+    finished_variation_index: usize,
+}
+
+impl BuildingConstruction {
+    // Optional tile variation shown while the building is under construction.
+    pub const SCAFFOLD_VARIATION: StrHashPair = StrHashPair::from_str("scaffold");
+
+    // Wait before sending out another carrier when none of the materials could be found.
+    const FETCH_RETRY_SECS: Seconds = 10.0;
+
+    pub fn new(config: &BuildingConstructionConfig, finished_variation_index: usize) -> Self {
+        Self {
+            materials: config
+                .materials
+                .iter()
+                .map(|material| ConstructionMaterial { kind: material.kind, required: material.count, delivered: 0 })
+                .collect(),
+            build_timer: CountdownTimer::new(config.build_time_secs),
+            carrier: Runner::default(),
+            fetch_cooldown: CountdownTimer::new(0.0),
+            finished_variation_index,
+        }
+    }
+
+    pub fn register_callbacks() {
+        let _: Callback<UnitTaskFetchCompletionCallback> = callback::register!(BuildingConstruction::on_materials_fetched);
+    }
+
+    #[inline]
+    pub fn materials(&self) -> &[ConstructionMaterial] {
+        &self.materials
+    }
+
+    #[inline]
+    pub fn has_all_materials(&self) -> bool {
+        self.materials.iter().all(|material| material.missing() == 0)
+    }
+
+    // [0,1] fraction of the required materials delivered so far. 1 if none are required.
+    pub fn materials_progress(&self) -> f32 {
+        let required: u32 = self.materials.iter().map(|material| material.required).sum();
+        let delivered: u32 = self.materials.iter().map(|material| material.delivered).sum();
+        if required == 0 { 1.0 } else { delivered as f32 / required as f32 }
+    }
+
+    #[inline]
+    pub fn build_remaining_secs(&self) -> Seconds {
+        self.build_timer.remaining_secs()
+    }
+
+    #[inline]
+    pub fn finished_variation_index(&self) -> usize {
+        self.finished_variation_index
+    }
+
+    #[inline]
+    pub fn is_waiting_on_carrier(&self) -> bool {
+        self.carrier.is_spawned_or_pending_spawn()
+    }
+
+    // Returns the amount taken, which can be < `count` if not that much was missing.
+    pub fn receive_materials(&mut self, kind: ResourceKind, count: u32) -> u32 {
+        debug_assert!(kind.is_single_resource());
+
+        let Some(material) = self.materials.iter_mut().find(|material| material.kind == kind) else {
+            return 0;
+        };
+
+        let received = count.min(material.missing());
+        material.delivered += received;
+        received
+    }
+
+    // Returns true once the building is finished.
+    pub fn update(&mut self, cmds: &mut SimCmds, context: &BuildingContext, config: &BuildingConstructionConfig) -> bool {
+        let delta_time_secs = context.sim_ctx.delta_time_secs();
+
+        self.carrier.update(cmds);
+        self.carrier.reset_if_unit_lost(context.sim_ctx);
+
+        if !self.has_all_materials() {
+            if self.fetch_cooldown.tick(delta_time_secs) {
+                self.fetch_materials(cmds, context, config);
+            }
+            return false;
+        }
+
+        self.build_timer.tick(delta_time_secs)
+    }
+
+    fn fetch_materials(&mut self, cmds: &mut SimCmds, context: &BuildingContext, config: &BuildingConstructionConfig) {
+        if self.is_waiting_on_carrier() {
+            return; // A carrier is already out fetching materials.
+        }
+
+        // Carrier spawns at the nearest road link.
+        let Some(unit_origin) = context.road_link else {
+            return; // Not connected to a road. No fetching possible!
+        };
+
+        let missing: ArrayVec<StockItem, RESOURCE_KIND_COUNT> = self
+            .materials
+            .iter()
+            .filter(|material| material.missing() != 0)
+            .map(|material| StockItem { kind: material.kind, count: material.missing() })
+            .collect();
+
+        if missing.is_empty() {
+            return;
+        }
+
+        self.carrier.try_fetch_from_storage(
+            cmds,
+            context,
+            unit_origin,
+            config.fetch_from_storage_kinds,
+            ShoppingList::from_items(&missing),
+            callback::create!(BuildingConstruction::on_materials_fetched),
+        );
+    }
+
+    fn on_materials_fetched(_context: &SimContext, this_building: &mut Building, carrier_unit: &mut Unit) {
+        let Some(construction) = this_building.construction.as_mut() else {
+            // Finished while the carrier was out. Fetch task ships the cargo back to a storage.
+            return;
+        };
+
+        debug_assert!(construction.carrier.unit_id() == carrier_unit.id());
+
+        // Any surplus stays in the carrier's inventory -- the fetch
+        // task's recovery path will ship it to a storage that can accept it.
+        if let Some(item) = carrier_unit.peek_inventory() {
+            let received_count = construction.receive_materials(item.kind, item.count);
+            if received_count != 0 {
+                let removed_count = carrier_unit.remove_resources(item.kind, received_count);
+                debug_assert!(removed_count == received_count);
+            }
+        } else {
+            // No storage had any of the materials. Try again in a while.
+            construction.fetch_cooldown.reset(Self::FETCH_RETRY_SECS);
+        }
+
+        construction.carrier.reset();
+    }
+
+    // Construction was cancelled (building demolished before it was finished).
+    // Delivered materials go back to the nearest storages that have room for them.
+    pub fn cancel(&mut self, context: &BuildingContext, config: &BuildingConstructionConfig) {
+        self.carrier.discard_spawn_promise(context.sim_ctx.cmds_mut());
+
+        let Some(start) = context.road_link else {
+            if self.materials.iter().any(|material| material.delivered != 0) {
+                log::info!(log::channel!("building"), "{}: No road access; materials lost.", context.debug_name());
+            }
+            return;
+        };
+
+        for material in &mut self.materials {
+            while material.delivered != 0 {
+                let result = context.sim_ctx.find_nearest_buildings_mut(
+                    start,
+                    config.fetch_from_storage_kinds,
+                    PathNodeKind::Road,
+                    None,
+                    |building, _path| building.receivable_resources(material.kind) == 0, // Skip full storages.
+                );
+
+                let Some((storage, _path)) = result else {
+                    break;
+                };
+
+                let refunded_count = storage.receive_resources(material.kind, material.delivered);
+                if refunded_count == 0 {
+                    break;
+                }
+                material.delivered -= refunded_count;
+            }
+
+            if material.delivered != 0 {
+                log::info!(
+                    log::channel!("building"),
+                    "{}: No storage room to refund {} {}.",
+                    context.debug_name(),
+                    material.delivered,
+                    material.kind
+                );
+            }
+        }
+    }
+
+    pub fn pre_save(&mut self, cmds: &mut SimCmds) {
+        self.carrier.pre_save(cmds);
+    }
+
+    pub fn post_save(&mut self) {
+        self.carrier.post_save();
+    }
+
+    pub fn post_load(&mut self) {
+        self.carrier.post_load();
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> BuildingConstructionConfig {
+        BuildingConstructionConfig {
+            build_time_secs: 5.0,
+            materials: vec![
+                StockItem { kind: ResourceKind::Wood, count: 4 },
+                StockItem { kind: ResourceKind::Bricks, count: 2 },
+            ],
+            fetch_from_storage_kinds: BuildingKind::StorageYard,
+        }
+    }
+
+    #[test]
+    fn receive_materials_caps_at_required_count() {
+        let config = test_config();
+        assert!(config.validate("Test Building"));
+
+        let mut construction = BuildingConstruction::new(&config, 0);
+        assert!(!construction.has_all_materials());
+
+        assert_eq!(construction.receive_materials(ResourceKind::Wood, 3), 3);
+        assert_eq!(construction.receive_materials(ResourceKind::Wood, 3), 1);
+        assert_eq!(construction.receive_materials(ResourceKind::Rice, 3), 0);
+        assert!(!construction.has_all_materials());
+
+        assert_eq!(construction.receive_materials(ResourceKind::Bricks, 2), 2);
+        assert!(construction.has_all_materials());
+        assert_eq!(construction.materials_progress(), 1.0);
+    }
+}
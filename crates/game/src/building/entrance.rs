@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
+
+use common::coords::{Cell, CellRange};
+
+use crate::pathfind::{Graph, Node, NodeKind as PathNodeKind};
+
+// ----------------------------------------------
+// BuildingFacing
+// ----------------------------------------------
+
+// Side of the building footprint an entrance opens to.
+// Same isometric directions as unit movement: +X = NE, -X = SW, +Y = NW, -Y = SE.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Display, EnumCount, EnumIter, Serialize, Deserialize)]
+pub enum BuildingFacing {
+    #[default]
+    SE,
+    SW,
+    NW,
+    NE,
+}
+
+impl BuildingFacing {
+    // Facing after `steps` quarter turns clockwise.
+    #[inline]
+    pub fn rotated(self, steps: usize) -> Self {
+        Self::iter().nth((self as usize + steps) % Self::COUNT).unwrap()
+    }
+}
+
+// ----------------------------------------------
+// BuildingEntranceConfig
+// ----------------------------------------------
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BuildingEntranceConfig {
+    // Side of the footprint the entrance is on in the default orientation.
+    pub facing: BuildingFacing,
+
+    // Offset along that side, from the side's first cell. Clamped to the side length.
+    #[serde(default)]
+    pub offset: i32,
+
+    // If set the building can be reoriented so its entrance faces a road.
+    #[serde(default)]
+    pub rotatable: bool,
+
+    // Optional door-facing tile variation index for each facing.
+    // Facings not listed keep the tile's current variation.
+    #[serde(default)]
+    pub door_variations: Vec<(BuildingFacing, usize)>,
+}
+
+impl BuildingEntranceConfig {
+    // Entrance cell just outside the footprint for the given facing.
+    pub fn entrance_cell(&self, cell_range: CellRange, facing: BuildingFacing) -> Cell {
+        debug_assert!(cell_range.is_valid());
+
+        let x = (cell_range.start.x + self.offset).min(cell_range.end.x);
+        let y = (cell_range.start.y + self.offset).min(cell_range.end.y);
+
+        match facing {
+            BuildingFacing::SE => Cell::new(x, cell_range.start.y - 1),
+            BuildingFacing::SW => Cell::new(cell_range.start.x - 1, y),
+            BuildingFacing::NW => Cell::new(x, cell_range.end.y + 1),
+            BuildingFacing::NE => Cell::new(cell_range.end.x + 1, y),
+        }
+    }
+
+    // Finds a facing whose entrance cell is a road, trying `current_facing` first.
+    // Only the current facing is considered if the building cannot be reoriented.
+    pub fn find_road_facing(
+        &self,
+        graph: &Graph,
+        cell_range: CellRange,
+        current_facing: BuildingFacing,
+    ) -> Option<(BuildingFacing, Cell)> {
+        let rotations = if self.rotatable { BuildingFacing::COUNT } else { 1 };
+
+        for steps in 0..rotations {
+            let facing = current_facing.rotated(steps);
+            let cell = self.entrance_cell(cell_range, facing);
+
+            if graph.node_kind(Node::new(cell)).is_some_and(|kind| kind.intersects(PathNodeKind::Road)) {
+                return Some((facing, cell));
+            }
+        }
+
+        None
+    }
+
+    #[inline]
+    pub fn door_variation(&self, facing: BuildingFacing) -> Option<usize> {
+        self.door_variations
+            .iter()
+            .find(|(variation_facing, _)| *variation_facing == facing)
+            .map(|(_, variation_index)| *variation_index)
+    }
+}
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use common::hash::StrHashPair;
+use engine::log;
+use proc_macros::DrawDebugUi;
+
+use crate::sim::calendar::Calendar;
+
+// ----------------------------------------------
+// FarmFieldStage
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug, Display, Serialize, Deserialize)]
+pub enum FarmFieldStage {
+    #[default]
+    Sowing,  // Workers plant the field.
+    Growing, // Crops grow on their own, at the rate of the current month.
+    Harvest, // Crops are ripe; workers collect them.
+}
+
+impl FarmFieldStage {
+    // Optional anim sets the farm tile switches to at each stage.
+    // Tiles that don't define them keep their current anim.
+    #[inline]
+    pub fn anim_set(self) -> StrHashPair {
+        match self {
+            Self::Sowing  => StrHashPair::from_str("sowing"),
+            Self::Growing => StrHashPair::from_str("growing"),
+            Self::Harvest => StrHashPair::from_str("harvest"),
+        }
+    }
+}
+
+// ----------------------------------------------
+// FarmFieldConfig
+// ----------------------------------------------
+
+#[derive(Clone, DrawDebugUi, Serialize, Deserialize)]
+pub struct FarmFieldConfig {
+    // Stage lengths in in-game days, at full staffing and growth rate.
+    pub sowing_days: f32,
+    pub growing_days: f32,
+    pub harvest_days: f32,
+
+    // Growth rate multiplier for each month of the year ([Calendar::MONTHS_PER_YEAR] entries).
+    // Zero stops growth for that month (e.g. winter). Full rate all year round if empty.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub growth_by_month: Vec<f32>,
+}
+
+impl FarmFieldConfig {
+    pub fn validate(&self, producer_name: &str) -> bool {
+        if self.sowing_days <= 0.0 || self.growing_days <= 0.0 || self.harvest_days <= 0.0 {
+            log::error!(
+                log::channel!("config"),
+                "ProducerConfig '{producer_name}': Farm field stage lengths must be greater than zero!"
+            );
+            return false;
+        }
+
+        if !self.growth_by_month.is_empty() && self.growth_by_month.len() != Calendar::MONTHS_PER_YEAR as usize {
+            log::error!(
+                log::channel!("config"),
+                "ProducerConfig '{producer_name}': Farm field `growth_by_month` must have {} entries, got {}!",
+                Calendar::MONTHS_PER_YEAR,
+                self.growth_by_month.len()
+            );
+            return false;
+        }
+
+        if self.growth_by_month.iter().any(|rate| !rate.is_finite() || *rate < 0.0) {
+            log::error!(
+                log::channel!("config"),
+                "ProducerConfig '{producer_name}': Farm field `growth_by_month` rates cannot be negative!"
+            );
+            return false;
+        }
+
+        true
+    }
+
+    #[inline]
+    pub fn growth_rate(&self, month_of_year: u32) -> f32 {
+        self.growth_by_month.get(month_of_year as usize).copied().unwrap_or(1.0)
+    }
+
+    #[inline]
+    fn stage_days(&self, stage: FarmFieldStage) -> f32 {
+        match stage {
+            FarmFieldStage::Sowing  => self.sowing_days,
+            FarmFieldStage::Growing => self.growing_days,
+            FarmFieldStage::Harvest => self.harvest_days,
+        }
+    }
+
+    #[inline]
+    fn cycle_days(&self) -> f32 {
+        self.sowing_days + self.growing_days + self.harvest_days
+    }
+}
+
+// ----------------------------------------------
+// FarmField
+// ----------------------------------------------
+
+// Crop cycle of a farm's field: sowing -> growing -> harvest, then back to sowing.
+// Producers with a field yield their output once per harvest instead of on a timer.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct FarmField {
+    stage: FarmFieldStage,
+    stage_days_elapsed: f32,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum FarmFieldUpdate {
+    None,
+    StageChanged(FarmFieldStage), // Entered a new stage.
+    Harvested,                    // Crop collected; back to sowing.
+}
+
+impl FarmField {
+    // `work_efficiency` scales sowing and harvesting (done by workers). Growing only depends on the month.
+    // Harvesting waits while `can_harvest` is false, e.g. no room left in the farm's stock.
+    pub fn update(
+        &mut self,
+        config: &FarmFieldConfig,
+        elapsed_days: f32,
+        work_efficiency: f32,
+        month_of_year: u32,
+        can_harvest: bool,
+    ) -> FarmFieldUpdate {
+        let rate = match self.stage {
+            FarmFieldStage::Sowing  => work_efficiency,
+            FarmFieldStage::Growing => config.growth_rate(month_of_year),
+            FarmFieldStage::Harvest => if can_harvest { work_efficiency } else { 0.0 },
+        };
+
+        self.stage_days_elapsed += elapsed_days * rate;
+
+        if self.stage_days_elapsed < config.stage_days(self.stage) {
+            return FarmFieldUpdate::None;
+        }
+
+        self.stage_days_elapsed = 0.0;
+
+        match self.stage {
+            FarmFieldStage::Sowing => {
+                self.stage = FarmFieldStage::Growing;
+                FarmFieldUpdate::StageChanged(self.stage)
+            }
+            FarmFieldStage::Growing => {
+                self.stage = FarmFieldStage::Harvest;
+                FarmFieldUpdate::StageChanged(self.stage)
+            }
+            FarmFieldStage::Harvest => {
+                self.stage = FarmFieldStage::Sowing;
+                FarmFieldUpdate::Harvested
+            }
+        }
+    }
+
+    #[inline]
+    pub fn stage(&self) -> FarmFieldStage {
+        self.stage
+    }
+
+    // Progress through the whole crop cycle, [0,1].
+    pub fn cycle_progress(&self, config: &FarmFieldConfig) -> f32 {
+        let days_before_stage = match self.stage {
+            FarmFieldStage::Sowing  => 0.0,
+            FarmFieldStage::Growing => config.sowing_days,
+            FarmFieldStage::Harvest => config.sowing_days + config.growing_days,
+        };
+
+        ((days_before_stage + self.stage_days_elapsed) / config.cycle_days()).clamp(0.0, 1.0)
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> FarmFieldConfig {
+        let mut growth_by_month = vec![1.0; Calendar::MONTHS_PER_YEAR as usize];
+        growth_by_month[11] = 0.0; // Nothing grows in the last month.
+        FarmFieldConfig { sowing_days: 2.0, growing_days: 4.0, harvest_days: 1.0, growth_by_month }
+    }
+
+    #[test]
+    fn crop_cycle_stages() {
+        let config = test_config();
+        assert!(config.validate("Test Farm"));
+
+        let mut field = FarmField::default();
+        assert_eq!(field.update(&config, 2.0, 1.0, 0, true), FarmFieldUpdate::StageChanged(FarmFieldStage::Growing));
+
+        // Growth stops in winter, regardless of workers.
+        assert_eq!(field.update(&config, 10.0, 1.0, 11, true), FarmFieldUpdate::None);
+        assert_eq!(field.update(&config, 4.0, 0.0, 0, true), FarmFieldUpdate::StageChanged(FarmFieldStage::Harvest));
+
+        // Ripe crops wait until they can be collected.
+        assert_eq!(field.update(&config, 5.0, 1.0, 0, false), FarmFieldUpdate::None);
+        assert_eq!(field.update(&config, 1.0, 1.0, 0, true), FarmFieldUpdate::Harvested);
+        assert!(field.stage() == FarmFieldStage::Sowing);
+    }
+
+    #[test]
+    fn understaffed_sowing_is_slower() {
+        let config = test_config();
+
+        let mut field = FarmField::default();
+        assert_eq!(field.update(&config, 2.0, 0.5, 0, true), FarmFieldUpdate::None);
+        assert!((field.cycle_progress(&config) - 1.0 / 7.0).abs() < 0.001);
+        assert_eq!(field.update(&config, 2.0, 0.5, 0, true), FarmFieldUpdate::StageChanged(FarmFieldStage::Growing));
+    }
+}
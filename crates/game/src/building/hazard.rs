@@ -0,0 +1,245 @@
+use serde::{Deserialize, Serialize};
+
+use common::{
+    hash::StrHashPair,
+    time::{CountdownTimer, Seconds},
+};
+
+use super::{Building, BuildingKind};
+use crate::{
+    config::GameConfigs,
+    sim::SimContext,
+    unit::Unit,
+    world::{
+        World,
+        component::{Component, GameObjectKey},
+        object::GameObject,
+    },
+};
+
+// ----------------------------------------------
+// BuildingHazards
+// ----------------------------------------------
+
+// Fire and collapse state for a single building.
+// Updated periodically by the `HazardsSystem`.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct BuildingHazards {
+    // Accumulated risks, in the [0,1] range.
+    fire_risk: f32,
+    collapse_risk: f32,
+
+    // Counts down until the building burns down. None if not on fire.
+    burning: Option<CountdownTimer>,
+
+    // Burned down, collapsed or sacked. Will be replaced by rubble on the next hazards update.
+    destroyed: bool,
+}
+
+impl BuildingHazards {
+    // Optional anim set a building tile switches to while on fire.
+    pub const BURNING_ANIM_SET: StrHashPair = StrHashPair::from_str("burning");
+
+    #[inline]
+    pub fn fire_risk(&self) -> f32 {
+        self.fire_risk
+    }
+
+    #[inline]
+    pub fn collapse_risk(&self) -> f32 {
+        self.collapse_risk
+    }
+
+    #[inline]
+    pub fn is_burning(&self) -> bool {
+        self.burning.is_some()
+    }
+
+    #[inline]
+    pub fn is_destroyed(&self) -> bool {
+        self.destroyed
+    }
+
+    #[inline]
+    pub fn burn_remaining_secs(&self) -> Seconds {
+        self.burning.as_ref().map_or(0.0, |countdown| countdown.remaining_secs())
+    }
+
+    #[inline]
+    pub fn accumulate_risks(&mut self, fire_risk: f32, collapse_risk: f32) {
+        self.fire_risk = (self.fire_risk + fire_risk).clamp(0.0, 1.0);
+        self.collapse_risk = (self.collapse_risk + collapse_risk).clamp(0.0, 1.0);
+    }
+
+    #[inline]
+    pub fn reset_risks(&mut self) {
+        self.fire_risk = 0.0;
+        self.collapse_risk = 0.0;
+    }
+
+    // Returns true once the fire has burned the building down.
+    pub fn tick_burning(&mut self, delta_time_secs: Seconds) -> bool {
+        if let Some(countdown) = &mut self.burning {
+            if countdown.tick(delta_time_secs) {
+                self.burning = None;
+                self.destroyed = true;
+                return true;
+            }
+        }
+        false
+    }
+
+    #[inline]
+    pub fn collapse(&mut self) {
+        self.burning = None;
+        self.destroyed = true;
+    }
+
+    // Patrol units sent out by prevention services (e.g. water carriers) clear the accumulated risks.
+    pub fn visited_by(&mut self, unit: &Unit, context: &SimContext) {
+        if let Some(origin_kind) = unit.patrol_task_building_kind(context) {
+            let configs = &GameConfigs::get().sim;
+            if origin_kind.intersects(configs.fire_prevention_services) {
+                self.fire_risk = 0.0;
+            }
+            if origin_kind.intersects(configs.collapse_prevention_services) {
+                self.collapse_risk = 0.0;
+            }
+        }
+    }
+}
+
+// ----------------------------------------------
+// RaidDamage
+// ----------------------------------------------
+
+// Accumulated damage from raider attacks. Only buildings under attack have any,
+// so it is stored as a World component rather than on every building.
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct RaidDamage {
+    amount: f32,
+}
+
+impl Component for RaidDamage {
+    const NAME: &'static str = "RaidDamage";
+}
+
+impl RaidDamage {
+    #[inline]
+    pub fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    // Returns true once the damage taken reaches the building health.
+    pub fn take(&mut self, amount: f32, building_health: f32) -> bool {
+        debug_assert!(amount >= 0.0);
+
+        if self.amount >= building_health {
+            return false; // Already destroyed.
+        }
+
+        self.amount = (self.amount + amount).min(building_health);
+        self.amount >= building_health
+    }
+}
+
+// ----------------------------------------------
+// Building hazards helpers
+// ----------------------------------------------
+
+impl Building {
+    #[inline]
+    pub fn hazards(&self) -> &BuildingHazards {
+        &self.hazards
+    }
+
+    #[inline]
+    pub fn hazards_mut(&mut self) -> &mut BuildingHazards {
+        &mut self.hazards
+    }
+
+    #[inline]
+    pub fn raid_damage(&self, world: &World) -> f32 {
+        let owner = GameObjectKey::Building(self.kind_and_id());
+        world.components().get::<RaidDamage>(owner).map_or(0.0, RaidDamage::amount)
+    }
+
+    // Adds raider damage. Returns true if this blow sacked the building.
+    pub fn take_raid_damage(&mut self, world: &mut World, amount: f32) -> bool {
+        debug_assert!(self.is_spawned());
+
+        if self.hazards.is_destroyed() {
+            return false;
+        }
+
+        let owner = GameObjectKey::Building(self.kind_and_id());
+        let building_health = GameConfigs::get().sim.building_health;
+
+        let mut damage = world.components().get::<RaidDamage>(owner).copied().unwrap_or_default();
+        let sacked = damage.take(amount, building_health);
+        world.components_mut().insert(owner, damage);
+
+        if sacked {
+            self.hazards.collapse();
+        }
+
+        sacked
+    }
+
+    // Sets the building on fire. Returns false if already burning or destroyed.
+    pub fn set_on_fire(&mut self, context: &SimContext, burn_duration_secs: Seconds) -> bool {
+        debug_assert!(self.is_spawned());
+
+        if self.hazards.is_burning() || self.hazards.is_destroyed() {
+            return false;
+        }
+
+        self.hazards.burning = Some(CountdownTimer::new(burn_duration_secs));
+        self.hazards.fire_risk = 0.0;
+
+        // Switch to the burning anim set if the tile has one.
+        let tile = self.find_tile_mut(context);
+        let tile_def = tile.tile_def();
+        let variation_index = tile.variation_index();
+
+        if variation_index < tile_def.variations.len() {
+            let anim_sets = &tile_def.variations[variation_index].anim_sets;
+            if let Some(index) = anim_sets.iter().position(|anim_set| anim_set.hash == BuildingHazards::BURNING_ANIM_SET.hash) {
+                tile.set_anim_set_index(index);
+            }
+        }
+
+        true
+    }
+
+    // Whether this building is covered by any of the given prevention services.
+    pub fn has_hazard_prevention(&self, context: &SimContext, services: BuildingKind) -> bool {
+        if services.is_empty() {
+            return false;
+        }
+
+        let context = self.new_context(context);
+        (services & BuildingKind::services()).iter().any(|service_kind| context.has_access_to_service(service_kind))
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raider_damage_destroys_building() {
+        let mut damage = RaidDamage::default();
+
+        assert!(!damage.take(60.0, 100.0));
+        assert!(damage.take(60.0, 100.0));
+        assert_eq!(damage.amount(), 100.0);
+
+        // Already destroyed; further blows don't count again.
+        assert!(!damage.take(10.0, 100.0));
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashSet, VecDeque};
+use smallvec::SmallVec;
+
+use common::coords::{Cell, CellRange};
+
+use super::{Building, BuildingKind, BuildingKindAndId};
+use crate::{
+    config::GameConfigs,
+    pathfind::{Node, NodeKind as PathNodeKind},
+    sim::SimContext,
+};
+
+// ----------------------------------------------
+// HiringArea
+// ----------------------------------------------
+
+// Houses an employer can hire workers from, sorted by road distance.
+// Computed with a flood fill over the road network from the employer's
+// road link and cached until the road network or road link changes,
+// or a house is placed or removed within the area's bounds.
+#[derive(Clone, Default)]
+pub struct HiringArea {
+    // Graph road network version the cache was built for. None if never computed.
+    road_network_version: Option<u32>,
+    origin: Cell,
+    radius: i32,
+
+    // Bounding box of all road cells visited by the last flood fill.
+    bounds: CellRange,
+
+    // (house, road distance), nearest first.
+    houses: SmallVec<[(BuildingKindAndId, i32); 16]>,
+}
+
+impl HiringArea {
+    #[inline]
+    pub fn houses(&self) -> &[(BuildingKindAndId, i32)] {
+        &self.houses
+    }
+
+    #[inline]
+    pub fn radius(&self) -> i32 {
+        self.radius
+    }
+
+    #[inline]
+    pub fn is_stale(&self, context: &SimContext, origin: Cell, radius: i32) -> bool {
+        self.road_network_version != Some(context.graph().road_network_version())
+            || self.origin != origin
+            || self.radius != radius
+    }
+
+    #[inline]
+    pub fn invalidate(&mut self) {
+        *self = Self::default();
+    }
+
+    // A house linked to any of our road cells is adjacent to them,
+    // so grow its footprint by one cell before testing against our bounds.
+    pub fn invalidate_if_near(&mut self, house_cells: CellRange) {
+        if self.road_network_version.is_none() {
+            return;
+        }
+
+        let overlaps = house_cells.start.x - 1 <= self.bounds.end.x
+            && house_cells.end.x + 1 >= self.bounds.start.x
+            && house_cells.start.y - 1 <= self.bounds.end.y
+            && house_cells.end.y + 1 >= self.bounds.start.y;
+
+        if overlaps {
+            self.invalidate();
+        }
+    }
+
+    pub fn refresh(&mut self, context: &SimContext, origin: Cell, radius: i32) {
+        debug_assert!(origin.is_valid());
+        debug_assert!(radius > 0);
+
+        let graph = context.graph();
+
+        self.road_network_version = Some(graph.road_network_version());
+        self.origin = origin;
+        self.radius = radius;
+        self.bounds = CellRange::new(origin, origin);
+        self.houses.clear();
+
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+
+        visited.insert(Node::new(origin));
+        queue.push_back((Node::new(origin), 0));
+
+        // Breadth-first, so houses are found in order of increasing road distance.
+        while let Some((node, distance)) = queue.pop_front() {
+            self.bounds.start.x = self.bounds.start.x.min(node.cell.x);
+            self.bounds.start.y = self.bounds.start.y.min(node.cell.y);
+            self.bounds.end.x = self.bounds.end.x.max(node.cell.x);
+            self.bounds.end.y = self.bounds.end.y.max(node.cell.y);
+
+            if graph.node_kind(node).is_some_and(|kind| kind.intersects(PathNodeKind::BuildingRoadLink)) {
+                self.add_houses_linked_to(context, node, distance);
+            }
+
+            if distance < radius {
+                for neighbor in graph.neighbors(node, PathNodeKind::Road) {
+                    if visited.insert(neighbor) {
+                        queue.push_back((neighbor, distance + 1));
+                    }
+                }
+            }
+        }
+    }
+
+    fn add_houses_linked_to(&mut self, context: &SimContext, road_link: Node, distance: i32) {
+        // Buildings can share the same road link tile, so check each neighbor owns it.
+        for neighbor in context.graph().neighbors(road_link, PathNodeKind::Building) {
+            if let Some(house) = context.find_building_for_cell(neighbor.cell) {
+                if house.is(BuildingKind::House) && house.road_link() == Some(road_link.cell) {
+                    let kind_and_id = house.kind_and_id();
+                    if !self.houses.iter().any(|(entry, _)| *entry == kind_and_id) {
+                        self.houses.push((kind_and_id, distance));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ----------------------------------------------
+// HiringTarget
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum HiringTarget {
+    // Hire up to the minimum workers required to operate.
+    MinRequired,
+    // Hire up to full capacity.
+    MaxCapacity,
+}
+
+// ----------------------------------------------
+// Building hiring helpers
+// ----------------------------------------------
+
+impl Building {
+    // Max road distance to houses this building hires workers from.
+    #[inline]
+    pub fn hiring_radius(&self) -> i32 {
+        self.configs().hiring_radius().unwrap_or(GameConfigs::get().sim.workers_search_radius)
+    }
+
+    #[inline]
+    pub fn hiring_area(&self) -> &HiringArea {
+        &self.hiring_area
+    }
+
+    // A house was placed or removed at `house_cells`; drop our cached hiring area if it could be affected.
+    #[inline]
+    pub(crate) fn house_cells_changed(&mut self, house_cells: CellRange) {
+        self.hiring_area.invalidate_if_near(house_cells);
+    }
+
+    // Hires unemployed workers from houses within our hiring radius, nearest first,
+    // until the target is reached or no more workers are available. Returns the number hired.
+    pub fn hire_workers(&mut self, context: &SimContext, target: HiringTarget) -> u32 {
+        let Some(employer) = self.workers().and_then(|workers| workers.as_employer()) else {
+            return 0;
+        };
+
+        let target_count = match target {
+            HiringTarget::MinRequired => employer.min_employees(),
+            HiringTarget::MaxCapacity => employer.max_employees(),
+        };
+
+        let wanted = target_count.saturating_sub(employer.employee_count());
+        if wanted == 0 {
+            return 0;
+        }
+
+        let Some(origin) = self.road_link() else {
+            return 0;
+        };
+
+        let radius = self.hiring_radius();
+        debug_assert!(radius > 0);
+
+        if self.hiring_area.is_stale(context, origin, radius) {
+            self.hiring_area.refresh(context, origin, radius);
+        }
+
+        let mut hired = 0;
+
+        for index in 0..self.hiring_area.houses().len() {
+            let (house_kind_and_id, _distance) = self.hiring_area.houses()[index];
+
+            let Some(house) = context.find_building_mut(house_kind_and_id.kind, house_kind_and_id.id) else {
+                continue; // House despawned since the cache was built.
+            };
+
+            let available = house.workers_count().min(wanted - hired);
+            if available == 0 {
+                continue;
+            }
+
+            let workers_added = self.add_workers(available, house_kind_and_id);
+            let workers_removed = house.remove_workers(workers_added, self.kind_and_id());
+            debug_assert!(workers_added == workers_removed);
+
+            hired += workers_added;
+            if hired >= wanted {
+                break;
+            }
+        }
+
+        hired
+    }
+}
@@ -6,7 +6,7 @@ use strum::{Display, EnumCount, EnumIter};
 
 use common::{
     Color,
-    hash::{self, StringHash},
+    hash::{self, StrHashPair, StringHash},
     time::{Seconds, UpdateTimer},
 };
 use engine::{log, ui::UiSystem};
@@ -22,8 +22,10 @@ use super::{
     BuildingStock,
     house_upgrade,
     config::{BuildingConfig, BuildingConfigs, building_config},
+    entrance::BuildingEntranceConfig,
 };
 use crate::{
+    config::GameConfigs,
     save_context::PostLoadContext,
     world::stats::WorldStats,
     system::settlers::Settler,
@@ -34,16 +36,20 @@ use crate::{
         SimCmds,
         SimCmdQueue,
         commands::ImmediateModeSimCmds,
+        events::{SimEventSource, sim_event},
+        notifications::notify,
         RandomGenerator,
         resources::{
             Population,
             RESOURCE_KIND_COUNT,
+            SERVICE_KIND_COUNT,
             ResourceKind,
             ResourceKinds,
             ServiceKind,
             ServiceKinds,
             StockItem,
             Workers,
+            default_tax_rate_percent,
         },
     },
     unit::{
@@ -77,6 +83,9 @@ pub struct HouseConfig {
     pub upgrade_update_frequency_secs: Seconds,
     pub generate_tax_frequency_secs: Seconds,
 
+    // How long (secs) the house tile plays its "upgrading"/"downgrading" anim after a level change.
+    pub level_transition_secs: Seconds,
+
     // Max units of each resource a house will buy per market vendor visit.
     pub shop_batch_size: u32,
 
@@ -87,6 +96,29 @@ pub struct HouseConfig {
     // Number of residents that leave the city each time the deprivation grace window elapses.
     pub eviction_batch_size: u32,
 
+    // How long (secs) a visit from a service patrol unit (water carrier, vendor, monk, etc)
+    // grants the house access to that service. Access lapses unless a walker passes by again.
+    pub service_coverage_secs: Seconds,
+
+    // Household happiness [0,1] is a weighted sum of needs met, services covered,
+    // tax burden and recent negative events (see `HouseHappiness`).
+    pub happiness_needs_weight: f32,
+    pub happiness_services_weight: f32,
+    pub happiness_tax_weight: f32,
+    pub happiness_events_weight: f32,
+
+    // Negative events knock the events factor down by a penalty, which then
+    // recovers by `happiness_event_recovery_per_update` every upgrade update.
+    pub happiness_event_recovery_per_update: f32,
+    pub happiness_downgrade_penalty: f32,
+    pub happiness_eviction_penalty: f32,
+    // Applied to houses within `happiness_disaster_radius` cells (manhattan) of a building that burned down or collapsed.
+    pub happiness_disaster_penalty: f32,
+    pub happiness_disaster_radius: i32,
+
+    // Households scoring below this are counted as unhappy in the world stats.
+    pub unhappy_household_threshold: f32,
+
     // Base consumption rate per resident, in units per day, keyed by ResourceKind.
     // Kinds not listed here default to 1.0 unit/day (see `consumption_rate_table`).
     #[debug_ui(skip)]
@@ -100,6 +132,10 @@ pub struct HouseConfig {
 
     #[debug_ui(nested)]
     pub ambient_patrol: AmbientPatrolConfig,
+
+    // Explicit entrance cell shared by all house levels. Any surrounding cell gives access if not set.
+    #[debug_ui(skip)]
+    pub entrance: Option<BuildingEntranceConfig>,
 }
 
 impl Default for HouseConfig {
@@ -114,9 +150,21 @@ impl Default for HouseConfig {
             stock_update_frequency_secs: 60.0,
             upgrade_update_frequency_secs: 10.0,
             generate_tax_frequency_secs: 60.0,
+            level_transition_secs: 2.0,
             shop_batch_size: 4,
             deprivation_grace_secs: 200.0,
             eviction_batch_size: 2,
+            service_coverage_secs: 60.0,
+            happiness_needs_weight: 0.4,
+            happiness_services_weight: 0.3,
+            happiness_tax_weight: 0.2,
+            happiness_events_weight: 0.1,
+            happiness_event_recovery_per_update: 0.05,
+            happiness_downgrade_penalty: 0.3,
+            happiness_eviction_penalty: 0.2,
+            happiness_disaster_penalty: 0.5,
+            happiness_disaster_radius: 6,
+            unhappy_household_threshold: 0.4,
             consumption_rates: vec![
                 (ResourceKind::Rice, 1.0),
                 (ResourceKind::Meat, 0.5),
@@ -131,12 +179,13 @@ impl Default for HouseConfig {
                 spawn_chance: 10,
                 max_distance: 40,
             },
+            entrance: None,
         }
     }
 }
 
 building_config! {
-    HouseConfig
+    HouseConfig, entrance
 }
 
 // ----------------------------------------------
@@ -156,6 +205,12 @@ pub struct HouseLevelConfig {
     #[serde(skip)] // Not serialized. Computed on post_load.
     pub tile_def_name_hash: StringHash,
 
+    // Fixed TileDef variation for this level. Consecutive levels can share a TileDef
+    // and only swap variations when upgrading/downgrading. Random variation if unset.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub tile_variation: Option<usize>,
+
     pub max_population: u32,
 
     // Base tax generated per employed resident.
@@ -182,6 +237,11 @@ pub struct HouseLevelConfig {
 
     #[serde(default)]
     pub stock_capacity: u32,
+
+    // Copied from HouseConfig::entrance in BuildingConfigs::post_load.
+    #[debug_ui(skip)]
+    #[serde(skip)]
+    pub entrance: Option<BuildingEntranceConfig>,
 }
 
 impl Default for HouseLevelConfig {
@@ -193,6 +253,7 @@ impl Default for HouseLevelConfig {
             name: "House Level 0".into(),
             tile_def_name: "house0".into(),
             tile_def_name_hash: hash::fnv1a_from_str("house0"),
+            tile_variation: None,
             max_population: 2,
             base_tax_generated: 0,
             tax_bonus: 0,
@@ -201,12 +262,13 @@ impl Default for HouseLevelConfig {
             services_required: ServiceKinds::none(),
             resources_required: ResourceKinds::none(),
             stock_capacity: 5,
+            entrance: None,
         }
     }
 }
 
 building_config! {
-    HouseLevelConfig
+    HouseLevelConfig, entrance
 }
 
 // ----------------------------------------------
@@ -266,15 +328,32 @@ pub struct HouseBuilding {
     pub(crate) upgrade_update_timer: UpdateTimer,
     upgrade_state: HouseUpgradeState,
 
+    // Upgrade/downgrade anim currently playing on the house tile, if any.
+    #[serde(default)]
+    level_transition: Option<HouseLevelTransition>,
+
     // Continuous time (secs) a Level 0 house has lacked access to food and water.
     // Resets to zero once access is restored; once it exceeds the configured grace
     // it drives progressive emigration and blocks organic population growth.
     #[serde(default)]
     deprivation_timer_secs: Seconds,
 
+    // Timed access to services granted by patrol units walking past the house.
+    // Missing from older saves, which get it seeded in post_load().
+    #[serde(default = "HouseServiceCoverage::unseeded")]
+    service_coverage: HouseServiceCoverage,
+
+    // Happiness breakdown, refreshed every upgrade update.
+    #[serde(default)]
+    happiness: HouseHappiness,
+
     pub(crate) generate_tax_timer: UpdateTimer,
     tax_available: u32,
 
+    // City tax rate as of the last upgrade update. Scales the tax generated.
+    #[serde(default = "default_tax_rate_percent")]
+    tax_rate_percent: u32,
+
     // House may spawn an ambient patrol unit every once in a while to wander around the neighborhood.
     pub(crate) ambient_patrol: TimedAmbientPatrol,
 
@@ -299,6 +378,13 @@ impl BuildingBehavior for HouseBuilding {
         self.current_level_config()
     }
 
+    fn spawned(&mut self, context: &BuildingContext) {
+        // Level with a fixed look; override the random variation picked on placement.
+        if self.current_level_config().tile_variation.is_some() {
+            HouseUpgradeState::set_level_variation(context, self.current_level_config());
+        }
+    }
+
     fn despawned(&mut self, context: &BuildingContext) {
         self.ambient_patrol.discard_spawn_promise(context.sim_ctx.cmds_mut());
     }
@@ -307,10 +393,12 @@ impl BuildingBehavior for HouseBuilding {
         let delta_time_secs = context.sim_ctx.delta_time_secs();
 
         self.ambient_patrol.update(cmds);
+        self.service_coverage.update(delta_time_secs);
+        self.level_transition_update(context, delta_time_secs);
 
         // Update house states:
         if self.stock_update_timer.tick(delta_time_secs).should_update() && !self.debug.freeze_stock_update() {
-            self.stock_update();
+            self.stock_update(context);
         }
 
         if self.upgrade_update_timer.tick(delta_time_secs).should_update() && !self.debug.freeze_upgrade_update() {
@@ -321,7 +409,8 @@ impl BuildingBehavior for HouseBuilding {
             self.population_update(cmds, context);
         }
 
-        if self.generate_tax_timer.tick(delta_time_secs).should_update() && !self.debug.freeze_tax_generation() {
+        let tax_delta_secs = context.sim_ctx.calendar().paced_secs(delta_time_secs);
+        if self.generate_tax_timer.tick(tax_delta_secs).should_update() && !self.debug.freeze_tax_generation() {
             self.generate_tax();
         }
 
@@ -331,6 +420,8 @@ impl BuildingBehavior for HouseBuilding {
     }
 
     fn visited_by(&mut self, unit: &mut Unit, context: &BuildingContext) -> BuildingVisitResult {
+        self.mark_service_coverage(unit, context);
+
         if unit.is_settler() {
             self.visited_by_settler(unit, context)
         } else if unit.is_market_vendor(context.sim_ctx) {
@@ -360,6 +451,10 @@ impl BuildingBehavior for HouseBuilding {
         self.upgrade_state.post_load();
         self.generate_tax_timer.post_load(config.generate_tax_frequency_secs);
         self.ambient_patrol.post_load(context, config.ambient_patrol.spawn_frequency_secs);
+
+        if self.service_coverage.needs_seeding() {
+            self.service_coverage.seed_level_services(self.current_level_config(), config.service_coverage_secs);
+        }
     }
 
     // ----------------------
@@ -411,6 +506,8 @@ impl BuildingBehavior for HouseBuilding {
 
         stats.treasury.tax_generated += self.tax_generated();
         stats.treasury.tax_available += self.tax_available();
+
+        stats.add_household_happiness(&self.happiness, self.population.count());
     }
 
     // ----------------------
@@ -546,9 +643,13 @@ impl HouseBuilding {
             consumption_accumulator: [0.0; RESOURCE_KIND_COUNT],
             upgrade_update_timer: UpdateTimer::new(house_config.upgrade_update_frequency_secs),
             upgrade_state,
+            level_transition: None,
             deprivation_timer_secs: 0.0,
+            service_coverage: HouseServiceCoverage::default(),
+            happiness: HouseHappiness::default(),
             generate_tax_timer: UpdateTimer::new(house_config.generate_tax_frequency_secs),
             tax_available: 0,
+            tax_rate_percent: default_tax_rate_percent(),
             ambient_patrol: TimedAmbientPatrol::new(rng, house_config.ambient_patrol.spawn_frequency_secs),
             debug: HouseDebug::default(),
         }
@@ -570,7 +671,7 @@ impl HouseBuilding {
     // Stock Update:
     // ----------------------
 
-    fn stock_update(&mut self) {
+    fn stock_update(&mut self, context: &BuildingContext) {
         // Each required resource is consumed at its own rate, expressed in units
         // per day and scaled by the current household occupancy (per resident).
         // Fractional amounts are carried in `consumption_accumulator` so whole
@@ -578,14 +679,9 @@ impl HouseBuilding {
         let resources_required = &self.upgrade_state.curr_level_config.unwrap().resources_required;
 
         let house_config = BuildingConfigs::get().house_config();
-        let seconds_per_day = crate::config::GameConfigs::get().sim.seconds_per_day;
 
         // Fraction of a day elapsed since the last stock update.
-        let day_frac = if seconds_per_day > 0.0 {
-            house_config.stock_update_frequency_secs / seconds_per_day
-        } else {
-            0.0
-        };
+        let day_frac = context.sim_ctx.calendar().secs_to_days(house_config.stock_update_frequency_secs);
         let population = self.population.count() as f32;
 
         // `iter()` yields combined OR-group flags (e.g. `Meat | Fish`), so we
@@ -730,9 +826,9 @@ impl HouseBuilding {
         debug_assert!(self.upgrade_state.next_level_config.is_some());
 
         // Attempt to upgrade or downgrade based on services and resources availability.
-        if self.upgrade_state.can_upgrade(context, &self.stock) {
+        if self.upgrade_state.can_upgrade(context, &self.stock, &self.service_coverage) {
             cmds.upgrade_house(context.kind_and_id(), HouseUpgradeDirection::Upgrade);
-        } else if self.upgrade_state.can_downgrade(context, &self.stock) {
+        } else if self.upgrade_state.can_downgrade(context, &self.stock, &self.service_coverage) {
             cmds.upgrade_house(context.kind_and_id(), HouseUpgradeDirection::Downgrade);
         }
 
@@ -745,6 +841,8 @@ impl HouseBuilding {
         } else {
             self.deprivation_timer_secs = 0.0; // Only tracked while at Level 0.
         }
+
+        self.happiness_update(context);
     }
 
     // A Level 0 house left without access to food and water for too long gradually
@@ -762,28 +860,97 @@ impl HouseBuilding {
         }
 
         let config = BuildingConfigs::get().house_config();
+        let was_within_grace = self.deprivation_timer_secs < config.deprivation_grace_secs;
         self.deprivation_timer_secs += config.upgrade_update_frequency_secs;
 
         if self.deprivation_timer_secs >= config.deprivation_grace_secs {
+            if was_within_grace {
+                notify!(NoFoodOrWater, context.base_cell(), "Residents leaving: no food or water");
+            }
+
             // Cap so it keeps firing each update for as long as the house stays deprived.
             self.deprivation_timer_secs = config.deprivation_grace_secs;
 
             let emigrated = self.emigrate_population(cmds, context, config.eviction_batch_size);
             if emigrated != 0 {
+                self.happiness.add_negative_event(config.happiness_eviction_penalty);
                 debug_popup_msg_color!(self.debug, Color::red(), "{emigrated} settlers leaving the city");
             }
         }
     }
 
     // A house has its basic needs met when it has access to both a source of water
-    // (a well) and a source of food (a market).
+    // (a well or fountain) and a source of food (a market).
     fn has_basic_needs_access(&self, context: &BuildingContext) -> bool {
-        let has_water = context.has_access_to_service(BuildingKind::SmallWell)
-                           || context.has_access_to_service(BuildingKind::LargeWell);
-        let has_food = context.has_access_to_service(BuildingKind::Market);
+        let coverage = &self.service_coverage;
+        let has_water = coverage.water_access(context) != HouseWaterAccess::None;
+        let has_food = coverage.has_access_to_service(context, BuildingKind::Market);
         has_water && has_food
     }
 
+    // Any service building patrol unit passing by grants this house timed access to its service.
+    fn mark_service_coverage(&mut self, unit: &Unit, context: &BuildingContext) {
+        if unit.is_settler() {
+            return;
+        }
+
+        if let Some(service_kind) = unit.patrol_task_building_kind(context.sim_ctx) {
+            if service_kind.intersects(BuildingKind::services()) {
+                let config = BuildingConfigs::get().house_config();
+                self.service_coverage.mark(service_kind, config.service_coverage_secs);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn service_coverage(&self) -> &HouseServiceCoverage {
+        &self.service_coverage
+    }
+
+    // ----------------------
+    // Happiness:
+    // ----------------------
+
+    #[inline]
+    pub fn happiness(&self) -> &HouseHappiness {
+        &self.happiness
+    }
+
+    // Fires, collapses, evictions, etc. Lowers the events factor, which recovers over time.
+    #[inline]
+    pub fn add_negative_event(&mut self, penalty: f32) {
+        self.happiness.add_negative_event(penalty);
+        debug_popup_msg_color!(self.debug, Color::red(), "Happiness -{penalty:.2}");
+    }
+
+    // Festivals and other good news. Raises the events factor, up to fully content.
+    #[inline]
+    pub fn add_positive_event(&mut self, amount: f32) {
+        self.happiness.recover_from_events(amount);
+        debug_popup_msg_color!(self.debug, Color::green(), "Happiness +{amount:.2}");
+    }
+
+    // Refreshed alongside upgrades so the factors reflect the same stock and
+    // service coverage the house is upgraded or downgraded on.
+    fn happiness_update(&mut self, context: &BuildingContext) {
+        let config = BuildingConfigs::get().house_config();
+        let requirements =
+            HouseLevelRequirements::new(context, self.current_level_config(), &self.stock, &self.service_coverage);
+
+        // Access to food and water counts as one more need on top of the level's required resources.
+        let basic_needs_met = self.has_basic_needs_access(context) as u32 as f32;
+        let resources_required = requirements.level_config.resources_required.len() as f32;
+        let resources_met = requirements.resources_ratio() * resources_required;
+        self.happiness.needs = (resources_met + basic_needs_met) / (resources_required + 1.0);
+
+        self.happiness.services = requirements.services_ratio();
+
+        self.tax_rate_percent = context.sim_ctx.treasury().tax_rate_percent();
+        self.happiness.tax = HouseHappiness::tax_factor(self.tax_rate_percent);
+
+        self.happiness.recover_from_events(config.happiness_event_recovery_per_update);
+    }
+
     pub fn is_upgrade_available(&self, context: &BuildingContext) -> bool {
         if self.debug.freeze_upgrade_update() {
             return false;
@@ -792,7 +959,7 @@ impl HouseBuilding {
     }
 
     pub fn upgrade_requirements(&self, context: &BuildingContext) -> HouseLevelRequirements {
-        HouseLevelRequirements::new(context, self.next_level_config(), &self.stock)
+        HouseLevelRequirements::new(context, self.next_level_config(), &self.stock, &self.service_coverage)
     }
 
     pub fn perform_upgrade(&mut self, cmds: &mut SimCmds, context: &BuildingContext, dir: HouseUpgradeDirection) {
@@ -806,6 +973,24 @@ impl HouseBuilding {
         };
 
         if success {
+            sim_event!(
+                StateChange,
+                SimEventSource::Building(context.kind_and_id()),
+                context.base_cell(),
+                "{} to {}",
+                match dir {
+                    HouseUpgradeDirection::Upgrade => "Upgraded",
+                    HouseUpgradeDirection::Downgrade => "Downgraded",
+                },
+                self.level()
+            );
+
+            if let HouseUpgradeDirection::Downgrade = dir {
+                self.happiness.add_negative_event(BuildingConfigs::get().house_config().happiness_downgrade_penalty);
+            }
+
+            self.start_level_transition(context, dir);
+
             self.stock.update_capacities(self.current_level_config().stock_capacity);
             self.adjust_population(cmds, context, self.population.count(), self.current_level_config().max_population);
         }
@@ -815,6 +1000,44 @@ impl HouseBuilding {
     // Upgrade Helpers:
     // ----------------------
 
+    #[inline]
+    pub fn level_transition(&self) -> Option<HouseUpgradeDirection> {
+        self.level_transition.map(|transition| transition.direction)
+    }
+
+    // Plays the upgrading/downgrading anim on the new house tile, if its TileDef has one.
+    fn start_level_transition(&mut self, context: &BuildingContext, dir: HouseUpgradeDirection) {
+        let tile = context.find_tile_mut();
+        let anim_set = dir.anim_set();
+
+        match tile.tile_def().find_anim_set_index(tile.variation_index(), anim_set.hash) {
+            Some(index) => {
+                tile.set_anim_set_index(index);
+                self.level_transition = Some(HouseLevelTransition {
+                    direction: dir,
+                    time_left_secs: BuildingConfigs::get().house_config().level_transition_secs,
+                });
+            }
+            None => {
+                // No transition anim; show the default anim of the new tile right away.
+                tile.set_anim_set_index(0);
+                self.level_transition = None;
+            }
+        }
+    }
+
+    fn level_transition_update(&mut self, context: &BuildingContext, delta_time_secs: Seconds) {
+        let Some(transition) = &mut self.level_transition else {
+            return;
+        };
+
+        transition.time_left_secs -= delta_time_secs;
+        if transition.time_left_secs <= 0.0 {
+            context.find_tile_mut().set_anim_set_index(0);
+            self.level_transition = None;
+        }
+    }
+
     #[inline]
     pub fn level(&self) -> HouseLevel {
         self.upgrade_state.level
@@ -1001,7 +1224,7 @@ impl HouseBuilding {
 
     // Like remove_population, but the evicted residents leave the city (head for the
     // map exit) instead of trying to resettle elsewhere.
-    fn emigrate_population(&mut self, cmds: &mut SimCmds, context: &BuildingContext, count: u32) -> u32 {
+    pub fn emigrate_population(&mut self, cmds: &mut SimCmds, context: &BuildingContext, count: u32) -> u32 {
         if count != 0 && self.population.count() != 0 {
             let amount_removed = self.population.remove(count);
             self.evict_population(cmds, context, amount_removed, true);
@@ -1078,13 +1301,21 @@ impl HouseBuilding {
 
         let base_tax_generated = level_config.base_tax_generated;
         let tax_bonus = level_config.tax_bonus.min(100); // 0-100%
+        let tax_rate_scale = self.tax_rate_percent as f32 / default_tax_rate_percent().max(1) as f32;
 
-        Self::calc_household_tax(employed_residents, total_residents, base_tax_generated, tax_bonus)
+        Self::calc_household_tax(employed_residents, total_residents, base_tax_generated, tax_bonus, tax_rate_scale)
     }
 
     // - `base_tax_generated`: base tax per employed resident
     // - `tax_bonus`: percentage bonus applied if all residents are employed (0–100)
-    fn calc_household_tax(employed_residents: u32, total_residents: u32, base_tax_generated: u32, tax_bonus: u32) -> u32 {
+    // - `tax_rate_scale`: city tax rate relative to the default rate (1 = default)
+    fn calc_household_tax(
+        employed_residents: u32,
+        total_residents: u32,
+        base_tax_generated: u32,
+        tax_bonus: u32,
+        tax_rate_scale: f32,
+    ) -> u32 {
         if employed_residents == 0 || total_residents == 0 || base_tax_generated == 0 {
             // If we have no working population we can't generate any tax income!
             return 0;
@@ -1097,7 +1328,7 @@ impl HouseBuilding {
             tax += tax * (tax_bonus as f32 / 100.0);
         }
 
-        tax.round() as u32
+        (tax * tax_rate_scale).round() as u32
     }
 
     fn generate_tax(&mut self) {
@@ -1235,6 +1466,205 @@ impl HouseLevel {
     }
 }
 
+// ----------------------------------------------
+// HouseWaterAccess
+// ----------------------------------------------
+
+// Best source of water a house can reach. Fountains need to be
+// connected to a reservoir through aqueducts to count.
+#[derive(Copy, Clone, Debug, Display, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HouseWaterAccess {
+    None,
+    Well,
+    Fountain,
+}
+
+// ----------------------------------------------
+// HouseServiceCoverage
+// ----------------------------------------------
+
+// Services delivered by patrol units are only available to a house for a while
+// after a walker from that service building has passed by. Each visit refreshes
+// the timer; if no walker comes back before it runs out, access is lost and the
+// house may devolve. Services without a patrol unit (e.g. small well, shrine) are
+// still based on proximity to the building.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct HouseServiceCoverage {
+    entries: ArrayVec<(ServiceKind, Seconds), SERVICE_KIND_COUNT>, // [(service, secs_remaining)]
+
+    // Loaded from a save that predates coverage tracking. See seed_level_services().
+    #[serde(skip)]
+    needs_seeding: bool,
+}
+
+impl HouseServiceCoverage {
+    fn unseeded() -> Self {
+        Self { needs_seeding: true, ..Default::default() }
+    }
+
+    #[inline]
+    fn needs_seeding(&self) -> bool {
+        self.needs_seeding
+    }
+
+    // Older saves granted every service by proximity. The house could only be at its
+    // current level with the level's services satisfied, so treat those as just visited,
+    // giving the patrol walkers a full coverage period to come by before it can devolve.
+    // One patrol service per requirement group is enough to satisfy it.
+    fn seed_level_services(&mut self, level_config: &HouseLevelConfig, duration_secs: Seconds) {
+        let configs = BuildingConfigs::get();
+
+        for services in level_config.services_required.iter() {
+            if let Some(service_kind) = services.iter().find(|kind| configs.find_service_config(*kind).has_patrol_unit) {
+                self.mark(service_kind, duration_secs);
+            }
+        }
+
+        self.needs_seeding = false;
+    }
+
+    pub fn mark(&mut self, service_kind: ServiceKind, duration_secs: Seconds) {
+        debug_assert!(service_kind.is_single_building());
+
+        if let Some(entry) = self.entries.iter_mut().find(|(kind, _)| *kind == service_kind) {
+            entry.1 = entry.1.max(duration_secs);
+        } else if !self.entries.is_full() {
+            self.entries.push((service_kind, duration_secs));
+        }
+    }
+
+    pub fn update(&mut self, delta_time_secs: Seconds) {
+        for entry in &mut self.entries {
+            entry.1 -= delta_time_secs;
+        }
+        self.entries.retain(|(_, secs_remaining)| *secs_remaining > 0.0);
+    }
+
+    #[inline]
+    pub fn is_covered(&self, service_kind: ServiceKind) -> bool {
+        self.entries.iter().any(|(kind, _)| *kind == service_kind)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    // Patrol based services require a recent walker visit; others fall back to proximity.
+    pub fn has_access_to_service(&self, context: &BuildingContext, service_kind: ServiceKind) -> bool {
+        let config = BuildingConfigs::get().find_service_config(service_kind);
+        if config.has_patrol_unit {
+            self.is_covered(service_kind)
+        } else {
+            context.has_access_to_service(service_kind)
+        }
+    }
+
+    pub fn water_access(&self, context: &BuildingContext) -> HouseWaterAccess {
+        if self.has_access_to_service(context, BuildingKind::Fountain) {
+            HouseWaterAccess::Fountain
+        } else if self.has_access_to_service(context, BuildingKind::SmallWell)
+            || self.has_access_to_service(context, BuildingKind::LargeWell)
+        {
+            HouseWaterAccess::Well
+        } else {
+            HouseWaterAccess::None
+        }
+    }
+
+    #[inline]
+    pub fn for_each<F>(&self, mut visitor_fn: F)
+    where
+        F: FnMut(ServiceKind, Seconds),
+    {
+        for &(kind, secs_remaining) in &self.entries {
+            visitor_fn(kind, secs_remaining);
+        }
+    }
+}
+
+// ----------------------------------------------
+// HouseHappiness
+// ----------------------------------------------
+
+// Breakdown of how content a household is. Each factor is normalized to [0,1]
+// (1 = fully content); `score()` is their weighted sum using the HouseConfig
+// weights. Drives city attractiveness (migration) and riot risk once aggregated
+// city-wide in WorldStats.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct HouseHappiness {
+    pub needs: f32,    // Level resources in stock plus access to food and water.
+    pub services: f32, // Level services covered.
+    pub tax: f32,      // 1 up to the tolerated tax rate, 0 at the max rate.
+    pub events: f32,   // Knocked down by fires, collapses, evictions and downgrades. Recovers over time.
+}
+
+impl HouseHappiness {
+    pub const CONTENT: Self = Self { needs: 1.0, services: 1.0, tax: 1.0, events: 1.0 };
+
+    // Weighted [0,1] score.
+    pub fn score(&self) -> f32 {
+        let config = BuildingConfigs::get().house_config();
+
+        let total_weight = config.happiness_needs_weight
+            + config.happiness_services_weight
+            + config.happiness_tax_weight
+            + config.happiness_events_weight;
+
+        if total_weight <= 0.0 {
+            return 1.0;
+        }
+
+        let score = (self.needs * config.happiness_needs_weight)
+            + (self.services * config.happiness_services_weight)
+            + (self.tax * config.happiness_tax_weight)
+            + (self.events * config.happiness_events_weight);
+
+        (score / total_weight).clamp(0.0, 1.0)
+    }
+
+    // Factor with the lowest value, i.e. what is upsetting residents the most.
+    pub fn worst_factor(&self) -> (&'static str, f32) {
+        [("Needs", self.needs), ("Services", self.services), ("Taxes", self.tax), ("Events", self.events)]
+            .into_iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap()
+    }
+
+    pub fn tax_factor(tax_rate_percent: u32) -> f32 {
+        let configs = &GameConfigs::get().sim;
+
+        if tax_rate_percent <= configs.tolerated_tax_rate_percent {
+            return 1.0;
+        }
+
+        let range = configs.max_tax_rate_percent.saturating_sub(configs.tolerated_tax_rate_percent);
+        if range == 0 {
+            return 0.0;
+        }
+
+        let excess = (tax_rate_percent - configs.tolerated_tax_rate_percent) as f32 / range as f32;
+        1.0 - excess.min(1.0)
+    }
+
+    #[inline]
+    pub fn add_negative_event(&mut self, penalty: f32) {
+        self.events = (self.events - penalty).max(0.0);
+    }
+
+    #[inline]
+    pub fn recover_from_events(&mut self, amount: f32) {
+        self.events = (self.events + amount).min(1.0);
+    }
+}
+
+impl Default for HouseHappiness {
+    #[inline]
+    fn default() -> Self {
+        Self::CONTENT
+    }
+}
+
 // ----------------------------------------------
 // HouseLevelRequirements
 // ----------------------------------------------
@@ -1246,7 +1676,12 @@ pub struct HouseLevelRequirements {
 }
 
 impl HouseLevelRequirements {
-    pub(crate) fn new(context: &BuildingContext, level_config: &'static HouseLevelConfig, stock: &BuildingStock) -> Self {
+    pub(crate) fn new(
+        context: &BuildingContext,
+        level_config: &'static HouseLevelConfig,
+        stock: &BuildingStock,
+        coverage: &HouseServiceCoverage,
+    ) -> Self {
         let mut reqs = Self {
             level_config,
             services_available: ServiceKind::empty(),
@@ -1254,11 +1689,7 @@ impl HouseLevelRequirements {
         };
 
         level_config.services_required.for_each(|service| {
-            // TODO:
-            // Should we make house access to services depend on it being visited by the
-            // service patrol unit? Right now access to a service is simply based on
-            // proximity to the building, measured from the house's road link tile.
-            if context.has_access_to_service(service) {
+            if coverage.has_access_to_service(context, service) {
                 reqs.services_available.insert(service);
             }
             true
@@ -1312,6 +1743,28 @@ impl HouseLevelRequirements {
         true
     }
 
+    // Fraction of the required service groups we have access to; [0,1]. 1 if none required.
+    pub fn services_ratio(&self) -> f32 {
+        let services_required = &self.level_config.services_required;
+        if services_required.is_empty() {
+            return 1.0;
+        }
+
+        let available = services_required.iter().filter(|service| self.services_available.intersects(**service)).count();
+        available as f32 / services_required.len() as f32
+    }
+
+    // Fraction of the required resource groups we have in stock; [0,1]. 1 if none required.
+    pub fn resources_ratio(&self) -> f32 {
+        let resources_required = &self.level_config.resources_required;
+        if resources_required.is_empty() {
+            return 1.0;
+        }
+
+        let available = resources_required.iter().filter(|resource| self.resources_available.intersects(**resource)).count();
+        available as f32 / resources_required.len() as f32
+    }
+
     pub fn services_missing(&self) -> ServiceKind {
         let mut missing = ServiceKind::empty();
 
@@ -1341,12 +1794,34 @@ impl HouseLevelRequirements {
 // HouseUpgradeDirection
 // ----------------------------------------------
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, Serialize, Deserialize)]
 pub enum HouseUpgradeDirection {
     Upgrade,
     Downgrade,
 }
 
+impl HouseUpgradeDirection {
+    // Optional anim sets the house tile plays for a while after changing level.
+    // Tiles that don't define them switch straight to their default anim.
+    #[inline]
+    pub fn anim_set(self) -> StrHashPair {
+        match self {
+            Self::Upgrade   => StrHashPair::from_str("upgrading"),
+            Self::Downgrade => StrHashPair::from_str("downgrading"),
+        }
+    }
+}
+
+// ----------------------------------------------
+// HouseLevelTransition
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+struct HouseLevelTransition {
+    direction: HouseUpgradeDirection,
+    time_left_secs: Seconds,
+}
+
 // ----------------------------------------------
 // HouseUpgradeState
 // ----------------------------------------------
@@ -1382,23 +1857,23 @@ impl HouseUpgradeState {
         }
     }
 
-    fn can_upgrade(&self, context: &BuildingContext, stock: &BuildingStock) -> bool {
+    fn can_upgrade(&self, context: &BuildingContext, stock: &BuildingStock, coverage: &HouseServiceCoverage) -> bool {
         if self.level.is_max() {
             return false;
         }
 
-        let next_level_requirements = HouseLevelRequirements::new(context, self.next_level_config.unwrap(), stock);
+        let next_level_requirements = HouseLevelRequirements::new(context, self.next_level_config.unwrap(), stock, coverage);
 
         // Upgrade if we have the required services and resources for the next level.
         next_level_requirements.has_required_services() && next_level_requirements.has_required_resources()
     }
 
-    fn can_downgrade(&self, context: &BuildingContext, stock: &BuildingStock) -> bool {
+    fn can_downgrade(&self, context: &BuildingContext, stock: &BuildingStock, coverage: &HouseServiceCoverage) -> bool {
         if self.level.is_min() {
             return false;
         }
 
-        let curr_level_requirements = HouseLevelRequirements::new(context, self.curr_level_config.unwrap(), stock);
+        let curr_level_requirements = HouseLevelRequirements::new(context, self.curr_level_config.unwrap(), stock, coverage);
 
         // Downgrade if we don't have the required services and resources for the current level.
         !curr_level_requirements.has_required_services() || !curr_level_requirements.has_required_resources()
@@ -1422,8 +1897,8 @@ impl HouseUpgradeState {
                     self.next_level_config = Some(configs.find_house_level_config(next_level.next()));
                 }
 
-                // Set a random variation for the new building tile:
-                context.set_random_building_variation();
+                // Set the level's variation (or a random one) for the new building tile:
+                Self::set_level_variation(context, next_level_config);
 
                 debug_popup_msg!(*debug, "[U] {} -> {}", self.curr_level_config.unwrap().tile_def_name, self.level);
                 upgraded_successfully = true;
@@ -1465,8 +1940,8 @@ impl HouseUpgradeState {
                 self.curr_level_config = Some(prev_level_config);
                 self.next_level_config = Some(configs.find_house_level_config(prev_level.next()));
 
-                // Set a random variation for the new building:
-                context.set_random_building_variation();
+                // Set the level's variation (or a random one) for the new building:
+                Self::set_level_variation(context, prev_level_config);
 
                 debug_popup_msg!(*debug, "[D] {} -> {}", self.curr_level_config.unwrap().tile_def_name, self.level);
                 downgraded_successfully = true;
@@ -1503,6 +1978,11 @@ impl HouseUpgradeState {
 
         let house_id = context.id;
 
+        // Levels sharing a TileDef only swap variations; the tile stays in place.
+        if std::ptr::eq(context.find_tile().tile_def(), target_tile_def) {
+            return true;
+        }
+
         let wants_to_expand =
             target_level > current_level && house_upgrade::requires_expansion(context, current_level, target_level);
 
@@ -1517,6 +1997,27 @@ impl HouseUpgradeState {
         }
     }
 
+    fn set_level_variation(context: &BuildingContext, level_config: &HouseLevelConfig) {
+        let Some(variation_index) = level_config.tile_variation else {
+            context.set_random_building_variation();
+            return;
+        };
+
+        let tile = context.find_tile_mut();
+        if variation_index >= tile.variation_count() {
+            log::error!(
+                log::channel!("house"),
+                "House level {}: Invalid tile_variation {variation_index} for TileDef '{}' ({} variations).",
+                level_config.level,
+                tile.name(),
+                tile.variation_count()
+            );
+            return;
+        }
+
+        tile.set_variation_index(variation_index);
+    }
+
     // Check if we can increment the level and if there's enough space to expand the house.
     fn is_upgrade_available(&self, context: &BuildingContext) -> bool {
         if self.level.is_max() {
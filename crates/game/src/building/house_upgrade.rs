@@ -151,6 +151,7 @@ pub fn try_replace_tile(
         debug_assert!(game_object_handle.is_valid(), "House tile doesn't have a valid associated TileGameObjectHandle!");
         debug_assert!(dest_house.kind() == BuildingKind::from_game_object_handle(game_object_handle));
         debug_assert!(dest_house.id().index() == game_object_handle.index());
+        debug_assert!(dest_house.id().generation() == game_object_handle.generation());
 
         (game_object_handle, cell_range, tile_def)
     };
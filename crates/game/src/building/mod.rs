@@ -11,7 +11,6 @@ use common::{
     coords::{Cell, CellRange, WorldToScreenTransform},
     hash::StringHash,
     mem::Mutable,
-    time::UpdateTimer,
 };
 use engine::{
     log,
@@ -22,6 +21,10 @@ use producer::ProducerBuilding;
 use service::ServiceBuilding;
 use storage::StorageBuilding;
 use config::{BuildingConfig, BuildingConfigs};
+use hazard::BuildingHazards;
+use construction::BuildingConstruction;
+use entrance::BuildingFacing;
+use hiring::HiringArea;
 
 use super::{
     undo_redo::GameObjectSavedState,
@@ -29,7 +32,10 @@ use super::{
         SimContext,
         SimCmds,
         SimCmdQueue,
+        RandomGenerator,
+        RngStream,
         commands::ImmediateModeSimCmds,
+        events::{SimEventSource, sim_event},
         resources::{
             Population,
             RESOURCE_KIND_COUNT,
@@ -53,13 +59,13 @@ use super::{
     },
 };
 use crate::{
+    cheats,
     save_context::*,
-    config::GameConfigs,
     debug::{
         DebugUiMode,
         game_object_debug::{GameObjectDebugOptions, debug_popup_msg_color},
     },
-    pathfind::{self, NodeKind as PathNodeKind},
+    pathfind,
     tile::{
         Tile,
         TileFlags,
@@ -68,17 +74,26 @@ use crate::{
         TileMap,
         TileMapLayerKind,
         sets::{OBJECTS_BUILDINGS_CATEGORY, TileDef},
+        water,
     },
 };
 
 pub mod config;
-pub use house::{HouseLevel, HouseUpgradeDirection};
+pub mod construction;
+pub mod entrance;
+pub mod farm_field;
+pub use house::{HouseHappiness, HouseLevel, HouseUpgradeDirection};
 
+pub mod hazard;
+pub mod hiring;
 pub(crate) mod house;
 pub(crate) mod house_upgrade;
+pub mod overlays;
 pub(crate) mod producer;
+pub mod production_chain;
 pub(crate) mod service;
 pub(crate) mod storage;
+pub mod unlocks;
 
 // ----------------------------------------------
 // BuildingKind
@@ -116,6 +131,8 @@ bitflags_with_display! {
         const Apothecary     = 1 << 19;
         const Hospital       = 1 << 20;
         const Garden         = 1 << 21;
+        const Reservoir      = 1 << 22;
+        const Fountain       = 1 << 23;
     }
 }
 
@@ -177,7 +194,9 @@ impl BuildingKind {
                 | Self::University.bits()
                 | Self::Apothecary.bits()
                 | Self::Hospital.bits()
-                | Self::Garden.bits(),
+                | Self::Garden.bits()
+                | Self::Reservoir.bits()
+                | Self::Fountain.bits(),
         )
     }
 
@@ -254,8 +273,26 @@ pub struct Building {
     map_cells: CellRange,
     road_link: Cell,
     kind: BuildingKind,
-    workers_update_timer: UpdateTimer,
     archetype: Option<BuildingArchetype>,
+
+    #[serde(default)]
+    hazards: BuildingHazards,
+
+    // Construction site state. None once the building is finished (or if it was placed fully built).
+    #[serde(default)]
+    construction: Option<BuildingConstruction>,
+
+    // Current orientation of the building entrance, if it has one.
+    #[serde(default)]
+    entrance_facing: BuildingFacing,
+
+    // Set by the player to staff this employer ahead of all others.
+    #[serde(default)]
+    labor_prioritized: bool,
+
+    // Cached houses within hiring radius. Not serialized, rebuilt on demand.
+    #[serde(skip)]
+    hiring_area: HiringArea,
 }
 
 impl GameObject for Building {
@@ -268,14 +305,22 @@ impl GameObject for Building {
         self.id
     }
 
+    #[inline]
+    fn relocate(&mut self, new_id: BuildingId) {
+        debug_assert!(self.is_spawned());
+        debug_assert!(new_id.generation() == self.id.generation());
+        self.id = new_id;
+    }
+
     fn update(&mut self, cmds: &mut SimCmds, context: &SimContext) {
         debug_assert!(self.is_spawned());
 
         // Refresh cached road link cell.
         self.update_road_link(Some(cmds), context);
 
-        if self.workers_update_timer.tick(context.delta_time_secs()).should_update() {
-            self.update_workers(cmds);
+        if self.is_under_construction() {
+            self.construction_update(cmds, context);
+            return;
         }
 
         {
@@ -293,7 +338,9 @@ impl GameObject for Building {
             stats.population.total += population.count();
         }
 
-        if let Some(workers) = self.archetype().workers() {
+        if let Some(workers) = self.archetype().workers()
+            && !self.is_under_construction()
+        {
             stats.workers.total += workers.count();
 
             if let Some(worker_pool) = workers.as_household_worker_pool() {
@@ -318,11 +365,19 @@ impl GameObject for Building {
     fn pre_save(&mut self, context: &mut PreSaveContext) {
         debug_assert!(self.is_spawned());
         self.archetype_mut().pre_save(context.cmds_mut());
+
+        if let Some(construction) = &mut self.construction {
+            construction.pre_save(context.cmds_mut());
+        }
     }
 
     fn post_save(&mut self, _context: &mut PostSaveContext) {
         debug_assert!(self.is_spawned());
         self.archetype_mut().post_save();
+
+        if let Some(construction) = &mut self.construction {
+            construction.post_save();
+        }
     }
 
     fn pre_load(&mut self, _context: &mut PreLoadContext) {
@@ -333,8 +388,6 @@ impl GameObject for Building {
     fn post_load(&mut self, context: &mut PostLoadContext) {
         debug_assert!(self.is_spawned());
 
-        self.workers_update_timer.post_load(context.configs().sim.workers_update_frequency_secs);
-
         let kind = self.kind();
         debug_assert!(kind.is_single_building());
 
@@ -343,6 +396,10 @@ impl GameObject for Building {
         debug_assert!(tile.is_valid());
 
         self.archetype_mut().post_load(context, kind, tile);
+
+        if let Some(construction) = &mut self.construction {
+            construction.post_load();
+        }
     }
 
     fn undo_redo_record(&self) -> Option<Box<dyn GameObjectSavedState>> {
@@ -351,9 +408,6 @@ impl GameObject for Building {
 
     fn undo_redo_apply(&mut self, state: &dyn GameObjectSavedState) {
         self.archetype_mut().undo_redo_apply(state);
-
-        // Force a workers update right after this.
-        self.workers_update_timer.force_update();
     }
 
     fn draw_debug_ui(&mut self, cmds: &mut SimCmds, context: &SimContext, ui_sys: &UiSystem, mode: DebugUiMode) {
@@ -397,16 +451,19 @@ impl Building {
         self.id = id;
         self.map_cells = map_cells;
         self.kind = kind;
-        self.workers_update_timer = UpdateTimer::new(GameConfigs::get().sim.workers_update_frequency_secs);
         self.archetype = Some(archetype);
+        self.entrance_facing = self.configs().entrance().map(|entrance| entrance.facing).unwrap_or_default();
 
         // Spawning usually happens inside a deferred command - execute this immediately.
         self.update_road_link(None, context);
+        self.update_door_variation(context);
 
         {
             let context = self.new_context(context);
             self.archetype_mut().spawned(&context);
         }
+
+        sim_event!(Spawn, SimEventSource::Building(self.kind_and_id()), self.base_cell(), "{} spawned", self.name());
     }
 
     pub fn despawned(&mut self, context: &SimContext) {
@@ -414,8 +471,26 @@ impl Building {
 
         // Don't spawn evicted settlers or perform other cleanups when we are resetting the world/map.
         if !context.is_world_teardown() {
+            sim_event!(
+                Despawn,
+                SimEventSource::Building(self.kind_and_id()),
+                self.base_cell(),
+                "{} despawned (destroyed: {})",
+                self.name(),
+                self.hazards.is_destroyed()
+            );
+
             self.remove_all_workers(context);
 
+            if let Some(mut construction) = self.construction.take() {
+                // Demolished before it was finished; refund delivered materials.
+                let config = self.configs().construction().expect("Construction config expected!");
+                construction.cancel(&self.new_context(context), config);
+
+                let cell = self.base_cell();
+                sim_event!(Despawn, SimEventSource::Building(self.kind_and_id()), cell, "Construction cancelled");
+            }
+
             // Despawning usually happens inside a deferred command - execute this immediately.
             let mut cmds = ImmediateModeSimCmds::new(context);
             self.remove_all_population(&mut cmds, context);
@@ -431,8 +506,18 @@ impl Building {
         self.id = BuildingId::default();
         self.map_cells = CellRange::default();
         self.kind = BuildingKind::default();
-        self.workers_update_timer = UpdateTimer::default();
         self.archetype = None;
+        self.hazards = BuildingHazards::default();
+        self.construction = None;
+        self.entrance_facing = BuildingFacing::default();
+        self.labor_prioritized = false;
+        self.hiring_area.invalidate();
+    }
+
+    // Building/Unit configs were hot-reloaded; refresh any values cached from them.
+    pub fn configs_reloaded(&mut self, rng: &mut RandomGenerator) {
+        debug_assert!(self.is_spawned());
+        self.archetype_mut().configs_reloaded(rng);
     }
 
     // ----------------------
@@ -447,6 +532,7 @@ impl Building {
             self.kind,
             self.archetype_kind(),
             self.road_link(),
+            self.entrance_cell(),
             sim_ctx,
         )
     }
@@ -461,12 +547,6 @@ impl Building {
         self.archetype.as_mut().unwrap()
     }
 
-    // Accessor for the debug UI (see `crate::debug::building`).
-    #[inline]
-    pub(crate) fn workers_update_timer_mut(&mut self) -> &mut UpdateTimer {
-        &mut self.workers_update_timer
-    }
-
     building_type_casts! { producer, ProducerBuilding } // as_producer()
     building_type_casts! { storage,  StorageBuilding  } // as_storage()
     building_type_casts! { service,  ServiceBuilding  } // as_service()
@@ -527,7 +607,7 @@ impl Building {
 
     #[inline]
     pub fn is_operational(&self) -> bool {
-        self.archetype().is_operational()
+        !self.is_under_construction() && self.archetype().is_operational()
     }
 
     #[inline]
@@ -535,6 +615,26 @@ impl Building {
         self.archetype().is_production_halted()
     }
 
+    #[inline]
+    pub fn can_pause_production(&self) -> bool {
+        self.archetype().can_pause_production()
+    }
+
+    #[inline]
+    pub fn is_production_paused(&self) -> bool {
+        self.archetype().is_production_paused()
+    }
+
+    pub fn set_production_paused(&mut self, paused: bool) {
+        debug_assert!(self.can_pause_production());
+        self.archetype_mut().set_production_paused(paused);
+    }
+
+    #[inline]
+    pub fn production_progress(&self) -> Option<f32> {
+        self.archetype().production_progress()
+    }
+
     #[inline]
     pub fn has_min_required_resources(&self) -> bool {
         self.archetype().has_min_required_resources()
@@ -547,6 +647,12 @@ impl Building {
 
     pub fn visited_by(&mut self, unit: &mut Unit, context: &SimContext) -> BuildingVisitResult {
         debug_assert!(self.is_spawned());
+        self.hazards.visited_by(unit, context);
+
+        if self.is_under_construction() {
+            return BuildingVisitResult::Refused;
+        }
+
         let context = self.new_context(context);
         self.archetype_mut().visited_by(unit, &context)
     }
@@ -587,22 +693,85 @@ impl Building {
             .expect("Building should have an associated Tile in the TileMap!")
     }
 
+    // ----------------------
+    // Construction:
+    // ----------------------
+
+    #[inline]
+    pub fn is_under_construction(&self) -> bool {
+        self.construction.is_some()
+    }
+
+    #[inline]
+    pub fn construction(&self) -> Option<&BuildingConstruction> {
+        self.construction.as_ref()
+    }
+
+    // Turns a freshly placed building into a construction site, if its config has construction
+    // requirements. Returns false if the building is operational right away.
+    pub fn start_construction(&mut self, context: &SimContext) -> bool {
+        debug_assert!(self.is_spawned());
+        debug_assert!(!self.is_under_construction());
+
+        let Some(config) = self.configs().construction() else {
+            return false;
+        };
+
+        if cheats::get().instant_construction {
+            return false;
+        }
+
+        let tile = self.find_tile_mut(context);
+        let scaffold_variation_index = tile.tile_def().find_variation_index(BuildingConstruction::SCAFFOLD_VARIATION.hash);
+        let finished_variation_index = match scaffold_variation_index {
+            Some(index) if index == tile.variation_index() => 0, // Random placement picked the scaffold.
+            _ => tile.variation_index(),
+        };
+        if let Some(index) = scaffold_variation_index {
+            tile.set_variation_index(index);
+        }
+
+        self.construction = Some(BuildingConstruction::new(config, finished_variation_index));
+
+        sim_event!(StateChange, SimEventSource::Building(self.kind_and_id()), self.base_cell(), "Construction started");
+        true
+    }
+
+    fn construction_update(&mut self, cmds: &mut SimCmds, context: &SimContext) {
+        let config = self.configs().construction().expect("Construction config expected!");
+        let building_context = self.new_context(context);
+
+        let construction = self.construction.as_mut().unwrap();
+        if !construction.update(cmds, &building_context, config) {
+            return;
+        }
+
+        // Done; take the scaffold down.
+        let finished_variation_index = construction.finished_variation_index();
+        self.construction = None;
+        self.find_tile_mut(context).set_variation_index(finished_variation_index);
+
+        log::info!(log::channel!("building"), "{} finished construction at {}.", self.name(), self.base_cell());
+        sim_event!(StateChange, SimEventSource::Building(self.kind_and_id()), self.base_cell(), "Construction finished");
+    }
+
     // ----------------------
     // Building Resources:
     // ----------------------
 
+    // Construction sites don't trade resources; their materials are tracked separately.
     #[inline]
     pub fn available_resources(&self, kind: ResourceKind) -> u32 {
         debug_assert!(kind.is_single_resource());
         debug_assert!(self.is_spawned());
-        self.archetype().available_resources(kind)
+        if self.is_under_construction() { 0 } else { self.archetype().available_resources(kind) }
     }
 
     #[inline]
     pub fn receivable_resources(&self, kind: ResourceKind) -> u32 {
         debug_assert!(kind.is_single_resource());
         debug_assert!(self.is_spawned());
-        self.archetype().receivable_resources(kind)
+        if self.is_under_construction() { 0 } else { self.archetype().receivable_resources(kind) }
     }
 
     #[inline]
@@ -676,6 +845,14 @@ impl Building {
         self.archetype_mut().remove_population(cmds, &context, count)
     }
 
+    // Residents removed leave the city (head for the map exit) instead of resettling. Houses only.
+    #[inline]
+    pub fn emigrate_population(&mut self, cmds: &mut SimCmds, context: &SimContext, count: u32) -> u32 {
+        debug_assert!(self.is_spawned());
+        let context = self.new_context(context);
+        self.as_house_mut().emigrate_population(cmds, &context, count)
+    }
+
     #[inline]
     pub(crate) fn remove_all_population(&mut self, cmds: &mut SimCmds, context: &SimContext) {
         self.remove_population(cmds, context, self.population_count());
@@ -700,11 +877,36 @@ impl Building {
         self.archetype().workers().is_none_or(|workers| workers.is_max())
     }
 
+    #[inline]
+    pub fn is_employer(&self) -> bool {
+        self.archetype().workers().is_some_and(|workers| workers.as_employer().is_some())
+    }
+
+    #[inline]
+    pub fn is_labor_prioritized(&self) -> bool {
+        self.labor_prioritized
+    }
+
+    pub fn set_labor_prioritized(&mut self, prioritized: bool) {
+        debug_assert!(self.is_employer());
+        self.labor_prioritized = prioritized;
+    }
+
+    // Configured labor priority, raised above every configured
+    // priority while the player has prioritized this building.
+    #[inline]
+    pub fn labor_priority(&self) -> u32 {
+        const PRIORITIZED_LABOR_BONUS: u32 = 1000;
+
+        let priority = self.configs().labor_priority();
+        if self.labor_prioritized { priority.saturating_add(PRIORITIZED_LABOR_BONUS) } else { priority }
+    }
+
     // These return the amount added/removed, which can be <= the `count` parameter.
     pub fn add_workers(&mut self, count: u32, source: BuildingKindAndId) -> u32 {
         debug_assert!(self.is_spawned());
         let mut workers_added = 0;
-        if count != 0 && !self.workers_is_maxed() {
+        if count != 0 && !self.workers_is_maxed() && !self.is_under_construction() {
             if let Some(workers) = self.archetype_mut().workers_mut() {
                 workers_added = workers.add(count, source);
             }
@@ -763,54 +965,6 @@ impl Building {
         }
     }
 
-    fn update_workers(&mut self, cmds: &mut SimCmds) {
-        if !self.is_linked_to_road() {
-            return;
-        }
-
-        // Search for employees if we're an Employer and not already at max capacity.
-        if let Some(workers) = self.archetype().workers() {
-            if let Some(employer) = workers.as_employer() {
-                if !employer.is_at_max_capacity() {
-                    cmds.defer_building_update(self.kind_and_id(), |context, building| {
-                        if let Some(house) = building.find_house_with_available_workers_mut(context) {
-                            let workers_available = house.workers_count();
-                            let workers_added = building.add_workers(workers_available, house.kind_and_id());
-                            let workers_removed = house.remove_workers(workers_added, building.kind_and_id());
-                            debug_assert!(workers_added == workers_removed);
-                        }
-                    });
-                }
-            }
-        }
-    }
-
-    fn find_house_with_available_workers_mut<'game>(&self, context: &'game SimContext) -> Option<&'game mut Building> {
-        let workers_search_radius = GameConfigs::get().sim.workers_search_radius;
-        debug_assert!(workers_search_radius > 0);
-
-        let result = context.find_nearest_buildings_mut(
-            self.road_link().unwrap(),
-            BuildingKind::House,
-            PathNodeKind::Road,
-            Some(workers_search_radius),
-            |house, _path| {
-                if house.workers_count() != 0 {
-                    return false; // Accept and stop search.
-                }
-                true // Continue searching.
-            },
-        );
-
-        if let Some((house, _path)) = result {
-            debug_assert!(house.is(BuildingKind::House));
-            debug_assert!(house.workers_count() != 0);
-            return Some(house);
-        }
-
-        None
-    }
-
     // ----------------------
     // Building Road Link:
     // ----------------------
@@ -820,6 +974,14 @@ impl Building {
         self.road_link().is_some()
     }
 
+    // True if our road link belongs to a road network that reaches the map edge,
+    // where settlers and traders enter. Buildings failing this are unreachable.
+    #[inline]
+    pub fn is_road_connected_to_map_edge(&self, context: &SimContext) -> bool {
+        self.road_link()
+            .is_some_and(|road_link| context.graph().is_road_connected_to_map_edge(pathfind::Node::new(road_link)))
+    }
+
     #[inline]
     pub fn road_link(&self) -> Option<Cell> {
         debug_assert!(self.is_spawned());
@@ -850,12 +1012,26 @@ impl Building {
         None
     }
 
+    fn find_road_link(&self, context: &SimContext) -> Option<(Cell, BuildingFacing)> {
+        match self.configs().entrance() {
+            // Buildings with an explicit entrance can only link to a road through it.
+            Some(entrance) => entrance
+                .find_road_facing(context.graph(), self.cell_range(), self.entrance_facing)
+                .map(|(facing, cell)| (cell, facing)),
+            None => context.find_nearest_road_link(self.cell_range()).map(|cell| (cell, self.entrance_facing)),
+        }
+    }
+
     fn update_road_link(&mut self, opt_cmds: Option<&mut SimCmds>, context: &SimContext) {
-        if let Some(new_road_link) = context.find_nearest_road_link(self.cell_range()) {
+        if let Some((new_road_link, new_facing)) = self.find_road_link(context) {
             debug_assert!(new_road_link.is_valid());
             let prev_road_link = self.road_link;
 
-            let set_tile_road_link_flag = move |context: &SimContext, _: &mut Building| {
+            // Entrance was reoriented to face a different road.
+            let facing_changed = new_facing != self.entrance_facing;
+            self.entrance_facing = new_facing;
+
+            let set_tile_road_link_flag = move |context: &SimContext, building: &mut Building| {
                 let tile_map = context.tile_map_mut();
 
                 if new_road_link != prev_road_link && prev_road_link.is_valid() {
@@ -865,6 +1041,10 @@ impl Building {
 
                 // Set new underlying tile flag:
                 tile_map.set_tile_flags(new_road_link, TileKind::Terrain, TileFlags::BuildingRoadLink, true);
+
+                if facing_changed {
+                    building.update_door_variation(context);
+                }
             };
 
             if let Some(cmds) = opt_cmds {
@@ -880,6 +1060,47 @@ impl Building {
         }
     }
 
+    // ----------------------
+    // Building Entrance:
+    // ----------------------
+
+    #[inline]
+    pub fn entrance_facing(&self) -> BuildingFacing {
+        self.entrance_facing
+    }
+
+    // Explicit entrance cell for the current orientation, if the building has one.
+    #[inline]
+    pub fn entrance_cell(&self) -> Option<Cell> {
+        self.configs()
+            .entrance()
+            .map(|entrance| entrance.entrance_cell(self.cell_range(), self.entrance_facing))
+    }
+
+    // Switch to the door-facing tile variation for the current entrance orientation, if any.
+    fn update_door_variation(&self, context: &SimContext) {
+        let Some(variation_index) =
+            self.configs().entrance().and_then(|entrance| entrance.door_variation(self.entrance_facing))
+        else {
+            return;
+        };
+
+        let tile = self.find_tile_mut(context);
+        if variation_index >= tile.variation_count() {
+            log::error!(
+                log::channel!("building"),
+                "{}: Invalid door variation {variation_index} ({} facing) for TileDef '{}' ({} variations).",
+                self.name(),
+                self.entrance_facing,
+                tile.name(),
+                tile.variation_count()
+            );
+            return;
+        }
+
+        tile.set_variation_index(variation_index);
+    }
+
     fn clear_road_link(&mut self, tile_map: &mut TileMap) {
         if self.road_link.is_valid() {
             tile_map.set_tile_flags(self.road_link, TileKind::Terrain, TileFlags::BuildingRoadLink, false);
@@ -892,6 +1113,7 @@ impl Building {
     // ----------------------
 
     pub fn register_callbacks() {
+        BuildingConstruction::register_callbacks();
         ProducerBuilding::register_callbacks();
         StorageBuilding::register_callbacks();
         ServiceBuilding::register_callbacks();
@@ -967,6 +1189,10 @@ pub(crate) trait BuildingBehavior {
     fn post_save(&mut self);
     fn post_load(&mut self, context: &mut PostLoadContext, kind: BuildingKind, tile: &Tile);
 
+    // Our config was patched in place by a hot-reload. Refresh timers and
+    // other values copied from it when the building was created or loaded.
+    fn configs_reloaded(&mut self, _rng: &mut RandomGenerator) {}
+
     // ----------------------
     // Resources/Stock:
     // ----------------------
@@ -983,6 +1209,22 @@ pub(crate) trait BuildingBehavior {
         false
     }
 
+    // Production can be paused by the player from the building inspector.
+    fn can_pause_production(&self) -> bool {
+        false
+    }
+
+    fn is_production_paused(&self) -> bool {
+        false
+    }
+
+    fn set_production_paused(&mut self, _paused: bool) {}
+
+    // Progress of the current production cycle in [0,1], for buildings that produce goods.
+    fn production_progress(&self) -> Option<f32> {
+        None
+    }
+
     // How many resources of this kind do we currently hold?
     fn available_resources(&self, kind: ResourceKind) -> u32;
 
@@ -1111,6 +1353,7 @@ pub struct BuildingContext<'game> {
     pub kind: BuildingKind,
     pub archetype_kind: BuildingArchetypeKind,
     pub road_link: Option<Cell>,
+    pub entrance: Option<Cell>,
     pub sim_ctx: &'game SimContext,
 }
 
@@ -1122,6 +1365,7 @@ impl<'game> BuildingContext<'game> {
         kind: BuildingKind,
         archetype_kind: BuildingArchetypeKind,
         road_link: Option<Cell>,
+        entrance: Option<Cell>,
         sim_ctx: &'game SimContext,
     ) -> Self {
         Self {
@@ -1130,6 +1374,7 @@ impl<'game> BuildingContext<'game> {
             kind,
             archetype_kind,
             road_link,
+            entrance,
             sim_ctx,
         }
     }
@@ -1167,6 +1412,19 @@ impl<'game> BuildingContext<'game> {
         self.road_link.is_some()
     }
 
+    #[inline]
+    pub fn is_road_connected_to_map_edge(&self) -> bool {
+        self.road_link.is_some_and(|road_link| {
+            self.sim_ctx.graph().is_road_connected_to_map_edge(pathfind::Node::new(road_link))
+        })
+    }
+
+    // Building sits within an area enclosed by city walls.
+    #[inline]
+    pub fn is_inside_walls(&self) -> bool {
+        self.sim_ctx.is_inside_walls(self.base_cell())
+    }
+
     #[inline]
     pub fn debug_name(&self) -> &'static str {
         if cfg!(debug_assertions) {
@@ -1208,19 +1466,27 @@ impl<'game> BuildingContext<'game> {
                 service_kind,
                 config.requires_road_access,
                 config.effect_radius,
+                config.requires_water_supply, // A dry fountain serves no one.
             );
         }
 
         false
     }
 
+    // Water cell where boats spawn and berth. Only valid for port/wharf buildings.
+    #[inline]
+    pub fn dock_water_cell(&self) -> Option<Cell> {
+        debug_assert!(water::is_port_or_wharf(self.find_tile().tile_def()));
+        water::find_dock_water_cell(self.sim_ctx.tile_map(), self.cell_range())
+    }
+
     #[inline]
     pub fn set_random_building_variation(&self) {
         let tile = self.find_tile_mut();
-        tile.set_random_variation_index(self.sim_ctx.rng_mut());
+        tile.set_random_variation_index(self.sim_ctx.rng_stream_mut(RngStream::Placement));
     }
 
-    // Road link if valid, any unobstructed surrounding cell otherwise.
+    // Road link if valid, the entrance cell or any unobstructed surrounding cell otherwise.
     pub fn road_link_or_building_access_tile(&self) -> Cell {
         if let Some(road_link) = self.road_link {
             if road_link.is_valid() {
@@ -1229,6 +1495,12 @@ impl<'game> BuildingContext<'game> {
         }
 
         let tile_map = self.sim_ctx.tile_map();
+
+        if let Some(entrance) = self.entrance {
+            if tile_map.try_tile_from_layer(entrance, TileMapLayerKind::Objects).is_none() {
+                return entrance;
+            }
+        }
         let mut access_cell = Cell::invalid();
 
         pathfind::for_each_surrounding_cell(self.cell_range(), |cell| {
@@ -0,0 +1,159 @@
+use common::coords::Cell;
+
+use super::{Building, BuildingKind, house::HouseWaterAccess};
+use crate::{
+    pathfind::Node,
+    sim::SimContext,
+    tile::{
+        color_palette::ColorPalette,
+        heatmap::HeatmapGradient,
+        minimap::{MinimapOverlayCanvas, MinimapOverlayProvider, MinimapOverlays},
+    },
+};
+
+// ----------------------------------------------
+// Built-in minimap overlays
+// ----------------------------------------------
+
+pub fn register_minimap_overlays() {
+    let overlays = MinimapOverlays::get_mut();
+    overlays.register(Box::new(WaterAccessOverlay));
+    overlays.register(Box::new(EmploymentOverlay { gradient: HeatmapGradient::bad_to_good() }));
+    overlays.register(Box::new(HappinessOverlay { gradient: HeatmapGradient::bad_to_good() }));
+    overlays.register(Box::new(InsideWallsOverlay));
+}
+
+fn for_each_employer(context: &SimContext, mut visitor_fn: impl FnMut(&Building)) {
+    let world = context.world();
+    for kinds in [BuildingKind::producers(), BuildingKind::storage(), BuildingKind::services()] {
+        world.for_each_building(kinds, |building| {
+            visitor_fn(building);
+            true
+        });
+    }
+}
+
+// ----------------------------------------------
+// WaterAccessOverlay
+// ----------------------------------------------
+
+// Houses in green if near a working fountain, yellow if only covered by a well,
+// red otherwise. Aqueducts and reservoirs carrying water are drawn in blue.
+struct WaterAccessOverlay;
+
+impl MinimapOverlayProvider for WaterAccessOverlay {
+    fn name(&self) -> &'static str {
+        "Water Access"
+    }
+
+    fn paint(&self, context: &SimContext, canvas: &mut MinimapOverlayCanvas) {
+        let palette = ColorPalette::current();
+
+        context.world().for_each_building(BuildingKind::House, |building| {
+            let building_ctx = building.new_context(context);
+            let coverage = building.as_house().service_coverage();
+
+            let color = match coverage.water_access(&building_ctx) {
+                HouseWaterAccess::Fountain => palette.good(),
+                HouseWaterAccess::Well     => palette.fair(),
+                HouseWaterAccess::None     => palette.bad(),
+            };
+
+            canvas.fill_cells(building.cell_range(), color);
+            true
+        });
+
+        let graph = context.graph();
+        let size = graph.grid_size();
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let cell = Cell::new(x, y);
+                if graph.is_water_supplied(Node::new(cell)) {
+                    canvas.set_cell_color(cell, palette.water());
+                }
+            }
+        }
+    }
+}
+
+// ----------------------------------------------
+// EmploymentOverlay
+// ----------------------------------------------
+
+// Employers colored by how fully staffed they are.
+struct EmploymentOverlay {
+    gradient: HeatmapGradient,
+}
+
+impl MinimapOverlayProvider for EmploymentOverlay {
+    fn name(&self) -> &'static str {
+        "Employment"
+    }
+
+    fn paint(&self, context: &SimContext, canvas: &mut MinimapOverlayCanvas) {
+        let palette = ColorPalette::current();
+        for_each_employer(context, |building| {
+            if let Some(employer) = building.workers().and_then(|workers| workers.as_employer()) {
+                if employer.max_employees() != 0 {
+                    let staffed = employer.employee_count() as f32 / employer.max_employees() as f32;
+                    canvas.fill_cells(building.cell_range(), self.gradient.sample(palette, staffed));
+                }
+            }
+        });
+    }
+}
+
+// ----------------------------------------------
+// HappinessOverlay
+// ----------------------------------------------
+
+// Houses colored by their happiness score.
+struct HappinessOverlay {
+    gradient: HeatmapGradient,
+}
+
+impl MinimapOverlayProvider for HappinessOverlay {
+    fn name(&self) -> &'static str {
+        "Happiness"
+    }
+
+    fn paint(&self, context: &SimContext, canvas: &mut MinimapOverlayCanvas) {
+        let palette = ColorPalette::current();
+        context.world().for_each_building(BuildingKind::House, |building| {
+            let score = building.as_house().happiness().score();
+            canvas.fill_cells(building.cell_range(), self.gradient.sample(palette, score));
+            true
+        });
+    }
+}
+
+// ----------------------------------------------
+// InsideWallsOverlay
+// ----------------------------------------------
+
+// Cells enclosed by city walls in green.
+struct InsideWallsOverlay;
+
+impl MinimapOverlayProvider for InsideWallsOverlay {
+    fn name(&self) -> &'static str {
+        "Inside Walls"
+    }
+
+    fn paint(&self, context: &SimContext, canvas: &mut MinimapOverlayCanvas) {
+        let graph = context.graph();
+        if graph.inside_walls_count() == 0 {
+            return; // No enclosure yet.
+        }
+
+        let palette = ColorPalette::current();
+        let size = graph.grid_size();
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let cell = Cell::new(x, y);
+                if context.is_inside_walls(cell) {
+                    canvas.set_cell_color(cell, palette.good());
+                }
+            }
+        }
+    }
+}
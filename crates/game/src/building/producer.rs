@@ -19,18 +19,27 @@ use super::{
     BuildingContext,
     BuildingKind,
     config::{BuildingConfig, BuildingConfigs, building_config},
+    construction::BuildingConstructionConfig,
+    entrance::BuildingEntranceConfig,
+    farm_field::{FarmField, FarmFieldConfig, FarmFieldStage, FarmFieldUpdate},
 };
 use crate::{
+    campaign,
     cheats,
     tile::Tile,
     save_context::PostLoadContext,
-    world::{object::GameObject, stats::WorldStats},
+    world::{
+        object::GameObject,
+        stats::WorldStats,
+        config_reload::{HotReloadableConfig, reload_field},
+    },
     undo_redo::{GameObjectSavedState, game_object_undo_redo_state},
     debug::game_object_debug::{GameObjectDebugOptions, debug_popup_msg, debug_popup_msg_color, game_object_debug_options},
     sim::{
         SimCmds,
         SimContext,
         RandomGenerator,
+        events::{SimEventSource, sim_event},
         resources::{RESOURCE_KIND_COUNT, ResourceKind, ResourceKinds, ShoppingList, StockItem, Workers},
     },
     unit::{
@@ -68,6 +77,15 @@ pub struct ProducerConfig {
     pub min_workers: u32,
     pub max_workers: u32,
 
+    // Max road distance to houses we hire from. Uses the global workers search radius if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub hiring_radius: Option<i32>,
+
+    // Higher priority employers are staffed first when workers are scarce.
+    #[serde(default)]
+    pub labor_priority: u32,
+
     pub production_output_frequency_secs: Seconds,
     pub production_output_amount: u32, // How many units produced per cycle.
 
@@ -96,6 +114,22 @@ pub struct ProducerConfig {
     #[serde(default)]
     #[debug_ui(nested)]
     pub ambient_patrol: AmbientPatrolConfig,
+
+    // If this is set, production comes from harvesting a field with a seasonal crop cycle
+    // instead of the production timer (e.g., rice farm).
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub farm_field: Option<FarmFieldConfig>,
+
+    // Explicit entrance cell. Any surrounding cell gives access if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub entrance: Option<BuildingEntranceConfig>,
+
+    // Materials and build time when placed by the player. Appears fully built if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub construction: Option<BuildingConstructionConfig>,
 }
 
 impl Default for ProducerConfig {
@@ -123,12 +157,62 @@ impl Default for ProducerConfig {
                 spawn_chance: 10,
                 max_distance: 40,
             },
+            farm_field: None,
+            entrance: None,
+            construction: None,
+            hiring_radius: None,
+            labor_priority: 0,
         }
     }
 }
 
 building_config! {
-    ProducerConfig
+    ProducerConfig, entrance, hiring_radius, labor_priority, construction
+}
+
+impl HotReloadableConfig for ProducerConfig {
+    fn reload_key(&self) -> &str {
+        &self.tile_def_name
+    }
+
+    // Production rates and ambient patrol tuning. Stock capacities and workers need a restart.
+    fn hot_reload_from(&mut self, new_config: &Self) -> bool {
+        if self.kind != new_config.kind {
+            log::warning!(
+                log::channel!("config"),
+                "ProducerConfig '{}': Building kind changed. Restart to apply.",
+                self.name
+            );
+            return false;
+        }
+
+        let name = &new_config.name;
+        let patrol = &mut self.ambient_patrol;
+        let new_patrol = &new_config.ambient_patrol;
+
+        let mut changed = reload_field(name, "labor_priority", &mut self.labor_priority, new_config.labor_priority);
+        changed |= reload_field(
+            name,
+            "production_output_frequency_secs",
+            &mut self.production_output_frequency_secs,
+            new_config.production_output_frequency_secs,
+        );
+        changed |= reload_field(
+            name,
+            "production_output_amount",
+            &mut self.production_output_amount,
+            new_config.production_output_amount,
+        );
+        changed |= reload_field(
+            name,
+            "ambient_patrol.spawn_frequency_secs",
+            &mut patrol.spawn_frequency_secs,
+            new_patrol.spawn_frequency_secs,
+        );
+        changed |= reload_field(name, "ambient_patrol.spawn_chance", &mut patrol.spawn_chance, new_patrol.spawn_chance);
+        changed |= reload_field(name, "ambient_patrol.max_distance", &mut patrol.max_distance, new_patrol.max_distance);
+        changed
+    }
 }
 
 // ----------------------------------------------
@@ -185,6 +269,15 @@ pub struct ProducerBuilding {
     // Optional ambient patrol unit to wander around the vicinity.
     pub(crate) ambient_patrol: TimedAmbientPatrol,
 
+    // Crop cycle for farms with a field. See `ProducerConfig::farm_field`.
+    #[serde(default)]
+    pub(crate) farm_field: Option<FarmField>,
+
+    // Paused by the player. Stops production and fetching of raw
+    // materials, but goods already produced are still delivered.
+    #[serde(default)]
+    pub(crate) production_paused: bool,
+
     #[serde(skip)]
     debug: ProducerDebug,
 }
@@ -225,11 +318,16 @@ impl BuildingBehavior for ProducerBuilding {
         // gate below still halts it entirely if it drops under the minimum).
         let production_delta_secs = delta_time_secs * self.work_efficiency();
 
+        if self.farm_field.is_some() && !self.production_paused && !self.debug.freeze_production() {
+            self.farm_field_update(context);
+        }
+
         // Update producer states:
         if self.production_update_timer.tick(production_delta_secs).should_update() && self.has_min_required_workers() {
             let is_harvester_building = self.is_harvester_building();
+            let is_producing = !self.production_paused;
 
-            if !self.debug.freeze_harvesting() && is_harvester_building {
+            if is_producing && !self.debug.freeze_harvesting() && is_harvester_building {
                 // If we've sent out a harvester unit wait until
                 // next update to send out a delivery runner.
                 if self.harvesting_update(cmds, context) {
@@ -237,16 +335,16 @@ impl BuildingBehavior for ProducerBuilding {
                 }
             }
 
-            // Harvester buildings are handled by harvesting_update() above.
-            if !self.debug.freeze_production() && !is_harvester_building {
-                self.production_update();
+            // Harvester buildings are handled by harvesting_update() above, farm fields by farm_field_update().
+            if is_producing && !self.debug.freeze_production() && !is_harvester_building && self.farm_field.is_none() {
+                self.production_update(context);
             }
 
             if !self.debug.freeze_storage_delivery() {
                 self.deliver_to_storage(cmds, context);
             }
 
-            if !self.debug.freeze_storage_fetching() {
+            if is_producing && !self.debug.freeze_storage_fetching() {
                 self.fetch_from_storage(cmds, context);
             }
         }
@@ -312,6 +410,13 @@ impl BuildingBehavior for ProducerBuilding {
         self.config = Some(config);
     }
 
+    fn configs_reloaded(&mut self, rng: &mut RandomGenerator) {
+        let config = self.config.unwrap();
+
+        self.production_update_timer.post_load(config.production_output_frequency_secs);
+        self.ambient_patrol.set_spawn_frequency(rng, config.ambient_patrol.spawn_frequency_secs);
+    }
+
     // ----------------------
     // Resources/Stock:
     // ----------------------
@@ -341,7 +446,7 @@ impl BuildingBehavior for ProducerBuilding {
     }
 
     fn is_production_halted(&self) -> bool {
-        if self.debug.freeze_production() || self.debug.freeze_harvesting() {
+        if self.production_paused || self.debug.freeze_production() || self.debug.freeze_harvesting() {
             return true;
         }
         if self.production_output_stock.is_full() {
@@ -353,6 +458,27 @@ impl BuildingBehavior for ProducerBuilding {
         false
     }
 
+    fn can_pause_production(&self) -> bool {
+        true
+    }
+
+    fn is_production_paused(&self) -> bool {
+        self.production_paused
+    }
+
+    fn set_production_paused(&mut self, paused: bool) {
+        self.production_paused = paused;
+    }
+
+    fn production_progress(&self) -> Option<f32> {
+        if let Some(field) = &self.farm_field {
+            return Some(field.cycle_progress(self.config.unwrap().farm_field.as_ref().unwrap()));
+        }
+
+        let timer = &self.production_update_timer;
+        Some((timer.time_since_last_secs() / timer.frequency_secs()).clamp(0.0, 1.0))
+    }
+
     fn available_resources(&self, kind: ResourceKind) -> u32 {
         if self.has_min_required_workers() {
             return self.production_output_stock.available_resources(kind);
@@ -483,6 +609,8 @@ impl ProducerBuilding {
             runner: Runner::default(),
             harvester: Harvester::default(),
             ambient_patrol: TimedAmbientPatrol::new(rng, config.ambient_patrol.spawn_frequency_secs),
+            farm_field: config.farm_field.as_ref().map(|_| FarmField::default()),
+            production_paused: false,
             debug: ProducerDebug::default(),
         }
     }
@@ -503,6 +631,11 @@ impl ProducerBuilding {
         let _: Callback<UnitTaskFetchCompletionCallback>    = callback::register!(ProducerBuilding::on_resources_harvested);
     }
 
+    #[inline]
+    pub fn production_output(&self) -> ResourceKind {
+        self.production_output_stock.resource_kind()
+    }
+
     pub fn add_production_output_stock(&mut self, kind: ResourceKind, count: u32) -> bool {
         if self.production_output_stock.item.kind == kind && !self.production_output_stock.is_full() {
             self.production_output_stock.store_resources(count);
@@ -542,7 +675,7 @@ impl ProducerBuilding {
         true
     }
 
-    fn production_update(&mut self) {
+    fn production_update(&mut self, context: &BuildingContext) {
         // Production halts if the local stock is full.
         if !self.production_output_stock.is_full() {
             let mut produce_items = true;
@@ -561,13 +694,63 @@ impl ProducerBuilding {
 
             // Produce items and store locally:
             if produce_items {
-                let count = self.config.unwrap().production_output_amount;
-                self.production_output_stock.store_resources(count);
-                self.debug.log_resources_gained(self.production_output_stock.resource_kind(), count);
+                self.store_production_output(context);
+            }
+        }
+    }
+
+    fn store_production_output(&mut self, context: &BuildingContext) {
+        let count = self.config.unwrap().production_output_amount;
+        self.production_output_stock.store_resources(count);
+        self.debug.log_resources_gained(self.production_output_stock.resource_kind(), count);
+        campaign::record_production(self.production_output_stock.resource_kind(), count);
+
+        sim_event!(
+            Production,
+            SimEventSource::Building(context.kind_and_id()),
+            context.base_cell(),
+            "Produced {count} {}",
+            self.production_output_stock.resource_kind()
+        );
+    }
+
+    fn farm_field_update(&mut self, context: &BuildingContext) {
+        let field_config = self.config.unwrap().farm_field.as_ref().unwrap();
+        let calendar = context.sim_ctx.calendar();
+
+        let elapsed_days = calendar.secs_to_days(context.sim_ctx.delta_time_secs());
+        let work_efficiency = if self.has_min_required_workers() { self.work_efficiency() } else { 0.0 };
+        let can_harvest = !self.production_output_stock.is_full();
+
+        let field = self.farm_field.as_mut().unwrap();
+        match field.update(field_config, elapsed_days, work_efficiency, calendar.month_of_year(), can_harvest) {
+            FarmFieldUpdate::None => {}
+            FarmFieldUpdate::StageChanged(stage) => {
+                Self::set_farm_field_anim(context, stage);
             }
+            FarmFieldUpdate::Harvested => {
+                // Workers bring the crop in to the farm's local stock.
+                self.store_production_output(context);
+                Self::set_farm_field_anim(context, FarmFieldStage::Sowing);
+                debug_popup_msg!(self.debug, "{} harvested its field", self.name());
+            }
+        }
+    }
+
+    fn set_farm_field_anim(context: &BuildingContext, stage: FarmFieldStage) {
+        let tile = context.find_tile_mut();
+        let anim_set = stage.anim_set();
+
+        if let Some(index) = tile.tile_def().find_anim_set_index(tile.variation_index(), anim_set.hash) {
+            tile.set_anim_set_index(index);
         }
     }
 
+    #[inline]
+    pub fn farm_field_stage(&self) -> Option<FarmFieldStage> {
+        self.farm_field.as_ref().map(|field| field.stage())
+    }
+
     fn deliver_to_storage(&mut self, cmds: &mut SimCmds, context: &BuildingContext) {
         if self.production_output_stock.is_empty() {
             return; // Nothing to deliver.
@@ -690,6 +873,7 @@ impl ProducerBuilding {
             debug_assert!(item.count != 0, "{item}");
             debug_assert!(item.kind == ResourceKind::Wood, "Expected wood, got {} instead", item.kind);
             this_producer.production_output_stock.store_resources(item.count);
+            campaign::record_production(item.kind, item.count);
             harvester_unit.clear_inventory();
         } else {
             log::error!(
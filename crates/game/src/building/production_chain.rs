@@ -0,0 +1,221 @@
+use arrayvec::ArrayVec;
+use smallvec::SmallVec;
+use strum::Display;
+
+use super::{
+    Building,
+    BuildingArchetypeKind,
+    BuildingKind,
+    config::BuildingConfigs,
+    producer::{ProducerBuilding, ProducerConfig},
+};
+use crate::sim::{
+    SimContext,
+    resources::{RESOURCE_KIND_COUNT, ResourceKind},
+};
+
+// ----------------------------------------------
+// ProductionChainNode
+// ----------------------------------------------
+
+// One producer config in the production chain graph. Edges are implicit:
+// each required input links to the nodes producing that resource.
+pub struct ProductionChainNode {
+    pub config_index: usize, // Index into BuildingConfigs producer configs.
+    pub kind: BuildingKind,
+    pub name: String,
+    pub output: ResourceKind,
+    pub inputs: ArrayVec<ResourceKind, RESOURCE_KIND_COUNT>,
+
+    // Longest chain of producers feeding into this one. Raw material producers are depth 0.
+    pub depth: u32,
+}
+
+// ----------------------------------------------
+// ProductionChains
+// ----------------------------------------------
+
+// Dependency graph of production chains (e.g. Clay Pit -> Clay -> Pottery Kiln -> Pottery),
+// derived from the `production_output` and `resources_required` of each ProducerConfig.
+#[derive(Default)]
+pub struct ProductionChains {
+    nodes: Vec<ProductionChainNode>,
+    producers_by_resource: [SmallVec<[usize; 2]>; RESOURCE_KIND_COUNT], // ResourceKind index => nodes[index]
+}
+
+impl ProductionChains {
+    pub fn build(producer_configs: &[ProducerConfig]) -> Self {
+        let mut chains = Self::default();
+
+        for (config_index, config) in producer_configs.iter().enumerate() {
+            if !config.production_output.is_single_resource() {
+                continue; // Invalid config, already reported on load.
+            }
+
+            let mut inputs = ArrayVec::new();
+            config.resources_required.for_each(|kind| {
+                inputs.push(kind);
+                true
+            });
+
+            chains.producers_by_resource[config.production_output.index()].push(chains.nodes.len());
+            chains.nodes.push(ProductionChainNode {
+                config_index,
+                kind: config.kind,
+                name: config.name.clone(),
+                output: config.production_output,
+                inputs,
+                depth: 0,
+            });
+        }
+
+        chains.compute_depths();
+        chains
+    }
+
+    #[inline]
+    pub fn nodes(&self) -> &[ProductionChainNode] {
+        &self.nodes
+    }
+
+    #[inline]
+    pub fn max_depth(&self) -> u32 {
+        self.nodes.iter().map(|node| node.depth).max().unwrap_or(0)
+    }
+
+    // Nodes producing the given resource kind.
+    pub fn producers_of(&self, kind: ResourceKind) -> impl Iterator<Item = &ProductionChainNode> {
+        debug_assert!(kind.is_single_resource());
+        self.producers_by_resource[kind.index()].iter().map(|&index| &self.nodes[index])
+    }
+
+    pub fn find_node(&self, config_index: usize) -> Option<&ProductionChainNode> {
+        self.nodes.iter().find(|node| node.config_index == config_index)
+    }
+
+    // Every resource kind needed upstream to produce `kind`, not including `kind` itself.
+    pub fn upstream_resources(&self, kind: ResourceKind) -> ResourceKind {
+        let mut visited = ResourceKind::empty();
+        let mut pending: SmallVec<[ResourceKind; RESOURCE_KIND_COUNT]> = SmallVec::new();
+        pending.push(kind);
+
+        while let Some(resource) = pending.pop() {
+            for producer in self.producers_of(resource) {
+                for &input in &producer.inputs {
+                    if !visited.intersects(input) {
+                        visited.insert(input);
+                        pending.push(input);
+                    }
+                }
+            }
+        }
+
+        visited.remove(kind); // Cyclic chains may loop back to the requested kind.
+        visited
+    }
+
+    fn compute_depths(&mut self) {
+        // Longest path relaxation. Bounded by node count so cyclic configs can't loop forever.
+        for _ in 0..self.nodes.len() {
+            let mut changed = false;
+
+            for index in 0..self.nodes.len() {
+                let mut depth = 0;
+                for &input in &self.nodes[index].inputs {
+                    for &supplier in &self.producers_by_resource[input.index()] {
+                        depth = depth.max(self.nodes[supplier].depth + 1);
+                    }
+                }
+
+                if depth != self.nodes[index].depth {
+                    self.nodes[index].depth = depth;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+}
+
+// ----------------------------------------------
+// StarvedInput
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq, Display)]
+pub enum StarvedInputReason {
+    // No building in the world produces this resource.
+    NoProducer,
+    // Producers exist but nothing is in storage to fetch from.
+    NoStock,
+    // Resource is in storage but hasn't been delivered to us yet.
+    AwaitingDelivery,
+}
+
+#[derive(Copy, Clone)]
+pub struct StarvedInput {
+    pub kind: ResourceKind,
+    pub reason: StarvedInputReason,
+}
+
+pub type StarvedInputs = SmallVec<[StarvedInput; 2]>;
+
+// ----------------------------------------------
+// Building production chain queries
+// ----------------------------------------------
+
+impl Building {
+    // Required inputs this building has run out of, and why. Empty if not a producer.
+    pub fn starved_inputs(&self, context: &SimContext) -> StarvedInputs {
+        if self.archetype_kind() != BuildingArchetypeKind::ProducerBuilding {
+            return StarvedInputs::new();
+        }
+        self.as_producer().starved_inputs(context)
+    }
+}
+
+impl ProducerBuilding {
+    pub fn starved_inputs(&self, context: &SimContext) -> StarvedInputs {
+        let mut starved = StarvedInputs::new();
+
+        for slot in &self.production_input_stock.slots {
+            if slot.count != 0 {
+                continue;
+            }
+
+            let reason = if context.world().stats().resources.stored(slot.kind) != 0 {
+                StarvedInputReason::AwaitingDelivery
+            } else if Self::has_producer_in_world(context, slot.kind) {
+                StarvedInputReason::NoStock
+            } else {
+                StarvedInputReason::NoProducer
+            };
+
+            starved.push(StarvedInput { kind: slot.kind, reason });
+        }
+
+        starved
+    }
+
+    fn has_producer_in_world(context: &SimContext, kind: ResourceKind) -> bool {
+        let chains = BuildingConfigs::get().production_chains();
+
+        let mut producer_kinds = BuildingKind::empty();
+        for node in chains.producers_of(kind) {
+            producer_kinds |= node.kind;
+        }
+
+        if producer_kinds.is_empty() {
+            return false;
+        }
+
+        let mut found = false;
+        context.world().for_each_building(producer_kinds, |building| {
+            found = building.as_producer().production_output() == kind;
+            !found // Stop at the first match.
+        });
+        found
+    }
+}
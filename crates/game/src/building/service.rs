@@ -18,17 +18,24 @@ use super::{
     BuildingKind,
     BuildingStock,
     config::{BuildingConfig, BuildingConfigs, building_config},
+    construction::BuildingConstructionConfig,
+    entrance::BuildingEntranceConfig,
 };
 use crate::{
     cheats,
     tile::Tile,
     save_context::PostLoadContext,
-    world::{object::GameObject, stats::WorldStats},
+    world::{
+        object::GameObject,
+        stats::WorldStats,
+        config_reload::{HotReloadableConfig, reload_field},
+    },
     undo_redo::{GameObjectSavedState, game_object_undo_redo_state},
     debug::game_object_debug::{GameObjectDebugOptions, debug_popup_msg_color, game_object_debug_options},
     sim::{
         SimCmds,
         SimContext,
+        RandomGenerator,
         resources::{RESOURCE_KIND_COUNT, ResourceKind, ResourceKinds, ShoppingList, StockItem, Workers},
     },
     unit::{
@@ -59,9 +66,22 @@ pub struct ServiceConfig {
     pub min_workers: u32,
     pub max_workers: u32,
 
+    // Max road distance to houses we hire from. Uses the global workers search radius if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub hiring_radius: Option<i32>,
+
+    // Higher priority employers are staffed first when workers are scarce.
+    #[serde(default)]
+    pub labor_priority: u32,
+
     pub effect_radius: i32, // How far our patrol unit can go.
     pub requires_road_access: bool,
 
+    // Only runs when next to an aqueduct or reservoir with water (e.g. fountains).
+    #[serde(default)]
+    pub requires_water_supply: bool,
+
     #[serde(default)]
     pub has_patrol_unit: bool,
 
@@ -81,6 +101,16 @@ pub struct ServiceConfig {
     // Capacity for each resource kind it accepts.
     #[serde(default)]
     pub stock_capacity: u32,
+
+    // Explicit entrance cell. Any surrounding cell gives access if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub entrance: Option<BuildingEntranceConfig>,
+
+    // Materials and build time when placed by the player. Appears fully built if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub construction: Option<BuildingConstructionConfig>,
 }
 
 impl Default for ServiceConfig {
@@ -95,18 +125,59 @@ impl Default for ServiceConfig {
             max_workers: 0,
             effect_radius: 5,
             requires_road_access: false,
+            requires_water_supply: false,
             has_patrol_unit: false,
             patrol_unit: UnitConfigKey::default(),
             patrol_frequency_secs: 0.0,
             stock_update_frequency_secs: 0.0,
             resources_required: ResourceKinds::none(),
             stock_capacity: 0,
+            entrance: None,
+            construction: None,
+            hiring_radius: None,
+            labor_priority: 0,
         }
     }
 }
 
 building_config! {
-    ServiceConfig
+    ServiceConfig, entrance, hiring_radius, labor_priority, construction
+}
+
+impl HotReloadableConfig for ServiceConfig {
+    fn reload_key(&self) -> &str {
+        &self.tile_def_name
+    }
+
+    // Patrol and stock update tuning. Stock capacities, patrol units and workers need a restart.
+    fn hot_reload_from(&mut self, new_config: &Self) -> bool {
+        if self.kind != new_config.kind {
+            log::warning!(
+                log::channel!("config"),
+                "ServiceConfig '{}': Building kind changed. Restart to apply.",
+                self.name
+            );
+            return false;
+        }
+
+        let name = &new_config.name;
+
+        let mut changed = reload_field(name, "labor_priority", &mut self.labor_priority, new_config.labor_priority);
+        changed |= reload_field(name, "effect_radius", &mut self.effect_radius, new_config.effect_radius);
+        changed |= reload_field(
+            name,
+            "patrol_frequency_secs",
+            &mut self.patrol_frequency_secs,
+            new_config.patrol_frequency_secs,
+        );
+        changed |= reload_field(
+            name,
+            "stock_update_frequency_secs",
+            &mut self.stock_update_frequency_secs,
+            new_config.stock_update_frequency_secs,
+        );
+        changed
+    }
 }
 
 // ----------------------------------------------
@@ -151,6 +222,10 @@ pub struct ServiceBuilding {
     pub(crate) patrol: Patrol,            // Unit we may send out on patrol to provide the service.
     pub(crate) patrol_timer: UpdateTimer, // Min time before we can send out a new patrol unit.
 
+    // Connected to the aqueduct network as of the last update. Only tracked if `requires_water_supply`.
+    #[serde(default)]
+    has_water_supply: bool,
+
     #[serde(skip)]
     debug: ServiceDebug,
 }
@@ -183,6 +258,13 @@ impl BuildingBehavior for ServiceBuilding {
         self.runner.update(cmds);
         self.patrol.update(cmds);
 
+        // Soldiers can be killed while out on patrol, which never completes their task.
+        self.patrol.reset_if_unit_lost(context.sim_ctx);
+
+        if self.requires_water_supply() {
+            self.has_water_supply = context.sim_ctx.graph().has_water_supply_nearby(context.cell_range());
+        }
+
         let delta_time_secs = context.sim_ctx.delta_time_secs();
         let has_min_required_workers = self.has_min_required_workers();
         let has_stock_requirements = self.stock_or_treasury.is_stock_and_requires_resources();
@@ -237,6 +319,16 @@ impl BuildingBehavior for ServiceBuilding {
         self.config = Some(config);
     }
 
+    fn configs_reloaded(&mut self, _rng: &mut RandomGenerator) {
+        let config = self.config.unwrap();
+
+        if let StockOrTreasury::Stock { update_timer, .. } = &mut self.stock_or_treasury {
+            update_timer.post_load(config.stock_update_frequency_secs);
+        }
+
+        self.patrol_timer.post_load(config.patrol_frequency_secs);
+    }
+
     // ----------------------
     // Resources/Stock:
     // ----------------------
@@ -417,6 +509,10 @@ impl BuildingBehavior for ServiceBuilding {
         self.workers.as_employer().unwrap().has_min_required()
     }
 
+    fn is_operational(&self) -> bool {
+        self.has_min_required_workers() && self.has_min_required_resources() && self.has_water_supply()
+    }
+
     // ----------------------
     // Undo/Redo:
     // ----------------------
@@ -471,10 +567,22 @@ impl ServiceBuilding {
             runner: Runner::default(),
             patrol: Patrol::default(),
             patrol_timer: UpdateTimer::new(config.patrol_frequency_secs),
+            has_water_supply: false,
             debug: ServiceDebug::default(),
         }
     }
 
+    #[inline]
+    pub(crate) fn requires_water_supply(&self) -> bool {
+        self.config.unwrap().requires_water_supply
+    }
+
+    // Always true for services that do not need water.
+    #[inline]
+    pub(crate) fn has_water_supply(&self) -> bool {
+        !self.requires_water_supply() || self.has_water_supply
+    }
+
     pub fn register_callbacks() {
         let _: Callback<UnitTaskFetchCompletionCallback> = callback::register!(ServiceBuilding::on_resources_fetched);
         let _: Callback<PatrolCompletionCallback> = callback::register!(ServiceBuilding::on_patrol_completed);
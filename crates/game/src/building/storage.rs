@@ -11,12 +11,14 @@ use super::{
     BuildingContext,
     BuildingKind,
     config::{BuildingConfig, BuildingConfigs, building_config},
+    construction::BuildingConstructionConfig,
+    entrance::BuildingEntranceConfig,
 };
 use crate::{
     cheats,
     tile::Tile,
     save_context::PostLoadContext,
-    world::stats::WorldStats,
+    world::{stats::WorldStats, config_reload::{HotReloadableConfig, reload_field}},
     undo_redo::{GameObjectSavedState, game_object_undo_redo_state},
     debug::game_object_debug::{GameObjectDebugOptions, debug_popup_msg, game_object_debug_options},
     sim::{
@@ -47,12 +49,31 @@ pub struct StorageConfig {
     pub min_workers: u32,
     pub max_workers: u32,
 
+    // Max road distance to houses we hire from. Uses the global workers search radius if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub hiring_radius: Option<i32>,
+
+    // Higher priority employers are staffed first when workers are scarce.
+    #[serde(default)]
+    pub labor_priority: u32,
+
     // Resources we can store.
     pub resources_accepted: ResourceKinds,
 
     // Number of storage slots and capacity of each slot.
     pub num_slots: u32,
     pub slot_capacity: u32,
+
+    // Explicit entrance cell. Any surrounding cell gives access if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub entrance: Option<BuildingEntranceConfig>,
+
+    // Materials and build time when placed by the player. Appears fully built if not set.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub construction: Option<BuildingConstructionConfig>,
 }
 
 impl Default for StorageConfig {
@@ -68,12 +89,36 @@ impl Default for StorageConfig {
             resources_accepted: ResourceKinds::all(),
             num_slots: 8,
             slot_capacity: 4,
+            entrance: None,
+            construction: None,
+            hiring_radius: None,
+            labor_priority: 0,
         }
     }
 }
 
 building_config! {
-    StorageConfig
+    StorageConfig, entrance, hiring_radius, labor_priority, construction
+}
+
+impl HotReloadableConfig for StorageConfig {
+    fn reload_key(&self) -> &str {
+        &self.tile_def_name
+    }
+
+    // Storage slots and workers need a restart.
+    fn hot_reload_from(&mut self, new_config: &Self) -> bool {
+        if self.kind != new_config.kind {
+            log::warning!(
+                log::channel!("config"),
+                "StorageConfig '{}': Building kind changed. Restart to apply.",
+                self.name
+            );
+            return false;
+        }
+
+        reload_field(&new_config.name, "labor_priority", &mut self.labor_priority, new_config.labor_priority)
+    }
 }
 
 // ----------------------------------------------
@@ -92,6 +137,7 @@ game_object_undo_redo_state! {
     StorageUndoRedoSavedState,
 
     storage_slots: StorageSlots,
+    refused_resources: ResourceKind,
 }
 
 // ----------------------------------------------
@@ -108,6 +154,11 @@ pub struct StorageBuilding {
     // Stockpiles:
     pub(crate) storage_slots: Box<StorageSlots>,
 
+    // Resource kinds we currently refuse to receive, even if accepted by the config.
+    // Stock already held is kept and can still be fetched, so refusing a kind drains it.
+    #[serde(default)]
+    refused_resources: ResourceKind,
+
     #[serde(skip)]
     debug: StorageDebug,
 }
@@ -239,7 +290,7 @@ impl BuildingBehavior for StorageBuilding {
     }
 
     fn receivable_resources(&self, kind: ResourceKind) -> u32 {
-        if self.has_min_required_workers() {
+        if self.has_min_required_workers() && self.accepts_resource(kind) {
             return self.storage_slots.receivable_resources(kind);
         }
         0
@@ -248,7 +299,7 @@ impl BuildingBehavior for StorageBuilding {
     // Returns number of resources it was able to accommodate, which can be less
     // than `count`.
     fn receive_resources(&mut self, kind: ResourceKind, count: u32) -> u32 {
-        if count != 0 && self.has_min_required_workers() {
+        if count != 0 && self.has_min_required_workers() && self.accepts_resource(kind) {
             let received_count = self.storage_slots.receive_resources(kind, count);
             self.debug.log_resources_gained(kind, received_count);
             return received_count;
@@ -277,6 +328,10 @@ impl BuildingBehavior for StorageBuilding {
         } else {
             unimplemented!("Missing resource tally for storage kind {kind}.");
         }
+
+        for accepted_kind in self.storage_slots.accepted_kinds().iter() {
+            stats.add_storage_free_capacity(accepted_kind, self.receivable_resources(accepted_kind));
+        }
     }
 
     // ----------------------
@@ -306,14 +361,16 @@ impl BuildingBehavior for StorageBuilding {
     fn undo_redo_record(&self) -> Option<Box<dyn GameObjectSavedState>> {
         StorageUndoRedoSavedState::new_state(StorageUndoRedoSavedState {
             storage_slots: self.storage_slots.as_ref().clone(),
+            refused_resources: self.refused_resources,
         })
     }
 
     fn undo_redo_apply(&mut self, state: &dyn GameObjectSavedState) {
         let saved_state = StorageUndoRedoSavedState::downcast(state);
 
-        // NOTE: Only stock is preserved on undo/redo. Runners and workers are reset.
+        // NOTE: Only stock and filters are preserved on undo/redo. Runners and workers are reset.
         *self.storage_slots = saved_state.storage_slots.clone();
+        self.refused_resources = saved_state.refused_resources;
     }
 
     // ----------------------
@@ -340,11 +397,33 @@ impl StorageBuilding {
             config: Some(config),
             workers: Workers::employer(config.min_workers, config.max_workers),
             storage_slots: StorageSlots::new(&config.resources_accepted, config.num_slots, config.slot_capacity),
+            refused_resources: ResourceKind::empty(),
             debug: StorageDebug::default(),
         }
     }
 
     pub fn register_callbacks() {}
+
+    // ----------------------
+    // Accept/Refuse Filters:
+    // ----------------------
+
+    // True if the config accepts `kind` and it is not currently refused.
+    #[inline]
+    pub fn accepts_resource(&self, kind: ResourceKind) -> bool {
+        self.storage_slots.accepted_kinds().intersects(kind) && !self.refused_resources.intersects(kind)
+    }
+
+    #[inline]
+    pub fn refused_resources(&self) -> ResourceKind {
+        self.refused_resources
+    }
+
+    // Carriers stop delivering refused kinds here and look for another storage instead.
+    pub fn set_resource_refused(&mut self, kind: ResourceKind, refused: bool) {
+        debug_assert!(kind.is_single_resource());
+        self.refused_resources.set(kind, refused);
+    }
 }
 
 // ----------------------------------------------
@@ -494,6 +573,12 @@ impl StorageSlots {
         self.slot_capacity
     }
 
+    // All resource kinds accepted by the config (every slot accepts the same kinds).
+    #[inline]
+    pub(crate) fn accepted_kinds(&self) -> ResourceKind {
+        self.slots.first().map(|slot| slot.accepted_kinds()).unwrap_or_default()
+    }
+
     #[inline]
     pub(crate) fn is_slot_free(&self, slot_index: usize) -> bool {
         self.slots[slot_index].is_free()
@@ -0,0 +1,165 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use common::coords::Cell;
+
+use super::{BuildingKind, config::BuildingConfigs};
+use crate::{
+    campaign,
+    cheats,
+    sim::notifications::notify,
+    tile::sets::TileDef,
+    world::stats::WorldStats,
+};
+
+// ----------------------------------------------
+// UnlockMilestone
+// ----------------------------------------------
+
+// Progress a building type is gated behind. Once reached, the building
+// stays unlocked for the rest of the game, even if the milestone is lost.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum UnlockMilestone {
+    Population { min: u32 },
+    // Campaign mission reached (0-based). Always met outside of campaigns.
+    Mission { index: usize },
+}
+
+impl UnlockMilestone {
+    pub fn is_reached(&self, stats: &WorldStats) -> bool {
+        match self {
+            Self::Population { min } => stats.population.total >= *min,
+            Self::Mission { index }  => campaign::active_mission().is_none_or(|active| active.mission_index >= *index),
+        }
+    }
+}
+
+impl Display for UnlockMilestone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Population { min } => write!(f, "Reach {min} population"),
+            Self::Mission { index }  => write!(f, "Reach mission {}", index + 1),
+        }
+    }
+}
+
+// ----------------------------------------------
+// BuildingUnlockConfigs
+// ----------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BuildingUnlockDef {
+    pub kind: BuildingKind,
+    pub milestone: UnlockMilestone,
+}
+
+// Building types not listed here are available from the start.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildingUnlockConfigs {
+    pub unlocks: Vec<BuildingUnlockDef>,
+}
+
+impl BuildingUnlockConfigs {
+    // All building types gated behind a milestone.
+    pub fn gated_kinds(&self) -> BuildingKind {
+        self.unlocks.iter().fold(BuildingKind::empty(), |kinds, def| kinds | def.kind)
+    }
+
+    pub fn find_milestone(&self, kind: BuildingKind) -> Option<&UnlockMilestone> {
+        self.unlocks.iter().find(|def| def.kind.intersects(kind)).map(|def| &def.milestone)
+    }
+}
+
+// ----------------------------------------------
+// BuildingUnlockConfigs Global Singleton
+// ----------------------------------------------
+
+engine::configurations! { BUILDING_UNLOCK_CONFIGS_SINGLETON, BuildingUnlockConfigs, "buildings/unlocks" }
+
+// ----------------------------------------------
+// BuildingUnlocks
+// ----------------------------------------------
+
+// Building types unlocked so far in the current game. Saved with the Simulation.
+// Everything is unlocked if the unlock configs are not loaded (e.g. headless tests).
+#[derive(Default, Serialize, Deserialize)]
+pub struct BuildingUnlocks {
+    unlocked: BuildingKind, // Gated kinds whose milestone has been reached.
+}
+
+impl BuildingUnlocks {
+    // True if every building type in `kind` can be placed.
+    pub fn is_building_unlocked(&self, kind: BuildingKind) -> bool {
+        if cheats::get().ignore_building_unlocks || !BuildingUnlockConfigs::is_initialized() {
+            return true;
+        }
+
+        let locked = BuildingUnlockConfigs::get().gated_kinds().difference(self.unlocked);
+        !locked.intersects(kind)
+    }
+
+    // Non-building tiles are never locked.
+    pub fn is_tile_def_unlocked(&self, tile_def: &TileDef) -> bool {
+        match BuildingConfigs::get().find_building_kind_for_tile_def(tile_def) {
+            Some(kind) => self.is_building_unlocked(kind),
+            None => true,
+        }
+    }
+
+    pub fn update(&mut self, stats: &WorldStats) {
+        if !BuildingUnlockConfigs::is_initialized() {
+            return;
+        }
+
+        let mut newly_unlocked = BuildingKind::empty();
+
+        for def in &BuildingUnlockConfigs::get().unlocks {
+            if !self.unlocked.contains(def.kind) && def.milestone.is_reached(stats) {
+                newly_unlocked |= def.kind;
+            }
+        }
+
+        if !newly_unlocked.is_empty() {
+            self.unlocked |= newly_unlocked;
+
+            let names: Vec<String> = newly_unlocked.iter().map(|kind| kind.to_string()).collect();
+            notify!(BuildingUnlocked, Cell::invalid(), "New buildings available: {}", names.join(", "));
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.unlocked = BuildingKind::empty();
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gated_kinds_and_milestone_lookup() {
+        let configs = BuildingUnlockConfigs {
+            unlocks: vec![
+                BuildingUnlockDef { kind: BuildingKind::Market, milestone: UnlockMilestone::Population { min: 50 } },
+                BuildingUnlockDef { kind: BuildingKind::Temple, milestone: UnlockMilestone::Mission { index: 1 } },
+            ],
+        };
+
+        assert!(configs.gated_kinds() == BuildingKind::Market | BuildingKind::Temple);
+        assert!(configs.find_milestone(BuildingKind::Farm).is_none());
+        assert!(matches!(configs.find_milestone(BuildingKind::Market), Some(UnlockMilestone::Population { min: 50 })));
+
+        let mut stats = WorldStats::default();
+        let milestone = UnlockMilestone::Population { min: 50 };
+        assert!(!milestone.is_reached(&stats));
+
+        stats.population.total = 50;
+        assert!(milestone.is_reached(&stats));
+    }
+}
@@ -9,14 +9,19 @@ use common::{
     time::Seconds,
 };
 use engine::{
-    app::input::{InputAction, InputKey, InputModifiers},
+    app::input::{InputAction, InputKey, InputModifiers, InputSystem},
     render::debug::DebugDraw,
     save::*,
     ui::{self, UiInputEvent, UiSystem},
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{config::GameConfigs, save_context::*};
+use crate::{
+    config::GameConfigs,
+    key_bindings::{GameAction, KeyBindings},
+    save_context::*,
+    unit::UnitId,
+};
 
 // ----------------------------------------------
 // Camera Coordinates and Conventions
@@ -95,6 +100,69 @@ impl CameraZoom {
     pub const SPEED: f32 = 1.0; // pixels per second
 }
 
+// Per-frame input driving manual camera scrolling. See `Camera::update_scrolling()`.
+#[derive(Copy, Clone, Default)]
+pub struct CameraScrollInput {
+    pub cursor_screen_pos: Vec2,
+    pub key_scroll_dir: Vec2, // Scroll offset direction from held pan keys (arrows/WASD by default). Zero if none.
+    pub is_drag_button_down: bool,
+    pub is_cursor_over_ui: bool, // Suppresses edge scrolling and starting a drag.
+}
+
+impl CameraScrollInput {
+    // Pass `keys_enabled = false` when the UI has keyboard focus (e.g. text inputs).
+    pub fn new(input_sys: &InputSystem, cursor_screen_pos: Vec2, is_cursor_over_ui: bool, keys_enabled: bool) -> Self {
+        let key_bindings = KeyBindings::get();
+        let is_action_down = |action| key_bindings.is_action_down(action, input_sys);
+
+        let mut key_scroll_dir = Vec2::zero();
+        if keys_enabled {
+            // Same convention as edge scrolling: +X scrolls left, +Y scrolls up.
+            if is_action_down(GameAction::PanLeft) {
+                key_scroll_dir.x += 1.0;
+            }
+            if is_action_down(GameAction::PanRight) {
+                key_scroll_dir.x -= 1.0;
+            }
+            if is_action_down(GameAction::PanUp) {
+                key_scroll_dir.y += 1.0;
+            }
+            if is_action_down(GameAction::PanDown) {
+                key_scroll_dir.y -= 1.0;
+            }
+        }
+
+        Self {
+            cursor_screen_pos,
+            key_scroll_dir,
+            is_drag_button_down: is_action_down(GameAction::DragPan),
+            is_cursor_over_ui,
+        }
+    }
+}
+
+// Eased camera movement from one isometric point to another. See `Camera::pan_to()`.
+#[derive(Copy, Clone)]
+struct CameraPan {
+    from_iso: IsoPointF32,
+    to_iso: IsoPointF32,
+    elapsed_secs: Seconds,
+    duration_secs: Seconds,
+}
+
+impl CameraPan {
+    #[inline]
+    fn is_finished(&self) -> bool {
+        self.elapsed_secs >= self.duration_secs
+    }
+
+    #[inline]
+    fn current_iso(&self) -> IsoPointF32 {
+        let t = if self.duration_secs > 0.0 { (self.elapsed_secs / self.duration_secs).min(1.0) } else { 1.0 };
+        IsoPointF32(common::lerp(self.from_iso.0, self.to_iso.0, ease_in_out_cubic(t)))
+    }
+}
+
 // ----------------------------------------------
 // Camera
 // ----------------------------------------------
@@ -110,6 +178,21 @@ pub struct Camera {
 
     #[serde(skip)]
     is_scrolling: bool,
+
+    // Screen point kept fixed while smooth zooming. None zooms around the view.
+    #[serde(skip)]
+    zoom_anchor: Option<Vec2>,
+
+    #[serde(skip)]
+    pan: Option<CameraPan>,
+
+    // Cursor position on the previous frame while middle mouse drag panning.
+    #[serde(skip)]
+    drag_anchor: Option<Vec2>,
+
+    // Unit the camera is locked onto, if any. Resolved by the session every frame.
+    #[serde(skip)]
+    followed_unit: Option<UnitId>,
 }
 
 impl Camera {
@@ -130,6 +213,10 @@ impl Camera {
             target_zoom: clamped_scaling,
             is_zooming: false,
             is_scrolling: false,
+            zoom_anchor: None,
+            pan: None,
+            drag_anchor: None,
+            followed_unit: None,
         }
     }
 
@@ -143,6 +230,18 @@ impl Camera {
         self.transform
     }
 
+    // Sets zoom and scroll directly, bypassing zoom smoothing and map bounds clamping.
+    // Used by the map exporter to frame areas outside of the regular scroll limits.
+    #[inline]
+    pub fn set_transform(&mut self, transform: WorldToScreenTransform) {
+        debug_assert!(transform.is_valid());
+        self.transform = transform;
+        self.current_zoom = transform.scaling;
+        self.target_zoom = transform.scaling;
+        self.is_zooming = false;
+        self.zoom_anchor = None;
+    }
+
     #[inline]
     pub fn set_viewport_size(&mut self, new_size: Size) {
         self.viewport_size = new_size;
@@ -332,8 +431,23 @@ impl Camera {
         self.transform.scaling = new_zoom;
     }
 
+    // Like `set_zoom()`, but keeps the world point under `screen_point` fixed on screen.
+    pub fn set_zoom_around(&mut self, zoom: f32, screen_point: Vec2) {
+        let new_zoom = zoom.clamp(CameraZoom::MIN, CameraZoom::MAX);
+
+        let anchor_iso = coords::screen_to_iso_point_f32(screen_point, self.transform);
+
+        let transform_no_offset = WorldToScreenTransform::new(new_zoom, Vec2::zero());
+        let anchor_screen_no_offset = coords::iso_to_screen_point_f32(anchor_iso, transform_no_offset);
+
+        self.transform.scaling = new_zoom;
+        self.set_scroll(screen_point - anchor_screen_no_offset);
+    }
+
+    // Smoothly zoom in/out. With `anchor_screen_pos` the zoom is centered on that
+    // screen point (e.g. the cursor) instead of the current view.
     #[inline]
-    pub fn request_zoom(&mut self, zoom: CameraZoom) {
+    pub fn request_zoom(&mut self, zoom: CameraZoom, anchor_screen_pos: Option<Vec2>) {
         match zoom {
             CameraZoom::In => {
                 // request zoom-in
@@ -344,6 +458,7 @@ impl Camera {
                 self.target_zoom = (self.target_zoom - 1.0).clamp(CameraZoom::MIN, CameraZoom::MAX);
             }
         }
+        self.zoom_anchor = anchor_screen_pos;
         self.is_zooming = true;
     }
 
@@ -356,7 +471,15 @@ impl Camera {
                 self.current_zoom = self.target_zoom;
                 self.is_zooming = false;
             }
-            self.set_zoom(self.current_zoom);
+
+            match self.zoom_anchor {
+                Some(anchor) => self.set_zoom_around(self.current_zoom, anchor),
+                None => self.set_zoom(self.current_zoom),
+            }
+
+            if !self.is_zooming {
+                self.zoom_anchor = None;
+            }
         }
     }
 
@@ -389,22 +512,56 @@ impl Camera {
         };
     }
 
-    pub fn update_scrolling(&mut self, cursor_screen_pos: Vec2, delta_time_secs: Seconds) {
+    // Middle mouse drag panning, keyboard scrolling and screen edge scrolling, in that
+    // order of precedence. Manual scrolling cancels any automatic camera panning.
+    pub fn update_scrolling(&mut self, input: CameraScrollInput, delta_time_secs: Seconds) {
         let configs = &GameConfigs::get().camera;
 
-        let scroll_dir = calc_scroll_delta(cursor_screen_pos, self.viewport_size, configs.scroll_margin);
+        let mut desired_delta = Vec2::zero();
+
+        let is_dragging = input.is_drag_button_down
+            && !configs.disable_drag_panning
+            && (self.drag_anchor.is_some() || !input.is_cursor_over_ui);
+
+        if is_dragging {
+            // The map sticks to the cursor while dragging.
+            if let Some(anchor) = self.drag_anchor {
+                desired_delta = (input.cursor_screen_pos - anchor) * configs.drag_pan_speed;
+            }
+            self.drag_anchor = Some(input.cursor_screen_pos);
+        } else {
+            self.drag_anchor = None;
+
+            if !configs.disable_key_scrolling && input.key_scroll_dir != Vec2::zero() {
+                desired_delta = input.key_scroll_dir.normalize() * configs.key_scroll_speed * delta_time_secs;
+            } else if !configs.disable_edge_scrolling && !input.is_cursor_over_ui {
+                let scroll_dir = calc_scroll_delta(input.cursor_screen_pos, self.viewport_size, configs.scroll_margin);
 
-        let scroll_speed =
-            calc_scroll_speed(cursor_screen_pos, self.viewport_size, configs.scroll_margin, configs.scroll_speed);
+                let scroll_speed = calc_scroll_speed(
+                    input.cursor_screen_pos,
+                    self.viewport_size,
+                    configs.scroll_margin,
+                    configs.scroll_speed,
+                );
 
-        let desired_delta = scroll_dir * scroll_speed * delta_time_secs;
+                desired_delta = scroll_dir * scroll_speed * delta_time_secs;
+            }
+        }
+
+        self.scroll_by(desired_delta);
+    }
+
+    fn scroll_by(&mut self, desired_delta: Vec2) {
         if desired_delta == Vec2::zero() {
             self.is_scrolling = false;
             return;
         }
 
+        // Manual scrolling takes over from any automatic camera movement.
+        self.cancel_panning();
+
         // If unconstrained, move freely.
-        if !configs.constrain_to_playable_map_area {
+        if !GameConfigs::get().camera.constrain_to_playable_map_area {
             self.set_scroll(self.current_scroll() + desired_delta);
             self.is_scrolling = true;
             return;
@@ -490,22 +647,84 @@ impl Camera {
         true
     }
 
+    // ----------------------
+    // Camera Panning:
+    // ----------------------
+
+    // Smoothly moves the camera to `destination_cell` over `duration_secs`.
+    // Stops following any unit. Manual scrolling cancels the pan.
+    pub fn pan_to(&mut self, destination_cell: Cell, duration_secs: Seconds) -> bool {
+        if !destination_cell.is_valid() {
+            return false;
+        }
+
+        self.pan_to_iso(IsoPointF32::from_integer_iso(coords::cell_to_iso(destination_cell)), duration_secs);
+        true
+    }
+
+    pub fn pan_to_iso(&mut self, destination_iso: IsoPointF32, duration_secs: Seconds) {
+        self.followed_unit = None;
+        self.pan = Some(CameraPan {
+            from_iso: self.iso_world_position(),
+            to_iso: destination_iso,
+            elapsed_secs: 0.0,
+            duration_secs,
+        });
+    }
+
+    #[inline]
+    pub fn is_panning(&self) -> bool {
+        self.pan.is_some()
+    }
+
+    // Keeps the camera centered on `unit_id` until cancelled or the unit despawns.
+    #[inline]
+    pub fn follow_unit(&mut self, unit_id: UnitId) {
+        self.pan = None;
+        self.followed_unit = Some(unit_id);
+    }
+
+    #[inline]
+    pub fn followed_unit(&self) -> Option<UnitId> {
+        self.followed_unit
+    }
+
+    // Stops both panning and unit following.
+    #[inline]
+    pub fn cancel_panning(&mut self) {
+        self.pan = None;
+        self.followed_unit = None;
+    }
+
+    pub fn update_panning(&mut self, delta_time_secs: Seconds) {
+        if let Some(pan) = &mut self.pan {
+            pan.elapsed_secs += delta_time_secs;
+
+            let current_iso = pan.current_iso();
+            let is_finished = pan.is_finished();
+
+            self.teleport_iso(current_iso);
+
+            if is_finished {
+                self.pan = None;
+            }
+        }
+    }
+
     // ----------------------
     // Input events:
     // ----------------------
 
     pub fn on_key_input(&mut self, key: InputKey, action: InputAction, modifiers: InputModifiers) -> UiInputEvent {
         let configs = &GameConfigs::get().camera;
+        let key_bindings = KeyBindings::get();
 
-        // [CTRL]+[-] / [CTRL]+[=]: Zoom in/out by a fixed step.
-        if !configs.disable_key_shortcut_zoom
-            && action == InputAction::Press
-            && modifiers.intersects(InputModifiers::Control)
-        {
-            if key == InputKey::Minus {
+        // Zoom in/out by a fixed step. [CTRL]+[-] / [CTRL]+[=] by default.
+        if !configs.disable_key_shortcut_zoom && action == InputAction::Press {
+            if key_bindings.is_key_bound_to(GameAction::ZoomOut, key, modifiers) {
                 self.set_zoom(self.current_zoom() - configs.fixed_step_zoom_amount);
                 return UiInputEvent::Handled;
-            } else if key == InputKey::Equal {
+            } else if key_bindings.is_key_bound_to(GameAction::ZoomIn, key, modifiers) {
                 self.set_zoom(self.current_zoom() + configs.fixed_step_zoom_amount);
                 return UiInputEvent::Handled;
             }
@@ -514,32 +733,67 @@ impl Camera {
         UiInputEvent::NotHandled
     }
 
-    pub fn on_mouse_scroll(&mut self, amount: Vec2) -> UiInputEvent {
+    pub fn on_mouse_scroll(&mut self, amount: Vec2, cursor_screen_pos: Vec2) -> UiInputEvent {
         let configs = &GameConfigs::get().camera;
 
-        if !configs.disable_mouse_scroll_zoom {
-            if configs.disable_smooth_mouse_scroll_zoom {
-                // Fixed step zoom.
-                if amount.y < 0.0 {
-                    self.set_zoom(self.current_zoom() + configs.fixed_step_zoom_amount);
-                    return UiInputEvent::Handled;
-                } else if amount.y > 0.0 {
-                    self.set_zoom(self.current_zoom() - configs.fixed_step_zoom_amount);
-                    return UiInputEvent::Handled;
-                }
-            } else {
-                // Smooth interpolated zoom.
-                if amount.y < 0.0 {
-                    self.request_zoom(CameraZoom::In);
-                    return UiInputEvent::Handled;
-                } else if amount.y > 0.0 {
-                    self.request_zoom(CameraZoom::Out);
-                    return UiInputEvent::Handled;
-                }
+        if configs.disable_mouse_scroll_zoom || amount.y == 0.0 {
+            return UiInputEvent::NotHandled;
+        }
+
+        let zoom_anchor = configs.zoom_to_cursor.then_some(cursor_screen_pos);
+
+        if configs.disable_smooth_mouse_scroll_zoom {
+            // Fixed step zoom.
+            let step = if amount.y < 0.0 { configs.fixed_step_zoom_amount } else { -configs.fixed_step_zoom_amount };
+            match zoom_anchor {
+                Some(anchor) => self.set_zoom_around(self.current_zoom() + step, anchor),
+                None => self.set_zoom(self.current_zoom() + step),
             }
+        } else {
+            // Smooth interpolated zoom.
+            let zoom = if amount.y < 0.0 { CameraZoom::In } else { CameraZoom::Out };
+            self.request_zoom(zoom, zoom_anchor);
         }
 
-        UiInputEvent::NotHandled
+        UiInputEvent::Handled
+    }
+
+    // Trackpad pinch. Applied immediately, since the platform sends small deltas every
+    // frame while the fingers move; smoothing them would make the zoom lag behind.
+    pub fn on_pinch_zoom(&mut self, magnification: f32, cursor_screen_pos: Vec2) -> UiInputEvent {
+        let configs = &GameConfigs::get().camera;
+
+        if configs.disable_trackpad_gestures || magnification == 0.0 {
+            return UiInputEvent::NotHandled;
+        }
+
+        let new_zoom = self.current_zoom() * (1.0 + magnification * configs.pinch_zoom_speed);
+
+        if configs.zoom_to_cursor {
+            self.set_zoom_around(new_zoom, cursor_screen_pos);
+        } else {
+            self.set_zoom(new_zoom);
+        }
+
+        // Stop any smooth zoom in progress so `update_zooming()` doesn't pull against the gesture.
+        self.current_zoom = self.transform.scaling;
+        self.target_zoom = self.transform.scaling;
+        self.is_zooming = false;
+        self.zoom_anchor = None;
+
+        UiInputEvent::Handled
+    }
+
+    // Trackpad two-finger pan. The map follows the fingers, like middle mouse drag panning.
+    pub fn on_pan_gesture(&mut self, delta: Vec2) -> UiInputEvent {
+        let configs = &GameConfigs::get().camera;
+
+        if configs.disable_trackpad_gestures || delta == Vec2::zero() {
+            return UiInputEvent::NotHandled;
+        }
+
+        self.scroll_by(delta * configs.gesture_pan_speed);
+        UiInputEvent::Handled
     }
 
     // ----------------------
@@ -587,6 +841,7 @@ impl Load for Camera {
         // Stop zooming and snap to target zoom.
         self.current_zoom = self.target_zoom;
         self.is_zooming = false;
+        self.zoom_anchor = None;
         self.set_zoom(self.current_zoom);
     }
 }
@@ -955,6 +1210,12 @@ fn calc_visible_cells_range(map_size_in_cells: Size, viewport_size: Size, transf
     crate::tile::selection::bounds(&screen_rect, map_size_in_cells, transform)
 }
 
+// Slow start, fast middle, slow stop. `t` in [0,1].
+#[inline]
+fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) * 0.5 }
+}
+
 fn calc_scroll_delta(cursor_screen_pos: Vec2, viewport_size: Size, scroll_margin: f32) -> Vec2 {
     let mut scroll_delta = Vec2::zero();
 
@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::{sim::resources::ResourceKind, world::stats::WorldStats};
+use crate::{
+    sim::{
+        calendar::CalendarPacing,
+        resources::{ResourceKind, ResourceStock},
+    },
+    world::stats::WorldStats,
+};
 
 // ----------------------------------------------
 // Campaign / Mission Definitions (data-driven)
@@ -29,6 +35,16 @@ pub struct MissionDef {
     pub map: MissionMap,
     #[serde(default)]
     pub requirements: MissionRequirements,
+    // Optional calendar pacing override. Uses the game configs if not set.
+    #[serde(default)]
+    pub calendar: Option<CalendarPacing>,
+    // Scripted events: rewards, messages, early victory or defeat.
+    #[serde(default)]
+    pub triggers: Vec<MissionTrigger>,
+    // Choices offered on completion. The one the player picks is carried into
+    // every later mission of the campaign (see `campaign::meta`).
+    #[serde(default)]
+    pub rewards: Vec<MissionReward>,
 }
 
 // How a mission's starting map is loaded.
@@ -47,32 +63,131 @@ pub struct MissionRequirements {
     pub goals: Vec<MissionGoal>,
 }
 
-// A single completion goal. Extend by adding a variant + a match arm in `is_met`.
+// Everything goals and trigger conditions are evaluated against.
+pub struct MissionState<'a> {
+    // `WorldStats` already aggregates population, employment, treasury total and resource counts.
+    pub stats: &'a WorldStats,
+    // Resources produced since the mission started (see `campaign::record_production`).
+    pub produced: &'a ResourceStock,
+    // In-game days since the mission started.
+    pub days_elapsed: u32,
+}
+
+// A single completion goal. Extend by adding a variant + a match arm in `progress`.
 #[derive(Serialize, Deserialize)]
 pub enum MissionGoal {
     Population { min: u32 },
     Employment { min_employed: u32 },
     Treasury   { min_gold: u32 },
     Resource   { kind: ResourceKind, min: u32 },
+    Produced   { kind: ResourceKind, min: u32 },
 }
 
 impl MissionGoal {
-    // Evaluate this goal against the current world stats. `WorldStats` already
-    // aggregates population, employment, treasury total and resource counts.
-    pub fn is_met(&self, stats: &WorldStats) -> bool {
+    // Value the goal must reach.
+    pub fn target(&self) -> u32 {
         match self {
-            Self::Population { min } => stats.population.total >= *min,
-            Self::Employment { min_employed } => stats.population.employed >= *min_employed,
-            Self::Treasury   { min_gold } => stats.treasury.gold_units_total >= *min_gold,
-            Self::Resource   { kind, min } => stats.resources.all.count(*kind) >= *min,
+            Self::Population { min } | Self::Resource { min, .. } | Self::Produced { min, .. } => *min,
+            Self::Employment { min_employed } => *min_employed,
+            Self::Treasury   { min_gold } => *min_gold,
+        }
+    }
+
+    // Current value and target of this goal. The goal is met once current >= target.
+    pub fn progress(&self, state: &MissionState) -> (u32, u32) {
+        let current = match self {
+            Self::Population { .. } => state.stats.population.total,
+            Self::Employment { .. } => state.stats.population.employed,
+            Self::Treasury   { .. } => state.stats.treasury.gold_units_total,
+            Self::Resource   { kind, .. } => state.stats.resources.all.count(*kind),
+            Self::Produced   { kind, .. } => state.produced.count(*kind),
+        };
+        (current, self.target())
+    }
+
+    pub fn is_met(&self, state: &MissionState) -> bool {
+        let (current, target) = self.progress(state);
+        current >= target
+    }
+}
+
+impl std::fmt::Display for MissionGoal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Population { .. }       => write!(f, "Population"),
+            Self::Employment { .. }       => write!(f, "Employed workers"),
+            Self::Treasury   { .. }       => write!(f, "Gold"),
+            Self::Resource   { kind, .. } => write!(f, "{kind} in stock"),
+            Self::Produced   { kind, .. } => write!(f, "{kind} produced"),
         }
     }
 }
 
 impl MissionRequirements {
     // All goals must be satisfied. An empty goal list is considered already met.
-    pub fn all_met(&self, stats: &WorldStats) -> bool {
-        self.goals.iter().all(|goal| goal.is_met(stats))
+    pub fn all_met(&self, state: &MissionState) -> bool {
+        self.goals.iter().all(|goal| goal.is_met(state))
+    }
+}
+
+// ----------------------------------------------
+// Mission Triggers
+// ----------------------------------------------
+
+// Runs its actions once, on the first sim tick where the condition holds.
+#[derive(Serialize, Deserialize)]
+pub struct MissionTrigger {
+    pub condition: MissionCondition,
+    pub actions: Vec<MissionAction>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum MissionCondition {
+    Goal(MissionGoal),
+    DaysElapsed     { days: u32 },
+    // World stats start out empty, so pair these with a DaysElapsed in an All
+    // condition to give the player some time before they can fire.
+    PopulationBelow { max: u32 },
+    TreasuryBelow   { max_gold: u32 },
+    All(Vec<MissionCondition>),
+}
+
+impl MissionCondition {
+    pub fn is_met(&self, state: &MissionState) -> bool {
+        match self {
+            Self::Goal(goal) => goal.is_met(state),
+            Self::DaysElapsed     { days } => state.days_elapsed >= *days,
+            Self::PopulationBelow { max } => state.stats.population.total < *max,
+            Self::TreasuryBelow   { max_gold } => state.stats.treasury.gold_units_total < *max_gold,
+            Self::All(conditions) => conditions.iter().all(|condition| condition.is_met(state)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum MissionAction {
+    GrantGold { amount: u32 },
+    // Shown to the player in the in-game notification feed.
+    Message { text: String },
+    CompleteMission,
+    FailMission,
+}
+
+// ----------------------------------------------
+// Mission Rewards
+// ----------------------------------------------
+
+// Applied when a later mission of the same campaign starts.
+#[derive(Clone, Serialize, Deserialize)]
+pub enum MissionReward {
+    StartingGold { amount: u32 },
+}
+
+impl std::fmt::Display for MissionReward {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StartingGold { amount } => write!(f, "+{amount} starting gold"),
+        }
     }
 }
 
@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+
+use super::config::MissionReward;
+
+// ----------------------------------------------
+// Campaign Meta-State (persistent, outside of saves)
+// ----------------------------------------------
+
+// Player progress across all campaigns, saved to `assets/configs/game/campaign_progress.json`
+// independently of save games: which missions are unlocked and the rewards picked on
+// completed missions, which are carried into every later mission of the same campaign.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CampaignMetaState {
+    campaigns: Vec<CampaignMetaEntry>,
+}
+
+// Keyed by campaign name, so reordering `campaigns.json` doesn't mix up progress.
+#[derive(Serialize, Deserialize)]
+struct CampaignMetaEntry {
+    name: String,
+    unlocked_missions: usize,
+    #[serde(default)]
+    selected_rewards: Vec<SelectedReward>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SelectedReward {
+    mission_index: usize, // Mission the reward was earned on.
+    reward: MissionReward,
+}
+
+impl CampaignMetaState {
+    // The first mission of every campaign is always unlocked.
+    pub fn unlocked_mission_count(&self, campaign_name: &str) -> usize {
+        self.find_entry(campaign_name).map_or(1, |entry| entry.unlocked_missions.max(1))
+    }
+
+    #[inline]
+    pub fn is_mission_unlocked(&self, campaign_name: &str, mission_index: usize) -> bool {
+        mission_index < self.unlocked_mission_count(campaign_name)
+    }
+
+    // Returns true if the mission was locked before.
+    pub fn unlock_mission(&mut self, campaign_name: &str, mission_index: usize) -> bool {
+        if self.is_mission_unlocked(campaign_name, mission_index) {
+            return false;
+        }
+
+        self.find_or_add_entry(campaign_name).unlocked_missions = mission_index + 1;
+        true
+    }
+
+    // Replaces any reward previously picked on the same mission (e.g. when replaying it).
+    pub fn select_reward(&mut self, campaign_name: &str, mission_index: usize, reward: MissionReward) {
+        let entry = self.find_or_add_entry(campaign_name);
+        entry.selected_rewards.retain(|selected| selected.mission_index != mission_index);
+        entry.selected_rewards.push(SelectedReward { mission_index, reward });
+    }
+
+    // Rewards picked on all missions before `mission_index`.
+    pub fn rewards_carried_into(&self, campaign_name: &str, mission_index: usize) -> Vec<MissionReward> {
+        let Some(entry) = self.find_entry(campaign_name) else {
+            return Vec::new();
+        };
+
+        entry
+            .selected_rewards
+            .iter()
+            .filter(|selected| selected.mission_index < mission_index)
+            .map(|selected| selected.reward.clone())
+            .collect()
+    }
+
+    fn find_entry(&self, campaign_name: &str) -> Option<&CampaignMetaEntry> {
+        self.campaigns.iter().find(|entry| entry.name == campaign_name)
+    }
+
+    fn find_or_add_entry(&mut self, campaign_name: &str) -> &mut CampaignMetaEntry {
+        let index = match self.campaigns.iter().position(|entry| entry.name == campaign_name) {
+            Some(index) => index,
+            None => {
+                self.campaigns.push(CampaignMetaEntry {
+                    name: campaign_name.into(),
+                    unlocked_missions: 1,
+                    selected_rewards: Vec::new(),
+                });
+                self.campaigns.len() - 1
+            }
+        };
+        &mut self.campaigns[index]
+    }
+}
+
+// ----------------------------------------------
+// CampaignMetaState Global Singleton
+// ----------------------------------------------
+
+engine::configurations! { CAMPAIGN_META_STATE_SINGLETON, CampaignMetaState, "game/campaign_progress" }
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missions_unlock_in_order_per_campaign() {
+        let mut meta = CampaignMetaState::default();
+        assert!(meta.is_mission_unlocked("A", 0));
+        assert!(!meta.is_mission_unlocked("A", 1));
+
+        assert!(meta.unlock_mission("A", 1));
+        assert!(!meta.unlock_mission("A", 1)); // Already unlocked.
+        assert!(!meta.unlock_mission("A", 0));
+        assert_eq!(meta.unlocked_mission_count("A"), 2);
+
+        // Other campaigns are unaffected.
+        assert_eq!(meta.unlocked_mission_count("B"), 1);
+    }
+
+    #[test]
+    fn selected_rewards_carry_into_later_missions() {
+        let mut meta = CampaignMetaState::default();
+        meta.select_reward("A", 0, MissionReward::StartingGold { amount: 100 });
+        meta.select_reward("A", 1, MissionReward::StartingGold { amount: 200 });
+
+        assert!(meta.rewards_carried_into("A", 0).is_empty());
+        assert_eq!(meta.rewards_carried_into("A", 1).len(), 1);
+        assert_eq!(meta.rewards_carried_into("A", 2).len(), 2);
+
+        // Replaying a mission replaces its reward instead of stacking another one.
+        meta.select_reward("A", 0, MissionReward::StartingGold { amount: 300 });
+        let carried = meta.rewards_carried_into("A", 1);
+        assert!(matches!(carried[..], [MissionReward::StartingGold { amount: 300 }]));
+    }
+}
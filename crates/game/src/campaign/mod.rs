@@ -8,17 +8,34 @@
 // inside the session. To still persist progress into the player's own saves,
 // the session snapshots this singleton on save and restores it on load.
 //
+// Mission unlocks and the rewards carried between missions are player meta-state
+// that outlives any single save, so they live in `meta::CampaignMetaState`,
+// saved to its own file whenever it changes.
+//
 // The manager is a pure state machine: it never touches `GameLoop`/`Engine`.
 // Functions that change the active mission return the `MissionMap` to load and
 // the caller (UI layer) performs the actual load. This keeps the manager fully
 // unit-testable in the headless test harness.
 
 pub mod config;
+pub mod meta;
 
-use config::{CampaignConfigs, MissionMap};
+use config::{CampaignConfigs, MissionAction, MissionDef, MissionMap, MissionReward, MissionState};
+use meta::CampaignMetaState;
 use serde::{Deserialize, Serialize};
 
-use crate::sim::SimContext;
+use common::coords::Cell;
+use engine::log;
+
+use crate::{
+    sim::{
+        SimContext,
+        calendar::Calendar,
+        notifications::notify,
+        resources::{ResourceKind, ResourceStock},
+    },
+    world::stats::WorldStats,
+};
 
 // ----------------------------------------------
 // Campaign progress & prompts
@@ -37,11 +54,57 @@ pub struct ActiveMission {
     // True once the mission goals were met and acknowledged; prevents re-prompting
     // when the player chose to keep playing the completed mission.
     pub completed: bool,
+    // Set by a FailMission trigger action. Stops all further evaluation.
+    #[serde(default)]
+    pub failed: bool,
+    // Calendar day the mission started on, captured by the first tick.
+    #[serde(default)]
+    pub start_day: Option<u32>,
+    // Indices of the mission triggers that already ran.
+    #[serde(default)]
+    pub fired_triggers: Vec<usize>,
+    // Resources produced since the mission started, for `MissionGoal::Produced`.
+    #[serde(default = "ResourceStock::accept_all")]
+    pub produced: ResourceStock,
+    // Rewards picked on earlier missions, applied by the first tick.
+    #[serde(default)]
+    pub carried_rewards: Vec<MissionReward>,
+}
+
+impl ActiveMission {
+    fn new(campaign_id: usize, mission_index: usize) -> Self {
+        Self {
+            campaign_id,
+            mission_index,
+            completed: false,
+            failed: false,
+            start_day: None,
+            fired_triggers: Vec::new(),
+            produced: ResourceStock::accept_all(),
+            carried_rewards: rewards_carried_into(campaign_id, mission_index),
+        }
+    }
+
+    #[inline]
+    pub fn def(&self) -> Option<&'static MissionDef> {
+        CampaignConfigs::get().mission(self.campaign_id, self.mission_index)
+    }
+
+    #[inline]
+    pub fn is_over(&self) -> bool {
+        self.completed || self.failed
+    }
+
+    pub fn state<'a>(&'a self, stats: &'a WorldStats, calendar: &Calendar) -> MissionState<'a> {
+        let start_day = self.start_day.unwrap_or(calendar.days_elapsed());
+        MissionState { stats, produced: &self.produced, days_elapsed: calendar.days_elapsed().saturating_sub(start_day) }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum CampaignPrompt {
     MissionComplete,
+    MissionFailed,
     CampaignComplete,
 }
 
@@ -85,23 +148,79 @@ pub fn reset() {
     *CampaignManager::get_mut() = CampaignManager::new();
 }
 
+// None outside of a campaign, or before `initialize()` (e.g. headless tests and tools).
 pub fn active_mission() -> Option<ActiveMission> {
+    if !CampaignManager::is_initialized() {
+        return None;
+    }
     CampaignManager::get().progress.active.clone()
 }
 
 // Begin a campaign at its first mission. Returns the map to load, or None if the
 // campaign id is invalid / has no missions.
 pub fn start_campaign(campaign_id: usize) -> Option<MissionMap> {
-    let map = CampaignConfigs::get().mission(campaign_id, 0)?.map.clone();
+    start_mission(campaign_id, 0)
+}
+
+// Begin any unlocked mission of a campaign. Returns the map to load, or None if
+// the mission doesn't exist or is still locked.
+pub fn start_mission(campaign_id: usize, mission_index: usize) -> Option<MissionMap> {
+    let map = CampaignConfigs::get().mission(campaign_id, mission_index)?.map.clone();
+
+    if !is_mission_unlocked(campaign_id, mission_index) {
+        log::error!(log::channel!("campaign"), "Mission {campaign_id}.{mission_index} is locked!");
+        return None;
+    }
 
     let mgr = CampaignManager::get_mut();
-    mgr.progress.active = Some(ActiveMission { campaign_id, mission_index: 0, completed: false });
+    mgr.progress.active = Some(ActiveMission::new(campaign_id, mission_index));
     mgr.pending_prompt = None;
     mgr.suppress_snapshot_restore = true;
 
     Some(map)
 }
 
+// Without the meta-state (headless tests and tools) only first missions are unlocked.
+pub fn is_mission_unlocked(campaign_id: usize, mission_index: usize) -> bool {
+    let Some(campaign) = CampaignConfigs::get().campaign(campaign_id) else {
+        return false;
+    };
+
+    if mission_index >= campaign.missions.len() {
+        return false;
+    }
+
+    if !CampaignMetaState::is_initialized() {
+        return mission_index == 0;
+    }
+
+    CampaignMetaState::get().is_mission_unlocked(&campaign.name, mission_index)
+}
+
+// Player picked one of the active mission's `rewards` on completion. Remembered in
+// the meta-state and carried into the later missions of the campaign.
+pub fn select_reward(reward_index: usize) -> bool {
+    let Some(active) = CampaignManager::get().progress.active.as_ref() else {
+        return false;
+    };
+
+    let (Some(campaign), Some(mission)) = (CampaignConfigs::get().campaign(active.campaign_id), active.def()) else {
+        return false;
+    };
+
+    let Some(reward) = mission.rewards.get(reward_index) else {
+        return false;
+    };
+
+    if !CampaignMetaState::is_initialized() {
+        return false;
+    }
+
+    CampaignMetaState::get_mut().select_reward(&campaign.name, active.mission_index, reward.clone());
+    save_meta_state();
+    true
+}
+
 // Advance to the next mission. Returns its map to load, or None when there is no
 // next mission (in which case a CampaignComplete prompt is raised).
 pub fn advance_to_next_mission() -> Option<MissionMap> {
@@ -114,7 +233,7 @@ pub fn advance_to_next_mission() -> Option<MissionMap> {
 
     if let Some(mission) = CampaignConfigs::get().mission(campaign_id, next_index) {
         let map = mission.map.clone();
-        mgr.progress.active = Some(ActiveMission { campaign_id, mission_index: next_index, completed: false });
+        mgr.progress.active = Some(ActiveMission::new(campaign_id, next_index));
         mgr.pending_prompt = None;
         mgr.suppress_snapshot_restore = true;
         Some(map)
@@ -125,6 +244,24 @@ pub fn advance_to_next_mission() -> Option<MissionMap> {
     }
 }
 
+// Restart the active mission from scratch (e.g. after failing it). Returns its
+// map to load, or None if no mission is active.
+pub fn retry_mission() -> Option<MissionMap> {
+    let mgr = CampaignManager::get_mut();
+
+    let (campaign_id, mission_index) = match mgr.progress.active.as_ref() {
+        Some(active) => (active.campaign_id, active.mission_index),
+        None => return None,
+    };
+
+    let map = CampaignConfigs::get().mission(campaign_id, mission_index)?.map.clone();
+    mgr.progress.active = Some(ActiveMission::new(campaign_id, mission_index));
+    mgr.pending_prompt = None;
+    mgr.suppress_snapshot_restore = true;
+
+    Some(map)
+}
+
 // Player chose to keep playing the completed mission: dismiss the prompt. The
 // mission stays `completed`, so it won't re-trigger.
 pub fn continue_playing() {
@@ -156,23 +293,112 @@ pub fn restore_snapshot(progress: CampaignProgress) {
     mgr.pending_prompt = None;
 }
 
-// Per-tick requirement evaluation. Called from `CampaignSystem::update`.
+// Rewards picked on earlier missions of the campaign, applied when this mission starts.
+pub fn rewards_carried_into(campaign_id: usize, mission_index: usize) -> Vec<MissionReward> {
+    match CampaignConfigs::get().campaign(campaign_id) {
+        Some(campaign) if CampaignMetaState::is_initialized() => {
+            CampaignMetaState::get().rewards_carried_into(&campaign.name, mission_index)
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn unlock_mission(campaign_id: usize, mission_index: usize) {
+    if !CampaignMetaState::is_initialized() {
+        return;
+    }
+
+    // Past the last mission there's nothing to unlock.
+    if let Some(campaign) = CampaignConfigs::get().campaign(campaign_id)
+        && mission_index < campaign.missions.len()
+        && CampaignMetaState::get_mut().unlock_mission(&campaign.name, mission_index)
+    {
+        save_meta_state();
+    }
+}
+
+fn save_meta_state() {
+    if !CampaignMetaState::save() {
+        log::error!(log::channel!("campaign"), "Failed to save campaign meta-state.");
+    }
+}
+
+// Tally of goods produced by the world's producer buildings.
+pub fn record_production(kind: ResourceKind, count: u32) {
+    if !CampaignManager::is_initialized() {
+        return;
+    }
+
+    if let Some(active) = CampaignManager::get_mut().progress.active.as_mut()
+        && !active.is_over()
+    {
+        active.produced.add(kind, count);
+    }
+}
+
+// Per-tick trigger and requirement evaluation. Called from `CampaignSystem::update`.
 pub fn tick(context: &SimContext) {
+    if !CampaignManager::is_initialized() {
+        return;
+    }
+
     let mgr = CampaignManager::get_mut();
 
-    let (campaign_id, mission_index) = match mgr.progress.active.as_ref() {
-        Some(active) if !active.completed => (active.campaign_id, active.mission_index),
-        _ => return,
+    let Some(active) = mgr.progress.active.as_mut() else {
+        return;
     };
 
-    let Some(mission) = CampaignConfigs::get().mission(campaign_id, mission_index) else {
+    if active.is_over() {
+        return;
+    }
+
+    let Some(mission) = active.def() else {
         return;
     };
 
-    if mission.requirements.all_met(context.world().stats()) {
-        if let Some(active) = mgr.progress.active.as_mut() {
+    if active.start_day.is_none() {
+        // First tick of the mission: apply the rewards carried over from earlier missions.
+        for reward in &active.carried_rewards {
+            match reward {
+                MissionReward::StartingGold { amount } => context.treasury_mut().add_gold_units(*amount),
+            }
+        }
+        active.start_day = Some(context.calendar().days_elapsed());
+    }
+
+    // Triggers first, so a FailMission on the same tick the goals are met wins.
+    let mut outcome = None;
+    for (trigger_index, trigger) in mission.triggers.iter().enumerate() {
+        if active.fired_triggers.contains(&trigger_index)
+            || !trigger.condition.is_met(&active.state(context.world().stats(), context.calendar()))
+        {
+            continue;
+        }
+
+        active.fired_triggers.push(trigger_index);
+
+        for action in &trigger.actions {
+            match action {
+                MissionAction::GrantGold { amount } => context.treasury_mut().add_gold_units(*amount),
+                MissionAction::Message { text } => notify!(Objective, Cell::invalid(), "{text}"),
+                MissionAction::CompleteMission => outcome = outcome.or(Some(CampaignPrompt::MissionComplete)),
+                MissionAction::FailMission => outcome = Some(CampaignPrompt::MissionFailed),
+            }
+        }
+    }
+
+    if outcome.is_none() && mission.requirements.all_met(&active.state(context.world().stats(), context.calendar())) {
+        outcome = Some(CampaignPrompt::MissionComplete);
+    }
+
+    match outcome {
+        Some(CampaignPrompt::MissionFailed) => active.failed = true,
+        Some(_) => {
             active.completed = true;
+            unlock_mission(active.campaign_id, active.mission_index + 1);
         }
-        mgr.pending_prompt = Some(CampaignPrompt::MissionComplete);
+        None => return,
     }
+
+    mgr.pending_prompt = outcome;
 }
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use engine::{
+    file_sys::paths::PathRef,
+    log,
+    sound::{CaptionLine, SoundKey, SoundSystem},
+};
+
+// ----------------------------------------------
+// NarrationCaptionsDef
+// ----------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NarrationCaptionsDef {
+    // Narration path relative to "sounds/narration/".
+    pub narration: String,
+    // Caption text is a localization key, translated when displayed.
+    pub lines: Vec<CaptionLine>,
+}
+
+// ----------------------------------------------
+// CaptionConfigs
+// ----------------------------------------------
+
+// Narration caption tracks, loaded from `assets/configs/game/captions.json`.
+// Shown in the HUD while the narration plays, if captions are enabled in the settings.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionConfigs {
+    pub tracks: Vec<NarrationCaptionsDef>,
+}
+
+impl CaptionConfigs {
+    // Loads each captioned narration and attaches its track with the SoundSystem.
+    pub fn register(&self, sound_sys: &mut SoundSystem) {
+        sound_sys.clear_narration_captions();
+
+        if !sound_sys.is_initialized() {
+            return;
+        }
+
+        for track in &self.tracks {
+            let sound_key = sound_sys.load_narration(PathRef::from_str(&track.narration));
+            if !sound_key.is_valid() {
+                log::error!(log::channel!("captions"), "Failed to load captioned narration '{}'.", track.narration);
+                continue;
+            }
+
+            sound_sys.set_narration_captions(sound_key, track.lines.clone());
+        }
+    }
+}
+
+engine::configurations! { CAPTION_CONFIGS_SINGLETON, CaptionConfigs, "game/captions" }
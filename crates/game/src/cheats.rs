@@ -1,6 +1,20 @@
-use common::hash::{self, PreHashedKeyMap, StringHash};
+use std::str::{FromStr, SplitWhitespace};
+
+use common::{
+    coords::Cell,
+    hash::{self, PreHashedKeyMap, StringHash},
+};
+use engine::log;
 use proc_macros::DrawDebugUi;
 
+use crate::{
+    building::BuildingKind,
+    config::GameConfigs,
+    tile::{TileKind, TileMap},
+    ui_context::GameUiContext,
+    world::object::Spawner,
+};
+
 // ----------------------------------------------
 // CheatsLookup
 // ----------------------------------------------
@@ -78,6 +92,286 @@ macro_rules! game_cheats {
 game_cheats! {
     ignore_worker_requirements = false,
     ignore_tile_cost = false,
+    ignore_building_unlocks = false,
+    instant_construction = false,
+}
+
+// ----------------------------------------------
+// CheatArgs
+// ----------------------------------------------
+
+// Ok holds the text to print back to the user, Err the reason the command failed.
+pub type CheatResult = Result<String, String>;
+
+// Typed cursor over the whitespace separated arguments of a cheat command.
+pub struct CheatArgs<'a> {
+    tokens: SplitWhitespace<'a>,
+}
+
+impl<'a> CheatArgs<'a> {
+    pub fn new(args: &'a str) -> Self {
+        Self { tokens: args.split_whitespace() }
+    }
+
+    pub fn optional(&mut self) -> Option<&'a str> {
+        self.tokens.next()
+    }
+
+    pub fn name(&mut self, expected: &str) -> Result<&'a str, String> {
+        self.tokens.next().ok_or_else(|| format!("Missing argument: expected {expected}."))
+    }
+
+    pub fn number<T: FromStr>(&mut self, expected: &str) -> Result<T, String> {
+        let token = self.name(expected)?;
+        token.parse().map_err(|_| format!("Invalid {expected}: '{token}' is not a valid number."))
+    }
+
+    // Accepts on/off, true/false or 1/0.
+    pub fn bool(&mut self, expected: &str) -> Result<bool, String> {
+        self.optional_bool(expected)?.ok_or_else(|| format!("Missing argument: expected {expected}."))
+    }
+
+    pub fn optional_bool(&mut self, expected: &str) -> Result<Option<bool>, String> {
+        let Some(token) = self.tokens.next() else {
+            return Ok(None);
+        };
+
+        match token.to_ascii_lowercase().as_str() {
+            "on" | "true" | "1" => Ok(Some(true)),
+            "off" | "false" | "0" => Ok(Some(false)),
+            _ => Err(format!("Invalid {expected}: '{token}' is not on/off.")),
+        }
+    }
+
+    // Cell coordinates as "x,y". Must be inside the tile map.
+    pub fn cell(&mut self, tile_map: &TileMap) -> Result<Cell, String> {
+        let token = self.name("cell (x,y)")?;
+
+        let cell = token
+            .split_once(',')
+            .and_then(|(x, y)| Some(Cell::new(x.trim().parse().ok()?, y.trim().parse().ok()?)))
+            .ok_or_else(|| format!("Invalid cell: '{token}' is not in x,y format."))?;
+
+        if !tile_map.is_cell_within_bounds(cell) {
+            return Err(format!("Cell {cell} is outside the map bounds ({}).", tile_map.size_in_cells()));
+        }
+
+        Ok(cell)
+    }
+
+    // Single BuildingKind flag name, case insensitive (e.g. "farm", "StorageYard").
+    pub fn building_kind(&mut self) -> Result<BuildingKind, String> {
+        let token = self.name("building kind")?;
+        BuildingKind::all()
+            .iter_names()
+            .find(|(name, _)| name.eq_ignore_ascii_case(token))
+            .map(|(_, kind)| kind)
+            .ok_or_else(|| format!("Unknown building kind: '{token}'."))
+    }
+
+    // Fails if there are arguments left over. Call before the command does anything.
+    pub fn finish(&mut self) -> Result<(), String> {
+        match self.tokens.next() {
+            Some(token) => Err(format!("Unexpected argument: '{token}'.")),
+            None => Ok(()),
+        }
+    }
+}
+
+// ----------------------------------------------
+// CheatCommand
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum CheatPermission {
+    // Safe to run in any build.
+    Always,
+    // Removes game objects or otherwise can't be undone.
+    // Requires the `allow_destructive_cheat_commands` debug config.
+    Destructive,
+}
+
+struct CheatCommand {
+    name: &'static str,
+    usage: &'static str,
+    help: &'static str,
+    permission: CheatPermission,
+    handler: fn(&mut CheatArgs, &mut GameUiContext) -> CheatResult,
+}
+
+impl CheatCommand {
+    fn is_allowed(&self) -> bool {
+        self.permission == CheatPermission::Always || destructive_commands_allowed()
+    }
+
+    fn help_text(&self) -> String {
+        let disabled = if self.is_allowed() { "" } else { " [disabled]" };
+        format!("{} {} - {}{disabled}", self.name, self.usage, self.help)
+    }
+}
+
+const CHEAT_COMMANDS: &[CheatCommand] = &[
+    CheatCommand {
+        name: "help",
+        usage: "[command]",
+        help: "Lists all commands or shows help for one.",
+        permission: CheatPermission::Always,
+        handler: cmd_help,
+    },
+    CheatCommand {
+        name: "cheat",
+        usage: "<name> [on|off]",
+        help: "Shows or sets a cheat flag.",
+        permission: CheatPermission::Always,
+        handler: cmd_cheat,
+    },
+    CheatCommand {
+        name: "gold",
+        usage: "<amount>",
+        help: "Adds gold to the global treasury.",
+        permission: CheatPermission::Always,
+        handler: cmd_gold,
+    },
+    CheatCommand {
+        name: "find",
+        usage: "<building_kind>",
+        help: "Lists all buildings of a kind.",
+        permission: CheatPermission::Always,
+        handler: cmd_find,
+    },
+    CheatCommand {
+        name: "destroy",
+        usage: "<x,y>",
+        help: "Despawns the building, unit or prop at a cell.",
+        permission: CheatPermission::Destructive,
+        handler: cmd_destroy,
+    },
+    CheatCommand {
+        name: "destroy_all",
+        usage: "<building_kind>",
+        help: "Despawns all buildings of a kind.",
+        permission: CheatPermission::Destructive,
+        handler: cmd_destroy_all,
+    },
+];
+
+#[inline]
+pub fn destructive_commands_allowed() -> bool {
+    GameConfigs::get().debug.allow_destructive_cheat_commands
+}
+
+fn find_command(command_name: &str) -> Result<&'static CheatCommand, String> {
+    CHEAT_COMMANDS
+        .iter()
+        .find(|command| command.name.eq_ignore_ascii_case(command_name))
+        .ok_or_else(|| format!("Unknown command: '{command_name}'. Type 'help' for a list of commands."))
+}
+
+// Parses and runs a command line like "destroy 10,12".
+pub fn execute_command(command_line: &str, context: &mut GameUiContext) -> CheatResult {
+    let command_line = command_line.trim();
+    let (command_name, args) = command_line.split_once(char::is_whitespace).unwrap_or((command_line, ""));
+
+    if command_name.is_empty() {
+        return Err("Empty command.".into());
+    }
+
+    let command = find_command(command_name)?;
+
+    if !command.is_allowed() {
+        return Err(format!("Command '{}' is disabled in this build.", command.name));
+    }
+
+    log::info!(log::channel!("cheats"), "Running cheat command: {command_line}");
+    (command.handler)(&mut CheatArgs::new(args), context)
+}
+
+// ----------------------------------------------
+// Cheat Command Handlers
+// ----------------------------------------------
+
+fn cmd_help(args: &mut CheatArgs, _context: &mut GameUiContext) -> CheatResult {
+    let command_name = args.optional();
+    args.finish()?;
+
+    match command_name {
+        Some(command_name) => Ok(find_command(command_name)?.help_text()),
+        None => Ok(CHEAT_COMMANDS.iter().map(CheatCommand::help_text).collect::<Vec<_>>().join("\n")),
+    }
+}
+
+fn cmd_cheat(args: &mut CheatArgs, _context: &mut GameUiContext) -> CheatResult {
+    let cheat_name = args.name("cheat name")?;
+    let new_value = args.optional_bool("cheat value")?;
+    args.finish()?;
+
+    let cheats = get_mut();
+    if let Some(value) = new_value {
+        cheats.try_set_by_name(cheat_name, value).map_err(|err| format!("{err} ('{cheat_name}')"))?;
+    }
+
+    let value = cheats.find_by_name(cheat_name).ok_or_else(|| format!("Unknown cheat: '{cheat_name}'."))?;
+    Ok(format!("{cheat_name} = {value}"))
+}
+
+fn cmd_gold(args: &mut CheatArgs, context: &mut GameUiContext) -> CheatResult {
+    let amount: u32 = args.number("gold amount")?;
+    args.finish()?;
+
+    context.sim.treasury_mut().add_gold_units(amount);
+    Ok(format!("Added {amount} gold."))
+}
+
+fn cmd_find(args: &mut CheatArgs, context: &mut GameUiContext) -> CheatResult {
+    let kind = args.building_kind()?;
+    args.finish()?;
+
+    let mut lines = Vec::new();
+    context.world.for_each_building(kind, |building| {
+        lines.push(format!("{} '{}' @ {}", building.kind(), building.name(), building.base_cell()));
+        true
+    });
+
+    if lines.is_empty() {
+        return Ok(format!("No {kind} buildings."));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+fn cmd_destroy(args: &mut CheatArgs, context: &mut GameUiContext) -> CheatResult {
+    let cell = args.cell(context.tile_map)?;
+    args.finish()?;
+
+    let sim_context = context.new_sim_context();
+    let tile = sim_context
+        .find_tile(cell, TileKind::Building | TileKind::Unit | TileKind::Prop)
+        .ok_or_else(|| format!("Nothing to destroy at {cell}."))?;
+
+    let tile_name = tile.name();
+    Spawner::new(&sim_context).despawn_tile(tile);
+
+    Ok(format!("Destroyed '{tile_name}' at {cell}."))
+}
+
+fn cmd_destroy_all(args: &mut CheatArgs, context: &mut GameUiContext) -> CheatResult {
+    let kind = args.building_kind()?;
+    args.finish()?;
+
+    let mut buildings = Vec::new();
+    context.world.for_each_building(kind, |building| {
+        buildings.push(building.kind_and_id());
+        true
+    });
+
+    let sim_context = context.new_sim_context();
+    let spawner = Spawner::new(&sim_context);
+
+    for kind_and_id in &buildings {
+        spawner.despawn_building_with_id(*kind_and_id);
+    }
+
+    Ok(format!("Destroyed {} {kind} building(s).", buildings.len()))
 }
 
 // ----------------------------------------------
@@ -97,3 +391,39 @@ pub fn get() -> &'static Cheats {
 pub fn get_mut() -> &'static mut Cheats {
     Cheats::get_mut()
 }
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use common::Size;
+
+    use super::*;
+
+    #[test]
+    fn parse_typed_args() {
+        let tile_map = TileMap::new(Size::new(8, 8), None);
+        let mut args = CheatArgs::new("3,4 farm on 250");
+
+        assert!(args.cell(&tile_map) == Ok(Cell::new(3, 4)));
+        assert!(args.building_kind() == Ok(BuildingKind::Farm));
+        assert!(args.bool("value") == Ok(true));
+        assert!(args.number::<u32>("amount") == Ok(250));
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_args() {
+        let tile_map = TileMap::new(Size::new(8, 8), None);
+
+        assert!(CheatArgs::new("8,0").cell(&tile_map).is_err()); // Out of bounds.
+        assert!(CheatArgs::new("3;4").cell(&tile_map).is_err());
+        assert!(CheatArgs::new("castle").building_kind().is_err());
+        assert!(CheatArgs::new("maybe").bool("value").is_err());
+        assert!(CheatArgs::new("-5").number::<u32>("amount").is_err());
+        assert!(CheatArgs::new("").name("name").is_err());
+        assert!(CheatArgs::new("extra").finish().is_err());
+    }
+}
@@ -4,9 +4,15 @@ use common::{Size, time::Seconds};
 use engine::{config::EngineConfigs, runner::RunLoopConfigs};
 use proc_macros::DrawDebugUi;
 use serde::{Deserialize, Serialize};
-use strum::Display;
+use strum::{Display, VariantArray};
 
-use crate::camera::*;
+use crate::{
+    building::BuildingKind,
+    camera::*,
+    i18n::Language,
+    tile::color_palette::ColorPalette,
+    user_settings::UserSettings,
+};
 
 // ----------------------------------------------
 // GameConfigs
@@ -31,9 +37,23 @@ pub struct GameConfigs {
     #[debug_ui(nested)]
     pub sim: SimConfigs,
 
+    // Interface:
+    #[debug_ui(nested)]
+    pub tooltips: TooltipConfigs,
+
     // Debug:
     #[debug_ui(nested)]
     pub debug: DebugConfigs,
+
+    // Localization:
+    #[debug_ui(skip)]
+    pub language: Language,
+
+    // Accessibility:
+    pub show_captions: bool, // Narration captions in the HUD.
+    #[debug_ui(skip)]
+    pub color_palette: ColorPalette,
+    pub hatch_patterns: bool, // Stripes over invalid placement and danger zones.
 }
 
 // ----------------------------------------------
@@ -55,6 +75,32 @@ pub enum LoadMapSetting {
     SaveGame {
         save_file: PathBuf,
     },
+    // Procedurally generated city used by the `--benchmark` run mode.
+    SyntheticCity {
+        size_in_cells: Size,
+        building_count: u32,
+        unit_count: u32,
+    },
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Display, VariantArray, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    // Scales SimConfigs::starting_gold_units.
+    #[inline]
+    pub fn starting_gold_units(self, base_gold_units: u32) -> u32 {
+        match self {
+            Self::Easy   => base_gold_units * 2,
+            Self::Normal => base_gold_units,
+            Self::Hard   => base_gold_units / 2,
+        }
+    }
 }
 
 #[derive(DrawDebugUi, Serialize, Deserialize)]
@@ -63,11 +109,23 @@ pub struct SaveGameConfigs {
     pub load_map_setting: LoadMapSetting,
     pub enable_autosave: bool,
     pub autosave_frequency_secs: Seconds,
+
+    // Embed a minimap thumbnail in the save header, shown by the load game browser.
+    pub save_thumbnails: bool,
+
+    // Copy the last autosave to a separate crash save file when the game panics.
+    pub emergency_save_on_crash: bool,
 }
 
 impl Default for SaveGameConfigs {
     fn default() -> Self {
-        Self { load_map_setting: LoadMapSetting::default(), enable_autosave: true, autosave_frequency_secs: 60.0 }
+        Self {
+            load_map_setting: LoadMapSetting::default(),
+            enable_autosave: true,
+            autosave_frequency_secs: 60.0,
+            save_thumbnails: true,
+            emergency_save_on_crash: true,
+        }
     }
 }
 
@@ -77,7 +135,7 @@ pub struct CameraConfigs {
     pub zoom: f32,
     pub offset: CameraOffset,
 
-    // For fixed step zoom with CTRL +/= key shortcuts.
+    // For fixed step zoom with the Zoom In/Out key bindings (CTRL +/= by default).
     pub fixed_step_zoom_amount: f32,
 
     // Use fixed step zoom with mouse scroll zoom instead of smooth interpolation.
@@ -89,6 +147,9 @@ pub struct CameraConfigs {
     // Disables zooming with keyboard shortcuts.
     pub disable_key_shortcut_zoom: bool,
 
+    // Mouse scroll zoom keeps the world point under the cursor fixed instead of zooming around the view.
+    pub zoom_to_cursor: bool,
+
     // Constrain camera movement to inner map diamond playable area? (debug option).
     pub constrain_to_playable_map_area: bool,
 
@@ -98,11 +159,35 @@ pub struct CameraConfigs {
     // Display map debug bounds and camera debug overlays.
     pub enable_debug_draw: bool,
 
-    // Camera scroll/movement speed in pixels per second.
+    // Screen edge scrolling speed in pixels per second.
     pub scroll_speed: f32,
 
-    // In pixels from screen edge.
+    // Edge scrolling kicks in this many pixels from the screen edge.
     pub scroll_margin: f32,
+
+    // Disables scrolling when the cursor touches the screen edges.
+    pub disable_edge_scrolling: bool,
+
+    // Pan key bindings (arrow keys / WASD by default) scrolling speed in pixels per second.
+    pub key_scroll_speed: f32,
+
+    // Disables scrolling with the pan key bindings.
+    pub disable_key_scrolling: bool,
+
+    // Middle mouse drag panning multiplier. 1 = map moves exactly with the cursor.
+    pub drag_pan_speed: f32,
+
+    // Disables middle mouse press-and-drag panning.
+    pub disable_drag_panning: bool,
+
+    // Trackpad pinch zoom multiplier applied to the platform's magnification delta.
+    pub pinch_zoom_speed: f32,
+
+    // Trackpad two-finger panning multiplier. 1 = map moves exactly with the fingers.
+    pub gesture_pan_speed: f32,
+
+    // Disables trackpad pinch-to-zoom and two-finger panning.
+    pub disable_trackpad_gestures: bool,
 }
 
 impl Default for CameraConfigs {
@@ -114,11 +199,20 @@ impl Default for CameraConfigs {
             disable_smooth_mouse_scroll_zoom: false,
             disable_mouse_scroll_zoom: false,
             disable_key_shortcut_zoom: false,
+            zoom_to_cursor: true,
             constrain_to_playable_map_area: true,
             clamp_to_map_bounds: true,
             enable_debug_draw: false,
             scroll_speed: 500.0,
             scroll_margin: 20.0,
+            disable_edge_scrolling: false,
+            key_scroll_speed: 500.0,
+            disable_key_scrolling: false,
+            drag_pan_speed: 1.0,
+            disable_drag_panning: false,
+            pinch_zoom_speed: 1.0,
+            gesture_pan_speed: 1.0,
+            disable_trackpad_gestures: false,
         }
     }
 }
@@ -130,21 +224,91 @@ pub struct SimConfigs {
     pub random_seed: u64,
     pub update_frequency_secs: Seconds,
     pub paused_update_frequency_secs: Seconds,
+    // Fixed sim tick length. Unit movement advances once per tick; rendering interpolates in between.
+    pub fixed_tick_secs: Seconds,
+    // Ticks are skipped beyond this many per frame, so a slow frame can't snowball.
+    pub max_ticks_per_frame: u32,
     pub starting_gold_units: u32,
     pub start_paused: bool,
-
-    // Length of one in-game day, in simulation seconds. Lets rates (e.g. house
-    // resource consumption) be expressed in intuitive units-per-day.
+    // For sessions not started from the New Game menu.
+    pub difficulty: Difficulty,
+
+    // Calendar pacing. Length of one in-game day, in simulation seconds. Lets rates
+    // (e.g. house resource consumption) be expressed in intuitive units-per-day.
+    // Campaign missions may override these (see `sim::calendar::CalendarPacing`).
+    // Periodic timers (taxes, hazards, invasions, statistics) are tuned against
+    // this default pacing and scale with any override (see `Calendar::paced_secs`).
     pub seconds_per_day: Seconds,
+    pub days_per_month: u32,
+
+    // City statistics history (population, treasury, etc graphs) sampling interval, in simulation seconds.
+    pub statistics_sample_frequency_secs: Seconds,
 
     // Workers/Population:
+    // Default hiring radius (road distance) for workplaces that don't set one in their BuildingConfig.
     pub workers_search_radius: i32,
-    pub workers_update_frequency_secs: Seconds,
 
     // Game Systems:
     pub birds_spawn_frequency: Seconds,
     pub settlers_spawn_frequency_secs: Seconds,
     pub population_per_settler_unit: u32,
+    // Time settlers spend walking off-map (hidden at the map edge) when arriving or leaving.
+    pub settlers_off_map_travel_secs: Seconds,
+
+    // Immigration/Emigration:
+    // City attractiveness [0,1] is a weighted sum of housing availability, employment,
+    // food variety and average household happiness.
+    pub attractiveness_housing_weight: f32,
+    pub attractiveness_employment_weight: f32,
+    pub attractiveness_food_variety_weight: f32,
+    pub attractiveness_happiness_weight: f32,
+    // Settlers arrive while attractiveness is at least this; rate scales with attractiveness.
+    pub immigration_min_attractiveness: f32,
+    pub max_settlers_per_spawn: u32,
+    // Residents start leaving the city when attractiveness drops below this.
+    pub emigration_max_attractiveness: f32,
+    pub max_emigrants_per_update: u32,
+
+    // Taxes/Happiness:
+    // City tax rate (percent) a new game starts with. Household tax income is scaled
+    // by `tax_rate / default_tax_rate`, so the default rate yields the base level tax.
+    pub default_tax_rate_percent: u32,
+    pub max_tax_rate_percent: u32,
+    // Households tolerate rates up to this; above it the tax happiness factor drops
+    // linearly, reaching zero at the max rate.
+    pub tolerated_tax_rate_percent: u32,
+    // Riot risk builds up while city happiness stays below this. Each settlers update
+    // a riot breaks out with `riot_risk * riot_chance` odds, setting the unhappiest house on fire.
+    pub riot_max_happiness: f32,
+    pub riot_chance: f32,
+
+    // Hazards (fire/collapse):
+    pub hazards_update_frequency_secs: Seconds,
+    pub fire_risk_per_update: f32,
+    pub collapse_risk_per_update: f32,
+    pub fire_ignition_chance: f32,
+    pub collapse_chance: f32,
+    pub fire_spread_chance: f32,
+    pub building_burn_duration_secs: Seconds,
+    // Risk accumulation multiplier for buildings covered by a prevention service.
+    pub hazard_prevention_factor: f32,
+    pub fire_prevention_services: BuildingKind,
+    pub collapse_prevention_services: BuildingKind,
+    // Rocks TileDef placed over destroyed buildings. Must be cleared before rebuilding.
+    pub rubble_tile_def_name: String,
+
+    // Invasions/Combat:
+    // Raiders show up at the map edge every `invasion_frequency_secs`, once the city
+    // has at least `invasion_min_population` residents.
+    pub invasion_frequency_secs: Seconds,
+    pub invasion_min_population: u32,
+    pub raiders_per_invasion: u32,
+    // One more raider per this many residents above the minimum (0 for fixed size invasions).
+    pub population_per_extra_raider: u32,
+    // Combat units fight enemies up to this many cells away (manhattan distance).
+    pub melee_range: i32,
+    // Damage a building takes from raider attacks before it is destroyed.
+    pub building_health: f32,
 }
 
 impl Default for SimConfigs {
@@ -154,20 +318,73 @@ impl Default for SimConfigs {
             random_seed: 0xCAFE1CAFE2CAFE3A,
             update_frequency_secs: 0.5,
             paused_update_frequency_secs: 0.5,
+            fixed_tick_secs: 0.05, // 20Hz
+            max_ticks_per_frame: 25,
             starting_gold_units: 0,
             start_paused: false,
+            difficulty: Difficulty::Normal,
             seconds_per_day: 600.0,
+            days_per_month: 30,
+            statistics_sample_frequency_secs: 10.0,
             // Workers/Population:
             workers_search_radius: 20,
-            workers_update_frequency_secs: 20.0,
             // Game Systems:
             birds_spawn_frequency: 20.0,
             settlers_spawn_frequency_secs: 20.0,
             population_per_settler_unit: 1,
+            settlers_off_map_travel_secs: 5.0,
+            // Immigration/Emigration:
+            attractiveness_housing_weight: 0.5,
+            attractiveness_employment_weight: 0.3,
+            attractiveness_food_variety_weight: 0.2,
+            attractiveness_happiness_weight: 0.3,
+            immigration_min_attractiveness: 0.25,
+            max_settlers_per_spawn: 2,
+            emigration_max_attractiveness: 0.15,
+            max_emigrants_per_update: 2,
+            // Taxes/Happiness:
+            default_tax_rate_percent: 10,
+            max_tax_rate_percent: 25,
+            tolerated_tax_rate_percent: 10,
+            riot_max_happiness: 0.25,
+            riot_chance: 0.1,
+            // Hazards (fire/collapse):
+            hazards_update_frequency_secs: 5.0,
+            fire_risk_per_update: 0.01,
+            collapse_risk_per_update: 0.005,
+            fire_ignition_chance: 0.02,
+            collapse_chance: 0.02,
+            fire_spread_chance: 0.25,
+            building_burn_duration_secs: 30.0,
+            hazard_prevention_factor: 0.1,
+            fire_prevention_services: BuildingKind::SmallWell | BuildingKind::LargeWell,
+            collapse_prevention_services: BuildingKind::empty(),
+            rubble_tile_def_name: "round_stones".into(),
+            // Invasions/Combat:
+            invasion_frequency_secs: 1800.0,
+            invasion_min_population: 150,
+            raiders_per_invasion: 3,
+            population_per_extra_raider: 100,
+            melee_range: 1,
+            building_health: 100.0,
         }
     }
 }
 
+#[derive(DrawDebugUi, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TooltipConfigs {
+    // Hovering a building or unit on the map shows a tooltip with its live status.
+    pub show_map_tooltips: bool,
+    pub map_tooltip_delay_secs: Seconds, // Hover time before the tooltip appears.
+}
+
+impl Default for TooltipConfigs {
+    fn default() -> Self {
+        Self { show_map_tooltips: true, map_tooltip_delay_secs: 0.5 }
+    }
+}
+
 #[derive(DrawDebugUi, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DebugConfigs {
@@ -178,6 +395,16 @@ pub struct DebugConfigs {
     pub enable_dev_tile_inspector: bool,
     pub disable_ambient_music: bool,
     pub disable_ambient_sounds: bool,
+    // Sim updates kept in the sim event log (0 disables it).
+    pub sim_event_log_ticks: u32,
+    // Cheat commands that despawn objects. Off by default in release builds.
+    pub allow_destructive_cheat_commands: bool,
+    // Unit inspector path display toggle. Off by default in release builds.
+    pub enable_unit_path_display: bool,
+    // Reload tile sets edited on disk while the game runs. Off by default in release builds.
+    pub hot_reload_tile_sets: bool,
+    // Reload building and unit configs edited on disk while the game runs. Off by default in release builds.
+    pub hot_reload_object_configs: bool,
 }
 
 impl Default for DebugConfigs {
@@ -190,6 +417,11 @@ impl Default for DebugConfigs {
             enable_dev_tile_inspector: true,
             disable_ambient_music: false,
             disable_ambient_sounds: false,
+            sim_event_log_ticks: 240,
+            allow_destructive_cheat_commands: cfg!(debug_assertions),
+            enable_unit_path_display: cfg!(debug_assertions),
+            hot_reload_tile_sets: cfg!(debug_assertions),
+            hot_reload_object_configs: cfg!(debug_assertions),
         }
     }
 }
@@ -200,7 +432,10 @@ impl RunLoopConfigs for GameConfigs {
     }
 
     fn load() -> &'static Self {
-        GameConfigs::load()
+        GameConfigs::load();
+        // Player preferences override the shipped configs, before the Engine is initialized.
+        UserSettings::load_and_apply(GameConfigs::get_mut());
+        GameConfigs::get()
     }
 
     fn get() -> &'static Self {
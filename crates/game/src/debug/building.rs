@@ -32,6 +32,7 @@ use crate::{
         storage::{MAX_STORAGE_SLOTS, StorageBuilding, StorageSlots},
     },
     pathfind,
+    config::GameConfigs,
     debug::DebugUiMode,
     sim::{SimCmds, SimContext, resources::ResourceKind},
     tile::{TileFlags, TileKind},
@@ -142,6 +143,11 @@ impl Building {
             } else if let Some(employer) = workers.as_employer() {
                 color_bullet_bool("Has min workers", self.archetype().has_min_required_workers());
                 color_bullet_bool("Has all workers", employer.is_at_max_capacity());
+                ui.bullet_text(format_small!(
+                    "Hiring radius: {} ({} houses cached)",
+                    self.hiring_radius(),
+                    self.hiring_area().houses().len()
+                ));
                 if employer.is_below_min_required() {
                     ui.bullet_text("Workers:");
                     ui.same_line();
@@ -271,11 +277,6 @@ impl Building {
                 if ui.button("Remove All Workers") {
                     self.remove_all_workers(context.sim_ctx);
                 }
-
-                if is_employer {
-                    // Only employers need to search for workers.
-                    self.workers_update_timer_mut().draw_debug_ui_with_header("Update", ui_sys);
-                }
             }
         }
 
@@ -288,6 +289,10 @@ impl Building {
 
             ui.text(format_small!("Road Link Tile : {}", self.road_link().unwrap_or_default()));
 
+            if let Some(entrance_cell) = self.entrance_cell() {
+                ui.text(format_small!("Entrance Tile  : {} ({})", entrance_cell, self.entrance_facing()));
+            }
+
             let mut show_road_link = self.is_showing_road_link_debug(context.sim_ctx);
             if ui.checkbox("Show Road Link", &mut show_road_link) {
                 self.set_show_road_link_debug(context.sim_ctx, show_road_link);
@@ -298,6 +303,29 @@ impl Building {
             }
         }
 
+        if ui.collapsing_header("Hazards", imgui::TreeNodeFlags::empty()) {
+            let hazards = self.hazards();
+            ui.text(format_small!("Fire Risk     : {:.3}", hazards.fire_risk()));
+            ui.text(format_small!("Collapse Risk : {:.3}", hazards.collapse_risk()));
+
+            if hazards.is_burning() {
+                ui.text_colored(
+                    Color::red().to_array(),
+                    format_small!("Burning! ({:.1}s left)", hazards.burn_remaining_secs()),
+                );
+            } else if hazards.is_destroyed() {
+                ui.text_colored(Color::red().to_array(), "Destroyed.");
+            }
+
+            if ui.button("Set On Fire") {
+                self.set_on_fire(context.sim_ctx, GameConfigs::get().sim.building_burn_duration_secs);
+            }
+
+            if ui.button("Reset Risks") {
+                self.hazards_mut().reset_risks();
+            }
+        }
+
         self.archetype_mut().debug_options().draw_debug_ui(ui_sys);
         self.archetype_mut().draw_debug_ui(cmds, context, ui_sys);
     }
@@ -406,7 +434,8 @@ impl DrawDebugUi for StorageSlots {
                 } else {
                     let accepted_kinds = slot.accepted_kinds();
 
-                    // Pick a random resource kind from the accepted kinds.
+                    // Pick a random resource kind from the accepted kinds. No SimContext
+                    // in DrawDebugUi, and a manual debug fill isn't replayable anyway.
                     let mut rng = rand::rng();
                     let random_kind = accepted_kinds.iter().choose(&mut rng).unwrap_or(ResourceKind::Rice);
 
@@ -496,8 +525,23 @@ impl DrawDebugUi for StorageSlots {
 
 impl StorageBuilding {
     pub(crate) fn draw_debug_ui_dispatch(&mut self, _cmds: &mut SimCmds, _context: &BuildingContext, ui_sys: &UiSystem) {
+        self.draw_debug_ui_filters(ui_sys);
         self.storage_slots.draw_debug_ui_with_header("Stock Slots", ui_sys);
     }
+
+    fn draw_debug_ui_filters(&mut self, ui_sys: &UiSystem) {
+        let ui = ui_sys.ui();
+        if !ui.collapsing_header("Accept/Refuse Filters", imgui::TreeNodeFlags::empty()) {
+            return; // collapsed.
+        }
+
+        for kind in self.storage_slots.accepted_kinds().iter() {
+            let mut accepted = !self.refused_resources().intersects(kind);
+            if ui.checkbox(format_small!("Accept {}", kind), &mut accepted) {
+                self.set_resource_refused(kind, !accepted);
+            }
+        }
+    }
 }
 
 // ----------------------------------------------
@@ -506,17 +550,24 @@ impl StorageBuilding {
 
 impl ProducerBuilding {
     pub(crate) fn draw_debug_ui_dispatch(&mut self, cmds: &mut SimCmds, context: &BuildingContext, ui_sys: &UiSystem) {
-        self.draw_debug_ui_input_stock(ui_sys);
+        self.draw_debug_ui_input_stock(context, ui_sys);
         self.draw_debug_ui_production_output(context, ui_sys);
         self.draw_debug_ui_ambient_patrol(cmds, context, ui_sys);
     }
 
-    fn draw_debug_ui_input_stock(&mut self, ui_sys: &UiSystem) {
+    fn draw_debug_ui_input_stock(&mut self, context: &BuildingContext, ui_sys: &UiSystem) {
         if self.production_input_stock.requires_any_resource() {
             let ui = ui_sys.ui();
             if ui.collapsing_header("Raw Materials In Stock", imgui::TreeNodeFlags::empty()) {
                 self.production_input_stock.draw_debug_ui(ui_sys);
 
+                for starved in self.starved_inputs(context.sim_ctx) {
+                    ui.text_colored(
+                        Color::red().to_array(),
+                        format_small!("Starved of {}: {}", starved.kind, starved.reason),
+                    );
+                }
+
                 if ui.button("Fill Stock##_fill_input_stock") {
                     self.production_input_stock.fill();
                 }
@@ -711,6 +762,30 @@ impl HouseBuilding {
         self.draw_debug_ui_timers(cmds, context, ui_sys);
         self.draw_debug_ui_stock(context, ui_sys);
         self.draw_debug_ui_upgrade_state(cmds, context, ui_sys);
+        self.draw_debug_ui_happiness(ui_sys);
+    }
+
+    fn draw_debug_ui_happiness(&mut self, ui_sys: &UiSystem) {
+        let ui = ui_sys.ui();
+
+        if !ui.collapsing_header("Happiness", imgui::TreeNodeFlags::empty()) {
+            return; // collapsed.
+        }
+
+        let happiness = *self.happiness();
+        let (worst_factor, _) = happiness.worst_factor();
+
+        ui.text(format_small!("Score    : {:.2} (worst: {worst_factor})", happiness.score()));
+        ui.text(format_small!("Needs    : {:.2}", happiness.needs));
+        ui.text(format_small!("Services : {:.2}", happiness.services));
+        ui.text(format_small!("Tax      : {:.2}", happiness.tax));
+        ui.text(format_small!("Events   : {:.2}", happiness.events));
+
+        static PENALTY: UiStaticVar<f32> = UiStaticVar::new(0.25);
+        ui.input_scalar("Penalty", PENALTY.as_mut()).step(0.05).build();
+        if ui.button("Add Negative Event") {
+            self.add_negative_event(*PENALTY);
+        }
     }
 
     fn draw_debug_ui_upgrade_state(&mut self, cmds: &mut SimCmds, context: &BuildingContext, ui_sys: &UiSystem) {
@@ -804,16 +879,25 @@ impl HouseBuilding {
         let upgrade_state = self.upgrade_state();
 
         let curr_level_requirements =
-            HouseLevelRequirements::new(context, upgrade_state.curr_level_config.unwrap(), &self.stock);
+            HouseLevelRequirements::new(context, upgrade_state.curr_level_config.unwrap(), &self.stock, self.service_coverage());
 
         let next_level_requirements =
-            HouseLevelRequirements::new(context, upgrade_state.next_level_config.unwrap(), &self.stock);
+            HouseLevelRequirements::new(context, upgrade_state.next_level_config.unwrap(), &self.stock, self.service_coverage());
 
         color_text(" - Has room        :", upgrade_state.has_room_to_upgrade);
         color_text(" - Has services    :", next_level_requirements.has_required_services());
         color_text(" - Has resources   :", next_level_requirements.has_required_resources());
         color_text(" - Has road access :", context.is_linked_to_road());
 
+        if let Some(dir) = self.level_transition() {
+            ui.text(format_small!("Level transition: {dir}"));
+        }
+
+        ui.text("Service coverage:");
+        self.service_coverage().for_each(|service_kind, secs_remaining| {
+            ui.text(format_small!("  {service_kind} : {secs_remaining:.1}s"));
+        });
+
         draw_level_requirements(&format_small!("Curr level reqs ({}):", upgrade_state.level), &curr_level_requirements, 0);
 
         if !upgrade_state.level.is_max() {
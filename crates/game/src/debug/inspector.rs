@@ -9,7 +9,7 @@ use crate::{
     GameLoop,
     menu::TileInspector,
     pathfind::NodeKind as PathNodeKind,
-    tile::{Tile, TileDepthSortOverride, TileFlags, TileKind, TileMapLayerKind},
+    tile::{Tile, TileDepthSortOverride, TileFlags, TileKind, TileMapLayerKind, sets::TileSets},
     ui_context::GameUiContext,
 };
 
@@ -56,18 +56,28 @@ impl TileWeakRef {
 pub struct TileInspectorDevMenu {
     is_open: bool,
     selected: Option<TileWeakRef>,
+    multi_selected: Vec<TileWeakRef>, // [CTRL]+click/drag selection; edited in bulk when not empty.
     last_tile_cell: Cell,
 }
 
 impl TileInspector for TileInspectorDevMenu {
     fn open(&mut self, context: &mut GameUiContext) {
-        if let Some(selected_tile) = context.topmost_selected_tile() {
+        let multi_selected: Vec<&Tile> = context.tile_map.multi_selected_tiles(context.tile_selection).collect();
+
+        if multi_selected.len() > 1 {
+            self.open_multi(&multi_selected);
+        } else if let Some(selected_tile) = multi_selected.first().copied().or_else(|| context.topmost_selected_tile()) {
             self.open(selected_tile);
         }
     }
 
-    fn close(&mut self, _context: &mut GameUiContext) {
+    fn close(&mut self, context: &mut GameUiContext) {
         self.close();
+        context.tile_selection.clear_multi_selection();
+    }
+
+    fn supports_multi_select(&self) -> bool {
+        true
     }
 }
 
@@ -75,11 +85,19 @@ impl TileInspectorDevMenu {
     pub fn open(&mut self, selected_tile: &Tile) {
         self.is_open  = true;
         self.selected = Some(TileWeakRef::new(selected_tile));
+        self.multi_selected.clear();
+    }
+
+    pub fn open_multi(&mut self, selected_tiles: &[&Tile]) {
+        self.is_open  = true;
+        self.selected = None;
+        self.multi_selected = selected_tiles.iter().map(|tile| TileWeakRef::new(tile)).collect();
     }
 
     pub fn close(&mut self) {
         self.is_open  = false;
         self.selected = None;
+        self.multi_selected.clear();
     }
 
     pub fn on_tile_placed(&mut self, tile: &Tile, did_reallocate: bool) {
@@ -107,9 +125,19 @@ impl TileInspectorDevMenu {
                 self.close();
             }
         }
+
+        let is_removed_tile = |selected: &Tile| {
+            selected.base_cell() == tile.base_cell() && selected.layer_kind() == tile.layer_kind()
+        };
+        self.multi_selected.retain(|tile_ref| !tile_ref.try_tile().is_some_and(is_removed_tile));
     }
 
     pub fn draw(&mut self, context: &mut GameUiContext) {
+        if !self.multi_selected.is_empty() {
+            self.draw_bulk_editor(context);
+            return;
+        }
+
         let (tile_screen_rect, window_label) = {
             let tile = match self.try_get_selected_tile() {
                 Some(tile) => tile,
@@ -192,9 +220,10 @@ impl TileInspectorDevMenu {
         if game_object_handle.is_valid() {
             return format_fixed_string!(
                 128,
-                "{} - ID({},{:x})",
+                "{} - ID({},{},{:x})",
                 tile.kind(),
                 game_object_handle.index(),
+                game_object_handle.generation(),
                 game_object_handle.kind()
             );
         }
@@ -341,6 +370,7 @@ impl TileInspectorDevMenu {
         tile_flag_ui_checkbox!(ui, tile, Hidden);
         tile_flag_ui_checkbox!(ui, tile, Highlighted);
         tile_flag_ui_checkbox!(ui, tile, Invalidated);
+        tile_flag_ui_checkbox!(ui, tile, Selected);
         tile_flag_ui_checkbox!(ui, tile, OccludesTerrain);
         tile_flag_ui_checkbox!(ui, tile, BuildingRoadLink);
         tile_flag_ui_checkbox!(ui, tile, SettlersSpawnPoint);
@@ -365,6 +395,9 @@ impl TileInspectorDevMenu {
         let mut variation_index = tile.variation_index();
         if ui.input_scalar("Var idx", &mut variation_index).step(1).build() {
             tile.set_variation_index(variation_index);
+            if tile.is(TileKind::Terrain) {
+                context.tile_map.invalidate_terrain_chunks(tile.cell_range());
+            }
         }
 
         ui.text(format_small!("Variations    : {}", tile.variation_count()));
@@ -500,12 +533,32 @@ impl TileInspectorDevMenu {
                 ui.unindent_by(5.0);
             }
         }
+        if !tile.is(TileKind::Terrain) && ui.collapsing_header("Required Proximity", imgui::TreeNodeFlags::empty()) {
+            if let Some(editable_def) = tile.try_get_editable_tile_def() {
+                let _id = ui.push_id("required_proximity");
+                ui.indent_by(5.0);
+                editable_def.required_proximity.draw_debug_ui(context.ui_sys);
+                ui.unindent_by(5.0);
+            }
+        }
+        if tile.is(TileKind::Object) && ui.collapsing_header("Anim Timings", imgui::TreeNodeFlags::empty()) {
+            ui.indent_by(5.0);
+            Self::anim_timings_editor(context, tile);
+            ui.unindent_by(5.0);
+        }
         ui.unindent_by(3.0);
 
         if ui.button("Refresh all Tiles") {
             super::utils::refresh_cached_tile_visuals(context.tile_map);
         }
 
+        ui.same_line();
+
+        // Validated again on save; invalid edits are logged and the file is left untouched.
+        if ui.button("Save to TileSet File") {
+            TileSets::get().save_tile_def_to_file(tile.tile_def());
+        }
+
         if tile.is(TileKind::Object) {
             ui.separator();
 
@@ -528,6 +581,17 @@ impl TileInspectorDevMenu {
             }
         }
 
+        let mut palette_swap = tile.palette_swap();
+        let primary_changed = ui::input_color(ui, "Palette Primary:", &mut palette_swap.primary);
+        let secondary_changed = ui::input_color(ui, "Palette Secondary:", &mut palette_swap.secondary);
+        if primary_changed || secondary_changed {
+            if let Some(editable_def) = tile.try_get_editable_tile_def() {
+                editable_def.palette_swap.primary = palette_swap.primary.clamp();
+                editable_def.palette_swap.secondary = palette_swap.secondary.clamp();
+                context.tile_map.on_tile_def_edited(tile);
+            }
+        }
+
         ui.separator();
 
         let mut draw_size = tile.draw_size();
@@ -547,6 +611,40 @@ impl TileInspectorDevMenu {
 
         ui.separator();
 
+        let tile_def = tile.tile_def();
+        let mut randomize_placement = tile_def.randomize_placement;
+        let mut flying_object = tile_def.flying_object;
+        let mut water_object = tile_def.water_object;
+        let mut cost = tile_def.cost as i32;
+
+        if ui.checkbox("Randomize placement", &mut randomize_placement) {
+            if let Some(editable_def) = tile.try_get_editable_tile_def() {
+                editable_def.randomize_placement = randomize_placement;
+            }
+        }
+
+        if ui.checkbox("Flying object", &mut flying_object) {
+            if let Some(editable_def) = tile.try_get_editable_tile_def() {
+                editable_def.flying_object = flying_object;
+                context.tile_map.on_tile_def_edited(tile);
+            }
+        }
+
+        if ui.checkbox("Water object", &mut water_object) {
+            if let Some(editable_def) = tile.try_get_editable_tile_def() {
+                editable_def.water_object = water_object;
+                context.tile_map.on_tile_def_edited(tile);
+            }
+        }
+
+        if ui::input_i32(ui, "Cost:", &mut cost, false, Some(1)) {
+            if let Some(editable_def) = tile.try_get_editable_tile_def() {
+                editable_def.cost = cost.max(0) as u32;
+            }
+        }
+
+        ui.separator();
+
         let mut logical_size = tile.logical_size();
         if ui::input_i32_xy(
             ui,
@@ -584,4 +682,231 @@ impl TileInspectorDevMenu {
             );
         }
     }
+
+    // Frame timings for the anim sets of the tile's current variation.
+    // Edits apply live to every tile using this TileDef.
+    fn anim_timings_editor(context: &mut GameUiContext, tile: &mut Tile) {
+        let ui = context.ui_sys.ui();
+        let variation_index = tile.variation_index();
+        let anim_set_count = tile.tile_def().variations[variation_index].anim_sets.len();
+
+        if anim_set_count == 0 {
+            ui.text("No anim sets.");
+            return;
+        }
+
+        for anim_set_index in 0..anim_set_count {
+            let _id = ui.push_id_usize(anim_set_index);
+            let anim_set = &tile.tile_def().variations[variation_index].anim_sets[anim_set_index];
+
+            let mut duration_secs = anim_set.anim_duration_secs();
+            let mut looping = anim_set.looping;
+
+            ui.text(format_small!("{} ({} frames):", anim_set.name, anim_set.frames.len()));
+
+            let duration_changed = ui::input_f32(ui, "Duration:", &mut duration_secs, false, Some(0.1));
+            let looping_changed = ui.checkbox("Looping", &mut looping);
+
+            if (duration_changed || looping_changed)
+                && let Some(editable_def) = tile.try_get_editable_tile_def()
+            {
+                let editable_anim_set = &mut editable_def.variations[variation_index].anim_sets[anim_set_index];
+                editable_anim_set.set_anim_duration_secs(duration_secs);
+                editable_anim_set.looping = looping;
+                context.tile_map.on_tile_def_edited(tile);
+            }
+        }
+    }
+
+    // ----------------------
+    // Multi-selection:
+    // ----------------------
+
+    fn draw_bulk_editor(&mut self, context: &mut GameUiContext) {
+        // Drop any tiles that went away since the last frame.
+        self.multi_selected.retain(|tile_ref| tile_ref.try_tile().is_some());
+
+        if self.multi_selected.is_empty() {
+            self.close();
+            context.tile_selection.clear_multi_selection();
+            return;
+        }
+
+        let ui = context.ui_sys.ui();
+        let mut is_open = self.is_open;
+
+        ui.window("Tile Inspector - Multi-Selection")
+            .opened(&mut is_open)
+            .flags(imgui::WindowFlags::ALWAYS_AUTO_RESIZE)
+            .position([80.0, 80.0], imgui::Condition::FirstUseEver)
+            .build(|| {
+                ui.text(format_small!("{} tiles selected.", self.multi_selected.len()));
+                ui.text_disabled("[CTRL]+click toggles a tile, [CTRL]+drag adds a range.");
+                ui.separator();
+
+                Self::bulk_summary_dropdown(context, &self.multi_selected);
+                Self::bulk_flags_dropdown(context, &self.multi_selected);
+                Self::bulk_variations_dropdown(context, &mut self.multi_selected);
+                Self::bulk_animations_dropdown(context, &mut self.multi_selected);
+            });
+
+        if !is_open {
+            self.close();
+            context.tile_selection.clear_multi_selection();
+        }
+    }
+
+    fn bulk_summary_dropdown(context: &mut GameUiContext, tile_refs: &[TileWeakRef]) {
+        let ui = context.ui_sys.ui();
+        if !ui.collapsing_header("Summary", imgui::TreeNodeFlags::DEFAULT_OPEN) {
+            return; // collapsed.
+        }
+
+        let tiles = || tile_refs.iter().filter_map(TileWeakRef::try_tile);
+
+        ui.text(format_small!("Kind          : {}", mixed_value(tiles().map(|tile| tile.kind()))));
+        ui.text(format_small!("Layer         : {}", mixed_value(tiles().map(|tile| tile.layer_kind()))));
+        ui.text(format_small!("Variation idx : {}", mixed_value(tiles().map(|tile| tile.variation_index()))));
+        ui.text(format_small!("Anim Set idx  : {}", mixed_value(tiles().map(|tile| tile.anim_set_index()))));
+
+        // Tile name counts, most common first.
+        let mut name_counts: Vec<(&str, usize)> = Vec::new();
+        for tile in tiles() {
+            match name_counts.iter_mut().find(|(name, _)| *name == tile.name()) {
+                Some((_, count)) => *count += 1,
+                None => name_counts.push((tile.name(), 1)),
+            }
+        }
+        name_counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        ui.separator();
+        for (name, count) in &name_counts {
+            ui.text(format_small!("{count:>3} x {name}"));
+        }
+    }
+
+    fn bulk_flags_dropdown(context: &mut GameUiContext, tile_refs: &[TileWeakRef]) {
+        let ui = context.ui_sys.ui();
+        if !ui.collapsing_header("Flags", imgui::TreeNodeFlags::empty()) {
+            return; // collapsed.
+        }
+
+        let tile_count = tile_refs.len();
+
+        for (flag_name, flag) in BULK_EDITABLE_FLAGS {
+            let set_count = tile_refs.iter().filter_map(TileWeakRef::try_tile).filter(|tile| tile.has_flags(flag)).count();
+
+            // Mixed values show unchecked; checking applies the flag to every tile.
+            let mut value = set_count == tile_count;
+            if ui.checkbox(flag_name, &mut value) {
+                for tile in tile_refs.iter().filter_map(TileWeakRef::try_tile) {
+                    context.tile_map.set_tile_flags_at_index(tile.index(), tile.layer_kind(), flag, value);
+                }
+            }
+
+            if set_count != 0 && set_count != tile_count {
+                ui.same_line();
+                ui.text_disabled(format_small!("(mixed {set_count}/{tile_count})"));
+            }
+        }
+    }
+
+    fn bulk_variations_dropdown(context: &mut GameUiContext, tile_refs: &mut [TileWeakRef]) {
+        let has_variations = |tile: &Tile| tile.has_variations();
+        if !tile_refs.iter().filter_map(TileWeakRef::try_tile).any(has_variations) {
+            return;
+        }
+
+        let ui = context.ui_sys.ui();
+        if !ui.collapsing_header("Variations", imgui::TreeNodeFlags::empty()) {
+            return; // collapsed.
+        }
+
+        let variation_tiles = || tile_refs.iter().filter_map(TileWeakRef::try_tile).filter(|tile| has_variations(tile));
+        let mixed = mixed_value(variation_tiles().map(|tile| tile.variation_index()));
+        let max_count = variation_tiles().map(|tile| tile.variation_count()).max().unwrap_or(0);
+
+        ui.text(format_small!("Variation idx : {mixed}"));
+        ui.text(format_small!("Max variations: {max_count}"));
+
+        // Starts from the shared index, or the first tile's if mixed. Clamped per tile.
+        let mut variation_index = variation_tiles().next().map_or(0, |tile| tile.variation_index());
+        if ui.input_scalar("Set Var idx", &mut variation_index).step(1).build() {
+            for tile in tile_refs.iter_mut().filter_map(TileWeakRef::try_tile_mut).filter(|tile| has_variations(tile)) {
+                tile.set_variation_index(variation_index);
+                if tile.is(TileKind::Terrain) {
+                    context.tile_map.invalidate_terrain_chunks(tile.cell_range());
+                }
+            }
+        }
+    }
+
+    fn bulk_animations_dropdown(context: &mut GameUiContext, tile_refs: &mut [TileWeakRef]) {
+        let has_animations = |tile: &Tile| tile.has_animations();
+        if !tile_refs.iter().filter_map(TileWeakRef::try_tile).any(has_animations) {
+            return;
+        }
+
+        let ui = context.ui_sys.ui();
+        if !ui.collapsing_header("Animations", imgui::TreeNodeFlags::empty()) {
+            return; // collapsed.
+        }
+
+        let animated_tiles = || tile_refs.iter().filter_map(TileWeakRef::try_tile).filter(|tile| has_animations(tile));
+        let mixed = mixed_value(animated_tiles().map(|tile| tile.anim_set_index()));
+
+        ui.text(format_small!("Anim Set idx  : {mixed}"));
+        ui.text(format_small!("Anim Set name : {}", mixed_value(animated_tiles().map(|tile| tile.anim_set_name()))));
+
+        let mut anim_set_index = animated_tiles().next().map_or(0, |tile| tile.anim_set_index());
+        let mut changed = false;
+
+        // Anim set names are only meaningful if all tiles share the same TileDef and variation.
+        let shared_def = {
+            let mut def_keys = animated_tiles().map(|tile| (tile.tile_def().hash, tile.variation_index()));
+            def_keys.next().is_some_and(|first| def_keys.all(|key| key == first))
+        };
+
+        if shared_def && let Some(first_tile) = animated_tiles().next() {
+            let anim_set_names: Vec<&str> = (0..first_tile.anim_sets_count())
+                .map(|i| first_tile.tile_def().anim_set_name(first_tile.variation_index(), i))
+                .collect();
+            changed |= ui.combo_simple_string("Set Anim Set", &mut anim_set_index, &anim_set_names);
+        }
+
+        changed |= ui.input_scalar("Set Anim Set idx", &mut anim_set_index).step(1).build();
+
+        if changed {
+            for tile in tile_refs.iter_mut().filter_map(TileWeakRef::try_tile_mut).filter(|tile| has_animations(tile)) {
+                tile.set_anim_set_index(anim_set_index);
+            }
+        }
+    }
+}
+
+// Flags exposed in the multi-selection editor. Hover/placement flags are left
+// out since the tile selection rewrites them every frame.
+const BULK_EDITABLE_FLAGS: [(&str, TileFlags); 9] = [
+    ("Hidden",             TileFlags::Hidden),
+    ("OccludesTerrain",    TileFlags::OccludesTerrain),
+    ("BuildingRoadLink",   TileFlags::BuildingRoadLink),
+    ("SettlersSpawnPoint", TileFlags::SettlersSpawnPoint),
+    ("DirtRoadPlacement",  TileFlags::DirtRoadPlacement),
+    ("PavedRoadPlacement", TileFlags::PavedRoadPlacement),
+    ("RandomizePlacement", TileFlags::RandomizePlacement),
+    ("DrawDebugInfo",      TileFlags::DrawDebugInfo),
+    ("DrawDebugBounds",    TileFlags::DrawDebugBounds),
+];
+
+// Shared value of all items, or "(mixed)" if they differ.
+fn mixed_value<T: PartialEq + std::fmt::Display>(mut values: impl Iterator<Item = T>) -> String {
+    let Some(first) = values.next() else {
+        return "-".into();
+    };
+
+    if values.all(|value| value == first) {
+        first.to_string()
+    } else {
+        "(mixed)".into()
+    }
 }
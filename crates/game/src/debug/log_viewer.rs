@@ -5,12 +5,20 @@ use std::{
 
 use arrayvec::ArrayString;
 use common::format_fixed_string;
-use engine::{log, ui::UiSystem};
+use engine::{file_sys, log, ui::UiSystem};
 
 // ----------------------------------------------
 // LogViewerSingleton
 // ----------------------------------------------
 
+const LOG_DUMP_FILENAME: &str = "log_viewer_dump.log";
+
+// Line cap range for the ring buffer, adjustable at runtime from the window.
+const MIN_MAX_LINES: usize = 16;
+const MAX_MAX_LINES: usize = 8192;
+
+const FILTERABLE_LEVELS: [log::Level; 4] = [log::Level::Verbose, log::Level::Info, log::Level::Warning, log::Level::Error];
+
 struct LogViewerSingleton {
     is_window_open: bool,
     is_channel_filter_window_open: bool,
@@ -20,6 +28,14 @@ struct LogViewerSingleton {
     max_lines: usize,
     lines: VecDeque<log::Record>,
     channel_filter: HashMap<log::Channel, bool>,
+
+    // Console filtering & controls:
+    level_filter: [bool; FILTERABLE_LEVELS.len()],
+    search_text: String, // Case-insensitive substring match on the line prefix and message.
+    follow_tail: bool,   // Scroll to new lines as they arrive.
+    is_paused: bool,     // Lines received while paused are held in `paused_lines`.
+    paused_lines: VecDeque<log::Record>,
+    dump_status: Option<String>,
 }
 
 impl LogViewerSingleton {
@@ -33,6 +49,12 @@ impl LogViewerSingleton {
             max_lines,
             lines: VecDeque::with_capacity(max_lines),
             channel_filter: HashMap::new(),
+            level_filter: [true; FILTERABLE_LEVELS.len()],
+            search_text: String::new(),
+            follow_tail: true,
+            is_paused: false,
+            paused_lines: VecDeque::new(),
+            dump_status: None,
         }
     }
 
@@ -50,12 +72,49 @@ impl LogViewerSingleton {
             _ => {}
         }
 
-        if self.lines.len() == self.max_lines {
-            self.lines.pop_front();
+        if self.is_paused {
+            Self::push_capped(&mut self.paused_lines, line, self.max_lines);
+            return;
+        }
+
+        Self::push_capped(&mut self.lines, line, self.max_lines);
+        self.auto_scroll = self.follow_tail;
+    }
+
+    fn push_capped(lines: &mut VecDeque<log::Record>, line: log::Record, max_lines: usize) {
+        if lines.len() == max_lines {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        self.is_paused = paused;
+
+        if !paused {
+            while let Some(line) = self.paused_lines.pop_front() {
+                Self::push_capped(&mut self.lines, line, self.max_lines);
+            }
+            self.auto_scroll = self.follow_tail;
         }
+    }
+
+    fn set_max_lines(&mut self, max_lines: usize) {
+        self.max_lines = max_lines.clamp(MIN_MAX_LINES, MAX_MAX_LINES);
+
+        // Drop the oldest lines that no longer fit.
+        let excess = self.lines.len().saturating_sub(self.max_lines);
+        self.lines.drain(..excess);
+
+        let excess = self.paused_lines.len().saturating_sub(self.max_lines);
+        self.paused_lines.drain(..excess);
+    }
 
-        self.lines.push_back(line);
-        self.auto_scroll = true;
+    fn clear(&mut self) {
+        self.lines.clear();
+        self.paused_lines.clear();
+        self.error_count = 0;
+        self.warning_count = 0;
     }
 
     fn is_channel_enabled(&self, channel: &Option<log::Channel>) -> bool {
@@ -68,6 +127,52 @@ impl LogViewerSingleton {
         true
     }
 
+    fn is_level_enabled(&self, level: log::Level) -> bool {
+        FILTERABLE_LEVELS
+            .iter()
+            .position(|filterable| *filterable == level)
+            .is_none_or(|index| self.level_filter[index])
+    }
+
+    fn passes_filters(&self, line: &log::Record, search_lowercase: &str) -> bool {
+        if !self.is_channel_enabled(&line.channel) || !self.is_level_enabled(line.level) {
+            return false;
+        }
+
+        search_lowercase.is_empty()
+            || line.message.to_lowercase().contains(search_lowercase)
+            || Self::line_prefix(line).to_lowercase().contains(search_lowercase)
+    }
+
+    fn filtered_lines(&self) -> impl Iterator<Item = &log::Record> {
+        let search_lowercase = self.search_text.to_lowercase();
+        self.lines.iter().filter(move |line| self.passes_filters(line, &search_lowercase))
+    }
+
+    fn filtered_text(&self) -> String {
+        let mut text = String::new();
+        for line in self.filtered_lines() {
+            text.push_str(&Self::line_prefix(line));
+            text.push(' ');
+            text.push_str(&line.message);
+            text.push('\n');
+        }
+        text
+    }
+
+    fn dump_filtered_to_file(&mut self) {
+        let logs_path = log::logs_path();
+        let _ = file_sys::create_path(&logs_path);
+
+        // NOTE: Result is shown in the window rather than logged. Logging from
+        // here would re-enter the listener and deadlock on the viewer mutex.
+        let file_path = logs_path.join(LOG_DUMP_FILENAME);
+        self.dump_status = match file_sys::write_file(&file_path, self.filtered_text()) {
+            Ok(_) => Some(format!("Dumped to {file_path}")),
+            Err(err) => Some(format!("Failed to write {file_path}: {err}")),
+        };
+    }
+
     fn set_enabled_channels(&mut self, channels: &[(log::Channel, bool)]) {
         for (channel, is_enabled) in channels {
             self.channel_filter.insert(*channel, *is_enabled);
@@ -89,8 +194,7 @@ impl LogViewerSingleton {
         ui.window("Log Viewer")
             .opened(&mut is_window_open)
             .position([10.0, 20.0], imgui::Condition::FirstUseEver)
-            .size([550.0, 350.0], imgui::Condition::FirstUseEver)
-            .horizontal_scrollbar(true)
+            .size([650.0, 400.0], imgui::Condition::FirstUseEver)
             .menu_bar(true)
             .build(|| {
                 // Draw menu bar:
@@ -100,25 +204,44 @@ impl LogViewerSingleton {
                             self.is_channel_filter_window_open = true;
                         }
                     }
+
+                    if let Some(_menu) = ui.begin_menu("Output") {
+                        if ui.menu_item("Copy Filtered") {
+                            ui.set_clipboard_text(self.filtered_text());
+                        }
+                        if ui.menu_item(format_fixed_string!(64, "Dump Filtered to {LOG_DUMP_FILENAME}")) {
+                            self.dump_filtered_to_file();
+                        }
+                        if ui.menu_item("Clear") {
+                            self.clear();
+                        }
+                    }
                 }
 
+                self.draw_console_controls(ui);
+                ui.separator();
+
                 // Draw log lines:
-                for line in &self.lines {
-                    if !self.is_channel_enabled(&line.channel) {
-                        continue;
-                    }
+                ui.child_window("log_viewer_lines").horizontal_scrollbar(true).build(|| {
+                    let search_lowercase = self.search_text.to_lowercase();
 
-                    let color = line.level.color();
-                    ui.text_colored(color.to_array(), Self::line_prefix(line));
-                    ui.same_line();
-                    ui.text(&line.message);
-                }
+                    for line in &self.lines {
+                        if !self.passes_filters(line, &search_lowercase) {
+                            continue;
+                        }
 
-                // Auto-scroll to bottom if we just added something.
-                if ui.is_window_focused() && self.auto_scroll {
-                    ui.set_scroll_here_y_with_ratio(1.0);
-                    self.auto_scroll = false;
-                }
+                        let color = line.level.color();
+                        ui.text_colored(color.to_array(), Self::line_prefix(line));
+                        ui.same_line();
+                        ui.text(&line.message);
+                    }
+
+                    // Auto-scroll to bottom if we just added something.
+                    if self.auto_scroll {
+                        ui.set_scroll_here_y_with_ratio(1.0);
+                        self.auto_scroll = false;
+                    }
+                });
 
                 if self.is_channel_filter_window_open {
                     self.draw_channel_filter_child_window(ui);
@@ -133,6 +256,40 @@ impl LogViewerSingleton {
         is_window_open
     }
 
+    fn draw_console_controls(&mut self, ui: &imgui::Ui) {
+        for (index, level) in FILTERABLE_LEVELS.iter().enumerate() {
+            ui.checkbox(format_fixed_string!(32, "{level}"), &mut self.level_filter[index]);
+            ui.same_line();
+        }
+
+        ui.set_next_item_width(200.0);
+        ui.input_text("Search", &mut self.search_text).build();
+
+        let mut is_paused = self.is_paused;
+        if ui.checkbox("Pause", &mut is_paused) {
+            self.set_paused(is_paused);
+        }
+
+        ui.same_line();
+        ui.checkbox("Auto-scroll", &mut self.follow_tail);
+
+        ui.same_line();
+        let mut max_lines = self.max_lines as i32;
+        ui.set_next_item_width(150.0);
+        if ui.slider("Max Lines", MIN_MAX_LINES as i32, MAX_MAX_LINES as i32, &mut max_lines) {
+            self.set_max_lines(max_lines as usize);
+        }
+
+        if self.is_paused && !self.paused_lines.is_empty() {
+            ui.same_line();
+            ui.text(format_fixed_string!(32, "({} held)", self.paused_lines.len()));
+        }
+
+        if let Some(dump_status) = &self.dump_status {
+            ui.text(dump_status);
+        }
+    }
+
     fn line_prefix(line: &log::Record) -> ArrayString<128> {
         let chan_str = line.channel.as_ref().map(|chan| chan.name).unwrap_or_default();
         format_fixed_string!(128, "[{:?}]{}", line.level, chan_str)
@@ -143,7 +300,20 @@ impl LogViewerSingleton {
             .opened(&mut self.is_channel_filter_window_open)
             .size([250.0, 300.0], imgui::Condition::FirstUseEver)
             .build(|| {
-                for (channel, is_enabled) in self.channel_filter.iter_mut() {
+                if ui.button("All") {
+                    self.channel_filter.values_mut().for_each(|is_enabled| *is_enabled = true);
+                }
+                ui.same_line();
+                if ui.button("None") {
+                    self.channel_filter.values_mut().for_each(|is_enabled| *is_enabled = false);
+                }
+                ui.separator();
+
+                // HashMap order is random; keep the list stable between frames.
+                let mut channels: Vec<_> = self.channel_filter.iter_mut().collect();
+                channels.sort_unstable_by_key(|(channel, _)| channel.name);
+
+                for (channel, is_enabled) in channels {
                     ui.checkbox(channel.name, is_enabled);
                 }
             });
@@ -168,11 +338,18 @@ fn init_log_viewer_singleton_once() {
 
     LOG_VIEWER_SINGLETON.set(Mutex::new(viewer)).unwrap_or_else(|_| panic!("LogViewerSingleton already initialized!"));
 
-    log::set_listener(|line| {
+    log::add_sink(log::LogSinkFilter::all(), Box::new(LogViewerSink));
+}
+
+// Forwards every log record to the viewer.
+struct LogViewerSink;
+
+impl log::LogSink for LogViewerSink {
+    fn write(&mut self, record: &log::Record) {
         let mut viewer = log_viewer_singleton().lock().unwrap();
 
-        viewer.push_line(line);
-    });
+        viewer.push_line(record.clone());
+    }
 }
 
 fn log_viewer_singleton() -> &'static Mutex<LogViewerSingleton> {
@@ -218,4 +395,20 @@ impl LogViewer {
 
         viewer.draw(ui_sys)
     }
+
+    // Last `max_lines` log lines, one per line. For crash reports, so it never blocks:
+    // returns None if the log is locked (e.g. we panicked while logging) or not initialized.
+    pub fn recent_lines(max_lines: usize) -> Option<String> {
+        let viewer = LOG_VIEWER_SINGLETON.get()?.try_lock().ok()?;
+
+        let mut out = String::new();
+        for line in viewer.lines.iter().skip(viewer.lines.len().saturating_sub(max_lines)) {
+            out.push_str(&LogViewerSingleton::line_prefix(line));
+            out.push(' ');
+            out.push_str(&line.message);
+            out.push('\n');
+        }
+
+        Some(out)
+    }
 }
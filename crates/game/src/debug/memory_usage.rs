@@ -0,0 +1,110 @@
+use common::{Color, format_small};
+use engine::{Engine, ui::UiSystem};
+
+use crate::ui_context::GameUiContext;
+
+// ----------------------------------------------
+// MemoryUsageRegistry
+// ----------------------------------------------
+
+// Subsystems report their memory_usage_estimate() here once per frame. Each entry
+// keeps the peak since the last reset and the change since the previous report.
+pub struct MemoryUsageRegistry {
+    entries: Vec<MemoryUsageEntry>,
+}
+
+struct MemoryUsageEntry {
+    name: &'static str,
+    bytes: usize,
+    peak_bytes: usize,
+    delta_bytes: isize, // Since the previous report.
+}
+
+impl MemoryUsageRegistry {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub fn report(&mut self, name: &'static str, bytes: usize) {
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => {
+                entry.delta_bytes = bytes as isize - entry.bytes as isize;
+                entry.bytes = bytes;
+                entry.peak_bytes = entry.peak_bytes.max(bytes);
+            }
+            None => {
+                self.entries.push(MemoryUsageEntry { name, bytes, peak_bytes: bytes, delta_bytes: 0 });
+            }
+        }
+    }
+
+    pub fn report_all(&mut self, engine: &Engine, context: &GameUiContext) {
+        self.report("TileMap", context.tile_map.memory_usage_estimate());
+        self.report("World", context.world.memory_usage_estimate());
+        self.report("Pathfind Scratch", context.sim.search().memory_usage_estimate());
+        self.report("TextureCache", engine.texture_cache().memory_usage_estimate());
+        self.report("SoundRegistry", engine.sound_system().memory_usage_estimate());
+    }
+
+    pub fn total_bytes(&self) -> usize {
+        self.entries.iter().map(|entry| entry.bytes).sum()
+    }
+
+    pub fn reset_peaks(&mut self) {
+        for entry in &mut self.entries {
+            entry.peak_bytes = entry.bytes;
+        }
+    }
+
+    pub fn draw(&mut self, ui_sys: &UiSystem) -> bool {
+        let ui = ui_sys.ui();
+
+        let mut is_window_open = true;
+        let mut reset_peaks = false;
+
+        ui.window("Memory Usage")
+            .opened(&mut is_window_open)
+            .position([200.0, 80.0], imgui::Condition::FirstUseEver)
+            .size([460.0, 220.0], imgui::Condition::FirstUseEver)
+            .build(|| {
+                ui.text(format_small!("Total: {}", format_kb(self.total_bytes())));
+                ui.same_line();
+                reset_peaks = ui.button("Reset Peaks");
+                ui.separator();
+
+                ui.text(format_small!("{:<18} {:>12} {:>12} {:>12}", "Subsystem", "Current", "Peak", "Delta"));
+
+                for entry in &self.entries {
+                    let line = format_small!(
+                        "{:<18} {:>12} {:>12} {:>12}",
+                        entry.name,
+                        format_kb(entry.bytes),
+                        format_kb(entry.peak_bytes),
+                        format_delta_kb(entry.delta_bytes)
+                    );
+
+                    let color = match entry.delta_bytes.signum() {
+                        1  => Color::yellow(),
+                        -1 => Color::green(),
+                        _  => Color::white(),
+                    };
+                    ui.text_colored(color.to_array(), line);
+                }
+            });
+
+        if reset_peaks {
+            self.reset_peaks();
+        }
+
+        is_window_open
+    }
+}
+
+fn format_kb(bytes: usize) -> String {
+    format!("{:.1}kb", bytes as f64 / 1024.0)
+}
+
+fn format_delta_kb(delta_bytes: isize) -> String {
+    format!("{:+.1}kb", delta_bytes as f64 / 1024.0)
+}
+
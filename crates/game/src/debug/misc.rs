@@ -252,8 +252,11 @@ impl CampaignConfigs {
                     MissionGoal::Employment { min_employed } => ui.bullet_text(format_small!("Employment >= {min_employed}")),
                     MissionGoal::Treasury   { min_gold }     => ui.bullet_text(format_small!("Treasury >= {min_gold} gold")),
                     MissionGoal::Resource   { kind, min }    => ui.bullet_text(format_small!("Resource {kind} >= {min}")),
+                    MissionGoal::Produced   { kind, min }    => ui.bullet_text(format_small!("Produced {kind} >= {min}")),
                 }
             }
         }
+
+        ui.text(format_small!("Triggers: {}", mission.triggers.len()));
     }
 }
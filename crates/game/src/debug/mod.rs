@@ -13,8 +13,10 @@ use engine::{
 };
 use inspector::TileInspectorDevMenu;
 use log_viewer::LogViewer;
+use memory_usage::MemoryUsageRegistry;
 use palette::TilePaletteDevMenu;
 use settings::DebugSettingsDevMenu;
+use sim_event_log::SimEventLogViewer;
 
 use crate::{
     GameLoop,
@@ -32,18 +34,24 @@ use crate::{
 
 pub mod game_object_debug;
 pub mod log_viewer;
+pub mod palette;
 pub mod preset_maps;
 pub mod popups;
 pub mod utils;
 
 mod building;
 mod inspector;
+mod memory_usage;
 mod misc;
-mod palette;
+mod production_chains;
 mod prop;
+mod random_events;
 mod settings;
 mod sim;
+mod sim_event_log;
 mod systems;
+mod texture_atlas;
+mod tile_set_report;
 mod unit;
 mod world;
 
@@ -153,6 +161,8 @@ struct DevEditorMenusSingleton {
     enable_dev_tile_inspector: bool,
     minimap_renderer: DevUiMinimapRenderer,
     log_viewer: LogViewer,
+    sim_event_log_viewer: SimEventLogViewer,
+    memory_usage: MemoryUsageRegistry,
 }
 
 impl DevEditorMenusSingleton {
@@ -165,6 +175,8 @@ impl DevEditorMenusSingleton {
             enable_dev_tile_inspector: GameConfigs::get().debug.enable_dev_tile_inspector,
             minimap_renderer: DevUiMinimapRenderer::new(context),
             log_viewer: LogViewer::new(),
+            sim_event_log_viewer: SimEventLogViewer::new(),
+            memory_usage: MemoryUsageRegistry::new(),
         }
     }
 
@@ -188,6 +200,39 @@ impl DevEditorMenusSingleton {
             *show_log_viewer_window = self.log_viewer.draw(context.ui_sys);
         }
 
+        let show_sim_event_log_window = self.debug_settings_menu.show_sim_event_log_window();
+        if *show_sim_event_log_window {
+            *show_sim_event_log_window = self.sim_event_log_viewer.draw(context.ui_sys, context.camera, context.tile_map);
+        }
+
+        let show_production_chains_window = self.debug_settings_menu.show_production_chains_window();
+        if *show_production_chains_window {
+            *show_production_chains_window = production_chains::draw_production_chains_window(context);
+        }
+
+        let show_texture_atlas_window = self.debug_settings_menu.show_texture_atlas_window();
+        if *show_texture_atlas_window {
+            *show_texture_atlas_window = texture_atlas::draw_texture_atlas_window(context);
+        }
+
+        let show_tile_set_report_window = self.debug_settings_menu.show_tile_set_report_window();
+        if *show_tile_set_report_window {
+            *show_tile_set_report_window = tile_set_report::draw_tile_set_report_window(context);
+        }
+
+        let show_random_events_window = self.debug_settings_menu.show_random_events_window();
+        if *show_random_events_window {
+            *show_random_events_window = random_events::draw_random_events_window(context);
+        }
+
+        // Sampled every frame, even with the window closed, so peaks aren't missed.
+        self.memory_usage.report_all(engine, context);
+
+        let show_memory_usage_window = self.debug_settings_menu.show_memory_usage_window();
+        if *show_memory_usage_window {
+            *show_memory_usage_window = self.memory_usage.draw(context.ui_sys);
+        }
+
         self.tile_palette_menu.draw(context, engine.debug_draw_mut(), show_selection_bounds);
 
         self.debug_settings_menu.draw(context, &self.log_viewer, &mut self.enable_dev_tile_inspector);
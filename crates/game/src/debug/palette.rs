@@ -1,11 +1,14 @@
 #![allow(clippy::too_many_arguments)]
 
-use std::collections::HashMap;
+use std::ptr;
+
+use serde::{Deserialize, Serialize};
 
 use common::{self, Color, Rect, RectTexCoords, Vec2, constants::*, coords::WorldToScreenTransform};
 use engine::{
     app::input::{InputAction, MouseButton},
     file_sys::paths::PathRef,
+    log,
     render::{debug::DebugDraw, texture::TextureHandle},
     ui::{self, UiInputEvent, UiSystem, widgets::UiWidgetContext},
 };
@@ -16,24 +19,93 @@ use crate::{
     menu::{TilePalette, TilePaletteSelection},
     tile::{
         TileKind,
-        rendering::INVALID_TILE_COLOR,
-        sets::{TileCategory, TileDef, TileDefHandle, TileSet, TileSets},
+        color_palette::ColorPalette,
+        elevation::ElevationBrush,
+        sets::{SerializableTileDefHandle, TileDef, TileDefHandle, TileSets},
     },
     ui_context::GameUiContext,
     undo_redo,
 };
 
+// ----------------------------------------------
+// TilePalettePrefs (persistent editor settings)
+// ----------------------------------------------
+
+// Editor palette settings, saved to `assets/configs/game/tile_palette.json`:
+// pinned tiles, the most recently picked tiles and which sections are filtered out.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TilePalettePrefs {
+    favorites: Vec<SerializableTileDefHandle>,
+    recent: Vec<SerializableTileDefHandle>, // Most recent first.
+    hidden_sections: Vec<String>,
+}
+
+impl TilePalettePrefs {
+    const MAX_RECENT_TILES: usize = 8;
+
+    #[inline]
+    pub fn is_favorite(&self, handle: SerializableTileDefHandle) -> bool {
+        self.favorites.contains(&handle)
+    }
+
+    // Pins the tile, or unpins it if it was already a favorite.
+    pub fn toggle_favorite(&mut self, handle: SerializableTileDefHandle) {
+        if let Some(index) = self.favorites.iter().position(|favorite| *favorite == handle) {
+            self.favorites.remove(index);
+        } else {
+            self.favorites.push(handle);
+        }
+    }
+
+    pub fn add_recent(&mut self, handle: SerializableTileDefHandle) {
+        self.recent.retain(|recent| *recent != handle);
+        self.recent.insert(0, handle);
+        self.recent.truncate(Self::MAX_RECENT_TILES);
+    }
+
+    #[inline]
+    pub fn is_section_hidden(&self, section_name: &str) -> bool {
+        self.hidden_sections.iter().any(|hidden| hidden == section_name)
+    }
+
+    pub fn set_section_hidden(&mut self, section_name: &str, hidden: bool) {
+        self.hidden_sections.retain(|name| name != section_name);
+        if hidden {
+            self.hidden_sections.push(section_name.to_string());
+        }
+    }
+
+    // Tiles no longer in the TileSets (renamed or removed) are skipped.
+    fn resolve(handles: &[SerializableTileDefHandle]) -> SmallVec<[&'static TileDef; 16]> {
+        handles.iter().filter_map(|handle| TileSets::get().serializable_handle_to_tile_def(*handle)).collect()
+    }
+}
+
+// ----------------------------------------------
+// TilePalettePrefs Global Singleton
+// ----------------------------------------------
+
+engine::configurations! { TILE_PALETTE_PREFS_SINGLETON, TilePalettePrefs, "game/tile_palette" }
+
 // ----------------------------------------------
 // TilePaletteDevMenu
 // ----------------------------------------------
 
+const PALETTE_SECTIONS: [(&str, TileKind); 4] = [
+    ("Terrain",   TileKind::Terrain),
+    ("Buildings", TileKind::Building),
+    ("Props",     TileKind::Rocks.union(TileKind::Vegetation)),
+    ("Units",     TileKind::Unit),
+];
+
 #[derive(Default)]
 pub struct TilePaletteDevMenu {
     start_open: bool,
     left_mouse_button_pressed: bool,
     current_selection: TilePaletteSelection,
-    selected_index: HashMap<TileKind, usize>, // For highlighting the selected button.
     clear_button_image: TextureHandle,
+    search_filter: String, // Case-insensitive substring match on the tile name.
 }
 
 impl TilePalette for TilePaletteDevMenu {
@@ -125,17 +197,45 @@ impl TilePaletteDevMenu {
                         self.reset_selection_internal();
                         self.current_selection = TilePaletteSelection::Clear;
                     }
+
+                    for brush in [ElevationBrush::Raise, ElevationBrush::Lower] {
+                        let is_selected = self.current_selection().as_elevation_brush() == Some(brush);
+                        if ui.radio_button_bool(brush.label(), is_selected) {
+                            self.reset_selection_internal();
+                            self.current_selection = TilePaletteSelection::Elevation(brush);
+                        }
+                    }
                 }
 
-                let sections = [
-                    ("Terrain", TileKind::Terrain),
-                    ("", TileKind::Building),
-                    ("Props", TileKind::Rocks | TileKind::Vegetation),
-                    ("Units", TileKind::Unit),
-                ];
+                ui.text("Filter");
+                {
+                    ui.set_next_item_width(window_width);
+                    ui.input_text("##Search", &mut self.search_filter).hint("Search tiles...").build();
+
+                    for (index, (section_name, _)) in PALETTE_SECTIONS.iter().enumerate() {
+                        if index % tiles_per_row != 0 {
+                            ui.same_line();
+                        }
+
+                        let mut shown = !TilePalettePrefs::get().is_section_hidden(section_name);
+                        if ui.checkbox(section_name, &mut shown) {
+                            TilePalettePrefs::get_mut().set_section_hidden(section_name, !shown);
+                            Self::save_prefs();
+                        }
+                    }
+                }
 
-                for (label, tile_kind) in sections {
-                    self.draw_tile_list(label, tile_kind, ui_sys, tiles_per_row, spacing_between_tiles);
+                // Resolved upfront, since pinning/unpinning from these lists edits the prefs.
+                let favorites = TilePalettePrefs::resolve(&TilePalettePrefs::get().favorites);
+                let recent = TilePalettePrefs::resolve(&TilePalettePrefs::get().recent);
+
+                self.draw_pinned_tile_list("Favorites", &favorites, ui_sys, tiles_per_row, spacing_between_tiles);
+                self.draw_pinned_tile_list("Recent", &recent, ui_sys, tiles_per_row, spacing_between_tiles);
+
+                for (section_name, tile_kind) in PALETTE_SECTIONS {
+                    if !TilePalettePrefs::get().is_section_hidden(section_name) {
+                        self.draw_tile_list(section_name, tile_kind, ui_sys, tiles_per_row, spacing_between_tiles);
+                    }
                 }
             });
 
@@ -154,8 +254,7 @@ impl TilePaletteDevMenu {
             let rect = Rect::from_pos_and_size(context.cursor_screen_pos - (CLEAR_ICON_SIZE * 0.5), CLEAR_ICON_SIZE);
 
             debug_draw.textured_colored_rect(rect, &RectTexCoords::DEFAULT, self.clear_button_image, Color::white());
-        } else {
-            let selected_tile = self.current_selection().as_tile_def().unwrap();
+        } else if let Some(selected_tile) = self.current_selection().as_tile_def() {
             let rect = Rect::from_pos_and_size(context.cursor_screen_pos, selected_tile.draw_size.to_vec2());
 
             let offset = if selected_tile.is(TileKind::Building | TileKind::Rocks | TileKind::Vegetation) {
@@ -168,7 +267,8 @@ impl TilePaletteDevMenu {
             let transform = context.camera.transform();
 
             let cursor_transform = WorldToScreenTransform::new(transform.scaling, offset);
-            let highlight_color = if has_valid_placement { Color::white() } else { INVALID_TILE_COLOR };
+            let invalid_color = ColorPalette::current().invalid_tile_color();
+            let highlight_color = if has_valid_placement { Color::white() } else { invalid_color };
 
             if let Some(sprite_frame) = selected_tile.anim_frame_by_index(0, 0, 0) {
                 let tile_color = Color::new(
@@ -178,10 +278,12 @@ impl TilePaletteDevMenu {
                     0.7, // Semi-transparent
                 );
 
+                let tex_info = sprite_frame.resolve_tex_info();
+
                 debug_draw.textured_colored_rect(
                     cursor_transform.scale_and_offset_rect(rect),
-                    &sprite_frame.tex_info.coords,
-                    sprite_frame.tex_info.texture,
+                    &tex_info.coords,
+                    tex_info.texture,
                     tile_color * highlight_color,
                 );
             }
@@ -192,80 +294,129 @@ impl TilePaletteDevMenu {
         }
     }
 
-    fn draw_tile_list(
+    fn draw_tile_button(&mut self, ui_sys: &UiSystem, tile_def: &'static TileDef) {
+        let ui = ui_sys.ui();
+
+        let selected = self.current_selection.as_tile_def().is_some_and(|selected_tile| ptr::eq(selected_tile, tile_def));
+        let serializable_handle = SerializableTileDefHandle::from_tile_def(tile_def);
+
+        let tile_sprite = tile_def.texture_by_index(0, 0, 0);
+        let ui_texture = ui_sys.to_ui_texture(tile_sprite.texture);
+
+        let btn_id = common::fixed_string::snake_case_to_title::<64>(&tile_def.name);
+        let pin_hint = if TilePalettePrefs::get().is_favorite(serializable_handle) {
+            "Right-click to unpin"
+        } else {
+            "Right-click to pin"
+        };
+        let btn_tooltip = if tile_def.cost != 0 {
+            common::format_small!("{}\nCost: {} gold\n{}", btn_id, tile_def.cost, pin_hint)
+        } else {
+            common::format_small!("{}\n{}", btn_id, pin_hint)
+        };
+
+        let btn_params = ui::UiImageButtonParams {
+            id: &btn_id,
+            size: BASE_TILE_SIZE_F32,
+            ui_texture,
+            tooltip: Some(btn_tooltip.as_str()),
+            normal_color: Some(Color::gray()),
+            hovered_color: Some(Color::new(1.0, 1.0, 0.0, 0.1)), // Faint yellow
+            selected_color: Some(Color::white()),
+            tint_color: Some(tile_def.color),
+            top_left_uvs: Some(tile_sprite.coords.top_left()),
+            bottom_right_uvs: Some(tile_sprite.coords.bottom_right()),
+            selected,
+        };
+
+        if ui::image_button(ui_sys, &btn_params) {
+            self.reset_selection_internal();
+            self.current_selection = TilePaletteSelection::Tile(TileDefHandle::from_tile_def(tile_def));
+
+            TilePalettePrefs::get_mut().add_recent(serializable_handle);
+            Self::save_prefs();
+        }
+
+        if ui.is_item_clicked_with_button(imgui::MouseButton::Right) {
+            TilePalettePrefs::get_mut().toggle_favorite(serializable_handle);
+            Self::save_prefs();
+        }
+    }
+
+    // Favorites and recently used tiles.
+    fn draw_pinned_tile_list(
         &mut self,
         label: &str,
-        tile_kind: TileKind,
+        tile_defs: &[&'static TileDef],
         ui_sys: &UiSystem,
         tiles_per_row: usize,
         padding_between_tiles: f32,
     ) {
         let ui = ui_sys.ui();
 
-        if !label.is_empty() {
-            ui.text(label);
-        }
-
-        let mut tile_index = 0;
-        let mut draw_tile_button = |tile_set: &TileSet, tile_category: &TileCategory, tile_def: &TileDef| {
-            if !tile_def.is(tile_kind) {
-                return true;
-            }
+        let tile_defs: SmallVec<[&'static TileDef; 16]> =
+            tile_defs.iter().copied().filter(|tile_def| Self::matches_search(&self.search_filter, tile_def)).collect();
 
-            let selected = self.selected_index.get(&tile_kind) == Some(&tile_index);
+        if tile_defs.is_empty() {
+            return;
+        }
 
-            let tile_sprite = tile_def.texture_by_index(0, 0, 0);
-            let ui_texture = ui_sys.to_ui_texture(tile_sprite.texture);
+        // Same tiles also show in their own sections; keep the button ids apart.
+        let _list_id = ui.push_id(label);
+        ui.text(label);
 
-            let btn_id = common::fixed_string::snake_case_to_title::<64>(&tile_def.name);
-            let btn_tooltip_buf;
-            let btn_tooltip = if tile_def.cost != 0 {
-                btn_tooltip_buf = common::format_small!("{}\nCost: {} gold", btn_id, tile_def.cost);
-                btn_tooltip_buf.as_str()
-            } else {
-                btn_id.as_str()
-            };
-
-            let btn_params = ui::UiImageButtonParams {
-                id: &btn_id,
-                size: BASE_TILE_SIZE_F32,
-                ui_texture,
-                tooltip: Some(btn_tooltip),
-                normal_color: Some(Color::gray()),
-                hovered_color: Some(Color::new(1.0, 1.0, 0.0, 0.1)), // Faint yellow
-                selected_color: Some(Color::white()),
-                tint_color: Some(tile_def.color),
-                top_left_uvs: Some(tile_sprite.coords.top_left()),
-                bottom_right_uvs: Some(tile_sprite.coords.bottom_right()),
-                selected,
-            };
+        let mut button_count_for_row = 0;
 
-            if ui::image_button(ui_sys, &btn_params) {
-                self.reset_selection_internal();
-                self.current_selection = TilePaletteSelection::Tile(TileDefHandle::new(tile_set, tile_category, tile_def));
-                self.selected_index.insert(tile_kind, tile_index);
+        for tile_def in tile_defs {
+            if button_count_for_row == tiles_per_row {
+                button_count_for_row = 0;
+                ui.new_line();
             }
 
+            self.draw_tile_button(ui_sys, tile_def);
             ui.same_line_with_spacing(0.0, padding_between_tiles);
-            tile_index += 1;
-            true
-        };
+            button_count_for_row += 1;
+        }
+
+        ui.new_line();
+    }
 
-        let mut tile_defs = SmallVec::<[(&TileSet, &TileCategory, &TileDef, Option<BuildingArchetypeKind>); 32]>::new();
+    fn draw_tile_list(
+        &mut self,
+        label: &str,
+        tile_kind: TileKind,
+        ui_sys: &UiSystem,
+        tiles_per_row: usize,
+        padding_between_tiles: f32,
+    ) {
+        let ui = ui_sys.ui();
+
+        let mut tile_defs = SmallVec::<[(&'static TileDef, Option<BuildingArchetypeKind>); 32]>::new();
 
         // Gather relevant tiles:
-        TileSets::get().for_each_tile_def(|tile_set, tile_category, tile_def| {
-            if tile_def.is(tile_kind) {
+        TileSets::get().for_each_tile_def(|_tile_set, _tile_category, tile_def| {
+            if tile_def.is(tile_kind) && Self::matches_search(&self.search_filter, tile_def) {
                 let building_archetype = BuildingConfigs::get().find_building_archetype_kind_for_tile_def(tile_def);
 
-                tile_defs.push((tile_set, tile_category, tile_def, building_archetype));
+                tile_defs.push((tile_def, building_archetype));
             }
             true
         });
 
+        if tile_defs.is_empty() {
+            return; // Everything filtered out by the search.
+        }
+
+        let _list_id = ui.push_id(label);
+
+        // Buildings are labeled per archetype group instead.
+        if tile_kind != TileKind::Building {
+            ui.text(label);
+        }
+
         // Group by building archetype kind (if any):
         tile_defs.sort_by_key(|entry| {
-            if let Some(archetype) = entry.3 {
+            if let Some(archetype) = entry.1 {
                 match archetype {
                     // Custom sort order.
                     BuildingArchetypeKind::ServiceBuilding  => 0,
@@ -281,7 +432,7 @@ impl TilePaletteDevMenu {
         let mut button_count_for_row = 0;
         let mut prev_building_archetype: Option<BuildingArchetypeKind> = None;
 
-        for (tile_set, tile_category, tile_def, building_archetype) in tile_defs {
+        for (tile_def, building_archetype) in tile_defs {
             if button_count_for_row == tiles_per_row {
                 button_count_for_row = 0;
                 ui.new_line();
@@ -300,7 +451,8 @@ impl TilePaletteDevMenu {
             }
 
             // Draw ImGui button:
-            draw_tile_button(tile_set, tile_category, tile_def);
+            self.draw_tile_button(ui_sys, tile_def);
+            ui.same_line_with_spacing(0.0, padding_between_tiles);
             button_count_for_row += 1;
         }
 
@@ -318,9 +470,28 @@ impl TilePaletteDevMenu {
         }
     }
 
+    // Spaces match the underscores in snake_case tile names, so "small well" finds "small_well".
+    fn matches_search(search_filter: &str, tile_def: &TileDef) -> bool {
+        let search = search_filter.trim().as_bytes();
+        if search.is_empty() {
+            return true;
+        }
+
+        tile_def.name.as_bytes().windows(search.len()).any(|window| {
+            window.iter().zip(search).all(|(&name_char, &search_char)| {
+                name_char.eq_ignore_ascii_case(&search_char) || (name_char == b'_' && search_char == b' ')
+            })
+        })
+    }
+
+    fn save_prefs() {
+        if !TilePalettePrefs::save() {
+            log::error!(log::channel!("debug"), "Failed to save tile palette settings.");
+        }
+    }
+
     fn reset_selection_internal(&mut self) {
         self.left_mouse_button_pressed = false;
         self.current_selection = TilePaletteSelection::None;
-        self.selected_index.clear();
     }
 }
@@ -140,6 +140,8 @@ impl PopupMessage {
 
         // Add a random offset to the message so when we render multiple popups over
         // a building in the same frame they won't all overlap and cover each other.
+        // Debug display only; drawing from the sim RngStreams here would make the
+        // simulation play out differently with popups enabled.
         let mut rng = rand::rng();
         let offset_x = rng.random_range(1..=40);
         let offset_y = rng.random_range(1..=40);
@@ -210,7 +212,7 @@ fn draw_popup_message(
         }
     };
 
-    let pos = Vec2::new(screen_pos.x - message.offset.x, screen_pos.y - message.offset.y - text_scroll);
+    let pos = ui_sys.screen_to_ui(screen_pos) - Vec2::new(message.offset.x, message.offset.y + text_scroll);
 
     let text_color = message.color;
     let bg_color = Color::new(0.1, 0.1, 0.1, bg_alpha);
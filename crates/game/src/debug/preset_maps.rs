@@ -3,7 +3,7 @@ use common::{Size, coords::Cell};
 use engine::log;
 
 use crate::{
-    sim::Simulation,
+    sim::{RngStream, Simulation},
     config::GameConfigs,
     cheats::{self, Cheats},
     world::{World, object::{Spawner, SpawnerResult}},
@@ -762,6 +762,7 @@ fn build_tile_map(preset: &'static PresetTiles, world: &mut World) -> TileMap {
 
     let mut spawner = Spawner::new(&context);
     spawner.set_subtract_tile_cost(false);
+    spawner.set_check_unlocks(false);
 
     // Terrain:
     for y in 0..map_size_in_cells.height {
@@ -772,7 +773,7 @@ fn build_tile_map(preset: &'static PresetTiles, world: &mut World) -> TileMap {
                     SpawnerResult::Tile(tile) => {
                         // Set a random terrain tile variation:
                         if tile.has_flags(TileFlags::RandomizePlacement) {
-                            tile.set_random_variation_index(context.rng_mut());
+                            tile.set_random_variation_index(context.rng_stream_mut(RngStream::Placement));
                         }
                     },
                     SpawnerResult::Err(err) => {
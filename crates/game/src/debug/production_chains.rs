@@ -0,0 +1,122 @@
+use imgui::ImColor32;
+
+use common::{Color, format_small};
+
+use crate::{
+    building::{BuildingKind, config::BuildingConfigs, production_chain::ProductionChainNode},
+    ui_context::GameUiContext,
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const NODE_WIDTH: f32 = 150.0;
+const NODE_HEIGHT: f32 = 40.0;
+const COLUMN_SPACING: f32 = 70.0;
+const ROW_SPACING: f32 = 16.0;
+
+const NODE_COLOR: Color = Color::new(0.2, 0.3, 0.45, 1.0);
+const MISSING_NODE_COLOR: Color = Color::new(0.45, 0.2, 0.2, 1.0); // No instance of this producer in the world.
+const EDGE_COLOR: Color = Color::new(0.8, 0.8, 0.8, 1.0);
+
+// ----------------------------------------------
+// ProductionChainsDebugWindow
+// ----------------------------------------------
+
+// Renders the production chain dependency graph. Columns are chain depth (raw materials
+// on the left), edges connect a supplier's output to every producer requiring it.
+pub fn draw_production_chains_window(context: &GameUiContext) -> bool {
+    let ui = context.ui_sys.ui();
+    let chains = BuildingConfigs::get().production_chains();
+
+    let mut is_window_open = true;
+
+    ui.window("Production Chains")
+        .opened(&mut is_window_open)
+        .position([200.0, 80.0], imgui::Condition::FirstUseEver)
+        .size([760.0, 420.0], imgui::Condition::FirstUseEver)
+        .horizontal_scrollbar(true)
+        .build(|| {
+            // Screen-space layout: [node index] => top-left position.
+            let origin = ui.cursor_screen_pos();
+            let mut rows_per_column = vec![0; chains.max_depth() as usize + 1];
+
+            let positions: Vec<[f32; 2]> = chains
+                .nodes()
+                .iter()
+                .map(|node| {
+                    let column = node.depth as usize;
+                    let row = rows_per_column[column];
+                    rows_per_column[column] += 1;
+                    [
+                        origin[0] + column as f32 * (NODE_WIDTH + COLUMN_SPACING),
+                        origin[1] + row as f32 * (NODE_HEIGHT + ROW_SPACING),
+                    ]
+                })
+                .collect();
+
+            let draw_list = ui.get_window_draw_list();
+
+            // Edges first, so nodes draw on top.
+            for (node, pos) in chains.nodes().iter().zip(&positions) {
+                for &input in &node.inputs {
+                    for (supplier_index, supplier) in chains.nodes().iter().enumerate() {
+                        if supplier.output != input {
+                            continue;
+                        }
+
+                        let from = positions[supplier_index];
+                        draw_list
+                            .add_line(
+                                [from[0] + NODE_WIDTH, from[1] + NODE_HEIGHT * 0.5],
+                                [pos[0], pos[1] + NODE_HEIGHT * 0.5],
+                                to_imgui_color(EDGE_COLOR),
+                            )
+                            .thickness(2.0)
+                            .build();
+                    }
+                }
+            }
+
+            for (node, pos) in chains.nodes().iter().zip(&positions) {
+                let count = count_buildings_for_node(context, node);
+                let color = if count != 0 { NODE_COLOR } else { MISSING_NODE_COLOR };
+
+                draw_list
+                    .add_rect(*pos, [pos[0] + NODE_WIDTH, pos[1] + NODE_HEIGHT], to_imgui_color(color))
+                    .filled(true)
+                    .rounding(4.0)
+                    .build();
+
+                draw_list.add_text([pos[0] + 6.0, pos[1] + 4.0], ImColor32::WHITE, format_small!("{} ({count})", node.name));
+                draw_list.add_text([pos[0] + 6.0, pos[1] + 20.0], ImColor32::WHITE, format_small!("-> {}", node.output));
+            }
+
+            // Reserve the space we drew into so the window scrolls.
+            let columns = rows_per_column.len() as f32;
+            let rows = rows_per_column.iter().copied().max().unwrap_or(0) as f32;
+            ui.dummy([
+                columns * (NODE_WIDTH + COLUMN_SPACING),
+                rows * (NODE_HEIGHT + ROW_SPACING),
+            ]);
+        });
+
+    is_window_open
+}
+
+fn count_buildings_for_node(context: &GameUiContext, node: &ProductionChainNode) -> u32 {
+    let mut count = 0;
+    context.world.for_each_building(node.kind, |building| {
+        if building.is(BuildingKind::producers()) && building.as_producer().production_output() == node.output {
+            count += 1;
+        }
+        true
+    });
+    count
+}
+
+#[inline]
+fn to_imgui_color(color: Color) -> ImColor32 {
+    ImColor32::from_rgba_f32s(color.r, color.g, color.b, color.a)
+}
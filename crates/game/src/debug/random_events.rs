@@ -0,0 +1,85 @@
+use common::{Color, format_small};
+
+use crate::{
+    GameLoop,
+    random_events::config::{RandomEventConfigs, RandomEventState},
+    ui_context::GameUiContext,
+};
+
+// ----------------------------------------------
+// RandomEventsDebugWindow
+// ----------------------------------------------
+
+// Lists the random event definitions with their eligibility and lets any of them be fired on demand.
+pub fn draw_random_events_window(context: &mut GameUiContext) -> bool {
+    let ui = context.ui_sys.ui();
+    let configs = RandomEventConfigs::get();
+
+    let mut is_window_open = true;
+    let mut event_to_fire = None;
+
+    ui.window("Random Events")
+        .opened(&mut is_window_open)
+        .position([200.0, 80.0], imgui::Condition::FirstUseEver)
+        .size([450.0, 400.0], imgui::Condition::FirstUseEver)
+        .build(|| {
+            let calendar = context.sim.calendar();
+            let random_events = context.sim.random_events();
+            let state = RandomEventState::new(context.world, calendar);
+
+            ui.text(format_small!("Day: {} | Next Roll Day: {}", calendar.days_elapsed(), random_events.next_roll_day()));
+            ui.text(format_small!(
+                "Roll every {} days | Event Chance: {:.0}%",
+                configs.roll_frequency_days,
+                configs.event_chance * 100.0
+            ));
+            ui.separator();
+
+            if configs.events.is_empty() {
+                ui.text("No random events defined.");
+                return;
+            }
+
+            for (index, event) in configs.events.iter().enumerate() {
+                let header = format_small!("{} (weight {})##_random_event_{}", event.name, event.weight, index);
+                if !ui.collapsing_header(header, imgui::TreeNodeFlags::empty()) {
+                    continue;
+                }
+
+                ui.indent_by(10.0);
+
+                if random_events.is_eligible(event, &state) {
+                    ui.text_colored(Color::green().to_array(), "Eligible");
+                } else {
+                    ui.text_colored(Color::red().to_array(), "Not eligible");
+                }
+
+                match random_events.last_fired_day(&event.name) {
+                    Some(day) => ui.text(format_small!("Last fired on day {day} (cooldown {} days)", event.cooldown_days)),
+                    None => ui.text(format_small!("Never fired (cooldown {} days)", event.cooldown_days)),
+                }
+
+                for prerequisite in &event.prerequisites {
+                    let color = if prerequisite.is_met(&state) { Color::green() } else { Color::red() };
+                    ui.text_colored(color.to_array(), format_small!("- {prerequisite}"));
+                }
+
+                for effect in &event.effects {
+                    ui.bullet_text(format_small!("{effect}"));
+                }
+
+                if ui.button(format_small!("Fire Now##_fire_random_event_{}", index)) {
+                    event_to_fire = Some(index);
+                }
+
+                ui.unindent_by(10.0);
+            }
+        });
+
+    // Ignores prerequisites and cooldown.
+    if let Some(index) = event_to_fire {
+        GameLoop::get_mut().sim_mut().fire_random_event(context, index);
+    }
+
+    is_window_open
+}
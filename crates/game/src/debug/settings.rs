@@ -3,27 +3,80 @@ use engine::{
     Engine,
     file_sys::paths::PathRef,
     log,
+    render::ColorGrading,
     save,
     ui::{self, DrawDebugUi, UiStaticVar},
 };
 use proc_macros::DrawDebugUi;
+use strum::{Display, EnumIter, IntoEnumIterator};
 
 use super::{log_viewer, preset_maps};
 use crate::{
     cheats,
     GameLoop,
+    camera::CameraZoom,
     config::GameConfigs,
     ui_context::GameUiContext,
     unit::config::UnitConfigs,
     building::config::BuildingConfigs,
     prop::config::PropConfigs,
     campaign::config::CampaignConfigs,
+    world::config_reload,
     tile::{
-        rendering::{MAX_GRID_LINE_THICKNESS, MIN_GRID_LINE_THICKNESS, TileMapRenderFlags},
+        heatmap::HeatmapOverlays,
+        rendering::{MAX_GRID_LINE_THICKNESS, MIN_GRID_LINE_THICKNESS, TILE_FLAG_DEBUG_GLYPHS, TileMapRenderFlags},
         sets::PresetTiles,
     },
 };
 
+// ----------------------------------------------
+// ColorGradingPreset
+// ----------------------------------------------
+
+// Procedurally generated color lookup tables to preview the color grading post-process.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Display, EnumIter)]
+enum ColorGradingPreset {
+    #[default]
+    None,
+    Night,
+    Autumn,
+    Winter,
+}
+
+impl ColorGradingPreset {
+    const LUT_SIZE: u32 = 16;
+
+    fn grade(self, [r, g, b]: [f32; 3]) -> [f32; 3] {
+        match self {
+            Self::None => [r, g, b],
+            Self::Night => [r * 0.45, g * 0.55, b * 0.8 + 0.05],
+            Self::Autumn => [r * 1.1 + 0.03, g * 0.95, b * 0.75],
+            Self::Winter => {
+                // Desaturate halfway to luminance, then shift towards blue.
+                let luma = (r * 0.299) + (g * 0.587) + (b * 0.114);
+                let desaturate = |channel: f32| channel + (luma - channel) * 0.5;
+                [desaturate(r) * 0.95, desaturate(g), desaturate(b) * 1.08]
+            }
+        }
+    }
+
+    fn color_grading(self, strength: f32) -> Option<ColorGrading> {
+        if self == Self::None {
+            return None;
+        }
+
+        let tex_cache = Engine::get_mut().texture_cache_mut();
+        let lut_name = format_small!("color_grading_lut_{self}");
+
+        let lut = match tex_cache.find_loaded_texture(&lut_name) {
+            Some(lut) => lut,
+            None => tex_cache.new_color_lut(&lut_name, Self::LUT_SIZE, |color| self.grade(color)),
+        };
+
+        Some(ColorGrading { lut, strength })
+    }
+}
+
 // ----------------------------------------------
 // DebugSettingsDevMenu
 // ----------------------------------------------
@@ -42,6 +95,19 @@ pub struct DebugSettingsDevMenu {
     #[debug_ui(skip)]
     save_file_selected: usize,
 
+    #[debug_ui(skip)]
+    color_grading_preset: ColorGradingPreset,
+    #[debug_ui(skip)]
+    color_grading_strength: f32,
+
+    #[debug_ui(skip)]
+    map_export_zoom: f32,
+
+    #[debug_ui(skip)]
+    cheat_command: String,
+    #[debug_ui(skip)]
+    cheat_command_output: String,
+
     #[debug_ui(edit)]
     draw_terrain: bool,
     #[debug_ui(edit)]
@@ -67,8 +133,10 @@ pub struct DebugSettingsDevMenu {
     show_vegetation_debug: bool,
     #[debug_ui(edit)]
     show_blocker_tiles_debug: bool,
-    #[debug_ui(edit, separator)]
+    #[debug_ui(edit)]
     show_search_graph_debug: bool,
+    #[debug_ui(edit, separator)]
+    show_node_glyphs_debug: bool,
 
     #[debug_ui(edit)]
     show_tile_bounds: bool,
@@ -97,6 +165,18 @@ pub struct DebugSettingsDevMenu {
     show_game_systems_debug: bool,
     #[debug_ui(edit)]
     show_log_viewer_window: bool,
+    #[debug_ui(edit)]
+    show_sim_event_log_window: bool,
+    #[debug_ui(edit)]
+    show_production_chains_window: bool,
+    #[debug_ui(edit)]
+    show_texture_atlas_window: bool,
+    #[debug_ui(edit)]
+    show_tile_set_report_window: bool,
+    #[debug_ui(edit)]
+    show_random_events_window: bool,
+    #[debug_ui(edit)]
+    show_memory_usage_window: bool,
 }
 
 impl DebugSettingsDevMenu {
@@ -107,6 +187,8 @@ impl DebugSettingsDevMenu {
             draw_props: true,
             draw_units: true,
             draw_vegetation: true,
+            color_grading_strength: 1.0,
+            map_export_zoom: CameraZoom::DEFAULT,
             ..Default::default()
         }
     }
@@ -139,6 +221,30 @@ impl DebugSettingsDevMenu {
         &mut self.show_log_viewer_window
     }
 
+    pub fn show_sim_event_log_window(&mut self) -> &mut bool {
+        &mut self.show_sim_event_log_window
+    }
+
+    pub fn show_production_chains_window(&mut self) -> &mut bool {
+        &mut self.show_production_chains_window
+    }
+
+    pub fn show_texture_atlas_window(&mut self) -> &mut bool {
+        &mut self.show_texture_atlas_window
+    }
+
+    pub fn show_tile_set_report_window(&mut self) -> &mut bool {
+        &mut self.show_tile_set_report_window
+    }
+
+    pub fn show_random_events_window(&mut self) -> &mut bool {
+        &mut self.show_random_events_window
+    }
+
+    pub fn show_memory_usage_window(&mut self) -> &mut bool {
+        &mut self.show_memory_usage_window
+    }
+
     pub fn selected_render_flags(&self) -> TileMapRenderFlags {
         let mut flags = TileMapRenderFlags::empty();
         if self.draw_terrain {
@@ -189,6 +295,9 @@ impl DebugSettingsDevMenu {
         if self.show_search_graph_debug {
             flags.insert(TileMapRenderFlags::DrawSearchGraphDebug);
         }
+        if self.show_node_glyphs_debug {
+            flags.insert(TileMapRenderFlags::DrawNodeGlyphsDebug);
+        }
         flags
     }
 
@@ -259,8 +368,33 @@ impl DebugSettingsDevMenu {
         }
     }
 
-    fn cheats_menu(&self, context: &mut GameUiContext) {
+    fn cheats_menu(&mut self, context: &mut GameUiContext) {
         cheats::get_mut().draw_debug_ui(context.ui_sys);
+
+        // Cheat commands:
+        let ui = context.ui_sys.ui();
+        ui.separator();
+
+        let command_entered = ui
+            .input_text("Command", &mut self.cheat_command)
+            .hint("Type 'help' for a list of commands")
+            .enter_returns_true(true)
+            .build();
+
+        if (command_entered || ui.button("Run")) && !self.cheat_command.is_empty() {
+            self.cheat_command_output = match cheats::execute_command(&self.cheat_command, context) {
+                Ok(output) => output,
+                Err(err) => {
+                    log::error!(log::channel!("cheats"), "{err}");
+                    err
+                }
+            };
+            self.cheat_command.clear();
+        }
+
+        if !self.cheat_command_output.is_empty() {
+            ui.text_wrapped(&self.cheat_command_output);
+        }
     }
 
     fn game_menu(&mut self, context: &mut GameUiContext) {
@@ -354,12 +488,108 @@ impl DebugSettingsDevMenu {
         } else {
             ui.text(format_small!("Speed: {:1}x", context.sim.speed()));
         }
+
+        ui.text(format_small!("Date: {}", context.sim.calendar()));
+        ui.text(format_small!("Weather: {} ({:.2})", context.sim.weather().kind(), context.sim.weather().intensity()));
+
+        // Screenshots:
+        ui.separator();
+
+        if ui.button("Take Screenshot (F12)") {
+            game_loop.request_screenshot();
+        }
+
+        ui.slider_config("Map export zoom", CameraZoom::MIN, CameraZoom::MAX)
+            .display_format("%.1f")
+            .build(&mut self.map_export_zoom);
+
+        if let Some(progress) = game_loop.map_export_progress() {
+            ui.progress_bar(progress).overlay_text("Exporting map...").build();
+        } else if ui.button("Export Map to PNG (Shift+F12)") {
+            game_loop.export_map_to_png(self.map_export_zoom);
+        }
     }
 
     fn camera_menu(&self, context: &mut GameUiContext) {
         context.camera.draw_debug_ui(context.ui_sys);
     }
 
+    fn node_glyphs_mask_picker(&self, context: &mut GameUiContext) {
+        let ui = context.ui_sys.ui();
+        let game_loop = GameLoop::get_mut();
+        let mut mask = game_loop.node_glyphs_debug_mask();
+
+        if ui.collapsing_header("Node Glyphs: NodeKind bits", imgui::TreeNodeFlags::empty()) {
+            mask.node_kinds.draw_debug_ui(context.ui_sys);
+        }
+
+        if ui.collapsing_header("Node Glyphs: TileFlags", imgui::TreeNodeFlags::empty()) {
+            for (flag, _, glyph) in TILE_FLAG_DEBUG_GLYPHS {
+                let mut value = mask.tile_flags.intersects(flag);
+                ui.checkbox(format_small!("{flag} ({glyph})"), &mut value);
+                mask.tile_flags.set(flag, value);
+            }
+        }
+
+        game_loop.set_node_glyphs_debug_mask(mask);
+    }
+
+    fn heatmap_overlay_picker(&self, context: &mut GameUiContext) {
+        let ui = context.ui_sys.ui();
+        let overlays = HeatmapOverlays::get_mut();
+
+        // Lists every registered layer, so new overlays show up here automatically.
+        let active_name = overlays.active_layer_name();
+        if let Some(_combo) = ui.begin_combo("Heatmap Overlay", active_name.unwrap_or("None")) {
+            if ui.selectable_config("None").selected(active_name.is_none()).build() {
+                overlays.set_active_layer(None);
+            }
+
+            let mut selected = None;
+            for layer in overlays.layers() {
+                if ui.selectable_config(layer.name).selected(active_name == Some(layer.name)).build() {
+                    selected = Some(layer.name);
+                }
+            }
+
+            if selected.is_some() {
+                overlays.set_active_layer(selected);
+            }
+        }
+
+        let mut opacity = overlays.opacity();
+        if ui.slider_config("Heatmap opacity", 0.0, 1.0).display_format("%.2f").build(&mut opacity) {
+            overlays.set_opacity(opacity);
+        }
+    }
+
+    fn color_grading_picker(&mut self, context: &mut GameUiContext) {
+        let ui = context.ui_sys.ui();
+
+        let mut changed = false;
+        if let Some(_combo) = ui.begin_combo("Color Grading", format_small!("{}", self.color_grading_preset)) {
+            for preset in ColorGradingPreset::iter() {
+                if ui.selectable_config(format_small!("{preset}")).selected(self.color_grading_preset == preset).build() {
+                    self.color_grading_preset = preset;
+                    changed = true;
+                }
+            }
+        }
+
+        if ui
+            .slider_config("Color grading strength", 0.0, 1.0)
+            .display_format("%.2f")
+            .build(&mut self.color_grading_strength)
+        {
+            changed = true;
+        }
+
+        if changed {
+            let color_grading = self.color_grading_preset.color_grading(self.color_grading_strength);
+            Engine::get_mut().render_system_mut().set_color_grading(color_grading);
+        }
+    }
+
     fn debug_options_menu(&mut self, context: &mut GameUiContext, enable_dev_tile_inspector: &mut bool) {
         let ui = context.ui_sys.ui();
 
@@ -394,6 +624,22 @@ impl DebugSettingsDevMenu {
         ui.checkbox("Draw grid", &mut self.draw_grid);
         ui.checkbox("Draw grid (ignore depth)", &mut self.draw_grid_ignore_depth);
 
+        // Heatmap overlays:
+        ui.separator();
+
+        self.heatmap_overlay_picker(context);
+
+        // Color grading post-process:
+        ui.separator();
+
+        self.color_grading_picker(context);
+
+        // Node glyphs overlay bits:
+        if self.show_node_glyphs_debug {
+            ui.separator();
+            self.node_glyphs_mask_picker(context);
+        }
+
         ui.separator();
 
         if ui.button("Panic Now!") {
@@ -490,9 +736,15 @@ impl DebugSettingsDevMenu {
                         GameConfigs::get_mut().draw_debug_ui_with_header("GameConfigs", context.ui_sys);
                     }
                     if let Some(_tab) = ui.tab_item("Buildings") {
+                        if ui.button("Reload From File") {
+                            config_reload::reload_object_configs(context.world, context.sim.rng_mut(), true, false);
+                        }
                         BuildingConfigs::get_mut().draw_debug_ui_with_header("BuildingConfigs", context.ui_sys);
                     }
                     if let Some(_tab) = ui.tab_item("Units") {
+                        if ui.button("Reload From File") {
+                            config_reload::reload_object_configs(context.world, context.sim.rng_mut(), false, true);
+                        }
                         UnitConfigs::get_mut().draw_debug_ui_with_header("UnitConfigs", context.ui_sys);
                     }
                     if let Some(_tab) = ui.tab_item("Props") {
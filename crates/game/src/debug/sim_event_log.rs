@@ -0,0 +1,123 @@
+use strum::{EnumCount, IntoEnumIterator};
+
+use common::{Color, format_small, time::Seconds};
+use engine::ui::UiSystem;
+
+use crate::{
+    camera::Camera,
+    sim::events::{SimEvent, SimEventKind, SimEventLog, SimEventSource},
+    tile::TileMap,
+};
+
+// ----------------------------------------------
+// SimEventLogViewer
+// ----------------------------------------------
+
+pub struct SimEventLogViewer {
+    kind_filter: [bool; SimEventKind::COUNT],
+    source_filter: String, // Substring match on the event source, e.g. "House", "Unit[0,3]" or "Hazards".
+    rewind_ticks: i32,     // How far back from the current tick to inspect. 0 = live.
+}
+
+impl SimEventLogViewer {
+    const CAMERA_PAN_DURATION_SECS: Seconds = 0.75;
+
+    pub fn new() -> Self {
+        Self {
+            kind_filter: [true; SimEventKind::COUNT],
+            source_filter: String::new(),
+            rewind_ticks: 0,
+        }
+    }
+
+    // Clicking an event pans the camera to it, or follows the unit for unit events.
+    pub fn draw(&mut self, ui_sys: &UiSystem, camera: &mut Camera, tile_map: &TileMap) -> bool {
+        let ui = ui_sys.ui();
+        let log = SimEventLog::get();
+
+        let mut is_window_open = true;
+
+        ui.window("Sim Event Log")
+            .opened(&mut is_window_open)
+            .position([570.0, 20.0], imgui::Condition::FirstUseEver)
+            .size([600.0, 350.0], imgui::Condition::FirstUseEver)
+            .build(|| {
+                for kind in SimEventKind::iter() {
+                    ui.checkbox(format_small!("{kind}"), &mut self.kind_filter[kind as usize]);
+                    ui.same_line();
+                }
+                ui.new_line();
+
+                ui.set_next_item_width(200.0);
+                ui.input_text("Source Filter", &mut self.source_filter).build();
+
+                let oldest_tick = log.iter().next().map_or(log.current_tick(), |event| event.tick);
+                let max_rewind = (log.current_tick() - oldest_tick) as i32;
+
+                ui.set_next_item_width(200.0);
+                ui.slider("Rewind Ticks", 0, max_rewind.max(0), &mut self.rewind_ticks);
+                self.rewind_ticks = self.rewind_ticks.clamp(0, max_rewind.max(0));
+
+                let inspected_tick = log.current_tick().saturating_sub(self.rewind_ticks as u64);
+                ui.text(format_small!(
+                    "Tick: {inspected_tick} / {} | Events: {}",
+                    log.current_tick(),
+                    log.len()
+                ));
+
+                ui.separator();
+
+                ui.child_window("sim_event_log_lines").build(|| {
+                    for (index, event) in log.iter().take_while(|event| event.tick <= inspected_tick).enumerate() {
+                        if !self.passes_filter(event) {
+                            continue;
+                        }
+
+                        ui.text_colored(
+                            Self::event_color(event.kind).to_array(),
+                            format_small!("[{}] {}", event.tick, event.kind),
+                        );
+                        ui.same_line();
+                        let _id = ui.push_id_usize(index);
+                        if ui.selectable(format_small!("{} @{} {}", event.source, event.cell, event.message)) {
+                            Self::focus_camera_on_event(camera, tile_map, event);
+                        }
+                    }
+
+                    // Follow new events while live.
+                    if self.rewind_ticks == 0 {
+                        ui.set_scroll_here_y_with_ratio(1.0);
+                    }
+                });
+            });
+
+        is_window_open
+    }
+
+    fn passes_filter(&self, event: &SimEvent) -> bool {
+        if !self.kind_filter[event.kind as usize] {
+            return false;
+        }
+
+        self.source_filter.is_empty()
+            || format_small!("{}", event.source).to_lowercase().contains(&self.source_filter.to_lowercase())
+    }
+
+    fn focus_camera_on_event(camera: &mut Camera, tile_map: &TileMap, event: &SimEvent) {
+        if let SimEventSource::Unit(unit_id) = event.source {
+            camera.follow_unit(unit_id);
+        } else if event.cell.is_valid() {
+            // Event cells are map cells, the camera works in the rotated view.
+            camera.pan_to(tile_map.rotate_cell(event.cell), Self::CAMERA_PAN_DURATION_SECS);
+        }
+    }
+
+    fn event_color(kind: SimEventKind) -> Color {
+        match kind {
+            SimEventKind::Spawn => Color::green(),
+            SimEventKind::Despawn => Color::red(),
+            SimEventKind::Production => Color::cyan(),
+            SimEventKind::StateChange => Color::yellow(),
+        }
+    }
+}
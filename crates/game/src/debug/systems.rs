@@ -15,7 +15,10 @@ use crate::{
         ambient_effects::{AmbientEffectsSystem, BirdFlightPath, spawn_bird, spawn_bird_with_random_flight_path},
         ambient_music::AmbientMusicSystem,
         ambient_sounds::AmbientSoundsSystem,
+        hazards::HazardsSystem,
+        invasion::InvasionSystem,
         settlers::SettlersSpawnSystem,
+        spatial_sounds::SpatialSoundsSystem,
     },
 };
 
@@ -92,6 +95,47 @@ impl SettlersSpawnSystem {
                 true,
             );
         }
+
+        ui.separator();
+
+        let stats = self.migration_stats();
+        let attractiveness = &stats.attractiveness;
+
+        ui.text(format_small!("Attractiveness: {:.2}", attractiveness.overall()));
+        ui.text(format_small!(
+            "  Housing: {:.2} | Employment: {:.2} | Food Variety: {:.2} | Happiness: {:.2}",
+            attractiveness.housing,
+            attractiveness.employment,
+            attractiveness.food_variety,
+            attractiveness.happiness
+        ));
+        ui.text(format_small!("Immigrants: {} | Emigrants: {}", stats.total_immigrants, stats.total_emigrants));
+        ui.text(format_small!(
+            "Riot Risk: {:.2} | Riots: {}",
+            Self::riot_risk(attractiveness.happiness),
+            stats.total_riots
+        ));
+
+        let attractiveness_history: Vec<f32> = stats.samples.iter().map(|sample| sample.attractiveness).collect();
+        ui.plot_lines("Attractiveness", &attractiveness_history)
+            .scale_min(0.0)
+            .scale_max(1.0)
+            .graph_size([0.0, 50.0])
+            .build();
+
+        let riot_risk_history: Vec<f32> = stats.samples.iter().map(|sample| sample.riot_risk).collect();
+        ui.plot_lines("Riot Risk", &riot_risk_history)
+            .scale_min(0.0)
+            .scale_max(1.0)
+            .graph_size([0.0, 50.0])
+            .build();
+
+        let population_history: Vec<f32> = stats.samples.iter().map(|sample| sample.population as f32).collect();
+        ui.plot_lines("Population", &population_history).graph_size([0.0, 50.0]).build();
+
+        let net_migration_history: Vec<f32> =
+            stats.samples.iter().map(|sample| sample.immigrants as f32 - sample.emigrants as f32).collect();
+        ui.plot_histogram("Net Migration", &net_migration_history).graph_size([0.0, 50.0]).build();
     }
 }
 
@@ -140,12 +184,42 @@ impl AmbientSoundsSystem {
             ui.text("Current Ambient Sound Playing: None");
         }
 
+        let (weather, intensity) = self.current_weather();
+        ui.text(format_small!("Current Weather: {} ({:.2})", weather, intensity));
+
         if ui.button("Reset Ambient Sounds") {
             self.reset(engine);
         }
     }
 }
 
+// ----------------------------------------------
+// SpatialSoundsSystem Debug UI
+// ----------------------------------------------
+
+impl SpatialSoundsSystem {
+    pub(crate) fn draw_debug_ui_dispatch(&mut self, engine: &mut Engine, _cmds: &mut SimCmds, _context: &SimContext) {
+        let ui = engine.ui_system().ui();
+
+        if !self.is_enabled() {
+            ui.text_colored(Color::red().to_array(), "SpatialSoundsSystem DISABLED.");
+            return;
+        }
+
+        ui.text(format_small!("Emitters Playing: {}", self.emitters().len()));
+
+        for emitter in self.emitters() {
+            ui.text(format_small!("- {} at {} ('{}')", emitter.key, emitter.cell, emitter.key.event_name()));
+        }
+
+        ui.separator();
+
+        if ui.button("Stop Emitters") {
+            self.stop_all(engine.sound_system_mut());
+        }
+    }
+}
+
 // ----------------------------------------------
 // AmbientMusicSystem Debug UI
 // ----------------------------------------------
@@ -159,8 +233,8 @@ impl AmbientMusicSystem {
             return;
         }
 
-        if let Some(key) = self.current_track_playing() {
-            ui.text(format_small!("Current Track Playing: {} ('{}')", key, key.track_path()));
+        if let Some(track_path) = self.current_track_playing() {
+            ui.text(format_small!("Current Track Playing: '{}'", track_path));
         } else {
             ui.text("Current Track Playing: None");
         }
@@ -168,8 +242,65 @@ impl AmbientMusicSystem {
         ui.text(format_small!("Current Game State: {}", self.current_game_state()));
         ui.separator();
 
+        if ui.button("Next Track") {
+            self.skip_track(engine.sound_system_mut());
+        }
+
         if ui.button("Reset Track") {
             self.reset(engine);
         }
     }
 }
+
+// ----------------------------------------------
+// HazardsSystem Debug UI
+// ----------------------------------------------
+
+impl HazardsSystem {
+    pub(crate) fn draw_debug_ui_dispatch(&mut self, engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        self.update_timer.draw_debug_ui_with_header("Hazards Update", engine.ui_system());
+
+        let ui = engine.ui_system().ui();
+
+        let mut burning_count = 0;
+        let mut destroyed_count = 0;
+        for kinds in Self::BUILDING_KINDS {
+            context.world().for_each_building(kinds, |building| {
+                burning_count += building.hazards().is_burning() as u32;
+                destroyed_count += building.hazards().is_destroyed() as u32;
+                true
+            });
+        }
+
+        ui.text(format_small!("Buildings burning: {burning_count}"));
+        ui.text(format_small!("Buildings destroyed (pending rubble): {destroyed_count}"));
+
+        if ui.button("Force Hazards Update") {
+            self.update_hazards(cmds, context);
+        }
+    }
+}
+
+// ----------------------------------------------
+// InvasionSystem Debug UI
+// ----------------------------------------------
+
+impl InvasionSystem {
+    pub(crate) fn draw_debug_ui_dispatch(&mut self, engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        self.invasion_timer.draw_debug_ui_with_header("Invasion Timer", engine.ui_system());
+
+        let ui = engine.ui_system().ui();
+
+        let population = context.world().stats().population.total;
+        ui.text(format_small!("Raiders on map: {}", self.raiders_count()));
+        ui.text(format_small!("Raiders marching in: {}", self.raid_band().len()));
+        ui.text(format_small!("Raiders for current population: {}", Self::raiders_for_population(population)));
+
+        // Ignores the minimum population requirement.
+        if ui.button("Force Invasion")
+            && let Some(entry_cell) = Self::find_entry_cell(context)
+        {
+            Self::start_invasion(cmds, context, entry_cell, Self::raiders_for_population(population));
+        }
+    }
+}
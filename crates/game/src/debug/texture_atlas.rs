@@ -0,0 +1,94 @@
+use imgui::ImColor32;
+use strum::IntoEnumIterator;
+
+use common::format_small;
+
+use crate::{
+    tile::{TileMapLayerKind, sets::TileSets},
+    ui_context::GameUiContext,
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const PAGE_THUMBNAIL_SIZE: f32 = 256.0;
+const PAGE_BORDER_COLOR: ImColor32 = ImColor32::from_rgba(255, 255, 0, 160);
+
+// ----------------------------------------------
+// TextureAtlasDebugWindow
+// ----------------------------------------------
+
+// Lists the packed texture atlas pages of each tile set layer with
+// a thumbnail of the page and how much of its area sprites occupy.
+pub fn draw_texture_atlas_window(context: &GameUiContext) -> bool {
+    let ui = context.ui_sys.ui();
+    let tile_sets = TileSets::get();
+
+    let mut is_window_open = true;
+
+    ui.window("Texture Atlas")
+        .opened(&mut is_window_open)
+        .position([200.0, 80.0], imgui::Condition::FirstUseEver)
+        .size([PAGE_THUMBNAIL_SIZE * 2.0 + 40.0, 600.0], imgui::Condition::FirstUseEver)
+        .build(|| {
+            for layer in TileMapLayerKind::iter() {
+                let pages = tile_sets.atlas_pages(layer);
+
+                let header = format_small!("{layer} ({} pages)##_atlas_{layer}", pages.len());
+                if !ui.collapsing_header(header, imgui::TreeNodeFlags::DEFAULT_OPEN) {
+                    continue;
+                }
+
+                if pages.is_empty() {
+                    ui.text("Not using a packed texture atlas.");
+                    continue;
+                }
+
+                let total_sprites: usize = pages.iter().map(|page| page.sprite_count).sum();
+                let average_occupancy = pages.iter().map(|page| page.occupancy()).sum::<f32>() / pages.len() as f32;
+
+                ui.text(format_small!("Sprites: {total_sprites} | Avg. occupancy: {:.1}%", average_occupancy * 100.0));
+                ui.separator();
+
+                for (page_index, page) in pages.iter().enumerate() {
+                    ui.text(format_small!(
+                        "Page [{page_index}] {}x{}, {} sprites",
+                        page.size.width,
+                        page.size.height,
+                        page.sprite_count
+                    ));
+
+                    ui.progress_bar(page.occupancy())
+                        .size([PAGE_THUMBNAIL_SIZE, 0.0])
+                        .overlay_text(format_small!("{:.1}% used", page.occupancy() * 100.0))
+                        .build();
+
+                    // Page thumbnail. Hover to see it in full size.
+                    let ui_texture = context.ui_sys.to_ui_texture(page.texture);
+                    let thumbnail_min = ui.cursor_screen_pos();
+                    let thumbnail_max = [thumbnail_min[0] + PAGE_THUMBNAIL_SIZE, thumbnail_min[1] + PAGE_THUMBNAIL_SIZE];
+
+                    let draw_list = ui.get_window_draw_list();
+                    draw_list.add_image(ui_texture, thumbnail_min, thumbnail_max).build();
+                    draw_list.add_rect(thumbnail_min, thumbnail_max, PAGE_BORDER_COLOR).build();
+
+                    ui.dummy([PAGE_THUMBNAIL_SIZE, PAGE_THUMBNAIL_SIZE]);
+
+                    if ui.is_item_hovered() {
+                        ui.tooltip(|| {
+                            let tooltip_min = ui.cursor_screen_pos();
+                            let tooltip_size = [page.size.width as f32 * 0.25, page.size.height as f32 * 0.25];
+                            let tooltip_max = [tooltip_min[0] + tooltip_size[0], tooltip_min[1] + tooltip_size[1]];
+                            ui.get_window_draw_list().add_image(ui_texture, tooltip_min, tooltip_max).build();
+                            ui.dummy(tooltip_size);
+                        });
+                    }
+
+                    ui.spacing();
+                }
+            }
+        });
+
+    is_window_open
+}
@@ -0,0 +1,63 @@
+use strum::IntoEnumIterator;
+
+use common::format_small;
+use engine::Engine;
+
+use crate::{
+    tile::{sets::TileSets, validation::TileSetIssueKind},
+    ui_context::GameUiContext,
+};
+
+// ----------------------------------------------
+// TileSetReportDebugWindow
+// ----------------------------------------------
+
+// Lists the issues found by the TileSet validation pass, grouped by kind.
+pub fn draw_tile_set_report_window(context: &GameUiContext) -> bool {
+    let ui = context.ui_sys.ui();
+    let checked_textures = TileSets::get().validation_report().checked_textures();
+
+    let mut is_window_open = true;
+
+    ui.window("TileSet Validation")
+        .opened(&mut is_window_open)
+        .position([200.0, 80.0], imgui::Condition::FirstUseEver)
+        .size([600.0, 500.0], imgui::Condition::FirstUseEver)
+        .build(|| {
+            if ui.button("Re-validate") {
+                TileSets::revalidate(Engine::get().texture_cache(), checked_textures);
+            }
+
+            if !checked_textures {
+                ui.same_line();
+                ui.text_disabled("(Textures not loaded. Texture checks skipped.)");
+            }
+
+            ui.separator();
+
+            let report = TileSets::get().validation_report();
+
+            if report.is_empty() {
+                ui.text("No issues found.");
+                return;
+            }
+
+            for kind in TileSetIssueKind::iter() {
+                let count = report.count(kind);
+                let header = format_small!("{kind} ({count})##_issues_{kind}");
+
+                if count == 0 || !ui.collapsing_header(header, imgui::TreeNodeFlags::DEFAULT_OPEN) {
+                    continue;
+                }
+
+                for issue in report.issues().iter().filter(|issue| issue.kind == kind) {
+                    ui.bullet_text(format_small!("{} - {}", issue.layer, issue.location));
+                    ui.indent_by(20.0);
+                    ui.text_wrapped(&issue.message);
+                    ui.unindent_by(20.0);
+                }
+            }
+        });
+
+    is_window_open
+}
@@ -1,4 +1,4 @@
-use rand::{self, Rng};
+use rand::Rng;
 use bitflags::Flags;
 use smallvec::SmallVec;
 
@@ -28,6 +28,7 @@ use crate::{
         SimCmds,
         SimCmdQueue,
         SimContext,
+        RngStream,
         commands::ImmediateModeSimCmds,
         resources::{ResourceKind, ShoppingList, StockItem},
     },
@@ -116,7 +117,7 @@ impl Unit {
         self.draw_debug_ui_properties(ui_sys);
         self.draw_debug_ui_config(ui_sys);
         self.debug_mut().draw_debug_ui(ui_sys);
-        draw_inventory_debug_ui(self.inventory_mut(), ui_sys);
+        draw_inventory_debug_ui(self.inventory_mut(), context, ui_sys);
         self.draw_debug_ui_tasks(context, ui_sys);
         self.draw_debug_ui_navigation(context, ui_sys);
         self.draw_debug_ui_misc(cmds, context, ui_sys);
@@ -180,6 +181,19 @@ impl Unit {
         } else {
             ui.text("<no task>");
         }
+
+        let task_queue = self.task_queue();
+        ui.separator();
+        ui.text(format_small!("Priority : {}", task_queue.current_priority()));
+        ui.text(format_small!("Queued   : {}", task_queue.len()));
+
+        for queued in task_queue.iter() {
+            let task_name = context.task_manager()
+                .try_get_task_archetype_and_started(queued.task_id)
+                .map_or("<invalid>".to_string(), |(archetype, _)| archetype.to_string());
+            let interrupted = if queued.interrupted { " (interrupted)" } else { "" };
+            ui.text(format_small!(" - {task_name} [{}]{interrupted}", queued.priority));
+        }
     }
 
     fn draw_debug_ui_navigation(&mut self, context: &SimContext, ui_sys: &UiSystem) {
@@ -303,12 +317,13 @@ impl Unit {
                         origin_building: BuildingKindAndId { kind: building.kind(), id: building.id() },
                         origin_building_tile: BuildingTileInfo { road_link: start_cell, base_cell: building.base_cell() },
                         storage_buildings_accepted: BuildingKind::storage(), // any storage
-                        resource_kind_to_deliver: ResourceKind::random_food(&mut rand::rng()),
+                        resource_kind_to_deliver: ResourceKind::random_food(context.rng_stream_mut(RngStream::UnitAi)),
                         resource_count: 1,
                         completion_callback: callback::create!(unit_debug_delivery_task_completed),
                         completion_task,
                         allow_producer_fallback: true,
                         state: UnitTaskDeliveryState::default(),
+                        reservation: None,
                         visit_delivered: None,
                     });
                     self.assign_task(task_manager, task);
@@ -320,9 +335,9 @@ impl Unit {
             // We need a building to own the task, so this assumes there's at least one of
             // these placed on the map.
             if let Some(building) = world.find_building_by_name("Market", BuildingKind::Market) {
-                let mut rng = rand::rng();
+                let rng = context.rng_stream_mut(RngStream::UnitAi);
                 let resources_to_fetch = ShoppingList::from_items(&[StockItem {
-                    kind: ResourceKind::random(&mut rng),
+                    kind: ResourceKind::random(rng),
                     count: rng.random_range(1..5),
                 }]);
                 let start_cell = building.road_link().unwrap_or_default();
@@ -460,8 +475,13 @@ impl Unit {
             let connected_to_road_only = traversable_node_kinds.intersects(PathNodeKind::Road)
                 && !traversable_node_kinds.intersects(PathNodeKind::EmptyLand);
 
-            let is_near =
-                context.is_near_building(self.cell(), search_building_kind, connected_to_road_only, max_search_distance);
+            let is_near = context.is_near_building(
+                self.cell(),
+                search_building_kind,
+                connected_to_road_only,
+                max_search_distance,
+                false,
+            );
 
             if is_near {
                 debug_popup_msg_color!(self.debug_mut(), Color::green(), "{}: Near {}!", self.cell(), search_building_kind);
@@ -487,6 +507,7 @@ impl Unit {
                 emigrate: false,
                 population_to_add: 1,
                 state: UnitTaskSettlerState::default(),
+                off_map_time_left: 0.0,
                 visit_outcome: None,
             });
 
@@ -514,6 +535,7 @@ impl Unit {
                 emigrate: false,
                 population_to_add: 1,
                 state: UnitTaskSettlerState::default(),
+                off_map_time_left: 0.0,
                 visit_outcome: None,
             });
 
@@ -560,7 +582,7 @@ impl Unit {
 // ----------------------------------------------
 
 // Relocated from `unit/inventory.rs`; operates through the inventory's public API.
-fn draw_inventory_debug_ui(inventory: &mut UnitInventory, ui_sys: &UiSystem) {
+fn draw_inventory_debug_ui(inventory: &mut UnitInventory, context: &SimContext, ui_sys: &UiSystem) {
     let ui = ui_sys.ui();
 
     if !ui.collapsing_header("Inventory", imgui::TreeNodeFlags::empty()) {
@@ -569,7 +591,8 @@ fn draw_inventory_debug_ui(inventory: &mut UnitInventory, ui_sys: &UiSystem) {
 
     if ui.button("Give Random Item") {
         inventory.clear();
-        inventory.receive_resources(ResourceKind::random(&mut rand::rng()), rand::rng().random_range(1..10));
+        let rng = context.rng_stream_mut(RngStream::UnitAi);
+        inventory.receive_resources(ResourceKind::random(rng), rng.random_range(1..10));
     }
 
     ui.same_line();
@@ -134,10 +134,28 @@ pub fn draw_render_perf_stats(ui_sys: &UiSystem, render_sys_stats: &RenderStats,
                           render_sys_stats.draw_calls,
                           render_sys_stats.peak_draw_calls));
 
+        ui.text(format_small!("Sprite batches    : {}/{} | Peak: {}/{}",
+                              render_sys_stats.sprite_batches,
+                              render_sys_stats.sprites_submitted,
+                              render_sys_stats.peak_sprite_batches,
+                              render_sys_stats.peak_sprites_submitted));
+        ui.text(format_small!("Buffer reallocs   : {} | Peak: {}",
+                              render_sys_stats.buffer_reallocs,
+                              render_sys_stats.peak_buffer_reallocs));
+        ui.text(format_small!("Static meshes     : {} | Peak: {}",
+                              render_sys_stats.static_meshes,
+                              render_sys_stats.peak_static_meshes));
+
         ui.text(format_small!("Tile sort list    : {} | Peak: {}",
                               tile_render_stats.tile_sort_list_len,
                               tile_render_stats.peak_tile_sort_list_len));
 
+        ui.text(format_small!("Terrain chunks    : {}/{} | Peak: {}/{}",
+                              tile_render_stats.terrain_chunks_drawn,
+                              tile_render_stats.terrain_chunks_rebuilt,
+                              tile_render_stats.peak_terrain_chunks_drawn,
+                              tile_render_stats.peak_terrain_chunks_rebuilt));
+
         ui.text(format_small!("Tiles highlighted : {} | Peak: {}",
                               tile_render_stats.tiles_drawn_highlighted,
                               tile_render_stats.peak_tiles_drawn_highlighted));
@@ -186,6 +204,14 @@ pub fn draw_world_perf_stats(
         ui.text(format_small!("- Vegetation : {}", map_stats.vegetation_tiles));
         ui.text(format_small!("- Rocks      : {}", map_stats.rock_tiles));
         ui.text(format_small!("- Memory     : {}kb", map_mem_usage_bytes / 1024));
+        let anim_stats = tile_map.anim_stats();
+        ui.text("Tile Anims:");
+        ui.text(format_small!("- Updated    : {} | Peak: {}", anim_stats.tiles_updated, anim_stats.peak_tiles_updated));
+        ui.text(format_small!("- Batched    : {} | Peak: {}", anim_stats.tiles_batched, anim_stats.peak_tiles_batched));
+        ui.text(format_small!("- Offscreen  : {} | Peak: {}",
+                              anim_stats.offscreen_tiles_updated,
+                              anim_stats.peak_offscreen_tiles_updated));
+        ui.text(format_small!("- Vis Rate   : 1/{} frames", anim_stats.visible_update_interval));
         ui.text("Vis Cells:");
         ui.text(format_small!("- Start      : [{},{}]", visible_range.x(), visible_range.y()));
         ui.text(format_small!("- Count      : {}x{}", visible_range.width(), visible_range.height()));
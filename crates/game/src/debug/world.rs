@@ -83,11 +83,28 @@ impl WorldStats {
                 ui.text(format_small!("Number Of Houses    : {}", self.houses.total));
                 ui.text(format_small!("Lowest House Level  : {}", self.houses.lowest_level as u32));
                 ui.text(format_small!("Highest House Level : {}", self.houses.highest_level as u32));
+                ui.separator();
+
+                let happiness = self.happiness.average();
+                ui.bullet_text("Happiness:");
+                ui.spacing();
+                ui.text(format_small!("Score    : {:.2}", happiness.score()));
+                ui.text(format_small!("Needs    : {:.2}", happiness.needs));
+                ui.text(format_small!("Services : {:.2}", happiness.services));
+                ui.text(format_small!("Tax      : {:.2}", happiness.tax));
+                ui.text(format_small!("Events   : {:.2}", happiness.events));
+                highlight_nonzero_value("Unhappy Households", self.happiness.unhappy_households, Color::red());
             }
         }
 
         if let Some(_tab) = ui.tab_item("Tax/Treasury") {
             ui.bullet_text("Tax:");
+
+            let mut tax_rate_percent = treasury.tax_rate_percent();
+            if ui.input_scalar("Tax Rate %", &mut tax_rate_percent).step(1).build() {
+                treasury.set_tax_rate_percent(tax_rate_percent);
+            }
+
             highlight_zero_value("Tax Generated", self.treasury.tax_generated, Color::red());
             highlight_nonzero_value("Tax Available", self.treasury.tax_available, Color::yellow());
             highlight_zero_value("Tax Collected", self.treasury.tax_collected, Color::yellow());
@@ -122,6 +139,7 @@ impl WorldStats {
             ui.text("In Storage:");
             resources.storage_yards.draw_debug_ui_with_header("Storage Yards", ui_sys);
             resources.granaries.draw_debug_ui_with_header("Granaries", ui_sys);
+            resources.storage_free_capacity.draw_debug_ui_with_header("Free Capacity", ui_sys);
 
             ui.separator();
 
@@ -1,14 +1,15 @@
-use std::path::PathBuf;
+use std::{fmt::Write as _, path::PathBuf};
 
 use common::{
     Size,
     Vec2,
-    coords::CellRange,
+    coords::{CellRange, WorldToScreenTransform},
     time::{Milliseconds, PerfTimer, Seconds, UpdateTimer},
 };
 use engine::{
     log,
     save,
+    platform,
     Engine,
     ui::UiInputEvent,
     runner::RunLoop,
@@ -20,22 +21,36 @@ use engine::{
 };
 
 use crate::{
+    benchmark::Benchmark,
+    camera::CameraScrollInput,
     cheats,
-    debug,
+    debug::{self, palette::TilePalettePrefs},
+    headless::HeadlessRun,
     undo_redo,
     menu::GameMenusMode,
-    config::GameConfigs,
+    config::{Difficulty, GameConfigs, LoadMapSetting},
+    key_bindings::{GameAction, KeyBindings},
     unit::config::UnitConfigs,
-    building::config::BuildingConfigs,
+    building::{config::BuildingConfigs, overlays, unlocks::BuildingUnlockConfigs},
     prop::config::PropConfigs,
-    sim::Simulation,
-    system::GameSystems,
+    sim::{Simulation, SimEventLog},
+    system::{GameSystems, ambient_music::MusicPlaylistConfigs, hazards::HazardsSystem, invasion::InvasionSystem},
+    sound_banks::{GameSoundBanks, SoundBankConfigs, SoundBankState},
+    sound_events::SoundEventConfigs,
+    captions::CaptionConfigs,
     session::{self, GameSession, GameSessionCmdQueue},
-    campaign::{self, config::CampaignConfigs},
+    campaign::{self, config::CampaignConfigs, meta::CampaignMetaState},
+    random_events::config::RandomEventConfigs,
+    i18n::Localization,
+    screenshot::{self, MapExport},
     tile::{
-        rendering::{TileMapRenderFlags, TileMapRenderStats},
+        hot_reload::TileSetHotReloader,
+        prefab::PrefabConfigs,
+        rendering::{NodeGlyphsDebugMask, TileMapRenderFlags, TileMapRenderStats},
         sets::{TileDef, TileSets},
+        streaming::TileTextureStreamer,
     },
+    world::config_reload::ObjectConfigHotReloader,
 };
 
 // ----------------------------------------------
@@ -84,6 +99,17 @@ impl SaveSmokeTest {
     }
 }
 
+// ----------------------------------------------
+// MapExportRun
+// ----------------------------------------------
+
+// Map export in progress, plus the camera and simulation state to restore once it is done.
+struct MapExportRun {
+    export: MapExport,
+    camera_transform: WorldToScreenTransform,
+    sim_was_paused: bool,
+}
+
 // ----------------------------------------------
 // GameLoop
 // ----------------------------------------------
@@ -96,9 +122,18 @@ pub struct GameLoop {
     session_cmd_queue: GameSessionCmdQueue,
 
     autosave_timer: UpdateTimer,
-    enable_autosave: bool,
 
     smoke_test: Option<SaveSmokeTest>,
+    benchmark: Option<Benchmark>,
+    headless: Option<HeadlessRun>,
+
+    screenshot_requested: bool,
+    map_export: Option<MapExportRun>,
+
+    sound_banks: GameSoundBanks,
+
+    tile_set_hot_reloader: Option<TileSetHotReloader>,
+    object_config_hot_reloader: Option<ObjectConfigHotReloader>,
 
     stats: GameLoopStats,
 }
@@ -120,7 +155,10 @@ impl RunLoop for GameLoop {
         cheats::initialize();
         campaign::initialize();
         undo_redo::initialize();
+        overlays::register_minimap_overlays();
         Simulation::register_callbacks();
+        SimEventLog::register_crash_report_section();
+        Self::register_crash_report_section(configs);
         debug::set_show_popup_messages(configs.debug.show_popups);
 
         // Optional save-load smoke test run mode:
@@ -128,20 +166,46 @@ impl RunLoop for GameLoop {
             .any(|arg| arg == "--smoke-test-saves")
             .then(SaveSmokeTest::new);
 
+        // Optional benchmark run mode:
+        let benchmark = Benchmark::from_command_line();
+
+        // Headless run mode, if the engine was started with the Null backends:
+        let headless = engine.is_headless().then(HeadlessRun::from_command_line);
+
         // Create Session and GameLoop:
         let session = session::create(engine, configs, None);
-        let game_loop = Self {
+        let mut game_loop = Self {
             engine,
             configs,
             session: Box::new(session),
             session_cmd_queue: GameSessionCmdQueue::new(),
             autosave_timer: UpdateTimer::new(configs.save.autosave_frequency_secs),
-            // Never autosave during the smoke test - it would overwrite the saves under test.
-            enable_autosave: configs.save.enable_autosave && smoke_test.is_none(),
             smoke_test,
+            benchmark,
+            headless,
+            screenshot_requested: false,
+            map_export: None,
+            sound_banks: GameSoundBanks::default(),
+            tile_set_hot_reloader: if configs.debug.hot_reload_tile_sets && !configs.debug.skip_loading_tile_sets {
+                TileSetHotReloader::new(configs.engine.use_packed_texture_atlas)
+            } else {
+                None
+            },
+            object_config_hot_reloader: if configs.debug.hot_reload_object_configs {
+                ObjectConfigHotReloader::new()
+            } else {
+                None
+            },
             stats: GameLoopStats::default(),
         };
 
+        // The benchmark skips the home menu and starts straight into its synthetic city.
+        if let Some(benchmark) = &game_loop.benchmark {
+            game_loop.create_session(benchmark.load_map_setting(), configs.sim.difficulty);
+        } else if let Some(save_file) = game_loop.headless.as_ref().and_then(|run| run.save_file.clone()) {
+            game_loop.load_save_game(PathRef::from_str(&save_file));
+        }
+
         // Set global instance:
         Self::initialize(game_loop);
         Self::get_mut()
@@ -151,10 +215,12 @@ impl RunLoop for GameLoop {
         // Terminate game session:
         {
             let this = Self::get_mut();
+            this.sound_banks.exit_all(this.engine.sound_system_mut());
             session::destroy(&mut this.session, this.engine, this.configs);
         }
 
         // Terminate singleton instances.
+        platform::set_crash_emergency_fn(None);
         Self::terminate();
         Self::unload_assets();
     }
@@ -167,9 +233,14 @@ impl RunLoop for GameLoop {
     fn update(&mut self) {
         let frame_timer = PerfTimer::begin();
 
-        let (delta_time_secs, cursor_screen_pos, begin_frame_time_ms) = self.engine.begin_frame();
+        let (mut delta_time_secs, cursor_screen_pos, begin_frame_time_ms) = self.engine.begin_frame();
         self.stats.fps = if delta_time_secs > 0.0 { 1.0 / delta_time_secs } else { 0.0 };
 
+        // Headless runs step the simulation by exactly one fixed tick per frame, regardless of wall-clock time.
+        if self.headless.is_some() {
+            delta_time_secs = self.configs.sim.fixed_tick_secs;
+        }
+
         self.update_autosave();
         self.session_cmd_queue.execute(&mut self.session, self.engine, self.configs);
 
@@ -177,28 +248,59 @@ impl RunLoop for GameLoop {
             self.update_smoke_test(delta_time_secs);
         }
 
+        if self.benchmark.is_some() {
+            self.update_benchmark(delta_time_secs);
+        }
+
+        if self.headless.is_some() {
+            self.update_headless();
+        }
+
         // Input Events:
         for event in self.engine.app_events().clone() {
             self.handle_app_event(event);
         }
 
+        // Load streamed tile textures requested last frame and evict unused ones.
+        TileTextureStreamer::get_mut().update(self.engine.texture_cache_mut());
+
+        // Patch tile sets edited on disk.
+        if let Some(hot_reloader) = &mut self.tile_set_hot_reloader {
+            hot_reloader.update(self.engine.texture_cache_mut(), self.session.tile_map_mut(), delta_time_secs);
+        }
+
+        // Patch building/unit configs edited on disk.
+        if let Some(hot_reloader) = &mut self.object_config_hot_reloader {
+            let (world, rng) = self.session.world_and_rng_mut();
+            hot_reloader.update(world, rng, delta_time_secs);
+        }
+
         // Game Logic:
         let visible_range = self.update_simulation(cursor_screen_pos, delta_time_secs);
 
-        // Rendering:
-        let render_flags = self.menus_begin_frame();
-        self.draw_tile_map(delta_time_secs, visible_range, render_flags);
-        self.menus_end_frame(visible_range);
+        // Rendering (nothing to draw headless):
+        if self.headless.is_none() {
+            let render_flags = self.menus_begin_frame();
+            self.draw_tile_map(delta_time_secs, visible_range, render_flags);
+            self.menus_end_frame(visible_range);
+        }
 
         // Sound System Update:
         self.update_sound_system();
 
         let (end_frame_time_ms, present_frame_time_ms) = self.engine.end_frame();
 
+        // Screenshots read back the frame just rendered.
+        self.update_frame_capture();
+
         self.stats.engine_begin_frame_time_ms = begin_frame_time_ms;
         self.stats.engine_end_frame_time_ms   = end_frame_time_ms;
         self.stats.present_frame_time_ms      = present_frame_time_ms;
         self.stats.total_frame_time_ms        = frame_timer.end();
+
+        if let Some(benchmark) = &mut self.benchmark {
+            benchmark.record_frame(&self.stats);
+        }
     }
 
     #[inline]
@@ -222,6 +324,16 @@ impl GameLoop {
         self.session_cmd_queue.push_quit_to_main_menu();
     }
 
+    #[inline]
+    pub fn rebuild_menus(&mut self) {
+        self.session_cmd_queue.push_rebuild_menus();
+    }
+
+    #[inline]
+    pub fn create_session(&mut self, load_map_setting: LoadMapSetting, difficulty: Difficulty) {
+        self.session_cmd_queue.push_create_session(load_map_setting, difficulty);
+    }
+
     #[inline]
     pub fn reset_session(&mut self, reset_map_with_tile_def: Option<&'static TileDef>, new_map_size: Option<Size>) {
         self.session_cmd_queue.push_reset_session(reset_map_with_tile_def, new_map_size);
@@ -244,12 +356,22 @@ impl GameLoop {
 
     #[inline]
     pub fn is_autosave_enabled(&self) -> bool {
-        self.enable_autosave
+        self.session.settings().autosave_enabled
     }
 
     #[inline]
     pub fn enable_autosave(&mut self, enable: bool) {
-        self.enable_autosave = enable;
+        self.session.settings_mut().autosave_enabled = enable;
+    }
+
+    #[inline]
+    pub fn are_warnings_enabled(&self) -> bool {
+        self.session.settings().warnings_enabled
+    }
+
+    #[inline]
+    pub fn enable_warnings(&mut self, enable: bool) {
+        self.session.settings_mut().warnings_enabled = enable;
     }
 
     #[inline]
@@ -292,6 +414,40 @@ impl GameLoop {
         self.session.tile_map_render_stats()
     }
 
+    // Saves the world view (no UI) to a PNG once the current frame has been rendered.
+    #[inline]
+    pub fn request_screenshot(&mut self) {
+        self.screenshot_requested = true;
+    }
+
+    // Renders the whole map at `zoom` over the next frames and saves it to a PNG.
+    // The camera and simulation are taken over until the export is done.
+    pub fn export_map_to_png(&mut self, zoom: f32) {
+        if !self.is_in_game() || self.map_export.is_some() {
+            return;
+        }
+
+        let camera = self.session.camera();
+        if !camera.map_size_in_cells().is_valid() {
+            return;
+        }
+
+        let export = MapExport::new(camera.map_size_in_cells(), camera.viewport_size(), zoom);
+        let camera_transform = camera.transform();
+        let sim_was_paused = self.session.sim().is_paused();
+
+        // Keep units from moving between chunks.
+        self.session.sim_mut().pause();
+
+        self.map_export = Some(MapExportRun { export, camera_transform, sim_was_paused });
+    }
+
+    // Fraction of the map exported so far or None if no export is in progress.
+    #[inline]
+    pub fn map_export_progress(&self) -> Option<f32> {
+        self.map_export.as_ref().map(|run| run.export.progress())
+    }
+
     #[inline]
     pub fn set_grid_line_thickness(&mut self, thickness: f32) {
         self.session.set_grid_line_thickness(thickness);
@@ -302,6 +458,16 @@ impl GameLoop {
         self.session.grid_line_thickness()
     }
 
+    #[inline]
+    pub fn set_node_glyphs_debug_mask(&mut self, mask: NodeGlyphsDebugMask) {
+        self.session.set_node_glyphs_debug_mask(mask);
+    }
+
+    #[inline]
+    pub fn node_glyphs_debug_mask(&self) -> NodeGlyphsDebugMask {
+        self.session.node_glyphs_debug_mask()
+    }
+
     // ----------------------
     // Internal:
     // ----------------------
@@ -314,6 +480,9 @@ impl GameLoop {
         BuildingConfigs::load();
         log::info!(log::channel!("game"), "BuildingConfigs loaded.");
 
+        BuildingUnlockConfigs::load();
+        log::info!(log::channel!("game"), "BuildingUnlockConfigs loaded.");
+
         UnitConfigs::load();
         log::info!(log::channel!("game"), "UnitConfigs loaded.");
 
@@ -323,23 +492,144 @@ impl GameLoop {
         CampaignConfigs::load();
         log::info!(log::channel!("game"), "CampaignConfigs loaded.");
 
+        CampaignMetaState::load();
+        log::info!(log::channel!("game"), "CampaignMetaState loaded.");
+
+        RandomEventConfigs::load();
+        log::info!(log::channel!("game"), "RandomEventConfigs loaded.");
+
+        SoundBankConfigs::load();
+        log::info!(log::channel!("game"), "SoundBankConfigs loaded.");
+
+        MusicPlaylistConfigs::load();
+        log::info!(log::channel!("game"), "MusicPlaylistConfigs loaded.");
+
+        SoundEventConfigs::load();
+        SoundEventConfigs::get().register(engine.sound_system_mut());
+        log::info!(log::channel!("game"), "SoundEventConfigs loaded.");
+
+        CaptionConfigs::load();
+        CaptionConfigs::get().register(engine.sound_system_mut());
+        log::info!(log::channel!("game"), "CaptionConfigs loaded.");
+
+        KeyBindings::load();
+        log::info!(log::channel!("game"), "KeyBindings loaded.");
+
+        TilePalettePrefs::load();
+        log::info!(log::channel!("game"), "TilePalettePrefs loaded.");
+
+        Localization::load(configs.language);
+        engine.ui_system().request_font_glyph_ranges(configs.language.font_glyph_ranges());
+        log::info!(log::channel!("game"), "Localization loaded.");
+
         let tex_cache = engine.texture_cache_mut();
-        TileSets::load(tex_cache, configs.engine.use_packed_texture_atlas, configs.debug.skip_loading_tile_sets, false);
+        TileSets::load(
+            tex_cache,
+            configs.engine.use_packed_texture_atlas,
+            configs.engine.async_tile_set_loading,
+            configs.debug.skip_loading_tile_sets,
+            false,
+        );
         log::info!(log::channel!("game"), "TileSets loaded.");
 
+        // Resolves prefab parts into TileDefs, so it has to come after the TileSets.
+        PrefabConfigs::load();
+        log::info!(log::channel!("game"), "PrefabConfigs loaded.");
+
+        TileTextureStreamer::get_mut().set_preload_callback(Box::new(|progress| {
+            if progress.is_done() {
+                log::info!(log::channel!("game"), "Background loaded {} tile textures.", progress.total);
+            }
+        }));
+
         let load_assets_time_ms = load_assets_timer.end();
         log::info!(log::channel!("game"), "Load Assets took: {:.1}ms", load_assets_time_ms);
     }
 
     fn unload_assets() {
+        TileTextureStreamer::terminate();
         TileSets::terminate();
+        Localization::terminate();
+        PrefabConfigs::terminate();
+        CaptionConfigs::terminate();
+        SoundEventConfigs::terminate();
+        MusicPlaylistConfigs::terminate();
+        SoundBankConfigs::terminate();
+        CampaignMetaState::terminate();
         CampaignConfigs::terminate();
         PropConfigs::terminate();
         UnitConfigs::terminate();
+        BuildingUnlockConfigs::terminate();
         BuildingConfigs::terminate();
         GameConfigs::terminate();
     }
 
+    // ----------------------
+    // Crash Report:
+    // ----------------------
+
+    fn register_crash_report_section(configs: &GameConfigs) {
+        platform::add_crash_report_section("SESSION SNAPSHOT", || {
+            // Same as the sim event log, the session is only safe to read from the main thread.
+            if platform::is_main_thread() && Self::is_initialized() {
+                Self::get().crash_report_snapshot()
+            } else {
+                "Session snapshot only available for main thread panics.".into()
+            }
+        });
+
+        if configs.save.emergency_save_on_crash {
+            platform::set_crash_emergency_fn(Some(Self::emergency_save));
+        }
+    }
+
+    fn crash_report_snapshot(&self) -> String {
+        const LOG_TAIL_LINES: usize = 32;
+
+        let mut out = String::new();
+        let sim = self.session.sim();
+        let world = self.session.world();
+
+        writeln!(out, "In game: {}, Home menu: {}", self.is_in_game(), self.is_in_home_menu()).unwrap();
+        writeln!(out, "Map size: {}", self.session.tile_map().size_in_cells()).unwrap();
+        writeln!(out, "Sim tick: {}, Date: {}", SimEventLog::get().current_tick(), sim.calendar()).unwrap();
+        writeln!(out, "Sim speed: {:.1}, Paused: {}", sim.speed(), sim.is_paused()).unwrap();
+        writeln!(
+            out,
+            "Population: {}, Gold: {}, Buildings: {}, Units: {}",
+            world.stats().population.total,
+            sim.treasury().gold_units(),
+            world.buildings_stats().0,
+            world.units_stats().0
+        )
+        .unwrap();
+
+        writeln!(out, "\nLast session commands:").unwrap();
+        for cmd in self.session_cmd_queue.history() {
+            writeln!(out, "  {cmd}").unwrap();
+        }
+
+        writeln!(out, "\nLog tail:").unwrap();
+        match debug::log_viewer::LogViewer::recent_lines(LOG_TAIL_LINES) {
+            Some(lines) => out.push_str(&lines),
+            None => out.push_str("  <log unavailable>\n"),
+        }
+
+        out
+    }
+
+    // Copies the last autosave to a separate file, so the next autosave can't overwrite the
+    // state the player had before the crash. Never touches the live session: the panic may
+    // have fired halfway through mutating it and the game loop is still borrowed mutably.
+    fn emergency_save() {
+        log::error!(log::channel!("game"), "Copying last autosave to '{}' ...", save::storage::CRASH_SAVE_FILE_NAME);
+
+        match save::storage::copy_save_file(save::storage::AUTOSAVE_FILE_NAME, save::storage::CRASH_SAVE_FILE_NAME) {
+            Ok(_) => log::error!(log::channel!("game"), "Emergency save written."),
+            Err(err) => log::error!(log::channel!("game"), "Emergency save failed: {err}"),
+        }
+    }
+
     // ----------------------
     // Update & Rendering:
     // ----------------------
@@ -363,33 +653,78 @@ impl GameLoop {
     }
 
     fn update_camera(&mut self, cursor_screen_pos: Vec2, delta_time_secs: Seconds) -> CellRange {
+        // The map export drives the camera until it is done.
+        if let Some(run) = &self.map_export {
+            let camera = self.session.camera_mut();
+            camera.set_transform(run.export.chunk_transform());
+            return camera.visible_cells_range();
+        }
+
+        // The benchmark flies the camera along its scripted path.
+        if let Some(benchmark) = &self.benchmark {
+            let camera = self.session.camera_mut();
+            camera.set_viewport_size(self.engine.app().window_size());
+            camera.teleport_iso(benchmark.camera_position(camera.map_size_in_cells()));
+            return camera.visible_cells_range();
+        }
+
         let viewport_size = self.engine.app().window_size();
-        let is_any_ui_item_hovered = self.engine.ui_system().ui().is_any_item_hovered();
+        let ui_sys = self.engine.ui_system();
+
+        // No edge scrolling while hovering a menu item and no key scrolling while
+        // typing into a menu or waiting for a key press to rebind an action.
+        let scroll_input = CameraScrollInput::new(
+            self.engine.input_system(),
+            cursor_screen_pos,
+            ui_sys.ui().is_any_item_hovered(),
+            !ui_sys.is_handling_key_input() && !KeyBindings::get().is_capturing(),
+        );
 
         let camera = self.session.camera_mut();
         camera.set_viewport_size(viewport_size);
         camera.update_zooming(delta_time_secs);
+        camera.update_scrolling(scroll_input, delta_time_secs);
 
-        // Map scrolling, if cursor not hovering a menu item.
-        if !is_any_ui_item_hovered {
-            camera.update_scrolling(cursor_screen_pos, delta_time_secs);
-        }
-
-        camera.visible_cells_range()
+        self.session.update_camera_panning(delta_time_secs);
+        self.session.camera().visible_cells_range()
     }
 
     fn update_sound_system(&mut self) {
         let sound_update_timer = PerfTimer::begin();
+        self.update_sound_banks();
         let listener_position = self.session.camera().iso_world_position();
         self.engine.sound_system_mut().update(listener_position);
         self.stats.sound_frame_time_ms = sound_update_timer.end();
     }
 
+    // Preload sounds for the game states we're in and release the ones we've left.
+    fn update_sound_banks(&mut self) {
+        let in_game = self.is_in_game();
+        let systems = self.session.systems();
+        let disaster = in_game
+            && (systems.find_by_type::<HazardsSystem>().is_some_and(|hazards| hazards.is_disaster_active())
+                || systems.find_by_type::<InvasionSystem>().is_some_and(|invasion| invasion.is_invasion_active()));
+
+        let sound_sys = self.engine.sound_system_mut();
+        self.sound_banks.set_active_states(sound_sys, |state| match state {
+            SoundBankState::Menu     => !in_game,
+            SoundBankState::InGame   => in_game,
+            SoundBankState::Disaster => disaster,
+        });
+    }
+
     fn update_autosave(&mut self) {
-        if !self.enable_autosave || !self.is_in_game() {
+        if !self.is_autosave_enabled() || !self.is_in_game() {
             return; // Don't autosave while in the home/main menu.
         }
 
+        // Never autosave during the smoke test - it would overwrite the saves under test.
+        // Nor during the benchmark, where the save hitch would show up in the frame times,
+        // or headless runs, which only save the final state if asked to.
+        if self.smoke_test.is_some() || self.benchmark.is_some() || self.headless.is_some() {
+            return;
+        }
+
         let delta_time_secs = self.engine.frame_clock().delta_time();
 
         if self.autosave_timer.tick(delta_time_secs).should_update() {
@@ -439,6 +774,79 @@ impl GameLoop {
         }
     }
 
+    // Drives the `--benchmark` run mode: keeps the synthetic city simulating until
+    // the measured duration is over, then writes the timing report and quits.
+    fn update_benchmark(&mut self, delta_time_secs: Seconds) {
+        if !self.is_in_game() {
+            return; // Benchmark session not created yet.
+        }
+
+        // Ensure the simulation ticks regardless of the start_paused config.
+        self.session.sim_mut().resume();
+
+        if self.benchmark.as_mut().unwrap().update(delta_time_secs) {
+            let benchmark = self.benchmark.take().unwrap();
+            benchmark.write_report(self.session.world());
+            self.quit_game();
+        }
+    }
+
+    // Drives the `--headless` run mode: keeps the simulation running until the tick or
+    // time limit is reached, then optionally dumps the final world state and quits.
+    fn update_headless(&mut self) {
+        if !self.is_in_game() {
+            return; // Session not created yet.
+        }
+
+        // Ensure the simulation ticks regardless of the start_paused config.
+        self.session.sim_mut().resume();
+
+        let run = self.headless.as_mut().unwrap();
+        if !run.update() {
+            return;
+        }
+
+        let (ticks_run, dump_file) = (run.ticks_run(), run.dump_file.take());
+        log::info!(
+            log::channel!("headless"),
+            "--- Headless run done: {ticks_run} ticks. Date: {}, Population: {}, Gold: {} ---",
+            self.session.sim().calendar(),
+            self.session.world().stats().population.total,
+            self.session.sim().treasury().gold_units()
+        );
+
+        if let Some(dump_file) = dump_file {
+            self.session.save_game(PathRef::from_str(&dump_file));
+        }
+
+        self.quit_game();
+    }
+
+    fn update_frame_capture(&mut self) {
+        if std::mem::take(&mut self.screenshot_requested) && self.is_in_game() {
+            screenshot::save_screenshot(self.engine.render_system_mut());
+        }
+
+        // Returning to the home menu abandons any export; there is no map to restore.
+        if !self.is_in_game() {
+            self.map_export = None;
+            return;
+        }
+
+        let Some(run) = &mut self.map_export else {
+            return;
+        };
+
+        if run.export.on_frame_end(self.engine.render_system_mut()) {
+            let run = self.map_export.take().unwrap();
+            self.session.camera_mut().set_transform(run.camera_transform);
+
+            if !run.sim_was_paused {
+                self.session.sim_mut().resume();
+            }
+        }
+    }
+
     fn draw_tile_map(&mut self, delta_time_secs: Seconds, visible_range: CellRange, flags: TileMapRenderFlags) {
         if !self.is_in_game() {
             return; // We don't have a tile map to render while at the home menus.
@@ -456,20 +864,20 @@ impl GameLoop {
                 log::info!(log::channel!("game"), "Resized Window: {window_size}, Framebuffer: {framebuffer_size}");
             }
             ApplicationEvent::KeyInput(key, action, modifiers) => {
+                // The key bindings settings menu takes the next key press while rebinding an action.
+                if KeyBindings::get().is_capturing() {
+                    self.menus_on_key_input(key, action, modifiers);
+                    return;
+                }
+
                 let mut input_event = if self.is_in_game() {
                     self.session.camera_mut().on_key_input(key, action, modifiers)
                 } else {
                     UiInputEvent::NotHandled
                 };
 
-                // [CTRL]+[/]: Toggle between DevEditor menu / HUD menu.
-                if input_event.not_handled()
-                    && action == InputAction::Press
-                    && key == InputKey::Slash
-                    && modifiers.intersects(InputModifiers::Control)
-                {
-                    self.session_cmd_queue.push_toggle_menus_mode();
-                    input_event = UiInputEvent::Handled;
+                if input_event.not_handled() && action == InputAction::Press {
+                    input_event = self.handle_key_bindings(key, modifiers);
                 }
 
                 if input_event.not_handled() {
@@ -479,7 +887,8 @@ impl GameLoop {
             ApplicationEvent::Scroll(amount) => {
                 // If we're not hovering over an ImGui menu...
                 let input_event = if self.is_in_game() && !self.engine.ui_system().is_handling_mouse_input() {
-                    self.session.camera_mut().on_mouse_scroll(amount)
+                    let cursor_screen_pos = self.engine.input_system().cursor_pos();
+                    self.session.camera_mut().on_mouse_scroll(amount, cursor_screen_pos)
                 } else {
                     UiInputEvent::NotHandled
                 };
@@ -491,10 +900,68 @@ impl GameLoop {
             ApplicationEvent::MouseButton(button, action, modifiers) => {
                 self.menus_on_mouse_button(button, action, modifiers);
             }
+            ApplicationEvent::PinchZoom(magnification) => {
+                if self.is_in_game() && self.map_export.is_none() {
+                    let cursor_screen_pos = self.engine.input_system().cursor_pos();
+                    self.session.camera_mut().on_pinch_zoom(magnification, cursor_screen_pos);
+                }
+            }
+            ApplicationEvent::PanGesture(delta) => {
+                if self.is_in_game() && self.map_export.is_none() {
+                    self.session.camera_mut().on_pan_gesture(delta);
+                }
+            }
             _ => {}
         }
     }
 
+    // Global key bindings. Menu specific ones (Cancel, Undo/Redo) are handled by the menus.
+    fn handle_key_bindings(&mut self, key: InputKey, modifiers: InputModifiers) -> UiInputEvent {
+        let key_bindings = KeyBindings::get();
+        let is_bound_to = |action| key_bindings.is_key_bound_to(action, key, modifiers);
+
+        // Toggle between DevEditor menu / HUD menu.
+        if is_bound_to(GameAction::ToggleDevMenus) {
+            self.session_cmd_queue.push_toggle_menus_mode();
+            return UiInputEvent::Handled;
+        }
+
+        if !self.is_in_game() {
+            return UiInputEvent::NotHandled;
+        }
+
+        // Rotate the map view 90 degrees clockwise.
+        if is_bound_to(GameAction::RotateMap)
+            && !self.engine.ui_system().is_handling_key_input()
+            && self.session.rotate_map_view_clockwise()
+        {
+            return UiInputEvent::Handled;
+        }
+
+        if is_bound_to(GameAction::QuickSave) {
+            self.save_game(save::storage::QUICKSAVE_FILE_NAME);
+            return UiInputEvent::Handled;
+        }
+
+        if is_bound_to(GameAction::QuickLoad) {
+            self.load_save_game(save::storage::QUICKSAVE_FILE_NAME);
+            return UiInputEvent::Handled;
+        }
+
+        if is_bound_to(GameAction::Screenshot) {
+            self.request_screenshot();
+            return UiInputEvent::Handled;
+        }
+
+        // Export the whole map to PNG.
+        if is_bound_to(GameAction::ExportMap) {
+            self.export_map_to_png(screenshot::DEFAULT_MAP_EXPORT_ZOOM);
+            return UiInputEvent::Handled;
+        }
+
+        UiInputEvent::NotHandled
+    }
+
     // ----------------------
     // In-Game UI / Debug UI:
     // ----------------------
@@ -0,0 +1,114 @@
+// Headless run mode, enabled with the `--headless` command line argument.
+//
+// The engine runs with the Null app, render and sound backends (see `engine::runner`). The GameLoop
+// skips the home menu, the game menus and tile map rendering, and steps the simulation by exactly
+// one fixed tick per frame, as fast as it can. Meant for tests, CI balance runs and servers.
+// Optional arguments:
+//
+//   --headless-ticks=<N>   Quit after N simulation ticks.
+//   --headless-secs=<N>    Quit after N seconds of wall-clock time.
+//   --headless-save=<NAME> Start from this save game instead of the configured map.
+//   --headless-dump=<NAME> Save the final world state to this save game before quitting.
+//
+// Without a tick or time limit the run only ends when the process is terminated.
+
+use common::time::{self, Instant, Seconds};
+use engine::log;
+
+// ----------------------------------------------
+// HeadlessRun
+// ----------------------------------------------
+
+#[derive(Default)]
+pub struct HeadlessRun {
+    pub max_ticks: Option<u64>,
+    pub max_secs: Option<Seconds>,
+    pub save_file: Option<String>,
+    pub dump_file: Option<String>,
+
+    ticks_run: u64,
+    started: Option<Instant>, // Set on the first in-game frame.
+}
+
+impl HeadlessRun {
+    pub fn from_command_line() -> Self {
+        let run = Self::from_args(std::env::args());
+
+        log::info!(
+            log::channel!("headless"),
+            "--- Headless run: max ticks: {:?}, max secs: {:?} ---",
+            run.max_ticks,
+            run.max_secs
+        );
+
+        run
+    }
+
+    fn from_args(args: impl Iterator<Item = String>) -> Self {
+        let mut run = Self::default();
+
+        for arg in args {
+            let (name, value) = arg.split_once('=').unwrap_or((&arg, ""));
+            match name {
+                "--headless-ticks" => run.max_ticks = parse_arg(name, value),
+                "--headless-secs"  => run.max_secs  = parse_arg(name, value),
+                "--headless-save"  => run.save_file = Some(value.into()),
+                "--headless-dump"  => run.dump_file = Some(value.into()),
+                _ => {}
+            }
+        }
+
+        run
+    }
+
+    #[inline]
+    pub fn ticks_run(&self) -> u64 {
+        self.ticks_run
+    }
+
+    // Called once per in-game frame, before the simulation update. Returns
+    // true once the tick or time limit is reached and the run should end.
+    pub fn update(&mut self) -> bool {
+        let started = *self.started.get_or_insert_with(Instant::now);
+
+        let ticks_done = self.max_ticks.is_some_and(|max_ticks| self.ticks_run >= max_ticks);
+        let time_done = self.max_secs.is_some_and(|max_secs| time::elapsed_seconds(Instant::now(), started) >= max_secs);
+
+        if ticks_done || time_done {
+            return true;
+        }
+
+        self.ticks_run += 1;
+        false
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(name: &str, value: &str) -> Option<T> {
+    let parsed = value.parse().ok();
+    if parsed.is_none() {
+        log::error!(log::channel!("headless"), "Invalid value for {name}: '{value}'. Ignored.");
+    }
+    parsed
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stops_after_max_ticks() {
+        let args = ["game", "--headless", "--headless-ticks=3", "--headless-dump=final"];
+        let mut run = HeadlessRun::from_args(args.iter().map(|arg| arg.to_string()));
+
+        assert_eq!(run.max_ticks, Some(3));
+        assert_eq!(run.dump_file.as_deref(), Some("final"));
+
+        let frames = std::iter::from_fn(|| (!run.update()).then_some(())).count();
+        assert_eq!(frames, 3);
+        assert_eq!(run.ticks_run(), 3);
+    }
+}
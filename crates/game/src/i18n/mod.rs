@@ -0,0 +1,201 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, VariantArray};
+
+use engine::{Engine, config::Configs, file_sys::paths::PathRef, log, ui::UiFontGlyphRanges};
+
+// ----------------------------------------------
+// Language
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, Display, VariantArray, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Portuguese,
+}
+
+impl Language {
+    // English is the source language: UI strings are written in English
+    // in code and used as the lookup keys of every other string table.
+    #[inline]
+    pub const fn is_source_language(self) -> bool {
+        matches!(self, Self::English)
+    }
+
+    // String table file under `assets/configs/i18n/`.
+    #[inline]
+    pub const fn code(self) -> &'static str {
+        match self {
+            Self::English    => "en",
+            Self::Portuguese => "pt",
+        }
+    }
+
+    // Glyphs the UI font needs on top of Latin to render this language.
+    #[inline]
+    pub const fn font_glyph_ranges(self) -> UiFontGlyphRanges {
+        match self {
+            Self::English | Self::Portuguese => UiFontGlyphRanges::Latin,
+        }
+    }
+}
+
+// ----------------------------------------------
+// StringTable
+// ----------------------------------------------
+
+// Maps English source strings to their translation.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StringTable {
+    pub strings: HashMap<String, String>,
+}
+
+impl Configs for StringTable {}
+
+impl StringTable {
+    fn load(language: Language) -> Self {
+        if language.is_source_language() {
+            return Self::default(); // Nothing to load, keys are the English strings.
+        }
+
+        let path = format!("i18n/{}", language.code());
+        let table = Self::load_file::<Self>(PathRef::from_str(&path));
+
+        log::info!(log::channel!("i18n"), "Loaded {} strings for {language}.", table.strings.len());
+        table
+    }
+}
+
+// ----------------------------------------------
+// Localization
+// ----------------------------------------------
+
+pub struct Localization {
+    language: Language,
+    table: StringTable,
+    missing_keys: HashSet<String>, // Reported once per key.
+}
+
+impl Localization {
+    pub fn load(language: Language) {
+        Self::initialize(Self { language, table: StringTable::load(language), missing_keys: HashSet::new() });
+    }
+
+    #[inline]
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    // Menus built before the switch keep their old strings until rebuilt.
+    pub fn set_language(&mut self, language: Language) {
+        if self.language == language {
+            return;
+        }
+
+        self.language = language;
+        self.table = StringTable::load(language);
+        self.missing_keys.clear();
+
+        // Fallback font glyphs are merged into the UI font before the next frame.
+        Engine::get().ui_system().request_font_glyph_ranges(language.font_glyph_ranges());
+
+        log::info!(log::channel!("i18n"), "Language switched to {language}.");
+    }
+
+    // Falls back to the English key if the current table has no translation.
+    pub fn translate<'a>(&'a mut self, key: &'a str) -> &'a str {
+        if self.language.is_source_language() {
+            return key;
+        }
+
+        if let Some(text) = self.table.strings.get(key) {
+            return text;
+        }
+
+        if self.missing_keys.insert(key.into()) {
+            log::warning!(log::channel!("i18n"), "Missing {} string: '{key}'", self.language);
+        }
+
+        key
+    }
+
+    // Keys looked up so far that have no translation in the current language.
+    pub fn missing_keys(&self) -> impl Iterator<Item = &str> {
+        self.missing_keys.iter().map(|key| key.as_str())
+    }
+}
+
+// ----------------------------------------------
+// Localization Global Singleton
+// ----------------------------------------------
+
+common::singleton_late_init! { LOCALIZATION_SINGLETON, Localization }
+
+// Untranslated key if the Localization is not loaded (e.g. headless tests).
+pub fn translate(key: &str) -> String {
+    if !Localization::is_initialized() {
+        return key.into();
+    }
+    Localization::get_mut().translate(key).into()
+}
+
+// Replaces each `{name}` in the translated string with its value.
+pub fn translate_with(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = translate(key);
+    for (name, value) in args {
+        text = text.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    text
+}
+
+// Looks up the current language translation of an English UI string.
+// Usage: tr!("Load Game") or tr!("Version {version}", version = crate::version())
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::translate($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::translate_with($key, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+])
+    };
+}
+
+pub(crate) use tr;
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translate_falls_back_to_key_and_reports_missing() {
+        let mut localization = Localization {
+            language: Language::Portuguese,
+            table: StringTable { strings: HashMap::from([("Load Game".into(), "Carregar Jogo".into())]) },
+            missing_keys: HashSet::new(),
+        };
+
+        assert_eq!(localization.translate("Load Game"), "Carregar Jogo");
+        assert_eq!(localization.translate("Quit Game"), "Quit Game");
+        assert_eq!(localization.translate("Quit Game"), "Quit Game");
+        assert_eq!(localization.missing_keys().collect::<Vec<_>>(), ["Quit Game"]);
+
+        localization.language = Language::English;
+        assert_eq!(localization.translate("Load Game"), "Load Game");
+    }
+
+    #[test]
+    fn translate_with_substitutes_named_args() {
+        // Localization is not initialized here, so the key is used as-is.
+        assert_eq!(tr!("Version {version}", version = "1.0"), "Version 1.0");
+        assert_eq!(tr!("{a} of {b}", a = 1, b = 2), "1 of 2");
+    }
+}
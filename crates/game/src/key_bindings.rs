@@ -0,0 +1,270 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter};
+
+use engine::app::input::{InputBinding, InputBindingSlots, InputKey, InputKeyMap, InputModifiers, MouseButton};
+
+use crate::tile::selection::SELECTION_GROUP_COUNT;
+
+// ----------------------------------------------
+// GameAction
+// ----------------------------------------------
+
+// Input actions that can be rebound by the player.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display, EnumCount, EnumIter, Serialize, Deserialize)]
+pub enum GameAction {
+    // Camera:
+    #[strum(to_string = "Pan Left")]
+    PanLeft,
+    #[strum(to_string = "Pan Right")]
+    PanRight,
+    #[strum(to_string = "Pan Up")]
+    PanUp,
+    #[strum(to_string = "Pan Down")]
+    PanDown,
+    #[strum(to_string = "Drag Pan")]
+    DragPan,
+    #[strum(to_string = "Zoom In")]
+    ZoomIn,
+    #[strum(to_string = "Zoom Out")]
+    ZoomOut,
+    #[strum(to_string = "Rotate Map")]
+    RotateMap,
+
+    // Editing:
+    #[strum(to_string = "Cancel / Close")]
+    Cancel,
+    Undo,
+    Redo,
+
+    // Build menu categories:
+    #[strum(to_string = "Build: Clear Land")]
+    BuildClearLand,
+    #[strum(to_string = "Build: Housing")]
+    BuildHousing,
+    #[strum(to_string = "Build: Roads")]
+    BuildRoads,
+    #[strum(to_string = "Build: Food & Farming")]
+    BuildFoodAndFarming,
+    #[strum(to_string = "Build: Industry")]
+    BuildIndustry,
+    #[strum(to_string = "Build: Services")]
+    BuildServices,
+    #[strum(to_string = "Build: Infrastructure")]
+    BuildInfrastructure,
+    #[strum(to_string = "Build: Culture & Religion")]
+    BuildCulture,
+    #[strum(to_string = "Build: Trade")]
+    BuildTrade,
+    #[strum(to_string = "Build: Beautification")]
+    BuildBeautification,
+
+    // Unit selection groups:
+    #[strum(to_string = "Select Group 1")]
+    SelectGroup1,
+    #[strum(to_string = "Select Group 2")]
+    SelectGroup2,
+    #[strum(to_string = "Select Group 3")]
+    SelectGroup3,
+    #[strum(to_string = "Select Group 4")]
+    SelectGroup4,
+    #[strum(to_string = "Select Group 5")]
+    SelectGroup5,
+    #[strum(to_string = "Select Group 6")]
+    SelectGroup6,
+    #[strum(to_string = "Select Group 7")]
+    SelectGroup7,
+    #[strum(to_string = "Select Group 8")]
+    SelectGroup8,
+    #[strum(to_string = "Select Group 9")]
+    SelectGroup9,
+    #[strum(to_string = "Assign Group 1")]
+    AssignGroup1,
+    #[strum(to_string = "Assign Group 2")]
+    AssignGroup2,
+    #[strum(to_string = "Assign Group 3")]
+    AssignGroup3,
+    #[strum(to_string = "Assign Group 4")]
+    AssignGroup4,
+    #[strum(to_string = "Assign Group 5")]
+    AssignGroup5,
+    #[strum(to_string = "Assign Group 6")]
+    AssignGroup6,
+    #[strum(to_string = "Assign Group 7")]
+    AssignGroup7,
+    #[strum(to_string = "Assign Group 8")]
+    AssignGroup8,
+    #[strum(to_string = "Assign Group 9")]
+    AssignGroup9,
+
+    // Save games:
+    #[strum(to_string = "Quick Save")]
+    QuickSave,
+    #[strum(to_string = "Quick Load")]
+    QuickLoad,
+
+    // Screenshots:
+    Screenshot,
+    #[strum(to_string = "Export Map")]
+    ExportMap,
+
+    // Debug:
+    #[strum(to_string = "Toggle Dev Menus")]
+    ToggleDevMenus,
+}
+
+impl GameAction {
+    // Indexed by selection group. See `UnitOrders`.
+    pub const SELECT_GROUP: [Self; SELECTION_GROUP_COUNT] = [
+        Self::SelectGroup1,
+        Self::SelectGroup2,
+        Self::SelectGroup3,
+        Self::SelectGroup4,
+        Self::SelectGroup5,
+        Self::SelectGroup6,
+        Self::SelectGroup7,
+        Self::SelectGroup8,
+        Self::SelectGroup9,
+    ];
+
+    pub const ASSIGN_GROUP: [Self; SELECTION_GROUP_COUNT] = [
+        Self::AssignGroup1,
+        Self::AssignGroup2,
+        Self::AssignGroup3,
+        Self::AssignGroup4,
+        Self::AssignGroup5,
+        Self::AssignGroup6,
+        Self::AssignGroup7,
+        Self::AssignGroup8,
+        Self::AssignGroup9,
+    ];
+}
+
+// ----------------------------------------------
+// Default bindings
+// ----------------------------------------------
+
+const fn key(key: InputKey) -> Option<InputBinding> {
+    Some(InputBinding::key(key))
+}
+
+const fn ctrl(key: InputKey) -> Option<InputBinding> {
+    Some(InputBinding::key_with_modifiers(key, InputModifiers::Control))
+}
+
+const fn cmd(key: InputKey) -> Option<InputBinding> {
+    Some(InputBinding::key_with_modifiers(key, InputModifiers::Super))
+}
+
+const fn shift(key: InputKey) -> Option<InputBinding> {
+    Some(InputBinding::key_with_modifiers(key, InputModifiers::Shift))
+}
+
+const fn ctrl_shift(key: InputKey) -> Option<InputBinding> {
+    Some(InputBinding::key_with_modifiers(key, InputModifiers::Control.union(InputModifiers::Shift)))
+}
+
+const fn cmd_shift(key: InputKey) -> Option<InputBinding> {
+    Some(InputBinding::key_with_modifiers(key, InputModifiers::Super.union(InputModifiers::Shift)))
+}
+
+const fn bindings(primary: Option<InputBinding>, alternate: Option<InputBinding>) -> InputBindingSlots {
+    InputBindingSlots::new(primary, alternate)
+}
+
+const DEFAULT_KEY_BINDINGS: [(GameAction, InputBindingSlots); GameAction::COUNT] = [
+    (GameAction::PanLeft,             bindings(key(InputKey::Left),  key(InputKey::A))),
+    (GameAction::PanRight,            bindings(key(InputKey::Right), key(InputKey::D))),
+    (GameAction::PanUp,               bindings(key(InputKey::Up),    key(InputKey::W))),
+    (GameAction::PanDown,             bindings(key(InputKey::Down),  key(InputKey::S))),
+    (GameAction::DragPan,             bindings(Some(InputBinding::mouse(MouseButton::Middle)), None)),
+    (GameAction::ZoomIn,              bindings(ctrl(InputKey::Equal), ctrl(InputKey::KpAdd))),
+    (GameAction::ZoomOut,             bindings(ctrl(InputKey::Minus), ctrl(InputKey::KpSubtract))),
+    (GameAction::RotateMap,           bindings(key(InputKey::R), None)),
+    (GameAction::Cancel,              bindings(key(InputKey::Escape), None)),
+    (GameAction::Undo,                bindings(ctrl(InputKey::Z), cmd(InputKey::Z))),
+    (GameAction::Redo,                bindings(ctrl_shift(InputKey::Z), cmd_shift(InputKey::Z))),
+    (GameAction::BuildClearLand,      bindings(key(InputKey::Num1), None)),
+    (GameAction::BuildHousing,        bindings(key(InputKey::Num2), None)),
+    (GameAction::BuildRoads,          bindings(key(InputKey::Num3), None)),
+    (GameAction::BuildFoodAndFarming, bindings(key(InputKey::Num4), None)),
+    (GameAction::BuildIndustry,       bindings(key(InputKey::Num5), None)),
+    (GameAction::BuildServices,       bindings(key(InputKey::Num6), None)),
+    (GameAction::BuildInfrastructure, bindings(key(InputKey::Num7), None)),
+    (GameAction::BuildCulture,        bindings(key(InputKey::Num8), None)),
+    (GameAction::BuildTrade,          bindings(key(InputKey::Num9), None)),
+    (GameAction::BuildBeautification, bindings(key(InputKey::Num0), None)),
+    (GameAction::SelectGroup1,        bindings(shift(InputKey::Num1), None)),
+    (GameAction::SelectGroup2,        bindings(shift(InputKey::Num2), None)),
+    (GameAction::SelectGroup3,        bindings(shift(InputKey::Num3), None)),
+    (GameAction::SelectGroup4,        bindings(shift(InputKey::Num4), None)),
+    (GameAction::SelectGroup5,        bindings(shift(InputKey::Num5), None)),
+    (GameAction::SelectGroup6,        bindings(shift(InputKey::Num6), None)),
+    (GameAction::SelectGroup7,        bindings(shift(InputKey::Num7), None)),
+    (GameAction::SelectGroup8,        bindings(shift(InputKey::Num8), None)),
+    (GameAction::SelectGroup9,        bindings(shift(InputKey::Num9), None)),
+    (GameAction::AssignGroup1,        bindings(ctrl(InputKey::Num1), cmd(InputKey::Num1))),
+    (GameAction::AssignGroup2,        bindings(ctrl(InputKey::Num2), cmd(InputKey::Num2))),
+    (GameAction::AssignGroup3,        bindings(ctrl(InputKey::Num3), cmd(InputKey::Num3))),
+    (GameAction::AssignGroup4,        bindings(ctrl(InputKey::Num4), cmd(InputKey::Num4))),
+    (GameAction::AssignGroup5,        bindings(ctrl(InputKey::Num5), cmd(InputKey::Num5))),
+    (GameAction::AssignGroup6,        bindings(ctrl(InputKey::Num6), cmd(InputKey::Num6))),
+    (GameAction::AssignGroup7,        bindings(ctrl(InputKey::Num7), cmd(InputKey::Num7))),
+    (GameAction::AssignGroup8,        bindings(ctrl(InputKey::Num8), cmd(InputKey::Num8))),
+    (GameAction::AssignGroup9,        bindings(ctrl(InputKey::Num9), cmd(InputKey::Num9))),
+    (GameAction::QuickSave,           bindings(key(InputKey::F5), None)),
+    (GameAction::QuickLoad,           bindings(key(InputKey::F9), None)),
+    (GameAction::Screenshot,          bindings(key(InputKey::F12), None)),
+    (GameAction::ExportMap,           bindings(shift(InputKey::F12), None)),
+    (GameAction::ToggleDevMenus,      bindings(ctrl(InputKey::Slash), None)),
+];
+
+// ----------------------------------------------
+// KeyBindings
+// ----------------------------------------------
+
+// Player key bindings, loaded from and saved to `assets/configs/game/key_bindings.json`.
+// Actions missing from the file fall back to their default bindings.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    #[serde(flatten)]
+    keymap: InputKeyMap<GameAction>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self { keymap: InputKeyMap::with_defaults(&DEFAULT_KEY_BINDINGS) }
+    }
+}
+
+impl KeyBindings {
+    pub fn reset_to_defaults(&mut self) {
+        self.keymap = InputKeyMap::with_defaults(&DEFAULT_KEY_BINDINGS);
+    }
+
+    fn post_load(&'static mut self) {
+        self.keymap.merge_defaults(&DEFAULT_KEY_BINDINGS);
+    }
+}
+
+impl Deref for KeyBindings {
+    type Target = InputKeyMap<GameAction>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.keymap
+    }
+}
+
+impl DerefMut for KeyBindings {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.keymap
+    }
+}
+
+// ----------------------------------------------
+// KeyBindings Global Singleton
+// ----------------------------------------------
+
+engine::configurations! { KEY_BINDINGS_SINGLETON, KeyBindings, "game/key_bindings" }
@@ -4,24 +4,36 @@
 #![allow(dead_code)]
 
 // Core game modules:
+pub mod benchmark;
 pub mod building;
 pub mod camera;
 pub mod campaign;
+pub mod captions;
 pub mod cheats;
 pub mod config;
 pub mod constants;
 pub mod debug;
+pub mod headless;
+pub mod i18n;
+pub mod key_bindings;
 pub mod menu;
 pub mod pathfind;
 pub mod prop;
+pub mod random_events;
 pub mod save_context;
+pub mod save_header;
+pub mod screenshot;
 pub mod session;
+pub mod session_settings;
 pub mod sim;
+pub mod sound_banks;
+pub mod sound_events;
 pub mod system;
 pub mod tile;
 pub mod ui_context;
 pub mod undo_redo;
 pub mod unit;
+pub mod user_settings;
 pub mod world;
 
 // Re-export GameLoop and key types at the crate root.
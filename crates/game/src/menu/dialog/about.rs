@@ -44,9 +44,9 @@ impl About {
         );
 
         let about_text = vec![
-            UiText::new("A City Builder by Core System Games".into(), DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE),
-            UiText::new("Copyright © 2026. All Rights Reserved".into(), DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE),
-            UiText::new(format!("Version {}", crate::version()), DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE),
+            UiText::new(tr!("A City Builder by Core System Games"), DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE),
+            UiText::new(tr!("Copyright © 2026. All Rights Reserved"), DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE),
+            UiText::new(tr!("Version {version}", version = crate::version()), DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE),
         ];
 
         menu.add_widget(UiMenuHeading::new(context, UiMenuHeadingParams {
@@ -0,0 +1,140 @@
+use engine::log;
+
+use super::*;
+use crate::{
+    campaign::{self, config::CampaignConfigs},
+    menu::TEXT_BUTTON_HOVERED_SPRITE,
+};
+
+// ----------------------------------------------
+// CampaignMissions
+// ----------------------------------------------
+
+// Lists the missions of every campaign with their unlock state.
+// Unlocked missions open a MissionBriefing from where they can be started.
+pub struct CampaignMissions {
+    missions: Vec<(usize, usize)>, // (campaign_id, mission_index) for each list item.
+    menu: UiMenuRcMut,
+}
+
+implement_dialog_menu! { CampaignMissions, ["Campaign"] }
+
+impl CampaignMissions {
+    pub fn new(context: &mut GameUiContext) -> Self {
+        // -------------
+        // Menu:
+        // -------------
+
+        let mut menu = make_default_layout_dialog_menu(
+            context,
+            Self::KIND,
+            Self::TITLE,
+            DEFAULT_DIALOG_MENU_WIDGET_SPACING,
+            Option::<Vec<UiWidgetImpl>>::None,
+        );
+
+        // -------------
+        // Widgets:
+        // -------------
+
+        let missions_list = UiItemList::new(context, UiItemListParams {
+            font_scale: DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE,
+            size: Some(Vec2::new(0.0, 250.0)), // Use whole parent window width minus margin, fixed height.
+            margin_left: 95.0,
+            margin_right: 100.0,
+            flags: UiItemListFlags::Border | UiItemListFlags::Scrollbars | UiItemListFlags::Scrollable,
+            ..Default::default()
+        });
+
+        // -------------
+        // Buttons:
+        // -------------
+
+        let menu_weak_ref = menu.downgrade().into_not_mut();
+        let briefing_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Briefing"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_closure(move |_, context| {
+                let menu_rc = menu_weak_ref.upgrade().unwrap();
+                let (_, missions_list) = menu_rc.find_widget_of_type::<UiItemList>().unwrap();
+
+                let Some(selected_index) = missions_list.current_selection_index() else {
+                    return;
+                };
+
+                let (campaign_id, mission_index) = super::find::<CampaignMissions>().missions[selected_index];
+                if !campaign::is_mission_unlocked(campaign_id, mission_index) {
+                    log::info!(log::channel!("campaign"), "Mission {campaign_id}.{mission_index} is locked.");
+                    return;
+                }
+
+                super::find::<MissionBriefing>().set_mission(campaign_id, mission_index);
+                super::open(DialogMenuKind::MissionBriefing, false, ui::widgets::context_as_mut::<GameUiContext>(context));
+            }),
+            ..Default::default()
+        });
+
+        let back_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Back"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_, context| {
+                super::close_current(ui::widgets::context_as_mut::<GameUiContext>(context));
+            }),
+            ..Default::default()
+        });
+
+        let mut side_by_side_button_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING * 2.0,
+            center_vertically: false,
+            center_horizontally: true,
+            stack_vertically: false,
+            ..Default::default()
+        });
+
+        side_by_side_button_group.add_widget(briefing_button);
+        side_by_side_button_group.add_widget(back_button);
+
+        // -------------
+        // Link all:
+        // -------------
+
+        // When menu opens, refresh the unlock state of each mission:
+        menu.set_open_close_callback(UiMenuOpenClose::with_fn(|campaign_menu, _context, is_open| {
+            if is_open {
+                let (_, missions_list) = campaign_menu.find_widget_of_type_mut::<UiItemList>().unwrap();
+                let (missions, labels) = Self::list_missions();
+                missions_list.reset_items(None, labels);
+                super::find::<CampaignMissions>().missions = missions;
+            }
+        }));
+
+        let spacing = UiSeparator::new(context, UiSeparatorParams {
+            thickness: Some(DEFAULT_DIALOG_MENU_WIDGET_SPACING.x),
+            ..Default::default()
+        });
+
+        menu.add_widget(missions_list);
+        menu.add_widget(spacing);
+        menu.add_widget(side_by_side_button_group);
+
+        Self { missions: Vec::new(), menu }
+    }
+
+    fn list_missions() -> (Vec<(usize, usize)>, Vec<String>) {
+        let mut missions = Vec::new();
+        let mut labels = Vec::new();
+
+        for (campaign_id, campaign) in CampaignConfigs::get().campaigns.iter().enumerate() {
+            for (mission_index, mission) in campaign.missions.iter().enumerate() {
+                let is_unlocked = campaign::is_mission_unlocked(campaign_id, mission_index);
+                let lock_state = if is_unlocked { String::new() } else { tr!(" (Locked)") };
+                labels.push(format!("{}: {}. {}{lock_state}", campaign.name, mission_index + 1, mission.name));
+                missions.push((campaign_id, mission_index));
+            }
+        }
+
+        (missions, labels)
+    }
+}
@@ -12,6 +12,7 @@ use crate::menu::TEXT_BUTTON_HOVERED_SPRITE;
 #[derive(EnumCount)]
 enum TaxStatsIdx {
     Taxes,
+    TaxRate,
     TaxGenerated,
     TaxAvailable,
     TaxCollected,
@@ -46,6 +47,7 @@ impl FinancesManagement {
             PLACEHOLDER_BODY,
             PLACEHOLDER_BODY,
             PLACEHOLDER_BODY,
+            PLACEHOLDER_BODY,
         ];
 
         // Treasury stats placeholder text.
@@ -83,8 +85,29 @@ impl FinancesManagement {
         let mut button_group =
             UiWidgetGroup::new(context, UiWidgetGroupParams { center_vertically: false, ..Default::default() });
 
+        // Raising taxes brings in more gold but upsets households above the tolerated rate.
+        let lower_taxes_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Lower Taxes"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_, context| {
+                Self::adjust_tax_rate(ui::widgets::context_as_mut::<GameUiContext>(context), -1);
+            }),
+            ..Default::default()
+        });
+
+        let raise_taxes_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Raise Taxes"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_, context| {
+                Self::adjust_tax_rate(ui::widgets::context_as_mut::<GameUiContext>(context), 1);
+            }),
+            ..Default::default()
+        });
+
         let ok_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Ok".into(),
+            label: tr!("Ok"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_, context| {
@@ -101,12 +124,23 @@ impl FinancesManagement {
             }
         }));
 
+        button_group.add_widget(lower_taxes_button);
+        button_group.add_widget(raise_taxes_button);
         button_group.add_widget(ok_button);
         menu.add_widget(button_group);
 
         Self { menu, tax_stats_heading_index, treasury_stats_heading_index }
     }
 
+    fn adjust_tax_rate(context: &mut GameUiContext, delta: i32) {
+        let treasury = context.sim.treasury_mut();
+        let tax_rate_percent = treasury.tax_rate_percent().saturating_add_signed(delta);
+        treasury.set_tax_rate_percent(tax_rate_percent);
+
+        let this_dialog = super::find::<FinancesManagement>();
+        this_dialog.update_stats(context);
+    }
+
     fn update_stats(&mut self, context: &GameUiContext) {
         const FMT_LEN: usize = 128;
 
@@ -118,6 +152,11 @@ impl FinancesManagement {
 
             heading.set_line_string(TaxStatsIdx::Taxes as usize, "Taxes");
 
+            heading.set_line_string(
+                TaxStatsIdx::TaxRate as usize,
+                &format_fixed_string!(FMT_LEN, "Tax Rate: {}%", global_treasury.tax_rate_percent()),
+            );
+
             heading.set_line_string(
                 TaxStatsIdx::TaxGenerated as usize,
                 &format_fixed_string!(FMT_LEN, "Tax Generated: {}", treasury_stats.tax_generated),
@@ -12,6 +12,9 @@ pub use resources::ResourcesManagement;
 mod finances;
 pub use finances::FinancesManagement;
 
+mod statistics;
+pub use statistics::StatisticsManagement;
+
 // ----------------------------------------------
 // Constants
 // ----------------------------------------------
@@ -39,6 +42,9 @@ enum CityManagementButtonKind {
     #[strum(props(Label = "Finances"))]
     Finances,
 
+    #[strum(props(Label = "Statistics"))]
+    Statistics,
+
     #[strum(props(Label = "Back ->"))]
     Back,
 }
@@ -50,6 +56,7 @@ impl ButtonDef for CityManagementButtonKind {
             Self::Population => super::open(DialogMenuKind::PopulationManagement, CLOSE_ALL_OTHERS, context),
             Self::Resources  => super::open(DialogMenuKind::ResourcesManagement,  CLOSE_ALL_OTHERS, context),
             Self::Finances   => super::open(DialogMenuKind::FinancesManagement,   CLOSE_ALL_OTHERS, context),
+            Self::Statistics => super::open(DialogMenuKind::StatisticsManagement, CLOSE_ALL_OTHERS, context),
             Self::Back       => super::close_current(context),
         }
     }
@@ -3,7 +3,7 @@ use engine::log;
 use strum::EnumCount;
 
 use super::*;
-use crate::menu::TEXT_BUTTON_HOVERED_SPRITE;
+use crate::{menu::TEXT_BUTTON_HOVERED_SPRITE, system::settlers::SettlersSpawnSystem};
 
 // ----------------------------------------------
 // Enums / Constants
@@ -17,6 +17,10 @@ enum PopulationStatsIdx {
     Unemployed,
     EmploymentRate,
     UnemploymentRate,
+    Happiness,
+    HappinessBreakdown,
+    UnhappyHouseholds,
+    RiotRisk,
 }
 
 #[repr(usize)]
@@ -50,6 +54,10 @@ impl PopulationManagement {
             PLACEHOLDER_BODY,
             PLACEHOLDER_BODY,
             PLACEHOLDER_BODY,
+            PLACEHOLDER_BODY,
+            PLACEHOLDER_BODY,
+            PLACEHOLDER_BODY,
+            PLACEHOLDER_BODY,
         ];
 
         // Workforce stats placeholder text.
@@ -89,7 +97,7 @@ impl PopulationManagement {
             UiWidgetGroup::new(context, UiWidgetGroupParams { center_vertically: false, ..Default::default() });
 
         let ok_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Ok".into(),
+            label: tr!("Ok"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_, context| {
@@ -159,6 +167,40 @@ impl PopulationManagement {
                 PopulationStatsIdx::UnemploymentRate as usize,
                 &format_fixed_string!(FMT_LEN, "Unemployment Rate: {}%", unemployment_percent.round() as u32),
             );
+
+            let happiness = world_stats.happiness.average();
+            let percent = |value: f32| (value * 100.0).round() as u32;
+
+            heading.set_line_string(
+                PopulationStatsIdx::Happiness as usize,
+                &format_fixed_string!(FMT_LEN, "Happiness: {}%", percent(happiness.score())),
+            );
+
+            heading.set_line_string(
+                PopulationStatsIdx::HappinessBreakdown as usize,
+                &format_fixed_string!(
+                    FMT_LEN,
+                    "Needs {}% | Services {}% | Taxes {}% | Events {}%",
+                    percent(happiness.needs),
+                    percent(happiness.services),
+                    percent(happiness.tax),
+                    percent(happiness.events)
+                ),
+            );
+
+            heading.set_line_string(
+                PopulationStatsIdx::UnhappyHouseholds as usize,
+                &format_fixed_string!(FMT_LEN, "Unhappy Households: {}", world_stats.happiness.unhappy_households),
+            );
+
+            heading.set_line_string(
+                PopulationStatsIdx::RiotRisk as usize,
+                &format_fixed_string!(
+                    FMT_LEN,
+                    "Riot Risk: {}%",
+                    percent(SettlersSpawnSystem::riot_risk(happiness.score()))
+                ),
+            );
         }
 
         {
@@ -53,7 +53,7 @@ impl ResourcesManagement {
             UiWidgetGroup::new(context, UiWidgetGroupParams { center_vertically: false, ..Default::default() });
 
         let ok_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Ok".into(),
+            label: tr!("Ok"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_, context| {
@@ -0,0 +1,201 @@
+use common::format_fixed_string;
+use strum::IntoEnumIterator;
+
+use super::*;
+use crate::{
+    menu::TEXT_BUTTON_HOVERED_SPRITE,
+    sim::{resources::ResourceKind, statistics::CityStatistics},
+};
+
+// ----------------------------------------------
+// Enums / Constants
+// ----------------------------------------------
+
+const CHART_SIZE: Vec2 = Vec2::new(320.0, 50.0);
+const CHART_LINE_COLOR: Color = Color::new(0.3, 0.2, 0.1, 1.0);
+const CHART_FRAME_COLOR: Color = Color::new(0.3, 0.2, 0.1, 0.5);
+
+#[derive(Copy, Clone, EnumCount, EnumIter)]
+enum StatisticsChart {
+    Population,
+    Employment,
+    Treasury,
+    FoodStock,
+}
+
+impl StatisticsChart {
+    fn values(self, statistics: &CityStatistics) -> Vec<f32> {
+        match self {
+            Self::Population => statistics.series(|sample| sample.population as f32),
+            Self::Employment => statistics.series(|sample| sample.employment_ratio() * 100.0),
+            Self::Treasury   => statistics.series(|sample| sample.treasury as f32),
+            Self::FoodStock  => statistics.series(|sample| sample.food_stock() as f32),
+        }
+    }
+
+    // Percentages have a fixed vertical range; counts fit their values.
+    fn scale_range(self) -> (Option<f32>, Option<f32>) {
+        match self {
+            Self::Employment => (Some(0.0), Some(100.0)),
+            _ => (None, None),
+        }
+    }
+
+    fn caption(self, statistics: &CityStatistics) -> String {
+        let Some(latest) = statistics.latest() else {
+            return tr!(self.title());
+        };
+
+        let value = match self {
+            Self::Population => latest.population.to_string(),
+            Self::Employment => format!("{}%", (latest.employment_ratio() * 100.0).round() as u32),
+            Self::Treasury   => latest.treasury.to_string(),
+            Self::FoodStock  => latest.food_stock().to_string(),
+        };
+
+        format!("{}: {value}", tr!(self.title()))
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::Population => "Population",
+            Self::Employment => "Employment",
+            Self::Treasury   => "Treasury",
+            Self::FoodStock  => "Food Stock",
+        }
+    }
+}
+
+// ----------------------------------------------
+// StatisticsManagement
+// ----------------------------------------------
+
+pub struct StatisticsManagement {
+    menu: UiMenuRcMut,
+    heading_group_index: UiMenuWidgetIndex,
+    chart_group_index: UiWidgetGroupWidgetIndex,
+    chart_indices: Vec<(UiWidgetGroupWidgetIndex, UiWidgetGroupWidgetIndex)>, // (caption, chart) per StatisticsChart.
+    resources_heading_index: UiWidgetGroupWidgetIndex,
+}
+
+implement_dialog_menu! { StatisticsManagement, ["Statistics"] }
+
+impl StatisticsManagement {
+    pub fn new(context: &mut GameUiContext) -> Self {
+        // Charts on the left, per-resource breakdown on the right.
+        let mut side_by_side_heading_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: Vec2::new(30.0, DEFAULT_DIALOG_MENU_WIDGET_SPACING.y),
+            center_vertically: false,
+            center_horizontally: true,
+            stack_vertically: false,
+            ..Default::default()
+        });
+
+        let mut chart_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: Vec2::new(0.0, 4.0),
+            center_vertically: false,
+            center_horizontally: false,
+            ..Default::default()
+        });
+
+        let mut chart_indices = Vec::with_capacity(StatisticsChart::COUNT);
+        for chart in StatisticsChart::iter() {
+            let caption =
+                UiMenuHeading::new(context, UiMenuHeadingParams { lines: vec![PLACEHOLDER_BODY], ..Default::default() });
+
+            let (scale_min, scale_max) = chart.scale_range();
+            let line_chart = UiLineChart::new(context, UiLineChartParams {
+                size: CHART_SIZE,
+                line_color: Some(CHART_LINE_COLOR),
+                frame_color: Some(CHART_FRAME_COLOR),
+                scale_min,
+                scale_max,
+                ..Default::default()
+            });
+
+            chart_indices.push((chart_group.add_widget(caption), chart_group.add_widget(line_chart)));
+        }
+
+        let chart_group_index = side_by_side_heading_group.add_widget(chart_group);
+
+        let mut resources_text = vec![PLACEHOLDER_HEADING];
+        for _ in ResourceKind::all_except(ResourceKind::Gold) {
+            resources_text.push(PLACEHOLDER_BODY);
+        }
+
+        let resources_heading =
+            UiMenuHeading::new(context, UiMenuHeadingParams { lines: resources_text, ..Default::default() });
+
+        let resources_heading_index = side_by_side_heading_group.add_widget(resources_heading);
+
+        let mut button_group =
+            UiWidgetGroup::new(context, UiWidgetGroupParams { center_vertically: false, ..Default::default() });
+
+        let ok_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Ok"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_, context| {
+                super::close_current(ui::widgets::context_as_mut::<GameUiContext>(context));
+            }),
+            ..Default::default()
+        });
+
+        button_group.add_widget(ok_button);
+
+        let mut menu = make_default_layout_dialog_menu(
+            context,
+            Self::KIND,
+            Self::TITLE,
+            DEFAULT_DIALOG_MENU_WIDGET_SPACING,
+            Option::<Vec<UiWidgetImpl>>::None,
+        );
+
+        let heading_group_index = menu.add_widget(side_by_side_heading_group);
+        menu.add_widget(button_group);
+
+        // Refresh charts when menu is opened.
+        menu.set_open_close_callback(UiMenuOpenClose::with_fn(|_, context, is_open| {
+            if is_open {
+                let this_dialog = super::find::<StatisticsManagement>();
+                this_dialog.update_stats(ui::widgets::context_as_mut::<GameUiContext>(context));
+            }
+        }));
+
+        Self { menu, heading_group_index, chart_group_index, chart_indices, resources_heading_index }
+    }
+
+    fn update_stats(&mut self, context: &GameUiContext) {
+        const FMT_LEN: usize = 128;
+        let statistics = context.sim.statistics();
+
+        let group = self.menu.widget_as_mut::<UiWidgetGroup>(self.heading_group_index).unwrap();
+
+        {
+            let chart_group = group.widget_as_mut::<UiWidgetGroup>(self.chart_group_index).unwrap();
+
+            for (chart, (caption_index, chart_index)) in StatisticsChart::iter().zip(&self.chart_indices) {
+                let caption = chart_group.widget_as_mut::<UiMenuHeading>(*caption_index).unwrap();
+                caption.set_line_string(0, &chart.caption(statistics));
+
+                let line_chart = chart_group.widget_as_mut::<UiLineChart>(*chart_index).unwrap();
+                line_chart.set_values(chart.values(statistics));
+            }
+        }
+
+        {
+            let heading = group.widget_as_mut::<UiMenuHeading>(self.resources_heading_index).unwrap();
+
+            let mut index = 0;
+            heading.set_line_string(index, &tr!("Stored Resources")); // Title/Heading
+            index += 1;
+
+            for kind in ResourceKind::all_except(ResourceKind::Gold) {
+                let count = statistics.latest().map_or(0, |sample| sample.stored(kind));
+                let change = statistics.stored_change(kind);
+                heading.set_line_string(index, &format_fixed_string!(FMT_LEN, "{kind}: {count} ({change:+})"));
+                index += 1;
+            }
+        }
+    }
+}
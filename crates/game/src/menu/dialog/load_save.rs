@@ -1,8 +1,25 @@
 use bitflags::bitflags;
-use engine::{file_sys::paths::PathRef, save};
+use common::time;
+use engine::{
+    file_sys::paths::PathRef,
+    render::texture::{TextureFilter, TextureSettings, TextureWrapMode},
+    save,
+    ui::UiTextureHandle,
+};
 
 use super::*;
-use crate::{GameLoop, menu::TEXT_BUTTON_HOVERED_SPRITE};
+use crate::{
+    GameLoop,
+    menu::TEXT_BUTTON_HOVERED_SPRITE,
+    save_header::{SAVE_THUMBNAIL_MAX_SIZE, SaveGameHeader, SaveGameThumbnail},
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Shared by all save game dialogs; only one is open at a time.
+const SAVE_THUMBNAIL_TEXTURE_NAME: &str = "save_thumbnail";
 
 // ----------------------------------------------
 // LoadGame
@@ -106,6 +123,79 @@ impl SaveGameHelper {
         (PathRef::from_str(save_file_name), save_files_list.items())
     }
 
+    fn save_details_text(header: &SaveGameHeader) -> String {
+        // Saves written before headers were added have no timestamp.
+        let saved_at = {
+            if header.timestamp != 0 {
+                format!("{} UTC", time::format_unix_timestamp(header.timestamp))
+            } else {
+                String::from("-")
+            }
+        };
+
+        [
+            tr!("Saved: {time}", time = saved_at),
+            tr!("Map Size: {width}x{height}", width = header.map_size.width, height = header.map_size.height),
+            tr!("Population: {population}", population = header.population),
+            tr!("Playtime: {playtime}", playtime = header.playtime_text()),
+        ]
+        .join("\n")
+    }
+
+    fn upload_thumbnail_texture(thumbnail: &SaveGameThumbnail, context: &mut dyn UiWidgetContext) -> UiTextureHandle {
+        let tex_handle = {
+            let mut render_sys = context.render_sys();
+            let tex_cache = render_sys.texture_cache_mut();
+
+            // Replace the thumbnail of the previously selected save.
+            if let Some(mut existing_texture) = tex_cache.find_loaded_texture(SAVE_THUMBNAIL_TEXTURE_NAME) {
+                tex_cache.release_texture(&mut existing_texture);
+            }
+
+            let thumbnail_texture_settings = TextureSettings {
+                filter: TextureFilter::Nearest,
+                wrap_mode: TextureWrapMode::ClampToEdge,
+                mipmaps: false,
+            };
+            tex_cache.new_initialized_texture(
+                SAVE_THUMBNAIL_TEXTURE_NAME,
+                thumbnail.size,
+                &thumbnail.pixels,
+                Some(thumbnail_texture_settings),
+            )
+        };
+
+        context.ui_sys().to_ui_texture(tex_handle)
+    }
+
+    // Shows the header metadata and thumbnail of the selected save file.
+    // NOTE: Reading the header parses the whole save file, so only do it on selection changes.
+    fn refresh_save_details(menu: &mut UiMenu, save_file_name: Option<&str>, context: &mut dyn UiWidgetContext) {
+        let header = save_file_name.and_then(|file_name| SaveGameHeader::load(PathRef::from_str(file_name)));
+
+        let details = header.as_ref().map_or_else(|| tr!("No save game selected."), Self::save_details_text);
+        let details_size = context.calc_text_size(DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE, &details);
+
+        let (_, details_label) = menu.find_widget_of_type_mut::<UiSizedTextLabel>().unwrap();
+        details_label.set_label(details);
+        details_label.set_size(details_size);
+
+        let thumbnail = header.and_then(|header| header.thumbnail).filter(SaveGameThumbnail::is_valid);
+        let thumbnail_texture = thumbnail.as_ref().map(|thumbnail| Self::upload_thumbnail_texture(thumbnail, context));
+
+        let (_, thumbnail_icon) = menu.find_widget_of_type_mut::<UiSpriteIcon>().unwrap();
+        match (thumbnail, thumbnail_texture) {
+            (Some(thumbnail), Some(texture)) => {
+                // Scale up to fill the thumbnail box, keeping the map aspect ratio.
+                let size = Vec2::new(thumbnail.size.width as f32, thumbnail.size.height as f32);
+                let scale = SAVE_THUMBNAIL_MAX_SIZE as f32 / size.x.max(size.y);
+                thumbnail_icon.set_sprite(texture);
+                thumbnail_icon.set_size(size * scale);
+            }
+            _ => thumbnail_icon.clear_sprite(),
+        }
+    }
+
     fn open_overwrite_save_game_message_box(menu: &mut UiMenuRcMut, context: &mut GameUiContext) {
         let menu_rc = menu.clone();
 
@@ -117,14 +207,14 @@ impl SaveGameHelper {
                 label: Some("Overwrite Save Game Popup".into()),
                 background: Some(DEFAULT_DIALOG_POPUP_BACKGROUND_SPRITE),
                 contents: vec![UiWidgetImpl::from(UiMenuHeading::new(context, UiMenuHeadingParams {
-                    lines: vec![UiText::new("Overwrite existing save game?".into(), DEFAULT_DIALOG_POPUP_FONT_SCALE)],
+                    lines: vec![UiText::new(tr!("Overwrite existing save game?"), DEFAULT_DIALOG_POPUP_FONT_SCALE)],
                     separator: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                     margin_top: 2.0,
                     ..Default::default()
                 }))],
                 buttons: vec![
                     UiWidgetImpl::from(UiTextButton::new(context, UiTextButtonParams {
-                        label: "Yes".into(),
+                        label: tr!("Yes"),
                         size: UiTextButtonSize::Normal,
                         hover: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                         sounds_enabled: UiButtonSoundsEnabled::all(),
@@ -137,7 +227,7 @@ impl SaveGameHelper {
                         ..Default::default()
                     })),
                     UiWidgetImpl::from(UiTextButton::new(context, UiTextButtonParams {
-                        label: "No".into(),
+                        label: tr!("No"),
                         size: UiTextButtonSize::Normal,
                         hover: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                         sounds_enabled: UiButtonSoundsEnabled::all(),
@@ -175,15 +265,32 @@ impl SaveGameHelper {
         // Widgets:
         // -------------
 
+        // Loading shows the details of the selected save below the list.
+        let show_save_details = self.actions.intersects(SaveGameActions::Load);
+
+        let on_selection_changed = {
+            if show_save_details {
+                let menu_weak_ref = menu.downgrade();
+                UiItemListSelectionChanged::with_closure(move |save_files_list, context| {
+                    let mut menu_rc = menu_weak_ref.upgrade().unwrap();
+                    Self::refresh_save_details(&mut menu_rc, save_files_list.current_selection(), context);
+                })
+            } else {
+                UiItemListSelectionChanged::default()
+            }
+        };
+
         let save_files_list = UiItemList::new(context, UiItemListParams {
             font_scale: DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE,
-            size: Some(Vec2::new(0.0, 250.0)), // Use whole parent window width minus margin, fixed height.
+            // Use whole parent window width minus margin, fixed height.
+            size: Some(Vec2::new(0.0, if show_save_details { 200.0 } else { 250.0 })),
             margin_left: 95.0,
             margin_right: 100.0,
             flags: UiItemListFlags::Border
                 | UiItemListFlags::TextInputField
                 | UiItemListFlags::Scrollbars
                 | UiItemListFlags::Scrollable,
+            on_selection_changed,
             ..Default::default()
         });
 
@@ -203,7 +310,7 @@ impl SaveGameHelper {
             let menu_weak_ref = menu.downgrade().into_not_mut();
 
             let load_game_button = UiTextButton::new(context, UiTextButtonParams {
-                label: "Load Game".into(),
+                label: tr!("Load Game"),
                 hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
                 sounds_enabled: UiButtonSoundsEnabled::all(),
                 on_pressed: UiTextButtonPressed::with_closure(move |_, _context| {
@@ -221,7 +328,7 @@ impl SaveGameHelper {
             let menu_weak_ref = menu.downgrade();
 
             let save_game_button = UiTextButton::new(context, UiTextButtonParams {
-                label: "Save Game".into(),
+                label: tr!("Save Game"),
                 hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
                 sounds_enabled: UiButtonSoundsEnabled::all(),
                 on_pressed: UiTextButtonPressed::with_closure(move |_, context| {
@@ -250,7 +357,7 @@ impl SaveGameHelper {
         }
 
         let cancel_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Cancel".into(),
+            label: tr!("Cancel"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_, context| {
@@ -267,7 +374,7 @@ impl SaveGameHelper {
 
         // When menu opens, refresh list of available save game files:
         let save_game_actions = self.actions;
-        menu.set_open_close_callback(UiMenuOpenClose::with_closure(move |save_game_menu, _context, is_open| {
+        menu.set_open_close_callback(UiMenuOpenClose::with_closure(move |save_game_menu, context, is_open| {
             if is_open {
                 let (_, save_files_list) = save_game_menu.find_widget_of_type_mut::<UiItemList>().unwrap();
 
@@ -282,6 +389,11 @@ impl SaveGameHelper {
                     save_files_list.reset_items(None, available_save_files);
                     save_files_list.reset_text_input_field(default_save_file_name.to_string());
                 }
+
+                if show_save_details {
+                    let selected_save_file = save_files_list.current_selection().map(String::from);
+                    Self::refresh_save_details(save_game_menu, selected_save_file.as_deref(), context);
+                }
             }
         }));
 
@@ -291,6 +403,28 @@ impl SaveGameHelper {
         });
 
         menu.add_widget(save_files_list);
+
+        if show_save_details {
+            let thumbnail_icon = UiSpriteIcon::new(context, UiSpriteIconParams {
+                size: Vec2::new(SAVE_THUMBNAIL_MAX_SIZE as f32, SAVE_THUMBNAIL_MAX_SIZE as f32),
+                margin_top: DEFAULT_DIALOG_MENU_WIDGET_SPACING.y,
+                clip_to_parent_menu: true,
+                outline: true,
+                ..Default::default()
+            });
+
+            // Placeholder text, replaced with the selected save's details when the menu opens.
+            let placeholder = tr!("No save game selected.");
+            let details_label = UiSizedTextLabel::new(context, UiSizedTextLabelParams {
+                font_scale: DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE,
+                size: context.calc_text_size(DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE, &placeholder),
+                label: placeholder,
+            });
+
+            menu.add_widget(thumbnail_icon);
+            menu.add_widget(details_label);
+        }
+
         menu.add_widget(spacing);
         menu.add_widget(side_by_side_button_group);
 
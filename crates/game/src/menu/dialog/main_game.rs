@@ -98,8 +98,8 @@ impl MainGame {
                 background: Some(DEFAULT_DIALOG_POPUP_BACKGROUND_SPRITE),
                 contents: vec![UiWidgetImpl::from(UiMenuHeading::new(context, UiMenuHeadingParams {
                     lines: vec![
-                        UiText::new("Quit Game?".into(), DEFAULT_DIALOG_POPUP_FONT_SCALE),
-                        UiText::new("Any unsaved progress will be lost...".into(), DEFAULT_DIALOG_POPUP_FONT_SCALE),
+                        UiText::new(tr!("Quit Game?"), DEFAULT_DIALOG_POPUP_FONT_SCALE),
+                        UiText::new(tr!("Any unsaved progress will be lost..."), DEFAULT_DIALOG_POPUP_FONT_SCALE),
                     ],
                     separator: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                     margin_top: 2.0,
@@ -107,7 +107,7 @@ impl MainGame {
                 }))],
                 buttons: vec![
                     UiWidgetImpl::from(UiTextButton::new(context, UiTextButtonParams {
-                        label: "Quit to Main Menu".into(),
+                        label: tr!("Quit to Main Menu"),
                         size: UiTextButtonSize::Normal,
                         hover: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                         sounds_enabled: UiButtonSoundsEnabled::all(),
@@ -115,7 +115,7 @@ impl MainGame {
                         ..Default::default()
                     })),
                     UiWidgetImpl::from(UiTextButton::new(context, UiTextButtonParams {
-                        label: "Exit Game".into(),
+                        label: tr!("Exit Game"),
                         size: UiTextButtonSize::Normal,
                         hover: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                         sounds_enabled: UiButtonSoundsEnabled::all(),
@@ -123,7 +123,7 @@ impl MainGame {
                         ..Default::default()
                     })),
                     UiWidgetImpl::from(UiTextButton::new(context, UiTextButtonParams {
-                        label: "Cancel".into(),
+                        label: tr!("Cancel"),
                         size: UiTextButtonSize::Normal,
                         hover: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
                         sounds_enabled: UiButtonSoundsEnabled::all(),
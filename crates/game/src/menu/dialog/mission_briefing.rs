@@ -0,0 +1,144 @@
+use std::fmt::Write;
+
+use super::*;
+use crate::{
+    campaign::{self, config::CampaignConfigs},
+    menu::TEXT_BUTTON_HOVERED_SPRITE,
+};
+
+// ----------------------------------------------
+// MissionBriefing
+// ----------------------------------------------
+
+// Description, objectives and carried rewards of a campaign mission,
+// shown before starting it. Opened from the CampaignMissions list.
+pub struct MissionBriefing {
+    campaign_id: usize,
+    mission_index: usize,
+    menu: UiMenuRcMut,
+}
+
+implement_dialog_menu! { MissionBriefing, ["Mission Briefing"] }
+
+impl MissionBriefing {
+    pub fn new(context: &mut GameUiContext) -> Self {
+        // -------------
+        // Widgets:
+        // -------------
+
+        // Placeholder text, replaced by the selected mission's briefing when the menu opens.
+        let placeholder = String::from("Briefing");
+        let briefing_label = UiSizedTextLabel::new(context, UiSizedTextLabelParams {
+            font_scale: DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE,
+            size: context.calc_text_size(DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE, &placeholder),
+            label: placeholder,
+        });
+
+        // -------------
+        // Buttons:
+        // -------------
+
+        let start_mission_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Start Mission"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_button, _context| {
+                let briefing_menu = super::find::<MissionBriefing>();
+                if let Some(map) = campaign::start_mission(briefing_menu.campaign_id, briefing_menu.mission_index) {
+                    super::load_mission_map(&map);
+                }
+            }),
+            ..Default::default()
+        });
+
+        let back_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Back"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_button, context| {
+                super::close_current(ui::widgets::context_as_mut::<GameUiContext>(context));
+            }),
+            ..Default::default()
+        });
+
+        let mut side_by_side_button_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING * 2.0,
+            center_vertically: false,
+            center_horizontally: true,
+            stack_vertically: false,
+            ..Default::default()
+        });
+
+        side_by_side_button_group.add_widget(start_mission_button);
+        side_by_side_button_group.add_widget(back_button);
+
+        // -------------
+        // Menu:
+        // -------------
+
+        let mut menu = make_default_layout_dialog_menu(
+            context,
+            Self::KIND,
+            Self::TITLE,
+            DEFAULT_DIALOG_MENU_WIDGET_SPACING,
+            Option::<Vec<UiWidgetImpl>>::None,
+        );
+
+        // When menu opens, fill in the briefing of the selected mission:
+        menu.set_open_close_callback(UiMenuOpenClose::with_fn(|briefing_menu, context, is_open| {
+            if is_open {
+                let briefing = super::find::<MissionBriefing>().briefing_text();
+                let size = context.calc_text_size(DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE, &briefing);
+
+                let (_, briefing_label) = briefing_menu.find_widget_of_type_mut::<UiSizedTextLabel>().unwrap();
+                briefing_label.set_label(briefing);
+                briefing_label.set_size(size);
+            }
+        }));
+
+        let spacing = UiSeparator::new(context, UiSeparatorParams {
+            thickness: Some(DEFAULT_DIALOG_MENU_WIDGET_SPACING.x),
+            ..Default::default()
+        });
+
+        menu.add_widget(briefing_label);
+        menu.add_widget(spacing);
+        menu.add_widget(side_by_side_button_group);
+
+        Self { campaign_id: 0, mission_index: 0, menu }
+    }
+
+    pub fn set_mission(&mut self, campaign_id: usize, mission_index: usize) {
+        self.campaign_id = campaign_id;
+        self.mission_index = mission_index;
+    }
+
+    fn briefing_text(&self) -> String {
+        let Some(mission) = CampaignConfigs::get().mission(self.campaign_id, self.mission_index) else {
+            return format!("Invalid mission {}.{}", self.campaign_id, self.mission_index);
+        };
+
+        let mut text = mission.name.clone();
+
+        if !mission.description.is_empty() {
+            let _ = write!(text, "\n\n{}", mission.description);
+        }
+
+        if !mission.requirements.goals.is_empty() {
+            let _ = write!(text, "\n\n{}", tr!("Objectives:"));
+            for goal in &mission.requirements.goals {
+                let _ = write!(text, "\n- {goal}: {}", goal.target());
+            }
+        }
+
+        let carried_rewards = campaign::rewards_carried_into(self.campaign_id, self.mission_index);
+        if !carried_rewards.is_empty() {
+            let _ = write!(text, "\n\n{}", tr!("Carried Rewards:"));
+            for reward in &carried_rewards {
+                let _ = write!(text, "\n- {reward}");
+            }
+        }
+
+        text
+    }
+}
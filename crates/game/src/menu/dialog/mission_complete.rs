@@ -31,8 +31,10 @@ impl ButtonDef for MissionCompleteButtonKind {
 // MissionComplete
 // ----------------------------------------------
 
-// Placeholder prompt shown when the active mission's requirements are met.
+// Prompt shown when the active mission's requirements are met.
+// Missions with rewards let the player pick one to carry into the next missions.
 pub struct MissionComplete {
+    selected_reward: usize,
     menu: UiMenuRcMut,
 }
 
@@ -40,20 +42,102 @@ implement_dialog_menu! { MissionComplete, ["Mission Complete!"] }
 
 impl MissionComplete {
     pub fn new(context: &mut GameUiContext) -> Self {
+        // -------------
+        // Widgets:
+        // -------------
+
+        let reward_dropdown = UiDropdown::new(context, UiDropdownParams {
+            font_scale: DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE,
+            current_item: 0,
+            items: vec!["None".into()], // Replaced by the mission rewards when the menu opens.
+            on_selection_changed: UiDropdownSelectionChanged::with_fn(|dropdown, _context| {
+                let mission_complete_menu = super::find::<MissionComplete>();
+                mission_complete_menu.selected_reward = dropdown.current_selection_index();
+            }),
+            ..Default::default()
+        });
+
+        let mut labeled_widget_group = UiLabeledWidgetGroup::new(context, UiLabeledWidgetGroupParams {
+            label_spacing: DEFAULT_DIALOG_MENU_WIDGET_LABEL_SPACING,
+            widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING.y,
+            center_vertically: false,
+            center_horizontally: true,
+            margin_left: 50.0,
+            margin_right: 40.0,
+        });
+
+        labeled_widget_group.add_widget("Reward".into(), reward_dropdown);
+
+        // -------------
+        // Buttons:
+        // -------------
+
         let buttons = make_dialog_button_widgets::<MissionCompleteButtonKind, MISSION_COMPLETE_BUTTON_COUNT>(context);
 
-        Self {
-            menu: make_default_layout_dialog_menu(
-                context,
-                Self::KIND,
-                Self::TITLE,
-                DEFAULT_DIALOG_MENU_BUTTON_SPACING,
-                Some(buttons),
-            ),
+        let mut button_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: DEFAULT_DIALOG_MENU_BUTTON_SPACING,
+            center_vertically: false,
+            center_horizontally: true,
+            ..Default::default()
+        });
+
+        for button in buttons {
+            button_group.add_widget(button);
+        }
+
+        // -------------
+        // Menu:
+        // -------------
+
+        let mut menu = make_default_layout_dialog_menu(
+            context,
+            Self::KIND,
+            Self::TITLE,
+            DEFAULT_DIALOG_MENU_BUTTON_SPACING,
+            Option::<Vec<UiWidgetImpl>>::None,
+        );
+
+        // When menu opens, list the rewards offered by the completed mission:
+        menu.set_open_close_callback(UiMenuOpenClose::with_fn(|mission_complete_menu, _context, is_open| {
+            if is_open {
+                let mut reward_labels: Vec<String> = campaign::active_mission()
+                    .and_then(|active| active.def())
+                    .map(|mission| mission.rewards.iter().map(|reward| reward.to_string()).collect())
+                    .unwrap_or_default();
+
+                if reward_labels.is_empty() {
+                    reward_labels.push("None".into());
+                }
+
+                let (_, labeled_widget_group) =
+                    mission_complete_menu.find_widget_of_type_mut::<UiLabeledWidgetGroup>().unwrap();
+                let (_, reward_dropdown) = labeled_widget_group.find_widget_of_type_mut::<UiDropdown>().unwrap();
+                reward_dropdown.reset_items(0, reward_labels);
+
+                super::find::<MissionComplete>().selected_reward = 0;
+            }
+        }));
+
+        menu.add_widget(labeled_widget_group);
+        menu.add_widget(button_group);
+
+        Self { selected_reward: 0, menu }
+    }
+
+    // Remember the picked reward before the mission is left behind.
+    fn commit_selected_reward() {
+        let has_rewards = campaign::active_mission()
+            .and_then(|active| active.def())
+            .is_some_and(|mission| !mission.rewards.is_empty());
+
+        if has_rewards {
+            campaign::select_reward(super::find::<MissionComplete>().selected_reward);
         }
     }
 
     fn on_next_mission(context: &mut GameUiContext) -> bool {
+        Self::commit_selected_reward();
+
         match campaign::advance_to_next_mission() {
             Some(map) => {
                 // Loading the next mission's map tears down and rebuilds the
@@ -68,6 +152,7 @@ impl MissionComplete {
     }
 
     fn on_continue_playing(context: &mut GameUiContext) -> bool {
+        Self::commit_selected_reward();
         campaign::continue_playing();
         super::close_current(context)
     }
@@ -0,0 +1,70 @@
+use strum::{EnumCount, EnumIter, EnumProperty};
+
+use super::*;
+use crate::{GameLoop, campaign, menu::ButtonDef};
+
+// ----------------------------------------------
+// MissionFailedButtonKind
+// ----------------------------------------------
+
+const MISSION_FAILED_BUTTON_COUNT: usize = MissionFailedButtonKind::COUNT;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumCount, EnumProperty, EnumIter)]
+enum MissionFailedButtonKind {
+    #[strum(props(Label = "Retry Mission"))]
+    RetryMission,
+
+    #[strum(props(Label = "Back to Main Menu"))]
+    BackToMainMenu,
+}
+
+impl ButtonDef for MissionFailedButtonKind {
+    fn on_pressed(self, context: &mut GameUiContext) -> bool {
+        match self {
+            Self::RetryMission => MissionFailed::on_retry_mission(context),
+            // quit_to_main_menu also resets the campaign (see cmd_quit_to_main_menu).
+            Self::BackToMainMenu => {
+                GameLoop::get_mut().quit_to_main_menu();
+                true
+            }
+        }
+    }
+}
+
+// ----------------------------------------------
+// MissionFailed
+// ----------------------------------------------
+
+// Prompt shown when a mission trigger fails the active mission.
+pub struct MissionFailed {
+    menu: UiMenuRcMut,
+}
+
+implement_dialog_menu! { MissionFailed, ["Mission Failed!"] }
+
+impl MissionFailed {
+    pub fn new(context: &mut GameUiContext) -> Self {
+        let buttons = make_dialog_button_widgets::<MissionFailedButtonKind, MISSION_FAILED_BUTTON_COUNT>(context);
+
+        Self {
+            menu: make_default_layout_dialog_menu(
+                context,
+                Self::KIND,
+                Self::TITLE,
+                DEFAULT_DIALOG_MENU_BUTTON_SPACING,
+                Some(buttons),
+            ),
+        }
+    }
+
+    fn on_retry_mission(context: &mut GameUiContext) -> bool {
+        match campaign::retry_mission() {
+            Some(map) => {
+                // Reloading the mission map rebuilds the session and resets the dialog stack.
+                super::load_mission_map(&map);
+                true
+            }
+            None => super::close_current(context),
+        }
+    }
+}
@@ -7,6 +7,7 @@ use engine::{
     ui::{
         self,
         UiFontScale,
+        UiInputEvent,
         UiStaticVar,
         sound::{self, UiButtonSoundsEnabled, UiSoundKey},
         widgets::*,
@@ -16,7 +17,12 @@ use enum_dispatch::enum_dispatch;
 use strum::{Display, EnumCount, EnumDiscriminants, EnumIter, IntoEnumIterator};
 
 use super::LARGE_HORIZONTAL_SEPARATOR_SPRITE;
-use crate::{menu::ButtonDef, ui_context::GameUiContext, campaign::config::MissionMap};
+use crate::{
+    i18n::tr,
+    menu::{ButtonDef, GameMenusInputArgs},
+    ui_context::GameUiContext,
+    campaign::config::MissionMap,
+};
 
 mod home;
 use home::*;
@@ -42,9 +48,18 @@ use settings::*;
 mod mission_complete;
 use mission_complete::*;
 
+mod mission_failed;
+use mission_failed::*;
+
 mod campaign_complete;
 use campaign_complete::*;
 
+mod campaign_missions;
+use campaign_missions::*;
+
+mod mission_briefing;
+use mission_briefing::*;
+
 // ----------------------------------------------
 // Macro: dialog_menu_factories
 // ----------------------------------------------
@@ -82,15 +97,20 @@ pub enum DialogMenuImpl {
     PopulationManagement,
     ResourcesManagement,
     FinancesManagement,
+    StatisticsManagement,
 
     // Settings menus:
     MainSettings,
     GameSettings,
     SoundSettings,
     GraphicsSettings,
+    KeyBindingsSettings,
 
     // Campaign progression menus:
+    CampaignMissions,
+    MissionBriefing,
     MissionComplete,
+    MissionFailed,
     CampaignComplete,
 }
 
@@ -108,13 +128,18 @@ const DIALOG_MENU_FACTORIES: [DialogMenuFactoryFn; DIALOG_MENU_COUNT] = dialog_m
     PopulationManagement,
     ResourcesManagement,
     FinancesManagement,
+    StatisticsManagement,
 
     MainSettings,
     GameSettings,
     SoundSettings,
     GraphicsSettings,
+    KeyBindingsSettings,
 
+    CampaignMissions,
+    MissionBriefing,
     MissionComplete,
+    MissionFailed,
     CampaignComplete,
 ];
 
@@ -142,6 +167,13 @@ pub fn reset() {
     DialogMenusSingleton::get_mut().reset();
 }
 
+// Next initialize() builds all dialog menus again.
+pub fn terminate() {
+    if DialogMenusSingleton::is_initialized() {
+        DialogMenusSingleton::terminate();
+    }
+}
+
 pub fn is_open(dialog_menu_kind: DialogMenuKind) -> bool {
     // Only the current stack top is considered "open" here.
     current().is_some_and(|dialog| dialog == dialog_menu_kind)
@@ -179,6 +211,14 @@ pub fn close_current(context: &mut GameUiContext) -> bool {
     DialogMenusSingleton::get_mut().close_current(context)
 }
 
+// Lets the current dialog consume input before the menus process it.
+pub fn handle_input(args: GameMenusInputArgs) -> UiInputEvent {
+    if let Some(key_bindings_settings) = current_as::<KeyBindingsSettings>() {
+        return key_bindings_settings.handle_input(args);
+    }
+    UiInputEvent::NotHandled
+}
+
 pub fn draw_current(context: &mut GameUiContext) {
     DialogMenusSingleton::get_mut().draw_current(context);
 }
@@ -582,7 +622,7 @@ fn make_default_layout_dialog_menu(
     let heading = UiMenuHeading::new(context, UiMenuHeadingParams {
         lines: heading_title
             .iter()
-            .map(|line| UiText::new(line.to_string(), DEFAULT_DIALOG_MENU_HEADING_FONT_SCALE))
+            .map(|line| UiText::new(tr!(line), DEFAULT_DIALOG_MENU_HEADING_FONT_SCALE))
             .collect(),
         separator: Some(LARGE_HORIZONTAL_SEPARATOR_SPRITE),
         margin_top: DEFAULT_DIALOG_MENU_HEADING_MARGINS.0,
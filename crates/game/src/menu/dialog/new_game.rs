@@ -1,7 +1,14 @@
 use common::Size;
+use engine::log;
+use strum::VariantArray;
 
 use super::*;
-use crate::{GameLoop, menu::TEXT_BUTTON_HOVERED_SPRITE, tile::sets::PresetTiles};
+use crate::{
+    GameLoop,
+    config::{Difficulty, GameConfigs, LoadMapSetting},
+    menu::TEXT_BUTTON_HOVERED_SPRITE,
+    tile::sets::PresetTiles,
+};
 
 // ----------------------------------------------
 // Constants
@@ -24,6 +31,7 @@ const TERRAIN_TILE_PRESETS: [PresetTiles; 3] = [
 pub struct NewGame {
     new_map_size: Size,
     terrain_tile_preset_index: usize,
+    difficulty: Difficulty,
     menu: UiMenuRcMut,
 }
 
@@ -78,6 +86,17 @@ impl NewGame {
             ..Default::default()
         });
 
+        let difficulty_dropdown = UiDropdown::with_strings(context, UiDropdownParams {
+            font_scale: DEFAULT_DIALOG_MENU_WIDGET_FONT_SCALE,
+            current_item: GameConfigs::get().sim.difficulty as usize,
+            items: Difficulty::VARIANTS.iter().map(|difficulty| tr!(&difficulty.to_string())).collect(),
+            on_selection_changed: UiDropdownSelectionChanged::with_fn(|dropdown, _context| {
+                let new_game_menu = super::find::<NewGame>();
+                new_game_menu.difficulty = Difficulty::VARIANTS[dropdown.current_selection_index()];
+            }),
+            ..Default::default()
+        });
+
         let mut labeled_widget_group = UiLabeledWidgetGroup::new(context, UiLabeledWidgetGroupParams {
             label_spacing: DEFAULT_DIALOG_MENU_WIDGET_LABEL_SPACING,
             widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING.y,
@@ -87,32 +106,30 @@ impl NewGame {
             margin_right: 40.0,
         });
 
-        labeled_widget_group.add_widget("New Map Width".into(), new_map_width_input);
-        labeled_widget_group.add_widget("New Map Height".into(), new_map_height_input);
-        labeled_widget_group.add_widget("Terrain Kind".into(), terrain_kind_dropdown);
+        labeled_widget_group.add_widget(tr!("New Map Width"), new_map_width_input);
+        labeled_widget_group.add_widget(tr!("New Map Height"), new_map_height_input);
+        labeled_widget_group.add_widget(tr!("Terrain Kind"), terrain_kind_dropdown);
+        labeled_widget_group.add_widget(tr!("Difficulty"), difficulty_dropdown);
 
         // -------------
         // Buttons:
         // -------------
 
         let start_new_game_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Start New Game".into(),
+            label: tr!("Start New Game"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_button, _context| {
                 let new_game_menu = super::find::<NewGame>();
-
-                let new_map_size = Some(new_game_menu.new_map_size);
-                let terrain_tile_preset = TERRAIN_TILE_PRESETS[new_game_menu.terrain_tile_preset_index];
-                let reset_map_with_tile_def = terrain_tile_preset.find_tile_def();
-
-                GameLoop::get_mut().reset_session(reset_map_with_tile_def, new_map_size);
+                if let Some(load_map_setting) = new_game_menu.load_map_setting() {
+                    GameLoop::get_mut().create_session(load_map_setting, new_game_menu.difficulty);
+                }
             }),
             ..Default::default()
         });
 
         let cancel_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Cancel".into(),
+            label: tr!("Cancel"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_button, context| {
@@ -153,6 +170,26 @@ impl NewGame {
         menu.add_widget(spacing);
         menu.add_widget(side_by_side_button_group);
 
-        Self { new_map_size: Size::new(MIN_MAP_SIZE, MIN_MAP_SIZE), terrain_tile_preset_index: 0, menu }
+        Self {
+            new_map_size: Size::new(MIN_MAP_SIZE, MIN_MAP_SIZE),
+            terrain_tile_preset_index: 0,
+            difficulty: GameConfigs::get().sim.difficulty,
+            menu,
+        }
+    }
+
+    fn load_map_setting(&self) -> Option<LoadMapSetting> {
+        let terrain_tile_preset = TERRAIN_TILE_PRESETS[self.terrain_tile_preset_index];
+
+        let Some(terrain_tile_def) = terrain_tile_preset.find_tile_def() else {
+            log::error!(log::channel!("session"), "New Game: Terrain tile '{terrain_tile_preset}' not found!");
+            return None;
+        };
+
+        Some(LoadMapSetting::EmptyMap {
+            size_in_cells: self.new_map_size,
+            terrain_tile_category: terrain_tile_preset.terrain_category().string.into(),
+            terrain_tile_name: terrain_tile_def.name.clone(),
+        })
     }
 }
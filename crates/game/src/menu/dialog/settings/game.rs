@@ -1,5 +1,14 @@
+use strum::VariantArray;
+
 use super::*;
-use crate::{GameLoop, config::GameConfigs};
+use crate::{
+    GameLoop,
+    config::GameConfigs,
+    i18n::{Language, Localization},
+};
+
+const MAP_TOOLTIP_DELAY_MIN_MS: u32 = 0;
+const MAP_TOOLTIP_DELAY_MAX_MS: u32 = 2000;
 
 // ----------------------------------------------
 // GameSettings
@@ -16,13 +25,37 @@ impl GameSettings {
     pub fn new(context: &mut GameUiContext) -> Self {
         let mut category = SettingsCategory::new();
 
+        // Language names are always shown untranslated.
+        let language_options: Vec<String> = Language::VARIANTS.iter().map(|language| language.to_string()).collect();
+
         category
+            .add_setting(SettingImpl::new(
+                "Language",
+                SettingsWidgetKind::Dropdown(language_options),
+                || Localization::get().language() as usize,
+                |selected_index: usize| {
+                    let language = Language::VARIANTS[selected_index];
+                    GameConfigs::get_mut().language = language;
+
+                    if Localization::get().language() != language {
+                        Localization::get_mut().set_language(language);
+                        // Deferred, menus can't be rebuilt from inside their own widget callbacks.
+                        GameLoop::get_mut().rebuild_menus();
+                    }
+                },
+            ))
             .add_setting(SettingImpl::new(
                 "Autosave",
                 SettingsWidgetKind::Checkbox,
                 || GameLoop::get().is_autosave_enabled(),
                 |enable| GameLoop::get_mut().enable_autosave(enable),
             ))
+            .add_setting(SettingImpl::new(
+                "Show Warnings",
+                SettingsWidgetKind::Checkbox,
+                || GameLoop::get().are_warnings_enabled(),
+                |enable| GameLoop::get_mut().enable_warnings(enable),
+            ))
             .add_setting(SettingImpl::new(
                 "Keyboard Shortcut Camera Zoom",
                 SettingsWidgetKind::Checkbox,
@@ -40,6 +73,24 @@ impl GameSettings {
                 SettingsWidgetKind::Checkbox,
                 || !GameConfigs::get().camera.disable_smooth_mouse_scroll_zoom,
                 |enable| GameConfigs::get_mut().camera.disable_smooth_mouse_scroll_zoom = !enable,
+            ))
+            .add_setting(SettingImpl::new(
+                "Trackpad Pinch Zoom & Pan",
+                SettingsWidgetKind::Checkbox,
+                || !GameConfigs::get().camera.disable_trackpad_gestures,
+                |enable| GameConfigs::get_mut().camera.disable_trackpad_gestures = !enable,
+            ))
+            .add_setting(SettingImpl::new(
+                "Map Tooltips",
+                SettingsWidgetKind::Checkbox,
+                || GameConfigs::get().tooltips.show_map_tooltips,
+                |enable| GameConfigs::get_mut().tooltips.show_map_tooltips = enable,
+            ))
+            .add_setting(SettingImpl::new(
+                "Map Tooltip Delay (ms)",
+                SettingsWidgetKind::SliderU32(MAP_TOOLTIP_DELAY_MIN_MS, MAP_TOOLTIP_DELAY_MAX_MS),
+                || (GameConfigs::get().tooltips.map_tooltip_delay_secs * 1000.0).round() as u32,
+                |delay_ms: u32| GameConfigs::get_mut().tooltips.map_tooltip_delay_secs = delay_ms as f32 / 1000.0,
             ));
 
         let menu = category.build_menu(
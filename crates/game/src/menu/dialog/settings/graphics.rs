@@ -1,9 +1,13 @@
-use engine::{Engine, render::texture::TextureFilter};
+use engine::{
+    Engine,
+    render::texture::TextureFilter,
+    ui::UiSystem,
+};
 use num_enum::TryFromPrimitive;
 use strum::VariantArray;
 
 use super::*;
-use crate::config::GameConfigs;
+use crate::{config::GameConfigs, tile::color_palette::ColorPalette};
 
 // ----------------------------------------------
 // GraphicsSettings
@@ -21,6 +25,7 @@ impl GraphicsSettings {
         let mut category = SettingsCategory::new();
 
         let texture_filter_options: Vec<String> = TextureFilter::VARIANTS.iter().map(|filter| filter.to_string()).collect();
+        let color_palette_options: Vec<String> = ColorPalette::VARIANTS.iter().map(|palette| palette.to_string()).collect();
 
         category
             .add_setting(SettingImpl::new(
@@ -52,6 +57,42 @@ impl GraphicsSettings {
                     tex_cache.change_texture_settings(texture_settings);
                     GameConfigs::get_mut().engine.texture_settings = texture_settings;
                 },
+            ))
+            .add_setting(SettingImpl::new(
+                "UI Scale %",
+                SettingsWidgetKind::SliderU32(
+                    (UiSystem::MIN_UI_SCALE * 100.0) as u32,
+                    (UiSystem::MAX_UI_SCALE * 100.0) as u32,
+                ),
+                || (Engine::get().ui_system().ui_scale() * 100.0).round() as u32,
+                |ui_scale_percent: u32| {
+                    let ui_scale = ui_scale_percent as f32 / 100.0;
+                    Engine::get().ui_system().set_ui_scale(ui_scale);
+                    GameConfigs::get_mut().engine.ui_scale = ui_scale;
+                },
+            ))
+            .add_setting(SettingImpl::new(
+                "Selection Outline",
+                SettingsWidgetKind::Checkbox,
+                || GameConfigs::get().engine.selection_outline,
+                |selection_outline| {
+                    // Disabling falls back to tinting selected objects (cheaper on weak GPUs).
+                    GameConfigs::get_mut().engine.selection_outline = selection_outline;
+                },
+            ))
+            .add_setting(SettingImpl::new(
+                "Color Palette",
+                SettingsWidgetKind::Dropdown(color_palette_options),
+                || GameConfigs::get().color_palette as usize,
+                |selected_index: usize| {
+                    GameConfigs::get_mut().color_palette = ColorPalette::VARIANTS[selected_index];
+                },
+            ))
+            .add_setting(SettingImpl::new(
+                "Hatch Patterns",
+                SettingsWidgetKind::Checkbox,
+                || GameConfigs::get().hatch_patterns,
+                |hatch_patterns| GameConfigs::get_mut().hatch_patterns = hatch_patterns,
             ));
 
         let menu = category.build_menu(
@@ -0,0 +1,228 @@
+use engine::app::input::{InputAction, InputBinding, InputBindingSlot, InputKey, InputModifiers, InputTrigger, MouseButton};
+
+use super::*;
+use crate::{
+    key_bindings::{GameAction, KeyBindings},
+    menu::GameMenusInputArgs,
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const UNBOUND_LABEL: &str = "---";
+const CAPTURING_LABEL: &str = "<Press a key>";
+
+const BINDING_SLOTS: [InputBindingSlot; 2] = [InputBindingSlot::Primary, InputBindingSlot::Alternate];
+
+// ----------------------------------------------
+// KeyBindingsSettings
+// ----------------------------------------------
+
+// Lists every GameAction with its primary and alternate bindings.
+// Clicking a binding waits for the next key (plus modifiers) or
+// mouse button press and binds it. Changes are saved immediately.
+pub struct KeyBindingsSettings {
+    menu: UiMenuRcMut,
+}
+
+implement_dialog_menu! { KeyBindingsSettings, ["Key Bindings"] }
+
+impl KeyBindingsSettings {
+    pub fn new(context: &mut GameUiContext) -> Self {
+        // -------------
+        // Menu:
+        // -------------
+
+        let mut menu = make_default_layout_dialog_menu(
+            context,
+            Self::KIND,
+            Self::TITLE,
+            DEFAULT_DIALOG_MENU_WIDGET_SPACING,
+            Option::<Vec<UiWidgetImpl>>::None,
+        );
+
+        // -------------
+        // Widgets:
+        // -------------
+
+        let mut labeled_widget_group = UiLabeledWidgetGroup::new(context, UiLabeledWidgetGroupParams {
+            label_spacing: DEFAULT_DIALOG_MENU_WIDGET_LABEL_SPACING,
+            widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING.y,
+            center_vertically: false,
+            center_horizontally: true,
+            ..Default::default()
+        });
+
+        // One row per action, in GameAction declaration order. See `refresh_labels()`.
+        for action in GameAction::iter() {
+            let mut slot_button_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+                widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING,
+                center_vertically: false,
+                center_horizontally: false,
+                stack_vertically: false,
+                ..Default::default()
+            });
+
+            for slot in BINDING_SLOTS {
+                let menu_weak_ref = menu.downgrade();
+                slot_button_group.add_widget(UiTextButton::new(context, UiTextButtonParams {
+                    label: Self::binding_label(action, slot),
+                    sounds_enabled: UiButtonSoundsEnabled::all(),
+                    on_pressed: UiTextButtonPressed::with_closure(move |_, _| {
+                        KeyBindings::get_mut().begin_capture(action, slot);
+                        Self::refresh_labels(&mut menu_weak_ref.upgrade().unwrap());
+                    }),
+                    ..Default::default()
+                }));
+            }
+
+            labeled_widget_group.add_widget(action.to_string(), slot_button_group);
+        }
+
+        // -------------
+        // Buttons:
+        // -------------
+
+        let reset_button_menu_weak_ref = menu.downgrade();
+        let reset_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Reset Defaults"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_closure(move |_, _| {
+                KeyBindings::get_mut().reset_to_defaults();
+                Self::save_key_bindings();
+                Self::refresh_labels(&mut reset_button_menu_weak_ref.upgrade().unwrap());
+            }),
+            ..Default::default()
+        });
+
+        let back_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Back ->"),
+            hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+            sounds_enabled: UiButtonSoundsEnabled::all(),
+            on_pressed: UiTextButtonPressed::with_fn(|_, context| {
+                super::close_current(ui::widgets::context_as_mut::<GameUiContext>(context));
+            }),
+            ..Default::default()
+        });
+
+        let mut side_by_side_button_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: DEFAULT_DIALOG_MENU_WIDGET_SPACING * 4.0,
+            center_vertically: false,
+            center_horizontally: true,
+            stack_vertically: false,
+            ..Default::default()
+        });
+
+        side_by_side_button_group.add_widget(reset_button);
+        side_by_side_button_group.add_widget(back_button);
+
+        // Show current bindings when the menu opens. Never leave a capture pending once closed.
+        menu.set_open_close_callback(UiMenuOpenClose::with_fn(|menu, _, is_open| {
+            KeyBindings::get_mut().cancel_capture();
+            if is_open {
+                Self::refresh_labels(menu);
+            }
+        }));
+
+        let spacing = UiSeparator::new(context, UiSeparatorParams {
+            thickness: Some(DEFAULT_DIALOG_MENU_WIDGET_SPACING.x),
+            ..Default::default()
+        });
+
+        menu.add_widget(labeled_widget_group);
+        menu.add_widget(spacing);
+        menu.add_widget(side_by_side_button_group);
+
+        Self { menu }
+    }
+
+    // Completes a pending rebind with the next key or mouse button press.
+    // The GameLoop routes all key input to the menus while a capture is in progress.
+    pub fn handle_input(&mut self, args: GameMenusInputArgs) -> UiInputEvent {
+        let key_bindings = KeyBindings::get_mut();
+        if !key_bindings.is_capturing() {
+            return UiInputEvent::NotHandled;
+        }
+
+        let binding = match args {
+            GameMenusInputArgs::Key { key, action, modifiers } => {
+                if action != InputAction::Press || Self::is_modifier_key(key) {
+                    return UiInputEvent::Handled; // Wait for a non-modifier key press.
+                }
+
+                if key_bindings.is_key_bound_to(GameAction::Cancel, key, modifiers) {
+                    key_bindings.cancel_capture();
+                    Self::refresh_labels(&mut self.menu);
+                    return UiInputEvent::Handled;
+                }
+
+                InputBinding::key_with_modifiers(key, modifiers & InputModifiers::BINDABLE)
+            }
+            GameMenusInputArgs::Mouse { button, action, modifiers } => {
+                // Left clicks still go to the UI so the menu buttons keep working.
+                if action != InputAction::Press || button == MouseButton::Left {
+                    return UiInputEvent::NotHandled;
+                }
+
+                InputBinding { trigger: InputTrigger::Mouse(button), modifiers: modifiers & InputModifiers::BINDABLE }
+            }
+            GameMenusInputArgs::Scroll { .. } => return UiInputEvent::NotHandled,
+        };
+
+        if let Some(action) = key_bindings.complete_capture(binding) {
+            log::info!(log::channel!("settings"), "Bound '{binding}' to {action}.");
+            Self::save_key_bindings();
+        }
+
+        Self::refresh_labels(&mut self.menu);
+        UiInputEvent::Handled
+    }
+
+    fn save_key_bindings() {
+        if KeyBindings::save() {
+            log::info!(log::channel!("settings"), "KeyBindings saved successfully.");
+        }
+    }
+
+    fn is_modifier_key(key: InputKey) -> bool {
+        matches!(
+            key,
+            InputKey::LeftShift
+                | InputKey::LeftControl
+                | InputKey::LeftAlt
+                | InputKey::LeftSuper
+                | InputKey::RightShift
+                | InputKey::RightControl
+                | InputKey::RightAlt
+                | InputKey::RightSuper
+        )
+    }
+
+    fn binding_label(action: GameAction, slot: InputBindingSlot) -> String {
+        let key_bindings = KeyBindings::get();
+
+        if key_bindings.capture_target() == Some((action, slot)) {
+            return CAPTURING_LABEL.into();
+        }
+
+        match key_bindings.bindings(action).get(slot) {
+            Some(binding) => binding.to_string(),
+            None => UNBOUND_LABEL.into(),
+        }
+    }
+
+    fn refresh_labels(menu: &mut UiMenu) {
+        let (_, labeled_widget_group) = menu.find_widget_of_type_mut::<UiLabeledWidgetGroup>().unwrap();
+
+        for ((_, widget), action) in labeled_widget_group.labels_and_widgets_mut().iter_mut().zip(GameAction::iter()) {
+            let slot_button_group = widget.as_any_mut().downcast_mut::<UiWidgetGroup>().unwrap();
+
+            for (button, slot) in slot_button_group.widgets_mut().iter_mut().zip(BINDING_SLOTS) {
+                let button = button.as_any_mut().downcast_mut::<UiTextButton>().unwrap();
+                button.set_label(Self::binding_label(action, slot));
+            }
+        }
+    }
+}
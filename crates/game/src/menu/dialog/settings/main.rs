@@ -20,6 +20,9 @@ enum MainSettingsButtonKind {
     #[strum(props(Label = "Graphics"))]
     Graphics,
 
+    #[strum(props(Label = "Key Bindings"))]
+    KeyBindings,
+
     #[strum(props(Label = "Back ->"))]
     Back,
 }
@@ -28,10 +31,11 @@ impl ButtonDef for MainSettingsButtonKind {
     fn on_pressed(self, context: &mut GameUiContext) -> bool {
         const CLOSE_ALL_OTHERS: bool = false;
         match self {
-            Self::Game     => super::open(DialogMenuKind::GameSettings, CLOSE_ALL_OTHERS, context),
-            Self::Sound    => super::open(DialogMenuKind::SoundSettings, CLOSE_ALL_OTHERS, context),
-            Self::Graphics => super::open(DialogMenuKind::GraphicsSettings, CLOSE_ALL_OTHERS, context),
-            Self::Back     => super::close_current(context),
+            Self::Game        => super::open(DialogMenuKind::GameSettings, CLOSE_ALL_OTHERS, context),
+            Self::Sound       => super::open(DialogMenuKind::SoundSettings, CLOSE_ALL_OTHERS, context),
+            Self::Graphics    => super::open(DialogMenuKind::GraphicsSettings, CLOSE_ALL_OTHERS, context),
+            Self::KeyBindings => super::open(DialogMenuKind::KeyBindingsSettings, CLOSE_ALL_OTHERS, context),
+            Self::Back        => super::close_current(context),
         }
     }
 }
@@ -4,7 +4,7 @@ use common::mem::{RcMut, WeakMut};
 use engine::log;
 
 use super::*;
-use crate::{config::GameConfigs, menu::TEXT_BUTTON_HOVERED_SPRITE};
+use crate::{config::GameConfigs, menu::TEXT_BUTTON_HOVERED_SPRITE, user_settings::UserSettings};
 
 mod main;
 pub use main::MainSettings;
@@ -18,6 +18,9 @@ pub use sound::SoundSettings;
 mod graphics;
 pub use graphics::GraphicsSettings;
 
+mod key_bindings;
+pub use key_bindings::KeyBindingsSettings;
+
 // ----------------------------------------------
 // SettingsWidgetKind
 // ----------------------------------------------
@@ -163,7 +166,7 @@ where
     }
 
     fn widget_label(&self) -> String {
-        self.widget_label.into()
+        tr!(self.widget_label)
     }
 
     fn create_widget(&self, this: WeakMut<dyn Setting>, context: &mut GameUiContext) -> UiWidgetImpl {
@@ -256,9 +259,8 @@ impl SettingsCategory {
             any_commited |= setting.commit();
         }
 
-        if any_commited {
-            GameConfigs::save();
-            log::info!(log::channel!("settings"), "GameConfigs saved successfully.");
+        if any_commited && UserSettings::save(GameConfigs::get()) {
+            log::info!(log::channel!("settings"), "User settings saved successfully.");
         }
     }
 
@@ -301,7 +303,7 @@ impl SettingsCategory {
 
         let ok_button_weak_ref = this.clone().into_not_mut();
         let ok_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Ok".into(),
+            label: tr!("Ok"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_closure(move |_, context| {
@@ -313,7 +315,7 @@ impl SettingsCategory {
         });
 
         let cancel_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Cancel".into(),
+            label: tr!("Cancel"),
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
             on_pressed: UiTextButtonPressed::with_fn(|_, context| {
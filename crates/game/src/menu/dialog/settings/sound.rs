@@ -1,4 +1,4 @@
-use engine::Engine;
+use engine::{Engine, sound::SoundBuses};
 
 use super::*;
 use crate::config::GameConfigs;
@@ -24,9 +24,29 @@ macro_rules! write_mater_volume_u32 {
     }};
 }
 
+macro_rules! read_bus_muted {
+    ($bus:ident) => {{
+        let sound_settings = Engine::get().sound_system().current_sound_settings();
+        sound_settings.is_bus_muted(SoundBuses::$bus)
+    }};
+}
+
+macro_rules! write_bus_muted {
+    ($bus:ident, $muted:ident) => {{
+        let sound_sys = Engine::get_mut().sound_system_mut();
+        let mut sound_settings = sound_sys.current_sound_settings();
+        sound_settings.set_bus_muted(SoundBuses::$bus, $muted);
+        sound_sys.change_sound_settings(sound_settings);
+        GameConfigs::get_mut().engine.sound_settings = sound_settings;
+    }};
+}
+
 const VOLUME_MIN: u32 = 0;
 const VOLUME_MAX: u32 = 100;
 
+// Solo dropdown entries. Index 0 is no solo, then SoundBuses::CATEGORIES in order.
+const SOLO_BUS_OPTIONS: [&str; 6] = ["None", "Music", "SFX", "Ambience", "Narration", "UI"];
+
 // ----------------------------------------------
 // SoundSettings
 // ----------------------------------------------
@@ -42,7 +62,42 @@ impl SoundSettings {
     pub fn new(context: &mut GameUiContext) -> Self {
         let mut category = SettingsCategory::new();
 
+        // Output device dropdown entries. Index 0 is the system default, then the devices found.
+        let output_devices = Engine::get().sound_system().output_devices();
+        let mut output_device_options = vec!["System Default".to_string()];
+        output_device_options.extend(output_devices.iter().cloned());
+
+        category.add_setting(SettingImpl::new(
+            "Output Device",
+            SettingsWidgetKind::Dropdown(output_device_options),
+            {
+                let output_devices = output_devices.clone();
+                move || {
+                    let current_device = Engine::get().sound_system().current_output_device();
+                    output_devices
+                        .iter()
+                        .position(|device| Some(device.as_str()) == current_device)
+                        .map_or(0, |index| index + 1)
+                }
+            },
+            move |selected_index: usize| {
+                let output_device = match selected_index {
+                    0 => None,
+                    index => output_devices.get(index - 1).cloned(),
+                };
+                if Engine::get_mut().sound_system_mut().set_output_device(output_device.as_deref()) {
+                    GameConfigs::get_mut().engine.sound_output_device = output_device;
+                }
+            },
+        ));
+
         category
+            .add_setting(SettingImpl::new(
+                "Master Volume",
+                SettingsWidgetKind::SliderU32(VOLUME_MIN, VOLUME_MAX),
+                || read_master_volume_u32!(master_bus_volume),
+                |volume| write_mater_volume_u32!(master_bus_volume, volume),
+            ))
             .add_setting(SettingImpl::new(
                 "SFX Volume",
                 SettingsWidgetKind::SliderU32(VOLUME_MIN, VOLUME_MAX),
@@ -72,6 +127,81 @@ impl SoundSettings {
                 SettingsWidgetKind::SliderU32(VOLUME_MIN, VOLUME_MAX),
                 || read_master_volume_u32!(spatial_master_volume),
                 |volume| write_mater_volume_u32!(spatial_master_volume, volume),
+            ))
+            .add_setting(SettingImpl::new(
+                "UI Volume",
+                SettingsWidgetKind::SliderU32(VOLUME_MIN, VOLUME_MAX),
+                || read_master_volume_u32!(ui_master_volume),
+                |volume| write_mater_volume_u32!(ui_master_volume, volume),
+            ))
+            .add_setting(SettingImpl::new(
+                "Lower Music During Narration %",
+                SettingsWidgetKind::SliderU32(VOLUME_MIN, VOLUME_MAX),
+                || read_master_volume_u32!(narration_ducking_amount),
+                |amount| write_mater_volume_u32!(narration_ducking_amount, amount),
+            ))
+            .add_setting(SettingImpl::new(
+                "Mute All",
+                SettingsWidgetKind::Checkbox,
+                || read_bus_muted!(Master),
+                |muted| write_bus_muted!(Master, muted),
+            ))
+            .add_setting(SettingImpl::new(
+                "Mute Music",
+                SettingsWidgetKind::Checkbox,
+                || read_bus_muted!(Music),
+                |muted| write_bus_muted!(Music, muted),
+            ))
+            .add_setting(SettingImpl::new(
+                "Mute SFX",
+                SettingsWidgetKind::Checkbox,
+                || read_bus_muted!(Sfx),
+                |muted| write_bus_muted!(Sfx, muted),
+            ))
+            .add_setting(SettingImpl::new(
+                "Mute Ambience",
+                SettingsWidgetKind::Checkbox,
+                || read_bus_muted!(Ambience),
+                |muted| write_bus_muted!(Ambience, muted),
+            ))
+            .add_setting(SettingImpl::new(
+                "Mute Narration",
+                SettingsWidgetKind::Checkbox,
+                || read_bus_muted!(Narration),
+                |muted| write_bus_muted!(Narration, muted),
+            ))
+            .add_setting(SettingImpl::new(
+                "Mute UI",
+                SettingsWidgetKind::Checkbox,
+                || read_bus_muted!(Ui),
+                |muted| write_bus_muted!(Ui, muted),
+            ))
+            .add_setting(SettingImpl::new(
+                "Solo",
+                SettingsWidgetKind::Dropdown(SOLO_BUS_OPTIONS.iter().map(|option| option.to_string()).collect()),
+                || {
+                    let sound_settings = Engine::get().sound_system().current_sound_settings();
+                    SoundBuses::CATEGORIES
+                        .iter()
+                        .position(|bus| sound_settings.is_bus_soloed(*bus))
+                        .map_or(0, |index| index + 1)
+                },
+                |selected_index: usize| {
+                    let sound_sys = Engine::get_mut().sound_system_mut();
+                    let mut sound_settings = sound_sys.current_sound_settings();
+                    sound_settings.soloed_buses = match selected_index {
+                        0 => SoundBuses::empty(),
+                        index => SoundBuses::CATEGORIES[index - 1],
+                    };
+                    sound_sys.change_sound_settings(sound_settings);
+                    GameConfigs::get_mut().engine.sound_settings = sound_settings;
+                },
+            ))
+            .add_setting(SettingImpl::new(
+                "Show Narration Captions",
+                SettingsWidgetKind::Checkbox,
+                || GameConfigs::get().show_captions,
+                |show_captions| GameConfigs::get_mut().show_captions = show_captions,
             ));
 
         let menu = category.build_menu(
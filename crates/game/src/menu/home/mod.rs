@@ -2,7 +2,7 @@ use std::any::Any;
 
 use common::{coords::CellRange, format_fixed_string, time::Seconds};
 use engine::{
-    app::input::{InputAction, InputKey},
+    app::input::InputAction,
     file_sys::paths::AssetPath,
     ui::{
         UiInputEvent,
@@ -21,6 +21,7 @@ use super::{
     dialog::{self, DialogMenuKind},
 };
 use crate::{
+    key_bindings::{GameAction, KeyBindings},
     save_context::{Load, PreLoadContext, Save},
     tile::rendering::TileMapRenderFlags,
     ui_context::GameUiContext,
@@ -112,9 +113,13 @@ impl GameMenusSystem for HomeMenus {
     fn begin_frame(&mut self, _context: &mut GameUiContext) {}
 
     fn handle_input(&mut self, context: &mut GameUiContext, args: GameMenusInputArgs) -> UiInputEvent {
-        if let GameMenusInputArgs::Key { key, action, .. } = args {
+        if dialog::handle_input(args).is_handled() {
+            return UiInputEvent::Handled;
+        }
+
+        if let GameMenusInputArgs::Key { key, action, modifiers } = args {
             // [ESCAPE]: Close child dialog menu.
-            if key == InputKey::Escape && action == InputAction::Press {
+            if action == InputAction::Press && KeyBindings::get().is_key_bound_to(GameAction::Cancel, key, modifiers) {
                 // Close if we're not already at the Main Home Menu.
                 if dialog::current().is_some_and(|dialog| dialog != DialogMenuKind::Home) {
                     if dialog::close_current(context) {
@@ -2,6 +2,7 @@ use std::rc::Rc;
 
 use arrayvec::ArrayVec;
 use common::{
+    Color,
     Vec2,
     mem::{RcMut, WeakMut, WeakRef},
     time::Seconds,
@@ -14,6 +15,7 @@ use num_enum::TryFromPrimitive;
 use strum::{Display, EnumCount, EnumIter, EnumProperty, IntoEnumIterator};
 
 use crate::{
+    i18n::tr,
     menu::{
         ButtonDef,
         SMALL_VERTICAL_SEPARATOR_SPRITE,
@@ -21,6 +23,7 @@ use crate::{
         TOOLTIP_FONT_SCALE,
         dialog::{self, DialogMenuKind},
     },
+    tile::{color_palette::ColorPalette, heatmap::HeatmapOverlays},
     ui_context::GameUiContext,
 };
 
@@ -65,6 +68,7 @@ enum MenuBarKind {
     Top,
     Left,
     SpeedControls,
+    Overlays,
 }
 
 impl MenuBarKind {
@@ -74,6 +78,7 @@ impl MenuBarKind {
                 Self::Top => TopBar::new(context),
                 Self::Left => LeftBar::new(context),
                 Self::SpeedControls => SpeedControlsBar::new(context),
+                Self::Overlays => OverlaysBar::new(context),
             }
         };
         RcMut::from(rc)
@@ -587,3 +592,136 @@ impl std::fmt::Display for SimState {
         }
     }
 }
+
+// ----------------------------------------------
+// OverlaysBar
+// ----------------------------------------------
+
+const OVERLAYS_BAR_SPACING: Vec2 = Vec2::new(4.0, 4.0);
+const OVERLAYS_LEGEND_SIZE: Vec2 = Vec2::new(80.0, 12.0);
+const OVERLAYS_LEGEND_COLOR_STOPS: usize = 8;
+
+// Toggles the tile map heatmap overlays and shows a legend for the active one.
+struct OverlaysBar {
+    current_layer: Option<&'static str>,
+    current_palette: ColorPalette,
+    menu: UiMenuRcMut,
+}
+
+impl MenuBar for OverlaysBar {
+    fn draw(&mut self, context: &mut GameUiContext) {
+        let active_layer = HeatmapOverlays::get().active_layer_name();
+        let palette = ColorPalette::current();
+
+        if self.current_layer != active_layer || self.current_palette != palette {
+            self.current_palette = palette;
+            self.update_active_layer(context, active_layer);
+        }
+
+        self.menu.draw(context);
+    }
+}
+
+impl OverlaysBar {
+    fn new(context: &mut GameUiContext) -> Rc<Self> {
+        let mut group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+            widget_spacing: OVERLAYS_BAR_SPACING,
+            center_horizontally: false, // Let content float left.
+            stack_vertically: false,    // Layout widgets side-by-side.
+            ..Default::default()
+        });
+
+        let overlay_tooltip = UiTooltipText::new(context, UiTooltipTextParams {
+            text: tr!("Cycle map overlay"),
+            font_scale: TOOLTIP_FONT_SCALE,
+            background: Some(TOOLTIP_BACKGROUND_SPRITE),
+        });
+
+        let overlay_button = UiTextButton::new(context, UiTextButtonParams {
+            label: tr!("Overlay"),
+            tooltip: Some(overlay_tooltip),
+            size: UiTextButtonSize::Small,
+            sounds_enabled: UiButtonSoundsEnabled::Pressed,
+            on_pressed: UiTextButtonPressed::with_fn(|_, _| {
+                HeatmapOverlays::get_mut().cycle_active_layer();
+            }),
+            ..Default::default()
+        });
+
+        group.add_widget(overlay_button);
+
+        const SEPARATOR_THICKNESS: f32 = 8.0;
+        let separator = UiSeparator::new(context, UiSeparatorParams {
+            separator: Some(SMALL_VERTICAL_SEPARATOR_SPRITE),
+            thickness: Some(SEPARATOR_THICKNESS),
+            vertical: true,
+            ..Default::default()
+        });
+
+        group.add_widget(separator);
+
+        let (label_text, label_size) = Self::layer_label_and_size(context, None);
+        let layer_label = UiSizedTextLabel::new(context, UiSizedTextLabelParams {
+            font_scale: TOOLTIP_FONT_SCALE,
+            label: label_text,
+            size: label_size,
+        });
+
+        group.add_widget(layer_label);
+
+        // Legend: low values on the left, high on the right.
+        let legend = UiColorRamp::new(context, UiColorRampParams {
+            colors: Self::legend_colors(),
+            size: OVERLAYS_LEGEND_SIZE,
+        });
+
+        group.add_widget(legend);
+
+        let mut menu = UiMenu::new(context, UiMenuParams {
+            label: Some("OverlaysBar".into()),
+            flags: UiMenuFlags::IsOpen | UiMenuFlags::AlignRight,
+            widget_spacing: Some(OVERLAYS_BAR_SPACING),
+            background: Some(PathRef::from_str("misc/wide_page_bg.png")),
+            ..Default::default()
+        });
+
+        menu.add_widget(group);
+
+        Rc::new(Self { current_layer: None, current_palette: ColorPalette::current(), menu })
+    }
+
+    fn update_active_layer(&mut self, context: &GameUiContext, active_layer: Option<&'static str>) {
+        self.current_layer = active_layer;
+
+        let (_, group) = self.menu.find_widget_of_type_mut::<UiWidgetGroup>().unwrap();
+
+        let (label_text, label_size) = Self::layer_label_and_size(context, active_layer);
+        let (_, label) = group.find_widget_of_type_mut::<UiSizedTextLabel>().unwrap();
+        label.set_label(label_text);
+        label.set_size(label_size);
+
+        let (_, legend) = group.find_widget_of_type_mut::<UiColorRamp>().unwrap();
+        legend.set_colors(Self::legend_colors());
+    }
+
+    fn layer_label_and_size(context: &GameUiContext, active_layer: Option<&str>) -> (String, Vec2) {
+        let label = active_layer.unwrap_or("None").to_string();
+
+        let mut size = context.calc_text_size(TOOLTIP_FONT_SCALE, &label);
+        size += Vec2::new(10.0, 5.0); // explicit padding.
+
+        (label, size)
+    }
+
+    // Empty when no overlay is active, which hides the legend strip.
+    fn legend_colors() -> Vec<Color> {
+        let Some(layer) = HeatmapOverlays::get().active_layer() else {
+            return Vec::new();
+        };
+
+        let palette = ColorPalette::current();
+        (0..OVERLAYS_LEGEND_COLOR_STOPS)
+            .map(|stop| layer.gradient.sample(palette, stop as f32 / (OVERLAYS_LEGEND_COLOR_STOPS - 1) as f32))
+            .collect()
+    }
+}
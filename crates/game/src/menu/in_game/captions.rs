@@ -0,0 +1,71 @@
+use common::Vec2;
+use engine::{Engine, file_sys::paths::PathRef, ui::widgets::*};
+
+use crate::{config::GameConfigs, i18n, menu::TOOLTIP_FONT_SCALE, ui_context::GameUiContext};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const CAPTIONS_PANEL_BOTTOM_MARGIN: f32 = 80.0; // Clear of the bottom bar.
+const CAPTIONS_LABEL_PADDING: Vec2 = Vec2::new(20.0, 10.0);
+
+// ----------------------------------------------
+// CaptionsPanel
+// ----------------------------------------------
+
+// HUD caption for the narration line playing, centered at the bottom of the screen.
+// Hidden when captions are disabled in the settings or between lines.
+pub struct CaptionsPanel {
+    caption: Option<String>,
+    menu: Option<UiMenuRcMut>, // None while there's no caption to show.
+}
+
+impl CaptionsPanel {
+    pub fn new() -> Self {
+        Self { caption: None, menu: None }
+    }
+
+    pub fn draw(&mut self, context: &mut GameUiContext) {
+        let caption = if GameConfigs::get().show_captions {
+            Engine::get().sound_system().current_caption().map(i18n::translate)
+        } else {
+            None
+        };
+
+        // Only rebuild the panel when the caption line changes.
+        if caption != self.caption {
+            self.menu = caption.as_deref().map(|text| Self::build_menu(context, text));
+            self.caption = caption;
+        }
+
+        if let Some(menu) = &mut self.menu {
+            menu.draw(context);
+        }
+    }
+
+    fn build_menu(context: &mut GameUiContext, text: &str) -> UiMenuRcMut {
+        let mut menu = UiMenu::new(context, UiMenuParams {
+            label: Some("CaptionsPanel".into()),
+            flags: UiMenuFlags::IsOpen,
+            position: UiMenuPosition::Callback(UiMenuCalcPosition::with_closure(|menu, context| {
+                let menu_size = menu.measure(context);
+                let viewport_size = context.viewport_size().to_vec2();
+                let x = (viewport_size.x - menu_size.x) * 0.5;
+                let y = viewport_size.y - menu_size.y - CAPTIONS_PANEL_BOTTOM_MARGIN;
+                Vec2::new(x, y)
+            })),
+            background: Some(PathRef::from_str("misc/tall_page_bg.png")),
+            ..Default::default()
+        });
+
+        let label = UiSizedTextLabel::new(context, UiSizedTextLabelParams {
+            font_scale: TOOLTIP_FONT_SCALE,
+            label: text.into(),
+            size: context.calc_text_size(TOOLTIP_FONT_SCALE, text) + CAPTIONS_LABEL_PADDING,
+        });
+
+        menu.add_widget(label);
+        menu
+    }
+}
@@ -4,12 +4,17 @@ use common::{
 };
 use engine::ui::{
     self,
+    sound::UiButtonSoundsEnabled,
     text::UiTextCategory,
-    widgets::{UiMenu, UiWidget},
+    widgets::{UiMenu, UiTextButton, UiTextButtonParams, UiTextButtonPressed, UiTextButtonSize, UiWidget},
 };
+use strum::VariantArray;
 
 use crate::{
-    menu::TileInspector,
+    config::GameConfigs,
+    i18n::tr,
+    menu::{TEXT_BUTTON_HOVERED_SPRITE, TileInspector},
+    pathfind::{self, Path},
     tile::{Tile, TileKind},
     ui_context::GameUiContext,
     sim::resources::{ResourceKind, StockItem},
@@ -160,51 +165,165 @@ fn find_unit_dialog(unit_tile: &Tile) -> &'static str {
     ui::text::find_str(UiTextCategory::UnitDialog, unit_tile.tile_def().hash).unwrap_or("")
 }
 
+// Buttons added to an inspector's tab or action rows.
+fn new_inspector_button<F>(context: &mut GameUiContext, label: String, on_pressed: F) -> UiTextButton
+where
+    F: Fn(&mut GameUiContext) + 'static,
+{
+    UiTextButton::new(context, UiTextButtonParams {
+        label,
+        size: UiTextButtonSize::Normal,
+        hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+        sounds_enabled: UiButtonSoundsEnabled::all(),
+        on_pressed: UiTextButtonPressed::with_closure(move |_, context| {
+            on_pressed(ui::widgets::context_as_mut::<GameUiContext>(context));
+        }),
+        ..Default::default()
+    })
+}
+
 // ----------------------------------------------
 // UnitInspector
 // ----------------------------------------------
 
+// Index of the UnitInspector path display action button.
+const SHOW_PATH_ACTION: usize = 0;
+
 struct UnitInspector {
     renderer: InspectorMenuRenderer,
+
+    // Debug path display (see DebugConfigs::enable_unit_path_display).
+    show_path: bool,
+    highlighted_path: Path,
 }
 
 impl GameObjectInspector for UnitInspector {
     fn update_selection(&mut self, context: &mut GameUiContext) {
+        self.clear_path_highlight(context);
+
+        let task_manager = context.sim.task_manager();
         let selected_tile = context.topmost_selected_tile().unwrap();
 
         if let Some(unit) = context.world.find_unit_for_tile(selected_tile) {
             self.renderer.set_icon(context, selected_tile.icon_sprite(), selected_tile.kind());
             self.renderer.set_title(unit.name());
             self.renderer.set_body_text(find_unit_dialog(selected_tile));
-            self.set_inventory(unit.peek_inventory());
+
+            let home_building = unit
+                .home_building(task_manager)
+                .and_then(|building| context.world.find_building(building.kind, building.id));
+
+            let mut headings = InspectorMenuHeadings::new();
+
+            match home_building {
+                Some(building) => add_heading!(&mut headings, "Home", "{} @{}", building.name(), building.base_cell()),
+                None => add_heading!(&mut headings, "Home", "{}", tr!("None")),
+            }
+
+            match unit.peek_inventory() {
+                Some(item) => add_heading!(&mut headings, "Carrying", "{} {}", item.count, item.kind),
+                None => add_heading!(&mut headings, "Carrying", "{}", tr!("Nothing")),
+            }
+
+            add_heading!(&mut headings, "Task", "{}", tr!(unit.current_task_description(task_manager)));
+
+            if unit.is_combatant() {
+                add_heading!(&mut headings, "Health", "{}%", (unit.health_ratio() * 100.0).round() as u32);
+            }
+
+            if unit.can_take_orders(task_manager) {
+                add_heading!(&mut headings, "Orders", "{}", tr!("Right-click a tile to go there"));
+            }
+
+            self.renderer.set_headings(&headings);
+
+            let path_display_enabled = GameConfigs::get().debug.enable_unit_path_display;
+            let has_path = !unit.remaining_path().is_empty();
+
+            if self.show_path && path_display_enabled && has_path {
+                self.highlighted_path.extend_from_slice(unit.remaining_path());
+                pathfind::highlight_path_tiles(context.tile_map, &self.highlighted_path);
+            }
+
+            let show_path_button = self.renderer.action_button_mut(SHOW_PATH_ACTION);
+            show_path_button.enable(path_display_enabled && has_path);
+            show_path_button.set_label(if self.show_path { tr!("Hide Path") } else { tr!("Show Path") });
         }
     }
 
     fn menu(&mut self) -> &mut UiMenu {
         self.renderer.menu()
     }
+
+    fn close(&mut self, context: &mut GameUiContext) {
+        self.clear_path_highlight(context);
+        self.show_path = false;
+        self.menu().close(context);
+    }
 }
 
 impl UnitInspector {
     fn new(context: &mut GameUiContext, tile_inspector_menu_weak_ref: &TileInspectorMenuWeakMut) -> Self {
-        Self { renderer: InspectorMenuRenderer::new(context, tile_inspector_menu_weak_ref, stringify!(UnitInspector)) }
+        let show_path_inspector_menu_weak_ref = tile_inspector_menu_weak_ref.clone();
+        let show_path_button = new_inspector_button(context, tr!("Show Path"), move |context| {
+            let mut inspector_menu = show_path_inspector_menu_weak_ref.upgrade().unwrap();
+            let unit_inspector = &mut inspector_menu.unit_inspector;
+            unit_inspector.show_path = !unit_inspector.show_path;
+            unit_inspector.update_selection(context);
+        });
+
+        Self {
+            renderer: InspectorMenuRenderer::with_tabs_and_actions(
+                context,
+                tile_inspector_menu_weak_ref,
+                stringify!(UnitInspector),
+                Vec::new(),
+                vec![show_path_button],
+            ),
+            show_path: false,
+            highlighted_path: Path::new(),
+        }
     }
 
-    fn set_inventory(&mut self, inventory: Option<StockItem>) {
-        if let Some(item) = inventory {
-            self.renderer.set_heading_pairs(&[(item.kind, item.count)]);
-        } else {
-            self.renderer.clear_headings();
+    fn clear_path_highlight(&mut self, context: &mut GameUiContext) {
+        pathfind::clear_path_highlight(context.tile_map, &self.highlighted_path);
+        self.highlighted_path.clear();
+    }
+}
+
+// ----------------------------------------------
+// BuildingInspectorTab
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Default, PartialEq, Eq, VariantArray)]
+enum BuildingInspectorTab {
+    #[default]
+    Production,
+    Workers,
+    Storage,
+}
+
+impl BuildingInspectorTab {
+    fn label(self) -> String {
+        match self {
+            Self::Production => tr!("Production"),
+            Self::Workers    => tr!("Workers"),
+            Self::Storage    => tr!("Storage"),
         }
     }
 }
 
+// Indices of the BuildingInspector action buttons.
+const PAUSE_PRODUCTION_ACTION: usize = 0;
+const PRIORITIZE_LABOR_ACTION: usize = 1;
+
 // ----------------------------------------------
 // BuildingInspector
 // ----------------------------------------------
 
 struct BuildingInspector {
     renderer: InspectorMenuRenderer,
+    current_tab: BuildingInspectorTab,
 }
 
 impl GameObjectInspector for BuildingInspector {
@@ -217,8 +336,18 @@ impl GameObjectInspector for BuildingInspector {
             self.renderer.set_title(building.name());
             self.set_population_and_workers(building);
 
-            let building_ctx = building.new_context(&sim_context);
-            self.set_stats_info(&building_ctx, building);
+            let body = match self.current_tab {
+                BuildingInspectorTab::Production => {
+                    let building_ctx = building.new_context(&sim_context);
+                    Self::gather_production_info(&building_ctx, building)
+                }
+                BuildingInspectorTab::Workers => Self::gather_workers_info(building),
+                BuildingInspectorTab::Storage => Self::gather_storage_info(building),
+            };
+
+            self.renderer.set_body(&body);
+            self.renderer.set_current_tab(self.current_tab as usize);
+            self.set_actions(building);
         }
     }
 
@@ -229,65 +358,109 @@ impl GameObjectInspector for BuildingInspector {
 
 impl BuildingInspector {
     fn new(context: &mut GameUiContext, tile_inspector_menu_weak_ref: &TileInspectorMenuWeakMut) -> Self {
-        Self { renderer: InspectorMenuRenderer::new(context, tile_inspector_menu_weak_ref, stringify!(BuildingInspector)) }
-    }
-
-    fn set_population_and_workers(&mut self, building: &Building) {
-        let mut headings = InspectorMenuHeadings::new();
-
-        // Population + Workers: Household.
-        if let Some(population) = building.population() {
-            add_heading!(&mut headings, "Residents", "{}/{}", population.count(), population.max());
-
-            if let Some(workers) = building.workers() {
-                let household = workers.as_household_worker_pool().expect("Expected Household!");
-                let total_workers = household.total_workers();
-
-                add_heading!(&mut headings, "Workers Available", "{}", total_workers);
-                if total_workers != 0 {
-                    add_heading!(&mut headings, "Workers Employed", "{}", household.employed_count());
+        let tab_buttons = BuildingInspectorTab::VARIANTS
+            .iter()
+            .map(|&tab| {
+                let inspector_menu_weak_ref = tile_inspector_menu_weak_ref.clone();
+                new_inspector_button(context, tab.label(), move |context| {
+                    let mut inspector_menu = inspector_menu_weak_ref.upgrade().unwrap();
+                    inspector_menu.building_inspector.current_tab = tab;
+                    inspector_menu.building_inspector.update_selection(context);
+                })
+            })
+            .collect();
+
+        // Labels are refreshed for the selected building by `set_actions()`.
+        let pause_inspector_menu_weak_ref = tile_inspector_menu_weak_ref.clone();
+        let pause_button = new_inspector_button(context, tr!("Pause Production"), move |context| {
+            if let Some(tile) = context.tile_map.topmost_selected_tile(context.tile_selection) {
+                if let Some(building) = context.world.find_building_for_tile_mut(tile) {
+                    if building.can_pause_production() {
+                        building.set_production_paused(!building.is_production_paused());
+                    }
                 }
             }
 
-            let house = building.as_house();
-            let tax_generated = house.tax_generated();
+            let mut inspector_menu = pause_inspector_menu_weak_ref.upgrade().unwrap();
+            inspector_menu.building_inspector.update_selection(context);
+        });
 
-            if tax_generated != 0 {
-                add_heading!(&mut headings, "Tax", "Generated {} / Available {}", tax_generated, house.tax_available());
-            } else {
-                add_heading!(&mut headings, "Tax", "No Income Generated");
-            }
-        // Just Workers: Employer Building.
-        } else if let Some(workers) = building.workers() {
-            let employer = workers.as_employer().expect("Expected Employer Building!");
-            if employer.min_employees() != 0 {
-                add_heading!(&mut headings, "Workers", "{}/{}", employer.employee_count(), employer.max_employees());
-                add_heading!(&mut headings, "Min Workers Required", "{}", employer.min_employees());
+        let prioritize_inspector_menu_weak_ref = tile_inspector_menu_weak_ref.clone();
+        let prioritize_button = new_inspector_button(context, tr!("Prioritize Labor"), move |context| {
+            if let Some(tile) = context.tile_map.topmost_selected_tile(context.tile_selection) {
+                if let Some(building) = context.world.find_building_for_tile_mut(tile) {
+                    if building.is_employer() {
+                        building.set_labor_prioritized(!building.is_labor_prioritized());
+                    }
+                }
             }
-        }
 
-        self.renderer.set_headings(&headings);
+            let mut inspector_menu = prioritize_inspector_menu_weak_ref.upgrade().unwrap();
+            inspector_menu.building_inspector.update_selection(context);
+        });
+
+        // Same order as the PAUSE_PRODUCTION_ACTION / PRIORITIZE_LABOR_ACTION indices.
+        let action_buttons = vec![pause_button, prioritize_button];
+
+        Self {
+            renderer: InspectorMenuRenderer::with_tabs_and_actions(
+                context,
+                tile_inspector_menu_weak_ref,
+                stringify!(BuildingInspector),
+                tab_buttons,
+                action_buttons,
+            ),
+            current_tab: BuildingInspectorTab::default(),
+        }
     }
 
-    fn set_stats_info(&mut self, building_ctx: &BuildingContext, building: &Building) {
-        let body = {
-            if building.is(BuildingKind::House) {
-                Self::gather_house_stats(building_ctx, building)
-            } else {
-                Self::gather_building_stats(building)
-            }
-        };
-        self.renderer.set_body(&body);
+    fn set_actions(&mut self, building: &Building) {
+        let pause_button = self.renderer.action_button_mut(PAUSE_PRODUCTION_ACTION);
+        pause_button.enable(building.can_pause_production());
+        pause_button.set_label(if building.is_production_paused() {
+            tr!("Resume Production")
+        } else {
+            tr!("Pause Production")
+        });
+
+        let prioritize_button = self.renderer.action_button_mut(PRIORITIZE_LABOR_ACTION);
+        prioritize_button.enable(building.is_employer());
+        prioritize_button.set_label(if building.is_labor_prioritized() {
+            tr!("Reset Labor Priority")
+        } else {
+            tr!("Prioritize Labor")
+        });
     }
 
     fn gather_house_stats(building_ctx: &BuildingContext, building: &Building) -> InspectorMenuBody {
         let mut body = InspectorMenuBody::new();
 
         let house = building.as_house();
+        let happiness = house.happiness();
+
+        let percent = |value: f32| (value * 100.0).round() as u32;
+        add_body_line!(&mut body, "Happiness: {}%", percent(happiness.score()));
+        add_body_line!(
+            &mut body,
+            "Needs {}% | Services {}% | Taxes {}% | Events {}%",
+            percent(happiness.needs),
+            percent(happiness.services),
+            percent(happiness.tax),
+            percent(happiness.events)
+        );
+
+        let (worst_factor, worst_value) = happiness.worst_factor();
+        if worst_value < 1.0 {
+            add_body_line!(&mut body, "Residents are most upset about: {}", worst_factor);
+        }
+
+        add_body_line!(&mut body, "Water Access: {}", house.service_coverage().water_access(building_ctx));
 
         if !house.level().is_max() {
             if !building.is_linked_to_road() {
                 add_body_line!(&mut body, "House lacks road access!");
+            } else if !building_ctx.is_road_connected_to_map_edge() {
+                add_body_line!(&mut body, "House road is not connected to the map edge!");
             } else if !house.is_upgrade_available(building_ctx) {
                 add_body_line!(&mut body, "House has no room to expand!");
             } else {
@@ -309,25 +482,28 @@ impl BuildingInspector {
             add_body_line!(&mut body, "This house is upgraded to its highest level!");
         }
 
-        let skip_empty = true;
-        let stock = building.stock();
-
-        if !stock.is_empty() {
-            let stock_items = Self::gather_stock_items(&stock, skip_empty);
-            if !stock_items.is_empty() {
-                add_body_line!(&mut body, "Resources stocked:");
-                body.append(&stock_items);
-            }
-        }
-
         body
     }
 
-    fn gather_building_stats(building: &Building) -> InspectorMenuBody {
+    fn gather_building_stats(building_ctx: &BuildingContext, building: &Building) -> InspectorMenuBody {
         let mut body = InspectorMenuBody::new();
 
+        if building.is_linked_to_road() && !building_ctx.is_road_connected_to_map_edge() {
+            add_body_line!(&mut body, "Warning: Building road is not connected to the map edge!");
+        }
+
         let is_operational = building.is_operational();
-        if !is_operational {
+        if let Some(construction) = building.construction() {
+            if !construction.has_all_materials() {
+                let progress = (construction.materials_progress() * 100.0).round() as u32;
+                add_body_line!(&mut body, "Under construction. Waiting on materials: {progress}% delivered.");
+                if construction.is_waiting_on_carrier() {
+                    add_body_line!(&mut body, "Carrier is out fetching construction materials.");
+                }
+            } else {
+                add_body_line!(&mut body, "Under construction. Done in {:.0}s.", construction.build_remaining_secs());
+            }
+        } else if !is_operational {
             let is_linked_to_road = building.is_linked_to_road();
 
             let has_min_required_workers = building.has_min_required_workers();
@@ -355,17 +531,105 @@ impl BuildingInspector {
             }
         }
 
-        let skip_empty = building.archetype_kind() == BuildingArchetypeKind::ServiceBuilding
+        if let Some(progress) = building.production_progress() {
+            if building.is_production_paused() {
+                add_body_line!(&mut body, "Production paused. Goods already produced are still delivered.");
+            } else {
+                add_body_line!(&mut body, "Production: {}%", (progress * 100.0).round() as u32);
+            }
+        }
+
+        if building.archetype_kind() == BuildingArchetypeKind::ProducerBuilding
+            && let Some(stage) = building.as_producer().farm_field_stage()
+        {
+            add_body_line!(&mut body, "Field: {stage}");
+        }
+
+        let damage = building.raid_damage(building_ctx.sim_ctx.world());
+        if damage > 0.0 && !building.hazards().is_destroyed() {
+            let building_health = GameConfigs::get().sim.building_health;
+            add_body_line!(&mut body, "Damaged by raiders: {}%", (damage / building_health * 100.0).round() as u32);
+        }
+
+        if building_ctx.is_inside_walls() {
+            add_body_line!(&mut body, "Protected by the city walls.");
+        }
+
+        if building.archetype_kind() == BuildingArchetypeKind::ServiceBuilding {
+            let service = building.as_service();
+
+            if !service.has_water_supply() {
+                add_body_line!(&mut body, "No water! Connect it to a reservoir with aqueducts.");
+            }
+
+            if service.has_patrol_unit() {
+                if service.is_waiting_on_patrol() {
+                    add_body_line!(&mut body, "Service patrol is out on its rounds.");
+                } else {
+                    add_body_line!(&mut body, "Service patrol is waiting to set out.");
+                }
+            }
+
+            if service.is_waiting_on_runner() {
+                add_body_line!(&mut body, "Runner is out fetching resources.");
+            }
+        }
+
+        body
+    }
+
+    fn gather_production_info(building_ctx: &BuildingContext, building: &Building) -> InspectorMenuBody {
+        if building.is(BuildingKind::House) {
+            Self::gather_house_stats(building_ctx, building)
+        } else {
+            Self::gather_building_stats(building_ctx, building)
+        }
+    }
+
+    fn gather_workers_info(building: &Building) -> InspectorMenuBody {
+        let mut body = InspectorMenuBody::new();
+
+        let Some(workers) = building.workers() else {
+            add_body_line!(&mut body, "This building has no workers.");
+            return body;
+        };
+
+        if let Some(employer) = workers.as_employer() {
+            add_body_line!(&mut body, "Workers: {}/{}", employer.employee_count(), employer.max_employees());
+            add_body_line!(&mut body, "Min Workers Required: {}", employer.min_employees());
+
+            if building.is_labor_prioritized() {
+                add_body_line!(&mut body, "Labor Priority: {} (prioritized)", building.labor_priority());
+            } else {
+                add_body_line!(&mut body, "Labor Priority: {}", building.labor_priority());
+            }
+
+            if !building.workers_is_maxed() {
+                add_body_line!(&mut body, "Prioritize labor to staff this building ahead of others.");
+            }
+        } else if let Some(household) = workers.as_household_worker_pool() {
+            add_body_line!(&mut body, "Workers Available: {}", household.total_workers());
+            add_body_line!(&mut body, "Workers Employed: {}", household.employed_count());
+        }
+
+        body
+    }
+
+    fn gather_storage_info(building: &Building) -> InspectorMenuBody {
+        let mut body = InspectorMenuBody::new();
+
+        let skip_empty = building.is(BuildingKind::House)
+            || building.archetype_kind() == BuildingArchetypeKind::ServiceBuilding
             || building.archetype_kind() == BuildingArchetypeKind::StorageBuilding;
 
         let stock = building.stock();
+        let stock_items = Self::gather_stock_items(&stock, skip_empty);
 
-        if !stock.is_empty() || !skip_empty {
-            let stock_items = Self::gather_stock_items(&stock, skip_empty);
-            if !stock_items.is_empty() || !skip_empty {
-                add_body_line!(&mut body, "Stock:");
-                body.append(&stock_items);
-            }
+        if !stock_items.is_empty() {
+            add_body_line!(&mut body, "Stock:");
+            body.append(&stock_items);
+        } else {
+            add_body_line!(&mut body, "No goods stored.");
         }
 
         body
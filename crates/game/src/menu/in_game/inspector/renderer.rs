@@ -8,6 +8,7 @@ use strum::EnumCount;
 
 use super::{TileInspector, TileInspectorMenuWeakMut};
 use crate::{
+    i18n::tr,
     menu::TEXT_BUTTON_HOVERED_SPRITE,
     tile::{TileKind, sets::TileIconSprite},
     ui_context::GameUiContext,
@@ -125,8 +126,13 @@ pub struct InspectorMenuRenderer {
     icon_index: UiWidgetGroupWidgetIndex,
     heading_index: UiWidgetGroupWidgetIndex,
 
+    // Indices within `tab_group` / `button_group`.
+    tab_button_indices: Vec<UiWidgetGroupWidgetIndex>,
+    action_button_indices: Vec<UiWidgetGroupWidgetIndex>,
+
     // Indices withing `self.menu`.
     icon_and_heading_group_index: UiMenuWidgetIndex,
+    tab_group_index: Option<UiMenuWidgetIndex>,
     body_text_index: UiMenuWidgetIndex,
     button_group_index: UiMenuWidgetIndex,
 }
@@ -185,6 +191,27 @@ impl InspectorMenuRenderer {
         }
     }
 
+    // Highlights the current tab by disabling its button.
+    pub fn set_current_tab(&mut self, tab_index: usize) {
+        let Some(tab_group_index) = self.tab_group_index else {
+            return;
+        };
+
+        let tab_group = self.menu.widget_as_mut::<UiWidgetGroup>(tab_group_index).unwrap();
+        for (i, button_index) in self.tab_button_indices.iter().enumerate() {
+            let button = tab_group.widget_as_mut::<UiTextButton>(*button_index).unwrap();
+            button.enable(i != tab_index);
+        }
+    }
+
+    // Action buttons are placed before the Close button, in the order given on construction.
+    pub fn action_button_mut(&mut self, action_index: usize) -> &mut UiTextButton {
+        let button_index = self.action_button_indices[action_index];
+        let button_group_index = self.button_group_index;
+        let button_group = self.menu.widget_as_mut::<UiWidgetGroup>(button_group_index).unwrap();
+        button_group.widget_as_mut::<UiTextButton>(button_index).unwrap()
+    }
+
     pub fn set_icon(&mut self, context: &GameUiContext, icon_sprite: TileIconSprite, tile_kind: TileKind) {
         let icon = self.find_icon();
 
@@ -228,6 +255,18 @@ impl InspectorMenuRenderer {
         context: &mut GameUiContext,
         tile_inspector_menu_weak_ref: &TileInspectorMenuWeakMut,
         menu_name: &str,
+    ) -> Self {
+        Self::with_tabs_and_actions(context, tile_inspector_menu_weak_ref, menu_name, Vec::new(), Vec::new())
+    }
+
+    // Tab buttons are laid out side by side above the body text.
+    // Action buttons go next to the Close button.
+    pub fn with_tabs_and_actions(
+        context: &mut GameUiContext,
+        tile_inspector_menu_weak_ref: &TileInspectorMenuWeakMut,
+        menu_name: &str,
+        tab_buttons: Vec<UiTextButton>,
+        action_buttons: Vec<UiTextButton>,
     ) -> Self {
         let icon = UiSpriteIcon::new(context, UiSpriteIconParams {
             size: Vec2::one(), // placeholder
@@ -265,7 +304,7 @@ impl InspectorMenuRenderer {
 
         let close_button_inspector_menu_weak_ref = tile_inspector_menu_weak_ref.clone();
         let close_button = UiTextButton::new(context, UiTextButtonParams {
-            label: "Close".into(),
+            label: tr!("Close"),
             size: UiTextButtonSize::Normal,
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled: UiButtonSoundsEnabled::all(),
@@ -283,8 +322,31 @@ impl InspectorMenuRenderer {
             ..Default::default()
         });
 
+        let action_button_indices = action_buttons
+            .into_iter()
+            .map(|button| button_group.add_widget(button))
+            .collect();
+
         button_group.add_widget(close_button);
 
+        let tab_group = if !tab_buttons.is_empty() {
+            let mut tab_group = UiWidgetGroup::new(context, UiWidgetGroupParams {
+                center_vertically: false,
+                center_horizontally: true,
+                stack_vertically: false,
+                ..Default::default()
+            });
+
+            let tab_button_indices = tab_buttons
+                .into_iter()
+                .map(|button| tab_group.add_widget(button))
+                .collect::<Vec<_>>();
+
+            Some((tab_group, tab_button_indices))
+        } else {
+            None
+        };
+
         let body_text = UiMenuHeading::new(context, UiMenuHeadingParams {
             // placeholder text
             lines: vec![UiText::empty(INSPECTOR_BODY_TEXT_FONT_SCALE); INSPECTOR_BODY_TEXT_MAX_LINES],
@@ -316,6 +378,15 @@ impl InspectorMenuRenderer {
         let icon_and_heading_group_index = menu.add_widget(icon_and_heading_group);
 
         menu.add_widget(separator.clone());
+        let (tab_group_index, tab_button_indices) = match tab_group {
+            Some((tab_group, tab_button_indices)) => {
+                let tab_group_index = menu.add_widget(tab_group);
+                menu.add_widget(separator.clone());
+                (Some(tab_group_index), tab_button_indices)
+            }
+            None => (None, Vec::new()),
+        };
+
         let body_text_index = menu.add_widget(body_text);
 
         menu.add_widget(separator.clone());
@@ -323,7 +394,17 @@ impl InspectorMenuRenderer {
 
         menu.add_widget(separator);
 
-        Self { menu, icon_index, heading_index, icon_and_heading_group_index, body_text_index, button_group_index }
+        Self {
+            menu,
+            icon_index,
+            heading_index,
+            tab_button_indices,
+            action_button_indices,
+            icon_and_heading_group_index,
+            tab_group_index,
+            body_text_index,
+            button_group_index,
+        }
     }
 
     // ----------------------
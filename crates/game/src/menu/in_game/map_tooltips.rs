@@ -0,0 +1,170 @@
+use common::time::Seconds;
+use engine::ui::{self, UiTextureHandle, widgets::UiWidgetContext};
+
+use crate::{
+    building::{Building, BuildingId, BuildingKind},
+    config::GameConfigs,
+    i18n::tr,
+    menu::{TOOLTIP_BACKGROUND_SPRITE, TOOLTIP_FONT_SCALE},
+    tile::TileKind,
+    ui_context::GameUiContext,
+    unit::{Unit, UnitId, task::UnitTaskManager},
+    world::{World, object::GameObject},
+};
+
+// ----------------------------------------------
+// MapTooltips
+// ----------------------------------------------
+
+// Tooltip with the live status of the building or unit under the cursor.
+// Shown after hovering the same object for TooltipConfigs::map_tooltip_delay_secs.
+pub struct MapTooltips {
+    hovered: Option<HoveredObject>,
+    hover_secs: Seconds,
+    background: UiTextureHandle,
+}
+
+// Tracked by id, so a unit walking under the cursor doesn't restart the delay every cell.
+#[derive(Copy, Clone, PartialEq)]
+enum HoveredObject {
+    Building(BuildingKind, BuildingId),
+    Unit(UnitId),
+}
+
+impl MapTooltips {
+    pub fn new(context: &mut GameUiContext) -> Self {
+        Self {
+            hovered: None,
+            hover_secs: 0.0,
+            background: context.load_ui_texture(TOOLTIP_BACKGROUND_SPRITE),
+        }
+    }
+
+    // `can_show` is false while placing tiles or with a dialog open.
+    pub fn draw(&mut self, context: &mut GameUiContext, can_show: bool) {
+        let configs = &GameConfigs::get().tooltips;
+
+        let hovered = if can_show && configs.show_map_tooltips && !context.ui_sys.is_handling_mouse_input() {
+            Self::find_hovered_object(context)
+        } else {
+            None
+        };
+
+        if hovered != self.hovered {
+            self.hovered = hovered;
+            self.hover_secs = 0.0;
+        }
+
+        let Some(hovered) = self.hovered else {
+            return;
+        };
+
+        self.hover_secs += context.delta_time_secs;
+        if self.hover_secs < configs.map_tooltip_delay_secs {
+            return;
+        }
+
+        let ui = context.ui_sys.ui();
+        let task_manager = context.sim.task_manager();
+
+        ui::custom_tooltip(context.ui_sys, TOOLTIP_FONT_SCALE, Some(self.background), || match hovered {
+            HoveredObject::Building(kind, id) => {
+                if let Some(building) = context.world.find_building(kind, id) {
+                    for line in building_tooltip_lines(building, context.world) {
+                        ui.text(line);
+                    }
+                }
+            }
+            HoveredObject::Unit(id) => {
+                if let Some(unit) = context.world.find_unit(id) {
+                    for line in unit_tooltip_lines(unit, task_manager) {
+                        ui.text(line);
+                    }
+                }
+            }
+        });
+    }
+
+    fn find_hovered_object(context: &GameUiContext) -> Option<HoveredObject> {
+        let tile = context.tile_map.topmost_tile_at_cursor(context.cursor_screen_pos, context.camera.transform())?;
+
+        if tile.is(TileKind::Building) {
+            let building = context.world.find_building_for_tile(tile)?;
+            Some(HoveredObject::Building(building.kind(), building.id()))
+        } else if tile.is(TileKind::Unit) {
+            let unit = context.world.find_unit_for_tile(tile)?;
+            Some(HoveredObject::Unit(unit.id()))
+        } else {
+            None
+        }
+    }
+}
+
+// ----------------------------------------------
+// Tooltip contents
+// ----------------------------------------------
+
+// Name, then the most pressing status line, then key stats.
+fn building_tooltip_lines(building: &Building, world: &World) -> Vec<String> {
+    let percent = |value: f32| (value * 100.0).round() as u32;
+
+    let mut lines = vec![building.name().to_string()];
+
+    let status = if building.hazards().is_burning() {
+        tr!("On fire!")
+    } else if let Some(construction) = building.construction() {
+        if construction.has_all_materials() {
+            tr!("Under construction.")
+        } else {
+            tr!("Under construction. Waiting on materials.")
+        }
+    } else if building.is_operational() {
+        if building.is_production_paused() { tr!("Production paused.") } else { tr!("Operational.") }
+    } else if !building.is_linked_to_road() {
+        tr!("Not running: no road access!")
+    } else if !building.has_min_required_workers() {
+        tr!("Not running: not enough workers!")
+    } else if !building.has_min_required_resources() {
+        tr!("Not running: missing resources!")
+    } else {
+        tr!("Not running.")
+    };
+    lines.push(status);
+
+    if let Some(population) = building.population() {
+        lines.push(format!("{}: {}/{}", tr!("Residents"), population.count(), population.max()));
+    }
+
+    if let Some(employer) = building.workers().and_then(|workers| workers.as_employer()) {
+        lines.push(format!("{}: {}/{}", tr!("Workers"), employer.employee_count(), employer.max_employees()));
+    }
+
+    if let Some(progress) = building.production_progress() {
+        lines.push(format!("{}: {}%", tr!("Production"), percent(progress)));
+    }
+
+    let damage = building.raid_damage(world);
+    if damage > 0.0 && !building.hazards().is_destroyed() {
+        let building_health = GameConfigs::get().sim.building_health;
+        lines.push(format!("{}: {}%", tr!("Damage"), percent(damage / building_health)));
+    }
+
+    lines
+}
+
+fn unit_tooltip_lines(unit: &Unit, task_manager: &UnitTaskManager) -> Vec<String> {
+    let mut lines = vec![
+        unit.name().to_string(),
+        format!("{}: {}", tr!("Task"), tr!(unit.current_task_description(task_manager))),
+    ];
+
+    if let Some(item) = unit.peek_inventory() {
+        lines.push(format!("{}: {} {}", tr!("Carrying"), item.count, item.kind));
+    }
+
+    if unit.is_combatant() {
+        lines.push(format!("{}: {}%", tr!("Health"), (unit.health_ratio() * 100.0).round() as u32));
+    }
+
+    lines
+}
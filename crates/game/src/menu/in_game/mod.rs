@@ -1,24 +1,35 @@
 use std::any::Any;
 
 use bars::{InGameMenuBars, InGameMenuBarsRcMut};
+use captions::CaptionsPanel;
 use common::coords::CellRange;
 use engine::{
-    app::input::{InputAction, InputKey},
+    app::input::{InputAction, MouseButton},
     ui::{UiInputEvent, UiTheme, widgets::UiMenuFlags},
 };
 use inspector::{TileInspectorMenu, TileInspectorMenuRcMut};
+use map_tooltips::MapTooltips;
+use notifications::NotificationFeed;
+use objectives::ObjectivesPanel;
+use orders::UnitOrders;
 use palette::{TilePaletteMenu, TilePaletteMenuRcMut};
 
 use super::{GameMenusInputArgs, GameMenusMode, GameMenusSystem, TileInspector, TilePalette, TilePlacement, dialog};
 use crate::{
     campaign::{self, CampaignPrompt},
+    key_bindings::{GameAction, KeyBindings},
     save_context::{Load, PreLoadContext, Save},
     tile::minimap::{InGameUiMinimapRenderer, MinimapRenderer},
     ui_context::GameUiContext,
 };
 
 mod bars;
+mod captions;
 mod inspector;
+mod map_tooltips;
+mod notifications;
+mod objectives;
+mod orders;
 mod palette;
 
 // ----------------------------------------------
@@ -30,6 +41,11 @@ pub struct InGameMenus {
     tile_palette: TilePaletteMenuRcMut,
     tile_inspector: TileInspectorMenuRcMut,
     menu_bars: InGameMenuBarsRcMut,
+    notification_feed: NotificationFeed,
+    objectives_panel: ObjectivesPanel,
+    captions_panel: CaptionsPanel,
+    unit_orders: UnitOrders,
+    map_tooltips: MapTooltips,
     minimap_renderer: InGameUiMinimapRenderer,
 }
 
@@ -46,6 +62,11 @@ impl InGameMenus {
             tile_palette: TilePaletteMenu::new(context),
             tile_inspector: TileInspectorMenu::new(context),
             menu_bars: InGameMenuBars::new(context),
+            notification_feed: NotificationFeed::new(),
+            objectives_panel: ObjectivesPanel::new(),
+            captions_panel: CaptionsPanel::new(),
+            unit_orders: UnitOrders::new(),
+            map_tooltips: MapTooltips::new(context),
             minimap_renderer: InGameUiMinimapRenderer::new(context),
         }
     }
@@ -73,24 +94,69 @@ impl GameMenusSystem for InGameMenus {
     }
 
     fn handle_custom_input(&mut self, context: &mut GameUiContext, args: GameMenusInputArgs) -> UiInputEvent {
-        if let GameMenusInputArgs::Key { key, action, .. } = args {
-            // [ESCAPE]: Close all dialog menus and return to game.
-            if key == InputKey::Escape && action == InputAction::Press {
-                if dialog::close_current(context) {
-                    return UiInputEvent::Handled; // Key press is handled.
+        if dialog::handle_input(args).is_handled() {
+            return UiInputEvent::Handled;
+        }
+
+        match args {
+            GameMenusInputArgs::Key { key, action, modifiers } => {
+                // [ESCAPE]: Close all dialog menus and return to game.
+                if action == InputAction::Press && KeyBindings::get().is_key_bound_to(GameAction::Cancel, key, modifiers) {
+                    self.unit_orders.clear_selection(context);
+                    if dialog::close_current(context) {
+                        return UiInputEvent::Handled; // Key press is handled.
+                    }
+                }
+
+                // Unit selection groups ([CTRL]/[SHIFT]+[1-9] by default). Checked before the build hotkeys.
+                if action == InputAction::Press
+                    && dialog::current().is_none()
+                    && self.unit_orders.handle_group_hotkey(context, key, modifiers)
+                {
+                    return UiInputEvent::Handled;
+                }
+
+                // [1-0]: Open build menu categories, unless a dialog has focus.
+                if action == InputAction::Press
+                    && dialog::current().is_none()
+                    && self.tile_palette.handle_build_hotkey(context, key, modifiers)
+                {
+                    return UiInputEvent::Handled;
+                }
+            }
+            GameMenusInputArgs::Mouse { button, action, modifiers } => {
+                // Unit orders only while not placing tiles.
+                if !self.tile_palette.has_selection() {
+                    // A marquee drag selects units instead of inspecting the tile it started on.
+                    if button == MouseButton::Left
+                        && action == InputAction::Release
+                        && self.unit_orders.end_marquee(context, modifiers)
+                    {
+                        self.tile_inspector.close(context);
+                    }
+                    return self.unit_orders.on_mouse_button(context, button, action, modifiers);
                 }
             }
+            GameMenusInputArgs::Scroll { .. } => {}
         }
 
         UiInputEvent::NotHandled // Let the event propagate.
     }
 
     fn end_frame(&mut self, context: &mut GameUiContext, _visible_range: CellRange) {
+        self.unit_orders.update(context);
         self.minimap_renderer.draw(context);
         self.tile_palette.draw(context);
         self.menu_bars.draw(context);
+        self.notification_feed.draw(context);
+        self.objectives_panel.draw(context);
+        self.captions_panel.draw(context);
         self.tile_inspector.draw(context);
 
+        // No map tooltips while placing tiles or over a dialog.
+        let can_show_map_tooltips = !self.tile_palette.has_selection() && dialog::current().is_none();
+        self.map_tooltips.draw(context, can_show_map_tooltips);
+
         // Open a pending campaign prompt, but only when no other dialog is open
         // (don't interrupt the pause menu, etc.). The prompt stays pending until
         // then, so it is taken only when it is actually shown.
@@ -99,6 +165,9 @@ impl GameMenusSystem for InGameMenus {
                 Some(CampaignPrompt::MissionComplete) => {
                     dialog::open(dialog::DialogMenuKind::MissionComplete, false, context);
                 }
+                Some(CampaignPrompt::MissionFailed) => {
+                    dialog::open(dialog::DialogMenuKind::MissionFailed, false, context);
+                }
                 Some(CampaignPrompt::CampaignComplete) => {
                     dialog::open(dialog::DialogMenuKind::CampaignComplete, false, context);
                 }
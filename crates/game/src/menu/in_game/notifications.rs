@@ -0,0 +1,115 @@
+use common::{Vec2, time::Seconds};
+use engine::{
+    file_sys::paths::PathRef,
+    ui::{self, sound::UiButtonSoundsEnabled, widgets::*},
+};
+
+use crate::{
+    GameLoop,
+    menu::TEXT_BUTTON_HOVERED_SPRITE,
+    sim::notifications::Notifications,
+    ui_context::GameUiContext,
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const NOTIFICATION_LIFETIME_SECS: Seconds = 20.0;
+const NOTIFICATION_ICON_SIZE: Vec2 = Vec2::new(20.0, 20.0);
+const NOTIFICATION_SPACING: Vec2 = Vec2::new(4.0, 4.0);
+
+const CAMERA_PAN_DURATION_SECS: Seconds = 0.5;
+
+// ----------------------------------------------
+// NotificationFeed
+// ----------------------------------------------
+
+// HUD list of the latest notifications, newest on top, below the overlays bar.
+// Clicking a notification pans the camera to its cell and dismisses it.
+pub struct NotificationFeed {
+    revision: Option<u32>,
+    menu: Option<UiMenuRcMut>, // None while there's nothing to show.
+}
+
+impl NotificationFeed {
+    pub fn new() -> Self {
+        Self { revision: None, menu: None }
+    }
+
+    pub fn draw(&mut self, context: &mut GameUiContext) {
+        let notifications = Notifications::get_mut();
+        notifications.update(context.delta_time_secs, NOTIFICATION_LIFETIME_SECS);
+
+        // Keep expiring them while hidden, so old alerts don't pop up when re-enabled.
+        if !GameLoop::get().are_warnings_enabled() {
+            return;
+        }
+
+        // Menus can't remove widgets, so rebuild the whole feed when the queue changes.
+        if self.revision != Some(notifications.revision()) {
+            self.revision = Some(notifications.revision());
+            self.menu = Self::build_menu(context, notifications);
+        }
+
+        if let Some(menu) = &mut self.menu {
+            menu.draw(context);
+        }
+    }
+
+    fn build_menu(context: &mut GameUiContext, notifications: &Notifications) -> Option<UiMenuRcMut> {
+        if notifications.is_empty() {
+            return None;
+        }
+
+        let mut menu = UiMenu::new(context, UiMenuParams {
+            label: Some("NotificationFeed".into()),
+            flags: UiMenuFlags::IsOpen | UiMenuFlags::AlignRight,
+            position: UiMenuPosition::Vec2(0.0, 60.0),
+            widget_spacing: Some(NOTIFICATION_SPACING),
+            background: Some(PathRef::from_str("misc/tall_page_bg.png")),
+            ..Default::default()
+        });
+
+        for (index, notification) in notifications.iter().enumerate().rev() {
+            let mut row = UiWidgetGroup::new(context, UiWidgetGroupParams {
+                widget_spacing: NOTIFICATION_SPACING,
+                center_horizontally: false, // Let content float left.
+                stack_vertically: false,    // Icon and message side-by-side.
+                ..Default::default()
+            });
+
+            let icon = UiSpriteIcon::new(context, UiSpriteIconParams {
+                sprite: Some(notification.kind.icon_path()),
+                size: NOTIFICATION_ICON_SIZE,
+                clip_to_parent_menu: true,
+                ..Default::default()
+            });
+
+            // Index is stable until the next queue change, which rebuilds the feed.
+            let cell = notification.cell;
+            let message_button = UiTextButton::new(context, UiTextButtonParams {
+                label: notification.message.clone(),
+                size: UiTextButtonSize::Small,
+                hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
+                sounds_enabled: UiButtonSoundsEnabled::Pressed,
+                on_pressed: UiTextButtonPressed::with_closure(move |_, context| {
+                    let context = ui::widgets::context_as_mut::<GameUiContext>(context);
+                    Notifications::get_mut().dismiss(index);
+
+                    if cell.is_valid() {
+                        // Notification cells are map cells, the camera works in the rotated view.
+                        context.camera.pan_to(context.tile_map.rotate_cell(cell), CAMERA_PAN_DURATION_SECS);
+                    }
+                }),
+                ..Default::default()
+            });
+
+            row.add_widget(icon);
+            row.add_widget(message_button);
+            menu.add_widget(row);
+        }
+
+        Some(menu)
+    }
+}
@@ -0,0 +1,91 @@
+use common::Vec2;
+use engine::{file_sys::paths::PathRef, ui::widgets::*};
+
+use crate::{campaign, menu::TOOLTIP_FONT_SCALE, ui_context::GameUiContext};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const OBJECTIVES_PANEL_POSITION: Vec2 = Vec2::new(0.0, 240.0); // Below the LeftBar.
+const OBJECTIVES_PANEL_SPACING: Vec2 = Vec2::new(2.0, 2.0);
+const OBJECTIVES_LABEL_PADDING: Vec2 = Vec2::new(10.0, 5.0);
+
+// ----------------------------------------------
+// ObjectivesPanel
+// ----------------------------------------------
+
+// HUD list of the active campaign mission goals and their progress.
+// Hidden outside of campaign missions.
+pub struct ObjectivesPanel {
+    lines: Vec<String>,
+    menu: Option<UiMenuRcMut>, // None while there's no active mission.
+}
+
+impl ObjectivesPanel {
+    pub fn new() -> Self {
+        Self { lines: Vec::new(), menu: None }
+    }
+
+    pub fn draw(&mut self, context: &mut GameUiContext) {
+        // Only rebuild the panel when the displayed progress actually changes.
+        let lines = Self::objective_lines(context);
+        if lines != self.lines {
+            self.menu = Self::build_menu(context, &lines);
+            self.lines = lines;
+        }
+
+        if let Some(menu) = &mut self.menu {
+            menu.draw(context);
+        }
+    }
+
+    // Mission name followed by one line per goal. Empty if no mission is active.
+    fn objective_lines(context: &GameUiContext) -> Vec<String> {
+        let Some(active) = campaign::active_mission() else {
+            return Vec::new();
+        };
+
+        let Some(mission) = active.def() else {
+            return Vec::new();
+        };
+
+        let state = active.state(context.world.stats(), context.sim.calendar());
+        let mut lines = vec![mission.name.clone()];
+
+        for goal in &mission.requirements.goals {
+            let (current, target) = goal.progress(&state);
+            let check_mark = if current >= target { "[x]" } else { "[ ]" };
+            lines.push(format!("{check_mark} {goal}: {} / {target}", current.min(target)));
+        }
+
+        lines
+    }
+
+    fn build_menu(context: &mut GameUiContext, lines: &[String]) -> Option<UiMenuRcMut> {
+        if lines.is_empty() {
+            return None;
+        }
+
+        let mut menu = UiMenu::new(context, UiMenuParams {
+            label: Some("ObjectivesPanel".into()),
+            flags: UiMenuFlags::IsOpen | UiMenuFlags::AlignLeft,
+            position: UiMenuPosition::Vec2(OBJECTIVES_PANEL_POSITION.x, OBJECTIVES_PANEL_POSITION.y),
+            widget_spacing: Some(OBJECTIVES_PANEL_SPACING),
+            background: Some(PathRef::from_str("misc/tall_page_bg.png")),
+            ..Default::default()
+        });
+
+        for line in lines {
+            let label = UiSizedTextLabel::new(context, UiSizedTextLabelParams {
+                font_scale: TOOLTIP_FONT_SCALE,
+                label: line.clone(),
+                size: context.calc_text_size(TOOLTIP_FONT_SCALE, line) + OBJECTIVES_LABEL_PADDING,
+            });
+
+            menu.add_widget(label);
+        }
+
+        Some(menu)
+    }
+}
@@ -0,0 +1,189 @@
+use common::Rect;
+use engine::{
+    app::input::{InputAction, InputKey, InputModifiers, MouseButton},
+    ui::UiInputEvent,
+};
+
+use crate::{
+    key_bindings::{GameAction, KeyBindings},
+    tile::{TileFlags, TileKind, TileMapLayerKind},
+    ui_context::GameUiContext,
+    unit::UnitId,
+    world::object::GameObject,
+};
+
+// ----------------------------------------------
+// UnitOrders
+// ----------------------------------------------
+
+// Left-clicking a controllable unit selects it, dragging a marquee selects every
+// controllable unit inside it and [SHIFT] adds to the current selection instead.
+// Right-clicking a tile afterwards orders the selected units to walk there.
+// "Assign Group N" stores the selection in a numbered group and "Select Group N" selects
+// it again ([CTRL]+[1-9] and [SHIFT]+[1-9] by default, see KeyBindings).
+// The selection itself lives in the TileSelection, so it survives tile clicks.
+#[derive(Default)]
+pub struct UnitOrders;
+
+impl UnitOrders {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn clear_selection(&mut self, context: &mut GameUiContext) {
+        for unit_id in context.tile_selection.take_selected_units() {
+            set_unit_selected_flag(context, unit_id, false);
+        }
+    }
+
+    // Once per frame. Drops despawned units and keeps the selected highlight on the rest,
+    // since saving a game clears the selection flags from all tiles.
+    pub fn update(&mut self, context: &mut GameUiContext) {
+        let world = &*context.world;
+        context.tile_selection.retain_units(|unit_id| world.find_unit(unit_id).is_some());
+
+        for index in 0..context.tile_selection.selected_units().len() {
+            let unit_id = context.tile_selection.selected_units()[index];
+            set_unit_selected_flag(context, unit_id, true);
+        }
+    }
+
+    // Assign Group N: Assign the selected units to a group. Select Group N: Select the group again.
+    pub fn handle_group_hotkey(&mut self, context: &mut GameUiContext, key: InputKey, modifiers: InputModifiers) -> bool {
+        let key_bindings = KeyBindings::get();
+        let is_bound_to = |action| key_bindings.is_key_bound_to(action, key, modifiers);
+
+        if let Some(group) = GameAction::ASSIGN_GROUP.into_iter().position(is_bound_to) {
+            context.tile_selection.assign_selection_group(group);
+            true
+        } else if let Some(group) = GameAction::SELECT_GROUP.into_iter().position(is_bound_to) {
+            let group_units: Vec<UnitId> = context.tile_selection.selection_group(group).to_vec();
+            self.select_units(context, group_units, false);
+            true
+        } else {
+            false
+        }
+    }
+
+    // Ends a marquee drag on left button release. Returns true if the drag selected
+    // units, false if it was just a click.
+    pub fn end_marquee(&mut self, context: &mut GameUiContext, modifiers: InputModifiers) -> bool {
+        let Some(marquee_rect) = context.tile_selection.end_marquee(context.cursor_screen_pos) else {
+            return false;
+        };
+
+        let units = self.find_units_in_rect(context, &marquee_rect);
+        self.select_units(context, units, modifiers.intersects(InputModifiers::Shift));
+        true
+    }
+
+    pub fn on_mouse_button(
+        &mut self,
+        context: &mut GameUiContext,
+        button: MouseButton,
+        action: InputAction,
+        modifiers: InputModifiers,
+    ) -> UiInputEvent {
+        if action != InputAction::Press {
+            return UiInputEvent::NotHandled;
+        }
+
+        match button {
+            MouseButton::Left => {
+                context.tile_selection.begin_marquee(context.cursor_screen_pos);
+
+                let clicked_unit = context
+                    .topmost_selected_tile()
+                    .filter(|tile| tile.is(TileKind::Unit))
+                    .and_then(|tile| context.world.find_unit_for_tile(tile))
+                    .filter(|unit| unit.is_controllable())
+                    .map(|unit| unit.id());
+
+                let additive = modifiers.intersects(InputModifiers::Shift);
+
+                match clicked_unit {
+                    // [SHIFT]+click on a selected unit removes it from the selection.
+                    Some(unit_id) if additive && context.tile_selection.is_unit_selected(unit_id) => {
+                        context.tile_selection.deselect_unit(unit_id);
+                        set_unit_selected_flag(context, unit_id, false);
+                    }
+                    Some(unit_id) => self.select_units(context, [unit_id], additive),
+                    None if !additive => self.clear_selection(context),
+                    None => {}
+                }
+
+                // Let the click propagate so the tile inspector still opens.
+                UiInputEvent::NotHandled
+            }
+            MouseButton::Right => {
+                if context.tile_selection.selected_units().is_empty() {
+                    return UiInputEvent::NotHandled;
+                }
+
+                let destination = context.tile_map.find_exact_cell_for_point(
+                    TileMapLayerKind::Terrain,
+                    context.cursor_screen_pos,
+                    context.camera.transform(),
+                );
+
+                if !destination.is_valid() {
+                    return UiInputEvent::NotHandled;
+                }
+
+                let selected_units: Vec<UnitId> = context.tile_selection.selected_units().to_vec();
+                let sim_context = context.new_sim_context();
+
+                let mut any_ordered = false;
+                for unit_id in selected_units {
+                    // Units that despawned since they were selected are pruned in update().
+                    if let Some(unit) = sim_context.find_unit_mut(unit_id) {
+                        unit.try_order_go_to(&sim_context, destination);
+                        any_ordered = true;
+                    }
+                }
+
+                if any_ordered { UiInputEvent::Handled } else { UiInputEvent::NotHandled }
+            }
+            _ => UiInputEvent::NotHandled,
+        }
+    }
+
+    fn select_units(&mut self, context: &mut GameUiContext, units: impl IntoIterator<Item = UnitId>, additive: bool) {
+        if !additive {
+            self.clear_selection(context);
+        }
+
+        for unit_id in units {
+            context.tile_selection.select_unit(unit_id);
+            set_unit_selected_flag(context, unit_id, true);
+        }
+    }
+
+    fn find_units_in_rect(&self, context: &GameUiContext, screen_rect: &Rect) -> Vec<UnitId> {
+        let transform = context.camera.transform();
+        let rotation = context.tile_map.rotation();
+        let map_size_in_cells = context.tile_map.size_in_cells();
+        let units_layer = context.tile_map.layer(TileMapLayerKind::Objects);
+
+        let mut units = Vec::new();
+
+        context.world.for_each_unit(|unit| {
+            if unit.is_controllable() {
+                let unit_rect = units_layer[unit.tile_index()].view_screen_rect(transform, rotation, map_size_in_cells);
+                if unit_rect.intersects(screen_rect) {
+                    units.push(unit.id());
+                }
+            }
+            true
+        });
+
+        units
+    }
+}
+
+fn set_unit_selected_flag(context: &mut GameUiContext, unit_id: UnitId, selected: bool) {
+    if let Some(unit) = context.world.find_unit(unit_id) {
+        let tile_index = unit.tile_index();
+        context.tile_map.set_tile_flags_at_index(tile_index, TileMapLayerKind::Objects, TileFlags::Selected, selected);
+    }
+}
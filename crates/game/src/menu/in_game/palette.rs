@@ -4,13 +4,14 @@ use common::{
     Color,
     Rect,
     RectTexCoords,
+    Size,
     Vec2,
     constants::BASE_TILE_SIZE_F32,
-    coords::WorldToScreenTransform,
+    coords::{self, WorldToScreenTransform},
     mem::{RcMut, WeakMut, WeakRef},
 };
 use engine::{
-    app::input::{InputAction, MouseButton},
+    app::input::{InputAction, InputKey, InputModifiers, MouseButton},
     file_sys::paths::PathRef,
     render::texture::TextureHandle,
     ui::{
@@ -23,10 +24,18 @@ use engine::{
 use strum::{EnumCount, EnumIter, EnumProperty, IntoEnumIterator};
 
 use crate::{
+    building::{
+        BuildingKind,
+        config::BuildingConfigs,
+        unlocks::{BuildingUnlockConfigs, BuildingUnlocks},
+    },
+    i18n::tr,
+    key_bindings::{GameAction, KeyBindings},
     menu::*,
     tile::{
         TileKind,
-        rendering::INVALID_TILE_COLOR,
+        color_palette::ColorPalette,
+        prefab::{PrefabConfigs, PrefabDef},
         sets::{
             OBJECTS_BUILDINGS_CATEGORY,
             PresetTiles,
@@ -142,10 +151,34 @@ impl TilePaletteMainButtonDef {
             true
         });
 
+        // Multi-tile prefabs are listed after the individual tiles of the same sector.
+        for (index, prefab) in PrefabConfigs::get().prefabs.iter().enumerate() {
+            if prefab.sector.name() == button_name {
+                children.push(TilePaletteChildButtonDef::new_prefab(index, prefab));
+            }
+        }
+
         children
     }
 
-    fn to_tile_selection(self, tile_def_handle: Option<TileDefHandle>) -> TilePaletteSelection {
+    // Hotkey that toggles this button from the keyboard. Undo/Redo have their own bindings.
+    fn build_action(self) -> Option<GameAction> {
+        match self {
+            TilePaletteMainButtonDef::ClearLand            => Some(GameAction::BuildClearLand),
+            TilePaletteMainButtonDef::Housing              => Some(GameAction::BuildHousing),
+            TilePaletteMainButtonDef::Roads                => Some(GameAction::BuildRoads),
+            TilePaletteMainButtonDef::FoodAndFarming       => Some(GameAction::BuildFoodAndFarming),
+            TilePaletteMainButtonDef::IndustryAndResources => Some(GameAction::BuildIndustry),
+            TilePaletteMainButtonDef::Services             => Some(GameAction::BuildServices),
+            TilePaletteMainButtonDef::Infrastructure       => Some(GameAction::BuildInfrastructure),
+            TilePaletteMainButtonDef::CultureAndReligion   => Some(GameAction::BuildCulture),
+            TilePaletteMainButtonDef::TradeAndEconomy      => Some(GameAction::BuildTrade),
+            TilePaletteMainButtonDef::Beautification       => Some(GameAction::BuildBeautification),
+            TilePaletteMainButtonDef::Undo | TilePaletteMainButtonDef::Redo => None,
+        }
+    }
+
+    fn to_tile_selection(self) -> TilePaletteSelection {
         match self {
             TilePaletteMainButtonDef::ClearLand => TilePaletteSelection::Clear,
             TilePaletteMainButtonDef::Housing => {
@@ -155,7 +188,7 @@ impl TilePaletteMainButtonDef {
                     TilePaletteSelection::None
                 }
             }
-            _ => TilePaletteSelection::Tile(tile_def_handle.unwrap()),
+            _ => TilePaletteSelection::None,
         }
     }
 }
@@ -169,16 +202,89 @@ impl ButtonDef for TilePaletteMainButtonDef {}
 struct TilePaletteChildButtonDef {
     label: String,
     tooltip: Option<String>,
-    tile_def_handle: TileDefHandle,
+    selection: TilePaletteSelection,
+    building_kinds: BuildingKind, // Buildings placed by this button. Greyed out while any is locked.
 }
 
 impl TilePaletteChildButtonDef {
     fn new(tile_def: &TileDef) -> Self {
         let label = common::fixed_string::snake_case_to_title::<128>(&tile_def.name).to_string();
+        let building_kinds = Self::building_kinds([tile_def]);
+
+        let size = tile_def.is(TileKind::Building).then(|| tile_def.size_in_cells());
+        let workers = BuildingConfigs::get().find_workers_for_tile_def(tile_def);
+
+        let tooltip = Self::build_tooltip(&label, tile_def.cost, size, workers);
+        let tooltip = Some(Self::append_unlock_milestone(tooltip, building_kinds));
 
-        let tooltip = { if tile_def.cost != 0 { Some(format!("Cost: {} gold", tile_def.cost)) } else { None } };
+        Self {
+            label,
+            tooltip,
+            selection: TilePaletteSelection::Tile(TileDefHandle::from_tile_def(tile_def)),
+            building_kinds,
+        }
+    }
+
+    fn new_prefab(index: usize, prefab: &PrefabDef) -> Self {
+        let label = common::fixed_string::snake_case_to_title::<128>(&prefab.name).to_string();
+        let parts = prefab.resolved_parts().unwrap_or_default();
+        let building_kinds = Self::building_kinds(parts.iter().map(|(_, tile_def)| *tile_def));
+
+        // Combined cost and workers of all tiles in the prefab.
+        let cost = prefab.cost();
+        let workers = parts
+            .iter()
+            .filter_map(|(_, tile_def)| BuildingConfigs::get().find_workers_for_tile_def(tile_def))
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a + min_b, max_a + max_b));
+
+        let tooltip = Self::build_tooltip(&label, cost, None, workers);
+        let tooltip = Some(Self::append_unlock_milestone(tooltip, building_kinds));
 
-        Self { label, tooltip, tile_def_handle: TileDefHandle::from_tile_def(tile_def) }
+        Self { label, tooltip, selection: TilePaletteSelection::Prefab(index), building_kinds }
+    }
+
+    fn building_kinds<'a>(tile_defs: impl IntoIterator<Item = &'a TileDef>) -> BuildingKind {
+        tile_defs
+            .into_iter()
+            .filter_map(|tile_def| BuildingConfigs::get().find_building_kind_for_tile_def(tile_def))
+            .fold(BuildingKind::empty(), |kinds, kind| kinds | kind)
+    }
+
+    // Name, followed by cost, footprint and staffing, when they apply.
+    fn build_tooltip(label: &str, cost: u32, size: Option<Size>, workers: Option<(u32, u32)>) -> String {
+        let mut tooltip = label.to_string();
+
+        if cost != 0 {
+            tooltip += &format!("\n{}", tr!("Cost: {cost} gold", cost = cost));
+        }
+
+        if let Some(size) = size {
+            tooltip += &format!("\n{}", tr!("Size: {width}x{height}", width = size.width, height = size.height));
+        }
+
+        match workers {
+            Some((min, max)) if max != 0 && min != max => {
+                tooltip += &format!("\n{}", tr!("Workers: {min}-{max}", min = min, max = max));
+            }
+            Some((_, max)) if max != 0 => {
+                tooltip += &format!("\n{}", tr!("Workers: {count}", count = max));
+            }
+            _ => {}
+        }
+
+        tooltip
+    }
+
+    // Lets the player know what unlocks a gated building.
+    fn append_unlock_milestone(tooltip: String, building_kinds: BuildingKind) -> String {
+        if !BuildingUnlockConfigs::is_initialized() {
+            return tooltip;
+        }
+
+        match BuildingUnlockConfigs::get().find_milestone(building_kinds) {
+            Some(milestone) => format!("{tooltip}\n{}", tr!("Requires: {milestone}", milestone = milestone)),
+            None => tooltip,
+        }
     }
 }
 
@@ -196,10 +302,9 @@ impl TilePaletteMainButtonsBuilder {
         let mut buttons = Self { main: ArrayVec::new(), ui: ArrayVec::new() };
 
         for main_button_def in TilePaletteMainButtonDef::iter() {
-            let mut main_button = TilePaletteMainButton::new(context, main_button_def, tile_palette);
+            let main_button = TilePaletteMainButton::new(context, main_button_def, tile_palette);
 
             let tile_palette_weak_ref = tile_palette.clone();
-            let child_menu_weak_ref = main_button.child_menu_weak_mut();
 
             let on_main_button_state_changed =
                 UiSpriteButtonStateChanged::with_closure(move |button, context, prev_state| {
@@ -215,31 +320,8 @@ impl TilePaletteMainButtonsBuilder {
                     }
 
                     if is_pressed {
-                        let mut stay_pressed = true;
-
-                        // Open new child menu for pressed main button, if any.
-                        if let Some(child_menu_weak_ref) = &child_menu_weak_ref {
-                            let mut child_menu_rc = child_menu_weak_ref.upgrade().unwrap();
-                            child_menu_rc.open(context);
-                        } else {
-                            match main_button_def {
-                                TilePaletteMainButtonDef::Undo => {
-                                    let game_ui_context = ui::widgets::context_as_mut::<GameUiContext>(context);
-                                    undo_redo::undo(&game_ui_context.new_sim_context());
-                                    stay_pressed = false;
-                                }
-                                TilePaletteMainButtonDef::Redo => {
-                                    let game_ui_context = ui::widgets::context_as_mut::<GameUiContext>(context);
-                                    undo_redo::redo(&game_ui_context.new_sim_context());
-                                    stay_pressed = false;
-                                }
-                                _ => {
-                                    // If parent button has no child menu, choose tile directly here (e.g.: Housing, ClearLand).
-                                    let selection = main_button_def.to_tile_selection(None);
-                                    tile_palette_rc.set_selection_internal(selection);
-                                }
-                            }
-                        }
+                        let game_ui_context = ui::widgets::context_as_mut::<GameUiContext>(context);
+                        let stay_pressed = tile_palette_rc.on_main_button_pressed(main_button_def, game_ui_context);
 
                         // Stay pressed, except for undo/redo (reset_selection_internal above would have unpressed all).
                         button.press(stay_pressed);
@@ -271,6 +353,7 @@ impl TilePaletteMainButtonsBuilder {
 struct TilePaletteMainButton {
     def: TilePaletteMainButtonDef,
     child_menu: Option<UiMenuRcMut>,
+    child_building_kinds: Vec<BuildingKind>, // Parallel to the child menu buttons.
 }
 
 impl TilePaletteMainButton {
@@ -280,6 +363,7 @@ impl TilePaletteMainButton {
         tile_palette: &TilePaletteMenuWeakMut,
     ) -> Self {
         let children = main_button_def.build_child_button_defs();
+        let child_building_kinds = children.iter().map(|child| child.building_kinds).collect();
 
         let child_menu = {
             if children.is_empty() {
@@ -289,7 +373,7 @@ impl TilePaletteMainButton {
             }
         };
 
-        Self { def: main_button_def, child_menu }
+        Self { def: main_button_def, child_menu, child_building_kinds }
     }
 
     fn build_child_menu(
@@ -319,8 +403,7 @@ impl TilePaletteMainButton {
                 })
             });
 
-            let parent_button_def = main_button_def;
-            let child_button_tile_def_handle = child_def.tile_def_handle;
+            let child_button_selection = child_def.selection;
 
             let tile_palette_weak_ref = tile_palette.clone();
             let child_menu_weak_ref = child_menu.downgrade();
@@ -329,8 +412,7 @@ impl TilePaletteMainButton {
                 let mut tile_palette_rc = tile_palette_weak_ref.upgrade().unwrap();
                 let mut child_menu_rc = child_menu_weak_ref.upgrade().unwrap();
 
-                let selection = parent_button_def.to_tile_selection(Some(child_button_tile_def_handle));
-                tile_palette_rc.set_selection_internal(selection);
+                tile_palette_rc.set_selection_internal(child_button_selection);
 
                 // Keep the parent button pressed but close the child menu when we have a selection.
                 child_menu_rc.close(context);
@@ -357,10 +439,22 @@ impl TilePaletteMainButton {
         if let Some(child_menu) = &mut self.child_menu
             && child_menu.is_open()
         {
+            Self::enable_unlocked_children(child_menu, &self.child_building_kinds, context.sim.building_unlocks());
             child_menu.draw(context);
         }
     }
 
+    // Grey out the buildings that are still locked. Unlocks can happen at any time, so refresh while open.
+    fn enable_unlocked_children(child_menu: &mut UiMenu, child_building_kinds: &[BuildingKind], unlocks: &BuildingUnlocks) {
+        let (_, child_button_group) = child_menu.find_widget_of_type_mut::<UiWidgetGroup>().unwrap();
+
+        for (widget, building_kinds) in child_button_group.widgets_mut().iter_mut().zip(child_building_kinds) {
+            if let Some(child_button) = widget.as_any_mut().downcast_mut::<UiTextButton>() {
+                child_button.enable(unlocks.is_building_unlocked(*building_kinds));
+            }
+        }
+    }
+
     fn open_child_menu(&mut self, context: &mut GameUiContext) {
         if let Some(child_menu) = &mut self.child_menu {
             child_menu.open(context);
@@ -373,10 +467,6 @@ impl TilePaletteMainButton {
         }
     }
 
-    fn child_menu_weak_mut(&mut self) -> Option<UiMenuWeakMut> {
-        self.child_menu.as_ref().map(|menu| menu.downgrade())
-    }
-
     fn has_children(&self) -> bool {
         self.child_menu.is_some()
     }
@@ -520,10 +610,65 @@ impl TilePaletteMenu {
         self.selection_renderer.draw(context, self.current_selection);
     }
 
+    // Toggles the main button bound to a build hotkey, as if it was clicked. Returns true if the key was consumed.
+    pub fn handle_build_hotkey(&mut self, context: &mut GameUiContext, key: InputKey, modifiers: InputModifiers) -> bool {
+        let key_bindings = KeyBindings::get();
+
+        let Some(def) = TilePaletteMainButtonDef::iter().find(|def| {
+            def.build_action().is_some_and(|action| key_bindings.is_key_bound_to(action, key, modifiers))
+        }) else {
+            return false;
+        };
+
+        let (_, button) = self.menu.find_widget_with_label_mut::<UiSpriteButton>(def.label_str()).unwrap();
+        if button.is_disabled() {
+            return true;
+        }
+
+        let was_pressed = button.is_pressed();
+        self.reset_selection_internal(context);
+
+        // Pressing the hotkey of the current category again closes it.
+        if !was_pressed {
+            let stay_pressed = self.on_main_button_pressed(def, context);
+            let (_, button) = self.menu.find_widget_with_label_mut::<UiSpriteButton>(def.label_str()).unwrap();
+            button.press(stay_pressed);
+            sound::play(*context.sound_sys(), UiSoundKey::ButtonPressed);
+        }
+
+        true
+    }
+
     // ----------------------
     // Internal:
     // ----------------------
 
+    // Opens the button's child menu or selects its tile directly. Returns whether the button should stay pressed.
+    fn on_main_button_pressed(&mut self, def: TilePaletteMainButtonDef, context: &mut GameUiContext) -> bool {
+        let main_button = self.main_buttons.iter_mut().find(|button| button.def == def).unwrap();
+
+        if main_button.has_children() {
+            main_button.open_child_menu(context);
+            return true;
+        }
+
+        match def {
+            TilePaletteMainButtonDef::Undo => {
+                undo_redo::undo(&context.new_sim_context());
+                false
+            }
+            TilePaletteMainButtonDef::Redo => {
+                undo_redo::redo(&context.new_sim_context());
+                false
+            }
+            _ => {
+                // If parent button has no child menu, choose tile directly here (e.g.: Housing, ClearLand).
+                self.set_selection_internal(def.to_tile_selection());
+                true
+            }
+        }
+    }
+
     fn enable_button(menu: &mut UiMenu, def: TilePaletteMainButtonDef, enable: bool) {
         let (_, button) = menu.find_widget_with_label_mut::<UiSpriteButton>(def.label_str()).unwrap();
 
@@ -596,37 +741,59 @@ impl TileSelectionRenderer {
             let rect = Rect::from_pos_and_size(context.cursor_screen_pos - (CLEAR_ICON_SIZE * 0.5), CLEAR_ICON_SIZE);
 
             context.render_sys().draw_textured_colored_rect(rect, &RectTexCoords::DEFAULT, self.clear_icon, Color::white());
+        } else if let Some(prefab) = current_selection.as_prefab() {
+            // Draw every prefab part at its offset from the cursor cell.
+            let Some(parts) = prefab.resolved_parts() else {
+                return;
+            };
+
+            let cursor_screen_pos = context.cursor_screen_pos;
+            let scaling = context.camera.transform().scaling;
+            let anchor_iso = coords::cell_to_iso(parts[0].0).to_vec2();
+
+            for &(offset, tile_def) in parts {
+                let part_screen_offset = (coords::cell_to_iso(offset).to_vec2() - anchor_iso) * scaling;
+                Self::draw_tile(context, tile_def, cursor_screen_pos + part_screen_offset);
+            }
         } else {
             let selected_tile = current_selection.as_tile_def().unwrap();
-            let rect = Rect::from_pos_and_size(context.cursor_screen_pos, selected_tile.draw_size.to_vec2());
+            let cursor_screen_pos = context.cursor_screen_pos;
+            Self::draw_tile(context, selected_tile, cursor_screen_pos);
+        }
+    }
 
-            let offset = if selected_tile.is(TileKind::Building | TileKind::Rocks | TileKind::Vegetation) {
-                Vec2::new(-(selected_tile.draw_size.width as f32 / 2.0), -(selected_tile.draw_size.height as f32))
-            } else {
-                Vec2::new(-(selected_tile.draw_size.width as f32 / 2.0), -(selected_tile.draw_size.height as f32 / 2.0))
-            };
+    fn draw_tile(context: &mut GameUiContext, selected_tile: &TileDef, screen_pos: Vec2) {
+        let rect = Rect::from_pos_and_size(screen_pos, selected_tile.draw_size.to_vec2());
 
-            let has_valid_placement = context.tile_selection.has_valid_placement();
-            let transform = context.camera.transform();
-
-            let cursor_transform = WorldToScreenTransform::new(transform.scaling, offset);
-            let highlight_color = if has_valid_placement { Color::white() } else { INVALID_TILE_COLOR };
-
-            if let Some(sprite_frame) = selected_tile.anim_frame_by_index(0, 0, 0) {
-                let tile_color = Color::new(
-                    selected_tile.color.r,
-                    selected_tile.color.g,
-                    selected_tile.color.b,
-                    0.7, // Semi-transparent
-                );
-
-                context.render_sys().draw_textured_colored_rect(
-                    cursor_transform.scale_and_offset_rect(rect),
-                    &sprite_frame.tex_info.coords,
-                    sprite_frame.tex_info.texture,
-                    tile_color * highlight_color,
-                );
-            }
+        let offset = if selected_tile.is(TileKind::Building | TileKind::Rocks | TileKind::Vegetation) {
+            Vec2::new(-(selected_tile.draw_size.width as f32 / 2.0), -(selected_tile.draw_size.height as f32))
+        } else {
+            Vec2::new(-(selected_tile.draw_size.width as f32 / 2.0), -(selected_tile.draw_size.height as f32 / 2.0))
+        };
+
+        let has_valid_placement = context.tile_selection.has_valid_placement();
+        let transform = context.camera.transform();
+
+        let cursor_transform = WorldToScreenTransform::new(transform.scaling, offset);
+        let invalid_color = ColorPalette::current().invalid_tile_color();
+        let highlight_color = if has_valid_placement { Color::white() } else { invalid_color };
+
+        if let Some(sprite_frame) = selected_tile.anim_frame_by_index(0, 0, 0) {
+            let tile_color = Color::new(
+                selected_tile.color.r,
+                selected_tile.color.g,
+                selected_tile.color.b,
+                0.7, // Semi-transparent
+            );
+
+            let tex_info = sprite_frame.resolve_tex_info();
+
+            context.render_sys().draw_textured_colored_rect(
+                cursor_transform.scale_and_offset_rect(rect),
+                &tex_info.coords,
+                tex_info.texture,
+                tile_color * highlight_color,
+            );
         }
     }
 }
@@ -34,6 +34,8 @@ use engine::{
 };
 
 use crate::{
+    i18n::tr,
+    key_bindings::{GameAction, KeyBindings},
     sim::SimContext,
     ui_context::GameUiContext,
     save_context::{Load, Save},
@@ -44,7 +46,10 @@ use crate::{
         TileKind,
         TileMapLayerKind,
         placement::{TilePlacementErrReason, TilePlacementOp},
+        prefab::{PrefabConfigs, PrefabDef},
         rendering::TileMapRenderFlags,
+        defense::{self, WallSegment},
+        elevation::{self, ElevationBrush, TerrainElevation},
         road::{self, RoadKind, RoadSegment},
         sets::{PresetTiles, TileDef, TileDefHandle, TileSets},
         water,
@@ -83,6 +88,12 @@ pub enum GameMenusInputArgs {
     },
 }
 
+// Destroys the shared dialog menus, so the next GameMenusSystem created
+// builds them again with the current language strings.
+pub fn terminate_dialog_menus() {
+    dialog::terminate();
+}
+
 // ----------------------------------------------
 // Internal helper functions
 // ----------------------------------------------
@@ -157,6 +168,16 @@ pub trait GameMenusSystem: Any + Save + Load {
 
         update_selection(context, placement_op);
 
+        // Raise/lower terrain under the cursor while the mouse button is held:
+        if let Some(brush) = selection.as_elevation_brush() {
+            if self.palette().wants_to_place_or_clear_tile() {
+                self.placement().paint_elevation(brush, context);
+            } else {
+                self.placement().end_elevation_stroke();
+            }
+            return;
+        }
+
         // Incrementally build road segment (drag and draw segment):
         let is_road_tile_selected = self.palette().is_road_tile_selected();
         if is_road_tile_selected {
@@ -166,8 +187,16 @@ pub trait GameMenusSystem: Any + Save + Load {
             }
         }
 
-        // Place a regular (non-road) tile or clear a tile:
-        if !is_road_tile_selected && self.palette().wants_to_place_or_clear_tile() {
+        // Walls are also placed in drag strokes:
+        let is_wall_tile_selected = self.palette().is_wall_tile_selected();
+        if is_wall_tile_selected {
+            if let Some((start, end)) = range_selection_cells(context) {
+                self.placement().update_wall_segment(start, end, context);
+            }
+        }
+
+        // Place a regular (non-road/wall) tile or clear a tile:
+        if !is_road_tile_selected && !is_wall_tile_selected && self.palette().wants_to_place_or_clear_tile() {
             enum PlacementResult {
                 ClearedTile,
                 PlacedTile,
@@ -236,8 +265,10 @@ pub trait GameMenusSystem: Any + Save + Load {
         match args {
             GameMenusInputArgs::Key { key, action, modifiers } => {
                 if action == InputAction::Press {
+                    let key_bindings = KeyBindings::get();
+
                     // [ESCAPE]: Clear current selection / close tile inspector.
-                    if key == InputKey::Escape {
+                    if key_bindings.is_key_bound_to(GameAction::Cancel, key, modifiers) {
                         self.palette().on_tile_placement_canceled(context);
                         clear_selection(context);
                         if let Some(tile_inspector) = self.tile_inspector() {
@@ -246,27 +277,25 @@ pub trait GameMenusSystem: Any + Save + Load {
                         return UiInputEvent::Handled;
                     }
 
-                    let shift = modifiers.intersects(InputModifiers::Shift);
-                    let ctrl_or_cmd = modifiers.intersects(InputModifiers::Control | InputModifiers::Super);
-
                     // [SHIFT]+[CTRL]+[Z] / [SHIFT]+[CMD]+[Z] (MacOS): Redo last action.
-                    if key == InputKey::Z && ctrl_or_cmd && shift {
+                    if key_bindings.is_key_bound_to(GameAction::Redo, key, modifiers) {
                         undo_redo::redo(&context.new_sim_context());
                         return UiInputEvent::Handled;
                     }
 
                     // [CTRL]+[Z] / [CMD]+[Z] (MacOs): Undo last action.
-                    if key == InputKey::Z && ctrl_or_cmd {
+                    if key_bindings.is_key_bound_to(GameAction::Undo, key, modifiers) {
                         undo_redo::undo(&context.new_sim_context());
                         return UiInputEvent::Handled;
                     }
                 }
             }
-            GameMenusInputArgs::Mouse { button, action, .. } => {
+            GameMenusInputArgs::Mouse { button, action, modifiers } => {
                 let is_road_tile_selected = self.palette().is_road_tile_selected();
+                let is_wall_tile_selected = self.palette().is_wall_tile_selected();
                 let is_clear_selected = self.palette().current_selection().is_clear();
 
-                if !is_road_tile_selected && !is_clear_selected && self.palette().has_selection() {
+                if !is_road_tile_selected && !is_wall_tile_selected && !is_clear_selected && self.palette().has_selection() {
                     let input_event = self.palette().on_mouse_button(button, action);
                     if input_event.not_handled() {
                         // Mouse button click other than [LEFT_BTN], clear selection state.
@@ -276,15 +305,22 @@ pub trait GameMenusSystem: Any + Save + Load {
                     return input_event;
                 }
 
+                // Grab the dragged range before the selection handler resets it on button release.
+                let multi_select_range = range_selection_cells(context);
+
                 if selection_handle_mouse_button(context, button, action) {
                     // Handle road placement (drag and draw segment).
-                    if is_road_tile_selected {
-                        // Place road segment if valid & we can afford it.
-                        let result = self.placement().try_place_road_segment(context);
+                    if is_road_tile_selected || is_wall_tile_selected {
+                        // Place road/wall segment if valid & we can afford it.
+                        let result = if is_road_tile_selected {
+                            self.placement().try_place_road_segment(context)
+                        } else {
+                            self.placement().try_place_wall_segment(context)
+                        };
                         match result {
-                            PlaceRoadSegmentResult::Placed => self.palette().on_road_segment_placed(context),
-                            PlaceRoadSegmentResult::Failed => self.palette().on_tile_placement_failed(context),
-                            PlaceRoadSegmentResult::Empty => {}
+                            PlaceSegmentResult::Placed => self.palette().on_road_segment_placed(context),
+                            PlaceSegmentResult::Failed => self.palette().on_tile_placement_failed(context),
+                            PlaceSegmentResult::Empty => {}
                         }
                     } else if is_clear_selected && !context.tile_selection.cells().is_empty() {
                         // Clear batch of selected tiles:
@@ -349,11 +385,29 @@ pub trait GameMenusSystem: Any + Save + Load {
                 }
 
                 // Left click on a tile can open the TileInspector:
-                if action == InputAction::Press && button == MouseButton::Left {
-                    // Open inspector only if we're not in road placement or clear mode.
-                    if !is_road_tile_selected && !is_clear_selected {
+                if button == MouseButton::Left {
+                    // Open inspector only if we're not in road/wall placement or clear mode.
+                    if !is_road_tile_selected && !is_wall_tile_selected && !is_clear_selected {
                         if let Some(tile_inspector) = self.tile_inspector() {
-                            if context.topmost_selected_tile().is_some() {
+                            // [CTRL]+click / [CTRL]+drag: Add or remove tiles from the inspector's multi-selection.
+                            if modifiers.intersects(InputModifiers::Control) && tile_inspector.supports_multi_select() {
+                                if action == InputAction::Press {
+                                    context.tile_selection.begin_multi_select();
+                                } else if action == InputAction::Release
+                                    && let Some((start, end)) = multi_select_range
+                                {
+                                    context.tile_selection.multi_select_range(context.tile_map, start, end);
+                                    if context.tile_selection.multi_selection().is_empty() {
+                                        tile_inspector.close(context);
+                                    } else {
+                                        tile_inspector.open(context);
+                                    }
+                                }
+                                return UiInputEvent::Handled;
+                            }
+
+                            if action == InputAction::Press && context.topmost_selected_tile().is_some() {
+                                context.tile_selection.clear_multi_selection();
                                 tile_inspector.open(context);
                                 context.tile_selection.reset();
                                 return UiInputEvent::Handled;
@@ -400,7 +454,7 @@ impl PlaceOrClearResult {
     }
 }
 
-enum PlaceRoadSegmentResult {
+enum PlaceSegmentResult {
     Placed,
     Failed,
     Empty,
@@ -408,14 +462,44 @@ enum PlaceRoadSegmentResult {
 
 pub struct TilePlacement {
     current_road_segment: RoadSegment, // For road placement.
+    current_wall_segment: WallSegment, // For wall placement.
+    elevation_stroke_target: Option<TerrainElevation>, // Level the current terrain brush stroke is moving towards.
 }
 
 impl TilePlacement {
     pub fn new() -> Self {
-        Self { current_road_segment: RoadSegment::default() }
+        Self {
+            current_road_segment: RoadSegment::default(),
+            current_wall_segment: WallSegment::default(),
+            elevation_stroke_target: None,
+        }
     }
 
-    fn try_place_road_segment(&mut self, context: &mut GameUiContext) -> PlaceRoadSegmentResult {
+    // NOTE: Elevation edits are not recorded by undo/redo.
+    fn paint_elevation(&mut self, brush: ElevationBrush, context: &mut GameUiContext) {
+        let target_cell = context.tile_map.find_exact_cell_for_point(
+            TileMapLayerKind::Terrain,
+            context.cursor_screen_pos,
+            context.camera.transform(),
+        );
+
+        if !target_cell.is_valid() {
+            return;
+        }
+
+        // The first cell brushed decides the level for the whole stroke.
+        let target = *self
+            .elevation_stroke_target
+            .get_or_insert_with(|| brush.stroke_target(context.tile_map.terrain_elevation(target_cell)));
+
+        elevation::paint(context.tile_map, target_cell, elevation::ELEVATION_BRUSH_RADIUS, brush, target);
+    }
+
+    fn end_elevation_stroke(&mut self) {
+        self.elevation_stroke_target = None;
+    }
+
+    fn try_place_road_segment(&mut self, context: &mut GameUiContext) -> PlaceSegmentResult {
         let road_segment_is_empty = self.current_road_segment.is_empty();
 
         // Place road segment if valid & we can afford it:
@@ -454,14 +538,68 @@ impl TilePlacement {
         }
 
         if road_segment_is_empty {
-            PlaceRoadSegmentResult::Empty
+            PlaceSegmentResult::Empty
         } else if is_valid_road_placement {
-            PlaceRoadSegmentResult::Placed
+            PlaceSegmentResult::Placed
+        } else {
+            PlaceSegmentResult::Failed
+        }
+    }
+
+    fn try_place_wall_segment(&mut self, context: &mut GameUiContext) -> PlaceSegmentResult {
+        let wall_segment_is_empty = self.current_wall_segment.is_empty();
+
+        // Place wall segment if valid & we can afford it:
+        let is_valid_wall_placement = !wall_segment_is_empty
+            && self.current_wall_segment.is_valid
+            && can_afford_cost(context, self.current_wall_segment.cost());
+
+        if is_valid_wall_placement && let Some(wall_tile_def) = self.current_wall_segment.tile_def() {
+            let sim_context = context.new_sim_context();
+            let spawner = Spawner::new(&sim_context);
+
+            for cell in &self.current_wall_segment.path {
+                spawner.try_spawn_tile_with_def(*cell, wall_tile_def);
+            }
+
+            undo_redo::record(
+                EditAction::PlacedTiles,
+                &self.current_wall_segment.path,
+                EditedLayer::Terrain,
+                context.tile_map,
+                context.world,
+            );
+        }
+
+        // Clear wall segment highlight:
+        if !wall_segment_is_empty {
+            defense::mark_tiles(context.tile_map, &self.current_wall_segment, false, false);
+            self.current_wall_segment.clear();
+            clear_selection(context);
+        }
+
+        if wall_segment_is_empty {
+            PlaceSegmentResult::Empty
+        } else if is_valid_wall_placement {
+            PlaceSegmentResult::Placed
         } else {
-            PlaceRoadSegmentResult::Failed
+            PlaceSegmentResult::Failed
         }
     }
 
+    fn update_wall_segment(&mut self, start: Cell, end: Cell, context: &mut GameUiContext) {
+        // Clear previous segment highlight:
+        defense::mark_tiles(context.tile_map, &self.current_wall_segment, false, false);
+
+        self.current_wall_segment = defense::build_segment(context.tile_map, start, end);
+
+        let is_valid_wall_placement =
+            self.current_wall_segment.is_valid && can_afford_cost(context, self.current_wall_segment.cost());
+
+        // Highlight new segment:
+        defense::mark_tiles(context.tile_map, &self.current_wall_segment, true, is_valid_wall_placement);
+    }
+
     fn update_road_segment(&mut self, road_kind: RoadKind, start: Cell, end: Cell, context: &mut GameUiContext) {
         // Clear previous segment highlight:
         road::mark_tiles(context.tile_map, &self.current_road_segment, false, false);
@@ -476,8 +614,21 @@ impl TilePlacement {
     }
 
     fn placement_operation(&self, selection: TilePaletteSelection, context: &mut GameUiContext) -> TilePlacementOp {
-        if let Some(tile_def) = selection.as_tile_def() {
-            if Spawner::new(&context.new_sim_context()).can_afford_tile(tile_def) {
+        if let Some(prefab) = selection.as_prefab() {
+            // Highlight the anchor tile, validated against the combined prefab cost and unlocks.
+            let Some(anchor_tile_def) = prefab.anchor_tile_def() else {
+                return TilePlacementOp::None;
+            };
+            let sim_context = context.new_sim_context();
+            if prefab.can_afford(&sim_context) && prefab.is_unlocked(&sim_context) {
+                TilePlacementOp::Place(anchor_tile_def)
+            } else {
+                TilePlacementOp::Invalidate(anchor_tile_def)
+            }
+        } else if let Some(tile_def) = selection.as_tile_def() {
+            let sim_context = context.new_sim_context();
+            let spawner = Spawner::new(&sim_context);
+            if spawner.can_afford_tile(tile_def) && spawner.is_tile_unlocked(tile_def) {
                 TilePlacementOp::Place(tile_def)
             } else {
                 TilePlacementOp::Invalidate(tile_def)
@@ -492,7 +643,20 @@ impl TilePlacement {
     fn try_place_or_clear_tile(selection: TilePaletteSelection, context: &mut GameUiContext) -> PlaceOrClearResult {
         // If we have a selection, place it. Otherwise we want to try removing the tile
         // under the cursor. Do not remove terrain tiles though.
-        if let Some(tile_def) = selection.as_tile_def() {
+        if let Some(prefab) = selection.as_prefab() {
+            if let Some(anchor_tile_def) = prefab.anchor_tile_def() {
+                let target_cell = context.tile_map.find_exact_cell_for_point(
+                    anchor_tile_def.layer_kind(),
+                    context.cursor_screen_pos,
+                    context.camera.transform(),
+                );
+
+                if target_cell.is_valid() {
+                    let sim_context = context.new_sim_context();
+                    return prefab.try_place(&sim_context, target_cell);
+                }
+            }
+        } else if let Some(tile_def) = selection.as_tile_def() {
             let target_cell = context.tile_map.find_exact_cell_for_point(
                 tile_def.layer_kind(),
                 context.cursor_screen_pos,
@@ -501,7 +665,7 @@ impl TilePlacement {
 
             if target_cell.is_valid() {
                 let sim_context = context.new_sim_context();
-                return Self::place(&sim_context, target_cell, tile_def, true, true);
+                return Self::place(&sim_context, target_cell, tile_def, true, true, true);
             }
         } else if selection.is_clear() {
             // Clear/remove tile:
@@ -522,10 +686,12 @@ impl TilePlacement {
         target_cell: Cell,
         tile_def: &'static TileDef,
         subtract_tile_cost: bool,
+        start_construction: bool,
         undo_redo: bool,
     ) -> PlaceOrClearResult {
         let mut spawner = Spawner::new(context);
         spawner.set_subtract_tile_cost(subtract_tile_cost);
+        spawner.set_start_construction(start_construction);
 
         let spawn_result = spawner.try_spawn_tile_with_def(target_cell, tile_def);
         match &spawn_result {
@@ -534,6 +700,8 @@ impl TilePlacement {
                 road::update_junctions(context.tile_map_mut(), target_cell);
                 // In case we've placed a water tile or replaced water with terrain.
                 water::update_transitions(context.tile_map_mut(), target_cell);
+                // New terrain tile on a slope or cliff edge.
+                elevation::update_transitions(context.tile_map_mut(), target_cell);
             }
             SpawnerResult::Building(_) if water::is_port_or_wharf(tile_def) => {
                 // If we've placed a port/wharf, select the correct
@@ -572,9 +740,11 @@ impl TilePlacement {
         let is_terrain = tile.is(TileKind::Terrain);
         let is_road = tile_def.is_road();
         let is_vacant_lot = tile_def.is_vacant_lot();
+        let is_defense = tile_def.is_defense();
+        let is_aqueduct = tile_def.is_aqueduct();
 
-        // Cannot explicit remove terrain tiles except for roads and vacant lots.
-        if !is_terrain || is_road || is_vacant_lot {
+        // Cannot explicit remove terrain tiles except for roads, vacant lots, walls and aqueducts.
+        if !is_terrain || is_road || is_vacant_lot || is_defense || is_aqueduct {
             let target_cell = tile.base_cell();
 
             if undo_redo {
@@ -587,7 +757,7 @@ impl TilePlacement {
 
             spawner.despawn_tile(tile);
 
-            if is_road || is_vacant_lot {
+            if is_road || is_vacant_lot || is_defense || is_aqueduct {
                 // Replace removed road tile with a regular terrain tile.
                 if let Some(terrain_tile_def) = PresetTiles::Grass.find_tile_def() {
                     if let SpawnerResult::Err(err) = spawner.try_spawn_tile_with_def(target_cell, terrain_tile_def) {
@@ -615,8 +785,10 @@ impl TilePlacement {
         let is_terrain = tile.is(TileKind::Terrain);
         let is_road = tile_def.is_road();
         let is_vacant_lot = tile_def.is_vacant_lot();
+        let is_defense = tile_def.is_defense();
+        let is_aqueduct = tile_def.is_aqueduct();
 
-        !is_terrain || is_road || is_vacant_lot
+        !is_terrain || is_road || is_vacant_lot || is_defense || is_aqueduct
     }
 }
 
@@ -630,6 +802,8 @@ pub enum TilePaletteSelection {
     None,
     Clear,
     Tile(TileDefHandle),
+    Prefab(usize), // Index into PrefabConfigs.
+    Elevation(ElevationBrush),
 }
 
 impl TilePaletteSelection {
@@ -649,6 +823,14 @@ impl TilePaletteSelection {
         matches!(self, Self::Tile(_))
     }
 
+    pub fn is_prefab(&self) -> bool {
+        matches!(self, Self::Prefab(_))
+    }
+
+    pub fn is_elevation_brush(&self) -> bool {
+        matches!(self, Self::Elevation(_))
+    }
+
     pub fn is_tile_kind(&self, kinds: TileKind) -> bool {
         if let Some(tile_def) = self.as_tile_def() {
             return tile_def.is(kinds);
@@ -662,6 +844,20 @@ impl TilePaletteSelection {
             _ => None,
         }
     }
+
+    pub fn as_prefab(&self) -> Option<&'static PrefabDef> {
+        match self {
+            Self::Prefab(index) => PrefabConfigs::get().prefab(*index),
+            _ => None,
+        }
+    }
+
+    pub fn as_elevation_brush(&self) -> Option<ElevationBrush> {
+        match self {
+            Self::Elevation(brush) => Some(*brush),
+            _ => None,
+        }
+    }
 }
 
 // ----------------------------------------------
@@ -689,6 +885,10 @@ pub trait TilePalette {
         self.current_selection().as_tile_def().is_some_and(|tile_def| tile_def.is_road())
     }
 
+    fn is_wall_tile_selected(&self) -> bool {
+        self.current_selection().as_tile_def().is_some_and(defense::is_wall_tile_def)
+    }
+
     fn selected_road_kind(&self) -> RoadKind {
         if let Some(tile_def) = self.current_selection().as_tile_def() {
             if tile_def.is_road() {
@@ -710,6 +910,11 @@ pub trait TilePalette {
 pub trait TileInspector {
     fn open(&mut self, context: &mut GameUiContext);
     fn close(&mut self, context: &mut GameUiContext);
+
+    // Inspectors that can edit several tiles at once read them from TileSelection::multi_selection().
+    fn supports_multi_select(&self) -> bool {
+        false
+    }
 }
 
 // ----------------------------------------------
@@ -786,7 +991,7 @@ trait ButtonDef: Sized + Copy + Clone + EnumProperty {
             // Only give it a tooltip if we have a Tooltip property.
             if self.has_custom_tooltip() {
                 Some(UiTooltipText::new(context, UiTooltipTextParams {
-                    text: self.tooltip(),
+                    text: tr!(&self.tooltip()),
                     font_scale: TOOLTIP_FONT_SCALE,
                     background: Some(TOOLTIP_BACKGROUND_SPRITE),
                 }))
@@ -796,7 +1001,7 @@ trait ButtonDef: Sized + Copy + Clone + EnumProperty {
         };
 
         UiTextButton::new(context, UiTextButtonParams {
-            label: self.label(),
+            label: tr!(self.label_str()),
             tooltip,
             hover: Some(TEXT_BUTTON_HOVERED_SPRITE),
             sounds_enabled,
@@ -818,7 +1023,7 @@ trait ButtonDef: Sized + Copy + Clone + EnumProperty {
     ) -> UiSpriteButton {
         // Always give a tooltip for sprite buttons.
         let tooltip = UiTooltipText::new(context, UiTooltipTextParams {
-            text: self.tooltip(),
+            text: tr!(&self.tooltip()),
             font_scale: TOOLTIP_FONT_SCALE,
             background: Some(TOOLTIP_BACKGROUND_SPRITE),
         });
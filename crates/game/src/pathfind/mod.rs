@@ -5,6 +5,7 @@ use std::{
     cmp::Reverse,
     hash::{DefaultHasher, Hash, Hasher},
     ops::{Index, IndexMut},
+    sync::atomic::{AtomicU64, Ordering},
 };
 use rand::Rng;
 use arrayvec::ArrayVec;
@@ -16,8 +17,15 @@ use common::{
     Color,
     bitflags_with_display,
     coords::{Cell, CellRange},
+    hash::PreHashedKeyMap,
+};
+use crate::tile::{
+    TileFlags,
+    TileKind,
+    TileMap,
+    TileMapLayerRefs,
+    elevation::{self, TerrainElevation},
 };
-use crate::tile::{TileFlags, TileKind, TileMap, TileMapLayerKind, TileMapLayerRefs};
 
 #[cfg(test)]
 mod tests;
@@ -46,6 +54,10 @@ bitflags_with_display! {
         const Rocks              = 1 << 8;
         const Vegetation         = 1 << 9;
         const HarvestableTree    = 1 << 10;
+        const Wall               = 1 << 11; // City walls and towers.
+        const Gate               = 1 << 12; // Wall gate; the Graph also flags it as Road.
+        const Aqueduct           = 1 << 13; // Carries water from reservoirs to fountains.
+        const Reservoir          = 1 << 14; // Reservoir building; water source of the aqueduct network.
     }
 }
 
@@ -107,6 +119,26 @@ impl NodeKind {
         self.intersects(Self::HarvestableTree)
     }
 
+    #[inline]
+    pub fn is_wall(self) -> bool {
+        self.intersects(Self::Wall)
+    }
+
+    #[inline]
+    pub fn is_gate(self) -> bool {
+        self.intersects(Self::Gate)
+    }
+
+    #[inline]
+    pub fn is_aqueduct(self) -> bool {
+        self.intersects(Self::Aqueduct)
+    }
+
+    #[inline]
+    pub fn is_reservoir(self) -> bool {
+        self.intersects(Self::Reservoir)
+    }
+
     #[inline]
     pub fn is_prop(self) -> bool {
         self.intersects(Self::Rocks | Self::Vegetation)
@@ -135,6 +167,11 @@ impl NodeKind {
                       | Self::Rocks)
     }
 
+    #[inline]
+    pub fn is_water_object_placeable(self) -> bool {
+        self.intersects(Self::Water)
+    }
+
     #[inline]
     pub fn is_object_placeable(self) -> bool {
         self.intersects(Self::EmptyLand)
@@ -152,9 +189,13 @@ impl NodeKind {
         // NOTE: If multiple flags are set, higher on this list will match first and win.
         map_to_color!(self, BuildingAccess,     Color::new(0.50, 0.50, 0.50, 1.0)); // light gray
         map_to_color!(self, BuildingRoadLink,   Color::new(1.00, 0.00, 0.00, 1.0)); // red
+        map_to_color!(self, Reservoir,          Color::new(0.15, 0.45, 0.95, 1.0)); // blue
         map_to_color!(self, Building,           Color::new(0.66, 0.23, 0.74, 1.0)); // purple
         map_to_color!(self, VacantLot,          Color::new(0.00, 0.90, 0.90, 1.0)); // cyan
         map_to_color!(self, SettlersSpawnPoint, Color::new(0.66, 0.13, 0.13, 1.0)); // dark red
+        map_to_color!(self, Gate,               Color::new(0.90, 0.55, 0.10, 1.0)); // orange
+        map_to_color!(self, Wall,               Color::new(0.85, 0.85, 0.85, 1.0)); // white
+        map_to_color!(self, Aqueduct,           Color::new(0.40, 0.75, 1.00, 1.0)); // light blue
         map_to_color!(self, Rocks,              Color::new(0.20, 0.20, 0.20, 1.0)); // dark gray
         map_to_color!(self, HarvestableTree,    Color::new(0.10, 0.85, 0.15, 1.0)); // green
         map_to_color!(self, Vegetation,         Color::new(0.00, 0.45, 0.00, 1.0)); // dark green
@@ -206,6 +247,22 @@ impl Node {
         ]
     }
 
+    // 4 diagonal neighbor cells of this node's cell.
+    #[inline]
+    pub fn diagonal_neighbors(self) -> [Node; 4] {
+        [
+            Node::new(Cell::new(self.cell.x + 1, self.cell.y + 1)), // top-right
+            Node::new(Cell::new(self.cell.x - 1, self.cell.y + 1)), // top-left
+            Node::new(Cell::new(self.cell.x + 1, self.cell.y - 1)), // bottom-right
+            Node::new(Cell::new(self.cell.x - 1, self.cell.y - 1)), // bottom-left
+        ]
+    }
+
+    #[inline]
+    pub fn is_diagonal_to(self, other: Node) -> bool {
+        (self.cell.x - other.cell.x).abs() == 1 && (self.cell.y - other.cell.y).abs() == 1
+    }
+
     #[inline]
     pub fn manhattan_distance(self, other: Node) -> i32 {
         (self.cell.x - other.cell.x).abs() + (self.cell.y - other.cell.y).abs()
@@ -292,18 +349,6 @@ impl<T> IndexMut<Node> for Grid<T> {
     }
 }
 
-// ----------------------------------------------
-// GraphUpdateAction
-// ----------------------------------------------
-
-pub enum GraphUpdateAction {
-    TilePlaced(CellRange, TileMapLayerKind, NodeKind),
-    TileCleared(CellRange, TileMapLayerKind, TileKind),
-    TileDefEdited(CellRange, TileMapLayerKind, TileKind, NodeKind),
-    TileMoved(CellRange, CellRange, TileMapLayerKind, TileKind, NodeKind),
-    TileFlagsChanged(CellRange, TileFlags, NodeKind),
-}
-
 // ----------------------------------------------
 // Graph
 // ----------------------------------------------
@@ -314,16 +359,49 @@ pub struct Graph {
     grid: Grid<NodeKind>,               // WxH nodes grid.
     vacant_lots: usize,                 // VacantLot count.
     settlers_spawn_point: Option<Node>, // Cached SettlersSpawnPoint for fast query.
+    road_network_version: u32,          // Bumped whenever road or road link nodes change.
+    region_edits: Grid<u64>,            // Edit stamp of the last node change in each region (see PATH_REGION_SIZE).
+    last_edit: u64,                     // Latest edit stamp of any region.
+    road_components: RoadComponents,    // Connectivity index over the Road nodes.
+    wall_network_version: u32,          // Bumped whenever wall or gate nodes change.
+    wall_enclosure: WallEnclosure,      // Nodes enclosed by walls.
+    elevation: Grid<TerrainElevation>,  // Terrain elevation of each node. Steep steps between nodes are impassable.
+    water_network_version: u32,         // Bumped whenever aqueduct or reservoir nodes change.
+    water_network: WaterNetwork,        // Aqueduct/reservoir nodes reached by water.
+}
+
+// Graph is split into square regions of this many cells for the PathCache
+// invalidation. Each region remembers when one of its nodes last changed.
+pub const PATH_REGION_SIZE: i32 = 8;
+
+// Edit stamps come from a global counter so stamps are never reused across
+// graph instances: a freshly created graph is stamped as fully edited, which
+// invalidates any cached path computed against a previous graph.
+static GRAPH_EDIT_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+#[inline]
+fn next_graph_edit_stamp() -> u64 {
+    GRAPH_EDIT_COUNTER.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Graph {
     pub fn with_empty_grid(grid_size: Size) -> Self {
         debug_assert!(grid_size.is_valid());
         let node_count = (grid_size.width * grid_size.height) as usize;
+        let (region_edits, last_edit) = Self::new_region_edits(grid_size);
         Self {
             grid: Grid::new(grid_size, vec![NodeKind::empty(); node_count]),
             vacant_lots: 0,
             settlers_spawn_point: None,
+            road_network_version: 0,
+            region_edits,
+            last_edit,
+            road_components: RoadComponents::new(node_count),
+            wall_network_version: 0,
+            wall_enclosure: WallEnclosure::default(),
+            elevation: Grid::new(grid_size, vec![0; node_count]),
+            water_network_version: 0,
+            water_network: WaterNetwork::new(node_count),
         }
     }
 
@@ -333,11 +411,25 @@ impl Graph {
         debug_assert!(!node_kind.intersects(NodeKind::SettlersSpawnPoint), "SettlersSpawnPoint cannot be specified here!");
 
         let node_count = (grid_size.width * grid_size.height) as usize;
-        Self {
+        let (region_edits, last_edit) = Self::new_region_edits(grid_size);
+        let mut graph = Self {
             grid: Grid::new(grid_size, vec![node_kind; node_count]),
             vacant_lots: if node_kind.intersects(NodeKind::VacantLot) { node_count } else { 0 },
             settlers_spawn_point: None,
-        }
+            road_network_version: 0,
+            region_edits,
+            last_edit,
+            road_components: RoadComponents::new(node_count),
+            wall_network_version: 0,
+            wall_enclosure: WallEnclosure::default(),
+            elevation: Grid::new(grid_size, vec![0; node_count]),
+            water_network_version: 0,
+            water_network: WaterNetwork::new(node_count),
+        };
+        graph.rebuild_road_components();
+        graph.rebuild_wall_enclosure();
+        graph.rebuild_water_network();
+        graph
     }
 
     pub fn with_node_grid(grid_size: Size, nodes: Vec<NodeKind>) -> Self {
@@ -360,11 +452,26 @@ impl Graph {
             }
         }
 
-        Self {
+        let node_count = nodes.len();
+        let (region_edits, last_edit) = Self::new_region_edits(grid_size);
+        let mut graph = Self {
             grid: Grid::new(grid_size, nodes),
             vacant_lots,
             settlers_spawn_point,
-        }
+            road_network_version: 0,
+            region_edits,
+            last_edit,
+            road_components: RoadComponents::new(node_count),
+            wall_network_version: 0,
+            wall_enclosure: WallEnclosure::default(),
+            elevation: Grid::new(grid_size, vec![0; node_count]),
+            water_network_version: 0,
+            water_network: WaterNetwork::new(node_count),
+        };
+        graph.rebuild_road_components();
+        graph.rebuild_wall_enclosure();
+        graph.rebuild_water_network();
+        graph
     }
 
     pub fn from_tile_map(tile_map: &TileMap) -> Self {
@@ -381,134 +488,58 @@ impl Graph {
         self.grid.fill(NodeKind::empty());
         self.vacant_lots = 0;
         self.settlers_spawn_point = None;
-    }
+        self.road_network_version = self.road_network_version.wrapping_add(1);
 
-    pub fn rebuild_from_tile_map(&mut self, tile_map: &TileMap) {
-        // We assume size hasn't changed.
-        debug_assert_eq!(self.grid_size(), tile_map.size_in_cells());
+        self.last_edit = next_graph_edit_stamp();
+        self.region_edits.fill(self.last_edit);
 
-        let layers = tile_map.layers();
+        self.road_components = RoadComponents::new(self.grid.nodes.len());
 
-        // Terrain layer:
-        layers.for_each_tile(TileKind::Terrain, |layers, tile| {
-            self.update(layers, GraphUpdateAction::TilePlaced(
-                tile.cell_range(),
-                TileMapLayerKind::Terrain,
-                tile.path_kind(),
-            ));
-        });
+        self.wall_network_version = self.wall_network_version.wrapping_add(1);
+        self.wall_enclosure = WallEnclosure::default();
 
-        // Objects layer:
-        layers.for_each_tile(Self::OBJECT_KINDS, |layers, tile| {
-            self.update(layers, GraphUpdateAction::TilePlaced(
-                tile.cell_range(),
-                TileMapLayerKind::Objects,
-                tile.path_kind(),
-            ));
-        });
-    }
+        self.elevation.fill(0);
 
-    pub fn update(&mut self, layers: TileMapLayerRefs, action: GraphUpdateAction) {
-        fn tile_placed(
-            graph: &mut Graph,
-            layers: TileMapLayerRefs,
-            cell_range: CellRange,
-            layer_kind: TileMapLayerKind,
-            path_kind: NodeKind,
-        ) {
-            match layer_kind {
-                TileMapLayerKind::Terrain => {
-                    // Terrain tiles always occupy a single cell.
-                    graph.set_node_kind(Node::new(cell_range.start), path_kind);
-                }
-                TileMapLayerKind::Objects => {
-                    for cell in &cell_range {
-                        graph.set_node_kind(Node::new(cell), path_kind);
-                    }
-
-                    if path_kind.is_building() {
-                        debug_assert!(layer_kind == TileMapLayerKind::Objects);
-
-                        // Add surrounding building access nodes:
-                        for_each_surrounding_cell(cell_range, |cell| {
-                            if !layers.has_tile(cell, Graph::OBJECT_KINDS)
-                                && layers.is_cell_within_bounds(cell)
-                            {
-                                graph.append_node_kind_internal(Node::new(cell), NodeKind::BuildingAccess);
-                            }
-                            true // Continue to next.
-                        });
-                    }
-                }
-            }
-        }
+        self.water_network_version = self.water_network_version.wrapping_add(1);
+        self.water_network = WaterNetwork::new(self.grid.nodes.len());
+    }
 
-        fn tile_cleared(
-            graph: &mut Graph,
-            layers: TileMapLayerRefs,
-            cell_range: CellRange,
-            layer_kind: TileMapLayerKind,
-            tile_kind: TileKind,
-        ) {
-            debug_assert!(layer_kind == tile_kind.layer_kind());
+    pub fn rebuild_from_tile_map(&mut self, tile_map: &TileMap) {
+        // We assume size hasn't changed.
+        debug_assert_eq!(self.grid_size(), tile_map.size_in_cells());
 
-            match layer_kind {
-                TileMapLayerKind::Terrain => {
-                    // Terrain tiles always occupy a single cell.
-                    graph.set_node_kind(Node::new(cell_range.start), NodeKind::empty());
-                }
-                TileMapLayerKind::Objects => {
-                    for cell in &cell_range {
-                        // Restore the underlying Terrain's path_kind so the
-                        // node reflects the true tile-map state (e.g. a
-                        // VacantLot/Road/Water terrain re-emerges when the
-                        // Object above is removed).
-                        let terrain_kind = layers
-                            .find_tile(cell, TileKind::Terrain)
-                            .map(|t| t.path_kind())
-                            .unwrap_or(NodeKind::EmptyLand);
-                        graph.set_node_kind(Node::new(cell), terrain_kind);
-                    }
-
-                    if tile_kind.intersects(TileKind::Building | TileKind::Blocker) {
-                        // Clear surrounding building access nodes:
-                        for_each_surrounding_cell(cell_range, |cell| {
-                            if !layers.has_tile(cell, Graph::OBJECT_KINDS)
-                                && layers.is_cell_within_bounds(cell)
-                            {
-                                graph.clear_node_kind_internal(Node::new(cell), NodeKind::BuildingAccess);
-                            }
-                            true // Continue to next.
-                        });
-                    }
-                }
-            }
+        let grid_size = self.grid_size();
+        if grid_size.is_valid() {
+            let all_cells = CellRange::new(Cell::zero(), Cell::new(grid_size.width - 1, grid_size.height - 1));
+            self.update_cells(tile_map.layers(), all_cells);
         }
+    }
 
-        match action {
-            GraphUpdateAction::TilePlaced(cell_range, layer_kind, path_kind) => {
-                tile_placed(self, layers, cell_range, layer_kind, path_kind);
-            }
-            GraphUpdateAction::TileCleared(cell_range, layer_kind, tile_kind) => {
-                tile_cleared(self, layers, cell_range, layer_kind, tile_kind);
-            }
-            GraphUpdateAction::TileDefEdited(cell_range, layer_kind, tile_kind, path_kind) => {
-                // NOTE: Simulate TileCleared followed by TilePlaced to refresh the graph state.
-                tile_cleared(self, layers, cell_range, layer_kind, tile_kind);
-                tile_placed(self,  layers, cell_range, layer_kind, path_kind);
-            }
-            GraphUpdateAction::TileMoved(from_cells, to_cells, layer_kind, tile_kind, path_kind) => {
-                debug_assert!(from_cells.size() == to_cells.size());
-                // NOTE: Same as TileCleared(from_cells) followed by TilePlaced(to_cells).
-                tile_cleared(self, layers, from_cells, layer_kind, tile_kind);
-                tile_placed(self,  layers, to_cells,   layer_kind, path_kind);
-            }
-            GraphUpdateAction::TileFlagsChanged(cell_range, new_flags, path_kind) => {
-                if Self::tile_flags_affect_node_kind(new_flags) {
-                    self.set_node_kind(Node::new(cell_range.start), path_kind);
-                }
+    // Recomputes the nodes under `cell_range` plus the ring of cells around it from
+    // the current tile map state. Called by the TileMap after every tile placement,
+    // removal, move or flags change with the cells that tile covers, so only those
+    // nodes and the BuildingAccess annotations of their neighbors are touched.
+    // Nodes are derived from scratch rather than patched, so the result always
+    // matches a full rebuild_from_tile_map() (e.g. access nodes shared by two
+    // adjacent buildings survive when one of them is removed).
+    pub fn update_cells(&mut self, layers: TileMapLayerRefs, cell_range: CellRange) {
+        let expanded_range = CellRange::new(
+            Cell::new(cell_range.start.x - 1, cell_range.start.y - 1),
+            Cell::new(cell_range.end.x + 1, cell_range.end.y + 1),
+        );
+
+        for cell in &expanded_range {
+            let node = Node::new(cell);
+            if self.grid.is_node_within_bounds(node) {
+                // Elevation first, so new road nodes only join neighbors they can actually reach.
+                self.set_node_elevation_internal(node, Self::elevation_from_tiles(layers, cell));
+                self.set_node_kind_internal(node, Self::node_kind_from_tiles(layers, cell));
             }
         }
+
+        self.flush_road_components();
+        self.flush_wall_enclosure();
+        self.flush_water_network();
     }
 
     // True if the given TileFlags affect NodeKind flags and a Graph update should be performed.
@@ -521,6 +552,17 @@ impl Graph {
     pub fn set_node_kind(&mut self, node: Node, kind: NodeKind) {
         if self.grid.is_node_within_bounds(node) {
             self.set_node_kind_internal(node, kind);
+            self.flush_road_components();
+            self.flush_wall_enclosure();
+            self.flush_water_network();
+        }
+    }
+
+    #[inline]
+    pub fn set_node_elevation(&mut self, node: Node, elevation: TerrainElevation) {
+        if self.grid.is_node_within_bounds(node) {
+            self.set_node_elevation_internal(node, elevation);
+            self.flush_road_components();
         }
     }
 
@@ -537,12 +579,26 @@ impl Graph {
         self.grid.size
     }
 
+    #[inline]
+    pub fn node_elevation(&self, node: Node) -> TerrainElevation {
+        if self.elevation.is_node_within_bounds(node) {
+            return self.elevation[node];
+        }
+        0
+    }
+
+    // Cliffs: neighboring nodes too far apart in elevation to walk between.
+    #[inline]
+    pub fn is_steep_step(&self, from: Node, to: Node) -> bool {
+        elevation::is_steep(self.node_elevation(from), self.node_elevation(to))
+    }
+
     #[inline]
     pub fn neighbors(&self, node: Node, wanted_node_kinds: NodeKind) -> ArrayVec<Node, 4> {
         let mut nodes = ArrayVec::new();
         for neighbor in node.neighbors() {
             if let Some(node_kind) = self.node_kind(neighbor) {
-                if node_kind.intersects(wanted_node_kinds) {
+                if node_kind.intersects(wanted_node_kinds) && !self.is_steep_step(node, neighbor) {
                     nodes.push(neighbor);
                 }
             }
@@ -550,6 +606,36 @@ impl Graph {
         nodes
     }
 
+    // Same as neighbors() plus the 4 diagonals with Movement::EightWay. A diagonal step
+    // is only allowed when both orthogonal nodes it passes between are also wanted, so
+    // paths never cut across the corner of a building or a patch of water. Steps up or
+    // down a cliff are never allowed.
+    pub fn neighbors_for_movement(&self, node: Node, wanted_node_kinds: NodeKind, movement: Movement) -> ArrayVec<Node, 8> {
+        let is_wanted = |neighbor: Node| {
+            self.node_kind(neighbor).is_some_and(|kind| kind.intersects(wanted_node_kinds))
+                && !self.is_steep_step(node, neighbor)
+        };
+
+        let mut nodes = ArrayVec::new();
+        for neighbor in node.neighbors() {
+            if is_wanted(neighbor) {
+                nodes.push(neighbor);
+            }
+        }
+
+        if movement == Movement::EightWay {
+            for diagonal in node.diagonal_neighbors() {
+                let horizontal = Node::new(Cell::new(diagonal.cell.x, node.cell.y));
+                let vertical = Node::new(Cell::new(node.cell.x, diagonal.cell.y));
+                if is_wanted(diagonal) && is_wanted(horizontal) && is_wanted(vertical) {
+                    nodes.push(diagonal);
+                }
+            }
+        }
+
+        nodes
+    }
+
     #[inline]
     pub fn has_vacant_lot_nodes(&self) -> bool {
         self.vacant_lots != 0
@@ -565,9 +651,108 @@ impl Graph {
         self.settlers_spawn_point
     }
 
+    // Changes whenever a Road or BuildingRoadLink node is added or removed.
+    // Lets callers cache road distances and recompute them only when stale.
+    #[inline]
+    pub fn road_network_version(&self) -> u32 {
+        self.road_network_version
+    }
+
+    // Stamp of the most recent node change anywhere in the graph.
+    // Anything computed at or after this stamp is still up to date.
+    #[inline]
+    pub fn last_edit_stamp(&self) -> u64 {
+        self.last_edit
+    }
+
+    // Stamp of the most recent node change within the given region.
+    // Out of bounds regions read as "edited now" so they never validate stale data.
+    #[inline]
+    pub fn region_edit_stamp(&self, region_index: usize) -> u64 {
+        self.region_edits.nodes.get(region_index).copied().unwrap_or(u64::MAX)
+    }
+
+    #[inline]
+    pub fn region_index(&self, node: Node) -> Option<usize> {
+        self.region_edits.node_to_grid_index(Self::region_node(node))
+    }
+
+    #[inline]
+    pub fn region_count(&self) -> usize {
+        self.region_edits.nodes.len()
+    }
+
+    // Id of the connected road component `node` belongs to, or None if it is not a Road node.
+    // Two road nodes are reachable from each other if and only if their component ids match.
+    #[inline]
+    pub fn road_component(&self, node: Node) -> Option<u32> {
+        let index = self.grid.node_to_grid_index(node)?;
+        self.road_components.find(index)
+    }
+
+    #[inline]
+    pub fn are_road_connected(&self, a: Node, b: Node) -> bool {
+        match (self.road_component(a), self.road_component(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    // True if `node` is a Road node with a road path to any road node on the border of the map.
+    #[inline]
+    pub fn is_road_connected_to_map_edge(&self, node: Node) -> bool {
+        self.road_component(node).is_some_and(|root| self.road_components.touches_map_edge[root as usize])
+    }
+
+    // Changes whenever a Wall or Gate node is added or removed.
+    #[inline]
+    pub fn wall_network_version(&self) -> u32 {
+        self.wall_network_version
+    }
+
+    // True if `node` is fully enclosed by walls: no path from it reaches the map
+    // border without crossing a wall, tower or gate. Wall nodes are never inside.
+    #[inline]
+    pub fn is_inside_walls(&self, node: Node) -> bool {
+        self.grid.node_to_grid_index(node).is_some_and(|index| self.wall_enclosure.is_inside(index))
+    }
+
+    #[inline]
+    pub fn inside_walls_count(&self) -> usize {
+        self.wall_enclosure.inside_count
+    }
+
+    // Changes whenever an Aqueduct or Reservoir node is added or removed.
+    #[inline]
+    pub fn water_network_version(&self) -> u32 {
+        self.water_network_version
+    }
+
+    // True if `node` is a reservoir or an aqueduct connected to one.
+    #[inline]
+    pub fn is_water_supplied(&self, node: Node) -> bool {
+        self.grid.node_to_grid_index(node).is_some_and(|index| self.water_network.is_supplied(index))
+    }
+
+    // True if any cell orthogonally adjacent to `cell_range` carries water,
+    // e.g. a fountain sitting next to a connected aqueduct or reservoir.
+    pub fn has_water_supply_nearby(&self, cell_range: CellRange) -> bool {
+        let mut found = false;
+        for_each_surrounding_cell(cell_range, |cell| {
+            found = self.is_water_supplied(Node::new(cell));
+            !found
+        });
+        found
+    }
+
     #[inline]
     pub fn memory_usage_estimate(&self) -> usize {
         self.grid.nodes.len() * std::mem::size_of::<NodeKind>()
+            + self.region_edits.nodes.len() * std::mem::size_of::<u64>()
+            + self.road_components.memory_usage_estimate()
+            + self.wall_enclosure.memory_usage_estimate()
+            + self.elevation.nodes.len() * std::mem::size_of::<TerrainElevation>()
+            + self.water_network.memory_usage_estimate()
     }
 
     // ----------------------
@@ -585,6 +770,81 @@ impl Graph {
         TileKind::Vegetation.bits()
     );
 
+    // Objects override the terrain below them. Free cells orthogonally adjacent
+    // to a building are also flagged as BuildingAccess so building searches can
+    // reach it.
+    fn node_kind_from_tiles(layers: TileMapLayerRefs, cell: Cell) -> NodeKind {
+        if let Some(object) = layers.find_tile(cell, Self::OBJECT_KINDS) {
+            return object.path_kind();
+        }
+
+        let mut node_kind = layers
+            .find_tile(cell, TileKind::Terrain)
+            .map(|terrain| terrain.path_kind())
+            .unwrap_or(NodeKind::empty());
+
+        let next_to_building = Node::new(cell).neighbors().into_iter().any(|neighbor| {
+            layers.find_tile(neighbor.cell, Self::OBJECT_KINDS).is_some_and(|object| object.path_kind().is_building())
+        });
+
+        if next_to_building {
+            node_kind |= NodeKind::BuildingAccess;
+        }
+
+        // Gates let units through, so they join the road network.
+        if node_kind.is_gate() {
+            node_kind |= NodeKind::Road;
+        }
+
+        node_kind
+    }
+
+    // Objects stand on the terrain, so the terrain tile alone decides the elevation.
+    #[inline]
+    fn elevation_from_tiles(layers: TileMapLayerRefs, cell: Cell) -> TerrainElevation {
+        layers.find_tile(cell, TileKind::Terrain).map_or(0, |terrain| terrain.elevation())
+    }
+
+    fn new_region_edits(grid_size: Size) -> (Grid<u64>, u64) {
+        let region_grid_size = Size::new(
+            (grid_size.width + PATH_REGION_SIZE - 1) / PATH_REGION_SIZE,
+            (grid_size.height + PATH_REGION_SIZE - 1) / PATH_REGION_SIZE,
+        );
+        let region_count = (region_grid_size.width * region_grid_size.height) as usize;
+        let stamp = next_graph_edit_stamp();
+        (Grid::new(region_grid_size, vec![stamp; region_count]), stamp)
+    }
+
+    #[inline]
+    fn region_node(node: Node) -> Node {
+        Node::new(Cell::new(node.cell.x.div_euclid(PATH_REGION_SIZE), node.cell.y.div_euclid(PATH_REGION_SIZE)))
+    }
+
+    #[inline]
+    fn track_region_edit(&mut self, node: Node, prev_kind: NodeKind, new_kind: NodeKind) {
+        if prev_kind != new_kind {
+            self.stamp_region_edit(node);
+        }
+    }
+
+    #[inline]
+    fn stamp_region_edit(&mut self, node: Node) {
+        self.last_edit = next_graph_edit_stamp();
+        self.region_edits[Self::region_node(node)] = self.last_edit;
+    }
+
+    #[inline]
+    fn set_node_elevation_internal(&mut self, node: Node, elevation: TerrainElevation) {
+        if self.elevation[node] != elevation {
+            // Steepness changes which neighbors connect, so roads and cached paths must be refreshed.
+            if self.grid[node].is_road() {
+                self.road_components.needs_rebuild = true;
+            }
+            self.stamp_region_edit(node);
+            self.elevation[node] = elevation;
+        }
+    }
+
     #[inline]
     fn set_node_kind_internal(&mut self, node: Node, kind: NodeKind) {
         let had_vacant_lot  = self.grid[node].intersects(NodeKind::VacantLot);
@@ -593,8 +853,30 @@ impl Graph {
         let had_spawn_point = self.grid[node].intersects(NodeKind::SettlersSpawnPoint);
         let has_spawn_point = kind.intersects(NodeKind::SettlersSpawnPoint);
 
+        let was_road = self.grid[node].is_road();
+        let prev_water_kinds = self.grid[node] & WATER_NETWORK_KINDS;
+
+        self.track_road_network_change(self.grid[node], kind);
+        self.track_wall_network_change(self.grid[node], kind);
+        self.track_water_network_change(self.grid[node], kind);
+        self.track_region_edit(node, self.grid[node], kind);
         self.grid[node] = kind; // NOTE: Override previous.
 
+        match (was_road, kind.is_road()) {
+            (false, true) => self.add_road_component_node(node),
+            // Union-find cannot split components; rebuild once this batch of edits is done.
+            (true, false) => self.road_components.needs_rebuild = true,
+            _ => {}
+        }
+
+        let water_kinds = kind & WATER_NETWORK_KINDS;
+        if prev_water_kinds.is_empty() && !water_kinds.is_empty() {
+            self.add_water_network_node(node);
+        } else if prev_water_kinds != water_kinds {
+            // Removing a link can cut off everything downstream of it; refill the whole network.
+            self.water_network.needs_rebuild = true;
+        }
+
         match (had_vacant_lot, has_vacant_lot) {
             (false, true) => self.vacant_lots += 1,
             (true, false) => {
@@ -610,6 +892,7 @@ impl Graph {
             // state matches the single-spawn invariant.
             if let Some(prev) = self.settlers_spawn_point {
                 if prev != node {
+                    self.track_region_edit(prev, self.grid[prev], self.grid[prev] - NodeKind::SettlersSpawnPoint);
                     self.grid[prev].remove(NodeKind::SettlersSpawnPoint);
                 }
             }
@@ -621,37 +904,318 @@ impl Graph {
     }
 
     #[inline]
-    fn append_node_kind_internal(&mut self, node: Node, kind: NodeKind) {
-        let had_vacant_lot = self.grid[node].intersects(NodeKind::VacantLot);
+    fn is_map_edge_node(&self, node: Node) -> bool {
+        let size = self.grid.size;
+        node.cell.x == 0 || node.cell.y == 0 || node.cell.x == size.width - 1 || node.cell.y == size.height - 1
+    }
+
+    // New road node: joins the components of its road neighbors.
+    fn add_road_component_node(&mut self, node: Node) {
+        if self.road_components.needs_rebuild {
+            return; // Stale anyway.
+        }
 
-        self.grid[node] |= kind; // NOTE: OR instead of assigning.
+        let index = self.grid.node_to_grid_index(node).unwrap();
+        self.road_components.make_set(index, self.is_map_edge_node(node));
 
-        if kind.intersects(NodeKind::VacantLot) && !had_vacant_lot {
-            self.vacant_lots += 1;
+        for neighbor in node.neighbors() {
+            if self.node_kind(neighbor).is_some_and(|kind| kind.is_road()) && !self.is_steep_step(node, neighbor) {
+                let neighbor_index = self.grid.node_to_grid_index(neighbor).unwrap();
+                self.road_components.union(index, neighbor_index);
+            }
         }
+    }
 
-        // We can have a single SettlersSpawnPoint node.
-        if kind.intersects(NodeKind::SettlersSpawnPoint) {
-            debug_assert!(self.settlers_spawn_point.is_none(), "Cannot have multiple SettlersSpawnPoint nodes!");
-            self.settlers_spawn_point = Some(node);
+    #[inline]
+    fn flush_road_components(&mut self) {
+        if self.road_components.needs_rebuild {
+            self.rebuild_road_components();
+        }
+    }
+
+    fn rebuild_road_components(&mut self) {
+        self.road_components = RoadComponents::new(self.grid.nodes.len());
+
+        for y in 0..self.grid.size.height {
+            for x in 0..self.grid.size.width {
+                let node = Node::new(Cell::new(x, y));
+                if self.grid[node].is_road() {
+                    self.add_road_component_node(node);
+                }
+            }
         }
     }
 
     #[inline]
-    fn clear_node_kind_internal(&mut self, node: Node, kind: NodeKind) {
-        let had_vacant_lot = self.grid[node].intersects(NodeKind::VacantLot);
+    fn track_road_network_change(&mut self, prev_kind: NodeKind, new_kind: NodeKind) {
+        const ROAD_NETWORK_KINDS: NodeKind = NodeKind::Road.union(NodeKind::BuildingRoadLink);
+        if (prev_kind & ROAD_NETWORK_KINDS) != (new_kind & ROAD_NETWORK_KINDS) {
+            self.road_network_version = self.road_network_version.wrapping_add(1);
+        }
+    }
 
-        self.grid[node].remove(kind); // NOTE: Clear flag.
+    #[inline]
+    fn track_wall_network_change(&mut self, prev_kind: NodeKind, new_kind: NodeKind) {
+        if (prev_kind & WALL_ENCLOSURE_KINDS) != (new_kind & WALL_ENCLOSURE_KINDS) {
+            self.wall_network_version = self.wall_network_version.wrapping_add(1);
+            self.wall_enclosure.needs_rebuild = true;
+        }
+    }
 
-        if kind.intersects(NodeKind::VacantLot) && had_vacant_lot {
-            debug_assert!(self.vacant_lots != 0, "Search Graph does not contain any VacantLot nodes!");
-            self.vacant_lots -= 1;
+    #[inline]
+    fn flush_wall_enclosure(&mut self) {
+        if self.wall_enclosure.needs_rebuild {
+            self.rebuild_wall_enclosure();
         }
+    }
 
-        // We can have a single SettlersSpawnPoint node.
-        if kind.intersects(NodeKind::SettlersSpawnPoint) {
-            self.settlers_spawn_point = None;
+    // Flood fills the graph from the map border. Walls and gates stop the fill
+    // (a gate can be shut), so any other node left unreached is enclosed.
+    fn rebuild_wall_enclosure(&mut self) {
+        let node_count = self.grid.nodes.len();
+
+        if !self.grid.nodes.iter().any(|kind| kind.intersects(WALL_ENCLOSURE_KINDS)) {
+            // No walls, nothing enclosed. Skip the fill.
+            self.wall_enclosure = WallEnclosure::default();
+            return;
+        }
+
+        let mut reached = vec![false; node_count];
+        let mut open_nodes = Vec::new();
+
+        for y in 0..self.grid.size.height {
+            for x in 0..self.grid.size.width {
+                let node = Node::new(Cell::new(x, y));
+                if self.is_map_edge_node(node) && !self.grid[node].intersects(WALL_ENCLOSURE_KINDS) {
+                    reached[self.grid.node_to_grid_index(node).unwrap()] = true;
+                    open_nodes.push(node);
+                }
+            }
+        }
+
+        while let Some(node) = open_nodes.pop() {
+            for neighbor in node.neighbors() {
+                if let Some(index) = self.grid.node_to_grid_index(neighbor)
+                    && !reached[index]
+                    && !self.grid.nodes[index].intersects(WALL_ENCLOSURE_KINDS)
+                {
+                    reached[index] = true;
+                    open_nodes.push(neighbor);
+                }
+            }
         }
+
+        let inside: Vec<bool> = reached
+            .iter()
+            .zip(&self.grid.nodes)
+            .map(|(reached, kind)| !reached && !kind.intersects(WALL_ENCLOSURE_KINDS))
+            .collect();
+
+        let inside_count = inside.iter().filter(|inside| **inside).count();
+        self.wall_enclosure = WallEnclosure { inside, inside_count, needs_rebuild: false };
+    }
+
+    #[inline]
+    fn track_water_network_change(&mut self, prev_kind: NodeKind, new_kind: NodeKind) {
+        if (prev_kind & WATER_NETWORK_KINDS) != (new_kind & WATER_NETWORK_KINDS) {
+            self.water_network_version = self.water_network_version.wrapping_add(1);
+        }
+    }
+
+    #[inline]
+    fn flush_water_network(&mut self) {
+        if self.water_network.needs_rebuild {
+            self.rebuild_water_network();
+        }
+    }
+
+    fn rebuild_water_network(&mut self) {
+        self.water_network = WaterNetwork::new(self.grid.nodes.len());
+
+        for y in 0..self.grid.size.height {
+            for x in 0..self.grid.size.width {
+                let node = Node::new(Cell::new(x, y));
+                if self.grid[node].is_reservoir() {
+                    self.add_water_network_node(node);
+                }
+            }
+        }
+    }
+
+    // New aqueduct or reservoir node: if it is a reservoir or touches a supplied node,
+    // water flows from it into every connected aqueduct not yet supplied.
+    fn add_water_network_node(&mut self, node: Node) {
+        if self.water_network.needs_rebuild {
+            return; // Stale anyway.
+        }
+
+        let index = self.grid.node_to_grid_index(node).unwrap();
+        if self.water_network.is_supplied(index) {
+            return;
+        }
+
+        let has_water =
+            self.grid[node].is_reservoir() || node.neighbors().into_iter().any(|neighbor| self.is_water_supplied(neighbor));
+        if !has_water {
+            return;
+        }
+
+        self.water_network.supplied[index] = true;
+        let mut open_nodes = vec![node];
+
+        while let Some(node) = open_nodes.pop() {
+            for neighbor in node.neighbors() {
+                if let Some(neighbor_index) = self.grid.node_to_grid_index(neighbor)
+                    && !self.water_network.supplied[neighbor_index]
+                    && self.grid.nodes[neighbor_index].intersects(WATER_NETWORK_KINDS)
+                {
+                    self.water_network.supplied[neighbor_index] = true;
+                    open_nodes.push(neighbor);
+                }
+            }
+        }
+    }
+
+}
+
+// ----------------------------------------------
+// RoadComponents
+// ----------------------------------------------
+
+const NO_ROAD_COMPONENT: u32 = u32::MAX;
+
+// Union-find (disjoint sets) over the Road nodes of the graph, indexed like the
+// node grid. Answers "are these roads connected?" in near constant time. Placing
+// roads merges components incrementally; removing a road forces a full rebuild
+// since union-find cannot split a component.
+#[derive(Default)]
+struct RoadComponents {
+    parents: Vec<u32>,           // Parent of each node or NO_ROAD_COMPONENT if not a road.
+    sizes: Vec<u32>,             // Node count of each component (valid at roots).
+    touches_map_edge: Vec<bool>, // Component has a node on the map border (valid at roots).
+    needs_rebuild: bool,
+}
+
+impl RoadComponents {
+    fn new(node_count: usize) -> Self {
+        Self {
+            parents: vec![NO_ROAD_COMPONENT; node_count],
+            sizes: vec![0; node_count],
+            touches_map_edge: vec![false; node_count],
+            needs_rebuild: false,
+        }
+    }
+
+    #[inline]
+    fn make_set(&mut self, index: usize, on_map_edge: bool) {
+        self.parents[index] = index as u32;
+        self.sizes[index] = 1;
+        self.touches_map_edge[index] = on_map_edge;
+    }
+
+    // Union by size keeps trees shallow, so read-only finds stay cheap without path compression.
+    #[inline]
+    fn find(&self, index: usize) -> Option<u32> {
+        let mut current = *self.parents.get(index)?;
+        if current == NO_ROAD_COMPONENT {
+            return None;
+        }
+        while self.parents[current as usize] != current {
+            current = self.parents[current as usize];
+        }
+        Some(current)
+    }
+
+    fn find_and_compress(&mut self, index: usize) -> u32 {
+        let root = self.find(index).unwrap();
+        let mut current = index as u32;
+        while current != root {
+            let next = self.parents[current as usize];
+            self.parents[current as usize] = root;
+            current = next;
+        }
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let mut root_a = self.find_and_compress(a) as usize;
+        let mut root_b = self.find_and_compress(b) as usize;
+        if root_a == root_b {
+            return;
+        }
+
+        if self.sizes[root_a] < self.sizes[root_b] {
+            std::mem::swap(&mut root_a, &mut root_b);
+        }
+
+        self.parents[root_b] = root_a as u32;
+        self.sizes[root_a] += self.sizes[root_b];
+        self.touches_map_edge[root_a] |= self.touches_map_edge[root_b];
+    }
+
+    #[inline]
+    fn memory_usage_estimate(&self) -> usize {
+        self.parents.len() * (std::mem::size_of::<u32>() * 2 + std::mem::size_of::<bool>())
+    }
+}
+
+// ----------------------------------------------
+// WallEnclosure
+// ----------------------------------------------
+
+// Node kinds that close off an enclosure.
+const WALL_ENCLOSURE_KINDS: NodeKind = NodeKind::Wall.union(NodeKind::Gate);
+
+// Which nodes are inside city walls, indexed like the node grid. Empty when the
+// map has no walls. Rebuilt with a flood fill whenever a wall or gate changes.
+#[derive(Default)]
+struct WallEnclosure {
+    inside: Vec<bool>,
+    inside_count: usize,
+    needs_rebuild: bool,
+}
+
+impl WallEnclosure {
+    #[inline]
+    fn is_inside(&self, index: usize) -> bool {
+        self.inside.get(index).copied().unwrap_or(false)
+    }
+
+    #[inline]
+    fn memory_usage_estimate(&self) -> usize {
+        self.inside.len() * std::mem::size_of::<bool>()
+    }
+}
+
+// ----------------------------------------------
+// WaterNetwork
+// ----------------------------------------------
+
+// Node kinds water flows through.
+const WATER_NETWORK_KINDS: NodeKind = NodeKind::Aqueduct.union(NodeKind::Reservoir);
+
+// Which aqueduct and reservoir nodes have water, indexed like the node grid.
+// Grows incrementally as aqueducts are laid; refilled from the reservoirs
+// whenever a link is removed, since that may cut off a whole branch.
+#[derive(Default)]
+struct WaterNetwork {
+    supplied: Vec<bool>,
+    needs_rebuild: bool,
+}
+
+impl WaterNetwork {
+    fn new(node_count: usize) -> Self {
+        Self { supplied: vec![false; node_count], needs_rebuild: false }
+    }
+
+    #[inline]
+    fn is_supplied(&self, index: usize) -> bool {
+        self.supplied.get(index).copied().unwrap_or(false)
+    }
+
+    #[inline]
+    fn memory_usage_estimate(&self) -> usize {
+        self.supplied.len() * std::mem::size_of::<bool>()
     }
 }
 
@@ -659,6 +1223,14 @@ impl Graph {
 // Heuristic
 // ----------------------------------------------
 
+// Neighborhood expanded by the search for each node.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum Movement {
+    #[default]
+    FourWay,  // Orthogonal steps only.
+    EightWay, // Orthogonal + diagonal steps, without cutting corners.
+}
+
 pub trait Heuristic {
     // Returns the estimated cost from `node` to `goal` node.
     // For grids this is typically the Manhattan Distance.
@@ -666,15 +1238,44 @@ pub trait Heuristic {
 
     // Returns the cost of moving `from` node `to` node, AKA the Edge Cost.
     fn movement_cost(&self, graph: &Graph, from: Node, to: Node) -> NodeCost;
+
+    // Neighbors considered by the search. estimate_cost_to_goal() must
+    // remain admissible for the chosen movement.
+    #[inline]
+    fn movement(&self) -> Movement {
+        Movement::FourWay
+    }
+
+    // Cost of one orthogonal step over the cheapest terrain.
+    // Search biases are expressed in steps and scaled by this.
+    #[inline]
+    fn step_cost(&self) -> NodeCost {
+        1
+    }
+}
+
+// Heuristics selectable by name, e.g. for cached searches, which need them as part of the cache key.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum HeuristicKind {
+    #[default]
+    UniformCost, // AStarUniformCostHeuristic
+    TerrainCost, // AStarTerrainCostHeuristic
 }
 
 // Uniform movement cost (movement_cost() always = 1).
-pub struct AStarUniformCostHeuristic;
+pub struct AStarUniformCostHeuristic {
+    movement: Movement,
+}
 
 impl AStarUniformCostHeuristic {
     #[inline]
     pub fn new() -> Self {
-        Self
+        Self { movement: Movement::FourWay }
+    }
+
+    #[inline]
+    pub fn with_movement(movement: Movement) -> Self {
+        Self { movement }
     }
 }
 
@@ -682,15 +1283,91 @@ impl Heuristic for AStarUniformCostHeuristic {
     #[inline]
     fn estimate_cost_to_goal(&self, _graph: &Graph, node: Node, goal: Node) -> NodeCost {
         // Estimating 0 here would turn A* into Dijkstra's.
-        node.manhattan_distance(goal)
+        match self.movement {
+            Movement::FourWay => node.manhattan_distance(goal),
+            // Diagonal steps cost the same as orthogonal ones: Chebyshev distance.
+            Movement::EightWay => (node.cell.x - goal.cell.x).abs().max((node.cell.y - goal.cell.y).abs()),
+        }
     }
 
     #[inline]
     fn movement_cost(&self, _graph: &Graph, _from: Node, _to: Node) -> NodeCost {
         // Uniform movement cost.
-        // Could be a dynamic cost based on terrain kind in the future.
+        // See AStarTerrainCostHeuristic for a terrain-aware cost.
         1
     }
+
+    #[inline]
+    fn movement(&self) -> Movement {
+        self.movement
+    }
+}
+
+// Movement costs are scaled by 10 so diagonal steps can be weighted ~sqrt(2) using integers.
+pub const MOVEMENT_COST_ROAD: NodeCost = 10;
+pub const MOVEMENT_COST_LAND: NodeCost = 25; // Dirt, vacant lots, vegetation, anything but roads.
+pub const MOVEMENT_COST_SLOPE: NodeCost = 15; // Extra cost of walking one elevation step up or down.
+const DIAGONAL_COST_NUM: NodeCost = 14;
+const DIAGONAL_COST_DEN: NodeCost = 10;
+
+// Movement cost based on the kind of node being entered, so roads are cheaper
+// than walking over dirt and searches genuinely prefer them. Optionally allows
+// diagonal steps (Movement::EightWay), weighted ~1.4x an orthogonal step.
+pub struct AStarTerrainCostHeuristic {
+    movement: Movement,
+}
+
+impl AStarTerrainCostHeuristic {
+    #[inline]
+    pub fn new() -> Self {
+        Self { movement: Movement::FourWay }
+    }
+
+    #[inline]
+    pub fn with_movement(movement: Movement) -> Self {
+        Self { movement }
+    }
+
+    #[inline]
+    pub fn node_kind_cost(node_kind: NodeKind) -> NodeCost {
+        if node_kind.is_road() { MOVEMENT_COST_ROAD } else { MOVEMENT_COST_LAND }
+    }
+}
+
+impl Heuristic for AStarTerrainCostHeuristic {
+    #[inline]
+    fn estimate_cost_to_goal(&self, _graph: &Graph, node: Node, goal: Node) -> NodeCost {
+        // Assume the cheapest terrain all the way so the estimate stays admissible.
+        let dx = (node.cell.x - goal.cell.x).abs();
+        let dy = (node.cell.y - goal.cell.y).abs();
+        match self.movement {
+            Movement::FourWay => (dx + dy) * MOVEMENT_COST_ROAD,
+            Movement::EightWay => {
+                // Octile distance.
+                let diagonal_cost = (MOVEMENT_COST_ROAD * DIAGONAL_COST_NUM) / DIAGONAL_COST_DEN;
+                (dx.max(dy) * MOVEMENT_COST_ROAD) + (dx.min(dy) * (diagonal_cost - MOVEMENT_COST_ROAD))
+            }
+        }
+    }
+
+    #[inline]
+    fn movement_cost(&self, graph: &Graph, from: Node, to: Node) -> NodeCost {
+        let mut cost = graph.node_kind(to).map_or(MOVEMENT_COST_LAND, Self::node_kind_cost);
+        if graph.node_elevation(from) != graph.node_elevation(to) {
+            cost += MOVEMENT_COST_SLOPE;
+        }
+        if from.is_diagonal_to(to) { (cost * DIAGONAL_COST_NUM) / DIAGONAL_COST_DEN } else { cost }
+    }
+
+    #[inline]
+    fn movement(&self) -> Movement {
+        self.movement
+    }
+
+    #[inline]
+    fn step_cost(&self) -> NodeCost {
+        MOVEMENT_COST_ROAD
+    }
 }
 
 // ----------------------------------------------
@@ -880,6 +1557,184 @@ impl std::fmt::Display for PathHistory {
     }
 }
 
+// ----------------------------------------------
+// PathCache
+// ----------------------------------------------
+
+pub const PATH_CACHE_DEFAULT_CAPACITY: usize = 128;
+
+// Caches A* results keyed by (start, goal, traversable kinds, heuristic, movement) so the common
+// walker trips (house -> market, producer -> storage) don't redo the same search.
+// Each entry remembers the graph regions the search explored; it goes stale as soon
+// as any of those regions is edited (Graph::set_node_kind & friends), since only
+// those nodes could change the search outcome. Only found paths are cached.
+// Least recently used entries are evicted once the cache is full.
+pub struct PathCache {
+    entries: PreHashedKeyMap<u64, PathCacheEntry>,
+    capacity: usize,
+    use_counter: u64,
+    hits: u64,
+    misses: u64,
+}
+
+struct PathCacheEntry {
+    key: PathCacheKey,
+    path: Path,
+    explored_regions: Vec<u32>, // Graph region indices the search looked at.
+    edit_stamp: u64,            // Graph::last_edit_stamp() when the path was computed.
+    last_used: u64,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PathCacheKey {
+    pub heuristic: HeuristicKind,
+    pub movement: Movement,
+    pub traversable_node_kinds: NodeKind,
+    pub start: Node,
+    pub goal: Node,
+}
+
+impl PathCacheKey {
+    #[inline]
+    fn hash_key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+impl Default for PathCache {
+    fn default() -> Self {
+        Self::with_capacity(PATH_CACHE_DEFAULT_CAPACITY)
+    }
+}
+
+impl PathCache {
+    pub fn with_capacity(capacity: usize) -> Self {
+        debug_assert!(capacity != 0);
+        Self {
+            entries: PreHashedKeyMap::default(),
+            capacity,
+            use_counter: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    // Returns the cached path if it is still valid for the current graph state.
+    // Stale entries are dropped.
+    pub fn get(&mut self, graph: &Graph, key: PathCacheKey) -> Option<&Path> {
+        let key_hash = key.hash_key();
+
+        let is_valid = match self.entries.get(&key_hash) {
+            Some(entry) if entry.key == key => Self::is_entry_valid(graph, entry),
+            _ => {
+                self.misses += 1;
+                return None;
+            }
+        };
+
+        if !is_valid {
+            self.entries.remove(&key_hash);
+            self.misses += 1;
+            return None;
+        }
+
+        self.hits += 1;
+        self.use_counter += 1;
+
+        let entry = self.entries.get_mut(&key_hash).unwrap();
+        entry.last_used = self.use_counter;
+        Some(&entry.path)
+    }
+
+    pub fn insert(&mut self, graph: &Graph, key: PathCacheKey, path: &Path, explored_regions: Vec<u32>) {
+        debug_assert!(!path.is_empty());
+
+        let key_hash = key.hash_key();
+
+        if !self.entries.contains_key(&key_hash) && self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+
+        self.use_counter += 1;
+        self.entries.insert(key_hash, PathCacheEntry {
+            key,
+            path: path.clone(),
+            explored_regions,
+            edit_stamp: graph.last_edit_stamp(),
+            last_used: self.use_counter,
+        });
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    #[inline]
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn memory_usage_estimate(&self) -> usize {
+        let mut estimate = self.entries.capacity() * std::mem::size_of::<(u64, PathCacheEntry)>();
+        for entry in self.entries.values() {
+            estimate += entry.path.capacity() * std::mem::size_of::<Node>();
+            estimate += entry.explored_regions.capacity() * std::mem::size_of::<u32>();
+        }
+        estimate
+    }
+
+    // ----------------------
+    // Internal:
+    // ----------------------
+
+    fn is_entry_valid(graph: &Graph, entry: &PathCacheEntry) -> bool {
+        // Fast path: Nothing changed since the entry was computed.
+        if graph.last_edit_stamp() <= entry.edit_stamp {
+            return true;
+        }
+
+        entry
+            .explored_regions
+            .iter()
+            .all(|&region_index| graph.region_edit_stamp(region_index as usize) <= entry.edit_stamp)
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let oldest = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key_hash, _)| *key_hash);
+
+        if let Some(key_hash) = oldest {
+            self.entries.remove(&key_hash);
+        }
+    }
+}
+
 // ----------------------------------------------
 // Search
 // ----------------------------------------------
@@ -931,6 +1786,10 @@ pub struct Search {
 
     // Scratchpad for find_waypoints.
     possible_waypoints: Vec<Node>,
+
+    // Results of find_path_cached() and the regions explored by its last search (bitset).
+    path_cache: PathCache,
+    explored_regions: Vec<u64>,
 }
 
 impl Search {
@@ -954,6 +1813,8 @@ impl Search {
             cost_so_far: Grid::new(grid_size, vec![Versioned::<NodeCost>::default(); node_count]),
             generation: 0,
             possible_waypoints: Vec::with_capacity(64),
+            path_cache: PathCache::default(),
+            explored_regions: Vec::new(),
         }
     }
 
@@ -969,7 +1830,7 @@ impl Search {
         start: Node,
         goal: Node,
     ) -> SearchResult<'_> {
-        self.find_paths_internal::<DefaultPathFilter, false>(
+        self.find_paths_internal::<DefaultPathFilter, false, false>(
             graph,
             heuristic,
             &mut DefaultPathFilter::new(),
@@ -980,6 +1841,77 @@ impl Search {
         )
     }
 
+    // Same as find_path() with the given heuristic and movement, but served from the
+    // PathCache when a still valid result for the same query exists. Misses run the
+    // search while tracking the explored graph regions and store the path found.
+    pub fn find_path_cached(
+        &mut self,
+        graph: &Graph,
+        heuristic: HeuristicKind,
+        movement: Movement,
+        traversable_node_kinds: NodeKind,
+        start: Node,
+        goal: Node,
+    ) -> SearchResult<'_> {
+        let key = PathCacheKey { heuristic, movement, traversable_node_kinds, start, goal };
+
+        if let Some(cached_path) = self.path_cache.get(graph, key) {
+            self.path.clear();
+            self.path.extend_from_slice(cached_path);
+            return SearchResult::PathFound(&self.path);
+        }
+
+        self.explored_regions.clear();
+        self.explored_regions.resize(graph.region_count().div_ceil(64), 0);
+
+        let found = match heuristic {
+            HeuristicKind::UniformCost => {
+                self.find_path_tracking_regions(graph, &AStarUniformCostHeuristic::with_movement(movement), key)
+            }
+            HeuristicKind::TerrainCost => {
+                self.find_path_tracking_regions(graph, &AStarTerrainCostHeuristic::with_movement(movement), key)
+            }
+        };
+
+        if !found {
+            return SearchResult::PathNotFound;
+        }
+
+        let mut explored_regions = Vec::new();
+        for (word_index, &word) in self.explored_regions.iter().enumerate() {
+            let mut bits = word;
+            while bits != 0 {
+                let bit = bits.trailing_zeros();
+                explored_regions.push((word_index as u32 * 64) + bit);
+                bits &= bits - 1;
+            }
+        }
+
+        self.path_cache.insert(graph, key, &self.path, explored_regions);
+        SearchResult::PathFound(&self.path)
+    }
+
+    #[inline]
+    pub fn path_cache(&self) -> &PathCache {
+        &self.path_cache
+    }
+
+    #[inline]
+    pub fn path_cache_mut(&mut self) -> &mut PathCache {
+        &mut self.path_cache
+    }
+
+    // Scratch buffers used by the searches plus the path cache.
+    pub fn memory_usage_estimate(&self) -> usize {
+        self.path.capacity() * std::mem::size_of::<Node>()
+            + self.frontier.capacity() * std::mem::size_of::<(Node, Reverse<NodeCost>)>()
+            + self.came_from.nodes.capacity() * std::mem::size_of::<Versioned<Node>>()
+            + self.cost_so_far.nodes.capacity() * std::mem::size_of::<Versioned<NodeCost>>()
+            + self.possible_waypoints.capacity() * std::mem::size_of::<Node>()
+            + self.explored_regions.capacity() * std::mem::size_of::<u64>()
+            + self.path_cache.memory_usage_estimate()
+    }
+
     // Searches for all paths leading to the goal.
     // Returns the first path which PathFilter accepts.
     #[inline]
@@ -996,7 +1928,7 @@ impl Search {
     where
         Filter: PathFilter,
     {
-        self.find_paths_internal::<Filter, true>(
+        self.find_paths_internal::<Filter, true, false>(
             graph,
             heuristic,
             path_filter,
@@ -1048,7 +1980,7 @@ impl Search {
                 self.possible_waypoints.push(current);
             }
 
-            let mut neighbors = graph.neighbors(current, traversable_node_kinds);
+            let mut neighbors = graph.neighbors_for_movement(current, traversable_node_kinds, heuristic.movement());
             path_filter.shuffle(&mut neighbors);
 
             for neighbor in neighbors {
@@ -1057,7 +1989,7 @@ impl Search {
                 if let Some(new_cost) = self.relax_neighbor(current, neighbor, movement_cost, false) {
                     // Apply optional directional bias. With no bias this is Dijkstra's
                     // search using only node cost (no heuristic / explicit goal).
-                    let bias_amount = bias.cost_for(start, neighbor);
+                    let bias_amount = bias.cost_for(start, neighbor) * heuristic.step_cost() as f32;
                     let priority = ((new_cost as f32) + bias_amount).round() as i32;
                     self.frontier.push(neighbor, Reverse(priority));
                 }
@@ -1170,12 +2102,27 @@ impl Search {
     // Internal:
     // ----------------------
 
+    // Uncached part of `find_path_cached`.
+    fn find_path_tracking_regions(&mut self, graph: &Graph, heuristic: &impl Heuristic, key: PathCacheKey) -> bool {
+        self.find_paths_internal::<DefaultPathFilter, false, true>(
+            graph,
+            heuristic,
+            &mut DefaultPathFilter::new(),
+            1, // max_paths
+            key.traversable_node_kinds,
+            key.start,
+            key.goal,
+        )
+        .found()
+    }
+
     // A* graph search shared by `find_path` and `find_paths`.
     // `ALLOW_GOAL_REENTRY` controls whether the goal is force-relaxed during
     // neighbor expansion (so the goal can be popped multiple times to surface
     // alternative routes). It's a const generic so the per-neighbor branch
-    // folds away in the `find_path` monomorphization.
-    fn find_paths_internal<Filter, const ALLOW_GOAL_REENTRY: bool>(
+    // folds away in the `find_path` monomorphization. Likewise, `TRACK_EXPLORED_REGIONS`
+    // records every graph region read by the search for `find_path_cached`.
+    fn find_paths_internal<Filter, const ALLOW_GOAL_REENTRY: bool, const TRACK_EXPLORED_REGIONS: bool>(
         &mut self,
         graph: &Graph,
         heuristic: &impl Heuristic,
@@ -1209,7 +2156,11 @@ impl Search {
                 continue;
             }
 
-            let neighbors = graph.neighbors(current, traversable_node_kinds);
+            if TRACK_EXPLORED_REGIONS {
+                self.mark_explored_regions(graph, current);
+            }
+
+            let neighbors = graph.neighbors_for_movement(current, traversable_node_kinds, heuristic.movement());
 
             for neighbor in neighbors {
                 let movement_cost = heuristic.movement_cost(graph, current, neighbor);
@@ -1277,7 +2228,7 @@ impl Search {
                 return SearchResult::PathFound(&self.path);
             }
 
-            let mut neighbors = graph.neighbors(current, wanted_neighbor_kinds);
+            let mut neighbors = graph.neighbors_for_movement(current, wanted_neighbor_kinds, heuristic.movement());
             path_filter.shuffle(&mut neighbors);
 
             for neighbor in neighbors {
@@ -1286,7 +2237,7 @@ impl Search {
                 if let Some(new_cost) = self.relax_neighbor(current, neighbor, movement_cost, false) {
                     // Apply optional directional bias. With no bias this is Dijkstra's
                     // search using only node cost (no heuristic / explicit goal).
-                    let bias_amount = bias.cost_for(start, neighbor);
+                    let bias_amount = bias.cost_for(start, neighbor) * heuristic.step_cost() as f32;
                     let priority = ((new_cost as f32) + bias_amount).round() as i32;
                     self.frontier.push(neighbor, Reverse(priority));
                 }
@@ -1349,6 +2300,21 @@ impl Search {
         self.cost_so_far[node] = Versioned { generation: self.generation, value };
     }
 
+    // Expanding `node` reads its own and its 4 neighbors' kinds, which may sit across a region border.
+    #[inline]
+    fn mark_explored_regions(&mut self, graph: &Graph, node: Node) {
+        let mut mark = |node: Node| {
+            if let Some(region_index) = graph.region_index(node) {
+                self.explored_regions[region_index / 64] |= 1 << (region_index % 64);
+            }
+        };
+
+        mark(node);
+        for neighbor in node.neighbors() {
+            mark(neighbor);
+        }
+    }
+
     #[inline]
     fn set_came_from(&mut self, node: Node, value: Node) {
         self.came_from[node] = Versioned { generation: self.generation, value };
@@ -1487,12 +2453,18 @@ pub fn for_each_surrounding_cell(start_cells: CellRange, mut visitor_fn: impl Fn
     }
 }
 
-pub fn highlight_path_tiles(tile_map: &mut TileMap, path: &Path) {
+pub fn highlight_path_tiles(tile_map: &mut TileMap, path: &[Node]) {
     for node in path {
         tile_map.set_tile_flags(node.cell, TileKind::Terrain, TileFlags::Highlighted, true);
     }
 }
 
+pub fn clear_path_highlight(tile_map: &mut TileMap, path: &[Node]) {
+    for node in path {
+        tile_map.set_tile_flags(node.cell, TileKind::Terrain, TileFlags::Highlighted, false);
+    }
+}
+
 pub fn highlight_building_access_tiles(tile_map: &mut TileMap, start_cells: CellRange) {
     for_each_surrounding_cell(start_cells, |cell| {
         tile_map.set_tile_flags(cell, TileKind::Terrain, TileFlags::Invalidated, true);
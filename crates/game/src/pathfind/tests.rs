@@ -1196,3 +1196,350 @@ fn test_for_each_surrounding_cell_skips_corners() {
         }
     }
 }
+
+#[test]
+fn test_road_network_version() {
+    let mut graph = Graph::with_node_kind(Size::new(4, 4), NodeKind::EmptyLand);
+    let initial_version = graph.road_network_version();
+
+    // Non-road changes leave the version untouched.
+    graph.set_node_kind(Node::new(Cell::new(0, 0)), NodeKind::Water);
+    assert_eq!(graph.road_network_version(), initial_version);
+
+    // Placing a road bumps it.
+    graph.set_node_kind(Node::new(Cell::new(1, 1)), NodeKind::Road);
+    let road_placed_version = graph.road_network_version();
+    assert_ne!(road_placed_version, initial_version);
+
+    // Flagging a road as a building road link bumps it again.
+    graph.set_node_kind(Node::new(Cell::new(1, 1)), NodeKind::Road | NodeKind::BuildingRoadLink);
+    assert_ne!(graph.road_network_version(), road_placed_version);
+
+    // Out of bounds nodes are ignored.
+    let version = graph.road_network_version();
+    graph.set_node_kind(Node::new(Cell::new(8, 8)), NodeKind::Road);
+    assert_eq!(graph.road_network_version(), version);
+}
+
+#[test]
+fn test_road_components() {
+    let mut graph = Graph::with_node_kind(Size::new(8, 8), NodeKind::EmptyLand);
+    let node = |x, y| Node::new(Cell::new(x, y));
+
+    // Road from the left map edge to the middle of the map.
+    for x in 0..4 {
+        graph.set_node_kind(node(x, 3), NodeKind::Road);
+    }
+
+    // Isolated road island.
+    graph.set_node_kind(node(6, 5), NodeKind::Road);
+
+    assert!(graph.are_road_connected(node(0, 3), node(3, 3)));
+    assert!(graph.is_road_connected_to_map_edge(node(3, 3)));
+    assert!(!graph.are_road_connected(node(3, 3), node(6, 5)));
+    assert!(!graph.is_road_connected_to_map_edge(node(6, 5)));
+    assert!(graph.road_component(node(5, 5)).is_none());
+
+    // Bridge the island to the main road: components merge incrementally.
+    for (x, y) in [(4, 3), (5, 3), (6, 3), (6, 4)] {
+        graph.set_node_kind(node(x, y), NodeKind::Road);
+    }
+    assert!(graph.are_road_connected(node(0, 3), node(6, 5)));
+    assert!(graph.is_road_connected_to_map_edge(node(6, 5)));
+
+    // Cut the road near the edge: the rest of the network splits off.
+    graph.set_node_kind(node(1, 3), NodeKind::EmptyLand);
+    assert!(graph.is_road_connected_to_map_edge(node(0, 3)));
+    assert!(!graph.is_road_connected_to_map_edge(node(6, 5)));
+    assert!(!graph.are_road_connected(node(0, 3), node(2, 3)));
+    assert!(graph.are_road_connected(node(2, 3), node(6, 5)));
+
+    // Same answers as a graph built from scratch.
+    let mut nodes = Vec::new();
+    for y in 0..8 {
+        for x in 0..8 {
+            nodes.push(graph.node_kind(node(x, y)).unwrap());
+        }
+    }
+    let rebuilt = Graph::with_node_grid(Size::new(8, 8), nodes);
+    for y in 0..8 {
+        for x in 0..8 {
+            assert_eq!(rebuilt.is_road_connected_to_map_edge(node(x, y)), graph.is_road_connected_to_map_edge(node(x, y)));
+        }
+    }
+}
+
+// ----------------------------------------------
+// PathCache
+// ----------------------------------------------
+
+#[test]
+fn test_path_cache_invalidated_by_edits_in_explored_regions() {
+    // Road patch in the top-left region, everything else is Water.
+    let mut graph = Graph::with_node_kind(Size::new(32, 32), NodeKind::Water);
+    for y in 0..PATH_REGION_SIZE {
+        for x in 0..PATH_REGION_SIZE {
+            graph.set_node_kind(Node::new(Cell::new(x, y)), NodeKind::Road);
+        }
+    }
+
+    let mut search = Search::with_graph(&graph);
+    let start = Node::new(Cell::new(0, 3));
+    let goal  = Node::new(Cell::new(7, 3));
+
+    let find = |search: &mut Search, graph: &Graph| -> Path {
+        match search.find_path_cached(graph, HeuristicKind::UniformCost, Movement::FourWay, NodeKind::Road, start, goal) {
+            SearchResult::PathFound(p) => p.clone(),
+            _ => panic!("Expected a path!"),
+        }
+    };
+
+    let first_path = find(&mut search, &graph);
+    assert_eq!(first_path.len(), 8);
+    assert_eq!(search.path_cache().len(), 1);
+    assert_eq!(search.path_cache().hits(), 0);
+
+    // Same query: served from the cache.
+    assert_eq!(find(&mut search, &graph), first_path);
+    assert_eq!(search.path_cache().hits(), 1);
+
+    // Edit far away from anything the search looked at: still a hit.
+    graph.set_node_kind(Node::new(Cell::new(30, 30)), NodeKind::Road);
+    assert_eq!(find(&mut search, &graph), first_path);
+    assert_eq!(search.path_cache().hits(), 2);
+
+    // Setting a node to the kind it already has is not an edit.
+    graph.set_node_kind(Node::new(Cell::new(2, 2)), NodeKind::Road);
+    assert_eq!(find(&mut search, &graph), first_path);
+    assert_eq!(search.path_cache().hits(), 3);
+
+    // Block the cached route: entry goes stale and the search detours.
+    let blocker = Node::new(Cell::new(4, 3));
+    graph.set_node_kind(blocker, NodeKind::Water);
+
+    let detour_path = find(&mut search, &graph);
+    assert_eq!(search.path_cache().hits(), 3);
+    assert!(detour_path.len() > first_path.len());
+    assert!(!detour_path.contains(&blocker), "Detour path must not cross the blocker: {:?}", detour_path);
+
+    // Uncached search agrees with the cached one.
+    let heuristic = AStarUniformCostHeuristic::new();
+    match search.find_path(&graph, &heuristic, NodeKind::Road, start, goal) {
+        SearchResult::PathFound(p) => assert_eq!(p, &detour_path),
+        _ => panic!("Expected a path!"),
+    }
+
+    // Unreachable goals are never cached.
+    let water_goal = Node::new(Cell::new(20, 20));
+    let water_path =
+        search.find_path_cached(&graph, HeuristicKind::UniformCost, Movement::FourWay, NodeKind::Road, start, water_goal);
+    assert!(water_path.not_found());
+    assert_eq!(search.path_cache().len(), 1);
+}
+
+#[test]
+fn test_path_cache_lru_eviction() {
+    let graph = Graph::with_node_kind(Size::new(8, 8), NodeKind::Road);
+    let mut cache = PathCache::with_capacity(2);
+
+    let node = |x, y| Node::new(Cell::new(x, y));
+    let path = vec![node(0, 0), node(1, 0)];
+
+    let key = |goal| PathCacheKey {
+        heuristic: HeuristicKind::UniformCost,
+        movement: Movement::FourWay,
+        traversable_node_kinds: NodeKind::Road,
+        start: node(0, 0),
+        goal,
+    };
+
+    cache.insert(&graph, key(node(1, 0)), &path, Vec::new());
+    cache.insert(&graph, key(node(2, 0)), &path, Vec::new());
+
+    // Touch the first entry so the second becomes the least recently used.
+    assert!(cache.get(&graph, key(node(1, 0))).is_some());
+
+    cache.insert(&graph, key(node(3, 0)), &path, Vec::new());
+    assert_eq!(cache.len(), 2);
+
+    assert!(cache.get(&graph, key(node(1, 0))).is_some());
+    assert!(cache.get(&graph, key(node(2, 0))).is_none());
+    assert!(cache.get(&graph, key(node(3, 0))).is_some());
+
+    // Traversable kinds, heuristic and movement are all part of the key.
+    let other_kinds = PathCacheKey { traversable_node_kinds: NodeKind::EmptyLand, ..key(node(1, 0)) };
+    let other_heuristic = PathCacheKey { heuristic: HeuristicKind::TerrainCost, ..key(node(1, 0)) };
+    let other_movement = PathCacheKey { movement: Movement::EightWay, ..key(node(1, 0)) };
+    assert!(cache.get(&graph, other_kinds).is_none());
+    assert!(cache.get(&graph, other_heuristic).is_none());
+    assert!(cache.get(&graph, other_movement).is_none());
+}
+
+#[test]
+fn test_terrain_cost_prefers_roads() {
+    // Straight line of dirt from (0,0) to (6,0), with a road detour one row up.
+    let mut graph = Graph::with_node_kind(Size::new(8, 4), NodeKind::EmptyLand);
+    for x in 0..=6 {
+        graph.set_node_kind(Node::new(Cell::new(x, 1)), NodeKind::Road);
+    }
+
+    let traversable = NodeKind::EmptyLand | NodeKind::Road;
+    let start = Node::new(Cell::new(0, 0));
+    let goal = Node::new(Cell::new(6, 0));
+    let mut search = Search::with_graph(&graph);
+
+    // Uniform cost walks straight over the dirt.
+    match search.find_path(&graph, &AStarUniformCostHeuristic::new(), traversable, start, goal) {
+        SearchResult::PathFound(path) => assert_eq!(path.len(), 7),
+        SearchResult::PathNotFound => panic!("Expected a path!"),
+    }
+
+    // Terrain cost takes the longer road detour.
+    match search.find_path(&graph, &AStarTerrainCostHeuristic::new(), traversable, start, goal) {
+        SearchResult::PathFound(path) => {
+            assert_eq!(path.len(), 9);
+            assert!(path[1..path.len() - 1].iter().all(|node| graph.node_kind(*node).unwrap().is_road()));
+        }
+        SearchResult::PathNotFound => panic!("Expected a path!"),
+    }
+
+    // Cached searches for the same endpoints keep one entry per heuristic.
+    let mut cached_len = |heuristic| {
+        match search.find_path_cached(&graph, heuristic, Movement::FourWay, traversable, start, goal) {
+            SearchResult::PathFound(path) => path.len(),
+            SearchResult::PathNotFound => panic!("Expected a path!"),
+        }
+    };
+    assert_eq!(cached_len(HeuristicKind::UniformCost), 7);
+    assert_eq!(cached_len(HeuristicKind::TerrainCost), 9);
+    assert_eq!(cached_len(HeuristicKind::UniformCost), 7);
+    assert_eq!(search.path_cache().len(), 2);
+    assert_eq!(search.path_cache().hits(), 1);
+}
+
+#[test]
+fn test_diagonal_movement_without_corner_cutting() {
+    let mut graph = Graph::with_node_kind(Size::new(8, 8), NodeKind::Road);
+    let heuristic = AStarTerrainCostHeuristic::with_movement(Movement::EightWay);
+    let mut search = Search::with_graph(&graph);
+
+    let start = Node::new(Cell::new(0, 0));
+    let goal = Node::new(Cell::new(5, 5));
+
+    match search.find_path(&graph, &heuristic, NodeKind::Road, start, goal) {
+        SearchResult::PathFound(path) => {
+            assert_eq!(path.len(), 6);
+            assert!(path.windows(2).all(|step| step[0].is_diagonal_to(step[1])));
+        }
+        SearchResult::PathNotFound => panic!("Expected a path!"),
+    }
+
+    // Default 4-way movement is unchanged.
+    assert_eq!(graph.neighbors_for_movement(start, NodeKind::Road, Movement::FourWay).len(), 2);
+    assert_eq!(graph.neighbors_for_movement(Node::new(Cell::new(3, 3)), NodeKind::Road, Movement::EightWay).len(), 8);
+
+    // Block one of the orthogonal cells: the diagonal step around its corner is no longer allowed.
+    graph.set_node_kind(Node::new(Cell::new(1, 0)), NodeKind::Water);
+    let neighbors = graph.neighbors_for_movement(start, NodeKind::Road, Movement::EightWay);
+    assert!(!neighbors.contains(&Node::new(Cell::new(1, 1))));
+    assert!(neighbors.contains(&Node::new(Cell::new(0, 1))));
+
+    match search.find_path(&graph, &heuristic, NodeKind::Road, start, goal) {
+        SearchResult::PathFound(path) => {
+            assert_eq!(path.len(), 7);
+            assert_eq!(path[1], Node::new(Cell::new(0, 1)));
+        }
+        SearchResult::PathNotFound => panic!("Expected a path!"),
+    }
+}
+
+#[test]
+fn test_wall_enclosure() {
+    let mut graph = Graph::with_node_kind(Size::new(8, 8), NodeKind::EmptyLand);
+    let center = Node::new(Cell::new(3, 3));
+    assert!(!graph.is_inside_walls(center));
+
+    // Ring of walls around cells (2,2)..(4,4), with a gate on one side.
+    let version = graph.wall_network_version();
+    for i in 1..=5 {
+        for cell in [Cell::new(i, 1), Cell::new(i, 5), Cell::new(1, i), Cell::new(5, i)] {
+            graph.set_node_kind(Node::new(cell), NodeKind::Wall);
+        }
+    }
+    graph.set_node_kind(Node::new(Cell::new(3, 1)), NodeKind::Gate | NodeKind::Road);
+
+    assert_ne!(graph.wall_network_version(), version);
+    assert!(graph.is_inside_walls(center));
+    assert_eq!(graph.inside_walls_count(), 9);
+
+    // Walls, gates and cells outside the ring are not inside.
+    assert!(!graph.is_inside_walls(Node::new(Cell::new(1, 1))));
+    assert!(!graph.is_inside_walls(Node::new(Cell::new(3, 1))));
+    assert!(!graph.is_inside_walls(Node::new(Cell::new(6, 6))));
+
+    // Knocking down a wall section opens the enclosure.
+    graph.set_node_kind(Node::new(Cell::new(5, 3)), NodeKind::EmptyLand);
+    assert!(!graph.is_inside_walls(center));
+    assert_eq!(graph.inside_walls_count(), 0);
+}
+
+#[test]
+fn test_find_path_steep_elevation() {
+    let mut graph = Graph::with_node_kind(Size::new(8, 3), NodeKind::EmptyLand);
+    let heuristic = AStarUniformCostHeuristic::new();
+
+    let start = Node::new(Cell::new(0, 1));
+    let goal  = Node::new(Cell::new(7, 1));
+
+    // A plateau two steps high across the whole map: cliffs on both sides block the way.
+    for y in 0..3 {
+        graph.set_node_elevation(Node::new(Cell::new(4, y)), 2);
+    }
+    assert!(graph.is_steep_step(Node::new(Cell::new(3, 1)), Node::new(Cell::new(4, 1))));
+    assert!(matches!(Search::with_graph(&graph).find_path(&graph, &heuristic, NodeKind::EmptyLand, start, goal),
+                     SearchResult::PathNotFound));
+
+    // A one step slope leading up to it can be walked.
+    for y in 0..3 {
+        graph.set_node_elevation(Node::new(Cell::new(3, y)), 1);
+        graph.set_node_elevation(Node::new(Cell::new(5, y)), 1);
+    }
+    match Search::with_graph(&graph).find_path(&graph, &heuristic, NodeKind::EmptyLand, start, goal) {
+        SearchResult::PathFound(path) => assert_eq!(path.len(), 8),
+        SearchResult::PathNotFound => panic!("Expected a path over the slope!"),
+    }
+}
+
+#[test]
+fn test_water_network() {
+    let mut graph = Graph::with_node_kind(Size::new(8, 3), NodeKind::EmptyLand);
+    let reservoir = Node::new(Cell::new(0, 1));
+    let fountain_cells = CellRange::new(Cell::new(5, 0), Cell::new(5, 0));
+
+    // Aqueduct laid before the reservoir stays dry.
+    for x in 1..=5 {
+        graph.set_node_kind(Node::new(Cell::new(x, 1)), NodeKind::Aqueduct);
+    }
+    assert!(!graph.is_water_supplied(Node::new(Cell::new(3, 1))));
+    assert!(!graph.has_water_supply_nearby(fountain_cells));
+
+    let version = graph.water_network_version();
+    graph.set_node_kind(reservoir, NodeKind::Building | NodeKind::Reservoir);
+    assert_ne!(graph.water_network_version(), version);
+    assert!(graph.is_water_supplied(reservoir));
+    assert!(graph.is_water_supplied(Node::new(Cell::new(5, 1))));
+    assert!(graph.has_water_supply_nearby(fountain_cells));
+
+    // Only cell sides count, not corners.
+    assert!(!graph.has_water_supply_nearby(CellRange::new(Cell::new(6, 0), Cell::new(6, 0))));
+
+    // Breaking the aqueduct cuts off everything past the gap.
+    graph.set_node_kind(Node::new(Cell::new(3, 1)), NodeKind::EmptyLand);
+    assert!(graph.is_water_supplied(Node::new(Cell::new(2, 1))));
+    assert!(!graph.is_water_supplied(Node::new(Cell::new(4, 1))));
+    assert!(!graph.has_water_supply_nearby(fountain_cells));
+
+    // Repairing it brings the water back.
+    graph.set_node_kind(Node::new(Cell::new(3, 1)), NodeKind::Aqueduct);
+    assert!(graph.has_water_supply_nearby(fountain_cells));
+}
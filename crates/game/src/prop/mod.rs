@@ -106,6 +106,13 @@ impl GameObject for Prop {
         self.id
     }
 
+    #[inline]
+    fn relocate(&mut self, new_id: PropId) {
+        debug_assert!(self.is_spawned());
+        debug_assert!(new_id.generation() == self.id.generation());
+        self.id = new_id;
+    }
+
     #[inline]
     fn update(&mut self, cmds: &mut SimCmds, context: &SimContext) {
         debug_assert!(self.is_spawned());
@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use super::RandomEvents;
+use crate::{
+    building::BuildingKind,
+    sim::calendar::Calendar,
+    world::{World, stats::WorldStats},
+};
+
+// ----------------------------------------------
+// Random Event Definitions (data-driven)
+// ----------------------------------------------
+
+// Top-level random event configs, loaded from `assets/configs/game/random_events.json`.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct RandomEventConfigs {
+    // Every this many in-game days the scheduler rolls for an event.
+    pub roll_frequency_days: u32,
+    // [0,1] chance that a roll fires one of the eligible events, picked by weight.
+    pub event_chance: f32,
+    pub events: Vec<RandomEventDef>,
+}
+
+impl Default for RandomEventConfigs {
+    fn default() -> Self {
+        Self { roll_frequency_days: 30, event_chance: 0.25, events: Vec::new() }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RandomEventDef {
+    pub name: String,
+    // Relative probability of this event among the eligible ones. Zero disables it.
+    pub weight: u32,
+    // All must hold for the event to be eligible.
+    #[serde(default)]
+    pub prerequisites: Vec<RandomEventPrerequisite>,
+    // Minimum in-game days before the same event can fire again.
+    #[serde(default)]
+    pub cooldown_days: u32,
+    pub effects: Vec<RandomEventEffect>,
+    // Shown to the player in the in-game notification feed.
+    pub notification: String,
+    // Disasters use the alert notification icon.
+    #[serde(default)]
+    pub is_disaster: bool,
+}
+
+// Everything prerequisites are evaluated against.
+pub struct RandomEventState<'a> {
+    pub stats: &'a WorldStats,
+    pub calendar: &'a Calendar,
+    // Building kinds with at least one building in the world.
+    pub building_kinds: BuildingKind,
+}
+
+impl<'a> RandomEventState<'a> {
+    pub fn new(world: &'a World, calendar: &'a Calendar) -> Self {
+        let mut building_kinds = BuildingKind::empty();
+
+        for kinds in RandomEvents::BUILDING_KINDS {
+            world.for_each_building(kinds, |building| {
+                building_kinds.insert(building.kind());
+                true
+            });
+        }
+
+        Self { stats: world.stats(), calendar, building_kinds }
+    }
+}
+
+// Extend by adding a variant + a match arm in `is_met`.
+#[derive(Serialize, Deserialize)]
+pub enum RandomEventPrerequisite {
+    MinPopulation { min: u32 },
+    MinGold       { min: u32 },
+    MinDays       { days: u32 },
+    // Zero-based months of the year the event can happen in.
+    Months        { months: Vec<u32> },
+    // At least one building of any of these kinds.
+    HasBuilding   { kinds: BuildingKind },
+}
+
+impl RandomEventPrerequisite {
+    pub fn is_met(&self, state: &RandomEventState) -> bool {
+        match self {
+            Self::MinPopulation { min } => state.stats.population.total >= *min,
+            Self::MinGold       { min } => state.stats.treasury.gold_units_total >= *min,
+            Self::MinDays       { days } => state.calendar.days_elapsed() >= *days,
+            Self::Months        { months } => months.contains(&state.calendar.month_of_year()),
+            Self::HasBuilding   { kinds } => state.building_kinds.intersects(*kinds),
+        }
+    }
+}
+
+impl std::fmt::Display for RandomEventPrerequisite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MinPopulation { min } => write!(f, "Population >= {min}"),
+            Self::MinGold       { min } => write!(f, "Gold >= {min}"),
+            Self::MinDays       { days } => write!(f, "Day >= {days}"),
+            Self::Months        { months } => write!(f, "Months {months:?}"),
+            Self::HasBuilding   { kinds } => write!(f, "Has {kinds}"),
+        }
+    }
+}
+
+// Changes applied to the sim state when the event fires. See `RandomEvents::apply_effect`.
+#[derive(Serialize, Deserialize)]
+pub enum RandomEventEffect {
+    // Random buildings collapse and are replaced by rubble.
+    CollapseBuildings  { count: u32 },
+    // Added to the collapse risk of every building.
+    AddCollapseRisk    { amount: f32 },
+    // Extra output added to the local stock of producers of these kinds.
+    BonusProduction    { kinds: BuildingKind, amount: u32 },
+    // Raises or lowers the events factor of every household's happiness.
+    RaiseHappiness     { amount: f32 },
+    LowerHappiness     { penalty: f32 },
+    GrantGold          { amount: u32 },
+    SpendGold          { amount: u32 },
+}
+
+impl std::fmt::Display for RandomEventEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CollapseBuildings { count } => write!(f, "Collapse {count} buildings"),
+            Self::AddCollapseRisk   { amount } => write!(f, "+{amount} collapse risk"),
+            Self::BonusProduction   { kinds, amount } => write!(f, "+{amount} output for {kinds}"),
+            Self::RaiseHappiness    { amount } => write!(f, "+{amount} happiness"),
+            Self::LowerHappiness    { penalty } => write!(f, "-{penalty} happiness"),
+            Self::GrantGold         { amount } => write!(f, "+{amount} gold"),
+            Self::SpendGold         { amount } => write!(f, "-{amount} gold"),
+        }
+    }
+}
+
+impl RandomEventDef {
+    pub fn prerequisites_met(&self, state: &RandomEventState) -> bool {
+        self.prerequisites.iter().all(|prerequisite| prerequisite.is_met(state))
+    }
+}
+
+impl RandomEventConfigs {
+    #[inline]
+    pub fn event(&self, index: usize) -> Option<&RandomEventDef> {
+        self.events.get(index)
+    }
+}
+
+// ----------------------------------------------
+// RandomEventConfigs Global Singleton
+// ----------------------------------------------
+
+engine::configurations! { RANDOM_EVENT_CONFIGS_SINGLETON, RandomEventConfigs, "game/random_events" }
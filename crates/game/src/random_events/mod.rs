@@ -0,0 +1,268 @@
+// ----------------------------------------------
+// Random Events
+// ----------------------------------------------
+//
+// Data-driven events (earthquakes, bumper harvests, festivals, ...) defined in
+// `config::RandomEventConfigs`. The `Simulation` owns a `RandomEvents` scheduler
+// and updates it once per sim update; every `roll_frequency_days` in-game days it
+// rolls `event_chance` and fires one of the eligible events, picked by weight.
+//
+// Effects on buildings are queued as deferred sim commands, so they are applied
+// together with the rest of the commands of that sim update.
+
+pub mod config;
+
+use config::{RandomEventConfigs, RandomEventDef, RandomEventEffect, RandomEventState};
+use serde::{Deserialize, Serialize};
+
+use common::coords::Cell;
+use engine::log;
+
+use crate::{
+    building::{Building, BuildingKind, BuildingKindAndId},
+    sim::{
+        RngStream,
+        SimCmds,
+        SimContext,
+        SimCmdQueue,
+        events::{SimEventSource, sim_event},
+        notifications::notify,
+    },
+};
+
+// ----------------------------------------------
+// RandomEvents
+// ----------------------------------------------
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct RandomEvents {
+    // Calendar day of the next event roll.
+    next_roll_day: u32,
+
+    // Calendar day each event last fired on, by event name.
+    last_fired: Vec<(String, u32)>,
+}
+
+impl RandomEvents {
+    // Buildings that random events can affect.
+    pub(crate) const BUILDING_KINDS: [BuildingKind; 4] =
+        [BuildingKind::House, BuildingKind::producers(), BuildingKind::storage(), BuildingKind::services()];
+
+    pub fn update(&mut self, cmds: &mut SimCmds, context: &SimContext) {
+        let calendar = context.calendar();
+        if !calendar.day_started() || calendar.days_elapsed() < self.next_roll_day {
+            return;
+        }
+
+        let configs = RandomEventConfigs::get();
+        self.next_roll_day = calendar.days_elapsed() + configs.roll_frequency_days.max(1);
+
+        let event_roll: f32 = context.random_range_in(RngStream::Events, 0.0..1.0);
+        if event_roll >= configs.event_chance {
+            return;
+        }
+
+        let state = RandomEventState::new(context.world(), calendar);
+        let weights: Vec<u32> = configs
+            .events
+            .iter()
+            .map(|event| if self.is_eligible(event, &state) { event.weight } else { 0 })
+            .collect();
+
+        let total_weight: u32 = weights.iter().sum();
+        if total_weight == 0 {
+            return; // Nothing can happen right now.
+        }
+
+        if let Some(index) = pick_weighted(&weights, context.random_range_in(RngStream::Events, 0..total_weight)) {
+            self.fire(cmds, context, index);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    // Prerequisites hold and the event is not cooling down.
+    pub fn is_eligible(&self, event: &RandomEventDef, state: &RandomEventState) -> bool {
+        if event.weight == 0 || !event.prerequisites_met(state) {
+            return false;
+        }
+
+        match self.last_fired_day(&event.name) {
+            Some(day) => state.calendar.days_elapsed() >= day + event.cooldown_days,
+            None => true,
+        }
+    }
+
+    #[inline]
+    pub fn next_roll_day(&self) -> u32 {
+        self.next_roll_day
+    }
+
+    pub fn last_fired_day(&self, event_name: &str) -> Option<u32> {
+        self.last_fired.iter().find(|(name, _)| name == event_name).map(|(_, day)| *day)
+    }
+
+    // Fires the event regardless of its prerequisites and cooldown. Used by the debug UI.
+    pub fn fire(&mut self, cmds: &mut SimCmds, context: &SimContext, event_index: usize) {
+        let Some(event) = RandomEventConfigs::get().event(event_index) else {
+            log::error!(log::channel!("sim"), "Invalid random event index: {event_index}");
+            return;
+        };
+
+        let mut event_cell = Cell::invalid();
+        for effect in &event.effects {
+            if let Some(cell) = Self::apply_effect(cmds, context, effect)
+                && !event_cell.is_valid()
+            {
+                event_cell = cell;
+            }
+        }
+
+        let today = context.calendar().days_elapsed();
+        match self.last_fired.iter_mut().find(|(name, _)| *name == event.name) {
+            Some((_, day)) => *day = today,
+            None => self.last_fired.push((event.name.clone(), today)),
+        }
+
+        log::info!(log::channel!("sim"), "Random event '{}' fired on day {today}.", event.name);
+        sim_event!(StateChange, SimEventSource::System("RandomEvents"), event_cell, "{}", event.name);
+
+        if event.is_disaster {
+            notify!(Disaster, event_cell, "{}", event.notification);
+        } else {
+            notify!(CityEvent, event_cell, "{}", event.notification);
+        }
+    }
+
+    // Returns the map cell the player can jump to for this effect, if any.
+    fn apply_effect(cmds: &mut SimCmds, context: &SimContext, effect: &RandomEventEffect) -> Option<Cell> {
+        match *effect {
+            RandomEventEffect::CollapseBuildings { count } => Self::collapse_random_buildings(cmds, context, count),
+            RandomEventEffect::AddCollapseRisk { amount } => {
+                Self::for_each_building_deferred(cmds, context, BuildingKind::all(), move |building| {
+                    building.hazards_mut().accumulate_risks(0.0, amount);
+                });
+                None
+            }
+            RandomEventEffect::BonusProduction { kinds, amount } => {
+                Self::for_each_building_deferred(cmds, context, kinds & BuildingKind::producers(), move |building| {
+                    let producer = building.as_producer_mut();
+                    producer.add_production_output_stock(producer.production_output(), amount);
+                });
+                None
+            }
+            RandomEventEffect::RaiseHappiness { amount } => {
+                Self::for_each_building_deferred(cmds, context, BuildingKind::House, move |house| {
+                    house.as_house_mut().add_positive_event(amount);
+                });
+                None
+            }
+            RandomEventEffect::LowerHappiness { penalty } => {
+                Self::for_each_building_deferred(cmds, context, BuildingKind::House, move |house| {
+                    house.as_house_mut().add_negative_event(penalty);
+                });
+                None
+            }
+            RandomEventEffect::GrantGold { amount } => {
+                context.treasury_mut().add_gold_units(amount);
+                None
+            }
+            RandomEventEffect::SpendGold { amount } => {
+                context.treasury_mut().subtract_gold_units_global(context.world_mut(), amount);
+                None
+            }
+        }
+    }
+
+    // Collapses up to `count` random buildings that are not already burning or destroyed.
+    // The HazardsSystem replaces them with rubble on its next update.
+    fn collapse_random_buildings(cmds: &mut SimCmds, context: &SimContext, count: u32) -> Option<Cell> {
+        let mut candidates: Vec<(BuildingKindAndId, Cell)> = Vec::new();
+
+        for kinds in Self::BUILDING_KINDS {
+            context.world().for_each_building(kinds, |building| {
+                let hazards = building.hazards();
+                if !hazards.is_burning() && !hazards.is_destroyed() {
+                    candidates.push((building.kind_and_id(), building.base_cell()));
+                }
+                true
+            });
+        }
+
+        let mut first_cell = None;
+
+        for _ in 0..count {
+            if candidates.is_empty() {
+                break;
+            }
+
+            let index = context.random_range_in(RngStream::Events, 0..candidates.len());
+            let (kind_and_id, cell) = candidates.swap_remove(index);
+            first_cell.get_or_insert(cell);
+
+            cmds.defer_building_update(kind_and_id, |_context, building| {
+                building.hazards_mut().collapse();
+                log::info!(log::channel!("hazards"), "{} collapsed at {}.", building.name(), building.base_cell());
+                sim_event!(
+                    StateChange,
+                    SimEventSource::Building(building.kind_and_id()),
+                    building.base_cell(),
+                    "Collapsed (random event)"
+                );
+            });
+        }
+
+        first_cell
+    }
+
+    fn for_each_building_deferred<F>(cmds: &mut SimCmds, context: &SimContext, kinds: BuildingKind, update_fn: F)
+    where
+        F: Fn(&mut Building) + Copy + 'static,
+    {
+        for archetype_kinds in Self::BUILDING_KINDS {
+            let kinds = kinds & archetype_kinds;
+            if kinds.is_empty() {
+                continue;
+            }
+
+            context.world().for_each_building(archetype_kinds, |building| {
+                if kinds.intersects(building.kind()) {
+                    cmds.defer_building_update(building.kind_and_id(), move |_context, building| update_fn(building));
+                }
+                true
+            });
+        }
+    }
+}
+
+// Index of the weighted entry `roll` lands on, with `roll` in [0, sum of weights).
+fn pick_weighted(weights: &[u32], mut roll: u32) -> Option<usize> {
+    for (index, &weight) in weights.iter().enumerate() {
+        if roll < weight {
+            return Some(index);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weighted_pick_skips_zero_weights() {
+        let weights = [0, 3, 0, 1];
+
+        assert_eq!(pick_weighted(&weights, 0), Some(1));
+        assert_eq!(pick_weighted(&weights, 2), Some(1));
+        assert_eq!(pick_weighted(&weights, 3), Some(3));
+        assert_eq!(pick_weighted(&weights, 4), None);
+    }
+}
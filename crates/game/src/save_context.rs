@@ -95,7 +95,7 @@ impl<'game> PreLoadContext<'game> {
 // ----------------------------------------------
 
 pub struct PostLoadContext<'game> {
-    engine: &'game mut Engine,
+    engine: Option<&'game mut Engine>, // None when restoring headless (no render/sound/UI).
     configs: &'static GameConfigs,
     rng: RcMut<RandomGenerator>,
     tile_map: RcMut<TileMap>,
@@ -109,7 +109,14 @@ impl<'game> PostLoadContext<'game> {
         rng: RcMut<RandomGenerator>,
         tile_map: RcMut<TileMap>,
     ) -> Self {
-        Self { engine, configs, rng, tile_map }
+        Self { engine: Some(engine), configs, rng, tile_map }
+    }
+
+    // Headless post-load, for restoring just the simulation state (TileMap/World/Simulation)
+    // without an Engine, e.g. from tests. Anything that needs the Engine must not be loaded.
+    #[inline]
+    pub fn without_engine(configs: &'static GameConfigs, rng: RcMut<RandomGenerator>, tile_map: RcMut<TileMap>) -> Self {
+        Self { engine: None, configs, rng, tile_map }
     }
 
     #[inline]
@@ -119,12 +126,12 @@ impl<'game> PostLoadContext<'game> {
 
     #[inline]
     pub fn engine(&self) -> &Engine {
-        self.engine
+        self.engine.as_deref().expect("PostLoadContext has no Engine!")
     }
 
     #[inline]
     pub fn engine_mut(&mut self) -> &mut Engine {
-        self.engine
+        self.engine.as_deref_mut().expect("PostLoadContext has no Engine!")
     }
 
     #[inline]
@@ -149,6 +156,6 @@ impl<'game> PostLoadContext<'game> {
 
     #[inline]
     pub fn configs_and_engine(&mut self) -> (&'static GameConfigs, &mut Engine) {
-        (self.configs, self.engine)
+        (self.configs, self.engine.as_deref_mut().expect("PostLoadContext has no Engine!"))
     }
 }
@@ -0,0 +1,103 @@
+// Save game metadata shown by the load game browser.
+//
+// The header is serialized ahead of the rest of the GameSession, so it can
+// be read back with a lightweight probe instead of loading the whole session.
+
+use serde::{Deserialize, Serialize};
+
+use common::{
+    Size,
+    time::{self, Seconds},
+};
+use engine::{file_sys::paths::PathRef, log, save};
+
+use crate::{tile::TileMap, world::World};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Thumbnails are at most this many pixels on either side.
+pub const SAVE_THUMBNAIL_MAX_SIZE: i32 = 96;
+
+// ----------------------------------------------
+// SaveGameThumbnail
+// ----------------------------------------------
+
+// Downscaled minimap captured at save time. RGBA8 pixels, row by row.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SaveGameThumbnail {
+    pub size: Size,
+    pub pixels: Vec<u8>,
+}
+
+impl SaveGameThumbnail {
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.size.is_valid() && self.pixels.len() == (self.size.width * self.size.height * 4) as usize
+    }
+}
+
+// ----------------------------------------------
+// SaveGameHeader
+// ----------------------------------------------
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveGameHeader {
+    pub timestamp: u64, // Wall-clock seconds since the Unix epoch.
+    pub map_size: Size,
+    pub population: u32,
+    pub playtime_secs: Seconds, // Real time spent in game, accumulated across saves.
+    pub thumbnail: Option<SaveGameThumbnail>,
+}
+
+// Minimal struct to read just the header of a save file.
+#[derive(Deserialize)]
+struct SaveGameHeaderProbe {
+    #[serde(default)]
+    header: SaveGameHeader,
+}
+
+impl SaveGameHeader {
+    // Reads the header of an existing save file. Saves written before
+    // headers were added report default (empty) metadata.
+    pub fn load(save_file: PathRef) -> Option<Self> {
+        match save::storage::load_save_file::<SaveGameHeaderProbe>(save_file) {
+            Ok(probe) => Some(probe.header),
+            Err(err) => {
+                log::error!(log::channel!("session"), "Failed to read save header '{save_file}': {err}");
+                None
+            }
+        }
+    }
+
+    #[inline]
+    pub fn add_playtime(&mut self, delta_time_secs: Seconds) {
+        self.playtime_secs += delta_time_secs;
+    }
+
+    // Refreshes the metadata from the current session state just before saving.
+    pub fn capture(&mut self, tile_map: &TileMap, world: &World, with_thumbnail: bool) {
+        self.timestamp = time::unix_timestamp_secs();
+        self.map_size = tile_map.size_in_cells();
+        self.population = world.stats().population.total;
+
+        self.thumbnail = if with_thumbnail {
+            tile_map
+                .minimap()
+                .capture_thumbnail(SAVE_THUMBNAIL_MAX_SIZE)
+                .map(|(size, pixels)| SaveGameThumbnail { size, pixels })
+        } else {
+            None
+        };
+    }
+
+    // "2h 05m" or "12m" for playtimes under an hour.
+    pub fn playtime_text(&self) -> String {
+        let total_minutes = (self.playtime_secs.max(0.0) / 60.0) as u32;
+        let (hours, minutes) = (total_minutes / 60, total_minutes % 60);
+
+        if hours > 0 { format!("{hours}h {minutes:02}m") } else { format!("{minutes}m") }
+    }
+}
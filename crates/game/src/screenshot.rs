@@ -0,0 +1,213 @@
+use image::RgbaImage;
+
+use common::{
+    Rect,
+    Size,
+    Vec2,
+    constants::BASE_TILE_HEIGHT_F32,
+    coords::{IsoDiamond, WorldToScreenTransform},
+    format_fixed_string,
+};
+use engine::{
+    file_sys::{self, paths},
+    log,
+    render::RenderSystem,
+};
+
+use crate::camera::CameraZoom;
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+const SCREENSHOTS_DIR: &str = "screenshots";
+
+// Zoom used by the map export key binding.
+pub const DEFAULT_MAP_EXPORT_ZOOM: f32 = CameraZoom::DEFAULT;
+
+// Extra room above the map diamond for sprites taller than their tile (buildings, trees).
+const MAP_EXPORT_TOP_MARGIN_TILES: f32 = 8.0;
+
+// Frames rendered with each chunk in view before capturing it. Streamed tile
+// textures requested by the first frame are resident by the second.
+const MAP_EXPORT_FRAMES_PER_CHUNK: u32 = 2;
+
+// ----------------------------------------------
+// Screenshots
+// ----------------------------------------------
+
+// Saves the world drawn by the last completed frame (no UI) to `screenshots/screenshot_<N>.png`.
+pub fn save_screenshot(render_sys: &mut RenderSystem) -> bool {
+    let Some(frame) = render_sys.capture_frame() else {
+        log::error!(log::channel!("screenshot"), "Failed to capture frame for screenshot.");
+        return false;
+    };
+
+    save_png("screenshot", &frame.into_image())
+}
+
+// ----------------------------------------------
+// MapExport
+// ----------------------------------------------
+
+// Renders the whole tile map at a fixed zoom in viewport sized chunks, one chunk every
+// MAP_EXPORT_FRAMES_PER_CHUNK frames, and stitches the captured chunks into a single
+// image saved to `screenshots/map_<N>.png`. The game loop drives the camera with
+// `chunk_transform()` while an export is in progress and calls `on_frame_end()`.
+pub struct MapExport {
+    zoom: f32,
+    map_rect: Rect,      // Screen space map bounds at `zoom`, without camera offset.
+    chunk_size: Size,    // Logical (viewport) size of each chunk.
+    chunk_counts: Size,  // Columns x rows.
+    current_chunk: i32,  // Chunk in view, row major.
+    frames_in_chunk: u32,
+
+    // Allocated with the first captured chunk, once the render target size is known.
+    image: Option<RgbaImage>,
+    chunk_pixel_size: Size,
+}
+
+impl MapExport {
+    pub fn new(map_size_in_cells: Size, viewport_size: Size, zoom: f32) -> Self {
+        debug_assert!(map_size_in_cells.is_valid());
+        debug_assert!(viewport_size.is_valid());
+
+        let zoom = zoom.clamp(CameraZoom::MIN, CameraZoom::MAX);
+
+        let transform = WorldToScreenTransform::new(zoom, Vec2::zero());
+        let diamond_rect = IsoDiamond::from_tile_map(map_size_in_cells, transform).bounding_rect();
+
+        let top_margin = MAP_EXPORT_TOP_MARGIN_TILES * BASE_TILE_HEIGHT_F32 * zoom;
+        let map_rect = Rect::from_extents(diamond_rect.min - Vec2::new(0.0, top_margin), diamond_rect.max);
+
+        let chunk_counts = Size::new(
+            (map_rect.width()  / viewport_size.width  as f32).ceil() as i32,
+            (map_rect.height() / viewport_size.height as f32).ceil() as i32,
+        );
+
+        log::info!(
+            log::channel!("screenshot"),
+            "Exporting map at zoom {zoom:.2}: {} chunks ({}x{})...",
+            chunk_counts.width * chunk_counts.height,
+            chunk_counts.width,
+            chunk_counts.height
+        );
+
+        Self {
+            zoom,
+            map_rect,
+            chunk_size: viewport_size,
+            chunk_counts,
+            current_chunk: 0,
+            frames_in_chunk: 0,
+            image: None,
+            chunk_pixel_size: Size::zero(),
+        }
+    }
+
+    // Camera transform that frames the current chunk in the viewport.
+    pub fn chunk_transform(&self) -> WorldToScreenTransform {
+        let chunk_origin = self.map_rect.min + self.chunk_position().to_vec2() * self.chunk_size.to_vec2();
+        WorldToScreenTransform::new(self.zoom, -chunk_origin)
+    }
+
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        self.current_chunk as f32 / self.chunk_count() as f32
+    }
+
+    // Call after the frame has been presented. Returns true once the export is
+    // finished, either saved or aborted on error.
+    pub fn on_frame_end(&mut self, render_sys: &mut RenderSystem) -> bool {
+        self.frames_in_chunk += 1;
+        if self.frames_in_chunk < MAP_EXPORT_FRAMES_PER_CHUNK {
+            return false;
+        }
+        self.frames_in_chunk = 0;
+
+        let Some(frame) = render_sys.capture_frame() else {
+            log::error!(log::channel!("screenshot"), "Map export aborted: failed to capture chunk {}.", self.current_chunk);
+            return true;
+        };
+
+        if self.image.is_none() {
+            self.chunk_pixel_size = frame.size;
+            self.image = Some(RgbaImage::new(
+                (frame.size.width  * self.chunk_counts.width)  as u32,
+                (frame.size.height * self.chunk_counts.height) as u32,
+            ));
+        }
+
+        // The render target is recreated if the window grows; chunks would no longer line up.
+        if frame.size != self.chunk_pixel_size {
+            log::error!(log::channel!("screenshot"), "Map export aborted: render target resized during export.");
+            return true;
+        }
+
+        let chunk_position = self.chunk_position();
+        let chunk_pixel_x = (chunk_position.width  * self.chunk_pixel_size.width)  as i64;
+        let chunk_pixel_y = (chunk_position.height * self.chunk_pixel_size.height) as i64;
+        image::imageops::replace(self.image.as_mut().unwrap(), &frame.into_image(), chunk_pixel_x, chunk_pixel_y);
+
+        self.current_chunk += 1;
+        if self.current_chunk < self.chunk_count() {
+            return false;
+        }
+
+        // Crop the partially covered last column and row to the map bounds.
+        let pixels_per_unit = self.chunk_pixel_size.to_vec2() / self.chunk_size.to_vec2();
+        let map_pixel_size = self.map_rect.size() * pixels_per_unit;
+        let stitched = self.image.take().unwrap();
+        let cropped = image::imageops::crop_imm(
+            &stitched,
+            0,
+            0,
+            (map_pixel_size.x.ceil() as u32).min(stitched.width()),
+            (map_pixel_size.y.ceil() as u32).min(stitched.height()),
+        )
+        .to_image();
+
+        save_png("map", &cropped);
+        true
+    }
+
+    #[inline]
+    fn chunk_count(&self) -> i32 {
+        self.chunk_counts.width * self.chunk_counts.height
+    }
+
+    // Column and row of the current chunk.
+    #[inline]
+    fn chunk_position(&self) -> Size {
+        Size::new(self.current_chunk % self.chunk_counts.width, self.current_chunk / self.chunk_counts.width)
+    }
+}
+
+// ----------------------------------------------
+// Internal helpers
+// ----------------------------------------------
+
+// Saves to the first free `<name_prefix>_<N>.png` in the screenshots directory.
+fn save_png(name_prefix: &str, image: &RgbaImage) -> bool {
+    let save_path = paths::base_path().join(SCREENSHOTS_DIR);
+    let _ = file_sys::create_path(&save_path);
+
+    let file_path = (0..)
+        .map(|index| save_path.join(format_fixed_string!(64, "{name_prefix}_{index:03}.png")))
+        .find(|file_path| !file_sys::exists(file_path))
+        .unwrap();
+
+    let mut png_bytes = Vec::new();
+    if let Err(err) = image.write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png) {
+        log::error!(log::channel!("screenshot"), "Failed to encode PNG image {file_path}: {err}");
+        return false;
+    }
+
+    if let Err(err) = file_sys::write_file(&file_path, &png_bytes) {
+        log::error!(log::channel!("screenshot"), "Failed to write PNG image {file_path}: {err}");
+        return false;
+    }
+
+    log::info!(log::channel!("screenshot"), "Saved {}x{} image to {file_path}", image.width(), image.height());
+    true
+}
@@ -1,6 +1,14 @@
 use std::{collections::VecDeque, path::PathBuf};
 
-use common::{Size, Vec2, coords::CellRange, hash, mem::RcMut, time::Seconds};
+use common::{
+    Size,
+    Vec2,
+    constants::{HALF_BASE_TILE_HEIGHT_F32, HALF_BASE_TILE_WIDTH_F32},
+    coords::{CellRange, IsoPointF32, MapRotation},
+    hash,
+    mem::RcMut,
+    time::Seconds,
+};
 use engine::{
     log,
     Engine,
@@ -14,19 +22,24 @@ use serde::{Deserialize, Serialize};
 use crate::{
     camera::*,
     undo_redo,
+    benchmark,
     world::World,
     campaign::{self, CampaignProgress},
-    config::{GameConfigs, LoadMapSetting},
+    config::{Difficulty, GameConfigs, LoadMapSetting},
     debug::{DevEditorMenus, preset_maps},
-    menu::{GameMenusInputArgs, GameMenusMode, GameMenusSystem, home::HomeMenus, in_game::InGameMenus},
+    menu::{self, GameMenusInputArgs, GameMenusMode, GameMenusSystem, home::HomeMenus, in_game::InGameMenus},
     save_context::*,
-    sim::Simulation,
+    save_header::SaveGameHeader,
+    session_settings::SessionSettings,
+    sim::{RandomGenerator, RngStream, RngStreams, Simulation},
     system::GameSystems,
     tile::{
         TileFlags,
         TileKind,
         TileMap,
-        rendering::{TileMapRenderFlags, TileMapRenderStats, TileMapRenderer},
+        TileMapLayerKind,
+        color_palette::ColorPalette,
+        rendering::{NodeGlyphsDebugMask, TileMapRenderFlags, TileMapRenderStats, TileMapRenderer},
         selection::TileSelection,
         sets::TileDef,
     },
@@ -42,23 +55,57 @@ use crate::{
 enum GameSessionCmd {
     QuitToMainMenu,
     ToggleMenusMode,
+    RebuildMenus,
+    Create { load_map_setting: LoadMapSetting, difficulty: Difficulty },
     Reset { reset_map_with_tile_def: Option<&'static TileDef>, new_map_size: Option<Size> },
     LoadPreset { preset_number: usize },
     LoadSaveGame { save_file: PathBuf },
     SaveGame { save_file: PathBuf },
 }
 
+impl std::fmt::Display for GameSessionCmd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::QuitToMainMenu  => write!(f, "QuitToMainMenu"),
+            Self::ToggleMenusMode => write!(f, "ToggleMenusMode"),
+            Self::RebuildMenus    => write!(f, "RebuildMenus"),
+            Self::Create { difficulty, .. } => write!(f, "Create (difficulty: {difficulty})"),
+            Self::Reset { reset_map_with_tile_def, new_map_size } => write!(
+                f,
+                "Reset (tile: {}, map size: {})",
+                reset_map_with_tile_def.map_or("<none>", |tile_def| tile_def.name.as_str()),
+                new_map_size.map_or("<unchanged>".into(), |size| size.to_string())
+            ),
+            Self::LoadPreset { preset_number } => write!(f, "LoadPreset ({preset_number})"),
+            Self::LoadSaveGame { save_file }   => write!(f, "LoadSaveGame ({})", save_file.display()),
+            Self::SaveGame { save_file }       => write!(f, "SaveGame ({})", save_file.display()),
+        }
+    }
+}
+
 // ----------------------------------------------
 // GameSessionCmdQueue
 // ----------------------------------------------
 
 pub struct GameSessionCmdQueue {
     queue: VecDeque<GameSessionCmd>,
+    history: VecDeque<String>, // Last executed commands, for crash reports.
 }
 
 impl GameSessionCmdQueue {
+    const HISTORY_LEN: usize = 8;
+
     pub fn new() -> Self {
-        Self { queue: VecDeque::with_capacity(8) }
+        Self {
+            queue: VecDeque::with_capacity(8),
+            history: VecDeque::with_capacity(Self::HISTORY_LEN),
+        }
+    }
+
+    // Most recently executed commands, oldest first.
+    #[inline]
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(String::as_str)
     }
 
     pub fn push_quit_to_main_menu(&mut self) {
@@ -69,6 +116,14 @@ impl GameSessionCmdQueue {
         self.queue.push_back(GameSessionCmd::ToggleMenusMode);
     }
 
+    pub fn push_rebuild_menus(&mut self) {
+        self.queue.push_back(GameSessionCmd::RebuildMenus);
+    }
+
+    pub fn push_create_session(&mut self, load_map_setting: LoadMapSetting, difficulty: Difficulty) {
+        self.queue.push_back(GameSessionCmd::Create { load_map_setting, difficulty });
+    }
+
     pub fn push_reset_session(&mut self, reset_map_with_tile_def: Option<&'static TileDef>, new_map_size: Option<Size>) {
         self.queue.push_back(GameSessionCmd::Reset { reset_map_with_tile_def, new_map_size });
     }
@@ -97,6 +152,11 @@ impl GameSessionCmdQueue {
 
     pub fn execute(&mut self, session: &mut GameSession, engine: &mut Engine, configs: &'static GameConfigs) {
         while let Some(cmd) = self.queue.pop_front() {
+            if self.history.len() == Self::HISTORY_LEN {
+                self.history.pop_front();
+            }
+            self.history.push_back(cmd.to_string());
+
             match cmd {
                 GameSessionCmd::QuitToMainMenu => {
                     self.cmd_quit_to_main_menu(session, engine, configs);
@@ -104,6 +164,12 @@ impl GameSessionCmdQueue {
                 GameSessionCmd::ToggleMenusMode => {
                     self.cmd_toggle_menus_mode(session, engine);
                 }
+                GameSessionCmd::RebuildMenus => {
+                    self.cmd_rebuild_menus(session, engine);
+                }
+                GameSessionCmd::Create { load_map_setting, difficulty } => {
+                    self.cmd_create_session(session, engine, configs, &load_map_setting, difficulty);
+                }
                 GameSessionCmd::Reset { reset_map_with_tile_def, new_map_size } => {
                     self.cmd_reset_session(session, engine, configs, reset_map_with_tile_def, new_map_size);
                 }
@@ -144,6 +210,26 @@ impl GameSessionCmdQueue {
         session.toggle_menus_mode(engine);
     }
 
+    fn cmd_rebuild_menus(&mut self, session: &mut GameSession, engine: &mut Engine) {
+        session.rebuild_menus(engine);
+    }
+
+    fn cmd_create_session(
+        &mut self,
+        session: &mut GameSession,
+        engine: &mut Engine,
+        configs: &'static GameConfigs,
+        load_map_setting: &LoadMapSetting,
+        difficulty: Difficulty,
+    ) {
+        // A new game from the menus abandons any active campaign, like cmd_reset_session.
+        campaign::reset();
+
+        destroy(session, engine, configs);
+        *session = GameSession::create_with_settings(engine, configs, load_map_setting, difficulty, false);
+        log::info!(log::channel!("session"), "--- Game Session Created ({difficulty}) ---");
+    }
+
     fn cmd_reset_session(
         &mut self,
         session: &mut GameSession,
@@ -200,9 +286,9 @@ pub fn create(engine: &mut Engine, configs: &'static GameConfigs, preset_number:
             GameSession::create_with_preset_map(engine, configs, preset_number)
         } else {
             let load_map_setting = &configs.save.load_map_setting;
-            let home_menu = !configs.debug.skip_home_menu;
+            let home_menu = !configs.debug.skip_home_menu && !engine.is_headless(); // No menus to show headless.
 
-            GameSession::create_with_settings(engine, configs, load_map_setting, home_menu)
+            GameSession::create_with_settings(engine, configs, load_map_setting, configs.sim.difficulty, home_menu)
         }
     };
 
@@ -212,6 +298,7 @@ pub fn create(engine: &mut Engine, configs: &'static GameConfigs, preset_number:
 
 pub fn destroy(session: &mut GameSession, engine: &mut Engine, configs: &'static GameConfigs) {
     session.reset(engine, configs, false, None, None, false);
+    session.tile_map_renderer.clear_terrain_chunk_cache(engine.render_system_mut());
     log::info!(log::channel!("session"), "--- Game Session Destroyed ---");
 }
 
@@ -245,6 +332,11 @@ pub struct GameSession {
     #[serde(default)]
     save_version: u32,
 
+    // Metadata for the load game browser. Kept ahead of the session data so it
+    // can be probed cheaply. `#[serde(default)]` keeps older saves loadable.
+    #[serde(default)]
+    header: SaveGameHeader,
+
     tile_map: RcMut<TileMap>,
     world: World,
     sim: Simulation,
@@ -256,6 +348,11 @@ pub struct GameSession {
     #[serde(default)]
     campaign_progress: CampaignProgress,
 
+    // Player options that travel with the save (autosave, speed, overlay, warnings).
+    // `#[serde(default)]` keeps older saves loadable.
+    #[serde(default)]
+    settings: SessionSettings,
+
     // NOTE: The following members are not serialized on save games.
     // We only need to invoke pre_load/post_load on them.
     #[serde(skip)]
@@ -303,6 +400,16 @@ impl GameSession {
         &mut self.sim
     }
 
+    #[inline]
+    pub fn settings(&self) -> &SessionSettings {
+        &self.settings
+    }
+
+    #[inline]
+    pub fn settings_mut(&mut self) -> &mut SessionSettings {
+        &mut self.settings
+    }
+
     #[inline]
     pub fn world(&self) -> &World {
         &self.world
@@ -313,6 +420,11 @@ impl GameSession {
         &mut self.world
     }
 
+    #[inline]
+    pub fn world_and_rng_mut(&mut self) -> (&mut World, &mut RandomGenerator) {
+        (&mut self.world, self.sim.rng_mut())
+    }
+
     #[inline]
     pub fn tile_map(&self) -> &TileMap {
         &self.tile_map
@@ -343,18 +455,71 @@ impl GameSession {
         self.tile_map_renderer.grid_line_thickness()
     }
 
+    #[inline]
+    pub fn set_node_glyphs_debug_mask(&mut self, mask: NodeGlyphsDebugMask) {
+        self.tile_map_renderer.set_node_glyphs_debug_mask(mask);
+    }
+
+    #[inline]
+    pub fn node_glyphs_debug_mask(&self) -> NodeGlyphsDebugMask {
+        self.tile_map_renderer.node_glyphs_debug_mask()
+    }
+
     // ----------------------
     // Update & Rendering:
     // ----------------------
 
+    // Pan animation and unit following. Run after manual camera scrolling, which cancels both.
+    pub fn update_camera_panning(&mut self, delta_time_secs: Seconds) {
+        if let Some(unit_id) = self.camera.followed_unit() {
+            match self.world.find_unit(unit_id) {
+                Some(unit) => {
+                    // Track the interpolated tile position, not the unit's cell, so movement stays smooth.
+                    let tile = self.tile_map.tile_at_index(unit.tile_index(), TileMapLayerKind::Objects);
+                    let tile_iso = tile.view_iso_coords_f32(self.tile_map.rotation(), self.tile_map.size_in_cells());
+                    let half_size = tile.draw_size().to_vec2() * 0.5;
+                    self.camera.teleport_iso(IsoPointF32(tile_iso.0 + half_size));
+                }
+                None => self.camera.cancel_panning(), // Unit despawned.
+            }
+        }
+
+        self.camera.update_panning(delta_time_secs);
+    }
+
+    // Rotates the map view 90 degrees clockwise, keeping the same map spot at the screen center.
+    pub fn rotate_map_view_clockwise(&mut self) -> bool {
+        let old_rotation = self.tile_map.rotation();
+        if !self.tile_map.set_rotation(old_rotation.next_clockwise()) {
+            return false;
+        }
+
+        // Screen center in the same iso convention teleport_iso() takes (iso_world_position()
+        // includes the half tile offset). Rotating the view once more is a 90 degree rotation
+        // of the current view space around the map center.
+        let half_tile = Vec2::new(HALF_BASE_TILE_WIDTH_F32, HALF_BASE_TILE_HEIGHT_F32);
+        let center_iso = IsoPointF32(self.camera.iso_world_position().0 - half_tile);
+        let new_center_iso = MapRotation::Deg90.rotate_iso_point(center_iso, self.tile_map.size_in_cells());
+
+        // Followed units re-center themselves, in-flight pans target the old view space.
+        if self.camera.followed_unit().is_none() {
+            self.camera.cancel_panning();
+        }
+        self.camera.teleport_iso(new_center_iso);
+
+        log::info!(log::channel!("game"), "Map view rotation: {}", self.tile_map.rotation());
+        true
+    }
+
     pub fn update_simulation(&mut self, engine: &mut Engine, delta_time_secs: Seconds) {
+        self.header.add_playtime(delta_time_secs);
         self.sim.update(engine, &mut self.world, &mut self.systems, &mut self.tile_map, delta_time_secs);
     }
 
     pub fn update_anims(&mut self, visible_range: CellRange, delta_time_secs: Seconds) {
         if !self.sim.is_paused() {
             let scaled_delta_time_secs = delta_time_secs * self.sim.speed();
-            self.tile_map.update_anims(visible_range, scaled_delta_time_secs);
+            self.tile_map.update_anims(visible_range, self.camera.current_zoom(), scaled_delta_time_secs);
         }
     }
 
@@ -368,7 +533,32 @@ impl GameSession {
         let systems = engine.systems_mut_refs();
         let tex_cache = systems.render_sys.texture_cache_mut();
 
-        self.tile_map.minimap_mut().update(&mut self.camera, tex_cache, systems.input_sys, systems.ui_sys, delta_time_secs);
+        let view_rotation = self.tile_map.rotation();
+        self.tile_map.minimap_mut().update(
+            &mut self.camera,
+            view_rotation,
+            tex_cache,
+            systems.input_sys,
+            systems.ui_sys,
+            delta_time_secs,
+        );
+
+        let mut flags = flags;
+        if GameConfigs::get().engine.selection_outline {
+            flags.insert(TileMapRenderFlags::DrawSelectionOutline);
+        }
+        if GameConfigs::get().engine.cache_terrain_chunks {
+            flags.insert(TileMapRenderFlags::CacheTerrainChunks);
+        }
+        if GameConfigs::get().engine.parallel_tile_gather {
+            flags.insert(TileMapRenderFlags::ParallelTileGather);
+        }
+        if GameConfigs::get().color_palette == ColorPalette::ColorBlindSafe {
+            flags.insert(TileMapRenderFlags::ColorBlindPalette);
+        }
+        if GameConfigs::get().hatch_patterns {
+            flags.insert(TileMapRenderFlags::DrawHatchPatterns);
+        }
 
         if self.tile_map.size_in_cells().is_valid() {
             self.tile_map_renderer.draw_map(
@@ -393,6 +583,7 @@ impl GameSession {
         engine: &mut Engine,
         configs: &'static GameConfigs,
         load_map_setting: &LoadMapSetting,
+        difficulty: Difficulty,
         home_menu: bool,
     ) -> Self {
         let viewport_size = engine.viewport().integer_size();
@@ -404,19 +595,21 @@ impl GameSession {
         let mut world = World::new();
         let tile_map = Self::create_tile_map(&mut world, load_map_setting);
 
-        let sim = Simulation::new(tile_map.size_in_cells(), configs);
+        let sim = Simulation::with_difficulty(tile_map.size_in_cells(), configs, difficulty);
         let systems = GameSystems::register_all();
 
         let camera = Camera::new(viewport_size, tile_map.size_in_cells(), configs.camera.zoom, configs.camera.offset);
 
         let mut session = Self {
             save_version: CURRENT_SAVE_VERSION,
+            header: SaveGameHeader::default(),
             tile_map,
             world,
             sim,
             systems,
             camera,
             campaign_progress: CampaignProgress::default(),
+            settings: SessionSettings::from_configs(configs),
             tile_selection: TileSelection::default(),
             tile_map_renderer: TileMapRenderer::new(configs.engine.grid_color, configs.engine.grid_line_thickness),
             menus: None,
@@ -439,7 +632,7 @@ impl GameSession {
 
     fn create_with_preset_map(engine: &mut Engine, configs: &'static GameConfigs, preset_number: usize) -> Self {
         // Override GameConfigs.load_map_setting
-        Self::create_with_settings(engine, configs, &LoadMapSetting::Preset { preset_number }, false)
+        Self::create_with_settings(engine, configs, &LoadMapSetting::Preset { preset_number }, configs.sim.difficulty, false)
     }
 
     fn reset(
@@ -453,6 +646,9 @@ impl GameSession {
     ) {
         undo_redo::clear();
 
+        self.header = SaveGameHeader::default();
+        self.settings = SessionSettings::from_configs(configs);
+        self.settings.apply(&mut self.sim);
         self.tile_selection = TileSelection::default();
         self.menus = Some(self.create_game_menus_from_config(engine, configs, home_menu));
         self.sim.reset_world(engine, &mut self.world, &mut self.systems, &mut self.tile_map);
@@ -462,9 +658,10 @@ impl GameSession {
 
             if reset_map_with_tile_def.is_some() {
                 // Randomize terrain tiles.
+                let rng = self.sim.rng_stream_mut(RngStream::Placement);
                 self.tile_map.for_each_tile_mut(TileKind::Terrain, |_tile_map, terrain| {
                     if terrain.has_flags(TileFlags::RandomizePlacement) {
-                        terrain.set_random_variation_index(&mut rand::rng());
+                        terrain.set_random_variation_index(rng);
                     }
                 });
             }
@@ -501,6 +698,15 @@ impl GameSession {
         }
     }
 
+    // Recreates the current menus and all dialog menus, e.g. to pick up a language switch.
+    fn rebuild_menus(&mut self, engine: &mut Engine) {
+        if let Some(mode) = self.current_menus_mode() {
+            self.menus = None; // Drop old menus first, they reset the dialog menus.
+            menu::terminate_dialog_menus();
+            self.menus = Some(self.create_game_menus(engine, mode));
+        }
+    }
+
     fn create_game_menus(&mut self, engine: &mut Engine, menu_mode: GameMenusMode) -> Box<dyn GameMenusSystem> {
         let tile_map_rc = self.tile_map.clone();
         let mut context = make_ui_widget_context!(self, engine);
@@ -566,9 +772,11 @@ impl GameSession {
                     hash::fnv1a_from_str(terrain_tile_name),
                 );
 
+                // No Simulation yet, so seed a standalone placement stream.
+                let mut rng = RngStreams::new_stream(GameConfigs::get().sim.random_seed, RngStream::Placement);
                 tile_map.for_each_tile_mut(TileKind::Terrain, |_tile_map, terrain| {
                     if terrain.has_flags(TileFlags::RandomizePlacement) {
-                        terrain.set_random_variation_index(&mut rand::rng());
+                        terrain.set_random_variation_index(&mut rng);
                     }
                 });
 
@@ -586,6 +794,9 @@ impl GameSession {
                 // a dummy map here. The actual loading will be handled by the caller.
                 TileMap::default()
             }
+            LoadMapSetting::SyntheticCity { size_in_cells, building_count, unit_count } => {
+                benchmark::create_synthetic_city_tile_map(world, *size_in_cells, *building_count, *unit_count)
+            }
         })
     }
 }
@@ -596,8 +807,9 @@ impl GameSession {
 
 impl Save for GameSession {
     fn pre_save(&mut self, context: &mut PreSaveContext) {
-        // Capture campaign progress so it is serialized with the session.
+        // Capture campaign progress and session settings so they are serialized with the session.
         self.campaign_progress = campaign::capture_snapshot();
+        self.settings.capture(&self.sim);
 
         self.tile_map.pre_save(context);
         self.world.pre_save(context);
@@ -652,8 +864,10 @@ impl Load for GameSession {
 
         self.tile_map.post_load(context);
         self.world.post_load(context);
+        self.world.compact_spawn_pools(&mut self.tile_map);
         self.sim.post_load(context);
         self.systems.post_load(context);
+        self.world.clear_spawn_pool_relocations();
         self.camera.post_load(context);
         self.tile_selection.post_load(context);
 
@@ -672,6 +886,9 @@ impl Load for GameSession {
         // Restore campaign progress into the manager. No-op for campaign-driven
         // mission loads (the manager keeps its intended active mission instead).
         campaign::restore_snapshot(self.campaign_progress.clone());
+
+        // Restore the player's options from when the game was saved.
+        self.settings.apply(&mut self.sim);
     }
 }
 
@@ -682,7 +899,7 @@ impl Load for GameSession {
 // Save file format version. Bumped whenever the serialized layout changes in a
 // way that older saves cannot be deserialized directly. Old saves must be run
 // through the matching script in crates/tools/save_migration_scripts/.
-const CURRENT_SAVE_VERSION: u32 = 2;
+const CURRENT_SAVE_VERSION: u32 = 4;
 
 // Minimal struct to read just the save version ahead of a full deserialize.
 #[derive(Deserialize)]
@@ -692,7 +909,7 @@ struct SaveVersionProbe {
 }
 
 impl GameSession {
-    fn save_game(&mut self, save_file: PathRef) -> bool {
+    pub(crate) fn save_game(&mut self, save_file: PathRef) -> bool {
         log::info!(log::channel!("session"), "Saving game '{save_file}' ...");
 
         if !save::storage::can_write_save_file(save_file) {
@@ -701,6 +918,7 @@ impl GameSession {
         }
 
         self.save_version = CURRENT_SAVE_VERSION;
+        self.header.capture(&self.tile_map, &self.world, GameConfigs::get().save.save_thumbnails);
 
         self.pre_save(&mut PreSaveContext::new(self.sim.cmds().clone()));
         let save_result = save::storage::write_save_file(save_file, self);
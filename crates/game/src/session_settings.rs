@@ -0,0 +1,54 @@
+// Per-session game options, serialized with the save game.
+//
+// Unlike the global GameConfigs, these follow a save around: loading a save
+// restores the options the player had when it was saved. New sessions start
+// from the GameConfigs defaults.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{config::GameConfigs, sim::Simulation, tile::minimap::MinimapOverlays};
+
+// ----------------------------------------------
+// SessionSettings
+// ----------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)] // Saves from before SessionSettings get the GameConfigs defaults.
+pub struct SessionSettings {
+    pub autosave_enabled: bool,
+
+    // Player alerts (fires, invasions, random events) in the HUD notification feed.
+    pub warnings_enabled: bool,
+
+    // Captured from the Simulation and MinimapOverlays when saving.
+    pub game_speed: f32,
+    pub minimap_overlay: Option<String>,
+}
+
+impl SessionSettings {
+    pub fn from_configs(configs: &GameConfigs) -> Self {
+        Self {
+            autosave_enabled: configs.save.enable_autosave,
+            warnings_enabled: true,
+            game_speed: 1.0,
+            minimap_overlay: None,
+        }
+    }
+
+    // Snapshot of the options that live elsewhere while playing.
+    pub fn capture(&mut self, sim: &Simulation) {
+        self.game_speed = sim.speed();
+        self.minimap_overlay = MinimapOverlays::get().active_overlay_name().map(String::from);
+    }
+
+    pub fn apply(&self, sim: &mut Simulation) {
+        sim.set_speed(self.game_speed);
+        MinimapOverlays::get_mut().set_active_overlay(self.minimap_overlay.as_deref());
+    }
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        Self::from_configs(GameConfigs::get())
+    }
+}
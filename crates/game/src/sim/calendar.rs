@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+
+use common::time::Seconds;
+use proc_macros::DrawDebugUi;
+
+use crate::{
+    campaign::{self, config::CampaignConfigs},
+    config::{GameConfigs, SimConfigs},
+};
+
+// ----------------------------------------------
+// CalendarPacing
+// ----------------------------------------------
+
+// How fast in-game time passes. Defaults come from the game SimConfigs,
+// but a scenario (campaign mission) can override them.
+#[derive(Copy, Clone, PartialEq, DrawDebugUi, Serialize, Deserialize)]
+#[serde(default)] // Missing fields fall back to the game configs.
+pub struct CalendarPacing {
+    // Length of one in-game day, in simulation seconds.
+    pub seconds_per_day: Seconds,
+    pub days_per_month: u32,
+}
+
+impl CalendarPacing {
+    // Active mission pacing if it has one, otherwise the default pacing.
+    // Sims running without a campaign (tests, tools, free play) always get the default.
+    pub fn current() -> Self {
+        let Some(active) = campaign::active_mission() else {
+            return Self::default();
+        };
+
+        if !CampaignConfigs::is_initialized() {
+            return Self::default();
+        }
+
+        CampaignConfigs::get()
+            .mission(active.campaign_id, active.mission_index)
+            .and_then(|mission| mission.calendar)
+            .unwrap_or_default()
+    }
+
+    // Global game configs, or the built-in SimConfigs defaults if they are not loaded yet.
+    pub fn from_configs() -> Self {
+        if !GameConfigs::is_initialized() {
+            let configs = SimConfigs::default();
+            return Self { seconds_per_day: configs.seconds_per_day, days_per_month: configs.days_per_month };
+        }
+
+        let configs = &GameConfigs::get().sim;
+        Self { seconds_per_day: configs.seconds_per_day, days_per_month: configs.days_per_month }
+    }
+
+    #[inline]
+    pub fn seconds_per_month(&self) -> Seconds {
+        self.seconds_per_day * self.days_per_month as Seconds
+    }
+}
+
+impl Default for CalendarPacing {
+    fn default() -> Self {
+        Self::from_configs()
+    }
+}
+
+// ----------------------------------------------
+// Calendar
+// ----------------------------------------------
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Calendar {
+    // Not serialized. Resolved from configs on creation and post_load.
+    #[serde(skip)]
+    pacing: CalendarPacing,
+
+    // Whole days elapsed since the start of the game.
+    days_elapsed: u32,
+
+    // Seconds elapsed into the current day.
+    day_secs: Seconds,
+
+    // Set for the single sim update where a new day/month starts.
+    #[serde(skip)]
+    day_started: bool,
+    #[serde(skip)]
+    month_started: bool,
+}
+
+impl Calendar {
+    pub const MONTHS_PER_YEAR: u32 = 12;
+
+    pub fn new() -> Self {
+        Self { pacing: CalendarPacing::current(), ..Default::default() }
+    }
+
+    pub fn tick(&mut self, delta_time_secs: Seconds) {
+        self.day_started = false;
+        self.month_started = false;
+
+        if self.pacing.seconds_per_day <= 0.0 {
+            return; // Time is frozen.
+        }
+
+        self.day_secs += delta_time_secs;
+
+        while self.day_secs >= self.pacing.seconds_per_day {
+            self.day_secs -= self.pacing.seconds_per_day;
+            self.days_elapsed += 1;
+            self.day_started = true;
+
+            if self.day_of_month() == 0 {
+                self.month_started = true;
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    pub fn post_load(&mut self) {
+        self.pacing = CalendarPacing::current();
+    }
+
+    #[inline]
+    pub fn pacing(&self) -> &CalendarPacing {
+        &self.pacing
+    }
+
+    #[inline]
+    pub fn set_pacing(&mut self, pacing: CalendarPacing) {
+        self.pacing = pacing;
+    }
+
+    #[inline]
+    pub fn seconds_per_day(&self) -> Seconds {
+        self.pacing.seconds_per_day
+    }
+
+    #[inline]
+    pub fn seconds_per_month(&self) -> Seconds {
+        self.pacing.seconds_per_month()
+    }
+
+    // Converts a duration in simulation seconds to a fraction of an in-game day.
+    #[inline]
+    pub fn secs_to_days(&self, secs: Seconds) -> f32 {
+        if self.pacing.seconds_per_day > 0.0 { secs / self.pacing.seconds_per_day } else { 0.0 }
+    }
+
+    // Converts elapsed simulation seconds into seconds at the default game pacing.
+    // Periodic timers tuned in seconds (taxes, hazards, invasions, city statistics)
+    // tick with this, so they speed up or slow down along with a scenario's calendar.
+    #[inline]
+    pub fn paced_secs(&self, secs: Seconds) -> Seconds {
+        self.secs_to_days(secs) * CalendarPacing::from_configs().seconds_per_day
+    }
+
+    #[inline]
+    pub fn day_started(&self) -> bool {
+        self.day_started
+    }
+
+    #[inline]
+    pub fn month_started(&self) -> bool {
+        self.month_started
+    }
+
+    #[inline]
+    pub fn days_elapsed(&self) -> u32 {
+        self.days_elapsed
+    }
+
+    #[inline]
+    pub fn months_elapsed(&self) -> u32 {
+        self.days_elapsed / self.pacing.days_per_month.max(1)
+    }
+
+    // Zero-based day within the current month.
+    #[inline]
+    pub fn day_of_month(&self) -> u32 {
+        self.days_elapsed % self.pacing.days_per_month.max(1)
+    }
+
+    // Zero-based month within the current year.
+    #[inline]
+    pub fn month_of_year(&self) -> u32 {
+        self.months_elapsed() % Self::MONTHS_PER_YEAR
+    }
+
+    #[inline]
+    pub fn year(&self) -> u32 {
+        self.months_elapsed() / Self::MONTHS_PER_YEAR
+    }
+}
+
+impl std::fmt::Display for Calendar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Displayed one-based.
+        write!(f, "Day {}, Month {}, Year {}", self.day_of_month() + 1, self.month_of_year() + 1, self.year() + 1)
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pacing_override_scales_days_and_timers() {
+        // A scenario running days 10x faster than the default pacing.
+        let default_pacing = CalendarPacing::from_configs();
+        let mut calendar = Calendar::default();
+        calendar.set_pacing(CalendarPacing { seconds_per_day: default_pacing.seconds_per_day / 10.0, days_per_month: 3 });
+
+        let secs_per_day = calendar.seconds_per_day();
+        assert_eq!(calendar.secs_to_days(secs_per_day * 0.5), 0.5);
+        assert_eq!(calendar.paced_secs(secs_per_day), default_pacing.seconds_per_day);
+
+        calendar.tick(secs_per_day * 2.0);
+        assert!(calendar.day_started() && !calendar.month_started());
+
+        calendar.tick(secs_per_day);
+        assert!(calendar.month_started());
+        assert_eq!(calendar.months_elapsed(), 1);
+
+        // Frozen time.
+        calendar.set_pacing(CalendarPacing { seconds_per_day: 0.0, days_per_month: 3 });
+        assert_eq!(calendar.secs_to_days(secs_per_day), 0.0);
+        assert_eq!(calendar.paced_secs(secs_per_day), 0.0);
+    }
+}
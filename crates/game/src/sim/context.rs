@@ -16,18 +16,21 @@ use common::{
 };
 use engine::log;
 
-use super::{GlobalTreasury, RandomGenerator, SimCmds};
+use super::{Calendar, GlobalTreasury, RandomGenerator, RngStream, RngStreams, SimCmds, Weather};
 use crate::{
     world::{World, object::GameObject},
-    building::{Building, BuildingId, BuildingKind},
+    building::{Building, BuildingId, BuildingKind, unlocks::BuildingUnlocks},
     unit::{Unit, UnitId, task::UnitTaskManager},
     prop::{Prop, PropId},
     pathfind::{
         self,
+        AStarTerrainCostHeuristic,
         AStarUniformCostHeuristic,
         Bias,
         DefaultPathFilter,
         Graph,
+        HeuristicKind,
+        Movement,
         Node,
         NodeKind as PathNodeKind,
         Path,
@@ -48,6 +51,10 @@ use crate::{
     },
 };
 
+// Walkers and patrols prefer roads over open land and step orthogonally.
+const WALKER_HEURISTIC: HeuristicKind = HeuristicKind::TerrainCost;
+const WALKER_MOVEMENT: Movement = Movement::FourWay;
+
 // ----------------------------------------------
 // SimContext
 // ----------------------------------------------
@@ -60,8 +67,9 @@ pub struct SimContext {
     // so we don't want any of these lifetimes to be associated with the
     // SimContext's lifetime. This also allows us to pass immutable SimContext refs.
 
-    // Random generator:
+    // Random generators:
     rng: RawPtr<RandomGenerator>,
+    rng_streams: RawPtr<RngStreams>,
 
     // Path finding:
     search: RawPtr<Search>,
@@ -76,6 +84,15 @@ pub struct SimContext {
     // World resource stats:
     treasury: RawPtr<GlobalTreasury>,
 
+    // In-game date & time pacing:
+    calendar: RawPtr<Calendar>,
+
+    // Current weather over the city:
+    weather: RawPtr<Weather>,
+
+    // Building types available for placement:
+    building_unlocks: RawPtr<BuildingUnlocks>,
+
     // Deferred sim command queue:
     cmds: RawPtr<SimCmds>,
 
@@ -97,11 +114,15 @@ impl SimContext {
     #[inline]
     pub(super) fn new(
         rng: &mut RandomGenerator,
+        rng_streams: &mut RngStreams,
         search: &mut Search,
         task_manager: &mut UnitTaskManager,
         world: &mut World,
         tile_map: &mut TileMap,
         treasury: &mut GlobalTreasury,
+        calendar: &mut Calendar,
+        weather: &mut Weather,
+        building_unlocks: &mut BuildingUnlocks,
         cmds: &mut SimCmds,
         delta_time_secs: Seconds,
         is_world_teardown: bool,
@@ -109,11 +130,15 @@ impl SimContext {
     ) -> Self {
         Self {
             rng: RawPtr::from_ref(rng),
+            rng_streams: RawPtr::from_ref(rng_streams),
             search: RawPtr::from_ref(search),
             task_manager: RawPtr::from_ref(task_manager),
             world: RawPtr::from_ref(world),
             tile_map: RawPtr::from_ref(tile_map),
             treasury: RawPtr::from_ref(treasury),
+            calendar: RawPtr::from_ref(calendar),
+            weather: RawPtr::from_ref(weather),
+            building_unlocks: RawPtr::from_ref(building_unlocks),
             cmds: RawPtr::from_ref(cmds),
             delta_time_secs,
             is_world_teardown,
@@ -138,6 +163,14 @@ impl SimContext {
         self.rng.mut_ref_cast()
     }
 
+    // Prefer a named stream over the shared generator for anything that must replay
+    // identically from a seed, so unrelated systems don't consume each other's draws.
+    #[inline(always)]
+    pub fn rng_stream_mut(&self, stream: RngStream) -> &mut RandomGenerator {
+        // NOTE: Same as rng_mut(), allowed on read-only contexts.
+        self.rng_streams.mut_ref_cast().get_mut(stream)
+    }
+
     #[inline]
     pub fn random_range<T, R>(&self, range: R) -> T
     where
@@ -147,6 +180,15 @@ impl SimContext {
         self.rng_mut().random_range(range)
     }
 
+    #[inline]
+    pub fn random_range_in<T, R>(&self, stream: RngStream, range: R) -> T
+    where
+        T: SampleUniform,
+        R: SampleRange<T>,
+    {
+        self.rng_stream_mut(stream).random_range(range)
+    }
+
     // ----------------------
     // Read-Only (const) API:
     // ----------------------
@@ -171,6 +213,12 @@ impl SimContext {
         self.tile_map.graph()
     }
 
+    // True if the cell is enclosed by city walls (see `Graph::is_inside_walls()`).
+    #[inline]
+    pub fn is_inside_walls(&self, cell: Cell) -> bool {
+        self.graph().is_inside_walls(Node::new(cell))
+    }
+
     #[inline(always)]
     pub fn world(&self) -> &World {
         &self.world
@@ -186,6 +234,21 @@ impl SimContext {
         &self.treasury
     }
 
+    #[inline(always)]
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    #[inline(always)]
+    pub fn weather(&self) -> &Weather {
+        &self.weather
+    }
+
+    #[inline(always)]
+    pub fn building_unlocks(&self) -> &BuildingUnlocks {
+        &self.building_unlocks
+    }
+
     #[inline]
     pub fn find_tile_def(
         &self,
@@ -284,17 +347,34 @@ impl SimContext {
         pathfind::find_nearest_road_link(self.graph(), start_cells)
     }
 
+    // Walker path: prefers roads over open land (WALKER_HEURISTIC).
+    // Served from the search PathCache when possible; repeated walker trips are common.
     #[inline]
     pub fn find_path(&self, traversable_node_kinds: PathNodeKind, start: Cell, goal: Cell) -> SearchResult<'_> {
-        self.search_mut().find_path(
+        self.find_path_with_heuristic(WALKER_HEURISTIC, WALKER_MOVEMENT, traversable_node_kinds, start, goal)
+    }
+
+    // Cached search with a caller selected heuristic and movement.
+    #[inline]
+    pub fn find_path_with_heuristic(
+        &self,
+        heuristic: HeuristicKind,
+        movement: Movement,
+        traversable_node_kinds: PathNodeKind,
+        start: Cell,
+        goal: Cell,
+    ) -> SearchResult<'_> {
+        self.search_mut().find_path_cached(
             self.graph(),
-            &AStarUniformCostHeuristic::new(),
+            heuristic,
+            movement,
             traversable_node_kinds,
             Node::new(start),
             Node::new(goal),
         )
     }
 
+    // Patrol return paths, with the same road preference as find_path().
     #[inline]
     pub fn find_paths<Filter>(
         &self,
@@ -309,7 +389,7 @@ impl SimContext {
     {
         self.search_mut().find_paths(
             self.graph(),
-            &AStarUniformCostHeuristic::new(),
+            &AStarTerrainCostHeuristic::with_movement(WALKER_MOVEMENT),
             path_filter,
             max_paths,
             traversable_node_kinds,
@@ -318,6 +398,7 @@ impl SimContext {
         )
     }
 
+    // Patrol waypoints, with the same road preference as find_path().
     #[inline]
     pub fn find_waypoints<Filter>(
         &self,
@@ -332,7 +413,7 @@ impl SimContext {
     {
         self.search_mut().find_waypoints(
             self.graph(),
-            &AStarUniformCostHeuristic::new(),
+            &AStarTerrainCostHeuristic::with_movement(WALKER_MOVEMENT),
             bias,
             path_filter,
             traversable_node_kinds,
@@ -513,6 +594,7 @@ impl SimContext {
         building_kinds: BuildingKind,
         connected_to_road_only: bool,
         effect_radius: i32,
+        operational_only: bool,
     ) -> bool {
         debug_assert!(start.is_valid());
         debug_assert!(!building_kinds.is_empty());
@@ -534,8 +616,9 @@ impl SimContext {
             building_kinds,
             traversable_node_kinds,
             Some(effect_radius),
-            |_building, _path| {
-                false // Stop iterating, we'll take the first match.
+            |building, _path| {
+                // Skip buildings that are not running if required, otherwise stop iterating and take the first match.
+                operational_only && !building.is_operational()
             },
         ).is_some()
     }
@@ -725,11 +808,15 @@ macro_rules! make_context {
     ($self:ident, $delta_time_secs:expr, $tile_map:expr, $world:expr, $is_world_teardown:expr, $is_read_only:expr) => {
         $crate::sim::context::SimContext::new(
             &mut $self.rng,
+            &mut $self.rng_streams,
             &mut $self.search,
             &mut $self.task_manager,
             $world,
             $tile_map,
             &mut $self.treasury,
+            &mut $self.calendar,
+            &mut $self.weather,
+            &mut $self.building_unlocks,
             &mut $self.cmds,
             $delta_time_secs,
             $is_world_teardown,
@@ -0,0 +1,178 @@
+use std::{collections::VecDeque, fmt::Write as _};
+
+use arrayvec::ArrayString;
+use strum::{Display, EnumCount, EnumIter};
+
+use common::coords::Cell;
+use engine::platform;
+
+use crate::{building::BuildingKindAndId, unit::UnitId};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+pub const SIM_EVENT_MESSAGE_CAPACITY: usize = 96;
+
+// Hard cap on recorded events, so a burst of activity can't grow the log unbounded.
+const MAX_SIM_EVENTS: usize = 4096;
+
+// ----------------------------------------------
+// SimEventKind / SimEventSource
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq, Display, EnumCount, EnumIter)]
+pub enum SimEventKind {
+    Spawn,
+    Despawn,
+    Production,
+    StateChange,
+}
+
+// Entity or game system that emitted an event.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum SimEventSource {
+    Building(BuildingKindAndId),
+    Unit(UnitId),
+    System(&'static str),
+}
+
+impl std::fmt::Display for SimEventSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Building(kind_and_id) => write!(f, "{}{}", kind_and_id.kind, kind_and_id.id),
+            Self::Unit(id) => write!(f, "Unit{id}"),
+            Self::System(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+// ----------------------------------------------
+// SimEvent
+// ----------------------------------------------
+
+pub struct SimEvent {
+    pub tick: u64, // Sim update the event was recorded in.
+    pub kind: SimEventKind,
+    pub source: SimEventSource,
+    pub cell: Cell,
+    pub message: ArrayString<SIM_EVENT_MESSAGE_CAPACITY>,
+}
+
+// ----------------------------------------------
+// SimEventLog
+// ----------------------------------------------
+
+// Rolling in-memory log of significant simulation events for the last N sim updates.
+// Inspected with the dev editor Sim Event Log window and appended to crash reports,
+// to help answer questions like "why did this building just disappear?".
+pub struct SimEventLog {
+    events: VecDeque<SimEvent>,
+    tick: u64,
+    max_ticks: u64,
+}
+
+impl SimEventLog {
+    const fn new() -> Self {
+        Self { events: VecDeque::new(), tick: 0, max_ticks: 0 }
+    }
+
+    // Advances to the next sim update and drops events older than `max_ticks` updates.
+    pub fn begin_tick(&mut self, max_ticks: u32) {
+        self.tick += 1;
+        self.max_ticks = max_ticks as u64;
+
+        while self.events.front().is_some_and(|event| self.tick - event.tick >= self.max_ticks) {
+            self.events.pop_front();
+        }
+    }
+
+    pub fn push(
+        &mut self,
+        kind: SimEventKind,
+        source: SimEventSource,
+        cell: Cell,
+        message: ArrayString<SIM_EVENT_MESSAGE_CAPACITY>,
+    ) {
+        if self.max_ticks == 0 {
+            return; // Logging disabled.
+        }
+
+        if self.events.len() == MAX_SIM_EVENTS {
+            self.events.pop_front();
+        }
+
+        self.events.push_back(SimEvent { tick: self.tick, kind, source, cell, message });
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.tick = 0;
+    }
+
+    #[inline]
+    pub fn current_tick(&self) -> u64 {
+        self.tick
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    // Oldest first.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &SimEvent> {
+        self.events.iter()
+    }
+
+    pub fn dump(&self) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "Sim tick: {}, {} events (last {} ticks):", self.tick, self.events.len(), self.max_ticks).unwrap();
+
+        for event in &self.events {
+            writeln!(out, "  [{}] {:<11} {} @{} {}", event.tick, event.kind, event.source, event.cell, event.message).unwrap();
+        }
+
+        out
+    }
+
+    pub fn register_crash_report_section() {
+        platform::add_crash_report_section("SIM EVENT LOG", || {
+            // The log is not thread-safe; only dump it when panicking on the sim (main) thread.
+            if platform::is_main_thread() {
+                SimEventLog::get().dump()
+            } else {
+                "Sim event log only available for main thread panics.".into()
+            }
+        });
+    }
+}
+
+// ----------------------------------------------
+// SimEventLog Global Singleton
+// ----------------------------------------------
+
+common::singleton! { SIM_EVENT_LOG_SINGLETON, SimEventLog }
+
+// Records an event in the global SimEventLog. Message is formatted without allocating
+// and truncated to SIM_EVENT_MESSAGE_CAPACITY.
+// Usage: sim_event!(Spawn, SimEventSource::Unit(id), cell, "{} spawned", name);
+macro_rules! sim_event {
+    ($kind:ident, $source:expr, $cell:expr, $($arg:tt)*) => {
+        $crate::sim::events::SimEventLog::get_mut().push(
+            $crate::sim::events::SimEventKind::$kind,
+            $source,
+            $cell,
+            common::format_fixed_string_trunc!({ $crate::sim::events::SIM_EVENT_MESSAGE_CAPACITY }, $($arg)*),
+        )
+    };
+}
+
+pub(crate) use sim_event;
@@ -0,0 +1,79 @@
+use std::cmp::Reverse;
+
+use engine::log;
+
+use super::SimContext;
+use crate::building::{BuildingKind, BuildingKindAndId, hiring::HiringTarget};
+
+// ----------------------------------------------
+// LaborMarket
+// ----------------------------------------------
+
+// Distributes unemployed workers from households to employers every sim update.
+// Employers are served in order of their labor priority (highest first),
+// first up to their minimum required workers, then up to their maximum, so that
+// important buildings become operational before others are fully staffed.
+#[derive(Default)]
+pub struct LaborMarket {
+    // Scratch list of (priority, employer) reused across updates.
+    employers: Vec<(u32, BuildingKindAndId)>,
+}
+
+impl LaborMarket {
+    const EMPLOYER_KINDS: [BuildingKind; 3] = [
+        BuildingKind::producers(),
+        BuildingKind::storage(),
+        BuildingKind::services(),
+    ];
+
+    // NOTE: Mutates buildings directly, so must run with a mutable
+    // context after the deferred sim commands have been executed.
+    pub fn update(&mut self, context: &SimContext) {
+        debug_assert!(self.employers.is_empty());
+
+        if !Self::has_unemployed_workers(context) {
+            return;
+        }
+
+        for kinds in Self::EMPLOYER_KINDS {
+            context.world().for_each_building(kinds, |building| {
+                let needs_workers = building
+                    .workers()
+                    .and_then(|workers| workers.as_employer())
+                    .is_some_and(|employer| !employer.is_at_max_capacity());
+
+                if needs_workers && building.is_linked_to_road() {
+                    self.employers.push((building.labor_priority(), building.kind_and_id()));
+                }
+                true
+            });
+        }
+
+        // Stable sort, so equal priorities keep world order.
+        self.employers.sort_by_key(|(priority, _)| Reverse(*priority));
+
+        let mut workers_hired = 0;
+        for target in [HiringTarget::MinRequired, HiringTarget::MaxCapacity] {
+            for (_, employer) in &self.employers {
+                if let Some(building) = context.find_building_mut(employer.kind, employer.id) {
+                    workers_hired += building.hire_workers(context, target);
+                }
+            }
+        }
+
+        if workers_hired != 0 {
+            log::verbose!(log::channel!("labor"), "Hired {workers_hired} workers for {} employers.", self.employers.len());
+        }
+
+        self.employers.clear();
+    }
+
+    fn has_unemployed_workers(context: &SimContext) -> bool {
+        let mut found = false;
+        context.world().for_each_building(BuildingKind::House, |house| {
+            found = house.workers_count() != 0;
+            !found // Stop at the first house with unemployed workers.
+        });
+        found
+    }
+}
@@ -13,10 +13,12 @@ use engine::{Engine, save::*};
 use super::{
     constants::*,
     world::World,
+    building::unlocks::BuildingUnlocks,
     system::GameSystems,
-    config::GameConfigs,
+    config::{Difficulty, GameConfigs},
     ui_context::GameUiContext,
     unit::task::UnitTaskManager,
+    random_events::RandomEvents,
 };
 use crate::{
     save_context::*,
@@ -34,6 +36,27 @@ pub use context::SimContext;
 pub mod resources;
 pub use resources::GlobalTreasury;
 
+pub mod calendar;
+pub use calendar::Calendar;
+
+pub mod labor;
+pub use labor::LaborMarket;
+
+pub mod events;
+pub use events::SimEventLog;
+
+pub mod notifications;
+pub use notifications::Notifications;
+
+pub mod statistics;
+pub use statistics::CityStatistics;
+
+pub mod rng;
+pub use rng::{RngStream, RngStreams};
+
+pub mod weather;
+pub use weather::Weather;
+
 // ----------------------------------------------
 // RandomGenerator
 // ----------------------------------------------
@@ -48,14 +71,39 @@ pub type RandomGenerator = Pcg64;
 pub struct Simulation {
     rng: RcMut<RandomGenerator>,
 
+    #[serde(default)]
+    rng_streams: RngStreams,
+
     update_timer: UpdateTimer,
     paused_update_timer: UpdateTimer,
 
+    // Frame time not yet consumed by fixed sim ticks.
+    #[serde(skip)]
+    tick_accumulator_secs: Seconds,
+
     #[serde(skip)]
     cmds: RcMut<SimCmds>,
     task_manager: UnitTaskManager,
     treasury: GlobalTreasury,
 
+    #[serde(default)]
+    calendar: Calendar,
+
+    #[serde(default)]
+    weather: Weather,
+
+    #[serde(default)]
+    building_unlocks: BuildingUnlocks,
+
+    #[serde(default)]
+    statistics: CityStatistics,
+
+    #[serde(default)]
+    random_events: RandomEvents,
+
+    #[serde(skip)]
+    labor_market: LaborMarket,
+
     // Path finding:
     #[serde(skip)]
     search: Search,
@@ -63,23 +111,44 @@ pub struct Simulation {
     // Sim speed:
     speed: f32,
     is_paused: bool,
+
+    #[serde(default)]
+    difficulty: Difficulty,
 }
 
 impl Simulation {
     pub fn new(map_size_in_cells: Size, configs: &GameConfigs) -> Self {
+        Self::with_difficulty(map_size_in_cells, configs, configs.sim.difficulty)
+    }
+
+    pub fn with_difficulty(map_size_in_cells: Size, configs: &GameConfigs, difficulty: Difficulty) -> Self {
         Self {
             rng: RcMut::new(RandomGenerator::seed_from_u64(configs.sim.random_seed)),
+            rng_streams: RngStreams::new(configs.sim.random_seed),
             update_timer: UpdateTimer::new(configs.sim.update_frequency_secs),
             paused_update_timer: UpdateTimer::new(configs.sim.paused_update_frequency_secs),
+            tick_accumulator_secs: 0.0,
             cmds: RcMut::new(commands::DeferredSimCmds::new(SIM_CMDS_CAPACITY)),
             task_manager: UnitTaskManager::new(UNIT_TASK_POOL_CAPACITY),
-            treasury: GlobalTreasury::new(configs.sim.starting_gold_units),
+            treasury: GlobalTreasury::new(difficulty.starting_gold_units(configs.sim.starting_gold_units)),
+            calendar: Calendar::new(),
+            weather: Weather::default(),
+            building_unlocks: BuildingUnlocks::default(),
+            statistics: CityStatistics::new(configs.sim.statistics_sample_frequency_secs),
+            random_events: RandomEvents::default(),
+            labor_market: LaborMarket::default(),
             search: Search::with_grid_size(map_size_in_cells),
             speed: Self::MIN_SIM_SPEED,
             is_paused: false,
+            difficulty,
         }
     }
 
+    #[inline]
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
     #[inline]
     pub fn new_sim_context<'game>(
         &'game mut self,
@@ -105,6 +174,31 @@ impl Simulation {
         &mut self.treasury
     }
 
+    #[inline]
+    pub fn calendar(&self) -> &Calendar {
+        &self.calendar
+    }
+
+    #[inline]
+    pub fn weather(&self) -> &Weather {
+        &self.weather
+    }
+
+    #[inline]
+    pub fn building_unlocks(&self) -> &BuildingUnlocks {
+        &self.building_unlocks
+    }
+
+    #[inline]
+    pub fn statistics(&self) -> &CityStatistics {
+        &self.statistics
+    }
+
+    #[inline]
+    pub fn random_events(&self) -> &RandomEvents {
+        &self.random_events
+    }
+
     #[inline]
     pub fn task_manager(&self) -> &UnitTaskManager {
         &self.task_manager
@@ -115,6 +209,11 @@ impl Simulation {
         &mut self.task_manager
     }
 
+    #[inline]
+    pub fn search(&self) -> &Search {
+        &self.search
+    }
+
     #[inline]
     pub fn rng(&self) -> &RcMut<RandomGenerator> {
         &self.rng
@@ -125,6 +224,11 @@ impl Simulation {
         &mut self.rng
     }
 
+    #[inline]
+    pub fn rng_stream_mut(&mut self, stream: RngStream) -> &mut RandomGenerator {
+        self.rng_streams.get_mut(stream)
+    }
+
     pub fn update(
         &mut self,
         engine: &mut Engine,
@@ -138,27 +242,70 @@ impl Simulation {
             if self.paused_update_timer.tick(delta_time_secs).should_update() {
                 let context = context::make_update_context_mut!(self, delta_time_secs, tile_map, world);
                 systems.paused_update(engine, &context);
+                context.minimap_mut().refresh_overlay(&context);
             }
             return; // Early out.
         }
 
-        // Units movement needs to be smooth, so it updates every frame.
+        // Step the simulation in fixed ticks, independent of the render frame rate. Sim speed
+        // runs more ticks per frame rather than longer ones, so results don't depend on it either.
+        let configs = &GameConfigs::get().sim;
+        let tick_secs = configs.fixed_tick_secs;
+        debug_assert!(tick_secs > 0.0);
+
+        self.tick_accumulator_secs += delta_time_secs * self.speed;
+
+        let mut tick_count = 0;
+        while self.tick_accumulator_secs >= tick_secs {
+            if tick_count == configs.max_ticks_per_frame {
+                // Can't keep up (e.g. after a long frame hitch). Drop the backlog rather than fall further behind.
+                self.tick_accumulator_secs = 0.0;
+                break;
+            }
+
+            self.tick(engine, world, systems, tile_map, tick_secs);
+
+            self.tick_accumulator_secs -= tick_secs;
+            tick_count += 1;
+        }
+
+        // Draw units in between their last two ticked positions.
         {
-            let scaled_delta_time_secs = delta_time_secs * self.speed;
-            let context = context::make_update_context_mut!(self, scaled_delta_time_secs, tile_map, world);
+            let alpha = self.tick_accumulator_secs / tick_secs;
+            let context = context::make_update_context_mut!(self, delta_time_secs, tile_map, world);
+            world.interpolate_unit_render_positions(&context, alpha);
+        }
+    }
+
+    fn tick(
+        &mut self,
+        engine: &mut Engine,
+        world: &mut World,
+        systems: &mut GameSystems,
+        tile_map: &mut TileMap,
+        tick_secs: Seconds,
+    ) {
+        // Units movement needs to be smooth, so it updates every tick.
+        {
+            let context = context::make_update_context_mut!(self, tick_secs, tile_map, world);
             world.update_unit_navigation(&context);
         }
 
-        // Fixed step world & game systems update.
+        // World & game systems update, at the slower `update_frequency_secs`.
         {
             const LOCK_WORLD_AND_MAP_DURING_UPDATE: bool = cfg!(debug_assertions);
 
-            let scaled_delta_time_secs = delta_time_secs * self.speed;
-            let world_update_delta_time_secs = self.update_timer.time_since_last_secs() * self.speed;
+            let world_update_delta_time_secs = self.update_timer.time_since_last_secs();
 
-            if self.update_timer.tick(scaled_delta_time_secs).should_update() {
+            if self.update_timer.tick(tick_secs).should_update() {
                 debug_assert!(self.cmds.is_empty());
 
+                // Advance in-game time before the world so day/month changes are visible this update.
+                self.calendar.tick(world_update_delta_time_secs);
+                let weather_rng = self.rng_streams.get_mut(RngStream::Events);
+                self.weather.update(&self.calendar, weather_rng, world_update_delta_time_secs);
+                SimEventLog::get_mut().begin_tick(GameConfigs::get().debug.sim_event_log_ticks);
+
                 if LOCK_WORLD_AND_MAP_DURING_UPDATE {
                     tile_map.lock();
                     world.lock();
@@ -179,9 +326,22 @@ impl Simulation {
                 // Any world or tile map mutation would have been deferred until now.
                 {
                     let context = context::make_update_context_mut!(self, world_update_delta_time_secs, tile_map, world);
+
+                    // Random event effects are queued with the other commands of this update.
+                    self.random_events.update(&mut self.cmds, &context);
                     self.cmds.execute(&context);
+
+                    // Distribute workers once all buildings are settled for this update.
+                    self.labor_market.update(&context);
+
+                    // Repaint the active minimap overlay with the settled simulation state.
+                    context.minimap_mut().refresh_overlay(&context);
                 }
 
+                // Milestones are checked and city history recorded against the settled world stats.
+                self.building_unlocks.update(world.stats());
+                self.statistics.update(self.calendar.paced_secs(world_update_delta_time_secs), world.stats());
+
                 debug_assert!(self.cmds.is_empty());
             }
         }
@@ -200,6 +360,14 @@ impl Simulation {
         }
         systems.reset(engine);
         self.cmds.reset();
+        self.tick_accumulator_secs = 0.0;
+        self.calendar.reset();
+        self.weather.reset();
+        self.building_unlocks.reset();
+        self.statistics.reset();
+        self.random_events.reset();
+        SimEventLog::get_mut().clear();
+        Notifications::get_mut().clear();
     }
 
     pub fn reset_search_graph(&mut self, tile_map: &mut TileMap) {
@@ -244,6 +412,11 @@ impl Simulation {
         self.speed = (self.speed - 1.0).max(Self::MIN_SIM_SPEED);
     }
 
+    #[inline]
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(Self::MIN_SIM_SPEED, Self::MAX_SIM_SPEED);
+    }
+
     // ----------------------
     // Callbacks:
     // ----------------------
@@ -275,6 +448,14 @@ impl Simulation {
         systems.draw_debug_ui(engine, &mut cmds, &sim_context, context.ui_sys);
     }
 
+    // Random Events:
+    pub fn fire_random_event(&mut self, context: &mut GameUiContext, event_index: usize) {
+        let sim_context = context::make_update_context_mut!(self, context.delta_time_secs, context.tile_map, context.world);
+        let mut cmds = commands::ImmediateModeSimCmds::new(&sim_context);
+
+        self.random_events.fire(&mut cmds, &sim_context, event_index);
+    }
+
     // Generic GameObjects:
     pub fn draw_game_object_debug_ui(&mut self, context: &mut GameUiContext, tile: &Tile, mode: DebugUiMode) {
         if tile.is(TileKind::Building) {
@@ -363,5 +544,11 @@ impl Load for Simulation {
         self.paused_update_timer.post_load(context.configs().sim.paused_update_frequency_secs);
         self.task_manager.post_load();
         self.cmds.post_load();
+        self.calendar.post_load();
+        self.statistics.post_load(context.configs().sim.statistics_sample_frequency_secs);
+
+        // Events from before the load no longer refer to valid entities.
+        SimEventLog::get_mut().clear();
+        Notifications::get_mut().clear();
     }
 }
@@ -0,0 +1,207 @@
+use std::collections::VecDeque;
+
+use strum::{Display, EnumProperty};
+
+use common::{coords::Cell, time::Seconds};
+use engine::file_sys::paths::PathRef;
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Oldest notifications are dropped once the queue is full.
+pub const MAX_NOTIFICATIONS: usize = 5;
+
+// ----------------------------------------------
+// NotificationKind
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq, Display, EnumProperty)]
+pub enum NotificationKind {
+    #[strum(props(Icon = "icons/alert_icon.png"))]
+    Fire,
+
+    #[strum(props(Icon = "icons/alert_icon.png"))]
+    Collapse,
+
+    #[strum(props(Icon = "icons/alert_icon.png"))]
+    Riot,
+
+    #[strum(props(Icon = "icons/alert_icon.png"))]
+    Invasion,
+
+    #[strum(props(Icon = "icons/alert_icon.png"))]
+    Disaster,
+
+    #[strum(props(Icon = "icons/player_icon.png"))]
+    CityEvent,
+
+    #[strum(props(Icon = "icons/red_x_icon.png"))]
+    NoFoodOrWater,
+
+    #[strum(props(Icon = "icons/population_icon.png"))]
+    SettlersArrived,
+
+    #[strum(props(Icon = "icons/player_icon.png"))]
+    Objective,
+
+    #[strum(props(Icon = "icons/player_icon.png"))]
+    BuildingUnlocked,
+}
+
+impl NotificationKind {
+    pub fn icon_path(self) -> PathRef<'static> {
+        // ui/icons/{sprite}.png
+        PathRef::from_str(self.get_str("Icon").unwrap())
+    }
+}
+
+// ----------------------------------------------
+// Notification
+// ----------------------------------------------
+
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub cell: Cell, // Map cell the player can jump to. Invalid if none.
+    pub message: String,
+    pub age_secs: Seconds,
+}
+
+// ----------------------------------------------
+// Notifications
+// ----------------------------------------------
+
+// Queue of player facing alerts. Game systems push with `notify!()` during the sim
+// update; the in-game HUD feed displays them and lets the player jump to their cell.
+pub struct Notifications {
+    queue: VecDeque<Notification>,
+    revision: u32, // Changes whenever the queue does, so the HUD knows when to rebuild.
+}
+
+impl Notifications {
+    const fn new() -> Self {
+        Self { queue: VecDeque::new(), revision: 0 }
+    }
+
+    pub fn push(&mut self, kind: NotificationKind, cell: Cell, message: String) {
+        // Same alert for the same place is already showing; move it to the front instead.
+        let is_same = |notification: &Notification| notification.kind == kind && notification.cell == cell;
+        if let Some(index) = self.queue.iter().position(is_same) {
+            self.queue.remove(index);
+        }
+
+        if self.queue.len() == MAX_NOTIFICATIONS {
+            self.queue.pop_front();
+        }
+
+        self.queue.push_back(Notification { kind, cell, message, age_secs: 0.0 });
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    // Ages all notifications and drops the ones older than `lifetime_secs`.
+    pub fn update(&mut self, delta_time_secs: Seconds, lifetime_secs: Seconds) {
+        let count_before = self.queue.len();
+
+        for notification in &mut self.queue {
+            notification.age_secs += delta_time_secs;
+        }
+
+        self.queue.retain(|notification| notification.age_secs < lifetime_secs);
+
+        if self.queue.len() != count_before {
+            self.revision = self.revision.wrapping_add(1);
+        }
+    }
+
+    pub fn dismiss(&mut self, index: usize) -> Option<Notification> {
+        let notification = self.queue.remove(index);
+        if notification.is_some() {
+            self.revision = self.revision.wrapping_add(1);
+        }
+        notification
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    #[inline]
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    // Oldest first.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Notification> {
+        self.queue.iter()
+    }
+}
+
+// ----------------------------------------------
+// Notifications Global Singleton
+// ----------------------------------------------
+
+common::singleton! { NOTIFICATIONS_SINGLETON, Notifications }
+
+// Raises a player notification for the in-game HUD feed.
+// Usage: notify!(Fire, building.base_cell(), "{} caught fire", building.name());
+macro_rules! notify {
+    ($kind:ident, $cell:expr, $($arg:tt)*) => {
+        $crate::sim::notifications::Notifications::get_mut().push(
+            $crate::sim::notifications::NotificationKind::$kind,
+            $cell,
+            format!($($arg)*),
+        )
+    };
+}
+
+pub(crate) use notify;
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_alerts_are_merged_and_oldest_dropped() {
+        let mut notifications = Notifications::new();
+
+        for x in 0..MAX_NOTIFICATIONS as i32 {
+            notifications.push(NotificationKind::Fire, Cell::new(x, 0), String::new());
+        }
+
+        // Same kind and cell as the oldest: moved to the back, nothing dropped.
+        notifications.push(NotificationKind::Fire, Cell::new(0, 0), String::new());
+        assert_eq!(notifications.iter().count(), MAX_NOTIFICATIONS);
+        assert!(notifications.iter().next_back().unwrap().cell == Cell::new(0, 0));
+
+        // New alert drops the oldest, now at [1,0].
+        notifications.push(NotificationKind::Riot, Cell::new(1, 0), String::new());
+        assert_eq!(notifications.iter().count(), MAX_NOTIFICATIONS);
+        assert!(notifications.iter().next().unwrap().cell == Cell::new(2, 0));
+    }
+
+    #[test]
+    fn expired_alerts_are_removed() {
+        let mut notifications = Notifications::new();
+        notifications.push(NotificationKind::Collapse, Cell::new(1, 1), String::new());
+
+        let revision = notifications.revision();
+        notifications.update(5.0, 10.0);
+        assert!(!notifications.is_empty());
+        assert_eq!(notifications.revision(), revision);
+
+        notifications.update(5.0, 10.0);
+        assert!(notifications.is_empty());
+        assert_ne!(notifications.revision(), revision);
+    }
+}
@@ -19,6 +19,7 @@ use smallvec::SmallVec;
 use crate::{
     building::{Building, BuildingId, BuildingKind, BuildingKindAndId},
     cheats,
+    config::GameConfigs,
     world::{World, stats::WorldStats},
 };
 
@@ -731,15 +732,32 @@ impl Population {
 //   treasury of its earnings.
 // - GlobalTreasury: Global stash of gold units for initial gold count, gold
 //   cheats, etc.
+//
+// The global treasury also holds the city tax rate set by the player,
+// which scales household tax income and weighs on household happiness.
 #[derive(Serialize, Deserialize)]
 pub struct GlobalTreasury {
     gold_units: u32,
+
+    #[serde(default = "default_tax_rate_percent")]
+    tax_rate_percent: u32,
 }
 
 impl GlobalTreasury {
     #[inline]
     pub fn new(starting_gold_units: u32) -> Self {
-        Self { gold_units: starting_gold_units }
+        Self { gold_units: starting_gold_units, tax_rate_percent: default_tax_rate_percent() }
+    }
+
+    #[inline]
+    pub fn tax_rate_percent(&self) -> u32 {
+        self.tax_rate_percent
+    }
+
+    // Clamped to [0, max_tax_rate_percent]. Houses pick up the new rate on their next upgrade update.
+    #[inline]
+    pub fn set_tax_rate_percent(&mut self, tax_rate_percent: u32) {
+        self.tax_rate_percent = tax_rate_percent.min(GameConfigs::get().sim.max_tax_rate_percent);
     }
 
     #[inline]
@@ -802,6 +820,12 @@ impl GlobalTreasury {
     }
 }
 
+// Also used by houses for their cached rate (see HouseBuilding::tax_rate_percent).
+#[inline]
+pub(crate) fn default_tax_rate_percent() -> u32 {
+    GameConfigs::get().sim.default_tax_rate_percent
+}
+
 // ----------------------------------------------
 // ResourceStock
 // ----------------------------------------------
@@ -0,0 +1,83 @@
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, EnumIter, IntoEnumIterator, IntoStaticStr};
+
+use common::hash;
+use super::RandomGenerator;
+use crate::config::SimConfigs;
+
+// ----------------------------------------------
+// RngStream
+// ----------------------------------------------
+
+// Independent random sequences used by the simulation. Each stream is seeded from the
+// same sim seed, so adding or removing draws from one stream never shifts the others.
+// E.g. placing an extra building will not change what the units or random events do next.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumCount, EnumIter, IntoStaticStr)]
+pub enum RngStream {
+    Placement, // Tile variations and building archetypes.
+    UnitAi,    // Unit task decisions and path biasing.
+    Events,    // Random events, hazards, invasions and riots.
+}
+
+// ----------------------------------------------
+// RngStreams
+// ----------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RngStreams {
+    streams: [RandomGenerator; RngStream::COUNT],
+}
+
+impl RngStreams {
+    pub fn new(seed: u64) -> Self {
+        Self { streams: std::array::from_fn(|index| Self::new_stream(seed, RngStream::iter().nth(index).unwrap())) }
+    }
+
+    // Standalone generator for a single stream, for when there's no Simulation
+    // around yet (e.g. while creating a new tile map).
+    pub fn new_stream(seed: u64, stream: RngStream) -> RandomGenerator {
+        let stream_name: &'static str = stream.into();
+        RandomGenerator::seed_from_u64(seed ^ hash::fnv1a_from_str(stream_name))
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, stream: RngStream) -> &mut RandomGenerator {
+        &mut self.streams[stream as usize]
+    }
+}
+
+impl Default for RngStreams {
+    // Used for saves that predate the streams.
+    fn default() -> Self {
+        Self::new(SimConfigs::default().random_seed)
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use super::*;
+
+    #[test]
+    fn streams_are_reproducible_and_independent() {
+        let mut a = RngStreams::new(1234);
+        let mut b = RngStreams::new(1234);
+
+        // Extra draws from one stream must not shift the others.
+        for _ in 0..10 {
+            a.get_mut(RngStream::Placement).random::<u64>();
+        }
+
+        let a_events: Vec<u64> = (0..8).map(|_| a.get_mut(RngStream::Events).random()).collect();
+        let b_events: Vec<u64> = (0..8).map(|_| b.get_mut(RngStream::Events).random()).collect();
+        assert_eq!(a_events, b_events);
+
+        let b_unit_ai: Vec<u64> = (0..8).map(|_| b.get_mut(RngStream::UnitAi).random()).collect();
+        assert_ne!(b_events, b_unit_ai);
+    }
+}
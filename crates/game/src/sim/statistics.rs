@@ -0,0 +1,187 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use common::time::{Seconds, UpdateTimer};
+
+use super::resources::{RESOURCE_KIND_COUNT, ResourceKind};
+use crate::world::stats::WorldStats;
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Number of samples kept in the city history (for graphs). Oldest samples are dropped once full.
+pub const CITY_STATISTICS_MAX_SAMPLES: usize = 240;
+
+// ----------------------------------------------
+// CityStatisticsSample
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+pub struct CityStatisticsSample {
+    pub population: u32,
+    pub employed: u32,
+    pub unemployed: u32,
+    pub treasury: u32, // Gold units in the treasury and buildings.
+
+    // Resources held in storage buildings, indexed by `ResourceKind::index()`.
+    pub stored_resources: [u32; RESOURCE_KIND_COUNT],
+}
+
+impl CityStatisticsSample {
+    pub fn from_world_stats(stats: &WorldStats) -> Self {
+        let mut stored_resources = [0; RESOURCE_KIND_COUNT];
+        for kind in ResourceKind::all().iter() {
+            stored_resources[kind.index()] = stats.resources.stored(kind);
+        }
+
+        Self {
+            population: stats.population.total,
+            employed: stats.population.employed,
+            unemployed: stats.population.unemployed,
+            treasury: stats.treasury.gold_units_total,
+            stored_resources,
+        }
+    }
+
+    // Returns normalized [0,1] ratio.
+    pub fn employment_ratio(&self) -> f32 {
+        let workforce = self.employed + self.unemployed;
+        if workforce == 0 { 0.0 } else { self.employed as f32 / workforce as f32 }
+    }
+
+    #[inline]
+    pub fn stored(&self, kind: ResourceKind) -> u32 {
+        self.stored_resources[kind.index()]
+    }
+
+    // Combined stock of all foods in storage.
+    pub fn food_stock(&self) -> u32 {
+        ResourceKind::foods().iter().map(|kind| self.stored(kind)).sum()
+    }
+}
+
+// ----------------------------------------------
+// CityStatistics
+// ----------------------------------------------
+
+// City history recorded from the settled world stats every `statistics_sample_frequency_secs`
+// of simulation time at the default calendar pacing (see `Calendar::paced_secs`).
+// Saved with the game so the graphs survive a reload.
+#[derive(Default, Serialize, Deserialize)]
+pub struct CityStatistics {
+    sample_timer: UpdateTimer,
+    samples: VecDeque<CityStatisticsSample>, // Oldest first.
+}
+
+impl CityStatistics {
+    pub fn new(sample_frequency_secs: Seconds) -> Self {
+        Self { sample_timer: UpdateTimer::new(sample_frequency_secs), samples: VecDeque::new() }
+    }
+
+    pub fn update(&mut self, delta_time_secs: Seconds, stats: &WorldStats) {
+        if self.sample_timer.tick(delta_time_secs).should_update() {
+            self.record(CityStatisticsSample::from_world_stats(stats));
+        }
+    }
+
+    pub fn record(&mut self, sample: CityStatisticsSample) {
+        if self.samples.len() == CITY_STATISTICS_MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    pub fn reset(&mut self) {
+        self.sample_timer.reset();
+        self.samples.clear();
+    }
+
+    pub fn post_load(&mut self, sample_frequency_secs: Seconds) {
+        self.sample_timer.post_load(sample_frequency_secs);
+    }
+
+    #[inline]
+    pub fn samples(&self) -> &VecDeque<CityStatisticsSample> {
+        &self.samples
+    }
+
+    #[inline]
+    pub fn latest(&self) -> Option<&CityStatisticsSample> {
+        self.samples.back()
+    }
+
+    // Time series of a single value, oldest first. Ready for plotting.
+    pub fn series<F>(&self, value_fn: F) -> Vec<f32>
+    where
+        F: Fn(&CityStatisticsSample) -> f32,
+    {
+        self.samples.iter().map(value_fn).collect()
+    }
+
+    // Change in a resource stock between the oldest and newest samples.
+    pub fn stored_change(&self, kind: ResourceKind) -> i64 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(oldest), Some(newest)) => newest.stored(kind) as i64 - oldest.stored(kind) as i64,
+            _ => 0,
+        }
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_with_population(population: u32) -> CityStatisticsSample {
+        CityStatisticsSample { population, ..Default::default() }
+    }
+
+    #[test]
+    fn oldest_samples_are_dropped_when_full() {
+        let mut statistics = CityStatistics::new(1.0);
+
+        for population in 0..(CITY_STATISTICS_MAX_SAMPLES as u32 + 10) {
+            statistics.record(sample_with_population(population));
+        }
+
+        assert_eq!(statistics.samples().len(), CITY_STATISTICS_MAX_SAMPLES);
+        assert_eq!(statistics.samples().front().unwrap().population, 10);
+        assert_eq!(statistics.latest().unwrap().population, CITY_STATISTICS_MAX_SAMPLES as u32 + 9);
+    }
+
+    #[test]
+    fn food_stock_and_stored_change() {
+        let mut statistics = CityStatistics::new(1.0);
+        assert_eq!(statistics.stored_change(ResourceKind::Rice), 0);
+
+        let mut first = CityStatisticsSample::default();
+        first.stored_resources[ResourceKind::Rice.index()] = 10;
+        first.stored_resources[ResourceKind::Wood.index()] = 5;
+
+        let mut second = first;
+        second.stored_resources[ResourceKind::Rice.index()] = 4;
+        second.stored_resources[ResourceKind::Fish.index()] = 3;
+
+        statistics.record(first);
+        statistics.record(second);
+
+        assert_eq!(first.food_stock(), 10);
+        assert_eq!(second.food_stock(), 7);
+        assert_eq!(statistics.stored_change(ResourceKind::Rice), -6);
+        assert_eq!(statistics.stored_change(ResourceKind::Wood), 0);
+        assert_eq!(statistics.series(|sample| sample.food_stock() as f32), vec![10.0, 7.0]);
+    }
+
+    #[test]
+    fn employment_ratio_with_no_workforce() {
+        assert_eq!(CityStatisticsSample::default().employment_ratio(), 0.0);
+
+        let sample = CityStatisticsSample { employed: 3, unemployed: 1, ..Default::default() };
+        assert!((sample.employment_ratio() - 0.75).abs() < f32::EPSILON);
+    }
+}
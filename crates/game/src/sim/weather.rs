@@ -0,0 +1,139 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use common::time::Seconds;
+use super::{Calendar, RandomGenerator};
+
+// ----------------------------------------------
+// WeatherKind
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Display, Serialize, Deserialize)]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Windy,
+    Rain,
+    Storm,
+}
+
+impl WeatherKind {
+    // Relative odds of each kind being rolled and its [min, max] intensity.
+    const ODDS: [(Self, u32, f32, f32); 4] = [
+        (Self::Clear, 50, 0.0, 0.0),
+        (Self::Windy, 20, 0.3, 1.0),
+        (Self::Rain,  20, 0.3, 1.0),
+        (Self::Storm, 10, 0.7, 1.0),
+    ];
+}
+
+// ----------------------------------------------
+// Weather
+// ----------------------------------------------
+
+// Weather over the city. May change at the start of each in-game day. The current
+// weather dies down before the next one builds up, so ambience fades between them
+// instead of switching abruptly.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Weather {
+    kind: WeatherKind,
+    next_kind: WeatherKind,
+
+    // [0,1] strength of the current weather, eased towards `target_intensity`.
+    intensity: f32,
+    target_intensity: f32,
+}
+
+impl Weather {
+    const CHANGE_CHANCE_PER_DAY: f32 = 0.3;
+    const INTENSITY_CHANGE_PER_SEC: f32 = 0.05;
+
+    pub fn update(&mut self, calendar: &Calendar, rng: &mut RandomGenerator, delta_time_secs: Seconds) {
+        if calendar.day_started() && rng.random::<f32>() < Self::CHANGE_CHANCE_PER_DAY {
+            self.roll_next(rng);
+        }
+
+        let step = Self::INTENSITY_CHANGE_PER_SEC * delta_time_secs;
+
+        if self.kind != self.next_kind {
+            self.intensity = (self.intensity - step).max(0.0);
+            if self.intensity <= 0.0 {
+                self.kind = self.next_kind;
+            }
+        } else if self.intensity < self.target_intensity {
+            self.intensity = (self.intensity + step).min(self.target_intensity);
+        } else {
+            self.intensity = (self.intensity - step).max(self.target_intensity);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    #[inline]
+    pub fn kind(&self) -> WeatherKind {
+        self.kind
+    }
+
+    #[inline]
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    pub fn set(&mut self, kind: WeatherKind, intensity: f32) {
+        self.next_kind = kind;
+        self.target_intensity = if kind == WeatherKind::Clear { 0.0 } else { intensity.clamp(0.0, 1.0) };
+    }
+
+    fn roll_next(&mut self, rng: &mut RandomGenerator) {
+        let total_odds: u32 = WeatherKind::ODDS.iter().map(|(_, odds, _, _)| odds).sum();
+        let mut roll = rng.random_range(0..total_odds);
+
+        for (kind, odds, min_intensity, max_intensity) in WeatherKind::ODDS {
+            if roll < odds {
+                self.set(kind, rng.random_range(min_intensity..=max_intensity));
+                return;
+            }
+            roll -= odds;
+        }
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use super::*;
+
+    #[test]
+    fn weather_dies_down_before_changing() {
+        let calendar = Calendar::default(); // No day starts, so no random changes.
+        let mut rng = RandomGenerator::seed_from_u64(1234);
+        let mut weather = Weather::default();
+
+        weather.set(WeatherKind::Rain, 0.5);
+        weather.update(&calendar, &mut rng, 1.0);
+        assert_eq!(weather.kind(), WeatherKind::Rain); // Clear has no intensity to fade out.
+
+        for _ in 0..20 {
+            weather.update(&calendar, &mut rng, 1.0);
+        }
+        assert_eq!(weather.intensity(), 0.5);
+
+        // Rain fades out first, then the storm builds up.
+        weather.set(WeatherKind::Storm, 1.0);
+        weather.update(&calendar, &mut rng, 5.0);
+        assert_eq!(weather.kind(), WeatherKind::Rain);
+        assert!(weather.intensity() < 0.5);
+
+        for _ in 0..10 {
+            weather.update(&calendar, &mut rng, 1.0);
+        }
+        assert_eq!(weather.kind(), WeatherKind::Storm);
+    }
+}
@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
+
+use engine::sound::{SoundBank, SoundBankDef, SoundSystem};
+
+// ----------------------------------------------
+// SoundBankState
+// ----------------------------------------------
+
+// Game states that have their own sound bank.
+// Multiple states can be active at once (e.g. InGame + Disaster).
+#[derive(Copy, Clone, PartialEq, Eq, Display, EnumCount, EnumIter)]
+pub enum SoundBankState {
+    Menu,
+    InGame,
+    Disaster,
+}
+
+// ----------------------------------------------
+// SoundBankConfigs
+// ----------------------------------------------
+
+// Sound bank definitions per game state, loaded from `assets/configs/game/sound_banks.json`.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundBankConfigs {
+    pub menu: SoundBankDef,
+    pub in_game: SoundBankDef,
+    pub disaster: SoundBankDef, // Fires and raids.
+}
+
+impl SoundBankConfigs {
+    #[inline]
+    pub fn bank_def(&self, state: SoundBankState) -> &SoundBankDef {
+        match state {
+            SoundBankState::Menu => &self.menu,
+            SoundBankState::InGame => &self.in_game,
+            SoundBankState::Disaster => &self.disaster,
+        }
+    }
+}
+
+engine::configurations! { SOUND_BANK_CONFIGS_SINGLETON, SoundBankConfigs, "game/sound_banks" }
+
+// ----------------------------------------------
+// GameSoundBanks
+// ----------------------------------------------
+
+// Preloads each state's sound bank when entering that state and unloads it on exit.
+#[derive(Default)]
+pub struct GameSoundBanks {
+    loaded: [Option<SoundBank>; SoundBankState::COUNT],
+}
+
+impl GameSoundBanks {
+    #[inline]
+    pub fn is_loaded(&self, state: SoundBankState) -> bool {
+        self.loaded[state as usize].is_some()
+    }
+
+    // Enters every newly active bank before exiting the inactive ones, so sounds shared
+    // between them (e.g. a music track in both Menu and InGame) are never unloaded and
+    // reloaded in the middle of a switch.
+    pub fn set_active_states<F>(&mut self, sound_sys: &mut SoundSystem, is_active: F)
+    where
+        F: Fn(SoundBankState) -> bool,
+    {
+        for state in SoundBankState::iter().filter(|state| is_active(*state)) {
+            self.enter(sound_sys, state);
+        }
+
+        for state in SoundBankState::iter().filter(|state| !is_active(*state)) {
+            self.exit(sound_sys, state);
+        }
+    }
+
+    pub fn enter(&mut self, sound_sys: &mut SoundSystem, state: SoundBankState) {
+        let slot = &mut self.loaded[state as usize];
+        if slot.is_none() {
+            let bank_def = SoundBankConfigs::get().bank_def(state);
+            *slot = Some(sound_sys.load_bank(&state.to_string(), bank_def));
+        }
+    }
+
+    pub fn exit(&mut self, sound_sys: &mut SoundSystem, state: SoundBankState) {
+        if let Some(bank) = self.loaded[state as usize].take() {
+            sound_sys.unload_bank(bank);
+        }
+    }
+
+    pub fn exit_all(&mut self, sound_sys: &mut SoundSystem) {
+        for state in SoundBankState::iter() {
+            self.exit(sound_sys, state);
+        }
+    }
+}
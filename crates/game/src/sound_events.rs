@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+use engine::sound::{SoundEventDef, SoundSystem};
+
+// ----------------------------------------------
+// SoundEventConfigs
+// ----------------------------------------------
+
+// Sound event manifest, loaded from `assets/configs/game/sound_events.json`.
+// Game code plays these by `SoundEventKey`, hashed from the event name.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundEventConfigs {
+    pub events: Vec<SoundEventDef>,
+}
+
+impl SoundEventConfigs {
+    // Registers every event in the manifest with the SoundSystem.
+    pub fn register(&self, sound_sys: &mut SoundSystem) {
+        sound_sys.load_sound_events(&self.events);
+    }
+}
+
+engine::configurations! { SOUND_EVENT_CONFIGS_SINGLETON, SoundEventConfigs, "game/sound_events" }
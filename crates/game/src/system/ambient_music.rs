@@ -4,92 +4,185 @@ use engine::{
     Engine,
     file_sys::paths::PathRef,
     log,
-    sound::{MusicSoundKey, SoundHandle, SoundKey, SoundKind, SoundSystem},
+    sound::{MusicSoundKey, SoundHandle, SoundKey, SoundSystem},
 };
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
-use strum::{Display, EnumCount, EnumIter, EnumProperty, IntoEnumIterator};
+use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
 
-use super::GameSystem;
+use super::{GameSystem, invasion::InvasionSystem};
 use crate::{GameLoop, config::GameConfigs, save_context::PostLoadContext, sim::{SimCmds, SimContext}};
 
 // ----------------------------------------------
-// MusicTrackKey
+// MusicPlaylistConfigs
 // ----------------------------------------------
 
-#[derive(Copy, Clone, PartialEq, Eq, Display, EnumCount, EnumProperty, EnumIter)]
-pub(crate) enum MusicTrackKey {
-    // Home menu track.
-    #[strum(props(TrackPath = "dynastys_legacy_1.mp3"))]
-    HomeMenu,
+// Music tracks per game state, loaded from `assets/configs/game/music_playlists.json`.
+// Track paths are relative to the "music/" sound assets directory.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct MusicPlaylistConfigs {
+    pub home_menu: Vec<String>,
+    pub in_game: Vec<String>,
+    // Tension tracks played during invasions. Empty keeps the in-game playlist going.
+    pub crisis: Vec<String>,
+    // Play each playlist in random order, reshuffled every time it runs out.
+    pub shuffle: bool,
+}
 
-    // In-game default track.
-    #[strum(props(TrackPath = "dynastys_legacy_2.mp3"))]
-    InGame,
+impl MusicPlaylistConfigs {
+    #[inline]
+    pub fn playlist(&self, state: GameState) -> &[String] {
+        match state {
+            GameState::Unknown  => &[],
+            GameState::HomeMenu => &self.home_menu,
+            GameState::InGame   => &self.in_game,
+            GameState::Crisis   => &self.crisis,
+        }
+    }
 }
 
-impl MusicTrackKey {
-    pub(crate) fn track_path(self) -> PathRef<'static> {
-        PathRef::from_str(self.get_str("TrackPath").unwrap())
+impl Default for MusicPlaylistConfigs {
+    fn default() -> Self {
+        Self {
+            home_menu: vec!["dynastys_legacy_1.mp3".into()],
+            in_game: vec!["dynastys_legacy_2.mp3".into()],
+            crisis: Vec::new(),
+            shuffle: true,
+        }
     }
 }
 
+engine::configurations! { MUSIC_PLAYLIST_CONFIGS_SINGLETON, MusicPlaylistConfigs, "game/music_playlists" }
+
 // ----------------------------------------------
 // MusicTrack
 // ----------------------------------------------
 
-const MUSIC_TRACK_COUNT: usize = MusicTrackKey::COUNT;
-
 struct MusicTrack {
     key: MusicSoundKey,
-    handle: SoundHandle,
+    path: String,
 }
 
 impl MusicTrack {
-    fn load(sound_sys: &mut SoundSystem, track_path: PathRef) -> Self {
+    fn new(track_path: &str) -> Self {
         debug_assert!(!track_path.is_empty());
-        Self {
-            // All music track assets are under "music/{track_path}"
-            key: sound_sys.load_music(track_path),
-            handle: SoundHandle::invalid(SoundKind::Music), // Handle set when we first play the track.
-        }
+        Self { key: MusicSoundKey::invalid(), path: track_path.into() }
+    }
+
+    fn load(&mut self, sound_sys: &mut SoundSystem) {
+        debug_assert!(!self.is_loaded());
+        // All music track assets are under "music/{track_path}"
+        self.key = sound_sys.load_music(PathRef::from_str(&self.path));
+    }
+
+    fn unload(&mut self, sound_sys: &mut SoundSystem) {
+        sound_sys.unload_music(self.key);
+        self.key = MusicSoundKey::invalid();
     }
 
     fn is_loaded(&self) -> bool {
         self.key.is_valid()
     }
+}
+
+// ----------------------------------------------
+// MusicPlaylist
+// ----------------------------------------------
+
+#[derive(Default)]
+struct MusicPlaylist {
+    tracks: Vec<MusicTrack>,
 
-    fn is_playing(&self, sound_sys: &SoundSystem) -> bool {
-        sound_sys.is_playing(self.handle)
+    // Play order of `tracks` indices. Rebuilt once every track has been played.
+    order: Vec<usize>,
+    next: usize,
+}
+
+impl MusicPlaylist {
+    // Tracks are only loaded while their playlist is the one playing.
+    fn new(track_paths: &[String]) -> Self {
+        Self {
+            tracks: track_paths.iter().map(|path| MusicTrack::new(path)).collect(),
+            order: Vec::new(),
+            next: 0,
+        }
     }
 
-    fn play(&mut self, sound_sys: &mut SoundSystem, looping: bool) {
-        if !self.is_loaded() {
-            return;
+    fn load(&mut self, sound_sys: &mut SoundSystem) {
+        for track in &mut self.tracks {
+            track.load(sound_sys);
         }
+    }
 
-        self.handle = sound_sys.play_music(self.key, looping);
+    fn unload(&mut self, sound_sys: &mut SoundSystem) {
+        for track in &mut self.tracks {
+            track.unload(sound_sys);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.tracks.is_empty()
+    }
+
+    // Index of the next track to play, or None if there are no playable tracks.
+    fn next_track(&mut self, last_played: Option<usize>, shuffle: bool) -> Option<usize> {
+        for _ in 0..self.tracks.len() {
+            if self.next >= self.order.len() {
+                // Music is presentation only and not saved, so it must not draw from
+                // the sim RngStreams, where it would shift what the simulation rolls next.
+                self.order = play_order(self.tracks.len(), last_played, shuffle, &mut rand::rng());
+                self.next = 0;
+            }
+
+            let index = self.order[self.next];
+            self.next += 1;
+
+            if self.tracks[index].is_loaded() {
+                return Some(index);
+            }
+        }
+        None
     }
 }
 
+// Playlist order for `track_count` tracks. When shuffling, the previous
+// track is never picked first, so no track plays twice in a row.
+fn play_order(track_count: usize, last_played: Option<usize>, shuffle: bool, rng: &mut impl rand::Rng) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..track_count).collect();
+
+    if shuffle {
+        order.shuffle(rng);
+
+        if track_count > 1 && order.first().copied() == last_played {
+            order.swap(0, track_count - 1);
+        }
+    }
+
+    order
+}
+
 // ----------------------------------------------
 // GameState
 // ----------------------------------------------
 
-#[derive(Copy, Clone, Default, PartialEq, Eq, Display)]
-pub(crate) enum GameState {
+#[derive(Copy, Clone, Default, PartialEq, Eq, Display, EnumCount, EnumIter)]
+pub enum GameState {
     #[default]
     Unknown,
     HomeMenu,
     InGame,
+    Crisis, // In-game with raiders on the map.
 }
 
 impl GameState {
-    fn query() -> Self {
+    fn query(context: &SimContext) -> Self {
         let game_loop = GameLoop::get();
         if game_loop.is_in_home_menu() {
             Self::HomeMenu
         } else if game_loop.is_in_game() {
-            Self::InGame
+            if InvasionSystem::count_raiders(context) != 0 { Self::Crisis } else { Self::InGame }
         } else {
             Self::Unknown
         }
@@ -100,13 +193,24 @@ impl GameState {
 // AmbientMusicSystem
 // ----------------------------------------------
 
+// Plays the playlist of the current game state, one track after the other.
+// Switching tracks stops the current one with the music fade out while the
+// next one fades in, so changes in game state crossfade between playlists.
 #[derive(Default, Serialize, Deserialize)]
 pub struct AmbientMusicSystem {
     #[serde(skip)]
-    tracks: Vec<MusicTrack>,
+    playlists: Vec<MusicPlaylist>,
+
+    // Playlist with its tracks loaded. Others are released so their sound banks can unload them.
+    #[serde(skip)]
+    loaded_playlist: Option<GameState>,
 
+    // Game state of the playlist playing and the track index in it.
     #[serde(skip)]
-    current_track_playing: Option<MusicTrackKey>,
+    current_track_playing: Option<(GameState, usize)>,
+
+    #[serde(skip)]
+    current_handle: Option<SoundHandle>,
 
     #[serde(skip)]
     current_game_state: GameState,
@@ -117,17 +221,19 @@ impl GameSystem for AmbientMusicSystem {
         self
     }
 
-    fn update(&mut self, engine: &mut Engine, _cmds: &mut SimCmds, _context: &SimContext) {
-        self.update_internal(engine);
+    fn update(&mut self, engine: &mut Engine, _cmds: &mut SimCmds, context: &SimContext) {
+        self.update_internal(engine, context);
     }
 
-    fn paused_update(&mut self, engine: &mut Engine, _context: &SimContext) {
+    fn paused_update(&mut self, engine: &mut Engine, context: &SimContext) {
         // We want to update as normal when paused since the home menu will pause the game simulation.
-        self.update_internal(engine);
+        self.update_internal(engine, context);
     }
 
     fn reset(&mut self, engine: &mut Engine) {
-        self.stop_music(engine.sound_system_mut());
+        let sound_sys = engine.sound_system_mut();
+        self.stop_music(sound_sys);
+        self.unload_playlist(sound_sys);
         self.current_game_state = GameState::default();
     }
 
@@ -144,9 +250,10 @@ impl GameSystem for AmbientMusicSystem {
 }
 
 impl AmbientMusicSystem {
-    #[inline]
-    pub(crate) fn current_track_playing(&self) -> Option<MusicTrackKey> {
+    // Path of the track currently playing.
+    pub(crate) fn current_track_playing(&self) -> Option<&str> {
         self.current_track_playing
+            .map(|(state, index)| self.playlists[state as usize].tracks[index].path.as_str())
     }
 
     #[inline]
@@ -159,94 +266,129 @@ impl AmbientMusicSystem {
         !GameConfigs::get().debug.disable_ambient_music
     }
 
-    #[inline]
-    fn track(&self, key: MusicTrackKey) -> &MusicTrack {
-        debug_assert!(self.tracks_are_loaded());
-        &self.tracks[key as usize]
+    // Crossfades to the next track of the current playlist.
+    pub(crate) fn skip_track(&mut self, sound_sys: &mut SoundSystem) {
+        if self.playlists_are_loaded() {
+            self.start_new_track(sound_sys);
+        }
     }
 
     #[inline]
-    fn track_mut(&mut self, key: MusicTrackKey) -> &mut MusicTrack {
-        debug_assert!(self.tracks_are_loaded());
-        &mut self.tracks[key as usize]
+    fn playlists_are_loaded(&self) -> bool {
+        !self.playlists.is_empty()
     }
 
-    #[inline]
-    fn tracks_are_loaded(&self) -> bool {
-        !self.tracks.is_empty()
+    fn load_playlists(&mut self) {
+        log::info!(log::channel!("ambient_music"), "Loading ambient music playlists...");
+
+        debug_assert!(!self.playlists_are_loaded(), "Ambient music playlists already loaded!");
+        self.playlists.reserve(GameState::COUNT);
+
+        let configs = MusicPlaylistConfigs::get();
+        for state in GameState::iter() {
+            self.playlists.push(MusicPlaylist::new(configs.playlist(state)));
+        }
     }
 
-    fn load_tracks(&mut self, sound_sys: &mut SoundSystem) {
-        log::info!(log::channel!("ambient_music"), "Loading ambient music tracks...");
+    // Loads the tracks of the current game state's playlist, releasing the previous one.
+    fn switch_loaded_playlist(&mut self, sound_sys: &mut SoundSystem) {
+        let state = self.playlist_state(self.current_game_state);
+        if self.loaded_playlist == Some(state) {
+            return;
+        }
+
+        self.unload_playlist(sound_sys);
+        self.playlists[state as usize].load(sound_sys);
+        self.loaded_playlist = Some(state);
+    }
 
-        debug_assert!(!self.tracks_are_loaded(), "Ambient music tracks already loaded!");
-        self.tracks.reserve(MUSIC_TRACK_COUNT);
+    // Tracks still playing fade out as usual; unloading doesn't cut them off.
+    fn unload_playlist(&mut self, sound_sys: &mut SoundSystem) {
+        if let Some(state) = self.loaded_playlist.take() {
+            self.playlists[state as usize].unload(sound_sys);
+        }
+    }
 
-        for key in MusicTrackKey::iter() {
-            let track = MusicTrack::load(sound_sys, key.track_path());
-            self.tracks.push(track);
+    // Playlist for a game state. Falls back to the in-game playlist if there are no crisis tracks.
+    fn playlist_state(&self, state: GameState) -> GameState {
+        if state == GameState::Crisis && self.playlists[state as usize].is_empty() {
+            GameState::InGame
+        } else {
+            state
         }
     }
 
-    fn play_track(&mut self, sound_sys: &mut SoundSystem, key: MusicTrackKey) {
-        log::verbose!(log::channel!("ambient_music"), "Starting music track {} ('{}')", key, key.track_path());
+    fn play_track(&mut self, sound_sys: &mut SoundSystem, state: GameState, index: usize) {
+        let track = &self.playlists[state as usize].tracks[index];
+        log::verbose!(log::channel!("ambient_music"), "Starting {state} music track '{}'", track.path);
 
         const LOOPING: bool = false;
-        self.track_mut(key).play(sound_sys, LOOPING);
-
-        self.current_track_playing = Some(key);
+        self.current_handle = Some(sound_sys.play_music(track.key, LOOPING));
+        self.current_track_playing = Some((state, index));
     }
 
     fn stop_music(&mut self, sound_sys: &mut SoundSystem) {
         sound_sys.stop_music();
         self.current_track_playing = None;
+        self.current_handle = None;
     }
 
     fn start_new_track(&mut self, sound_sys: &mut SoundSystem) {
-        self.stop_music(sound_sys);
+        // Fades out while the next track fades in.
+        if let Some(handle) = self.current_handle.take() {
+            sound_sys.stop(handle);
+        }
+
+        let state = self.playlist_state(self.current_game_state);
+        let last_played = self.current_track_playing
+            .take()
+            .filter(|(playing_state, _)| *playing_state == state)
+            .map(|(_, index)| index);
 
-        match self.current_game_state {
-            GameState::HomeMenu => self.play_track(sound_sys, MusicTrackKey::HomeMenu),
-            GameState::InGame   => self.play_track(sound_sys, MusicTrackKey::InGame),
-            GameState::Unknown  => {}, // Silence.
+        let shuffle = MusicPlaylistConfigs::get().shuffle;
+        if let Some(index) = self.playlists[state as usize].next_track(last_played, shuffle) {
+            self.play_track(sound_sys, state, index);
         }
     }
 
     fn update_current_track(&mut self, sound_sys: &SoundSystem) -> bool {
-        if let Some(key) = self.current_track_playing {
-            if !self.track(key).is_playing(sound_sys) {
-                self.current_track_playing = None;
-            }
+        if let Some(handle) = self.current_handle
+            && !sound_sys.is_playing(handle)
+        {
+            self.current_handle = None;
         }
 
-        self.current_track_playing.is_some() // == is playing
+        self.current_handle.is_some() // == is playing
     }
 
-    fn update_game_state(&mut self) -> bool {
-        let new_state = GameState::query();
-        let mut state_changed = false;
+    // True if the new state plays from a different playlist.
+    fn update_game_state(&mut self, context: &SimContext) -> bool {
+        let new_state = GameState::query(context);
+        let mut playlist_changed = false;
 
         if self.current_game_state != new_state {
+            playlist_changed = self.playlist_state(self.current_game_state) != self.playlist_state(new_state);
             self.current_game_state = new_state;
-            state_changed = true;
         }
 
-        state_changed
+        playlist_changed
     }
 
-    fn update_internal(&mut self, engine: &mut Engine) {
+    fn update_internal(&mut self, engine: &mut Engine, context: &SimContext) {
         if !self.is_enabled() {
             return;
         }
 
         let sound_sys = engine.sound_system_mut();
 
-        if !self.tracks_are_loaded() {
-            self.load_tracks(sound_sys);
+        if !self.playlists_are_loaded() {
+            self.load_playlists();
         }
 
         let track_is_playing   = self.update_current_track(sound_sys);
-        let game_state_changed = self.update_game_state();
+        let game_state_changed = self.update_game_state(context);
+
+        self.switch_loaded_playlist(sound_sys);
 
         // If nothing is currently playing or if the game state has changed, start a new track.
         if !track_is_playing || game_state_changed {
@@ -254,3 +396,29 @@ impl AmbientMusicSystem {
         }
     }
 }
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::sim::RandomGenerator;
+
+    #[test]
+    fn shuffled_order_never_repeats_last_track() {
+        let mut rng = RandomGenerator::seed_from_u64(0);
+
+        for _ in 0..100 {
+            let order = play_order(3, Some(1), true, &mut rng);
+            assert_eq!(order.len(), 3);
+            assert_ne!(order[0], 1);
+        }
+
+        assert_eq!(play_order(3, Some(0), false, &mut rng), vec![0, 1, 2]);
+        assert_eq!(play_order(1, Some(0), true, &mut rng), vec![0]);
+    }
+}
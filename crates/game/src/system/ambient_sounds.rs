@@ -1,16 +1,21 @@
 use std::any::Any;
 
+use common::time::Seconds;
 use engine::{
     Engine,
     file_sys::paths::PathRef,
     log,
-    sound::{AmbienceSoundKey, SoundHandle, SoundKey, SoundKind, SoundSystem},
+    sound::{AmbienceSoundKey, SoundEventKey, SoundHandle, SoundKey, SoundKind, SoundSystem},
 };
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumCount, EnumIter, EnumProperty, IntoEnumIterator};
 
 use super::GameSystem;
-use crate::{config::GameConfigs, save_context::PostLoadContext, sim::{SimCmds, SimContext}};
+use crate::{
+    config::GameConfigs,
+    save_context::PostLoadContext,
+    sim::{SimCmds, SimContext, weather::WeatherKind},
+};
 
 // ----------------------------------------------
 // AmbientSoundKey
@@ -20,14 +25,35 @@ use crate::{config::GameConfigs, save_context::PostLoadContext, sim::{SimCmds, S
 pub(crate) enum AmbientSoundKey {
     #[strum(props(SoundPath = "birds_chirping.mp3"))]
     BirdsChirping,
+
+    #[strum(props(SoundPath = "wind.mp3"))]
+    Wind,
+
+    #[strum(props(SoundPath = "rain.mp3"))]
+    Rain,
 }
 
 impl AmbientSoundKey {
     pub(crate) fn sound_path(self) -> PathRef<'static> {
         PathRef::from_str(self.get_str("SoundPath").unwrap())
     }
+
+    fn for_weather(weather: WeatherKind) -> Self {
+        match weather {
+            WeatherKind::Clear => Self::BirdsChirping,
+            WeatherKind::Windy => Self::Wind,
+            WeatherKind::Rain | WeatherKind::Storm => Self::Rain,
+        }
+    }
 }
 
+// Occasional thunder over the storm loop, more frequent the stronger the storm.
+const THUNDER_EVENT: SoundEventKey = SoundEventKey::new("weather.thunder");
+const THUNDER_CHANCE_PER_SEC: f32 = 0.05;
+
+// Weather intensity change that restarts the current loop at the new volume.
+const INTENSITY_CHANGE_THRESHOLD: f32 = 0.25;
+
 // ----------------------------------------------
 // AmbientSound
 // ----------------------------------------------
@@ -57,12 +83,12 @@ impl AmbientSound {
         sound_sys.is_playing(self.handle)
     }
 
-    fn play(&mut self, sound_sys: &mut SoundSystem, looping: bool) {
+    fn play(&mut self, sound_sys: &mut SoundSystem, looping: bool, volume: f32) {
         if !self.is_loaded() {
             return;
         }
 
-        self.handle = sound_sys.play_ambience(self.key, looping);
+        self.handle = sound_sys.play_ambience_at_volume(self.key, looping, volume);
     }
 }
 
@@ -77,6 +103,12 @@ pub struct AmbientSoundsSystem {
 
     #[serde(skip)]
     current_sound_playing: Option<AmbientSoundKey>,
+
+    // Weather the current sound was started for.
+    #[serde(skip)]
+    current_weather: WeatherKind,
+    #[serde(skip)]
+    current_intensity: f32,
 }
 
 impl GameSystem for AmbientSoundsSystem {
@@ -84,7 +116,7 @@ impl GameSystem for AmbientSoundsSystem {
         self
     }
 
-    fn update(&mut self, engine: &mut Engine, _cmds: &mut SimCmds, _context: &SimContext) {
+    fn update(&mut self, engine: &mut Engine, _cmds: &mut SimCmds, context: &SimContext) {
         if !self.is_enabled() {
             return;
         }
@@ -96,16 +128,24 @@ impl GameSystem for AmbientSoundsSystem {
         }
 
         let sound_is_playing   = self.update_current_sound(sound_sys);
-        let game_state_changed = self.update_game_state();
+        let game_state_changed = self.update_game_state(context);
 
         // If nothing is currently playing or if the game state has changed, start a new sound.
         if !sound_is_playing || game_state_changed {
             self.start_new_sound(sound_sys);
         }
+
+        if self.current_weather == WeatherKind::Storm {
+            Self::play_thunder(sound_sys, self.current_intensity, context.delta_time_secs());
+        }
     }
 
     fn reset(&mut self, engine: &mut Engine) {
-        self.stop_sounds(engine.sound_system_mut());
+        let sound_sys = engine.sound_system_mut();
+        self.stop_sounds(sound_sys);
+        // Let go of our references so the state's sound bank can unload them.
+        // Reloaded on the next update if we are still playing.
+        self.unload_sounds(sound_sys);
     }
 
     fn post_load(&mut self, context: &mut PostLoadContext) {
@@ -126,6 +166,11 @@ impl AmbientSoundsSystem {
         self.current_sound_playing
     }
 
+    #[inline]
+    pub(crate) fn current_weather(&self) -> (WeatherKind, f32) {
+        (self.current_weather, self.current_intensity)
+    }
+
     #[inline]
     pub(crate) fn is_enabled(&self) -> bool {
         !GameConfigs::get().debug.disable_ambient_sounds
@@ -160,11 +205,17 @@ impl AmbientSoundsSystem {
         }
     }
 
-    fn play_sound(&mut self, sound_sys: &mut SoundSystem, key: AmbientSoundKey) {
+    fn unload_sounds(&mut self, sound_sys: &mut SoundSystem) {
+        for sound in self.sounds.drain(..) {
+            sound_sys.unload_ambience(sound.key);
+        }
+    }
+
+    fn play_sound(&mut self, sound_sys: &mut SoundSystem, key: AmbientSoundKey, volume: f32) {
         log::verbose!(log::channel!("ambient_sounds"), "Starting ambient sound {} ('{}')", key, key.sound_path());
 
         const LOOPING: bool = false;
-        self.sound_mut(key).play(sound_sys, LOOPING);
+        self.sound_mut(key).play(sound_sys, LOOPING, volume);
 
         self.current_sound_playing = Some(key);
     }
@@ -177,8 +228,17 @@ impl AmbientSoundsSystem {
     fn start_new_sound(&mut self, sound_sys: &mut SoundSystem) {
         self.stop_sounds(sound_sys);
 
-        // TODO: Choose sound to play based on game state.
-        self.play_sound(sound_sys, AmbientSoundKey::BirdsChirping);
+        // Clear skies play at full volume, weather loops follow its intensity.
+        let volume = if self.current_weather == WeatherKind::Clear { 1.0 } else { self.current_intensity };
+        self.play_sound(sound_sys, AmbientSoundKey::for_weather(self.current_weather), volume);
+    }
+
+    fn play_thunder(sound_sys: &mut SoundSystem, intensity: f32, delta_time_secs: Seconds) {
+        // Cosmetic only, so it doesn't draw from the sim RNG streams.
+        if rand::random::<f32>() < THUNDER_CHANCE_PER_SEC * intensity * delta_time_secs {
+            const LOOPING: bool = false;
+            sound_sys.play_event(THUNDER_EVENT, LOOPING);
+        }
     }
 
     fn update_current_sound(&mut self, sound_sys: &SoundSystem) -> bool {
@@ -191,8 +251,16 @@ impl AmbientSoundsSystem {
         self.current_sound_playing.is_some() // == is playing
     }
 
-    fn update_game_state(&mut self) -> bool {
-        // TODO: Query game state so we can choose an ambient sound based on it.
-        false
+    fn update_game_state(&mut self, context: &SimContext) -> bool {
+        let weather = context.weather();
+        let weather_changed = self.current_weather != weather.kind()
+            || (self.current_intensity - weather.intensity()).abs() >= INTENSITY_CHANGE_THRESHOLD;
+
+        if weather_changed {
+            self.current_weather = weather.kind();
+            self.current_intensity = weather.intensity();
+        }
+
+        weather_changed
     }
 }
@@ -0,0 +1,301 @@
+use std::any::Any;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use common::{
+    hash,
+    coords::{Cell, CellRange},
+    time::UpdateTimer,
+};
+use engine::{Engine, log};
+
+use super::GameSystem;
+use crate::{
+    config::GameConfigs,
+    save_context::PostLoadContext,
+    building::{Building, BuildingKind, BuildingKindAndId, config::BuildingConfigs},
+    sim::{
+        RngStream,
+        SimCmds,
+        SimContext,
+        SimCmdQueue,
+        events::{SimEventSource, sim_event},
+        notifications::notify,
+    },
+    tile::{
+        TileMapLayerKind,
+        heatmap::{HeatmapGradient, HeatmapOverlays},
+        sets::{OBJECTS_ROCKS_CATEGORY, TileDef},
+    },
+};
+
+// ----------------------------------------------
+// HazardsSystem
+// ----------------------------------------------
+
+// Buildings slowly accumulate fire and collapse risk. Burning buildings
+// may spread the fire to their neighbors and eventually burn down.
+// Destroyed buildings are replaced by rubble that must be cleared.
+#[derive(Serialize, Deserialize)]
+pub struct HazardsSystem {
+    pub(crate) update_timer: UpdateTimer,
+
+    // Buildings on fire as of the last hazards update.
+    #[serde(skip)]
+    burning_count: u32,
+}
+
+impl GameSystem for HazardsSystem {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn update(&mut self, _engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        if self.update_timer.tick(context.calendar().paced_secs(context.delta_time_secs())).should_update() {
+            self.update_hazards(cmds, context);
+        }
+    }
+
+    fn reset(&mut self, _engine: &mut Engine) {
+        self.update_timer.reset();
+        self.burning_count = 0;
+    }
+
+    fn post_load(&mut self, context: &mut PostLoadContext) {
+        self.update_timer.post_load(context.configs().sim.hazards_update_frequency_secs);
+    }
+
+    fn draw_debug_ui(&mut self, engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        // Debug-UI drawing lives in `crate::debug::systems`.
+        self.draw_debug_ui_dispatch(engine, cmds, context);
+    }
+
+    fn register_callbacks(&self) {
+        let overlays = HeatmapOverlays::get_mut();
+        overlays.register_grid(Self::FIRE_RISK_HEATMAP, HeatmapGradient::cold_to_hot());
+        overlays.set_danger_threshold(Self::FIRE_RISK_HEATMAP, Self::FIRE_RISK_DANGER_THRESHOLD);
+    }
+}
+
+impl Default for HazardsSystem {
+    fn default() -> Self {
+        Self {
+            update_timer: UpdateTimer::new(GameConfigs::get().sim.hazards_update_frequency_secs),
+            burning_count: 0,
+        }
+    }
+}
+
+impl HazardsSystem {
+    pub(crate) const BUILDING_KINDS: [BuildingKind; 4] = [
+        BuildingKind::House,
+        BuildingKind::producers(),
+        BuildingKind::storage(),
+        BuildingKind::services(),
+    ];
+
+    pub const FIRE_RISK_HEATMAP: &str = "Fire Risk";
+    // Fire risk cells at or above this are hatched as danger zones, if enabled.
+    const FIRE_RISK_DANGER_THRESHOLD: f32 = 0.75;
+
+    // Any building currently on fire.
+    #[inline]
+    pub fn is_disaster_active(&self) -> bool {
+        self.burning_count != 0
+    }
+
+    pub(crate) fn update_hazards(&mut self, cmds: &mut SimCmds, context: &SimContext) {
+        let elapsed_secs = self.update_timer.frequency_secs();
+        let mut burning_count = 0;
+
+        for kinds in Self::BUILDING_KINDS {
+            context.world().for_each_building(kinds, |building| {
+                let hazards = building.hazards();
+                if hazards.is_destroyed() {
+                    Self::replace_with_rubble(cmds, context, building);
+                } else if hazards.is_burning() {
+                    Self::update_burning(cmds, context, building, elapsed_secs);
+                    burning_count += 1;
+                } else {
+                    Self::update_risks(cmds, context, building);
+                }
+                true
+            });
+        }
+
+        self.burning_count = burning_count;
+        Self::update_fire_risk_heatmap(context);
+    }
+
+    fn update_fire_risk_heatmap(context: &SimContext) {
+        let overlays = HeatmapOverlays::get_mut();
+        if overlays.active_layer_name() != Some(Self::FIRE_RISK_HEATMAP) {
+            return; // Not visible, skip the work.
+        }
+
+        overlays.update_grid(Self::FIRE_RISK_HEATMAP, context.map_size_in_cells(), |grid| {
+            grid.clear();
+            for kinds in Self::BUILDING_KINDS {
+                context.world().for_each_building(kinds, |building| {
+                    let hazards = building.hazards();
+                    let value = if hazards.is_burning() { 1.0 } else { hazards.fire_risk() };
+                    for cell in &building.cell_range() {
+                        grid.set_value_at(cell, value);
+                    }
+                    true
+                });
+            }
+        });
+    }
+
+    pub(crate) fn set_on_fire(cmds: &mut SimCmds, kind_and_id: BuildingKindAndId) {
+        cmds.defer_building_update(kind_and_id, |context, building| {
+            if building.set_on_fire(context, GameConfigs::get().sim.building_burn_duration_secs) {
+                log::info!(log::channel!("hazards"), "{} caught fire at {}.", building.name(), building.base_cell());
+                Self::log_event(building, "Caught fire (spread)");
+            }
+        });
+    }
+
+    fn update_risks(cmds: &mut SimCmds, context: &SimContext, building: &Building) {
+        let configs = &GameConfigs::get().sim;
+
+        let mut fire_risk = configs.fire_risk_per_update;
+        if building.has_hazard_prevention(context, configs.fire_prevention_services) {
+            fire_risk *= configs.hazard_prevention_factor;
+        }
+
+        let mut collapse_risk = configs.collapse_risk_per_update;
+        if building.has_hazard_prevention(context, configs.collapse_prevention_services) {
+            collapse_risk *= configs.hazard_prevention_factor;
+        }
+
+        cmds.defer_building_update(building.kind_and_id(), move |context, building| {
+            let configs = &GameConfigs::get().sim;
+
+            let hazards = building.hazards_mut();
+            hazards.accumulate_risks(fire_risk, collapse_risk);
+
+            let fire_roll: f32 = context.random_range_in(RngStream::Events, 0.0..1.0);
+            let collapse_roll: f32 = context.random_range_in(RngStream::Events, 0.0..1.0);
+
+            if collapse_roll < hazards.collapse_risk() * configs.collapse_chance {
+                hazards.collapse();
+                log::info!(log::channel!("hazards"), "{} collapsed at {}.", building.name(), building.base_cell());
+                Self::log_event(building, "Collapsed");
+                notify!(Collapse, building.base_cell(), "{} collapsed", building.name());
+            } else if fire_roll < hazards.fire_risk() * configs.fire_ignition_chance
+                && building.set_on_fire(context, configs.building_burn_duration_secs)
+            {
+                log::info!(log::channel!("hazards"), "{} caught fire at {}.", building.name(), building.base_cell());
+                Self::log_event(building, "Caught fire");
+                notify!(Fire, building.base_cell(), "{} caught fire", building.name());
+            }
+        });
+    }
+
+    fn update_burning(cmds: &mut SimCmds, context: &SimContext, building: &Building, elapsed_secs: f32) {
+        // Spread to neighboring buildings first, while we still exist.
+        let fire_spread_chance = GameConfigs::get().sim.fire_spread_chance;
+        for neighbor in Self::find_neighbor_buildings(context, building) {
+            if context.random_range_in(RngStream::Events, 0.0..1.0) < fire_spread_chance {
+                Self::set_on_fire(cmds, neighbor);
+            }
+        }
+
+        cmds.defer_building_update(building.kind_and_id(), move |_context, building| {
+            if building.hazards_mut().tick_burning(elapsed_secs) {
+                log::info!(log::channel!("hazards"), "{} burned down at {}.", building.name(), building.base_cell());
+                Self::log_event(building, "Burned down");
+            }
+        });
+    }
+
+    fn replace_with_rubble(cmds: &mut SimCmds, context: &SimContext, building: &Building) {
+        Self::upset_nearby_households(cmds, context, building);
+        cmds.despawn_building_with_id(building.kind_and_id());
+
+        let Some(rubble_tile_def) = Self::find_rubble_tile_def(context) else {
+            log::error!(log::channel!("hazards"), "Rubble TileDef not found!");
+            return;
+        };
+
+        for cell in &building.cell_range() {
+            cmds.spawn_tile_with_tile_def_cb(cell, rubble_tile_def, move |_context, result| {
+                if let Err(err) = result {
+                    log::error!(log::channel!("hazards"), "Failed to place rubble at {cell}: {}", err.message);
+                }
+            });
+        }
+    }
+
+    // Residents living close to a building that burned down or collapsed lose some happiness.
+    // Only looks up the cells around the disaster rather than visiting every house.
+    fn upset_nearby_households(cmds: &mut SimCmds, context: &SimContext, building: &Building) {
+        let config = BuildingConfigs::get().house_config();
+        let this_building = building.kind_and_id();
+        let disaster_cell = building.base_cell();
+        let radius = config.happiness_disaster_radius;
+
+        let area = CellRange::new(
+            Cell::new(disaster_cell.x - radius, disaster_cell.y - radius),
+            Cell::new(disaster_cell.x + radius, disaster_cell.y + radius),
+        );
+
+        for cell in &area {
+            if cell.manhattan_distance(disaster_cell) > radius {
+                continue;
+            }
+
+            // Match houses by their base cell so each one is only visited once.
+            let Some(house) = context.find_building_for_cell(cell) else {
+                continue;
+            };
+
+            if house.is(BuildingKind::House) && house.base_cell() == cell && house.kind_and_id() != this_building {
+                let penalty = config.happiness_disaster_penalty;
+                cmds.defer_building_update(house.kind_and_id(), move |_context, house| {
+                    house.as_house_mut().add_negative_event(penalty);
+                });
+            }
+        }
+    }
+
+    fn log_event(building: &Building, message: &str) {
+        sim_event!(StateChange, SimEventSource::Building(building.kind_and_id()), building.base_cell(), "{message}");
+    }
+
+    // Buildings adjacent to any of this building's cells, excluding itself and any already destroyed.
+    fn find_neighbor_buildings(context: &SimContext, building: &Building) -> SmallVec<[BuildingKindAndId; 8]> {
+        let this_building = building.kind_and_id();
+        let objects_layer = context.tile_map().layer(TileMapLayerKind::Objects);
+        let mut neighbors = SmallVec::new();
+
+        for cell in &building.cell_range() {
+            for neighbor_tile in objects_layer.tile_neighbors(cell, false).into_iter().flatten() {
+                let Some(neighbor) = context.find_building_for_cell(neighbor_tile.base_cell()) else {
+                    continue;
+                };
+
+                let kind_and_id = neighbor.kind_and_id();
+                if kind_and_id != this_building
+                    && !neighbor.hazards().is_burning()
+                    && !neighbor.hazards().is_destroyed()
+                    && !neighbors.contains(&kind_and_id)
+                {
+                    neighbors.push(kind_and_id);
+                }
+            }
+        }
+
+        neighbors
+    }
+
+    fn find_rubble_tile_def(context: &SimContext) -> Option<&'static TileDef> {
+        context.find_tile_def(
+            TileMapLayerKind::Objects,
+            OBJECTS_ROCKS_CATEGORY.hash,
+            hash::fnv1a_from_str(&GameConfigs::get().sim.rubble_tile_def_name),
+        )
+    }
+}
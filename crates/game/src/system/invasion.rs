@@ -0,0 +1,355 @@
+use std::any::Any;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+
+use common::{
+    coords::{Cell, CellRange},
+    time::{Seconds, UpdateTimer},
+};
+use engine::{Engine, log};
+
+use super::GameSystem;
+use crate::{
+    config::GameConfigs,
+    save_context::PostLoadContext,
+    pathfind::Node,
+    sim::{
+        RngStream,
+        SimCmds,
+        SimContext,
+        events::{SimEventSource, sim_event},
+        notifications::notify,
+    },
+    unit::{
+        Unit,
+        UnitId,
+        config::{UnitConfigKey, UnitConfigs},
+        group::{MAX_UNIT_GROUP_SIZE, UnitGroup},
+        task::{UnitJobKind, UnitTaskFlee, UnitTaskFleeState, UnitTaskPriority, UnitTaskRaid, UnitTaskRaidState},
+    },
+    world::object::GameObject,
+};
+
+// ----------------------------------------------
+// InvasionSystem
+// ----------------------------------------------
+
+// Periodically sends a band of raiders in from a random map edge once the city is
+// big enough to be worth raiding. Raiders sack buildings until killed by soldiers
+// patrolling from a Citadel, or until there is nothing left to sack. Raiders march
+// in together as a band up to a rally point short of the nearest target, then
+// break up and go after buildings on their own.
+#[derive(Serialize, Deserialize)]
+pub struct InvasionSystem {
+    pub(crate) invasion_timer: UpdateTimer,
+
+    // Raiders of the current invasion still marching in. Empty between invasions.
+    #[serde(default)]
+    raid_band: UnitGroup,
+
+    // Time the band has been on the march; it breaks up if it takes too long to get there.
+    #[serde(default)]
+    march_time_secs: Seconds,
+
+    // Raiders on the map as of the last update.
+    #[serde(skip)]
+    raiders_count: u32,
+}
+
+impl GameSystem for InvasionSystem {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn update(&mut self, _engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        self.raiders_count = Self::count_raiders(context);
+
+        if self.is_invasion_active() {
+            self.update_raid_band(context);
+            Self::scare_civilians_near_raiders(context);
+        } else if self.raid_band.destination().is_some() {
+            // Whole band got killed on the way in.
+            self.raid_band.clear();
+            self.march_time_secs = 0.0;
+        }
+
+        if self.invasion_timer.tick(context.calendar().paced_secs(context.delta_time_secs())).should_update() {
+            self.try_start_invasion(cmds, context);
+        }
+    }
+
+    fn reset(&mut self, _engine: &mut Engine) {
+        self.invasion_timer.reset();
+        self.raid_band.clear();
+        self.march_time_secs = 0.0;
+        self.raiders_count = 0;
+    }
+
+    fn post_load(&mut self, context: &mut PostLoadContext) {
+        self.invasion_timer.post_load(context.configs().sim.invasion_frequency_secs);
+    }
+
+    fn draw_debug_ui(&mut self, engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        // Debug-UI drawing lives in `crate::debug::systems`.
+        self.draw_debug_ui_dispatch(engine, cmds, context);
+    }
+}
+
+impl Default for InvasionSystem {
+    fn default() -> Self {
+        Self {
+            invasion_timer: UpdateTimer::new(GameConfigs::get().sim.invasion_frequency_secs),
+            raid_band: UnitGroup::new(),
+            march_time_secs: 0.0,
+            raiders_count: 0,
+        }
+    }
+}
+
+impl InvasionSystem {
+    // Attempts at finding a walkable map border cell before giving up on an invasion.
+    const MAX_ENTRY_CELL_ATTEMPTS: u32 = 16;
+
+    // Civilians this close to a raider (in cells) run away from it, this far.
+    const FLEE_RADIUS: i32 = 3;
+    const FLEE_DISTANCE: i32 = 8;
+
+    // The band halts this many cells short of the nearest target building.
+    const RALLY_DISTANCE: usize = 6;
+
+    // Give up on marching together after this long (members stuck or held up in melee).
+    const MAX_MARCH_SECS: Seconds = 60.0;
+
+    // Any raiders still roaming the map.
+    #[inline]
+    pub fn is_invasion_active(&self) -> bool {
+        self.raiders_count != 0
+    }
+
+    #[inline]
+    pub fn raiders_count(&self) -> u32 {
+        self.raiders_count
+    }
+
+    #[inline]
+    pub fn raid_band(&self) -> &UnitGroup {
+        &self.raid_band
+    }
+
+    // Returns true if an invasion was started.
+    pub(crate) fn try_start_invasion(&mut self, cmds: &mut SimCmds, context: &SimContext) -> bool {
+        let population = context.world().stats().population.total;
+        if population < GameConfigs::get().sim.invasion_min_population {
+            return false;
+        }
+
+        let Some(entry_cell) = Self::find_entry_cell(context) else {
+            log::error!(log::channel!("combat"), "InvasionSystem: Couldn't find a map edge cell for raiders to enter from.");
+            return false;
+        };
+
+        Self::start_invasion(cmds, context, entry_cell, Self::raiders_for_population(population));
+        true
+    }
+
+    pub(crate) fn start_invasion(cmds: &mut SimCmds, context: &SimContext, entry_cell: Cell, raider_count: u32) {
+        debug_assert!(entry_cell.is_valid());
+        debug_assert!(raider_count != 0);
+
+        for _ in 0..raider_count {
+            Unit::try_spawn_with_task_deferred_cb(cmds, context, entry_cell, UnitConfigKey::Raider,
+                UnitTaskRaid { target: None, state: UnitTaskRaidState::Marching },
+                |_context, result| {
+                    if let Err(err) = result {
+                        log::error!(log::channel!("unit"), "InvasionSystem: {}", err.message);
+                    }
+                },
+            );
+        }
+
+        log::info!(log::channel!("combat"), "{raider_count} raiders invading from {entry_cell}.");
+        sim_event!(StateChange, SimEventSource::System("InvasionSystem"), entry_cell, "{raider_count} raiders invading");
+        notify!(Invasion, entry_cell, "{raider_count} raiders approaching the city!");
+    }
+
+    pub fn raiders_for_population(population: u32) -> u32 {
+        let configs = &GameConfigs::get().sim;
+        let extra = population
+            .saturating_sub(configs.invasion_min_population)
+            .checked_div(configs.population_per_extra_raider)
+            .unwrap_or(0);
+        (configs.raiders_per_invasion + extra).max(1)
+    }
+
+    // Random cell along one of the four map borders that raiders can walk on.
+    pub(crate) fn find_entry_cell(context: &SimContext) -> Option<Cell> {
+        let map_size = context.map_size_in_cells();
+        let max_x = map_size.width - 1;
+        let max_y = map_size.height - 1;
+        let traversable_node_kinds = UnitConfigs::get().find_config_by_key(UnitConfigKey::Raider).traversable_node_kinds;
+
+        for _ in 0..Self::MAX_ENTRY_CELL_ATTEMPTS {
+            let cell = match context.random_range_in(RngStream::Events, 0..4) {
+                0 => Cell::new(context.random_range_in(RngStream::Events, 0..=max_x), 0),
+                1 => Cell::new(context.random_range_in(RngStream::Events, 0..=max_x), max_y),
+                2 => Cell::new(0, context.random_range_in(RngStream::Events, 0..=max_y)),
+                _ => Cell::new(max_x, context.random_range_in(RngStream::Events, 0..=max_y)),
+            };
+
+            if context.graph().node_kind(Node::new(cell)).is_some_and(|kind| kind.intersects(traversable_node_kinds)) {
+                return Some(cell);
+            }
+        }
+
+        None
+    }
+
+    pub(crate) fn count_raiders(context: &SimContext) -> u32 {
+        let mut count = 0;
+        context.world().for_each_unit(|unit| {
+            count += unit.is_hostile() as u32;
+            true
+        });
+        count
+    }
+
+    // Newly spawned raiders are gathered into the band and sent to the rally point.
+    // Once everyone got there (or the march took too long) the band breaks up.
+    fn update_raid_band(&mut self, context: &SimContext) {
+        let task_manager = context.task_manager();
+        let mut marching: SmallVec<[UnitId; MAX_UNIT_GROUP_SIZE]> = SmallVec::new();
+
+        context.world().for_each_unit(|unit| {
+            let is_marching = unit
+                .current_task_as::<UnitTaskRaid>(task_manager)
+                .is_some_and(|raid| raid.state == UnitTaskRaidState::Marching);
+
+            if is_marching && !self.raid_band.contains(unit.id()) {
+                marching.push(unit.id());
+            }
+            true
+        });
+
+        if self.raid_band.destination().is_none() {
+            for unit_id in marching {
+                // Band is full; extra raiders go on their own.
+                if !self.raid_band.add(unit_id) {
+                    Self::release_raider(context, unit_id);
+                }
+            }
+
+            if self.raid_band.is_empty() {
+                return;
+            }
+
+            let is_moving = Self::find_rally_cell(context, &self.raid_band)
+                .is_some_and(|rally_cell| self.raid_band.move_to(context, rally_cell));
+
+            if !is_moving {
+                self.break_up_raid_band(context);
+            }
+            return;
+        }
+
+        // Latecomers don't catch up with a band already on the move.
+        for unit_id in marching {
+            Self::release_raider(context, unit_id);
+        }
+
+        self.raid_band.update(context);
+        self.march_time_secs += context.delta_time_secs();
+
+        if self.raid_band.is_empty()
+            || self.raid_band.has_arrived(context)
+            || self.march_time_secs >= Self::MAX_MARCH_SECS
+        {
+            self.break_up_raid_band(context);
+        }
+    }
+
+    // A few cells short of the building nearest to the band leader, along the way there.
+    fn find_rally_cell(context: &SimContext, band: &UnitGroup) -> Option<Cell> {
+        let leader = band.leader().and_then(|leader_id| context.find_unit(leader_id))?;
+        let start = leader.cell();
+
+        let (_, path) = context.find_nearest_buildings(
+            start,
+            UnitTaskRaid::target_building_kinds(),
+            leader.traversable_node_kinds(),
+            None,
+            |building, _path| building.hazards().is_destroyed(), // Skip destroyed buildings.
+        )?;
+
+        if path.len() <= Self::RALLY_DISTANCE {
+            return Some(start); // Close enough already.
+        }
+
+        Some(path[path.len() - 1 - Self::RALLY_DISTANCE].cell)
+    }
+
+    fn break_up_raid_band(&mut self, context: &SimContext) {
+        for member in self.raid_band.members() {
+            Self::release_raider(context, member.unit_id);
+        }
+
+        self.raid_band.clear();
+        self.march_time_secs = 0.0;
+    }
+
+    // Stops the raider wherever it is and lets its raid task pick a target.
+    fn release_raider(context: &SimContext, unit_id: UnitId) {
+        let Some(unit) = context.find_unit_mut(unit_id) else {
+            return;
+        };
+
+        if let Some(raid) = unit.current_task_as_mut::<UnitTaskRaid>(context.task_manager_mut())
+            && raid.state == UnitTaskRaidState::Marching
+        {
+            raid.state = UnitTaskRaidState::Searching;
+            unit.follow_path(None);
+        }
+    }
+
+    // Civilians near raiders drop what they are doing and run. The flee task
+    // interrupts their current task, which resumes once they are out of harm's way.
+    fn scare_civilians_near_raiders(context: &SimContext) {
+        let world = context.world();
+        let mut civilians: SmallVec<[(UnitId, Cell); 16]> = SmallVec::new();
+
+        world.for_each_unit(|raider| {
+            if raider.is_hostile() {
+                let danger_cell = raider.cell();
+                world.for_each_unit_near(CellRange::new(danger_cell, danger_cell), Self::FLEE_RADIUS, |unit| {
+                    // Birds just fly over the fighting.
+                    if !unit.is_combatant() && !unit.is(UnitConfigKey::Bird) {
+                        civilians.push((unit.id(), danger_cell));
+                    }
+                    true
+                });
+            }
+            true
+        });
+
+        let task_manager = context.task_manager_mut();
+
+        for (unit_id, danger_cell) in civilians {
+            let Some(unit) = context.find_unit_mut(unit_id) else {
+                continue;
+            };
+
+            if unit.is_hidden(context)
+                || unit.has_job(task_manager, UnitJobKind::Flee)
+                || !unit.can_interrupt_current_task(task_manager, UnitTaskPriority::Urgent)
+            {
+                continue;
+            }
+
+            let task_id = task_manager.new_task(UnitTaskFlee {
+                danger_cell,
+                flee_distance: Self::FLEE_DISTANCE,
+                state: UnitTaskFleeState::default(),
+            });
+            unit.push_task(task_manager, task_id, UnitTaskPriority::Urgent);
+        }
+    }
+}
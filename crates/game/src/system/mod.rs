@@ -31,6 +31,15 @@ use ambient_sounds::AmbientSoundsSystem;
 pub mod campaign;
 use self::campaign::CampaignSystem;
 
+pub mod hazards;
+use hazards::HazardsSystem;
+
+pub mod invasion;
+use invasion::InvasionSystem;
+
+pub mod spatial_sounds;
+use spatial_sounds::SpatialSoundsSystem;
+
 // ----------------------------------------------
 // GameSystem
 // ----------------------------------------------
@@ -63,6 +72,9 @@ pub enum GameSystemImpl {
     AmbientMusicSystem,
     AmbientSoundsSystem,
     CampaignSystem,
+    HazardsSystem,
+    InvasionSystem,
+    SpatialSoundsSystem,
 }
 
 // ----------------------------------------------
@@ -139,6 +151,14 @@ impl GameSystems {
         None
     }
 
+    // Finds the first registered system of the given type.
+    pub fn find_by_type<System>(&self) -> Option<&System>
+    where
+        System: GameSystem + 'static,
+    {
+        self.systems.iter().find_map(|entry| entry.system.as_any().downcast_ref::<System>())
+    }
+
     pub fn has(&self, system_type: TypeId) -> bool {
         for entry in &self.systems {
             if entry.system.as_any().type_id() == system_type {
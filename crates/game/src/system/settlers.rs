@@ -1,5 +1,6 @@
-use std::any::Any;
+use std::{any::Any, collections::VecDeque};
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 
 use common::{
     hash,
@@ -9,13 +10,22 @@ use common::{
 };
 use engine::{Engine, log};
 
-use super::GameSystem;
+use super::{GameSystem, hazards::HazardsSystem};
 use crate::{
-    pathfind::Node,
+    pathfind::{Node, SearchResult},
     config::GameConfigs,
     save_context::PostLoadContext,
-    building::BuildingKind,
-    sim::{SimCmds, SimContext, SimCmdQueue, commands::ImmediateModeSimCmds},
+    building::{BuildingKind, BuildingKindAndId},
+    sim::{
+        RngStream,
+        SimCmds,
+        SimContext,
+        SimCmdQueue,
+        commands::ImmediateModeSimCmds,
+        events::{SimEventSource, sim_event},
+        notifications::notify,
+        resources::ResourceKind,
+    },
     tile::{
         TileFlags,
         TileKind,
@@ -33,12 +43,133 @@ use crate::{
             UnitTaskDespawnWithCallback,
             UnitTaskDespawnWithCallbackState,
             UnitTaskPostDespawnCallback,
+            SETTLER_TRAVERSABLE_NODE_KINDS,
             UnitTaskSettler,
             UnitTaskSettlerState,
         },
     },
 };
 
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Number of spawn timer updates kept in the migration history (for graphs).
+const MIGRATION_HISTORY_MAX_SAMPLES: usize = 120;
+
+// ----------------------------------------------
+// CityAttractiveness
+// ----------------------------------------------
+
+// How appealing the city is to settlers. Each factor is normalized to [0,1].
+#[derive(Copy, Clone, Default)]
+pub struct CityAttractiveness {
+    pub housing: f32,      // Vacant lots or houses with room available.
+    pub employment: f32,   // 1 - unemployment ratio.
+    pub food_variety: f32, // Kinds of food available / all food kinds.
+    pub happiness: f32,    // Population weighted household happiness.
+}
+
+impl CityAttractiveness {
+    pub fn compute(context: &SimContext) -> Self {
+        let stats = context.world().stats();
+
+        let housing = {
+            if SettlersSpawnSystem::has_vacant_lots(context) {
+                1.0
+            } else {
+                let mut houses = 0;
+                let mut houses_with_room = 0;
+                context.world().for_each_building(BuildingKind::House, |building| {
+                    houses += 1;
+                    if !building.population_is_maxed() {
+                        houses_with_room += 1;
+                    }
+                    true
+                });
+                if houses == 0 { 0.0 } else { houses_with_room as f32 / houses as f32 }
+            }
+        };
+
+        let employment = 1.0 - stats.population.unemployment_ratio();
+
+        let food_variety = {
+            let mut food_kinds = 0;
+            let mut food_kinds_available = 0;
+            for food in ResourceKind::foods() {
+                food_kinds += 1;
+                if stats.resources.all.count(food) != 0 {
+                    food_kinds_available += 1;
+                }
+            }
+            if food_kinds == 0 { 0.0 } else { food_kinds_available as f32 / food_kinds as f32 }
+        };
+
+        let happiness = stats.happiness.average().score();
+
+        Self { housing, employment, food_variety, happiness }
+    }
+
+    // Weighted [0,1] score.
+    pub fn overall(&self) -> f32 {
+        let configs = &GameConfigs::get().sim;
+
+        let total_weight = configs.attractiveness_housing_weight
+            + configs.attractiveness_employment_weight
+            + configs.attractiveness_food_variety_weight
+            + configs.attractiveness_happiness_weight;
+
+        if total_weight <= 0.0 {
+            return 0.0;
+        }
+
+        let score = (self.housing * configs.attractiveness_housing_weight)
+            + (self.employment * configs.attractiveness_employment_weight)
+            + (self.food_variety * configs.attractiveness_food_variety_weight)
+            + (self.happiness * configs.attractiveness_happiness_weight);
+
+        (score / total_weight).clamp(0.0, 1.0)
+    }
+}
+
+// ----------------------------------------------
+// MigrationStats
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Default)]
+pub struct MigrationSample {
+    pub attractiveness: f32,
+    pub population: u32,
+    pub immigrants: u32, // Population brought in by settlers spawned this update.
+    pub emigrants: u32,  // Population removed from houses this update.
+    pub riot_risk: f32,
+}
+
+// Per-update migration history, recorded every spawn timer update.
+#[derive(Default)]
+pub struct MigrationStats {
+    pub attractiveness: CityAttractiveness, // Most recent.
+    pub samples: VecDeque<MigrationSample>, // Oldest first.
+    pub total_immigrants: u32,
+    pub total_emigrants: u32,
+    pub total_riots: u32,
+}
+
+impl MigrationStats {
+    fn record(&mut self, sample: MigrationSample) {
+        if self.samples.len() == MIGRATION_HISTORY_MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.total_immigrants += sample.immigrants;
+        self.total_emigrants += sample.emigrants;
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+}
+
 // ----------------------------------------------
 // SettlersSpawnSystem
 // ----------------------------------------------
@@ -47,6 +178,15 @@ use crate::{
 pub struct SettlersSpawnSystem {
     pub(crate) spawn_timer: UpdateTimer,
     pub(crate) population_per_settler_unit: u32,
+
+    // Fractional settlers carried over between updates, so low rates still add up.
+    #[serde(default)]
+    immigration_accumulator: f32,
+    #[serde(default)]
+    emigration_accumulator: f32,
+
+    #[serde(skip)]
+    migration_stats: MigrationStats,
 }
 
 impl GameSystem for SettlersSpawnSystem {
@@ -56,15 +196,33 @@ impl GameSystem for SettlersSpawnSystem {
 
     fn update(&mut self, _engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
         if self.spawn_timer.tick(context.delta_time_secs()).should_update() {
-            // Only attempt to spawn if we have any empty housing lots available.
-            if Self::has_vacant_lots(context) {
-                self.spawn_settler(cmds, context);
+            let attractiveness = CityAttractiveness::compute(context);
+            let score = attractiveness.overall();
+
+            let immigrants = self.immigration_update(cmds, context, &attractiveness, score);
+            let emigrants = self.emigration_update(cmds, context, score);
+
+            let riot_risk = Self::riot_risk(attractiveness.happiness);
+            if self.riot_update(cmds, context, riot_risk) {
+                self.migration_stats.total_riots += 1;
             }
+
+            self.migration_stats.attractiveness = attractiveness;
+            self.migration_stats.record(MigrationSample {
+                attractiveness: score,
+                population: context.world().stats().population.total,
+                immigrants,
+                emigrants,
+                riot_risk,
+            });
         }
     }
 
     fn reset(&mut self, _engine: &mut Engine) {
         self.spawn_timer.reset();
+        self.immigration_accumulator = 0.0;
+        self.emigration_accumulator = 0.0;
+        self.migration_stats.clear();
     }
 
     fn post_load(&mut self, context: &mut PostLoadContext) {
@@ -87,6 +245,9 @@ impl Default for SettlersSpawnSystem {
         Self {
             spawn_timer: UpdateTimer::new(configs.sim.settlers_spawn_frequency_secs),
             population_per_settler_unit: configs.sim.population_per_settler_unit,
+            immigration_accumulator: 0.0,
+            emigration_accumulator: 0.0,
+            migration_stats: MigrationStats::default(),
         }
     }
 }
@@ -119,9 +280,185 @@ impl SettlersSpawnSystem {
         })
     }
 
-    pub(crate) fn spawn_settler(&self, cmds: &mut SimCmds, context: &SimContext) {
+    // Map border cell nearest to `cell` that settlers can walk to from `cell`.
+    // Settlers enter and leave through these, so they appear to come from off-screen.
+    pub(crate) fn find_map_edge_cell(context: &SimContext, cell: Cell) -> Option<Cell> {
+        let map_size = context.map_size_in_cells();
+        let max_x = map_size.width - 1;
+        let max_y = map_size.height - 1;
+
+        // Projections of `cell` onto each map border, nearest first.
+        let mut candidates = [
+            (cell.x, Cell::new(0, cell.y)),
+            (max_x - cell.x, Cell::new(max_x, cell.y)),
+            (cell.y, Cell::new(cell.x, 0)),
+            (max_y - cell.y, Cell::new(cell.x, max_y)),
+        ];
+        candidates.sort_by_key(|(distance, _)| *distance);
+
+        for (distance, edge_cell) in candidates {
+            if distance == 0 {
+                return Some(edge_cell); // Already on the border.
+            }
+
+            if let SearchResult::PathFound(_) = context.find_path(SETTLER_TRAVERSABLE_NODE_KINDS, cell, edge_cell) {
+                return Some(edge_cell);
+            }
+        }
+
+        None
+    }
+
+    // Returns the map cell the settler enters the city from.
+    pub(crate) fn spawn_settler(&self, cmds: &mut SimCmds, context: &SimContext) -> Cell {
         let spawn_point = Self::find_spawn_point(cmds, context);
-        Settler::spawn(cmds, context, spawn_point.cell, self.population_per_settler_unit);
+        let entry_cell = Self::find_map_edge_cell(context, spawn_point.cell).unwrap_or(spawn_point.cell);
+        Settler::immigrate(cmds, context, entry_cell, self.population_per_settler_unit);
+        entry_cell
+    }
+
+    // Spawns settlers at a rate proportional to city attractiveness.
+    // Returns the population brought in.
+    fn immigration_update(
+        &mut self,
+        cmds: &mut SimCmds,
+        context: &SimContext,
+        attractiveness: &CityAttractiveness,
+        score: f32,
+    ) -> u32 {
+        let configs = &GameConfigs::get().sim;
+
+        // Nowhere to settle or not appealing enough; don't bank settlers for later.
+        if attractiveness.housing <= 0.0 || score < configs.immigration_min_attractiveness {
+            self.immigration_accumulator = 0.0;
+            return 0;
+        }
+
+        self.immigration_accumulator += score * configs.max_settlers_per_spawn as f32;
+
+        let settlers_to_spawn = self.immigration_accumulator.floor();
+        self.immigration_accumulator -= settlers_to_spawn;
+
+        let mut entry_cell = Cell::invalid();
+        for _ in 0..settlers_to_spawn as u32 {
+            entry_cell = self.spawn_settler(cmds, context);
+        }
+
+        if entry_cell.is_valid() {
+            notify!(SettlersArrived, entry_cell, "{} settlers arriving", settlers_to_spawn as u32);
+        }
+
+        settlers_to_spawn as u32 * self.population_per_settler_unit
+    }
+
+    // Residents leave from random houses while attractiveness stays below the emigration
+    // threshold, faster the lower it gets. Returns the population asked to leave.
+    fn emigration_update(&mut self, cmds: &mut SimCmds, context: &SimContext, score: f32) -> u32 {
+        let configs = &GameConfigs::get().sim;
+
+        if score >= configs.emigration_max_attractiveness || configs.emigration_max_attractiveness <= 0.0 {
+            self.emigration_accumulator = 0.0;
+            return 0;
+        }
+
+        let severity = 1.0 - (score / configs.emigration_max_attractiveness);
+        self.emigration_accumulator += severity * configs.max_emigrants_per_update as f32;
+
+        let emigrants = self.emigration_accumulator.floor();
+        self.emigration_accumulator -= emigrants;
+
+        if emigrants < 1.0 {
+            return 0;
+        }
+
+        let mut inhabited_houses: SmallVec<[(BuildingKindAndId, u32); 32]> = SmallVec::new();
+        context.world().for_each_building(BuildingKind::House, |building| {
+            if building.population_count() != 0 {
+                inhabited_houses.push((building.kind_and_id(), building.population_count()));
+            }
+            true
+        });
+
+        if inhabited_houses.is_empty() {
+            return 0;
+        }
+
+        // Start from a random house and move on to the next ones until the emigrant count is met.
+        // Each house gives up at most its current population, so the total is what actually leaves.
+        let first = context.random_range_in(RngStream::Events, 0..inhabited_houses.len());
+        let mut remaining = emigrants as u32;
+        let mut total_removed = 0;
+
+        for i in 0..inhabited_houses.len() {
+            if remaining == 0 {
+                break;
+            }
+
+            let (house, population) = inhabited_houses[(first + i) % inhabited_houses.len()];
+            let count = remaining.min(population);
+
+            cmds.defer_building_update(house, move |context, building| {
+                // We are already within a deferred command; execute recursive commands immediately.
+                let mut cmds = ImmediateModeSimCmds::new(context);
+                building.emigrate_population(&mut cmds, context, count);
+            });
+
+            remaining -= count;
+            total_removed += count;
+        }
+
+        total_removed
+    }
+
+    // [0,1] odds scale for a riot breaking out. Zero while city happiness is above the riot threshold.
+    pub fn riot_risk(city_happiness: f32) -> f32 {
+        let configs = &GameConfigs::get().sim;
+        if city_happiness >= configs.riot_max_happiness || configs.riot_max_happiness <= 0.0 {
+            return 0.0;
+        }
+        1.0 - (city_happiness / configs.riot_max_happiness)
+    }
+
+    // Unhappy residents riot and set the unhappiest inhabited house on fire.
+    // The fire then spreads and gets handled by the HazardsSystem as usual.
+    fn riot_update(&mut self, cmds: &mut SimCmds, context: &SimContext, riot_risk: f32) -> bool {
+        if riot_risk <= 0.0 {
+            return false;
+        }
+
+        let riot_roll: f32 = context.random_range_in(RngStream::Events, 0.0..1.0);
+        if riot_roll >= riot_risk * GameConfigs::get().sim.riot_chance {
+            return false;
+        }
+
+        let mut unhappiest: Option<(BuildingKindAndId, f32)> = None;
+        context.world().for_each_building(BuildingKind::House, |building| {
+            if building.population_count() != 0 && !building.hazards().is_burning() {
+                let score = building.as_house().happiness().score();
+                if unhappiest.is_none_or(|(_, lowest)| score < lowest) {
+                    unhappiest = Some((building.kind_and_id(), score));
+                }
+            }
+            true
+        });
+
+        let Some((house, _)) = unhappiest else {
+            return false;
+        };
+
+        if let Some(building) = context.find_building(house.kind, house.id) {
+            log::info!(log::channel!("settlers"), "Riot broke out at {}.", building.base_cell());
+            sim_event!(StateChange, SimEventSource::Building(house), building.base_cell(), "Riot broke out");
+            notify!(Riot, building.base_cell(), "Riot broke out at {}", building.name());
+        }
+
+        HazardsSystem::set_on_fire(cmds, house);
+        true
+    }
+
+    #[inline]
+    pub fn migration_stats(&self) -> &MigrationStats {
+        &self.migration_stats
     }
 }
 
@@ -133,6 +470,22 @@ pub struct Settler;
 
 impl Settler {
     pub fn spawn(cmds: &mut SimCmds, context: &SimContext, unit_origin: Cell, population_to_add: u32) {
+        Self::spawn_with_state(cmds, context, unit_origin, population_to_add, UnitTaskSettlerState::default());
+    }
+
+    // Spawns a settler arriving from off-map at the `map_edge_cell`. It stays hidden there
+    // for the configured off-map travel time, then walks in and looks for a place to settle.
+    pub fn immigrate(cmds: &mut SimCmds, context: &SimContext, map_edge_cell: Cell, population_to_add: u32) {
+        Self::spawn_with_state(cmds, context, map_edge_cell, population_to_add, UnitTaskSettlerState::ArrivingFromOffMap);
+    }
+
+    fn spawn_with_state(
+        cmds: &mut SimCmds,
+        context: &SimContext,
+        unit_origin: Cell,
+        population_to_add: u32,
+        state: UnitTaskSettlerState,
+    ) {
         debug_assert!(unit_origin.is_valid());
         debug_assert!(population_to_add != 0);
 
@@ -152,7 +505,8 @@ impl Settler {
                 return_to_spawn_point_if_failed: true,
                 emigrate: false,
                 population_to_add,
-                state: UnitTaskSettlerState::default(),
+                state,
+                off_map_time_left: 0.0,
                 visit_outcome: None,
             },
             |_context, result| {
@@ -164,7 +518,8 @@ impl Settler {
     }
 
     // Spawns a settler that carries `population_leaving` residents straight to the
-    // map exit (spawn point) and leaves, without trying to settle a lot or house.
+    // map exit (spawn point), then walks off the nearest map edge and leaves,
+    // without trying to settle a lot or house.
     // Used to evict residents from houses left without basic needs for too long.
     pub fn emigrate(cmds: &mut SimCmds, context: &SimContext, unit_origin: Cell, population_leaving: u32) {
         debug_assert!(unit_origin.is_valid());
@@ -186,6 +541,7 @@ impl Settler {
                 emigrate: true,
                 population_to_add: population_leaving,
                 state: UnitTaskSettlerState::default(),
+                off_map_time_left: 0.0,
                 visit_outcome: None,
             },
             |_context, result| {
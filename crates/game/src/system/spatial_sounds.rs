@@ -0,0 +1,249 @@
+use std::any::Any;
+
+use common::coords::Cell;
+use engine::{
+    Engine,
+    sound::{SoundEventKey, SoundHandle, SoundSystem},
+};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
+use strum::{Display, EnumProperty};
+
+use super::GameSystem;
+use crate::{
+    building::{Building, BuildingKind, BuildingKindAndId},
+    config::GameConfigs,
+    random_events::RandomEvents,
+    save_context::PostLoadContext,
+    sim::{SimCmds, SimContext},
+    unit::task::{UnitTaskRaid, UnitTaskRaidState},
+};
+
+// ----------------------------------------------
+// SpatialSoundKey
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq, Display, EnumProperty)]
+pub(crate) enum SpatialSoundKey {
+    #[strum(props(SoundEvent = "building.construction"))]
+    Construction,
+
+    #[strum(props(SoundEvent = "building.fire"))]
+    Fire,
+
+    #[strum(props(SoundEvent = "building.market_chatter"))]
+    MarketChatter,
+
+    #[strum(props(SoundEvent = "building.raid"))]
+    Raid,
+}
+
+impl SpatialSoundKey {
+    // Name of the event in the sound events manifest.
+    pub(crate) fn event_name(self) -> &'static str {
+        self.get_str("SoundEvent").unwrap()
+    }
+
+    #[inline]
+    fn event_key(self) -> SoundEventKey {
+        SoundEventKey::new(self.event_name())
+    }
+
+    // Looping sound a building emits in its current state, if any.
+    // Earlier checks take priority: a burning construction site sounds like a fire.
+    fn for_building(building: &Building, is_being_raided: bool) -> Option<Self> {
+        if building.hazards().is_destroyed() {
+            None
+        } else if is_being_raided {
+            Some(Self::Raid)
+        } else if building.hazards().is_burning() {
+            Some(Self::Fire)
+        } else if building.is_under_construction() {
+            Some(Self::Construction)
+        } else if building.is(BuildingKind::Market) {
+            Some(Self::MarketChatter)
+        } else {
+            None
+        }
+    }
+}
+
+// ----------------------------------------------
+// SpatialEmitter
+// ----------------------------------------------
+
+// Max looping emitters playing at once. The closest buildings win.
+const MAX_EMITTERS: usize = 8;
+
+// Emitters start when their building is within the spatial cutoff distance
+// and stop once it is this much further out, so they don't flicker on and off
+// while the camera pans around the edge of the audible range.
+const STOP_DISTANCE_SCALE: f32 = 1.25;
+
+pub(crate) struct SpatialEmitter {
+    pub(crate) building: BuildingKindAndId,
+    pub(crate) cell: Cell,
+    pub(crate) key: SpatialSoundKey,
+    handle: SoundHandle,
+}
+
+// Building that wants to emit a sound this update.
+struct EmitterCandidate {
+    building: BuildingKindAndId,
+    cell: Cell,
+    key: SpatialSoundKey,
+    distance: f32,
+}
+
+// ----------------------------------------------
+// SpatialSoundsSystem
+// ----------------------------------------------
+
+// Looping positional sounds for buildings near the listener (construction sites,
+// fires, market chatter). Emitters are started and stopped automatically as
+// buildings enter/leave the audible range or change state.
+#[derive(Default, Serialize, Deserialize)]
+pub struct SpatialSoundsSystem {
+    #[serde(skip)]
+    emitters: Vec<SpatialEmitter>,
+}
+
+impl GameSystem for SpatialSoundsSystem {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn update(&mut self, engine: &mut Engine, _cmds: &mut SimCmds, context: &SimContext) {
+        self.update_emitters(engine.sound_system_mut(), context);
+    }
+
+    fn paused_update(&mut self, engine: &mut Engine, context: &SimContext) {
+        // Keep emitters in sync with the camera while paused.
+        self.update_emitters(engine.sound_system_mut(), context);
+    }
+
+    fn reset(&mut self, engine: &mut Engine) {
+        self.stop_all(engine.sound_system_mut());
+    }
+
+    fn post_load(&mut self, context: &mut PostLoadContext) {
+        // Building ids from before the load are stale. Next update restarts the emitters.
+        self.reset(context.engine_mut());
+    }
+
+    fn draw_debug_ui(&mut self, engine: &mut Engine, cmds: &mut SimCmds, context: &SimContext) {
+        // Debug-UI drawing lives in `crate::debug::systems`.
+        self.draw_debug_ui_dispatch(engine, cmds, context);
+    }
+}
+
+impl SpatialSoundsSystem {
+    #[inline]
+    pub(crate) fn is_enabled(&self) -> bool {
+        !GameConfigs::get().debug.disable_ambient_sounds
+    }
+
+    #[inline]
+    pub(crate) fn emitters(&self) -> &[SpatialEmitter] {
+        &self.emitters
+    }
+
+    pub(crate) fn stop_all(&mut self, sound_sys: &mut SoundSystem) {
+        for emitter in self.emitters.drain(..) {
+            sound_sys.stop(emitter.handle);
+        }
+    }
+
+    fn update_emitters(&mut self, sound_sys: &mut SoundSystem, context: &SimContext) {
+        if !self.is_enabled() {
+            self.stop_all(sound_sys);
+            return;
+        }
+
+        let mut candidates = Self::find_candidates(sound_sys, context);
+        let stop_distance = sound_sys.current_sound_settings().spatial_cutoff_distance * STOP_DISTANCE_SCALE;
+
+        // Stop emitters whose building is gone, changed sound, moved out of range or finished playing.
+        self.emitters.retain(|emitter| {
+            let keep = candidates.iter().any(|candidate| {
+                candidate.building == emitter.building && candidate.key == emitter.key && candidate.distance < stop_distance
+            }) && sound_sys.is_playing(emitter.handle);
+
+            if !keep {
+                sound_sys.stop(emitter.handle);
+            }
+            keep
+        });
+
+        // Start emitters for the closest audible buildings not already playing.
+        candidates.retain(|candidate| {
+            sound_sys.is_cell_audible(candidate.cell)
+                && !self.emitters.iter().any(|emitter| emitter.building == candidate.building)
+        });
+        candidates.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        for candidate in candidates {
+            if self.emitters.len() >= MAX_EMITTERS {
+                break;
+            }
+
+            // Events missing from the manifest (or whose files failed to load) never play.
+            let event_key = candidate.key.event_key();
+            if !sound_sys.has_sound_event(event_key) {
+                continue;
+            }
+
+            const LOOPING: bool = true;
+            let handle = sound_sys.play_event_at_cell(event_key, candidate.cell, LOOPING);
+
+            self.emitters.push(SpatialEmitter {
+                building: candidate.building,
+                cell: candidate.cell,
+                key: candidate.key,
+                handle,
+            });
+        }
+    }
+
+    fn find_candidates(sound_sys: &SoundSystem, context: &SimContext) -> Vec<EmitterCandidate> {
+        let mut candidates = Vec::new();
+        let raid_targets = Self::find_raid_targets(context);
+
+        for kinds in RandomEvents::BUILDING_KINDS {
+            context.world().for_each_building(kinds, |building| {
+                let is_being_raided = raid_targets.contains(&building.kind_and_id());
+                if let Some(key) = SpatialSoundKey::for_building(building, is_being_raided) {
+                    let cell = building.base_cell();
+                    candidates.push(EmitterCandidate {
+                        building: building.kind_and_id(),
+                        cell,
+                        key,
+                        distance: sound_sys.cell_listener_distance(cell),
+                    });
+                }
+                true
+            });
+        }
+
+        candidates
+    }
+
+    // Buildings raiders are sacking right now.
+    fn find_raid_targets(context: &SimContext) -> SmallVec<[BuildingKindAndId; 8]> {
+        let mut targets = SmallVec::new();
+
+        context.world().for_each_unit(|unit| {
+            if unit.is_hostile()
+                && let Some(raid) = unit.current_task_as::<UnitTaskRaid>(context.task_manager())
+                && raid.state == UnitTaskRaidState::Attacking
+                && let Some(target) = raid.target
+                && !targets.contains(&target)
+            {
+                targets.push(target);
+            }
+            true
+        });
+
+        targets
+    }
+}
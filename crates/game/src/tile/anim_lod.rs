@@ -0,0 +1,214 @@
+use common::{
+    Size,
+    coords::{Cell, CellRange},
+    hash::PreHashedKeyMap,
+    time::Seconds,
+};
+
+use super::TileAnimState;
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// At or below this camera zoom tiles are drawn small enough that
+// updating their anims every frame makes no visible difference.
+const ZOOMED_OUT_MAX_ZOOM: f32 = 0.75;
+const ZOOMED_OUT_UPDATE_INTERVAL: u32 = 2; // In frames.
+
+// Ring of cells around the visible range that keeps animating at a low rate,
+// so tiles scrolled into view don't all resume from the same stale frame.
+const OFFSCREEN_MARGIN_CELLS: i32 = 8;
+const OFFSCREEN_UPDATE_INTERVAL: u32 = 8; // In frames.
+
+// ----------------------------------------------
+// TileAnimLod
+// ----------------------------------------------
+
+// Decides which tiles get their anims updated each frame and with how much time.
+// Throttled tiers accumulate the skipped time, so anims play at the same speed,
+// just with coarser steps.
+#[derive(Default)]
+pub struct TileAnimLod {
+    frame_counter: u32,
+    visible_pending_secs: Seconds,
+    offscreen_pending_secs: Seconds,
+    batch: TileAnimBatch,
+    stats: TileAnimStats,
+}
+
+// Anim update passes to run this frame, with the delta time to apply to each.
+pub struct TileAnimLodPasses {
+    pub visible: Option<Seconds>,
+    pub offscreen: Option<(CellRange, Seconds)>, // Expanded range; cells inside the visible range are skipped.
+}
+
+impl TileAnimLod {
+    pub fn begin_frame(
+        &mut self,
+        visible_range: CellRange,
+        map_size_in_cells: Size,
+        zoom: f32,
+        delta_time_secs: Seconds,
+    ) -> TileAnimLodPasses {
+        self.frame_counter = self.frame_counter.wrapping_add(1);
+        self.visible_pending_secs += delta_time_secs;
+        self.offscreen_pending_secs += delta_time_secs;
+
+        let visible_interval = if zoom <= ZOOMED_OUT_MAX_ZOOM { ZOOMED_OUT_UPDATE_INTERVAL } else { 1 };
+        self.stats.begin_frame(visible_interval);
+
+        let visible = if self.frame_counter.is_multiple_of(visible_interval) {
+            Some(std::mem::take(&mut self.visible_pending_secs))
+        } else {
+            None
+        };
+
+        let offscreen = if self.frame_counter.is_multiple_of(OFFSCREEN_UPDATE_INTERVAL) {
+            let pending_secs = std::mem::take(&mut self.offscreen_pending_secs);
+            expand_range(visible_range, OFFSCREEN_MARGIN_CELLS, map_size_in_cells).map(|range| (range, pending_secs))
+        } else {
+            None
+        };
+
+        TileAnimLodPasses { visible, offscreen }
+    }
+
+    // Starts a pass where every tile advances by the same delta time.
+    #[inline]
+    pub fn begin_pass(&mut self) -> &mut TileAnimBatch {
+        self.batch.clear();
+        &mut self.batch
+    }
+
+    #[inline]
+    pub fn end_pass(&mut self, is_offscreen: bool) {
+        self.stats.add_pass(&self.batch, is_offscreen);
+    }
+
+    #[inline]
+    pub fn stats(&self) -> &TileAnimStats {
+        &self.stats
+    }
+}
+
+// ----------------------------------------------
+// TileAnimBatch
+// ----------------------------------------------
+
+// Tiles sharing the same TileDef, variation and anim state (e.g. a forest placed in one go)
+// all end up in the same next state, so within a pass it is computed once and reused.
+#[derive(Default)]
+pub struct TileAnimBatch {
+    results: PreHashedKeyMap<u64, (TileAnimBatchKey, TileAnimState)>,
+    tiles_updated: usize,
+    tiles_batched: usize,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub(super) struct TileAnimBatchKey {
+    pub tile_def_addr: usize,
+    pub variation_index: usize,
+    pub state: TileAnimState,
+}
+
+impl TileAnimBatchKey {
+    #[inline]
+    fn hash(&self) -> u64 {
+        // Cheap mix; collisions are resolved by comparing the full key.
+        let mut hash = self.tile_def_addr as u64;
+        hash = hash.wrapping_mul(0x9E3779B97F4A7C15) ^ self.variation_index as u64;
+        hash = hash.wrapping_mul(0x9E3779B97F4A7C15) ^ self.state.anim_set_index as u64;
+        hash = hash.wrapping_mul(0x9E3779B97F4A7C15) ^ self.state.frame_index as u64;
+        hash = hash.wrapping_mul(0x9E3779B97F4A7C15) ^ self.state.frame_play_time_secs.to_bits() as u64;
+        hash
+    }
+}
+
+impl TileAnimBatch {
+    // Returns the next anim state for `key`, computing it with `advance_fn` only if no tile
+    // with an identical state was updated earlier in this pass.
+    #[inline]
+    pub(super) fn advance<F>(&mut self, key: TileAnimBatchKey, advance_fn: F) -> TileAnimState
+    where
+        F: FnOnce() -> TileAnimState,
+    {
+        self.tiles_updated += 1;
+
+        let hash = key.hash();
+        if let Some((cached_key, cached_state)) = self.results.get(&hash)
+            && *cached_key == key
+        {
+            self.tiles_batched += 1;
+            return *cached_state;
+        }
+
+        let new_state = advance_fn();
+        self.results.insert(hash, (key, new_state));
+        new_state
+    }
+
+    #[inline]
+    fn clear(&mut self) {
+        self.results.clear();
+        self.tiles_updated = 0;
+        self.tiles_batched = 0;
+    }
+}
+
+// ----------------------------------------------
+// TileAnimStats
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Default)]
+pub struct TileAnimStats {
+    pub visible_update_interval: u32, // 1 = every frame.
+    pub tiles_updated: usize,
+    pub tiles_batched: usize, // Updates that reused the result of an identical anim state.
+    pub offscreen_tiles_updated: usize,
+
+    pub peak_tiles_updated: usize,
+    pub peak_tiles_batched: usize,
+    pub peak_offscreen_tiles_updated: usize,
+}
+
+impl TileAnimStats {
+    #[inline]
+    fn begin_frame(&mut self, visible_update_interval: u32) {
+        self.visible_update_interval = visible_update_interval;
+        self.tiles_updated = 0;
+        self.tiles_batched = 0;
+        self.offscreen_tiles_updated = 0;
+    }
+
+    fn add_pass(&mut self, batch: &TileAnimBatch, is_offscreen: bool) {
+        if is_offscreen {
+            self.offscreen_tiles_updated += batch.tiles_updated;
+        }
+
+        self.tiles_updated += batch.tiles_updated;
+        self.tiles_batched += batch.tiles_batched;
+
+        self.peak_tiles_updated = self.peak_tiles_updated.max(self.tiles_updated);
+        self.peak_tiles_batched = self.peak_tiles_batched.max(self.tiles_batched);
+        self.peak_offscreen_tiles_updated = self.peak_offscreen_tiles_updated.max(self.offscreen_tiles_updated);
+    }
+}
+
+// ----------------------------------------------
+// Internal helpers
+// ----------------------------------------------
+
+fn expand_range(range: CellRange, margin: i32, map_size_in_cells: Size) -> Option<CellRange> {
+    if !range.is_valid() || !map_size_in_cells.is_valid() {
+        return None;
+    }
+
+    let start = Cell::new((range.start.x - margin).max(0), (range.start.y - margin).max(0));
+    let end = Cell::new(
+        (range.end.x + margin).min(map_size_in_cells.width - 1),
+        (range.end.y + margin).min(map_size_in_cells.height - 1),
+    );
+
+    Some(CellRange::new(start, end))
+}
@@ -32,6 +32,43 @@ pub trait TextureAtlas {
     fn load_texture(&mut self, tex_cache: &mut TextureCache, texture_path: PathRef) -> TileTexInfo;
     fn commit_textures(&self, tex_cache: &mut TextureCache);
     fn save_textures_to_file(&self, base_path: PathRef);
+    fn page_infos(&self) -> Vec<AtlasPageInfo>;
+}
+
+// ----------------------------------------------
+// AtlasPageInfo
+// ----------------------------------------------
+
+// Summary of a packed atlas page, for the debug views.
+#[derive(Copy, Clone)]
+pub struct AtlasPageInfo {
+    pub texture: TextureHandle,
+    pub size: Size,
+    pub sprite_count: usize,
+    pub used_pixels: u64, // Sum of all sprite rects, excluding padding.
+}
+
+impl AtlasPageInfo {
+    fn new(texture: TextureHandle, size: Size, sprites: &[packer::AtlasSprite]) -> Self {
+        let used_pixels = sprites.iter().map(|sprite| {
+            let coords = &sprite.rect;
+            let width  = (coords.top_right().x - coords.top_left().x).abs() * size.width as f32;
+            let height = (coords.bottom_left().y - coords.top_left().y).abs() * size.height as f32;
+            (width.round() as u64) * (height.round() as u64)
+        });
+
+        Self { texture, size, sprite_count: sprites.len(), used_pixels: used_pixels.sum() }
+    }
+
+    // Fraction of the page area covered by sprites, [0,1].
+    #[inline]
+    pub fn occupancy(&self) -> f32 {
+        let total_pixels = (self.size.width as u64) * (self.size.height as u64);
+        if total_pixels == 0 {
+            return 0.0;
+        }
+        (self.used_pixels as f64 / total_pixels as f64) as f32
+    }
 }
 
 // ----------------------------------------------
@@ -74,6 +111,11 @@ impl TextureAtlas for PassthroughTextureAtlas {
 
     #[inline]
     fn save_textures_to_file(&self, _base_path: PathRef) {}
+
+    #[inline]
+    fn page_infos(&self) -> Vec<AtlasPageInfo> {
+        Vec::new() // No pages, one texture per sprite.
+    }
 }
 
 // ----------------------------------------------
@@ -145,6 +187,17 @@ impl TextureAtlas for RuntimePackedTextureAtlas {
     fn save_textures_to_file(&self, _base_path: PathRef) {
         // No-op on Web/WASM: atlas caching is a desktop optimization.
     }
+
+    fn page_infos(&self) -> Vec<AtlasPageInfo> {
+        self.packer
+            .pages()
+            .iter()
+            .map(|page| {
+                let page_size = Size::new(page.image.width() as i32, page.image.height() as i32);
+                AtlasPageInfo::new(page.texture, page_size, &page.sprites)
+            })
+            .collect()
+    }
 }
 
 // ----------------------------------------------
@@ -220,7 +273,11 @@ impl OfflinePackedTextureAtlas {
             // Drop it now so we can release some memory.
             page_images[page_index] = None;
 
-            pages.push(OfflinePackedAtlasPage { texture, sprites: sprite_metadata.sprites });
+            pages.push(OfflinePackedAtlasPage {
+                texture,
+                size: sprite_metadata.page_size,
+                sprites: sprite_metadata.sprites,
+            });
         }
 
         Self { layer, pages, mapping }
@@ -248,6 +305,10 @@ impl TextureAtlas for OfflinePackedTextureAtlas {
 
     #[inline]
     fn save_textures_to_file(&self, _base_path: PathRef) {}
+
+    fn page_infos(&self) -> Vec<AtlasPageInfo> {
+        self.pages.iter().map(|page| AtlasPageInfo::new(page.texture, page.size, &page.sprites)).collect()
+    }
 }
 
 // ----------------------------------------------
@@ -264,6 +325,7 @@ pub fn cached_packed_atlas_exists(layer: TileMapLayerKind) -> bool {
 
 struct OfflinePackedAtlasPage {
     texture: TextureHandle,
+    size: Size,
     sprites: Vec<packer::AtlasSprite>,
 }
 
@@ -285,7 +347,7 @@ fn load_cached_atlas_page_images(layer_name: &str, page_count: usize) -> Vec<Opt
     .collect()
 }
 
-fn load_image_file(base_path: PathRef, path: PathRef) -> Option<RgbaImage> {
+pub(super) fn load_image_file(base_path: PathRef, path: PathRef) -> Option<RgbaImage> {
     let absolute_path: FixedPath = base_path.join(path);
 
     match file_sys::load_bytes(&absolute_path) {
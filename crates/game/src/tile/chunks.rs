@@ -0,0 +1,349 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use smallvec::SmallVec;
+
+use common::{
+    Rect,
+    Size,
+    coords::{Cell, CellRange, IsoPointF32, MapRotation, WorldToScreenTransform},
+};
+use engine::render::{self, RenderSystem, StaticSprite, StaticSpriteMeshHandle};
+
+use super::{Tile, TileFlags, TileMap, TileMapLayerKind, streaming::TileTextureStreamer};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Width and height of a terrain chunk in map cells.
+pub const TERRAIN_CHUNK_SIZE: i32 = 16;
+
+// Road junctions and water shore transitions rewrite the variations of tiles
+// around an edit, so edits dirty the chunks of this many neighboring cells too.
+const DIRTY_CELL_MARGIN: i32 = 2;
+
+// ----------------------------------------------
+// TerrainChunkRevisions
+// ----------------------------------------------
+
+// Per-chunk edit counters, owned by the TileMap. Bumped whenever a terrain
+// tile in the chunk changes, so renderers know which cached meshes are stale.
+#[derive(Default)]
+pub struct TerrainChunkRevisions {
+    map_id: u32, // Unique per map instance, changes on reset/load.
+    size_in_chunks: Size,
+    revisions: Vec<u32>,
+}
+
+impl TerrainChunkRevisions {
+    pub fn new(map_size_in_cells: Size) -> Self {
+        static NEXT_MAP_ID: AtomicU32 = AtomicU32::new(1);
+
+        let to_chunks = |cells: i32| if cells > 0 { ((cells - 1) / TERRAIN_CHUNK_SIZE) + 1 } else { 0 };
+        let size_in_chunks = Size::new(to_chunks(map_size_in_cells.width), to_chunks(map_size_in_cells.height));
+
+        Self {
+            map_id: NEXT_MAP_ID.fetch_add(1, Ordering::Relaxed),
+            size_in_chunks,
+            revisions: vec![0; (size_in_chunks.width * size_in_chunks.height) as usize],
+        }
+    }
+
+    #[inline]
+    pub fn map_id(&self) -> u32 {
+        self.map_id
+    }
+
+    #[inline]
+    pub fn size_in_chunks(&self) -> Size {
+        self.size_in_chunks
+    }
+
+    #[inline]
+    pub fn chunk_count(&self) -> usize {
+        self.revisions.len()
+    }
+
+    #[inline]
+    pub fn chunk_index(&self, chunk: Cell) -> usize {
+        debug_assert!(chunk.x >= 0 && chunk.x < self.size_in_chunks.width);
+        debug_assert!(chunk.y >= 0 && chunk.y < self.size_in_chunks.height);
+        (chunk.x + (chunk.y * self.size_in_chunks.width)) as usize
+    }
+
+    #[inline]
+    pub fn revision(&self, chunk: Cell) -> u32 {
+        self.revisions[self.chunk_index(chunk)]
+    }
+
+    #[inline]
+    pub fn chunk_for_cell(cell: Cell) -> Cell {
+        Cell::new(cell.x.div_euclid(TERRAIN_CHUNK_SIZE), cell.y.div_euclid(TERRAIN_CHUNK_SIZE))
+    }
+
+    // Map cells covered by `chunk`, clamped to the map bounds.
+    pub fn chunk_cell_range(chunk: Cell, map_size_in_cells: Size) -> CellRange {
+        let start = Cell::new(chunk.x * TERRAIN_CHUNK_SIZE, chunk.y * TERRAIN_CHUNK_SIZE);
+        let end = Cell::new(
+            (start.x + TERRAIN_CHUNK_SIZE - 1).min(map_size_in_cells.width - 1),
+            (start.y + TERRAIN_CHUNK_SIZE - 1).min(map_size_in_cells.height - 1),
+        );
+        CellRange::new(start, end)
+    }
+
+    // Chunks overlapping `cells` (map cells), clamped to the map bounds.
+    // Returns None if the range does not touch the map.
+    pub fn chunk_range(&self, cells: CellRange) -> Option<CellRange> {
+        if self.revisions.is_empty() {
+            return None;
+        }
+
+        let start = Self::chunk_for_cell(cells.start);
+        let end = Self::chunk_for_cell(cells.end);
+
+        let max_x = self.size_in_chunks.width - 1;
+        let max_y = self.size_in_chunks.height - 1;
+
+        if end.x < 0 || end.y < 0 || start.x > max_x || start.y > max_y {
+            return None;
+        }
+
+        Some(CellRange::new(
+            Cell::new(start.x.max(0), start.y.max(0)),
+            Cell::new(end.x.min(max_x), end.y.min(max_y)),
+        ))
+    }
+
+    pub fn mark_dirty(&mut self, cells: CellRange) {
+        let expanded = CellRange::new(
+            Cell::new(cells.start.x - DIRTY_CELL_MARGIN, cells.start.y - DIRTY_CELL_MARGIN),
+            Cell::new(cells.end.x + DIRTY_CELL_MARGIN, cells.end.y + DIRTY_CELL_MARGIN),
+        );
+
+        if let Some(chunks) = self.chunk_range(expanded) {
+            for chunk in &chunks {
+                let index = self.chunk_index(chunk);
+                self.revisions[index] = self.revisions[index].wrapping_add(1);
+            }
+        }
+    }
+
+    pub fn mark_all_dirty(&mut self) {
+        for revision in &mut self.revisions {
+            *revision = revision.wrapping_add(1);
+        }
+    }
+}
+
+// ----------------------------------------------
+// TerrainChunkCache
+// ----------------------------------------------
+
+// Tile flags that change how a terrain tile renders without bumping the chunk revision.
+// Tiles with any of these set are drawn individually on top of the cached chunk mesh.
+pub const TERRAIN_CHUNK_OVERLAY_FLAGS: TileFlags = TileFlags::Highlighted
+    .union(TileFlags::Invalidated)
+    .union(TileFlags::DirtRoadPlacement)
+    .union(TileFlags::PavedRoadPlacement)
+    .union(TileFlags::DrawDebugInfo)
+    .union(TileFlags::DrawBlockerInfo)
+    .union(TileFlags::DrawDebugBounds);
+
+#[derive(Default)]
+struct TerrainChunk {
+    mesh: StaticSpriteMeshHandle,
+    built_revision: Option<u32>,
+    sprite_count: u32,
+
+    // Union of all sprite rects in iso world space, for culling.
+    iso_bounds: Rect,
+
+    // Tiles with streamed sprites are left out of the mesh while their
+    // texture may still change once loaded. These draw every frame.
+    dynamic_cells: SmallVec<[Cell; 4]>,
+}
+
+// Terrain sprites cached in static GPU meshes, one per chunk. Meshes are in iso world
+// space and the camera transform is applied on the GPU, so panning/zooming reuses them.
+// A chunk is rebuilt only when first drawn after its TerrainChunkRevisions counter changes.
+#[derive(Default)]
+pub struct TerrainChunkCache {
+    map_id: u32,
+    rotation: MapRotation,
+    texture_preload_done: bool,
+    chunks: Vec<TerrainChunk>,
+    scratch_sprites: Vec<StaticSprite>,
+    visible_chunks: Vec<(Cell, Cell)>, // (view cell sort key, chunk).
+}
+
+impl TerrainChunkCache {
+    // Releases all cached meshes.
+    pub fn clear(&mut self, render_sys: &mut RenderSystem) {
+        for chunk in &mut self.chunks {
+            if chunk.mesh.is_valid() {
+                render_sys.release_static_sprite_mesh(chunk.mesh);
+            }
+        }
+        self.chunks.clear();
+    }
+
+    // Draws the cached meshes of all chunks overlapping `visible_range` (view cells), rebuilding
+    // stale ones first. `visit_chunk_fn` gets each chunk's map cells in back to front order, plus
+    // the cells excluded from its mesh, so the caller can draw those and any overlay tiles on top.
+    // Returns (chunks drawn, chunks rebuilt, sprites drawn).
+    pub fn draw(
+        &mut self,
+        render_sys: &mut RenderSystem,
+        tile_map: &TileMap,
+        transform: WorldToScreenTransform,
+        visible_range: CellRange,
+        mut visit_chunk_fn: impl FnMut(&mut RenderSystem, CellRange, &[Cell]),
+    ) -> (u32, u32, u32) {
+        self.sync_with_map(render_sys, tile_map);
+
+        let revisions = tile_map.terrain_chunk_revisions();
+        let map_size = tile_map.size_in_cells();
+        let rotation = tile_map.rotation();
+
+        let Some(chunk_range) = revisions.chunk_range(rotation.unrotate_cell_range(visible_range, map_size)) else {
+            return (0, 0, 0);
+        };
+
+        // Same back to front order as the per-tile path (CellRange::iter_rev() over view cells).
+        self.visible_chunks.clear();
+        for chunk in &chunk_range {
+            let chunk_cells = TerrainChunkRevisions::chunk_cell_range(chunk, map_size);
+            let view_start = rotation.rotate_cell_range(chunk_cells, map_size).start;
+            self.visible_chunks.push((view_start, chunk));
+        }
+        self.visible_chunks.sort_unstable_by(|(a, _), (b, _)| b.y.cmp(&a.y).then(b.x.cmp(&a.x)));
+
+        let viewport = render_sys.viewport();
+        let (mut chunks_drawn, mut chunks_rebuilt, mut sprites_drawn) = (0, 0, 0);
+
+        for i in 0..self.visible_chunks.len() {
+            let chunk = self.visible_chunks[i].1;
+            let index = revisions.chunk_index(chunk);
+            let revision = revisions.revision(chunk);
+
+            if self.chunks[index].built_revision != Some(revision) {
+                self.rebuild_chunk(render_sys, tile_map, chunk, index, revision);
+                chunks_rebuilt += 1;
+            }
+
+            let cached = &self.chunks[index];
+
+            let screen_bounds = Rect::from_extents(
+                transform.apply_to_iso_point(IsoPointF32(cached.iso_bounds.min)),
+                transform.apply_to_iso_point(IsoPointF32(cached.iso_bounds.max)),
+            );
+
+            if cached.sprite_count != 0 && !render::is_rect_fully_offscreen(&viewport, &screen_bounds) {
+                render_sys.draw_static_sprite_mesh(cached.mesh, transform);
+                chunks_drawn += 1;
+                sprites_drawn += cached.sprite_count;
+            }
+
+            visit_chunk_fn(render_sys, TerrainChunkRevisions::chunk_cell_range(chunk, map_size), &cached.dynamic_cells);
+        }
+
+        (chunks_drawn, chunks_rebuilt, sprites_drawn)
+    }
+
+    fn sync_with_map(&mut self, render_sys: &mut RenderSystem, tile_map: &TileMap) {
+        let revisions = tile_map.terrain_chunk_revisions();
+
+        if self.map_id != revisions.map_id()
+            || self.rotation != tile_map.rotation()
+            || self.chunks.len() != revisions.chunk_count()
+        {
+            self.clear(render_sys);
+            self.chunks.resize_with(revisions.chunk_count(), TerrainChunk::default);
+            self.map_id = revisions.map_id();
+            self.rotation = tile_map.rotation();
+        }
+
+        // Rebuild everything once background texture loading completes, so
+        // tiles drawn with placeholders until then get baked into the meshes.
+        let texture_preload_done = TileTextureStreamer::get().preload_progress().is_done();
+        if texture_preload_done && !self.texture_preload_done {
+            for chunk in &mut self.chunks {
+                chunk.built_revision = None;
+            }
+        }
+        self.texture_preload_done = texture_preload_done;
+    }
+
+    fn rebuild_chunk(
+        &mut self,
+        render_sys: &mut RenderSystem,
+        tile_map: &TileMap,
+        chunk: Cell,
+        index: usize,
+        revision: u32,
+    ) {
+        let map_size = tile_map.size_in_cells();
+        let rotation = tile_map.rotation();
+        let terrain = tile_map.layer(TileMapLayerKind::Terrain);
+
+        let cached = &mut self.chunks[index];
+        cached.dynamic_cells.clear();
+        self.scratch_sprites.clear();
+
+        let mut iso_bounds: Option<Rect> = None;
+
+        let chunk_cells = TerrainChunkRevisions::chunk_cell_range(chunk, map_size);
+        for view_cell in rotation.rotate_cell_range(chunk_cells, map_size).iter_rev() {
+            let cell = tile_map.unrotate_cell(view_cell);
+            let Some(tile) = terrain.try_tile(cell) else {
+                continue;
+            };
+
+            if tile.has_flags(TileFlags::Hidden) {
+                continue;
+            }
+
+            let Some(sprite) = tile.view_anim_frame(rotation) else {
+                continue;
+            };
+
+            if sprite.has_transient_texture() {
+                cached.dynamic_cells.push(cell);
+                continue;
+            }
+
+            let tex_info = sprite.resolve_tex_info();
+            let rect = Self::iso_rect(tile, tile_map);
+
+            iso_bounds = Some(match iso_bounds {
+                Some(bounds) => Rect::from_points(&[bounds.min, bounds.max, rect.min, rect.max]),
+                None => rect,
+            });
+
+            self.scratch_sprites.push(StaticSprite {
+                rect,
+                tex_coords: tex_info.coords,
+                texture: tex_info.texture,
+                color: tile.tint_color(),
+                palette: tile.palette_swap(),
+            });
+        }
+
+        if !cached.mesh.is_valid() && !self.scratch_sprites.is_empty() {
+            cached.mesh = render_sys.new_static_sprite_mesh();
+        }
+
+        if cached.mesh.is_valid() {
+            render_sys.update_static_sprite_mesh(cached.mesh, &self.scratch_sprites);
+        }
+
+        cached.built_revision = Some(revision);
+        cached.sprite_count = self.scratch_sprites.len() as u32;
+        cached.iso_bounds = iso_bounds.unwrap_or_default();
+    }
+
+    // Sprite rect with the identity transform, i.e. in iso world space.
+    #[inline]
+    fn iso_rect(tile: &Tile, tile_map: &TileMap) -> Rect {
+        tile_map.tile_view_screen_rect(tile, WorldToScreenTransform::default())
+    }
+}
@@ -0,0 +1,163 @@
+use common::Color;
+use serde::{Deserialize, Serialize};
+use strum::{Display, VariantArray};
+
+use super::rendering::{
+    HIGHLIGHT_GRID_COLOR,
+    HIGHLIGHT_OUTLINE_COLOR,
+    HIGHLIGHT_TILE_COLOR,
+    INVALID_GRID_COLOR,
+    INVALID_OUTLINE_COLOR,
+    INVALID_TILE_COLOR,
+    SELECTED_OUTLINE_COLOR,
+    SELECTED_TILE_COLOR,
+    TileMapRenderFlags,
+};
+use crate::config::GameConfigs;
+
+// ----------------------------------------------
+// Color-blind safe colors
+// ----------------------------------------------
+
+// From the Okabe-Ito palette, distinguishable with all common forms of color blindness.
+const CB_BLUE:       Color = Color::new(0.0,  0.45, 0.70, 1.0);
+const CB_SKY_BLUE:   Color = Color::new(0.34, 0.71, 0.91, 1.0);
+const CB_YELLOW:     Color = Color::new(0.94, 0.89, 0.26, 1.0);
+const CB_ORANGE:     Color = Color::new(0.90, 0.62, 0.0,  1.0);
+const CB_VERMILLION: Color = Color::new(0.84, 0.37, 0.0,  1.0);
+
+const CB_HIGHLIGHT_TILE_COLOR: Color = Color::new(0.65, 0.85, 0.98, 1.0); // light blue
+const CB_INVALID_TILE_COLOR:   Color = Color::new(0.98, 0.75, 0.45, 1.0); // light orange
+const CB_SELECTED_TILE_COLOR:  Color = Color::new(0.98, 0.95, 0.60, 1.0); // light yellow
+
+// ----------------------------------------------
+// ColorPalette
+// ----------------------------------------------
+
+// Colors used to convey state on the map: placement highlights, heatmap
+// overlays and minimap overlays. Selected in the Graphics settings.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Display, VariantArray, Serialize, Deserialize)]
+pub enum ColorPalette {
+    #[default]
+    Standard,
+
+    // Avoids relying on red vs. green. Blue/orange for good/bad and viridis-like heatmap ramps.
+    #[strum(to_string = "Color-Blind Safe")]
+    ColorBlindSafe,
+}
+
+impl ColorPalette {
+    #[inline]
+    pub fn current() -> Self {
+        GameConfigs::get().color_palette
+    }
+
+    #[inline]
+    pub fn from_render_flags(flags: TileMapRenderFlags) -> Self {
+        if flags.contains(TileMapRenderFlags::ColorBlindPalette) { Self::ColorBlindSafe } else { Self::Standard }
+    }
+
+    // ----------------------
+    // Overlay status colors:
+    // ----------------------
+
+    #[inline]
+    pub const fn good(self) -> Color {
+        match self {
+            Self::Standard       => Color::green(),
+            Self::ColorBlindSafe => CB_BLUE,
+        }
+    }
+
+    #[inline]
+    pub const fn fair(self) -> Color {
+        match self {
+            Self::Standard       => Color::yellow(),
+            Self::ColorBlindSafe => CB_YELLOW,
+        }
+    }
+
+    #[inline]
+    pub const fn bad(self) -> Color {
+        match self {
+            Self::Standard       => Color::red(),
+            Self::ColorBlindSafe => CB_VERMILLION,
+        }
+    }
+
+    #[inline]
+    pub const fn water(self) -> Color {
+        match self {
+            Self::Standard       => Color::blue(),
+            Self::ColorBlindSafe => CB_SKY_BLUE,
+        }
+    }
+
+    // ----------------------
+    // Placement highlights:
+    // ----------------------
+
+    #[inline]
+    pub const fn highlight_tile_color(self) -> Color {
+        match self {
+            Self::Standard       => HIGHLIGHT_TILE_COLOR,
+            Self::ColorBlindSafe => CB_HIGHLIGHT_TILE_COLOR,
+        }
+    }
+
+    #[inline]
+    pub const fn invalid_tile_color(self) -> Color {
+        match self {
+            Self::Standard       => INVALID_TILE_COLOR,
+            Self::ColorBlindSafe => CB_INVALID_TILE_COLOR,
+        }
+    }
+
+    #[inline]
+    pub const fn highlight_outline_color(self) -> Color {
+        match self {
+            Self::Standard       => HIGHLIGHT_OUTLINE_COLOR,
+            Self::ColorBlindSafe => CB_SKY_BLUE,
+        }
+    }
+
+    #[inline]
+    pub const fn invalid_outline_color(self) -> Color {
+        match self {
+            Self::Standard       => INVALID_OUTLINE_COLOR,
+            Self::ColorBlindSafe => CB_ORANGE,
+        }
+    }
+
+    #[inline]
+    pub const fn selected_tile_color(self) -> Color {
+        match self {
+            Self::Standard       => SELECTED_TILE_COLOR,
+            Self::ColorBlindSafe => CB_SELECTED_TILE_COLOR,
+        }
+    }
+
+    #[inline]
+    pub const fn selected_outline_color(self) -> Color {
+        match self {
+            Self::Standard       => SELECTED_OUTLINE_COLOR,
+            Self::ColorBlindSafe => CB_YELLOW,
+        }
+    }
+
+    #[inline]
+    pub const fn highlight_grid_color(self) -> Color {
+        match self {
+            Self::Standard       => HIGHLIGHT_GRID_COLOR,
+            Self::ColorBlindSafe => CB_SKY_BLUE,
+        }
+    }
+
+    #[inline]
+    pub const fn invalid_grid_color(self) -> Color {
+        match self {
+            Self::Standard       => INVALID_GRID_COLOR,
+            Self::ColorBlindSafe => CB_ORANGE,
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use common::coords::Cell;
+
+use crate::tile::{
+    TileFlags,
+    TileMap,
+    TileMapLayerKind,
+    road,
+    sets::{PresetTiles, TileDef},
+};
+
+// ----------------------------------------------
+// WallSegment
+// ----------------------------------------------
+
+// Stretch of wall placed in one drag stroke. Gates and towers are placed one at a
+// time afterwards; towers need to be next to a wall (see the tile def `required_proximity`).
+#[derive(Default)]
+pub struct WallSegment {
+    pub path: Vec<Cell>,
+    pub is_valid: bool,
+}
+
+impl WallSegment {
+    #[inline]
+    pub fn clear(&mut self) {
+        self.path.clear();
+        self.is_valid = false;
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
+    #[inline]
+    pub fn cost(&self) -> u32 {
+        self.tile_def().map_or(0, |tile_def| (self.path.len() as u32) * tile_def.cost)
+    }
+
+    #[inline]
+    pub fn tile_def(&self) -> Option<&'static TileDef> {
+        PresetTiles::StoneWall.find_tile_def()
+    }
+}
+
+// ----------------------------------------------
+// Wall Placement API
+// ----------------------------------------------
+
+#[inline]
+pub fn is_wall_tile_def(tile_def: &TileDef) -> bool {
+    tile_def.hash == PresetTiles::StoneWall.hash()
+}
+
+// Walls go over empty land or existing wall sections. They never replace towers, gates or roads.
+fn can_place_wall(tile_map: &TileMap, cell: Cell) -> bool {
+    if !tile_map.is_cell_within_bounds(cell) {
+        return false;
+    }
+
+    if tile_map.try_tile_from_layer(cell, TileMapLayerKind::Objects).is_some() {
+        return false;
+    }
+
+    if let Some(tile) = tile_map.try_tile_from_layer(cell, TileMapLayerKind::Terrain) {
+        let path_kind = tile.path_kind();
+        if !path_kind.is_empty_land() && !(path_kind.is_wall() && is_wall_tile_def(tile.tile_def())) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn is_path_valid(tile_map: &TileMap, path: &[Cell]) -> bool {
+    path.iter().all(|cell| can_place_wall(tile_map, *cell))
+}
+
+// Walls are always straight lines or L shapes, never zigzags, so they can close off an area.
+pub fn build_segment(tile_map: &TileMap, start: Cell, end: Cell) -> WallSegment {
+    let hv_path = road::horizontal_vertical_path(start, end);
+    if is_path_valid(tile_map, &hv_path) {
+        return WallSegment { path: hv_path, is_valid: true };
+    }
+
+    let vh_path = road::vertical_horizontal_path(start, end);
+    if is_path_valid(tile_map, &vh_path) {
+        return WallSegment { path: vh_path, is_valid: true };
+    }
+
+    WallSegment { path: hv_path, is_valid: false }
+}
+
+pub fn mark_tiles(tile_map: &mut TileMap, segment: &WallSegment, highlight: bool, valid_placement: bool) {
+    for cell in &segment.path {
+        if let Some(tile) = tile_map.try_tile_from_layer_mut(*cell, TileMapLayerKind::Terrain) {
+            if !highlight {
+                tile.set_flags(TileFlags::Highlighted | TileFlags::Invalidated, false);
+            } else if valid_placement {
+                tile.set_flags(TileFlags::Highlighted, true);
+            } else {
+                tile.set_flags(TileFlags::Invalidated, true);
+            }
+        }
+    }
+}
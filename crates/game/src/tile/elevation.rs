@@ -0,0 +1,227 @@
+use common::{
+    Vec2,
+    constants::HALF_BASE_TILE_HEIGHT_F32,
+    coords::{Cell, CellRange},
+    format_small,
+    hash,
+};
+
+use super::{TileMap, TileMapLayerKind, sets::TileDef};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Height of a terrain tile in elevation steps. Flat ground is 0.
+pub type TerrainElevation = u8;
+
+pub const MAX_TERRAIN_ELEVATION: TerrainElevation = 4;
+
+// Screen-space rise of one elevation step at 1x zoom.
+pub const ELEVATION_STEP_HEIGHT: f32 = HALF_BASE_TILE_HEIGHT_F32;
+
+// Units walk up or down slopes of this many steps between neighboring cells.
+// Anything steeper is a cliff and blocks movement.
+pub const MAX_WALKABLE_ELEVATION_STEP: TerrainElevation = 1;
+
+// Cells around the brush center affected by a stroke (1 = 3x3 cells).
+pub const ELEVATION_BRUSH_RADIUS: i32 = 1;
+
+#[inline]
+pub fn is_steep(a: TerrainElevation, b: TerrainElevation) -> bool {
+    a.abs_diff(b) > MAX_WALKABLE_ELEVATION_STEP
+}
+
+// Offset applied to the screen rect of tiles standing on elevated terrain.
+#[inline]
+pub fn screen_offset(elevation: TerrainElevation, scaling: f32) -> Vec2 {
+    Vec2::new(0.0, -(elevation as f32) * ELEVATION_STEP_HEIGHT * scaling)
+}
+
+// ----------------------------------------------
+// ElevationBrush
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum ElevationBrush {
+    Raise,
+    Lower,
+}
+
+impl ElevationBrush {
+    // Level a stroke starting over a cell at `elevation` brings the brushed area to.
+    #[inline]
+    pub fn stroke_target(self, elevation: TerrainElevation) -> TerrainElevation {
+        match self {
+            Self::Raise => (elevation + 1).min(MAX_TERRAIN_ELEVATION),
+            Self::Lower => elevation.saturating_sub(1),
+        }
+    }
+
+    #[inline]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Raise => "Raise Terrain",
+            Self::Lower => "Lower Terrain",
+        }
+    }
+}
+
+// Only bare land can be raised or lowered. Water, roads, walls, vacant
+// lots and anything under an object keep their current elevation.
+pub fn can_edit_elevation(tile_map: &TileMap, cell: Cell) -> bool {
+    if tile_map.try_tile_from_layer(cell, TileMapLayerKind::Objects).is_some() {
+        return false;
+    }
+
+    tile_map.try_tile_from_layer(cell, TileMapLayerKind::Terrain).is_some_and(|tile| {
+        let path_kind = tile.path_kind();
+        path_kind.is_empty_land() && !path_kind.is_vacant_lot()
+    })
+}
+
+// Moves the cells within `radius` of `center` towards `target`. Cells already past the target
+// are left alone, so dragging a stroke builds a level plateau (or basin) instead of a spike.
+// Returns true if any cell changed.
+pub fn paint(tile_map: &mut TileMap, center: Cell, radius: i32, brush: ElevationBrush, target: TerrainElevation) -> bool {
+    debug_assert!(radius >= 0);
+
+    let brush_cells =
+        CellRange::new(Cell::new(center.x - radius, center.y - radius), Cell::new(center.x + radius, center.y + radius));
+
+    let mut changed_any = false;
+
+    for cell in &brush_cells {
+        if !can_edit_elevation(tile_map, cell) {
+            continue;
+        }
+
+        let elevation = tile_map.terrain_elevation(cell);
+        let new_elevation = match brush {
+            ElevationBrush::Raise => elevation.max(target),
+            ElevationBrush::Lower => elevation.min(target),
+        };
+
+        changed_any |= tile_map.set_terrain_elevation(cell, new_elevation);
+    }
+
+    if changed_any {
+        update_transitions_in_range(tile_map, brush_cells);
+    }
+
+    changed_any
+}
+
+// ----------------------------------------------
+// Cliff / Slope Tile Transitions
+// ----------------------------------------------
+
+// Each bit represents whether the terrain drops in that direction
+// relative to the current tile. Same layout as the water transitions.
+const WEST_BIT:  usize = 1 << 0; // 0001
+const SOUTH_BIT: usize = 1 << 1; // 0010
+const EAST_BIT:  usize = 1 << 2; // 0100
+const NORTH_BIT: usize = 1 << 3; // 1000
+
+// Variation name suffix for each transition mask, e.g. "slope_ne" or "cliff_s".
+const TRANSITION_SUFFIXES: [&str; 16] = [
+    "",     // 0000 — level with all neighbors
+    "w",    // 0001
+    "s",    // 0010
+    "sw",   // 0011
+    "e",    // 0100
+    "ew",   // 0101
+    "es",   // 0110
+    "esw",  // 0111
+    "n",    // 1000
+    "nw",   // 1001
+    "ns",   // 1010
+    "nsw",  // 1011
+    "ne",   // 1100
+    "new",  // 1101
+    "nes",  // 1110
+    "nesw", // 1111
+];
+
+const SLOPE_VARIATION_PREFIX: &str = "slope_";
+const CLIFF_VARIATION_PREFIX: &str = "cliff_";
+
+// Level terrain, or no variation for the transition.
+const FLAT_VARIATION: usize = 0;
+
+// Terrain tiles opt into elevation auto-tiling by providing slope/cliff variations.
+// Roads and water manage their own variations and are left alone.
+fn has_elevation_variations(tile_def: &TileDef) -> bool {
+    tile_def.variations.iter().any(|variation| {
+        variation.name.starts_with(SLOPE_VARIATION_PREFIX) || variation.name.starts_with(CLIFF_VARIATION_PREFIX)
+    })
+}
+
+// Mask of the sides where the terrain drops, plus whether any of them is a cliff.
+fn compute_elevation_transitions(tile_map: &TileMap, cell: Cell) -> (usize, bool) {
+    let elevation = tile_map.terrain_elevation(cell);
+
+    let neighbors = [
+        (NORTH_BIT, Cell::new(cell.x + 1, cell.y)),
+        (SOUTH_BIT, Cell::new(cell.x - 1, cell.y)),
+        (EAST_BIT,  Cell::new(cell.x, cell.y - 1)),
+        (WEST_BIT,  Cell::new(cell.x, cell.y + 1)),
+    ];
+
+    let mut mask = 0;
+    let mut is_cliff = false;
+
+    for (bit, neighbor) in neighbors {
+        // Map edges count as level ground.
+        if !tile_map.is_cell_within_bounds(neighbor) {
+            continue;
+        }
+
+        let neighbor_elevation = tile_map.terrain_elevation(neighbor);
+        if neighbor_elevation < elevation {
+            mask |= bit;
+            is_cliff |= is_steep(elevation, neighbor_elevation);
+        }
+    }
+
+    (mask, is_cliff)
+}
+
+fn update_tile_transitions(tile_map: &mut TileMap, cell: Cell) {
+    let (mask, is_cliff) = compute_elevation_transitions(tile_map, cell);
+
+    if let Some(tile) = tile_map.try_tile_from_layer_mut(cell, TileMapLayerKind::Terrain) {
+        let tile_def = tile.tile_def();
+        if !has_elevation_variations(tile_def) {
+            return;
+        }
+
+        let variation_index = if mask != 0 {
+            let prefix = if is_cliff { CLIFF_VARIATION_PREFIX } else { SLOPE_VARIATION_PREFIX };
+            let variation_name = format_small!("{prefix}{}", TRANSITION_SUFFIXES[mask]);
+            // No sprite for this combination; fall back to level terrain.
+            tile_def.find_variation_index(hash::fnv1a_from_str(&variation_name)).unwrap_or(FLAT_VARIATION)
+        } else {
+            FLAT_VARIATION
+        };
+
+        tile.set_variation_index(variation_index);
+    }
+}
+
+fn update_transitions_in_range(tile_map: &mut TileMap, cells: CellRange) {
+    // Include the surrounding ring, since the neighbors see a new drop on their side too.
+    let expanded_range =
+        CellRange::new(Cell::new(cells.start.x - 1, cells.start.y - 1), Cell::new(cells.end.x + 1, cells.end.y + 1));
+
+    for cell in &expanded_range {
+        if tile_map.is_cell_within_bounds(cell) {
+            update_tile_transitions(tile_map, cell);
+        }
+    }
+}
+
+// Picks the slope/cliff variation for the tile at `cell` and its neighbors.
+pub fn update_transitions(tile_map: &mut TileMap, cell: Cell) {
+    update_transitions_in_range(tile_map, CellRange::new(cell, cell));
+}
@@ -0,0 +1,325 @@
+use common::{
+    Color,
+    Size,
+    coords::Cell,
+};
+use engine::log;
+
+use super::color_palette::ColorPalette;
+
+// ----------------------------------------------
+// HeatmapGradient
+// ----------------------------------------------
+
+// Maps a normalized [0,1] heatmap value to a color, interpolating linearly between stops.
+#[derive(Clone)]
+pub struct HeatmapGradient {
+    stops: Vec<(f32, Color)>, // Sorted by position in [0,1].
+    color_blind_stops: Vec<(f32, Color)>, // Used with ColorPalette::ColorBlindSafe. Empty to use `stops`.
+}
+
+impl HeatmapGradient {
+    pub fn new(stops: Vec<(f32, Color)>) -> Self {
+        debug_assert!(!stops.is_empty(), "Heatmap gradient needs at least one color stop!");
+        Self { stops: Self::sorted(stops), color_blind_stops: Vec::new() }
+    }
+
+    pub fn with_color_blind_stops(mut self, stops: Vec<(f32, Color)>) -> Self {
+        self.color_blind_stops = Self::sorted(stops);
+        self
+    }
+
+    // Blue (low) -> green -> yellow -> red (high).
+    // Color-blind safe: viridis (dark purple -> blue -> green -> yellow).
+    pub fn cold_to_hot() -> Self {
+        Self::new(vec![
+            (0.0,  Color::blue()),
+            (0.33, Color::green()),
+            (0.66, Color::yellow()),
+            (1.0,  Color::red()),
+        ])
+        .with_color_blind_stops(vec![
+            (0.0,  Color::new(0.27, 0.00, 0.33, 1.0)),
+            (0.33, Color::new(0.19, 0.41, 0.56, 1.0)),
+            (0.66, Color::new(0.21, 0.72, 0.47, 1.0)),
+            (1.0,  Color::new(0.99, 0.91, 0.14, 1.0)),
+        ])
+    }
+
+    // Red (low) -> yellow -> green (high). Good for coverage/desirability style layers.
+    // Color-blind safe: the palette's bad -> fair -> good colors (vermillion -> yellow -> blue).
+    pub fn bad_to_good() -> Self {
+        let palette = ColorPalette::ColorBlindSafe;
+        Self::new(vec![
+            (0.0, Color::red()),
+            (0.5, Color::yellow()),
+            (1.0, Color::green()),
+        ])
+        .with_color_blind_stops(vec![
+            (0.0, palette.bad()),
+            (0.5, palette.fair()),
+            (1.0, palette.good()),
+        ])
+    }
+
+    pub fn sample(&self, palette: ColorPalette, value: f32) -> Color {
+        let value = value.clamp(0.0, 1.0);
+
+        let stops = if palette == ColorPalette::ColorBlindSafe && !self.color_blind_stops.is_empty() {
+            &self.color_blind_stops
+        } else {
+            &self.stops
+        };
+
+        let (first_pos, first_color) = stops[0];
+        if value <= first_pos {
+            return first_color;
+        }
+
+        for pair in stops.windows(2) {
+            let (from_pos, from_color) = pair[0];
+            let (to_pos, to_color) = pair[1];
+
+            if value <= to_pos {
+                let t = if to_pos > from_pos { (value - from_pos) / (to_pos - from_pos) } else { 1.0 };
+                return Color::new(
+                    from_color.r + (to_color.r - from_color.r) * t,
+                    from_color.g + (to_color.g - from_color.g) * t,
+                    from_color.b + (to_color.b - from_color.b) * t,
+                    from_color.a + (to_color.a - from_color.a) * t,
+                );
+            }
+        }
+
+        stops.last().unwrap().1
+    }
+
+    fn sorted(mut stops: Vec<(f32, Color)>) -> Vec<(f32, Color)> {
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        stops
+    }
+}
+
+// ----------------------------------------------
+// HeatmapGrid
+// ----------------------------------------------
+
+// Backing grid of per-cell values, filled in by the owning system.
+#[derive(Clone, Default)]
+pub struct HeatmapGrid {
+    size: Size,
+    values: Vec<f32>,
+}
+
+impl HeatmapGrid {
+    pub fn new(size: Size) -> Self {
+        debug_assert!(size.is_valid());
+        Self { size, values: vec![0.0; (size.width * size.height) as usize] }
+    }
+
+    #[inline]
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    #[inline]
+    pub fn value_at(&self, cell: Cell) -> f32 {
+        self.index(cell).map_or(0.0, |index| self.values[index])
+    }
+
+    #[inline]
+    pub fn set_value_at(&mut self, cell: Cell, value: f32) {
+        if let Some(index) = self.index(cell) {
+            self.values[index] = value;
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.fill(0.0);
+    }
+
+    // Row-major, one value per cell.
+    #[inline]
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut [f32] {
+        &mut self.values
+    }
+
+    #[inline]
+    fn index(&self, cell: Cell) -> Option<usize> {
+        if cell.x < 0 || cell.y < 0 || cell.x >= self.size.width || cell.y >= self.size.height {
+            return None;
+        }
+        Some((cell.x + cell.y * self.size.width) as usize)
+    }
+}
+
+// ----------------------------------------------
+// HeatmapSource
+// ----------------------------------------------
+
+pub type HeatmapValueFn = Box<dyn Fn(Cell) -> f32>;
+
+// Where a heatmap layer gets its values from. Values are expected in [0,1].
+pub enum HeatmapSource {
+    // Evaluated on demand for each visible cell.
+    Function(HeatmapValueFn),
+    // Updated by the owning system via `HeatmapOverlays::update_grid()`.
+    Grid(HeatmapGrid),
+}
+
+impl HeatmapSource {
+    #[inline]
+    pub fn value_at(&self, cell: Cell) -> f32 {
+        match self {
+            Self::Function(value_fn) => value_fn(cell),
+            Self::Grid(grid) => grid.value_at(cell),
+        }
+    }
+}
+
+// ----------------------------------------------
+// HeatmapLayer
+// ----------------------------------------------
+
+pub struct HeatmapLayer {
+    pub name: &'static str,
+    pub gradient: HeatmapGradient,
+    pub source: HeatmapSource,
+    // Cells at or above this value are hatched when hatch patterns are enabled.
+    pub danger_threshold: Option<f32>,
+}
+
+// ----------------------------------------------
+// HeatmapOverlays
+// ----------------------------------------------
+
+// Registry of named heatmap layers. Any system (or mod) can register a layer and it
+// will be listed in the overlay picker automatically; the tile map renderer draws
+// whichever layer is active, so new overlays need no renderer changes.
+pub struct HeatmapOverlays {
+    layers: Vec<HeatmapLayer>,
+    active_layer: Option<usize>,
+    opacity: f32,
+}
+
+impl HeatmapOverlays {
+    const fn new() -> Self {
+        Self { layers: Vec::new(), active_layer: None, opacity: 0.5 }
+    }
+
+    // Registers a new layer or replaces an existing one with the same name.
+    pub fn register(&mut self, name: &'static str, gradient: HeatmapGradient, source: HeatmapSource) {
+        let layer = HeatmapLayer { name, gradient, source, danger_threshold: None };
+
+        if let Some(index) = self.find_index(name) {
+            self.layers[index] = layer;
+        } else {
+            log::info!(log::channel!("heatmap"), "Registered heatmap layer '{name}'.");
+            self.layers.push(layer);
+        }
+    }
+
+    pub fn register_fn<F>(&mut self, name: &'static str, gradient: HeatmapGradient, value_fn: F)
+    where
+        F: Fn(Cell) -> f32 + 'static,
+    {
+        self.register(name, gradient, HeatmapSource::Function(Box::new(value_fn)));
+    }
+
+    pub fn register_grid(&mut self, name: &'static str, gradient: HeatmapGradient) {
+        self.register(name, gradient, HeatmapSource::Grid(HeatmapGrid::default()));
+    }
+
+    pub fn unregister(&mut self, name: &str) {
+        if let Some(index) = self.find_index(name) {
+            self.layers.remove(index);
+
+            self.active_layer = match self.active_layer {
+                Some(active) if active == index => None,
+                Some(active) if active > index => Some(active - 1),
+                active => active,
+            };
+        }
+    }
+
+    // Marks values at or above `threshold` as dangerous, e.g. high fire risk.
+    pub fn set_danger_threshold(&mut self, name: &str, threshold: f32) {
+        if let Some(index) = self.find_index(name) {
+            self.layers[index].danger_threshold = Some(threshold);
+        }
+    }
+
+    // Gives mutable access to a grid layer's values, (re)allocating the grid if the map size changed.
+    // Does nothing if the layer is not registered or is not grid based.
+    pub fn update_grid<F>(&mut self, name: &str, map_size_in_cells: Size, update_fn: F)
+    where
+        F: FnOnce(&mut HeatmapGrid),
+    {
+        let Some(index) = self.find_index(name) else {
+            return;
+        };
+
+        if let HeatmapSource::Grid(grid) = &mut self.layers[index].source {
+            if grid.size() != map_size_in_cells {
+                *grid = HeatmapGrid::new(map_size_in_cells);
+            }
+            update_fn(grid);
+        }
+    }
+
+    #[inline]
+    pub fn layers(&self) -> &[HeatmapLayer] {
+        &self.layers
+    }
+
+    #[inline]
+    pub fn active_layer(&self) -> Option<&HeatmapLayer> {
+        self.active_layer.map(|index| &self.layers[index])
+    }
+
+    #[inline]
+    pub fn active_layer_name(&self) -> Option<&'static str> {
+        self.active_layer().map(|layer| layer.name)
+    }
+
+    // Pass None to hide the overlay.
+    pub fn set_active_layer(&mut self, name: Option<&str>) {
+        self.active_layer = name.and_then(|name| self.find_index(name));
+    }
+
+    // None -> first layer -> ... -> last layer -> None.
+    pub fn cycle_active_layer(&mut self) {
+        self.active_layer = match self.active_layer {
+            None if !self.layers.is_empty() => Some(0),
+            Some(active) if active + 1 < self.layers.len() => Some(active + 1),
+            _ => None,
+        };
+    }
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    #[inline]
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    #[inline]
+    fn find_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+}
+
+// ----------------------------------------------
+// HeatmapOverlays Global Singleton
+// ----------------------------------------------
+
+common::singleton! { HEATMAP_OVERLAYS_SINGLETON, HeatmapOverlays }
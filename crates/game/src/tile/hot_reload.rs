@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+use strum::IntoEnumIterator;
+
+use common::time::{CountdownTimer, Seconds};
+use engine::{
+    file_sys::{paths, watcher::FileWatcher},
+    log,
+    render::texture::TextureCache,
+};
+
+use super::{
+    TileKind,
+    TileMap,
+    TileMapLayerKind,
+    sets::{TileDef, TileSets},
+};
+
+// ----------------------------------------------
+// TileSetHotReloader
+// ----------------------------------------------
+
+// Watches the tile set assets while the game runs, so artists can iterate without restarting.
+// An edited `tile_set.json` patches the loaded TileDefs and edited sprite images are reloaded
+// in place, then every placed tile using them is refreshed. Desktop builds with loose assets only.
+pub struct TileSetHotReloader {
+    watcher: FileWatcher,
+    use_packed_texture_atlas: bool,
+
+    // Files edited since the last reload, relative to the assets directory.
+    changed_files: Vec<PathBuf>,
+
+    // Editors often save a file in several writes. Wait for them to settle before reloading.
+    settle_timer: CountdownTimer,
+}
+
+impl TileSetHotReloader {
+    const SETTLE_DELAY_SECS: Seconds = 0.5;
+
+    pub fn new(use_packed_texture_atlas: bool) -> Option<Self> {
+        let watcher = FileWatcher::new(paths::assets_path())?;
+
+        if use_packed_texture_atlas {
+            log::warning!(
+                log::channel!("tileset"),
+                "Sprite images are packed into a texture atlas. Only tile_set.json edits will be hot-reloaded."
+            );
+        }
+
+        Some(Self {
+            watcher,
+            use_packed_texture_atlas,
+            changed_files: Vec::new(),
+            settle_timer: CountdownTimer::new(0.0),
+        })
+    }
+
+    pub fn update(&mut self, tex_cache: &mut TextureCache, tile_map: &mut TileMap, delta_time_secs: Seconds) {
+        let changed_files = self.watcher.poll_changed_files();
+
+        if !changed_files.is_empty() {
+            for file in changed_files {
+                if !self.changed_files.contains(&file) {
+                    self.changed_files.push(file);
+                }
+            }
+            self.settle_timer.reset(Self::SETTLE_DELAY_SECS);
+            return;
+        }
+
+        if self.changed_files.is_empty() || !self.settle_timer.tick(delta_time_secs) {
+            return;
+        }
+
+        let changed_files = std::mem::take(&mut self.changed_files);
+        let mut edited_tile_defs: Vec<&'static TileDef> = Vec::new();
+
+        for layer in TileMapLayerKind::iter() {
+            let layer_path = Path::new(layer.assets_path().as_str());
+            let mut tile_set_json_changed = false;
+
+            for file in &changed_files {
+                let Ok(file_in_layer) = file.strip_prefix(layer_path) else {
+                    continue;
+                };
+
+                if file_in_layer == Path::new("tile_set.json") {
+                    tile_set_json_changed = true;
+                } else if file_in_layer.extension().is_some_and(|ext| ext == "png")
+                    && let Some(tile_def) = self.reload_sprite(tex_cache, layer, file, file_in_layer)
+                    && !edited_tile_defs.iter().any(|edited| std::ptr::eq(*edited, tile_def))
+                {
+                    edited_tile_defs.push(tile_def);
+                }
+            }
+
+            if tile_set_json_changed {
+                edited_tile_defs.extend(TileSets::get().hot_reload_tile_set_data(tex_cache, layer));
+            }
+        }
+
+        if edited_tile_defs.is_empty() {
+            return;
+        }
+
+        // Refresh state cached from the TileDefs (and terrain chunks drawing the old sprites).
+        let mut refreshed_count = 0;
+        tile_map.for_each_tile_mut(
+            TileKind::Terrain | TileKind::Building | TileKind::Unit | TileKind::Rocks | TileKind::Vegetation,
+            |tile_map, tile| {
+                if edited_tile_defs.iter().any(|edited| std::ptr::eq(*edited, tile.tile_def())) {
+                    tile_map.on_tile_def_edited(tile);
+                    refreshed_count += 1;
+                }
+            },
+        );
+
+        log::info!(
+            log::channel!("tileset"),
+            "Hot-reload: {} TileDefs edited, {refreshed_count} tiles refreshed.",
+            edited_tile_defs.len()
+        );
+    }
+
+    // Sprite images live under `<layer>/<category>/<tile_name>/...`.
+    // Returns the TileDef owning the image if its texture was reloaded.
+    fn reload_sprite(
+        &self,
+        tex_cache: &mut TextureCache,
+        layer: TileMapLayerKind,
+        file: &Path,
+        file_in_layer: &Path,
+    ) -> Option<&'static TileDef> {
+        let mut components = file_in_layer.components().filter_map(|component| component.as_os_str().to_str());
+        let category_name = components.next()?;
+        let tile_name = components.next()?;
+
+        let tile_def = TileSets::get().find_tile_def_by_name(layer, category_name, tile_name)?;
+
+        // Rebuild the path the same way the TileSet loader does, so it matches the TextureCache entry.
+        let mut texture_path = paths::assets_path().clone();
+        for component in file.components() {
+            texture_path.push(component.as_os_str().to_str()?);
+        }
+
+        if !tex_cache.reload_texture((&texture_path).into()) {
+            if self.use_packed_texture_atlas {
+                log::warning!(log::channel!("tileset"), "Hot-reload: Skipping packed sprite '{texture_path}'.");
+            } else {
+                // Not loaded yet (e.g. a streamed variation); it will be read from disk on first use.
+                log::info!(log::channel!("tileset"), "Hot-reload: Sprite '{texture_path}' isn't loaded. Nothing to reload.");
+            }
+            return None;
+        }
+
+        log::info!(log::channel!("tileset"), "Hot-reloaded sprite '{texture_path}'.");
+        Some(tile_def)
+    }
+}
@@ -8,7 +8,7 @@ use common::{
     RectEdges,
     Size,
     Vec2,
-    coords::{self, Cell, CellF32, IsoDiamond, IsoPointF32, WorldToScreenTransform},
+    coords::{self, Cell, CellF32, IsoDiamond, IsoPointF32, MapRotation, WorldToScreenTransform},
     format_fixed_string,
     mem::RawPtr,
     time::Seconds,
@@ -30,6 +30,7 @@ use super::{
 use crate::{
     camera::Camera,
     save_context::{PostLoadContext, PreLoadContext},
+    sim::SimContext,
     ui_context::GameUiContext,
 };
 
@@ -62,6 +63,7 @@ struct MinimapTileColor {
 impl MinimapTileColor {
     // Default:
     const BLACK:                  Self = Self { r: 0,   g: 0,   b: 0,   a: 255 };
+    const TRANSPARENT:            Self = Self { r: 0,   g: 0,   b: 0,   a: 0   };
 
     // Terrain:
     const WATER:                  Self = Self { r: 30,  g: 100, b: 115, a: 255 }; // dark blue
@@ -76,6 +78,13 @@ impl MinimapTileColor {
     const DIRT_ROAD:              Self = Self { r: 165, g: 122, b: 81,  a: 255 }; // light brown
     const PAVED_ROAD:             Self = Self { r: 138, g: 92,  b: 68,  a: 255 }; // dark brown
 
+    // Walls & Gates:
+    const WALL:                   Self = Self { r: 200, g: 195, b: 185, a: 255 }; // light gray
+    const GATE:                   Self = Self { r: 120, g: 80,  b: 45,  a: 255 }; // dark brown
+
+    // Aqueducts:
+    const AQUEDUCT:               Self = Self { r: 90,  g: 160, b: 220, a: 255 }; // light blue
+
     // Building Sectors:
     const VACANT_LOT:             Self = Self { r: 210, g: 225, b: 20,  a: 255 }; // bright yellow
     const HOUSING:                Self = Self { r: 225, g: 195, b: 120, a: 255 }; // light yellow
@@ -87,6 +96,17 @@ impl MinimapTileColor {
     const TRADE_AND_ECONOMY:      Self = Self { r: 230, g: 185, b: 40,  a: 255 }; // gold
     const BEAUTIFICATION:         Self = Self { r: 60,  g: 200, b: 110, a: 255 }; // light green
 
+    #[inline]
+    fn transparent() -> Self {
+        Self::TRANSPARENT
+    }
+
+    #[inline]
+    fn from_color(color: Color, opacity: f32) -> Self {
+        let to_u8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self { r: to_u8(color.r), g: to_u8(color.g), b: to_u8(color.b), a: to_u8(color.a * opacity) }
+    }
+
     #[inline]
     fn vacant_lot() -> Self {
         Self::VACANT_LOT
@@ -109,6 +129,8 @@ impl MinimapTileColor {
     fn empty_land() -> Self {
         // Alternate randomly between two similar colors
         // to give the minimap a more pleasant texture.
+        // Purely cosmetic, so the thread rng is fine here (and below);
+        // the sim RngStreams are kept for state that ends up in saves.
         if rand::rng().random_bool(0.5) {
             Self::EMPTY_LAND_1
         } else {
@@ -159,6 +181,12 @@ impl MinimapTileColor {
                 Self::water()
             } else if tile_def.path_kind.is_road() {
                 Self::road(tile_def)
+            } else if tile_def.path_kind.is_gate() {
+                Self::GATE
+            } else if tile_def.path_kind.is_wall() {
+                Self::WALL
+            } else if tile_def.path_kind.is_aqueduct() {
+                Self::AQUEDUCT
             } else if tile_def.path_kind.is_rocks() {
                 Self::rocks()
             } else if tile_def.path_kind.is_vegetation() {
@@ -186,6 +214,7 @@ impl Default for MinimapTileColor {
 
 #[derive(Default)]
 struct MinimapTexture {
+    name: &'static str,
     size: Size,
     pixels: Vec<MinimapTileColor>,
     handle: TextureHandle,
@@ -194,9 +223,10 @@ struct MinimapTexture {
 }
 
 impl MinimapTexture {
-    fn new(size: Size) -> Self {
+    fn new(name: &'static str, size: Size) -> Self {
         let pixel_count = (size.width * size.height) as usize;
         Self {
+            name,
             size,
             pixels: vec![MinimapTileColor::default(); pixel_count],
             handle: TextureHandle::invalid(),
@@ -240,10 +270,8 @@ impl MinimapTexture {
         }
 
         if !self.handle.is_valid() {
-            const TEXTURE_NAME: &str = "minimap";
-
             // Make sure we remove it from the cache first before attempting to recreate it.
-            if let Some(mut existing_texture) = tex_cache.find_loaded_texture(TEXTURE_NAME) {
+            if let Some(mut existing_texture) = tex_cache.find_loaded_texture(self.name) {
                 tex_cache.release_texture(&mut existing_texture);
             }
 
@@ -252,7 +280,7 @@ impl MinimapTexture {
                 wrap_mode: TextureWrapMode::ClampToBorder,
                 mipmaps: false,
             };
-            self.handle = tex_cache.new_uninitialized_texture(TEXTURE_NAME, self.size, Some(minimap_texture_settings));
+            self.handle = tex_cache.new_uninitialized_texture(self.name, self.size, Some(minimap_texture_settings));
         }
 
         let len_in_bytes  = self.pixels.len() * std::mem::size_of::<MinimapTileColor>();
@@ -381,6 +409,134 @@ impl MinimapIconTexCache {
 
 common::singleton! { MINIMAP_ICON_TEX_CACHE_SINGLETON, MinimapIconTexCache }
 
+// ----------------------------------------------
+// MinimapOverlayProvider / MinimapOverlayCanvas
+// ----------------------------------------------
+
+// Simulation data layer drawn over the minimap tile colors (water access,
+// employment, etc). Game systems implement this and register it with
+// MinimapOverlays; the minimap repaints the active overlay every sim update.
+pub trait MinimapOverlayProvider {
+    fn name(&self) -> &'static str;
+
+    // Paint the cells this overlay cares about. Untouched cells show the plain minimap.
+    fn paint(&self, context: &SimContext, canvas: &mut MinimapOverlayCanvas);
+}
+
+pub struct MinimapOverlayCanvas<'minimap> {
+    texture: &'minimap mut MinimapTexture,
+    opacity: f32,
+}
+
+impl MinimapOverlayCanvas<'_> {
+    #[inline]
+    pub fn set_cell_color(&mut self, cell: Cell, color: Color) {
+        if self.texture.is_cell_within_bounds(cell) {
+            self.texture.set_pixel(cell, MinimapTileColor::from_color(color, self.opacity));
+        }
+    }
+
+    #[inline]
+    pub fn fill_cells(&mut self, cells: CellRange, color: Color) {
+        for cell in &cells {
+            self.set_cell_color(cell, color);
+        }
+    }
+
+    #[inline]
+    pub fn size_in_cells(&self) -> Size {
+        self.texture.size
+    }
+}
+
+// ----------------------------------------------
+// MinimapOverlays
+// ----------------------------------------------
+
+// Registry of minimap overlays. Registered overlays can be cycled from the minimap
+// menu; only the active one is painted.
+pub struct MinimapOverlays {
+    providers: Vec<Box<dyn MinimapOverlayProvider>>,
+    active_overlay: Option<usize>,
+    opacity: f32,
+}
+
+impl MinimapOverlays {
+    const fn new() -> Self {
+        Self { providers: Vec::new(), active_overlay: None, opacity: 0.75 }
+    }
+
+    // Registers a new overlay or replaces an existing one with the same name.
+    pub fn register(&mut self, provider: Box<dyn MinimapOverlayProvider>) {
+        let name = provider.name();
+
+        if let Some(index) = self.find_index(name) {
+            self.providers[index] = provider;
+        } else {
+            log::info!(log::channel!("minimap"), "Registered minimap overlay '{name}'.");
+            self.providers.push(provider);
+        }
+    }
+
+    pub fn unregister(&mut self, name: &str) {
+        if let Some(index) = self.find_index(name) {
+            self.providers.remove(index);
+
+            self.active_overlay = match self.active_overlay {
+                Some(active) if active == index => None,
+                Some(active) if active > index => Some(active - 1),
+                active => active,
+            };
+        }
+    }
+
+    #[inline]
+    pub fn overlay_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.providers.iter().map(|provider| provider.name())
+    }
+
+    #[inline]
+    pub fn active_overlay_name(&self) -> Option<&'static str> {
+        self.active_overlay().map(|provider| provider.name())
+    }
+
+    // Pass None to hide the overlay.
+    pub fn set_active_overlay(&mut self, name: Option<&str>) {
+        self.active_overlay = name.and_then(|name| self.find_index(name));
+    }
+
+    // None -> first overlay -> ... -> last overlay -> None.
+    pub fn cycle_active_overlay(&mut self) {
+        self.active_overlay = match self.active_overlay {
+            None if !self.providers.is_empty() => Some(0),
+            Some(active) if active + 1 < self.providers.len() => Some(active + 1),
+            _ => None,
+        };
+    }
+
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    #[inline]
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    #[inline]
+    fn active_overlay(&self) -> Option<&dyn MinimapOverlayProvider> {
+        self.active_overlay.map(|index| self.providers[index].as_ref())
+    }
+
+    #[inline]
+    fn find_index(&self, name: &str) -> Option<usize> {
+        self.providers.iter().position(|provider| provider.name() == name)
+    }
+}
+
+common::singleton! { MINIMAP_OVERLAYS_SINGLETON, MinimapOverlays }
+
 // ----------------------------------------------
 // Minimap
 // ----------------------------------------------
@@ -388,6 +544,7 @@ common::singleton! { MINIMAP_ICON_TEX_CACHE_SINGLETON, MinimapIconTexCache }
 #[derive(Default)]
 pub struct Minimap {
     texture: MinimapTexture,
+    overlay_texture: MinimapTexture, // Same size as `texture`, painted by the active MinimapOverlayProvider.
     icons: Vec<MinimapIconInstance>,
     widget: MinimapWidget,
 }
@@ -396,14 +553,18 @@ impl Minimap {
     pub fn new(map_size_in_cells: Size) -> Self {
         Self {
             // One pixel per tile map cell.
-            texture: MinimapTexture::new(map_size_in_cells),
+            texture: MinimapTexture::new("minimap", map_size_in_cells),
+            overlay_texture: MinimapTexture::new("minimap_overlay", map_size_in_cells),
             ..Default::default()
         }
     }
 
+    // `view_rotation` is the tile map view rotation. The minimap itself is always drawn
+    // unrotated, so only camera rect and cursor picking need to account for it.
     pub fn update(
         &mut self,
         camera: &mut Camera,
+        view_rotation: MapRotation,
         tex_cache: &mut TextureCache,
         input_sys: &InputSystem,
         ui_sys: &UiSystem,
@@ -415,24 +576,27 @@ impl Minimap {
         }
 
         self.texture.update(tex_cache);
+        self.overlay_texture.update(tex_cache);
         self.update_icons(delta_time_secs);
-        self.widget.update(camera, input_sys, ui_sys, self.size_in_cells(), delta_time_secs);
+        self.widget.update(camera, view_rotation, input_sys, ui_sys, self.size_in_cells(), delta_time_secs);
     }
 
     #[inline]
     pub fn pre_load(&mut self, context: &mut PreLoadContext) {
         self.texture.pre_load(context.engine_mut().texture_cache_mut());
+        self.overlay_texture.pre_load(context.engine_mut().texture_cache_mut());
         self.icons.clear();
     }
 
     #[inline]
     pub fn post_load(&mut self, context: &PostLoadContext) {
         self.texture.post_load(context.tile_map());
+        self.overlay_texture.reset(context.tile_map().size_in_cells(), MinimapTileColor::transparent);
     }
 
     #[inline]
     pub fn memory_usage_estimate(&self) -> usize {
-        self.texture.memory_usage_estimate()
+        self.texture.memory_usage_estimate() + self.overlay_texture.memory_usage_estimate()
     }
 
     #[inline]
@@ -456,9 +620,58 @@ impl Minimap {
                 MinimapTileColor::default()
             }
         });
+        self.overlay_texture.reset(size, MinimapTileColor::transparent);
         self.widget.reset();
     }
 
+    // Downscaled RGBA8 copy of the minimap, at most `max_size` pixels on either side.
+    // Used as the save game thumbnail. None if the minimap is empty.
+    pub fn capture_thumbnail(&self, max_size: i32) -> Option<(Size, Vec<u8>)> {
+        let map_size = self.texture.size;
+        if !map_size.is_valid() || max_size <= 0 {
+            return None;
+        }
+
+        let scale = (max_size as f32 / map_size.width.max(map_size.height) as f32).min(1.0);
+        let thumbnail_size = Size::new(
+            ((map_size.width as f32 * scale) as i32).max(1),
+            ((map_size.height as f32 * scale) as i32).max(1),
+        );
+
+        let mut pixels = Vec::with_capacity((thumbnail_size.width * thumbnail_size.height * 4) as usize);
+
+        // Nearest neighbor sampling. Overlays and icons are not included.
+        for y in 0..thumbnail_size.height {
+            for x in 0..thumbnail_size.width {
+                let cell = Cell::new(
+                    x * map_size.width / thumbnail_size.width,
+                    y * map_size.height / thumbnail_size.height,
+                );
+                let color = self.texture.pixels[self.texture.cell_to_index(cell)];
+                pixels.extend_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+
+        Some((thumbnail_size, pixels))
+    }
+
+    // ----------------------
+    // Overlays:
+    // ----------------------
+
+    // Repaints the active MinimapOverlays entry from the current simulation state.
+    pub fn refresh_overlay(&mut self, context: &SimContext) {
+        let overlays = MinimapOverlays::get();
+        let Some(provider) = overlays.active_overlay() else {
+            return; // Overlay texture is not drawn.
+        };
+
+        self.overlay_texture.reset(self.texture.size, MinimapTileColor::transparent);
+
+        let mut canvas = MinimapOverlayCanvas { texture: &mut self.overlay_texture, opacity: overlays.opacity() };
+        provider.paint(context, &mut canvas);
+    }
+
     // ----------------------
     // Tile placement:
     // ----------------------
@@ -673,6 +886,7 @@ struct MinimapWidget {
     scroll_speed_px_per_sec: f32, // Scroll speed in pixels per second when `auto_scroll=true`.
     desired_visible_cells: Size,  // Desired number of visible cells we want to display for when `auto_zoom=true`.
     camera_rect: Rect,            // Camera overlay rect in screen space, recomputed every update().
+    view_rotation: MapRotation,   // Tile map view rotation, cached on update().
 }
 
 impl Default for MinimapWidget {
@@ -690,6 +904,7 @@ impl Default for MinimapWidget {
             scroll_speed_px_per_sec: 30.0,
             desired_visible_cells: Size::new(95, 95),
             camera_rect: Rect::default(),
+            view_rotation: MapRotation::Deg0,
         }
     }
 }
@@ -702,6 +917,7 @@ impl MinimapWidget {
     fn update(
         &mut self,
         camera: &mut Camera,
+        view_rotation: MapRotation,
         input_sys: &InputSystem,
         ui_sys: &UiSystem,
         map_size_in_cells: Size,
@@ -715,8 +931,9 @@ impl MinimapWidget {
         debug_assert!(self.transform.is_valid());
 
         // Must update these every frame:
-        self.cursor_pos        = input_sys.cursor_pos();
+        self.cursor_pos        = ui_sys.screen_to_ui(input_sys.cursor_pos());
         self.map_size_in_cells = map_size_in_cells.to_vec2();
+        self.view_rotation     = view_rotation;
         self.window_rect       = self.calc_window_rect(ui_sys);
         self.draw_data         = self.calc_minimap_draw_data();
         self.camera_rect       = self.calc_camera_minimap_rect(camera);
@@ -730,6 +947,7 @@ impl MinimapWidget {
         // Cursor -> minimap cell picking:
         if input_sys.mouse_button_state(MouseButton::Left) == InputAction::Press {
             if let Some(teleport_destination_iso) = self.pick_cursor_pos() {
+                camera.cancel_panning();
                 camera.teleport_iso(teleport_destination_iso);
             }
         }
@@ -823,7 +1041,9 @@ impl MinimapWidget {
             return None;
         }
 
-        Some(coords::cell_to_iso_f32(cell))
+        // Camera works in rotated view space.
+        let map_size = Size::new(self.map_size_in_cells.x as i32, self.map_size_in_cells.y as i32);
+        Some(self.view_rotation.rotate_iso_point(coords::cell_to_iso_f32(cell), map_size))
     }
 
     // Edges of camera rect near the playable area limits, with MINIMAP_EDGE_MARGINS.
@@ -837,6 +1057,13 @@ impl MinimapWidget {
     }
 
     // Rect in minimap widget screen space, ready to be rendered.
+    // Screen space is in rotated view cells, the minimap shows unrotated map cells.
+    fn view_to_map_cell_f32(&self, view_cell: CellF32) -> CellF32 {
+        let map_size = Size::new(self.map_size_in_cells.x as i32, self.map_size_in_cells.y as i32);
+        let view_iso = coords::cell_to_iso_f32(view_cell);
+        coords::iso_to_cell_f32(self.view_rotation.inverse().rotate_iso_point(view_iso, map_size))
+    }
+
     fn calc_camera_minimap_rect(&self, camera: &Camera) -> Rect {
         debug_assert!(self.draw_data.is_valid());
 
@@ -856,7 +1083,8 @@ impl MinimapWidget {
         let mut cell_min = Vec2::new(f32::MAX, f32::MAX);
         let mut cell_max = Vec2::new(f32::MIN, f32::MIN);
         for corner in camera_screen_corners {
-            let cell = coords::screen_point_to_cell_f32(corner, camera.transform()).0;
+            let view_cell = coords::screen_point_to_cell_f32(corner, camera.transform());
+            let cell = self.view_to_map_cell_f32(view_cell).0;
             cell_min = cell_min.min(cell);
             cell_max = cell_max.max(cell);
         }
@@ -1120,8 +1348,9 @@ impl BaseMinimapRenderer {
         debug_assert!(widget.window_rect.is_valid());
         debug_assert!(widget.draw_data.is_valid());
 
+        // In UI space, which differs from the viewport with a UI scale set.
         let open_button_size = Vec2::new(20.0, 20.0);
-        let open_button_pos = Vec2::new(0.0, context.viewport_size.height as f32 - open_button_size.y - 20.0);
+        let open_button_pos = Vec2::new(0.0, widget.window_rect.max.y - open_button_size.y - 20.0);
 
         let menu_margin = 12.0;
 
@@ -1168,10 +1397,38 @@ impl BaseMinimapRenderer {
             heading_width
         };
 
+        // Overlay button, cycles through the registered MinimapOverlays:
+        let overlay_button_width = {
+            let overlay_tooltip = UiTooltipText::new(context, UiTooltipTextParams {
+                text: "Cycle map overlay".into(),
+                font_scale,
+                background,
+            });
+
+            let overlay_button = UiTextButton::new(context, UiTextButtonParams {
+                label: "Overlay".into(),
+                tooltip: Some(overlay_tooltip),
+                size: UiTextButtonSize::Small,
+                sounds_enabled: button_sounds_enabled,
+                on_pressed: UiTextButtonPressed::with_fn(|_, _| {
+                    MinimapOverlays::get_mut().cycle_active_overlay();
+                }),
+                ..Default::default()
+            });
+
+            let button_width = overlay_button.measure(context).x;
+            menu_group.add_widget(overlay_button);
+            button_width
+        };
+
         // Add a separator to push the minimap buttons to the right-hand side of the menu widget.
         {
             let separator_size = Vec2::new(
-                menu_size.x - menu_heading_width - (menu_button_size.x * menu_button_count) - (menu_margin * 2.0),
+                menu_size.x
+                    - menu_heading_width
+                    - overlay_button_width
+                    - (menu_button_size.x * menu_button_count)
+                    - (menu_margin * 2.0),
                 menu_button_size.y,
             );
 
@@ -1339,14 +1596,18 @@ impl BaseMinimapRenderer {
     fn draw_minimap(&mut self, context: &mut GameUiContext) {
         self.draw_minimap_texture_rect(context);
         self.draw_camera_overlay_rect(context);
+        self.draw_overlay_name(context);
     }
 
     fn draw_minimap_texture_rect(&mut self, context: &mut GameUiContext) {
         let draw_list = context.ui_sys.ui().get_window_draw_list();
         let widget = &context.tile_map.minimap.widget;
 
-        let draw_minimap_texture = || {
-            let minimap_texture_handle = context.ui_sys.to_ui_texture(context.tile_map.minimap.texture.handle);
+        let overlay_texture = &context.tile_map.minimap.overlay_texture;
+        let draw_overlay = MinimapOverlays::get().active_overlay().is_some() && overlay_texture.handle.is_valid();
+
+        let draw_minimap_texture = |texture: &MinimapTexture| {
+            let minimap_texture_handle = context.ui_sys.to_ui_texture(texture.handle);
 
             let (uv_min, uv_max) = widget.current_minimap_uv_window();
             let minimap_corners = widget.draw_data.corners();
@@ -1369,16 +1630,35 @@ impl BaseMinimapRenderer {
                 .build();
         };
 
+        let draw_minimap_textures = || {
+            draw_minimap_texture(&context.tile_map.minimap.texture);
+            if draw_overlay {
+                draw_minimap_texture(overlay_texture);
+            }
+        };
+
         if self.apply_widget_clip_rect {
             // Draw inner playable rectangle of the minimap diamond only.
             let clip_rect = widget.draw_data.clip_rect();
-            draw_list.with_clip_rect(clip_rect.min.to_array(), clip_rect.max.to_array(), draw_minimap_texture);
+            draw_list.with_clip_rect(clip_rect.min.to_array(), clip_rect.max.to_array(), draw_minimap_textures);
         } else {
             // Draw whole minimap unclipped.
-            draw_minimap_texture();
+            draw_minimap_textures();
         }
     }
 
+    fn draw_overlay_name(&mut self, context: &mut GameUiContext) {
+        let Some(overlay_name) = MinimapOverlays::get().active_overlay_name() else {
+            return;
+        };
+
+        let draw_list = context.ui_sys.ui().get_window_draw_list();
+        let clip_rect = context.tile_map.minimap.widget.draw_data.clip_rect();
+
+        const TEXT_MARGIN: Vec2 = Vec2::new(4.0, 2.0);
+        draw_list.add_text((clip_rect.min + TEXT_MARGIN).to_array(), imgui::ImColor32::WHITE, overlay_name);
+    }
+
     fn draw_camera_overlay_rect(&mut self, context: &mut GameUiContext) {
         let draw_list = context.ui_sys.ui().get_window_draw_list();
         let widget = &context.tile_map.minimap.widget;
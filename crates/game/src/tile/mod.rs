@@ -17,26 +17,40 @@ use common::{
     hash::StringHash,
     mem::RawPtr,
     time::Seconds,
-    coords::{self, Cell, CellRange, IsoPoint, IsoPointF32, WorldToScreenTransform},
+    coords::{self, Cell, CellRange, IsoPoint, IsoPointF32, MapRotation, WorldToScreenTransform},
 };
-use engine::{file_sys::paths::PathRef, log, save::*};
+use engine::{file_sys::paths::PathRef, log, render::PaletteSwap, save::*};
 
+use anim_lod::{TileAnimBatch, TileAnimBatchKey, TileAnimLod};
+use chunks::TerrainChunkRevisions;
+use elevation::TerrainElevation;
 use minimap::Minimap;
 use selection::TileSelection;
 use placement::{Clearing, Placement, TileClearingErr, TilePlacementErr, TilePlacementOp};
-use sets::{SerializableTileDefHandle, TileAnimSet, TileDef, TileIconSprite, TileSets, TileTexInfo};
-use crate::{pathfind::{NodeKind as PathNodeKind, Graph, GraphUpdateAction}, save_context::*};
-
+use sets::{SerializableTileDefHandle, TileAnimSet, TileDef, TileIconSprite, TileSets, TileSprite, TileTexInfo};
+use crate::{pathfind::{NodeKind as PathNodeKind, Graph}, save_context::*};
+
+pub mod anim_lod;
+pub mod chunks;
+pub mod color_palette;
+pub mod defense;
+pub mod elevation;
+pub mod heatmap;
+pub mod hot_reload;
 pub mod minimap;
 pub mod placement;
+pub mod prefab;
 pub mod rendering;
 pub mod road;
 pub mod selection;
 pub mod sets;
+pub mod streaming;
+pub mod validation;
 pub mod water;
 
 // Internal:
 mod atlas;
+mod save_chunks;
 
 // ----------------------------------------------
 // Constants / Enums
@@ -98,18 +112,19 @@ impl TileKind {
         }
     }
 
+    // None if the hash doesn't name one of the known Objects categories.
     #[inline]
-    fn specialized_kind_for_category(category_hash: StringHash) -> Self {
+    fn specialized_kind_for_category(category_hash: StringHash) -> Option<Self> {
         if category_hash == sets::OBJECTS_BUILDINGS_CATEGORY.hash {
-            TileKind::Building
+            Some(TileKind::Building)
         } else if category_hash == sets::OBJECTS_UNITS_CATEGORY.hash {
-            TileKind::Unit
+            Some(TileKind::Unit)
         } else if category_hash == sets::OBJECTS_ROCKS_CATEGORY.hash {
-            TileKind::Rocks
+            Some(TileKind::Rocks)
         } else if category_hash == sets::OBJECTS_VEGETATION_CATEGORY.hash {
-            TileKind::Vegetation
+            Some(TileKind::Vegetation)
         } else {
-            panic!("Unknown Tile Category hash!");
+            None
         }
     }
 }
@@ -135,6 +150,9 @@ bitflags_with_display! {
         const DrawDebugInfo      = 1 << 9;
         const DrawDebugBounds    = 1 << 10;
         const DrawBlockerInfo    = 1 << 11;
+
+        // Player selected unit. Drawn distinctly from the hover Highlighted flag.
+        const Selected           = 1 << 12;
     }
 }
 
@@ -143,21 +161,25 @@ bitflags_with_display! {
 // ----------------------------------------------
 
 // Index into associated GameObject.
+// The generation is validated on lookup, so a stale handle can't reach an object recycled into the same slot.
 #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub struct TileGameObjectHandle {
     // Index into SpawnPool.
     index: u32,
-    // For buildings this holds the BuildingKind, for Units the generation count.
-    kind_or_generation: u32,
+    // Generation of the GameObject spawned in that slot.
+    generation: u32,
+    // For buildings this holds the BuildingKind (selects the spawn pool). Unused for Units and Props.
+    kind: u32,
 }
 
 impl TileGameObjectHandle {
     #[inline]
-    pub fn new_building(index: usize, kind: u32) -> Self {
+    pub fn new_building(index: usize, generation: u32, kind: u32) -> Self {
         // Reserved value for invalid.
         debug_assert!(index < u32::MAX as usize);
+        debug_assert!(generation < u32::MAX);
         debug_assert!(kind < u32::MAX);
-        Self { index: index.try_into().expect("Index cannot fit into u32!"), kind_or_generation: kind }
+        Self { index: index.try_into().expect("Index cannot fit into u32!"), generation, kind }
     }
 
     #[inline]
@@ -165,7 +187,7 @@ impl TileGameObjectHandle {
         // Reserved value for invalid.
         debug_assert!(index < u32::MAX as usize);
         debug_assert!(generation < u32::MAX);
-        Self { index: index.try_into().expect("Index cannot fit into u32!"), kind_or_generation: generation }
+        Self { index: index.try_into().expect("Index cannot fit into u32!"), generation, kind: 0 }
     }
 
     #[inline]
@@ -173,17 +195,17 @@ impl TileGameObjectHandle {
         // Reserved value for invalid.
         debug_assert!(index < u32::MAX as usize);
         debug_assert!(generation < u32::MAX);
-        Self { index: index.try_into().expect("Index cannot fit into u32!"), kind_or_generation: generation }
+        Self { index: index.try_into().expect("Index cannot fit into u32!"), generation, kind: 0 }
     }
 
     #[inline]
     pub const fn invalid() -> Self {
-        Self { index: u32::MAX, kind_or_generation: u32::MAX }
+        Self { index: u32::MAX, generation: u32::MAX, kind: u32::MAX }
     }
 
     #[inline]
     pub fn is_valid(self) -> bool {
-        self.index < u32::MAX && self.kind_or_generation < u32::MAX
+        self.index < u32::MAX && self.generation < u32::MAX
     }
 
     #[inline]
@@ -194,14 +216,14 @@ impl TileGameObjectHandle {
 
     #[inline]
     pub fn kind(self) -> u32 {
-        debug_assert!(self.kind_or_generation < u32::MAX);
-        self.kind_or_generation
+        debug_assert!(self.kind < u32::MAX);
+        self.kind
     }
 
     #[inline]
     pub fn generation(self) -> u32 {
-        debug_assert!(self.kind_or_generation < u32::MAX);
-        self.kind_or_generation
+        debug_assert!(self.generation < u32::MAX);
+        self.generation
     }
 }
 
@@ -216,7 +238,7 @@ impl Default for TileGameObjectHandle {
 // TileAnimState
 // ----------------------------------------------
 
-#[derive(Copy, Clone, Default, Serialize, Deserialize)]
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
 struct TileAnimState {
     anim_set_index: u16,
     frame_index: u16,
@@ -372,6 +394,14 @@ trait TileBehavior {
     fn tile_def(&self) -> &'static TileDef;
     fn is_valid(&self) -> bool;
 
+    // Terrain elevation (see `elevation` module). Always 0 for objects.
+    fn elevation(&self) -> TerrainElevation;
+    fn set_elevation(&mut self, elevation: TerrainElevation);
+
+    // Render-only displacement from iso_coords_f32(). Always zero for terrain.
+    fn render_iso_offset(&self) -> Vec2;
+    fn set_render_iso_offset(&mut self, offset: Vec2);
+
     // Animations:
     fn anim_state(&self) -> &TileAnimState;
     fn anim_state_mut(&mut self) -> &mut TileAnimState;
@@ -387,6 +417,7 @@ trait TileBehavior {
 //  - Terrain tile logical size is fixed (BASE_TILE_SIZE).
 //  - Terrain tile draw size can be customized.
 //  - No variations or animations.
+//  - Terrain tiles can be raised in elevation steps. Tiles render shifted up accordingly.
 //
 #[derive(Copy, Clone, Serialize, Deserialize)]
 struct TerrainTile {
@@ -395,6 +426,9 @@ struct TerrainTile {
     // Terrain tiles always occupy a single cell (of BASE_TILE_SIZE size).
     cell: Cell,
 
+    #[serde(default)]
+    elevation: TerrainElevation,
+
     // Cached on construction.
     iso_coords_f32: IsoPointF32,
 }
@@ -404,6 +438,7 @@ impl TerrainTile {
         Self {
             def: TileDefRef::new(tile_def),
             cell,
+            elevation: 0,
             iso_coords_f32: IsoPointF32::from_integer_iso(coords::cell_to_iso(cell)),
         }
     }
@@ -468,6 +503,26 @@ impl TileBehavior for TerrainTile {
         self.cell.is_valid() && self.def.as_ref().is_valid()
     }
 
+    #[inline]
+    fn elevation(&self) -> TerrainElevation {
+        self.elevation
+    }
+
+    #[inline]
+    fn set_elevation(&mut self, elevation: TerrainElevation) {
+        self.elevation = elevation.min(elevation::MAX_TERRAIN_ELEVATION);
+    }
+
+    #[inline]
+    fn render_iso_offset(&self) -> Vec2 {
+        Vec2::zero()
+    }
+
+    #[inline]
+    fn set_render_iso_offset(&mut self, _offset: Vec2) {
+        unimplemented!("Not implemented for TerrainTile!");
+    }
+
     // No support for animations on Terrain.
     #[inline]
     fn anim_state(&self) -> &TileAnimState {
@@ -505,6 +560,10 @@ struct ObjectTile {
 
     // Cached on construction.
     iso_coords_f32: IsoPointF32,
+
+    // Transient, only used for drawing.
+    #[serde(skip)]
+    render_iso_offset: Vec2,
 }
 
 impl ObjectTile {
@@ -516,6 +575,7 @@ impl ObjectTile {
             game_object_handle: TileGameObjectHandle::default(),
             anim_state: TileAnimState::default(),
             iso_coords_f32: calc_object_iso_coords(tile_def.kind(), cell, tile_def.logical_size, tile_def.draw_size),
+            render_iso_offset: Vec2::zero(),
         }
     }
 }
@@ -594,6 +654,24 @@ impl TileBehavior for ObjectTile {
         self.cell_range.is_valid() && self.def.as_ref().is_valid()
     }
 
+    #[inline]
+    fn elevation(&self) -> TerrainElevation {
+        0
+    }
+
+    #[inline]
+    fn set_elevation(&mut self, _elevation: TerrainElevation) {}
+
+    #[inline]
+    fn render_iso_offset(&self) -> Vec2 {
+        self.render_iso_offset
+    }
+
+    #[inline]
+    fn set_render_iso_offset(&mut self, offset: Vec2) {
+        self.render_iso_offset = offset;
+    }
+
     // Animations:
     #[inline]
     fn anim_state(&self) -> &TileAnimState {
@@ -749,6 +827,24 @@ impl TileBehavior for BlockerTile {
         self.cell.is_valid() && self.owner_cell.is_valid() && self.owner().is_valid()
     }
 
+    #[inline]
+    fn elevation(&self) -> TerrainElevation {
+        0
+    }
+
+    #[inline]
+    fn set_elevation(&mut self, _elevation: TerrainElevation) {}
+
+    #[inline]
+    fn render_iso_offset(&self) -> Vec2 {
+        self.owner().render_iso_offset()
+    }
+
+    #[inline]
+    fn set_render_iso_offset(&mut self, _offset: Vec2) {
+        unimplemented!("Not implemented for BlockerTile!");
+    }
+
     // Animations:
     #[inline]
     fn anim_state(&self) -> &TileAnimState {
@@ -911,6 +1007,11 @@ impl Tile {
         self.tile_def().color
     }
 
+    #[inline]
+    pub fn palette_swap(&self) -> PaletteSwap {
+        self.tile_def().palette_swap
+    }
+
     #[inline]
     pub fn occupies_multiple_cells(&self) -> bool {
         self.tile_def().occupies_multiple_cells()
@@ -949,6 +1050,18 @@ impl Tile {
         self.archetype.set_iso_coords_f32(iso_coords);
     }
 
+    // Offset added to the iso coords when drawing only, e.g. a unit blended in between
+    // sim ticks. Navigation and depth sorting keep using the simulated position.
+    #[inline]
+    pub fn render_iso_offset(&self) -> Vec2 {
+        self.archetype.render_iso_offset()
+    }
+
+    #[inline]
+    pub fn set_render_iso_offset(&mut self, offset: Vec2) {
+        self.archetype.set_render_iso_offset(offset);
+    }
+
     #[inline]
     pub fn screen_rect(&self, transform: WorldToScreenTransform, apply_variation_offset: bool) -> Rect {
         let draw_size = self.draw_size();
@@ -959,6 +1072,63 @@ impl Tile {
         coords::iso_to_screen_rect_f32(iso_position, draw_size, transform)
     }
 
+    // Base cell where the tile appears with the map view rotated.
+    #[inline]
+    pub fn view_base_cell(&self, rotation: MapRotation, map_size_in_cells: Size) -> Cell {
+        rotation.rotate_cell_range(self.cell_range(), map_size_in_cells).start
+    }
+
+    // Isometric coords of the tile with the map view rotated. Any fractional
+    // offset from the base cell anchor (e.g. a moving unit) is rotated with it.
+    pub fn view_iso_coords_f32(&self, rotation: MapRotation, map_size_in_cells: Size) -> IsoPointF32 {
+        let iso_coords = self.iso_coords_f32();
+        if rotation.is_identity() {
+            return iso_coords;
+        }
+
+        let kind = self.kind();
+        let logical_size = self.logical_size();
+        let draw_size = self.draw_size();
+
+        let view_base_cell = self.view_base_cell(rotation, map_size_in_cells);
+
+        let anchor = calc_object_iso_coords(kind, self.base_cell(), logical_size, draw_size);
+        let view_anchor = calc_object_iso_coords(kind, view_base_cell, logical_size, draw_size);
+
+        IsoPointF32(view_anchor.0 + rotation.rotate_iso_delta(iso_coords.0 - anchor.0))
+    }
+
+    // Same as screen_rect() but for the map view rotated. Always applies the variation offset.
+    #[inline]
+    pub fn view_screen_rect(
+        &self,
+        transform: WorldToScreenTransform,
+        rotation: MapRotation,
+        map_size_in_cells: Size,
+    ) -> Rect {
+        let tile_def = self.tile_def();
+        let variation_index = tile_def.rotated_variation_index(self.variation_index(), rotation);
+
+        let mut iso_position = self.view_iso_coords_f32(rotation, map_size_in_cells);
+        if let Some(variation) = tile_def.variations.get(variation_index) {
+            iso_position.0 += variation.iso_offset;
+        }
+        iso_position.0 += rotation.rotate_iso_delta(self.render_iso_offset());
+        coords::iso_to_screen_rect_f32(iso_position, tile_def.draw_size, transform)
+    }
+
+    // Elevation steps of a terrain tile. Objects read the terrain under them instead (see TileMap::terrain_elevation()).
+    #[inline]
+    pub fn elevation(&self) -> TerrainElevation {
+        self.archetype.elevation()
+    }
+
+    #[inline]
+    pub fn set_elevation(&mut self, elevation: TerrainElevation) {
+        debug_assert!(self.is(TileKind::Terrain), "Only terrain tiles have elevation!");
+        self.archetype.set_elevation(elevation);
+    }
+
     #[inline]
     pub fn is_stacked(&self) -> bool {
         self.next_index != INVALID_TILE_INDEX
@@ -996,8 +1166,14 @@ impl Tile {
         }
     }
 
-    pub fn is_screen_point_inside_base_cell(&self, screen_point: Vec2, transform: WorldToScreenTransform) -> bool {
-        let cell = self.actual_base_cell();
+    pub fn is_screen_point_inside_base_cell(
+        &self,
+        screen_point: Vec2,
+        transform: WorldToScreenTransform,
+        rotation: MapRotation,
+        map_size_in_cells: Size,
+    ) -> bool {
+        let cell = rotation.rotate_cell(self.actual_base_cell(), map_size_in_cells);
         let tile_size = self.logical_size();
         coords::is_screen_point_inside_cell(screen_point, cell, tile_size, transform)
     }
@@ -1136,14 +1312,34 @@ impl Tile {
     }
 
     #[inline]
-    pub fn anim_frame_tex_info(&self) -> Option<&'static TileTexInfo> {
+    pub fn anim_frame_tex_info(&self) -> Option<TileTexInfo> {
+        self.view_anim_frame_tex_info(MapRotation::Deg0)
+    }
+
+    // Current animation frame, using the rotated sprite variation if the TileDef has one.
+    pub fn view_anim_frame_tex_info(&self, rotation: MapRotation) -> Option<TileTexInfo> {
+        self.view_anim_frame(rotation).map(|sprite| sprite.resolve_tex_info())
+    }
+
+    // Current animation frame sprite as seen with the given view rotation.
+    pub fn view_anim_frame(&self, rotation: MapRotation) -> Option<&TileSprite> {
         let anim_set_index = self.anim_set_index();
-        let variation_index = self.variation_index();
+        let tile_def = self.tile_def();
 
-        if let Some(anim_set) = self.tile_def().anim_set_by_index(variation_index, anim_set_index) {
+        // Rotated variations without a matching anim set fall back to the unrotated one.
+        let variation_index = {
+            let rotated_index = tile_def.rotated_variation_index(self.variation_index(), rotation);
+            if tile_def.anim_set_by_index(rotated_index, anim_set_index).is_some() {
+                rotated_index
+            } else {
+                self.variation_index()
+            }
+        };
+
+        if let Some(anim_set) = tile_def.anim_set_by_index(variation_index, anim_set_index) {
             let anim_frame_index = self.anim_frame_index();
             if anim_frame_index < anim_set.frames.len() {
-                return Some(&anim_set.frames[anim_frame_index].tex_info);
+                return Some(&anim_set.frames[anim_frame_index]);
             }
         }
 
@@ -1151,38 +1347,62 @@ impl Tile {
     }
 
     #[inline]
-    fn update_anim(&mut self, delta_time_secs: Seconds) {
+    fn update_anim(&mut self, delta_time_secs: Seconds, batch: &mut TileAnimBatch) {
         if !self.is_animated_archetype() {
             return; // Not animated.
         }
 
         let def = self.tile_def();
-        let anim_set_index = self.anim_set_index();
         let variation_index = self.variation_index();
 
-        if let Some(anim_set) = def.anim_set_by_index(variation_index, anim_set_index) {
-            if anim_set.frames.len() <= 1 {
-                // Single frame sprite, nothing to update.
-                return;
-            }
+        let key = TileAnimBatchKey {
+            tile_def_addr: def as *const TileDef as usize,
+            variation_index,
+            state: *self.anim_state(),
+        };
 
-            let anim_state = self.anim_state_mut();
-            anim_state.frame_play_time_secs += delta_time_secs;
+        let new_state = batch.advance(key, || Self::advance_anim_state(def, variation_index, key.state, delta_time_secs));
+        *self.anim_state_mut() = new_state;
+    }
 
-            if anim_state.frame_play_time_secs >= anim_set.frame_duration_secs() {
-                if (anim_state.frame_index as usize) < anim_set.frames.len() - 1 {
-                    // Move to next frame.
-                    anim_state.frame_index += 1;
-                } else {
-                    // Played the whole anim.
-                    if anim_set.looping {
-                        anim_state.frame_index = 0;
-                    }
-                }
-                // Reset the clock.
+    fn advance_anim_state(
+        def: &TileDef,
+        variation_index: usize,
+        mut anim_state: TileAnimState,
+        delta_time_secs: Seconds,
+    ) -> TileAnimState {
+        let Some(anim_set) = def.anim_set_by_index(variation_index, anim_state.anim_set_index as usize) else {
+            return anim_state;
+        };
+
+        if anim_set.frames.len() <= 1 {
+            // Single frame sprite, nothing to update.
+            return anim_state;
+        }
+
+        let frame_duration_secs = anim_set.frame_duration_secs();
+        let last_frame_index = anim_set.frames.len() - 1;
+
+        anim_state.frame_play_time_secs += delta_time_secs;
+
+        // Throttled updates can cover more than one frame at once.
+        while anim_state.frame_play_time_secs >= frame_duration_secs {
+            anim_state.frame_play_time_secs -= frame_duration_secs;
+
+            if (anim_state.frame_index as usize) < last_frame_index {
+                // Move to next frame.
+                anim_state.frame_index += 1;
+            } else if anim_set.looping {
+                // Played the whole anim.
+                anim_state.frame_index = 0;
+            } else {
+                // Hold the last frame.
                 anim_state.frame_play_time_secs = 0.0;
+                break;
             }
         }
+
+        anim_state
     }
 
     // ----------------------
@@ -1412,6 +1632,9 @@ struct TilePool {
 
     // WxH tiles, INVALID_TILE_INDEX if empty. Idx to 1st tile in the tiles Slab pool.
     cell_to_slab_idx: Vec<TilePoolIndex>,
+
+    // Saved as per-region chunk records (see save_chunks.rs).
+    #[serde(rename = "chunks", with = "save_chunks")]
     slab: Slab<Tile>,
 }
 
@@ -1806,7 +2029,13 @@ impl TileMapLayer {
         neighbors.into_iter().map(|opt_ptr| opt_ptr.map(|ptr| unsafe { &mut *ptr })).collect()
     }
 
-    pub fn find_exact_cell_for_point(&self, screen_point: Vec2, transform: WorldToScreenTransform) -> Cell {
+    // Returns the (unrotated) map cell under the screen point for the given view rotation.
+    pub fn find_exact_cell_for_point(
+        &self,
+        screen_point: Vec2,
+        transform: WorldToScreenTransform,
+        rotation: MapRotation,
+    ) -> Cell {
         let iso_point = coords::screen_to_iso_point(screen_point, transform);
         let approx_cell = coords::iso_to_cell(iso_point);
 
@@ -1834,7 +2063,7 @@ impl TileMapLayer {
 
         for cell in neighbor_cells {
             if coords::is_screen_point_inside_cell(screen_point, cell, BASE_TILE_SIZE_I32, transform) {
-                return cell;
+                return rotation.unrotate_cell(cell, self.size_in_cells());
             }
         }
 
@@ -1951,12 +2180,23 @@ impl TileMapLayer {
         self.pool.try_get_tile_mut(owner_cell).expect("Blocker tile must have a valid owner cell!")
     }
 
+    // Cells inside `skip_range` (if any) are left untouched.
     #[inline]
-    fn update_anims(&mut self, visible_range: CellRange, delta_time_secs: Seconds) {
-        for cell in &visible_range {
+    fn update_anims(
+        &mut self,
+        range: CellRange,
+        skip_range: Option<CellRange>,
+        delta_time_secs: Seconds,
+        batch: &mut TileAnimBatch,
+    ) {
+        for cell in &range {
+            if skip_range.is_some_and(|skip_range| skip_range.contains(cell)) {
+                continue;
+            }
+
             let next_tile_index = {
                 if let Some(tile) = self.try_tile_mut(cell) {
-                    tile.update_anim(delta_time_secs);
+                    tile.update_anim(delta_time_secs, batch);
                     tile.next_index
                 } else {
                     INVALID_TILE_INDEX
@@ -1965,7 +2205,7 @@ impl TileMapLayer {
 
             // Update next tiles in the stack chain.
             self.visit_next_tiles_mut(next_tile_index, |next_tile| {
-                next_tile.update_anim(delta_time_secs);
+                next_tile.update_anim(delta_time_secs, batch);
             });
         }
     }
@@ -2171,6 +2411,19 @@ pub struct TileMap {
     #[serde(skip)]
     graph: Graph,
 
+    // Not serialized. View-only state; map cells are always stored unrotated.
+    #[serde(skip)]
+    rotation: MapRotation,
+
+    // Not serialized. Reconstructed on reset() and post_load().
+    // Bumped on terrain edits so renderers can rebuild cached chunks.
+    #[serde(skip)]
+    terrain_chunk_revisions: TerrainChunkRevisions,
+
+    // Not serialized. Per-frame anim update throttling state.
+    #[serde(skip)]
+    anim_lod: TileAnimLod,
+
     // NOTE: TileMap editor callbacks are *not* serialized. These must be
     // manually reset on the user's post_load() after deserialization.
     #[serde(skip)]
@@ -2179,43 +2432,27 @@ pub struct TileMap {
 
 macro_rules! update_search_graph {
     // NOTE: Units are skipped here because Units do not alter the search graph.
-    // `$layers` is a `TileMapLayerRefs` (Copy), forwarded to `Graph::update`.
-    ($layers:expr, $graph:expr, $cells:expr, $layer_kind:expr, $tile_kind:expr, TileCleared) => {{
+    // `$layers` is a `TileMapLayerRefs` (Copy), forwarded to `Graph::update_cells`.
+    // Only the cells covered by the tile (plus their surrounding ring) are recomputed.
+    ($layers:expr, $graph:expr, $cells:expr, $tile_kind:expr, TileCleared) => {{
         if !$tile_kind.intersects(TileKind::Unit) {
-            $graph.update($layers, GraphUpdateAction::TileCleared($cells, $layer_kind, $tile_kind));
+            $graph.update_cells($layers, $cells);
         }
     }};
-    ($layers:expr, $graph:expr, $tile:expr, TilePlaced) => {{
+    ($layers:expr, $graph:expr, $tile:expr, TileChanged) => {{
         if !$tile.is(TileKind::Unit) {
-            let cells      = $tile.cell_range();
-            let layer_kind = $tile.layer_kind();
-            let path_kind  = $tile.path_kind();
-            $graph.update($layers, GraphUpdateAction::TilePlaced(cells, layer_kind, path_kind));
-        }
-    }};
-    ($layers:expr, $graph:expr, $tile:expr, TileDefEdited) => {{
-        if !$tile.is(TileKind::Unit) {
-            let cells      = $tile.cell_range();
-            let layer_kind = $tile.layer_kind();
-            let tile_kind  = $tile.kind();
-            let path_kind  = $tile.path_kind();
-            $graph.update($layers, GraphUpdateAction::TileDefEdited(cells, layer_kind, tile_kind, path_kind));
+            $graph.update_cells($layers, $tile.cell_range());
         }
     }};
     ($layers:expr, $graph:expr, $tile:expr, TileMoved($from_cells:expr)) => {{
         if !$tile.is(TileKind::Unit) {
-            let to_cells   = $tile.cell_range();
-            let layer_kind = $tile.layer_kind();
-            let tile_kind  = $tile.kind();
-            let path_kind  = $tile.path_kind();
-            $graph.update($layers, GraphUpdateAction::TileMoved($from_cells, to_cells, layer_kind, tile_kind, path_kind));
+            $graph.update_cells($layers, $from_cells);
+            $graph.update_cells($layers, $tile.cell_range());
         }
     }};
     ($layers:expr, $graph:expr, $tile:expr, $new_flags:expr, TileFlagsChanged) => {{
         if !$tile.is(TileKind::Unit) && Graph::tile_flags_affect_node_kind($new_flags) {
-            let cells     = $tile.cell_range();
-            let path_kind = $tile.path_kind();
-            $graph.update($layers, GraphUpdateAction::TileFlagsChanged(cells, $new_flags, path_kind));
+            $graph.update_cells($layers, $tile.cell_range());
         }
     }};
 }
@@ -2231,6 +2468,9 @@ impl TileMap {
             playable_area: TileMapPlayableArea::with_inner_rect_margin(size_in_cells),
             minimap: Minimap::new(size_in_cells),
             graph: Graph::default(),
+            rotation: MapRotation::Deg0,
+            terrain_chunk_revisions: TerrainChunkRevisions::default(),
+            anim_lod: TileAnimLod::default(),
             callbacks: TileMapEditorCallbacks::default(),
         };
 
@@ -2258,8 +2498,11 @@ impl TileMap {
         if let Some(map_size) = new_map_size {
             self.playable_area = TileMapPlayableArea::with_inner_rect_margin(map_size);
             self.size_in_cells = map_size;
+            self.rotation = MapRotation::Deg0;
         }
 
+        self.terrain_chunk_revisions = TerrainChunkRevisions::new(self.size_in_cells);
+
         for layer_kind in TileMapLayerKind::iter() {
             // Find which layer this tile belong to if we're not just setting everything to empty.
             let fill_opt = {
@@ -2362,6 +2605,86 @@ impl TileMap {
         true
     }
 
+    #[inline]
+    pub fn rotation(&self) -> MapRotation {
+        self.rotation
+    }
+
+    // Only square maps can be rotated, so the view keeps the same cell bounds.
+    pub fn set_rotation(&mut self, rotation: MapRotation) -> bool {
+        if self.size_in_cells.width != self.size_in_cells.height && !rotation.is_identity() {
+            log::warning!(log::channel!("tile"), "Cannot rotate non-square map view ({}).", self.size_in_cells);
+            return false;
+        }
+        self.rotation = rotation;
+        true
+    }
+
+    // Map cell -> cell where it is displayed with the current view rotation.
+    #[inline]
+    pub fn rotate_cell(&self, cell: Cell) -> Cell {
+        self.rotation.rotate_cell(cell, self.size_in_cells)
+    }
+
+    // View cell -> map cell. Inverse of rotate_cell().
+    #[inline]
+    pub fn unrotate_cell(&self, cell: Cell) -> Cell {
+        self.rotation.unrotate_cell(cell, self.size_in_cells)
+    }
+
+    #[inline]
+    pub fn terrain_chunk_revisions(&self) -> &TerrainChunkRevisions {
+        &self.terrain_chunk_revisions
+    }
+
+    // Forces cached terrain chunks overlapping `cells` to rebuild. Placement and clearing
+    // already do this; only needed when editing terrain tile state directly (e.g. variations).
+    #[inline]
+    pub fn invalidate_terrain_chunks(&mut self, cells: CellRange) {
+        self.terrain_chunk_revisions.mark_dirty(cells);
+    }
+
+    // Elevation of the terrain tile at `cell`. 0 if there's no terrain there.
+    #[inline]
+    pub fn terrain_elevation(&self, cell: Cell) -> TerrainElevation {
+        self.try_tile_from_layer(cell, TileMapLayerKind::Terrain).map_or(0, |tile| tile.elevation())
+    }
+
+    // Returns true if the elevation changed. Does not update the slope/cliff
+    // variations around `cell`; see `elevation::update_transitions()`.
+    pub fn set_terrain_elevation(&mut self, cell: Cell, elevation: TerrainElevation) -> bool {
+        debug_assert!(!self.is_locked(), "Cannot edit terrain while map is locked!");
+
+        let elevation = elevation.min(elevation::MAX_TERRAIN_ELEVATION);
+        let Some(tile) = self.try_tile_from_layer_mut(cell, TileMapLayerKind::Terrain) else {
+            return false;
+        };
+
+        if tile.elevation() == elevation {
+            return false;
+        }
+
+        tile.set_elevation(elevation);
+
+        let cells = CellRange::new(cell, cell);
+        self.terrain_chunk_revisions.mark_dirty(cells);
+
+        let layers = self.layers();
+        self.graph.update_cells(layers, cells);
+        true
+    }
+
+    // Sprite rect of `tile` in the current map view, raised by the elevation of the terrain it stands on.
+    #[inline]
+    pub fn tile_view_screen_rect(&self, tile: &Tile, transform: WorldToScreenTransform) -> Rect {
+        let rect = tile.view_screen_rect(transform, self.rotation, self.size_in_cells);
+        let elevation = self.terrain_elevation(tile.base_cell());
+        if elevation == 0 {
+            return rect;
+        }
+        rect.translated(elevation::screen_offset(elevation, transform.scaling))
+    }
+
     #[inline]
     pub fn is_cell_within_playable_area(&self, cell: Cell) -> bool {
         self.is_cell_within_bounds(cell) && self.playable_area.contains(cell.x, cell.y)
@@ -2466,11 +2789,21 @@ impl TileMap {
         let layers = self.layers();
         let mut graph = RawPtr::from_ref(&self.graph);
 
+        let mut dirty_terrain_cells = None;
+
         if let Some(tile) = self.find_tile_mut(cell, tile_kinds) {
             tile.set_flags(flags, value);
 
+            if flags.intersects(TileFlags::Hidden) && tile.is(TileKind::Terrain) {
+                dirty_terrain_cells = Some(tile.cell_range());
+            }
+
             update_search_graph!(layers, graph, tile, flags, TileFlagsChanged);
         }
+
+        if let Some(cells) = dirty_terrain_cells {
+            self.terrain_chunk_revisions.mark_dirty(cells);
+        }
     }
 
     // Sets the given flags on the tile at the given `index` in `layer_kind`.
@@ -2489,6 +2822,11 @@ impl TileMap {
         tile.set_flags(flags, value);
 
         update_search_graph!(layers, graph, tile, flags, TileFlagsChanged);
+
+        if flags.intersects(TileFlags::Hidden) && layer_kind == TileMapLayerKind::Terrain {
+            let cells = self.tile_at_index(index, layer_kind).cell_range();
+            self.terrain_chunk_revisions.mark_dirty(cells);
+        }
     }
 
     #[inline]
@@ -2502,7 +2840,7 @@ impl TileMap {
             return Cell::invalid();
         }
 
-        self.layer(layer_kind).find_exact_cell_for_point(screen_point, transform)
+        self.layer(layer_kind).find_exact_cell_for_point(screen_point, transform, self.rotation)
     }
 
     #[inline]
@@ -2551,16 +2889,36 @@ impl TileMap {
     }
 
     #[inline]
-    pub fn update_anims(&mut self, visible_range: CellRange, delta_time_secs: Seconds) {
+    // `zoom` is the current camera zoom; zoomed out views update their anims at a reduced rate.
+    pub fn update_anims(&mut self, visible_range: CellRange, zoom: f32, delta_time_secs: Seconds) {
         debug_assert!(!self.is_locked(), "Cannot mutate locked TileMap!");
 
         if !self.layers.is_empty() {
+            // Visible range is in view cells.
+            let map_range = self.rotation.unrotate_cell_range(visible_range, self.size_in_cells);
+            let passes = self.anim_lod.begin_frame(map_range, self.size_in_cells, zoom, delta_time_secs);
+
             // NOTE: Terrain layer is not animated by design. Only objects animate.
-            let objects_layer = self.layer_mut(TileMapLayerKind::Objects);
-            objects_layer.update_anims(visible_range, delta_time_secs);
+            let objects_layer = &mut self.layers[TileMapLayerKind::Objects as usize];
+            debug_assert!(objects_layer.kind() == TileMapLayerKind::Objects);
+
+            if let Some(delta_time_secs) = passes.visible {
+                objects_layer.update_anims(map_range, None, delta_time_secs, self.anim_lod.begin_pass());
+                self.anim_lod.end_pass(false);
+            }
+
+            if let Some((offscreen_range, delta_time_secs)) = passes.offscreen {
+                objects_layer.update_anims(offscreen_range, Some(map_range), delta_time_secs, self.anim_lod.begin_pass());
+                self.anim_lod.end_pass(true);
+            }
         }
     }
 
+    #[inline]
+    pub fn anim_stats(&self) -> &anim_lod::TileAnimStats {
+        self.anim_lod.stats()
+    }
+
     // ----------------------
     // Search Graph:
     // ----------------------
@@ -2681,7 +3039,7 @@ impl TileMap {
         let tile_placed_callback = self.callbacks.on_tile_placed;
         let prev_pool_capacity = self.layer(layer_kind).pool_capacity();
 
-        let Self { layers, minimap, graph, .. } = self;
+        let Self { layers, minimap, graph, terrain_chunk_revisions, .. } = self;
         let layer = &mut *layers[layer_kind as usize];
 
         placement::internal::try_place_tile_in_layer(layer, target_cell, tile_def_to_place)
@@ -2691,8 +3049,12 @@ impl TileMap {
                     callback(tile, did_reallocate);
                 }
 
+                if layer_kind == TileMapLayerKind::Terrain {
+                    terrain_chunk_revisions.mark_dirty(tile.cell_range());
+                }
+
                 minimap.place_tile(target_cell, tile_def_to_place);
-                update_search_graph!(layer_refs, graph, tile, TilePlaced);
+                update_search_graph!(layer_refs, graph, tile, TileChanged);
 
                 tile
             })
@@ -2726,7 +3088,11 @@ impl TileMap {
             let tile_cells = tile_def.cell_range(target_cell);
             let tile_kind  = tile_def.kind();
 
-            update_search_graph!(layers, self.graph, tile_cells, layer_kind, tile_kind, TileCleared);
+            if layer_kind == TileMapLayerKind::Terrain {
+                self.terrain_chunk_revisions.mark_dirty(tile_cells);
+            }
+
+            update_search_graph!(layers, self.graph, tile_cells, tile_kind, TileCleared);
         }
 
         result.map(|_| ())
@@ -2763,7 +3129,11 @@ impl TileMap {
             let tile_cells = tile_def.cell_range(target_cell);
             let tile_kind  = tile_def.kind();
 
-            update_search_graph!(layers, self.graph, tile_cells, layer_kind, tile_kind, TileCleared);
+            if layer_kind == TileMapLayerKind::Terrain {
+                self.terrain_chunk_revisions.mark_dirty(tile_cells);
+            }
+
+            update_search_graph!(layers, self.graph, tile_cells, tile_kind, TileCleared);
         }
 
         result.map(|_| ())
@@ -2935,7 +3305,8 @@ impl TileMap {
         }
 
         let map_size_in_cells = self.size_in_cells();
-        selection.update(self.layers_mut(), map_size_in_cells, cursor_screen_pos, transform, placement_op);
+        let rotation = self.rotation;
+        selection.update(self.layers_mut(), map_size_in_cells, rotation, cursor_screen_pos, transform, placement_op);
     }
 
     #[inline]
@@ -2962,6 +3333,13 @@ impl TileMap {
         None
     }
 
+    pub fn multi_selected_tiles<'a>(&'a self, selection: &'a TileSelection) -> impl Iterator<Item = &'a Tile> {
+        selection
+            .multi_selection()
+            .iter()
+            .filter_map(|&(cell, layer_kind)| self.try_tile_from_layer(cell, layer_kind))
+    }
+
     pub fn topmost_tile_at_cursor(&self, cursor_screen_pos: Vec2, transform: WorldToScreenTransform) -> Option<&Tile> {
         debug_assert!(transform.is_valid());
 
@@ -2972,7 +3350,7 @@ impl TileMap {
         // Find topmost layer tile under the target cell.
         for layer_kind in TileMapLayerKind::iter().rev() {
             let layer = self.layer(layer_kind);
-            let target_cell = layer.find_exact_cell_for_point(cursor_screen_pos, transform);
+            let target_cell = layer.find_exact_cell_for_point(cursor_screen_pos, transform, self.rotation);
 
             let tile = layer.try_tile(target_cell);
             if tile.is_some() {
@@ -3003,7 +3381,12 @@ impl TileMap {
 
         tile.on_tile_def_edited();
 
-        update_search_graph!(layers, self.graph, tile, TileDefEdited);
+        if tile.is(TileKind::Terrain) {
+            // Tint color or sprites may have changed for every instance of this TileDef.
+            self.terrain_chunk_revisions.mark_all_dirty();
+        }
+
+        update_search_graph!(layers, self.graph, tile, TileChanged);
     }
 }
 
@@ -3021,7 +3404,7 @@ impl Save for TileMap {
         });
 
         self.for_each_tile_mut(TileKind::AllObjectKinds, |_tile_map, tile| {
-            tile.set_flags(TileFlags::Highlighted | TileFlags::Invalidated, false);
+            tile.set_flags(TileFlags::Highlighted | TileFlags::Invalidated | TileFlags::Selected, false);
         });
     }
 
@@ -3063,5 +3446,7 @@ impl Load for TileMap {
             self.playable_area = TileMapPlayableArea::with_inner_rect_margin(self.size_in_cells);
             self.graph = Graph::from_tile_map(self);
         }
+
+        self.terrain_chunk_revisions = TerrainChunkRevisions::new(self.size_in_cells);
     }
 }
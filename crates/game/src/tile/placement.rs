@@ -1,7 +1,7 @@
 use common::coords::Cell;
 use strum::Display;
 
-use super::{Tile, TileKind, TileMapLayer, TileMapLayerKind, TileMapLayerRefs, TilePoolIndex, sets::TileDef, water};
+use super::{Tile, TileKind, TileMapLayer, TileMapLayerKind, TileMapLayerRefs, TilePoolIndex, defense, sets::TileDef, water};
 use crate::{
     debug,
     pathfind::{self, NodeKind as PathNodeKind},
@@ -71,6 +71,7 @@ pub enum TilePlacementErrReason {
     CellOutOfBounds,
     SpawnFailed,
     CannotAffordCost,
+    BuildingLocked,
     RequiresProximity(PathNodeKind), // Required node proximity violated.
     Obstruction(&'static TileDef),   // Tile obstructing the placement.
 }
@@ -175,8 +176,30 @@ pub mod internal {
                                 tile_def_to_place.name,
                                 tile.name()
                             );
+                        } else if tile_def_to_place.is(TileKind::Object)
+                            && !tile_def_to_place.is(TileKind::Unit)
+                            && tile_def_to_place.water_object
+                            && !path_kind.is_water_object_placeable()
+                        {
+                            return err!(
+                                Placement::RequiresProximity(PathNodeKind::Water),
+                                "Cannot place water object '{}' over terrain tile '{}'.",
+                                tile_def_to_place.name,
+                                tile.name()
+                            );
+                        } else if tile_def_to_place.is(TileKind::Unit)
+                            && tile_def_to_place.water_object
+                            && !path_kind.is_water_object_placeable()
+                        {
+                            return err!(
+                                Placement::RequiresProximity(PathNodeKind::Water),
+                                "Cannot place water unit '{}' over terrain tile '{}'.",
+                                tile_def_to_place.name,
+                                tile.name()
+                            );
                         } else if tile_def_to_place.is(TileKind::Unit)
                             && !tile_def_to_place.flying_object
+                            && !tile_def_to_place.water_object
                             && !path_kind.is_unit_placeable()
                         {
                             return err!(
@@ -257,6 +280,47 @@ pub mod internal {
                     "Cannot place vacant lot here! Cell already occupied by an object."
                 );
             }
+        } else if tile_def_to_place.is_defense() || tile_def_to_place.is_aqueduct() {
+            // Walls, towers, gates and aqueducts go over empty land. Walls and gates can also replace a wall section.
+            if let Some(tile) = layers.get(TileMapLayerKind::Terrain).try_tile(target_cell) {
+                let replaces_wall = defense::is_wall_tile_def(tile.tile_def())
+                    && (tile_def_to_place.is_gate() || defense::is_wall_tile_def(tile_def_to_place));
+
+                if !tile.path_kind().is_empty_land() && !replaces_wall {
+                    return err!(
+                        Placement::Obstruction(tile.tile_def()),
+                        "Cannot place '{}' over terrain tile '{}'.",
+                        tile_def_to_place.name,
+                        tile.name()
+                    );
+                }
+            }
+
+            if let Some(object) = layers.get(TileMapLayerKind::Objects).try_tile(target_cell) {
+                return err!(
+                    Placement::Obstruction(object.tile_def()),
+                    "Cannot place '{}' here! Cell already occupied by an object.",
+                    tile_def_to_place.name
+                );
+            }
+
+            // Towers must be built onto a wall.
+            if !tile_def_to_place.required_proximity.is_empty() {
+                let neighbors = layers.get(TileMapLayerKind::Terrain).tile_neighbors(target_cell, false);
+                let is_near = neighbors
+                    .iter()
+                    .flatten()
+                    .any(|neighbor| neighbor.path_kind().intersects(tile_def_to_place.required_proximity));
+
+                if !is_near {
+                    return err!(
+                        Placement::RequiresProximity(tile_def_to_place.required_proximity),
+                        "'{}' must be placed next to a {}.",
+                        tile_def_to_place.name,
+                        tile_def_to_place.required_proximity
+                    );
+                }
+            }
         } else if tile_def_to_place.path_kind.intersects(PathNodeKind::Road | PathNodeKind::SettlersSpawnPoint) {
             if let Some(tile) = layers.get(TileMapLayerKind::Terrain).try_tile(target_cell) {
                 if tile.path_kind().is_water() {
@@ -312,6 +376,7 @@ pub mod internal {
         }
 
         let mut allow_stacking = false;
+        let mut terrain_elevation = 0;
 
         if tile_def_to_place.is(TileKind::Terrain) {
             debug_assert!(layer.kind() == TileMapLayerKind::Terrain);
@@ -328,6 +393,8 @@ pub mod internal {
                     );
                 }
 
+                // Replacing terrain keeps the ground level, e.g. a road built on a hill.
+                terrain_elevation = existing_tile.elevation();
                 layer.remove_tile(target_cell);
             }
         } else if tile_def_to_place.is(TileKind::Unit) {
@@ -386,6 +453,9 @@ pub mod internal {
         // Placement successful.
         let new_pool_capacity = layer.pool_capacity();
         let new_tile = layer.try_tile_mut(target_cell).unwrap();
+        if terrain_elevation != 0 {
+            new_tile.set_elevation(terrain_elevation);
+        }
         Ok((new_tile, new_pool_capacity))
     }
 
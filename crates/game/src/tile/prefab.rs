@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+
+use common::coords::Cell;
+use engine::log;
+
+use super::{
+    TileKind,
+    sets::{TileDef, TileSector, TileSets},
+};
+use crate::{
+    cheats,
+    menu::{PlaceOrClearResult, TilePlacement},
+    sim::SimContext,
+    undo_redo::{self, EditAction, EditedLayer},
+};
+
+// ----------------------------------------------
+// PrefabPart
+// ----------------------------------------------
+
+// A single tile of a prefab. Can be any building, prop or road/vacant lot terrain
+// tile. Other terrain tiles are not supported since they cannot be undone/cleared.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PrefabPart {
+    pub tile: String, // TileDef name.
+    pub offset: Cell, // Relative to the prefab anchor (first part).
+}
+
+pub type PrefabResolvedParts = Vec<(Cell, &'static TileDef)>; // [(offset, tile_def)]
+
+// ----------------------------------------------
+// PrefabDef
+// ----------------------------------------------
+
+// Group of tiles placed together as a single unit (e.g. farm + fields, fort + towers).
+// Validated and committed atomically: Either all parts are placed or none is, with
+// a single combined cost and a single undo/redo entry.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PrefabDef {
+    pub name: String,
+    pub sector: TileSector, // Build menu the prefab is listed under.
+    pub parts: Vec<PrefabPart>, // First part is the anchor; used for cursor placement and highlighting.
+
+    // TileDefs of `parts`, looked up once on load. Empty if any of them is missing.
+    #[serde(skip)]
+    resolved_parts: PrefabResolvedParts,
+}
+
+impl PrefabDef {
+    // Resolved TileDef of every part. None if any tile could not be found on load.
+    #[inline]
+    pub fn resolved_parts(&self) -> Option<&[(Cell, &'static TileDef)]> {
+        if self.resolved_parts.is_empty() { None } else { Some(&self.resolved_parts) }
+    }
+
+    #[inline]
+    pub fn anchor_tile_def(&self) -> Option<&'static TileDef> {
+        self.resolved_parts.first().map(|(_, tile_def)| *tile_def)
+    }
+
+    // Combined cost of all parts.
+    #[inline]
+    pub fn cost(&self) -> u32 {
+        self.resolved_parts.iter().map(|(_, tile_def)| tile_def.cost).sum()
+    }
+
+    pub fn can_afford(&self, context: &SimContext) -> bool {
+        let cost = self.cost();
+        cost == 0 || context.treasury().can_afford(context.world(), cost)
+    }
+
+    // True if none of the building parts is still locked.
+    pub fn is_unlocked(&self, context: &SimContext) -> bool {
+        let unlocks = context.building_unlocks();
+        self.resolved_parts
+            .iter()
+            .all(|(_, tile_def)| !tile_def.is(TileKind::Building) || unlocks.is_tile_def_unlocked(tile_def))
+    }
+
+    // Places all parts with the anchor part at `anchor_cell`. If any part fails, the
+    // ones already placed are removed again and the prefab is not charged for.
+    pub fn try_place(&self, context: &SimContext, anchor_cell: Cell) -> PlaceOrClearResult {
+        let Some(parts) = self.resolved_parts() else {
+            return PlaceOrClearResult::Failed { placement_attempt_tile_def: None, obstructing_tile_def: None };
+        };
+
+        let (anchor_offset, anchor_tile_def) = parts[0];
+
+        if !self.can_afford(context) || !self.is_unlocked(context) {
+            return PlaceOrClearResult::Failed {
+                placement_attempt_tile_def: Some(anchor_tile_def),
+                obstructing_tile_def: None,
+            };
+        }
+
+        let mut placed_parts = Vec::with_capacity(parts.len());
+        let mut placed_cost = 0;
+        let mut layers = EditedLayer::empty();
+
+        for &(offset, tile_def) in parts {
+            let target_cell =
+                Cell::new(anchor_cell.x + offset.x - anchor_offset.x, anchor_cell.y + offset.y - anchor_offset.y);
+
+            // Terrain parts already in place (e.g. an existing road) are left as is.
+            if tile_def.is(TileKind::Terrain)
+                && context.find_tile(target_cell, tile_def.kind()).is_some_and(|tile| tile.tile_def().hash == tile_def.hash)
+            {
+                continue;
+            }
+
+            // Terrain parts replace the terrain tile, so remember it in case we have to roll back.
+            let replaced_tile_def = if tile_def.is(TileKind::Terrain) {
+                context.find_tile(target_cell, TileKind::Terrain).map(|tile| tile.tile_def())
+            } else {
+                None
+            };
+
+            let result = TilePlacement::place(context, target_cell, tile_def, false, true, false);
+            if result.failed() {
+                Self::rollback(context, &placed_parts);
+                return result;
+            }
+
+            placed_parts.push(PlacedPart { cell: target_cell, tile_def, replaced_tile_def });
+            placed_cost += tile_def.cost;
+            layers |= if tile_def.is(TileKind::Terrain) { EditedLayer::Terrain } else { EditedLayer::Objects };
+        }
+
+        if placed_parts.is_empty() {
+            return PlaceOrClearResult::Failed {
+                placement_attempt_tile_def: Some(anchor_tile_def),
+                obstructing_tile_def: None,
+            };
+        }
+
+        if placed_cost != 0 && !cheats::get().ignore_tile_cost {
+            context.treasury_mut().subtract_gold_units_global(context.world_mut(), placed_cost);
+        }
+
+        // Single undo entry for the whole prefab.
+        undo_redo::record(
+            EditAction::PlacedTiles,
+            placed_parts.iter().map(|part| &part.cell),
+            layers,
+            context.tile_map(),
+            context.world(),
+        );
+
+        PlaceOrClearResult::PlacedTile(anchor_tile_def)
+    }
+
+    // Objects are removed again; terrain parts put back the terrain tile they replaced.
+    fn rollback(context: &SimContext, placed_parts: &[PlacedPart]) {
+        for part in placed_parts.iter().rev() {
+            if let Some(replaced_tile_def) = part.replaced_tile_def {
+                TilePlacement::place(context, part.cell, replaced_tile_def, false, false, false);
+            } else if let Some(tile) = context.find_tile(part.cell, part.tile_def.kind()) {
+                TilePlacement::clear(context, tile, false, false);
+            }
+        }
+    }
+
+    // Called by PrefabConfigs::post_load().
+    fn resolve_parts(&mut self) {
+        let mut resolved = PrefabResolvedParts::with_capacity(self.parts.len());
+
+        for part in &self.parts {
+            let Some(tile_def) = Self::find_tile_def(&part.tile) else {
+                log::error!(log::channel!("prefab"), "Prefab '{}': Can't find TileDef '{}'!", self.name, part.tile);
+                return;
+            };
+            resolved.push((part.offset, tile_def));
+        }
+
+        self.resolved_parts = resolved;
+    }
+
+    fn find_tile_def(tile_name: &str) -> Option<&'static TileDef> {
+        let mut found = None;
+        TileSets::get().for_each_tile_def(|_, _, tile_def| {
+            if tile_def.name == tile_name {
+                found = Some(tile_def);
+            }
+            found.is_none()
+        });
+        found
+    }
+}
+
+struct PlacedPart {
+    cell: Cell,
+    tile_def: &'static TileDef,
+    replaced_tile_def: Option<&'static TileDef>, // Terrain parts only.
+}
+
+// ----------------------------------------------
+// PrefabConfigs
+// ----------------------------------------------
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefabConfigs {
+    pub prefabs: Vec<PrefabDef>,
+}
+
+impl PrefabConfigs {
+    #[inline]
+    pub fn prefab(&self, index: usize) -> Option<&PrefabDef> {
+        self.prefabs.get(index)
+    }
+
+    // NOTE: Must be loaded after the TileSets.
+    fn post_load(&'static mut self) {
+        debug_assert!(TileSets::is_initialized());
+
+        for prefab in &mut self.prefabs {
+            prefab.resolve_parts();
+        }
+    }
+}
+
+// ----------------------------------------------
+// PrefabConfigs Global Singleton
+// ----------------------------------------------
+
+engine::configurations! { PREFAB_CONFIGS_SINGLETON, PrefabConfigs, "prefabs/configs" }
@@ -3,20 +3,35 @@
 use bitflags::bitflags;
 use smallvec::SmallVec;
 
+#[cfg(feature = "desktop")]
+use rayon::prelude::*;
+
 use common::{
     Color,
     Vec2,
     Rect,
     constants::*,
     mem::RawPtr,
-    coords::{self, CellRange, IsoPointF32, WorldToScreenTransform},
+    coords::{self, Cell, CellRange, IsoPointF32, WorldToScreenTransform},
 };
 use engine::{
-    render::{RenderSystem, debug::DebugDraw},
+    render::{DrawIndex, RenderSystem, debug::DebugDraw},
     ui::{self, UiSystem},
 };
 
-use super::{Tile, TileDepthSortOverride, TileFlags, TileKind, TileMap, TileMapLayerKind, road};
+use super::{
+    Tile,
+    TileDepthSortOverride,
+    TileFlags,
+    TileKind,
+    TileMap,
+    TileMapLayer,
+    TileMapLayerKind,
+    chunks::{TERRAIN_CHUNK_OVERLAY_FLAGS, TerrainChunkCache},
+    color_palette::ColorPalette,
+    heatmap::HeatmapOverlays,
+    road,
+};
 use crate::{debug, pathfind::{Node, NodeKind as PathNodeKind}};
 
 // ----------------------------------------------
@@ -27,6 +42,12 @@ pub const HIGHLIGHT_TILE_COLOR: Color = Color::new(0.76, 0.96, 0.39, 1.0); // li
 pub const INVALID_TILE_COLOR:   Color = Color::new(0.95, 0.60, 0.60, 1.0); // light red
 pub const SELECTION_RECT_COLOR: Color = Color::new(0.7,  0.2,  0.2,  1.0); // red-ish
 
+pub const HIGHLIGHT_OUTLINE_COLOR: Color = Color::new(0.55, 0.95, 0.20, 1.0); // green
+pub const INVALID_OUTLINE_COLOR:   Color = Color::new(0.95, 0.25, 0.25, 1.0); // red
+
+pub const SELECTED_TILE_COLOR:    Color = Color::new(0.98, 0.90, 0.55, 1.0); // light gold
+pub const SELECTED_OUTLINE_COLOR: Color = Color::new(1.0,  0.80, 0.10, 1.0); // gold
+
 pub const DEFAULT_GRID_COLOR:   Color = Color::white();
 pub const HIGHLIGHT_GRID_COLOR: Color = Color::green();
 pub const INVALID_GRID_COLOR:   Color = Color::red();
@@ -34,6 +55,10 @@ pub const INVALID_GRID_COLOR:   Color = Color::red();
 pub const MIN_GRID_LINE_THICKNESS: f32 = 0.5;
 pub const MAX_GRID_LINE_THICKNESS: f32 = 20.0;
 
+pub const HATCH_PATTERN_COLOR: Color = Color::new(0.0, 0.0, 0.0, 0.6);
+pub const HATCH_PATTERN_STRIPES: u32 = 4; // Stripes drawn = HATCH_PATTERN_STRIPES - 1.
+pub const HATCH_PATTERN_THICKNESS: f32 = 1.5;
+
 // ----------------------------------------------
 // TileMapRenderFlags
 // ----------------------------------------------
@@ -72,6 +97,70 @@ bitflags! {
         const DrawVegetationTileDebug = 1 << 13;
         const DrawBlockersTileDebug   = 1 << 14;
         const DrawSearchGraphDebug    = 1 << 15;
+        const DrawNodeGlyphsDebug     = 1 << 16;
+
+        // Highlighted/Invalidated objects get a screen-space outline around
+        // their sprite silhouette instead of a flat color tint.
+        const DrawSelectionOutline = 1 << 17;
+
+        // Terrain draws from per-chunk cached GPU meshes, rebuilt only when
+        // a tile in the chunk changes. Ignored by the terrain debug views.
+        const CacheTerrainChunks = 1 << 18;
+
+        // Visible tiles are collected and depth sorted on worker threads before
+        // the (serial) draw pass. Desktop only; ignored on the Web build.
+        const ParallelTileGather = 1 << 19;
+
+        // Accessibility: highlights and heatmaps use ColorPalette::ColorBlindSafe.
+        const ColorBlindPalette = 1 << 20;
+
+        // Accessibility: invalid placement and heatmap danger zones (e.g. high fire risk)
+        // are also marked with diagonal stripes, so they don't rely on color alone.
+        const DrawHatchPatterns = 1 << 21;
+    }
+}
+
+// ----------------------------------------------
+// NodeGlyphsDebugMask
+// ----------------------------------------------
+
+// Which search graph NodeKind bits and TileFlags the node glyphs
+// debug overlay prints per cell (TileMapRenderFlags::DrawNodeGlyphsDebug).
+#[derive(Copy, Clone)]
+pub struct NodeGlyphsDebugMask {
+    pub node_kinds: PathNodeKind,
+    pub tile_flags: TileFlags,
+}
+
+impl NodeGlyphsDebugMask {
+    // Tile flags that have a NodeKind counterpart in the search graph.
+    pub const TILE_FLAGS: TileFlags = TileFlags::BuildingRoadLink.union(TileFlags::SettlersSpawnPoint);
+}
+
+// Single letter per NodeKind bit. Drawn with the NodeKind debug color.
+pub const NODE_KIND_DEBUG_GLYPHS: [(PathNodeKind, &str); 11] = [
+    (PathNodeKind::EmptyLand,          "E"),
+    (PathNodeKind::Road,               "R"),
+    (PathNodeKind::Water,              "W"),
+    (PathNodeKind::Building,           "B"),
+    (PathNodeKind::BuildingAccess,     "A"),
+    (PathNodeKind::BuildingRoadLink,   "L"),
+    (PathNodeKind::VacantLot,          "V"),
+    (PathNodeKind::SettlersSpawnPoint, "S"),
+    (PathNodeKind::Rocks,              "K"),
+    (PathNodeKind::Vegetation,         "G"),
+    (PathNodeKind::HarvestableTree,    "T"),
+];
+
+// Lower case letter per TileFlag, paired with the NodeKind bit the graph should mirror it with.
+pub const TILE_FLAG_DEBUG_GLYPHS: [(TileFlags, PathNodeKind, &str); 2] = [
+    (TileFlags::BuildingRoadLink,   PathNodeKind::BuildingRoadLink,   "l"),
+    (TileFlags::SettlersSpawnPoint, PathNodeKind::SettlersSpawnPoint, "s"),
+];
+
+impl Default for NodeGlyphsDebugMask {
+    fn default() -> Self {
+        Self { node_kinds: PathNodeKind::all(), tile_flags: Self::TILE_FLAGS }
     }
 }
 
@@ -86,12 +175,16 @@ pub struct TileMapRenderStats {
     pub tiles_drawn_highlighted: u32,
     pub tiles_drawn_invalidated: u32,
     pub tile_sort_list_len: u32,
+    pub terrain_chunks_drawn: u32,
+    pub terrain_chunks_rebuilt: u32,
 
     // Peaks for the whole run:
     pub peak_tiles_drawn: u32,
     pub peak_tiles_drawn_highlighted: u32,
     pub peak_tiles_drawn_invalidated: u32,
     pub peak_tile_sort_list_len: u32,
+    pub peak_terrain_chunks_drawn: u32,
+    pub peak_terrain_chunks_rebuilt: u32,
 }
 
 // ----------------------------------------------
@@ -101,8 +194,11 @@ pub struct TileMapRenderStats {
 pub struct TileMapRenderer {
     grid_color: Color,
     grid_line_thickness: f32,
+    node_glyphs_debug_mask: NodeGlyphsDebugMask,
     stats: TileMapRenderStats,
     temp_tile_sort_list: Vec<TileDrawListEntry>, // For z-sorting.
+    temp_terrain_draw_list: Vec<TileDrawListEntry>, // For parallel gathering.
+    terrain_chunk_cache: TerrainChunkCache,
 }
 
 impl Default for TileMapRenderer {
@@ -116,8 +212,11 @@ impl TileMapRenderer {
         Self {
             grid_color,
             grid_line_thickness: grid_line_thickness.clamp(MIN_GRID_LINE_THICKNESS, MAX_GRID_LINE_THICKNESS),
+            node_glyphs_debug_mask: NodeGlyphsDebugMask::default(),
             stats: TileMapRenderStats::default(),
             temp_tile_sort_list: Vec::with_capacity(512),
+            temp_terrain_draw_list: Vec::with_capacity(512),
+            terrain_chunk_cache: TerrainChunkCache::default(),
         }
     }
 
@@ -137,10 +236,25 @@ impl TileMapRenderer {
         self.grid_line_thickness
     }
 
+    pub fn set_node_glyphs_debug_mask(&mut self, mask: NodeGlyphsDebugMask) {
+        self.node_glyphs_debug_mask = mask;
+    }
+
+    pub fn node_glyphs_debug_mask(&self) -> NodeGlyphsDebugMask {
+        self.node_glyphs_debug_mask
+    }
+
     pub fn stats(&self) -> &TileMapRenderStats {
         &self.stats
     }
 
+    // Releases the GPU meshes of the cached terrain chunks. They are recreated on demand.
+    pub fn clear_terrain_chunk_cache(&mut self, render_sys: &mut RenderSystem) {
+        self.terrain_chunk_cache.clear(render_sys);
+    }
+
+    // `visible_range` is in view cells, i.e. after applying the tile map view rotation.
+    // Each layer pass maps view cells back to the unrotated map cells for tile lookup.
     pub fn draw_map(
         &mut self,
         render_sys: &mut RenderSystem,
@@ -155,9 +269,12 @@ impl TileMapRenderer {
 
         self.draw_terrain_layer(render_sys, debug_draw, ui_sys, tile_map, transform, visible_range, flags);
 
+        // Heatmap overlay sits on the terrain, under grid and objects.
+        self.draw_heatmap_overlay(render_sys, tile_map, transform, visible_range, flags);
+
         if flags.contains(TileMapRenderFlags::DrawGrid) && !flags.contains(TileMapRenderFlags::DrawGridIgnoreDepth) {
             // Draw the grid now so that lines will be on top of the terrain but not on top of buildings.
-            self.draw_isometric_grid(render_sys, tile_map, transform, visible_range);
+            self.draw_isometric_grid(render_sys, tile_map, transform, visible_range, flags);
         } else if flags.contains(TileMapRenderFlags::DrawSearchGraphDebug) {
             // Base terrain search graph debug pass draws with respect to scene depth.
             self.draw_search_graph_debug_grid(render_sys, ui_sys, tile_map, transform, visible_range, TileMapLayerKind::Terrain);
@@ -168,12 +285,17 @@ impl TileMapRenderer {
         if flags.contains(TileMapRenderFlags::DrawGridIgnoreDepth) {
             // Allow grid lines to draw later and effectively bypass the draw order
             // and appear on top of everything else (useful for debugging).
-            self.draw_isometric_grid(render_sys, tile_map, transform, visible_range);
+            self.draw_isometric_grid(render_sys, tile_map, transform, visible_range, flags);
         } else if flags.contains(TileMapRenderFlags::DrawSearchGraphDebug) {
             // Search graph debug objects pass draws on top of everything else.
             self.draw_search_graph_debug_grid(render_sys, ui_sys, tile_map, transform, visible_range, TileMapLayerKind::Objects);
         }
 
+        if flags.contains(TileMapRenderFlags::DrawNodeGlyphsDebug) {
+            // Glyphs are text overlays, so they always draw on top of everything else.
+            self.draw_node_glyphs_debug(render_sys, ui_sys, tile_map, transform, visible_range);
+        }
+
         self.update_stats();
     }
 
@@ -198,25 +320,89 @@ impl TileMapRenderer {
         debug_assert!(terrain.size_in_cells() == tile_map.size_in_cells());
         debug_assert!(objects.size_in_cells() == tile_map.size_in_cells());
 
+        let use_chunk_cache = flags.contains(TileMapRenderFlags::CacheTerrainChunks)
+            && !flags.intersects(TileMapRenderFlags::DrawTerrainTileDebug | TileMapRenderFlags::DrawDebugBounds);
+
+        if use_chunk_cache {
+            // NOTE: Chunk meshes always include terrain under objects,
+            // so CullOccludedTerrainTiles only applies to the per-tile path.
+            let stats = &mut self.stats;
+            let (chunks_drawn, chunks_rebuilt, sprites_drawn) = self.terrain_chunk_cache.draw(
+                render_sys,
+                tile_map,
+                transform,
+                visible_range,
+                |render_sys, chunk_cells, dynamic_cells| {
+                    // Tiles not baked into the chunk mesh and tiles with per-frame overlays draw on top.
+                    for &cell in dynamic_cells {
+                        if let Some(tile) = terrain.try_tile(cell) {
+                            Self::draw_tile(render_sys, debug_draw, stats, ui_sys, transform, tile, tile_map, flags);
+                        }
+                    }
+
+                    let view_cells = tile_map.rotation().rotate_cell_range(chunk_cells, tile_map.size_in_cells());
+                    for view_cell in view_cells.iter_rev() {
+                        let cell = tile_map.unrotate_cell(view_cell);
+                        if let Some(tile) = terrain.try_tile(cell) {
+                            if tile.has_flags(TERRAIN_CHUNK_OVERLAY_FLAGS) && !dynamic_cells.contains(&cell) {
+                                Self::draw_tile(render_sys, debug_draw, stats, ui_sys, transform, tile, tile_map, flags);
+                            }
+                        }
+                    }
+                },
+            );
+
+            self.stats.tiles_drawn += sprites_drawn;
+            self.stats.terrain_chunks_drawn += chunks_drawn;
+            self.stats.terrain_chunks_rebuilt += chunks_rebuilt;
+            return;
+        }
+
         let cull_occluded_terrain = flags.intersects(TileMapRenderFlags::CullOccludedTerrainTiles);
 
-        for cell in visible_range.iter_rev() {
-            if let Some(tile) = terrain.try_tile(cell) {
-                // Terrain tiles size is constrained. Sanity check it:
-                debug_assert!(tile.is(TileKind::Terrain) && tile.logical_size() == BASE_TILE_SIZE_I32);
+        if flags.contains(TileMapRenderFlags::ParallelTileGather) {
+            debug_assert!(self.temp_terrain_draw_list.is_empty());
+            self.gather_terrain_tiles_parallel(tile_map, visible_range, cull_occluded_terrain);
 
-                // As an optimization, skip drawing terrain tile if fully occluded by any object.
-                if cull_occluded_terrain && let Some(object) = objects.try_tile(cell) {
-                    if object.has_flags(TileFlags::OccludesTerrain) {
-                        continue;
-                    }
-                }
+            for entry in &self.temp_terrain_draw_list {
+                Self::draw_tile(render_sys, debug_draw, &mut self.stats, ui_sys, transform, entry.tile(), tile_map, flags);
+            }
+
+            self.temp_terrain_draw_list.clear();
+            return;
+        }
 
+        for view_cell in visible_range.iter_rev() {
+            let cell = tile_map.unrotate_cell(view_cell);
+            if let Some(tile) = Self::visible_terrain_tile(terrain, objects, cell, cull_occluded_terrain) {
                 Self::draw_tile(render_sys, debug_draw, &mut self.stats, ui_sys, transform, tile, tile_map, flags);
             }
         }
     }
 
+    // Terrain tile to draw at `cell`, if any. As an optimization,
+    // tiles fully occluded by an object may be skipped.
+    #[inline]
+    fn visible_terrain_tile<'a>(
+        terrain: &'a TileMapLayer,
+        objects: &TileMapLayer,
+        cell: Cell,
+        cull_occluded_terrain: bool,
+    ) -> Option<&'a Tile> {
+        let tile = terrain.try_tile(cell)?;
+
+        // Terrain tiles size is constrained. Sanity check it:
+        debug_assert!(tile.is(TileKind::Terrain) && tile.logical_size() == BASE_TILE_SIZE_I32);
+
+        if cull_occluded_terrain && let Some(object) = objects.try_tile(cell) {
+            if object.has_flags(TileFlags::OccludesTerrain) {
+                return None;
+            }
+        }
+
+        Some(tile)
+    }
+
     fn draw_objects_layer(
         &mut self,
         render_sys: &mut RenderSystem,
@@ -239,64 +425,228 @@ impl TileMapRenderer {
         debug_assert!(objects.size_in_cells() == tile_map.size_in_cells());
         debug_assert!(self.temp_tile_sort_list.is_empty());
 
-        let mut try_add_to_sort_list = |tile: &Tile| {
-            fn should_draw(tile: &Tile, flags: TileMapRenderFlags) -> bool {
-                !tile.is(TileKind::Blocker)    &&
-                (tile.is(TileKind::Building)   && flags.contains(TileMapRenderFlags::DrawBuildings)) ||
-                (tile.is(TileKind::Unit)       && flags.contains(TileMapRenderFlags::DrawUnits))     ||
-                (tile.is(TileKind::Rocks)      && flags.contains(TileMapRenderFlags::DrawProps))     ||
-                (tile.is(TileKind::Vegetation) && flags.contains(TileMapRenderFlags::DrawVegetation))
+        let mut debug_draw_blocker_tile = |view_cell, tile: &Tile| {
+            // Debug display for blocker tiles:
+            let tile_iso_pos = coords::cell_to_iso(view_cell);
+            let tile_screen_rect = coords::iso_to_screen_rect(tile_iso_pos, BASE_TILE_SIZE_I32, transform);
+            debug::utils::draw_tile_debug(debug_draw, ui_sys, tile_screen_rect, transform, tile, flags);
+        };
+
+        if flags.contains(TileMapRenderFlags::ParallelTileGather) {
+            self.gather_object_tiles_parallel(tile_map, transform, visible_range, flags);
+
+            // Blockers flagged for debug display are gathered too, so they can be drawn here on the main thread.
+            self.temp_tile_sort_list.retain(|entry| {
+                let tile = entry.tile();
+                if tile.is(TileKind::Blocker) {
+                    debug_draw_blocker_tile(tile_map.rotate_cell(tile.actual_base_cell()), tile);
+                    return false;
+                }
+                true
+            });
+        } else {
+            // Drawing in reverse order (bottom to top) is required to ensure
+            // buildings with the same Z-sort value don't overlap in weird ways.
+            for view_cell in visible_range.iter_rev() {
+                if let Some(tile) = objects.try_tile(tile_map.unrotate_cell(view_cell)) {
+                    if Self::should_draw_blocker_debug(tile, flags) {
+                        debug_draw_blocker_tile(view_cell, tile);
+                    } else {
+                        Self::push_object_tiles(&mut self.temp_tile_sort_list, tile, tile_map, transform, flags);
+                    }
+                }
             }
 
-            if should_draw(tile, flags) {
-                self.temp_tile_sort_list.push(TileDrawListEntry::new(tile, transform));
+            self.temp_tile_sort_list.sort_by(TileDrawListEntry::compare);
+        }
+
+        for entry in &self.temp_tile_sort_list {
+            let tile = entry.tile();
+            debug_assert!(tile.is(TileKind::Object));
+
+            Self::draw_tile(render_sys, debug_draw, &mut self.stats, ui_sys, transform, tile, tile_map, flags);
+        }
 
-                // Push stacked chained tiles into the list so they will sort.
-                tile_map.visit_next_tiles(tile, |next_tile| {
-                    if should_draw(next_tile, flags) {
-                        self.temp_tile_sort_list.push(TileDrawListEntry::new(next_tile, transform));
+        self.stats.tile_sort_list_len += self.temp_tile_sort_list.len() as u32;
+        self.temp_tile_sort_list.clear();
+    }
+
+    #[inline]
+    fn should_draw_object(tile: &Tile, flags: TileMapRenderFlags) -> bool {
+        !tile.is(TileKind::Blocker)    &&
+        (tile.is(TileKind::Building)   && flags.contains(TileMapRenderFlags::DrawBuildings)) ||
+        (tile.is(TileKind::Unit)       && flags.contains(TileMapRenderFlags::DrawUnits))     ||
+        (tile.is(TileKind::Rocks)      && flags.contains(TileMapRenderFlags::DrawProps))     ||
+        (tile.is(TileKind::Vegetation) && flags.contains(TileMapRenderFlags::DrawVegetation))
+    }
+
+    #[inline]
+    fn should_draw_blocker_debug(tile: &Tile, flags: TileMapRenderFlags) -> bool {
+        tile.is(TileKind::Blocker)
+            && (tile.has_flags(TileFlags::DrawBlockerInfo) || flags.contains(TileMapRenderFlags::DrawBlockersTileDebug))
+    }
+
+    fn push_object_tiles(
+        draw_list: &mut Vec<TileDrawListEntry>,
+        tile: &Tile,
+        tile_map: &TileMap,
+        transform: WorldToScreenTransform,
+        flags: TileMapRenderFlags,
+    ) {
+        if Self::should_draw_object(tile, flags) {
+            draw_list.push(TileDrawListEntry::new(tile, tile_map, transform));
+
+            // Push stacked chained tiles into the list so they will sort.
+            tile_map.visit_next_tiles(tile, |next_tile| {
+                if Self::should_draw_object(next_tile, flags) {
+                    draw_list.push(TileDrawListEntry::new(next_tile, tile_map, transform));
+                }
+            });
+        }
+    }
+
+    // Gathers rows of visible cells on worker threads. Rows are concatenated back in the
+    // same bottom to top order as the serial CellRange::iter_rev() walk and the sort is
+    // stable, so the resulting draw order is identical to the serial path.
+    #[cfg(feature = "desktop")]
+    fn gather_terrain_tiles_parallel(&mut self, tile_map: &TileMap, visible_range: CellRange, cull_occluded_terrain: bool) {
+        let shared_map = SharedTileMapRef(tile_map);
+        let (start_x, end_x) = (visible_range.start.x, visible_range.end.x);
+
+        let rows = (visible_range.start.y..=visible_range.end.y).into_par_iter().rev();
+        self.temp_terrain_draw_list.par_extend(rows.flat_map_iter(move |y| {
+            let tile_map = shared_map.get();
+            let terrain = tile_map.layer(TileMapLayerKind::Terrain);
+            let objects = tile_map.layer(TileMapLayerKind::Objects);
+
+            let mut row = Vec::with_capacity((end_x - start_x + 1) as usize);
+            for x in (start_x..=end_x).rev() {
+                let cell = tile_map.unrotate_cell(Cell::new(x, y));
+                if let Some(tile) = Self::visible_terrain_tile(terrain, objects, cell, cull_occluded_terrain) {
+                    row.push(TileDrawListEntry::unsorted(tile));
+                }
+            }
+            row
+        }));
+    }
+
+    #[cfg(feature = "desktop")]
+    fn gather_object_tiles_parallel(
+        &mut self,
+        tile_map: &TileMap,
+        transform: WorldToScreenTransform,
+        visible_range: CellRange,
+        flags: TileMapRenderFlags,
+    ) {
+        let shared_map = SharedTileMapRef(tile_map);
+        let (start_x, end_x) = (visible_range.start.x, visible_range.end.x);
+
+        let rows = (visible_range.start.y..=visible_range.end.y).into_par_iter().rev();
+        self.temp_tile_sort_list.par_extend(rows.flat_map_iter(move |y| {
+            let tile_map = shared_map.get();
+            let objects = tile_map.layer(TileMapLayerKind::Objects);
+
+            let mut row = Vec::new();
+            for x in (start_x..=end_x).rev() {
+                if let Some(tile) = objects.try_tile(tile_map.unrotate_cell(Cell::new(x, y))) {
+                    if Self::should_draw_blocker_debug(tile, flags) {
+                        row.push(TileDrawListEntry::unsorted(tile));
+                    } else {
+                        Self::push_object_tiles(&mut row, tile, tile_map, transform, flags);
                     }
-                });
+                }
             }
-        };
+            row
+        }));
 
-        let mut debug_draw_blocker_tile = |cell, tile: &Tile| -> bool {
-            let should_draw = {
-                tile.is(TileKind::Blocker)
-                    && (tile.has_flags(TileFlags::DrawBlockerInfo)
-                        || flags.contains(TileMapRenderFlags::DrawBlockersTileDebug))
-            };
-
-            if should_draw {
-                // Debug display for blocker tiles:
-                let tile_iso_pos = coords::cell_to_iso(cell);
-                let tile_screen_rect = coords::iso_to_screen_rect(tile_iso_pos, BASE_TILE_SIZE_I32, transform);
-                debug::utils::draw_tile_debug(debug_draw, ui_sys, tile_screen_rect, transform, tile, flags);
+        self.temp_tile_sort_list.par_sort_by(TileDrawListEntry::compare);
+    }
+
+    // No worker threads on Web/WASM; same results as the serial path.
+    #[cfg(feature = "web")]
+    fn gather_terrain_tiles_parallel(&mut self, tile_map: &TileMap, visible_range: CellRange, cull_occluded_terrain: bool) {
+        let terrain = tile_map.layer(TileMapLayerKind::Terrain);
+        let objects = tile_map.layer(TileMapLayerKind::Objects);
+
+        for view_cell in visible_range.iter_rev() {
+            let cell = tile_map.unrotate_cell(view_cell);
+            if let Some(tile) = Self::visible_terrain_tile(terrain, objects, cell, cull_occluded_terrain) {
+                self.temp_terrain_draw_list.push(TileDrawListEntry::unsorted(tile));
             }
-            should_draw
-        };
+        }
+    }
 
-        // Drawing in reverse order (bottom to top) is required to ensure
-        // buildings with the same Z-sort value don't overlap in weird ways.
-        for cell in visible_range.iter_rev() {
-            if let Some(tile) = objects.try_tile(cell) {
-                if !debug_draw_blocker_tile(cell, tile) {
-                    try_add_to_sort_list(tile);
+    #[cfg(feature = "web")]
+    fn gather_object_tiles_parallel(
+        &mut self,
+        tile_map: &TileMap,
+        transform: WorldToScreenTransform,
+        visible_range: CellRange,
+        flags: TileMapRenderFlags,
+    ) {
+        let objects = tile_map.layer(TileMapLayerKind::Objects);
+
+        for view_cell in visible_range.iter_rev() {
+            if let Some(tile) = objects.try_tile(tile_map.unrotate_cell(view_cell)) {
+                if Self::should_draw_blocker_debug(tile, flags) {
+                    self.temp_tile_sort_list.push(TileDrawListEntry::unsorted(tile));
+                } else {
+                    Self::push_object_tiles(&mut self.temp_tile_sort_list, tile, tile_map, transform, flags);
                 }
             }
         }
 
         self.temp_tile_sort_list.sort_by(TileDrawListEntry::compare);
+    }
 
-        for entry in &self.temp_tile_sort_list {
-            let tile = entry.tile();
-            debug_assert!(tile.is(TileKind::Object));
+    fn draw_heatmap_overlay(
+        &self,
+        render_sys: &mut RenderSystem,
+        tile_map: &TileMap,
+        transform: WorldToScreenTransform,
+        visible_range: CellRange,
+        flags: TileMapRenderFlags,
+    ) {
+        let overlays = HeatmapOverlays::get();
+        let Some(layer) = overlays.active_layer() else {
+            return;
+        };
 
-            Self::draw_tile(render_sys, debug_draw, &mut self.stats, ui_sys, transform, tile, tile_map, flags);
+        let viewport = render_sys.viewport();
+        let opacity = overlays.opacity();
+        let palette = ColorPalette::from_render_flags(flags);
+
+        let danger_threshold = layer.danger_threshold.filter(|_| flags.contains(TileMapRenderFlags::DrawHatchPatterns));
+        let mut danger_cells = SmallVec::<[[Vec2; 4]; 64]>::new();
+
+        // Two triangles per diamond.
+        const INDICES: [DrawIndex; 6] = [0, 1, 2, 2, 3, 0];
+
+        for view_cell in &visible_range {
+            let value = layer.source.value_at(tile_map.unrotate_cell(view_cell));
+            if value <= 0.0 {
+                continue; // Leave cells without data untinted.
+            }
+
+            let points = coords::cell_to_screen_diamond_points(view_cell, BASE_TILE_SIZE_I32, transform);
+            if Self::is_fully_offscreen(&viewport, &points) {
+                continue; // Cull if fully offscreen.
+            }
+
+            let mut color = layer.gradient.sample(palette, value);
+            color.a *= opacity;
+
+            render_sys.draw_colored_indexed_triangles(&points, &INDICES, color);
+
+            if danger_threshold.is_some_and(|threshold| value >= threshold) {
+                danger_cells.push(points);
+            }
         }
 
-        self.stats.tile_sort_list_len += self.temp_tile_sort_list.len() as u32;
-        self.temp_tile_sort_list.clear();
+        // Stripes on top of all the colored cells:
+        for points in &danger_cells {
+            Self::draw_hatch_pattern(render_sys, points, transform);
+        }
     }
 
     fn draw_isometric_grid(
@@ -305,8 +655,10 @@ impl TileMapRenderer {
         tile_map: &TileMap,
         transform: WorldToScreenTransform,
         visible_range: CellRange,
+        flags: TileMapRenderFlags,
     ) {
         let viewport = render_sys.viewport();
+        let palette = ColorPalette::from_render_flags(flags);
 
         let terrain_layer = tile_map.layer(TileMapLayerKind::Terrain);
         let line_thickness = self.grid_line_thickness * transform.scaling;
@@ -314,15 +666,15 @@ impl TileMapRenderer {
         let mut highlighted_cells = SmallVec::<[[Vec2; 4]; 64]>::new();
         let mut invalidated_cells = SmallVec::<[[Vec2; 4]; 64]>::new();
 
-        for cell in &visible_range {
-            let points = coords::cell_to_screen_diamond_points(cell, BASE_TILE_SIZE_I32, transform);
+        for view_cell in &visible_range {
+            let points = coords::cell_to_screen_diamond_points(view_cell, BASE_TILE_SIZE_I32, transform);
             if Self::is_fully_offscreen(&viewport, &points) {
                 continue; // Cull if fully offscreen.
             }
 
             // Save highlighted grid cells for drawing at the end,
             // so they display in the correct order.
-            if let Some(tile) = terrain_layer.try_tile(cell) {
+            if let Some(tile) = terrain_layer.try_tile(tile_map.unrotate_cell(view_cell)) {
                 if tile.has_flags(TileFlags::Highlighted) {
                     highlighted_cells.push(points);
                     continue;
@@ -340,11 +692,24 @@ impl TileMapRenderer {
 
         // Highlighted on top:
         for points in &highlighted_cells {
-            render_sys.draw_polyline_with_thickness(points, HIGHLIGHT_GRID_COLOR, line_thickness, true);
+            render_sys.draw_polyline_with_thickness(points, palette.highlight_grid_color(), line_thickness, true);
         }
 
         for points in &invalidated_cells {
-            render_sys.draw_polyline_with_thickness(points, INVALID_GRID_COLOR, line_thickness, true);
+            render_sys.draw_polyline_with_thickness(points, palette.invalid_grid_color(), line_thickness, true);
+        }
+    }
+
+    // Diagonal stripes across a cell diamond, parallel to its top-right edge.
+    fn draw_hatch_pattern(render_sys: &mut RenderSystem, points: &[Vec2; 4], transform: WorldToScreenTransform) {
+        let [top, right, bottom, left] = *points;
+        let thickness = HATCH_PATTERN_THICKNESS * transform.scaling;
+
+        for stripe in 1..HATCH_PATTERN_STRIPES {
+            let t = stripe as f32 / HATCH_PATTERN_STRIPES as f32;
+            let from = common::lerp(top, left, t);
+            let to = common::lerp(right, bottom, t);
+            render_sys.draw_line_with_thickness(from, to, HATCH_PATTERN_COLOR, thickness);
         }
     }
 
@@ -365,12 +730,12 @@ impl TileMapRenderer {
                 let line_thickness = 2.0 * transform.scaling;
                 const OPACITY: f32 = 0.7;
 
-                for cell in &visible_range {
-                    let path_kind = graph.node_kind(Node::new(cell))
+                for view_cell in &visible_range {
+                    let path_kind = graph.node_kind(Node::new(tile_map.unrotate_cell(view_cell)))
                         .unwrap_or(PathNodeKind::empty());
 
                     if path_kind.intersects(PathNodeKind::Water) {
-                        let points = coords::cell_to_screen_diamond_points(cell, BASE_TILE_SIZE_I32, transform);
+                        let points = coords::cell_to_screen_diamond_points(view_cell, BASE_TILE_SIZE_I32, transform);
                         if Self::is_fully_offscreen(&viewport, &points) {
                             continue; // Cull if fully offscreen.
                         }
@@ -394,21 +759,21 @@ impl TileMapRenderer {
 
                 let mut nodes = Vec::with_capacity((visible_range.width() * visible_range.height()) as usize);
 
-                for cell in &visible_range {
-                    let path_kind = graph.node_kind(Node::new(cell))
+                for view_cell in &visible_range {
+                    let path_kind = graph.node_kind(Node::new(tile_map.unrotate_cell(view_cell)))
                         .unwrap_or(PathNodeKind::empty());
 
                     if !path_kind.intersects(PathNodeKind::EmptyLand | PathNodeKind::Water)
                         || path_kind.intersects(PathNodeKind::BuildingAccess | PathNodeKind::SettlersSpawnPoint)
                     {
-                        nodes.push((cell, path_kind));
+                        nodes.push((view_cell, path_kind));
                     }
                 }
 
                 nodes.sort_by(|a, b| b.1.cmp(&a.1));
 
-                for (cell, path_kind) in nodes {
-                    let points = coords::cell_to_screen_diamond_points(cell, BASE_TILE_SIZE_I32, transform);
+                for (view_cell, path_kind) in nodes {
+                    let points = coords::cell_to_screen_diamond_points(view_cell, BASE_TILE_SIZE_I32, transform);
                     if Self::is_fully_offscreen(&viewport, &points) {
                         continue; // Cull if fully offscreen.
                     }
@@ -422,6 +787,82 @@ impl TileMapRenderer {
         }
     }
 
+    // Prints a compact glyph string over each visible cell: upper case letters for the search
+    // graph NodeKind bits, followed by lower case letters for the TileFlags set on the tiles.
+    // Tile flags the graph node disagrees with (flag set on the tile but not in the graph or
+    // vice versa) are printed in red with a trailing '!', so desyncs stand out at a glance.
+    fn draw_node_glyphs_debug(
+        &self,
+        render_sys: &mut RenderSystem,
+        ui_sys: &UiSystem,
+        tile_map: &TileMap,
+        transform: WorldToScreenTransform,
+        visible_range: CellRange,
+    ) {
+        let graph = tile_map.graph();
+        let viewport = render_sys.viewport();
+        let mask = self.node_glyphs_debug_mask;
+
+        let ui = ui_sys.ui();
+        let draw_list = ui.get_background_draw_list();
+
+        let to_im_color = |color: Color| imgui::ImColor32::from_rgba_f32s(color.r, color.g, color.b, color.a);
+        let mismatch_color = to_im_color(Color::red());
+
+        for view_cell in &visible_range {
+            let points = coords::cell_to_screen_diamond_points(view_cell, BASE_TILE_SIZE_I32, transform);
+            if Self::is_fully_offscreen(&viewport, &points) {
+                continue; // Cull if fully offscreen.
+            }
+
+            let cell = tile_map.unrotate_cell(view_cell);
+            let node_kind = graph.node_kind(Node::new(cell)).unwrap_or(PathNodeKind::empty());
+
+            let mut tile_flags = TileFlags::empty();
+            for layer_kind in [TileMapLayerKind::Terrain, TileMapLayerKind::Objects] {
+                if let Some(tile) = tile_map.try_tile_from_layer(cell, layer_kind) {
+                    tile_flags |= tile.flags() & mask.tile_flags;
+                }
+            }
+
+            let mut glyphs = SmallVec::<[(&str, imgui::ImColor32); 16]>::new();
+
+            for (kind, glyph) in NODE_KIND_DEBUG_GLYPHS {
+                if mask.node_kinds.intersects(kind) && node_kind.intersects(kind) {
+                    glyphs.push((glyph, to_im_color(kind.debug_color())));
+                }
+            }
+
+            for (flag, kind, glyph) in TILE_FLAG_DEBUG_GLYPHS {
+                if !mask.tile_flags.intersects(flag) {
+                    continue;
+                }
+
+                let has_flag = tile_flags.intersects(flag);
+                if has_flag != node_kind.intersects(kind) {
+                    glyphs.push((glyph, mismatch_color));
+                    glyphs.push(("!", mismatch_color));
+                } else if has_flag {
+                    glyphs.push((glyph, to_im_color(Color::white())));
+                }
+            }
+
+            if glyphs.is_empty() {
+                continue;
+            }
+
+            // Center the glyph string over the cell's diamond.
+            let center = ui_sys.screen_to_ui((points[0] + points[1] + points[2] + points[3]) * 0.25);
+            let total_width: f32 = glyphs.iter().map(|(glyph, _)| ui.calc_text_size(glyph)[0]).sum();
+            let mut pos = Vec2::new(center.x - (total_width * 0.5), center.y - (ui.text_line_height() * 0.5));
+
+            for (glyph, color) in glyphs {
+                draw_list.add_text(pos.to_array(), color, glyph);
+                pos.x += ui.calc_text_size(glyph)[0];
+            }
+        }
+    }
+
     // Returns true only if all points are offscreen.
     #[inline]
     fn is_fully_offscreen(viewport: &Rect, points: &[Vec2; 4]) -> bool {
@@ -447,33 +888,63 @@ impl TileMapRenderer {
         debug_assert!(tile.is_valid());
         debug_assert!(!tile.is(TileKind::Blocker));
 
-        let tile_screen_rect = tile.screen_rect(transform, true);
+        let rotation = tile_map.rotation();
+        let tile_screen_rect = tile_map.tile_view_screen_rect(tile, transform);
+        let palette = ColorPalette::from_render_flags(flags);
 
         if !tile.has_flags(TileFlags::Hidden) {
-            if let Some(tile_sprite) = tile.anim_frame_tex_info() {
+            if let Some(tile_sprite) = tile.view_anim_frame_tex_info(rotation) {
+                let tex_coords = &tile_sprite.coords;
+                let texture = tile_sprite.texture;
+
+                // Only objects are outlined; terrain tiles are always tinted.
+                let outline_selection =
+                    flags.contains(TileMapRenderFlags::DrawSelectionOutline) && tile.is(TileKind::Object);
+
                 let highlight_color = {
                     if tile.has_flags(TileFlags::Highlighted) {
                         stats.tiles_drawn_highlighted += 1;
-                        HIGHLIGHT_TILE_COLOR
+                        Some((palette.highlight_tile_color(), palette.highlight_outline_color()))
                     } else if tile.has_flags(TileFlags::Invalidated) {
                         stats.tiles_drawn_invalidated += 1;
-                        INVALID_TILE_COLOR
+                        Some((palette.invalid_tile_color(), palette.invalid_outline_color()))
+                    } else if tile.has_flags(TileFlags::Selected) {
+                        // Hover highlights take precedence, so hovering a selected unit still reads as hover.
+                        Some((palette.selected_tile_color(), palette.selected_outline_color()))
                     } else {
-                        Color::white()
+                        None
+                    }
+                };
+
+                // The outline mask uses the same sprite rect and current animation frame, so
+                // the outline follows animated units and covers the full footprint of multi-cell
+                // buildings (drawn as a single sprite, blockers are skipped).
+                let color = match highlight_color {
+                    Some((_, outline_color)) if outline_selection => {
+                        render_sys.draw_outline_mask_rect(tile_screen_rect, tex_coords, texture, outline_color);
+                        tile.tint_color()
                     }
+                    Some((tint_color, _)) => tile.tint_color() * tint_color,
+                    None => tile.tint_color(),
                 };
 
                 // Standard render:
-                let tex_coords = &tile_sprite.coords;
-                let texture = tile_sprite.texture;
-                let color = tile.tint_color() * highlight_color;
 
-                render_sys.draw_textured_colored_rect(tile_screen_rect, tex_coords, texture, color);
+                render_sys.draw_palette_swapped_rect(tile_screen_rect, tex_coords, texture, color, tile.palette_swap());
                 stats.tiles_drawn += 1;
 
                 // Road placement overlay:
                 if tile.has_flags(TileFlags::DirtRoadPlacement | TileFlags::PavedRoadPlacement) {
-                    Self::draw_road_placement_overlay(render_sys, transform, tile, tile_map);
+                    Self::draw_road_placement_overlay(render_sys, transform, tile, tile_map, palette);
+                }
+
+                // Invalid placement stripes over the tile footprint:
+                if tile.has_flags(TileFlags::Invalidated) && flags.contains(TileMapRenderFlags::DrawHatchPatterns) {
+                    for cell in &tile.cell_range() {
+                        let view_cell = tile_map.rotate_cell(cell);
+                        let points = coords::cell_to_screen_diamond_points(view_cell, BASE_TILE_SIZE_I32, transform);
+                        Self::draw_hatch_pattern(render_sys, &points, transform);
+                    }
                 }
             }
         }
@@ -486,6 +957,7 @@ impl TileMapRenderer {
         transform: WorldToScreenTransform,
         tile: &Tile,
         tile_map: &TileMap,
+        palette: ColorPalette,
     ) {
         let cell = tile.base_cell();
 
@@ -508,20 +980,20 @@ impl TileMapRenderer {
         };
 
         if let Some(anim_set) = tile_def.anim_set_by_index(variation_index, 0) {
-            let tile_sprite = &anim_set.frames[0].tex_info;
+            let tile_sprite = anim_set.frames[0].resolve_tex_info();
             let tex_coords = &tile_sprite.coords;
             let texture = tile_sprite.texture;
 
-            let iso_position = IsoPointF32::from_integer_iso(coords::cell_to_iso(cell));
+            let iso_position = IsoPointF32::from_integer_iso(coords::cell_to_iso(tile_map.rotate_cell(cell)));
             let tile_screen_rect = coords::iso_to_screen_rect_f32(iso_position, tile_def.draw_size, transform);
 
             let mut color = tile_def.color;
             if tile.has_flags(TileFlags::Invalidated) {
-                color *= INVALID_TILE_COLOR;
+                color *= palette.invalid_tile_color();
             }
             color.a *= 0.7;
 
-            render_sys.draw_textured_colored_rect(tile_screen_rect, tex_coords, texture, color);
+            render_sys.draw_palette_swapped_rect(tile_screen_rect, tex_coords, texture, color, tile_def.palette_swap);
         }
     }
 
@@ -531,6 +1003,8 @@ impl TileMapRenderer {
         self.stats.tiles_drawn_highlighted = 0;
         self.stats.tiles_drawn_invalidated = 0;
         self.stats.tile_sort_list_len = 0;
+        self.stats.terrain_chunks_drawn = 0;
+        self.stats.terrain_chunks_rebuilt = 0;
     }
 
     #[inline]
@@ -539,6 +1013,8 @@ impl TileMapRenderer {
         self.stats.peak_tiles_drawn_highlighted = self.stats.tiles_drawn_highlighted.max(self.stats.peak_tiles_drawn_highlighted);
         self.stats.peak_tiles_drawn_invalidated = self.stats.tiles_drawn_invalidated.max(self.stats.peak_tiles_drawn_invalidated);
         self.stats.peak_tile_sort_list_len      = self.stats.tile_sort_list_len.max(self.stats.peak_tile_sort_list_len);
+        self.stats.peak_terrain_chunks_drawn    = self.stats.terrain_chunks_drawn.max(self.stats.peak_terrain_chunks_drawn);
+        self.stats.peak_terrain_chunks_rebuilt  = self.stats.terrain_chunks_rebuilt.max(self.stats.peak_terrain_chunks_rebuilt);
     }
 }
 
@@ -567,13 +1043,14 @@ struct TileDrawListEntry {
 
 impl TileDrawListEntry {
     #[inline]
-    fn new(tile: &Tile, transform: WorldToScreenTransform) -> Self {
+    fn new(tile: &Tile, tile_map: &TileMap, transform: WorldToScreenTransform) -> Self {
         let depth_sort_key = {
             // User defined override?
             match tile.depth_sort_override() {
                 TileDepthSortOverride::None => {
-                    // Compute from tile screen space diamond.
-                    coords::cell_to_screen_diamond_center_y(tile.base_cell(), tile.logical_size(), transform)
+                    // Compute from tile screen space diamond, as seen with the current view rotation.
+                    let view_base_cell = tile.view_base_cell(tile_map.rotation(), tile_map.size_in_cells());
+                    coords::cell_to_screen_diamond_center_y(view_base_cell, tile.logical_size(), transform)
                 }
                 TileDepthSortOverride::Topmost => TILE_DEPTH_SORT_KEY_TOPMOST,
                 TileDepthSortOverride::Bottommost => TILE_DEPTH_SORT_KEY_BOTTOMMOST,
@@ -583,6 +1060,12 @@ impl TileDrawListEntry {
         Self { tile: RawPtr::from_ref(tile), depth_sort_key }
     }
 
+    // Entry for a draw list that is not depth sorted.
+    #[inline]
+    fn unsorted(tile: &Tile) -> Self {
+        Self { tile: RawPtr::from_ref(tile), depth_sort_key: 0.0 }
+    }
+
     #[inline]
     fn compare(a: &TileDrawListEntry, b: &TileDrawListEntry) -> std::cmp::Ordering {
         let a_tile = a.tile();
@@ -591,15 +1074,23 @@ impl TileDrawListEntry {
         a.depth_sort_key
             .partial_cmp(&b.depth_sort_key)
             .unwrap()
-            .then_with(|| {
-                // In case of tie, draw units/props in front of buildings.
-                let a_prio = a_tile.is(TileKind::Unit | TileKind::Vegetation | TileKind::Rocks);
-                let b_prio = b_tile.is(TileKind::Unit | TileKind::Vegetation | TileKind::Rocks);
-                a_prio.cmp(&b_prio)
-            })
+            .then_with(|| Self::tie_break_priority(a_tile).cmp(&Self::tie_break_priority(b_tile)))
             .then_with(|| a_tile.index().cmp(&b_tile.index())) // Fallback to tile pool index if all else equal.
     }
 
+    // In case of tie, draw units/props in front of buildings. Boats and other water
+    // objects sit lower, at the waterline, so they go behind docks and anything on the shore.
+    #[inline]
+    fn tie_break_priority(tile: &Tile) -> u32 {
+        if tile.tile_def().water_object {
+            0
+        } else if tile.is(TileKind::Unit | TileKind::Vegetation | TileKind::Rocks) {
+            2
+        } else {
+            1
+        }
+    }
+
     #[inline(always)]
     fn tile(&self) -> &Tile {
         // SAFETY: This reference only lives for the scope of draw_map().
@@ -609,3 +1100,32 @@ impl TileDrawListEntry {
         &self.tile
     }
 }
+
+// SAFETY: Entries are only created by the draw_map() gather phase and read back on the main
+// thread within the same draw_map() call, while the TileMap is immutably borrowed.
+#[cfg(feature = "desktop")]
+unsafe impl Send for TileDrawListEntry {}
+
+// ----------------------------------------------
+// SharedTileMapRef
+// ----------------------------------------------
+
+// Read-only TileMap reference handed to the parallel gather workers.
+#[cfg(feature = "desktop")]
+#[derive(Copy, Clone)]
+struct SharedTileMapRef<'a>(&'a TileMap);
+
+#[cfg(feature = "desktop")]
+impl<'a> SharedTileMapRef<'a> {
+    #[inline]
+    fn get(self) -> &'a TileMap {
+        self.0
+    }
+}
+
+// SAFETY: Workers only read tiles, and draw_map() holds a shared borrow of the
+// TileMap for the whole gather phase, so nothing can mutate it concurrently.
+#[cfg(feature = "desktop")]
+unsafe impl Send for SharedTileMapRef<'_> {}
+#[cfg(feature = "desktop")]
+unsafe impl Sync for SharedTileMapRef<'_> {}
@@ -171,7 +171,7 @@ fn is_path_valid(tile_map: &TileMap, path: &[Cell]) -> bool {
     path.iter().all(|cell| can_place_road(tile_map, *cell))
 }
 
-fn horizontal_vertical_path(start: Cell, end: Cell) -> Vec<Cell> {
+pub(super) fn horizontal_vertical_path(start: Cell, end: Cell) -> Vec<Cell> {
     let mut path_hv = Vec::new();
 
     // Horizontal leg:
@@ -187,7 +187,7 @@ fn horizontal_vertical_path(start: Cell, end: Cell) -> Vec<Cell> {
     path_hv
 }
 
-fn vertical_horizontal_path(start: Cell, end: Cell) -> Vec<Cell> {
+pub(super) fn vertical_horizontal_path(start: Cell, end: Cell) -> Vec<Cell> {
     let mut path_vh = Vec::new();
 
     for y in range_inclusive(start.y, end.y) {
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "desktop")]
+use rayon::prelude::*;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, ser::Error as _};
+use serde_json::value::RawValue;
+use slab::Slab;
+
+use common::coords::Cell;
+
+use super::Tile;
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Width and height of the map region stored in each save chunk, in cells.
+// A 512x512 map layer is saved as 64 chunk records.
+const SAVE_CHUNK_SIZE: i32 = 64;
+
+// ----------------------------------------------
+// TilePool chunked serialization
+// ----------------------------------------------
+
+// Serializes a TilePool tile slab as one record per map region instead of a single map of all tiles.
+// Chunks are encoded to JSON in parallel, then written out as-is. Tiles link to each other by slab key,
+// so every tile is stored with its key and the slab is rebuilt with the exact same keys on load.
+//
+// Usage: #[serde(with = "save_chunks")]
+
+#[derive(Serialize)]
+struct SaveChunkRef<'a> {
+    region: Cell, // In chunk units, not cells.
+    tiles: Vec<(usize, &'a Tile)>,
+}
+
+#[derive(Deserialize)]
+struct SaveChunk {
+    tiles: Vec<(usize, Tile)>,
+}
+
+// SAFETY: Tiles hold raw pointers back to their owning layer, which makes them !Sync.
+// Those pointers are never serialized, and the TilePool is borrowed immutably for the
+// whole encoding pass, so the workers only ever read the tiles.
+#[cfg(feature = "desktop")]
+unsafe impl Send for SaveChunkRef<'_> {}
+#[cfg(feature = "desktop")]
+unsafe impl Sync for SaveChunkRef<'_> {}
+
+pub fn serialize<S>(slab: &Slab<Tile>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    // Bucket tiles by region. BTreeMap keeps the chunk order deterministic (row major).
+    let mut regions: BTreeMap<(i32, i32), Vec<(usize, &Tile)>> = BTreeMap::new();
+
+    for (key, tile) in slab {
+        // Blockers stay in their own region rather than resolving to their owner building.
+        let cell = tile.actual_base_cell();
+        let region = (cell.y / SAVE_CHUNK_SIZE, cell.x / SAVE_CHUNK_SIZE);
+        regions.entry(region).or_default().push((key, tile));
+    }
+
+    let chunks: Vec<SaveChunkRef> = regions
+        .into_iter()
+        .map(|((y, x), tiles)| SaveChunkRef { region: Cell::new(x, y), tiles })
+        .collect();
+
+    let encoded_chunks = encode_chunks(&chunks).map_err(S::Error::custom)?;
+    encoded_chunks.serialize(serializer)
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Slab<Tile>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let chunks = Vec::<SaveChunk>::deserialize(deserializer)?;
+    Ok(chunks.into_iter().flat_map(|chunk| chunk.tiles).collect())
+}
+
+#[cfg(feature = "desktop")]
+fn encode_chunks(chunks: &[SaveChunkRef]) -> serde_json::Result<Vec<Box<RawValue>>> {
+    // Encode chunks in parallel:
+    chunks.par_iter().map(serde_json::value::to_raw_value).collect()
+}
+
+#[cfg(feature = "web")]
+fn encode_chunks(chunks: &[SaveChunkRef]) -> serde_json::Result<Vec<Box<RawValue>>> {
+    // No worker threads on Web/WASM.
+    chunks.iter().map(serde_json::value::to_raw_value).collect()
+}
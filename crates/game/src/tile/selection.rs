@@ -2,7 +2,7 @@ use common::{
     Rect,
     Size,
     Vec2,
-    coords::{self, Cell, CellRange, WorldToScreenTransform},
+    coords::{self, Cell, CellRange, MapRotation, WorldToScreenTransform},
 };
 use engine::{
     app::input::{InputAction, MouseButton},
@@ -10,6 +10,7 @@ use engine::{
     ui::UiInputEvent,
 };
 use smallvec::SmallVec;
+use strum::IntoEnumIterator;
 
 use super::{
     Tile,
@@ -19,13 +20,22 @@ use super::{
     TileMapLayerKind,
     TileMapLayerMutRefs,
     placement::{self, TilePlacementOp},
-    rendering::SELECTION_RECT_COLOR,
+    rendering::{SELECTED_OUTLINE_COLOR, SELECTION_RECT_COLOR},
 };
 use crate::{
     pathfind::NodeKind as PathNodeKind,
     save_context::{Load, PostLoadContext, Save},
+    unit::UnitId,
 };
 
+// Numbered unit selection groups, assigned with [CTRL]+[1-9] by default (see KeyBindings).
+pub const SELECTION_GROUP_COUNT: usize = 9;
+
+// Cursor travel in screen pixels before a left button press becomes a marquee drag.
+const MIN_MARQUEE_DRAG_DISTANCE: f32 = 8.0;
+
+type UnitIdList = SmallVec<[UnitId; 16]>;
+
 // ----------------------------------------------
 // TileSelection
 // ----------------------------------------------
@@ -38,12 +48,29 @@ pub struct TileSelection {
     left_mouse_button_held: bool,
     valid_placement: bool,
     is_clearing: bool,
+    is_multi_selecting: bool,
     cells: SmallVec<[Cell; 64]>,
+
+    // Tiles picked with [CTRL]+click or [CTRL]+drag for bulk inspection.
+    // Base cell and layer of each tile, independent of the hover highlight cells.
+    multi_selection: SmallVec<[(Cell, TileMapLayerKind); 16]>,
+
+    // In-game unit selection. Outlives reset(), since clicking a tile resets the hover state.
+    marquee_start: Option<Vec2>,
+    marquee_rect: Rect,
+    selected_units: UnitIdList,
+    selection_groups: [UnitIdList; SELECTION_GROUP_COUNT],
 }
 
 impl TileSelection {
     pub fn reset(&mut self) {
-        *self = Self::default();
+        *self = Self {
+            marquee_start: self.marquee_start,
+            marquee_rect: self.marquee_rect,
+            selected_units: std::mem::take(&mut self.selected_units),
+            selection_groups: std::mem::take(&mut self.selection_groups),
+            ..Self::default()
+        };
     }
 
     pub fn has_valid_placement(&self) -> bool {
@@ -99,6 +126,8 @@ impl TileSelection {
                 self.cursor_drag_start = Vec2::zero();
                 self.cursor_drag_start_cell = Cell::invalid();
                 self.left_mouse_button_held = false;
+                self.is_multi_selecting = false;
+                self.marquee_start = None;
                 return UiInputEvent::Handled;
             }
         }
@@ -106,15 +135,20 @@ impl TileSelection {
     }
 
     pub fn draw(&self, render_sys: &mut RenderSystem) {
-        if self.is_selecting_range() && self.is_clearing {
+        if self.is_selecting_range() && (self.is_clearing || self.is_multi_selecting) {
             render_sys.draw_wireframe_rect_with_thickness(self.rect, SELECTION_RECT_COLOR, 1.5);
         }
+
+        if self.is_marquee_dragging() {
+            render_sys.draw_wireframe_rect_with_thickness(self.marquee_rect, SELECTED_OUTLINE_COLOR, 1.5);
+        }
     }
 
     pub fn update(
         &mut self,
         mut layers: TileMapLayerMutRefs,
         map_size_in_cells: Size,
+        rotation: MapRotation,
         cursor_screen_pos: Vec2,
         transform: WorldToScreenTransform,
         placement_op: TilePlacementOp,
@@ -126,6 +160,10 @@ impl TileSelection {
             self.rect = Rect::zero();
         }
 
+        if let Some(marquee_start) = self.marquee_start {
+            self.marquee_rect = Rect::from_extents(marquee_start, cursor_screen_pos);
+        }
+
         // Only using range selection for batch tile clearing/removal.
         self.is_clearing = matches!(placement_op, TilePlacementOp::Clear);
 
@@ -135,10 +173,13 @@ impl TileSelection {
 
             let range = bounds(&self.rect, map_size_in_cells, transform);
 
-            for cell in &range {
+            // Range is in view cells; map back to the unrotated tile map cells.
+            for view_cell in &range {
+                let cell = rotation.unrotate_cell(view_cell, map_size_in_cells);
                 if let Some(base_tile) = layers.get(TileMapLayerKind::Terrain).try_tile(cell) {
-                    let tile_iso_coords = base_tile.iso_coords();
-                    let tile_screen_rect = coords::iso_to_screen_rect(tile_iso_coords, base_tile.logical_size(), transform);
+                    let tile_iso_coords = base_tile.view_iso_coords_f32(rotation, map_size_in_cells);
+                    let tile_screen_rect =
+                        coords::iso_to_screen_rect_f32(tile_iso_coords, base_tile.logical_size(), transform);
 
                     if tile_screen_rect.intersects(&self.rect) {
                         let base_cell = base_tile.base_cell();
@@ -154,7 +195,7 @@ impl TileSelection {
                 // If the cursor is still inside this cell, we're done.
                 // This can happen because the isometric-to-cell conversion
                 // is not absolute but rather based on proximity to the cell's center.
-                if tile.is_screen_point_inside_base_cell(cursor_screen_pos, transform) {
+                if tile.is_screen_point_inside_base_cell(cursor_screen_pos, transform, rotation, map_size_in_cells) {
                     return;
                 }
 
@@ -163,7 +204,7 @@ impl TileSelection {
 
             // Set new selection highlight:
             let highlight_cell =
-                layers.get(TileMapLayerKind::Terrain).find_exact_cell_for_point(cursor_screen_pos, transform);
+                layers.get(TileMapLayerKind::Terrain).find_exact_cell_for_point(cursor_screen_pos, transform, rotation);
 
             self.toggle_selection(layers, highlight_cell, placement_op);
         }
@@ -183,6 +224,139 @@ impl TileSelection {
         self.cells.clear();
     }
 
+    // ----------------------
+    // Multi-selection:
+    // ----------------------
+
+    #[inline]
+    pub fn multi_selection(&self) -> &[(Cell, TileMapLayerKind)] {
+        &self.multi_selection
+    }
+
+    #[inline]
+    pub fn clear_multi_selection(&mut self) {
+        self.multi_selection.clear();
+    }
+
+    // Draws the selection rect while dragging, until the mouse button is released.
+    #[inline]
+    pub fn begin_multi_select(&mut self) {
+        self.is_multi_selecting = true;
+    }
+
+    // A single cell toggles the tile in or out of the multi-selection;
+    // a range adds every tile it covers. Picks the topmost tile of each cell.
+    pub fn multi_select_range(&mut self, tile_map: &TileMap, start: Cell, end: Cell) {
+        if !start.is_valid() || !end.is_valid() {
+            return;
+        }
+
+        let range = CellRange::new(
+            Cell::new(start.x.min(end.x), start.y.min(end.y)),
+            Cell::new(start.x.max(end.x), start.y.max(end.y)),
+        );
+        let is_single_cell = range.start == range.end;
+
+        for cell in &range {
+            let topmost = TileMapLayerKind::iter()
+                .rev()
+                .find_map(|layer_kind| tile_map.try_tile_from_layer(cell, layer_kind))
+                .map(|tile| (tile.base_cell(), tile.layer_kind()));
+
+            let Some(entry) = topmost else {
+                continue;
+            };
+
+            match self.multi_selection.iter().position(|selected| *selected == entry) {
+                Some(index) if is_single_cell => {
+                    self.multi_selection.remove(index);
+                }
+                Some(_) => {}
+                None => self.multi_selection.push(entry),
+            }
+        }
+    }
+
+    // ----------------------
+    // Unit selection:
+    // ----------------------
+
+    #[inline]
+    pub fn begin_marquee(&mut self, cursor_screen_pos: Vec2) {
+        self.marquee_start = Some(cursor_screen_pos);
+        self.marquee_rect = Rect::from_extents(cursor_screen_pos, cursor_screen_pos);
+    }
+
+    // Returns the marquee rect if the cursor was dragged far enough, or None for a plain click.
+    pub fn end_marquee(&mut self, cursor_screen_pos: Vec2) -> Option<Rect> {
+        let marquee_start = self.marquee_start.take()?;
+        self.marquee_rect = Rect::from_extents(marquee_start, cursor_screen_pos);
+
+        let is_drag = self.is_marquee_large_enough();
+        self.marquee_rect = Rect::zero();
+
+        if is_drag { Some(Rect::from_extents(marquee_start, cursor_screen_pos)) } else { None }
+    }
+
+    #[inline]
+    pub fn selected_units(&self) -> &[UnitId] {
+        &self.selected_units
+    }
+
+    #[inline]
+    pub fn is_unit_selected(&self, unit_id: UnitId) -> bool {
+        self.selected_units.contains(&unit_id)
+    }
+
+    pub fn select_unit(&mut self, unit_id: UnitId) {
+        if !self.is_unit_selected(unit_id) {
+            self.selected_units.push(unit_id);
+        }
+    }
+
+    pub fn deselect_unit(&mut self, unit_id: UnitId) -> bool {
+        let Some(index) = self.selected_units.iter().position(|id| *id == unit_id) else {
+            return false;
+        };
+        self.selected_units.remove(index);
+        true
+    }
+
+    // Clears the current unit selection, returning the units that were selected.
+    #[inline]
+    pub fn take_selected_units(&mut self) -> UnitIdList {
+        std::mem::take(&mut self.selected_units)
+    }
+
+    // Overwrites `group` with the current unit selection.
+    pub fn assign_selection_group(&mut self, group: usize) {
+        self.selection_groups[group] = self.selected_units.clone();
+    }
+
+    #[inline]
+    pub fn selection_group(&self, group: usize) -> &[UnitId] {
+        &self.selection_groups[group]
+    }
+
+    // Drops despawned units from the selection and all groups.
+    pub fn retain_units<F>(&mut self, mut is_alive: F)
+    where
+        F: FnMut(UnitId) -> bool,
+    {
+        self.selected_units.retain(|id| is_alive(*id));
+        for group in &mut self.selection_groups {
+            group.retain(|id| is_alive(*id));
+        }
+    }
+
+    fn is_marquee_dragging(&self) -> bool {
+        self.marquee_start.is_some() && self.is_marquee_large_enough()
+    }
+
+    fn is_marquee_large_enough(&self) -> bool {
+        self.marquee_rect.width() >= MIN_MARQUEE_DRAG_DISTANCE || self.marquee_rect.height() >= MIN_MARQUEE_DRAG_DISTANCE
+    }
+
     fn is_selecting_range(&self) -> bool {
         self.left_mouse_button_held && self.rect.is_valid()
     }
@@ -7,18 +7,24 @@ use common::{
     Size,
     Vec2,
     constants::*,
-    coords::{Cell, CellRange},
+    coords::{Cell, CellRange, MapRotation},
     format_fixed_string,
     hash::{self, PreHashedKeyMap, StrHashPair, StringHash},
     mem::{Mutable, RawPtr},
 };
 use engine::{
-    file_sys::paths::{self, AssetPath, PathRef},
+    file_sys::{
+        self,
+        paths::{self, AssetPath, PathRef},
+    },
     log,
-    render::texture::{TextureCache, TextureHandle},
+    render::{
+        PaletteSwap,
+        texture::{TextureCache, TextureHandle},
+    },
     save::{self, SaveState},
 };
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use smallvec::SmallVec;
 use strum::{Display, EnumProperty, IntoEnumIterator};
 
@@ -28,9 +34,13 @@ use super::{
     TileKind,
     TileMapLayerKind,
     atlas::{self, *},
+    streaming::{StreamedTextureId, TileTextureStreamer},
+    validation::TileSetReport,
 };
 use crate::pathfind::NodeKind as PathNodeKind;
 
+pub use super::atlas::AtlasPageInfo;
+
 // ----------------------------------------------
 // Constants
 // ----------------------------------------------
@@ -55,6 +65,10 @@ pub enum PresetTiles {
     VacantLot = hash::fnv1a_from_str("vacant_lot"),
     DirtRoad  = hash::fnv1a_from_str("dirt_road"),
     PavedRoad = hash::fnv1a_from_str("paved_road"),
+    StoneWall = hash::fnv1a_from_str("stone_wall"),
+    WallGate  = hash::fnv1a_from_str("wall_gate"),
+    WallTower = hash::fnv1a_from_str("wall_tower"),
+    Aqueduct  = hash::fnv1a_from_str("aqueduct"),
 }
 
 impl PresetTiles {
@@ -63,15 +77,19 @@ impl PresetTiles {
         self as StringHash
     }
 
+    #[inline]
+    pub const fn terrain_category(self) -> StrHashPair {
+        if matches!(self, Self::Water) { TERRAIN_WATER_CATEGORY } else { TERRAIN_LAND_CATEGORY }
+    }
+
     #[inline]
     pub fn find_tile_def(self) -> Option<&'static TileDef> {
-        let category_name_hash = if self == Self::Water { TERRAIN_WATER_CATEGORY.hash } else { TERRAIN_LAND_CATEGORY.hash };
-        TileSets::get().find_tile_def_by_hash(TileMapLayerKind::Terrain, category_name_hash, self.hash())
+        TileSets::get().find_tile_def_by_hash(TileMapLayerKind::Terrain, self.terrain_category().hash, self.hash())
     }
 }
 
 #[repr(u8)]
-#[derive(Copy, Clone, Default, PartialEq, Eq, Display, EnumProperty, Deserialize)]
+#[derive(Copy, Clone, Default, PartialEq, Eq, Display, EnumProperty, Serialize, Deserialize)]
 pub enum TileSector {
     #[default]
     None,
@@ -161,8 +179,45 @@ pub struct TileSprite {
     pub hash: StringHash,
 
     // Not stored in serialized data.
+    // For streamed sprites this is the placeholder drawn until the texture is loaded.
     #[serde(skip)]
     pub tex_info: TileTexInfo,
+
+    // Set if the texture is loaded on demand by the TileTextureStreamer.
+    #[serde(skip)]
+    pub stream_id: Option<StreamedTextureId>,
+
+    #[serde(skip)]
+    mirrored: bool,
+}
+
+impl TileSprite {
+    #[inline]
+    pub fn is_streamed(&self) -> bool {
+        self.stream_id.is_some()
+    }
+
+    // True if the texture returned by resolve_tex_info() may still change.
+    #[inline]
+    pub fn has_transient_texture(&self) -> bool {
+        self.stream_id.is_some_and(|stream_id| !TileTextureStreamer::get().is_preloaded_and_resident(stream_id))
+    }
+
+    // Texture to draw this sprite with. Streamed sprites request their
+    // texture on first use and return the placeholder until it is loaded.
+    #[inline]
+    pub fn resolve_tex_info(&self) -> TileTexInfo {
+        match self.stream_id {
+            Some(stream_id) => TileTextureStreamer::get_mut().resolve(stream_id, self.tex_info, self.mirrored),
+            None => self.tex_info,
+        }
+    }
+
+    #[inline]
+    fn mirror(&mut self) {
+        self.tex_info.coords.mirror();
+        self.mirrored = !self.mirrored;
+    }
 }
 
 // ----------------------------------------------
@@ -225,6 +280,11 @@ impl TileAnimSet {
         self.duration
     }
 
+    #[inline]
+    pub fn set_anim_duration_secs(&mut self, duration_secs: f32) {
+        self.duration = duration_secs.max(0.0);
+    }
+
     #[inline]
     pub fn frame_duration_secs(&self) -> f32 {
         let frame_count = self.frames.len();
@@ -240,6 +300,7 @@ impl TileAnimSet {
         variation_name: &str,
         tile_def_name: &str,
         tile_def_kind: TileKind,
+        streamed: bool,
         skip_loading_textures: bool,
     ) -> bool {
         self.hash = hash::fnv1a_from_str(&self.name);
@@ -268,11 +329,13 @@ impl TileAnimSet {
                         variation_name,
                         &self.name,
                         tile_def_name,
+                        tile_def_kind,
+                        streamed,
                         skip_loading_textures,
                     );
 
                     if self.mirror {
-                        frame.tex_info.coords.mirror();
+                        frame.mirror();
                     }
                 }
 
@@ -312,11 +375,13 @@ impl TileAnimSet {
                         variation_name,
                         &self.name,
                         tile_def_name,
+                        tile_def_kind,
+                        streamed,
                         skip_loading_textures,
                     );
 
                     if self.mirror {
-                        frame.tex_info.coords.mirror();
+                        frame.mirror();
                     }
 
                     self.frames.push(frame);
@@ -384,7 +449,7 @@ impl TileAnimSet {
 
                 if self.mirror {
                     for frame in &mut self.frames {
-                        frame.tex_info.coords.mirror();
+                        frame.mirror();
                     }
                 }
             }
@@ -458,12 +523,14 @@ impl TileAnimSet {
                             &variation.name,
                             &self.name,
                             tile_def_name,
+                            tile_def_kind,
+                            variation.streamed,
                             skip_loading_textures,
                         );
                     }
 
                     if self.mirror {
-                        frame.tex_info.coords.mirror();
+                        frame.mirror();
                     }
                 }
 
@@ -489,6 +556,8 @@ impl TileAnimSet {
         variation_name: &str,
         anim_set_name: &str,
         tile_def_name: &str,
+        tile_def_kind: TileKind,
+        streamed: bool,
         skip_loading_textures: bool,
     ) {
         debug_assert!(!frame.name.is_empty());
@@ -520,7 +589,18 @@ impl TileAnimSet {
         texture_path.push(frame.name);
         texture_path.set_extension("png");
 
-        frame.tex_info = tex_atlas.load_texture(tex_cache, (&texture_path).into());
+        let streamer = TileTextureStreamer::get_mut();
+        let layer = TileMapLayerKind::from_tile_kind(tile_def_kind);
+
+        if streamed {
+            // Loaded on first use. Bypasses the atlas, so it doesn't take space in the atlas pages.
+            frame.stream_id = Some(streamer.register((&texture_path).into(), layer));
+        } else if streamer.preload_all_textures() {
+            // Loaded in the background. `tex_info` stays as the default dummy texture until resident.
+            frame.stream_id = Some(streamer.register_preloaded((&texture_path).into(), layer));
+        } else {
+            frame.tex_info = tex_atlas.load_texture(tex_cache, (&texture_path).into());
+        }
     }
 }
 
@@ -544,6 +624,20 @@ pub struct TileVariation {
     // Optional draw offset applied to the isometric tile coordinated before rendering.
     #[serde(default)]
     pub iso_offset: Vec2,
+
+    // Rarely displayed variation. Textures are loaded on first use and evicted when
+    // unused, using the first variation as placeholder. Not allowed on the first variation.
+    #[serde(default)]
+    pub streamed: bool,
+
+    // Optional names of the variations drawn in place of this one when the map view is
+    // rotated by 90, 180 and 270 degrees. Empty names fall back to this variation's sprites.
+    #[serde(default)]
+    pub rotated_variations: [String; 3],
+
+    // Indices of `rotated_variations`, resolved post-load.
+    #[serde(skip)]
+    rotated_variation_indices: [Option<u32>; 3],
 }
 
 // ----------------------------------------------
@@ -573,6 +667,11 @@ pub struct TileDef {
     #[serde(default)]
     pub color: Color,
 
+    // Recolors the sprite's palette key pixels (e.g. team colors, upgrade tinting).
+    // Optional in serialized data. Defaults to no palette swap if missing.
+    #[serde(default)]
+    pub palette_swap: PaletteSwap,
+
     // Tile variations for buildings.
     // SmallVec optimizes for Terrain/Units with single variation.
     pub variations: SmallVec<[TileVariation; 1]>,
@@ -590,6 +689,10 @@ pub struct TileDef {
     #[serde(default)]
     pub flying_object: bool,
 
+    // True if this object floats on water (e.g. boats). Can only be placed over water tiles.
+    #[serde(default)]
+    pub water_object: bool,
+
     #[serde(default = "default_path_kind")]
     pub path_kind: PathNodeKind,
 
@@ -649,6 +752,22 @@ impl TileDef {
         self.path_kind.is_road()
     }
 
+    // City walls, towers and gates.
+    #[inline]
+    pub fn is_defense(&self) -> bool {
+        self.path_kind.intersects(PathNodeKind::Wall | PathNodeKind::Gate)
+    }
+
+    #[inline]
+    pub fn is_gate(&self) -> bool {
+        self.path_kind.is_gate()
+    }
+
+    #[inline]
+    pub fn is_aqueduct(&self) -> bool {
+        self.path_kind.is_aqueduct()
+    }
+
     #[inline]
     pub fn is_harvestable_prop(&self) -> bool {
         self.path_kind.is_harvestable_prop()
@@ -697,7 +816,7 @@ impl TileDef {
     #[inline]
     pub fn texture_by_index(&self, variation_index: usize, anim_set_index: usize, frame_index: usize) -> TileTexInfo {
         if let Some(frame) = self.anim_frame_by_index(variation_index, anim_set_index, frame_index) {
-            return frame.tex_info;
+            return frame.resolve_tex_info();
         }
         TileTexInfo::default()
     }
@@ -731,6 +850,12 @@ impl TileDef {
         Some(&variation.anim_sets[anim_set_index])
     }
 
+    #[inline]
+    pub fn find_anim_set_index(&self, variation_index: usize, anim_set_name_hash: StringHash) -> Option<usize> {
+        let variation = self.variations.get(variation_index)?;
+        variation.anim_sets.iter().position(|anim_set| anim_set.hash == anim_set_name_hash)
+    }
+
     #[inline]
     pub fn anim_sets_count(&self, variation_index: usize) -> usize {
         if variation_index >= self.variations.len() {
@@ -771,11 +896,195 @@ impl TileDef {
         &self.variations[variation_index].name
     }
 
+    #[inline]
+    pub fn find_variation_index(&self, variation_name_hash: StringHash) -> Option<usize> {
+        self.variations.iter().position(|variation| variation.hash == variation_name_hash)
+    }
+
     #[inline]
     pub fn has_variations(&self) -> bool {
         self.variations.len() > 1
     }
 
+    // Variation to draw for `variation_index` when the map view is rotated.
+    #[inline]
+    pub fn rotated_variation_index(&self, variation_index: usize, rotation: MapRotation) -> usize {
+        let slot = match rotation {
+            MapRotation::Deg0 => return variation_index,
+            MapRotation::Deg90 => 0,
+            MapRotation::Deg180 => 1,
+            MapRotation::Deg270 => 2,
+        };
+
+        self.variations
+            .get(variation_index)
+            .and_then(|variation| variation.rotated_variation_indices[slot])
+            .map_or(variation_index, |index| index as usize)
+    }
+
+    // Checks the fields editable from the TileDef editor with the same rules used on load.
+    pub fn validate_edits(&self) -> bool {
+        if !self.logical_size.is_valid()
+            || (self.logical_size.width  % BASE_TILE_WIDTH_I32)  != 0
+            || (self.logical_size.height % BASE_TILE_HEIGHT_I32) != 0
+        {
+            log::error!(
+                log::channel!("tileset"),
+                "TileDef '{}': Logical size ({}) must be a multiple of BASE_TILE_SIZE.",
+                self.name,
+                self.logical_size
+            );
+            return false;
+        }
+
+        if self.is(TileKind::Terrain) && self.logical_size != BASE_TILE_SIZE_I32 {
+            log::error!(log::channel!("tileset"), "TileDef '{}': Terrain logical size must be BASE_TILE_SIZE.", self.name);
+            return false;
+        }
+
+        if !self.draw_size.is_valid() {
+            log::error!(log::channel!("tileset"), "TileDef '{}': Invalid draw size ({}).", self.name, self.draw_size);
+            return false;
+        }
+
+        for variation in &self.variations {
+            for anim_set in &variation.anim_sets {
+                if !anim_set.duration.is_finite() || anim_set.duration < 0.0 {
+                    log::error!(
+                        log::channel!("tileset"),
+                        "TileDef '{}': Invalid duration for AnimSet '{}' in variation '{}'.",
+                        self.name,
+                        anim_set.name,
+                        variation.name
+                    );
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    // Writes the fields editable from the TileDef editor into this TileDef's entry of the TileSet json.
+    // Fields missing from the file are only added if they differ from the load defaults, and
+    // unchanged values are left as written, so saving an unedited TileDef doesn't touch the file.
+    fn write_edits_to_json(&self, json_def: &mut JsonMap) -> serde_json::Result<()> {
+        write_json_field(json_def, "logical_size", self.logical_size, default_tile_size())?;
+        write_json_field(json_def, "draw_size", self.draw_size, self.logical_size)?;
+        write_json_field(json_def, "color", self.color, Color::default())?;
+        write_json_field(json_def, "palette_swap", self.palette_swap, PaletteSwap::default())?;
+
+        // Always forced off on load for these.
+        if !self.is(TileKind::Terrain) && !self.water_object {
+            write_json_field(json_def, "occludes_terrain", self.occludes_terrain, default_occludes_terrain())?;
+        }
+
+        write_json_field(json_def, "randomize_placement", self.randomize_placement, false)?;
+        write_json_field(json_def, "flying_object", self.flying_object, false)?;
+        write_json_field(json_def, "water_object", self.water_object, false)?;
+        write_json_field(json_def, "path_kind", self.path_kind, default_path_kind())?;
+        write_json_field(json_def, "required_proximity", self.required_proximity, default_path_kind())?;
+        write_json_field(json_def, "cost", self.cost, 0)?;
+        write_json_field(json_def, "sector", self.sector, TileSector::default())?;
+
+        // Variations and anim sets are listed in the same order as in the file.
+        let Some(json_variations) = json_def.get_mut("variations").and_then(|value| value.as_array_mut()) else {
+            return Ok(());
+        };
+
+        if json_variations.len() != self.variations.len() {
+            return Ok(());
+        }
+
+        for (variation, json_variation) in self.variations.iter().zip(json_variations) {
+            let Some(json_variation) = json_variation.as_object_mut() else {
+                continue;
+            };
+
+            write_json_field(json_variation, "iso_offset", variation.iso_offset, Vec2::default())?;
+
+            let Some(json_anim_sets) = json_variation.get_mut("anim_sets").and_then(|value| value.as_array_mut()) else {
+                continue;
+            };
+
+            if json_anim_sets.len() != variation.anim_sets.len() {
+                continue;
+            }
+
+            for (anim_set, json_anim_set) in variation.anim_sets.iter().zip(json_anim_sets) {
+                if let Some(json_anim_set) = json_anim_set.as_object_mut() {
+                    write_json_field(json_anim_set, "duration", anim_set.duration, 0.0)?;
+                    write_json_field(json_anim_set, "looping", anim_set.looping, default_looping_anim())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Copies the edited data from `new_def`, a freshly loaded version of this TileDef. Sprites are
+    // kept, since placed tiles hold variation/animation indices into them; edited images are picked
+    // up by reloading their textures instead. Logical size and sprite layout changes need a restart.
+    fn hot_reload_from(&mut self, new_def: &TileDef) {
+        debug_assert!(self.hash == new_def.hash && self.kind == new_def.kind);
+
+        if self.logical_size != new_def.logical_size {
+            log::warning!(
+                log::channel!("tileset"),
+                "Hot-reload: Changing the logical size of TileDef '{}' requires a restart.",
+                self.name
+            );
+        }
+
+        self.draw_size           = new_def.draw_size;
+        self.color               = new_def.color;
+        self.palette_swap        = new_def.palette_swap;
+        self.occludes_terrain    = new_def.occludes_terrain;
+        self.randomize_placement = new_def.randomize_placement;
+        self.flying_object       = new_def.flying_object;
+        self.water_object        = new_def.water_object;
+        self.path_kind           = new_def.path_kind;
+        self.required_proximity  = new_def.required_proximity;
+        self.cost                = new_def.cost;
+        self.sector              = new_def.sector;
+
+        if !self.has_same_sprite_layout(new_def) {
+            log::warning!(
+                log::channel!("tileset"),
+                "Hot-reload: Adding, removing or renaming variations/animations of TileDef '{}' requires a restart.",
+                self.name
+            );
+            return;
+        }
+
+        for (variation, new_variation) in self.variations.iter_mut().zip(&new_def.variations) {
+            variation.iso_offset = new_variation.iso_offset;
+
+            for (anim_set, new_anim_set) in variation.anim_sets.iter_mut().zip(&new_variation.anim_sets) {
+                anim_set.duration = new_anim_set.duration;
+                anim_set.looping  = new_anim_set.looping;
+            }
+        }
+    }
+
+    fn has_same_sprite_layout(&self, other: &TileDef) -> bool {
+        fn same_frames(anim_set: &TileAnimSet, other: &TileAnimSet) -> bool {
+            anim_set.hash == other.hash
+                && anim_set.mirror == other.mirror
+                && anim_set.frames.len() == other.frames.len()
+                && anim_set.frames.iter().zip(&other.frames).all(|(frame, other_frame)| frame.hash == other_frame.hash)
+        }
+
+        fn same_anim_sets(variation: &TileVariation, other: &TileVariation) -> bool {
+            variation.hash == other.hash
+                && variation.anim_sets.len() == other.anim_sets.len()
+                && variation.anim_sets.iter().zip(&other.anim_sets).all(|(a, b)| same_frames(a, b))
+        }
+
+        self.variations.len() == other.variations.len()
+            && self.variations.iter().zip(&other.variations).all(|(a, b)| same_anim_sets(a, b))
+    }
+
     fn post_load(
         &mut self,
         tex_cache: &mut TextureCache,
@@ -791,7 +1100,18 @@ impl TileDef {
         let archetype = layer.to_tile_archetype_kind();
         let specialized_type = {
             if layer == TileMapLayerKind::Objects {
-                TileKind::specialized_kind_for_category(category_hash)
+                match TileKind::specialized_kind_for_category(category_hash) {
+                    Some(specialized_type) => specialized_type,
+                    None => {
+                        // Reported by the TileSet validation pass.
+                        log::error!(
+                            log::channel!("tileset"),
+                            "Unknown TileCategory hash ({category_hash:#X}) for TileDef '{}'.",
+                            self.name
+                        );
+                        return false;
+                    }
+                }
             } else {
                 TileKind::empty() // No specialization for Terrain.
             }
@@ -834,6 +1154,11 @@ impl TileDef {
             self.occludes_terrain = false;
         }
 
+        if self.water_object {
+            // Water under floating objects (e.g. boats) is animated, so it can't be culled.
+            self.occludes_terrain = false;
+        }
+
         if !self.draw_size.is_valid() {
             // Default to logical_size.
             self.draw_size = self.logical_size;
@@ -849,6 +1174,17 @@ impl TileDef {
             return false;
         }
 
+        // First variation is the placeholder for streamed variations, so it must always be loaded.
+        if self.variations[0].streamed {
+            log::warning!(
+                log::channel!("tileset"),
+                "First variation cannot be streamed. TileDef: '{}' - '{}'",
+                self.kind,
+                self.name
+            );
+            self.variations[0].streamed = false;
+        }
+
         // Validate deserialized data and resolve texture handles:
         for variation in &mut self.variations {
             variation.hash = hash::fnv1a_from_str(&variation.name);
@@ -861,6 +1197,7 @@ impl TileDef {
                     &variation.name,
                     &self.name,
                     self.kind,
+                    variation.streamed,
                     skip_loading_textures,
                 ) {
                     return false;
@@ -890,8 +1227,53 @@ impl TileDef {
             }
         }
 
+        // Resolve rotated view sprite variations by name.
+        for v in 0..self.variations.len() {
+            for slot in 0..self.variations[v].rotated_variations.len() {
+                let rotated_name = &self.variations[v].rotated_variations[slot];
+                if rotated_name.is_empty() {
+                    continue;
+                }
+
+                let rotated_hash = hash::fnv1a_from_str(rotated_name);
+                let rotated_index = self.variations.iter().position(|variation| variation.hash == rotated_hash);
+
+                if rotated_index.is_none() {
+                    log::warning!(
+                        log::channel!("tileset"),
+                        "Rotated variation '{rotated_name}' not found. TileDef: '{}' - '{}'",
+                        self.kind,
+                        self.name
+                    );
+                }
+
+                self.variations[v].rotated_variation_indices[slot] = rotated_index.map(|index| index as u32);
+            }
+        }
+
+        // Streamed variations draw the matching sprite of the first variation until loaded.
+        for v in 1..self.variations.len() {
+            if !self.variations[v].streamed {
+                continue;
+            }
+
+            for a in 0..self.variations[v].anim_sets.len() {
+                for f in 0..self.variations[v].anim_sets[a].frames.len() {
+                    let placeholder = self.placeholder_tex_info(a, f);
+                    self.variations[v].anim_sets[a].frames[f].tex_info = placeholder;
+                }
+            }
+        }
+
         true
     }
+
+    fn placeholder_tex_info(&self, anim_set_index: usize, frame_index: usize) -> TileTexInfo {
+        // Fall back to the last anim set/frame if the first variation has fewer.
+        let anim_set_index = anim_set_index.min(self.anim_sets_count(0).saturating_sub(1));
+        let frame_index = frame_index.min(self.anim_frames_count(0, anim_set_index).saturating_sub(1));
+        self.texture_by_index(0, anim_set_index, frame_index)
+    }
 }
 
 // ----------------------------------------------
@@ -923,6 +1305,26 @@ const fn default_looping_anim() -> bool {
     true
 }
 
+type JsonMap = serde_json::Map<String, serde_json::Value>;
+
+fn write_json_field<T>(json: &mut JsonMap, key: &str, value: T, default: T) -> serde_json::Result<()>
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    match json.get(key) {
+        // Unchanged; keep it as written (e.g. `1` rather than `1.0`).
+        Some(existing) if serde_json::from_value::<T>(existing.clone()).is_ok_and(|existing| existing == value) => {}
+        Some(_) => {
+            json.insert(key.to_string(), serde_json::to_value(value)?);
+        }
+        None if value != default => {
+            json.insert(key.to_string(), serde_json::to_value(value)?);
+        }
+        None => {}
+    }
+    Ok(())
+}
+
 // ----------------------------------------------
 // EditableTileDef
 // ----------------------------------------------
@@ -1223,7 +1625,7 @@ impl TileDefHandle {
 // Stores:
 //  (tileset_index, category_hash, tile_def_hash)
 //
-#[derive(Copy, Clone, Serialize, Deserialize)]
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SerializableTileDefHandle(u8, u64, u64);
 
 impl SerializableTileDefHandle {
@@ -1253,12 +1655,15 @@ impl SerializableTileDefHandle {
 
 pub struct TileSets {
     sets: [TileSet; TILE_MAP_LAYER_COUNT],
+    atlas_pages: [Vec<AtlasPageInfo>; TILE_MAP_LAYER_COUNT], // Empty if not using a packed texture atlas.
+    validation_report: TileSetReport,
 }
 
 impl TileSets {
     pub fn load(
         tex_cache: &mut TextureCache,
         use_packed_texture_atlas: bool,
+        load_textures_async: bool,
         skip_loading_tile_sets: bool,
         skip_loading_textures: bool,
     ) -> &'static Self {
@@ -1267,20 +1672,62 @@ impl TileSets {
                 TileSet::new(TileMapLayerKind::Terrain), // 0
                 TileSet::new(TileMapLayerKind::Objects), // 1
             ],
+            atlas_pages: Default::default(),
+            validation_report: TileSetReport::default(),
         };
 
+        // Background loading bypasses the atlas, since pages can only be built once all images are decoded.
+        if load_textures_async && use_packed_texture_atlas {
+            log::warning!(
+                log::channel!("tileset"),
+                "Asynchronous texture loading is not supported with a packed texture atlas. Loading synchronously."
+            );
+        }
+
+        // Must be ready before loading, since streamed variations register with it.
+        let preload_all_textures = load_textures_async && !use_packed_texture_atlas && !skip_loading_textures;
+        TileTextureStreamer::initialize(TileTextureStreamer::new(preload_all_textures));
+
         if !skip_loading_tile_sets {
             instance.load_all_layers(tex_cache, use_packed_texture_atlas, skip_loading_textures);
         }
 
         TileSets::initialize(instance); // Set global instance.
+
+        if !skip_loading_tile_sets {
+            Self::revalidate(tex_cache, !skip_loading_textures);
+        }
+
         TileSets::get()
     }
 
+    // Lint pass over the loaded TileSets. Reports missing textures, inconsistent anim frame sizes,
+    // bad category hashes and duplicate names. Nothing is changed or rejected.
+    pub fn validate(&'static self, tex_cache: &TextureCache, check_textures: bool) -> TileSetReport {
+        TileSetReport::build(self, tex_cache, check_textures)
+    }
+
+    // Re-runs validate() and keeps the results as the current validation_report().
+    pub fn revalidate(tex_cache: &TextureCache, check_textures: bool) {
+        let report = Self::get().validate(tex_cache, check_textures);
+        report.log_issues();
+        Self::get_mut().validation_report = report;
+    }
+
+    #[inline]
+    pub fn validation_report(&'static self) -> &'static TileSetReport {
+        &self.validation_report
+    }
+
     pub fn is_empty(&'static self) -> bool {
         self.sets.is_empty()
     }
 
+    #[inline]
+    pub fn atlas_pages(&'static self, layer: TileMapLayerKind) -> &'static [AtlasPageInfo] {
+        &self.atlas_pages[layer as usize]
+    }
+
     #[inline]
     pub fn handle_to_tile_def(&'static self, handle: TileDefHandle) -> Option<&'static TileDef> {
         let set_idx  = handle.0 as usize; // TileSet index into TileSets.
@@ -1456,6 +1903,153 @@ impl TileSets {
         None
     }
 
+    // Re-reads the TileSet json for `layer` and patches the loaded TileDefs in place, so tiles
+    // already on the map pick up the edits. New tiles or categories require a restart.
+    // Returns the TileDefs that were patched.
+    pub fn hot_reload_tile_set_data(
+        &'static self,
+        tex_cache: &mut TextureCache,
+        layer: TileMapLayerKind,
+    ) -> Vec<&'static TileDef> {
+        let tile_set_path = layer.assets_path();
+        let tile_set_json_path = paths::assets_path().join(tile_set_path).join("tile_set").with_extension("json");
+
+        let mut state = save::new_json_save_state(false);
+
+        if let Err(err) = state.read_file(&tile_set_json_path) {
+            log::error!(log::channel!("tileset"), "Hot-reload: Failed to read {tile_set_json_path}: {err}");
+            return Vec::new();
+        }
+
+        let mut new_tile_set: TileSet = match state.load_new_instance() {
+            Ok(tile_set) => tile_set,
+            Err(err) => {
+                log::error!(log::channel!("tileset"), "Hot-reload: Failed to deserialize {tile_set_json_path}: {err}");
+                return Vec::new();
+            }
+        };
+
+        // Same validation as a regular load, minus the textures.
+        let mut tex_atlas = PassthroughTextureAtlas::new(layer, tex_cache);
+        if new_tile_set.layer != layer || !new_tile_set.post_load(tex_cache, &mut tex_atlas, tile_set_path, true) {
+            log::error!(log::channel!("tileset"), "Hot-reload: Invalid {tile_set_json_path}. Keeping the loaded TileSet.");
+            return Vec::new();
+        }
+
+        let tile_set = &self.sets[layer as usize];
+        let mut patched_tile_defs: Vec<&'static TileDef> = Vec::new();
+
+        for new_category in &new_tile_set.categories {
+            let Some(&category_index) = tile_set.mapping.get(&new_category.hash) else {
+                log::warning!(
+                    log::channel!("tileset"),
+                    "Hot-reload: New TileSet category '{}' requires a restart.",
+                    new_category.name
+                );
+                continue;
+            };
+
+            let category = &tile_set.categories[category_index];
+
+            for new_def in &new_category.tile_defs {
+                let Some(&tile_def_index) = category.mapping.get(&new_def.hash) else {
+                    log::warning!(
+                        log::channel!("tileset"),
+                        "Hot-reload: New TileDef '{}' requires a restart.",
+                        new_def.name
+                    );
+                    continue;
+                };
+
+                let editable_def = &category.tile_defs[tile_def_index];
+                editable_def.as_mut().hot_reload_from(new_def);
+                patched_tile_defs.push(editable_def);
+            }
+        }
+
+        log::info!(
+            log::channel!("tileset"),
+            "Hot-reloaded {} TileDefs from {tile_set_json_path}.",
+            patched_tile_defs.len()
+        );
+
+        patched_tile_defs
+    }
+
+    // Writes the edits made to `tile_def` (see the debug TileInspector) back to its TileSet json file.
+    // The rest of the file is left as is. Returns false if the edits are invalid or the file couldn't be updated.
+    pub fn save_tile_def_to_file(&'static self, tile_def: &'static TileDef) -> bool {
+        if !tile_def.validate_edits() {
+            return false;
+        }
+
+        let Some(category) = self.find_category_for_tile_def(tile_def) else {
+            return false;
+        };
+
+        let tile_set_path = tile_def.layer_kind().assets_path();
+        let tile_set_json_path = paths::assets_path().join(tile_set_path).join("tile_set").with_extension("json");
+
+        let mut json: serde_json::Value = match file_sys::load_string(&tile_set_json_path)
+            .map_err(|err| err.to_string())
+            .and_then(|json_text| serde_json::from_str(&json_text).map_err(|err| err.to_string()))
+        {
+            Ok(json) => json,
+            Err(err) => {
+                log::error!(log::channel!("tileset"), "Failed to read TileSet json file {tile_set_json_path}: {err}");
+                return false;
+            }
+        };
+
+        let Some(json_def) = Self::find_json_tile_def(&mut json, &category.name, &tile_def.name) else {
+            log::error!(
+                log::channel!("tileset"),
+                "TileDef '{}' - '{}' not found in {tile_set_json_path}.",
+                category.name,
+                tile_def.name
+            );
+            return false;
+        };
+
+        let json_text = tile_def
+            .write_edits_to_json(json_def)
+            .and_then(|_| serde_json::to_string_pretty(&json))
+            .map(|json_text| json_text + "\n");
+
+        let result = match json_text {
+            Ok(json_text) => file_sys::write_file(&tile_set_json_path, json_text).map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+
+        if let Err(err) = result {
+            log::error!(log::channel!("tileset"), "Failed to write TileSet json file {tile_set_json_path}: {err}");
+            return false;
+        }
+
+        log::info!(log::channel!("tileset"), "Saved TileDef '{}' to {tile_set_json_path}.", tile_def.name);
+        true
+    }
+
+    fn find_json_tile_def<'json>(
+        json: &'json mut serde_json::Value,
+        category_name: &str,
+        tile_name: &str,
+    ) -> Option<&'json mut JsonMap> {
+        fn has_name(json: &serde_json::Value, name: &str) -> bool {
+            json.get("name").and_then(|value| value.as_str()) == Some(name)
+        }
+
+        json.get_mut("categories")?
+            .as_array_mut()?
+            .iter_mut()
+            .find(|json_category| has_name(json_category, category_name))?
+            .get_mut("tile_defs")?
+            .as_array_mut()?
+            .iter_mut()
+            .find(|json_def| has_name(json_def, tile_name))?
+            .as_object_mut()
+    }
+
     // TileSet file structure:
     // -------------------------
     //  <layer>/tile_set.json
@@ -1555,6 +2149,7 @@ impl TileSets {
                     return false;
                 }
                 tex_atlas.commit_textures(tex_cache);
+                self.atlas_pages[layer as usize] = tex_atlas.page_infos();
             } else {
                 log::info!(log::channel!("tileset"), "Packing Texture Atlas on-the-fly for '{layer}'...");
 
@@ -1564,6 +2159,7 @@ impl TileSets {
                     return false;
                 }
                 tex_atlas.commit_textures(tex_cache);
+                self.atlas_pages[layer as usize] = tex_atlas.page_infos();
 
                 // Save sprite sheet images and metadata so next time
                 // around we'll be able to use the OfflinePackedTextureAtlas.
@@ -1579,6 +2175,7 @@ impl TileSets {
                 return false;
             }
             tex_atlas.commit_textures(tex_cache);
+            self.atlas_pages[layer as usize] = tex_atlas.page_infos();
         }
 
         log::info!(log::channel!("tileset"), "Successfully loaded TileSet '{layer}' from path {tile_set_json_path}.");
@@ -0,0 +1,362 @@
+use image::RgbaImage;
+
+#[cfg(feature = "desktop")]
+use std::sync::mpsc;
+
+use common::{
+    RectTexCoords,
+    Size,
+    hash::{self, PreHashedKeyMap, StringHash},
+};
+use engine::{
+    file_sys::paths::{self, AssetPath, PathRef},
+    log,
+    render::texture::{TextureCache, TextureHandle, TextureSettings},
+};
+
+use super::{TileMapLayerKind, atlas, sets::TileTexInfo};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Max streamed textures kept in graphics memory at once.
+// Least recently used textures are evicted when exceeded.
+const MAX_RESIDENT_TEXTURES: usize = 256;
+
+// Max textures uploaded to the GPU per frame, to spread the cost over several frames.
+const MAX_UPLOADS_PER_FRAME: usize = 8;
+
+// Textures drawn within this many frames are never evicted, even if over budget.
+const MIN_FRAMES_BEFORE_EVICTION: u64 = 2;
+
+// ----------------------------------------------
+// StreamedTextureId
+// ----------------------------------------------
+
+pub type StreamedTextureId = u32;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum StreamedTextureState {
+    Unloaded,
+    Requested,
+    Loading,
+    Resident(TextureHandle),
+}
+
+struct StreamedTexture {
+    path: AssetPath, // Relative to the assets directory.
+    layer: TileMapLayerKind,
+    state: StreamedTextureState,
+    last_used_frame: u64,
+    preloaded: bool, // Loaded upfront in the background and never evicted.
+}
+
+// ----------------------------------------------
+// TileTexturePreloadProgress
+// ----------------------------------------------
+
+// Progress of the textures preloaded in the background when tile sets are loaded
+// asynchronously. Failed loads count as finished so progress always completes.
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
+pub struct TileTexturePreloadProgress {
+    pub finished: usize,
+    pub total: usize,
+}
+
+impl TileTexturePreloadProgress {
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.finished >= self.total
+    }
+
+    #[inline]
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 { 1.0 } else { self.finished as f32 / self.total as f32 }
+    }
+}
+
+// Invoked from update() whenever preload progress changes, e.g. to drive a loading screen.
+pub type TileTexturePreloadCallback = Box<dyn FnMut(TileTexturePreloadProgress)>;
+
+// ----------------------------------------------
+// TileTextureStreamer
+// ----------------------------------------------
+
+// Loads textures of rarely displayed tile variations on demand instead of upfront.
+// The first time a streamed sprite is drawn we kick off a background load and the
+// caller draws the placeholder sprite until the texture becomes resident. Resident
+// textures are evicted in least recently used order once over the memory budget.
+//
+// When tile sets are loaded asynchronously every other tile texture is also preloaded
+// through here, so startup doesn't block on image decoding. Preloaded textures are
+// requested immediately, uploaded progressively and are never evicted.
+pub struct TileTextureStreamer {
+    textures: Vec<StreamedTexture>,
+    lookup: PreHashedKeyMap<StringHash, StreamedTextureId>,
+
+    // Requested since the last update().
+    requests: Vec<StreamedTextureId>,
+
+    // Decoded images waiting to be uploaded.
+    #[cfg(feature = "desktop")]
+    loaded_sender: mpsc::Sender<(StreamedTextureId, Option<RgbaImage>)>,
+    #[cfg(feature = "desktop")]
+    loaded_receiver: mpsc::Receiver<(StreamedTextureId, Option<RgbaImage>)>,
+    #[cfg(feature = "web")]
+    loading: Vec<StreamedTextureId>,
+
+    resident_count: usize, // Excludes preloaded textures.
+    frame: u64,
+
+    preload_all_textures: bool,
+    preload_progress: TileTexturePreloadProgress,
+    preload_callback: Option<TileTexturePreloadCallback>,
+}
+
+impl TileTextureStreamer {
+    pub fn new(preload_all_textures: bool) -> Self {
+        #[cfg(feature = "desktop")]
+        let (loaded_sender, loaded_receiver) = mpsc::channel();
+
+        Self {
+            textures: Vec::new(),
+            lookup: PreHashedKeyMap::default(),
+            requests: Vec::new(),
+            #[cfg(feature = "desktop")]
+            loaded_sender,
+            #[cfg(feature = "desktop")]
+            loaded_receiver,
+            #[cfg(feature = "web")]
+            loading: Vec::new(),
+            resident_count: 0,
+            frame: 0,
+            preload_all_textures,
+            preload_progress: TileTexturePreloadProgress::default(),
+            preload_callback: None,
+        }
+    }
+
+    // Registers a texture for on-demand loading. Nothing is loaded until first use.
+    pub fn register(&mut self, texture_path: PathRef, layer: TileMapLayerKind) -> StreamedTextureId {
+        debug_assert!(!texture_path.is_empty());
+
+        let path_hash = hash::fnv1a_from_str(texture_path.as_str());
+        if let Some(&id) = self.lookup.get(&path_hash) {
+            return id;
+        }
+
+        let id = self.textures.len() as StreamedTextureId;
+        self.textures.push(StreamedTexture {
+            path: AssetPath::from_ref(texture_path),
+            layer,
+            state: StreamedTextureState::Unloaded,
+            last_used_frame: 0,
+            preloaded: false,
+        });
+        self.lookup.insert(path_hash, id);
+        id
+    }
+
+    // Registers a texture that is requested right away and stays resident once loaded.
+    pub fn register_preloaded(&mut self, texture_path: PathRef, layer: TileMapLayerKind) -> StreamedTextureId {
+        let id = self.register(texture_path, layer);
+
+        let texture = &mut self.textures[id as usize];
+        if texture.preloaded {
+            return id; // Shared by several sprites.
+        }
+
+        debug_assert!(texture.state == StreamedTextureState::Unloaded);
+        texture.preloaded = true;
+        texture.state = StreamedTextureState::Requested;
+
+        self.requests.push(id);
+        self.preload_progress.total += 1;
+        id
+    }
+
+    // Image file of a registered texture, relative to the assets directory.
+    #[inline]
+    pub fn texture_path(&self, id: StreamedTextureId) -> PathRef<'_> {
+        (&self.textures[id as usize].path).into()
+    }
+
+    // If set, TileSets register all their textures with register_preloaded() instead of loading them upfront.
+    #[inline]
+    pub fn preload_all_textures(&self) -> bool {
+        self.preload_all_textures
+    }
+
+    #[inline]
+    pub fn preload_progress(&self) -> TileTexturePreloadProgress {
+        self.preload_progress
+    }
+
+    #[inline]
+    pub fn set_preload_callback(&mut self, callback: TileTexturePreloadCallback) {
+        self.preload_callback = Some(callback);
+    }
+
+    // Returns the streamed texture if resident, otherwise requests it and returns `placeholder`.
+    pub fn resolve(&mut self, id: StreamedTextureId, placeholder: TileTexInfo, mirrored: bool) -> TileTexInfo {
+        let texture = &mut self.textures[id as usize];
+        texture.last_used_frame = self.frame;
+
+        match texture.state {
+            StreamedTextureState::Resident(handle) => {
+                let mut coords = RectTexCoords::default();
+                if mirrored {
+                    coords.mirror();
+                }
+                TileTexInfo { texture: handle, coords }
+            }
+            StreamedTextureState::Unloaded => {
+                texture.state = StreamedTextureState::Requested;
+                self.requests.push(id);
+                placeholder
+            }
+            StreamedTextureState::Requested | StreamedTextureState::Loading => placeholder,
+        }
+    }
+
+    // Starts requested loads, uploads finished ones and evicts unused textures. Call once per frame.
+    pub fn update(&mut self, tex_cache: &mut TextureCache) {
+        for id in std::mem::take(&mut self.requests) {
+            self.start_loading(id);
+        }
+
+        let prev_preload_progress = self.preload_progress;
+
+        self.upload_loaded_textures(tex_cache);
+        self.evict_least_recently_used(tex_cache);
+
+        if self.preload_progress != prev_preload_progress
+            && let Some(callback) = &mut self.preload_callback
+        {
+            callback(self.preload_progress);
+        }
+
+        self.frame += 1;
+    }
+
+    // Preloaded textures never change once resident, so they can be baked into cached geometry.
+    #[inline]
+    pub fn is_preloaded_and_resident(&self, id: StreamedTextureId) -> bool {
+        let texture = &self.textures[id as usize];
+        texture.preloaded && matches!(texture.state, StreamedTextureState::Resident(_))
+    }
+
+    #[inline]
+    pub fn registered_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    #[inline]
+    pub fn resident_count(&self) -> usize {
+        self.resident_count
+    }
+
+    // ----------------------
+    // Internal:
+    // ----------------------
+
+    #[cfg(feature = "desktop")]
+    fn start_loading(&mut self, id: StreamedTextureId) {
+        let texture = &mut self.textures[id as usize];
+        debug_assert!(texture.state == StreamedTextureState::Requested);
+        texture.state = StreamedTextureState::Loading;
+
+        let path = texture.path.clone();
+        let sender = self.loaded_sender.clone();
+
+        // Decode off the main thread; only the GPU upload happens in update().
+        rayon::spawn(move || {
+            let image = atlas::load_image_file(paths::assets_path().into(), (&path).into());
+            let _ = sender.send((id, image));
+        });
+    }
+
+    #[cfg(feature = "web")]
+    fn start_loading(&mut self, id: StreamedTextureId) {
+        // No threads on Web/WASM. Files are already in the preloaded asset
+        // cache, so decode lazily in update() within the per-frame budget.
+        let texture = &mut self.textures[id as usize];
+        debug_assert!(texture.state == StreamedTextureState::Requested);
+        texture.state = StreamedTextureState::Loading;
+        self.loading.push(id);
+    }
+
+    #[cfg(feature = "desktop")]
+    fn upload_loaded_textures(&mut self, tex_cache: &mut TextureCache) {
+        for (id, image) in self.loaded_receiver.try_iter().take(MAX_UPLOADS_PER_FRAME).collect::<Vec<_>>() {
+            self.upload_texture(tex_cache, id, image);
+        }
+    }
+
+    #[cfg(feature = "web")]
+    fn upload_loaded_textures(&mut self, tex_cache: &mut TextureCache) {
+        let count = self.loading.len().min(MAX_UPLOADS_PER_FRAME);
+        for id in self.loading.drain(..count).collect::<Vec<_>>() {
+            let image = atlas::load_image_file(paths::assets_path().into(), (&self.textures[id as usize].path).into());
+            self.upload_texture(tex_cache, id, image);
+        }
+    }
+
+    fn upload_texture(&mut self, tex_cache: &mut TextureCache, id: StreamedTextureId, image: Option<RgbaImage>) {
+        let texture = &mut self.textures[id as usize];
+        debug_assert!(texture.state == StreamedTextureState::Loading);
+
+        if texture.preloaded {
+            self.preload_progress.finished += 1;
+        }
+
+        let Some(image) = image else {
+            // Keep using the placeholder. Stay in Loading state so we don't retry every frame.
+            log::error!(log::channel!("streaming"), "Failed to stream tile texture '{}'.", texture.path);
+            return;
+        };
+
+        // Terrain must always use nearest-neighbor filtering (default) to avoid seams.
+        let settings = if texture.layer == TileMapLayerKind::Terrain { Some(TextureSettings::default()) } else { None };
+
+        let size = Size::new(image.width() as i32, image.height() as i32);
+        let handle = tex_cache.new_initialized_texture(texture.path.as_str(), size, image.as_raw(), settings);
+
+        texture.state = StreamedTextureState::Resident(handle);
+        if !texture.preloaded {
+            self.resident_count += 1;
+        }
+    }
+
+    fn evict_least_recently_used(&mut self, tex_cache: &mut TextureCache) {
+        let frame = self.frame;
+
+        while self.resident_count > MAX_RESIDENT_TEXTURES {
+            let lru = self
+                .textures
+                .iter_mut()
+                .filter(|texture| !texture.preloaded && matches!(texture.state, StreamedTextureState::Resident(_)))
+                .filter(|texture| frame - texture.last_used_frame >= MIN_FRAMES_BEFORE_EVICTION)
+                .min_by_key(|texture| texture.last_used_frame);
+
+            // Everything resident is currently on screen; allow going over budget.
+            let Some(texture) = lru else {
+                break;
+            };
+
+            if let StreamedTextureState::Resident(mut handle) = texture.state {
+                tex_cache.release_texture(&mut handle);
+            }
+
+            texture.state = StreamedTextureState::Unloaded;
+            self.resident_count -= 1;
+        }
+    }
+}
+
+// ----------------------------------------------
+// TileTextureStreamer Global Singleton
+// ----------------------------------------------
+
+common::singleton_late_init! { TILE_TEXTURE_STREAMER_SINGLETON, TileTextureStreamer }
@@ -0,0 +1,297 @@
+use strum::{Display, EnumIter};
+
+use common::{Size, hash::{self, StringHash}};
+use engine::{
+    file_sys::{self, paths},
+    log,
+    render::texture::TextureCache,
+};
+
+use super::{
+    TileKind,
+    TileMapLayerKind,
+    sets::{TileAnimSet, TileCategory, TileDef, TileSets, TileSprite},
+    streaming::TileTextureStreamer,
+};
+
+// ----------------------------------------------
+// TileSetIssueKind
+// ----------------------------------------------
+
+#[derive(Copy, Clone, PartialEq, Eq, Display, EnumIter)]
+pub enum TileSetIssueKind {
+    #[strum(to_string = "Missing Texture")]
+    MissingTexture,
+
+    #[strum(to_string = "Inconsistent Frame Size")]
+    InconsistentFrameSize,
+
+    #[strum(to_string = "Bad Category Hash")]
+    BadCategoryHash,
+
+    #[strum(to_string = "Duplicate Name")]
+    DuplicateName,
+}
+
+// ----------------------------------------------
+// TileSetIssue
+// ----------------------------------------------
+
+pub struct TileSetIssue {
+    pub kind: TileSetIssueKind,
+    pub layer: TileMapLayerKind,
+
+    // Where in the TileSet, e.g.: "buildings/house/var0/build/frame2".
+    pub location: String,
+    pub message: String,
+}
+
+// ----------------------------------------------
+// TileSetReport
+// ----------------------------------------------
+
+// Results of the lint pass run over the loaded TileSets. Issues are only reported;
+// anything that would break the game is already rejected by the TileSet post-load.
+#[derive(Default)]
+pub struct TileSetReport {
+    issues: Vec<TileSetIssue>,
+    checked_textures: bool, // False if textures were not loaded, so texture issues are not reported.
+}
+
+impl TileSetReport {
+    pub fn build(tile_sets: &'static TileSets, tex_cache: &TextureCache, check_textures: bool) -> Self {
+        let mut report = Self { issues: Vec::new(), checked_textures: check_textures };
+
+        let mut seen_categories: Vec<(TileMapLayerKind, StringHash)> = Vec::new();
+
+        tile_sets.for_each_category(|set, category| {
+            if seen_categories.contains(&(set.layer, category.hash)) {
+                report.push(
+                    TileSetIssueKind::DuplicateName,
+                    set.layer,
+                    category.name.clone(),
+                    "Category name is used more than once in this TileSet.".into(),
+                );
+            } else {
+                seen_categories.push((set.layer, category.hash));
+            }
+
+            report.check_category(tile_sets, set.layer, category, tex_cache);
+            true
+        });
+
+        report
+    }
+
+    #[inline]
+    pub fn issues(&self) -> &[TileSetIssue] {
+        &self.issues
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    #[inline]
+    pub fn count(&self, kind: TileSetIssueKind) -> usize {
+        self.issues.iter().filter(|issue| issue.kind == kind).count()
+    }
+
+    #[inline]
+    pub fn checked_textures(&self) -> bool {
+        self.checked_textures
+    }
+
+    pub fn log_issues(&self) {
+        for issue in &self.issues {
+            log::warning!(
+                log::channel!("tileset"),
+                "[{}] {} '{}': {}",
+                issue.kind,
+                issue.layer,
+                issue.location,
+                issue.message
+            );
+        }
+
+        if self.is_empty() {
+            log::info!(log::channel!("tileset"), "TileSet validation: No issues found.");
+        } else {
+            log::warning!(log::channel!("tileset"), "TileSet validation: {} issues found.", self.issues.len());
+        }
+    }
+
+    fn push(&mut self, kind: TileSetIssueKind, layer: TileMapLayerKind, location: String, message: String) {
+        self.issues.push(TileSetIssue { kind, layer, location, message });
+    }
+
+    fn check_category(
+        &mut self,
+        tile_sets: &'static TileSets,
+        layer: TileMapLayerKind,
+        category: &'static TileCategory,
+        tex_cache: &TextureCache,
+    ) {
+        if category.hash != hash::fnv1a_from_str(&category.name) {
+            self.push(
+                TileSetIssueKind::BadCategoryHash,
+                layer,
+                category.name.clone(),
+                format!("Category hash ({:#X}) doesn't match its name.", category.hash),
+            );
+        }
+
+        if layer == TileMapLayerKind::Objects && TileKind::specialized_kind_for_category(category.hash).is_none() {
+            self.push(
+                TileSetIssueKind::BadCategoryHash,
+                layer,
+                category.name.clone(),
+                "Not a known Objects category (buildings, units, rocks, vegetation). Its tiles are not loaded.".into(),
+            );
+            return;
+        }
+
+        let mut seen_tile_defs: Vec<StringHash> = Vec::new();
+
+        category.for_each_tile_def(|tile_def| {
+            let location = format!("{}/{}", category.name, tile_def.name);
+
+            if seen_tile_defs.contains(&tile_def.hash) {
+                self.push(
+                    TileSetIssueKind::DuplicateName,
+                    layer,
+                    location.clone(),
+                    "TileDef name is used more than once in this category.".into(),
+                );
+            } else {
+                seen_tile_defs.push(tile_def.hash);
+            }
+
+            // TileDef indices must lead back to the category listing it.
+            let owner_category = tile_sets.find_category_for_tile_def(tile_def);
+            if !owner_category.is_some_and(|owner| std::ptr::eq(owner, category)) {
+                self.push(
+                    TileSetIssueKind::BadCategoryHash,
+                    layer,
+                    location.clone(),
+                    "TileDef doesn't resolve back to its category.".into(),
+                );
+            }
+
+            self.check_tile_def(layer, tile_def, &location, tex_cache);
+            true
+        });
+    }
+
+    fn check_tile_def(&mut self, layer: TileMapLayerKind, tile_def: &TileDef, location: &str, tex_cache: &TextureCache) {
+        let mut seen_variations: Vec<StringHash> = Vec::new();
+
+        for variation in &tile_def.variations {
+            let variation_location = Self::join_location(location, &variation.name);
+
+            // Variation names are optional, but named ones are looked up by name.
+            if !variation.name.is_empty() {
+                if seen_variations.contains(&variation.hash) {
+                    self.push(
+                        TileSetIssueKind::DuplicateName,
+                        layer,
+                        variation_location.clone(),
+                        "Variation name is used more than once in this TileDef.".into(),
+                    );
+                } else {
+                    seen_variations.push(variation.hash);
+                }
+            }
+
+            let mut seen_anim_sets: Vec<StringHash> = Vec::new();
+
+            for anim_set in &variation.anim_sets {
+                let anim_set_location = Self::join_location(&variation_location, &anim_set.name);
+
+                if !anim_set.name.is_empty() {
+                    if seen_anim_sets.contains(&anim_set.hash) {
+                        self.push(
+                            TileSetIssueKind::DuplicateName,
+                            layer,
+                            anim_set_location.clone(),
+                            "AnimSet name is used more than once in this variation.".into(),
+                        );
+                    } else {
+                        seen_anim_sets.push(anim_set.hash);
+                    }
+                }
+
+                if self.checked_textures {
+                    self.check_anim_set_frames(layer, anim_set, &anim_set_location, tex_cache);
+                }
+            }
+        }
+    }
+
+    fn check_anim_set_frames(
+        &mut self,
+        layer: TileMapLayerKind,
+        anim_set: &TileAnimSet,
+        location: &str,
+        tex_cache: &TextureCache,
+    ) {
+        let mut first_frame_size: Option<Size> = None;
+
+        for (frame_index, frame) in anim_set.frames.iter().enumerate() {
+            let frame_location = Self::join_location(location, &frame.name);
+
+            match Self::frame_texture_status(frame, tex_cache) {
+                FrameTextureStatus::Missing(message) => {
+                    self.push(TileSetIssueKind::MissingTexture, layer, frame_location, message);
+                }
+                FrameTextureStatus::Loaded(frame_size) => match first_frame_size {
+                    Some(expected_size) if expected_size != frame_size => {
+                        self.push(
+                            TileSetIssueKind::InconsistentFrameSize,
+                            layer,
+                            frame_location,
+                            format!("Frame [{frame_index}] is {frame_size}, other frames are {expected_size}."),
+                        );
+                    }
+                    Some(_) => {}
+                    None => first_frame_size = Some(frame_size),
+                },
+                FrameTextureStatus::Streamed => {}
+            }
+        }
+    }
+
+    fn frame_texture_status(frame: &TileSprite, tex_cache: &TextureCache) -> FrameTextureStatus {
+        // Streamed textures might not be loaded yet; check the file instead.
+        if let Some(stream_id) = frame.stream_id {
+            let texture_path = TileTextureStreamer::get().texture_path(stream_id);
+            if !file_sys::exists(paths::assets_path().join(texture_path)) {
+                return FrameTextureStatus::Missing(format!("Image file '{texture_path}' not found."));
+            }
+            return FrameTextureStatus::Streamed;
+        }
+
+        if !frame.tex_info.is_valid() {
+            return FrameTextureStatus::Missing("Texture failed to load.".into());
+        }
+
+        // Sprites packed into an atlas only cover part of the texture.
+        let texture_size = tex_cache.texture_size(frame.tex_info.texture);
+        let coords = &frame.tex_info.coords;
+        let width = (coords.top_right().x - coords.top_left().x).abs() * texture_size.width as f32;
+        let height = (coords.bottom_left().y - coords.top_left().y).abs() * texture_size.height as f32;
+
+        FrameTextureStatus::Loaded(Size::new(width.round() as i32, height.round() as i32))
+    }
+
+    fn join_location(location: &str, name: &str) -> String {
+        if name.is_empty() { location.to_string() } else { format!("{location}/{name}") }
+    }
+}
+
+enum FrameTextureStatus {
+    Missing(String),
+    Loaded(Size),
+    Streamed,
+}
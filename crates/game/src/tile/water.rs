@@ -1,7 +1,10 @@
-use common::{coords::Cell, hash::StrHashPair};
+use common::{
+    coords::{Cell, CellRange},
+    hash::StrHashPair,
+};
 
 use super::{TileKind, TileMap, TileMapLayerKind, sets::TileDef};
-use crate::pathfind::NodeKind as PathNodeKind;
+use crate::pathfind::{self, Node, NodeKind as PathNodeKind};
 
 // ----------------------------------------------
 // Water Tile Transitions
@@ -222,6 +225,24 @@ pub fn is_port_or_wharf(tile_def: &TileDef) -> bool {
     false
 }
 
+// Ports/wharfs are the land/water interface: land units reach them through the
+// building road link, while water units (boats) spawn and berth at a water cell
+// bordering the building. Returns None if the building is not touching open water.
+pub fn find_dock_water_cell(tile_map: &TileMap, dock_cells: CellRange) -> Option<Cell> {
+    let graph = tile_map.graph();
+    let mut water_cell = None;
+
+    pathfind::for_each_surrounding_cell(dock_cells, |cell| {
+        if graph.node_kind(Node::new(cell)).is_some_and(|kind| kind.is_water()) {
+            water_cell = Some(cell);
+            return false; // done
+        }
+        true // continue
+    });
+
+    water_cell
+}
+
 pub fn update_port_wharf_orientation(tile_map: &mut TileMap, cell: Cell) {
     if let Some(tile) = tile_map.try_tile_from_layer(cell, TileMapLayerKind::Objects) {
         debug_assert!(is_port_or_wharf(tile.tile_def()));
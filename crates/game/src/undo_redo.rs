@@ -185,11 +185,11 @@ impl Record {
         let tile_variation_index = tile_state.tile_variation_index;
 
         let mut tile_flags = tile_state.tile_flags;
-        tile_flags.set(TileFlags::Highlighted | TileFlags::Invalidated, false); // clear these.
+        tile_flags.set(TileFlags::Highlighted | TileFlags::Invalidated | TileFlags::Selected, false); // clear these.
 
         debug_assert!(tile_def.layer_kind() == layer);
 
-        if TilePlacement::place(context, target_cell, tile_def, subtract_tile_cost, false).is_ok() {
+        if TilePlacement::place(context, target_cell, tile_def, subtract_tile_cost, false, false).is_ok() {
             if let Some(tile) = context.find_tile_mut(target_cell, tile_def.kind()) {
                 tile.set_variation_index(tile_variation_index as usize);
                 if !tile_flags.is_empty() {
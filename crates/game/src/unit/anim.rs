@@ -26,11 +26,27 @@ impl UnitAnimSets {
     pub const IDLE_NW: UnitAnimSetKey = UnitAnimSetKey::from_str("idle_nw");
     pub const IDLE_SE: UnitAnimSetKey = UnitAnimSetKey::from_str("idle_se");
     pub const IDLE_SW: UnitAnimSetKey = UnitAnimSetKey::from_str("idle_sw");
+    pub const IDLE_N:  UnitAnimSetKey = UnitAnimSetKey::from_str("idle_n");
+    pub const IDLE_S:  UnitAnimSetKey = UnitAnimSetKey::from_str("idle_s");
+    pub const IDLE_E:  UnitAnimSetKey = UnitAnimSetKey::from_str("idle_e");
+    pub const IDLE_W:  UnitAnimSetKey = UnitAnimSetKey::from_str("idle_w");
 
     pub const WALK_NE: UnitAnimSetKey = UnitAnimSetKey::from_str("walk_ne");
     pub const WALK_NW: UnitAnimSetKey = UnitAnimSetKey::from_str("walk_nw");
     pub const WALK_SE: UnitAnimSetKey = UnitAnimSetKey::from_str("walk_se");
     pub const WALK_SW: UnitAnimSetKey = UnitAnimSetKey::from_str("walk_sw");
+    pub const WALK_N:  UnitAnimSetKey = UnitAnimSetKey::from_str("walk_n");
+    pub const WALK_S:  UnitAnimSetKey = UnitAnimSetKey::from_str("walk_s");
+    pub const WALK_E:  UnitAnimSetKey = UnitAnimSetKey::from_str("walk_e");
+    pub const WALK_W:  UnitAnimSetKey = UnitAnimSetKey::from_str("walk_w");
+
+    const ALL: [UnitAnimSetKey; 17] = [
+        Self::IDLE,
+        Self::IDLE_NE, Self::IDLE_NW, Self::IDLE_SE, Self::IDLE_SW,
+        Self::IDLE_N,  Self::IDLE_S,  Self::IDLE_E,  Self::IDLE_W,
+        Self::WALK_NE, Self::WALK_NW, Self::WALK_SE, Self::WALK_SW,
+        Self::WALK_N,  Self::WALK_S,  Self::WALK_E,  Self::WALK_W,
+    ];
 
     pub fn new(tile: &mut Tile, new_anim_set_key: UnitAnimSetKey) -> Self {
         let mut anim_set = Self::default();
@@ -59,27 +75,12 @@ impl UnitAnimSets {
     pub fn current_anim_name(&self) -> &'static str {
         let curr = self.current_anim_set_key;
         if curr == hash::NULL_HASH {
-            "<none>"
-        } else if curr == Self::IDLE.hash {
-            Self::IDLE.string
-        } else if curr == Self::IDLE_NE.hash {
-            Self::IDLE_NE.string
-        } else if curr == Self::IDLE_NW.hash {
-            Self::IDLE_NW.string
-        } else if curr == Self::IDLE_SE.hash {
-            Self::IDLE_SE.string
-        } else if curr == Self::IDLE_SW.hash {
-            Self::IDLE_SW.string
-        } else if curr == Self::WALK_NE.hash {
-            Self::WALK_NE.string
-        } else if curr == Self::WALK_NW.hash {
-            Self::WALK_NW.string
-        } else if curr == Self::WALK_SE.hash {
-            Self::WALK_SE.string
-        } else if curr == Self::WALK_SW.hash {
-            Self::WALK_SW.string
-        } else {
-            panic!("Unknown current animation!")
+            return "<none>";
+        }
+
+        match Self::ALL.iter().find(|key| key.hash == curr) {
+            Some(key) => key.string,
+            None => panic!("Unknown current animation!"),
         }
     }
 
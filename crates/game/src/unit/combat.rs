@@ -0,0 +1,113 @@
+use common::coords::CellRange;
+use engine::log;
+
+use super::{Unit, config::UnitCombatConfig};
+use crate::{
+    config::GameConfigs,
+    sim::{
+        SimCmds,
+        SimContext,
+        events::{SimEventSource, sim_event},
+    },
+    world::object::GameObject,
+};
+
+// ----------------------------------------------
+// Unit combat helpers
+// ----------------------------------------------
+
+impl Unit {
+    #[inline]
+    pub fn combat_config(&self) -> Option<&'static UnitCombatConfig> {
+        self.config().combat.as_ref()
+    }
+
+    #[inline]
+    pub fn is_combatant(&self) -> bool {
+        self.combat_config().is_some()
+    }
+
+    #[inline]
+    pub fn is_hostile(&self) -> bool {
+        self.combat_config().is_some_and(|combat| combat.hostile)
+    }
+
+    // Hostile units fight the city's combatants and vice versa.
+    #[inline]
+    pub fn is_enemy_of(&self, other: &Unit) -> bool {
+        self.is_combatant() && other.is_combatant() && self.is_hostile() != other.is_hostile()
+    }
+
+    #[inline]
+    pub fn health(&self) -> f32 {
+        self.health
+    }
+
+    // [0,1] health left. Zero for non-combatants.
+    #[inline]
+    pub fn health_ratio(&self) -> f32 {
+        self.combat_config().map_or(0.0, |combat| (self.health / combat.max_health).clamp(0.0, 1.0))
+    }
+
+    // Combatant that ran out of health. Despawned by its own combat update.
+    #[inline]
+    pub fn is_dead(&self) -> bool {
+        self.is_combatant() && self.health <= 0.0
+    }
+
+    // Returns true if this blow killed the unit.
+    pub fn take_damage(&mut self, amount: f32) -> bool {
+        debug_assert!(self.is_spawned());
+        debug_assert!(amount >= 0.0);
+
+        if !self.is_combatant() || self.is_dead() {
+            return false;
+        }
+
+        self.health = (self.health - amount).max(0.0);
+        self.is_dead()
+    }
+
+    #[inline]
+    pub fn is_in_melee(&self) -> bool {
+        self.in_melee
+    }
+
+    // Takes damage from every living enemy within melee range. Enemies run the same
+    // update on their side, so both ends of a fight trade blows each sim update.
+    // Returns true if the unit was killed; its despawn is already queued.
+    pub(super) fn update_combat(&mut self, cmds: &mut SimCmds, context: &SimContext) -> bool {
+        debug_assert!(self.is_spawned());
+
+        if !self.is_combatant() {
+            return false;
+        }
+
+        let cell = self.cell();
+        let melee_range = GameConfigs::get().sim.melee_range;
+        let mut incoming_damage = 0.0;
+        let mut enemies_in_range = false;
+
+        context.world().for_each_unit_near(CellRange::new(cell, cell), melee_range, |other| {
+            if self.is_enemy_of(other) && !other.is_dead() {
+                incoming_damage += other.combat_config().map_or(0.0, |combat| combat.attack_damage);
+                enemies_in_range = true;
+            }
+            true
+        });
+
+        self.in_melee = enemies_in_range;
+
+        if !self.take_damage(incoming_damage * context.delta_time_secs()) {
+            return false;
+        }
+
+        log::info!(log::channel!("combat"), "{} killed in combat at {cell}.", self.name());
+        sim_event!(StateChange, SimEventSource::Unit(self.id()), cell, "Killed in combat");
+
+        // Units killed in combat skip their task completion, same as an abrupt despawn.
+        self.assign_task(context.task_manager_mut(), None);
+        cmds.despawn_unit_with_id(self.id());
+        true
+    }
+}
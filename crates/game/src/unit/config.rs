@@ -5,7 +5,10 @@ use proc_macros::DrawDebugUi;
 use serde::{Deserialize, Serialize};
 use strum::Display;
 
-use crate::pathfind::NodeKind as PathNodeKind;
+use crate::{
+    pathfind::NodeKind as PathNodeKind,
+    world::config_reload::{HotReloadableConfig, hot_reload_configs, reload_field},
+};
 
 // ----------------------------------------------
 // UnitConfigKey
@@ -29,6 +32,29 @@ pub enum UnitConfigKey {
     Dog          = hash::fnv1a_from_str("dog"),
     Bird         = hash::fnv1a_from_str("bird"),
     Buffalo      = hash::fnv1a_from_str("buffalo"),
+    Soldier      = hash::fnv1a_from_str("soldier"),
+    Raider       = hash::fnv1a_from_str("raider"),
+    FishingBoat  = hash::fnv1a_from_str("fishing_boat"),
+}
+
+// ----------------------------------------------
+// UnitCombatConfig
+// ----------------------------------------------
+
+// Stats for units that take part in combat (soldiers, raiders).
+// Damage values are applied per second while engaged.
+#[derive(Copy, Clone, DrawDebugUi, Serialize, Deserialize)]
+pub struct UnitCombatConfig {
+    pub max_health: f32,
+    pub attack_damage: f32, // Against enemy units in melee range.
+
+    // Against buildings. Only hostile units attack buildings.
+    #[serde(default)]
+    pub building_damage: f32,
+
+    // Hostile units (e.g. raiders) fight the city's combatants and attack its buildings.
+    #[serde(default)]
+    pub hostile: bool,
 }
 
 // ----------------------------------------------
@@ -48,6 +74,15 @@ pub struct UnitConfig {
     #[serde(default)]
     pub traversable_node_kinds: PathNodeKind,
     pub movement_speed: f32, // in tiles per second.
+
+    // Player can order this unit around with right-click "go to" orders.
+    #[serde(default)]
+    pub controllable: bool,
+
+    // Health and attack stats. None for units that never fight.
+    #[debug_ui(skip)]
+    #[serde(default)]
+    pub combat: Option<UnitCombatConfig>,
 }
 
 impl Default for UnitConfig {
@@ -59,6 +94,8 @@ impl Default for UnitConfig {
             tile_def_name_hash: UnitConfigKey::Peasant as StringHash,
             traversable_node_kinds: PathNodeKind::default(),
             movement_speed: 1.66,
+            controllable: false,
+            combat: None,
         }
     }
 }
@@ -79,6 +116,12 @@ impl UnitConfig {
         self.tile_def_name_hash
     }
 
+    // Boats: only sail over water, spawning and berthing at a dock (see `BuildingContext::dock_water_cell`).
+    #[inline]
+    pub fn is_water_unit(&self) -> bool {
+        self.traversable_node_kinds == PathNodeKind::Water
+    }
+
     fn post_load(&mut self, index: usize) -> bool {
         // Must have a unit name.
         if self.name.is_empty() {
@@ -92,6 +135,29 @@ impl UnitConfig {
             return false;
         }
 
+        // Must move forward at a finite pace.
+        if !self.movement_speed.is_finite() || self.movement_speed <= 0.0 {
+            log::error!(
+                log::channel!("config"),
+                "UnitConfig '{}': Invalid movement speed {}! Must be greater than zero.",
+                self.name,
+                self.movement_speed
+            );
+            return false;
+        }
+
+        // Combatants must be able to take some damage.
+        if let Some(combat) = &self.combat
+            && (combat.max_health <= 0.0 || combat.attack_damage < 0.0 || combat.building_damage < 0.0)
+        {
+            log::error!(
+                log::channel!("config"),
+                "UnitConfig '{}': Invalid combat stats! Health must be greater than zero and damage cannot be negative.",
+                self.name
+            );
+            return false;
+        }
+
         self.tile_def_name_hash = hash::fnv1a_from_str(&self.tile_def_name);
         debug_assert!(self.tile_def_name_hash != hash::NULL_HASH);
 
@@ -99,6 +165,37 @@ impl UnitConfig {
     }
 }
 
+impl HotReloadableConfig for UnitConfig {
+    fn reload_key(&self) -> &str {
+        &self.tile_def_name
+    }
+
+    fn hot_reload_from(&mut self, new_config: &Self) -> bool {
+        let name = &new_config.name;
+
+        let mut changed = reload_field(name, "movement_speed", &mut self.movement_speed, new_config.movement_speed);
+        changed |= reload_field(name, "controllable", &mut self.controllable, new_config.controllable);
+
+        match (&mut self.combat, &new_config.combat) {
+            (Some(combat), Some(new_combat)) => {
+                changed |= reload_field(name, "max_health", &mut combat.max_health, new_combat.max_health);
+                changed |= reload_field(name, "attack_damage", &mut combat.attack_damage, new_combat.attack_damage);
+                changed |= reload_field(name, "building_damage", &mut combat.building_damage, new_combat.building_damage);
+                changed |= reload_field(name, "hostile", &mut combat.hostile, new_combat.hostile);
+            }
+            (None, None) => {}
+            _ => {
+                log::warning!(
+                    log::channel!("config"),
+                    "UnitConfig '{name}': Combat stats added or removed. Restart to apply."
+                );
+            }
+        }
+
+        changed
+    }
+}
+
 // ----------------------------------------------
 // UnitConfigs
 // ----------------------------------------------
@@ -158,6 +255,27 @@ impl UnitConfigs {
         }
     }
 
+    // Re-reads the config file and patches the loaded configs in place (speeds, combat stats).
+    // Entries added, removed or failing validation are left as is until a restart.
+    // Returns true if any config changed; see `config_reload::reload_object_configs()`.
+    pub fn hot_reload() -> bool {
+        let Some(mut new_configs) = Self::read_file() else {
+            return false;
+        };
+
+        let mut index = 0;
+        new_configs.configs.retain_mut(|config| {
+            let is_valid = config.post_load(index);
+            index += 1;
+            is_valid
+        });
+
+        let changed_count = hot_reload_configs("UnitConfig", &mut Self::get_mut().configs, &new_configs.configs);
+
+        log::info!(log::channel!("config"), "UnitConfigs hot-reloaded: {changed_count} configs changed.");
+        changed_count != 0
+    }
+
     pub(crate) fn configs_mut(&mut self) -> &mut [UnitConfig] {
         &mut self.configs
     }
@@ -0,0 +1,306 @@
+use arrayvec::ArrayVec;
+use serde::{Deserialize, Serialize};
+
+use common::coords::Cell;
+
+use super::{Unit, UnitId, navigation::UnitNavGoal};
+use crate::{
+    pathfind::{Node, NodeKind as PathNodeKind, Path, SearchResult},
+    sim::SimContext,
+};
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+pub const MAX_UNIT_GROUP_SIZE: usize = 16;
+
+// How far from the group destination (in cells) we look for formation slots.
+const MAX_SLOT_SEARCH_RADIUS: i32 = 4;
+
+// ----------------------------------------------
+// UnitGroupMember
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct UnitGroupMember {
+    pub unit_id: UnitId,
+    pub slot: Cell, // Cell this unit settles on once the group arrives. Invalid until move_to().
+}
+
+// ----------------------------------------------
+// UnitGroup
+// ----------------------------------------------
+
+// Set of units ordered to move together (military squads, festival processions).
+// A single path is traced from the group leader (first member) to the destination;
+// every member joins that shared route at its closest node, then peels off to its
+// own formation slot around the destination. Slots are picked among free traversable
+// cells so members don't pile up on the same tile when arriving. Members that get
+// blocked on the way in are re-steered to the next free slot by update().
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct UnitGroup {
+    members: ArrayVec<UnitGroupMember, MAX_UNIT_GROUP_SIZE>, // Leader first.
+    destination: Option<Cell>,
+}
+
+impl UnitGroup {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn add(&mut self, unit_id: UnitId) -> bool {
+        if self.members.is_full() || self.contains(unit_id) {
+            return false;
+        }
+        self.members.push(UnitGroupMember { unit_id, slot: Cell::invalid() });
+        true
+    }
+
+    #[inline]
+    pub fn remove(&mut self, unit_id: UnitId) -> bool {
+        let count = self.members.len();
+        self.members.retain(|member| member.unit_id != unit_id);
+        self.members.len() != count
+    }
+
+    #[inline]
+    pub fn contains(&self, unit_id: UnitId) -> bool {
+        self.members.iter().any(|member| member.unit_id == unit_id)
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.members.clear();
+        self.destination = None;
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    #[inline]
+    pub fn leader(&self) -> Option<UnitId> {
+        self.members.first().map(|member| member.unit_id)
+    }
+
+    #[inline]
+    pub fn members(&self) -> &[UnitGroupMember] {
+        &self.members
+    }
+
+    #[inline]
+    pub fn destination(&self) -> Option<Cell> {
+        self.destination
+    }
+
+    // Orders the whole group to `destination`. Members no longer spawned are dropped.
+    // Returns false if the leader can't reach the destination, in which case no unit
+    // is moved. Members that can't join the shared route are left where they are.
+    pub fn move_to(&mut self, context: &SimContext, destination: Cell) -> bool {
+        self.remove_despawned_members(context);
+
+        let Some(leader) = self.leader().and_then(|leader_id| context.find_unit(leader_id)) else {
+            return false;
+        };
+
+        let traversable_node_kinds = leader.traversable_node_kinds();
+
+        let shared_path = match context.find_path(traversable_node_kinds, leader.cell(), destination) {
+            SearchResult::PathFound(path) => path.clone(),
+            SearchResult::PathNotFound => return false,
+        };
+
+        self.destination = Some(destination);
+        self.assign_slots(context, destination, traversable_node_kinds);
+
+        for member in &self.members {
+            let Some(unit) = context.find_unit_mut(member.unit_id) else {
+                continue;
+            };
+
+            match Self::member_route(context, unit, &shared_path, destination, member.slot) {
+                Some(route) => Self::follow_route(unit, &route),
+                None => unit.follow_path(None),
+            }
+        }
+
+        true
+    }
+
+    // Local steering for members arriving at the destination. Any member whose path
+    // got blocked (usually by another unit standing on its slot) is given the next
+    // free slot and routed there from wherever it stopped.
+    pub fn update(&mut self, context: &SimContext) {
+        let Some(destination) = self.destination else {
+            return;
+        };
+
+        self.remove_despawned_members(context);
+
+        for index in 0..self.members.len() {
+            let member = self.members[index];
+            let Some(unit) = context.find_unit_mut(member.unit_id) else {
+                continue;
+            };
+
+            if !unit.path_is_blocked() || unit.cell() == member.slot {
+                continue;
+            }
+
+            let traversable_node_kinds = unit.traversable_node_kinds();
+            // Whoever is blocking us has already settled, so avoid any occupied cell.
+            let Some(slot) = self.find_free_slot(context, destination, traversable_node_kinds, member.unit_id, true) else {
+                continue;
+            };
+
+            self.members[index].slot = slot;
+
+            if let SearchResult::PathFound(path) = context.find_path(traversable_node_kinds, unit.cell(), slot) {
+                let route = path.clone();
+                Self::follow_route(unit, &route);
+            }
+        }
+    }
+
+    // True once every member is standing on its formation slot.
+    pub fn has_arrived(&self, context: &SimContext) -> bool {
+        if self.destination.is_none() {
+            return false;
+        }
+
+        self.members.iter().all(|member| {
+            context.find_unit(member.unit_id).is_none_or(|unit| unit.cell() == member.slot && !unit.is_following_path())
+        })
+    }
+
+    // ----------------------
+    // Internal:
+    // ----------------------
+
+    fn remove_despawned_members(&mut self, context: &SimContext) {
+        self.members.retain(|member| context.find_unit(member.unit_id).is_some());
+    }
+
+    // Leader gets the destination itself, other members the closest free cells around it.
+    fn assign_slots(&mut self, context: &SimContext, destination: Cell, traversable_node_kinds: PathNodeKind) {
+        for member in &mut self.members {
+            member.slot = Cell::invalid();
+        }
+
+        for index in 0..self.members.len() {
+            let unit_id = self.members[index].unit_id;
+            // Other members are about to move, so only units outside the group block a slot.
+            let slot = self.find_free_slot(context, destination, traversable_node_kinds, unit_id, false);
+            // Fall back to the destination cell; units will bump and re-steer on arrival.
+            self.members[index].slot = slot.unwrap_or(destination);
+        }
+    }
+
+    // Scans rings of increasing distance around `destination` for a traversable cell
+    // not claimed by another member nor occupied by another unit. Cells occupied by
+    // group members only count as taken if `avoid_members` is set.
+    fn find_free_slot(
+        &self,
+        context: &SimContext,
+        destination: Cell,
+        traversable_node_kinds: PathNodeKind,
+        for_unit: UnitId,
+        avoid_members: bool,
+    ) -> Option<Cell> {
+        let graph = context.graph();
+
+        for radius in 0..=MAX_SLOT_SEARCH_RADIUS {
+            for y in (destination.y - radius)..=(destination.y + radius) {
+                for x in (destination.x - radius)..=(destination.x + radius) {
+                    // Only the outer ring of this radius; inner rings were already visited.
+                    if (x - destination.x).abs() != radius && (y - destination.y).abs() != radius {
+                        continue;
+                    }
+
+                    let cell = Cell::new(x, y);
+
+                    let is_traversable =
+                        graph.node_kind(Node::new(cell)).is_some_and(|kind| kind.intersects(traversable_node_kinds));
+                    if !is_traversable {
+                        continue;
+                    }
+
+                    let is_claimed = self
+                        .members
+                        .iter()
+                        .any(|member| member.unit_id != for_unit && member.slot == cell);
+                    if is_claimed {
+                        continue;
+                    }
+
+                    let is_occupied = context.find_unit_for_cell(cell).is_some_and(|unit| {
+                        unit.id() != for_unit && (avoid_members || !self.contains(unit.id()))
+                    });
+                    if is_occupied {
+                        continue;
+                    }
+
+                    return Some(cell);
+                }
+            }
+        }
+
+        None
+    }
+
+    // Route for a single member: from its cell to the closest node on the shared
+    // path, along the rest of the shared path, then on to its formation slot.
+    fn member_route(context: &SimContext, unit: &Unit, shared_path: &Path, destination: Cell, slot: Cell) -> Option<Path> {
+        debug_assert!(!shared_path.is_empty());
+
+        let traversable_node_kinds = unit.traversable_node_kinds();
+        let start = unit.cell();
+
+        let (join_index, join_node) = shared_path
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, node)| node.cell.manhattan_distance(start))
+            .unwrap();
+
+        let mut route = Path::new();
+
+        if join_node.cell != start {
+            match context.find_path(traversable_node_kinds, start, join_node.cell) {
+                SearchResult::PathFound(path) => route.extend_from_slice(path),
+                SearchResult::PathNotFound => return None,
+            }
+        } else {
+            route.push(*join_node);
+        }
+
+        route.extend_from_slice(&shared_path[join_index + 1..]);
+
+        if slot != destination {
+            match context.find_path(traversable_node_kinds, destination, slot) {
+                SearchResult::PathFound(path) => route.extend_from_slice(&path[1..]),
+                SearchResult::PathNotFound => return None,
+            }
+        }
+
+        Some(route)
+    }
+
+    fn follow_route(unit: &mut Unit, route: &Path) {
+        if route.len() < 2 {
+            unit.follow_path(None); // Already there.
+            return;
+        }
+
+        let goal = UnitNavGoal::tile(unit.cell(), route);
+        unit.move_to_goal(route, goal);
+    }
+}
@@ -1,7 +1,9 @@
 use common::{
     self,
     Color,
+    Vec2,
     hash,
+    callback::Callback,
     coords::{Cell, CellRange, IsoPointF32, WorldToScreenTransform},
 };
 use engine::{log, ui::UiSystem};
@@ -14,12 +16,13 @@ use patrol::*;
 use task::*;
 
 use super::{
-    building::{Building, BuildingKind},
+    building::{Building, BuildingKind, BuildingKindAndId},
     sim::{
         SimCmds,
         SimCmdQueue,
         SimContext,
         commands::{SpawnPromise, SpawnQueryResult, SpawnReadyResult},
+        events::{SimEventSource, sim_event},
         resources::{ResourceKind, StockItem},
     },
     world::{
@@ -29,7 +32,7 @@ use super::{
 };
 use crate::{
     save_context::PostLoadContext,
-    pathfind::{NodeKind as PathNodeKind, Path},
+    pathfind::{Node as PathNode, NodeKind as PathNodeKind, Path, SearchResult},
     debug::{
         DebugUiMode,
         game_object_debug::{GameObjectDebugOptions, debug_popup_msg, debug_popup_msg_color, game_object_debug_options},
@@ -38,6 +41,7 @@ use crate::{
         self,
         Tile,
         TileDepthSortOverride,
+        TileFlags,
         TileKind,
         TileMap,
         TileMapLayerKind,
@@ -47,7 +51,9 @@ use crate::{
 };
 
 pub mod anim;
+pub mod combat;
 pub mod config;
+pub mod group;
 pub mod harvester;
 pub mod navigation;
 pub mod patrol;
@@ -91,9 +97,24 @@ pub struct Unit {
     current_task_id: UnitTaskId, // invalid if no task.
     path_is_blocked: bool,
 
+    // Remaining health of combat units. Always zero for units without `UnitConfig::combat`.
+    #[serde(default)]
+    health: f32,
+
+    // Set by the combat update while an enemy is within melee range. Units hold position while fighting.
+    #[serde(skip)]
+    in_melee: bool,
+
+    // Tasks waiting to run after the current one, or interrupted by it.
+    #[serde(default)]
+    task_queue: UnitTaskQueue,
+
     #[serde(skip)]
     config: Option<&'static UnitConfig>, // patched on post_load.
 
+    #[serde(skip)]
+    render_interp: UnitRenderInterp,
+
     #[serde(skip)]
     debug: UnitDebug,
 }
@@ -108,9 +129,21 @@ impl GameObject for Unit {
         self.id
     }
 
+    #[inline]
+    fn relocate(&mut self, new_id: UnitId) {
+        debug_assert!(self.is_spawned());
+        debug_assert!(new_id.generation() == self.id.generation());
+        self.id = new_id;
+    }
+
     #[inline]
     fn update(&mut self, cmds: &mut SimCmds, context: &SimContext) {
         debug_assert!(self.config.is_some());
+
+        if self.update_combat(cmds, context) {
+            return; // Killed in combat, despawn pending.
+        }
+
         self.update_tasks(cmds, context);
     }
 
@@ -172,22 +205,33 @@ impl Unit {
         self.config_key = config.key();
         self.direction = UnitDirection::Idle;
         self.path_is_blocked = false;
+        self.health = config.combat.map_or(0.0, |combat| combat.max_health);
+        self.in_melee = false;
+        self.render_interp = UnitRenderInterp::default();
 
         self.anim_sets.set_anim(tile, UnitAnimSets::IDLE);
         self.navigation.set_traversable_node_kinds(config.traversable_node_kinds);
         self.navigation.set_movement_speed(config.movement_speed);
         self.debug.set_show_popups(crate::debug::show_popup_messages());
+
+        sim_event!(Spawn, SimEventSource::Unit(id), self.map_cell, "{} spawned", config.name);
     }
 
     pub fn despawned(&mut self, context: &SimContext) {
         debug_assert!(self.is_spawned());
 
+        if !context.is_world_teardown() {
+            sim_event!(Despawn, SimEventSource::Unit(self.id), self.map_cell, "{} despawned", self.name());
+        }
+
         self.id = UnitId::default();
         self.map_cell = Cell::default();
         self.tile_index = TilePoolIndex::default();
         self.config = None;
         self.config_key = UnitConfigKey::default();
         self.direction = UnitDirection::default();
+        self.health = 0.0;
+        self.in_melee = false;
 
         self.anim_sets.clear();
         self.inventory.clear();
@@ -195,8 +239,22 @@ impl Unit {
         self.navigation.set_traversable_node_kinds(PathNodeKind::default());
         self.debug.clear_popups();
 
-        context.task_manager_mut().free_task(self.current_task_id);
+        let task_manager = context.task_manager_mut();
+        task_manager.free_task(self.current_task_id);
         self.current_task_id = UnitTaskId::default();
+        self.task_queue.clear(task_manager);
+    }
+
+    // UnitConfigs were hot-reloaded; refresh values copied from our config on spawn.
+    pub fn configs_reloaded(&mut self) {
+        debug_assert!(self.is_spawned());
+        let config = self.config.unwrap();
+
+        self.navigation.set_movement_speed(config.movement_speed);
+
+        if let Some(combat) = &config.combat {
+            self.health = self.health.min(combat.max_health);
+        }
     }
 
     // ----------------------
@@ -289,6 +347,17 @@ impl Unit {
         tile.set_depth_sort_override(depth_sort_override);
     }
 
+    // Hidden units still update but are not rendered (e.g. settlers walking off-map).
+    pub fn set_hidden(&mut self, context: &SimContext, hidden: bool) {
+        debug_assert!(self.is_spawned());
+        context.tile_map_mut().set_tile_flags_at_index(self.tile_index, TileMapLayerKind::Objects, TileFlags::Hidden, hidden);
+    }
+
+    #[inline]
+    pub fn is_hidden(&self, context: &SimContext) -> bool {
+        self.find_tile(context).has_flags(TileFlags::Hidden)
+    }
+
     #[inline]
     pub fn patrol_task_origin_building<'game>(&self, context: &'game SimContext) -> Option<&'game mut Building> {
         debug_assert!(self.is_spawned());
@@ -307,6 +376,22 @@ impl Unit {
         None
     }
 
+    // Building that dispatched this unit on its current task, if any.
+    #[inline]
+    pub fn home_building(&self, task_manager: &UnitTaskManager) -> Option<BuildingKindAndId> {
+        debug_assert!(self.is_spawned());
+        let (archetype, _) = task_manager.try_get_task_archetype_and_started(self.current_task()?)?;
+        archetype.origin_building()
+    }
+
+    #[inline]
+    pub fn current_task_description(&self, task_manager: &UnitTaskManager) -> &'static str {
+        debug_assert!(self.is_spawned());
+        self.current_task()
+            .and_then(|task_id| task_manager.try_get_task_archetype_and_started(task_id))
+            .map_or("Idle", |(archetype, _)| archetype.description())
+    }
+
     #[inline]
     pub fn is(&self, config_key: UnitConfigKey) -> bool {
         debug_assert!(self.is_spawned());
@@ -398,24 +483,31 @@ impl Unit {
         self.navigation.goal().is_some_and(|goal| self.cell() == goal.destination_cell())
     }
 
+    #[inline]
+    pub fn remaining_path(&self) -> &[PathNode] {
+        debug_assert!(self.is_spawned());
+        self.navigation.remaining_path()
+    }
+
     pub fn update_navigation(&mut self, context: &SimContext) {
         debug_assert!(self.is_spawned());
 
-        // Path following and movement:
-        match self.navigation.update(context.graph(), context.delta_time_secs()) {
+        self.render_interp.begin_tick(self.find_tile(context));
+
+        // Path following and movement. Fighting units stay put and resume their path afterwards.
+        let nav_result = if self.in_melee {
+            UnitNavResult::Idle
+        } else {
+            self.navigation.update(context.graph(), context.delta_time_secs())
+        };
+
+        match nav_result {
             UnitNavResult::Idle => {
                 // Nothing.
             }
             UnitNavResult::Moving(from_cell, to_cell, progress, direction) => {
                 let tile = self.find_tile_mut(context);
-
-                let draw_size = tile.draw_size();
-                let from_iso = tile::calc_unit_iso_coords(from_cell, draw_size);
-                let to_iso = tile::calc_unit_iso_coords(to_cell, draw_size);
-
-                let new_iso_coords = IsoPointF32(common::lerp(from_iso.0, to_iso.0, progress));
-                tile.set_iso_coords_f32(new_iso_coords);
-
+                place_between_cells(tile, from_cell, to_cell, progress);
                 self.update_direction_and_anim(tile, direction);
             }
             UnitNavResult::AdvancedCell(cell, direction) => {
@@ -426,7 +518,18 @@ impl Unit {
                     debug_popup_msg_color!(self.debug, Color::yellow(), "Bump!");
                 }
 
-                self.update_direction_and_anim(self.find_tile_mut(context), direction);
+                let tile = self.find_tile_mut(context);
+
+                // Keep going with whatever progress carried over into the next segment,
+                // instead of pausing at the cell center for one tick.
+                if let Some((from_cell, to_cell, progress)) = self.navigation.current_segment()
+                    && from_cell == self.map_cell
+                    && progress > 0.0
+                {
+                    place_between_cells(tile, from_cell, to_cell, progress);
+                }
+
+                self.update_direction_and_anim(tile, direction);
             }
             UnitNavResult::ReachedGoal(cell, _) => {
                 self.teleport(context.tile_map_mut(), cell);
@@ -455,6 +558,66 @@ impl Unit {
                 debug_popup_msg_color!(self.debug, Color::red(), "Path Blocked!");
             }
         }
+
+        self.render_interp.end_tick(self.find_tile(context));
+    }
+
+    // Positions the unit tile in between its last two ticked positions for rendering.
+    // `alpha` is the fraction of the next sim tick elapsed, [0,1].
+    pub fn interpolate_render_position(&mut self, context: &SimContext, alpha: f32) {
+        debug_assert!(self.is_spawned());
+        self.render_interp.interpolate(self.find_tile_mut(context), alpha);
+    }
+
+    // ----------------------
+    // Player Orders:
+    // ----------------------
+
+    #[inline]
+    pub fn is_controllable(&self) -> bool {
+        debug_assert!(self.is_spawned());
+        self.config().controllable
+    }
+
+    // Only idle units or units already walking to a previous order take new orders.
+    // Orders never interrupt work tasks, since those are tracked by their origin building.
+    #[inline]
+    pub fn can_take_orders(&self, task_manager: &UnitTaskManager) -> bool {
+        self.is_controllable()
+            && self.current_task().is_none_or(|task_id| task_manager.is_task::<UnitTaskFollowPath>(task_id))
+    }
+
+    // Sends the unit walking to `destination` along a path it can traverse.
+    // Replaces any previous "go to" order still in progress.
+    pub fn try_order_go_to(&mut self, context: &SimContext, destination: Cell) -> bool {
+        debug_assert!(self.is_spawned());
+
+        if !self.can_take_orders(context.task_manager()) || destination == self.cell() {
+            return false;
+        }
+
+        let path = match context.find_path(self.traversable_node_kinds(), self.cell(), destination) {
+            SearchResult::PathFound(path) => path.clone(),
+            SearchResult::PathNotFound => {
+                log::info!(log::channel!("unit"), "{} cannot reach {destination}.", self.name());
+                return false;
+            }
+        };
+
+        // UnitTaskFollowPath starts from a unit with no goal.
+        self.follow_path(None);
+
+        let task_manager = context.task_manager_mut();
+        let task_id = task_manager.new_task(UnitTaskFollowPath {
+            path,
+            completion_callback: Callback::default(),
+            completion_task: None,
+            terminate_if_stuck: true,
+            state: UnitTaskFollowPathState::default(),
+        });
+
+        self.assign_task(task_manager, task_id);
+        task_id.is_some()
     }
 
     // ----------------------
@@ -508,6 +671,86 @@ impl Unit {
         self.current_task_id = task_id.unwrap_or_default();
     }
 
+    // Queues `task_id` to run with the given priority. It starts right away if the unit is
+    // idle, or if it outranks the current task and that task can be interrupted, in which case
+    // the interrupted task goes back to the queue and resumes once the new one is done.
+    pub fn push_task(
+        &mut self,
+        task_manager: &mut UnitTaskManager,
+        task_id: Option<UnitTaskId>,
+        priority: UnitTaskPriority,
+    ) {
+        debug_assert!(self.is_spawned());
+
+        let Some(task_id) = task_id else {
+            return;
+        };
+
+        match self.current_task() {
+            None => {
+                self.current_task_id = task_id;
+                self.task_queue.set_current_priority(priority);
+            }
+            Some(current_task_id) if self.can_interrupt_current_task(task_manager, priority) => {
+                self.task_queue.push_interrupted(current_task_id, self.task_queue.current_priority());
+                self.current_task_id = task_id;
+                self.task_queue.set_current_priority(priority);
+
+                // The new task plans its own route; the interrupted one re-routes when it resumes.
+                self.follow_path(None);
+                debug_popup_msg!(self.debug, "Task Interrupted");
+            }
+            Some(_) => {
+                self.task_queue.push(task_id, priority);
+            }
+        }
+    }
+
+    // True if a task pushed with `priority` would take over from the current task right away.
+    #[inline]
+    pub fn can_interrupt_current_task(&self, task_manager: &mut UnitTaskManager, priority: UnitTaskPriority) -> bool {
+        debug_assert!(self.is_spawned());
+        self.current_task().is_none_or(|task_id| {
+            priority > self.task_queue.current_priority() && task_manager.can_interrupt_task(task_id)
+        })
+    }
+
+    // Makes the next queued task current. Returns false if the queue is empty.
+    pub(crate) fn start_next_queued_task(&mut self) -> bool {
+        debug_assert!(self.is_spawned());
+        debug_assert!(self.current_task().is_none());
+
+        let Some(queued) = self.task_queue.pop() else {
+            return false;
+        };
+
+        self.current_task_id = queued.task_id;
+        self.task_queue.set_current_priority(queued.priority);
+
+        if queued.interrupted {
+            // Drop whatever path the interrupting task left behind.
+            self.follow_path(None);
+            debug_popup_msg!(self.debug, "Task Resumed");
+        }
+
+        true
+    }
+
+    #[inline]
+    pub fn task_queue(&self) -> &UnitTaskQueue {
+        debug_assert!(self.is_spawned());
+        &self.task_queue
+    }
+
+    // Running or queued task of the given kind.
+    pub fn has_job(&self, task_manager: &UnitTaskManager, job_kind: UnitJobKind) -> bool {
+        debug_assert!(self.is_spawned());
+        self.current_task()
+            .into_iter()
+            .chain(self.task_queue.iter().map(|queued| queued.task_id))
+            .any(|task_id| task_manager.task_job_kind(task_id) == Some(job_kind))
+    }
+
     // Deferred spawn, pushes a command into the sim command queue.
     // Returns a promise that must be polled for completion.
     // Can be called while the world is locked for update.
@@ -651,10 +894,11 @@ impl Unit {
 
     pub(crate) fn idle(&mut self, context: &SimContext) {
         if self.direction != UnitDirection::Idle {
-            let idle_anim_set_key = navigation::idle_anim_set_for_direction(self.direction);
-
             let tile = self.find_tile_mut(context);
-            if !self.anim_sets.set_anim(tile, idle_anim_set_key) {
+
+            if !self.anim_sets.set_anim(tile, navigation::idle_anim_set_for_direction(self.direction))
+                && !self.anim_sets.set_anim(tile, navigation::idle_anim_set_for_direction(self.direction.fallback()))
+            {
                 // Fallback to generic idle if no directional anim.
                 self.anim_sets.set_anim(tile, UnitAnimSets::IDLE);
             }
@@ -665,8 +909,10 @@ impl Unit {
 
     fn update_direction_and_anim(&mut self, tile: &mut Tile, new_direction: UnitDirection) {
         if self.direction != new_direction {
-            let new_anim_set_key = anim_set_for_direction(new_direction);
-            self.anim_sets.set_anim(tile, new_anim_set_key);
+            // Units without diagonal walk anims use the closest grid axis one.
+            if !self.anim_sets.set_anim(tile, anim_set_for_direction(new_direction)) {
+                self.anim_sets.set_anim(tile, anim_set_for_direction(new_direction.fallback()));
+            }
             self.direction = new_direction;
         }
     }
@@ -676,6 +922,52 @@ impl Unit {
     }
 }
 
+// Positions the unit tile in between two neighboring cells. `progress` is [0,1].
+fn place_between_cells(tile: &mut Tile, from_cell: Cell, to_cell: Cell, progress: f32) {
+    let draw_size = tile.draw_size();
+    let from_iso = tile::calc_unit_iso_coords(from_cell, draw_size);
+    let to_iso = tile::calc_unit_iso_coords(to_cell, draw_size);
+
+    let new_iso_coords = IsoPointF32(common::lerp(from_iso.0, to_iso.0, progress));
+    tile.set_iso_coords_f32(new_iso_coords);
+}
+
+// ----------------------------------------------
+// UnitRenderInterp
+// ----------------------------------------------
+
+// Units move in fixed sim ticks, which would look choppy when rendering at a higher rate.
+// Between ticks the unit tile is drawn at a blend of its previous and current simulated
+// positions. The blend goes into the tile's render offset, the simulated iso coords stay put.
+#[derive(Clone, Default)]
+struct UnitRenderInterp {
+    prev_iso_coords: IsoPointF32,
+    sim_iso_coords: IsoPointF32,
+}
+
+impl UnitRenderInterp {
+    fn begin_tick(&mut self, tile: &Tile) {
+        // Picks up spawns and teleports outside of navigation. Nothing to blend from.
+        self.sim_iso_coords = tile.iso_coords_f32();
+        self.prev_iso_coords = self.sim_iso_coords;
+    }
+
+    fn end_tick(&mut self, tile: &Tile) {
+        self.sim_iso_coords = tile.iso_coords_f32();
+    }
+
+    fn interpolate(&self, tile: &mut Tile, alpha: f32) {
+        // Moved outside of navigation since the last tick? Draw it where it is.
+        if tile.iso_coords_f32() != self.sim_iso_coords {
+            tile.set_render_iso_offset(Vec2::zero());
+            return;
+        }
+
+        let render_iso_coords = common::lerp(self.prev_iso_coords.0, self.sim_iso_coords.0, alpha);
+        tile.set_render_iso_offset(render_iso_coords - self.sim_iso_coords.0);
+    }
+}
+
 // ----------------------------------------------
 // UnitSpawnState
 // ----------------------------------------------
@@ -732,6 +1024,17 @@ pub trait UnitTaskHelper: Sized {
         context.find_unit_mut(self.unit_id())
     }
 
+    // Spawned unit is gone without completing its task (e.g. killed in combat).
+    // Resets the helper so a new unit can be dispatched. Returns true if reset.
+    #[inline]
+    fn reset_if_unit_lost(&mut self, context: &SimContext) -> bool {
+        if self.is_spawned() && self.try_unit(context).is_none() {
+            self.reset();
+            return true;
+        }
+        false
+    }
+
     #[inline]
     fn unit<'game>(&self, context: &'game SimContext) -> &'game Unit {
         self.try_unit(context).unwrap()
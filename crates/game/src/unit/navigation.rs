@@ -8,7 +8,7 @@ use super::anim::{UnitAnimSetKey, UnitAnimSets};
 use crate::{
     building::{BuildingKind, BuildingTileInfo},
     debug::{self as debug_utils},
-    pathfind::{Graph, NodeKind as PathNodeKind, Path},
+    pathfind::{Graph, Node as PathNode, NodeKind as PathNodeKind, Path},
     sim::SimContext,
     tile::TileMapLayerKind,
 };
@@ -21,31 +21,54 @@ use crate::{
 pub enum UnitDirection {
     #[default]
     Idle,
-    NE,
-    NW,
-    SE,
-    SW,
+    // Along the grid axes (diagonals on screen):
+    NE, // +X
+    NW, // +Y
+    SE, // -Y
+    SW, // -X
+    // Diagonal grid moves (straight up/down/sideways on screen):
+    N, // +X +Y
+    S, // -X -Y
+    E, // +X -Y
+    W, // -X +Y
 }
 
 impl UnitDirection {
     #[inline]
     pub fn is_north(self) -> bool {
-        matches!(self, Self::NE | Self::NW)
+        matches!(self, Self::NE | Self::NW | Self::N)
     }
 
     #[inline]
     pub fn is_south(self) -> bool {
-        matches!(self, Self::SE | Self::SW)
+        matches!(self, Self::SE | Self::SW | Self::S)
     }
 
     #[inline]
     pub fn is_east(self) -> bool {
-        matches!(self, Self::NE | Self::SE)
+        matches!(self, Self::NE | Self::SE | Self::E)
     }
 
     #[inline]
     pub fn is_west(self) -> bool {
-        matches!(self, Self::NW | Self::SW)
+        matches!(self, Self::NW | Self::SW | Self::W)
+    }
+
+    #[inline]
+    pub fn is_diagonal(self) -> bool {
+        matches!(self, Self::N | Self::S | Self::E | Self::W)
+    }
+
+    // Closest grid axis direction (clockwise) for units that have no diagonal anim sets.
+    #[inline]
+    pub fn fallback(self) -> Self {
+        match self {
+            Self::N => Self::NE,
+            Self::E => Self::SE,
+            Self::S => Self::SW,
+            Self::W => Self::NW,
+            _ => self,
+        }
     }
 }
 
@@ -62,6 +85,16 @@ pub fn direction_between(a: Cell, b: Cell) -> UnitDirection {
     let dx = b.x - a.x;
     let dy = b.y - a.y;
 
+    // Diagonal unless one axis clearly dominates.
+    if dx != 0 && dy != 0 && dx.abs() < dy.abs() * 2 && dy.abs() < dx.abs() * 2 {
+        return match (dx > 0, dy > 0) {
+            (true,  true)  => UnitDirection::N,
+            (false, false) => UnitDirection::S,
+            (true,  false) => UnitDirection::E,
+            (false, true)  => UnitDirection::W,
+        };
+    }
+
     if dx.abs() > dy.abs() {
         // Move horizontally in grid space
         if dx > 0 {
@@ -89,6 +122,10 @@ pub fn anim_set_for_direction(direction: UnitDirection) -> UnitAnimSetKey {
         UnitDirection::NW   => UnitAnimSets::WALK_NW,
         UnitDirection::SE   => UnitAnimSets::WALK_SE,
         UnitDirection::SW   => UnitAnimSets::WALK_SW,
+        UnitDirection::N    => UnitAnimSets::WALK_N,
+        UnitDirection::S    => UnitAnimSets::WALK_S,
+        UnitDirection::E    => UnitAnimSets::WALK_E,
+        UnitDirection::W    => UnitAnimSets::WALK_W,
     }
 }
 
@@ -100,6 +137,10 @@ pub fn idle_anim_set_for_direction(direction: UnitDirection) -> UnitAnimSetKey {
         UnitDirection::NW   => UnitAnimSets::IDLE_NW,
         UnitDirection::SE   => UnitAnimSets::IDLE_SE,
         UnitDirection::SW   => UnitAnimSets::IDLE_SW,
+        UnitDirection::N    => UnitAnimSets::IDLE_N,
+        UnitDirection::S    => UnitAnimSets::IDLE_S,
+        UnitDirection::E    => UnitAnimSets::IDLE_E,
+        UnitDirection::W    => UnitAnimSets::IDLE_W,
     }
 }
 
@@ -310,7 +351,10 @@ impl UnitNavigation {
 
         if self.progress >= 1.0 {
             self.path_index += 1;
-            self.progress = 0.0;
+
+            // Carry the overshoot into the next segment so the pace doesn't depend on the
+            // tick rate. Never skip a whole cell; each one must be entered (AdvancedCell).
+            self.progress = if self.path_index + 1 < self.path.len() { (self.progress - 1.0).min(0.99) } else { 0.0 };
 
             // Look ahead for next turn:
             if self.path_index + 1 < self.path.len() {
@@ -373,6 +417,21 @@ impl UnitNavigation {
         self.goal.as_ref()
     }
 
+    // (from, to, progress) of the path segment currently being walked, if any.
+    #[inline]
+    pub fn current_segment(&self) -> Option<(Cell, Cell, f32)> {
+        if !self.is_following_path() {
+            return None;
+        }
+        Some((self.path[self.path_index].cell, self.path[self.path_index + 1].cell, self.progress))
+    }
+
+    // Path nodes from the current segment start up to the destination.
+    #[inline]
+    pub fn remaining_path(&self) -> &[PathNode] {
+        &self.path[self.path_index.min(self.path.len())..]
+    }
+
     #[inline]
     pub fn traversable_node_kinds(&self) -> PathNodeKind {
         self.traversable_node_kinds
@@ -386,7 +445,13 @@ impl UnitNavigation {
 
     #[inline]
     pub fn set_movement_speed(&mut self, movement_speed: f32) {
-        debug_assert!(movement_speed.is_finite() && movement_speed != 0.0);
+        debug_assert!(movement_speed.is_finite() && movement_speed > 0.0);
         self.segment_duration = 1.0 / movement_speed;
     }
+
+    // In tiles per second.
+    #[inline]
+    pub fn movement_speed(&self) -> f32 {
+        1.0 / self.segment_duration
+    }
 }
@@ -16,7 +16,7 @@ use super::{
     UnitTaskHelper,
     UnitSpawnState,
     SpawnedUnitWithTask,
-    config::UnitConfigKey,
+    config::{UnitConfigKey, UnitConfigs},
     task::{
         UnitTaskDespawn,
         UnitPatrolPathRecord,
@@ -28,7 +28,7 @@ use super::{
 use crate::{
     save_context::PostLoadContext,
     world::object::GameObject,
-    building::{Building, BuildingContext, BuildingKind},
+    building::{Building, BuildingContext, BuildingKind, BuildingTileInfo},
     sim::{RandomGenerator, SimContext, commands::{SimCmds, SpawnPromise}},
 };
 
@@ -124,10 +124,13 @@ impl Patrol {
             (state.max_distance, state.path_bias_min, state.path_bias_max, state.path_record.clone())
         };
 
+        // Patrol returns to where it started from: the road link, or the dock water cell for boats.
+        let origin_building_tile = BuildingTileInfo { road_link: unit_origin, base_cell: context.base_cell() };
+
         self.try_spawn_with_task(context.debug_name(), cmds, context.sim_ctx, unit_origin, unit_config,
             UnitTaskRandomizedPatrol {
                 origin_building: context.kind_and_id(),
-                origin_building_tile: context.tile_info(),
+                origin_building_tile,
                 max_distance,
                 path_bias_min,
                 path_bias_max,
@@ -273,8 +276,11 @@ impl TimedAmbientPatrol {
 
     pub fn post_load(&mut self, context: &mut PostLoadContext, spawn_frequency_secs: [Seconds; 2]) {
         self.patrol.post_load();
+        self.set_spawn_frequency(context.rng_mut(), spawn_frequency_secs);
+    }
 
-        let frequency_secs = Self::randomized_spawn_frequency(context.rng_mut(), spawn_frequency_secs);
+    pub fn set_spawn_frequency(&mut self, rng: &mut RandomGenerator, spawn_frequency_secs: [Seconds; 2]) {
+        let frequency_secs = Self::randomized_spawn_frequency(rng, spawn_frequency_secs);
         self.spawn_timer.post_load(frequency_secs);
     }
 
@@ -304,10 +310,17 @@ impl TimedAmbientPatrol {
             }
         }
 
-        // Unit spawns at the nearest road link.
-        let unit_origin = match context.road_link {
-            Some(road_link) => road_link,
-            None => return, // We are not connected to a road!
+        // Unit spawns at the nearest road link. Boats set sail from the dock instead.
+        let unit_origin = if UnitConfigs::get().find_config_by_key(unit_config).is_water_unit() {
+            match context.dock_water_cell() {
+                Some(water_cell) => water_cell,
+                None => return, // Dock not touching open water!
+            }
+        } else {
+            match context.road_link {
+                Some(road_link) => road_link,
+                None => return, // We are not connected to a road!
+            }
         };
 
         self.patrol.start_randomized_patrol(
@@ -89,6 +89,7 @@ impl Runner {
                 // If we can't find a Storage that will take our goods, try delivering directly to other Producers.
                 allow_producer_fallback: true,
                 state: UnitTaskDeliveryState::default(),
+                reservation: None,
                 visit_delivered: None,
             },
         );
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use common::coords::Cell;
 use engine::log;
 
-use super::UnitTask;
+use super::{StorageReservation, UnitTask};
 use crate::{
     pathfind::{NodeKind as PathNodeKind, Path},
     world::object::{GameObject, GenerationalIndex},
@@ -238,6 +238,8 @@ impl PathFindResult<'_> {
     }
 }
 
+// Also returns the room to reserve at the chosen building, so other carriers
+// searching before we arrive look elsewhere for whatever is left over.
 pub(super) fn find_delivery_candidate(
     context: &SimContext,
     origin_kind: BuildingKind,
@@ -245,11 +247,16 @@ pub(super) fn find_delivery_candidate(
     traversable_node_kinds: PathNodeKind,
     building_kinds_accepted: BuildingKind,
     resource_kind_to_deliver: ResourceKind,
-) -> PathFindResult<'_> {
+    resource_count: u32,
+) -> (PathFindResult<'_>, Option<StorageReservation>) {
     debug_assert!(origin_base_cell.is_valid());
     debug_assert!(!building_kinds_accepted.is_empty());
     debug_assert!(resource_kind_to_deliver.is_single_resource()); // Only one resource kind at a time.
     debug_assert!(traversable_node_kinds == PathNodeKind::Road, "Traversable Nodes={traversable_node_kinds}");
+    debug_assert!(resource_count != 0);
+
+    let reservations = context.task_manager().storage_reservations();
+    let mut reservation = None;
 
     // Try to find a building that can accept our delivery:
     let result = context.find_nearest_buildings(
@@ -258,16 +265,28 @@ pub(super) fn find_delivery_candidate(
         traversable_node_kinds,
         None,
         |building, _path| {
-            if building.receivable_resources(resource_kind_to_deliver) != 0 && building.is_linked_to_road() {
+            let unreserved_count = reservations.unreserved_count(building, resource_kind_to_deliver);
+            if unreserved_count != 0 && building.is_linked_to_road() {
+                reservation = Some(StorageReservation {
+                    building: building.kind_and_id(),
+                    kind: resource_kind_to_deliver,
+                    count: unreserved_count.min(resource_count),
+                });
                 return false; // Accept this building and end the search.
             }
-            // Else we couldn't find a free slot in this building or it
-            // is not connected to a road. Try again with another one.
+            // Else we couldn't find a free slot in this building (or the free slots are
+            // already claimed by other carriers) or it is not connected to a road.
+            // Try again with another one.
             true
         },
     );
 
-    PathFindResult::from_query_result(origin_kind, origin_base_cell, result)
+    let path_find_result = PathFindResult::from_query_result(origin_kind, origin_base_cell, result);
+    if path_find_result.not_found() {
+        reservation = None;
+    }
+
+    (path_find_result, reservation)
 }
 
 pub(super) fn find_storage_fetch_candidate(
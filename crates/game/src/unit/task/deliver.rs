@@ -7,6 +7,8 @@ use proc_macros::DrawDebugUi;
 
 use super::{
     PathFindResult,
+    StorageReservation,
+    StorageReservations,
     UnitTaskOriginBuildingDebug,
     UnitTaskContext,
     UnitTaskState,
@@ -73,6 +75,10 @@ pub struct UnitTaskDeliverToStorage {
 
     pub state: UnitTaskDeliveryState,
 
+    // Room claimed at the building we are walking to, until the visit resolves.
+    #[serde(default)]
+    pub reservation: Option<StorageReservation>,
+
     // Outcome of the deferred storage visit, written by the visit callback and
     // consumed by `VisitingStorage`: `Some(true)` delivered, `Some(false)` refused.
     #[serde(skip)]
@@ -85,30 +91,40 @@ impl UnitTaskDeliverToStorage {
         let origin_base_cell = ctx.unit.cell();
         let traversable_node_kinds = ctx.unit.traversable_node_kinds();
 
+        // Searching again; give up any room we held at the previous destination.
+        self.release_reservation(ctx.sim_context.task_manager_mut().storage_reservations_mut());
+
         // Prefer delivering to a storage building.
-        let mut path_find_result = find_delivery_candidate(
+        let mut candidate = find_delivery_candidate(
             ctx.sim_context,
             origin_kind,
             origin_base_cell,
             traversable_node_kinds,
             self.storage_buildings_accepted,
             self.resource_kind_to_deliver,
+            self.resource_count,
         );
 
-        if path_find_result.not_found() && self.allow_producer_fallback {
+        if candidate.0.not_found() && self.allow_producer_fallback {
             // Find any producer that can take our resources as fallback.
-            path_find_result = find_delivery_candidate(
+            candidate = find_delivery_candidate(
                 ctx.sim_context,
                 origin_kind,
                 origin_base_cell,
                 traversable_node_kinds,
                 BuildingKind::producers(),
                 self.resource_kind_to_deliver,
+                self.resource_count,
             );
         }
 
-        if let PathFindResult::Success { path, goal } = path_find_result {
+        if let (PathFindResult::Success { path, goal }, reservation) = candidate {
             ctx.unit.move_to_goal(path, goal);
+
+            if let Some(reservation) = reservation {
+                ctx.sim_context.task_manager_mut().storage_reservations_mut().reserve(reservation);
+                self.reservation = Some(reservation);
+            }
             true
         } else {
             // No path or Storage/Producer building found. Try again later.
@@ -116,6 +132,12 @@ impl UnitTaskDeliverToStorage {
         }
     }
 
+    fn release_reservation(&mut self, reservations: &mut StorageReservations) {
+        if let Some(reservation) = self.reservation.take() {
+            reservations.release(reservation);
+        }
+    }
+
     // Schedules the deferred building visit.
     // Returns false if the destination building is no longer valid.
     fn try_visit_storage(&mut self, ctx: &mut UnitTaskContext) -> bool {
@@ -172,7 +194,12 @@ impl UnitTaskDeliverToStorage {
         }
     }
 
-    fn update_visiting_storage(&mut self, _ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskDeliveryState> {
+    fn update_visiting_storage(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskDeliveryState> {
+        if self.visit_delivered.is_some() {
+            // Whatever fit was received, so the claimed room is no longer pending.
+            self.release_reservation(ctx.sim_context.task_manager_mut().storage_reservations_mut());
+        }
+
         match self.visit_delivered.take() {
             None        => UnitTaskTransition::Stay,                                   // Deferred visit not resolved yet.
             Some(true)  => UnitTaskTransition::Goto(UnitTaskDeliveryState::Done),      // Delivered.
@@ -192,6 +219,10 @@ impl UnitTaskState for UnitTaskDeliveryState {
             Self::Done            => UnitTaskTransition::Done,
         }
     }
+
+    fn is_interruptible(self) -> bool {
+        matches!(self, Self::Searching | Self::MovingToStorage)
+    }
 }
 
 impl UnitTask for UnitTaskDeliverToStorage {
@@ -214,6 +245,8 @@ impl UnitTask for UnitTaskDeliverToStorage {
     }
 
     fn terminate(&mut self, task_pool: &mut UnitTaskPool) {
+        self.release_reservation(task_pool.storage_reservations_mut());
+
         if let Some(task_id) = self.completion_task {
             task_pool.free(task_id);
         }
@@ -158,18 +158,19 @@ impl UnitTaskFetchFromStorage {
     // Recovery: look for any storage that will receive the surplus cargo.
     fn try_route_surplus_to_storage(&mut self, ctx: &mut UnitTaskContext) -> bool {
         let Some(item) = ctx.unit.peek_inventory() else { return false; };
-        let item_kind = item.kind;
 
         let origin_cell = ctx.unit.cell();
         let traversable_node_kinds = ctx.unit.traversable_node_kinds();
 
-        let path_find_result = find_delivery_candidate(
+        // Honors other carriers' reservations, but a one-off surplus drop doesn't hold one itself.
+        let (path_find_result, _) = find_delivery_candidate(
             ctx.sim_context,
             self.origin_building.kind,
             origin_cell,
             traversable_node_kinds,
             self.storage_buildings_accepted,
-            item_kind,
+            item.kind,
+            item.count,
         );
 
         if let PathFindResult::Success { path, goal } = path_find_result {
@@ -360,6 +361,10 @@ impl UnitTaskState for UnitTaskFetchState {
             Self::Done               => task.update_done(ctx),
         }
     }
+
+    fn is_interruptible(self) -> bool {
+        matches!(self, Self::Searching | Self::MovingToStorage | Self::ReturningToOrigin | Self::RoutingSurplus)
+    }
 }
 
 impl UnitTask for UnitTaskFetchFromStorage {
@@ -0,0 +1,127 @@
+use std::any::Any;
+use serde::{Deserialize, Serialize};
+
+use common::coords::Cell;
+use engine::ui::{DrawDebugUi, UiSystem};
+use proc_macros::DrawDebugUi;
+
+use super::{
+    UnitTaskContext,
+    UnitTaskState,
+    UnitTaskTransition,
+    UnitTask,
+};
+use crate::{
+    pathfind::SearchResult,
+    sim::{RngStream, SimContext},
+    unit::{Unit, navigation::UnitNavGoal},
+};
+
+// ----------------------------------------------
+// UnitTaskFlee
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitTaskFleeState {
+    // Running away; completes once out of reach (or if there is nowhere to run).
+    #[default]
+    Fleeing,
+}
+
+// Runs away from `danger_cell`, e.g. raiders attacking nearby. Normally pushed with
+// `UnitTaskPriority::Urgent` so it interrupts whatever the unit was doing. The
+// interrupted task then resumes from wherever the unit ended up.
+#[derive(Serialize, Deserialize)]
+pub struct UnitTaskFlee {
+    pub danger_cell: Cell,
+
+    // How far to run, in cells along each axis.
+    pub flee_distance: i32,
+
+    pub state: UnitTaskFleeState,
+}
+
+impl UnitTaskFlee {
+    // Tries cells directly away from the danger first, then along each axis,
+    // then the same at half the distance.
+    fn try_find_safe_cell(&self, ctx: &mut UnitTaskContext) -> bool {
+        let start = ctx.unit.cell();
+        let traversable_node_kinds = ctx.unit.traversable_node_kinds();
+
+        let mut dir_x = (start.x - self.danger_cell.x).signum();
+        let mut dir_y = (start.y - self.danger_cell.y).signum();
+        if dir_x == 0 && dir_y == 0 {
+            // Standing right on the danger; any way out will do.
+            dir_x = if ctx.sim_context.random_range_in(RngStream::UnitAi, 0..2) == 0 { -1 } else { 1 };
+            dir_y = if ctx.sim_context.random_range_in(RngStream::UnitAi, 0..2) == 0 { -1 } else { 1 };
+        }
+
+        for distance in [self.flee_distance, self.flee_distance / 2] {
+            for (x, y) in [(dir_x, dir_y), (dir_x, 0), (0, dir_y)] {
+                if distance <= 0 || (x == 0 && y == 0) {
+                    continue;
+                }
+
+                let goal = Cell::new(start.x + (x * distance), start.y + (y * distance));
+                if !ctx.sim_context.tile_map().is_cell_within_bounds(goal) {
+                    continue;
+                }
+
+                if let SearchResult::PathFound(path) = ctx.sim_context.find_path(traversable_node_kinds, start, goal) {
+                    ctx.unit.move_to_goal(path, UnitNavGoal::tile(start, path));
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+impl UnitTaskState for UnitTaskFleeState {
+    type Task = UnitTaskFlee;
+
+    fn update(self, _task: &mut UnitTaskFlee, ctx: &mut UnitTaskContext) -> UnitTaskTransition<Self> {
+        // No goal means there was nowhere to run or the escape route got blocked; stop here.
+        if ctx.unit.goal().is_some() && !ctx.unit.has_reached_goal() {
+            return UnitTaskTransition::Stay;
+        }
+
+        ctx.unit.follow_path(None);
+        UnitTaskTransition::Done
+    }
+}
+
+impl UnitTask for UnitTaskFlee {
+    type State = UnitTaskFleeState;
+
+    fn initialize(&mut self, ctx: &mut UnitTaskContext) {
+        debug_assert!(ctx.unit.goal().is_none());
+        debug_assert!(self.danger_cell.is_valid());
+        self.try_find_safe_cell(ctx);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn state(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+
+    fn draw_debug_ui(&mut self, _unit: &mut Unit, _sim_context: &SimContext, ui_sys: &UiSystem) {
+        #[derive(DrawDebugUi)]
+        struct View {
+            #[debug_ui(debug, separator)]
+            state: UnitTaskFleeState,
+            danger_cell: Cell,
+            flee_distance: i32,
+        }
+        View {
+            state: self.state,
+            danger_cell: self.danger_cell,
+            flee_distance: self.flee_distance,
+        }
+        .draw_debug_ui(ui_sys);
+    }
+}
@@ -14,7 +14,7 @@ use super::{
     UnitTaskPool,
 };
 use crate::{
-    pathfind::Path,
+    pathfind::{Path, SearchResult},
     sim::SimContext,
     unit::{Unit, navigation::UnitNavGoal},
 };
@@ -50,10 +50,29 @@ pub struct UnitTaskFollowPath {
     pub state: UnitTaskFollowPathState,
 }
 
+impl UnitTaskFollowPath {
+    // Retries next update if there is no way to the destination from here.
+    fn reroute(&mut self, ctx: &mut UnitTaskContext) {
+        let start = ctx.unit.cell();
+        let destination = self.path.last().unwrap().cell;
+        let traversable_node_kinds = ctx.unit.traversable_node_kinds();
+
+        if let SearchResult::PathFound(path) = ctx.sim_context.find_path(traversable_node_kinds, start, destination) {
+            self.path = path.clone();
+            ctx.unit.move_to_goal(&self.path, UnitNavGoal::tile(start, &self.path));
+        }
+    }
+}
+
 impl UnitTaskState for UnitTaskFollowPathState {
     type Task = UnitTaskFollowPath;
 
     fn update(self, task: &mut UnitTaskFollowPath, ctx: &mut UnitTaskContext) -> UnitTaskTransition<Self> {
+        if ctx.unit.goal().is_none() && !ctx.unit.path_is_blocked() {
+            // Resumed after being interrupted by a higher priority task; route back to the destination.
+            task.reroute(ctx);
+        }
+
         let reached_goal = ctx.unit.has_reached_goal();
         let stuck = ctx.unit.path_is_blocked() && task.terminate_if_stuck;
 
@@ -77,6 +96,10 @@ impl UnitTaskState for UnitTaskFollowPathState {
 
         UnitTaskTransition::Done
     }
+
+    fn is_interruptible(self) -> bool {
+        true
+    }
 }
 
 impl UnitTask for UnitTaskFollowPath {
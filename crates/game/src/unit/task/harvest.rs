@@ -33,7 +33,7 @@ use crate::{
     prop::PropId,
     unit::{Unit, UnitId, navigation::UnitNavGoal},
     building::{Building, BuildingKindAndId, BuildingTileInfo},
-    sim::{SimCmdQueue, SimContext, resources::ResourceKind},
+    sim::{RngStream, SimCmdQueue, SimContext, resources::ResourceKind},
     world::object::GameObject,
 };
 
@@ -127,7 +127,7 @@ impl PathFilter for FindHarvestableTreeFilter<'_> {
     }
 
     fn shuffle(&mut self, nodes: &mut [Node]) {
-        nodes.shuffle(self.context.rng_mut());
+        nodes.shuffle(self.context.rng_stream_mut(RngStream::UnitAi));
     }
 }
 
@@ -244,7 +244,7 @@ impl UnitTaskHarvestWood {
     // Schedules the deferred harvest mutation: the timer has elapsed,
     // so the tree is harvested and the wood handed to the unit.
     fn schedule_harvest(&mut self, ctx: &mut UnitTaskContext) {
-        let harvest_amount = ctx.sim_context.random_range(1..*WOOD_HARVEST_MAX_AMOUNT);
+        let harvest_amount = ctx.sim_context.random_range_in(RngStream::UnitAi, 1..*WOOD_HARVEST_MAX_AMOUNT);
         let unit_id = ctx.unit.id();
         let harvest_target = self.harvest_target;
 
@@ -395,6 +395,10 @@ impl UnitTaskState for UnitTaskHarvestState {
             Self::Done               => task.update_done(ctx),
         }
     }
+
+    fn is_interruptible(self) -> bool {
+        matches!(self, Self::Searching | Self::MovingToTree | Self::ReturningToOrigin)
+    }
 }
 
 impl UnitTask for UnitTaskHarvestWood {
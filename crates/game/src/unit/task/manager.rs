@@ -1,4 +1,6 @@
+use std::collections::HashMap;
 use slab::Slab;
+use smallvec::SmallVec;
 use serde::{Deserialize, Serialize};
 
 use common::mem;
@@ -10,11 +12,13 @@ use super::{
     UnitTask,
     UnitTaskArchetype,
     UnitTaskId,
+    UnitJobKind,
 };
 use crate::{
     constants::*,
     unit::Unit,
-    sim::{SimCmds, SimCmdQueue, SimContext},
+    building::{Building, BuildingKindAndId},
+    sim::{SimCmds, SimCmdQueue, SimContext, resources::{ResourceKind, StockItem}},
     world::object::GameObject,
 };
 
@@ -63,17 +67,34 @@ impl UnitTaskInstance {
 pub struct UnitTaskPool {
     tasks: Slab<UnitTaskInstance>,
     generation: u32,
+
+    // Held by delivery tasks; rebuilt from them on load.
+    #[serde(skip)]
+    storage_reservations: StorageReservations,
 }
 
 impl Default for UnitTaskPool {
     fn default() -> Self {
-        Self { tasks: Slab::default(), generation: INITIAL_GENERATION }
+        Self {
+            tasks: Slab::default(),
+            generation: INITIAL_GENERATION,
+            storage_reservations: StorageReservations::default(),
+        }
     }
 }
 
 impl UnitTaskPool {
     fn new(capacity: usize) -> Self {
-        Self { tasks: Slab::with_capacity(capacity), generation: INITIAL_GENERATION }
+        Self {
+            tasks: Slab::with_capacity(capacity),
+            generation: INITIAL_GENERATION,
+            storage_reservations: StorageReservations::default(),
+        }
+    }
+
+    #[inline]
+    pub(super) fn storage_reservations_mut(&mut self) -> &mut StorageReservations {
+        &mut self.storage_reservations
     }
 
     fn allocate(&mut self, archetype: UnitTaskArchetype) -> UnitTaskId {
@@ -128,6 +149,7 @@ impl UnitTaskPool {
 
     fn pre_load(&mut self) {
         self.tasks.clear();
+        self.storage_reservations.clear();
         self.generation = RESERVED_GENERATION;
     }
 
@@ -141,6 +163,12 @@ impl UnitTaskPool {
             debug_assert!(task.id.generation() < self.generation);
 
             task.post_load();
+
+            if let UnitTaskArchetype::UnitTaskDeliverToStorage(deliver) = &task.archetype
+                && let Some(reservation) = deliver.reservation
+            {
+                self.storage_reservations.reserve(reservation);
+            }
         }
     }
 
@@ -235,6 +263,16 @@ impl UnitTaskManager {
         mem::mut_ref_cast(task.archetype.as_any()).downcast_mut::<Task>()
     }
 
+    #[inline]
+    pub fn can_interrupt_task(&mut self, task_id: UnitTaskId) -> bool {
+        self.task_pool.try_get_mut(task_id).is_some_and(|task| task.archetype.can_interrupt())
+    }
+
+    #[inline]
+    pub fn task_job_kind(&self, task_id: UnitTaskId) -> Option<UnitJobKind> {
+        self.task_pool.try_get(task_id)?.archetype.job_kind()
+    }
+
     #[inline]
     pub fn try_get_task_archetype_and_started(&self, task_id: UnitTaskId) -> Option<(&UnitTaskArchetype, bool)> {
         let task = self.task_pool.try_get(task_id)?;
@@ -252,7 +290,22 @@ impl UnitTaskManager {
         Some((&mut task.archetype, task.started))
     }
 
+    #[inline]
+    pub fn storage_reservations(&self) -> &StorageReservations {
+        &self.task_pool.storage_reservations
+    }
+
+    #[inline]
+    pub(super) fn storage_reservations_mut(&mut self) -> &mut StorageReservations {
+        self.task_pool.storage_reservations_mut()
+    }
+
     pub fn run_unit_tasks(&mut self, unit: &mut Unit, cmds: &mut SimCmds, context: &SimContext) {
+        // Previous task finished (or was cleared); move on to the next one in the queue.
+        if unit.current_task().is_none() {
+            unit.start_next_queued_task();
+        }
+
         if let Some(current_task_id) = unit.current_task() {
             if let Some(task) = self.task_pool.try_get_mut(current_task_id) {
                 match task.run(unit, cmds, context) {
@@ -291,3 +344,70 @@ impl UnitTaskManager {
         self.task_pool.post_load();
     }
 }
+
+// ----------------------------------------------
+// StorageReservations
+// ----------------------------------------------
+
+// Room a delivery task has claimed at its destination building.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct StorageReservation {
+    pub building: BuildingKindAndId,
+    pub kind: ResourceKind,
+    pub count: u32,
+}
+
+// Resources carriers are already on their way to deliver, per destination building.
+// Delivery searches subtract these from a building's receivable resources, so two
+// carriers never head for the same last free slots.
+#[derive(Default)]
+pub struct StorageReservations {
+    reserved: HashMap<BuildingKindAndId, SmallVec<[StockItem; 2]>>,
+}
+
+impl StorageReservations {
+    pub fn reserved_count(&self, building: BuildingKindAndId, kind: ResourceKind) -> u32 {
+        self.reserved
+            .get(&building)
+            .and_then(|items| items.iter().find(|item| item.kind == kind))
+            .map_or(0, |item| item.count)
+    }
+
+    // Receivable resources of `building` not yet claimed by another carrier.
+    pub fn unreserved_count(&self, building: &Building, kind: ResourceKind) -> u32 {
+        building.receivable_resources(kind).saturating_sub(self.reserved_count(building.kind_and_id(), kind))
+    }
+
+    pub(super) fn reserve(&mut self, reservation: StorageReservation) {
+        debug_assert!(reservation.kind.is_single_resource());
+        debug_assert!(reservation.count != 0);
+
+        let items = self.reserved.entry(reservation.building).or_default();
+        match items.iter_mut().find(|item| item.kind == reservation.kind) {
+            Some(item) => item.count += reservation.count,
+            None => items.push(StockItem { kind: reservation.kind, count: reservation.count }),
+        }
+    }
+
+    pub(super) fn release(&mut self, reservation: StorageReservation) {
+        let Some(items) = self.reserved.get_mut(&reservation.building) else {
+            return;
+        };
+
+        if let Some(index) = items.iter().position(|item| item.kind == reservation.kind) {
+            debug_assert!(items[index].count >= reservation.count);
+            items[index].count = items[index].count.saturating_sub(reservation.count);
+            if items[index].count == 0 {
+                items.swap_remove(index);
+            }
+        }
+
+        if items.is_empty() {
+            self.reserved.remove(&reservation.building);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.reserved.clear();
+    }
+}
@@ -18,10 +18,13 @@ mod common;
 mod despawn;
 mod deliver;
 mod fetch;
+mod flee;
 mod follow_path;
 mod harvest;
 mod manager;
 mod patrol;
+mod queue;
+mod raid;
 mod settler;
 mod state_machine;
 
@@ -29,10 +32,13 @@ pub use common::*;
 pub use despawn::*;
 pub use deliver::*;
 pub use fetch::*;
+pub use flee::*;
 pub use follow_path::*;
 pub use harvest::*;
 pub use manager::*;
 pub use patrol::*;
+pub use queue::*;
+pub use raid::*;
 pub use settler::*;
 pub use state_machine::*;
 
@@ -79,6 +85,8 @@ pub enum UnitTaskArchetype {
     UnitTaskSettler(UnitTaskSettler),
     UnitTaskHarvestWood(UnitTaskHarvestWood),
     UnitTaskFollowPath(UnitTaskFollowPath),
+    UnitTaskRaid(UnitTaskRaid),
+    UnitTaskFlee(UnitTaskFlee),
 }
 
 // Dispatches a method call to the wrapped concrete task, for every variant.
@@ -93,6 +101,8 @@ macro_rules! archetype_dispatch {
             UnitTaskArchetype::UnitTaskSettler($task) => $body,
             UnitTaskArchetype::UnitTaskHarvestWood($task) => $body,
             UnitTaskArchetype::UnitTaskFollowPath($task) => $body,
+            UnitTaskArchetype::UnitTaskRaid($task) => $body,
+            UnitTaskArchetype::UnitTaskFlee($task) => $body,
         }
     };
 }
@@ -114,6 +124,11 @@ impl UnitTaskArchetype {
         archetype_dispatch!(self, task => UnitTaskFsm::run(task, ctx))
     }
 
+    #[inline]
+    pub fn can_interrupt(&mut self) -> bool {
+        archetype_dispatch!(self, task => UnitTaskFsm::can_interrupt(task))
+    }
+
     #[inline]
     pub fn post_load(&mut self) {
         archetype_dispatch!(self, task => UnitTaskFsm::post_load(task))
@@ -128,6 +143,49 @@ impl UnitTaskArchetype {
     pub fn as_any(&self) -> &dyn Any {
         archetype_dispatch!(self, task => UnitTaskFsm::as_any(task))
     }
+
+    // Building that dispatched the unit running this task, if any.
+    pub fn origin_building(&self) -> Option<BuildingKindAndId> {
+        match self {
+            UnitTaskArchetype::UnitTaskRandomizedPatrol(task) => Some(task.origin_building),
+            UnitTaskArchetype::UnitTaskDeliverToStorage(task) => Some(task.origin_building),
+            UnitTaskArchetype::UnitTaskFetchFromStorage(task) => Some(task.origin_building),
+            UnitTaskArchetype::UnitTaskHarvestWood(task)      => Some(task.origin_building),
+            _ => None,
+        }
+    }
+
+    // What kind of job the task is, if any. Despawning isn't a job.
+    pub fn job_kind(&self) -> Option<UnitJobKind> {
+        match self {
+            UnitTaskArchetype::UnitTaskDespawn(_)             => None,
+            UnitTaskArchetype::UnitTaskDespawnWithCallback(_) => None,
+            UnitTaskArchetype::UnitTaskRandomizedPatrol(_)    => Some(UnitJobKind::Work),
+            UnitTaskArchetype::UnitTaskDeliverToStorage(_)    => Some(UnitJobKind::Deliver),
+            UnitTaskArchetype::UnitTaskFetchFromStorage(_)    => Some(UnitJobKind::PickUp),
+            UnitTaskArchetype::UnitTaskSettler(_)             => Some(UnitJobKind::GoTo),
+            UnitTaskArchetype::UnitTaskHarvestWood(_)         => Some(UnitJobKind::Work),
+            UnitTaskArchetype::UnitTaskFollowPath(_)          => Some(UnitJobKind::GoTo),
+            UnitTaskArchetype::UnitTaskRaid(_)                => Some(UnitJobKind::Work),
+            UnitTaskArchetype::UnitTaskFlee(_)                => Some(UnitJobKind::Flee),
+        }
+    }
+
+    // Short player-facing description of what the unit is doing.
+    pub fn description(&self) -> &'static str {
+        match self {
+            UnitTaskArchetype::UnitTaskDespawn(_)             => "Leaving",
+            UnitTaskArchetype::UnitTaskDespawnWithCallback(_) => "Leaving",
+            UnitTaskArchetype::UnitTaskRandomizedPatrol(_)    => "Patrolling",
+            UnitTaskArchetype::UnitTaskDeliverToStorage(_)    => "Delivering goods",
+            UnitTaskArchetype::UnitTaskFetchFromStorage(_)    => "Fetching goods",
+            UnitTaskArchetype::UnitTaskSettler(_)             => "Looking for a home",
+            UnitTaskArchetype::UnitTaskHarvestWood(_)         => "Harvesting wood",
+            UnitTaskArchetype::UnitTaskFollowPath(_)          => "Walking",
+            UnitTaskArchetype::UnitTaskRaid(_)                => "Raiding",
+            UnitTaskArchetype::UnitTaskFlee(_)                => "Fleeing",
+        }
+    }
 }
 
 // `From<Task>` for each variant, so `UnitTaskManager::new_task` can wrap a task.
@@ -153,4 +211,6 @@ archetype_from!(
     UnitTaskSettler,
     UnitTaskHarvestWood,
     UnitTaskFollowPath,
+    UnitTaskRaid,
+    UnitTaskFlee,
 );
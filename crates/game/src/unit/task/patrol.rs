@@ -30,7 +30,7 @@ use crate::{
         RandomDirectionalBias,
         SearchResult,
     },
-    sim::{SimCmdQueue, SimContext},
+    sim::{RngStream, SimCmdQueue, SimContext},
     unit::{Unit, navigation::{self, UnitDirection, UnitNavGoal}},
     building::{Building, BuildingKind, BuildingKindAndId, BuildingTileInfo},
     world::object::GameObject,
@@ -260,8 +260,9 @@ impl UnitTaskRandomizedPatrol {
         let start = unit.cell();
         let traversable_node_kinds = unit.traversable_node_kinds();
 
-        let bias = RandomDirectionalBias::new(sim_context.rng_mut(), self.path_bias_min, self.path_bias_max);
-        let mut filter = UnitPatrolWaypointFilter::new(sim_context.rng_mut(), &self.path_record);
+        let rng = sim_context.rng_stream_mut(RngStream::UnitAi);
+        let bias = RandomDirectionalBias::new(rng, self.path_bias_min, self.path_bias_max);
+        let mut filter = UnitPatrolWaypointFilter::new(rng, &self.path_record);
 
         match sim_context.find_waypoints(&bias, &mut filter, traversable_node_kinds, start, self.max_distance) {
             SearchResult::PathFound(path) => {
@@ -480,6 +481,10 @@ impl UnitTaskState for UnitTaskPatrolState {
             Self::Done               => UnitTaskTransition::Done,
         }
     }
+
+    fn is_interruptible(self) -> bool {
+        matches!(self, Self::Patrolling | Self::ReturningToOrigin)
+    }
 }
 
 impl UnitTask for UnitTaskRandomizedPatrol {
@@ -0,0 +1,151 @@
+use smallvec::SmallVec;
+use strum::Display;
+use serde::{Deserialize, Serialize};
+
+use super::{UnitTaskId, UnitTaskManager};
+
+// ----------------------------------------------
+// UnitTaskPriority
+// ----------------------------------------------
+
+// Higher priority tasks interrupt lower priority ones. Tasks of the same priority run in FIFO order.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Display, Serialize, Deserialize)]
+pub enum UnitTaskPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
+}
+
+// ----------------------------------------------
+// UnitJobKind
+// ----------------------------------------------
+
+// Broad category of what a task has the unit doing, so systems can reason
+// about a unit's queued work without knowing the concrete task types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display)]
+pub enum UnitJobKind {
+    GoTo,
+    PickUp,
+    Deliver,
+    Work,
+    Flee,
+}
+
+// ----------------------------------------------
+// UnitTaskQueue
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct UnitQueuedTask {
+    pub task_id: UnitTaskId,
+    pub priority: UnitTaskPriority,
+
+    // Task was running when a higher priority task took over. It resumes where it left off.
+    pub interrupted: bool,
+}
+
+// Tasks waiting for the unit's current task to finish, highest priority first.
+// Lives in the Unit and references tasks in the UnitTaskPool, so both are saved
+// together and the queue survives a save/load round trip.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct UnitTaskQueue {
+    current_priority: UnitTaskPriority,
+    pending: SmallVec<[UnitQueuedTask; 2]>,
+}
+
+impl UnitTaskQueue {
+    // Priority of the task the unit is currently running.
+    #[inline]
+    pub fn current_priority(&self) -> UnitTaskPriority {
+        self.current_priority
+    }
+
+    #[inline]
+    pub(crate) fn set_current_priority(&mut self, priority: UnitTaskPriority) {
+        self.current_priority = priority;
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &UnitQueuedTask> {
+        self.pending.iter()
+    }
+
+    // Waits behind any tasks of equal or higher priority.
+    pub(crate) fn push(&mut self, task_id: UnitTaskId, priority: UnitTaskPriority) {
+        debug_assert!(task_id.is_valid());
+        let index = self.pending.iter().position(|queued| queued.priority < priority).unwrap_or(self.pending.len());
+        self.pending.insert(index, UnitQueuedTask { task_id, priority, interrupted: false });
+    }
+
+    // Interrupted tasks go ahead of other tasks of the same priority, so they resume first.
+    pub(crate) fn push_interrupted(&mut self, task_id: UnitTaskId, priority: UnitTaskPriority) {
+        debug_assert!(task_id.is_valid());
+        let index = self.pending.iter().position(|queued| queued.priority <= priority).unwrap_or(self.pending.len());
+        self.pending.insert(index, UnitQueuedTask { task_id, priority, interrupted: true });
+    }
+
+    pub(crate) fn pop(&mut self) -> Option<UnitQueuedTask> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(self.pending.remove(0))
+    }
+
+    // Frees all pending tasks, e.g. when the unit despawns.
+    pub(crate) fn clear(&mut self, task_manager: &mut UnitTaskManager) {
+        for queued in self.pending.drain(..) {
+            task_manager.free_task(queued.task_id);
+        }
+        self.current_priority = UnitTaskPriority::default();
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_id(index: usize) -> UnitTaskId {
+        UnitTaskId::new(1, index)
+    }
+
+    #[test]
+    fn queue_orders_by_priority_then_fifo() {
+        let mut queue = UnitTaskQueue::default();
+        queue.push(task_id(0), UnitTaskPriority::Normal);
+        queue.push(task_id(1), UnitTaskPriority::Low);
+        queue.push(task_id(2), UnitTaskPriority::High);
+        queue.push(task_id(3), UnitTaskPriority::Normal);
+
+        let order: Vec<usize> = std::iter::from_fn(|| queue.pop()).map(|queued| queued.task_id.index()).collect();
+        assert_eq!(order, [2, 0, 3, 1]);
+    }
+
+    #[test]
+    fn interrupted_task_resumes_before_same_priority_tasks() {
+        let mut queue = UnitTaskQueue::default();
+        queue.push(task_id(0), UnitTaskPriority::Normal);
+        queue.push_interrupted(task_id(1), UnitTaskPriority::Normal);
+
+        let first = queue.pop().unwrap();
+        assert_eq!(first.task_id.index(), 1);
+        assert!(first.interrupted);
+        assert!(!queue.pop().unwrap().interrupted);
+        assert!(queue.is_empty());
+    }
+}
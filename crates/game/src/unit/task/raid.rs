@@ -0,0 +1,226 @@
+use std::any::Any;
+use serde::{Deserialize, Serialize};
+
+use engine::{
+    log,
+    ui::{DrawDebugUi, UiSystem},
+};
+use proc_macros::DrawDebugUi;
+
+use super::{
+    UnitPostDespawnCb,
+    UnitTaskContext,
+    UnitTaskState,
+    UnitTaskTransition,
+    UnitTask,
+};
+use crate::{
+    building::{BuildingKind, BuildingKindAndId},
+    pathfind::SearchResult,
+    sim::{
+        SimCmdQueue,
+        SimContext,
+        events::{SimEventSource, sim_event},
+        notifications::notify,
+    },
+    unit::{Unit, navigation::UnitNavGoal},
+    system::{hazards::HazardsSystem, settlers::SettlersSpawnSystem},
+};
+
+// ----------------------------------------------
+// UnitTaskRaid
+// ----------------------------------------------
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitTaskRaidState {
+    // Looking for the nearest standing building to sack.
+    #[default]
+    Searching,
+
+    // Walking in from the map edge with the rest of the band (see `InvasionSystem`).
+    // The band moves the raider; it goes back to Searching once the band breaks up.
+    Marching,
+
+    // Walking to the target building.
+    Advancing,
+
+    // Next to the target, damaging it until it is destroyed.
+    Attacking,
+
+    // Nothing left to sack (or no way to reach it); walking back to the map edge.
+    Retreating,
+}
+
+// Raiders walk in from the map edge and sack buildings one after the other, nearest
+// first. Damage accumulates in the building's `RaidDamage` component and a sacked
+// building collapses, turning into rubble just like any other. Soldiers stop them in melee (see `Unit::update_combat`).
+#[derive(Default, Serialize, Deserialize)]
+pub struct UnitTaskRaid {
+    pub target: Option<BuildingKindAndId>,
+    pub state: UnitTaskRaidState,
+}
+
+impl UnitTaskRaid {
+    // Only buildings tracked by the hazards system, since that is what turns sacked buildings into rubble.
+    pub(crate) fn target_building_kinds() -> BuildingKind {
+        HazardsSystem::BUILDING_KINDS.iter().fold(BuildingKind::empty(), |kinds, &kind| kinds | kind)
+    }
+
+    fn is_target_standing(&self, sim_context: &SimContext) -> bool {
+        self.target
+            .and_then(|target| sim_context.find_building(target.kind, target.id))
+            .is_some_and(|building| !building.hazards().is_destroyed())
+    }
+
+    fn update_searching(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskRaidState> {
+        let start = ctx.unit.cell();
+        let traversable_node_kinds = ctx.unit.traversable_node_kinds();
+
+        let result = ctx.sim_context.find_nearest_buildings(
+            start,
+            Self::target_building_kinds(),
+            traversable_node_kinds,
+            None,
+            |building, _path| building.hazards().is_destroyed(), // Skip destroyed buildings.
+        );
+
+        let Some((building, path)) = result else {
+            self.target = None;
+            return self.retreat(ctx);
+        };
+
+        self.target = Some(building.kind_and_id());
+
+        if path.last().is_some_and(|node| node.cell == start) {
+            return UnitTaskTransition::Goto(UnitTaskRaidState::Attacking);
+        }
+
+        ctx.unit.move_to_goal(path, UnitNavGoal::tile(start, path));
+        UnitTaskTransition::Goto(UnitTaskRaidState::Advancing)
+    }
+
+    fn update_advancing(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskRaidState> {
+        if ctx.unit.goal().is_none() || !self.is_target_standing(ctx.sim_context) {
+            // Lost the path or somebody else got to the target first; pick another.
+            ctx.unit.follow_path(None);
+            return UnitTaskTransition::Goto(UnitTaskRaidState::Searching);
+        }
+
+        if !ctx.unit.has_reached_goal() {
+            return UnitTaskTransition::Stay;
+        }
+
+        ctx.unit.follow_path(None);
+        UnitTaskTransition::Goto(UnitTaskRaidState::Attacking)
+    }
+
+    fn update_attacking(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskRaidState> {
+        if !self.is_target_standing(ctx.sim_context) {
+            return UnitTaskTransition::Goto(UnitTaskRaidState::Searching);
+        }
+
+        // Busy fighting off a soldier.
+        if ctx.unit.is_in_melee() {
+            return UnitTaskTransition::Stay;
+        }
+
+        let target = self.target.unwrap();
+        let building_damage = ctx.unit.combat_config().map_or(0.0, |combat| combat.building_damage);
+        let damage = building_damage * ctx.sim_context.delta_time_secs();
+
+        if damage > 0.0 {
+            ctx.sim_cmds.defer_building_update(target, move |sim_ctx, building| {
+                if building.take_raid_damage(sim_ctx.world_mut(), damage) {
+                    let cell = building.base_cell();
+                    log::info!(log::channel!("combat"), "{} sacked by raiders at {cell}.", building.name());
+                    sim_event!(StateChange, SimEventSource::Building(building.kind_and_id()), cell, "Sacked by raiders");
+                    notify!(Invasion, cell, "{} was sacked by raiders", building.name());
+                }
+            });
+        }
+
+        UnitTaskTransition::Stay
+    }
+
+    fn retreat(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskRaidState> {
+        let start = ctx.unit.cell();
+
+        if let Some(edge_cell) = SettlersSpawnSystem::find_map_edge_cell(ctx.sim_context, start)
+            && edge_cell != start
+        {
+            let traversable_node_kinds = ctx.unit.traversable_node_kinds();
+            if let SearchResult::PathFound(path) = ctx.sim_context.find_path(traversable_node_kinds, start, edge_cell) {
+                ctx.unit.move_to_goal(path, UnitNavGoal::tile(start, path));
+                return UnitTaskTransition::Goto(UnitTaskRaidState::Retreating);
+            }
+        }
+
+        // Already at the edge (or cornered with no way out); leave from here.
+        UnitTaskTransition::Despawn(UnitPostDespawnCb::none())
+    }
+
+    fn update_retreating(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskRaidState> {
+        if ctx.unit.goal().is_none() {
+            // Path blocked on the way out; re-route.
+            return self.retreat(ctx);
+        }
+
+        if !ctx.unit.has_reached_goal() {
+            return UnitTaskTransition::Stay;
+        }
+
+        ctx.unit.follow_path(None);
+        UnitTaskTransition::Despawn(UnitPostDespawnCb::none())
+    }
+}
+
+impl UnitTaskState for UnitTaskRaidState {
+    type Task = UnitTaskRaid;
+
+    fn update(self, task: &mut UnitTaskRaid, ctx: &mut UnitTaskContext) -> UnitTaskTransition<Self> {
+        match self {
+            Self::Searching  => task.update_searching(ctx),
+            Self::Marching   => UnitTaskTransition::Stay,
+            Self::Advancing  => task.update_advancing(ctx),
+            Self::Attacking  => task.update_attacking(ctx),
+            Self::Retreating => task.update_retreating(ctx),
+        }
+    }
+
+    // Not while attacking: the raider would keep damaging its target from wherever it resumed.
+    fn is_interruptible(self) -> bool {
+        !matches!(self, Self::Attacking)
+    }
+}
+
+impl UnitTask for UnitTaskRaid {
+    type State = UnitTaskRaidState;
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn state(&mut self) -> &mut Self::State {
+        &mut self.state
+    }
+
+    fn draw_debug_ui(&mut self, unit: &mut Unit, sim_context: &SimContext, ui_sys: &UiSystem) {
+        #[derive(DrawDebugUi)]
+        struct View {
+            #[debug_ui(debug, separator)]
+            state: UnitTaskRaidState,
+            target_kind: BuildingKind,
+            target_standing: bool,
+            health: f32,
+            in_melee: bool,
+        }
+        View {
+            state: self.state,
+            target_kind: self.target.map_or(BuildingKind::empty(), |target| target.kind),
+            target_standing: self.is_target_standing(sim_context),
+            health: unit.health(),
+            in_melee: unit.is_in_melee(),
+        }
+        .draw_debug_ui(ui_sys);
+    }
+}
@@ -1,7 +1,7 @@
 use std::any::Any;
 use serde::{Deserialize, Serialize};
 
-use common::callback::Callback;
+use common::{callback::Callback, time::Seconds};
 use engine::ui::{DrawDebugUi, UiSystem};
 use proc_macros::DrawDebugUi;
 
@@ -15,6 +15,7 @@ use super::{
     with_task,
 };
 use crate::{
+    config::GameConfigs,
     pathfind::{
         NodeKind as PathNodeKind,
         RandomDirectionalBias,
@@ -23,7 +24,8 @@ use crate::{
     tile::{Tile, TileFlags, TileKind, TileMapLayerKind},
     unit::{Unit, navigation::UnitNavGoal},
     building::{BuildingKind, BuildingTileInfo, BuildingVisitResult},
-    sim::{SimCmdQueue, SimContext},
+    sim::{RngStream, SimCmdQueue, SimContext},
+    system::settlers::SettlersSpawnSystem,
     world::object::GameObject,
 };
 
@@ -33,11 +35,20 @@ use crate::{
 
 pub type UnitTaskSettlerCompletionCallback = fn(&SimContext, &mut Unit, &Tile, u32);
 
+// Settlers can go off-road.
+pub const SETTLER_TRAVERSABLE_NODE_KINDS: PathNodeKind = PathNodeKind::from_bits_retain(
+    PathNodeKind::EmptyLand.bits()
+        | PathNodeKind::Road.bits()
+        | PathNodeKind::VacantLot.bits()
+        | PathNodeKind::SettlersSpawnPoint.bits(),
+);
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UnitTaskSettlerGoal {
     VacantLot,
     House,
     SpawnPointExit,
+    MapEdgeExit,
 }
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -46,13 +57,19 @@ pub enum UnitTaskSettlerState {
     #[default]
     Searching,
 
+    // Immigrant still traveling off-map. Hidden at the map edge until `off_map_time_left` runs out.
+    ArrivingFromOffMap,
+
+    // Walked off the map edge. Hidden until `off_map_time_left` runs out, then done.
+    LeavingOffMap,
+
     // Walking to the chosen destination.
     MovingTo(UnitTaskSettlerGoal),
 
     // At a house, waiting for the deferred visit to resolve.
     VisitingHouse,
 
-    // Settled (or left via the spawn point); terminal state.
+    // Settled (or left the map); terminal state.
     Done,
 }
 
@@ -81,6 +98,10 @@ pub struct UnitTaskSettler {
 
     pub state: UnitTaskSettlerState,
 
+    // Remaining time of the simulated off-map walk while arriving or leaving.
+    #[serde(default)]
+    pub off_map_time_left: Seconds,
+
     // Outcome of the deferred house visit, written by the visit callback and
     // consumed by `VisitingHouse`: `Some(true)` accepted, `Some(false)` refused.
     #[serde(skip)]
@@ -92,7 +113,7 @@ impl UnitTaskSettler {
         let sim_context = ctx.sim_context;
         let start = ctx.unit.cell();
         let traversable_node_kinds = ctx.unit.traversable_node_kinds();
-        let bias = RandomDirectionalBias::new(sim_context.rng_mut(), 0.1, 0.5);
+        let bias = RandomDirectionalBias::new(sim_context.rng_stream_mut(RngStream::UnitAi), 0.1, 0.5);
 
         // Emigrating settlers don't settle anywhere; they head straight for the exit.
         if !self.emigrate {
@@ -212,6 +233,10 @@ impl UnitTaskSettler {
             UnitTaskSettlerGoal::VacantLot | UnitTaskSettlerGoal::SpawnPointExit => {
                 self.finish_at_tile(ctx)
             }
+            UnitTaskSettlerGoal::MapEdgeExit => {
+                self.start_off_map_walk(ctx);
+                UnitTaskTransition::Goto(UnitTaskSettlerState::LeavingOffMap)
+            }
             UnitTaskSettlerGoal::House => {
                 if self.try_visit_house(ctx) {
                     UnitTaskTransition::Goto(UnitTaskSettlerState::VisitingHouse)
@@ -233,10 +258,15 @@ impl UnitTaskSettler {
         debug_assert!(destination_cell.is_valid());
 
         if let Some(tile) = sim_context.try_tile_from_layer(destination_cell, TileMapLayerKind::Terrain) {
-            if tile.path_kind().is_vacant_lot() || tile.has_flags(TileFlags::SettlersSpawnPoint) {
+            if tile.path_kind().is_vacant_lot() {
                 self.notify_completion(ctx.unit, sim_context, tile);
                 return UnitTaskTransition::Goto(UnitTaskSettlerState::Done);
             }
+
+            if tile.has_flags(TileFlags::SettlersSpawnPoint) {
+                self.notify_completion(ctx.unit, sim_context, tile);
+                return self.walk_to_map_edge(ctx);
+            }
         }
 
         // Destination tile is no longer a vacant lot / spawn point; search again.
@@ -244,6 +274,53 @@ impl UnitTaskSettler {
         UnitTaskTransition::Goto(UnitTaskSettlerState::Searching)
     }
 
+    // Keep walking from the spawn point to the nearest map edge, then off the map.
+    fn walk_to_map_edge(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskSettlerState> {
+        let start = ctx.unit.cell();
+
+        if let Some(edge_cell) = SettlersSpawnSystem::find_map_edge_cell(ctx.sim_context, start)
+            && edge_cell != start
+        {
+            let traversable_node_kinds = ctx.unit.traversable_node_kinds();
+            if let SearchResult::PathFound(path) = ctx.sim_context.find_path(traversable_node_kinds, start, edge_cell) {
+                ctx.unit.move_to_goal(path, UnitNavGoal::tile(start, path));
+                return UnitTaskTransition::Goto(UnitTaskSettlerState::MovingTo(UnitTaskSettlerGoal::MapEdgeExit));
+            }
+        }
+
+        // Already at the edge (or no way to it); leave from here.
+        self.start_off_map_walk(ctx);
+        UnitTaskTransition::Goto(UnitTaskSettlerState::LeavingOffMap)
+    }
+
+    // Hides the unit for the off-map segment of its walk, which is just a timed delay.
+    fn start_off_map_walk(&mut self, ctx: &mut UnitTaskContext) {
+        self.off_map_time_left = GameConfigs::get().sim.settlers_off_map_travel_secs;
+        ctx.unit.set_hidden(ctx.sim_context, true);
+    }
+
+    fn tick_off_map_walk(&mut self, ctx: &mut UnitTaskContext) -> bool {
+        self.off_map_time_left -= ctx.sim_context.delta_time_secs();
+        self.off_map_time_left <= 0.0
+    }
+
+    fn update_arriving(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskSettlerState> {
+        if !self.tick_off_map_walk(ctx) {
+            return UnitTaskTransition::Stay;
+        }
+
+        // Stepped onto the map; start looking for a place to settle.
+        ctx.unit.set_hidden(ctx.sim_context, false);
+        UnitTaskTransition::Goto(UnitTaskSettlerState::Searching)
+    }
+
+    fn update_leaving(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskSettlerState> {
+        if !self.tick_off_map_walk(ctx) {
+            return UnitTaskTransition::Stay;
+        }
+        UnitTaskTransition::Goto(UnitTaskSettlerState::Done)
+    }
+
     fn update_visiting_house(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskTransition<UnitTaskSettlerState> {
         match self.visit_outcome.take() {
             None => UnitTaskTransition::Stay, // Deferred visit not resolved yet.
@@ -274,12 +351,18 @@ impl UnitTaskState for UnitTaskSettlerState {
 
     fn update(self, task: &mut UnitTaskSettler, ctx: &mut UnitTaskContext) -> UnitTaskTransition<Self> {
         match self {
-            Self::Searching      => task.update_searching(ctx),
-            Self::MovingTo(goal) => task.update_moving(goal, ctx),
-            Self::VisitingHouse  => task.update_visiting_house(ctx),
-            Self::Done           => UnitTaskTransition::Done,
+            Self::Searching          => task.update_searching(ctx),
+            Self::ArrivingFromOffMap => task.update_arriving(ctx),
+            Self::LeavingOffMap      => task.update_leaving(ctx),
+            Self::MovingTo(goal)     => task.update_moving(goal, ctx),
+            Self::VisitingHouse      => task.update_visiting_house(ctx),
+            Self::Done               => UnitTaskTransition::Done,
         }
     }
+
+    fn is_interruptible(self) -> bool {
+        matches!(self, Self::Searching | Self::MovingTo(_))
+    }
 }
 
 impl UnitTask for UnitTaskSettler {
@@ -290,13 +373,13 @@ impl UnitTask for UnitTaskSettler {
 
         // Settlers can go off-road.
         let current_node_kinds = ctx.unit.traversable_node_kinds();
-        ctx.unit.set_traversable_node_kinds(
-            current_node_kinds
-            | PathNodeKind::EmptyLand
-            | PathNodeKind::Road
-            | PathNodeKind::VacantLot
-            | PathNodeKind::SettlersSpawnPoint
-        );
+        ctx.unit.set_traversable_node_kinds(current_node_kinds | SETTLER_TRAVERSABLE_NODE_KINDS);
+
+        // Immigrants start off-map; stay hidden at the map edge until they walk in.
+        if self.state == UnitTaskSettlerState::ArrivingFromOffMap {
+            self.off_map_time_left = GameConfigs::get().sim.settlers_off_map_travel_secs;
+            ctx.unit.set_hidden(ctx.sim_context, true);
+        }
     }
 
     fn terminate(&mut self, task_pool: &mut UnitTaskPool) {
@@ -331,6 +414,7 @@ impl UnitTask for UnitTaskSettler {
             return_to_spawn_point_if_failed: bool,
             #[debug_ui(label = "Emigrate (leave map)")]
             emigrate: bool,
+            off_map_time_left: Seconds,
             traversable_node_kinds: PathNodeKind,
         }
         View {
@@ -339,6 +423,7 @@ impl UnitTask for UnitTaskSettler {
             fallback_to_houses_with_room: self.fallback_to_houses_with_room,
             return_to_spawn_point_if_failed: self.return_to_spawn_point_if_failed,
             emigrate: self.emigrate,
+            off_map_time_left: self.off_map_time_left,
             traversable_node_kinds: unit.traversable_node_kinds(),
         }
         .draw_debug_ui(ui_sys);
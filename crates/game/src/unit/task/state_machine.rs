@@ -80,6 +80,12 @@ pub trait UnitTaskState: Copy + Default + Serialize + DeserializeOwned + 'static
 
     // Optional hook run when leaving this state (via `Goto`).
     fn on_exit(self, _task: &mut Self::Task, _ctx: &mut UnitTaskContext) {}
+
+    // Whether a higher priority task may take over while in this state (see `Unit::push_task`).
+    // The interrupted task resumes later from a different cell with its path cleared, so only
+    // states that re-route when the unit has no goal should opt in. States waiting on a
+    // deferred building visit must not, since the visit callback looks up the current task.
+    fn is_interruptible(self) -> bool { false }
 }
 
 // ----------------------------------------------
@@ -136,6 +142,7 @@ pub trait UnitTaskFsm {
     fn initialize(&mut self, ctx: &mut UnitTaskContext);
     fn terminate(&mut self, pool: &mut UnitTaskPool);
     fn run(&mut self, ctx: &mut UnitTaskContext) -> UnitTaskFlow;
+    fn can_interrupt(&mut self) -> bool;
     fn post_load(&mut self);
     fn draw_debug_ui(&mut self, unit: &mut Unit, sim_context: &SimContext, ui: &UiSystem);
     fn as_any(&self) -> &dyn Any;
@@ -167,6 +174,10 @@ impl<T: UnitTask> UnitTaskFsm for T {
         }
     }
 
+    fn can_interrupt(&mut self) -> bool {
+        self.state().is_interruptible()
+    }
+
     fn post_load(&mut self) {
         UnitTask::post_load(self);
     }
@@ -0,0 +1,180 @@
+// Player preferences edited from the Game/Sound/Graphics settings dialogs.
+//
+// Saved to `settings.json` under the per-user config directory (see `paths::user_config_path`),
+// so they survive reinstalls and never write into the (possibly read-only) assets directory.
+// Loaded right after GameConfigs and applied on top of it, before the Engine is initialized.
+
+use common::time::Seconds;
+use serde::{Deserialize, Serialize};
+
+use engine::{
+    file_sys::{self, paths::{self, FixedPath}},
+    log,
+    render::texture::TextureSettings,
+    save::{self, *},
+    sound::SoundGlobalSettings,
+};
+
+use crate::{config::GameConfigs, i18n::Language, tile::color_palette::ColorPalette};
+
+// ----------------------------------------------
+// UserSettings
+// ----------------------------------------------
+
+const USER_SETTINGS_FILE_NAME: &str = "settings.json";
+
+// Mirrors the GameConfigs fields exposed by the settings dialogs.
+#[derive(Serialize, Deserialize)]
+#[serde(default)] // Fields missing from the file keep the GameConfigs defaults.
+pub struct UserSettings {
+    // Game:
+    pub language: Language,
+    pub disable_key_shortcut_zoom: bool,
+    pub disable_mouse_scroll_zoom: bool,
+    pub disable_smooth_mouse_scroll_zoom: bool,
+    pub disable_trackpad_gestures: bool,
+    pub show_map_tooltips: bool,
+    pub map_tooltip_delay_secs: Seconds,
+
+    // Sound:
+    pub sound_settings: SoundGlobalSettings,
+    pub sound_output_device: Option<String>,
+    pub show_captions: bool,
+
+    // Graphics:
+    pub texture_settings: TextureSettings,
+    pub ui_scale: f32,
+    pub selection_outline: bool,
+    pub color_palette: ColorPalette,
+    pub hatch_patterns: bool,
+}
+
+impl UserSettings {
+    pub fn capture(configs: &GameConfigs) -> Self {
+        Self {
+            language: configs.language,
+            disable_key_shortcut_zoom: configs.camera.disable_key_shortcut_zoom,
+            disable_mouse_scroll_zoom: configs.camera.disable_mouse_scroll_zoom,
+            disable_smooth_mouse_scroll_zoom: configs.camera.disable_smooth_mouse_scroll_zoom,
+            disable_trackpad_gestures: configs.camera.disable_trackpad_gestures,
+            show_map_tooltips: configs.tooltips.show_map_tooltips,
+            map_tooltip_delay_secs: configs.tooltips.map_tooltip_delay_secs,
+            sound_settings: configs.engine.sound_settings,
+            sound_output_device: configs.engine.sound_output_device.clone(),
+            show_captions: configs.show_captions,
+            texture_settings: configs.engine.texture_settings,
+            ui_scale: configs.engine.ui_scale,
+            selection_outline: configs.engine.selection_outline,
+            color_palette: configs.color_palette,
+            hatch_patterns: configs.hatch_patterns,
+        }
+    }
+
+    pub fn apply(&self, configs: &mut GameConfigs) {
+        configs.language = self.language;
+        configs.camera.disable_key_shortcut_zoom = self.disable_key_shortcut_zoom;
+        configs.camera.disable_mouse_scroll_zoom = self.disable_mouse_scroll_zoom;
+        configs.camera.disable_smooth_mouse_scroll_zoom = self.disable_smooth_mouse_scroll_zoom;
+        configs.camera.disable_trackpad_gestures = self.disable_trackpad_gestures;
+        configs.tooltips.show_map_tooltips = self.show_map_tooltips;
+        configs.tooltips.map_tooltip_delay_secs = self.map_tooltip_delay_secs;
+        configs.engine.sound_settings = self.sound_settings;
+        configs.engine.sound_output_device = self.sound_output_device.clone();
+        configs.show_captions = self.show_captions;
+        configs.engine.texture_settings = self.texture_settings;
+        configs.engine.ui_scale = self.ui_scale;
+        configs.engine.selection_outline = self.selection_outline;
+        configs.color_palette = self.color_palette;
+        configs.hatch_patterns = self.hatch_patterns;
+    }
+
+    // Reads the user settings file and applies it to `configs`.
+    // Leaves `configs` untouched if there's no file yet (first run) or it fails to load.
+    pub fn load_and_apply(configs: &mut GameConfigs) {
+        let Some(file_path) = Self::file_path() else {
+            return;
+        };
+
+        if !file_sys::exists(&file_path) {
+            return;
+        }
+
+        let mut state = save::new_json_save_state(false);
+
+        if let Err(err) = state.read_file(&file_path) {
+            log::error!(log::channel!("settings"), "Failed to read user settings file {file_path}: {err}");
+            return;
+        }
+
+        match state.load_new_instance::<Self>() {
+            Ok(user_settings) => {
+                user_settings.apply(configs);
+                log::info!(log::channel!("settings"), "Loaded user settings from {file_path}.");
+            }
+            Err(err) => {
+                log::error!(log::channel!("settings"), "Failed to deserialize user settings file {file_path}: {err}");
+            }
+        }
+    }
+
+    // Writes the current GameConfigs values to the user settings file.
+    pub fn save(configs: &GameConfigs) -> bool {
+        let Some(file_path) = Self::file_path() else {
+            return false;
+        };
+
+        // First make sure the settings directory exists. Ignore any errors since
+        // this function might fail if any element of the path already exists.
+        let _ = file_sys::create_path(&file_path);
+
+        let mut state = save::new_json_save_state(true);
+
+        if let Err(err) = state.save(&Self::capture(configs)) {
+            log::error!(log::channel!("settings"), "Failed to save user settings: {err}");
+            return false;
+        }
+
+        if let Err(err) = state.write_file(&file_path) {
+            log::error!(log::channel!("settings"), "Failed to write user settings file {file_path}: {err}");
+            return false;
+        }
+
+        true
+    }
+
+    fn file_path() -> Option<FixedPath> {
+        paths::user_config_path().map(|dir| dir.join(USER_SETTINGS_FILE_NAME))
+    }
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self::capture(&GameConfigs::default())
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_restores_captured_settings() {
+        let mut configs = GameConfigs::default();
+        configs.language = Language::Portuguese;
+        configs.camera.disable_trackpad_gestures = true;
+        configs.engine.ui_scale = 1.5;
+
+        let user_settings = UserSettings::capture(&configs);
+
+        let mut new_configs = GameConfigs::default();
+        user_settings.apply(&mut new_configs);
+
+        assert!(new_configs.language == Language::Portuguese);
+        assert!(new_configs.camera.disable_trackpad_gestures);
+        assert_eq!(new_configs.engine.ui_scale, 1.5);
+    }
+}
@@ -0,0 +1,412 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned, ser::SerializeMap};
+
+use engine::log;
+
+use crate::{building::BuildingKindAndId, prop::PropId, unit::UnitId};
+
+// ----------------------------------------------
+// Component
+// ----------------------------------------------
+
+// Data that can be attached to any Building, Unit or Prop without touching their structs.
+// Components are saved with the World under their NAME, so renaming one breaks old saves.
+pub trait Component: Serialize + DeserializeOwned + 'static {
+    const NAME: &'static str;
+}
+
+type ComponentLoaderFn = fn(serde_json::Value) -> serde_json::Result<Box<dyn AnyComponentStorage>>;
+
+// NAME -> (TypeId, loader) for every registered component type.
+static COMPONENT_LOADERS: Mutex<Vec<(&'static str, TypeId, ComponentLoaderFn)>> = Mutex::new(Vec::new());
+
+// Component types must be registered at startup for their saved data to be restored on load.
+pub fn register_component<C: Component>() {
+    let mut loaders = COMPONENT_LOADERS.lock().unwrap();
+
+    if let Some((_, type_id, _)) = loaders.iter().find(|(name, ..)| *name == C::NAME) {
+        debug_assert!(*type_id == TypeId::of::<C>(), "Component name '{}' already registered by another type!", C::NAME);
+        return;
+    }
+
+    loaders.push((C::NAME, TypeId::of::<C>(), ComponentStorage::<C>::load));
+}
+
+// ----------------------------------------------
+// GameObjectKey
+// ----------------------------------------------
+
+// Identifies the GameObject owning a component. Keys hold the owner's pool index,
+// so World::compact_spawn_pools() remaps them along with the tile handles.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameObjectKey {
+    Building(BuildingKindAndId),
+    Unit(UnitId),
+    Prop(PropId),
+}
+
+impl std::fmt::Display for GameObjectKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Building(kind_and_id) => write!(f, "Building({}, {})", kind_and_id.kind, kind_and_id.id),
+            Self::Unit(id) => write!(f, "Unit({id})"),
+            Self::Prop(id) => write!(f, "Prop({id})"),
+        }
+    }
+}
+
+// ----------------------------------------------
+// ComponentStorage
+// ----------------------------------------------
+
+// Sparse set: components are packed in a dense array for fast iteration,
+// with a side table mapping each owner to its slot in the array.
+pub struct ComponentStorage<C> {
+    components: Vec<C>,
+    owners: Vec<GameObjectKey>, // Parallel to `components`.
+    slots: HashMap<GameObjectKey, u32>,
+}
+
+impl<C> ComponentStorage<C> {
+    pub fn new() -> Self {
+        Self { components: Vec::new(), owners: Vec::new(), slots: HashMap::new() }
+    }
+
+    // Returns the previous component if the owner already had one.
+    pub fn insert(&mut self, owner: GameObjectKey, component: C) -> Option<C> {
+        if let Some(&slot) = self.slots.get(&owner) {
+            return Some(std::mem::replace(&mut self.components[slot as usize], component));
+        }
+
+        self.slots.insert(owner, self.components.len() as u32);
+        self.components.push(component);
+        self.owners.push(owner);
+        None
+    }
+
+    pub fn remove(&mut self, owner: GameObjectKey) -> Option<C> {
+        let slot = self.slots.remove(&owner)? as usize;
+
+        // Move the last component into the vacated slot.
+        let component = self.components.swap_remove(slot);
+        self.owners.swap_remove(slot);
+
+        if let Some(moved_owner) = self.owners.get(slot) {
+            self.slots.insert(*moved_owner, slot as u32);
+        }
+
+        Some(component)
+    }
+
+    pub fn clear(&mut self) {
+        self.components.clear();
+        self.owners.clear();
+        self.slots.clear();
+    }
+
+    // Rekeys every component after its owner was moved to a new spawn pool slot.
+    pub fn remap_owners(&mut self, remap_fn: &dyn Fn(GameObjectKey) -> GameObjectKey) {
+        self.slots.clear();
+        for (slot, owner) in self.owners.iter_mut().enumerate() {
+            *owner = remap_fn(*owner);
+            self.slots.insert(*owner, slot as u32);
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, owner: GameObjectKey) -> Option<&C> {
+        let slot = *self.slots.get(&owner)?;
+        Some(&self.components[slot as usize])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, owner: GameObjectKey) -> Option<&mut C> {
+        let slot = *self.slots.get(&owner)?;
+        Some(&mut self.components[slot as usize])
+    }
+
+    #[inline]
+    pub fn contains(&self, owner: GameObjectKey) -> bool {
+        self.slots.contains_key(&owner)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    // Iteration order is unspecified and changes as components are removed.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (GameObjectKey, &C)> {
+        self.owners.iter().copied().zip(self.components.iter())
+    }
+
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (GameObjectKey, &mut C)> {
+        self.owners.iter().copied().zip(self.components.iter_mut())
+    }
+}
+
+impl<C: Component> ComponentStorage<C> {
+    fn load(saved: serde_json::Value) -> serde_json::Result<Box<dyn AnyComponentStorage>> {
+        let mut storage = Self::new();
+        for (owner, component) in serde_json::from_value::<Vec<(GameObjectKey, C)>>(saved)? {
+            storage.insert(owner, component);
+        }
+        Ok(Box::new(storage))
+    }
+}
+
+impl<C> Default for ComponentStorage<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Type-erased interface so Components can hold storages of any component type.
+trait AnyComponentStorage: Any {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn name(&self) -> &'static str;
+    fn remove_owner(&mut self, owner: GameObjectKey);
+    fn remap_owners(&mut self, remap_fn: &dyn Fn(GameObjectKey) -> GameObjectKey);
+    fn clear(&mut self);
+    fn is_empty(&self) -> bool;
+
+    fn save(&self) -> serde_json::Result<serde_json::Value>;
+}
+
+impl<C: Component> AnyComponentStorage for ComponentStorage<C> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn name(&self) -> &'static str {
+        C::NAME
+    }
+
+    fn remove_owner(&mut self, owner: GameObjectKey) {
+        self.remove(owner);
+    }
+
+    fn remap_owners(&mut self, remap_fn: &dyn Fn(GameObjectKey) -> GameObjectKey) {
+        ComponentStorage::remap_owners(self, remap_fn);
+    }
+
+    fn clear(&mut self) {
+        ComponentStorage::clear(self);
+    }
+
+    fn is_empty(&self) -> bool {
+        ComponentStorage::is_empty(self)
+    }
+
+    fn save(&self) -> serde_json::Result<serde_json::Value> {
+        let entries: Vec<(GameObjectKey, &C)> = self.iter().collect();
+        serde_json::to_value(entries)
+    }
+}
+
+// ----------------------------------------------
+// Components
+// ----------------------------------------------
+
+// Registry of all component storages in the World, one per component type.
+// Storages are created on first mutable access.
+#[derive(Default)]
+pub struct Components {
+    storages: HashMap<TypeId, Box<dyn AnyComponentStorage>>,
+
+    // Saved components of types that are not registered. Kept so that saving again doesn't drop them.
+    unknown: HashMap<String, serde_json::Value>,
+}
+
+impl Components {
+    #[inline]
+    pub fn storage<C: Component>(&self) -> Option<&ComponentStorage<C>> {
+        self.storages.get(&TypeId::of::<C>()).and_then(|storage| storage.as_any().downcast_ref())
+    }
+
+    pub fn storage_mut<C: Component>(&mut self) -> &mut ComponentStorage<C> {
+        let storage = self.storages.entry(TypeId::of::<C>()).or_insert_with(|| Box::new(ComponentStorage::<C>::new()));
+        storage.as_any_mut().downcast_mut().expect("Unexpected component storage type!")
+    }
+
+    #[inline]
+    pub fn get<C: Component>(&self, owner: GameObjectKey) -> Option<&C> {
+        self.storage::<C>()?.get(owner)
+    }
+
+    #[inline]
+    pub fn get_mut<C: Component>(&mut self, owner: GameObjectKey) -> Option<&mut C> {
+        self.storage_mut::<C>().get_mut(owner)
+    }
+
+    #[inline]
+    pub fn insert<C: Component>(&mut self, owner: GameObjectKey, component: C) -> Option<C> {
+        self.storage_mut::<C>().insert(owner, component)
+    }
+
+    #[inline]
+    pub fn remove<C: Component>(&mut self, owner: GameObjectKey) -> Option<C> {
+        self.storage_mut::<C>().remove(owner)
+    }
+
+    // Detaches every component from a despawned GameObject.
+    pub fn remove_all(&mut self, owner: GameObjectKey) {
+        for storage in self.storages.values_mut() {
+            storage.remove_owner(owner);
+        }
+    }
+
+    pub fn remap_owners(&mut self, remap_fn: &dyn Fn(GameObjectKey) -> GameObjectKey) {
+        for storage in self.storages.values_mut() {
+            storage.remap_owners(remap_fn);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for storage in self.storages.values_mut() {
+            storage.clear();
+        }
+        self.unknown.clear();
+    }
+}
+
+// ----------------------------------------------
+// Components Serialization
+// ----------------------------------------------
+
+// Saved as a map of component NAME -> [(owner, component), ...].
+impl Serialize for Components {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let non_empty_count = self.storages.values().filter(|storage| !storage.is_empty()).count();
+        let mut map = serializer.serialize_map(Some(non_empty_count + self.unknown.len()))?;
+
+        for storage in self.storages.values().filter(|storage| !storage.is_empty()) {
+            let value = storage.save().map_err(serde::ser::Error::custom)?;
+            map.serialize_entry(storage.name(), &value)?;
+        }
+
+        for (name, value) in &self.unknown {
+            map.serialize_entry(name, value)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Components {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let saved = HashMap::<String, serde_json::Value>::deserialize(deserializer)?;
+        let loaders = COMPONENT_LOADERS.lock().unwrap();
+
+        let mut components = Self::default();
+
+        for (name, value) in saved {
+            let Some((_, type_id, load_fn)) = loaders.iter().find(|(loader_name, ..)| *loader_name == name) else {
+                log::error!(log::channel!("world"), "Saved component '{name}' is not registered. Keeping it as-is.");
+                components.unknown.insert(name, value);
+                continue;
+            };
+
+            let storage = load_fn(value).map_err(|err| {
+                log::error!(log::channel!("world"), "Failed to load '{name}' components: {err}");
+                serde::de::Error::custom(err)
+            })?;
+
+            components.storages.insert(*type_id, storage);
+        }
+
+        Ok(components)
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Health(u32);
+
+    impl Component for Health {
+        const NAME: &'static str = "Health";
+    }
+
+    fn unit_key(generation: u32, index: usize) -> GameObjectKey {
+        GameObjectKey::Unit(UnitId::new(generation, index))
+    }
+
+    #[test]
+    fn remove_keeps_remaining_components_reachable() {
+        let mut storage = ComponentStorage::new();
+        storage.insert(unit_key(1, 0), Health(10));
+        storage.insert(unit_key(2, 1), Health(20));
+        storage.insert(unit_key(3, 2), Health(30));
+
+        assert_eq!(storage.remove(unit_key(1, 0)), Some(Health(10)));
+        assert_eq!(storage.len(), 2);
+        assert_eq!(storage.get(unit_key(2, 1)), Some(&Health(20)));
+        assert_eq!(storage.get(unit_key(3, 2)), Some(&Health(30)));
+        assert!(!storage.contains(unit_key(1, 0)));
+
+        assert_eq!(storage.insert(unit_key(3, 2), Health(35)), Some(Health(30)));
+        assert_eq!(storage.len(), 2);
+    }
+
+    #[test]
+    fn remapped_owners_follow_their_new_slots() {
+        let mut storage = ComponentStorage::new();
+        storage.insert(unit_key(7, 40), Health(5));
+        storage.insert(unit_key(8, 2), Health(6));
+
+        // Spawn pool compaction moved the first owner down to slot 3.
+        storage.remap_owners(&|owner| if owner == unit_key(7, 40) { unit_key(7, 3) } else { owner });
+
+        assert_eq!(storage.get(unit_key(7, 3)), Some(&Health(5)));
+        assert_eq!(storage.get(unit_key(8, 2)), Some(&Health(6)));
+        assert!(!storage.contains(unit_key(7, 40)));
+    }
+
+    #[test]
+    fn saved_components_round_trip() {
+        register_component::<Health>();
+
+        let mut components = Components::default();
+        components.insert(unit_key(1, 0), Health(42));
+        components.insert(unit_key(2, 1), Health(7));
+        components.remove_all(unit_key(2, 1));
+
+        let json = serde_json::to_string(&components).unwrap();
+        let mut loaded: Components = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(loaded.get::<Health>(unit_key(1, 0)), Some(&Health(42)));
+        assert!(loaded.get::<Health>(unit_key(2, 1)).is_none());
+        assert_eq!(loaded.storage_mut::<Health>().len(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+use std::{fmt::Debug, path::{Path, PathBuf}};
+
+use common::time::{CountdownTimer, Seconds};
+use engine::{config, file_sys::watcher::FileWatcher, log};
+
+use super::World;
+use crate::{
+    building::{BuildingKind, config::BuildingConfigs},
+    sim::RandomGenerator,
+    unit::config::UnitConfigs,
+};
+
+// ----------------------------------------------
+// HotReloadableConfig
+// ----------------------------------------------
+
+// Config entries that can be patched in place from a freshly loaded copy. World objects keep
+// `&'static` references to their configs, so only values that are safe to change under a live
+// object are copied; anything structural (kinds, stock layout, workers) still needs a restart.
+pub(crate) trait HotReloadableConfig {
+    // Identifies the same entry across reloads.
+    fn reload_key(&self) -> &str;
+
+    // Copies the runtime-safe values from `new_config`. Returns true if any of them changed.
+    fn hot_reload_from(&mut self, new_config: &Self) -> bool;
+}
+
+pub(crate) fn hot_reload_configs<C: HotReloadableConfig>(type_name: &str, configs: &mut [C], new_configs: &[C]) -> usize {
+    let mut changed_count = 0;
+
+    for new_config in new_configs {
+        match configs.iter_mut().find(|config| config.reload_key() == new_config.reload_key()) {
+            Some(config) => {
+                if config.hot_reload_from(new_config) {
+                    changed_count += 1;
+                }
+            }
+            None => {
+                log::warning!(
+                    log::channel!("config"),
+                    "{type_name} '{}': New entry. Restart to apply.",
+                    new_config.reload_key()
+                );
+            }
+        }
+    }
+
+    for config in configs.iter() {
+        if !new_configs.iter().any(|new_config| new_config.reload_key() == config.reload_key()) {
+            log::warning!(
+                log::channel!("config"),
+                "{type_name} '{}': Entry removed or invalid. Restart to apply.",
+                config.reload_key()
+            );
+        }
+    }
+
+    changed_count
+}
+
+// Assigns `new_value` to `field` and logs the change, if any.
+pub(crate) fn reload_field<T>(config_name: &str, field_name: &str, field: &mut T, new_value: T) -> bool
+where
+    T: Copy + PartialEq + Debug,
+{
+    if *field == new_value {
+        return false;
+    }
+
+    log::info!(log::channel!("config"), "'{config_name}': {field_name} {field:?} -> {new_value:?}");
+    *field = new_value;
+    true
+}
+
+// Reloads the building and/or unit config files and refreshes the values world objects cached from them.
+pub fn reload_object_configs(world: &mut World, rng: &mut RandomGenerator, reload_buildings: bool, reload_units: bool) {
+    if reload_buildings && BuildingConfigs::hot_reload() {
+        let mut building_count = 0;
+        for kinds in [BuildingKind::producers(), BuildingKind::services(), BuildingKind::storage()] {
+            world.for_each_building_mut(kinds, |building| {
+                building.configs_reloaded(rng);
+                building_count += 1;
+                true
+            });
+        }
+        log::info!(log::channel!("config"), "Config hot-reload: {building_count} buildings refreshed.");
+    }
+
+    if reload_units && UnitConfigs::hot_reload() {
+        let mut unit_count = 0;
+        world.for_each_unit_mut(|unit| {
+            unit.configs_reloaded();
+            unit_count += 1;
+            true
+        });
+        log::info!(log::channel!("config"), "Config hot-reload: {unit_count} units refreshed.");
+    }
+}
+
+// ----------------------------------------------
+// ObjectConfigHotReloader
+// ----------------------------------------------
+
+// Watches the building and unit config files while the game runs, so they
+// can be tuned without restarting. Desktop builds with loose assets only.
+pub struct ObjectConfigHotReloader {
+    watcher: FileWatcher,
+    reload_buildings: bool,
+    reload_units: bool,
+
+    // Editors often save a file in several writes. Wait for them to settle before reloading.
+    settle_timer: CountdownTimer,
+}
+
+impl ObjectConfigHotReloader {
+    const SETTLE_DELAY_SECS: Seconds = 0.5;
+
+    const BUILDING_CONFIGS_FILE: &str = "buildings/configs.json";
+    const UNIT_CONFIGS_FILE: &str = "units/configs.json";
+
+    pub fn new() -> Option<Self> {
+        let watcher = FileWatcher::new(config::configs_path())?;
+
+        Some(Self {
+            watcher,
+            reload_buildings: false,
+            reload_units: false,
+            settle_timer: CountdownTimer::new(0.0),
+        })
+    }
+
+    pub fn update(&mut self, world: &mut World, rng: &mut RandomGenerator, delta_time_secs: Seconds) {
+        let changed_files: Vec<PathBuf> = self.watcher.poll_changed_files();
+
+        if !changed_files.is_empty() {
+            for file in &changed_files {
+                self.reload_buildings |= file == Path::new(Self::BUILDING_CONFIGS_FILE);
+                self.reload_units |= file == Path::new(Self::UNIT_CONFIGS_FILE);
+            }
+            self.settle_timer.reset(Self::SETTLE_DELAY_SECS);
+            return;
+        }
+
+        if !(self.reload_buildings || self.reload_units) || !self.settle_timer.tick(delta_time_secs) {
+            return;
+        }
+
+        reload_object_configs(world, rng, self.reload_buildings, self.reload_units);
+
+        self.reload_buildings = false;
+        self.reload_units = false;
+    }
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestConfig {
+        key: &'static str,
+        speed: f32,
+    }
+
+    impl HotReloadableConfig for TestConfig {
+        fn reload_key(&self) -> &str {
+            self.key
+        }
+
+        fn hot_reload_from(&mut self, new_config: &Self) -> bool {
+            reload_field(self.key, "speed", &mut self.speed, new_config.speed)
+        }
+    }
+
+    #[test]
+    fn hot_reload_patches_matching_entries_only() {
+        let mut configs = [TestConfig { key: "a", speed: 1.0 }, TestConfig { key: "b", speed: 2.0 }];
+        let new_configs = [TestConfig { key: "b", speed: 3.0 }, TestConfig { key: "c", speed: 4.0 }];
+
+        assert_eq!(hot_reload_configs("TestConfig", &mut configs, &new_configs), 1);
+        assert_eq!(configs[0].speed, 1.0); // Removed from file; left as is.
+        assert_eq!(configs[1].speed, 3.0);
+
+        // Reloading the same values again changes nothing.
+        assert_eq!(hot_reload_configs("TestConfig", &mut configs, &new_configs), 0);
+    }
+}
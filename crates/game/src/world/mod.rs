@@ -6,6 +6,9 @@ use common::coords::{Cell, CellRange, WorldToScreenTransform};
 use engine::{log, save::*, ui::UiSystem};
 use object::*;
 use stats::*;
+use traffic::*;
+use spatial::UnitSpatialIndex;
+use component::{Components, GameObjectKey};
 
 use crate::{
     building::{
@@ -14,13 +17,15 @@ use crate::{
         BuildingId,
         BuildingKind,
         BuildingArchetypeKind,
+        BuildingKindAndId,
         config::BuildingConfigs,
+        hazard::RaidDamage,
     },
     constants::*,
     save_context::*,
     debug::DebugUiMode,
     prop::{Prop, PropId, config::PropConfigs},
-    sim::{SimCmds, SimContext},
+    sim::{RngStream, SimCmds, SimContext},
     tile::{
         Tile,
         TileKind,
@@ -38,8 +43,12 @@ use crate::{
     },
 };
 
+pub mod component;
+pub mod config_reload;
 pub mod object;
+pub mod spatial;
 pub mod stats;
+pub mod traffic;
 
 // ----------------------------------------------
 // World
@@ -54,6 +63,14 @@ pub struct World {
     #[serde(skip)]
     stats: WorldStats,
 
+    // Walker steps per cell, feeds the traffic heatmap.
+    #[serde(skip)]
+    traffic: TrafficCounters,
+
+    // Spawned units bucketed by map region, for proximity queries.
+    #[serde(skip)]
+    unit_spatial_index: UnitSpatialIndex,
+
     // One spawn pool per building archetype.
     // Iteration yields only *spawned* buildings.
     building_spawn_pools: [(BuildingArchetypeKind, SpawnPool<Building>); BUILDING_ARCHETYPE_COUNT],
@@ -64,6 +81,11 @@ pub struct World {
 
     // All world props (e.g. trees).
     prop_spawn_pool: SpawnPool<Prop>,
+
+    // Extra per-object data attached to buildings, units and props.
+    // Removed automatically when the owning object despawns.
+    #[serde(default)]
+    components: Components,
 }
 
 impl World {
@@ -72,6 +94,8 @@ impl World {
             locked: false,
             // World Stats:
             stats: WorldStats::default(),
+            traffic: TrafficCounters::default(),
+            unit_spatial_index: UnitSpatialIndex::default(),
             // Buildings:
             building_spawn_pools: [
                 (
@@ -95,6 +119,8 @@ impl World {
             unit_spawn_pool: SpawnPool::new(UNIT_SPAWN_POOL_CAPACITY, INITIAL_GENERATION),
             // Props:
             prop_spawn_pool: SpawnPool::new(PROP_SPAWN_POOL_CAPACITY, INITIAL_GENERATION),
+            // Components:
+            components: Components::default(),
         }
     }
 
@@ -107,11 +133,32 @@ impl World {
 
         self.unit_spawn_pool.clear(context, Unit::despawned);
         self.prop_spawn_pool.clear(context, Prop::despawned);
+        self.unit_spatial_index.clear();
+        self.components.clear();
+
+        // Nothing is spawned anymore, so this just releases slots the pools grew past their initial capacity.
+        self.compact_spawn_pools(context.tile_map_mut());
+        self.clear_spawn_pool_relocations();
+
+        self.traffic.reset();
     }
 
     pub fn update_unit_navigation(&mut self, context: &SimContext) {
         for unit in self.unit_spawn_pool.iter_mut() {
+            let cell_before = unit.cell();
             unit.update_navigation(context);
+
+            let cell_after = unit.cell();
+            if cell_after != cell_before && cell_after.is_valid() {
+                self.traffic.record_step(cell_after, context.map_size_in_cells());
+                self.unit_spatial_index.sync(unit.id(), cell_after);
+            }
+        }
+    }
+
+    pub fn interpolate_unit_render_positions(&mut self, context: &SimContext, alpha: f32) {
+        for unit in self.unit_spawn_pool.iter_mut() {
+            unit.interpolate_render_position(context, alpha);
         }
     }
 
@@ -123,6 +170,9 @@ impl World {
         for unit in self.unit_spawn_pool.iter_mut() {
             unit.update(cmds, context);
             unit.tally(&mut self.stats);
+
+            // Catches units moved outside of navigation, e.g. teleported by tasks.
+            self.unit_spatial_index.sync(unit.id(), unit.cell());
         }
 
         for prop in self.prop_spawn_pool.iter_mut() {
@@ -137,6 +187,8 @@ impl World {
                 building.tally(&mut self.stats);
             }
         }
+
+        self.traffic.update(context);
     }
 
     #[inline]
@@ -149,6 +201,11 @@ impl World {
         &mut self.stats
     }
 
+    #[inline]
+    pub fn traffic(&self) -> &TrafficCounters {
+        &self.traffic
+    }
+
     #[inline]
     pub fn lock(&mut self) {
         self.locked = true;
@@ -184,6 +241,15 @@ impl World {
         (self.prop_spawn_pool.spawned_count(), self.prop_spawn_pool.spawned_peak())
     }
 
+    // Spawn pools only. Heap data owned by individual objects is not counted.
+    pub fn memory_usage_estimate(&self) -> usize {
+        let mut estimate = self.unit_spawn_pool.memory_usage_estimate() + self.prop_spawn_pool.memory_usage_estimate();
+        for (_, buildings) in &self.building_spawn_pools {
+            estimate += buildings.memory_usage_estimate();
+        }
+        estimate
+    }
+
     pub fn find_game_object_for_tile(&self, tile: &Tile) -> Option<&dyn GameObject> {
         if tile.is(TileKind::Building) {
             self.find_building_for_tile(tile).map(|building| building as &dyn GameObject)
@@ -208,11 +274,78 @@ impl World {
         }
     }
 
+    // ----------------------
+    // Components:
+    // ----------------------
+
+    #[inline]
+    pub fn components(&self) -> &Components {
+        &self.components
+    }
+
+    #[inline]
+    pub fn components_mut(&mut self) -> &mut Components {
+        debug_assert!(!self.is_locked(), "Cannot mutate locked world!");
+        &mut self.components
+    }
+
+    // ----------------------
+    // Spawn pool compaction:
+    // ----------------------
+
+    // Defragments every spawn pool and patches the tiles whose GameObject was moved.
+    // Only call at safe points (load, map reset). Returns the number of relocated objects.
+    // Ids issued before the compaction keep resolving until clear_spawn_pool_relocations().
+    pub fn compact_spawn_pools(&mut self, tile_map: &mut TileMap) -> usize {
+        debug_assert!(!self.is_locked(), "Cannot mutate locked world!");
+
+        let fixup = GameObjectHandleFixup {
+            buildings: self.building_spawn_pools.each_mut().map(|(_, buildings)| buildings.compact()),
+            units: self.unit_spawn_pool.compact(),
+            props: self.prop_spawn_pool.compact(),
+        };
+
+        let relocated_count = fixup.relocated_count();
+        if relocated_count != 0 {
+            tile_map.for_each_tile_mut(TileKind::Building | TileKind::Unit | TileKind::Prop, |_tile_map, tile| {
+                fixup.visit(tile);
+            });
+        }
+
+        // Indexed unit ids and component owners still hold their pre-compaction slot indices.
+        if !fixup.units.is_empty() {
+            self.rebuild_unit_spatial_index();
+        }
+
+        if relocated_count != 0 {
+            self.components.remap_owners(&|owner| fixup.remap_owner(owner));
+        }
+
+        relocated_count
+    }
+
+    pub fn clear_spawn_pool_relocations(&mut self) {
+        for (_, buildings) in &mut self.building_spawn_pools {
+            buildings.clear_relocations();
+        }
+        self.unit_spawn_pool.clear_relocations();
+        self.prop_spawn_pool.clear_relocations();
+    }
+
+    fn rebuild_unit_spatial_index(&mut self) {
+        self.unit_spatial_index.clear();
+        for unit in self.unit_spawn_pool.iter() {
+            self.unit_spatial_index.insert(unit.id(), unit.cell());
+        }
+    }
+
     // ----------------------
     // Callbacks:
     // ----------------------
 
     pub fn register_callbacks() {
+        component::register_component::<RaidDamage>();
+
         Building::register_callbacks();
         Unit::register_callbacks();
         Prop::register_callbacks();
@@ -237,9 +370,14 @@ impl World {
         match context.try_place_tile(tile_base_cell, tile_def) {
             Ok(tile) => {
                 // Instantiate new Building:
-                match BuildingConfigs::get().new_building_archetype_for_tile_def(tile_def, context.rng_mut()) {
+                let rng = context.rng_stream_mut(RngStream::Placement);
+                match BuildingConfigs::get().new_building_archetype_for_tile_def(tile_def, rng) {
                     Ok((building_kind, building_archetype)) => {
                         let archetype_kind = building_archetype.discriminant();
+                        if archetype_kind == BuildingArchetypeKind::HouseBuilding {
+                            self.house_cells_changed(tile.cell_range());
+                        }
+
                         let buildings = self.buildings_pool_mut(archetype_kind);
 
                         let building = buildings.spawn(context, |building, context, id| {
@@ -247,9 +385,10 @@ impl World {
                         });
                         debug_assert!(building.is_spawned());
 
-                        // Store building index and kind so we can refer back to it from the Tile instance.
+                        // Store building index, generation and kind so we can refer back to it from the Tile instance.
                         tile.set_game_object_handle(TileGameObjectHandle::new_building(
                             building.id().index(),
+                            building.id().generation(),
                             building_kind.bits(),
                         ));
 
@@ -314,12 +453,19 @@ impl World {
         let pool_index = game_object_handle.index();
         let building_kind = BuildingKind::from_game_object_handle(game_object_handle);
         let archetype_kind = building_kind.archetype_kind();
+        if archetype_kind == BuildingArchetypeKind::HouseBuilding {
+            self.house_cells_changed(building.cell_range());
+        }
+
         let buildings = self.buildings_pool_mut(archetype_kind);
 
         debug_assert!(pool_index == building.id().index());
+        debug_assert!(game_object_handle.generation() == building.id().generation());
 
         // Put the building instance back into the spawn pool.
+        let owner = GameObjectKey::Building(building.kind_and_id());
         buildings.despawn(building, context, Building::despawned);
+        self.components.remove_all(owner);
         Ok(())
     }
 
@@ -355,11 +501,16 @@ impl World {
     pub fn find_building_for_tile(&self, tile: &Tile) -> Option<&Building> {
         let game_object_handle = tile.game_object_handle();
         if game_object_handle.is_valid() {
-            let pool_index = game_object_handle.index();
+            let id = BuildingId::new(game_object_handle.generation(), game_object_handle.index());
             let building_kind = BuildingKind::from_game_object_handle(game_object_handle);
             let archetype_kind = building_kind.archetype_kind();
             let buildings = self.buildings_pool(archetype_kind);
-            return buildings.try_get_at(pool_index); // NOTE: Does not perform generation check.
+
+            let building = buildings.try_get(id);
+            if building.is_none() {
+                Self::log_stale_building_handle(tile, game_object_handle);
+            }
+            return building;
         }
         None
     }
@@ -369,15 +520,35 @@ impl World {
         debug_assert!(!self.is_locked(), "Cannot mutate locked world!");
         let game_object_handle = tile.game_object_handle();
         if game_object_handle.is_valid() {
-            let pool_index = game_object_handle.index();
+            let id = BuildingId::new(game_object_handle.generation(), game_object_handle.index());
             let building_kind = BuildingKind::from_game_object_handle(game_object_handle);
             let archetype_kind = building_kind.archetype_kind();
             let buildings = self.buildings_pool_mut(archetype_kind);
-            return buildings.try_get_at_mut(pool_index); // NOTE: Does not perform generation check.
+
+            let building = buildings.try_get_mut(id);
+            if building.is_none() {
+                Self::log_stale_building_handle(tile, game_object_handle);
+            }
+            return building;
         }
         None
     }
 
+    // A valid handle that doesn't resolve means the tile outlived its building or
+    // the pool slot was recycled for a different building. Either way it's a bug.
+    #[cold]
+    fn log_stale_building_handle(tile: &Tile, game_object_handle: TileGameObjectHandle) {
+        if cfg!(debug_assertions) {
+            log::error!(
+                log::channel!("world"),
+                "Stale TileGameObjectHandle on Building tile '{}' {}: {:?}",
+                tile.name(),
+                tile.base_cell(),
+                game_object_handle
+            );
+        }
+    }
+
     #[inline]
     pub fn find_building_for_cell(&self, cell: Cell, tile_map: &TileMap) -> Option<&Building> {
         if let Some(tile) = tile_map.find_tile(cell, TileKind::Building | TileKind::Blocker) {
@@ -439,6 +610,17 @@ impl World {
         }
     }
 
+    // Employers cache the houses they can hire from; let them know a house came or went.
+    fn house_cells_changed(&mut self, house_cells: CellRange) {
+        for (archetype_kind, buildings) in &mut self.building_spawn_pools {
+            if *archetype_kind != BuildingArchetypeKind::HouseBuilding {
+                for building in buildings.iter_mut() {
+                    building.house_cells_changed(house_cells);
+                }
+            }
+        }
+    }
+
     #[inline]
     fn buildings_pool(&self, archetype_kind: BuildingArchetypeKind) -> &SpawnPool<Building> {
         let (pool_archetype, buildings) = &self.building_spawn_pools[archetype_kind as usize];
@@ -510,6 +692,7 @@ impl World {
 
                     // Store unit index so we can refer back to it from the Tile instance.
                     tile.set_game_object_handle(TileGameObjectHandle::new_unit(unit.id().index(), unit.id().generation()));
+                    self.unit_spatial_index.insert(unit.id(), unit.cell());
 
                     Ok(unit)
                 }
@@ -559,6 +742,7 @@ impl World {
 
                 // Store unit index so we can refer back to it from the Tile instance.
                 tile.set_game_object_handle(TileGameObjectHandle::new_unit(unit.id().index(), unit.id().generation()));
+                self.unit_spatial_index.insert(unit.id(), unit.cell());
 
                 Ok(unit)
             }
@@ -622,7 +806,10 @@ impl World {
                 tile_map.try_clear_tile_from_layer_by_index(*tile_index, tile_cell, TileMapLayerKind::Objects)?;
 
                 // Put the unit instance back into the spawn pool.
+                let owner = GameObjectKey::Unit(unit.id());
+                self.unit_spatial_index.remove(unit.id());
                 self.unit_spawn_pool.despawn(unit, context, Unit::despawned);
+                self.components.remove_all(owner);
                 return Ok(());
             }
         }
@@ -688,7 +875,11 @@ impl World {
 
         // Despawn all units at this cell.
         for unit in units {
-            context.world_mut().unit_spawn_pool.despawn(unit, context, Unit::despawned);
+            let world = context.world_mut();
+            let owner = GameObjectKey::Unit(unit.id());
+            world.unit_spatial_index.remove(unit.id());
+            world.unit_spawn_pool.despawn(unit, context, Unit::despawned);
+            world.components.remove_all(owner);
         }
 
         Ok(())
@@ -780,6 +971,26 @@ impl World {
         }
     }
 
+    // Visits spawned units within `radius` cells (manhattan) of `area`, e.g. a building's cell range.
+    // Only looks at units bucketed near the area. Visitor returns false to stop early.
+    #[inline]
+    pub fn for_each_unit_near<F>(&self, area: CellRange, radius: i32, mut visitor_fn: F)
+    where
+        F: FnMut(&Unit) -> bool,
+    {
+        self.unit_spatial_index.for_each_unit_near(area, radius, |id, _cell| {
+            match self.unit_spawn_pool.try_get(id) {
+                Some(unit) => visitor_fn(unit),
+                None => true,
+            }
+        });
+    }
+
+    #[inline]
+    pub fn unit_spatial_index(&self) -> &UnitSpatialIndex {
+        &self.unit_spatial_index
+    }
+
     // ----------------------
     // Units debug:
     // ----------------------
@@ -881,7 +1092,9 @@ impl World {
         tile_map.try_clear_tile_from_layer(tile_base_cell, TileMapLayerKind::Objects)?;
 
         // Despawn prop instance:
+        let owner = GameObjectKey::Prop(prop.id());
         self.prop_spawn_pool.despawn(prop, context, Prop::despawned);
+        self.components.remove_all(owner);
         Ok(())
     }
 
@@ -1010,6 +1223,68 @@ impl World {
 
 }
 
+// ----------------------------------------------
+// GameObjectHandleFixup
+// ----------------------------------------------
+
+// Rewrites the TileGameObjectHandle of tiles and the component owners whose GameObject was moved by a spawn pool compaction.
+struct GameObjectHandleFixup {
+    buildings: [SpawnPoolRemap; BUILDING_ARCHETYPE_COUNT], // Indexed by BuildingArchetypeKind.
+    units: SpawnPoolRemap,
+    props: SpawnPoolRemap,
+}
+
+impl GameObjectHandleFixup {
+    fn relocated_count(&self) -> usize {
+        self.buildings.iter().map(SpawnPoolRemap::len).sum::<usize>() + self.units.len() + self.props.len()
+    }
+
+    fn remap_owner(&self, owner: GameObjectKey) -> GameObjectKey {
+        match owner {
+            GameObjectKey::Building(BuildingKindAndId { kind, id }) => {
+                match self.buildings[kind.archetype_kind() as usize].new_index(id.index()) {
+                    Some(new_index) => GameObjectKey::Building(BuildingKindAndId {
+                        kind,
+                        id: BuildingId::new(id.generation(), new_index),
+                    }),
+                    None => owner,
+                }
+            }
+            GameObjectKey::Unit(id) => match self.units.new_index(id.index()) {
+                Some(new_index) => GameObjectKey::Unit(UnitId::new(id.generation(), new_index)),
+                None => owner,
+            },
+            GameObjectKey::Prop(id) => match self.props.new_index(id.index()) {
+                Some(new_index) => GameObjectKey::Prop(PropId::new(id.generation(), new_index)),
+                None => owner,
+            },
+        }
+    }
+
+    fn visit(&self, tile: &mut Tile) {
+        let handle = tile.game_object_handle();
+        if !handle.is_valid() {
+            return;
+        }
+
+        if tile.is(TileKind::Building) {
+            let archetype_kind = BuildingKind::from_game_object_handle(handle).archetype_kind();
+            if let Some(new_index) = self.buildings[archetype_kind as usize].new_index(handle.index()) {
+                let new_handle = TileGameObjectHandle::new_building(new_index, handle.generation(), handle.kind());
+                tile.set_game_object_handle(new_handle);
+            }
+        } else if tile.is(TileKind::Unit)
+            && let Some(new_index) = self.units.new_index(handle.index())
+        {
+            tile.set_game_object_handle(TileGameObjectHandle::new_unit(new_index, handle.generation()));
+        } else if tile.is(TileKind::Prop)
+            && let Some(new_index) = self.props.new_index(handle.index())
+        {
+            tile.set_game_object_handle(TileGameObjectHandle::new_prop(new_index, handle.generation()));
+        }
+    }
+}
+
 // ----------------------------------------------
 // Save/Load for World
 // ----------------------------------------------
@@ -1094,6 +1369,8 @@ impl Load for World {
             unit.tally(&mut self.stats);
         }
 
+        self.rebuild_unit_spatial_index();
+
         for prop in self.prop_spawn_pool.iter_mut() {
             prop.post_load(context);
             prop.tally(&mut self.stats);
@@ -1,6 +1,7 @@
 #![allow(clippy::while_let_on_iterator)]
 
 use core::{iter, slice};
+use std::collections::HashMap;
 use bitvec::vec::BitVec;
 use serde::{
     Deserialize,
@@ -20,7 +21,7 @@ use crate::{
     constants::*,
     save_context::*,
     debug::DebugUiMode,
-    sim::{SimCmds, SimContext},
+    sim::{RngStream, SimCmds, SimContext},
     undo_redo::GameObjectSavedState,
     prop::{Prop, PropId},
     building::{Building, BuildingKindAndId},
@@ -108,6 +109,9 @@ pub trait GameObject {
     fn update(&mut self, cmds: &mut SimCmds, context: &SimContext);
     fn tally(&self, stats: &mut WorldStats);
 
+    // Spawn pool compaction moved the instance to a new slot. Same generation, new index.
+    fn relocate(&mut self, new_id: GenerationalIndex);
+
     // Save/load support:
     fn pre_save(&mut self, _context: &mut PreSaveContext)   {}
     fn post_save(&mut self, _context: &mut PostSaveContext) {}
@@ -145,6 +149,35 @@ pub struct SpawnPool<T> {
     spawned: BitVec,
     generation: u32,
     peak: usize,
+    capacity: usize, // Preallocated slots. Compaction never shrinks the pool below this.
+
+    // Generation -> current index of every instance moved by the last compact(), so that
+    // ids issued before the move still resolve in try_get() until clear_relocations().
+    // Not serialized.
+    relocations: HashMap<u32, u32>,
+}
+
+// Old -> new pool index of every instance moved by SpawnPool::compact().
+#[derive(Default)]
+pub struct SpawnPoolRemap {
+    moved: HashMap<usize, usize>,
+}
+
+impl SpawnPoolRemap {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.moved.is_empty()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.moved.len()
+    }
+
+    #[inline]
+    pub fn new_index(&self, old_index: usize) -> Option<usize> {
+        self.moved.get(&old_index).copied()
+    }
 }
 
 pub struct SpawnPoolIter<'a, T> {
@@ -199,7 +232,9 @@ where
             instances: vec![default_instance; capacity],
             spawned: BitVec::repeat(false, capacity),
             generation,
-            peak: 0
+            peak: 0,
+            capacity,
+            relocations: HashMap::new(),
         }
     }
 
@@ -215,6 +250,7 @@ where
 
         self.instances.fill(T::default());
         self.spawned.fill(false);
+        self.relocations.clear();
     }
 
     pub fn spawn<F>(&mut self, context: &SimContext, on_spawned_fn: F) -> &mut T
@@ -264,10 +300,62 @@ where
         debug_assert!(self.spawned[index]);
         debug_assert!(std::ptr::eq(&self.instances[index], instance)); // Ensure addresses are the same.
 
+        if !self.relocations.is_empty() {
+            self.relocations.remove(&instance.id().generation());
+        }
+
         on_despawned_fn(instance, context);
         self.spawned.set(index, false);
     }
 
+    // Moves spawned instances down into the free slots left by despawned ones, then releases
+    // the unused tail. Moved instances keep their generation but get a new index, so any index
+    // cached outside the pool (e.g. TileGameObjectHandle) must be patched with the returned remap.
+    // Only call at safe points, when no references into the pool are held (load, map reset).
+    pub fn compact(&mut self) -> SpawnPoolRemap {
+        debug_assert!(self.is_valid());
+
+        self.relocations.clear();
+
+        let spawned_count = self.spawned_count();
+
+        // Once compacted, the first `spawned_count` slots are all spawned. Each instance
+        // past that boundary moves into one of the free slots below it; counts always match.
+        let free_slots = self.spawned[..spawned_count].iter_zeros();
+        let stray_instances = self.spawned[spawned_count..].iter_ones().map(|index| index + spawned_count);
+
+        let moved: HashMap<usize, usize> = stray_instances.zip(free_slots).collect();
+
+        for (&old_index, &new_index) in &moved {
+            self.instances.swap(old_index, new_index);
+            self.spawned.set(old_index, false);
+            self.spawned.set(new_index, true);
+
+            let instance = &mut self.instances[new_index];
+            let generation = instance.id().generation();
+            instance.relocate(GenerationalIndex::new(generation, new_index));
+
+            self.relocations.insert(generation, new_index as u32);
+        }
+
+        let new_len = spawned_count.max(self.capacity);
+        if new_len < self.instances.len() {
+            self.instances.truncate(new_len);
+            self.instances.shrink_to_fit();
+            self.spawned.truncate(new_len);
+            self.spawned.shrink_to_fit();
+        }
+
+        SpawnPoolRemap { moved }
+    }
+
+    // Call once every index cached outside the pool has been patched after a compact().
+    #[inline]
+    pub fn clear_relocations(&mut self) {
+        self.relocations.clear();
+        self.relocations.shrink_to_fit();
+    }
+
     #[inline]
     pub fn spawned_count(&self) -> usize {
         self.spawned.count_ones()
@@ -278,6 +366,13 @@ where
         self.peak
     }
 
+    #[inline]
+    pub fn memory_usage_estimate(&self) -> usize {
+        self.instances.capacity() * std::mem::size_of::<T>()
+            + self.spawned.capacity() / 8
+            + self.relocations.capacity() * std::mem::size_of::<(u32, u32)>()
+    }
+
     #[inline]
     pub fn is_valid(&self) -> bool {
         self.instances.len() == self.spawned.len()
@@ -296,73 +391,50 @@ where
     #[inline]
     pub fn try_get(&self, id: GenerationalIndex) -> Option<&T> {
         debug_assert!(self.is_valid());
-
-        if !id.is_valid() {
-            return None;
-        }
-
-        let index = id.index();
-        if !self.spawned[index] {
-            return None;
-        }
-
-        let instance = &self.instances[index];
-        debug_assert!(instance.is_spawned());
-
-        if instance.id().generation != id.generation() {
-            return None;
-        }
-
-        Some(instance)
+        let index = self.resolve_index(id)?;
+        Some(&self.instances[index])
     }
 
     #[inline]
     pub fn try_get_mut(&mut self, id: GenerationalIndex) -> Option<&mut T> {
         debug_assert!(self.is_valid());
-
-        if !id.is_valid() {
-            return None;
-        }
-
-        let index = id.index();
-        if !self.spawned[index] {
-            return None;
-        }
-
-        let instance = &mut self.instances[index];
-        debug_assert!(instance.is_spawned());
-
-        if instance.id().generation != id.generation() {
-            return None;
-        }
-
-        Some(instance)
+        let index = self.resolve_index(id)?;
+        Some(&mut self.instances[index])
     }
 
     #[inline]
-    pub fn try_get_at(&self, index: usize) -> Option<&T> {
-        debug_assert!(self.is_valid());
+    fn is_spawned_at(&self, index: usize) -> bool {
+        // Indices past the end are possible after a compaction released the tail.
+        self.spawned.get(index).is_some_and(|spawned| *spawned)
+    }
 
-        if !self.spawned[index] {
+    // Index of the spawned instance the id refers to, following
+    // relocations if the instance was moved by a compaction.
+    #[inline]
+    fn resolve_index(&self, id: GenerationalIndex) -> Option<usize> {
+        if !id.is_valid() {
             return None;
         }
 
-        let instance = &self.instances[index];
-        debug_assert!(instance.is_spawned());
-        Some(instance)
-    }
+        let matches_at = |index: usize| {
+            self.is_spawned_at(index) && {
+                let instance = &self.instances[index];
+                debug_assert!(instance.is_spawned());
+                instance.id().generation() == id.generation()
+            }
+        };
 
-    #[inline]
-    pub fn try_get_at_mut(&mut self, index: usize) -> Option<&mut T> {
-        debug_assert!(self.is_valid());
+        let index = id.index();
+        if matches_at(index) {
+            return Some(index);
+        }
 
-        if !self.spawned[index] {
+        if self.relocations.is_empty() {
             return None;
         }
 
-        let instance = &mut self.instances[index];
-        debug_assert!(instance.is_spawned());
-        Some(instance)
+        let relocated_index = *self.relocations.get(&id.generation())? as usize;
+        matches_at(relocated_index).then_some(relocated_index)
     }
 }
 
@@ -375,6 +447,10 @@ struct SpawnPoolSerializedHeader {
     spawned_count: usize,
     instance_count: usize,
     generation: u32,
+
+    // Older saves don't have it. Capacity then defaults to the instance count.
+    #[serde(default)]
+    capacity: usize,
 }
 
 impl<T> Serialize for SpawnPool<T>
@@ -392,6 +468,7 @@ where
             spawned_count: self.spawned_count(),
             instance_count: self.instances.len(),
             generation: self.generation,
+            capacity: self.capacity,
         };
 
         let mut serialized_count = 0;
@@ -467,6 +544,9 @@ where
 
                 // Remaining elements: spawned instances
                 let mut pool = SpawnPool::<T>::new(header.instance_count, header.generation);
+                if header.capacity != 0 {
+                    pool.capacity = header.capacity;
+                }
 
                 let mut deserialized_count = 0;
                 loop {
@@ -520,6 +600,8 @@ pub struct Spawner<'game> {
     context: &'game SimContext,
     subtract_tile_cost: bool, // Decrement tile cost when spawning? Default = true.
     restore_tile_cost: bool,  // Restore back tile cost when despawning? Default = false.
+    check_unlocks: bool,      // Refuse buildings that are not unlocked yet? Default = true.
+    start_construction: bool, // Place buildings as construction sites? Default = false.
 }
 
 pub enum SpawnerResult<'game> {
@@ -545,7 +627,13 @@ impl SpawnerResult<'_> {
 impl<'game> Spawner<'game> {
     #[inline]
     pub fn new(context: &'game SimContext) -> Self {
-        Self { context, subtract_tile_cost: true, restore_tile_cost: false }
+        Self {
+            context,
+            subtract_tile_cost: true,
+            restore_tile_cost: false,
+            check_unlocks: true,
+            start_construction: false,
+        }
     }
 
     #[inline]
@@ -558,6 +646,16 @@ impl<'game> Spawner<'game> {
         self.restore_tile_cost = restore;
     }
 
+    #[inline]
+    pub fn set_check_unlocks(&mut self, check: bool) {
+        self.check_unlocks = check;
+    }
+
+    #[inline]
+    pub fn set_start_construction(&mut self, start: bool) {
+        self.start_construction = start;
+    }
+
     // Spawn a GameObject (Building, Unit, Prop) or place a Tile
     // without any associated game state.
     pub fn try_spawn_tile_with_def(&self, target_cell: Cell, tile_def: &'static TileDef) -> SpawnerResult<'_> {
@@ -633,16 +731,24 @@ impl<'game> Spawner<'game> {
         building_base_cell: Cell,
         building_tile_def: &'static TileDef,
     ) -> Result<&'game mut Building, TilePlacementErr> {
+        if !self.is_tile_unlocked(building_tile_def) {
+            return locked_error(building_tile_def);
+        }
+
         if !self.can_afford_tile(building_tile_def) {
             return cost_error(building_tile_def);
         }
 
-        let result =
+        let mut result =
             self.context.world_mut().try_spawn_building_with_tile_def(self.context, building_base_cell, building_tile_def);
 
-        if let Ok(ref building) = result {
+        if let Ok(ref mut building) = result {
             self.subtract_tile_cost(building_tile_def);
             building.set_random_variation(self.context);
+
+            if self.start_construction {
+                building.start_construction(self.context);
+            }
         }
 
         result
@@ -771,6 +877,14 @@ impl<'game> Spawner<'game> {
         true
     }
 
+    #[inline]
+    pub fn is_tile_unlocked(&self, tile_def: &'static TileDef) -> bool {
+        if self.check_unlocks && tile_def.is(TileKind::Building) {
+            return self.context.building_unlocks().is_tile_def_unlocked(tile_def);
+        }
+        true
+    }
+
     #[inline]
     fn subtract_tile_cost(&self, tile_def: &'static TileDef) {
         if self.subtract_tile_cost && tile_def.cost != 0 && !cheats::get().ignore_tile_cost {
@@ -807,7 +921,7 @@ impl<'game> Spawner<'game> {
                 } else {
                     // Set a random tile variation:
                     if tile.has_flags(TileFlags::RandomizePlacement) {
-                        tile.set_random_variation_index(self.context.rng_mut());
+                        tile.set_random_variation_index(self.context.rng_stream_mut(RngStream::Placement));
                     }
                 }
 
@@ -823,6 +937,11 @@ fn cost_error<T>(tile_def: &'static TileDef) -> Result<T, TilePlacementErr> {
     placement::err!(Placement::CannotAffordCost, "Cannot afford tile '{}'. Cost: {} gold", tile_def.name, tile_def.cost)
 }
 
+#[cold]
+fn locked_error<T>(tile_def: &'static TileDef) -> Result<T, TilePlacementErr> {
+    placement::err!(Placement::BuildingLocked, "Building '{}' is not unlocked yet", tile_def.name)
+}
+
 #[cold]
 fn despawn_error(what: &str, err: &TileClearingErr) {
     if cfg!(debug_assertions) {
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use smallvec::SmallVec;
+
+use common::coords::{Cell, CellRange};
+
+use crate::unit::UnitId;
+
+// ----------------------------------------------
+// Constants
+// ----------------------------------------------
+
+// Width and height of each spatial index bucket, in cells.
+const UNIT_BUCKET_SIZE: i32 = 8;
+
+// ----------------------------------------------
+// UnitSpatialIndex
+// ----------------------------------------------
+
+// Buckets spawned units by map region so proximity queries only
+// visit units near the query area instead of scanning every unit.
+// Used by melee combat and to scare civilians away from raiders.
+// Services and hazards don't look for units; they query nearby buildings
+// through the tile map instead (road searches, tile neighbors and cell areas).
+// Not saved; the World rebuilds it from the unit spawn pool on load.
+#[derive(Default)]
+pub struct UnitSpatialIndex {
+    buckets: HashMap<Cell, SmallVec<[UnitId; 8]>>, // Keyed by bucket coords, not cells.
+    unit_cells: HashMap<UnitId, Cell>,             // Cell each unit was last indexed at.
+}
+
+impl UnitSpatialIndex {
+    pub fn insert(&mut self, id: UnitId, cell: Cell) {
+        debug_assert!(cell.is_valid());
+
+        if let Some(prev_cell) = self.unit_cells.insert(id, cell) {
+            // Already indexed; treat as a move.
+            self.remove_from_bucket(id, prev_cell);
+        }
+
+        self.buckets.entry(Self::bucket_for(cell)).or_default().push(id);
+    }
+
+    pub fn remove(&mut self, id: UnitId) {
+        if let Some(cell) = self.unit_cells.remove(&id) {
+            self.remove_from_bucket(id, cell);
+        }
+    }
+
+    // Updates the unit's bucket if it moved since it was last indexed.
+    // Cheap when the unit stays in the same cell, so it can run for every unit each update.
+    pub fn sync(&mut self, id: UnitId, cell: Cell) {
+        let Some(indexed_cell) = self.unit_cells.get_mut(&id) else {
+            if cell.is_valid() {
+                self.insert(id, cell);
+            }
+            return;
+        };
+
+        if *indexed_cell == cell {
+            return;
+        }
+
+        let prev_cell = *indexed_cell;
+        *indexed_cell = cell;
+
+        if Self::bucket_for(prev_cell) != Self::bucket_for(cell) {
+            self.remove_from_bucket(id, prev_cell);
+            self.buckets.entry(Self::bucket_for(cell)).or_default().push(id);
+        }
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+        self.unit_cells.clear();
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.unit_cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.unit_cells.is_empty()
+    }
+
+    #[inline]
+    pub fn cell_of(&self, id: UnitId) -> Option<Cell> {
+        self.unit_cells.get(&id).copied()
+    }
+
+    // Visits every unit indexed within `radius` cells (manhattan) of `area`.
+    // Visitor returns false to stop early.
+    pub fn for_each_unit_near<F>(&self, area: CellRange, radius: i32, mut visitor_fn: F)
+    where
+        F: FnMut(UnitId, Cell) -> bool,
+    {
+        debug_assert!(area.is_valid());
+        debug_assert!(radius >= 0);
+
+        let min_bucket = Self::bucket_for(Cell::new(area.start.x - radius, area.start.y - radius));
+        let max_bucket = Self::bucket_for(Cell::new(area.end.x + radius, area.end.y + radius));
+
+        for bucket_y in min_bucket.y..=max_bucket.y {
+            for bucket_x in min_bucket.x..=max_bucket.x {
+                let Some(bucket) = self.buckets.get(&Cell::new(bucket_x, bucket_y)) else {
+                    continue;
+                };
+
+                for id in bucket {
+                    let cell = self.unit_cells[id];
+                    if distance_to_area(cell, area) <= radius && !visitor_fn(*id, cell) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn bucket_for(cell: Cell) -> Cell {
+        Cell::new(cell.x.div_euclid(UNIT_BUCKET_SIZE), cell.y.div_euclid(UNIT_BUCKET_SIZE))
+    }
+
+    fn remove_from_bucket(&mut self, id: UnitId, cell: Cell) {
+        let bucket_cell = Self::bucket_for(cell);
+
+        let Some(bucket) = self.buckets.get_mut(&bucket_cell) else {
+            debug_assert!(false, "Unit {id} spatial index bucket for cell {cell} is missing!");
+            return;
+        };
+
+        let position = bucket.iter().position(|bucket_id| *bucket_id == id);
+        debug_assert!(position.is_some(), "Unit {id} not found in spatial index bucket for cell {cell}!");
+
+        if let Some(position) = position {
+            bucket.swap_remove(position);
+        }
+
+        if bucket.is_empty() {
+            self.buckets.remove(&bucket_cell);
+        }
+    }
+}
+
+// Manhattan distance from `cell` to the nearest cell of `area`. Zero if inside the area.
+#[inline]
+fn distance_to_area(cell: Cell, area: CellRange) -> i32 {
+    let dx = (area.start.x - cell.x).max(cell.x - area.end.x).max(0);
+    let dy = (area.start.y - cell.y).max(cell.y - area.end.y).max(0);
+    dx + dy
+}
+
+// ----------------------------------------------
+// Unit Tests
+// ----------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect_near(index: &UnitSpatialIndex, area: CellRange, radius: i32) -> Vec<u32> {
+        let mut found = Vec::new();
+        index.for_each_unit_near(area, radius, |id, _cell| {
+            found.push(id.generation());
+            true
+        });
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn query_finds_units_within_radius() {
+        let mut index = UnitSpatialIndex::default();
+        index.insert(UnitId::new(1, 0), Cell::new(2, 2));
+        index.insert(UnitId::new(2, 1), Cell::new(10, 3));
+        index.insert(UnitId::new(3, 2), Cell::new(40, 40));
+
+        let building = CellRange::new(Cell::new(5, 2), Cell::new(6, 3));
+
+        assert_eq!(collect_near(&index, building, 3), vec![1]);
+        assert_eq!(collect_near(&index, building, 4), vec![1, 2]);
+        assert_eq!(collect_near(&index, building, 0), Vec::<u32>::new());
+        assert_eq!(index.len(), 3);
+    }
+
+    #[test]
+    fn sync_moves_units_between_buckets() {
+        let mut index = UnitSpatialIndex::default();
+        let id = UnitId::new(1, 0);
+        index.insert(id, Cell::new(0, 0));
+
+        let origin = CellRange::new(Cell::new(0, 0), Cell::new(0, 0));
+        let far_away = CellRange::new(Cell::new(30, 30), Cell::new(30, 30));
+
+        index.sync(id, Cell::new(1, 1)); // Same bucket.
+        assert_eq!(collect_near(&index, origin, 2), vec![1]);
+
+        index.sync(id, Cell::new(29, 30)); // Different bucket.
+        assert_eq!(collect_near(&index, origin, 2), Vec::<u32>::new());
+        assert_eq!(collect_near(&index, far_away, 1), vec![1]);
+
+        index.remove(id);
+        assert!(index.is_empty());
+        assert_eq!(collect_near(&index, far_away, 1), Vec::<u32>::new());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    building::HouseLevel,
+    building::{HouseHappiness, HouseLevel, config::BuildingConfigs},
     sim::resources::{ResourceKind, ResourceStock},
 };
 
@@ -68,6 +68,54 @@ pub struct TreasuryStats {
     pub tax_collected: u32,
 }
 
+// Household happiness aggregated city-wide, weighted by household population
+// so crowded houses count for more. Empty houses are left out.
+#[derive(Default)]
+pub struct HappinessStats {
+    pub residents: u32,
+    pub unhappy_households: u32, // Inhabited houses below HouseConfig::unhappy_household_threshold.
+
+    // Population weighted sums of each factor. See `average()`.
+    needs: f32,
+    services: f32,
+    tax: f32,
+    events: f32,
+}
+
+impl HappinessStats {
+    pub fn add(&mut self, happiness: &HouseHappiness, residents: u32, is_unhappy: bool) {
+        if residents == 0 {
+            return;
+        }
+
+        let weight = residents as f32;
+        self.needs += happiness.needs * weight;
+        self.services += happiness.services * weight;
+        self.tax += happiness.tax * weight;
+        self.events += happiness.events * weight;
+
+        self.residents += residents;
+        if is_unhappy {
+            self.unhappy_households += 1;
+        }
+    }
+
+    // City-wide factor breakdown. Fully content if nobody lives in the city yet.
+    pub fn average(&self) -> HouseHappiness {
+        if self.residents == 0 {
+            return HouseHappiness::CONTENT;
+        }
+
+        let inv_residents = 1.0 / self.residents as f32;
+        HouseHappiness {
+            needs: self.needs * inv_residents,
+            services: self.services * inv_residents,
+            tax: self.tax * inv_residents,
+            events: self.events * inv_residents,
+        }
+    }
+}
+
 pub struct HousingStats {
     pub total: u32,
     pub lowest_level: HouseLevel,
@@ -88,6 +136,18 @@ pub struct GlobalResourceCounts {
     pub markets: ResourceStock,
     pub producers: ResourceStock,
     pub services: ResourceStock,
+
+    // Room left in storage buildings for each resource kind, honoring their refuse filters.
+    // Not included in `all`.
+    pub storage_free_capacity: ResourceStock,
+}
+
+impl GlobalResourceCounts {
+    // Resources held in storage buildings (storage yards + granaries).
+    #[inline]
+    pub fn stored(&self, kind: ResourceKind) -> u32 {
+        self.storage_yards.count(kind) + self.granaries.count(kind)
+    }
 }
 
 pub struct WorldStats {
@@ -98,6 +158,7 @@ pub struct WorldStats {
 
     // Housing stats:
     pub houses: HousingStats,
+    pub happiness: HappinessStats,
 
     // Global resource tally:
     pub resources: GlobalResourceCounts,
@@ -114,6 +175,7 @@ impl Default for WorldStats {
                 lowest_level: HouseLevel::max(),
                 highest_level: HouseLevel::min(),
             },
+            happiness: HappinessStats::default(),
             resources: GlobalResourceCounts {
                 all: ResourceStock::accept_all(),
                 units: ResourceStock::accept_all(),
@@ -123,6 +185,7 @@ impl Default for WorldStats {
                 markets: ResourceStock::with_accepted_kinds(ResourceKind::foods() | ResourceKind::consumer_goods()),
                 producers: ResourceStock::accept_all(),
                 services: ResourceStock::accept_all(),
+                storage_free_capacity: ResourceStock::accept_all(),
             },
         }
     }
@@ -155,6 +218,12 @@ impl WorldStats {
         }
     }
 
+    pub fn add_storage_free_capacity(&mut self, kind: ResourceKind, count: u32) {
+        if count != 0 {
+            self.resources.storage_free_capacity.add(kind, count);
+        }
+    }
+
     pub fn add_house_resources(&mut self, kind: ResourceKind, count: u32) {
         if count != 0 {
             self.resources.houses.add(kind, count);
@@ -194,6 +263,10 @@ impl WorldStats {
         self.houses.total += 1;
     }
 
+    pub fn add_household_happiness(&mut self, happiness: &HouseHappiness, residents: u32) {
+        let threshold = BuildingConfigs::get().house_config().unhappy_household_threshold;
+        self.happiness.add(happiness, residents, happiness.score() < threshold);
+    }
 }
 
 // ----------------------------------------------
@@ -216,6 +289,32 @@ mod tests {
         assert!((stats.unemployment_ratio() - 0.25).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn happiness_average_is_population_weighted() {
+        let mut stats = HappinessStats::default();
+
+        let unhappy = HouseHappiness { needs: 0.0, services: 0.0, tax: 1.0, events: 0.5 };
+        stats.add(&unhappy, 3, true);
+        stats.add(&HouseHappiness::CONTENT, 1, false);
+        stats.add(&unhappy, 0, true); // Empty house; ignored.
+
+        let average = stats.average();
+        assert_eq!(stats.residents, 4);
+        assert_eq!(stats.unhappy_households, 1);
+        assert!((average.needs - 0.25).abs() < f32::EPSILON);
+        assert!((average.services - 0.25).abs() < f32::EPSILON);
+        assert!((average.tax - 1.0).abs() < f32::EPSILON);
+        assert!((average.events - 0.625).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn happiness_average_with_no_residents() {
+        let stats = HappinessStats::default();
+        let average = stats.average();
+        assert_eq!(average.needs, 1.0);
+        assert_eq!(average.events, 1.0);
+    }
+
     #[test]
     fn employment_rate_zero_population() {
         let stats = PopulationStats {
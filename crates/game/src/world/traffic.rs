@@ -0,0 +1,83 @@
+use common::{Size, coords::Cell};
+
+use crate::{
+    sim::SimContext,
+    tile::heatmap::{HeatmapGradient, HeatmapGrid, HeatmapOverlays},
+};
+
+// ----------------------------------------------
+// TrafficCounters
+// ----------------------------------------------
+
+// Number of walker steps taken through each tile map cell. Counts decay every
+// world update, so the "Traffic" heatmap reflects recent movement only.
+pub struct TrafficCounters {
+    counts: HeatmapGrid,
+}
+
+impl Default for TrafficCounters {
+    fn default() -> Self {
+        HeatmapOverlays::get_mut().register_grid(Self::TRAFFIC_HEATMAP, HeatmapGradient::cold_to_hot());
+        Self { counts: HeatmapGrid::default() }
+    }
+}
+
+impl TrafficCounters {
+    pub const TRAFFIC_HEATMAP: &str = "Traffic";
+
+    // Fraction of the counts kept after each world update.
+    const DECAY_PER_UPDATE: f32 = 0.95;
+
+    // Below this a cell is considered to have no traffic.
+    const MIN_COUNT: f32 = 0.01;
+
+    pub fn record_step(&mut self, cell: Cell, map_size_in_cells: Size) {
+        if self.counts.size() != map_size_in_cells {
+            self.counts = HeatmapGrid::new(map_size_in_cells);
+        }
+        self.counts.set_value_at(cell, self.counts.value_at(cell) + 1.0);
+    }
+
+    #[inline]
+    pub fn count_at(&self, cell: Cell) -> f32 {
+        self.counts.value_at(cell)
+    }
+
+    pub fn update(&mut self, context: &SimContext) {
+        for count in self.counts.values_mut() {
+            *count *= Self::DECAY_PER_UPDATE;
+            if *count < Self::MIN_COUNT {
+                *count = 0.0;
+            }
+        }
+
+        self.update_traffic_heatmap(context);
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.counts.clear();
+    }
+
+    fn update_traffic_heatmap(&self, context: &SimContext) {
+        let overlays = HeatmapOverlays::get_mut();
+        if overlays.active_layer_name() != Some(Self::TRAFFIC_HEATMAP) {
+            return; // Not visible, skip the work.
+        }
+
+        // Normalize by the busiest cell.
+        let peak_count = self.counts.values().iter().copied().fold(0.0, f32::max);
+        let inv_peak_count = if peak_count > 0.0 { 1.0 / peak_count } else { 0.0 };
+
+        overlays.update_grid(Self::TRAFFIC_HEATMAP, context.map_size_in_cells(), |grid| {
+            if grid.size() != self.counts.size() {
+                grid.clear();
+                return; // No steps recorded for this map yet.
+            }
+
+            for (value, count) in grid.values_mut().iter_mut().zip(self.counts.values()) {
+                *value = count * inv_peak_count;
+            }
+        });
+    }
+}
@@ -3,10 +3,25 @@ use game::{
         self,
         CampaignProgress,
         CampaignPrompt,
-        config::{CampaignConfigs, CampaignDef, MissionDef, MissionGoal, MissionMap, MissionRequirements},
+        config::{
+            CampaignConfigs,
+            CampaignDef,
+            MissionAction,
+            MissionCondition,
+            MissionDef,
+            MissionGoal,
+            MissionMap,
+            MissionRequirements,
+            MissionState,
+            MissionTrigger,
+        },
     },
     debug::preset_maps,
-    sim::resources::ResourceKind,
+    sim::{
+        Calendar,
+        calendar::CalendarPacing,
+        resources::{ResourceKind, ResourceStock},
+    },
     world::stats::WorldStats,
 };
 
@@ -23,12 +38,19 @@ fn main() {
         test_utils::test_fn!(test_mission_goal_is_met),
         test_utils::test_fn!(test_mission_goal_resource),
         test_utils::test_fn!(test_requirements_all_met),
+        test_utils::test_fn!(test_mission_goal_produced),
+        test_utils::test_fn!(test_mission_conditions),
         // Manager state machine:
         test_utils::test_fn!(test_start_campaign_sets_active_mission),
+        test_utils::test_fn!(test_locked_mission_cannot_start),
         test_utils::test_fn!(test_tick_detects_mission_completion),
         test_utils::test_fn!(test_advance_to_next_mission),
         test_utils::test_fn!(test_continue_playing_no_requeue),
         test_utils::test_fn!(test_snapshot_restore_and_suppress),
+        test_utils::test_fn!(test_trigger_fails_mission),
+        test_utils::test_fn!(test_retry_mission),
+        // Mission calendar pacing:
+        test_utils::test_fn!(test_mission_calendar_pacing),
     ]);
 }
 
@@ -37,27 +59,71 @@ fn main() {
 // stats directly.
 const TEST_PRESET: usize = preset_maps::PRESET_1_FARM_1_GRANARY_1_HOUSE_2_WELLS_1_MARKET;
 
-// A self-contained 2-mission campaign, injected into the CampaignConfigs
-// singleton so the tests don't depend on the shipped campaigns.json.
+// Index of the "Trigger Campaign" in `build_test_configs()`.
+const TRIGGER_CAMPAIGN_ID: usize = 1;
+
+// Index of the "Paced Campaign" in `build_test_configs()`. Its days last a minute.
+const PACED_CAMPAIGN_ID: usize = 2;
+const PACED_SECONDS_PER_DAY: f32 = 60.0;
+
+// Self-contained campaigns, injected into the CampaignConfigs singleton so the
+// tests don't depend on the shipped campaigns.json: a 2-mission campaign, a
+// single mission campaign that fails as soon as the treasury is empty and a
+// single mission campaign that overrides the calendar pacing.
 fn build_test_configs() -> CampaignConfigs {
     CampaignConfigs {
-        campaigns: vec![CampaignDef {
-            name: "Test Campaign".into(),
-            missions: vec![
-                MissionDef {
-                    name: "Mission 1".into(),
+        campaigns: vec![
+            CampaignDef {
+                name: "Test Campaign".into(),
+                missions: vec![
+                    MissionDef {
+                        name: "Mission 1".into(),
+                        description: String::new(),
+                        map: MissionMap::Preset { preset_number: TEST_PRESET },
+                        requirements: MissionRequirements { goals: vec![MissionGoal::Population { min: 10 }] },
+                        calendar: None,
+                        triggers: Vec::new(),
+                        rewards: Vec::new(),
+                    },
+                    MissionDef {
+                        name: "Mission 2".into(),
+                        description: String::new(),
+                        map: MissionMap::Preset { preset_number: TEST_PRESET },
+                        requirements: MissionRequirements { goals: vec![MissionGoal::Treasury { min_gold: 100 }] },
+                        calendar: None,
+                        triggers: Vec::new(),
+                        rewards: Vec::new(),
+                    },
+                ],
+            },
+            CampaignDef {
+                name: "Trigger Campaign".into(),
+                missions: vec![MissionDef {
+                    name: "Bankruptcy Mission".into(),
                     description: String::new(),
                     map: MissionMap::Preset { preset_number: TEST_PRESET },
                     requirements: MissionRequirements { goals: vec![MissionGoal::Population { min: 10 }] },
-                },
-                MissionDef {
-                    name: "Mission 2".into(),
+                    calendar: None,
+                    triggers: vec![MissionTrigger {
+                        condition: MissionCondition::TreasuryBelow { max_gold: 1 },
+                        actions: vec![MissionAction::Message { text: "Bankrupt!".into() }, MissionAction::FailMission],
+                    }],
+                    rewards: Vec::new(),
+                }],
+            },
+            CampaignDef {
+                name: "Paced Campaign".into(),
+                missions: vec![MissionDef {
+                    name: "Fast Mission".into(),
                     description: String::new(),
                     map: MissionMap::Preset { preset_number: TEST_PRESET },
-                    requirements: MissionRequirements { goals: vec![MissionGoal::Treasury { min_gold: 100 }] },
-                },
-            ],
-        }],
+                    requirements: MissionRequirements { goals: vec![MissionGoal::Population { min: 10 }] },
+                    calendar: Some(CalendarPacing { seconds_per_day: PACED_SECONDS_PER_DAY, days_per_month: 10 }),
+                    triggers: Vec::new(),
+                    rewards: Vec::new(),
+                }],
+            },
+        ],
     }
 }
 
@@ -81,25 +147,31 @@ fn test_mission_goal_is_met() {
     stats.population.employed = 30;
     stats.treasury.gold_units_total = 1000;
 
-    assert!(MissionGoal::Population  { min: 50 }.is_met(&stats));
-    assert!(!MissionGoal::Population { min: 51 }.is_met(&stats));
+    let produced = ResourceStock::accept_all();
+    let state = MissionState { stats: &stats, produced: &produced, days_elapsed: 0 };
+
+    assert!(MissionGoal::Population  { min: 50 }.is_met(&state));
+    assert!(!MissionGoal::Population { min: 51 }.is_met(&state));
 
-    assert!(MissionGoal::Employment  { min_employed: 30 }.is_met(&stats));
-    assert!(!MissionGoal::Employment { min_employed: 31 }.is_met(&stats));
+    assert!(MissionGoal::Employment  { min_employed: 30 }.is_met(&state));
+    assert!(!MissionGoal::Employment { min_employed: 31 }.is_met(&state));
 
-    assert!(MissionGoal::Treasury  { min_gold: 1000 }.is_met(&stats));
-    assert!(!MissionGoal::Treasury { min_gold: 1001 }.is_met(&stats));
+    assert!(MissionGoal::Treasury  { min_gold: 1000 }.is_met(&state));
+    assert!(!MissionGoal::Treasury { min_gold: 1001 }.is_met(&state));
 }
 
 fn test_mission_goal_resource() {
     let mut stats = WorldStats::default();
     stats.resources.all.add(ResourceKind::Rice, 20);
 
-    assert!(MissionGoal::Resource  { kind: ResourceKind::Rice, min: 20 }.is_met(&stats));
-    assert!(!MissionGoal::Resource { kind: ResourceKind::Rice, min: 21 }.is_met(&stats));
+    let produced = ResourceStock::accept_all();
+    let state = MissionState { stats: &stats, produced: &produced, days_elapsed: 0 };
+
+    assert!(MissionGoal::Resource  { kind: ResourceKind::Rice, min: 20 }.is_met(&state));
+    assert!(!MissionGoal::Resource { kind: ResourceKind::Rice, min: 21 }.is_met(&state));
 
     // A different resource the world has none of.
-    assert!(!MissionGoal::Resource { kind: ResourceKind::Wood, min: 1 }.is_met(&stats));
+    assert!(!MissionGoal::Resource { kind: ResourceKind::Wood, min: 1 }.is_met(&state));
 }
 
 fn test_requirements_all_met() {
@@ -107,18 +179,67 @@ fn test_requirements_all_met() {
     stats.population.total = 50;
     stats.treasury.gold_units_total = 500;
 
+    let produced = ResourceStock::accept_all();
+    let state = MissionState { stats: &stats, produced: &produced, days_elapsed: 0 };
+
     let all = MissionRequirements {
         goals: vec![MissionGoal::Population { min: 50 }, MissionGoal::Treasury { min_gold: 500 }],
     };
-    assert!(all.all_met(&stats));
+    assert!(all.all_met(&state));
 
     let partial = MissionRequirements {
         goals: vec![MissionGoal::Population { min: 50 }, MissionGoal::Treasury { min_gold: 501 }],
     };
-    assert!(!partial.all_met(&stats));
+    assert!(!partial.all_met(&state));
 
     // No goals => already met.
-    assert!(MissionRequirements::default().all_met(&stats));
+    assert!(MissionRequirements::default().all_met(&state));
+}
+
+fn test_mission_goal_produced() {
+    let mut stats = WorldStats::default();
+    stats.resources.all.add(ResourceKind::Rice, 50); // Stock on hand doesn't count as produced.
+
+    let mut produced = ResourceStock::accept_all();
+    produced.add(ResourceKind::Rice, 10);
+    let state = MissionState { stats: &stats, produced: &produced, days_elapsed: 0 };
+
+    let goal = MissionGoal::Produced { kind: ResourceKind::Rice, min: 20 };
+    assert_eq!(goal.progress(&state), (10, 20));
+    assert!(!goal.is_met(&state));
+
+    produced.add(ResourceKind::Rice, 10);
+    let state = MissionState { stats: &stats, produced: &produced, days_elapsed: 0 };
+    assert!(goal.is_met(&state));
+}
+
+fn test_mission_conditions() {
+    let mut stats = WorldStats::default();
+    stats.population.total = 5;
+
+    let produced = ResourceStock::accept_all();
+    let state = MissionState { stats: &stats, produced: &produced, days_elapsed: 30 };
+
+    assert!(MissionCondition::DaysElapsed  { days: 30 }.is_met(&state));
+    assert!(!MissionCondition::DaysElapsed { days: 31 }.is_met(&state));
+
+    assert!(MissionCondition::PopulationBelow  { max: 6 }.is_met(&state));
+    assert!(!MissionCondition::PopulationBelow { max: 5 }.is_met(&state));
+
+    assert!(MissionCondition::Goal(MissionGoal::Population { min: 5 }).is_met(&state));
+
+    // All: every sub-condition must hold.
+    let grace_period_over_and_too_few_people = MissionCondition::All(vec![
+        MissionCondition::DaysElapsed { days: 30 },
+        MissionCondition::PopulationBelow { max: 10 },
+    ]);
+    assert!(grace_period_over_and_too_few_people.is_met(&state));
+
+    let still_in_grace_period = MissionCondition::All(vec![
+        MissionCondition::DaysElapsed { days: 60 },
+        MissionCondition::PopulationBelow { max: 10 },
+    ]);
+    assert!(!still_in_grace_period.is_met(&state));
 }
 
 // ----------------------------------------------
@@ -143,6 +264,21 @@ fn test_start_campaign_sets_active_mission() {
     assert!(campaign::active_mission().is_none());
 }
 
+fn test_locked_mission_cannot_start() {
+    ensure_campaign_setup();
+
+    // Without the persistent meta-state only first missions are unlocked.
+    assert!(campaign::is_mission_unlocked(0, 0));
+    assert!(!campaign::is_mission_unlocked(0, 1));
+    assert!(!campaign::is_mission_unlocked(0, 2)); // Out of range.
+
+    assert!(campaign::start_mission(0, 1).is_none());
+    assert!(campaign::active_mission().is_none());
+
+    assert!(campaign::start_mission(0, 0).is_some());
+    assert_eq!(campaign::active_mission().unwrap().mission_index, 0);
+}
+
 fn test_tick_detects_mission_completion() {
     ensure_campaign_setup();
     campaign::start_campaign(0); // Mission 1 goal: Population >= 10.
@@ -236,3 +372,89 @@ fn test_snapshot_restore_and_suppress() {
     campaign::restore_snapshot(CampaignProgress::default());
     assert!(campaign::active_mission().is_none());
 }
+
+fn test_trigger_fails_mission() {
+    ensure_campaign_setup();
+    campaign::start_campaign(TRIGGER_CAMPAIGN_ID); // Fails once the treasury drops below 1 gold.
+
+    let mut env = TestEnvironment::with_preset_map(TEST_PRESET);
+
+    // Goals met, but the FailMission trigger takes precedence on the same tick.
+    env.world.stats_mut().population.total = 10;
+    env.world.stats_mut().treasury.gold_units_total = 0;
+    {
+        let context = env.new_sim_context(0.0);
+        campaign::tick(&context);
+    }
+
+    let active = campaign::active_mission().unwrap();
+    assert!(active.failed);
+    assert!(!active.completed);
+    assert_eq!(active.fired_triggers, vec![0]);
+    assert_eq!(campaign::take_pending_prompt(), Some(CampaignPrompt::MissionFailed));
+
+    // A failed mission is no longer evaluated.
+    env.world.stats_mut().treasury.gold_units_total = 100;
+    {
+        let context = env.new_sim_context(0.0);
+        campaign::tick(&context);
+    }
+    assert!(!campaign::active_mission().unwrap().completed);
+    assert!(!campaign::has_pending_prompt());
+}
+
+fn test_retry_mission() {
+    ensure_campaign_setup();
+    assert!(campaign::retry_mission().is_none()); // No active mission.
+
+    campaign::start_campaign(TRIGGER_CAMPAIGN_ID);
+    campaign::record_production(ResourceKind::Rice, 5);
+
+    let mut env = TestEnvironment::with_preset_map(TEST_PRESET);
+    {
+        let context = env.new_sim_context(0.0);
+        campaign::tick(&context);
+    }
+    assert!(campaign::active_mission().unwrap().failed);
+
+    // Retrying restarts the same mission with fresh progress.
+    let map = campaign::retry_mission().expect("mission should be retryable");
+    assert!(matches!(map, MissionMap::Preset { .. }));
+
+    let active = campaign::active_mission().unwrap();
+    assert_eq!(active.campaign_id, TRIGGER_CAMPAIGN_ID);
+    assert_eq!(active.mission_index, 0);
+    assert!(!active.is_over());
+    assert!(active.fired_triggers.is_empty());
+    assert_eq!(active.produced.count(ResourceKind::Rice), 0);
+    assert!(!campaign::has_pending_prompt());
+}
+
+// ----------------------------------------------
+// Mission calendar pacing
+// ----------------------------------------------
+
+fn test_mission_calendar_pacing() {
+    ensure_campaign_setup();
+    let default_pacing = CalendarPacing::from_configs();
+
+    // Missions without an override run at the default pacing.
+    assert!(campaign::start_campaign(0).is_some());
+    assert!(*Calendar::new().pacing() == default_pacing);
+
+    // A new game calendar picks up the active mission's override.
+    campaign::reset();
+    assert!(campaign::start_campaign(PACED_CAMPAIGN_ID).is_some());
+
+    let calendar = Calendar::new();
+    assert_eq!(calendar.seconds_per_day(), PACED_SECONDS_PER_DAY);
+    assert_eq!(calendar.seconds_per_month(), PACED_SECONDS_PER_DAY * 10.0);
+    assert_eq!(calendar.secs_to_days(PACED_SECONDS_PER_DAY * 1.5), 1.5);
+
+    // Second-based timers keep in step: a mission day counts as a default-paced day.
+    assert_eq!(calendar.paced_secs(PACED_SECONDS_PER_DAY), default_pacing.seconds_per_day);
+
+    // Back to the default once the campaign is over.
+    campaign::reset();
+    assert!(*Calendar::new().pacing() == default_pacing);
+}
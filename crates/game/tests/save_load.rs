@@ -0,0 +1,280 @@
+use std::fmt::Write;
+
+use common::{coords::Cell, hash, time::Seconds, Size};
+use game::{
+    building::{BuildingKind, BuildingKindAndId},
+    debug::{game_object_debug::GameObjectDebugVarRef, preset_maps},
+    sim::resources::ResourceKind,
+    tile::TileKind,
+    unit::{config::UnitConfigKey, task::UnitTaskDespawn, UnitId},
+    world::object::GameObject,
+};
+
+mod test_utils;
+use test_utils::{
+    TestEnvironment,
+    assign_task, fill_terrain, find_building, find_building_id, find_building_mut, find_unit, find_unit_by_config,
+    save_and_reload, save_and_reload_with, spawn_building, spawn_unit, tick, tick_until, unit_exists,
+};
+
+// ----------------------------------------------
+// Save/Load round-trip regression tests.
+// Guards the manual pre/post save & load fixups
+// against silently dropping or corrupting state.
+// ----------------------------------------------
+
+fn main() {
+    test_utils::run_tests("Save Load", &[
+        test_utils::test_fn!(test_round_trip_preserves_state_hash),
+        test_utils::test_fn!(test_round_trip_resumes_deterministically),
+        test_utils::test_fn!(test_spawn_pool_compaction_keeps_handles_valid),
+        test_utils::test_fn!(test_old_save_seeds_house_service_coverage),
+    ]);
+}
+
+// ----------------------------------------------
+// Helpers
+// ----------------------------------------------
+
+const TICK_DELTA_SECS: Seconds = 1.0;
+
+// Semantic snapshot of the session: tiles, buildings, units, props and sim
+// globals. Only covers serialized state; transient data rebuilt on load
+// (stats, search graph, debug options) is left out on purpose. Iteration
+// follows spawn pool / layer order, so the digest is deterministic.
+fn state_digest(env: &TestEnvironment) -> String {
+    let mut digest = String::new();
+
+    let _ = writeln!(digest, "map: {}", env.tile_map.size_in_cells());
+    env.tile_map.for_each_tile(TileKind::Terrain | TileKind::AllObjectKinds, |_, tile| {
+        let _ = writeln!(
+            digest,
+            "tile: {} {} {:#x} var={}",
+            tile.base_cell(),
+            tile.kind(),
+            tile.tile_def().hash,
+            tile.variation_index()
+        );
+    });
+
+    env.world.for_each_building(BuildingKind::all(), |building| {
+        let _ = write!(
+            digest,
+            "building: {} #{} @{} pop={} workers={} stock=",
+            building.kind(),
+            building.kind_and_id().id,
+            building.base_cell(),
+            building.population_count(),
+            building.workers_count()
+        );
+        for item in building.stock() {
+            let _ = write!(digest, "[{item}]");
+        }
+        digest.push('\n');
+        true
+    });
+
+    env.world.for_each_unit(|unit| {
+        let _ = write!(
+            digest,
+            "unit: {} #{} @{} task={} following_path={}",
+            unit.name(),
+            unit.id(),
+            unit.cell(),
+            unit.current_task().is_some(),
+            unit.is_following_path()
+        );
+        if let Some(item) = unit.peek_inventory() {
+            let _ = write!(digest, " carrying=[{item}]");
+        }
+        digest.push('\n');
+        true
+    });
+
+    env.world.for_each_prop(|prop| {
+        let _ = writeln!(digest, "prop: {} #{} @{}", prop.name(), prop.id(), prop.cell_range().start);
+        true
+    });
+
+    let calendar = env.sim.calendar();
+    let _ = writeln!(digest, "gold: {}", env.sim.treasury().gold_units());
+    let _ = writeln!(digest, "date: day {} of month {}, year {}", calendar.day_of_month(), calendar.month_of_year(), calendar.year());
+
+    let statistics = env.sim.statistics();
+    let latest = statistics.latest().map(|sample| (sample.population, sample.treasury, sample.food_stock()));
+    let _ = writeln!(digest, "statistics: {} samples, latest={latest:?}", statistics.samples().len());
+
+    digest
+}
+
+fn state_hash(env: &TestEnvironment) -> u64 {
+    hash::fnv1a_from_str(&state_digest(env))
+}
+
+fn assert_same_state(original: &TestEnvironment, loaded: &TestEnvironment, when: &str) {
+    let (original_digest, loaded_digest) = (state_digest(original), state_digest(loaded));
+    assert!(
+        state_hash(original) == state_hash(loaded),
+        "state hash mismatch {when}!\n--- original ---\n{original_digest}\n--- loaded ---\n{loaded_digest}"
+    );
+}
+
+fn set_building_debug_bool(env: &mut TestEnvironment, handle: BuildingKindAndId, name: &str, mut value: bool) {
+    let ok = find_building_mut(env, handle)
+        .debug_options()
+        .set_debug_option_by_name(name, GameObjectDebugVarRef::Bool(&mut value));
+    assert!(ok, "Building {} does not expose debug option '{}'", handle.kind, name);
+}
+
+// Debug options are not serialized, so they have to be set again on the loaded session.
+fn freeze_lumberyard(env: &mut TestEnvironment, lumberyard: BuildingKindAndId) {
+    set_building_debug_bool(env, lumberyard, "freeze_harvesting", true);
+    set_building_debug_bool(env, lumberyard, "freeze_storage_delivery", true);
+}
+
+// Drops `field` from every object in the saved JSON, as if written by an older build.
+fn remove_json_field(json: &mut serde_json::Value, field: &str) {
+    match json {
+        serde_json::Value::Object(object) => {
+            object.remove(field);
+            object.values_mut().for_each(|value| remove_json_field(value, field));
+        }
+        serde_json::Value::Array(array) => {
+            array.iter_mut().for_each(|value| remove_json_field(value, field));
+        }
+        _ => {}
+    }
+}
+
+// Lumberyard -> StorageYard preset with a Runner dispatched and partway through
+// its delivery path, so the session has a unit mid-path, a live unit task with a
+// completion callback and building timers part way through their cycles.
+fn setup_session_with_runner_mid_path() -> (TestEnvironment, BuildingKindAndId, UnitId) {
+    let mut env = TestEnvironment::with_preset_map(preset_maps::PRESET_1_LUMBERYARD_1_STORAGE_YARD);
+
+    let lumberyard = find_building_id(&env, BuildingKind::Lumberyard);
+    set_building_debug_bool(&mut env, lumberyard, "freeze_harvesting", true);
+
+    let stored = find_building_mut(&mut env, lumberyard)
+        .as_producer_mut()
+        .add_production_output_stock(ResourceKind::Wood, 4);
+    assert!(stored, "Lumberyard refused to store Wood");
+
+    tick_until(&mut env, 30, TICK_DELTA_SECS, |env| find_unit_by_config(env, UnitConfigKey::Runner).is_some());
+    let runner = find_unit_by_config(&env, UnitConfigKey::Runner).expect("Runner should have been dispatched");
+
+    // Stop the producer from dispatching again once this delivery is done.
+    set_building_debug_bool(&mut env, lumberyard, "freeze_storage_delivery", true);
+
+    // Let the runner walk a couple of tiles away from the lumberyard.
+    tick(&mut env, TICK_DELTA_SECS);
+    tick(&mut env, TICK_DELTA_SECS);
+    assert!(find_unit(&env, runner).is_following_path(), "Runner should be mid-path");
+
+    (env, lumberyard, runner)
+}
+
+// Ticks until the runner finishes its delivery and despawns, draining the
+// task and spawn promise pools before the TestEnvironment is dropped.
+fn tick_until_runner_despawned(env: &mut TestEnvironment, runner: UnitId) -> usize {
+    const MAX_TICKS: usize = 100;
+    let ticks = tick_until(env, MAX_TICKS, TICK_DELTA_SECS, |env| !unit_exists(env, runner));
+    assert!(ticks < MAX_TICKS, "runner should have despawned within {MAX_TICKS} ticks");
+    ticks
+}
+
+// ----------------------------------------------
+// Tests
+// ----------------------------------------------
+
+// Saving then loading into a new session reproduces the same state hash.
+fn test_round_trip_preserves_state_hash() {
+    let (mut original, lumberyard, runner) = setup_session_with_runner_mid_path();
+    let mut loaded = save_and_reload(&mut original);
+    freeze_lumberyard(&mut loaded, lumberyard);
+
+    assert_same_state(&original, &loaded, "right after load");
+    assert!(find_unit(&loaded, runner).is_following_path(), "loaded Runner should still be mid-path");
+
+    tick_until_runner_despawned(&mut original, runner);
+    tick_until_runner_despawned(&mut loaded, runner);
+}
+
+// The loaded session picks up exactly where the original left off: ticking
+// both by the same amount keeps them in lockstep through to the delivery.
+fn test_round_trip_resumes_deterministically() {
+    let (mut original, lumberyard, runner) = setup_session_with_runner_mid_path();
+    let mut loaded = save_and_reload(&mut original);
+    freeze_lumberyard(&mut loaded, lumberyard);
+
+    for i in 0..3 {
+        tick(&mut original, TICK_DELTA_SECS);
+        tick(&mut loaded, TICK_DELTA_SECS);
+        assert_same_state(&original, &loaded, &format!("after {} ticks", i + 1));
+    }
+
+    let original_ticks = tick_until_runner_despawned(&mut original, runner);
+    let loaded_ticks = tick_until_runner_despawned(&mut loaded, runner);
+
+    assert_eq!(original_ticks, loaded_ticks, "both sessions should finish the delivery on the same tick");
+    assert_same_state(&original, &loaded, "after the delivery");
+}
+
+// Loading compacts the spawn pools: the last unit moves into the slot freed by a
+// despawned one and the unit's tile must point at its new slot. The original
+// session is not compacted by saving and keeps its ids as they were.
+fn test_spawn_pool_compaction_keeps_handles_valid() {
+    let mut original = TestEnvironment::new();
+    let first = spawn_unit(&mut original, Cell::new(5, 5), UnitConfigKey::Peasant);
+    let second = spawn_unit(&mut original, Cell::new(6, 6), UnitConfigKey::Peasant);
+    let third = spawn_unit(&mut original, Cell::new(7, 7), UnitConfigKey::Peasant);
+
+    assign_task(&mut original, first, UnitTaskDespawn::default());
+    tick(&mut original, TestEnvironment::TICK_DELTA_SECS);
+    assert!(!unit_exists(&original, first), "first unit should be despawned");
+
+    let loaded = save_and_reload(&mut original);
+
+    assert!(unit_exists(&original, second) && unit_exists(&original, third), "saving should not move units");
+
+    for unit_id in [second, third] {
+        let cell = find_unit(&original, unit_id).cell();
+        let unit = loaded.world.find_unit_for_cell(cell, &loaded.tile_map).expect("unit tile should resolve to its unit");
+
+        assert!(unit.id().generation() == unit_id.generation(), "unit tile should resolve to the same unit");
+        assert!(unit.id().index() <= unit_id.index());
+    }
+
+    let third_cell = find_unit(&original, third).cell();
+    let relocated = loaded.world.find_unit_for_cell(third_cell, &loaded.tile_map).unwrap();
+    assert!(relocated.id().index() < third.index(), "last unit should move into the freed slot");
+}
+
+// Houses saved before timed service coverage existed have no `service_coverage`.
+// Loading one seeds coverage for the services its level relies on, instead of
+// leaving it uncovered until the next upgrade update downgrades it.
+fn test_old_save_seeds_house_service_coverage() {
+    let mut original = TestEnvironment::with_map_size(Size::new(12, 12));
+    fill_terrain(&mut original, "grass");
+
+    let house = spawn_building(&mut original, Cell::new(5, 5), "house1");
+    let is_covered = |env: &TestEnvironment, service_kind| {
+        find_building(env, house).as_house().service_coverage().is_covered(service_kind)
+    };
+    let has_water_covered = |env: &TestEnvironment| {
+        [BuildingKind::SmallWell, BuildingKind::LargeWell, BuildingKind::Fountain]
+            .into_iter()
+            .any(|service_kind| is_covered(env, service_kind))
+    };
+
+    assert!(!is_covered(&original, BuildingKind::Market), "no walker has visited the house yet");
+
+    let loaded = save_and_reload_with(&mut original, |world_json| remove_json_field(world_json, "service_coverage"));
+    assert!(is_covered(&loaded, BuildingKind::Market), "old save should seed the required food service");
+    assert!(has_water_covered(&loaded), "old save should seed one of the required water services");
+
+    // Saves that already track coverage load it as is.
+    let reloaded = save_and_reload(&mut original);
+    assert!(!is_covered(&reloaded, BuildingKind::Market));
+    assert!(!has_water_covered(&reloaded));
+}
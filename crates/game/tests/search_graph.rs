@@ -1,8 +1,8 @@
 use common::{coords::Cell, Size};
 use game::{
-    pathfind::Node,
+    pathfind::{Graph, Node, NodeKind},
     tile::{
-        TileKind, TileMap, TileMapLayerKind,
+        TileKind, TileMap, TileMapLayerKind, water,
         placement::{TileClearingErr, TilePlacementErr},
     },
 };
@@ -19,6 +19,8 @@ fn main() {
         test_utils::test_fn!(test_vacant_lot_obstructed_by_building),
         test_utils::test_fn!(test_non_vacant_terrain_restored_on_object_clear),
         test_utils::test_fn!(test_vacant_lot_counter_no_underflow),
+        test_utils::test_fn!(test_shared_building_access_survives_clear),
+        test_utils::test_fn!(test_boats_stay_on_water_and_sail_from_dock),
     ]);
 }
 
@@ -138,3 +140,62 @@ fn test_vacant_lot_counter_no_underflow() {
         assert!(!tile_map.graph().has_vacant_lot_nodes());
     }
 }
+
+// Two houses with a single free cell between them share that BuildingAccess node.
+// Clearing one of them must leave the node flagged for the other, and the
+// incrementally updated graph must match a full rebuild from the tile map.
+fn test_shared_building_access_survives_clear() {
+    let mut tile_map = TileMap::new(MAP_SIZE_IN_CELLS, None);
+    let house = test_utils::find_building_def("house0");
+    let left_cell = Cell::new(10, 10);
+    let right_cell = Cell::new(left_cell.x + house.size_in_cells().width + 1, left_cell.y);
+    let shared_cell = Cell::new(right_cell.x - 1, right_cell.y);
+
+    tile_map.try_place_tile(left_cell, house).ok_or_panic("place left house");
+    tile_map.try_place_tile(right_cell, house).ok_or_panic("place right house");
+    assert!(tile_map.graph().node_kind(Node::new(shared_cell)).unwrap().intersects(NodeKind::BuildingAccess));
+
+    tile_map.try_clear_tile_from_layer(left_cell, TileMapLayerKind::Objects).ok_or_panic("clear left house");
+    assert!(tile_map.graph().node_kind(Node::new(shared_cell)).unwrap().intersects(NodeKind::BuildingAccess),
+        "BuildingAccess node shared with the remaining house was cleared");
+    assert!(!tile_map.graph().node_kind(Node::new(left_cell)).unwrap().is_building());
+
+    let rebuilt = Graph::from_tile_map(&tile_map);
+    for y in 0..MAP_SIZE_IN_CELLS.height {
+        for x in 0..MAP_SIZE_IN_CELLS.width {
+            let node = Node::new(Cell::new(x, y));
+            assert!(tile_map.graph().node_kind(node) == rebuilt.node_kind(node),
+                "Incremental graph differs from a full rebuild at {}", node.cell);
+        }
+    }
+}
+
+// Lake on the left half of the map, grass on the right. Boats only go on water,
+// and a fishing wharf built on the shore hands them a water cell to set sail from.
+fn test_boats_stay_on_water_and_sail_from_dock() {
+    let mut tile_map = TileMap::new(MAP_SIZE_IN_CELLS, None);
+    let water_def = test_utils::find_water_def();
+    let grass_def = test_utils::find_terrain_def("grass");
+
+    for y in 0..MAP_SIZE_IN_CELLS.height {
+        for x in 0..MAP_SIZE_IN_CELLS.width {
+            let tile_def = if x < 16 { water_def } else { grass_def };
+            tile_map.try_place_tile(Cell::new(x, y), tile_def).ok_or_panic("place terrain");
+        }
+    }
+
+    let boat_def = test_utils::find_unit_def("fishing_boat");
+    assert!(tile_map.try_place_tile(Cell::new(20, 5), boat_def).is_err(), "boats can't be placed on land");
+    tile_map.try_place_tile(Cell::new(5, 5), boat_def).ok_or_panic("place boat on water");
+
+    // 2x2 wharf over the last two water columns, right at the shore.
+    let wharf_def = test_utils::find_building_def("fishing_wharf");
+    let wharf_cell = Cell::new(14, 10);
+    tile_map.try_place_tile(wharf_cell, wharf_def).ok_or_panic("place fishing wharf");
+
+    let wharf_cells = wharf_def.cell_range(wharf_cell);
+    let dock_cell = water::find_dock_water_cell(&tile_map, wharf_cells).expect("wharf should border open water");
+    assert!(!wharf_cells.contains(dock_cell), "dock cell {dock_cell} should be outside the wharf");
+    assert!(tile_map.graph().node_kind(Node::new(dock_cell)).unwrap().is_water());
+    tile_map.try_place_tile(dock_cell, boat_def).ok_or_panic("place boat at the dock");
+}
@@ -1,10 +1,11 @@
 // NOTE: Allow for the whole crate.
 #![allow(dead_code)]
 
-use common::{coords::Cell, time::Seconds, Size};
-use engine::{log, render::texture::TextureCache};
+use common::{coords::Cell, mem::RcMut, time::Seconds, Size};
+use engine::{log, render::texture::TextureCache, save::{self, SaveState}};
 use game::{
     cheats,
+    save_context::{Load, PostLoadContext, PostSaveContext, PreSaveContext, Save},
     debug::preset_maps,
     config::GameConfigs,
     world::{World, object::GameObject},
@@ -14,7 +15,10 @@ use game::{
         SimCmds, SimContext, Simulation,
     },
     tile::{
-        sets::{TileDef, TileSets, OBJECTS_BUILDINGS_CATEGORY, OBJECTS_VEGETATION_CATEGORY, TERRAIN_LAND_CATEGORY},
+        sets::{
+            TileDef, TileSets, OBJECTS_BUILDINGS_CATEGORY, OBJECTS_UNITS_CATEGORY, OBJECTS_VEGETATION_CATEGORY,
+            TERRAIN_LAND_CATEGORY, TERRAIN_WATER_CATEGORY,
+        },
         TileMap, TileMapLayerKind,
     },
     building::{
@@ -57,7 +61,7 @@ fn setup(test_suite_name: &str) {
 
     let mut tex_cache = TextureCache::default();
     let skip_loading_textures = true;
-    TileSets::load(&mut tex_cache, false, false, skip_loading_textures);
+    TileSets::load(&mut tex_cache, false, false, false, skip_loading_textures);
 }
 
 fn print_passed() {
@@ -160,6 +164,18 @@ pub fn find_terrain_def(name: &str) -> &'static TileDef {
         .unwrap_or_else(|| panic!("Missing terrain tile def '{name}'"))
 }
 
+pub fn find_water_def() -> &'static TileDef {
+    TileSets::get()
+        .find_tile_def_by_name(TileMapLayerKind::Terrain, TERRAIN_WATER_CATEGORY.string, "water")
+        .unwrap_or_else(|| panic!("Missing water terrain tile def"))
+}
+
+pub fn find_unit_def(name: &str) -> &'static TileDef {
+    TileSets::get()
+        .find_tile_def_by_name(TileMapLayerKind::Objects, OBJECTS_UNITS_CATEGORY.string, name)
+        .unwrap_or_else(|| panic!("Missing unit tile def '{name}'"))
+}
+
 pub fn find_building_def(name: &str) -> &'static TileDef {
     TileSets::get()
         .find_tile_def_by_name(TileMapLayerKind::Objects, OBJECTS_BUILDINGS_CATEGORY.string, name)
@@ -396,6 +412,76 @@ pub fn find_building_mut<'world>(env: &'world mut TestEnvironment, handle: Build
         .unwrap_or_else(|| panic!("Building {} #{} not found", handle.kind, handle.id))
 }
 
+// ----------------------------------------------
+// Save / Load
+// ----------------------------------------------
+
+// Saves the TileMap / World / Simulation triple and loads it back into a brand
+// new TestEnvironment, running the same pre/post save and post_load fixups the
+// GameSession does. The source environment is left intact and can keep ticking.
+pub fn save_and_reload(env: &mut TestEnvironment) -> TestEnvironment {
+    save_and_reload_with(env, |_| {})
+}
+
+// Same as save_and_reload(), but lets `edit_world_fn` modify the saved World JSON
+// before loading it back, e.g. to strip fields missing from older saves.
+pub fn save_and_reload_with(
+    env: &mut TestEnvironment,
+    edit_world_fn: impl FnOnce(&mut serde_json::Value),
+) -> TestEnvironment {
+    let mut tile_map_state = save::new_json_save_state(false);
+    let mut world_state = save::new_json_save_state(false);
+    let mut sim_state = save::new_json_save_state(false);
+
+    {
+        let mut context = PreSaveContext::new(env.sim.cmds().clone());
+        env.tile_map.pre_save(&mut context);
+        env.world.pre_save(&mut context);
+        env.sim.pre_save(&mut context);
+    }
+
+    env.tile_map.save(&mut tile_map_state).unwrap_or_else(|err| panic!("Failed to save TileMap: {err}"));
+    env.world.save(&mut world_state).unwrap_or_else(|err| panic!("Failed to save World: {err}"));
+    env.sim.save(&mut sim_state).unwrap_or_else(|err| panic!("Failed to save Simulation: {err}"));
+
+    let world_state = {
+        let json = world_state.as_any().downcast_ref::<save::JsonSaveState>().unwrap();
+        let mut world_json: serde_json::Value = serde_json::from_str(json.to_str()).unwrap();
+        edit_world_fn(&mut world_json);
+        save::new_json_save_state_with_data(false, world_json.to_string())
+    };
+
+    {
+        let mut context = PostSaveContext::new();
+        env.tile_map.post_save(&mut context);
+        env.world.post_save(&mut context);
+        env.sim.post_save(&mut context);
+    }
+
+    let mut tile_map = RcMut::new(
+        tile_map_state.load_new_instance::<TileMap>().unwrap_or_else(|err| panic!("Failed to load TileMap: {err}"))
+    );
+    let mut world = world_state.load_new_instance::<World>()
+        .unwrap_or_else(|err| panic!("Failed to load World: {err}"));
+    let mut sim = sim_state.load_new_instance::<Simulation>()
+        .unwrap_or_else(|err| panic!("Failed to load Simulation: {err}"));
+
+    {
+        let mut context = PostLoadContext::without_engine(GameConfigs::get(), sim.rng().clone(), tile_map.clone());
+        tile_map.post_load(&mut context);
+        world.post_load(&mut context);
+        world.compact_spawn_pools(&mut tile_map);
+        sim.post_load(&mut context);
+        world.clear_spawn_pool_relocations();
+    }
+
+    TestEnvironment {
+        tile_map: std::mem::take(tile_map.as_mut()),
+        world,
+        sim,
+    }
+}
+
 // ----------------------------------------------
 // Internal helpers
 // ----------------------------------------------
@@ -13,6 +13,7 @@ use game::{
         Unit,
         UnitId,
         config::UnitConfigKey,
+        group::UnitGroup,
         navigation::UnitNavGoal,
         task::{
             UnitPatrolPathRecord, UnitTaskArg, UnitTaskArgs, UnitTaskDeliverToStorage,
@@ -21,7 +22,8 @@ use game::{
             UnitTaskPatrolCompletionCallback, UnitTaskPatrolState,
             UnitTaskPostDespawnCallback, UnitTaskRandomizedPatrol,
             UnitTaskFollowPathState, UnitTaskDespawnWithCallbackState,
-            UnitPostDespawnCb,
+            UnitPostDespawnCb, UnitTaskFlee, UnitTaskFleeState, UnitTaskPriority, UnitTask,
+            UnitTaskArchetype, UnitTaskDeliveryState,
         },
     },
 };
@@ -30,8 +32,8 @@ mod test_utils;
 use test_utils::{
     TestEnvironment,
     assign_task, clear_terrain, despawn_building, find_building, find_building_id,
-    find_building_mut, find_unit, find_unit_by_config, place_road, spawn_unit, tick,
-    tick_until, unit_exists,
+    find_building_mut, find_unit, find_unit_by_config, place_road, save_and_reload, spawn_unit,
+    tick, tick_until, unit_exists,
 };
 
 // ----------------------------------------------
@@ -50,11 +52,13 @@ fn main() {
         // UnitTaskFollowPath
         test_utils::test_fn!(test_follow_path_reaches_goal),
         test_utils::test_fn!(test_follow_path_chains_to_completion_task),
+        test_utils::test_fn!(test_order_go_to_moves_controllable_unit),
 
         // UnitTaskDeliverToStorage
         test_utils::test_fn!(test_deliver_transfers_resources_to_storage),
         test_utils::test_fn!(test_deliver_producer_fallback_when_no_storage),
         test_utils::test_fn!(test_delivery_path_blocked_recovery),
+        test_utils::test_fn!(test_deliver_reservations_prevent_overbooking),
 
         // UnitTaskFetchFromStorage
         test_utils::test_fn!(test_fetch_picks_up_and_returns_with_resource),
@@ -78,6 +82,15 @@ fn main() {
         test_utils::test_fn!(test_patrol_leaves_and_returns_to_origin),
         test_utils::test_fn!(test_patrol_visits_target_buildings),
         test_utils::test_fn!(test_patrol_respects_max_distance),
+
+        // UnitGroup
+        test_utils::test_fn!(test_unit_group_moves_into_formation_slots),
+
+        // Combat
+        test_utils::test_fn!(test_soldier_kills_raider_in_melee),
+
+        // UnitTaskQueue
+        test_utils::test_fn!(test_task_queue_interrupts_and_resumes),
     ]);
 }
 
@@ -266,6 +279,39 @@ fn test_follow_path_chains_to_completion_task() {
     assert!(ticks < 200, "chained despawn should finish within 200 ticks");
 }
 
+// Player "go to" order: a controllable Guard is sent down the road with a
+// UnitTaskFollowPath, while a non-controllable Peasant refuses the order.
+fn test_order_go_to_moves_controllable_unit() {
+    let mut env = TestEnvironment::new();
+    let start = Cell::new(3, 3);
+    let end = Cell::new(3, 8);
+    straight_road_path(&mut env, start, end);
+
+    let guard_id = spawn_unit(&mut env, start, UnitConfigKey::Guard);
+    let peasant_id = spawn_unit(&mut env, Cell::new(3, 5), UnitConfigKey::Peasant);
+
+    {
+        let context = env.new_sim_context(0.0);
+
+        let peasant = context.find_unit_mut(peasant_id).unwrap();
+        assert!(!peasant.try_order_go_to(&context, end), "peasant is not controllable");
+        assert!(peasant.current_task().is_none());
+
+        let guard = context.find_unit_mut(guard_id).unwrap();
+        assert!(guard.try_order_go_to(&context, end), "guard should accept the order");
+        assert!(guard.is_running_task::<UnitTaskFollowPath>(context.task_manager()));
+    }
+
+    let ticks = tick_until(&mut env, 200, TestEnvironment::TICK_DELTA_SECS, |env| {
+        find_unit(env, guard_id).cell() == end
+    });
+    assert!(ticks < 200, "guard should have reached the ordered destination within 200 ticks");
+
+    // Drain the completed order so the task pool is empty before drop.
+    tick(&mut env, TestEnvironment::TICK_DELTA_SECS);
+    assert!(find_unit(&env, guard_id).current_task().is_none(), "completed order should be cleared");
+}
+
 // ----------------------------------------------
 // UnitTaskDeliverToStorage
 // ----------------------------------------------
@@ -458,6 +504,79 @@ fn test_delivery_path_blocked_recovery() {
     tick_until_runner_despawned(&mut env, runner_id, 20);
 }
 
+// Two carriers leave the lumberyard in the same update, but the storage yard only has
+// room for one load. The first one reserves that room, so the second must not head
+// for the same storage; it waits until room frees up again.
+fn test_deliver_reservations_prevent_overbooking() {
+    let mut env = TestEnvironment::with_preset_map(
+        preset_maps::PRESET_1_LUMBERYARD_1_STORAGE_YARD,
+    );
+
+    let lumberyard = find_building_id(&env, BuildingKind::Lumberyard);
+    let storage = find_building_id(&env, BuildingKind::StorageYard);
+
+    // Only our hand-made carriers deliver in this test.
+    set_building_debug_bool(&mut env, lumberyard, "freeze_harvesting", true);
+    set_building_debug_bool(&mut env, lumberyard, "freeze_storage_delivery", true);
+
+    // Fill the storage yard until exactly one load of N fits.
+    const N: u32 = 4;
+    let capacity = find_building(&env, storage).receivable_resources(ResourceKind::Wood);
+    assert!(capacity > N, "Storage yard should have room for more than {N} Wood");
+    seed_storage(&mut env, storage, ResourceKind::Wood, capacity - N);
+    assert_eq!(find_building(&env, storage).receivable_resources(ResourceKind::Wood), N);
+
+    let origin_building_tile = find_building(&env, lumberyard).tile_info();
+
+    let spawn_carrier = |env: &mut TestEnvironment| {
+        let unit_id = spawn_unit(env, origin_building_tile.road_link, UnitConfigKey::Runner);
+        let despawn_task_id = env.sim.task_manager_mut().new_task(UnitTaskDespawn::default())
+            .expect("task pool full");
+
+        assign_task(env, unit_id, UnitTaskDeliverToStorage {
+            origin_building: lumberyard,
+            origin_building_tile,
+            storage_buildings_accepted: BuildingKind::storage(),
+            resource_kind_to_deliver: ResourceKind::Wood,
+            resource_count: N,
+            completion_callback: Callback::default(),
+            completion_task: Some(despawn_task_id),
+            allow_producer_fallback: false,
+            state: UnitTaskDeliveryState::default(),
+            reservation: None,
+            visit_delivered: None,
+        });
+        unit_id
+    };
+
+    let first_id = spawn_carrier(&mut env);
+    let second_id = spawn_carrier(&mut env);
+
+    // Both carriers search in this update. Only the first one gets the storage.
+    tick(&mut env, DELIVER_TICK_DELTA_SECS);
+
+    assert!(find_unit(&env, first_id).goal().is_some(), "first carrier should be heading to the storage");
+    assert!(find_unit(&env, second_id).goal().is_none(), "second carrier should not overbook the storage");
+    assert_eq!(env.sim.task_manager().storage_reservations().reserved_count(storage, ResourceKind::Wood), N);
+
+    // The first delivery fills the storage; the second carrier is still waiting with its cargo.
+    tick_until_runner_despawned(&mut env, first_id, 100);
+    assert_eq!(find_building(&env, storage).receivable_resources(ResourceKind::Wood), 0);
+    assert!(find_unit(&env, second_id).goal().is_none());
+    assert_eq!(env.sim.task_manager().storage_reservations().reserved_count(storage, ResourceKind::Wood), 0);
+
+    // Make room for the second load so its task chain drains before drop.
+    let mut to_remove = N;
+    while to_remove > 0 {
+        let removed = find_building_mut(&mut env, storage).remove_resources(ResourceKind::Wood, to_remove);
+        assert!(removed != 0, "Storage should still hold {to_remove} Wood");
+        to_remove -= removed;
+    }
+
+    tick_until_runner_despawned(&mut env, second_id, 100);
+    assert_eq!(find_building(&env, storage).receivable_resources(ResourceKind::Wood), 0);
+}
+
 // ----------------------------------------------
 // UnitTaskFetchFromStorage
 // ----------------------------------------------
@@ -1321,3 +1440,154 @@ fn test_patrol_respects_max_distance() {
     // Also confirm we actually saw movement -- otherwise the assertion is vacuous.
     assert!(max_observed > 0, "patrol should have moved at least one cell from origin");
 }
+
+// ----------------------------------------------
+// Combat
+// ----------------------------------------------
+
+// A soldier and a raider standing next to each other trade blows every update until the
+// raider (less health, weaker attack) dies and despawns. The soldier survives, wounded.
+fn test_soldier_kills_raider_in_melee() {
+    let mut env = TestEnvironment::new();
+    straight_road_path(&mut env, Cell::new(3, 3), Cell::new(3, 4));
+
+    let soldier_id = spawn_unit(&mut env, Cell::new(3, 3), UnitConfigKey::Soldier);
+    let raider_id = spawn_unit(&mut env, Cell::new(3, 4), UnitConfigKey::Raider);
+
+    {
+        let soldier = find_unit(&env, soldier_id);
+        let raider = find_unit(&env, raider_id);
+        assert!(soldier.is_enemy_of(raider) && raider.is_enemy_of(soldier));
+        assert!(raider.is_hostile() && !soldier.is_hostile());
+    }
+
+    // Raider has 60 health against 12 damage/sec: ~5 sec (50 ticks).
+    let ticks = tick_until(&mut env, 200, TestEnvironment::TICK_DELTA_SECS, |env| {
+        !unit_exists(env, raider_id)
+    });
+    assert!(ticks < 200, "raider should have been killed within 200 ticks");
+
+    let soldier = find_unit(&env, soldier_id);
+    assert!(!soldier.is_dead());
+    assert!(soldier.health_ratio() < 1.0, "soldier should have taken damage");
+}
+
+// ----------------------------------------------
+// UnitTaskQueue
+// ----------------------------------------------
+
+fn push_task<T>(env: &mut TestEnvironment, unit_id: UnitId, task: T, priority: UnitTaskPriority)
+where
+    T: UnitTask,
+    UnitTaskArchetype: From<T>,
+{
+    let task_manager = env.sim.task_manager_mut();
+    let task_id = task_manager.new_task(task);
+    env.world.find_unit_mut(unit_id).unwrap().push_task(task_manager, task_id, priority);
+}
+
+// A walker heading down the road gets interrupted by an urgent flee task, runs back
+// the way it came, then resumes its walk and still reaches the original destination.
+// The interrupted task survives a save/load round trip while the unit is fleeing.
+fn test_task_queue_interrupts_and_resumes() {
+    let mut env = TestEnvironment::new();
+    let start = Cell::new(3, 12);
+    let end = Cell::new(3, 24);
+    place_road(&mut env, &(3..12).map(|y| Cell::new(3, y)).collect::<Vec<_>>());
+    let path = straight_road_path(&mut env, start, end);
+
+    let unit_id = spawn_unit(&mut env, start, UnitConfigKey::Peasant);
+    push_task(&mut env, unit_id, UnitTaskFollowPath {
+        path,
+        completion_callback: Callback::default(),
+        completion_task: None,
+        terminate_if_stuck: false,
+        state: UnitTaskFollowPathState::default(),
+    }, UnitTaskPriority::Normal);
+
+    for _ in 0..12 {
+        tick(&mut env, TestEnvironment::TICK_DELTA_SECS);
+    }
+    let cell_before_flee = find_unit(&env, unit_id).cell();
+    assert!(cell_before_flee.y > start.y, "unit should be on its way");
+
+    // Danger ahead: the unit runs back up the road.
+    push_task(&mut env, unit_id, UnitTaskFlee {
+        danger_cell: end,
+        flee_distance: 8,
+        state: UnitTaskFleeState::default(),
+    }, UnitTaskPriority::Urgent);
+
+    {
+        let unit = find_unit(&env, unit_id);
+        assert!(unit.is_running_task::<UnitTaskFlee>(env.sim.task_manager()), "flee should take over right away");
+        assert_eq!(unit.task_queue().len(), 1);
+        assert!(unit.task_queue().iter().all(|queued| queued.interrupted));
+    }
+
+    tick(&mut env, TestEnvironment::TICK_DELTA_SECS);
+    let mut loaded = save_and_reload(&mut env);
+
+    for env in [&mut env, &mut loaded] {
+        assert_eq!(find_unit(env, unit_id).task_queue().len(), 1, "queued task should survive save/load");
+
+        let mut min_y = i32::MAX;
+        let ticks = tick_until(env, 600, TestEnvironment::TICK_DELTA_SECS, |env| {
+            let unit = find_unit(env, unit_id);
+            min_y = min_y.min(unit.cell().y);
+            unit.cell() == end
+        });
+        assert!(ticks < 600, "resumed walk should reach the destination within 600 ticks");
+        assert!(min_y < cell_before_flee.y, "unit should have fled away from the danger first");
+
+        // Drain the completed walk so the task pool is empty before drop.
+        tick(env, TestEnvironment::TICK_DELTA_SECS);
+        let unit = find_unit(env, unit_id);
+        assert!(unit.current_task().is_none() && unit.task_queue().is_empty());
+    }
+}
+
+// ----------------------------------------------
+// UnitGroup
+// ----------------------------------------------
+
+// Three peasants ordered to move as a group along the ring road: each gets its
+// own slot (leader on the destination itself) and all of them settle there.
+fn test_unit_group_moves_into_formation_slots() {
+    let mut env = TestEnvironment::with_preset_map(preset_maps::PRESET_EMPTY_MAP_WITH_RING_ROAD);
+
+    let mut group = UnitGroup::new();
+    for cell in [Cell::new(0, 0), Cell::new(1, 0), Cell::new(2, 0)] {
+        let unit_id = spawn_unit(&mut env, cell, UnitConfigKey::Peasant);
+        assert!(group.add(unit_id));
+    }
+    assert!(!group.add(group.leader().unwrap()), "units can only be added once");
+
+    let destination = Cell::new(8, 4);
+    {
+        let context = env.new_sim_context(0.0);
+        assert!(group.move_to(&context, destination), "leader should find a path to the destination");
+    }
+
+    let slots: Vec<Cell> = group.members().iter().map(|member| member.slot).collect();
+    assert_eq!(slots[0], destination, "leader slot should be the destination");
+    assert!(slots[1] != slots[0] && slots[2] != slots[0] && slots[1] != slots[2], "slots should be unique: {slots:?}");
+
+    // ~12 tiles at ~1.66 tiles/sec plus some bumping on the way in. Cap at 400 ticks (40 s).
+    let mut arrived = false;
+    for _ in 0..400 {
+        tick(&mut env, TestEnvironment::TICK_DELTA_SECS);
+
+        let context = env.new_sim_context(0.0);
+        group.update(&context);
+        if group.has_arrived(&context) {
+            arrived = true;
+            break;
+        }
+    }
+    assert!(arrived, "group should have settled into its formation slots");
+
+    for member in group.members() {
+        assert_eq!(find_unit(&env, member.unit_id).cell(), member.slot);
+    }
+}
@@ -1,9 +1,19 @@
-use std::{fs, path::Path, process::Command};
+use std::{
+    fs,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 // NOTE: APP_NAME must match `[package.metadata.bundle] name = ".."` in game/Cargo.toml!
 const APP_NAME: &str = "Heritage Builder.app";
 const ASSETS_DIR: &str = "assets";
 
+// NOTE: Must match the archive format in engine/src/file_sys/archive.rs!
+const ASSET_ARCHIVE_FILE_NAME: &str = "assets.pak";
+const ASSET_ARCHIVE_MAGIC: [u8; 4] = *b"HPAK";
+const ASSET_ARCHIVE_VERSION: u32 = 1;
+
 fn main() {
     println!("🏠 Running bundler from {:?}", std::env::current_dir().unwrap());
 
@@ -53,7 +63,7 @@ fn main() {
         panic!("❌ Cargo bundle failed!");
     }
 
-    // Step 3: Copy assets:
+    // Step 3: Copy assets (debug) or pack them into a single archive (release):
     let bundle_resources_dir = Path::new("../../")
         .join("target")
         .join(bundle_kind)
@@ -64,8 +74,23 @@ fn main() {
 
     if bundle_resources_dir.exists() {
         let assets_src = Path::new("../../").join(ASSETS_DIR);
-        println!("📦 Copying assets from {:?} to {:?}", assets_src, bundle_resources_dir);
-        copy_dir_recursive(&assets_src, &bundle_resources_dir.join(ASSETS_DIR));
+        let assets_dst = bundle_resources_dir.join(ASSETS_DIR);
+        let archive_dst = bundle_resources_dir.join(ASSET_ARCHIVE_FILE_NAME);
+
+        if release_flag.is_some() {
+            println!("📦 Packing assets from {:?} to {:?}", assets_src, archive_dst);
+            if assets_dst.exists() {
+                fs::remove_dir_all(&assets_dst).expect("Failed to remove old assets directory");
+            }
+            pack_asset_archive(&assets_src, &archive_dst);
+        } else {
+            println!("📦 Copying assets from {:?} to {:?}", assets_src, bundle_resources_dir);
+            if archive_dst.exists() {
+                // Would shadow the loose files.
+                fs::remove_file(&archive_dst).expect("Failed to remove old asset archive");
+            }
+            copy_dir_recursive(&assets_src, &assets_dst);
+        }
     } else {
         println!("⚠️ Resources directory ({bundle_resources_dir:?}) not found, skipping asset copy.");
     }
@@ -97,3 +122,69 @@ fn copy_dir_recursive(src: &Path, dst: &Path) {
         }
     }
 }
+
+// Writes every file under `src` into a single uncompressed archive at `dst`.
+// Layout (all integers little-endian):
+//   magic:   [u8; 4] = "HPAK"
+//   version: u32
+//   count:   u32
+//   index:   count * { path_len: u32, path: [u8; path_len], offset: u64, size: u64 }
+//   data:    raw file contents, offsets relative to the start of the archive.
+fn pack_asset_archive(src: &Path, dst: &Path) {
+    let mut files = Vec::new();
+    collect_files_recursive(src, &mut files);
+    files.sort(); // Deterministic archive layout.
+
+    // Entry paths are relative to the assets directory, with forward slash separators.
+    let entries: Vec<(String, u64)> = files
+        .iter()
+        .map(|file| {
+            let relative = file.strip_prefix(src).unwrap();
+            let path = relative.to_str().unwrap_or_else(|| panic!("❌ Non UTF-8 asset path: {file:?}")).replace('\\', "/");
+            let size = fs::metadata(file).unwrap_or_else(|_| panic!("❌ Failed to stat: {file:?}")).len();
+            (path, size)
+        })
+        .collect();
+
+    let index_size: u64 = 12 + entries.iter().map(|(path, _)| 4 + path.len() as u64 + 16).sum::<u64>();
+
+    let archive_file = fs::File::create(dst).unwrap_or_else(|err| panic!("❌ Failed to create {dst:?}: {err}"));
+    let mut writer = BufWriter::new(archive_file);
+    let mut write = |bytes: &[u8]| writer.write_all(bytes).unwrap_or_else(|err| panic!("❌ Failed to write {dst:?}: {err}"));
+
+    write(&ASSET_ARCHIVE_MAGIC);
+    write(&ASSET_ARCHIVE_VERSION.to_le_bytes());
+    write(&(entries.len() as u32).to_le_bytes());
+
+    let mut offset = index_size;
+    for (path, size) in &entries {
+        write(&(path.len() as u32).to_le_bytes());
+        write(path.as_bytes());
+        write(&offset.to_le_bytes());
+        write(&size.to_le_bytes());
+        offset += size;
+    }
+
+    for file in &files {
+        let data = fs::read(file).unwrap_or_else(|err| panic!("❌ Failed to read {file:?}: {err}"));
+        write(&data);
+    }
+
+    drop(write);
+    writer.flush().unwrap_or_else(|err| panic!("❌ Failed to write {dst:?}: {err}"));
+
+    println!("   Packed {} files ({} bytes).", entries.len(), offset);
+}
+
+fn collect_files_recursive(dir: &Path, out: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap_or_else(|_| panic!("❌ Failed to read dir: {dir:?}")) {
+        let entry = entry.unwrap_or_else(|_| panic!("❌ Failed to read dir entry for: {dir:?}"));
+        let path = entry.path();
+
+        if path.is_dir() {
+            collect_files_recursive(&path, out);
+        } else {
+            out.push(path);
+        }
+    }
+}
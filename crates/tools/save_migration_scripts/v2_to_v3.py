@@ -0,0 +1,124 @@
+#!/usr/bin/env python3
+"""
+Migrates Heritage Builder save files from v2 to v3.
+
+v3 saves each tile map layer in per-region chunk records instead of one flat
+map of every tile:
+  - The `slab` field of every `TilePool` (a map of slab key -> tile) is
+    replaced by `chunks`, a list of `{ "region": Cell, "tiles": [[key, tile]] }`
+    records covering 64x64 cells each. Tiles keep their original slab keys.
+  - `save_version` is bumped to 3.
+
+Usage:
+    python3 crates/tools/save_migration_scripts/v2_to_v3.py [save.json ...]
+
+With no arguments, migrates every saves/*.json. Files are rewritten in place.
+The migration is idempotent: only pools still carrying a `slab` field are
+converted, so re-running it (or running a partly-migrated save) is safe. Saves
+older than v2 are rejected with a message to run the earlier scripts first.
+"""
+import glob
+import json
+import os
+import sys
+
+FROM_VERSION = 2
+CURRENT_VERSION = 3
+
+# Must match SAVE_CHUNK_SIZE in crates/game/src/tile/save_chunks.rs.
+SAVE_CHUNK_SIZE = 64
+
+
+def tile_base_cell(tile):
+    """Cell used to pick the tile's region, mirroring `Tile::actual_base_cell()`.
+    Terrain and blocker tiles store a `cell`; objects store a `cell_range`.
+    """
+    archetype = tile.get("archetype") if isinstance(tile, dict) else None
+    if not isinstance(archetype, dict) or len(archetype) != 1:
+        return {"x": 0, "y": 0}
+
+    inner = next(iter(archetype.values()))
+    if not isinstance(inner, dict):
+        return {"x": 0, "y": 0}
+
+    if isinstance(inner.get("cell"), dict):
+        return inner["cell"]
+    if isinstance(inner.get("cell_range"), dict) and isinstance(inner["cell_range"].get("start"), dict):
+        return inner["cell_range"]["start"]
+
+    return {"x": 0, "y": 0}
+
+
+def chunk_slab(slab):
+    """Convert a serialized Slab (map of string key -> tile) into chunk records."""
+    regions = {}
+    for key, tile in slab.items():
+        cell = tile_base_cell(tile)
+        region = (cell.get("y", 0) // SAVE_CHUNK_SIZE, cell.get("x", 0) // SAVE_CHUNK_SIZE)
+        regions.setdefault(region, []).append([int(key), tile])
+
+    # Row major, same order the game writes them in.
+    return [
+        {"region": {"x": x, "y": y}, "tiles": tiles}
+        for (y, x), tiles in sorted(regions.items())
+    ]
+
+
+def migrate_tile_pools(node):
+    """Replace the `slab` of every serialized TilePool with `chunks`."""
+    if isinstance(node, list):
+        for item in node:
+            migrate_tile_pools(item)
+        return
+    if not isinstance(node, dict):
+        return
+
+    # A serialized TilePool carries this distinctive set of keys.
+    if {"layer_kind", "layer_size_in_cells", "cell_to_slab_idx", "slab"} <= node.keys():
+        slab = node.pop("slab")
+        node["chunks"] = chunk_slab(slab) if isinstance(slab, dict) else []
+        return
+
+    for value in node.values():
+        migrate_tile_pools(value)
+
+
+def migrate(data):
+    migrate_tile_pools(data)
+    data["save_version"] = CURRENT_VERSION
+    return data
+
+
+def main(argv):
+    paths = argv[1:]
+    if not paths:
+        here = os.path.dirname(os.path.abspath(__file__))
+        saves_dir = os.path.join(here, "..", "..", "..", "saves")
+        paths = sorted(glob.glob(os.path.join(saves_dir, "*.json")))
+
+    if not paths:
+        print("No save files found.")
+        return
+
+    for path in paths:
+        with open(path) as f:
+            data = json.load(f)
+
+        version = data.get("save_version", 0)
+        if version >= CURRENT_VERSION:
+            print(f"skip (already v{version}): {path}")
+            continue
+        if version < FROM_VERSION:
+            print(f"ERROR (v{version}, run v1_to_v2.py first): {path}")
+            continue
+
+        migrate(data)
+
+        with open(path, "w") as f:
+            json.dump(data, f, indent=2)
+            f.write("\n")
+        print(f"migrated -> v{CURRENT_VERSION}: {path}")
+
+
+if __name__ == "__main__":
+    main(sys.argv)
@@ -0,0 +1,148 @@
+#!/usr/bin/env python3
+"""
+Migrates Heritage Builder save files from v3 to v4.
+
+v4 adds a generation to every tile's `TileGameObjectHandle`, so that stale
+building handles are caught instead of silently reaching a recycled pool slot:
+  - The handle's `kind_or_generation` field is split into `generation` and
+    `kind`. Unit and Prop handles already held their generation there; their
+    `kind` is 0.
+  - Building handles held the BuildingKind bits, which move to `kind`. Their
+    `generation` is taken from the building spawned at the tile's base cell
+    (`Building::id.generation`).
+  - `save_version` is bumped to 4.
+
+Usage:
+    python3 crates/tools/save_migration_scripts/v3_to_v4.py [save.json ...]
+
+With no arguments, migrates every saves/*.json. Files are rewritten in place.
+The migration is idempotent: only handles still carrying `kind_or_generation`
+are converted, so re-running it (or running a partly-migrated save) is safe.
+Saves older than v3 are rejected with a message to run the earlier scripts
+first.
+"""
+import glob
+import json
+import os
+import sys
+
+FROM_VERSION = 3
+CURRENT_VERSION = 4
+
+INVALID = 4294967295  # u32::MAX
+
+
+def cell_key(cell):
+    return (cell.get("x"), cell.get("y"))
+
+
+def collect_building_generations(data):
+    """Map each spawned building's base cell to its generation."""
+    generations = {}
+
+    pools = data.get("world", {}).get("building_spawn_pools")
+    if not isinstance(pools, list):
+        return generations
+
+    for entry in pools:
+        # Each entry is `[archetype_kind, [header, building, ...]]`.
+        if not isinstance(entry, list) or len(entry) != 2 or not isinstance(entry[1], list):
+            continue
+        for building in entry[1][1:]:
+            if not isinstance(building, dict):
+                continue
+            map_cells = building.get("map_cells")
+            building_id = building.get("id")
+            if isinstance(map_cells, dict) and isinstance(building_id, dict):
+                generations[cell_key(map_cells.get("start", {}))] = building_id.get("generation", INVALID)
+
+    return generations
+
+
+def migrate_handle(tile, building_generations):
+    archetype = tile.get("archetype")
+    if not isinstance(archetype, dict):
+        return
+
+    object_tile = archetype.get("ObjectTile")
+    if not isinstance(object_tile, dict):
+        return
+
+    handle = object_tile.get("game_object_handle")
+    if not isinstance(handle, dict) or "kind_or_generation" not in handle:
+        return
+
+    kind_or_generation = handle.pop("kind_or_generation")
+
+    if kind_or_generation == INVALID or handle.get("index") == INVALID:
+        handle["generation"] = INVALID
+        handle["kind"] = INVALID
+    elif "Building" in str(tile.get("kind", "")):
+        base_cell = cell_key(object_tile.get("cell_range", {}).get("start", {}))
+        handle["generation"] = building_generations.get(base_cell, INVALID)
+        handle["kind"] = kind_or_generation
+        if handle["generation"] == INVALID:
+            print(f"  warning: no building found at {base_cell} for tile handle {handle}")
+    else:
+        handle["generation"] = kind_or_generation
+        handle["kind"] = 0
+
+
+def migrate_tiles(node, building_generations):
+    """Convert the handle of every serialized tile found in the tile map."""
+    if isinstance(node, list):
+        for item in node:
+            migrate_tiles(item, building_generations)
+        return
+    if not isinstance(node, dict):
+        return
+
+    # A serialized Tile carries this distinctive set of keys.
+    if {"kind", "flags", "self_index", "archetype"} <= node.keys():
+        migrate_handle(node, building_generations)
+        return
+
+    for value in node.values():
+        migrate_tiles(value, building_generations)
+
+
+def migrate(data):
+    building_generations = collect_building_generations(data)
+    migrate_tiles(data.get("tile_map"), building_generations)
+    data["save_version"] = CURRENT_VERSION
+    return data
+
+
+def main(argv):
+    paths = argv[1:]
+    if not paths:
+        here = os.path.dirname(os.path.abspath(__file__))
+        saves_dir = os.path.join(here, "..", "..", "..", "saves")
+        paths = sorted(glob.glob(os.path.join(saves_dir, "*.json")))
+
+    if not paths:
+        print("No save files found.")
+        return
+
+    for path in paths:
+        with open(path) as f:
+            data = json.load(f)
+
+        version = data.get("save_version", 0)
+        if version >= CURRENT_VERSION:
+            print(f"skip (already v{version}): {path}")
+            continue
+        if version < FROM_VERSION:
+            print(f"ERROR (v{version}, run v2_to_v3.py first): {path}")
+            continue
+
+        migrate(data)
+
+        with open(path, "w") as f:
+            json.dump(data, f, indent=2)
+            f.write("\n")
+        print(f"migrated -> v{CURRENT_VERSION}: {path}")
+
+
+if __name__ == "__main__":
+    main(sys.argv)